@@ -0,0 +1,161 @@
+package aeska.kistaverk
+
+import org.json.JSONArray
+import org.json.JSONObject
+import java.io.ByteArrayOutputStream
+import kotlin.math.pow
+
+/**
+ * CBOR (RFC 8949) for the JSON data model exchanged with Rust: maps, arrays, text,
+ * integers, floats, booleans and null map to [JSONObject], [JSONArray] and boxed values.
+ */
+internal object Cbor {
+
+    fun encode(value: Any?): ByteArray {
+        val out = ByteArrayOutputStream()
+        write(out, value)
+        return out.toByteArray()
+    }
+
+    /** Decodes one item; throws [IllegalArgumentException] on truncated or unsupported input. */
+    fun decode(bytes: ByteArray): Any? {
+        val reader = Reader(bytes)
+        val value = reader.read()
+        require(reader.atEnd()) { "trailing CBOR data" }
+        return value
+    }
+
+    private fun write(out: ByteArrayOutputStream, value: Any?) {
+        when (value) {
+            null, JSONObject.NULL -> out.write(0xf6)
+            is Boolean -> out.write(if (value) 0xf5 else 0xf4)
+            is Int, is Long, is Short, is Byte -> {
+                val v = (value as Number).toLong()
+                if (v >= 0) writeHead(out, 0, v) else writeHead(out, 1, -1 - v)
+            }
+            is Number -> {
+                out.write(0xfb)
+                writeBigEndian(out, value.toDouble().toRawBits(), 8)
+            }
+            is String -> {
+                val bytes = value.toByteArray(Charsets.UTF_8)
+                writeHead(out, 3, bytes.size.toLong())
+                out.write(bytes)
+            }
+            is JSONArray -> {
+                writeHead(out, 4, value.length().toLong())
+                for (i in 0 until value.length()) write(out, value.opt(i))
+            }
+            is JSONObject -> {
+                writeHead(out, 5, value.length().toLong())
+                for (key in value.keys()) {
+                    write(out, key)
+                    write(out, value.opt(key))
+                }
+            }
+            else -> write(out, value.toString())
+        }
+    }
+
+    private fun writeHead(out: ByteArrayOutputStream, major: Int, argument: Long) {
+        val type = major shl 5
+        when {
+            argument < 24 -> out.write(type or argument.toInt())
+            argument < 0x100 -> {
+                out.write(type or 24)
+                writeBigEndian(out, argument, 1)
+            }
+            argument < 0x10000 -> {
+                out.write(type or 25)
+                writeBigEndian(out, argument, 2)
+            }
+            argument < 0x100000000L -> {
+                out.write(type or 26)
+                writeBigEndian(out, argument, 4)
+            }
+            else -> {
+                out.write(type or 27)
+                writeBigEndian(out, argument, 8)
+            }
+        }
+    }
+
+    private fun writeBigEndian(out: ByteArrayOutputStream, value: Long, size: Int) {
+        for (i in size - 1 downTo 0) out.write(((value ushr (8 * i)) and 0xff).toInt())
+    }
+
+    private class Reader(private val bytes: ByteArray) {
+        private var pos = 0
+
+        fun atEnd() = pos == bytes.size
+
+        fun read(): Any? {
+            val initial = byte()
+            val major = initial ushr 5
+            val info = initial and 0x1f
+            if (major == 7) {
+                return when (info) {
+                    20 -> false
+                    21 -> true
+                    22, 23 -> JSONObject.NULL
+                    25 -> halfToDouble(uint(2).toInt())
+                    26 -> Float.fromBits(uint(4).toInt()).toDouble()
+                    27 -> Double.fromBits(uint(8))
+                    else -> throw IllegalArgumentException("unsupported CBOR simple value $info")
+                }
+            }
+            val argument = when {
+                info < 24 -> info.toLong()
+                info <= 27 -> uint(1 shl (info - 24))
+                else -> throw IllegalArgumentException("unsupported CBOR length $info")
+            }
+            return when (major) {
+                0 -> argument
+                1 -> -1 - argument
+                2 -> take(argument)
+                3 -> String(take(argument), Charsets.UTF_8)
+                4 -> JSONArray().apply { repeat(count(argument)) { put(read()) } }
+                5 -> JSONObject().apply {
+                    repeat(count(argument)) {
+                        val key = read() as? String ?: throw IllegalArgumentException("non-text CBOR map key")
+                        put(key, read())
+                    }
+                }
+                // Tags carry no meaning in the UI protocol; keep the tagged item.
+                else -> read()
+            }
+        }
+
+        private fun byte(): Int {
+            require(pos < bytes.size) { "truncated CBOR" }
+            return bytes[pos++].toInt() and 0xff
+        }
+
+        private fun uint(size: Int): Long {
+            var value = 0L
+            repeat(size) { value = (value shl 8) or byte().toLong() }
+            return value
+        }
+
+        private fun count(argument: Long): Int {
+            require(argument in 0..(bytes.size - pos).toLong()) { "truncated CBOR" }
+            return argument.toInt()
+        }
+
+        private fun take(length: Long): ByteArray {
+            val size = count(length)
+            return bytes.copyOfRange(pos, pos + size).also { pos += size }
+        }
+
+        private fun halfToDouble(bits: Int): Double {
+            val sign = if (bits and 0x8000 != 0) -1.0 else 1.0
+            val exponent = (bits ushr 10) and 0x1f
+            val mantissa = bits and 0x3ff
+            return sign * when (exponent) {
+                0 -> mantissa * 2.0.pow(-24)
+                31 -> if (mantissa == 0) Double.POSITIVE_INFINITY else Double.NaN
+                else -> (1 + mantissa / 1024.0) * 2.0.pow(exponent - 15)
+            }
+        }
+    }
+}
@@ -13,6 +13,7 @@ import android.content.IntentFilter
 import android.content.Context
 import android.util.Base64
 import android.os.StatFs
+import android.provider.OpenableColumns
 import android.os.BatteryManager
 import androidx.annotation.VisibleForTesting
 import androidx.activity.ComponentActivity
@@ -22,6 +23,8 @@ import androidx.core.content.ContextCompat
 import androidx.lifecycle.lifecycleScope
 import aeska.kistaverk.features.ConversionResult
 import aeska.kistaverk.features.KotlinImageConversion
+import aeska.kistaverk.features.PlatformImageDecoder
+import aeska.kistaverk.features.WatermarkTextRenderer
 import android.view.View
 import android.view.ViewGroup
 import android.view.HapticFeedbackConstants
@@ -60,6 +63,8 @@ class MainActivity : ComponentActivity() {
     private var pendingAllowMultiple: Boolean = false
     private var selectedOutputDir: Uri? = null
     private var pdfSourceUri: Uri? = null
+    private var fileInfoSourceUri: Uri? = null
+    private var fileInfoBatchUris: List<Uri> = emptyList()
     private var rootContainer: FrameLayout? = null
     private var contentHolder: FrameLayout? = null
     private var overlayView: View? = null
@@ -94,8 +99,7 @@ class MainActivity : ComponentActivity() {
         handlePickerResultMultiple(action, uris, bindings)
     }
 
-    private fun cacheLastResult(json: String) {
-        val obj = runCatching { JSONObject(json) }.getOrNull() ?: return
+    private fun cacheLastResult(obj: JSONObject) {
         lastFileOutputPath = null
         lastFileOutputMime = null
         fun findResult(o: JSONObject): String? {
@@ -134,8 +138,8 @@ class MainActivity : ComponentActivity() {
         findOutputPath(obj)
     }
 
-    private fun updateSensorSubscriptions(json: String) {
-        sensors.updateSubscriptions(json)
+    private fun updateSensorSubscriptions(root: JSONObject) {
+        sensors.updateSubscriptions(root)
     }
 
     private fun guessMimeFromPath(path: String): String? {
@@ -263,7 +267,8 @@ class MainActivity : ComponentActivity() {
                 direction?.let { b.put("find_direction", it) }
                 put("bindings", b)
             }
-            dispatch(cmd.toString())
+            val ui = dispatch(cmd.toString())
+            withContext(Dispatchers.Main) { showOneShotFeedback(ui) }
         }
     }
 
@@ -328,7 +333,11 @@ class MainActivity : ComponentActivity() {
             activity = this,
             scope = lifecycleScope,
             refreshUi = { action, bindings -> refreshUi(action, bindings = bindings) },
-            dispatchRaw = { command -> dispatch(command) }
+            // Not re-rendered, but a toast drained by this render must still be shown.
+            dispatchRaw = { command ->
+                val ui = dispatch(command)
+                runOnUiThread { showOneShotFeedback(ui) }
+            }
         )
         cameraManager = CameraManager(
             activity = this,
@@ -415,6 +424,14 @@ class MainActivity : ComponentActivity() {
                 stopSensorLogging()
                 return@UiRenderer
             }
+            if (action == "sensor_logger_pause") {
+                sensors.pauseLogging()
+                return@UiRenderer
+            }
+            if (action == "sensor_logger_resume") {
+                sensors.resumeLogging()
+                return@UiRenderer
+            }
             if (action == "text_viewer_find_submit" || action == "text_viewer_find_next" || action == "text_viewer_find_prev" || action == "text_viewer_find_clear") {
                 handleTextFind(action, bindings)
                 return@UiRenderer
@@ -429,9 +446,30 @@ class MainActivity : ComponentActivity() {
                 shareLastLog()
                 return@UiRenderer
             }
+            if (action == "recent_reopen") {
+                val uri = bindings["recent_uri"]
+                val tool = bindings["recent_tool"]
+                if (uri != null && tool != null) {
+                    handlePickerResult(tool, Uri.parse(uri), bindings - "recent_uri" - "recent_tool")
+                }
+                return@UiRenderer
+            }
+            if (action.startsWith("file_info_batch_hash:")) {
+                dispatchFileInfoBatchHash(action, bindings)
+                return@UiRenderer
+            }
+            if (action.startsWith("file_info_hex")) {
+                dispatchFileInfoHex(action, bindings)
+                return@UiRenderer
+            }
+            if (action == "file_info_open_map") {
+                bindings["geo_uri"]?.let { openGeoUri(it) }
+                return@UiRenderer
+            }
             if (action == "kotlin_image_save_as") {
-                val mime = lastFileOutputMime ?: "image/*"
-                launchSaveAs(lastFileOutputPath, mime)
+                val path = bindings["image_result_path"] ?: lastFileOutputPath
+                val mime = path?.let { guessMimeFromPath(it) } ?: lastFileOutputMime ?: "image/*"
+                launchSaveAs(path, mime)
                 return@UiRenderer
             }
             if (action == "gzip_save_as") {
@@ -454,6 +492,16 @@ class MainActivity : ComponentActivity() {
                 dispatchWithOptionalLoading(action, bindings = bindingsWithMetrics)
                 return@UiRenderer
             }
+            if (action == "watermark_apply" && bindings["watermark_mode"] == "text") {
+                lifecycleScope.launch {
+                    val png = withContext(Dispatchers.IO) {
+                        WatermarkTextRenderer.render(cacheDir, bindings["watermark_text"].orEmpty())
+                    }
+                    val extra = png?.let { mapOf("watermark_text_png" to it.absolutePath) }.orEmpty()
+                    dispatchWithOptionalLoading(action, bindings = bindings + extra)
+                }
+                return@UiRenderer
+            }
             if (action == "system_info_screen") {
                 dispatchWithOptionalLoading(action, bindings = bindings)
                 val metrics = collectSystemInfoBindings()
@@ -471,6 +519,16 @@ class MainActivity : ComponentActivity() {
                     action.startsWith("pdf_") -> arrayOf("application/pdf")
                     action == "logic_import" -> arrayOf("text/*", "text/csv", "application/csv")
                     action == "text_viewer_open" -> arrayOf("text/*", "text/plain", "text/csv", "application/csv")
+                    action == "metadata_strip_pick" -> arrayOf("image/jpeg", "image/png", "image/webp")
+                    action == "exif_edit_pick" -> arrayOf("image/jpeg")
+                    action.startsWith("watermark_pick_") -> arrayOf("image/*")
+                    action == "collage_pick_images" -> arrayOf("image/*")
+                    action == "dithering_palette_from_image" -> arrayOf("image/*")
+                    action == "gif_frames_pick" -> arrayOf("image/gif")
+                    action == "preset_import" -> arrayOf("application/json", "text/*")
+                    action == "c_scripting_open" -> arrayOf("text/*", "text/x-csrc", "text/x-c")
+                    action == "plugin_install" -> arrayOf("text/*", "text/x-csrc", "application/octet-stream")
+                    action == "cert_open" -> arrayOf("application/x-x509-ca-cert", "application/x-pem-file", "application/pkix-cert", "text/*", "application/octet-stream")
                     else -> arrayOf("*/*")
                 }
                 if (allowMultiple) {
@@ -482,6 +540,8 @@ class MainActivity : ComponentActivity() {
                 if (action == "reset") {
                     selectedOutputDir = null
                     pdfSourceUri = null
+                    fileInfoSourceUri = null
+                    fileInfoBatchUris = emptyList()
                     lastFileOutputPath = null
                     lastFileOutputMime = null
                     stopSensorLogging()
@@ -550,13 +610,21 @@ class MainActivity : ComponentActivity() {
         cameraManager.stopQrScanner()
     }
 
+    override fun onStop() {
+        super.onStop()
+        // Rust writes its state to disk only when asked, so a background kill resumes here.
+        lifecycleScope.launch(Dispatchers.IO) {
+            dispatch(JSONObject().apply { put("action", "persist_state") }.toString())
+        }
+    }
+
     override fun onResume() {
         super.onResume()
         // If current screen is QrReceive and permission is granted, restart scanner
         lifecycleScope.launch(Dispatchers.IO) { // Dispatch to IO thread for Rust call
-            val currentScreenJson = dispatch(JSONObject().apply { put("action", "snapshot_screen_only") }.toString())
+            val currentScreen = dispatch(JSONObject().apply { put("action", "snapshot_screen_only") }.toString())
             withContext(Dispatchers.Main) {
-                val currentScreenId = JSONObject(currentScreenJson).optString("id")
+                val currentScreenId = currentScreen?.optString("id")
                 if (currentScreenId == "QrReceiveScreen") {
                     cameraManager.onScreenChanged(true, contentHolder)
                 }
@@ -572,7 +640,7 @@ class MainActivity : ComponentActivity() {
         grantResults: IntArray
     ) {
         super.onRequestPermissionsResult(requestCode, permissions, grantResults)
-        if (requestCode == PERMISSION_LOCATION) {
+        if (requestCode == PERMISSION_LOCATION || requestCode == PERMISSION_ACTIVITY_RECOGNITION) {
             sensors.onPermissionResult(requestCode, grantResults, emptyMap<String, String>())
         } else if (requestCode == CAMERA_PERMISSION_REQUEST_CODE) {
             cameraManager.onPermissionResult(grantResults)
@@ -660,6 +728,23 @@ class MainActivity : ComponentActivity() {
         return NetworkSnapshot(connection = connection, ssid = ssid, ip = ip)
     }
 
+    /**
+     * Shows the root's `toast` and `haptic` feedback. Rust drains its toast queue into each
+     * render, so a message arrives exactly once; cached and re-rendered layouts never repeat it.
+     */
+    private fun showOneShotFeedback(root: JSONObject?) {
+        if (root == null) return
+        val toastText = root.optString("toast", "").trim()
+        if (toastText.isNotEmpty()) {
+            // Several queued confirmations arrive as one multi-line toast; give them time.
+            val duration = if (toastText.contains('\n')) Toast.LENGTH_LONG else Toast.LENGTH_SHORT
+            Toast.makeText(this, toastText, duration).show()
+        }
+        if (root.optBoolean("haptic", false)) {
+            window?.decorView?.performHapticFeedback(HapticFeedbackConstants.CONFIRM)
+        }
+    }
+
     private var networkToastShown = false
     private fun showNetworkPermissionToastOnce() {
         if (networkToastShown) return
@@ -728,6 +813,14 @@ class MainActivity : ComponentActivity() {
         AppCompatDelegate.setDefaultNightMode(nightMode)
     }
 
+    /** Follows the app-wide `theme` on the rendered root; a changed night mode recreates the activity. */
+    private fun applyRootTheme(root: JSONObject) {
+        val mode = root.optString("theme", "")
+        if (mode.isEmpty() || mode == loadThemeMode()) return
+        persistThemeMode(mode)
+        applyThemeMode(mode)
+    }
+
     internal fun refreshUi(
         action: String,
         extras: Map<String, Any?> = emptyMap(),
@@ -739,13 +832,6 @@ class MainActivity : ComponentActivity() {
             readClipboardText()?.let { clip ->
                 mergedBindings.putIfAbsent("clipboard", clip)
             }
-            if (action == "set_theme") {
-                val mode = mergedBindings["theme_mode"] ?: themeModeDefault
-                persistThemeMode(mode)
-                applyThemeMode(mode)
-                recreate()
-                return
-            }
             val command = JSONObject().apply {
                 put("action", action)
                 extras.forEach { (k, v) -> put(k, v) }
@@ -758,27 +844,12 @@ class MainActivity : ComponentActivity() {
                     put("loading_only", true)
                 }
             }
-                val newUiJson = dispatch(command.toString())
-                val haptic = runCatching { JSONObject(newUiJson).optBoolean("haptic", false) }
-                    .getOrNull() == true
-                val toastText = runCatching { JSONObject(newUiJson).optString("toast", "") }
-                    .getOrNull()
-                    .orEmpty()
-                    .trim()
-                if (toastText.isNotEmpty()) {
-                    runOnUiThread {
-                        Toast.makeText(this, toastText, Toast.LENGTH_SHORT).show()
-                    }
-                }
-                if (haptic) {
-                    runOnUiThread {
-                        window?.decorView?.performHapticFeedback(HapticFeedbackConstants.CONFIRM)
-                    }
-                }
+                val newUi = dispatch(command.toString())
+                runOnUiThread { showOneShotFeedback(newUi) }
                 if (loadingOnly) {
                     showOverlay(command.optString("action", "Working..."))
                 } else {
-                    val rootView = runCatching { renderer.render(newUiJson) }
+                    val rootView = runCatching { renderer.render(newUi) }
                         .getOrElse { throwable ->
                         renderer.renderFallback(
                             title = "Render error",
@@ -787,11 +858,14 @@ class MainActivity : ComponentActivity() {
                     }
                 attachContent(rootView)
                 hideOverlay()
-                cacheLastResult(newUiJson)
-                updateSensorSubscriptions(newUiJson)
-                scheduleAutoRefresh(newUiJson)
+                newUi?.let {
+                    cacheLastResult(it)
+                    updateSensorSubscriptions(it)
+                    scheduleAutoRefresh(it)
+                    applyRootTheme(it)
+                }
 
-                val currentScreen = JSONObject(newUiJson).optJSONObject("layout")?.optString("id")
+                val currentScreen = newUi?.optJSONObject("layout")?.optString("id")
                 cameraManager.onScreenChanged(currentScreen == "QrReceiveScreen", contentHolder)
             }
         } else {
@@ -800,13 +874,6 @@ class MainActivity : ComponentActivity() {
                 readClipboardText()?.let { clip ->
                     mergedBindings.putIfAbsent("clipboard", clip)
                 }
-                if (action == "set_theme") {
-                    val mode = mergedBindings["theme_mode"] ?: themeModeDefault
-                    persistThemeMode(mode)
-                    applyThemeMode(mode)
-                    withContext(Dispatchers.Main) { recreate() }
-                    return@launch
-                }
                 val command = JSONObject().apply {
                     put("action", action)
                     extras.forEach { (k, v) -> put(k, v) }
@@ -820,26 +887,16 @@ class MainActivity : ComponentActivity() {
                     }
                 }
 
-                val newUiJson = withContext(Dispatchers.IO) { dispatch(command.toString()) }
-                val haptic = runCatching { JSONObject(newUiJson).optBoolean("haptic", false) }
-                    .getOrNull() == true
-                val toastText = runCatching { JSONObject(newUiJson).optString("toast", "") }
-                    .getOrNull()
-                    .orEmpty()
-                    .trim()
+                val newUi = withContext(Dispatchers.IO) { dispatch(command.toString()) }
                 if (loadingOnly) {
                     withContext(Dispatchers.Main) {
+                        showOneShotFeedback(newUi)
                         showOverlay(command.optString("action", "Working..."))
                     }
                 } else {
                     withContext(Dispatchers.Main) {
-                        if (toastText.isNotEmpty()) {
-                            Toast.makeText(this@MainActivity, toastText, Toast.LENGTH_SHORT).show()
-                        }
-                        if (haptic) {
-                            window?.decorView?.performHapticFeedback(HapticFeedbackConstants.CONFIRM)
-                        }
-                        val rootView = runCatching { renderer.render(newUiJson) }
+                        showOneShotFeedback(newUi)
+                        val rootView = runCatching { renderer.render(newUi) }
                             .getOrElse { throwable ->
                                 renderer.renderFallback(
                                     title = "Render error",
@@ -848,12 +905,15 @@ class MainActivity : ComponentActivity() {
                             }
                         attachContent(rootView)
                         hideOverlay()
-                        cacheLastResult(newUiJson)
-                        updateSensorSubscriptions(newUiJson)
-                        scheduleAutoRefresh(newUiJson)
+                        newUi?.let {
+                            cacheLastResult(it)
+                            updateSensorSubscriptions(it)
+                            scheduleAutoRefresh(it)
+                            applyRootTheme(it)
+                        }
 
-                        // Check current screen from newUiJson and manage QR scanner lifecycle
-                        val currentScreen = JSONObject(newUiJson).optJSONObject("layout")?.optString("id")
+                        // Check current screen from newUi and manage QR scanner lifecycle
+                        val currentScreen = newUi?.optJSONObject("layout")?.optString("id")
                         if (currentScreen == "QrReceiveScreen") { // Assuming Rust sets screen ID
                              // Lazily add the PreviewView if not already there
                             cameraManager.onScreenChanged(true, contentHolder)
@@ -866,8 +926,7 @@ class MainActivity : ComponentActivity() {
         }
     }
 
-    private fun scheduleAutoRefresh(json: String) {
-        val obj = runCatching { JSONObject(json) }.getOrNull() ?: return
+    private fun scheduleAutoRefresh(obj: JSONObject) {
         val interval = obj.optLong("auto_refresh_ms", 0L)
         val action = obj.optString("auto_refresh_action", "")
         autoRefreshJob?.cancel()
@@ -884,11 +943,11 @@ class MainActivity : ComponentActivity() {
             put("snapshot", snapshot)
         }
 
-        val newUiJson = withContext(Dispatchers.IO) {
+        val newUi = withContext(Dispatchers.IO) {
             dispatch(command.toString())
         }
 
-        val rootView = runCatching { renderer.render(newUiJson) }
+        val rootView = runCatching { renderer.render(newUi) }
             .getOrElse { throwable ->
                 renderer.renderFallback(
                     title = "Render error",
@@ -897,18 +956,28 @@ class MainActivity : ComponentActivity() {
             }
         attachContent(rootView)
         hideOverlay()
-        cacheLastResult(newUiJson)
+        newUi?.let(::cacheLastResult)
     }
 
     private fun requestSnapshot(): String? {
         val command = JSONObject().apply {
             put("action", "snapshot")
         }
-        val json = dispatch(command.toString())
-        val obj = runCatching { JSONObject(json) }.getOrNull() ?: return null
+        val obj = dispatch(command.toString()) ?: return null
         return obj.optString("snapshot").takeIf { it.isNotEmpty() }
     }
 
+    private fun queryDisplayName(uri: Uri): String? {
+        return try {
+            contentResolver.query(uri, arrayOf(OpenableColumns.DISPLAY_NAME), null, null, null)
+                ?.use { cursor ->
+                    if (cursor.moveToFirst()) cursor.getString(0) else null
+                }
+        } catch (_: Exception) {
+            null
+        }
+    }
+
     private fun openFdForUri(uri: Uri): Int? {
         return try {
             contentResolver.openFileDescriptor(uri, "r")?.use { pfd ->
@@ -952,7 +1021,7 @@ class MainActivity : ComponentActivity() {
         uri: Uri?,
         bindings: Map<String, String>
     ): Boolean {
-        var action = actionInput ?: return false
+        val action = actionInput ?: return false
         if (uri == null) return false
 
         if (KotlinImageConversion.isConversionAction(action)) {
@@ -963,6 +1032,9 @@ class MainActivity : ComponentActivity() {
         if (action == "pdf_select") {
             pdfSourceUri = uri
         }
+        if (action == "file_info") {
+            fileInfoSourceUri = uri
+        }
 
         if (action == "pdf_signature_load") {
             val bytes = readBytes(uri)
@@ -982,7 +1054,38 @@ class MainActivity : ComponentActivity() {
             return true
         }
 
-        val fd = openFdForUri(uri)
+        try {
+            contentResolver.takePersistableUriPermission(uri, Intent.FLAG_GRANT_READ_URI_PERMISSION)
+        } catch (_: Exception) {
+            // Best-effort; recent entries may need a fresh pick if this fails
+        }
+
+        if (action in PlatformImageDecoder.RUST_IMAGE_ACTIONS &&
+            PlatformImageDecoder.isHeifOrAvif(contentResolver, uri)
+        ) {
+            lifecycleScope.launch {
+                val fd = withContext(Dispatchers.IO) { openDecodedImageFd(uri) }
+                dispatchPickedFile(action, uri, fd, bindings)
+            }
+            return true
+        }
+        dispatchPickedFile(action, uri, openFdForUri(uri), bindings)
+        return true
+    }
+
+    /** Transcodes a HEIC/AVIF pick to PNG; falls back to the original so Rust reports it. */
+    private fun openDecodedImageFd(uri: Uri): Int? {
+        val decoded = PlatformImageDecoder.transcodeToPng(this, uri, queryDisplayName(uri))
+        return decoded?.let { openFdForUri(Uri.fromFile(it)) } ?: openFdForUri(uri)
+    }
+
+    private fun dispatchPickedFile(
+        actionInput: String,
+        uri: Uri,
+        fd: Int?,
+        bindings: Map<String, String>
+    ) {
+        var action = actionInput
         val extras = mutableMapOf<String, Any?>()
         if (fd != null) {
             extras["fd"] = fd
@@ -1013,12 +1116,14 @@ class MainActivity : ComponentActivity() {
             action = "text_viewer_open"
         }
 
+        // The fd carries no name; Rust uses it for extension checks and the recent files list.
+        val dispatchBindings = queryDisplayName(uri)?.let { bindings + ("file_name" to it) } ?: bindings
+
         dispatchWithOptionalLoading(
             action = action,
-            bindings = bindings,
+            bindings = dispatchBindings,
             extras = extras
         )
-        return true
     }
 
     private fun handlePickerResultMultiple(
@@ -1032,25 +1137,56 @@ class MainActivity : ComponentActivity() {
         if (list.size == 1) {
             return handlePickerResult(action, list.first(), bindings)
         }
+        if (action in PlatformImageDecoder.RUST_IMAGE_ACTIONS &&
+            list.any { PlatformImageDecoder.isHeifOrAvif(contentResolver, it) }
+        ) {
+            lifecycleScope.launch {
+                val fds = withContext(Dispatchers.IO) {
+                    list.map { uri ->
+                        if (PlatformImageDecoder.isHeifOrAvif(contentResolver, uri)) {
+                            openDecodedImageFd(uri)
+                        } else {
+                            openFdForUri(uri)
+                        }
+                    }
+                }
+                dispatchPickedList(action, list, fds, bindings)
+            }
+            return true
+        }
+        dispatchPickedList(action, list, list.map { openFdForUri(it) }, bindings)
+        return true
+    }
+
+    private fun dispatchPickedList(
+        action: String,
+        list: List<Uri>,
+        fds: List<Int?>,
+        bindings: Map<String, String>
+    ) {
         val extras = mutableMapOf<String, Any?>()
         val paths = list.map { it.toString() }
         val arr = org.json.JSONArray()
         val fdArr = org.json.JSONArray()
         paths.forEachIndexed { idx, p ->
             arr.put(p)
-            val fd = openFdForUri(list[idx])
+            val fd = fds[idx]
             if (fd != null) {
                 fdArr.put(fd)
             }
         }
         extras["path_list"] = arr
         extras["fd_list"] = fdArr
+        if (action == "file_info") {
+            fileInfoBatchUris = list
+        }
+        val names = list.map { queryDisplayName(it) ?: it.lastPathSegment.orEmpty() }
+        val dispatchBindings = bindings + ("file_names" to names.joinToString("\n"))
         dispatchWithOptionalLoading(
             action = action,
-            bindings = bindings,
+            bindings = dispatchBindings,
             extras = extras
         )
-        return true
     }
 
     private fun handleIncomingIntent(intent: Intent?): Boolean {
@@ -1092,6 +1228,11 @@ class MainActivity : ComponentActivity() {
         val data = intent?.data ?: return false
         val action = intent.action ?: return false
         if (action != Intent.ACTION_VIEW) return false
+        if (data.scheme == "kistaverk") {
+            // Rust validates the tool id and arguments and lands on the tool.
+            refreshUi("open_tool", bindings = mapOf("uri" to data.toString()))
+            return true
+        }
         val fd = openFdForUri(data)
         val extras = mutableMapOf<String, Any?>()
         extras["path"] = data.toString()
@@ -1108,6 +1249,39 @@ class MainActivity : ComponentActivity() {
     private fun stopSensorLogging() = sensors.stopLogging()
     private fun shareLastLog() = sensors.shareLastLog()
 
+    private fun openGeoUri(uri: String) {
+        if (!uri.startsWith("geo:")) return
+        val intent = Intent(Intent.ACTION_VIEW, Uri.parse(uri))
+        runCatching { startActivity(intent) }
+            .onFailure { Toast.makeText(this, "No map app available", Toast.LENGTH_SHORT).show() }
+    }
+
+    private fun dispatchFileInfoBatchHash(action: String, bindings: Map<String, String>) {
+        val index = action.substringAfter(':').toIntOrNull()
+        val uri = index?.let { fileInfoBatchUris.getOrNull(it) }
+        val fd = uri?.let { openFdForUri(it) }
+        val extras = mutableMapOf<String, Any?>()
+        if (fd != null) {
+            extras["fd"] = fd
+        }
+        dispatchWithOptionalLoading(action, bindings = bindings, extras = extras)
+    }
+
+    private fun dispatchFileInfoHex(action: String, bindings: Map<String, String>) {
+        // The inspected file's fd was consumed; reopen it for every hex window.
+        val uri = fileInfoSourceUri
+        if (uri == null) {
+            dispatchWithOptionalLoading(action, bindings = bindings)
+            return
+        }
+        val fd = openFdForUri(uri)
+        val extras = mutableMapOf<String, Any?>("path" to uri.toString())
+        if (fd != null) {
+            extras["fd"] = fd
+        }
+        dispatchWithOptionalLoading(action, bindings = bindings, extras = extras)
+    }
+
     private fun dispatchPdfAction(action: String, bindings: Map<String, String>) {
         val uri = pdfSourceUri
         if (uri == null) {
@@ -1135,37 +1309,19 @@ class MainActivity : ComponentActivity() {
     private fun handleKotlinImageConversion(uri: Uri, action: String, bindings: Map<String, String>) {
         lifecycleScope.launch {
             val result = withContext(Dispatchers.IO) {
-                if (action == "kotlin_image_resize") {
-                    KotlinImageConversion.resize(
-                        context = this@MainActivity,
-                        cacheDir = cacheDir,
-                        picturesDir = getExternalFilesDir(Environment.DIRECTORY_PICTURES),
-                        outputDirUri = selectedOutputDir,
-                        uri = uri,
-                        bindings = bindings
-                    )
-                } else {
-                    KotlinImageConversion.convert(
-                        context = this@MainActivity,
-                        cacheDir = cacheDir,
-                        picturesDir = getExternalFilesDir(Environment.DIRECTORY_PICTURES),
-                        outputDirUri = selectedOutputDir,
-                        uri = uri,
-                        action = action,
-                        bindings = bindings
-                    )
-                }
+                KotlinImageConversion.convert(
+                    context = this@MainActivity,
+                    cacheDir = cacheDir,
+                    picturesDir = getExternalFilesDir(Environment.DIRECTORY_PICTURES),
+                    outputDirUri = selectedOutputDir,
+                    uri = uri,
+                    action = action,
+                    bindings = bindings
+                )
             }
 
-            val echoedBindings = bindings.toMutableMap()
-
             when (result) {
                 is ConversionResult.Success -> {
-                    result.scalePercent?.let { echoedBindings["resize_scale_pct"] = it.toString() }
-                    result.quality?.let { echoedBindings["resize_quality"] = it.toString() }
-                    result.targetBytes?.let { echoedBindings["resize_target_kb"] = (it / 1024).toString() }
-                    echoedBindings["resize_use_webp"] = (result.target.key == "webp").toString()
-
                     refreshUi(
                         "kotlin_image_result",
                         mapOf(
@@ -1174,7 +1330,7 @@ class MainActivity : ComponentActivity() {
                             "result_size" to result.size,
                             "result_format" to result.format
                         ),
-                        bindings = echoedBindings
+                        bindings = bindings
                     )
                 }
                 is ConversionResult.Failure -> {
@@ -1185,7 +1341,7 @@ class MainActivity : ComponentActivity() {
                             "target" to (result.target?.key ?: JSONObject.NULL),
                             "error" to reason
                         ),
-                        bindings = echoedBindings
+                        bindings = bindings
                     )
                 }
             }
@@ -1205,19 +1361,14 @@ class MainActivity : ComponentActivity() {
     private fun processKotlinImageBatch(bindings: Map<String, String>) {
         val paths = parsePathList(bindings["image_batch_paths"])
         if (paths.isEmpty()) return
-        val mode = bindings["image_batch_mode"] ?: "convert"
         val target = bindings["image_batch_target"] ?: "webp"
         lifecycleScope.launch {
             for (path in paths) {
                 val file = File(path)
-                val action = if (mode == "resize") {
-                    "kotlin_image_resize"
-                } else {
-                    when (target.lowercase(Locale.US)) {
-                        "png" -> "kotlin_image_convert_png"
-                        "jpeg", "jpg" -> "kotlin_image_convert_jpeg"
-                        else -> "kotlin_image_convert_webp"
-                    }
+                val action = when (target.lowercase(Locale.US)) {
+                    "png" -> "kotlin_image_convert_png"
+                    "jpeg", "jpg" -> "kotlin_image_convert_jpeg"
+                    else -> "kotlin_image_convert_webp"
                 }
                 val merged = bindings.toMutableMap()
                 merged["image_source_path"] = file.absolutePath
@@ -1333,7 +1484,27 @@ class MainActivity : ComponentActivity() {
         overlayView?.visibility = View.GONE
     }
 
-    external fun dispatch(input: String): String
+    private val uiPatcher = UiPatcher()
+
+    /** CBOR when the loaded library speaks it; builds that predate it only know JSON. */
+    private val cborDispatch by lazy {
+        runCatching { "cbor" in nativeUiEncodings().split(',') }.getOrDefault(false)
+    }
+
+    /**
+     * Dispatches to Rust, which may answer with a patch; returns the full UI tree, or null
+     * when the response is not JSON.
+     */
+    fun dispatch(input: String): JSONObject? =
+        if (cborDispatch) {
+            uiPatcher.dispatchCbor(input, ::nativeDispatchCbor, ::nativeDispatch)
+        } else {
+            uiPatcher.dispatch(input, ::nativeDispatch)
+        }
+
+    private external fun nativeDispatch(input: String): String
+    private external fun nativeDispatchCbor(input: ByteArray): ByteArray
+    private external fun nativeUiEncodings(): String
     external fun processQrCameraFrame(
         lumaData: ByteArray,
         width: Int,
@@ -1354,6 +1525,7 @@ class MainActivity : ComponentActivity() {
 
         // Arbitrary request code for location permission prompts
         internal const val PERMISSION_LOCATION = 1001
+        internal const val PERMISSION_ACTIVITY_RECOGNITION = 1002
         internal const val CAMERA_PERMISSION_REQUEST_CODE = 2001
     }
 }
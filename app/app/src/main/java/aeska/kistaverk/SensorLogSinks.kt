@@ -0,0 +1,137 @@
+package aeska.kistaverk
+
+import android.content.ContentValues
+import android.database.sqlite.SQLiteDatabase
+import java.io.File
+import java.io.FileOutputStream
+import java.io.OutputStreamWriter
+import java.util.Locale
+
+/**
+ * Destination for sensor log rows. Rows are always produced in the CSV row format
+ * (`ts,TAG,x,y,z,extra1,extra2`); each sink decides how to persist them.
+ */
+internal interface SensorLogSink {
+    val file: File
+
+    /** Approximate bytes written so far, used for size-based rotation. */
+    val bytesWritten: Long
+
+    fun write(row: String)
+    fun flush()
+    fun close()
+}
+
+internal class CsvLogSink(override val file: File) : SensorLogSink {
+    private val writer = OutputStreamWriter(FileOutputStream(file))
+    override var bytesWritten = 0L
+        private set
+
+    override fun write(row: String) {
+        writer.write(row)
+        // Rows are ASCII, so the character count is the byte count.
+        bytesWritten += row.length
+    }
+
+    override fun flush() = writer.flush()
+
+    override fun close() = writer.close()
+}
+
+/**
+ * JSON Lines output: each CSV row becomes one object,
+ * `{"ts":..,"sensor":"ACCEL","x":..,"y":..,"z":..,"extra1":..,"extra2":..}`.
+ * Non-numeric values are written as `null`.
+ */
+internal class NdjsonLogSink(override val file: File) : SensorLogSink {
+    private val writer = OutputStreamWriter(FileOutputStream(file), Charsets.UTF_8)
+    override var bytesWritten = 0L
+        private set
+
+    override fun write(row: String) {
+        val cols = row.trimEnd('\n').split(',')
+        if (cols.size < 2) return
+        val ts = cols[0].toLongOrNull() ?: return
+        val sensor = cols[1]
+        if (!sensor.all { it in 'A'..'Z' }) return
+        val line = buildString {
+            append("{\"ts\":").append(ts).append(",\"sensor\":\"").append(sensor).append('"')
+            COLUMNS.forEachIndexed { idx, name ->
+                val value = cols.getOrNull(idx + 2)?.toDoubleOrNull()?.takeIf { it.isFinite() }
+                append(",\"").append(name).append("\":").append(value?.toString() ?: "null")
+            }
+            append("}\n")
+        }
+        writer.write(line)
+        bytesWritten += line.length
+    }
+
+    override fun flush() = writer.flush()
+
+    override fun close() = writer.close()
+
+    private companion object {
+        val COLUMNS = listOf("x", "y", "z", "extra1", "extra2")
+    }
+}
+
+/**
+ * Writes each sensor into its own table (`accel`, `gyro`, `gps`, ...) with the columns
+ * `ts INTEGER, x REAL, y REAL, z REAL, extra1 REAL, extra2 REAL`.
+ * Table names mirror `sensor_logger::sensor_table_name` on the Rust side.
+ * Inserts are batched in a transaction that is committed at most once per second.
+ */
+internal class SqliteLogSink(override val file: File) : SensorLogSink {
+    private val db = SQLiteDatabase.openOrCreateDatabase(file, null)
+    private val createdTables = mutableSetOf<String>()
+    private var lastCommitMono = 0L
+
+    init {
+        db.beginTransaction()
+    }
+
+    override val bytesWritten: Long
+        get() = file.length()
+
+    override fun write(row: String) {
+        val cols = row.trimEnd('\n').split(',')
+        if (cols.size < 7) return
+        val ts = cols[0].toLongOrNull() ?: return
+        val table = cols[1].lowercase(Locale.US)
+        if (!table.all { it in 'a'..'z' }) return
+        if (createdTables.add(table)) {
+            db.execSQL(
+                "CREATE TABLE IF NOT EXISTS $table " +
+                    "(ts INTEGER NOT NULL, x REAL, y REAL, z REAL, extra1 REAL, extra2 REAL)"
+            )
+        }
+        val values = ContentValues().apply {
+            put("ts", ts)
+            put("x", cols[2].toDoubleOrNull())
+            put("y", cols[3].toDoubleOrNull())
+            put("z", cols[4].toDoubleOrNull())
+            put("extra1", cols[5].toDoubleOrNull())
+            put("extra2", cols[6].toDoubleOrNull())
+        }
+        db.insert(table, null, values)
+    }
+
+    override fun flush() {
+        val now = android.os.SystemClock.elapsedRealtime()
+        if (now - lastCommitMono < 1_000) return
+        lastCommitMono = now
+        commit()
+        db.beginTransaction()
+    }
+
+    override fun close() {
+        commit()
+        db.close()
+    }
+
+    private fun commit() {
+        if (!db.inTransaction()) return
+        db.setTransactionSuccessful()
+        db.endTransaction()
+    }
+}
@@ -18,10 +18,9 @@ import androidx.core.content.ContextCompat
 import kotlinx.coroutines.CoroutineScope
 import kotlinx.coroutines.Dispatchers
 import kotlinx.coroutines.launch
+import org.json.JSONArray
 import org.json.JSONObject
 import java.io.File
-import java.io.FileOutputStream
-import java.io.OutputStreamWriter
 import java.util.Locale
 
 /**
@@ -38,14 +37,23 @@ class AppSensorManager(
     private var sensorHandler: Handler? = null
     private var sensorListener: SensorEventListener? = null
     private var logFile: File? = null
-    private var logWriter: OutputStreamWriter? = null
+    private var logSink: SensorLogSink? = null
+    private var logBackend = LogBackend.CSV
+    private var logDir: File? = null
+    private var logBaseName: String? = null
+    private var logLimits: LogLimits = LogLimits(null, null)
+    private var segmentIndex = 0
+    private var segmentStartMono = 0L
     private var locationManager: LocationManager? = null
     private var locationListener: LocationListener? = null
     private var pendingSensorStart = false
     private var pendingSensorBindings: Map<String, String>? = null
     private var lastSensorLogPath: String? = null
     private var lastSensorUiTs: Long = 0L
+    private val latestSamples = linkedMapOf<String, Float>()
     @Volatile private var isLogging = false
+    @Volatile private var isPaused = false
+    private var pausedAtMono = 0L
 
     private var compassThread: HandlerThread? = null
     private var compassHandler: Handler? = null
@@ -56,6 +64,7 @@ class AppSensorManager(
     private var compassMag: FloatArray? = null
     private var lastCompassRadians: Float? = null
     private var lastCompassDispatchTs: Long = 0L
+    private val pendingUpdates = mutableListOf<JSONObject>()
     private var compassActive = false
     private var compassUnavailable = false
 
@@ -77,33 +86,49 @@ class AppSensorManager(
         val gyro = mgr.getDefaultSensor(Sensor.TYPE_GYROSCOPE)
         val mag = mgr.getDefaultSensor(Sensor.TYPE_MAGNETIC_FIELD)
         val pressure = mgr.getDefaultSensor(Sensor.TYPE_PRESSURE)
+        val light = mgr.getDefaultSensor(Sensor.TYPE_LIGHT)
+        val proximity = mgr.getDefaultSensor(Sensor.TYPE_PROXIMITY)
+        val gravity = mgr.getDefaultSensor(Sensor.TYPE_GRAVITY)
+        val linearAccel = mgr.getDefaultSensor(Sensor.TYPE_LINEAR_ACCELERATION)
+        val rotation = mgr.getDefaultSensor(Sensor.TYPE_ROTATION_VECTOR)
+        val steps = mgr.getDefaultSensor(Sensor.TYPE_STEP_COUNTER)
 
         val config = parseSensorConfig(bindings)
         if (config == null) {
             refreshUi("sensor_logger_status", mapOf("sensor_status" to "invalid_config"))
             return
         }
+        if (config.selection.steps && !hasActivityRecognitionPermission()) {
+            pendingSensorStart = true
+            pendingSensorBindings = bindings
+            activity.requestPermissions(
+                arrayOf(Manifest.permission.ACTIVITY_RECOGNITION),
+                MainActivity.PERMISSION_ACTIVITY_RECOGNITION
+            )
+            return
+        }
 
         val thread = HandlerThread("SensorLogger")
         thread.start()
         sensorThread = thread
         sensorHandler = Handler(thread.looper)
 
-        val dir = activity.getExternalFilesDir(null) ?: activity.filesDir
-        logFile = File(dir, "sensors_${System.currentTimeMillis()}.csv")
-        logWriter = runCatching { OutputStreamWriter(FileOutputStream(logFile!!)) }.getOrNull()
-        if (logWriter == null) {
+        logDir = activity.getExternalFilesDir(null) ?: activity.filesDir
+        logBaseName = "sensors_${System.currentTimeMillis()}"
+        logLimits = config.limits
+        logBackend = config.backend
+        segmentIndex = 0
+        if (!openSegment()) {
             refreshUi("sensor_logger_status", mapOf("sensor_status" to "log_open_failed"))
             return
         }
-        logWriter?.write("ts,sensor,x,y,z,extra1,extra2\n")
 
         val listener = object : SensorEventListener {
             override fun onSensorChanged(event: SensorEvent) {
-                if (!isLogging) return
+                if (!isLogging || isPaused) return
                 val ts = System.currentTimeMillis()
                 val values = event.values
-                val writer = logWriter ?: return
+                if (logSink == null) return
                 val bindings = mutableMapOf<String, String>()
                 bindings["sensor_status"] = "logging"
                 logFile?.absolutePath?.let { bindings["sensor_path"] = it }
@@ -111,26 +136,62 @@ class AppSensorManager(
                     when (event.sensor.type) {
                         Sensor.TYPE_ACCELEROMETER -> {
                             if (!config.selection.accel) return
-                            writer.write(formatRow(ts, "ACCEL", values))
+                            writeRow(formatRow(ts, "ACCEL", values))
                         }
                         Sensor.TYPE_GYROSCOPE -> {
                             if (!config.selection.gyro) return
-                            writer.write(formatRow(ts, "GYRO", values))
+                            writeRow(formatRow(ts, "GYRO", values))
                         }
                         Sensor.TYPE_MAGNETIC_FIELD -> {
                             if (!config.selection.mag) return
-                            writer.write(formatRow(ts, "MAG", values))
+                            writeRow(formatRow(ts, "MAG", values))
                         }
                         Sensor.TYPE_PRESSURE -> {
                             if (!config.selection.pressure) return
-                            writer.write("${ts},BARO,${values.getOrNull(0) ?: 0f},0,0,0,0\n")
+                            writeRow("${ts},BARO,${values.getOrNull(0) ?: 0f},0,0,0,0\n")
+                        }
+                        Sensor.TYPE_LIGHT -> {
+                            if (!config.selection.light) return
+                            writeRow(formatRow(ts, "LIGHT", values.copyOf(1)))
+                        }
+                        Sensor.TYPE_PROXIMITY -> {
+                            if (!config.selection.proximity) return
+                            writeRow(formatRow(ts, "PROX", values.copyOf(1)))
+                        }
+                        Sensor.TYPE_GRAVITY -> {
+                            if (!config.selection.gravity) return
+                            writeRow(formatRow(ts, "GRAVITY", values))
+                        }
+                        Sensor.TYPE_LINEAR_ACCELERATION -> {
+                            if (!config.selection.linearAccel) return
+                            writeRow(formatRow(ts, "LINACC", values))
+                        }
+                        Sensor.TYPE_ROTATION_VECTOR -> {
+                            if (!config.selection.rotation) return
+                            writeRow(formatRow(ts, "ROTVEC", values, withExtras = true))
+                        }
+                        Sensor.TYPE_STEP_COUNTER -> {
+                            if (!config.selection.steps) return
+                            writeRow("${ts},STEPS,${(values.getOrNull(0) ?: 0f).toLong()},0,0,0,0\n")
                         }
                     }
-                    writer.flush()
+                    logSink?.flush()
                     lastSensorLogPath = logFile?.absolutePath
+                    maybeRotate()
+                    chartSample(event.sensor.type, values)?.let { (tag, value) ->
+                        // Keep the peak magnitude so threshold checks in Rust see short spikes.
+                        latestSamples[tag] = if (tag in PEAK_TAGS) maxOf(latestSamples[tag] ?: value, value) else value
+                    }
                     val nowMono = android.os.SystemClock.elapsedRealtime()
                     if (nowMono - lastSensorUiTs > 500) {
                         lastSensorUiTs = nowMono
+                        if (latestSamples.isNotEmpty()) {
+                            bindings["sensor_samples"] = latestSamples.entries.joinToString(";") { (tag, v) ->
+                                String.format(Locale.US, "%s=%.5f", tag, v)
+                            }
+                            bindings["sensor_samples_ts"] = System.currentTimeMillis().toString()
+                            latestSamples.clear()
+                        }
                         refreshUi("sensor_logger_status", bindings)
                     }
                 } catch (_: Exception) {
@@ -145,8 +206,17 @@ class AppSensorManager(
         if (config.selection.gyro) gyro?.let { mgr.registerListener(listener, it, config.intervalMs.toInt(), sensorHandler) }
         if (config.selection.mag) mag?.let { mgr.registerListener(listener, it, config.intervalMs.toInt(), sensorHandler) }
         if (config.selection.pressure) pressure?.let { mgr.registerListener(listener, it, config.intervalMs.toInt(), sensorHandler) }
-
+        if (config.selection.light) light?.let { mgr.registerListener(listener, it, config.intervalMs.toInt(), sensorHandler) }
+        if (config.selection.proximity) proximity?.let { mgr.registerListener(listener, it, config.intervalMs.toInt(), sensorHandler) }
+        if (config.selection.gravity) gravity?.let { mgr.registerListener(listener, it, config.intervalMs.toInt(), sensorHandler) }
+        if (config.selection.linearAccel) linearAccel?.let { mgr.registerListener(listener, it, config.intervalMs.toInt(), sensorHandler) }
+        if (config.selection.rotation) rotation?.let { mgr.registerListener(listener, it, config.intervalMs.toInt(), sensorHandler) }
+        if (config.selection.steps) steps?.let { mgr.registerListener(listener, it, config.intervalMs.toInt(), sensorHandler) }
+
+        isPaused = false
         isLogging = true
+        // Let the Rust side record the validated selection and rotation limits.
+        refreshUi("sensor_logger_start", bindings)
 
         if (config.selection.gps) {
             if (!hasLocationPermission()) {
@@ -159,20 +229,55 @@ class AppSensorManager(
         }
     }
 
+    /** Stops sampling but keeps the session file open, writing a PAUSE gap marker. */
+    fun pauseLogging() {
+        val handler = sensorHandler
+        if (!isLogging || isPaused || handler == null) return
+        handler.post {
+            isPaused = true
+            pausedAtMono = android.os.SystemClock.elapsedRealtime()
+            runCatching {
+                writeRow("${System.currentTimeMillis()},PAUSE,0,0,0,0,0\n")
+                logSink?.flush()
+            }
+            refreshUi("sensor_logger_pause", emptyMap())
+        }
+    }
+
+    /** Resumes sampling into the same file; the RESUME marker carries the gap length in ms. */
+    fun resumeLogging() {
+        val handler = sensorHandler
+        if (!isLogging || !isPaused || handler == null) return
+        handler.post {
+            val pausedMs = android.os.SystemClock.elapsedRealtime() - pausedAtMono
+            // Paused time does not count toward the segment duration cap.
+            segmentStartMono += pausedMs
+            runCatching {
+                writeRow("${System.currentTimeMillis()},RESUME,$pausedMs,0,0,0,0\n")
+                logSink?.flush()
+            }
+            isPaused = false
+            refreshUi("sensor_logger_resume", emptyMap())
+        }
+    }
+
     fun stopLogging() {
         isLogging = false
+        isPaused = false
         (activity.getSystemService(Context.SENSOR_SERVICE) as? SensorManager)?.unregisterListener(sensorListener)
         sensorListener = null
         sensorHandler = null
         sensorThread?.quitSafely()
         sensorThread = null
         stopLocationLogging()
-        runCatching { logWriter?.close() }
-        logWriter = null
+        latestSamples.clear()
+        runCatching { logSink?.close() }
+        logSink = null
         val bindings = mutableMapOf<String, String>()
         bindings["sensor_status"] = "stopped"
         lastSensorLogPath?.let { bindings["sensor_path"] = it }
-        refreshUi("sensor_logger_status", bindings)
+        // Rust summarizes the recorded segments on its worker.
+        refreshUi("sensor_logger_stop", bindings)
     }
 
     fun shareLastLog() {
@@ -185,7 +290,11 @@ class AppSensorManager(
             file
         )
         val intent = Intent(Intent.ACTION_SEND).apply {
-            type = "text/csv"
+            type = when (file.extension) {
+                "db" -> "application/vnd.sqlite3"
+                "ndjson" -> "application/x-ndjson"
+                else -> "text/csv"
+            }
             putExtra(Intent.EXTRA_STREAM, uri)
             addFlags(Intent.FLAG_GRANT_READ_URI_PERMISSION)
         }
@@ -193,7 +302,9 @@ class AppSensorManager(
     }
 
     fun onPermissionResult(requestCode: Int, grantResults: IntArray, bindings: Map<String, String>) {
-        if (requestCode != MainActivity.PERMISSION_LOCATION) return
+        if (requestCode != MainActivity.PERMISSION_LOCATION &&
+            requestCode != MainActivity.PERMISSION_ACTIVITY_RECOGNITION
+        ) return
         val granted = grantResults.isNotEmpty() && grantResults[0] == PackageManager.PERMISSION_GRANTED
         if (granted && pendingSensorStart) {
             pendingSensorStart = false
@@ -201,8 +312,14 @@ class AppSensorManager(
             pendingSensorBindings = null
             startLogging(pending)
         } else {
+            pendingSensorStart = false
             pendingSensorBindings = null
-            refreshUi("sensor_logger_status", mapOf("sensor_status" to "location permission denied"))
+            val status = if (requestCode == MainActivity.PERMISSION_LOCATION) {
+                "location permission denied"
+            } else {
+                "activity recognition permission denied"
+            }
+            refreshUi("sensor_logger_status", mapOf("sensor_status" to status))
         }
     }
 
@@ -376,15 +493,31 @@ class AppSensorManager(
         magnetometerThread = null
     }
 
-    fun updateSubscriptions(json: String) {
-        val wantsCompass = jsonHasWidget(json, "Compass")
+    fun updateSubscriptions(root: JSONObject) {
+        val wantsCompass = hasWidget(root, "Compass")
         if (wantsCompass) startCompass() else stopCompass()
 
-        val wantsBaro = jsonHasWidget(json, "Barometer")
+        val wantsBaro = hasWidget(root, "Barometer")
         if (wantsBaro) startBarometer() else stopBarometer()
 
-        val wantsMag = jsonHasWidget(json, "Magnetometer")
+        val wantsMag = hasWidget(root, "Magnetometer")
         if (wantsMag) startMagnetometer() else stopMagnetometer()
+
+        appendLogRows(root)
+    }
+
+    /** Writes annotation rows produced by Rust (threshold alerts) into the open log. */
+    private fun appendLogRows(root: JSONObject) {
+        val rows = root.optJSONArray("sensor_log_rows") ?: return
+        val handler = sensorHandler ?: return
+        if (!isLogging) return
+        val lines = (0 until rows.length()).mapNotNull { rows.optString(it).takeIf { row -> row.isNotBlank() } }
+        handler.post {
+            runCatching {
+                lines.forEach { writeRow("$it\n") }
+                logSink?.flush()
+            }
+        }
     }
 
     fun onDestroy() {
@@ -406,17 +539,123 @@ class AppSensorManager(
             pressure = bindings["sensor_pressure"]?.toBoolean() ?: false,
             gps = bindings["sensor_gps"]?.toBoolean() ?: false,
             battery = bindings["sensor_battery"]?.toBoolean() ?: true,
+            light = bindings["sensor_light"]?.toBoolean() ?: false,
+            proximity = bindings["sensor_proximity"]?.toBoolean() ?: false,
+            gravity = bindings["sensor_gravity"]?.toBoolean() ?: false,
+            linearAccel = bindings["sensor_linear_accel"]?.toBoolean() ?: false,
+            rotation = bindings["sensor_rotation"]?.toBoolean() ?: false,
+            steps = bindings["sensor_steps"]?.toBoolean() ?: false,
         )
         if (!selection.any()) return null
         val interval = bindings["sensor_interval_ms"]?.toLongOrNull()?.coerceIn(50, 10_000) ?: 200
-        return SensorConfig(selection, interval)
+        // Ranges mirror the Rust-side validation in sensor_logger::parse_bindings.
+        val maxFileKb = bindings["sensor_max_file_kb"]?.trim()?.toLongOrNull()?.takeIf { it in 64..1_048_576 }
+        val maxDurationMin = bindings["sensor_max_duration_min"]?.trim()?.toLongOrNull()?.takeIf { it in 1..1_440 }
+        val limits = LogLimits(maxFileKb?.let { it * 1024 }, maxDurationMin?.let { it * 60_000 })
+        // Mirrors sensor_logger::SensorBackend::parse; `sensor_sqlite` is the older checkbox.
+        val backend = when (bindings["sensor_format"]?.trim()?.lowercase(Locale.US)) {
+            "ndjson", "jsonl" -> LogBackend.NDJSON
+            "sqlite", "db" -> LogBackend.SQLITE
+            "csv" -> LogBackend.CSV
+            else -> if (bindings["sensor_sqlite"]?.toBoolean() == true) LogBackend.SQLITE else LogBackend.CSV
+        }
+        return SensorConfig(selection, interval, limits, backend)
     }
 
-    private fun formatRow(ts: Long, name: String, vals: FloatArray): String {
+    /**
+     * Opens the next log segment. Without limits the log is a single `sensors_<ts>.csv`
+     * (or `.ndjson` / `.db`); with limits segments are numbered `sensors_<ts>_001.csv`, `_002`, ...
+     */
+    private fun openSegment(): Boolean {
+        val dir = logDir ?: return false
+        val base = logBaseName ?: return false
+        segmentIndex += 1
+        val ext = logBackend.extension
+        val name = if (logLimits.rotates()) {
+            String.format(Locale.US, "%s_%03d.%s", base, segmentIndex, ext)
+        } else {
+            "$base.$ext"
+        }
+        val file = File(dir, name)
+        val sink = runCatching {
+            when (logBackend) {
+                LogBackend.CSV -> CsvLogSink(file)
+                LogBackend.NDJSON -> NdjsonLogSink(file)
+                LogBackend.SQLITE -> SqliteLogSink(file)
+            }
+        }.getOrNull() ?: return false
+        logFile = file
+        logSink = sink
+        segmentStartMono = android.os.SystemClock.elapsedRealtime()
+        if (sink is CsvLogSink) writeRow(CSV_HEADER)
+        return true
+    }
+
+    private fun writeRow(row: String) {
+        logSink?.write(row)
+    }
+
+    /** Closes the current segment and opens the next one once a size or duration cap is hit. */
+    private fun maybeRotate() {
+        val limits = logLimits
+        if (!limits.rotates()) return
+        val elapsed = android.os.SystemClock.elapsedRealtime() - segmentStartMono
+        val sizeHit = limits.maxFileBytes?.let { (logSink?.bytesWritten ?: 0L) >= it } ?: false
+        val timeHit = limits.maxDurationMs?.let { elapsed >= it } ?: false
+        if (!sizeHit && !timeHit) return
+        val finished = logFile?.absolutePath
+        runCatching { logSink?.close() }
+        logSink = null
+        finished?.let { refreshUi("sensor_logger_segment", mapOf("sensor_segment_path" to it)) }
+        if (!openSegment()) {
+            isLogging = false
+            refreshUi("sensor_logger_status", mapOf("sensor_status" to "log_open_failed"))
+        }
+    }
+
+    /** Reduces a sensor event to the single value plotted by the live chart. */
+    private fun chartSample(type: Int, vals: FloatArray): Pair<String, Float>? {
+        fun magnitude(): Float {
+            val x = vals.getOrNull(0) ?: 0f
+            val y = vals.getOrNull(1) ?: 0f
+            val z = vals.getOrNull(2) ?: 0f
+            return kotlin.math.sqrt(x * x + y * y + z * z)
+        }
+        val first = vals.getOrNull(0) ?: 0f
+        return when (type) {
+            Sensor.TYPE_ACCELEROMETER -> "ACCEL" to magnitude()
+            Sensor.TYPE_GYROSCOPE -> "GYRO" to magnitude()
+            Sensor.TYPE_MAGNETIC_FIELD -> "MAG" to magnitude()
+            Sensor.TYPE_PRESSURE -> "BARO" to first
+            Sensor.TYPE_LIGHT -> "LIGHT" to first
+            Sensor.TYPE_PROXIMITY -> "PROX" to first
+            Sensor.TYPE_GRAVITY -> "GRAVITY" to magnitude()
+            Sensor.TYPE_LINEAR_ACCELERATION -> "LINACC" to magnitude()
+            Sensor.TYPE_ROTATION_VECTOR -> {
+                // Rotation angle (degrees) of the device relative to the reference frame.
+                val w = (vals.getOrNull(3) ?: kotlin.math.sqrt((1f - magnitude() * magnitude()).coerceAtLeast(0f)))
+                "ROTVEC" to Math.toDegrees(2.0 * kotlin.math.acos(w.coerceIn(-1f, 1f).toDouble())).toFloat()
+            }
+            Sensor.TYPE_STEP_COUNTER -> "STEPS" to first
+            else -> null
+        }
+    }
+
+    private fun formatRow(ts: Long, name: String, vals: FloatArray, withExtras: Boolean = false): String {
         val x = vals.getOrNull(0) ?: 0f
         val y = vals.getOrNull(1) ?: 0f
         val z = vals.getOrNull(2) ?: 0f
-        return String.format(Locale.US, "%d,%s,%.5f,%.5f,%.5f,0,0\n", ts, name, x, y, z)
+        if (!withExtras) {
+            return String.format(Locale.US, "%d,%s,%.5f,%.5f,%.5f,0,0\n", ts, name, x, y, z)
+        }
+        val e1 = vals.getOrNull(3) ?: 0f
+        val e2 = vals.getOrNull(4) ?: 0f
+        return String.format(Locale.US, "%d,%s,%.5f,%.5f,%.5f,%.5f,%.5f\n", ts, name, x, y, z, e1, e2)
+    }
+
+    private fun hasActivityRecognitionPermission(): Boolean {
+        if (android.os.Build.VERSION.SDK_INT < android.os.Build.VERSION_CODES.Q) return true
+        return ContextCompat.checkSelfPermission(activity, Manifest.permission.ACTIVITY_RECOGNITION) == PackageManager.PERMISSION_GRANTED
     }
 
     private fun hasLocationPermission(): Boolean {
@@ -430,20 +669,21 @@ class AppSensorManager(
         val mgr = locationManager ?: return
         val listener = object : LocationListener {
             override fun onLocationChanged(location: Location) {
-                if (!isLogging) return
+                if (!isLogging || isPaused) return
                 val ts = System.currentTimeMillis()
                 val nowMono = android.os.SystemClock.elapsedRealtime()
                 val lat = location.latitude
                 val lon = location.longitude
                 val acc = location.accuracy.toDouble()
-                val writer = logWriter ?: return
+                if (logSink == null) return
                 val bindings = mutableMapOf<String, String>()
                 bindings["sensor_status"] = "logging"
                 logFile?.absolutePath?.let { bindings["sensor_path"] = it }
                 try {
-                    writer.write("$ts,GPS,$lat,$lon,$acc,-1,-1\n")
-                    writer.flush()
+                    writeRow("$ts,GPS,$lat,$lon,$acc,-1,-1\n")
+                    logSink?.flush()
                     lastSensorLogPath = logFile?.absolutePath
+                    maybeRotate()
                     if (nowMono - lastSensorUiTs > 500) {
                         lastSensorUiTs = nowMono
                         refreshUi("sensor_logger_status", bindings)
@@ -489,8 +729,7 @@ class AppSensorManager(
         lastCompassDispatchTs = now
     }
 
-    private fun jsonHasWidget(json: String, widgetType: String): Boolean {
-        val root = runCatching { JSONObject(json) }.getOrNull() ?: return false
+    private fun hasWidget(root: JSONObject, widgetType: String): Boolean {
         fun walk(obj: JSONObject): Boolean {
             if (obj.optString("type") == widgetType) return true
             val children = obj.optJSONArray("children") ?: return false
@@ -504,14 +743,21 @@ class AppSensorManager(
     }
 
     private fun sendSensorUpdate(action: String, extras: Map<String, Any?>) {
-        scope.launch {
-            launch(Dispatchers.IO) {
-                val command = JSONObject().apply {
-                    put("action", action)
-                    extras.forEach { (k, v) -> put(k, v) }
-                }
-                dispatchRaw(command.toString())
+        val command = JSONObject().apply {
+            put("action", action)
+            extras.forEach { (k, v) -> put(k, v) }
+        }
+        val flushQueued = synchronized(pendingUpdates) {
+            pendingUpdates.add(command)
+            pendingUpdates.size > 1
+        }
+        // Updates arriving before the queued flush runs ride along in the same batch.
+        if (flushQueued) return
+        scope.launch(Dispatchers.IO) {
+            val batch = synchronized(pendingUpdates) {
+                JSONArray(pendingUpdates.toList()).also { pendingUpdates.clear() }
             }
+            dispatchRaw(batch.toString())
         }
     }
 
@@ -522,12 +768,35 @@ class AppSensorManager(
         val pressure: Boolean,
         val gps: Boolean,
         val battery: Boolean,
+        val light: Boolean = false,
+        val proximity: Boolean = false,
+        val gravity: Boolean = false,
+        val linearAccel: Boolean = false,
+        val rotation: Boolean = false,
+        val steps: Boolean = false,
+    ) {
+        fun any(): Boolean = accel || gyro || mag || pressure || gps || battery ||
+            light || proximity || gravity || linearAccel || rotation || steps
+    }
+
+    data class LogLimits(
+        val maxFileBytes: Long?,
+        val maxDurationMs: Long?,
     ) {
-        fun any(): Boolean = accel || gyro || mag || pressure || gps || battery
+        fun rotates(): Boolean = maxFileBytes != null || maxDurationMs != null
     }
 
     data class SensorConfig(
         val selection: SensorSelection,
-        val intervalMs: Long
+        val intervalMs: Long,
+        val limits: LogLimits = LogLimits(null, null),
+        val backend: LogBackend = LogBackend.CSV,
     )
+
+    enum class LogBackend(val extension: String) { CSV("csv"), NDJSON("ndjson"), SQLITE("db") }
+
+    private companion object {
+        const val CSV_HEADER = "ts,sensor,x,y,z,extra1,extra2\n"
+        val PEAK_TAGS = setOf("ACCEL", "GYRO")
+    }
 }
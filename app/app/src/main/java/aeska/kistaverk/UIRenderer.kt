@@ -1,5 +1,6 @@
 package aeska.kistaverk
 
+import android.app.AlertDialog
 import android.content.Context
 import android.graphics.Color
 import android.graphics.Bitmap
@@ -7,6 +8,7 @@ import android.graphics.BitmapFactory
 import android.graphics.Canvas
 import android.graphics.Paint
 import android.graphics.Path
+import android.graphics.drawable.BitmapDrawable
 import android.graphics.drawable.GradientDrawable
 import android.graphics.pdf.PdfRenderer
 import android.opengl.GLES20
@@ -19,7 +21,9 @@ import android.os.Handler
 import android.os.Looper
 import android.util.Base64
 import android.view.View
+import android.view.GestureDetector
 import android.view.MotionEvent
+import android.view.ScaleGestureDetector
 import android.view.ViewGroup
 import android.view.Gravity
 import android.graphics.Matrix
@@ -29,11 +33,13 @@ import android.text.InputType
 import android.widget.Button
 import android.widget.EditText
 import android.widget.CheckBox
+import android.widget.CompoundButton
 import android.widget.GridLayout
 import android.widget.LinearLayout.LayoutParams
 import android.widget.FrameLayout
 import android.widget.LinearLayout
 import android.widget.Space
+import android.widget.Switch
 import android.webkit.WebSettings
 import android.webkit.WebView
 import android.widget.ScrollView
@@ -42,8 +48,20 @@ import android.widget.TextView
 import java.io.ByteArrayOutputStream
 import org.json.JSONArray
 import android.text.Editable
+import android.text.SpannableString
+import android.text.SpannableStringBuilder
+import android.text.Spanned
+import android.text.method.LinkMovementMethod
+import android.text.style.BackgroundColorSpan
+import android.text.style.LeadingMarginSpan
+import android.text.style.RelativeSizeSpan
+import android.text.style.StyleSpan
+import android.text.style.TypefaceSpan
+import android.text.style.URLSpan
+import android.graphics.Typeface
 import android.text.TextWatcher
 import android.widget.ProgressBar
+import android.widget.SeekBar
 import android.widget.HorizontalScrollView
 import org.json.JSONObject
 import javax.microedition.khronos.egl.EGLConfig
@@ -51,6 +69,7 @@ import javax.microedition.khronos.opengles.GL10
 import kotlin.math.cos
 import kotlin.math.sin
 import kotlin.math.floor
+import kotlin.math.roundToInt
 
 // Added 'onAction' callback: (String, Boolean) -> Unit where the boolean flags file picker needs
 class UiRenderer(
@@ -70,27 +89,37 @@ class UiRenderer(
         "Section" to { data, matched -> createSection(data, matched as? LinearLayout) },
         "Card" to { data, matched -> createCard(data, matched as? LinearLayout) },
         "Text" to { data, matched -> createText(data, matched as? TextView) },
+        "RichText" to { data, matched -> createRichText(data, matched as? TextView) },
         "Button" to { data, matched -> createButton(data, matched as? Button) },
         "ShaderToy" to { data, matched -> createShaderToy(data, matched as? ShaderToyView) },
         "TextInput" to { data, matched -> createTextInput(data, matched as? EditText) },
         "Checkbox" to { data, matched -> createCheckbox(data, matched as? CheckBox) },
+        "Switch" to { data, matched -> createSwitch(data, matched as? Switch) },
+        "Slider" to { data, matched -> createSlider(data, matched as? SeekBar) },
         "Progress" to { data, matched -> createProgress(data, matched as? LinearLayout) },
+        "ProgressBar" to { data, matched -> createProgressBar(data, matched as? LinearLayout) },
+        "ConfirmDialog" to { data, matched -> createConfirmDialog(data, matched) },
         "Grid" to { data, matched -> createGrid(data, matched as? LinearLayout) },
+        "Tabs" to { data, matched -> createTabs(data, matched as? LinearLayout) },
+        "Table" to { data, matched -> createTable(data, matched as? LinearLayout) },
+        "ReorderableList" to { data, matched -> createReorderableList(data, matched as? LinearLayout) },
         "VirtualList" to { data, matched -> createVirtualList(data, matched as? LinearLayout) },
         "ImageBase64" to { data, matched -> createImageBase64(data, matched as? LinearLayout) },
+        "Image" to { data, matched -> createImage(data, matched as? ZoomImageView) },
         "ColorSwatch" to { data, matched -> createColorSwatch(data, matched) },
         "PdfPagePicker" to { data, matched -> createPdfPagePicker(data, matched as? HorizontalScrollView) },
         "SignaturePad" to { data, matched -> createSignaturePad(data, matched as? SignaturePadView) },
         "PdfSignPlacement" to { data, matched -> createPdfSignPlacement(data, matched as? SignPlacementView) },
         "PdfSignPreview" to { data, matched -> createPdfSignPreview(data, matched as? PdfSignPreview) },
         "PdfPreviewGrid" to { data, matched -> createPdfPreviewGrid(data, matched as? ScrollView) },
-        "PdfSinglePage" to { data, matched -> createPdfSinglePage(data, matched as? ImageView) },
+        "PdfSinglePage" to { data, matched -> createPdfSinglePage(data, matched as? ZoomImageView) },
         "CodeView" to { data, matched -> createCodeView(data, matched as? WebView) },
         "HtmlView" to { data, matched -> createHtmlView(data, matched as? WebView) },
         "Compass" to { data, matched -> createCompass(data, matched) },
         "Barometer" to { data, matched -> createBarometer(data, matched as? SensorShaderView) },
         "Magnetometer" to { data, matched -> createMagnetometer(data, matched as? SensorShaderView) },
         "Ruler" to { data, matched -> createRuler(data, matched as? RulerView) },
+        "Chart" to { data, matched -> createChart(data, matched as? ChartView) },
     )
     private val host = FrameLayout(context).apply {
         layoutParams = FrameLayout.LayoutParams(
@@ -105,19 +134,33 @@ class UiRenderer(
     private var findStatusView: TextView? = null
     private val bindings = mutableMapOf<String, String>()
     private val pendingBindingUpdates = mutableMapOf<String, Runnable>()
+    private var confirmDialog: AlertDialog? = null
+    private var confirmDialogKey: String? = null
+    private var confirmDialogRendered = false
+    /** From the root's `layout_direction`; views inherit it, glyphs that point read it. */
+    private var rtl = false
     private val allowedTypes = setOf(
         "Column",
         "Row",
         "Section",
         "Card",
         "Text",
+        "RichText",
         "Button",
         "ShaderToy",
         "TextInput",
         "Checkbox",
+        "Switch",
+        "Slider",
         "Progress",
+        "ProgressBar",
+        "ConfirmDialog",
         "Grid",
+        "Tabs",
+        "Table",
+        "ReorderableList",
         "ImageBase64",
+        "Image",
         "ColorSwatch",
         "PdfPagePicker",
         "SignaturePad",
@@ -131,15 +174,18 @@ class UiRenderer(
         "Barometer",
         "Magnetometer",
         "Ruler",
+        "Chart",
         "VirtualList"
     )
 
-    fun render(jsonString: String): View {
+    fun render(jsonString: String): View = render(runCatching { JSONObject(jsonString) }.getOrNull())
+
+    /** Renders an already parsed tree; null stands for a response that was not JSON. */
+    fun render(rootJson: JSONObject?): View {
         bindings.clear()
+        confirmDialogRendered = false
         findStatusView = null
-        val rootJson = try {
-            JSONObject(jsonString)
-        } catch (e: Exception) {
+        if (rootJson == null) {
             return setHostContent(renderFallback("Render error", "Invalid JSON"))
         }
 
@@ -148,7 +194,10 @@ class UiRenderer(
             return setHostContent(renderFallback("Render error", validationError))
         }
 
+        rtl = rootJson.optString("layout_direction", "ltr") == "rtl"
+        host.layoutDirection = if (rtl) View.LAYOUT_DIRECTION_RTL else View.LAYOUT_DIRECTION_LTR
         val root = createRoot(rootJson)
+        if (!confirmDialogRendered) dismissConfirmDialog()
         return setHostContent(root)
     }
 
@@ -249,6 +298,9 @@ class UiRenderer(
         if (type == "ImageBase64" && !node.has("base64")) {
             return "ImageBase64 missing base64"
         }
+        if (type == "Image" && !node.has("path") && !node.has("base64")) {
+            return "Image missing path or base64"
+        }
         if (type == "ColorSwatch" && !node.has("color")) {
             return "ColorSwatch missing color"
         }
@@ -258,6 +310,9 @@ class UiRenderer(
             val hasCopy = node.has("copy_text")
             if (!hasAction && !hasCopy) return "Button missing action or copy_text"
         }
+        if (type == "RichText" && !node.has("markdown")) {
+            return "RichText missing markdown"
+        }
         if (type == "Text" && !node.has("text")) {
             return "Text missing text"
         }
@@ -267,6 +322,12 @@ class UiRenderer(
         if (type == "Checkbox" && !node.has("bind_key")) {
             return "Checkbox missing bind_key"
         }
+        if (type == "Switch" && !node.has("bind_key")) {
+            return "Switch missing bind_key"
+        }
+        if (type == "Slider" && !node.has("bind_key")) {
+            return "Slider missing bind_key"
+        }
         if (type == "PdfPagePicker") {
             if (!node.has("page_count")) return "PdfPagePicker missing page_count"
             if (!node.has("source_uri")) return "PdfPagePicker missing source_uri"
@@ -307,6 +368,48 @@ class UiRenderer(
         if (type == "Magnetometer" && !node.has("magnitude_ut")) {
             return "Magnetometer missing magnitude_ut"
         }
+        if (type == "ConfirmDialog") {
+            if (!node.has("title")) return "ConfirmDialog missing title"
+            if (!node.has("confirm_action")) return "ConfirmDialog missing confirm_action"
+            if (!node.has("cancel_action")) return "ConfirmDialog missing cancel_action"
+        }
+        if (type == "ProgressBar" && !node.has("fraction")) {
+            return "ProgressBar missing fraction"
+        }
+        if (type == "Chart" && node.optJSONArray("series") == null) {
+            return "Chart missing series"
+        }
+        if (type == "Tabs") {
+            if (!node.has("bind_key")) return "Tabs missing bind_key"
+            val tabs = node.optJSONArray("children") ?: return "Tabs missing children"
+            for (i in 0 until tabs.length()) {
+                val tab = tabs.getJSONObject(i)
+                if (tab.optString("type", "") != "Tab") return "Tabs child must be Tab"
+                if (!tab.has("id")) return "Tab missing id"
+                val panes = tab.optJSONArray("children") ?: return "Tab missing children"
+                for (j in 0 until panes.length()) {
+                    val childErr = validate(panes.getJSONObject(j))
+                    if (childErr != null) return childErr
+                }
+            }
+        }
+        if (type == "ReorderableList") {
+            if (node.optString("bind_key", "").isEmpty()) return "ReorderableList missing bind_key"
+            if (node.optJSONArray("items") == null) return "ReorderableList missing items"
+        }
+        if (type == "Table") {
+            if (node.optJSONArray("columns") == null) return "Table missing columns"
+            val rows = node.optJSONArray("children") ?: return "Table missing children"
+            for (i in 0 until rows.length()) {
+                val row = rows.getJSONObject(i)
+                if (row.optString("type", "") != "TableRow") return "Table child must be TableRow"
+                val cells = row.optJSONArray("children") ?: return "TableRow missing children"
+                for (j in 0 until cells.length()) {
+                    val childErr = validate(cells.getJSONObject(j))
+                    if (childErr != null) return childErr
+                }
+            }
+        }
         if (type == "Grid" || type == "Column" || type == "Row" || type == "Section" || type == "Card" || type == "VirtualList") {
             val children = node.optJSONArray("children") ?: return "$type missing children"
             for (i in 0 until children.length()) {
@@ -507,7 +610,7 @@ class UiRenderer(
             row.addView(TextView(context).apply {
                 text = icon
                 textSize = 18f
-                setPadding(0, 0, dpToPx(context, 8f), 0)
+                setPaddingRelative(0, 0, dpToPx(context, 8f), 0)
             })
         }
         val textCol = LinearLayout(context).apply { orientation = LinearLayout.VERTICAL }
@@ -523,9 +626,39 @@ class UiRenderer(
             })
         }
         row.addView(textCol)
+        bindSectionToggle(row, textCol, data)
         return row
     }
 
+    /** Collapsible sections get a chevron, and a header tap asks Rust for the other state. */
+    private fun bindSectionToggle(row: LinearLayout, textCol: LinearLayout, data: JSONObject) {
+        val action = data.optString("toggle_action", "")
+        val key = data.optString("section_key", "")
+        if (action.isEmpty() || key.isEmpty() || !data.has("expanded")) return
+        val expanded = data.optBoolean("expanded", true)
+        textCol.layoutParams = LinearLayout.LayoutParams(0, LayoutParams.WRAP_CONTENT, 1f)
+        row.addView(TextView(context).apply {
+            text = when {
+                expanded -> "▾"
+                rtl -> "◂"
+                else -> "▸"
+            }
+            textSize = 18f
+            setPaddingRelative(dpToPx(context, 8f), 0, 0, 0)
+            importantForAccessibility = View.IMPORTANT_FOR_ACCESSIBILITY_NO
+        })
+        row.isClickable = true
+        if (android.os.Build.VERSION.SDK_INT >= android.os.Build.VERSION_CODES.R) {
+            row.stateDescription = if (expanded) "expanded" else "collapsed"
+        }
+        row.setOnClickListener {
+            val payload = bindings.toMutableMap()
+            payload["section_key"] = key
+            payload["section_expanded"] = (!expanded).toString()
+            onAction(action, false, false, payload)
+        }
+    }
+
     private fun createImageBase64(data: JSONObject, existing: LinearLayout?): View {
         val b64 = data.optString("base64", "")
         if (b64.isBlank()) return createErrorView("Missing base64")
@@ -566,6 +699,213 @@ class UiRenderer(
         return container
     }
 
+    private fun createImage(data: JSONObject, existing: ZoomImageView?): View {
+        val path = data.optString("path", "")
+        val b64 = data.optString("base64", "")
+        // Re-decode only when the picture changed, so re-renders keep the current zoom.
+        val sourceKey = if (path.isNotEmpty()) {
+            "path:$path:${java.io.File(path).lastModified()}"
+        } else {
+            "base64:${b64.length}:${b64.hashCode()}"
+        }
+        val image = existing ?: ZoomImageView(context)
+        if (image.getTag(dataTag) != sourceKey) {
+            val bmp = if (path.isNotEmpty()) decodeImageFile(path) else decodeImageBase64(b64)
+            if (bmp == null) return createErrorView("Image unavailable")
+            image.setImageBitmap(bmp)
+            image.setTag(dataTag, sourceKey)
+        }
+        val maxHeightDp = data.optInt("max_height_dp", 0)
+        image.maxHeight = if (maxHeightDp > 0) dpToPx(context, maxHeightDp.toFloat()) else Int.MAX_VALUE
+        image.layoutParams = LayoutParams(LayoutParams.MATCH_PARENT, LayoutParams.WRAP_CONTENT).apply {
+            topMargin = dpToPx(context, 8f)
+            bottomMargin = dpToPx(context, 8f)
+        }
+        val cd = data.optString("content_description", "")
+        image.contentDescription = cd.takeIf { it.isNotEmpty() }
+        bindZoom(image, data.optBoolean("zoomable", false))
+        setMeta(image, "Image", resolveNodeId(data))
+        return image
+    }
+
+    private fun bindZoom(image: ZoomImageView, zoomable: Boolean) {
+        image.zoomEnabled = zoomable
+        if (zoomable) {
+            image.setOnClickListener { view ->
+                val bmp = (view as ZoomImageView).bitmap ?: return@setOnClickListener
+                showImageFullscreen(bmp, view.contentDescription)
+            }
+        } else {
+            image.setOnClickListener(null)
+            image.isClickable = false
+        }
+    }
+
+    private fun showImageFullscreen(bitmap: Bitmap, description: CharSequence?) {
+        val dialog = android.app.Dialog(context, android.R.style.Theme_Black_NoTitleBar_Fullscreen)
+        val view = ZoomImageView(context).apply {
+            adjustViewBounds = false
+            scaleType = ImageView.ScaleType.MATRIX
+            setBackgroundColor(Color.BLACK)
+            setImageBitmap(bitmap)
+            zoomEnabled = true
+            contentDescription = description
+            setOnClickListener { dialog.dismiss() }
+        }
+        dialog.setContentView(
+            view,
+            ViewGroup.LayoutParams(ViewGroup.LayoutParams.MATCH_PARENT, ViewGroup.LayoutParams.MATCH_PARENT)
+        )
+        dialog.show()
+    }
+
+    private fun decodeImageBase64(b64: String): Bitmap? {
+        val bytes = try {
+            android.util.Base64.decode(b64, android.util.Base64.DEFAULT)
+        } catch (_: Exception) {
+            return null
+        }
+        return BitmapFactory.decodeByteArray(bytes, 0, bytes.size)
+    }
+
+    /** Decodes a file path or content:// uri, subsampled so the longest side stays near [IMAGE_MAX_DECODE_PX]. */
+    private fun decodeImageFile(path: String): Bitmap? {
+        val open = {
+            if (path.startsWith("content://")) {
+                context.contentResolver.openInputStream(Uri.parse(path))
+            } else {
+                java.io.FileInputStream(path)
+            }
+        }
+        return try {
+            val bounds = BitmapFactory.Options().apply { inJustDecodeBounds = true }
+            open()?.use { BitmapFactory.decodeStream(it, null, bounds) }
+            if (bounds.outWidth <= 0 || bounds.outHeight <= 0) return null
+            var sample = 1
+            while (maxOf(bounds.outWidth, bounds.outHeight) / sample > IMAGE_MAX_DECODE_PX) sample *= 2
+            val options = BitmapFactory.Options().apply { inSampleSize = sample }
+            open()?.use { BitmapFactory.decodeStream(it, null, options) }
+        } catch (_: Exception) {
+            null
+        }
+    }
+
+    /**
+     * Fits its bitmap like FIT_CENTER; when [zoomEnabled] it adds pinch-zoom, drag-to-pan and
+     * double-tap zoom on top of that fit. Single taps go to the regular click listener.
+     */
+    private class ZoomImageView(context: Context) : ImageView(context) {
+        var zoomEnabled = false
+            set(value) {
+                field = value
+                if (!value) resetZoom()
+            }
+        private var zoom = 1f
+        private var panX = 0f
+        private var panY = 0f
+        private val drawMatrix = Matrix()
+
+        private val scaleDetector = ScaleGestureDetector(
+            context,
+            object : ScaleGestureDetector.SimpleOnScaleGestureListener() {
+                override fun onScale(detector: ScaleGestureDetector): Boolean {
+                    zoomAround(zoom * detector.scaleFactor, detector.focusX, detector.focusY)
+                    return true
+                }
+            }
+        )
+        private val gestureDetector = GestureDetector(
+            context,
+            object : GestureDetector.SimpleOnGestureListener() {
+                override fun onDown(e: MotionEvent): Boolean = true
+
+                override fun onSingleTapConfirmed(e: MotionEvent): Boolean = performClick()
+
+                override fun onDoubleTap(e: MotionEvent): Boolean {
+                    zoomAround(if (zoom > 1f) 1f else DOUBLE_TAP_ZOOM, e.x, e.y)
+                    return true
+                }
+
+                override fun onScroll(e1: MotionEvent?, e2: MotionEvent, dx: Float, dy: Float): Boolean {
+                    if (zoom <= 1f) return false
+                    panX -= dx
+                    panY -= dy
+                    applyZoom()
+                    return true
+                }
+            }
+        )
+
+        init {
+            adjustViewBounds = true
+            // adjustViewBounds forces FIT_CENTER, so the matrix mode has to come after it.
+            scaleType = ScaleType.MATRIX
+        }
+
+        val bitmap: Bitmap?
+            get() = (drawable as? BitmapDrawable)?.bitmap
+
+        override fun setImageBitmap(bm: Bitmap?) {
+            super.setImageBitmap(bm)
+            resetZoom()
+        }
+
+        override fun onSizeChanged(w: Int, h: Int, oldw: Int, oldh: Int) {
+            super.onSizeChanged(w, h, oldw, oldh)
+            applyZoom()
+        }
+
+        override fun onTouchEvent(event: MotionEvent): Boolean {
+            if (!zoomEnabled) return super.onTouchEvent(event)
+            scaleDetector.onTouchEvent(event)
+            gestureDetector.onTouchEvent(event)
+            // While zoomed or pinching, drags pan the image instead of scrolling the screen.
+            parent?.requestDisallowInterceptTouchEvent(zoom > 1f || event.pointerCount > 1)
+            return true
+        }
+
+        private fun resetZoom() {
+            zoom = 1f
+            panX = 0f
+            panY = 0f
+            applyZoom()
+        }
+
+        private fun zoomAround(target: Float, focusX: Float, focusY: Float) {
+            val next = target.coerceIn(1f, MAX_ZOOM)
+            val ratio = next / zoom
+            val cx = focusX - paddingLeft - (width - paddingLeft - paddingRight) / 2f
+            val cy = focusY - paddingTop - (height - paddingTop - paddingBottom) / 2f
+            panX = cx - (cx - panX) * ratio
+            panY = cy - (cy - panY) * ratio
+            zoom = next
+            applyZoom()
+        }
+
+        private fun applyZoom() {
+            val d = drawable ?: return
+            val viewW = (width - paddingLeft - paddingRight).toFloat()
+            val viewH = (height - paddingTop - paddingBottom).toFloat()
+            if (viewW <= 0f || viewH <= 0f || d.intrinsicWidth <= 0 || d.intrinsicHeight <= 0) return
+            val scale = minOf(viewW / d.intrinsicWidth, viewH / d.intrinsicHeight) * zoom
+            val contentW = d.intrinsicWidth * scale
+            val contentH = d.intrinsicHeight * scale
+            // Pan is measured from the centred position and never reveals space past an edge.
+            val maxPanX = ((contentW - viewW) / 2f).coerceAtLeast(0f)
+            val maxPanY = ((contentH - viewH) / 2f).coerceAtLeast(0f)
+            panX = panX.coerceIn(-maxPanX, maxPanX)
+            panY = panY.coerceIn(-maxPanY, maxPanY)
+            drawMatrix.setScale(scale, scale)
+            drawMatrix.postTranslate((viewW - contentW) / 2f + panX, (viewH - contentH) / 2f + panY)
+            imageMatrix = drawMatrix
+        }
+
+        companion object {
+            private const val MAX_ZOOM = 8f
+            private const val DOUBLE_TAP_ZOOM = 2.5f
+        }
+    }
+
     private fun createColorSwatch(data: JSONObject, existing: View?): View {
         val colorLong = data.optLong("color", 0xFF000000)
         val view = existing ?: View(context)
@@ -619,7 +959,7 @@ class UiRenderer(
 
     private fun createText(data: JSONObject, existing: TextView?): View {
         val view = existing ?: TextView(context)
-        view.text = data.optString("text")
+        view.text = styledText(data.optString("text"), data.optJSONArray("spans"))
         view.textSize = data.optDouble("size", 14.0).toFloat()
         val contentDescription = data.optString("content_description", "")
         view.contentDescription = contentDescription.takeIf { it.isNotEmpty() }
@@ -631,6 +971,202 @@ class UiRenderer(
         return view
     }
 
+    private fun styledText(text: String, spans: JSONArray?): CharSequence {
+        if (spans == null || spans.length() == 0) return text
+        val styled = SpannableString(text)
+        for (i in 0 until spans.length()) {
+            val span = spans.optJSONObject(i) ?: continue
+            val start = span.optInt("start", 0).coerceIn(0, text.length)
+            val end = span.optInt("end", start).coerceIn(start, text.length)
+            if (start == end) continue
+            val background = span.optString("background", "")
+            if (background.isNotBlank()) {
+                val color = runCatching { Color.parseColor(background) }.getOrNull()
+                if (color != null) {
+                    styled.setSpan(BackgroundColorSpan(color), start, end, Spanned.SPAN_EXCLUSIVE_EXCLUSIVE)
+                }
+            }
+            if (span.optBoolean("bold", false)) {
+                styled.setSpan(StyleSpan(Typeface.BOLD), start, end, Spanned.SPAN_EXCLUSIVE_EXCLUSIVE)
+            }
+        }
+        return styled
+    }
+
+    private fun createRichText(data: JSONObject, existing: TextView?): View {
+        val view = existing ?: TextView(context)
+        val rendered = renderMarkdown(data.optString("markdown", ""))
+        view.text = rendered
+        view.textSize = data.optDouble("size", 14.0).toFloat()
+        // Links only become tappable with a movement method; skip it otherwise so plain
+        // rich text does not swallow scroll gestures.
+        val hasLinks = rendered.getSpans(0, rendered.length, URLSpan::class.java).isNotEmpty()
+        view.movementMethod = if (hasLinks) LinkMovementMethod.getInstance() else null
+        val contentDescription = data.optString("content_description", "")
+        view.contentDescription = contentDescription.takeIf { it.isNotEmpty() }
+        setMeta(view, "RichText", resolveNodeId(data))
+        return view
+    }
+
+    /**
+     * Renders the markdown subset used by help and preview text: paragraphs, `#` headings,
+     * fenced code blocks, `-`/`*`/`1.` lists nested by two-space indents, and inline
+     * bold, italic, code and links. Anything else is shown literally.
+     */
+    private fun renderMarkdown(markdown: String): SpannableStringBuilder {
+        val out = SpannableStringBuilder()
+        val paragraph = StringBuilder()
+        var gapPending = false
+        fun startBlock() {
+            if (out.isNotEmpty()) out.append(if (gapPending) "\n\n" else "\n")
+            gapPending = false
+        }
+        fun flushParagraph() {
+            if (paragraph.isEmpty()) return
+            startBlock()
+            appendInlineMarkdown(out, paragraph.toString())
+            paragraph.clear()
+        }
+        val lines = markdown.lines()
+        var i = 0
+        while (i < lines.size) {
+            val line = lines[i]
+            val trimmed = line.trim()
+            val heading = MARKDOWN_HEADING.matchEntire(trimmed)
+            val listItem = MARKDOWN_LIST_ITEM.matchEntire(line)
+            when {
+                trimmed.startsWith("```") -> {
+                    flushParagraph()
+                    val code = mutableListOf<String>()
+                    i++
+                    while (i < lines.size && !lines[i].trim().startsWith("```")) {
+                        code.add(lines[i])
+                        i++
+                    }
+                    startBlock()
+                    val start = out.length
+                    out.append(code.joinToString("\n"))
+                    out.setSpan(TypefaceSpan("monospace"), start, out.length, Spanned.SPAN_EXCLUSIVE_EXCLUSIVE)
+                    out.setSpan(BackgroundColorSpan(MARKDOWN_CODE_BACKGROUND), start, out.length, Spanned.SPAN_EXCLUSIVE_EXCLUSIVE)
+                    gapPending = true
+                }
+                trimmed.isEmpty() -> {
+                    flushParagraph()
+                    if (out.isNotEmpty()) gapPending = true
+                }
+                heading != null -> {
+                    flushParagraph()
+                    startBlock()
+                    val start = out.length
+                    appendInlineMarkdown(out, heading.groupValues[2])
+                    val scale = when (heading.groupValues[1].length) {
+                        1 -> 1.4f
+                        2 -> 1.2f
+                        else -> 1.1f
+                    }
+                    out.setSpan(StyleSpan(Typeface.BOLD), start, out.length, Spanned.SPAN_EXCLUSIVE_EXCLUSIVE)
+                    out.setSpan(RelativeSizeSpan(scale), start, out.length, Spanned.SPAN_EXCLUSIVE_EXCLUSIVE)
+                }
+                listItem != null -> {
+                    flushParagraph()
+                    startBlock()
+                    val level = listItem.groupValues[1].length / 2
+                    val marker = listItem.groupValues[2]
+                    val start = out.length
+                    out.append(if (marker[0].isDigit()) "$marker " else "\u2022 ")
+                    appendInlineMarkdown(out, listItem.groupValues[3])
+                    val indent = dpToPx(context, 16f) * level
+                    out.setSpan(
+                        LeadingMarginSpan.Standard(indent, indent + dpToPx(context, 12f)),
+                        start,
+                        out.length,
+                        Spanned.SPAN_EXCLUSIVE_EXCLUSIVE
+                    )
+                }
+                else -> {
+                    if (paragraph.isNotEmpty()) paragraph.append(' ')
+                    paragraph.append(trimmed)
+                }
+            }
+            i++
+        }
+        flushParagraph()
+        return out
+    }
+
+    private fun appendInlineMarkdown(out: SpannableStringBuilder, text: String) {
+        var i = 0
+        while (i < text.length) {
+            val c = text[i]
+            if (c == '\\' && i + 1 < text.length && text[i + 1] in MARKDOWN_ESCAPABLE) {
+                out.append(text[i + 1])
+                i += 2
+                continue
+            }
+            val consumed = when {
+                c == '`' -> {
+                    val end = text.indexOf('`', i + 1)
+                    if (end > i + 1) {
+                        val start = out.length
+                        out.append(text, i + 1, end)
+                        out.setSpan(TypefaceSpan("monospace"), start, out.length, Spanned.SPAN_EXCLUSIVE_EXCLUSIVE)
+                        out.setSpan(BackgroundColorSpan(MARKDOWN_CODE_BACKGROUND), start, out.length, Spanned.SPAN_EXCLUSIVE_EXCLUSIVE)
+                        end + 1
+                    } else null
+                }
+                text.startsWith("**", i) -> {
+                    val end = findUnescaped(text, "**", i + 2)
+                    if (end > i + 2) {
+                        val start = out.length
+                        appendInlineMarkdown(out, text.substring(i + 2, end))
+                        out.setSpan(StyleSpan(Typeface.BOLD), start, out.length, Spanned.SPAN_EXCLUSIVE_EXCLUSIVE)
+                        end + 2
+                    } else null
+                }
+                // `_` inside identifiers such as snake_case stays literal.
+                c == '*' || (c == '_' && (i == 0 || !text[i - 1].isLetterOrDigit())) -> {
+                    val end = findUnescaped(text, c.toString(), i + 1)
+                    if (end > i + 1) {
+                        val start = out.length
+                        appendInlineMarkdown(out, text.substring(i + 1, end))
+                        out.setSpan(StyleSpan(Typeface.ITALIC), start, out.length, Spanned.SPAN_EXCLUSIVE_EXCLUSIVE)
+                        end + 1
+                    } else null
+                }
+                c == '[' -> {
+                    val close = findUnescaped(text, "](", i + 1)
+                    val urlEnd = if (close > i) text.indexOf(')', close + 2) else -1
+                    if (urlEnd > close + 2) {
+                        val start = out.length
+                        appendInlineMarkdown(out, text.substring(i + 1, close))
+                        out.setSpan(URLSpan(text.substring(close + 2, urlEnd)), start, out.length, Spanned.SPAN_EXCLUSIVE_EXCLUSIVE)
+                        urlEnd + 1
+                    } else null
+                }
+                else -> null
+            }
+            if (consumed != null) {
+                i = consumed
+            } else {
+                out.append(c)
+                i++
+            }
+        }
+    }
+
+    private fun findUnescaped(text: String, delimiter: String, from: Int): Int {
+        var i = from
+        while (i < text.length) {
+            if (text[i] == '\\') {
+                i += 2
+                continue
+            }
+            if (text.startsWith(delimiter, i)) return i
+            i++
+        }
+        return -1
+    }
+
     private fun createCodeView(data: JSONObject, existing: WebView?): View {
         val text = data.optString("text", "")
         val language = data.optString("language", "none").ifBlank { "none" }
@@ -831,6 +1367,38 @@ class UiRenderer(
         return view
     }
 
+    private fun createChart(data: JSONObject, existing: ChartView?): View {
+        val view = existing ?: ChartView(context)
+        val heightDp = data.optInt("height_dp", 120).coerceAtLeast(48)
+        val seriesJson = data.optJSONArray("series") ?: JSONArray()
+        val series = (0 until seriesJson.length()).mapNotNull { i ->
+            val entry = seriesJson.optJSONObject(i) ?: return@mapNotNull null
+            val values = entry.optJSONArray("values") ?: JSONArray()
+            val points = FloatArray(values.length()) { j -> values.optDouble(j, 0.0).toFloat() }
+            val color = entry.optString("color", "").takeIf { it.isNotBlank() }?.let {
+                runCatching { Color.parseColor(it) }.getOrNull()
+            }
+            ChartView.Series(entry.optString("label", ""), points, color)
+        }
+        val categoriesJson = data.optJSONArray("categories") ?: JSONArray()
+        val axes = ChartView.Axes(
+            bars = data.optString("style", "") == "bar",
+            xLabel = data.optString("x_label", ""),
+            yLabel = data.optString("y_label", ""),
+            yMin = if (data.has("y_min")) data.optDouble("y_min").toFloat() else null,
+            yMax = if (data.has("y_max")) data.optDouble("y_max").toFloat() else null,
+            categories = (0 until categoriesJson.length()).map { categoriesJson.optString(it, "") },
+        )
+        view.setSeries(series, axes)
+        view.contentDescription = data.optString("content_description", "").takeIf { it.isNotBlank() }
+        view.layoutParams = LinearLayout.LayoutParams(
+            LinearLayout.LayoutParams.MATCH_PARENT,
+            dpToPx(context, heightDp.toFloat())
+        )
+        setMeta(view, "Chart", resolveNodeId(data))
+        return view
+    }
+
     private fun createTextInput(data: JSONObject, existing: EditText?): View {
         val editText = existing ?: EditText(context)
         val bindKey = data.optString("bind_key", "")
@@ -939,6 +1507,151 @@ class UiRenderer(
         return view
     }
 
+    private class ChartView(context: Context) : View(context) {
+        data class Series(val label: String, val values: FloatArray, val color: Int? = null)
+        data class Axes(
+            val bars: Boolean = false,
+            val xLabel: String = "",
+            val yLabel: String = "",
+            val yMin: Float? = null,
+            val yMax: Float? = null,
+            val categories: List<String> = emptyList(),
+        )
+
+        private val palette = intArrayOf(
+            Color.rgb(0x1E, 0x88, 0xE5),
+            Color.rgb(0xE5, 0x39, 0x35),
+            Color.rgb(0x43, 0xA0, 0x47),
+            Color.rgb(0xFB, 0x8C, 0x00),
+        )
+        private val linePaint = Paint(Paint.ANTI_ALIAS_FLAG).apply {
+            style = Paint.Style.STROKE
+            strokeWidth = dp(2f)
+        }
+        private val axisPaint = Paint(Paint.ANTI_ALIAS_FLAG).apply {
+            color = Color.LTGRAY
+            strokeWidth = dp(1f)
+        }
+        private val labelPaint = Paint(Paint.ANTI_ALIAS_FLAG).apply {
+            color = Color.DKGRAY
+            textSize = dp(11f)
+        }
+        private val barPaint = Paint(Paint.ANTI_ALIAS_FLAG).apply {
+            style = Paint.Style.FILL
+        }
+        private var series: List<Series> = emptyList()
+        private var axes = Axes()
+
+        fun setSeries(value: List<Series>, axes: Axes = Axes()) {
+            series = value
+            this.axes = axes
+            invalidate()
+        }
+
+        override fun onDraw(canvas: Canvas) {
+            super.onDraw(canvas)
+            if (width <= 0 || height <= 0) return
+            val all = series.flatMap { it.values.asIterable() }.filter { it.isFinite() }
+            val pad = dp(4f)
+            val lineHeight = labelPaint.textSize + pad
+            var min = axes.yMin ?: (all.minOrNull() ?: 0f).let { if (axes.bars) minOf(it, 0f) else it }
+            var max = axes.yMax ?: (all.maxOrNull() ?: 0f)
+            if (max - min < 1e-6f) {
+                min -= 1f
+                max += 1f
+            }
+            val span = max - min
+            val maxText = formatTick(max)
+            val minText = formatTick(min)
+
+            var left = maxOf(labelPaint.measureText(maxText), labelPaint.measureText(minText)) + pad
+            if (axes.yLabel.isNotBlank()) left += lineHeight
+            val top = lineHeight + pad
+            var bottom = height - pad
+            if (axes.xLabel.isNotBlank()) bottom -= lineHeight
+            if (axes.categories.isNotEmpty()) bottom -= lineHeight
+            val right = width - pad
+            if (bottom <= top || right <= left) return
+
+            labelPaint.color = Color.DKGRAY
+            canvas.drawLine(left, bottom, right, bottom, axisPaint)
+            canvas.drawLine(left, top, left, bottom, axisPaint)
+            canvas.drawText(maxText, left - pad - labelPaint.measureText(maxText), top + labelPaint.textSize / 2, labelPaint)
+            canvas.drawText(minText, left - pad - labelPaint.measureText(minText), bottom, labelPaint)
+            if (axes.yLabel.isNotBlank()) {
+                canvas.save()
+                canvas.rotate(-90f, labelPaint.textSize, (top + bottom) / 2)
+                val w = labelPaint.measureText(axes.yLabel)
+                canvas.drawText(axes.yLabel, labelPaint.textSize - w / 2, (top + bottom) / 2, labelPaint)
+                canvas.restore()
+            }
+            if (axes.xLabel.isNotBlank()) {
+                val w = labelPaint.measureText(axes.xLabel)
+                canvas.drawText(axes.xLabel, (left + right - w) / 2, height - pad, labelPaint)
+            }
+            if (all.isEmpty()) return
+
+            val plotWidth = right - left
+            fun yOf(v: Float) = bottom - ((v.coerceIn(min, max) - min) / span) * (bottom - top)
+            val slots = series.maxOf { it.values.size }.coerceAtLeast(1)
+            val slotWidth = plotWidth / slots
+            fun xOf(i: Int, n: Int) = if (axes.bars) {
+                left + slotWidth * (i + 0.5f)
+            } else {
+                left + if (n > 1) i * plotWidth / (n - 1) else 0f
+            }
+
+            if (axes.bars) {
+                val baseline = yOf(0f)
+                val groupWidth = slotWidth * 0.8f
+                val barWidth = groupWidth / series.size.coerceAtLeast(1)
+                series.forEachIndexed { idx, s ->
+                    barPaint.color = s.color ?: palette[idx % palette.size]
+                    s.values.forEachIndexed { i, v ->
+                        if (!v.isFinite()) return@forEachIndexed
+                        val x0 = left + slotWidth * i + (slotWidth - groupWidth) / 2 + idx * barWidth
+                        val y = yOf(v)
+                        canvas.drawRect(x0, minOf(y, baseline), x0 + barWidth, maxOf(y, baseline), barPaint)
+                    }
+                }
+            } else {
+                series.forEachIndexed { idx, s ->
+                    linePaint.color = s.color ?: palette[idx % palette.size]
+                    val n = s.values.size
+                    if (n == 0) return@forEachIndexed
+                    val path = Path()
+                    s.values.forEachIndexed { i, v ->
+                        val x = xOf(i, n)
+                        val y = yOf(v)
+                        if (i == 0) path.moveTo(x, y) else path.lineTo(x, y)
+                    }
+                    canvas.drawPath(path, linePaint)
+                }
+            }
+
+            series.forEachIndexed { idx, s ->
+                labelPaint.color = s.color ?: palette[idx % palette.size]
+                canvas.drawText(s.label, left + pad + idx * (plotWidth / series.size), labelPaint.textSize, labelPaint)
+            }
+            labelPaint.color = Color.DKGRAY
+            // Labels that would overlap the previous one are skipped, so dense axes stay legible.
+            var lastEnd = Float.NEGATIVE_INFINITY
+            axes.categories.take(slots).forEachIndexed { i, label ->
+                val w = labelPaint.measureText(label)
+                val x = (xOf(i, slots) - w / 2).coerceIn(left, (right - w).coerceAtLeast(left))
+                if (x < lastEnd + pad) return@forEachIndexed
+                canvas.drawText(label, x, bottom + lineHeight, labelPaint)
+                lastEnd = x + w
+            }
+        }
+
+        private fun formatTick(value: Float): String = String.format(java.util.Locale.US, "%.3g", value)
+
+        private fun dp(value: Float): Float {
+            return value * resources.displayMetrics.density
+        }
+    }
+
     private class RulerView(context: Context) : View(context) {
         private val tickPaint = Paint(Paint.ANTI_ALIAS_FLAG).apply {
             color = Color.BLACK
@@ -1112,27 +1825,118 @@ class UiRenderer(
         return container
     }
 
-    private fun createCheckbox(data: JSONObject, existing: CheckBox?): View {
-        val checkBox = existing ?: CheckBox(context)
+    /** Determinate counterpart of Progress: `fraction` in 0..1 with an optional `label`. */
+    private fun createProgressBar(data: JSONObject, existing: LinearLayout?): View {
+        // Built once and updated in place, so polling re-renders move the bar instead of
+        // rebuilding it.
+        val container = existing ?: LinearLayout(context).apply {
+            orientation = LinearLayout.VERTICAL
+            val header = LinearLayout(context).apply {
+                orientation = LinearLayout.HORIZONTAL
+                addView(TextView(context).apply {
+                    textSize = 14f
+                    layoutParams = LayoutParams(0, LayoutParams.WRAP_CONTENT, 1f)
+                })
+                addView(TextView(context).apply { textSize = 14f })
+            }
+            addView(header)
+            addView(ProgressBar(context, null, android.R.attr.progressBarStyleHorizontal).apply {
+                isIndeterminate = false
+                max = PROGRESS_MAX
+                layoutParams = LayoutParams(LayoutParams.MATCH_PARENT, LayoutParams.WRAP_CONTENT)
+            })
+        }
+        container.layoutParams = LayoutParams(
+            LayoutParams.MATCH_PARENT,
+            LayoutParams.WRAP_CONTENT
+        )
+        val fraction = data.optDouble("fraction", 0.0).coerceIn(0.0, 1.0)
+        val header = container.getChildAt(0) as LinearLayout
+        (header.getChildAt(0) as TextView).text = data.optString("label", "")
+        (header.getChildAt(1) as TextView).text = "${(fraction * 100).roundToInt()}%"
+        (container.getChildAt(1) as ProgressBar).progress = (fraction * PROGRESS_MAX).toInt()
+        val contentDescription = data.optString("content_description", "")
+        container.contentDescription = contentDescription.takeIf { it.isNotEmpty() }
+        setMeta(container, "ProgressBar", resolveNodeId(data))
+        return container
+    }
+
+    /**
+     * Shown as a modal dialog; the node only leaves a hidden placeholder in the layout. The
+     * same question stays up across re-renders and closes once a render no longer carries it.
+     */
+    private fun createConfirmDialog(data: JSONObject, existing: View?): View {
+        confirmDialogRendered = true
+        val title = data.optString("title", "")
+        val message = data.optString("message", "")
+        val confirmAction = data.optString("confirm_action", "")
+        val cancelAction = data.optString("cancel_action", "")
+        val key = listOf(title, message, confirmAction, cancelAction).joinToString("\u0000")
+        if (confirmDialog?.isShowing != true || confirmDialogKey != key) {
+            dismissConfirmDialog()
+            var answered = false
+            val answer = { action: String ->
+                if (!answered && action.isNotEmpty()) {
+                    answered = true
+                    onAction(action, false, false, bindings.toMap())
+                }
+            }
+            val confirmLabel = data.optString("confirm_label", "")
+                .ifEmpty { context.getString(android.R.string.ok) }
+            val cancelLabel = data.optString("cancel_label", "")
+                .ifEmpty { context.getString(android.R.string.cancel) }
+            confirmDialog = AlertDialog.Builder(context)
+                .setTitle(title)
+                .setMessage(message.takeIf { it.isNotEmpty() })
+                .setPositiveButton(confirmLabel) { _, _ -> answer(confirmAction) }
+                .setNegativeButton(cancelLabel) { _, _ -> answer(cancelAction) }
+                // Back and outside taps count as "no", so Rust never keeps a stale question.
+                .setOnCancelListener { answer(cancelAction) }
+                .create()
+                .also { it.show() }
+            confirmDialogKey = key
+        }
+        val placeholder = existing ?: View(context)
+        placeholder.visibility = View.GONE
+        setMeta(placeholder, "ConfirmDialog", resolveNodeId(data))
+        return placeholder
+    }
+
+    private fun dismissConfirmDialog() {
+        confirmDialog?.takeIf { it.isShowing }?.dismiss()
+        confirmDialog = null
+        confirmDialogKey = null
+    }
+
+    private fun createCheckbox(data: JSONObject, existing: CheckBox?): View =
+        bindToggle(existing ?: CheckBox(context), data, "Checkbox")
+
+    private fun createSwitch(data: JSONObject, existing: Switch?): View =
+        bindToggle(existing ?: Switch(context), data, "Switch")
+
+    /** Checkbox and Switch share one contract: `bind_key` carries "true"/"false". */
+    private fun bindToggle(toggle: CompoundButton, data: JSONObject, type: String): View {
         val text = data.optString("text", data.optString("label", ""))
-        checkBox.text = text
+        toggle.text = text
 
         val bindKey = data.optString("bind_key", "")
         val checked = data.optBoolean("checked", false)
-        checkBox.isChecked = checked
+        // A reused view still holds the previous listener; setting the state must not fire it.
+        toggle.setOnCheckedChangeListener(null)
+        toggle.isChecked = checked
         if (bindKey.isNotEmpty()) {
             bindings[bindKey] = checked.toString()
         }
 
         val contentDescription = data.optString("content_description", "")
         if (contentDescription.isNotEmpty()) {
-            checkBox.contentDescription = contentDescription
+            toggle.contentDescription = contentDescription
         }
 
         val actionName = data.optString("action", "")
         val needsFilePicker = data.optBoolean("requires_file_picker", false)
         if (bindKey.isNotEmpty()) {
-            checkBox.setOnCheckedChangeListener { _, isChecked ->
+            toggle.setOnCheckedChangeListener { _, isChecked ->
                 bindings[bindKey] = isChecked.toString()
                 flushPendingBindings()
                 if (actionName.isNotEmpty()) {
@@ -1140,8 +1944,330 @@ class UiRenderer(
                 }
             }
         }
-        setMeta(checkBox, "Checkbox", resolveNodeId(data))
-        return checkBox
+        setMeta(toggle, type, resolveNodeId(data))
+        return toggle
+    }
+
+    private fun createSlider(data: JSONObject, existing: SeekBar?): View {
+        val seekBar = existing ?: SeekBar(context)
+        val bindKey = data.optString("bind_key", "")
+        val min = data.optInt("min", 0)
+        val max = data.optInt("max", 100).coerceAtLeast(min)
+        val value = data.optInt("value", min).coerceIn(min, max)
+        // SeekBar.min needs API 26, so the range is shifted to start at 0.
+        seekBar.setOnSeekBarChangeListener(null)
+        seekBar.max = max - min
+        seekBar.progress = value - min
+        bindings[bindKey] = value.toString()
+        seekBar.contentDescription = data.optString("content_description", "").takeIf { it.isNotEmpty() }
+        seekBar.layoutParams = LinearLayout.LayoutParams(
+            LinearLayout.LayoutParams.MATCH_PARENT,
+            LinearLayout.LayoutParams.WRAP_CONTENT
+        )
+
+        val actionName = data.optString("action", "")
+        seekBar.setOnSeekBarChangeListener(object : SeekBar.OnSeekBarChangeListener {
+            override fun onProgressChanged(bar: SeekBar, progress: Int, fromUser: Boolean) {
+                if (fromUser) bindings[bindKey] = (progress + min).toString()
+            }
+
+            override fun onStartTrackingTouch(bar: SeekBar) = Unit
+
+            override fun onStopTrackingTouch(bar: SeekBar) {
+                bindings[bindKey] = (bar.progress + min).toString()
+                flushPendingBindings()
+                if (actionName.isNotEmpty()) {
+                    onAction(actionName, false, false, bindings.toMap())
+                }
+            }
+        })
+        setMeta(seekBar, "Slider", resolveNodeId(data))
+        return seekBar
+    }
+
+    /**
+     * Header row of tab buttons over the selected pane. Switching is local and instant;
+     * `bind_key` carries the selected tab id and `action`, when set, reports the switch.
+     */
+    private fun createTabs(data: JSONObject, existing: LinearLayout?): View {
+        val layout = existing ?: LinearLayout(context).apply { orientation = LinearLayout.VERTICAL }
+        val existingHeader = layout.getChildAt(0) as? LinearLayout
+        val existingContent = layout.getChildAt(1) as? LinearLayout
+        val header = existingHeader ?: LinearLayout(context).apply { orientation = LinearLayout.HORIZONTAL }
+        val contentDescription = data.optString("content_description", "")
+        layout.contentDescription = contentDescription.takeIf { it.isNotEmpty() }
+
+        val tabs = data.optJSONArray("children") ?: JSONArray()
+        val bindKey = data.optString("bind_key", "")
+        val actionName = data.optString("action", "")
+        val ids = (0 until tabs.length()).map { tabs.getJSONObject(it).optString("id", "") }
+        var selected = data.optString("selected", "").takeIf { it in ids } ?: ids.firstOrNull().orEmpty()
+
+        fun paneFor(id: String): JSONObject {
+            val tab = tabs.optJSONObject(ids.indexOf(id).coerceAtLeast(0))
+            return JSONObject()
+                .put("type", "Column")
+                .put("children", tab?.optJSONArray("children") ?: JSONArray())
+        }
+
+        val content = createColumn(paneFor(selected), existingContent) as LinearLayout
+        val buttons = ids.mapIndexed { i, id ->
+            val reuse = header.getChildAt(i) as? Button
+            (reuse ?: Button(context)).apply {
+                text = tabs.getJSONObject(i).optString("label", id)
+                layoutParams = LinearLayout.LayoutParams(0, LayoutParams.WRAP_CONTENT, 1f)
+            }
+        }
+
+        fun highlight() {
+            buttons.forEachIndexed { i, btn ->
+                val active = ids[i] == selected
+                btn.setTypeface(null, if (active) Typeface.BOLD else Typeface.NORMAL)
+                btn.alpha = if (active) 1f else 0.6f
+                btn.isSelected = active
+            }
+        }
+
+        buttons.forEachIndexed { i, btn ->
+            btn.setOnClickListener {
+                if (ids[i] == selected) return@setOnClickListener
+                selected = ids[i]
+                highlight()
+                createColumn(paneFor(selected), content)
+                if (bindKey.isNotEmpty()) {
+                    bindings[bindKey] = selected
+                }
+                flushPendingBindings()
+                if (actionName.isNotEmpty()) {
+                    onAction(actionName, false, false, bindings.toMap())
+                }
+            }
+        }
+        highlight()
+        if (bindKey.isNotEmpty()) {
+            bindings[bindKey] = selected
+        }
+
+        header.removeAllViews()
+        buttons.forEach { header.addView(it) }
+        detachFromParent(header, layout)
+        detachFromParent(content, layout)
+        layout.removeAllViews()
+        layout.addView(header)
+        layout.addView(content)
+        setMeta(layout, "Tabs", resolveNodeId(data))
+        return layout
+    }
+
+    /**
+     * Header row over body rows whose cells are regular nodes laid out per column. With
+     * `max_height_dp` the body scrolls under the header; `horizontal_scroll` pans both.
+     */
+    /**
+     * Each row keeps its item id in [dataTag] and the bound value is always the ids in screen
+     * order. A local order survives re-renders for as long as Rust keeps sending the same list.
+     */
+    private fun createReorderableList(data: JSONObject, existing: LinearLayout?): View {
+        val bindKey = data.optString("bind_key", "")
+        val action = data.optString("action", "").takeIf { it.isNotEmpty() }
+        val itemsJson = data.optJSONArray("items") ?: JSONArray()
+        val items = (0 until itemsJson.length()).map { i ->
+            val item = itemsJson.getJSONObject(i)
+            val id = item.optString("id", "")
+            id to item.optString("label", id)
+        }
+        val sentOrder = items.joinToString(",") { it.first }
+        val list = existing ?: LinearLayout(context)
+        list.orientation = LinearLayout.VERTICAL
+        list.layoutParams = LayoutParams(LayoutParams.MATCH_PARENT, LayoutParams.WRAP_CONTENT).apply {
+            topMargin = dpToPx(context, 8f)
+            bottomMargin = dpToPx(context, 8f)
+        }
+        val cd = data.optString("content_description", "")
+        list.contentDescription = cd.takeIf { it.isNotEmpty() }
+
+        val order = if (list.getTag(dataTag) == sentOrder) reorderIds(list) else items.map { it.first }
+        list.setTag(dataTag, sentOrder)
+        val labels = items.toMap()
+        val onMoved = {
+            bindings[bindKey] = reorderIds(list).joinToString(",")
+            if (action != null) onAction(action, false, false, bindings.toMap())
+        }
+        list.removeAllViews()
+        order.forEach { id -> list.addView(createReorderRow(list, id, labels[id] ?: id, onMoved)) }
+        bindings[bindKey] = order.joinToString(",")
+        setMeta(list, "ReorderableList", resolveNodeId(data))
+        return list
+    }
+
+    private fun reorderIds(list: LinearLayout): List<String> =
+        (0 until list.childCount).mapNotNull { list.getChildAt(it).getTag(dataTag) as? String }
+
+    private fun createReorderRow(list: LinearLayout, id: String, label: String, onMoved: () -> Unit): View {
+        val gap = dpToPx(context, 12f)
+        val row = LinearLayout(context).apply {
+            orientation = LinearLayout.HORIZONTAL
+            gravity = Gravity.CENTER_VERTICAL
+            layoutParams = LayoutParams(LayoutParams.MATCH_PARENT, LayoutParams.WRAP_CONTENT)
+            val pad = dpToPx(context, 6f)
+            setPadding(0, pad, 0, pad)
+            setBackgroundColor(Color.WHITE)
+            setTag(dataTag, id)
+        }
+        val handle = TextView(context).apply {
+            text = "≡"
+            textSize = 20f
+            setPadding(gap, 0, gap, 0)
+            contentDescription = "Drag to reorder"
+        }
+        row.addView(handle)
+        row.addView(TextView(context).apply {
+            text = label
+            textSize = 15f
+            layoutParams = LinearLayout.LayoutParams(0, LayoutParams.WRAP_CONTENT, 1f)
+        })
+        // Arrow buttons give a tap (and TalkBack) alternative to dragging.
+        for ((symbol, description, delta) in listOf(Triple("↑", "Move up", -1), Triple("↓", "Move down", 1))) {
+            row.addView(TextView(context).apply {
+                text = symbol
+                textSize = 18f
+                setPadding(gap, 0, gap, 0)
+                contentDescription = description
+                setOnClickListener {
+                    val to = list.indexOfChild(row) + delta
+                    if (to in 0 until list.childCount) {
+                        list.removeView(row)
+                        list.addView(row, to)
+                        onMoved()
+                    }
+                }
+            })
+        }
+
+        var lastY = 0f
+        var moved = false
+        handle.setOnTouchListener { _, event ->
+            when (event.actionMasked) {
+                MotionEvent.ACTION_DOWN -> {
+                    lastY = event.rawY
+                    moved = false
+                    list.parent?.requestDisallowInterceptTouchEvent(true)
+                    row.elevation = dpToPx(context, 4f).toFloat()
+                }
+                MotionEvent.ACTION_MOVE -> {
+                    row.translationY += event.rawY - lastY
+                    lastY = event.rawY
+                    val index = list.indexOfChild(row)
+                    val below = list.getChildAt(index + 1)
+                    val above = if (index > 0) list.getChildAt(index - 1) else null
+                    // Step the neighbour across rather than moving the dragged row: detaching
+                    // the row would cancel the gesture.
+                    if (below != null && row.translationY > below.height / 2f) {
+                        list.removeView(below)
+                        list.addView(below, index)
+                        row.translationY -= below.height
+                        moved = true
+                    } else if (above != null && row.translationY < -above.height / 2f) {
+                        list.removeView(above)
+                        list.addView(above, index)
+                        row.translationY += above.height
+                        moved = true
+                    }
+                }
+                MotionEvent.ACTION_UP, MotionEvent.ACTION_CANCEL -> {
+                    row.translationY = 0f
+                    row.elevation = 0f
+                    list.parent?.requestDisallowInterceptTouchEvent(false)
+                    if (moved) onMoved()
+                }
+            }
+            true
+        }
+        return row
+    }
+
+    private fun createTable(data: JSONObject, existing: LinearLayout?): View {
+        val columns = data.optJSONArray("columns") ?: JSONArray()
+        val rows = data.optJSONArray("children") ?: JSONArray()
+        val scrollsSideways = data.optBoolean("horizontal_scroll", false)
+        val maxHeightDp = data.optInt("max_height_dp", 0)
+        val wrapper = existing ?: LinearLayout(context).apply { orientation = LinearLayout.VERTICAL }
+        wrapper.layoutParams = LayoutParams(LayoutParams.MATCH_PARENT, LayoutParams.WRAP_CONTENT)
+        val contentDescription = data.optString("content_description", "")
+        wrapper.contentDescription = contentDescription.takeIf { it.isNotEmpty() }
+
+        val table = LinearLayout(context).apply { orientation = LinearLayout.VERTICAL }
+        table.addView(createTableRow(columns, scrollsSideways) { i ->
+            TextView(context).apply {
+                text = columns.optJSONObject(i)?.optString("title", "").orEmpty()
+                setTypeface(typeface, Typeface.BOLD)
+            }
+        })
+        val body = LinearLayout(context).apply { orientation = LinearLayout.VERTICAL }
+        for (r in 0 until rows.length()) {
+            val cells = rows.getJSONObject(r).optJSONArray("children") ?: JSONArray()
+            body.addView(createTableRow(columns, scrollsSideways) { i ->
+                cells.optJSONObject(i)?.let { createView(it, null) } ?: Space(context)
+            })
+        }
+        if (maxHeightDp > 0) {
+            val scroller = MaxHeightScrollView(context, dpToPx(context, maxHeightDp.toFloat()))
+            scroller.isNestedScrollingEnabled = true
+            scroller.addView(body)
+            table.addView(scroller)
+        } else {
+            table.addView(body)
+        }
+
+        wrapper.removeAllViews()
+        if (scrollsSideways) {
+            wrapper.addView(HorizontalScrollView(context).apply { addView(table) })
+        } else {
+            wrapper.addView(table)
+        }
+        setMeta(wrapper, "Table", resolveNodeId(data))
+        return wrapper
+    }
+
+    /** Columns with `width_dp` keep it; the rest share the width, or get 120dp when panning. */
+    private fun createTableRow(columns: JSONArray, scrollsSideways: Boolean, cell: (Int) -> View): LinearLayout {
+        val row = LinearLayout(context).apply {
+            orientation = LinearLayout.HORIZONTAL
+            gravity = Gravity.CENTER_VERTICAL
+        }
+        val pad = dpToPx(context, 4f)
+        for (i in 0 until columns.length()) {
+            val column = columns.optJSONObject(i) ?: JSONObject()
+            val widthDp = column.optInt("width_dp", if (scrollsSideways) 120 else 0)
+            val align = when (column.optString("align", "start")) {
+                "center" -> Gravity.CENTER_HORIZONTAL
+                "end" -> Gravity.END
+                else -> Gravity.START
+            }
+            val frame = FrameLayout(context).apply { setPadding(pad, pad, pad, pad) }
+            frame.addView(
+                cell(i),
+                FrameLayout.LayoutParams(
+                    FrameLayout.LayoutParams.WRAP_CONTENT,
+                    FrameLayout.LayoutParams.WRAP_CONTENT,
+                    align or Gravity.CENTER_VERTICAL
+                )
+            )
+            val lp = if (widthDp > 0) {
+                LinearLayout.LayoutParams(dpToPx(context, widthDp.toFloat()), LayoutParams.WRAP_CONTENT)
+            } else {
+                LinearLayout.LayoutParams(0, LayoutParams.WRAP_CONTENT, 1f)
+            }
+            row.addView(frame, lp)
+        }
+        return row
+    }
+
+    /** Grows with its content up to [maxHeightPx], then scrolls. */
+    private class MaxHeightScrollView(context: Context, private val maxHeightPx: Int) : ScrollView(context) {
+        override fun onMeasure(widthMeasureSpec: Int, heightMeasureSpec: Int) {
+            super.onMeasure(widthMeasureSpec, MeasureSpec.makeMeasureSpec(maxHeightPx, MeasureSpec.AT_MOST))
+        }
     }
 
     private fun createGrid(data: JSONObject, existing: LinearLayout?): View {
@@ -1377,7 +2503,7 @@ class UiRenderer(
         return container
     }
 
-    private fun createPdfSinglePage(data: JSONObject, existing: ImageView?): View {
+    private fun createPdfSinglePage(data: JSONObject, existing: ZoomImageView?): View {
         val sourceUri = data.optString("source_uri", "")
         val page = data.optInt("page", 1).coerceAtLeast(1) - 1
         val uri = try {
@@ -1385,18 +2511,23 @@ class UiRenderer(
         } catch (_: Exception) {
             return createErrorView("Invalid source_uri")
         }
-        val image = existing ?: ImageView(context)
-        image.adjustViewBounds = true
-        val bmp = renderPdfPage(uri, page)
-        if (bmp == null) return createErrorView("Preview unavailable")
-        image.setImageBitmap(bmp)
+        val zoomable = data.optBoolean("zoomable", false)
+        val image = existing ?: ZoomImageView(context)
+        val pageKey = "$sourceUri#$page#$zoomable"
+        if (image.getTag(dataTag) != pageKey) {
+            // Zoomable pages render at twice the width so text stays legible when enlarged.
+            val bmp = renderPdfPage(uri, page, if (zoomable) 2 else 1)
+            if (bmp == null) return createErrorView("Preview unavailable")
+            image.setImageBitmap(bmp)
+            image.setTag(dataTag, pageKey)
+        }
         image.layoutParams = LayoutParams(LayoutParams.MATCH_PARENT, LayoutParams.WRAP_CONTENT)
-        image.scaleType = ImageView.ScaleType.FIT_CENTER
+        bindZoom(image, zoomable)
         setMeta(image, "PdfSinglePage", resolveNodeId(data))
         return image
     }
 
-    private fun renderPdfPage(uri: Uri, index: Int): Bitmap? {
+    private fun renderPdfPage(uri: Uri, index: Int, scale: Int = 1): Bitmap? {
         val pfd: ParcelFileDescriptor = try {
             context.contentResolver.openFileDescriptor(uri, "r") ?: return null
         } catch (_: Exception) {
@@ -1407,7 +2538,7 @@ class UiRenderer(
                 PdfRenderer(descriptor).use { renderer ->
                     if (index < 0 || index >= renderer.pageCount) return null
                     renderer.openPage(index).use { page ->
-                        val targetWidth = dpToPx(context, 320f).coerceAtLeast(160)
+                        val targetWidth = dpToPx(context, 320f * scale).coerceAtLeast(160)
                         val aspect = page.height / page.width.toFloat()
                         val targetHeight = (targetWidth * aspect).toInt().coerceAtLeast(120)
                         val bmp = Bitmap.createBitmap(
@@ -1903,15 +3034,21 @@ class UiRenderer(
         val explicit = data.optString("id", "").takeIf { it.isNotBlank() }
         if (explicit != null) return explicit
         return when (data.optString("type", "")) {
-            "TextInput", "Checkbox", "PdfPagePicker", "SignaturePad", "PdfSignPlacement" ->
+            "TextInput", "Checkbox", "Switch", "Tabs", "PdfPagePicker", "SignaturePad", "PdfSignPlacement",
+            "ReorderableList" ->
                 data.optString("bind_key", "").takeIf { it.isNotBlank() }
             "Button" -> data.optString("action", "").takeIf { it.isNotBlank() }
             "Section", "Card" ->
                 data.optString("title", "").takeIf { it.isNotBlank() }
                     ?: data.optString("content_description", "").takeIf { it.isNotBlank() }
             "Compass" -> data.optString("content_description", "").takeIf { it.isNotBlank() }
-            "Barometer", "Magnetometer" -> data.optString("content_description", "").takeIf { it.isNotBlank() }
+            "Table" -> data.optString("content_description", "").takeIf { it.isNotBlank() }
+            "Barometer", "Magnetometer", "Chart", "ProgressBar" -> data.optString("content_description", "").takeIf { it.isNotBlank() }
             "CodeView" -> data.optString("content_description", "").takeIf { it.isNotBlank() } ?: "code_view"
+            // Stable ids let re-renders reuse the view and keep the user's zoom.
+            "Image" -> data.optString("content_description", "").takeIf { it.isNotBlank() }
+                ?: data.optString("path", "").takeIf { it.isNotBlank() }
+            "PdfSinglePage" -> "pdf_single_page"
             else -> null
         }
     }
@@ -2252,6 +3389,12 @@ class UiRenderer(
     }
 
     companion object {
+        private const val PROGRESS_MAX = 1000
+        private const val IMAGE_MAX_DECODE_PX = 2048
+        private const val MARKDOWN_ESCAPABLE = "\\`*_[]()#+-.!"
+        private val MARKDOWN_CODE_BACKGROUND = Color.argb(32, 128, 128, 128)
+        private val MARKDOWN_HEADING = Regex("^(#{1,6})\\s+(.*)$")
+        private val MARKDOWN_LIST_ITEM = Regex("^( *)([-*+]|\\d+[.)])\\s+(.*)$")
         private const val DEFAULT_FRAGMENT = """
             precision mediump float;
             uniform float u_time;
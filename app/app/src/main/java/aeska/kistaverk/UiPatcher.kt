@@ -0,0 +1,137 @@
+package aeska.kistaverk
+
+import org.json.JSONArray
+import org.json.JSONObject
+
+/**
+ * Keeps the last UI tree received from Rust so dispatches can be answered with a `Patch`
+ * instead of the whole screen. Callers always get a full tree back, which they may keep:
+ * patches are applied to copies.
+ */
+class UiPatcher {
+    private var revision = 0L
+    private var tree: JSONObject? = null
+
+    /**
+     * Sends [input] through [native] under one lock, so revisions cannot interleave.
+     * Returns null when the response is not a JSON object.
+     */
+    @Synchronized
+    fun dispatch(input: String, native: (String) -> String): JSONObject? {
+        val response = native(withRevision(input)?.toString() ?: input)
+        val obj = runCatching { JSONObject(response) }.getOrNull() ?: return null
+        return resolve(obj) ?: dispatch(RESYNC_COMMAND, native)
+    }
+
+    /**
+     * Like [dispatch] over CBOR, using [json] for what CBOR cannot carry: input that is not
+     * a command (so Rust reports it) and responses that do not decode.
+     */
+    @Synchronized
+    fun dispatchCbor(
+        input: String,
+        native: (ByteArray) -> ByteArray,
+        json: (String) -> String
+    ): JSONObject? {
+        val command = withRevision(input) ?: return dispatch(input, json)
+        val encoded = runCatching { Cbor.encode(command) }.getOrElse { return dispatch(input, json) }
+        val response = runCatching { Cbor.decode(native(encoded)) as JSONObject }
+            // Rust has applied the command by now: fetch the screen again, don't repeat it.
+            .getOrElse { return dispatch(RESYNC_COMMAND, json) }
+        return resolve(response) ?: dispatchCbor(RESYNC_COMMAND, native, json)
+    }
+
+    private fun withRevision(input: String): Any? {
+        val trimmed = input.trimStart()
+        return runCatching {
+            if (trimmed.startsWith("[")) {
+                val commands = JSONArray(trimmed)
+                for (i in 0 until commands.length()) {
+                    commands.optJSONObject(i)?.put("ui_revision", revision)
+                }
+                commands
+            } else {
+                JSONObject(trimmed).put("ui_revision", revision)
+            }
+        }.getOrNull()
+    }
+
+    /**
+     * Returns the full tree for [response], or [response] itself when it is not a patch.
+     * Returns null when the patch does not apply; the revision is reset by then, so the
+     * caller's next dispatch gets a full tree.
+     */
+    private fun resolve(response: JSONObject): JSONObject? {
+        if (response.optString("type") != PATCH_TYPE) {
+            if (response.has("ui_revision")) {
+                revision = response.optLong("ui_revision")
+                tree = response
+            }
+            return response
+        }
+        val current = tree
+        val patched = if (current != null && response.optLong("base", -1L) == revision) {
+            runCatching { applyOps(current, response.optJSONArray("ops") ?: JSONArray()) }.getOrNull()
+        } else {
+            null
+        }
+        if (patched == null) {
+            // Out of sync (or half-applied): drop what we hold and resync.
+            revision = 0L
+            tree = null
+            return null
+        }
+        revision = response.optLong("ui_revision")
+        patched.put("ui_revision", revision)
+        tree = patched
+        return patched
+    }
+
+    private fun applyOps(root: JSONObject, ops: JSONArray): JSONObject {
+        // Nodes along each path are copied, so the tree handed out last time stays as it was.
+        val patched = shallowCopy(root)
+        for (i in 0 until ops.length()) {
+            val op = ops.getJSONObject(i)
+            val path = op.getJSONArray("path")
+            var parent: JSONObject? = null
+            var node = patched
+            for (p in 0 until path.length()) {
+                val index = path.getInt(p)
+                val children = node.getJSONArray("children")
+                val copied = JSONArray()
+                for (c in 0 until children.length()) copied.put(children.get(c))
+                node.put("children", copied)
+                parent = node
+                node = shallowCopy(copied.getJSONObject(index))
+                copied.put(index, node)
+            }
+            when (op.getString("op")) {
+                "props" -> {
+                    val props = op.getJSONObject("props")
+                    node.keys().asSequence().filter { it != "children" }.toList().forEach { node.remove(it) }
+                    props.keys().forEach { node.put(it, props.get(it)) }
+                }
+                "children" -> {
+                    val children = op.optJSONArray("children")
+                    if (children == null) node.remove("children") else node.put("children", children)
+                }
+                "replace" -> {
+                    val target = parent ?: error("cannot replace the root")
+                    target.getJSONArray("children").put(path.getInt(path.length() - 1), op.getJSONObject("node"))
+                }
+                else -> error("unknown patch op")
+            }
+        }
+        return patched
+    }
+
+    private fun shallowCopy(node: JSONObject): JSONObject =
+        JSONObject().also { copy -> node.keys().forEach { copy.put(it, node.get(it)) } }
+
+    companion object {
+        const val PATCH_TYPE = "Patch"
+
+        /** Re-renders the current screen without repeating the command that was patched. */
+        private const val RESYNC_COMMAND = """{"action":"poll_progress"}"""
+    }
+}
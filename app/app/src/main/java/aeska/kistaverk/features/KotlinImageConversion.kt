@@ -3,7 +3,6 @@ package aeska.kistaverk.features
 import android.content.Context
 import android.content.ContentValues
 import android.graphics.Bitmap
-import android.graphics.BitmapFactory
 import android.net.Uri
 import android.os.Build
 import android.provider.MediaStore
@@ -15,23 +14,10 @@ import java.text.DecimalFormat
 import java.util.Locale
 
 object KotlinImageConversion {
-    data class ResizeOptions(
-        val scalePercent: Int,
-        val quality: Int,
-        val targetBytes: Long?,
-        val target: ImageTarget
-    )
-
-    private data class Compressed(
-        val bytes: ByteArray,
-        val quality: Int
-    )
-
     fun isConversionAction(action: String): Boolean {
         return action == "kotlin_image_convert_webp" ||
             action == "kotlin_image_convert_png" ||
-            action == "kotlin_image_convert_jpeg" ||
-            action == "kotlin_image_resize"
+            action == "kotlin_image_convert_jpeg"
     }
 
     fun convert(
@@ -49,9 +35,7 @@ object KotlinImageConversion {
         val resolver = context.contentResolver
 
         return runCatching {
-            val bitmap = resolver.openInputStream(uri)?.use { input ->
-                BitmapFactory.decodeStream(input)
-            } ?: error("decode_failed")
+            val bitmap = PlatformImageDecoder.decodeBitmap(resolver, uri) ?: error("decode_failed")
 
             val compressed = compressToBytes(bitmap, target, target.quality)
             val result = saveBytes(
@@ -61,9 +45,7 @@ object KotlinImageConversion {
                 outputDirUri = outputDirUri,
                 compressed = compressed,
                 target = target,
-                prefix = "converted",
-                scalePercent = null,
-                targetBytes = null
+                prefix = "converted"
             )
             bitmap.recycle()
             result
@@ -75,72 +57,12 @@ object KotlinImageConversion {
         }
     }
 
-    fun resize(
-        context: Context,
-        cacheDir: File,
-        picturesDir: File?,
-        outputDirUri: Uri?,
-        uri: Uri,
-        bindings: Map<String, String>
-    ): ConversionResult {
-        val opts = buildResizeOptions(bindings)
-        val resolver = context.contentResolver
-        return runCatching {
-            val bitmap = resolver.openInputStream(uri)?.use { input ->
-                BitmapFactory.decodeStream(input)
-            } ?: error("decode_failed")
-
-            val resized = scaleBitmap(bitmap, opts.scalePercent)
-            val compressed = compressWithBudget(resized, opts.target, opts.quality, opts.targetBytes)
-            val result = saveBytes(
-                context = context,
-                cacheDir = cacheDir,
-                picturesDir = picturesDir,
-                outputDirUri = outputDirUri,
-                compressed = compressed,
-                target = opts.target.copy(quality = compressed.quality),
-                prefix = "resized",
-                scalePercent = opts.scalePercent,
-                targetBytes = opts.targetBytes
-            )
-            if (resized !== bitmap) {
-                bitmap.recycle()
-            }
-            resized.recycle()
-            result
-        }.getOrElse { throwable ->
-            ConversionResult.Failure(
-                target = opts.target,
-                reason = throwable.message ?: "resize_failed"
-            )
-        }
-    }
-
-    private fun compressWithBudget(
-        bitmap: Bitmap,
-        target: ImageTarget,
-        quality: Int,
-        targetBytes: Long?
-    ): Compressed {
-        var q = quality.coerceIn(40, 100)
-        var compressed = compressToBytes(bitmap, target, q)
-        if (targetBytes != null && target.format != Bitmap.CompressFormat.PNG) {
-            var attempts = 0
-            while (compressed.bytes.size.toLong() > targetBytes && attempts < 5 && q > 40) {
-                q = (q - 10).coerceAtLeast(40)
-                compressed = compressToBytes(bitmap, target, q)
-                attempts += 1
-            }
-        }
-        return compressed
-    }
-
-    private fun compressToBytes(bitmap: Bitmap, target: ImageTarget, quality: Int): Compressed {
+    private fun compressToBytes(bitmap: Bitmap, target: ImageTarget, quality: Int): ByteArray {
         val stream = ByteArrayOutputStream()
         if (!bitmap.compress(target.format, quality, stream)) {
             error("compress_failed")
         }
-        return Compressed(stream.toByteArray(), quality)
+        return stream.toByteArray()
     }
 
     private fun saveBytes(
@@ -148,11 +70,9 @@ object KotlinImageConversion {
         cacheDir: File,
         picturesDir: File?,
         outputDirUri: Uri?,
-        compressed: Compressed,
+        compressed: ByteArray,
         target: ImageTarget,
-        prefix: String,
-        scalePercent: Int?,
-        targetBytes: Long?
+        prefix: String
     ): ConversionResult.Success {
         val resolver = context.contentResolver
         val displayName = ensureExtension(outputName(target, prefix), target.extension)
@@ -163,17 +83,14 @@ object KotlinImageConversion {
                 val outDoc = tree.createFile(target.mimeType, displayName)
                 if (outDoc != null) {
                     resolver.openOutputStream(outDoc.uri)?.use { out ->
-                        out.write(compressed.bytes)
+                        out.write(compressed)
                     } ?: error("open_output_failed")
-                    val size = compressed.bytes.size.toLong()
+                    val size = compressed.size.toLong()
                     return ConversionResult.Success(
                         destination = outDoc.uri.toString(),
                         format = target.extension.uppercase(),
                         size = readableBytes(size),
-                        target = target,
-                        quality = compressed.quality,
-                        scalePercent = scalePercent,
-                        targetBytes = targetBytes
+                        target = target
                     )
                 }
             }
@@ -192,7 +109,7 @@ object KotlinImageConversion {
         if (insertedUri != null) {
             val success = runCatching {
                 resolver.openOutputStream(insertedUri)?.use { out ->
-                    out.write(compressed.bytes)
+                    out.write(compressed)
                 } ?: error("open_output_failed")
 
                 if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.Q) {
@@ -202,11 +119,8 @@ object KotlinImageConversion {
                 ConversionResult.Success(
                     destination = insertedUri.toString(),
                     format = target.extension.uppercase(),
-                    size = readableBytes(compressed.bytes.size.toLong()),
-                    target = target,
-                    quality = compressed.quality,
-                    scalePercent = scalePercent,
-                    targetBytes = targetBytes
+                    size = readableBytes(compressed.size.toLong()),
+                    target = target
                 )
             }.getOrNull()
             if (success != null) {
@@ -218,38 +132,11 @@ object KotlinImageConversion {
 
         val baseDir = ensureOutputDir(cacheDir, picturesDir)
         val outFile = File(baseDir, displayName)
-        FileOutputStream(outFile).use { out -> out.write(compressed.bytes) }
+        FileOutputStream(outFile).use { out -> out.write(compressed) }
         return ConversionResult.Success(
             destination = outFile.absolutePath,
             format = target.extension.uppercase(),
             size = readableBytes(outFile.length()),
-            target = target,
-            quality = compressed.quality,
-            scalePercent = scalePercent,
-            targetBytes = targetBytes
-        )
-    }
-
-    private fun scaleBitmap(source: Bitmap, scalePercent: Int): Bitmap {
-        val pct = scalePercent.coerceIn(5, 100)
-        if (pct >= 100) return source
-        val width = (source.width * pct / 100f).toInt().coerceAtLeast(1)
-        val height = (source.height * pct / 100f).toInt().coerceAtLeast(1)
-        return Bitmap.createScaledBitmap(source, width, height, true)
-    }
-
-    private fun buildResizeOptions(bindings: Map<String, String>): ResizeOptions {
-        val scale = bindings["resize_scale_pct"]?.toIntOrNull()?.coerceIn(5, 100) ?: 70
-        val quality = bindings["resize_quality"]?.toIntOrNull()?.coerceIn(40, 100) ?: 85
-        val targetBytes = bindings["resize_target_kb"]?.toLongOrNull()?.takeIf { it > 0 }
-            ?.coerceAtMost(10_000)
-            ?.times(1024)
-        val useWebp = bindings["resize_use_webp"]?.toBooleanStrictOrNull() ?: false
-        val target = if (useWebp) webpTarget(quality) else jpegTarget(quality)
-        return ResizeOptions(
-            scalePercent = scale,
-            quality = quality,
-            targetBytes = targetBytes,
             target = target
         )
     }
@@ -329,10 +216,7 @@ sealed class ConversionResult {
         val destination: String,
         val format: String,
         val size: String,
-        val target: ImageTarget,
-        val quality: Int? = null,
-        val scalePercent: Int? = null,
-        val targetBytes: Long? = null
+        val target: ImageTarget
     ) : ConversionResult()
 
     data class Failure(
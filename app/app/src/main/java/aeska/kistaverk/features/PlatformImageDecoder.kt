@@ -0,0 +1,94 @@
+package aeska.kistaverk.features
+
+import android.content.ContentResolver
+import android.content.Context
+import android.graphics.Bitmap
+import android.graphics.BitmapFactory
+import android.graphics.ImageDecoder
+import android.net.Uri
+import android.os.Build
+import java.io.File
+import java.io.FileOutputStream
+import java.util.Locale
+
+/**
+ * HEIC/HEIF and AVIF go through Android's decoders (HEIF from API 28, AVIF from API 31);
+ * the Rust image tools only read JPEG, PNG and WebP.
+ */
+object PlatformImageDecoder {
+    /** Picker actions whose file is decoded by Rust and may need transcoding first. */
+    val RUST_IMAGE_ACTIONS = setOf(
+        "dithering_pick_image",
+        "dithering_palette_from_image",
+        "pixel_art_pick",
+        "image_edit_pick",
+        "image_adjust_pick",
+        "ascii_art_pick",
+        "image_histogram_pick",
+        "watermark_pick_images",
+        "watermark_pick_logo",
+        "collage_pick_images",
+        "kotlin_image_pick",
+        "kotlin_image_batch_pick"
+    )
+
+    private val HEIF_MIME_TYPES = setOf(
+        "image/heic",
+        "image/heif",
+        "image/heic-sequence",
+        "image/heif-sequence",
+        "image/avif"
+    )
+
+    private val HEIF_BRANDS = setOf(
+        "heic", "heix", "hevc", "hevx", "heim", "heis", "mif1", "msf1", "avif", "avis"
+    )
+
+    fun isHeifOrAvif(resolver: ContentResolver, uri: Uri): Boolean {
+        val mime = resolver.getType(uri)?.lowercase(Locale.US)
+        if (mime != null && mime in HEIF_MIME_TYPES) return true
+        // Files from other apps often come as application/octet-stream; check the ftyp box.
+        val header = ByteArray(12)
+        val read = runCatching {
+            resolver.openInputStream(uri)?.use { it.read(header) } ?: 0
+        }.getOrDefault(0)
+        if (read < header.size) return false
+        val box = String(header, 4, 4, Charsets.US_ASCII)
+        val brand = String(header, 8, 4, Charsets.US_ASCII)
+        return box == "ftyp" && brand in HEIF_BRANDS
+    }
+
+    /** ImageDecoder also applies the EXIF rotation; older devices fall back to BitmapFactory. */
+    fun decodeBitmap(resolver: ContentResolver, uri: Uri): Bitmap? {
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.P) {
+            return runCatching {
+                ImageDecoder.decodeBitmap(ImageDecoder.createSource(resolver, uri)) { decoder, _, _ ->
+                    // Hardware bitmaps cannot be compressed.
+                    decoder.allocator = ImageDecoder.ALLOCATOR_SOFTWARE
+                }
+            }.getOrNull()
+        }
+        return runCatching {
+            resolver.openInputStream(uri)?.use { BitmapFactory.decodeStream(it) }
+        }.getOrNull()
+    }
+
+    /**
+     * Re-encodes the picked image as a lossless PNG in the cache so Rust can read it.
+     * Returns null when this device cannot decode it.
+     */
+    fun transcodeToPng(context: Context, uri: Uri, displayName: String?): File? {
+        val bitmap = decodeBitmap(context.contentResolver, uri) ?: return null
+        val stem = displayName?.substringBeforeLast('.')?.takeIf { it.isNotBlank() } ?: "image"
+        val dir = File(context.cacheDir, "decoded").apply { mkdirs() }
+        val out = File(dir, "$stem.png")
+        return try {
+            FileOutputStream(out).use { bitmap.compress(Bitmap.CompressFormat.PNG, 100, it) }
+            out
+        } catch (_: Exception) {
+            null
+        } finally {
+            bitmap.recycle()
+        }
+    }
+}
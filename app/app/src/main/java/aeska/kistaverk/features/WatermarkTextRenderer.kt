@@ -0,0 +1,46 @@
+package aeska.kistaverk.features
+
+import android.graphics.Bitmap
+import android.graphics.Canvas
+import android.graphics.Color
+import android.graphics.Paint
+import android.graphics.Typeface
+import java.io.File
+import java.io.FileOutputStream
+import kotlin.math.ceil
+
+/**
+ * Rasterises the watermark text with the system fonts (so every script renders) into a
+ * transparent PNG; Rust scales and blends it onto each picture.
+ */
+object WatermarkTextRenderer {
+    private const val TEXT_SIZE_PX = 160f
+    private const val PADDING_PX = 24
+
+    fun render(cacheDir: File, text: String): File? {
+        val line = text.trim().lineSequence().joinToString(" ")
+        if (line.isEmpty()) return null
+        val paint = Paint(Paint.ANTI_ALIAS_FLAG).apply {
+            color = Color.WHITE
+            textSize = TEXT_SIZE_PX
+            typeface = Typeface.DEFAULT_BOLD
+            // Keeps light text readable on bright photos.
+            setShadowLayer(TEXT_SIZE_PX / 16f, 0f, TEXT_SIZE_PX / 32f, Color.argb(160, 0, 0, 0))
+        }
+        val metrics = paint.fontMetrics
+        val width = ceil(paint.measureText(line)).toInt() + PADDING_PX * 2
+        val height = ceil(metrics.descent - metrics.ascent).toInt() + PADDING_PX * 2
+        val bitmap = runCatching { Bitmap.createBitmap(width, height, Bitmap.Config.ARGB_8888) }
+            .getOrNull() ?: return null
+        Canvas(bitmap).drawText(line, PADDING_PX.toFloat(), PADDING_PX - metrics.ascent, paint)
+        val out = File(cacheDir, "watermark_text.png")
+        return try {
+            FileOutputStream(out).use { bitmap.compress(Bitmap.CompressFormat.PNG, 100, it) }
+            out
+        } catch (_: Exception) {
+            null
+        } finally {
+            bitmap.recycle()
+        }
+    }
+}
@@ -0,0 +1,57 @@
+package aeska.kistaverk
+
+import org.json.JSONArray
+import org.json.JSONObject
+import org.junit.Assert.assertArrayEquals
+import org.junit.Assert.assertEquals
+import org.junit.Assert.assertThrows
+import org.junit.Test
+import org.junit.runner.RunWith
+import org.robolectric.RobolectricTestRunner
+
+@RunWith(RobolectricTestRunner::class)
+class CborTest {
+
+    @Test
+    fun commandsRoundTrip() {
+        val command = JSONObject()
+            .put("action", "text_viewer_find")
+            .put("fd", -1)
+            .put("ui_revision", 300L)
+            .put("angle_radians", 1.5)
+            .put("loading_only", true)
+            .put("error", JSONObject.NULL)
+            .put("bindings", JSONObject().put("find_query", "ünïcode"))
+            .put("path_list", JSONArray().put("a").put("b"))
+
+        val decoded = Cbor.decode(Cbor.encode(command)) as JSONObject
+
+        assertEquals(command.toString(), decoded.toString())
+    }
+
+    @Test
+    fun encodesCanonicalHeads_andDecodesHalfFloats() {
+        assertArrayEquals(byteArrayOf(0x18, 0x64), Cbor.encode(100))
+        assertArrayEquals(byteArrayOf(0x20), Cbor.encode(-1))
+        assertArrayEquals(byteArrayOf(0x62, 0x68, 0x69), Cbor.encode("hi"))
+        // 0xf9 0x4e00 is the half-precision float 24.0, as Rust emits for `"size": 24.0`.
+        assertEquals(24.0, Cbor.decode(byteArrayOf(0xf9.toByte(), 0x4e, 0x00)))
+        assertThrows(IllegalArgumentException::class.java) { Cbor.decode(byteArrayOf(0x62, 0x68)) }
+    }
+
+    @Test
+    fun patcherSpeaksCbor_andSkipsNonCommands() {
+        val patcher = UiPatcher()
+        val tree = JSONObject().put("type", "Column").put("ui_revision", 1).put("children", JSONArray())
+        var sent: JSONObject? = null
+
+        val resolved = patcher.dispatchCbor("""{ "action": "init" }""") { input ->
+            sent = Cbor.decode(input) as JSONObject
+            Cbor.encode(tree)
+        }
+
+        assertEquals(0L, sent?.getLong("ui_revision"))
+        assertEquals(1L, JSONObject(resolved!!).getLong("ui_revision"))
+        assertEquals(null, patcher.dispatchCbor("not json") { error("must not dispatch") })
+    }
+}
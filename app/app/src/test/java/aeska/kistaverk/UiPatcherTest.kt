@@ -0,0 +1,117 @@
+package aeska.kistaverk
+
+import org.json.JSONObject
+import org.junit.Assert.assertEquals
+import org.junit.Test
+import org.junit.runner.RunWith
+import org.robolectric.RobolectricTestRunner
+
+@RunWith(RobolectricTestRunner::class)
+class UiPatcherTest {
+
+    private val fullTree = """
+        {
+          "type": "Column",
+          "ui_revision": 1,
+          "children": [
+            { "type": "Text", "text": "Title" },
+            { "type": "TextInput", "bind_key": "find_query", "text": "" },
+            { "type": "Column", "children": [ { "type": "Text", "text": "row" } ] }
+          ]
+        }
+    """.trimIndent()
+
+    private val patch = """
+        {
+          "type": "Patch",
+          "base": 1,
+          "ui_revision": 2,
+          "ops": [
+            { "op": "props", "path": [1], "props": { "type": "TextInput", "bind_key": "find_query", "text": "rust" } },
+            { "op": "replace", "path": [0], "node": { "type": "Button", "text": "Go", "action": "go" } },
+            { "op": "children", "path": [2], "children": [ { "type": "Text", "text": "a" }, { "type": "Text", "text": "b" } ] }
+          ]
+        }
+    """.trimIndent()
+
+    @Test
+    fun patchesApplyToTheLastTree_andRevisionsAreSent() {
+        val sent = mutableListOf<JSONObject>()
+        val responses = ArrayDeque(listOf(fullTree, patch))
+        val patcher = UiPatcher()
+
+        patcher.dispatch("""{ "action": "init" }""") { input -> sent.add(JSONObject(input)); responses.removeFirst() }
+        val resolved = patcher.dispatch("""{ "action": "text_viewer_find" }""") { input ->
+            sent.add(JSONObject(input))
+            responses.removeFirst()
+        }!!
+
+        assertEquals(listOf(0L, 1L), sent.map { it.getLong("ui_revision") })
+        assertEquals(2L, resolved.getLong("ui_revision"))
+        val children = resolved.getJSONArray("children")
+        assertEquals("go", children.getJSONObject(0).getString("action"))
+        assertEquals("rust", children.getJSONObject(1).getString("text"))
+        assertEquals(2, children.getJSONObject(2).getJSONArray("children").length())
+    }
+
+    @Test
+    fun patchForAnUnknownBase_resyncsWithAFullTree() {
+        val sent = mutableListOf<JSONObject>()
+        val responses = ArrayDeque(listOf(patch, fullTree))
+        val patcher = UiPatcher()
+
+        val resolved = patcher.dispatch("""[{ "action": "init" }]""") { input ->
+            sent.add(JSONObject(input.removePrefix("[").removeSuffix("]")))
+            responses.removeFirst()
+        }!!
+
+        assertEquals("Column", resolved.getString("type"))
+        assertEquals(1L, resolved.getLong("ui_revision"))
+        assertEquals(listOf("init", "poll_progress"), sent.map { it.getString("action") })
+        assertEquals(listOf(0L, 0L), sent.map { it.getLong("ui_revision") })
+    }
+
+    @Test
+    fun patchedTreesLeaveTheOnesHandedOutEarlierAlone() {
+        val responses = ArrayDeque(listOf(fullTree, patch))
+        val patcher = UiPatcher()
+
+        val first = patcher.dispatch("""{ "action": "init" }""") { responses.removeFirst() }!!
+        patcher.dispatch("""{ "action": "text_viewer_find" }""") { responses.removeFirst() }
+
+        assertEquals("", first.getJSONArray("children").getJSONObject(1).getString("text"))
+        assertEquals(1L, first.getLong("ui_revision"))
+    }
+
+    @Test
+    fun undecodableCborResponse_resyncsOverJson() {
+        val cborSent = mutableListOf<JSONObject>()
+        val jsonSent = mutableListOf<JSONObject>()
+        val patcher = UiPatcher()
+
+        val resolved = patcher.dispatchCbor(
+            """{ "action": "increment" }""",
+            native = { bytes -> cborSent.add(Cbor.decode(bytes) as JSONObject); byteArrayOf(0x1c) },
+            json = { input -> jsonSent.add(JSONObject(input)); fullTree }
+        )!!
+
+        assertEquals("Column", resolved.getString("type"))
+        assertEquals(listOf("increment"), cborSent.map { it.getString("action") })
+        assertEquals(listOf("poll_progress"), jsonSent.map { it.getString("action") })
+    }
+
+    @Test
+    fun inputThatIsNotACommand_goesOverJson() {
+        var cborCalls = 0
+        val patcher = UiPatcher()
+
+        val resolved = patcher.dispatchCbor(
+            "not json",
+            native = { cborCalls++; ByteArray(0) },
+            json = { """{ "type": "Column", "children": [] }""" }
+        )
+
+        assertEquals("Column", resolved?.getString("type"))
+        assertEquals(0, cborCalls)
+    }
+}
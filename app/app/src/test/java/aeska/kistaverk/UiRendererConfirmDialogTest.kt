@@ -0,0 +1,82 @@
+package aeska.kistaverk
+
+import android.app.AlertDialog
+import android.content.DialogInterface
+import androidx.test.core.app.ApplicationProvider
+import org.junit.Assert.assertEquals
+import org.junit.Assert.assertFalse
+import org.junit.Assert.assertSame
+import org.junit.Assert.assertTrue
+import org.junit.Test
+import org.junit.runner.RunWith
+import org.robolectric.RobolectricTestRunner
+import org.robolectric.shadows.ShadowAlertDialog
+
+@RunWith(RobolectricTestRunner::class)
+class UiRendererConfirmDialogTest {
+
+    private fun ui(withDialog: Boolean) = """
+        {
+          "type": "Column",
+          "children": [
+            { "type": "Text", "text": "Presets" }${if (withDialog) """,
+            {
+              "type": "ConfirmDialog",
+              "title": "Delete preset?",
+              "message": "The saved settings are removed.",
+              "confirm_label": "Delete",
+              "confirm_action": "confirm_pending",
+              "cancel_action": "cancel_pending"
+            }""" else ""}
+          ]
+        }
+    """.trimIndent()
+
+    private fun latestDialog() = ShadowAlertDialog.getLatestAlertDialog() as AlertDialog
+
+    @Test
+    fun confirmButton_dispatchesConfirmAction_once() {
+        val actions = mutableListOf<String>()
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { action, _, _, _ ->
+            actions.add(action)
+        }
+
+        renderer.render(ui(true))
+        val dialog = latestDialog()
+        assertTrue(dialog.isShowing)
+        assertEquals("Delete", dialog.getButton(DialogInterface.BUTTON_POSITIVE).text.toString())
+
+        dialog.getButton(DialogInterface.BUTTON_POSITIVE).performClick()
+        assertEquals(listOf("confirm_pending"), actions)
+    }
+
+    @Test
+    fun rerender_keepsDialog_andDropsItWhenNodeDisappears() {
+        val actions = mutableListOf<String>()
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { action, _, _, _ ->
+            actions.add(action)
+        }
+
+        renderer.render(ui(true))
+        val dialog = latestDialog()
+        renderer.render(ui(true))
+        assertSame(dialog, latestDialog())
+        assertTrue(dialog.isShowing)
+
+        renderer.render(ui(false))
+        assertFalse(dialog.isShowing)
+        assertEquals(emptyList<String>(), actions)
+    }
+
+    @Test
+    fun backPress_dispatchesCancelAction() {
+        val actions = mutableListOf<String>()
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { action, _, _, _ ->
+            actions.add(action)
+        }
+
+        renderer.render(ui(true))
+        latestDialog().cancel()
+        assertEquals(listOf("cancel_pending"), actions)
+    }
+}
@@ -0,0 +1,78 @@
+package aeska.kistaverk
+
+import android.graphics.Bitmap
+import android.widget.ImageView
+import android.widget.LinearLayout
+import android.widget.ScrollView
+import androidx.test.core.app.ApplicationProvider
+import org.junit.Assert.assertFalse
+import org.junit.Assert.assertNotNull
+import org.junit.Assert.assertSame
+import org.junit.Assert.assertTrue
+import org.junit.Test
+import org.junit.runner.RunWith
+import org.robolectric.RobolectricTestRunner
+import org.robolectric.shadows.ShadowDialog
+import java.io.File
+
+@RunWith(RobolectricTestRunner::class)
+class UiRendererImageTest {
+
+    private fun writePng(name: String): File {
+        val ctx = ApplicationProvider.getApplicationContext<android.content.Context>()
+        val file = File(ctx.cacheDir, name)
+        val bmp = Bitmap.createBitmap(16, 8, Bitmap.Config.ARGB_8888)
+        file.outputStream().use { bmp.compress(Bitmap.CompressFormat.PNG, 100, it) }
+        return file
+    }
+
+    private fun ui(path: String, zoomable: Boolean) = """
+        {
+          "type": "Column",
+          "children": [
+            { "type": "Image", "id": "result", "path": "$path", "zoomable": $zoomable, "max_height_dp": 360 }
+          ]
+        }
+    """.trimIndent()
+
+    private fun imageIn(view: android.view.View): ImageView {
+        val rootLayout = (TestViews.unwrap(view) as ScrollView).getChildAt(0) as LinearLayout
+        return rootLayout.getChildAt(0) as ImageView
+    }
+
+    @Test
+    fun zoomableImage_opensFullscreenOnTap() {
+        val file = writePng("zoomable.png")
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { _, _, _, _ -> }
+
+        val image = imageIn(renderer.render(ui(file.absolutePath, true)))
+        assertNotNull(image.drawable)
+        assertTrue(image.performClick())
+
+        val dialog = ShadowDialog.getLatestDialog()
+        assertNotNull(dialog)
+        assertTrue(dialog.isShowing)
+    }
+
+    @Test
+    fun rerender_keepsDecodedBitmap() {
+        val file = writePng("rerender.png")
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { _, _, _, _ -> }
+
+        val first = imageIn(renderer.render(ui(file.absolutePath, true)))
+        val drawable = first.drawable
+        val second = imageIn(renderer.render(ui(file.absolutePath, true)))
+
+        assertSame(first, second)
+        assertSame(drawable, second.drawable)
+    }
+
+    @Test
+    fun plainImage_isNotClickable() {
+        val file = writePng("plain.png")
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { _, _, _, _ -> }
+
+        val image = imageIn(renderer.render(ui(file.absolutePath, false)))
+        assertFalse(image.isClickable)
+    }
+}
@@ -3,8 +3,11 @@ package aeska.kistaverk
 import android.widget.LinearLayout
 import android.widget.ProgressBar
 import android.widget.ScrollView
+import android.widget.TextView
 import androidx.test.core.app.ApplicationProvider
 import org.junit.Assert.assertEquals
+import org.junit.Assert.assertFalse
+import org.junit.Assert.assertSame
 import org.junit.Assert.assertTrue
 import org.junit.Test
 import org.junit.runner.RunWith
@@ -33,4 +36,33 @@ class UiRendererProgressTest {
         assertEquals("In progress", progressContainer.contentDescription)
         assertTrue(progress.isIndeterminate)
     }
+
+    private fun progressBarUi(fraction: Double) = """
+        {
+          "type": "Column",
+          "children": [
+            { "type": "ProgressBar", "fraction": $fraction, "label": "Extracting 2 of 4", "content_description": "In progress" }
+          ]
+        }
+    """.trimIndent()
+
+    @Test
+    fun progressBar_showsFraction_andUpdatesInPlace() {
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { _, _, _, _ -> }
+
+        val first = (TestViews.unwrap(renderer.render(progressBarUi(0.25))) as ScrollView)
+            .getChildAt(0) as LinearLayout
+        val container = first.getChildAt(0) as LinearLayout
+        val header = container.getChildAt(0) as LinearLayout
+        val bar = container.getChildAt(1) as ProgressBar
+        assertFalse(bar.isIndeterminate)
+        assertEquals("Extracting 2 of 4", (header.getChildAt(0) as TextView).text.toString())
+        assertEquals("25%", (header.getChildAt(1) as TextView).text.toString())
+
+        val second = (TestViews.unwrap(renderer.render(progressBarUi(0.5))) as ScrollView)
+            .getChildAt(0) as LinearLayout
+        assertSame(container, second.getChildAt(0))
+        assertEquals("50%", (header.getChildAt(1) as TextView).text.toString())
+        assertEquals(bar.max / 2, bar.progress)
+    }
 }
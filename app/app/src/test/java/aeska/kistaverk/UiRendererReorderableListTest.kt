@@ -0,0 +1,69 @@
+package aeska.kistaverk
+
+import android.widget.LinearLayout
+import android.widget.ScrollView
+import android.widget.TextView
+import androidx.test.core.app.ApplicationProvider
+import org.junit.Assert.assertEquals
+import org.junit.Test
+import org.junit.runner.RunWith
+import org.robolectric.RobolectricTestRunner
+
+@RunWith(RobolectricTestRunner::class)
+class UiRendererReorderableListTest {
+
+    private val ui = """
+        {
+          "type": "Column",
+          "children": [
+            {
+              "type": "ReorderableList",
+              "bind_key": "preset_order",
+              "action": "preset_reorder",
+              "items": [
+                { "id": "a", "label": "Alpha" },
+                { "id": "b", "label": "Beta" },
+                { "id": "c", "label": "Gamma" }
+              ]
+            }
+          ]
+        }
+    """.trimIndent()
+
+    private fun listIn(view: android.view.View): LinearLayout {
+        val rootLayout = (TestViews.unwrap(view) as ScrollView).getChildAt(0) as LinearLayout
+        return rootLayout.getChildAt(0) as LinearLayout
+    }
+
+    private fun labels(list: LinearLayout) = (0 until list.childCount).map {
+        ((list.getChildAt(it) as LinearLayout).getChildAt(1) as TextView).text.toString()
+    }
+
+    @Test
+    fun moveDown_emitsNewOrder() {
+        val actions = mutableListOf<Pair<String, Map<String, String>>>()
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { action, _, _, bindings ->
+            actions.add(action to bindings)
+        }
+
+        val list = listIn(renderer.render(ui))
+        val firstRow = list.getChildAt(0) as LinearLayout
+        firstRow.getChildAt(3).performClick()
+
+        assertEquals(listOf("Beta", "Alpha", "Gamma"), labels(list))
+        val (action, bindings) = actions.single()
+        assertEquals("preset_reorder", action)
+        assertEquals("b,a,c", bindings["preset_order"])
+    }
+
+    @Test
+    fun rerenderOfSameList_keepsLocalOrder() {
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { _, _, _, _ -> }
+
+        val list = listIn(renderer.render(ui))
+        (list.getChildAt(2) as LinearLayout).getChildAt(2).performClick()
+        val again = listIn(renderer.render(ui))
+
+        assertEquals(listOf("Alpha", "Gamma", "Beta"), labels(again))
+    }
+}
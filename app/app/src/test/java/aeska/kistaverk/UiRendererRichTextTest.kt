@@ -0,0 +1,74 @@
+package aeska.kistaverk
+
+import android.graphics.Typeface
+import android.text.Spanned
+import android.text.method.LinkMovementMethod
+import android.text.style.LeadingMarginSpan
+import android.text.style.StyleSpan
+import android.text.style.TypefaceSpan
+import android.text.style.URLSpan
+import android.widget.LinearLayout
+import android.widget.ScrollView
+import android.widget.TextView
+import androidx.test.core.app.ApplicationProvider
+import org.json.JSONArray
+import org.json.JSONObject
+import org.junit.Assert.assertEquals
+import org.junit.Assert.assertNull
+import org.junit.Assert.assertTrue
+import org.junit.Test
+import org.junit.runner.RunWith
+import org.robolectric.RobolectricTestRunner
+
+@RunWith(RobolectricTestRunner::class)
+class UiRendererRichTextTest {
+
+    private fun ui(markdown: String): String = JSONObject()
+        .put("type", "Column")
+        .put("children", JSONArray().put(JSONObject().put("type", "RichText").put("markdown", markdown)))
+        .toString()
+
+    private fun richTextIn(view: android.view.View): TextView {
+        val rootLayout = (TestViews.unwrap(view) as ScrollView).getChildAt(0) as LinearLayout
+        return rootLayout.getChildAt(0) as TextView
+    }
+
+    private fun spanned(view: TextView) = view.text as Spanned
+
+    private fun textOf(view: TextView, span: Any): String {
+        val text = spanned(view)
+        return text.subSequence(text.getSpanStart(span), text.getSpanEnd(span)).toString()
+    }
+
+    @Test
+    fun inlineMarkup_becomesSpans_andLinksAreTappable() {
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { _, _, _, _ -> }
+
+        val view = richTextIn(renderer.render(ui("**Bold** and *soft* `code`, see [docs](https://example.org) \\*literal\\*")))
+
+        assertEquals("Bold and soft code, see docs *literal*", view.text.toString())
+        val styles = spanned(view).getSpans(0, view.text.length, StyleSpan::class.java)
+        assertEquals("Bold", textOf(view, styles.first { it.style == Typeface.BOLD }))
+        assertEquals("soft", textOf(view, styles.first { it.style == Typeface.ITALIC }))
+        val mono = spanned(view).getSpans(0, view.text.length, TypefaceSpan::class.java).single()
+        assertEquals("code", textOf(view, mono))
+        val link = spanned(view).getSpans(0, view.text.length, URLSpan::class.java).single()
+        assertEquals("https://example.org", link.url)
+        assertEquals("docs", textOf(view, link))
+        assertTrue(view.movementMethod is LinkMovementMethod)
+    }
+
+    @Test
+    fun headingsParagraphsAndNestedLists_areLaidOutAsBlocks() {
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { _, _, _, _ -> }
+
+        val view = richTextIn(renderer.render(ui("# Title\nfirst\nline\n\n- one\n  - nested\n1. numbered")))
+
+        assertEquals("Title\nfirst line\n\n• one\n• nested\n1. numbered", view.text.toString())
+        val margins = spanned(view).getSpans(0, view.text.length, LeadingMarginSpan.Standard::class.java)
+            .sortedBy { spanned(view).getSpanStart(it) }
+        assertEquals(3, margins.size)
+        assertTrue(margins[1].getLeadingMargin(true) > margins[0].getLeadingMargin(true))
+        assertNull(view.movementMethod)
+    }
+}
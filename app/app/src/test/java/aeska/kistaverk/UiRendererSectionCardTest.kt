@@ -1,5 +1,6 @@
 package aeska.kistaverk
 
+import android.view.View
 import android.widget.LinearLayout
 import android.widget.ScrollView
 import android.widget.TextView
@@ -88,4 +89,75 @@ class UiRendererSectionCardTest {
         val cardBody = card.getChildAt(1) as TextView
         assertEquals("Inside card", cardBody.text.toString())
     }
+
+    @Test
+    fun collapsible_section_header_requests_the_other_state() {
+        val json = """
+            {
+              "type": "Column",
+              "children": [
+                {
+                  "type": "Section",
+                  "title": "Utilities",
+                  "expanded": false,
+                  "section_key": "home_category:Utilities",
+                  "toggle_action": "section_toggle",
+                  "children": []
+                }
+              ]
+            }
+        """.trimIndent()
+        val actions = mutableListOf<Pair<String, Map<String, String>>>()
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { action, _, _, bindings ->
+            actions.add(action to bindings)
+        }
+
+        val scroll = TestViews.unwrap(renderer.render(json)) as ScrollView
+        val section = (scroll.getChildAt(0) as LinearLayout).getChildAt(0) as LinearLayout
+        assertEquals(1, section.childCount)
+        val header = section.getChildAt(0) as LinearLayout
+        assertEquals("▸", (header.getChildAt(1) as TextView).text.toString())
+
+        header.performClick()
+        val (action, bindings) = actions.single()
+        assertEquals("section_toggle", action)
+        assertEquals("home_category:Utilities", bindings["section_key"])
+        assertEquals("true", bindings["section_expanded"])
+    }
+
+    @Test
+    fun rtl_root_mirrors_the_layout_and_the_collapsed_chevron() {
+        fun screen(direction: String) = """
+            {
+              "type": "Column",
+              "layout_direction": "$direction",
+              "children": [
+                {
+                  "type": "Section",
+                  "title": "Utilities",
+                  "icon": "🧰",
+                  "expanded": false,
+                  "section_key": "home_category:Utilities",
+                  "toggle_action": "section_toggle",
+                  "children": []
+                }
+              ]
+            }
+        """.trimIndent()
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { _, _, _, _ -> }
+        fun chevron(host: View): String {
+            val scroll = TestViews.unwrap(host) as ScrollView
+            val section = (scroll.getChildAt(0) as LinearLayout).getChildAt(0) as LinearLayout
+            val header = section.getChildAt(0) as LinearLayout
+            return (header.getChildAt(2) as TextView).text.toString()
+        }
+
+        val rtl = renderer.render(screen("rtl"))
+        assertEquals(View.LAYOUT_DIRECTION_RTL, rtl.layoutDirection)
+        assertEquals("◂", chevron(rtl))
+
+        val ltr = renderer.render(screen("ltr"))
+        assertEquals(View.LAYOUT_DIRECTION_LTR, ltr.layoutDirection)
+        assertEquals("▸", chevron(ltr))
+    }
 }
@@ -0,0 +1,57 @@
+package aeska.kistaverk
+
+import android.widget.LinearLayout
+import android.widget.ScrollView
+import android.widget.Switch
+import androidx.test.core.app.ApplicationProvider
+import org.junit.Assert.assertEquals
+import org.junit.Test
+import org.junit.runner.RunWith
+import org.robolectric.RobolectricTestRunner
+
+@RunWith(RobolectricTestRunner::class)
+class UiRendererSwitchTest {
+
+    private fun ui(checked: Boolean) = """
+        {
+          "type": "Column",
+          "children": [
+            { "type": "Switch", "text": "Gzip", "bind_key": "sensor_gzip_segments", "checked": $checked, "action": "sensor_logger_refresh" }
+          ]
+        }
+    """.trimIndent()
+
+    private fun switchIn(view: android.view.View): Switch {
+        val rootLayout = (TestViews.unwrap(view) as ScrollView).getChildAt(0) as LinearLayout
+        return rootLayout.getChildAt(0) as Switch
+    }
+
+    @Test
+    fun switchToggle_updatesBindings_andTriggersAction() {
+        val actions = mutableListOf<Pair<String, Map<String, String>>>()
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { action, _, _, bindings ->
+            actions.add(action to bindings)
+        }
+
+        val toggle = switchIn(renderer.render(ui(false)))
+        toggle.isChecked = true
+
+        val (action, bindings) = actions.last()
+        assertEquals("sensor_logger_refresh", action)
+        assertEquals("true", bindings["sensor_gzip_segments"])
+    }
+
+    @Test
+    fun rerender_setsState_withoutFiringAction() {
+        val actions = mutableListOf<String>()
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { action, _, _, _ ->
+            actions.add(action)
+        }
+
+        renderer.render(ui(false))
+        val toggle = switchIn(renderer.render(ui(true)))
+
+        assertEquals(true, toggle.isChecked)
+        assertEquals(emptyList<String>(), actions)
+    }
+}
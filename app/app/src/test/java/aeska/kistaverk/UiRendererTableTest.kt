@@ -0,0 +1,76 @@
+package aeska.kistaverk
+
+import android.view.ViewGroup
+import android.widget.Button
+import android.widget.FrameLayout
+import android.widget.HorizontalScrollView
+import android.widget.LinearLayout
+import android.widget.ScrollView
+import android.widget.TextView
+import androidx.test.core.app.ApplicationProvider
+import org.junit.Assert.assertEquals
+import org.junit.Assert.assertTrue
+import org.junit.Test
+import org.junit.runner.RunWith
+import org.robolectric.RobolectricTestRunner
+
+@RunWith(RobolectricTestRunner::class)
+class UiRendererTableTest {
+
+    private fun ui(horizontalScroll: Boolean) = """
+        {
+          "type": "Column",
+          "children": [
+            {
+              "type": "Table",
+              "columns": [
+                { "title": "Name" },
+                { "title": "Size", "align": "end", "width_dp": 80 }
+              ],
+              "horizontal_scroll": $horizontalScroll,
+              "max_height_dp": 200,
+              "children": [
+                { "type": "TableRow", "children": [
+                  { "type": "Text", "text": "notes.txt" },
+                  { "type": "Button", "text": "Extract", "action": "archive_extract_entry:0" }
+                ] }
+              ]
+            }
+          ]
+        }
+    """.trimIndent()
+
+    private fun tableIn(view: android.view.View): LinearLayout {
+        val rootLayout = (TestViews.unwrap(view) as ScrollView).getChildAt(0) as LinearLayout
+        return rootLayout.getChildAt(0) as LinearLayout
+    }
+
+    private fun cell(row: ViewGroup, index: Int) = (row.getChildAt(index) as FrameLayout).getChildAt(0)
+
+    @Test
+    fun headerStaysAboveScrollingBody_andCellsAreInteractive() {
+        val actions = mutableListOf<String>()
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { action, _, _, _ ->
+            actions.add(action)
+        }
+
+        val table = tableIn(renderer.render(ui(false))).getChildAt(0) as LinearLayout
+        val header = table.getChildAt(0) as LinearLayout
+        assertEquals("Name", (cell(header, 0) as TextView).text.toString())
+        assertEquals("Size", (cell(header, 1) as TextView).text.toString())
+
+        val body = (table.getChildAt(1) as ScrollView).getChildAt(0) as LinearLayout
+        val row = body.getChildAt(0) as LinearLayout
+        assertEquals("notes.txt", (cell(row, 0) as TextView).text.toString())
+        (cell(row, 1) as Button).performClick()
+        assertEquals(listOf("archive_extract_entry:0"), actions)
+    }
+
+    @Test
+    fun horizontalScroll_wrapsTheWholeTable() {
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { _, _, _, _ -> }
+
+        val wrapper = tableIn(renderer.render(ui(true)))
+        assertTrue(wrapper.getChildAt(0) is HorizontalScrollView)
+    }
+}
@@ -0,0 +1,79 @@
+package aeska.kistaverk
+
+import android.widget.Button
+import android.widget.LinearLayout
+import android.widget.ScrollView
+import androidx.test.core.app.ApplicationProvider
+import org.junit.Assert.assertEquals
+import org.junit.Test
+import org.junit.runner.RunWith
+import org.robolectric.RobolectricTestRunner
+
+@RunWith(RobolectricTestRunner::class)
+class UiRendererTabsTest {
+
+    private val ui = """
+        {
+          "type": "Column",
+          "children": [
+            {
+              "type": "Tabs",
+              "bind_key": "text_tools_tab",
+              "selected": "transform",
+              "action": "text_tools_refresh",
+              "children": [
+                { "type": "Tab", "id": "transform", "label": "Transform", "children": [
+                  { "type": "Button", "text": "Uppercase", "action": "text_tools_upper" }
+                ] },
+                { "type": "Tab", "id": "encode", "label": "Encode", "children": [
+                  { "type": "Button", "text": "Base64 encode", "action": "text_tools_base64_encode" },
+                  { "type": "Button", "text": "Base64 decode", "action": "text_tools_base64_decode" }
+                ] }
+              ]
+            }
+          ]
+        }
+    """.trimIndent()
+
+    private fun tabsIn(view: android.view.View): LinearLayout {
+        val rootLayout = (TestViews.unwrap(view) as ScrollView).getChildAt(0) as LinearLayout
+        return rootLayout.getChildAt(0) as LinearLayout
+    }
+
+    @Test
+    fun selectedPaneIsBuilt_andItsIdIsBound() {
+        val actions = mutableListOf<Pair<String, Map<String, String>>>()
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { action, _, _, bindings ->
+            actions.add(action to bindings)
+        }
+
+        val tabs = tabsIn(renderer.render(ui))
+        val header = tabs.getChildAt(0) as LinearLayout
+        val content = tabs.getChildAt(1) as LinearLayout
+        assertEquals(2, header.childCount)
+        assertEquals(1, content.childCount)
+        assertEquals("Uppercase", (content.getChildAt(0) as Button).text.toString())
+
+        (content.getChildAt(0) as Button).performClick()
+        assertEquals("transform", actions.last().second["text_tools_tab"])
+    }
+
+    @Test
+    fun headerClick_switchesPane_andReportsSelection() {
+        val actions = mutableListOf<Pair<String, Map<String, String>>>()
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { action, _, _, bindings ->
+            actions.add(action to bindings)
+        }
+
+        val tabs = tabsIn(renderer.render(ui))
+        val header = tabs.getChildAt(0) as LinearLayout
+        header.getChildAt(1).performClick()
+
+        val content = tabs.getChildAt(1) as LinearLayout
+        assertEquals(2, content.childCount)
+        assertEquals("Base64 encode", (content.getChildAt(0) as Button).text.toString())
+        val (action, bindings) = actions.last()
+        assertEquals("text_tools_refresh", action)
+        assertEquals("encode", bindings["text_tools_tab"])
+    }
+}
@@ -0,0 +1,46 @@
+package aeska.kistaverk
+
+import android.text.Spanned
+import android.text.style.BackgroundColorSpan
+import android.widget.LinearLayout
+import android.widget.ScrollView
+import android.widget.TextView
+import androidx.test.core.app.ApplicationProvider
+import org.junit.Assert.assertEquals
+import org.junit.Test
+import org.junit.runner.RunWith
+import org.robolectric.RobolectricTestRunner
+
+@RunWith(RobolectricTestRunner::class)
+class UiRendererTextSpansTest {
+
+    @Test
+    fun textSpans_applyBackgroundHighlights() {
+        val renderer = UiRenderer(ApplicationProvider.getApplicationContext()) { _, _, _, _ -> }
+        val ui = """
+            {
+              "type": "Column",
+              "children": [
+                {
+                  "type": "Text",
+                  "text": "foo 123 bar 456",
+                  "spans": [
+                    { "start": 4, "end": 7, "background": "#FFF59D" },
+                    { "start": 12, "end": 99, "background": "#A5D6A7" }
+                  ]
+                }
+              ]
+            }
+        """.trimIndent()
+
+        val view = TestViews.unwrap(renderer.render(ui)) as ScrollView
+        val rootLayout = view.getChildAt(0) as LinearLayout
+        val text = (rootLayout.getChildAt(0) as TextView).text as Spanned
+        val spans = text.getSpans(0, text.length, BackgroundColorSpan::class.java)
+
+        assertEquals(2, spans.size)
+        assertEquals(4, text.getSpanStart(spans[0]))
+        assertEquals(7, text.getSpanEnd(spans[0]))
+        assertEquals(text.length, text.getSpanEnd(spans[1]))
+    }
+}
@@ -58,4 +58,20 @@ class UiRendererValidationTest {
         assertTrue(title.text.toString().contains("Render error"))
         assertTrue(msg.text.toString().contains("PdfPreviewGrid missing source_uri"))
     }
+
+    @Test
+    fun chart_without_series_fails_validation() {
+        val ui = """{ "type": "Chart", "height_dp": 96 }"""
+        val (title, msg) = render(ui)
+        assertTrue(title.text.toString().contains("Render error"))
+        assertTrue(msg.text.toString().contains("Chart missing series"))
+    }
+
+    @Test
+    fun slider_without_bind_key_fails_validation() {
+        val ui = """{ "type": "Slider", "min": 0, "max": 10, "value": 3 }"""
+        val (title, msg) = render(ui)
+        assertTrue(title.text.toString().contains("Render error"))
+        assertTrue(msg.text.toString().contains("Slider missing bind_key"))
+    }
 }
@@ -0,0 +1,252 @@
+use rust_i18n::t;
+use std::fmt;
+
+/// A failure shown to the user. State and worker plumbing still carry errors as
+/// `code[:detail]` strings (`Display` produces exactly that), so this type parses them
+/// back and turns them into a translated message at render time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppError {
+    MissingPath,
+    MissingFd,
+    InvalidFd,
+    MissingSource,
+    NoFileSelected,
+    NoImageSelected,
+    NoPagesSelected,
+    PageOutOfRange,
+    ArchiveCompressRequiresPath,
+    GzipRequiresPath,
+    VaultRequiresPath,
+    VaultMissingPassword,
+    OpenFailed(String),
+    ReadFailed(String),
+    WriteFailed(String),
+    MkdirFailed(String),
+    SaveFailed(String),
+    WorkerSendFailed(String),
+    UnknownTool(String),
+    /// Any code without a dedicated variant; shown as a humanized code.
+    Other {
+        code: String,
+        detail: Option<String>,
+    },
+}
+
+impl AppError {
+    pub fn parse(raw: &str) -> Self {
+        let raw = raw.trim();
+        let (code, detail) = match raw.split_once(':') {
+            Some((code, detail)) => (code, Some(detail.trim().to_string())),
+            None => (raw, None),
+        };
+        if !is_code(code) {
+            // Library and parser errors are stored as readable text; keep them whole.
+            return AppError::Other {
+                code: raw.to_string(),
+                detail: None,
+            };
+        }
+        let detail_or_empty = || detail.clone().unwrap_or_default();
+        match code {
+            "missing_path" => AppError::MissingPath,
+            "missing_fd" => AppError::MissingFd,
+            "invalid_fd" => AppError::InvalidFd,
+            "missing_source" => AppError::MissingSource,
+            "no_file_selected" => AppError::NoFileSelected,
+            "no_image_selected" => AppError::NoImageSelected,
+            "no_pages_selected" => AppError::NoPagesSelected,
+            "page_out_of_range" => AppError::PageOutOfRange,
+            "archive_compress_requires_path" => AppError::ArchiveCompressRequiresPath,
+            "gzip_requires_path" => AppError::GzipRequiresPath,
+            "vault_requires_path" => AppError::VaultRequiresPath,
+            "vault_missing_password" => AppError::VaultMissingPassword,
+            "open_failed" => AppError::OpenFailed(detail_or_empty()),
+            "read_failed" => AppError::ReadFailed(detail_or_empty()),
+            "write_failed" => AppError::WriteFailed(detail_or_empty()),
+            "mkdir_failed" => AppError::MkdirFailed(detail_or_empty()),
+            "save_failed" => AppError::SaveFailed(detail_or_empty()),
+            "worker_send_failed" => AppError::WorkerSendFailed(detail_or_empty()),
+            "unknown_tool" => AppError::UnknownTool(detail_or_empty()),
+            _ => AppError::Other {
+                code: code.to_string(),
+                detail,
+            },
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        match self {
+            AppError::MissingPath => "missing_path",
+            AppError::MissingFd => "missing_fd",
+            AppError::InvalidFd => "invalid_fd",
+            AppError::MissingSource => "missing_source",
+            AppError::NoFileSelected => "no_file_selected",
+            AppError::NoImageSelected => "no_image_selected",
+            AppError::NoPagesSelected => "no_pages_selected",
+            AppError::PageOutOfRange => "page_out_of_range",
+            AppError::ArchiveCompressRequiresPath => "archive_compress_requires_path",
+            AppError::GzipRequiresPath => "gzip_requires_path",
+            AppError::VaultRequiresPath => "vault_requires_path",
+            AppError::VaultMissingPassword => "vault_missing_password",
+            AppError::OpenFailed(_) => "open_failed",
+            AppError::ReadFailed(_) => "read_failed",
+            AppError::WriteFailed(_) => "write_failed",
+            AppError::MkdirFailed(_) => "mkdir_failed",
+            AppError::SaveFailed(_) => "save_failed",
+            AppError::WorkerSendFailed(_) => "worker_send_failed",
+            AppError::UnknownTool(_) => "unknown_tool",
+            AppError::Other { code, .. } => code,
+        }
+    }
+
+    pub fn detail(&self) -> Option<&str> {
+        let detail = match self {
+            AppError::OpenFailed(d)
+            | AppError::ReadFailed(d)
+            | AppError::WriteFailed(d)
+            | AppError::MkdirFailed(d)
+            | AppError::SaveFailed(d)
+            | AppError::WorkerSendFailed(d)
+            | AppError::UnknownTool(d) => Some(d.as_str()),
+            AppError::Other { detail, .. } => detail.as_deref(),
+            _ => None,
+        };
+        detail.filter(|d| !d.is_empty())
+    }
+
+    /// Locale key of the message; every code with a dedicated variant has one.
+    pub fn i18n_key(&self) -> Option<&'static str> {
+        Some(match self {
+            AppError::MissingPath => "error_missing_path",
+            AppError::MissingFd => "error_missing_fd",
+            AppError::InvalidFd => "error_invalid_fd",
+            AppError::MissingSource => "error_missing_source",
+            AppError::NoFileSelected => "error_no_file_selected",
+            AppError::NoImageSelected => "error_no_image_selected",
+            AppError::NoPagesSelected => "error_no_pages_selected",
+            AppError::PageOutOfRange => "error_page_out_of_range",
+            AppError::ArchiveCompressRequiresPath => "error_archive_compress_requires_path",
+            AppError::GzipRequiresPath => "error_gzip_requires_path",
+            AppError::VaultRequiresPath => "error_vault_requires_path",
+            AppError::VaultMissingPassword => "error_vault_missing_password",
+            AppError::OpenFailed(_) => "error_open_failed",
+            AppError::ReadFailed(_) => "error_read_failed",
+            AppError::WriteFailed(_) => "error_write_failed",
+            AppError::MkdirFailed(_) => "error_mkdir_failed",
+            AppError::SaveFailed(_) => "error_save_failed",
+            AppError::WorkerSendFailed(_) => "error_worker_send_failed",
+            AppError::UnknownTool(_) => "error_unknown_tool",
+            AppError::Other { .. } => return None,
+        })
+    }
+
+    /// Translated message, followed by the detail when there is one.
+    pub fn message(&self) -> String {
+        let text = match self.i18n_key() {
+            Some(key) => t!(key).to_string(),
+            None if is_code(self.code()) => humanize_code(self.code()),
+            None => self.code().to_string(),
+        };
+        match self.detail() {
+            Some(detail) => format!("{text}: {detail}"),
+            None => text,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.detail() {
+            Some(detail) => write!(f, "{}:{detail}", self.code()),
+            None => f.write_str(self.code()),
+        }
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(raw: &str) -> Self {
+        AppError::parse(raw)
+    }
+}
+
+impl From<String> for AppError {
+    fn from(raw: String) -> Self {
+        AppError::parse(&raw)
+    }
+}
+
+impl From<AppError> for String {
+    fn from(err: AppError) -> Self {
+        err.to_string()
+    }
+}
+
+/// Codes are non-empty snake_case identifiers such as `open_failed`.
+fn is_code(code: &str) -> bool {
+    !code.is_empty()
+        && code
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// `archive_reopen_failed` -> `Archive reopen failed`.
+fn humanize_code(code: &str) -> String {
+    let spaced = code.replace('_', " ");
+    let mut chars = spaced.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Display line for a stored error string: localized prefix and message, with the code
+/// kept at the end so bug reports still name it.
+pub fn error_text(raw: &str) -> String {
+    let err = AppError::parse(raw);
+    let prefix = t!("multi_hash_error_prefix");
+    if is_code(err.code()) {
+        format!("{prefix}{} ({})", err.message(), err.code())
+    } else {
+        format!("{prefix}{}", err.message())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip_through_display() {
+        for raw in [
+            "missing_path",
+            "open_failed:No such file",
+            "unknown_tool:x",
+            "archive_reopen_failed:bad header",
+            "resize_missing_dimensions",
+        ] {
+            assert_eq!(AppError::parse(raw).to_string(), raw);
+        }
+        assert_eq!(
+            AppError::parse("open_failed:denied"),
+            AppError::OpenFailed("denied".into())
+        );
+        assert_eq!(AppError::parse("open_failed").detail(), None);
+    }
+
+    #[test]
+    fn messages_are_translated_or_humanized() {
+        let known = AppError::MissingPath;
+        let message = known.message();
+        assert!(!message.is_empty());
+        assert_ne!(message, "error_missing_path");
+
+        let other = AppError::parse("archive_reopen_failed:bad header");
+        assert_eq!(other.i18n_key(), None);
+        assert_eq!(other.message(), "Archive reopen failed: bad header");
+        assert!(error_text("archive_reopen_failed").ends_with("(archive_reopen_failed)"));
+
+        let text = AppError::parse("Invalid regex: unclosed group");
+        assert_eq!(text.to_string(), "Invalid regex: unclosed group");
+        assert!(error_text("Invalid regex: unclosed group").ends_with(": unclosed group"));
+    }
+}
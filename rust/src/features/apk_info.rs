@@ -0,0 +1,658 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Seek, SeekFrom};
+use zip::ZipArchive;
+
+/// Largest manifest or signature entry read from the archive.
+const MAX_ENTRY_BYTES: u64 = 4 << 20;
+/// Largest APK Signing Block loaded into memory.
+const MAX_SIGNING_BLOCK_BYTES: u64 = 16 << 20;
+const APK_SIG_BLOCK_MAGIC: &[u8; 16] = b"APK Sig Block 42";
+/// Signature scheme IDs stored in the APK Signing Block.
+const SIGNATURE_SCHEMES: [(u32, &str); 3] = [
+    (0x7109_871a, "v2"),
+    (0xf053_68c0, "v3"),
+    (0x1b93_ad61, "v3.1"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageKind {
+    Apk,
+    Jar,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// SHA-256 of the DER certificate, as colon-separated uppercase hex.
+    pub sha256: String,
+}
+
+/// Package and signing details of an APK or JAR.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageInfo {
+    pub kind: PackageKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_sdk: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_sdk: Option<String>,
+    /// Signature schemes found, e.g. `v1 (JAR)`, `v2`, `v3`.
+    #[serde(default)]
+    pub schemes: Vec<String>,
+    #[serde(default)]
+    pub certificates: Vec<CertificateInfo>,
+}
+
+/// Inspects a ZIP that is an APK (`AndroidManifest.xml`) or a JAR (`META-INF/MANIFEST.MF`).
+/// Returns `None` for other archives. Signatures are listed, not verified.
+pub fn inspect_package<R: Read + Seek>(reader: &mut R) -> Option<PackageInfo> {
+    let mut archive = ZipArchive::new(&mut *reader).ok()?;
+    let read_entry = |archive: &mut ZipArchive<&mut R>, name: &str| -> Option<Vec<u8>> {
+        let entry = archive.by_name(name).ok()?;
+        let mut bytes = Vec::new();
+        entry.take(MAX_ENTRY_BYTES).read_to_end(&mut bytes).ok()?;
+        Some(bytes)
+    };
+
+    let mut info = if let Some(axml) = read_entry(&mut archive, "AndroidManifest.xml") {
+        let mut info = PackageInfo::new(PackageKind::Apk);
+        apply_manifest(&mut info, &axml);
+        info
+    } else if archive.by_name("META-INF/MANIFEST.MF").is_ok() {
+        PackageInfo::new(PackageKind::Jar)
+    } else {
+        return None;
+    };
+
+    let signature_files: Vec<String> = archive
+        .file_names()
+        .filter(|name| {
+            let upper = name.to_ascii_uppercase();
+            upper.starts_with("META-INF/")
+                && [".RSA", ".DSA", ".EC"]
+                    .iter()
+                    .any(|ext| upper.ends_with(ext))
+        })
+        .map(str::to_string)
+        .collect();
+    let mut certificates = Vec::new();
+    for name in &signature_files {
+        if let Some(pkcs7) = read_entry(&mut archive, name) {
+            certificates.extend(pkcs7_certificates(&pkcs7).into_iter().map(<[u8]>::to_vec));
+        }
+    }
+    if !signature_files.is_empty() {
+        info.schemes.push("v1 (JAR)".into());
+    }
+    drop(archive);
+
+    if info.kind == PackageKind::Apk {
+        if let Some(block) = read_signing_block(reader) {
+            for (id, value) in signing_block_pairs(&block) {
+                if let Some((_, scheme)) = SIGNATURE_SCHEMES.iter().find(|(sid, _)| *sid == id) {
+                    info.schemes.push((*scheme).into());
+                    certificates.extend(scheme_certificates(value).into_iter().map(<[u8]>::to_vec));
+                }
+            }
+        }
+    }
+
+    for der in certificates {
+        let sha256 = Sha256::digest(&der)
+            .iter()
+            .map(|b| format!("{b:02X}"))
+            .collect::<Vec<_>>()
+            .join(":");
+        if info.certificates.iter().all(|c| c.sha256 != sha256) {
+            info.certificates.push(CertificateInfo {
+                subject: certificate_subject(&der),
+                sha256,
+            });
+        }
+    }
+    Some(info)
+}
+
+impl PackageInfo {
+    fn new(kind: PackageKind) -> Self {
+        Self {
+            kind,
+            package: None,
+            version_name: None,
+            version_code: None,
+            min_sdk: None,
+            target_sdk: None,
+            schemes: Vec::new(),
+            certificates: Vec::new(),
+        }
+    }
+}
+
+fn le_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn le_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn le_u64(data: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(at..at + 8)?.try_into().ok()?))
+}
+
+// --- Binary AndroidManifest.xml --------------------------------------------
+
+const RES_STRING_POOL_TYPE: u16 = 0x0001;
+const RES_XML_START_ELEMENT_TYPE: u16 = 0x0102;
+const STRING_POOL_UTF8_FLAG: u32 = 0x100;
+const TYPE_STRING: u8 = 0x03;
+const TYPE_INT_DEC: u8 = 0x10;
+const TYPE_INT_HEX: u8 = 0x11;
+const NO_ENTRY: u32 = 0xFFFF_FFFF;
+
+fn parse_string_pool(chunk: &[u8], header_size: usize) -> Vec<String> {
+    let (Some(count), Some(flags), Some(strings_start)) =
+        (le_u32(chunk, 8), le_u32(chunk, 16), le_u32(chunk, 20))
+    else {
+        return Vec::new();
+    };
+    let utf8 = flags & STRING_POOL_UTF8_FLAG != 0;
+    (0..count as usize)
+        .map(|i| {
+            le_u32(chunk, header_size + 4 * i)
+                .and_then(|offset| {
+                    let at = strings_start as usize + offset as usize;
+                    if utf8 {
+                        pool_string_utf8(chunk, at)
+                    } else {
+                        pool_string_utf16(chunk, at)
+                    }
+                })
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn pool_string_utf8(chunk: &[u8], at: usize) -> Option<String> {
+    // A UTF-16 length, then the UTF-8 byte length; each is one or two bytes.
+    let len8 = |at: usize| -> Option<(usize, usize)> {
+        let first = *chunk.get(at)? as usize;
+        if first & 0x80 != 0 {
+            Some((((first & 0x7F) << 8) | *chunk.get(at + 1)? as usize, 2))
+        } else {
+            Some((first, 1))
+        }
+    };
+    let (_, skip) = len8(at)?;
+    let (len, used) = len8(at + skip)?;
+    let start = at + skip + used;
+    Some(String::from_utf8_lossy(chunk.get(start..start + len)?).into_owned())
+}
+
+fn pool_string_utf16(chunk: &[u8], at: usize) -> Option<String> {
+    let first = le_u16(chunk, at)? as usize;
+    let (len, used) = if first & 0x8000 != 0 {
+        (
+            ((first & 0x7FFF) << 16) | le_u16(chunk, at + 2)? as usize,
+            4,
+        )
+    } else {
+        (first, 2)
+    };
+    let units = (0..len)
+        .map(|i| le_u16(chunk, at + used + 2 * i))
+        .collect::<Option<Vec<_>>>()?;
+    Some(String::from_utf16_lossy(&units))
+}
+
+/// Reads `package`, `versionCode`, `versionName` and the `uses-sdk` levels.
+fn apply_manifest(info: &mut PackageInfo, axml: &[u8]) {
+    let Some(header_size) = le_u16(axml, 2) else {
+        return;
+    };
+    let mut strings = Vec::new();
+    let mut pos = header_size as usize;
+    while let (Some(kind), Some(chunk_header), Some(size)) = (
+        le_u16(axml, pos),
+        le_u16(axml, pos + 2),
+        le_u32(axml, pos + 4),
+    ) {
+        let size = size as usize;
+        let Some(chunk) = axml.get(pos..pos + size).filter(|_| size >= 8) else {
+            break;
+        };
+        match kind {
+            RES_STRING_POOL_TYPE => strings = parse_string_pool(chunk, chunk_header as usize),
+            RES_XML_START_ELEMENT_TYPE => {
+                apply_element(info, &strings, chunk, chunk_header as usize);
+            }
+            _ => {}
+        }
+        pos += size;
+    }
+}
+
+fn apply_element(info: &mut PackageInfo, strings: &[String], chunk: &[u8], header: usize) {
+    let string = |idx: u32| strings.get(idx as usize).cloned();
+    let (Some(name), Some(attr_start), Some(attr_size), Some(attr_count)) = (
+        le_u32(chunk, header + 4),
+        le_u16(chunk, header + 8),
+        le_u16(chunk, header + 10),
+        le_u16(chunk, header + 12),
+    ) else {
+        return;
+    };
+    let element = string(name);
+    for i in 0..attr_count as usize {
+        let at = header + attr_start as usize + i * attr_size as usize;
+        let (Some(attr_name), Some(raw), Some(data_type), Some(data)) = (
+            le_u32(chunk, at + 4),
+            le_u32(chunk, at + 8),
+            chunk.get(at + 15),
+            le_u32(chunk, at + 16),
+        ) else {
+            return;
+        };
+        let value = if raw != NO_ENTRY {
+            string(raw)
+        } else {
+            match *data_type {
+                TYPE_STRING => string(data),
+                TYPE_INT_DEC | TYPE_INT_HEX => Some(data.to_string()),
+                _ => None,
+            }
+        };
+        let slot = match (element.as_deref(), string(attr_name).as_deref()) {
+            (Some("manifest"), Some("package")) => &mut info.package,
+            (Some("manifest"), Some("versionCode")) => &mut info.version_code,
+            (Some("manifest"), Some("versionName")) => &mut info.version_name,
+            (Some("uses-sdk"), Some("minSdkVersion")) => &mut info.min_sdk,
+            (Some("uses-sdk"), Some("targetSdkVersion")) => &mut info.target_sdk,
+            _ => continue,
+        };
+        *slot = value;
+    }
+}
+
+// --- Signatures ------------------------------------------------------------
+
+/// Reads one DER TLV at `pos`, returning `(tag, body, end)`.
+fn der_tlv(data: &[u8], pos: usize) -> Option<(u8, &[u8], usize)> {
+    let tag = *data.get(pos)?;
+    let first = *data.get(pos + 1)? as usize;
+    let (len, header) = if first < 0x80 {
+        (first, 2)
+    } else {
+        let n = first & 0x7F;
+        if n == 0 || n > 4 {
+            return None;
+        }
+        let len = data
+            .get(pos + 2..pos + 2 + n)?
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        (len, 2 + n)
+    };
+    let end = pos + header + len;
+    Some((tag, data.get(pos + header..end)?, end))
+}
+
+/// Iterates `(tag, body, whole TLV)` for the elements of a constructed DER value.
+fn der_children(data: &[u8]) -> impl Iterator<Item = (u8, &[u8], &[u8])> {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let (tag, body, end) = der_tlv(data, pos)?;
+        let whole = &data[pos..end];
+        pos = end;
+        Some((tag, body, whole))
+    })
+}
+
+/// Certificates of a PKCS#7 `SignedData` (the v1 `META-INF/*.RSA` files).
+fn pkcs7_certificates(pkcs7: &[u8]) -> Vec<&[u8]> {
+    let signed_data = der_tlv(pkcs7, 0)
+        .and_then(|(_, content_info, _)| {
+            der_children(content_info).find(|(tag, _, _)| *tag == 0xA0)
+        })
+        .and_then(|(_, explicit, _)| der_tlv(explicit, 0));
+    let Some((_, signed_data, _)) = signed_data else {
+        return Vec::new();
+    };
+    // version, digestAlgorithms, contentInfo, then [0] IMPLICIT certificates.
+    der_children(signed_data)
+        .skip(3)
+        .find(|(tag, _, _)| *tag == 0xA0)
+        .map(|(_, certs, _)| der_children(certs).map(|(_, _, whole)| whole).collect())
+        .unwrap_or_default()
+}
+
+/// `CN=.., O=..` from the subject of a DER X.509 certificate.
+fn certificate_subject(der: &[u8]) -> Option<String> {
+    let (_, cert, _) = der_tlv(der, 0)?;
+    let (_, tbs, _) = der_tlv(cert, 0)?;
+    let mut fields = der_children(tbs).peekable();
+    // Optional explicit [0] version.
+    fields.next_if(|(tag, _, _)| *tag == 0xA0);
+    // serialNumber, signature, issuer, validity, subject.
+    let (_, subject, _) = fields.nth(4)?;
+    let parts: Vec<String> = der_children(subject)
+        .flat_map(|(_, rdn, _)| der_children(rdn))
+        .filter_map(|(_, attribute, _)| {
+            let mut items = der_children(attribute);
+            let (_, oid, _) = items.next()?;
+            let (_, value, _) = items.next()?;
+            let label = match oid {
+                [0x55, 0x04, 0x03] => "CN",
+                [0x55, 0x04, 0x06] => "C",
+                [0x55, 0x04, 0x07] => "L",
+                [0x55, 0x04, 0x08] => "ST",
+                [0x55, 0x04, 0x0A] => "O",
+                [0x55, 0x04, 0x0B] => "OU",
+                _ => return None,
+            };
+            Some(format!("{label}={}", String::from_utf8_lossy(value)))
+        })
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+/// Locates the APK Signing Block that sits right before the central directory.
+fn read_signing_block<R: Read + Seek>(reader: &mut R) -> Option<Vec<u8>> {
+    let size = reader.seek(SeekFrom::End(0)).ok()?;
+    // End of central directory: 22 bytes plus a comment of up to 64 KiB.
+    let tail_len = size.min(22 + 0xFFFF);
+    reader.seek(SeekFrom::Start(size - tail_len)).ok()?;
+    let mut tail = vec![0u8; tail_len as usize];
+    reader.read_exact(&mut tail).ok()?;
+    let eocd = (0..tail.len().saturating_sub(21))
+        .rev()
+        .find(|&i| tail[i..].starts_with(b"PK\x05\x06"))?;
+    let cd_offset = u64::from(le_u32(&tail, eocd + 16)?);
+
+    if cd_offset < 32 {
+        return None;
+    }
+    reader.seek(SeekFrom::Start(cd_offset - 24)).ok()?;
+    let mut footer = [0u8; 24];
+    reader.read_exact(&mut footer).ok()?;
+    if &footer[8..] != APK_SIG_BLOCK_MAGIC {
+        return None;
+    }
+    // The size field excludes itself; the block starts with a copy of it.
+    let block_size = le_u64(&footer, 0)?;
+    if block_size > MAX_SIGNING_BLOCK_BYTES || block_size + 8 > cd_offset {
+        return None;
+    }
+    reader
+        .seek(SeekFrom::Start(cd_offset - block_size - 8))
+        .ok()?;
+    let mut block = vec![0u8; (block_size + 8) as usize];
+    reader.read_exact(&mut block).ok()?;
+    Some(block)
+}
+
+/// `(id, value)` pairs between the leading size and the trailing size + magic.
+fn signing_block_pairs(block: &[u8]) -> impl Iterator<Item = (u32, &[u8])> {
+    let end = block.len().saturating_sub(24);
+    let mut pos = 8;
+    std::iter::from_fn(move || {
+        let len = usize::try_from(le_u64(block, pos)?).ok()?;
+        let value_end = pos.checked_add(8 + len).filter(|e| *e <= end && len >= 4)?;
+        let id = le_u32(block, pos + 8)?;
+        let value = &block[pos + 12..value_end];
+        pos = value_end;
+        Some((id, value))
+    })
+}
+
+/// Splits a sequence of `u32` little-endian length-prefixed items.
+fn length_prefixed(data: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let len = le_u32(data, pos)? as usize;
+        let item = data.get(pos + 4..pos + 4 + len)?;
+        pos += 4 + len;
+        Some(item)
+    })
+}
+
+/// Certificates of every signer in a v2/v3 scheme block:
+/// signers → signer → signed data → (digests, certificates).
+fn scheme_certificates(value: &[u8]) -> Vec<&[u8]> {
+    let Some(signers) = length_prefixed(value).next() else {
+        return Vec::new();
+    };
+    length_prefixed(signers)
+        .filter_map(|signer| length_prefixed(signer).next())
+        .filter_map(|signed_data| length_prefixed(signed_data).nth(1))
+        .flat_map(length_prefixed)
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::write::FileOptions;
+    use zip::ZipWriter;
+
+    fn der(tag: u8, body: &[u8]) -> Vec<u8> {
+        let mut out = vec![tag];
+        if body.len() < 0x80 {
+            out.push(body.len() as u8);
+        } else {
+            out.push(0x82);
+            out.extend_from_slice(&(body.len() as u16).to_be_bytes());
+        }
+        out.extend_from_slice(body);
+        out
+    }
+
+    fn certificate(cn: &str) -> Vec<u8> {
+        let name = |cn: &str| {
+            der(
+                0x30,
+                &der(
+                    0x31,
+                    &der(
+                        0x30,
+                        &[der(0x06, &[0x55, 0x04, 0x03]), der(0x0C, cn.as_bytes())].concat(),
+                    ),
+                ),
+            )
+        };
+        let tbs = der(
+            0x30,
+            &[
+                der(0xA0, &der(0x02, &[2])),
+                der(0x02, &[1]),
+                der(0x30, &der(0x06, &[0x2A, 0x86, 0x48])),
+                name("Issuer"),
+                der(0x30, &[]),
+                name(cn),
+            ]
+            .concat(),
+        );
+        der(0x30, &tbs)
+    }
+
+    fn pkcs7(cert: &[u8]) -> Vec<u8> {
+        let signed_data = der(
+            0x30,
+            &[
+                der(0x02, &[1]),
+                der(0x31, &[]),
+                der(0x30, &der(0x06, &[0x2A, 0x86, 0x48])),
+                der(0xA0, cert),
+                der(0x31, &[]),
+            ]
+            .concat(),
+        );
+        der(
+            0x30,
+            &[der(0x06, &[0x2A, 0x86, 0x48]), der(0xA0, &signed_data)].concat(),
+        )
+    }
+
+    fn chunk(kind: u16, header: &[u8], body: &[u8]) -> Vec<u8> {
+        let mut out = kind.to_le_bytes().to_vec();
+        out.extend_from_slice(&((header.len() + 8) as u16).to_le_bytes());
+        out.extend_from_slice(&((header.len() + body.len() + 8) as u32).to_le_bytes());
+        out.extend_from_slice(header);
+        out.extend_from_slice(body);
+        out
+    }
+
+    /// Binary manifest: `<manifest package versionCode versionName><uses-sdk min target/>`.
+    fn manifest() -> Vec<u8> {
+        let strings = [
+            "manifest",
+            "package",
+            "versionCode",
+            "versionName",
+            "uses-sdk",
+            "minSdkVersion",
+            "targetSdkVersion",
+            "aeska.kistaverk",
+            "1.4.2",
+        ];
+        let mut offsets = Vec::new();
+        let mut data = Vec::new();
+        for s in strings {
+            offsets.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            data.extend_from_slice(&[s.len() as u8, s.len() as u8]);
+            data.extend_from_slice(s.as_bytes());
+            data.push(0);
+        }
+        let mut pool_header = Vec::new();
+        for v in [
+            strings.len() as u32,
+            0,
+            STRING_POOL_UTF8_FLAG,
+            28 + offsets.len() as u32,
+            0,
+        ] {
+            pool_header.extend_from_slice(&v.to_le_bytes());
+        }
+        let pool = chunk(
+            RES_STRING_POOL_TYPE,
+            &pool_header,
+            &[offsets, data].concat(),
+        );
+
+        let element = |name: u32, attrs: &[(u32, u32, u8, u32)]| {
+            let mut body = Vec::new();
+            for v in [NO_ENTRY, name] {
+                body.extend_from_slice(&v.to_le_bytes());
+            }
+            for v in [20u16, 20, attrs.len() as u16, 0, 0, 0] {
+                body.extend_from_slice(&v.to_le_bytes());
+            }
+            for &(attr, raw, data_type, data) in attrs {
+                for v in [NO_ENTRY, attr, raw] {
+                    body.extend_from_slice(&v.to_le_bytes());
+                }
+                body.extend_from_slice(&[8, 0, 0, data_type]);
+                body.extend_from_slice(&data.to_le_bytes());
+            }
+            // Start-element header: line number and comment.
+            chunk(
+                RES_XML_START_ELEMENT_TYPE,
+                &[1, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF],
+                &body,
+            )
+        };
+        let manifest = element(
+            0,
+            &[
+                (1, 7, TYPE_STRING, 7),
+                (2, NO_ENTRY, TYPE_INT_DEC, 42),
+                (3, 8, TYPE_STRING, 8),
+            ],
+        );
+        let uses_sdk = element(
+            4,
+            &[
+                (5, NO_ENTRY, TYPE_INT_DEC, 24),
+                (6, NO_ENTRY, TYPE_INT_DEC, 34),
+            ],
+        );
+        chunk(0x0003, &[], &[pool, manifest, uses_sdk].concat())
+    }
+
+    /// A v1 + v2 signed APK whose certificates have CN `Kistaverk`.
+    pub(crate) fn sample_apk() -> Vec<u8> {
+        let cert = certificate("Kistaverk");
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, bytes) in [
+            ("AndroidManifest.xml", manifest()),
+            ("META-INF/CERT.RSA", pkcs7(&cert)),
+            ("classes.dex", b"dex\n035\0".to_vec()),
+        ] {
+            zip.start_file(name, FileOptions::default()).unwrap();
+            zip.write_all(&bytes).unwrap();
+        }
+        let mut apk = zip.finish().unwrap().into_inner();
+
+        let lp = |item: &[u8]| [&(item.len() as u32).to_le_bytes()[..], item].concat();
+        let signed_data = [lp(&[]), lp(&lp(&cert))].concat();
+        let v2 = lp(&lp(&lp(&signed_data)));
+        let mut pair = ((v2.len() + 4) as u64).to_le_bytes().to_vec();
+        pair.extend_from_slice(&0x7109_871au32.to_le_bytes());
+        pair.extend_from_slice(&v2);
+        let block_size = (pair.len() + 24) as u64;
+        let block = [
+            &block_size.to_le_bytes()[..],
+            &pair,
+            &block_size.to_le_bytes(),
+            APK_SIG_BLOCK_MAGIC,
+        ]
+        .concat();
+
+        let eocd = apk.len() - 22;
+        let cd_offset = le_u32(&apk, eocd + 16).unwrap();
+        let new_offset = cd_offset + block.len() as u32;
+        apk[eocd + 16..eocd + 20].copy_from_slice(&new_offset.to_le_bytes());
+        let (entries, central_directory) = apk.split_at(cd_offset as usize);
+        [entries, &block, central_directory].concat()
+    }
+
+    #[test]
+    fn reads_manifest_and_signatures() {
+        let info = inspect_package(&mut Cursor::new(sample_apk())).unwrap();
+        assert_eq!(info.kind, PackageKind::Apk);
+        assert_eq!(info.package.as_deref(), Some("aeska.kistaverk"));
+        assert_eq!(info.version_name.as_deref(), Some("1.4.2"));
+        assert_eq!(info.version_code.as_deref(), Some("42"));
+        assert_eq!(info.min_sdk.as_deref(), Some("24"));
+        assert_eq!(info.target_sdk.as_deref(), Some("34"));
+        assert_eq!(info.schemes, vec!["v1 (JAR)", "v2"]);
+        // Both schemes carry the same certificate.
+        assert_eq!(info.certificates.len(), 1);
+        let cert = &info.certificates[0];
+        assert_eq!(cert.subject.as_deref(), Some("CN=Kistaverk"));
+        assert_eq!(cert.sha256.len(), 32 * 3 - 1);
+    }
+
+    #[test]
+    fn detects_unsigned_jar_and_ignores_plain_zip() {
+        let build = |name: &str| {
+            let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+            zip.start_file(name, FileOptions::default()).unwrap();
+            zip.write_all(b"Manifest-Version: 1.0\n").unwrap();
+            zip.finish().unwrap().into_inner()
+        };
+        let jar = inspect_package(&mut Cursor::new(build("META-INF/MANIFEST.MF"))).unwrap();
+        assert_eq!(jar.kind, PackageKind::Jar);
+        assert!(jar.schemes.is_empty() && jar.certificates.is_empty());
+        assert!(inspect_package(&mut Cursor::new(build("notes.txt"))).is_none());
+    }
+}
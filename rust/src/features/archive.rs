@@ -1,7 +1,11 @@
+use crate::error::error_text;
 use crate::features::storage::output_dir_for;
 use crate::features::text_viewer::read_text_from_reader;
 use crate::state::AppState;
-use crate::ui::{Button as UiButton, Column as UiColumn, Text as UiText, TextInput as UiTextInput};
+use crate::ui::{
+    Button as UiButton, Column as UiColumn, Table as UiTable, TableColumn as UiTableColumn,
+    Text as UiText, TextInput as UiTextInput,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::fs::{self, File};
@@ -234,7 +238,7 @@ pub fn render_archive_screen(state: &AppState) -> Value {
 
     if let Some(err) = &state.archive.error {
         children.push(to_value_or_text(
-            UiText::new(&format!("{}{}", t!("multi_hash_error_prefix"), err))
+            UiText::new(&error_text(err))
                 .size(14.0)
                 .content_description("archive_error"),
             "archive_error",
@@ -280,7 +284,16 @@ pub fn render_archive_screen(state: &AppState) -> Value {
             .filter_query
             .as_deref()
             .map(|s| s.to_ascii_lowercase());
-        let mut rows = Vec::new();
+        let name_title = t!("file_inspector_batch_name_header");
+        let size_title = t!("file_inspector_batch_size_header");
+        // Capped height keeps the header in view while long listings scroll.
+        let mut table = UiTable::new(vec![
+            UiTableColumn::new(&name_title),
+            UiTableColumn::new(&size_title).align("end").width_dp(80),
+            UiTableColumn::new("").align("end").width_dp(96),
+        ])
+        .max_height_dp(360)
+        .content_description("archive_entry_list");
         for entry in state.archive.entries.iter() {
             if let Some(fq) = &filter {
                 if !entry.name.to_ascii_lowercase().contains(fq) {
@@ -291,38 +304,37 @@ pub fn render_archive_screen(state: &AppState) -> Value {
             let size_str = if entry.is_dir {
                 String::new()
             } else {
-                format!("({})", human_bytes(entry.size))
+                human_bytes(entry.size)
             };
-            let label = format!("{} {} {}", icon, entry.name, size_str);
-            let mut entry_children = Vec::new();
-            if is_text_entry(entry) {
+            let label = format!("{} {}", icon, entry.name);
+            let name_cell = if is_text_entry(entry) {
                 let action = format!("archive_open_text:{}", entry.original_index);
-                entry_children.push(to_value_or_text(
+                to_value_or_text(
                     UiButton::new(&label, &action).content_description("archive_entry_text"),
                     "archive_entry_text",
-                ));
+                )
             } else {
-                entry_children.push(to_value_or_text(
+                to_value_or_text(
                     UiText::new(&label)
                         .size(14.0)
                         .content_description("archive_entry"),
                     "archive_entry_label",
-                ));
-            }
-            entry_children.push(to_value_or_text(
-                UiButton::new(&t!("archive_extract_button"), &format!("archive_extract_entry:{}", entry.original_index))
+                )
+            };
+            table = table.row(vec![
+                name_cell,
+                to_value_or_text(UiText::new(&size_str).size(12.0), "archive_entry_size"),
+                to_value_or_text(
+                    UiButton::new(
+                        &t!("archive_extract_button"),
+                        &format!("archive_extract_entry:{}", entry.original_index),
+                    )
                     .content_description("archive_extract_entry"),
-                "archive_extract_entry",
-            ));
-            rows.push(to_value_or_text(
-                UiColumn::new(entry_children).padding(8),
-                "archive_entry_row",
-            ));
+                    "archive_extract_entry",
+                ),
+            ]);
         }
-        children.push(to_value_or_text(
-            UiColumn::new(rows).padding(8),
-            "archive_entry_list",
-        ));
+        children.push(to_value_or_text(table, "archive_entry_list"));
         if state.archive.truncated {
             children.push(to_value_or_text(
                 UiText::new(&t!("archive_viewer_truncated_message"))
@@ -438,12 +450,19 @@ pub fn read_text_entry(state: &AppState, index: u32) -> Result<(String, String),
     Ok((label, text))
 }
 
-pub fn extract_all(archive_path: &str, dest_root: &Path) -> Result<usize, String> {
+/// Extracts every entry, calling `on_entry(index, total)` before each one.
+pub fn extract_all(
+    archive_path: &str,
+    dest_root: &Path,
+    mut on_entry: impl FnMut(usize, usize),
+) -> Result<usize, String> {
     fs::create_dir_all(dest_root).map_err(|e| format!("create_dest_failed:{e}"))?;
     let file = File::open(archive_path).map_err(|e| format!("archive_reopen_failed:{e}"))?;
     let mut archive = ZipArchive::new(file).map_err(|e| format!("archive_reopen_failed:{e}"))?;
     let mut count = 0;
-    for i in 0..archive.len() {
+    let total = archive.len();
+    for i in 0..total {
+        on_entry(i, total);
         let mut entry = archive
             .by_index(i)
             .map_err(|e| format!("archive_entry_open_failed:{e}"))?;
@@ -551,7 +570,7 @@ mod tests {
         }
 
         let dest = dir.path().join("out");
-        let res = extract_all(zip_path.to_str().unwrap(), &dest);
+        let res = extract_all(zip_path.to_str().unwrap(), &dest, |_, _| {});
         assert!(res.is_err());
         assert!(!dest.join("evil.txt").exists());
     }
@@ -578,6 +597,31 @@ mod tests {
         assert!(names.contains(&"root/sub/b.txt".to_string()));
     }
 
+    #[test]
+    fn extract_all_reports_each_entry() {
+        let dir = tempdir().unwrap();
+        let zip_path = dir.path().join("three.zip");
+        {
+            let file = File::create(&zip_path).unwrap();
+            let mut writer = zip::ZipWriter::new(file);
+            for name in ["a.txt", "b.txt", "c.txt"] {
+                writer.start_file(name, FileOptions::default()).unwrap();
+                writer.write_all(name.as_bytes()).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let dest = dir.path().join("out");
+        let count = extract_all(zip_path.to_str().unwrap(), &dest, |i, total| {
+            seen.push((i, total))
+        })
+        .expect("extracted");
+        assert_eq!(count, 3);
+        assert_eq!(seen, vec![(0, 3), (1, 3), (2, 3)]);
+        assert_eq!(fs::read(dest.join("c.txt")).unwrap(), b"c.txt");
+    }
+
     #[test]
     fn create_archive_from_single_file_uses_flat_name() {
         let dir = tempdir().unwrap();
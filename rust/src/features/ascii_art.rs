@@ -0,0 +1,348 @@
+use crate::error::error_text;
+use crate::features::image_resize::open_oriented;
+use crate::state::AppState;
+use crate::ui::{
+    maybe_push_back, Button as UiButton, Column as UiColumn, Grid as UiGrid, Slider as UiSlider,
+    Text as UiText,
+};
+use image::imageops::FilterType;
+use image::DynamicImage;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write;
+use tempfile::Builder;
+
+pub const MIN_COLUMNS: u32 = 20;
+pub const MAX_COLUMNS: u32 = 200;
+pub const DEFAULT_COLUMNS: u32 = 80;
+/// Densest first; plain text assumes dark characters on a light page.
+const RAMP: &[u8] = b"@%#*+=-:. ";
+/// Character cells are roughly twice as tall as they are wide.
+const CELL_ASPECT: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AsciiMode {
+    Ascii,
+    /// 24-bit colour escapes for terminals, with bright pixels drawn densest.
+    Ansi,
+}
+
+impl AsciiMode {
+    pub fn key(self) -> &'static str {
+        match self {
+            AsciiMode::Ascii => "ascii",
+            AsciiMode::Ansi => "ansi",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "ascii" => Some(AsciiMode::Ascii),
+            "ansi" => Some(AsciiMode::Ansi),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsciiArt {
+    pub columns: u32,
+    pub rows: u32,
+    /// Uncoloured characters, shown in the text viewer.
+    pub plain: String,
+    pub ansi: String,
+}
+
+impl AsciiArt {
+    pub fn text(&self, mode: AsciiMode) -> &str {
+        match mode {
+            AsciiMode::Ascii => &self.plain,
+            AsciiMode::Ansi => &self.ansi,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AsciiArtState {
+    pub source_name: Option<String>,
+    pub source_path: Option<String>,
+    pub output_dir: Option<String>,
+    pub mode: AsciiMode,
+    pub columns: u32,
+    /// Regenerated from the source on demand; kept out of snapshots.
+    #[serde(skip)]
+    pub art: Option<AsciiArt>,
+    pub saved_path: Option<String>,
+    pub error: Option<String>,
+}
+
+impl AsciiArtState {
+    pub const fn new() -> Self {
+        Self {
+            source_name: None,
+            source_path: None,
+            output_dir: None,
+            mode: AsciiMode::Ascii,
+            columns: DEFAULT_COLUMNS,
+            art: None,
+            saved_path: None,
+            error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Applies the mode and width bindings; returns true when the art must be regenerated.
+    pub fn sync_bindings(&mut self, bindings: &HashMap<String, String>) -> bool {
+        if let Some(mode) = bindings
+            .get("ascii_art_mode")
+            .and_then(|key| AsciiMode::from_key(key))
+        {
+            self.mode = mode;
+        }
+        let columns = bindings
+            .get("ascii_art_columns")
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .map_or(self.columns, |v| v.clamp(MIN_COLUMNS, MAX_COLUMNS));
+        let changed = columns != self.columns;
+        self.columns = columns;
+        changed
+    }
+
+    /// File name stem for exports and the text viewer title.
+    pub fn stem(&self) -> String {
+        self.source_name
+            .as_deref()
+            .map(|name| name.rsplit_once('.').map_or(name, |(stem, _)| stem))
+            .filter(|stem| !stem.is_empty())
+            .unwrap_or("image")
+            .to_string()
+    }
+}
+
+/// Maps each cell to a ramp character by Rec. 709 luminance; transparency reads as white.
+pub fn convert_image(image: &DynamicImage, columns: u32) -> Result<AsciiArt, String> {
+    let (width, height) = (image.width(), image.height());
+    if width == 0 || height == 0 {
+        return Err("ascii_art_empty_image".into());
+    }
+    let columns = columns.clamp(MIN_COLUMNS, MAX_COLUMNS);
+    let rows = ((height as f32 / width as f32) * columns as f32 * CELL_ASPECT)
+        .round()
+        .max(1.0) as u32;
+    let cells = image
+        .resize_exact(columns, rows, FilterType::Triangle)
+        .to_rgba8();
+
+    let last = RAMP.len() - 1;
+    let mut plain = String::with_capacity(((columns + 1) * rows) as usize);
+    let mut ansi = String::new();
+    for row in cells.rows() {
+        let mut current = None;
+        for px in row {
+            let [r, g, b, a] = px.0;
+            let over_white = |c: u8| (c as u32 * a as u32 + 255 * (255 - a as u32)) / 255;
+            let (r, g, b) = (over_white(r), over_white(g), over_white(b));
+            let luma = (2126 * r + 7152 * g + 722 * b) / 10_000;
+            let level = luma as usize * last / 255;
+            plain.push(RAMP[level] as char);
+            if current != Some((r, g, b)) {
+                let _ = write!(ansi, "\x1b[38;2;{r};{g};{b}m");
+                current = Some((r, g, b));
+            }
+            ansi.push(RAMP[last - level] as char);
+        }
+        plain.push('\n');
+        ansi.push_str("\x1b[0m\n");
+    }
+    Ok(AsciiArt {
+        columns,
+        rows,
+        plain,
+        ansi,
+    })
+}
+
+pub fn convert_image_file(path: &str, columns: u32) -> Result<AsciiArt, String> {
+    convert_image(&open_oriented(path)?, columns)
+}
+
+/// Writes the art as `{stem}_{mode}_*.txt` into `output_dir`.
+pub fn export_art(
+    art: &AsciiArt,
+    mode: AsciiMode,
+    stem: &str,
+    output_dir: &str,
+) -> Result<String, String> {
+    fs::create_dir_all(output_dir).map_err(|e| format!("output_dir_create_failed:{e}"))?;
+    let mut tmp = Builder::new()
+        .prefix(&format!("{stem}_{}_", mode.key()))
+        .suffix(".txt")
+        .tempfile_in(output_dir)
+        .map_err(|e| format!("tempfile_failed:{e}"))?;
+    tmp.write_all(art.text(mode).as_bytes())
+        .map_err(|e| format!("save_failed:{e}"))?;
+    let path = tmp.into_temp_path();
+    let final_path = path
+        .to_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "path_utf8".to_string())?;
+    path.keep().map_err(|e| format!("persist_failed:{e}"))?;
+    Ok(final_path)
+}
+
+fn text(value: &str, size: f64) -> Value {
+    serde_json::to_value(UiText::new(value).size(size)).unwrap()
+}
+
+pub fn render_ascii_art_screen(state: &AppState) -> Value {
+    let ascii = &state.ascii_art;
+    let mut children = vec![
+        text(&t!("ascii_art_title"), 20.0),
+        text(&t!("ascii_art_description"), 14.0),
+        serde_json::to_value(
+            UiButton::new(&t!("ascii_art_pick_button"), "ascii_art_pick")
+                .requires_file_picker(true)
+                .id("ascii_art_pick"),
+        )
+        .unwrap(),
+    ];
+
+    if let Some(name) = &ascii.source_name {
+        children.push(text(
+            &format!("{}{name}", t!("ascii_art_source_prefix")),
+            12.0,
+        ));
+    }
+
+    let mode_buttons = [
+        (AsciiMode::Ascii, t!("ascii_art_mode_ascii")),
+        (AsciiMode::Ansi, t!("ascii_art_mode_ansi")),
+    ]
+    .into_iter()
+    .map(|(mode, label)| {
+        let id = format!("ascii_art_mode_{}", mode.key());
+        let mut button = UiButton::new(&label, "ascii_art_sync")
+            .payload(json!({ "ascii_art_mode": mode.key() }))
+            .id(&id);
+        if mode == ascii.mode {
+            button = button.content_description("selected");
+        }
+        serde_json::to_value(button).unwrap()
+    })
+    .collect();
+    children.push(serde_json::to_value(UiGrid::new(mode_buttons).columns(2)).unwrap());
+
+    children.push(text(
+        &format!("{}{}", t!("ascii_art_columns_prefix"), ascii.columns),
+        14.0,
+    ));
+    children.push(
+        serde_json::to_value(
+            UiSlider::new("ascii_art_columns", MIN_COLUMNS as i32, MAX_COLUMNS as i32)
+                .value(ascii.columns as i32)
+                .action("ascii_art_sync")
+                .content_description("ascii_art_columns"),
+        )
+        .unwrap(),
+    );
+
+    if let Some(err) = &ascii.error {
+        children.push(text(&error_text(err), 12.0));
+    }
+
+    if let Some(art) = &ascii.art {
+        children.push(text(
+            &format!(
+                "{}{}×{}",
+                t!("ascii_art_size_prefix"),
+                art.columns,
+                art.rows
+            ),
+            12.0,
+        ));
+        children.push(
+            serde_json::to_value(
+                UiGrid::new(vec![
+                    serde_json::to_value(
+                        UiButton::new(&t!("ascii_art_view_button"), "ascii_art_view")
+                            .id("ascii_art_view"),
+                    )
+                    .unwrap(),
+                    serde_json::to_value(
+                        UiButton::new(&t!("ascii_art_export_button"), "ascii_art_export")
+                            .id("ascii_art_export"),
+                    )
+                    .unwrap(),
+                ])
+                .columns(2),
+            )
+            .unwrap(),
+        );
+    }
+
+    if let Some(path) = &ascii.saved_path {
+        children.push(text(
+            &format!("{}{path}", t!("ascii_art_saved_prefix")),
+            12.0,
+        ));
+        children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("dithering_copy_result_path_button"), "copy_clipboard")
+                    .copy_text(path),
+            )
+            .unwrap(),
+        );
+    }
+
+    maybe_push_back(&mut children, state);
+    serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn converts_luminance_to_ramp_and_keeps_aspect() {
+        // Black left half, white right half, transparent bottom half.
+        let img = RgbaImage::from_fn(80, 80, |x, y| match (x, y) {
+            (_, 40..) => Rgba([0, 0, 0, 0]),
+            (0..=39, _) => Rgba([0, 0, 0, 255]),
+            _ => Rgba([255, 255, 255, 255]),
+        });
+        let art = convert_image(&DynamicImage::ImageRgba8(img), 20).unwrap();
+        assert_eq!((art.columns, art.rows), (20, 10));
+        let lines: Vec<&str> = art.plain.lines().collect();
+        assert_eq!(lines.len(), 10);
+        assert!(lines[0].starts_with("@@@@"));
+        assert!(lines[0].ends_with("    "));
+        assert_eq!(lines[9], " ".repeat(20));
+
+        assert!(art.ansi.starts_with("\x1b[38;2;0;0;0m    "));
+        assert!(art.ansi.contains("\x1b[38;2;255;255;255m@@@@"));
+        assert_eq!(art.ansi.matches("\x1b[0m\n").count(), 10);
+    }
+
+    #[test]
+    fn bindings_clamp_width_and_report_changes() {
+        let mut state = AsciiArtState::new();
+        state.source_name = Some("cat.photo.jpg".into());
+        assert_eq!(state.stem(), "cat.photo");
+        assert!(!state.sync_bindings(&HashMap::from([("ascii_art_mode".into(), "ansi".into())])));
+        assert_eq!(state.mode, AsciiMode::Ansi);
+        assert!(state.sync_bindings(&HashMap::from([("ascii_art_columns".into(), "999".into())])));
+        assert_eq!(state.columns, MAX_COLUMNS);
+        assert!(!state.sync_bindings(&HashMap::from([(
+            "ascii_art_columns".into(),
+            "wide".into()
+        )])));
+    }
+}
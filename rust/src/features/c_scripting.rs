@@ -1,17 +1,36 @@
+use crate::error::error_text;
 use crate::state::AppState;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::ffi::{CStr, CString};
 use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering};
 use std::sync::mpsc;
 use std::thread;
 use std::time::{Duration, Instant};
-use std::io::Read;
+use std::io::{Read, Write};
 use std::os::unix::io::FromRawFd;
 use libc::{self, c_int, c_void, c_char};
 use mir_sys::*;
+use rust_i18n::t;
 use uuid::Uuid;
 
+/// Wall-clock budget of one run; benchmarks get [`BENCHMARK_TIME_BUDGET_MS`].
+pub const TIME_BUDGET_MS: u64 = 10_000;
+pub const BENCHMARK_TIME_BUDGET_MS: u64 = 30_000;
+/// Heap a script, or one plugin call, may hold at once through `malloc`/`calloc`/`realloc`.
+pub const MEMORY_BUDGET_BYTES: usize = 64 * 1024 * 1024;
+/// Largest source file `c_scripting_open` loads.
+pub const MAX_SOURCE_BYTES: usize = 256 * 1024;
+
+/// Set while a script runs; new runs are refused meanwhile.
+static SCRIPT_RUNNING: AtomicBool = AtomicBool::new(false);
+/// Bytes currently allocated by the running script.
+static SCRIPT_HEAP_USED: AtomicUsize = AtomicUsize::new(0);
+/// Write end of the reply pipe, in a [`run_forked`] child.
+static REPLY_FD: AtomicI32 = AtomicI32::new(-1);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CScriptingState {
     pub source: String,
@@ -44,6 +63,26 @@ impl CScriptingState {
         }
     }
 
+    /// Replaces the editor contents with a source read from a picked file.
+    pub fn load_source(&mut self, content: Result<String, String>) {
+        match content.and_then(|text| {
+            if text.len() > MAX_SOURCE_BYTES {
+                Err("c_script_too_large".to_string())
+            } else {
+                Ok(text)
+            }
+        }) {
+            Ok(text) => {
+                self.source = text;
+                self.output.clear();
+                self.error = None;
+                self.compilation_time_us = None;
+                self.avg_execution_time_us = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
     pub fn execute(&mut self, timeout_ms: u64) {
         self.output.clear();
         self.error = None;
@@ -52,55 +91,33 @@ impl CScriptingState {
         self.avg_execution_time_us = None;
         self.execution_count = 0;
 
-        let source = self.source.clone();
-        let args = self.args.clone();
-        let use_jit = self.use_jit;
-        let benchmark = self.benchmark;
-        let (tx, rx) = mpsc::channel();
-
-        // Spawn a thread to run the C execution
-        thread::spawn(move || {
-            let result = execute_c_code(source, args, use_jit, benchmark);
-            let _ = tx.send(result);
-        });
-
-        // Wait for result with timeout
-        // Increase timeout for benchmarks
-        let effective_timeout = if benchmark { 30_000 } else { timeout_ms };
-        
-        match rx.recv_timeout(Duration::from_millis(effective_timeout)) {
-            Ok(result) => {
-                match result {
-                    Ok(exec_res) => {
-                        self.output = exec_res.output;
-                        if benchmark {
-                            self.compilation_time_us = Some(exec_res.compilation_time_us);
-                            self.avg_execution_time_us = Some(exec_res.avg_execution_time_us);
-                            self.execution_count = exec_res.runs;
-                        }
-                    }
-                    Err(err) => {
-                        self.error = Some(err);
-                    }
+        match execute_with_budget(
+            self.source.clone(),
+            self.args.clone(),
+            self.use_jit,
+            self.benchmark,
+            timeout_ms,
+        ) {
+            Ok(exec_res) => {
+                self.output = exec_res.output;
+                if self.benchmark {
+                    self.compilation_time_us = Some(exec_res.compilation_time_us);
+                    self.avg_execution_time_us = Some(exec_res.avg_execution_time_us);
+                    self.execution_count = exec_res.runs;
                 }
             }
-            Err(mpsc::RecvTimeoutError::Timeout) => {
-                self.error = Some("Error. 10 seconds timeout over".to_string());
-            }
-            Err(mpsc::RecvTimeoutError::Disconnected) => {
-                self.error = Some("Worker thread disconnected unexpectedly".to_string());
-            }
+            Err(err) => self.error = Some(err),
         }
         self.is_running = false;
     }
 }
 
-struct StringReader {
-    data: Vec<u8>,
-    cursor: usize,
+pub(crate) struct StringReader {
+    pub(crate) data: Vec<u8>,
+    pub(crate) cursor: usize,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionResult {
     pub output: String,
     pub compilation_time_us: u128,
@@ -108,7 +125,7 @@ pub struct ExecutionResult {
     pub runs: u32,
 }
 
-unsafe extern "C" fn getc_func(data: *mut c_void) -> c_int {
+pub(crate) unsafe extern "C" fn getc_func(data: *mut c_void) -> c_int {
     let reader = &mut *(data as *mut StringReader);
     if reader.cursor < reader.data.len() {
         let byte = reader.data[reader.cursor];
@@ -136,6 +153,173 @@ unsafe extern "C" fn free_rust_string(s: *mut c_char) {
     }
 }
 
+/// Each script allocation is prefixed with its size, so `free` can give it back to the
+/// budget; 16 bytes keep the returned pointer aligned like `malloc`'s.
+const ALLOC_HEADER: usize = 16;
+
+/// `malloc` for scripts: NULL once [`MEMORY_BUDGET_BYTES`] would be exceeded.
+pub(crate) unsafe extern "C" fn script_malloc(size: usize) -> *mut c_void {
+    let reserved = SCRIPT_HEAP_USED.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+        used.checked_add(size)
+            .filter(|total| *total <= MEMORY_BUDGET_BYTES)
+    });
+    if reserved.is_err() {
+        return ptr::null_mut();
+    }
+    let base = libc::malloc(size + ALLOC_HEADER) as *mut usize;
+    if base.is_null() {
+        SCRIPT_HEAP_USED.fetch_sub(size, Ordering::SeqCst);
+        return ptr::null_mut();
+    }
+    base.write(size);
+    (base as *mut u8).add(ALLOC_HEADER) as *mut c_void
+}
+
+pub(crate) unsafe extern "C" fn script_free(p: *mut c_void) {
+    if p.is_null() {
+        return;
+    }
+    let base = (p as *mut u8).sub(ALLOC_HEADER) as *mut usize;
+    SCRIPT_HEAP_USED.fetch_sub(base.read(), Ordering::SeqCst);
+    libc::free(base as *mut c_void);
+}
+
+pub(crate) unsafe extern "C" fn script_calloc(count: usize, size: usize) -> *mut c_void {
+    let Some(total) = count.checked_mul(size) else {
+        return ptr::null_mut();
+    };
+    let p = script_malloc(total);
+    if !p.is_null() {
+        ptr::write_bytes(p as *mut u8, 0, total);
+    }
+    p
+}
+
+/// Only the growth counts against the budget, since the old block is given up.
+pub(crate) unsafe extern "C" fn script_realloc(p: *mut c_void, size: usize) -> *mut c_void {
+    if p.is_null() {
+        return script_malloc(size);
+    }
+    let base = (p as *mut u8).sub(ALLOC_HEADER) as *mut usize;
+    let old = base.read();
+    let growth = size.saturating_sub(old);
+    let reserved = SCRIPT_HEAP_USED.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+        used.checked_add(growth)
+            .filter(|total| *total <= MEMORY_BUDGET_BYTES)
+    });
+    if reserved.is_err() {
+        return ptr::null_mut();
+    }
+    let fresh = libc::realloc(base as *mut c_void, size + ALLOC_HEADER) as *mut usize;
+    if fresh.is_null() {
+        SCRIPT_HEAP_USED.fetch_sub(growth, Ordering::SeqCst);
+        return ptr::null_mut();
+    }
+    SCRIPT_HEAP_USED.fetch_sub(old.saturating_sub(size), Ordering::SeqCst);
+    fresh.write(size);
+    (fresh as *mut u8).add(ALLOC_HEADER) as *mut c_void
+}
+
+/// Runs [`execute_c_code`] in a forked child process and kills it after `timeout_ms`, or
+/// [`BENCHMARK_TIME_BUDGET_MS`] for benchmarks. Only one script runs at a time.
+pub fn execute_with_budget(
+    source_code: String,
+    args_str: String,
+    use_jit: bool,
+    benchmark: bool,
+    timeout_ms: u64,
+) -> Result<ExecutionResult, String> {
+    if SCRIPT_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("c_script_busy".into());
+    }
+    let budget = if benchmark {
+        BENCHMARK_TIME_BUDGET_MS
+    } else {
+        timeout_ms
+    };
+    let result = unsafe {
+        run_forked(budget, || {
+            execute_c_code(source_code, args_str, use_jit, benchmark)
+        })
+    };
+    SCRIPT_RUNNING.store(false, Ordering::SeqCst);
+    match result {
+        Ok(result) => result,
+        Err(ForkFailure::TimedOut) => Err(format!("c_script_timeout:{} s", budget / 1000)),
+        Err(ForkFailure::Crashed) => Err("c_script_crashed".into()),
+        Err(ForkFailure::SpawnFailed) => Err("c_script_spawn_failed".into()),
+    }
+}
+
+/// Why [`run_forked`] has no value to return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ForkFailure {
+    SpawnFailed,
+    /// The child died without answering, e.g. on a bad pointer.
+    Crashed,
+    TimedOut,
+}
+
+/// Runs `work` in a forked child process with an empty script heap and returns its value,
+/// sent back as JSON over a pipe. Past `budget_ms` the child is killed with `SIGKILL`, so
+/// native code that loops forever or crashes only takes the child down.
+pub(crate) unsafe fn run_forked<T, F>(budget_ms: u64, work: F) -> Result<T, ForkFailure>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> T,
+{
+    let mut pipe_fds: [c_int; 2] = [0; 2];
+    if libc::pipe(pipe_fds.as_mut_ptr()) < 0 {
+        return Err(ForkFailure::SpawnFailed);
+    }
+    let (read_fd, write_fd) = (pipe_fds[0], pipe_fds[1]);
+    let pid = libc::fork();
+    if pid < 0 {
+        libc::close(read_fd);
+        libc::close(write_fd);
+        return Err(ForkFailure::SpawnFailed);
+    }
+    if pid == 0 {
+        libc::close(read_fd);
+        SCRIPT_HEAP_USED.store(0, Ordering::SeqCst);
+        REPLY_FD.store(write_fd, Ordering::SeqCst);
+        // Never unwind into the parent's frames from here; a panic answers nothing.
+        if let Ok(value) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(work)) {
+            answer_and_exit(&value);
+        }
+        libc::_exit(0);
+    }
+
+    libc::close(write_fd);
+    let mut reader = std::fs::File::from_raw_fd(read_fd);
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut reply = Vec::new();
+        let _ = reader.read_to_end(&mut reply);
+        let _ = tx.send(reply);
+    });
+    let reply = rx.recv_timeout(Duration::from_millis(budget_ms));
+    if reply.is_err() {
+        libc::kill(pid, libc::SIGKILL);
+    }
+    libc::waitpid(pid, ptr::null_mut(), 0);
+    match reply {
+        Ok(reply) => serde_json::from_slice(&reply).map_err(|_| ForkFailure::Crashed),
+        Err(_) => Err(ForkFailure::TimedOut),
+    }
+}
+
+/// Sends `value` as the answer of the [`run_forked`] child this runs in, then ends the
+/// child. Lets callbacks that must not return, like MIR's error handler, still answer.
+pub(crate) unsafe fn answer_and_exit<T: Serialize>(value: &T) -> ! {
+    let fd = REPLY_FD.load(Ordering::SeqCst);
+    if fd >= 0 {
+        let mut out = std::fs::File::from_raw_fd(fd);
+        let _ = out.write_all(&serde_json::to_vec(value).unwrap_or_default());
+    }
+    libc::_exit(0);
+}
+
 pub fn execute_c_code(source_code: String, args_str: String, use_jit: bool, benchmark: bool) -> Result<ExecutionResult, String> {
     unsafe {
         // 1. Setup output capturing (pipe)
@@ -164,6 +348,7 @@ pub fn execute_c_code(source_code: String, args_str: String, use_jit: bool, benc
         
         // Measure Compilation Start
         let compile_start = Instant::now();
+        SCRIPT_HEAP_USED.store(0, Ordering::SeqCst);
 
         // 2. Initialize MIR
         let ctx = _MIR_init(ptr::null_mut(), ptr::null_mut());
@@ -185,8 +370,11 @@ pub fn execute_c_code(source_code: String, args_str: String, use_jit: bool, benc
         // 3. Register standard functions
         MIR_load_external(ctx, CString::new("printf").unwrap().as_ptr(), libc::printf as *mut c_void);
         MIR_load_external(ctx, CString::new("puts").unwrap().as_ptr(), libc::puts as *mut c_void);
-        MIR_load_external(ctx, CString::new("malloc").unwrap().as_ptr(), libc::malloc as *mut c_void);
-        MIR_load_external(ctx, CString::new("free").unwrap().as_ptr(), libc::free as *mut c_void);
+        // Scripts allocate from a budgeted heap.
+        MIR_load_external(ctx, CString::new("malloc").unwrap().as_ptr(), script_malloc as *mut c_void);
+        MIR_load_external(ctx, CString::new("calloc").unwrap().as_ptr(), script_calloc as *mut c_void);
+        MIR_load_external(ctx, CString::new("realloc").unwrap().as_ptr(), script_realloc as *mut c_void);
+        MIR_load_external(ctx, CString::new("free").unwrap().as_ptr(), script_free as *mut c_void);
         MIR_load_external(ctx, CString::new("strtol").unwrap().as_ptr(), libc::strtol as *mut c_void);
         MIR_load_external(ctx, CString::new("atoi").unwrap().as_ptr(), libc::atoi as *mut c_void);
         MIR_load_external(ctx, CString::new("fflush").unwrap().as_ptr(), libc::fflush as *mut c_void);
@@ -335,7 +523,7 @@ pub fn execute_c_code(source_code: String, args_str: String, use_jit: bool, benc
                 avg_execution_time_us,
                 runs,
             }),
-            Err(e) => Err(format!("Error: {}\nOutput so far:\n{}", e, output)),
+            Err(e) => Err(format!("{}\nOutput so far:\n{}", e, output)),
         }
     }
 }
@@ -347,7 +535,7 @@ pub fn render_c_scripting_screen(state: &AppState) -> serde_json::Value {
 
     components.push(json!({
         "type": "Text",
-        "text": "C Scripting Lab",
+        "text": t!("c_scripting_title"),
         "size": 24.0,
         "bold": true,
         "margin_bottom": 16.0
@@ -365,7 +553,7 @@ pub fn render_c_scripting_screen(state: &AppState) -> serde_json::Value {
 
     components.push(json!({
         "type": "Text",
-        "text": "Program Arguments (space separated):",
+        "text": t!("c_scripting_args_label"),
         "size": 14.0,
         "margin_bottom": 6.0
     }));
@@ -374,7 +562,7 @@ pub fn render_c_scripting_screen(state: &AppState) -> serde_json::Value {
         "type": "TextInput",
         "bind_key": "c_scripting.args",
         "text": cs.args,
-        "hint": "e.g. 2025 12 25",
+        "hint": t!("c_scripting_args_hint"),
         "single_line": true,
         "max_lines": 1,
         "margin_bottom": 12.0
@@ -385,7 +573,7 @@ pub fn render_c_scripting_screen(state: &AppState) -> serde_json::Value {
         "children": [
             {
                 "type": "Checkbox",
-                "text": "Use JIT (Faster)",
+                "text": t!("c_scripting_use_jit"),
                 "bind_key": "c_scripting_use_jit",
                 "checked": cs.use_jit,
                 "action": "c_scripting_toggle_jit",
@@ -393,14 +581,14 @@ pub fn render_c_scripting_screen(state: &AppState) -> serde_json::Value {
             },
             {
                 "type": "Checkbox",
-                "text": "Benchmark",
+                "text": t!("c_scripting_benchmark"),
                 "bind_key": "c_scripting_benchmark",
                 "checked": cs.benchmark,
                 "action": "c_scripting_toggle_benchmark"
             },
             {
                 "type": "Checkbox",
-                "text": "Run in background",
+                "text": t!("c_scripting_run_in_background"),
                 "bind_key": "c_scripting_run_in_thread",
                 "checked": cs.run_in_thread,
                 "action": "c_scripting_toggle_thread"
@@ -414,23 +602,47 @@ pub fn render_c_scripting_screen(state: &AppState) -> serde_json::Value {
         "children": [
             {
                 "type": "Button",
-                "text": if cs.benchmark { "Run Benchmark" } else { "Run (10s limit)" },
+                "text": if cs.benchmark {
+                    t!("c_scripting_run_benchmark_button")
+                } else {
+                    t!("c_scripting_run_button", seconds = TIME_BUDGET_MS / 1000)
+                },
                 "action": "c_scripting_execute",
                 "margin_right": 8.0,
                 "disabled": cs.is_running
             },
             {
                 "type": "Button",
-                "text": "Load Example",
+                "text": t!("c_scripting_load_example_button"),
                 "action": "c_scripting_load_example",
+                "margin_right": 8.0,
+                "disabled": cs.is_running
+            },
+            {
+                "type": "Button",
+                "text": t!("c_scripting_open_file"),
+                "action": "c_scripting_open",
+                "requires_file_picker": true,
                 "disabled": cs.is_running
             }
         ]
     }));
+
+    components.push(json!({
+        "type": "Text",
+        "text": t!(
+            "c_scripting_limits",
+            run = TIME_BUDGET_MS / 1000,
+            benchmark = BENCHMARK_TIME_BUDGET_MS / 1000,
+            heap = MEMORY_BUDGET_BYTES / (1024 * 1024)
+        ),
+        "size": 12.0,
+        "content_description": "c_scripting_limits"
+    }));
     
     components.push(json!({
         "type": "Button",
-        "text": "Clear Output",
+        "text": t!("c_scripting_clear_output_button"),
         "action": "c_scripting_clear",
         "margin_top": 8.0,
         "margin_bottom": 16.0
@@ -439,17 +651,24 @@ pub fn render_c_scripting_screen(state: &AppState) -> serde_json::Value {
     if cs.benchmark && cs.compilation_time_us.is_some() {
         components.push(json!({
             "type": "Card",
-            "title": "Benchmark Results",
+            "title": t!("c_scripting_benchmark_results"),
             "children": [
                 {
                     "type": "Text",
-                    "text": format!("Compilation: {:.3} ms", cs.compilation_time_us.unwrap() as f64 / 1000.0),
+                    "text": t!(
+                        "c_scripting_compilation_time",
+                        ms = format!("{:.3}", cs.compilation_time_us.unwrap() as f64 / 1000.0)
+                    ),
                     "size": 14.0,
                     "margin_bottom": 4.0
                 },
                 {
                     "type": "Text",
-                    "text": format!("Avg Execution: {:.3} ms ({} runs)", cs.avg_execution_time_us.unwrap() as f64 / 1000.0, cs.execution_count),
+                    "text": t!(
+                        "c_scripting_avg_execution_time",
+                        ms = format!("{:.3}", cs.avg_execution_time_us.unwrap() as f64 / 1000.0),
+                        runs = cs.execution_count
+                    ),
                     "size": 14.0,
                     "margin_bottom": 4.0
                 }
@@ -460,18 +679,18 @@ pub fn render_c_scripting_screen(state: &AppState) -> serde_json::Value {
 
     components.push(json!({
         "type": "Text",
-        "text": "Output:",
+        "text": t!("c_scripting_output_label"),
         "size": 18.0,
         "bold": true,
         "margin_bottom": 8.0
     }));
 
     let output_text = if cs.is_running {
-        "Running...".to_string()
+        t!("c_scripting_running").into_owned()
     } else if let Some(error) = &cs.error {
-        format!("Error: {}", error)
+        error_text(error)
     } else if cs.output.is_empty() {
-        "Ready.".to_string()
+        t!("c_scripting_ready").into_owned()
     } else {
         cs.output.clone()
     };
@@ -507,7 +726,7 @@ pub fn handle_c_scripting_actions(
             if let Some(a) = args {
                 state.c_scripting.args = a;
             }
-            state.c_scripting.execute(10_000); // 10s default timeout
+            state.c_scripting.execute(TIME_BUDGET_MS);
             Some(render_c_scripting_screen(state))
         }
         CScriptingClear => {
@@ -637,5 +856,53 @@ mod tests {
             Err(_) => panic!("Test timed out after 5 seconds"),
         }
     }
+
+    #[test]
+    fn script_heap_is_capped_and_released() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        SCRIPT_HEAP_USED.store(0, Ordering::SeqCst);
+
+        unsafe {
+            assert!(script_malloc(MEMORY_BUDGET_BYTES + 1).is_null());
+            let p = script_calloc(4, 256);
+            assert!(!p.is_null());
+            assert_eq!(SCRIPT_HEAP_USED.load(Ordering::SeqCst), 1024);
+            let p = script_realloc(p, 4096);
+            assert!(!p.is_null());
+            assert_eq!(SCRIPT_HEAP_USED.load(Ordering::SeqCst), 4096);
+            // Growing to the whole budget fits once the old block is released.
+            let p = script_realloc(p, MEMORY_BUDGET_BYTES);
+            assert!(!p.is_null());
+            assert_eq!(SCRIPT_HEAP_USED.load(Ordering::SeqCst), MEMORY_BUDGET_BYTES);
+            let p = script_realloc(p, 16);
+            assert_eq!(SCRIPT_HEAP_USED.load(Ordering::SeqCst), 16);
+            script_free(p);
+        }
+        assert_eq!(SCRIPT_HEAP_USED.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn runaway_scripts_are_killed_at_the_budget() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        let source = "int main() { for (;;) {} return 0; }".to_string();
+        let started = Instant::now();
+        let result = execute_with_budget(source, String::new(), false, false, 1_000);
+        assert_eq!(result.unwrap_err(), "c_script_timeout:1 s");
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(!SCRIPT_RUNNING.load(Ordering::SeqCst));
+
+        let source = r#"int main() { printf("after\n"); return 0; }"#.to_string();
+        let result = execute_with_budget(source, String::new(), false, false, 5_000);
+        assert!(result.unwrap().output.contains("after"));
+    }
+
+    #[test]
+    fn oversized_sources_are_refused() {
+        let mut cs = CScriptingState::new();
+        cs.source = "int main() { return 0; }".into();
+        cs.load_source(Ok("x".repeat(MAX_SOURCE_BYTES + 1)));
+        assert_eq!(cs.error.as_deref(), Some("c_script_too_large"));
+        assert_eq!(cs.source, "int main() { return 0; }");
+    }
 }
 
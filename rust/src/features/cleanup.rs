@@ -0,0 +1,667 @@
+use crate::features::storage::preferred_temp_dir;
+use crate::state::AppState;
+use crate::ui::{
+    format_bytes, maybe_push_back, Button as UiButton, Checkbox as UiCheckbox, Column as UiColumn,
+    Section as UiSection, Text as UiText, TextInput as UiTextInput,
+};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_TEMP_MAX_AGE_DAYS: u32 = 7;
+const OUTPUT_REGISTRY_MAX: usize = 200;
+const DAY_SECS: u64 = 86_400;
+/// The retention policy is set once, so its section starts folded away.
+const POLICY_SECTION_KEY: &str = "cleanup_policy";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GeneratedKind {
+    /// Lives in the app temp dir: picker copies, previews, intermediate files.
+    Temp,
+    /// Written next to the source or into Downloads by a tool and recorded via `record_output`.
+    Output,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeneratedFile {
+    pub path: String,
+    pub name: String,
+    pub kind: GeneratedKind,
+    pub is_dir: bool,
+    pub size_bytes: u64,
+    pub modified_at: u64,
+}
+
+impl GeneratedFile {
+    pub fn age_secs(&self, now: u64) -> u64 {
+        now.saturating_sub(self.modified_at)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CleanupPolicy {
+    /// Delete stale temp entries once per app start.
+    pub auto_clean: bool,
+    pub temp_max_age_days: u32,
+}
+
+impl Default for CleanupPolicy {
+    fn default() -> Self {
+        Self {
+            auto_clean: false,
+            temp_max_age_days: DEFAULT_TEMP_MAX_AGE_DAYS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeleteSummary {
+    pub deleted: usize,
+    pub freed_bytes: u64,
+    pub failed: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CleanupState {
+    pub entries: Vec<GeneratedFile>,
+    /// Selected entry paths; paths rather than indices so a rescan keeps the selection.
+    pub selected: Vec<String>,
+    pub policy: CleanupPolicy,
+    pub status: Option<String>,
+    pub error: Option<String>,
+}
+
+impl CleanupState {
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            selected: Vec::new(),
+            policy: CleanupPolicy {
+                auto_clean: false,
+                temp_max_age_days: DEFAULT_TEMP_MAX_AGE_DAYS,
+            },
+            status: None,
+            error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.entries.clear();
+        self.selected.clear();
+        self.status = None;
+        self.error = None;
+    }
+
+    pub fn is_selected(&self, path: &str) -> bool {
+        self.selected.iter().any(|p| p == path)
+    }
+
+    pub fn set_selected(&mut self, path: &str, selected: bool) {
+        self.selected.retain(|p| p != path);
+        if selected {
+            self.selected.push(path.to_string());
+        }
+    }
+
+    /// Rescans disk and drops selections for entries that disappeared.
+    pub fn refresh(&mut self) {
+        self.entries = scan_generated_files();
+        let entries = &self.entries;
+        self.selected
+            .retain(|p| entries.iter().any(|e| &e.path == p));
+    }
+
+    pub fn select_stale(&mut self, now: u64) {
+        let max_age = u64::from(self.policy.temp_max_age_days) * DAY_SECS;
+        self.selected = self
+            .entries
+            .iter()
+            .filter(|e| e.kind == GeneratedKind::Temp && e.age_secs(now) >= max_age)
+            .map(|e| e.path.clone())
+            .collect();
+    }
+}
+
+impl Default for CleanupState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn app_root_dir() -> PathBuf {
+    // Same place as presets and recent files, so clearing the cache keeps our bookkeeping.
+    let temp = preferred_temp_dir();
+    temp.parent().map(Path::to_path_buf).unwrap_or(temp)
+}
+
+pub fn outputs_registry_path() -> PathBuf {
+    app_root_dir().join("generated_outputs.json")
+}
+
+pub fn cleanup_policy_path() -> PathBuf {
+    app_root_dir().join("cleanup_policy.json")
+}
+
+fn registry_lock() -> &'static Mutex<()> {
+    // Workers record outputs concurrently; serialize the read-modify-write.
+    static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| Mutex::new(()))
+}
+
+fn load_registry() -> Vec<String> {
+    fs::read_to_string(outputs_registry_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_registry(paths: &[String]) -> Result<(), String> {
+    let path = outputs_registry_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("mkdir_failed:{e}"))?;
+    }
+    let content = serde_json::to_string(paths).map_err(|e| format!("json_err:{e}"))?;
+    fs::write(&path, content).map_err(|e| format!("write_failed:{e}"))
+}
+
+/// Remembers a file or directory a tool wrote outside the temp dir so the cleanup screen can list it.
+/// Best-effort: a failed write only means the output is not offered for cleanup.
+pub fn record_output(path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    if path.starts_with(preferred_temp_dir()) {
+        return;
+    }
+    let Some(path) = path.to_str().map(str::to_string) else {
+        return;
+    };
+    let _guard = registry_lock().lock().unwrap_or_else(|e| e.into_inner());
+    let mut registry = load_registry();
+    registry.retain(|p| p != &path);
+    registry.insert(0, path);
+    registry.truncate(OUTPUT_REGISTRY_MAX);
+    let _ = save_registry(&registry);
+}
+
+pub fn load_policy() -> CleanupPolicy {
+    fs::read_to_string(cleanup_policy_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_policy(policy: &CleanupPolicy) -> Result<(), String> {
+    let path = cleanup_policy_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("mkdir_failed:{e}"))?;
+    }
+    let content = serde_json::to_string(policy).map_err(|e| format!("json_err:{e}"))?;
+    fs::write(&path, content).map_err(|e| format!("write_failed:{e}"))
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            // DirEntry::metadata does not follow symlinks, so links are never descended.
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+fn generated_entry(path: &Path, kind: GeneratedKind) -> Option<GeneratedFile> {
+    let meta = fs::symlink_metadata(path).ok()?;
+    let is_dir = meta.is_dir();
+    let size_bytes = if is_dir { dir_size(path) } else { meta.len() };
+    let modified_at = meta
+        .modified()
+        .ok()
+        .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    Some(GeneratedFile {
+        path: path.to_string_lossy().into_owned(),
+        name: path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_string_lossy().into_owned()),
+        kind,
+        is_dir,
+        size_bytes,
+        modified_at,
+    })
+}
+
+/// Lists temp dir entries plus recorded outputs that still exist, oldest first.
+/// Recorded outputs that vanished are pruned from the registry.
+pub fn scan_generated_files() -> Vec<GeneratedFile> {
+    let temp = preferred_temp_dir();
+    let mut files: Vec<GeneratedFile> = fs::read_dir(&temp)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| generated_entry(&entry.path(), GeneratedKind::Temp))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let _guard = registry_lock().lock().unwrap_or_else(|e| e.into_inner());
+    let registry = load_registry();
+    let mut kept = Vec::with_capacity(registry.len());
+    for path in &registry {
+        if Path::new(path).starts_with(&temp) {
+            continue;
+        }
+        if let Some(entry) = generated_entry(Path::new(path), GeneratedKind::Output) {
+            files.push(entry);
+            kept.push(path.clone());
+        }
+    }
+    if kept.len() != registry.len() {
+        let _ = save_registry(&kept);
+    }
+
+    files.sort_by(|a, b| a.modified_at.cmp(&b.modified_at).then(a.name.cmp(&b.name)));
+    files
+}
+
+/// Deletes the given paths, but only those a fresh scan still reports as generated files,
+/// so a stale or forged list can never reach arbitrary user files.
+pub fn delete_generated(paths: &[String]) -> DeleteSummary {
+    let known = scan_generated_files();
+    let mut summary = DeleteSummary::default();
+    let mut removed_outputs = Vec::new();
+    for path in paths {
+        let Some(entry) = known.iter().find(|e| &e.path == path) else {
+            summary.failed.push(path.clone());
+            continue;
+        };
+        let result = if entry.is_dir {
+            fs::remove_dir_all(&entry.path)
+        } else {
+            fs::remove_file(&entry.path)
+        };
+        match result {
+            Ok(()) => {
+                summary.deleted += 1;
+                summary.freed_bytes += entry.size_bytes;
+                if entry.kind == GeneratedKind::Output {
+                    removed_outputs.push(entry.path.clone());
+                }
+            }
+            Err(_) => summary.failed.push(entry.name.clone()),
+        }
+    }
+    if !removed_outputs.is_empty() {
+        let _guard = registry_lock().lock().unwrap_or_else(|e| e.into_inner());
+        let mut registry = load_registry();
+        registry.retain(|p| !removed_outputs.contains(p));
+        let _ = save_registry(&registry);
+    }
+    summary
+}
+
+/// Deletes temp entries older than `max_age_days`. Outputs are never auto-deleted.
+pub fn clean_stale_temp(max_age_days: u32, now: u64) -> DeleteSummary {
+    let max_age = u64::from(max_age_days) * DAY_SECS;
+    let stale: Vec<String> = scan_generated_files()
+        .into_iter()
+        .filter(|e| e.kind == GeneratedKind::Temp && e.age_secs(now) >= max_age)
+        .map(|e| e.path)
+        .collect();
+    if stale.is_empty() {
+        return DeleteSummary::default();
+    }
+    delete_generated(&stale)
+}
+
+/// Applies the saved policy on the first call per process; later calls are no-ops.
+pub fn auto_clean_once() -> Option<DeleteSummary> {
+    static DONE: AtomicBool = AtomicBool::new(false);
+    if DONE.swap(true, Ordering::SeqCst) {
+        return None;
+    }
+    let policy = load_policy();
+    if !policy.auto_clean {
+        return None;
+    }
+    Some(clean_stale_temp(policy.temp_max_age_days, now_secs()))
+}
+
+pub fn parse_max_age_days(raw: &str) -> Result<u32, String> {
+    match raw.trim().parse::<u32>() {
+        Ok(days) if days > 0 => Ok(days),
+        _ => Err("cleanup_invalid_max_age".into()),
+    }
+}
+
+pub fn summary_text(summary: &DeleteSummary) -> String {
+    format!(
+        "{}{} ({})",
+        t!("cleanup_deleted_prefix"),
+        summary.deleted,
+        format_bytes(summary.freed_bytes)
+    )
+}
+
+fn format_age(secs: u64) -> String {
+    if secs >= DAY_SECS {
+        format!("{} d", secs / DAY_SECS)
+    } else if secs >= 3600 {
+        format!("{} h", secs / 3600)
+    } else {
+        format!("{} min", secs / 60)
+    }
+}
+
+fn entry_label(entry: &GeneratedFile, now: u64) -> String {
+    let name = if entry.is_dir {
+        format!("{}/", entry.name)
+    } else {
+        entry.name.clone()
+    };
+    let mut label = format!(
+        "{name} — {} · {}",
+        format_bytes(entry.size_bytes),
+        format_age(entry.age_secs(now))
+    );
+    if entry.kind == GeneratedKind::Output {
+        if let Some(parent) = Path::new(&entry.path).parent() {
+            label.push_str(&format!("\n{}", parent.display()));
+        }
+    }
+    label
+}
+
+fn render_entries_section(
+    title: &str,
+    state: &AppState,
+    kind: GeneratedKind,
+    now: u64,
+) -> Option<Value> {
+    let cleanup = &state.cleanup;
+    let mut rows = Vec::new();
+    let mut total = 0u64;
+    for (idx, entry) in cleanup.entries.iter().enumerate() {
+        if entry.kind != kind {
+            continue;
+        }
+        total += entry.size_bytes;
+        let label = entry_label(entry, now);
+        let bind_key = format!("cleanup_select_{idx}");
+        let action = format!("cleanup_toggle:{idx}");
+        rows.push(
+            serde_json::to_value(
+                UiCheckbox::new(&label, &bind_key)
+                    .checked(cleanup.is_selected(&entry.path))
+                    .action(&action),
+            )
+            .unwrap(),
+        );
+    }
+    if rows.is_empty() {
+        return None;
+    }
+    let heading = format!("{title} ({}, {})", rows.len(), format_bytes(total));
+    Some(
+        serde_json::to_value(UiSection::new(rows).title(&heading).padding(12)).unwrap(),
+    )
+}
+
+pub fn render_cleanup_screen(state: &AppState) -> Value {
+    let cleanup = &state.cleanup;
+    let now = now_secs();
+    let mut children = vec![serde_json::to_value(UiText::new(&t!("cleanup_title")).size(20.0)).unwrap()];
+
+    if let Some(status) = &cleanup.status {
+        children.push(serde_json::to_value(UiText::new(status).size(14.0)).unwrap());
+    }
+    if let Some(err) = &cleanup.error {
+        children.push(
+            serde_json::to_value(
+                UiText::new(&format!("{}{err}", t!("cleanup_error_prefix"))).size(14.0),
+            )
+            .unwrap(),
+        );
+    }
+
+    let total: u64 = cleanup.entries.iter().map(|e| e.size_bytes).sum();
+    let selected_bytes: u64 = cleanup
+        .entries
+        .iter()
+        .filter(|e| cleanup.is_selected(&e.path))
+        .map(|e| e.size_bytes)
+        .sum();
+    children.push(
+        serde_json::to_value(
+            UiText::new(&format!(
+                "{}{} ({})",
+                t!("cleanup_total_prefix"),
+                cleanup.entries.len(),
+                format_bytes(total)
+            ))
+            .size(14.0),
+        )
+        .unwrap(),
+    );
+    children.push(
+        serde_json::to_value(
+            UiText::new(&format!(
+                "{}{} ({})",
+                t!("cleanup_selected_prefix"),
+                cleanup.selected.len(),
+                format_bytes(selected_bytes)
+            ))
+            .size(14.0),
+        )
+        .unwrap(),
+    );
+
+    children.push(
+        serde_json::to_value(UiButton::new(&t!("cleanup_refresh_button"), "cleanup_refresh"))
+            .unwrap(),
+    );
+    if cleanup.entries.is_empty() {
+        children.push(serde_json::to_value(UiText::new(&t!("cleanup_empty")).size(14.0)).unwrap());
+    } else {
+        children.push(
+            serde_json::to_value(UiButton::new(
+                &t!("cleanup_select_all_button"),
+                "cleanup_select_all",
+            ))
+            .unwrap(),
+        );
+        children.push(
+            serde_json::to_value(UiButton::new(
+                &t!("cleanup_select_stale_button"),
+                "cleanup_select_stale",
+            ))
+            .unwrap(),
+        );
+        if !cleanup.selected.is_empty() {
+            children.push(
+                serde_json::to_value(UiButton::new(
+                    &t!("cleanup_select_none_button"),
+                    "cleanup_select_none",
+                ))
+                .unwrap(),
+            );
+            children.push(
+                serde_json::to_value(UiButton::new(
+                    &t!("cleanup_delete_selected_button"),
+                    "cleanup_delete_selected",
+                ))
+                .unwrap(),
+            );
+        }
+        if let Some(section) = render_entries_section(
+            &t!("cleanup_temp_section"),
+            state,
+            GeneratedKind::Temp,
+            now,
+        ) {
+            children.push(section);
+        }
+        if let Some(section) = render_entries_section(
+            &t!("cleanup_output_section"),
+            state,
+            GeneratedKind::Output,
+            now,
+        ) {
+            children.push(section);
+        }
+    }
+
+    let max_age = cleanup.policy.temp_max_age_days.to_string();
+    let policy_children = vec![
+        serde_json::to_value(
+            UiCheckbox::new(&t!("cleanup_auto_clean_checkbox"), "cleanup_auto_clean")
+                .checked(cleanup.policy.auto_clean),
+        )
+        .unwrap(),
+        serde_json::to_value(
+            UiTextInput::new("cleanup_max_age_days")
+                .hint(&t!("cleanup_max_age_hint"))
+                .text(&max_age)
+                .single_line(true),
+        )
+        .unwrap(),
+        serde_json::to_value(UiButton::new(
+            &t!("cleanup_save_policy_button"),
+            "cleanup_save_policy",
+        ))
+        .unwrap(),
+        serde_json::to_value(UiButton::new(
+            &t!("cleanup_clean_now_button"),
+            "cleanup_clean_stale",
+        ))
+        .unwrap(),
+    ];
+    children.push(
+        serde_json::to_value(
+            UiSection::new(policy_children)
+                .title(&t!("cleanup_policy_section"))
+                .padding(12)
+                .collapsible(
+                    POLICY_SECTION_KEY,
+                    state.is_section_expanded(POLICY_SECTION_KEY, false),
+                ),
+        )
+        .unwrap(),
+    );
+
+    maybe_push_back(&mut children, state);
+    serde_json::to_value(UiColumn::new(children).padding(24)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::storage::test_env_lock;
+    use std::time::Duration;
+
+    fn age_file(path: &Path, days: u64) {
+        let when = SystemTime::now() - Duration::from_secs(days * DAY_SECS);
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(when)
+            .unwrap();
+    }
+
+    #[test]
+    fn scan_lists_temp_entries_and_recorded_outputs() {
+        let _guard = test_env_lock().lock().unwrap();
+        let root = tempfile::tempdir().unwrap();
+        let cache = root.path().join("cache");
+        let downloads = root.path().join("Download");
+        fs::create_dir_all(cache.join("report_extracted")).unwrap();
+        fs::create_dir_all(&downloads).unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", &cache);
+
+        fs::write(cache.join("dither_src_1.png"), [0u8; 10]).unwrap();
+        fs::write(cache.join("report_extracted").join("a.txt"), [0u8; 5]).unwrap();
+        let output = downloads.join("notes.txt.gz");
+        fs::write(&output, [0u8; 3]).unwrap();
+        record_output(&output);
+        record_output(cache.join("dither_src_1.png"));
+        record_output(downloads.join("gone.pdf"));
+
+        let files = scan_generated_files();
+        assert_eq!(files.len(), 3);
+        let dir = files.iter().find(|f| f.name == "report_extracted").unwrap();
+        assert!(dir.is_dir);
+        assert_eq!(dir.size_bytes, 5);
+        assert_eq!(dir.kind, GeneratedKind::Temp);
+        let out = files.iter().find(|f| f.name == "notes.txt.gz").unwrap();
+        assert_eq!(out.kind, GeneratedKind::Output);
+        // Missing outputs are pruned; temp paths never enter the registry.
+        assert_eq!(load_registry(), vec![output.to_string_lossy().into_owned()]);
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
+
+    #[test]
+    fn delete_only_touches_scanned_entries_and_stale_cleanup_skips_outputs() {
+        let _guard = test_env_lock().lock().unwrap();
+        let root = tempfile::tempdir().unwrap();
+        let cache = root.path().join("cache");
+        fs::create_dir_all(&cache).unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", &cache);
+
+        let old_temp = cache.join("pixel_src_old.png");
+        let fresh_temp = cache.join("pixel_src_new.png");
+        let old_output = root.path().join("photo_dithered.png");
+        let stranger = root.path().join("keep.txt");
+        for path in [&old_temp, &fresh_temp, &old_output, &stranger] {
+            fs::write(path, [1u8; 4]).unwrap();
+        }
+        age_file(&old_temp, 10);
+        age_file(&old_output, 10);
+        record_output(&old_output);
+
+        let summary = delete_generated(&[stranger.to_string_lossy().into_owned()]);
+        assert_eq!(summary.deleted, 0);
+        assert_eq!(summary.failed.len(), 1);
+        assert!(stranger.exists());
+
+        let summary = clean_stale_temp(7, now_secs());
+        assert_eq!(summary.deleted, 1);
+        assert_eq!(summary.freed_bytes, 4);
+        assert!(!old_temp.exists());
+        assert!(fresh_temp.exists());
+        assert!(old_output.exists());
+
+        let summary = delete_generated(&[old_output.to_string_lossy().into_owned()]);
+        assert_eq!(summary.deleted, 1);
+        assert!(!old_output.exists());
+        assert!(load_registry().is_empty());
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
+
+    #[test]
+    fn max_age_must_be_a_positive_day_count() {
+        assert_eq!(parse_max_age_days(" 14 "), Ok(14));
+        assert!(parse_max_age_days("0").is_err());
+        assert!(parse_max_age_days("week").is_err());
+    }
+}
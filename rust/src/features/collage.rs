@@ -0,0 +1,384 @@
+use crate::error::error_text;
+use crate::features::color_tools::parse_hex;
+use crate::features::image_edit::write_result_image;
+use crate::features::image_resize::open_oriented;
+use crate::state::AppState;
+use crate::ui::{
+    maybe_push_back, Button as UiButton, ColorSwatch as UiColorSwatch, Column as UiColumn,
+    Text as UiText, TextInput as UiTextInput,
+};
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, Rgba, RgbaImage};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+pub const DEFAULT_CELL_SIZE: u32 = 480;
+pub const MIN_CELL_SIZE: u32 = 64;
+pub const MAX_CELL_SIZE: u32 = 2048;
+pub const DEFAULT_PADDING: u32 = 16;
+pub const MAX_PADDING: u32 = 256;
+/// Keeps the canvas (four bytes per pixel) within what a phone can hold in memory.
+pub const MAX_CANVAS_EDGE: u32 = 12_000;
+const DEFAULT_BACKGROUND: &str = "#FFFFFF";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollageSource {
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollageLayout {
+    /// 0 picks a value from the image count and the other dimension.
+    pub columns: u32,
+    pub rows: u32,
+    /// Edge of the square cell each picture is fitted into.
+    pub cell_size: u32,
+    /// Gap between cells and around the border.
+    pub padding: u32,
+    pub background: [u8; 3],
+}
+
+impl CollageLayout {
+    /// Resolves automatic rows/columns for `count` pictures.
+    pub fn grid(&self, count: usize) -> Result<(u32, u32), String> {
+        if count == 0 {
+            return Err("collage_no_images".into());
+        }
+        let n = count as u32;
+        let (columns, rows) = match (self.columns, self.rows) {
+            (0, 0) => {
+                let columns = (n as f64).sqrt().ceil() as u32;
+                (columns, n.div_ceil(columns))
+            }
+            (columns, 0) => (columns, n.div_ceil(columns)),
+            (0, rows) => (n.div_ceil(rows), rows),
+            (columns, rows) if columns * rows < n => {
+                return Err(format!("collage_grid_too_small:{columns}×{rows}<{n}"));
+            }
+            fixed => fixed,
+        };
+        Ok((columns, rows))
+    }
+
+    pub fn canvas_size(&self, columns: u32, rows: u32) -> Result<(u32, u32), String> {
+        let edge = |cells: u32| cells * self.cell_size + (cells + 1) * self.padding;
+        let (width, height) = (edge(columns), edge(rows));
+        if width > MAX_CANVAS_EDGE || height > MAX_CANVAS_EDGE {
+            return Err(format!("collage_too_large:{width}×{height}"));
+        }
+        Ok((width, height))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollageState {
+    pub sources: Vec<CollageSource>,
+    pub output_dir: Option<String>,
+    pub columns: u32,
+    pub rows: u32,
+    pub cell_size: u32,
+    pub padding: u32,
+    /// As typed; validated when the collage is built.
+    pub background_hex: String,
+    pub result_path: Option<String>,
+    pub error: Option<String>,
+}
+
+impl CollageState {
+    pub fn new() -> Self {
+        Self {
+            sources: Vec::new(),
+            output_dir: None,
+            columns: 0,
+            rows: 0,
+            cell_size: DEFAULT_CELL_SIZE,
+            padding: DEFAULT_PADDING,
+            background_hex: DEFAULT_BACKGROUND.to_string(),
+            result_path: None,
+            error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Pulls the form values; blank rows/columns mean automatic, other unparsable numbers
+    /// keep the previous setting.
+    pub fn sync_bindings(&mut self, bindings: &HashMap<String, String>) {
+        let read = |key: &str, current: u32, min: u32, max: u32| match bindings.get(key) {
+            Some(v) if v.trim().is_empty() && min == 0 => 0,
+            Some(v) => v
+                .trim()
+                .parse::<u32>()
+                .map_or(current, |v| v.clamp(min, max)),
+            None => current,
+        };
+        self.columns = read("collage_columns", self.columns, 0, 50);
+        self.rows = read("collage_rows", self.rows, 0, 50);
+        self.cell_size = read(
+            "collage_cell_size",
+            self.cell_size,
+            MIN_CELL_SIZE,
+            MAX_CELL_SIZE,
+        );
+        self.padding = read("collage_padding", self.padding, 0, MAX_PADDING);
+        if let Some(hex) = bindings.get("collage_background") {
+            self.background_hex = hex.trim().to_string();
+        }
+    }
+
+    pub fn background(&self) -> Option<[u8; 3]> {
+        parse_hex(&self.background_hex)
+            .ok()
+            .map(|rgb| [rgb.r, rgb.g, rgb.b])
+    }
+
+    pub fn layout(&self) -> Result<CollageLayout, String> {
+        let background = self
+            .background()
+            .ok_or_else(|| format!("collage_invalid_background:{}", self.background_hex))?;
+        Ok(CollageLayout {
+            columns: self.columns,
+            rows: self.rows,
+            cell_size: self.cell_size,
+            padding: self.padding,
+            background,
+        })
+    }
+}
+
+impl Default for CollageState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fits each picture into its cell, centred, in pick order (left to right, top to bottom).
+pub fn compose_collage(
+    images: &[DynamicImage],
+    layout: &CollageLayout,
+) -> Result<RgbaImage, String> {
+    let (columns, rows) = layout.grid(images.len())?;
+    let (width, height) = layout.canvas_size(columns, rows)?;
+    let [r, g, b] = layout.background;
+    let mut canvas = RgbaImage::from_pixel(width, height, Rgba([r, g, b, 255]));
+    let step = layout.cell_size + layout.padding;
+    for (i, image) in images.iter().enumerate() {
+        let (col, row) = (i as u32 % columns, i as u32 / columns);
+        let fitted = image
+            .resize(layout.cell_size, layout.cell_size, FilterType::Triangle)
+            .to_rgba8();
+        let x = layout.padding + col * step + (layout.cell_size - fitted.width()) / 2;
+        let y = layout.padding + row * step + (layout.cell_size - fitted.height()) / 2;
+        imageops::overlay(&mut canvas, &fitted, x as i64, y as i64);
+    }
+    Ok(canvas)
+}
+
+/// Builds the collage and writes it next to the sources; the format follows the first picture.
+pub fn build_collage(
+    sources: &[CollageSource],
+    layout: &CollageLayout,
+    output_dir: &str,
+) -> Result<String, String> {
+    let first = sources.first().ok_or("collage_no_images")?;
+    // Validate the grid before decoding anything; pictures are shrunk as they load.
+    let (columns, rows) = layout.grid(sources.len())?;
+    layout.canvas_size(columns, rows)?;
+    let mut images = Vec::with_capacity(sources.len());
+    for source in sources {
+        let image = open_oriented(&source.path).map_err(|e| format!("{}: {e}", source.name))?;
+        images.push(image.resize(layout.cell_size, layout.cell_size, FilterType::Triangle));
+    }
+    let canvas = compose_collage(&images, layout)?;
+    write_result_image(
+        &DynamicImage::ImageRgba8(canvas),
+        &first.path,
+        "collage_",
+        output_dir,
+    )
+}
+
+fn text(value: &str, size: f64) -> Value {
+    serde_json::to_value(UiText::new(value).size(size)).unwrap()
+}
+
+fn number_input(bind_key: &str, value: u32, auto: bool) -> Value {
+    let shown = if auto && value == 0 {
+        String::new()
+    } else {
+        value.to_string()
+    };
+    let hint = t!("collage_auto_hint");
+    let mut input = UiTextInput::new(bind_key).text(&shown).single_line(true);
+    if auto {
+        input = input.hint(&hint);
+    }
+    serde_json::to_value(input).unwrap()
+}
+
+pub fn render_collage_screen(state: &AppState) -> Value {
+    let collage = &state.collage;
+    let mut children = vec![
+        text(&t!("collage_title"), 20.0),
+        text(&t!("collage_description"), 14.0),
+        serde_json::to_value(
+            UiButton::new(&t!("collage_pick_images_button"), "collage_pick_images")
+                .requires_file_picker(true)
+                .allow_multiple_files(true)
+                .id("collage_pick_images"),
+        )
+        .unwrap(),
+    ];
+    if !collage.sources.is_empty() {
+        let names: Vec<&str> = collage.sources.iter().map(|s| s.name.as_str()).collect();
+        children.push(text(
+            &format!(
+                "{}{} — {}",
+                t!("watermark_selected_prefix"),
+                collage.sources.len(),
+                names.join(", ")
+            ),
+            12.0,
+        ));
+    }
+
+    children.push(text(&t!("collage_columns_label"), 14.0));
+    children.push(number_input("collage_columns", collage.columns, true));
+    children.push(text(&t!("collage_rows_label"), 14.0));
+    children.push(number_input("collage_rows", collage.rows, true));
+    children.push(text(&t!("collage_cell_size_label"), 14.0));
+    children.push(number_input("collage_cell_size", collage.cell_size, false));
+    children.push(text(&t!("collage_padding_label"), 14.0));
+    children.push(number_input("collage_padding", collage.padding, false));
+    children.push(text(&t!("collage_background_label"), 14.0));
+    children.push(
+        serde_json::to_value(
+            UiTextInput::new("collage_background")
+                .text(&collage.background_hex)
+                .hint("#RRGGBB")
+                .single_line(true),
+        )
+        .unwrap(),
+    );
+    if let Some([r, g, b]) = collage.background() {
+        let color = (0xFF000000u32 | (r as u32) << 16 | (g as u32) << 8 | b as u32) as i64;
+        children.push(serde_json::to_value(UiColorSwatch::new(color)).unwrap());
+    }
+
+    if let Ok((columns, rows)) = collage.layout().and_then(|l| l.grid(collage.sources.len())) {
+        children.push(text(
+            &format!("{}{columns}×{rows}", t!("collage_grid_prefix")),
+            12.0,
+        ));
+    }
+    children.push(
+        serde_json::to_value(
+            UiButton::new(&t!("collage_build_button"), "collage_build").id("collage_build"),
+        )
+        .unwrap(),
+    );
+
+    if let Some(err) = &collage.error {
+        children.push(text(&error_text(err), 12.0));
+    }
+    if let Some(path) = &collage.result_path {
+        children.push(text(
+            &format!("{}{path}", t!("image_edit_result_prefix")),
+            12.0,
+        ));
+        children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("dithering_copy_result_path_button"), "copy_clipboard")
+                    .copy_text(path),
+            )
+            .unwrap(),
+        );
+    }
+
+    maybe_push_back(&mut children, state);
+    serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(columns: u32, rows: u32) -> CollageLayout {
+        CollageLayout {
+            columns,
+            rows,
+            cell_size: 10,
+            padding: 2,
+            background: [0, 0, 255],
+        }
+    }
+
+    #[test]
+    fn grid_fills_in_automatic_dimensions() {
+        assert_eq!(layout(0, 0).grid(5).unwrap(), (3, 2));
+        assert_eq!(layout(0, 0).grid(4).unwrap(), (2, 2));
+        assert_eq!(layout(4, 0).grid(5).unwrap(), (4, 2));
+        assert_eq!(layout(0, 1).grid(5).unwrap(), (5, 1));
+        assert_eq!(layout(3, 3).grid(5).unwrap(), (3, 3));
+        assert_eq!(
+            layout(2, 2).grid(5).unwrap_err(),
+            "collage_grid_too_small:2×2<5"
+        );
+        assert_eq!(layout(0, 0).grid(0).unwrap_err(), "collage_no_images");
+        let big = CollageLayout {
+            cell_size: MAX_CELL_SIZE,
+            ..layout(0, 0)
+        };
+        assert!(big
+            .canvas_size(6, 1)
+            .unwrap_err()
+            .starts_with("collage_too_large"));
+    }
+
+    #[test]
+    fn pictures_are_fitted_and_centred_in_cells() {
+        let red = DynamicImage::ImageRgba8(RgbaImage::from_pixel(20, 10, Rgba([255, 0, 0, 255])));
+        let green = DynamicImage::ImageRgba8(RgbaImage::from_pixel(5, 5, Rgba([0, 255, 0, 255])));
+        let canvas = compose_collage(&[red, green], &layout(2, 0)).unwrap();
+        // 2 cells of 10 plus 3 gaps of 2 wide, 1 cell plus 2 gaps tall.
+        assert_eq!(canvas.dimensions(), (26, 14));
+        assert_eq!(canvas.get_pixel(0, 0).0, [0, 0, 255, 255]);
+        // 20×10 scales to 10×5, centred vertically in the first cell (rows 4..9).
+        assert_eq!(canvas.get_pixel(2, 3).0, [0, 0, 255, 255]);
+        assert_eq!(canvas.get_pixel(2, 4).0, [255, 0, 0, 255]);
+        assert_eq!(canvas.get_pixel(11, 8).0, [255, 0, 0, 255]);
+        // 5×5 upscales to fill the second cell.
+        assert_eq!(canvas.get_pixel(14, 2).0, [0, 255, 0, 255]);
+        assert_eq!(canvas.get_pixel(23, 11).0, [0, 255, 0, 255]);
+        assert_eq!(canvas.get_pixel(24, 11).0, [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn bindings_treat_blank_grid_fields_as_automatic() {
+        let mut state = CollageState::new();
+        state.sync_bindings(&HashMap::from([
+            ("collage_columns".into(), "3".into()),
+            ("collage_cell_size".into(), "9".into()),
+            ("collage_background".into(), "#102030".into()),
+        ]));
+        assert_eq!(state.columns, 3);
+        assert_eq!(state.cell_size, MIN_CELL_SIZE);
+        assert_eq!(state.layout().unwrap().background, [0x10, 0x20, 0x30]);
+        state.sync_bindings(&HashMap::from([
+            ("collage_columns".into(), " ".into()),
+            ("collage_padding".into(), "lots".into()),
+            ("collage_background".into(), "teal".into()),
+        ]));
+        assert_eq!(state.columns, 0);
+        assert_eq!(state.padding, DEFAULT_PADDING);
+        assert_eq!(
+            state.layout().unwrap_err(),
+            "collage_invalid_background:teal"
+        );
+    }
+}
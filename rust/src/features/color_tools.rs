@@ -174,7 +174,7 @@ fn color_strings(state: &AppState, fallback: &str) -> (String, String, String) {
     (hex, rgb, hsl)
 }
 
-fn parse_hex(raw: &str) -> Result<Rgb, String> {
+pub(crate) fn parse_hex(raw: &str) -> Result<Rgb, String> {
     let trimmed = raw.trim().trim_start_matches('#');
     if trimmed.len() != 6 {
         return Err("invalid_hex_length".into());
@@ -185,6 +185,67 @@ fn parse_hex(raw: &str) -> Result<Rgb, String> {
     Ok(Rgb { r, g, b })
 }
 
+/// Parses `#RRGGBB` entries separated by commas, semicolons or whitespace.
+pub(crate) fn parse_hex_list(raw: &str) -> Result<Vec<Rgb>, String> {
+    raw.split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| parse_hex(part).map_err(|e| format!("{e}:{part}")))
+        .collect()
+}
+
+/// Reduces `pixels` to at most `count` representative colors with median cut,
+/// most common first.
+pub(crate) fn extract_palette(pixels: &[[u8; 3]], count: usize) -> Vec<Rgb> {
+    if pixels.is_empty() || count == 0 {
+        return Vec::new();
+    }
+    let mut boxes = vec![pixels.to_vec()];
+    while boxes.len() < count {
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .map(|(idx, bucket)| (idx, widest_channel(bucket)))
+            .filter(|(_, (_, span))| *span > 0)
+            .max_by_key(|(_, (_, span))| *span);
+        let Some((idx, (channel, _))) = widest else {
+            break;
+        };
+        let mut bucket = boxes.swap_remove(idx);
+        bucket.sort_unstable_by_key(|p| p[channel]);
+        let upper = bucket.split_off(bucket.len() / 2);
+        boxes.push(bucket);
+        boxes.push(upper);
+    }
+    boxes.sort_by_key(|bucket| std::cmp::Reverse(bucket.len()));
+    boxes
+        .iter()
+        .map(|bucket| {
+            let len = bucket.len() as u64;
+            let mean = |c: usize| {
+                let sum: u64 = bucket.iter().map(|p| p[c] as u64).sum();
+                ((sum + len / 2) / len) as u8
+            };
+            Rgb {
+                r: mean(0),
+                g: mean(1),
+                b: mean(2),
+            }
+        })
+        .collect()
+}
+
+fn widest_channel(pixels: &[[u8; 3]]) -> (usize, u8) {
+    (0..3)
+        .map(|c| {
+            let (lo, hi) = pixels.iter().fold((u8::MAX, u8::MIN), |(lo, hi), p| {
+                (lo.min(p[c]), hi.max(p[c]))
+            });
+            (c, hi - lo)
+        })
+        .max_by_key(|(_, span)| *span)
+        .unwrap_or((0, 0))
+}
+
 fn parse_rgb_triplet(raw: &str) -> Result<Rgb, String> {
     let parts: Vec<_> = raw.split(',').map(|p| p.trim()).collect();
     if parts.len() != 3 {
@@ -228,3 +289,37 @@ fn rgb_to_hsl(rgb: Rgb) -> Hsl {
         l,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_list_accepts_mixed_separators_and_reports_bad_entries() {
+        let colors = parse_hex_list("#000000, #FF8000;\n00ff00").unwrap();
+        let rgb: Vec<_> = colors.iter().map(|c| (c.r, c.g, c.b)).collect();
+        assert_eq!(rgb, vec![(0, 0, 0), (255, 128, 0), (0, 255, 0)]);
+
+        assert_eq!(
+            parse_hex_list("#000000 #12345").unwrap_err(),
+            "invalid_hex_length:#12345"
+        );
+    }
+
+    #[test]
+    fn median_cut_finds_dominant_colors() {
+        let mut pixels = vec![[250, 10, 10]; 64];
+        pixels.extend(vec![[10, 10, 240]; 32]);
+        pixels.extend(vec![[12, 200, 14]; 32]);
+
+        let palette = extract_palette(&pixels, 3);
+        let rgb: Vec<_> = palette.iter().map(|c| (c.r, c.g, c.b)).collect();
+        assert_eq!(rgb.len(), 3);
+        assert_eq!(rgb[0], (250, 10, 10));
+        assert!(rgb.contains(&(10, 10, 240)));
+        assert!(rgb.contains(&(12, 200, 14)));
+
+        assert_eq!(extract_palette(&[[7, 7, 7]; 5], 4).len(), 1);
+        assert!(extract_palette(&[], 4).is_empty());
+    }
+}
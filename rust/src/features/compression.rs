@@ -1,16 +1,68 @@
+use crate::error::error_text;
 use crate::features::storage::output_dir_for;
 use crate::state::AppState;
-use crate::ui::{maybe_push_back, Button as UiButton, Column as UiColumn, Text as UiText};
+use crate::ui::{
+    format_bytes, maybe_push_back, Button as UiButton, Chart as UiChart, ChartSeries,
+    Column as UiColumn, Text as UiText,
+};
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::fs::File;
 use std::io::{copy, BufReader, Write};
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 use rust_i18n::t;
 
+/// Levels compared by the benchmark: fastest, flate2's default and best.
+pub const BENCHMARK_LEVELS: [u32; 3] = [1, 6, 9];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GzipLevelResult {
+    pub level: u32,
+    pub compressed_bytes: u64,
+    pub millis: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GzipBenchmark {
+    pub original_bytes: u64,
+    pub levels: Vec<GzipLevelResult>,
+}
+
+impl GzipBenchmark {
+    /// Compressed size as a percentage of the original, per level.
+    pub fn shares(&self) -> Vec<f64> {
+        self.levels
+            .iter()
+            .map(|r| {
+                if self.original_bytes == 0 {
+                    100.0
+                } else {
+                    r.compressed_bytes as f64 / self.original_bytes as f64 * 100.0
+                }
+            })
+            .collect()
+    }
+}
+
+/// Discards what is written and only counts the bytes.
+#[derive(Default)]
+struct CountingSink(u64);
+
+impl Write for CountingSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 fn to_value_or_text<T: Serialize>(value: T, context: &str) -> Value {
     serde_json::to_value(value).unwrap_or_else(|e| {
         json!({
@@ -39,6 +91,12 @@ pub fn render_compression_screen(state: &AppState) -> Value {
                 .content_description("gzip_decompress_btn"),
             "gzip_decompress_btn",
         ),
+        to_value_or_text(
+            UiButton::new(&t!("compression_benchmark_button"), "gzip_benchmark")
+                .requires_file_picker(true)
+                .content_description("gzip_benchmark_btn"),
+            "gzip_benchmark_btn",
+        ),
     ];
 
     if let Some(msg) = &state.compression_status {
@@ -61,9 +119,13 @@ pub fn render_compression_screen(state: &AppState) -> Value {
         }
     }
 
+    if let Some(bench) = &state.compression_benchmark {
+        children.push(render_benchmark(bench));
+    }
+
     if let Some(err) = &state.compression_error {
         children.push(to_value_or_text(
-            UiText::new(&format!("{}{}", t!("multi_hash_error_prefix"), err))
+            UiText::new(&error_text(err))
                 .size(12.0)
                 .content_description("gzip_error"),
             "gzip_error",
@@ -75,6 +137,85 @@ pub fn render_compression_screen(state: &AppState) -> Value {
     to_value_or_text(UiColumn::new(children).padding(20), "gzip_root")
 }
 
+fn render_benchmark(bench: &GzipBenchmark) -> Value {
+    let mut rows = vec![
+        to_value_or_text(
+            UiText::new(&t!("compression_benchmark_title")).size(16.0),
+            "gzip_benchmark_title",
+        ),
+        to_value_or_text(
+            UiText::new(&format!(
+                "{}{}",
+                t!("compression_benchmark_original_prefix"),
+                format_bytes(bench.original_bytes)
+            ))
+            .size(12.0),
+            "gzip_benchmark_original",
+        ),
+    ];
+    let shares = bench.shares();
+    for (result, share) in bench.levels.iter().zip(&shares) {
+        rows.push(to_value_or_text(
+            UiText::new(&format!(
+                "{}{}: {} ({share:.1}%) · {} ms",
+                t!("compression_benchmark_level_prefix"),
+                result.level,
+                format_bytes(result.compressed_bytes),
+                result.millis
+            ))
+            .size(12.0),
+            "gzip_benchmark_level",
+        ));
+    }
+    let title = t!("compression_benchmark_title");
+    let level_axis = t!("compression_benchmark_axis_level");
+    let share_axis = t!("compression_benchmark_axis_share");
+    let levels = bench.levels.iter().map(|r| r.level.to_string()).collect();
+    rows.push(to_value_or_text(
+        UiChart::new(vec![ChartSeries::new(&title, shares)])
+            .bar()
+            .axes(&level_axis, &share_axis)
+            .y_range(0.0, 100.0)
+            .categories(levels)
+            .height_dp(140)
+            .content_description("gzip_benchmark_chart"),
+        "gzip_benchmark_chart",
+    ));
+    to_value_or_text(UiColumn::new(rows).padding(8), "gzip_benchmark")
+}
+
+/// Compresses `path` at each of [`BENCHMARK_LEVELS`] without writing any output.
+pub fn gzip_benchmark(path: &str) -> Result<GzipBenchmark, String> {
+    let input = Path::new(path);
+    if input.is_dir() {
+        return Err("gzip_source_is_directory".into());
+    }
+    let original_bytes = input
+        .metadata()
+        .map_err(|_| "gzip_source_missing".to_string())?
+        .len();
+    let mut levels = Vec::with_capacity(BENCHMARK_LEVELS.len());
+    for level in BENCHMARK_LEVELS {
+        let mut reader =
+            BufReader::new(File::open(input).map_err(|e| format!("gzip_open_failed:{e}"))?);
+        let started = Instant::now();
+        let mut encoder = GzEncoder::new(CountingSink::default(), Compression::new(level));
+        copy(&mut reader, &mut encoder).map_err(|e| format!("gzip_compress_failed:{e}"))?;
+        let sink = encoder
+            .finish()
+            .map_err(|e| format!("gzip_compress_failed:{e}"))?;
+        levels.push(GzipLevelResult {
+            level,
+            compressed_bytes: sink.0,
+            millis: started.elapsed().as_millis() as u64,
+        });
+    }
+    Ok(GzipBenchmark {
+        original_bytes,
+        levels,
+    })
+}
+
 pub fn gzip_compress(path: &str) -> Result<PathBuf, String> {
     let input = Path::new(path);
     if !input.exists() {
@@ -183,4 +324,27 @@ mod tests {
         let data = fs::read(out_path).unwrap();
         assert_eq!(data, b"hello gzip");
     }
+
+    #[test]
+    fn benchmark_compares_levels_without_writing_output() {
+        let dir = tempdir().unwrap();
+        let input_path = dir.path().join("sample.txt");
+        let text: String = (0..5000)
+            .map(|i| format!("line {} of {}\n", i % 97, i % 13))
+            .collect();
+        fs::write(&input_path, &text).unwrap();
+
+        let bench = gzip_benchmark(input_path.to_str().unwrap()).expect("benchmark ok");
+        assert_eq!(bench.original_bytes, text.len() as u64);
+        let levels: Vec<u32> = bench.levels.iter().map(|r| r.level).collect();
+        assert_eq!(levels, BENCHMARK_LEVELS);
+        assert!(bench.levels[2].compressed_bytes <= bench.levels[0].compressed_bytes);
+        assert!(bench.shares().iter().all(|share| *share < 50.0));
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        let mut state = AppState::new();
+        state.compression_benchmark = Some(bench);
+        let ui = render_compression_screen(&state);
+        assert!(ui.to_string().contains("\"gzip_benchmark_chart\""));
+    }
 }
@@ -0,0 +1,910 @@
+//! Date arithmetic, time zone conversion and cron previews. Dates are wall-clock times
+//! without a zone; only the converter and the cron preview place them in a time zone,
+//! using the IANA database bundled with chrono-tz.
+
+use crate::error::error_text;
+use crate::state::AppState;
+use crate::ui::{
+    maybe_push_back, Button as UiButton, Card as UiCard, Column as UiColumn, Row as UiRow,
+    Section as UiSection, Text as UiText, TextInput as UiTextInput,
+};
+use chrono::{
+    DateTime, Datelike, Local, LocalResult, Months, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeDelta, TimeZone, Timelike, Utc, Weekday,
+};
+use chrono_tz::{OffsetComponents, Tz, TZ_VARIANTS};
+use cron::Schedule;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+pub const CRON_PREVIEW_RUNS: usize = 5;
+
+const DATE_TIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M",
+];
+
+const WEEKDAY_KEYS: [&str; 7] = [
+    "date_weekday_1",
+    "date_weekday_2",
+    "date_weekday_3",
+    "date_weekday_4",
+    "date_weekday_5",
+    "date_weekday_6",
+    "date_weekday_7",
+];
+
+const ZONED_FORMAT: &str = "%Y-%m-%d %H:%M %Z (UTC%:z)";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ShiftUnit {
+    Days,
+    Weeks,
+    Months,
+    Years,
+}
+
+impl ShiftUnit {
+    pub const ALL: [ShiftUnit; 4] = [
+        ShiftUnit::Days,
+        ShiftUnit::Weeks,
+        ShiftUnit::Months,
+        ShiftUnit::Years,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            ShiftUnit::Days => "days",
+            ShiftUnit::Weeks => "weeks",
+            ShiftUnit::Months => "months",
+            ShiftUnit::Years => "years",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|unit| unit.id() == id)
+    }
+
+    fn label_key(self) -> &'static str {
+        match self {
+            ShiftUnit::Days => "date_unit_days",
+            ShiftUnit::Weeks => "date_unit_weeks",
+            ShiftUnit::Months => "date_unit_months",
+            ShiftUnit::Years => "date_unit_years",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateDifference {
+    /// Positive when the second date is the later one.
+    pub total_seconds: i64,
+    /// Calendar days from the first date to the second, signed like `total_seconds`.
+    pub days: i64,
+    /// Years, months and days between the two dates, whichever comes first.
+    pub years: u32,
+    pub months: u32,
+    pub rem_days: u32,
+    /// Monday to Friday days from the earlier date up to, not including, the later one.
+    pub weekdays: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateFacts {
+    pub date: String,
+    /// 1 for Monday to 7 for Sunday.
+    pub weekday: u32,
+    pub iso_year: i32,
+    pub iso_week: u32,
+    pub iso_weeks_in_year: u32,
+    pub day_of_year: u32,
+    pub days_in_year: u32,
+    pub quarter: u32,
+}
+
+/// A formatted point in time with its weekday, 1 for Monday to 7 for Sunday.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateStamp {
+    pub text: String,
+    pub weekday: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TzConversion {
+    pub from: String,
+    pub to: String,
+    pub from_dst: bool,
+    pub to_dst: bool,
+    /// Locale key explaining how a skipped or repeated wall time was read.
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DateCalcState {
+    pub date_a: String,
+    pub date_b: String,
+    pub shift_amount: String,
+    pub shift_unit: ShiftUnit,
+    pub tz_time: String,
+    pub from_tz: String,
+    pub to_tz: String,
+    pub cron: String,
+    pub facts: Option<DateFacts>,
+    pub difference: Option<DateDifference>,
+    pub shifted: Option<DateStamp>,
+    pub conversion: Option<TzConversion>,
+    pub cron_runs: Vec<DateStamp>,
+    pub date_error: Option<String>,
+    pub shift_error: Option<String>,
+    pub tz_error: Option<String>,
+    pub cron_error: Option<String>,
+}
+
+impl DateCalcState {
+    pub const fn new() -> Self {
+        Self {
+            date_a: String::new(),
+            date_b: String::new(),
+            shift_amount: String::new(),
+            shift_unit: ShiftUnit::Days,
+            tz_time: String::new(),
+            from_tz: String::new(),
+            to_tz: String::new(),
+            cron: String::new(),
+            facts: None,
+            difference: None,
+            shifted: None,
+            conversion: None,
+            cron_runs: Vec::new(),
+            date_error: None,
+            shift_error: None,
+            tz_error: None,
+            cron_error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Recomputes every section from the inputs. Empty dates and times stand for `now`.
+    pub fn compute(&mut self, now: DateTime<Utc>) {
+        self.facts = None;
+        self.difference = None;
+        self.shifted = None;
+        self.conversion = None;
+        self.cron_runs.clear();
+        self.date_error = None;
+        self.shift_error = None;
+        self.tz_error = None;
+        self.cron_error = None;
+
+        let today = now.with_timezone(&Local).naive_local();
+        match (parse_date_time(&self.date_a), parse_date_time(&self.date_b)) {
+            (Ok(a), Ok(b)) => {
+                let start = a.unwrap_or(today);
+                self.facts = Some(date_facts(start.date()));
+                if a.is_some() || b.is_some() {
+                    self.difference = Some(date_difference(start, b.unwrap_or(today)));
+                }
+                let amount = self.shift_amount.trim();
+                if !amount.is_empty() {
+                    let shifted = amount
+                        .parse::<i32>()
+                        .map_err(|_| format!("date_amount_invalid:{amount}"))
+                        .and_then(|n| shift_date(start, n, self.shift_unit));
+                    match shifted {
+                        Ok(dt) => {
+                            self.shifted = Some(DateStamp {
+                                text: format_date_time(dt),
+                                weekday: dt.weekday().number_from_monday(),
+                            })
+                        }
+                        Err(e) => self.shift_error = Some(e),
+                    }
+                }
+            }
+            (Err(e), _) | (_, Err(e)) => self.date_error = Some(e),
+        }
+
+        let zones = find_time_zone(&self.from_tz)
+            .and_then(|from| find_time_zone(&self.to_tz).map(|to| (from, to)));
+        let conversion = zones.clone().and_then(|(from, to)| {
+            parse_date_time(&self.tz_time).map(|time| convert_time_zone(time, from, to, now))
+        });
+        match conversion {
+            Ok(conversion) => self.conversion = Some(conversion),
+            Err(e) => self.tz_error = Some(e),
+        }
+
+        if !self.cron.trim().is_empty() {
+            let tz = zones.map_or(Tz::UTC, |(from, _)| from);
+            match cron_preview(&self.cron, tz, now, CRON_PREVIEW_RUNS) {
+                Ok(runs) => {
+                    self.cron_runs = runs
+                        .iter()
+                        .map(|run| DateStamp {
+                            text: run.format("%Y-%m-%d %H:%M %Z").to_string(),
+                            weekday: run.weekday().number_from_monday(),
+                        })
+                        .collect()
+                }
+                Err(e) => self.cron_error = Some(e),
+            }
+        }
+    }
+}
+
+impl Default for DateCalcState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `YYYY-MM-DD`, optionally followed by `HH:MM[:SS]` after a space or `T`. Empty input
+/// is `None`, which callers read as now.
+pub fn parse_date_time(input: &str) -> Result<Option<NaiveDateTime>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(input, "%Y-%m-%d")
+                .ok()
+                .map(|date| date.and_time(NaiveTime::MIN))
+        })
+        .map(Some)
+        .ok_or_else(|| format!("date_invalid:{input}"))
+}
+
+/// Drops the time of day when it is midnight, and the seconds when they are zero.
+pub fn format_date_time(dt: NaiveDateTime) -> String {
+    let format = if dt.time() == NaiveTime::MIN {
+        "%Y-%m-%d"
+    } else if dt.second() == 0 {
+        "%Y-%m-%d %H:%M"
+    } else {
+        "%Y-%m-%d %H:%M:%S"
+    };
+    dt.format(format).to_string()
+}
+
+pub fn date_difference(a: NaiveDateTime, b: NaiveDateTime) -> DateDifference {
+    let (start, end) = if b.date() >= a.date() {
+        (a.date(), b.date())
+    } else {
+        (b.date(), a.date())
+    };
+    // Adding months clamps to the end of shorter months, so Jan 31 to Feb 28 is a month.
+    let add = |months: i32| start.checked_add_months(Months::new(months as u32));
+    let mut months = (end.year() - start.year()) * 12 + end.month() as i32 - start.month() as i32;
+    while months > 0 && add(months).is_none_or(|date| date > end) {
+        months -= 1;
+    }
+    let anchor = add(months).unwrap_or(start);
+    let span_days = (end - start).num_days();
+    DateDifference {
+        total_seconds: (b - a).num_seconds(),
+        days: (b.date() - a.date()).num_days(),
+        years: (months / 12) as u32,
+        months: (months % 12) as u32,
+        rem_days: (end - anchor).num_days() as u32,
+        weekdays: weekdays_between(start.weekday(), span_days),
+    }
+}
+
+/// Monday to Friday days among `span_days` consecutive days starting on `first`.
+fn weekdays_between(first: Weekday, span_days: i64) -> i64 {
+    let first = i64::from(first.num_days_from_monday());
+    let partial = (0..span_days % 7)
+        .filter(|offset| (first + offset) % 7 < 5)
+        .count() as i64;
+    span_days / 7 * 5 + partial
+}
+
+pub fn date_facts(date: NaiveDate) -> DateFacts {
+    let iso = date.iso_week();
+    let leap = NaiveDate::from_ymd_opt(date.year(), 2, 29).is_some();
+    let long_iso_year = NaiveDate::from_isoywd_opt(iso.year(), 53, Weekday::Mon).is_some();
+    DateFacts {
+        date: date.format("%Y-%m-%d").to_string(),
+        weekday: date.weekday().number_from_monday(),
+        iso_year: iso.year(),
+        iso_week: iso.week(),
+        iso_weeks_in_year: if long_iso_year { 53 } else { 52 },
+        day_of_year: date.ordinal(),
+        days_in_year: if leap { 366 } else { 365 },
+        quarter: (date.month() - 1) / 3 + 1,
+    }
+}
+
+/// Moves `dt` by `amount` units, backwards when negative. Months and years keep the
+/// day of month, clamped to the length of the target month.
+pub fn shift_date(
+    dt: NaiveDateTime,
+    amount: i32,
+    unit: ShiftUnit,
+) -> Result<NaiveDateTime, String> {
+    let amount_i64 = i64::from(amount);
+    let shifted = match unit {
+        ShiftUnit::Days => TimeDelta::try_days(amount_i64).and_then(|d| dt.checked_add_signed(d)),
+        ShiftUnit::Weeks => TimeDelta::try_weeks(amount_i64).and_then(|d| dt.checked_add_signed(d)),
+        ShiftUnit::Months | ShiftUnit::Years => {
+            let months = if unit == ShiftUnit::Years {
+                amount.checked_mul(12)
+            } else {
+                Some(amount)
+            };
+            months.and_then(|months| {
+                let step = Months::new(months.unsigned_abs());
+                if months < 0 {
+                    dt.checked_sub_months(step)
+                } else {
+                    dt.checked_add_months(step)
+                }
+            })
+        }
+    };
+    shifted.ok_or_else(|| "date_out_of_range".to_string())
+}
+
+/// IANA zone by name, ignoring case and accepting spaces for underscores. Empty is UTC.
+pub fn find_time_zone(name: &str) -> Result<Tz, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Ok(Tz::UTC);
+    }
+    if let Ok(tz) = name.parse::<Tz>() {
+        return Ok(tz);
+    }
+    let wanted = name.replace(' ', "_");
+    TZ_VARIANTS
+        .iter()
+        .copied()
+        .find(|tz| tz.name().eq_ignore_ascii_case(&wanted))
+        .ok_or_else(|| format!("tz_unknown:{name}"))
+}
+
+/// Reads `time` (or `now` when `None`) as a wall time in `from` and shows it in `to`.
+/// A wall time skipped by a forward DST shift is read with the offset in force before
+/// the shift, as a clock nobody moved yet would; a repeated one is the first pass.
+pub fn convert_time_zone(
+    time: Option<NaiveDateTime>,
+    from: Tz,
+    to: Tz,
+    now: DateTime<Utc>,
+) -> TzConversion {
+    let mut note = None;
+    let source = match time {
+        None => now.with_timezone(&from),
+        Some(naive) => match from.from_local_datetime(&naive) {
+            LocalResult::Single(dt) => dt,
+            LocalResult::Ambiguous(earliest, _) => {
+                note = Some("date_tz_ambiguous".to_string());
+                earliest
+            }
+            LocalResult::None => {
+                note = Some("date_tz_gap".to_string());
+                let before = naive
+                    .checked_sub_signed(TimeDelta::days(1))
+                    .unwrap_or(naive);
+                let offset = from.offset_from_utc_datetime(&before).fix();
+                let utc = naive - TimeDelta::seconds(i64::from(offset.local_minus_utc()));
+                from.from_utc_datetime(&utc)
+            }
+        },
+    };
+    let target = source.with_timezone(&to);
+    TzConversion {
+        from: source.format(ZONED_FORMAT).to_string(),
+        to: target.format(ZONED_FORMAT).to_string(),
+        from_dst: source.offset().dst_offset() != TimeDelta::zero(),
+        to_dst: target.offset().dst_offset() != TimeDelta::zero(),
+        note,
+    }
+}
+
+/// Accepts crontab's five fields (minute to day of week) as well as the cron crate's own
+/// syntax with seconds and an optional year. Note that, unlike crontab, a run must match
+/// both the day of month and the day of week when both are restricted.
+pub fn normalize_cron(expr: &str) -> String {
+    let fields: Vec<&str> = expr.split_whitespace().collect();
+    if fields.len() != 5 {
+        return fields.join(" ");
+    }
+    let days_of_week = fields[4]
+        .split(',')
+        .map(crontab_days_of_week)
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("0 {} {days_of_week}", fields[..4].join(" "))
+}
+
+/// Crontab numbers days 0 (or 7) = Sunday to 6 = Saturday; the cron crate 1 = Sunday to
+/// 7 = Saturday. Names and `*` mean the same in both.
+fn crontab_days_of_week(item: &str) -> String {
+    let (range, step, every) = match item.split_once('/') {
+        Some((range, step)) => match step.parse::<u32>() {
+            Ok(every) if every > 0 => (range, format!("/{step}"), every),
+            _ => return item.to_string(),
+        },
+        None => (item, String::new(), 1),
+    };
+    let day = |s: &str| s.parse::<u32>().ok().filter(|d| *d <= 7);
+    match range.split_once('-') {
+        Some((a, b)) => match (day(a), day(b)) {
+            (Some(0), Some(7)) => format!("1-7{step}"),
+            // The step only lands on the trailing 7 (Sunday) when it divides the distance.
+            (Some(a), Some(7)) if (7 - a) % every == 0 => format!("{}-7{step},1", a + 1),
+            (Some(a), Some(7)) => format!("{}-7{step}", a + 1),
+            (Some(a), Some(b)) => format!("{}-{}{step}", a + 1, b + 1),
+            _ => item.to_string(),
+        },
+        None => match day(range) {
+            Some(d) => format!("{}{step}", d % 7 + 1),
+            None => item.to_string(),
+        },
+    }
+}
+
+/// The next `count` runs of `expr` after `after`, in `tz`.
+pub fn cron_preview(
+    expr: &str,
+    tz: Tz,
+    after: DateTime<Utc>,
+    count: usize,
+) -> Result<Vec<DateTime<Tz>>, String> {
+    let schedule =
+        Schedule::from_str(&normalize_cron(expr)).map_err(|e| format!("cron_invalid:{e}"))?;
+    let runs: Vec<DateTime<Tz>> = schedule
+        .after(&after.with_timezone(&tz))
+        .take(count)
+        .collect();
+    if runs.is_empty() {
+        return Err("cron_no_upcoming_runs".into());
+    }
+    Ok(runs)
+}
+
+pub fn apply_date_calc_bindings(state: &mut AppState, bindings: &HashMap<String, String>) {
+    let calc = &mut state.date_calc;
+    for (key, field) in [
+        ("date_a", &mut calc.date_a),
+        ("date_b", &mut calc.date_b),
+        ("date_shift_amount", &mut calc.shift_amount),
+        ("date_tz_time", &mut calc.tz_time),
+        ("date_tz_from", &mut calc.from_tz),
+        ("date_tz_to", &mut calc.to_tz),
+        ("date_cron", &mut calc.cron),
+    ] {
+        if let Some(value) = bindings.get(key) {
+            *field = value.clone();
+        }
+    }
+    if let Some(unit) = bindings
+        .get("date_shift_unit")
+        .and_then(|id| ShiftUnit::from_id(id))
+    {
+        calc.shift_unit = unit;
+    }
+}
+
+fn weekday_name(weekday: u32) -> String {
+    let index = weekday.clamp(1, 7) as usize - 1;
+    t!(WEEKDAY_KEYS[index]).to_string()
+}
+
+fn to_value_or_text<T: Serialize>(value: T, context: &str) -> Value {
+    serde_json::to_value(value).unwrap_or_else(|e| {
+        json!({
+            "type": "Text",
+            "text": format!("{context}_serialize_error:{e}")
+        })
+    })
+}
+
+fn date_input(id: &'static str, text: &str, hint: &str) -> Value {
+    to_value_or_text(
+        UiTextInput::new(id)
+            .text(text)
+            .hint(hint)
+            .single_line(true)
+            .debounce_ms(300)
+            .action_on_submit("date_calc_compute")
+            .content_description(id),
+        id,
+    )
+}
+
+fn small_text(text: &str, context: &str) -> Value {
+    to_value_or_text(
+        UiText::new(text).size(12.0).content_description(context),
+        context,
+    )
+}
+
+fn difference_card(diff: &DateDifference) -> Value {
+    let hours = diff.total_seconds / 3600;
+    let minutes = (diff.total_seconds % 3600 / 60).abs();
+    let mut rows = vec![
+        small_text(
+            &t!(
+                "date_calc_diff_days",
+                days = diff.days,
+                weeks = diff.days / 7,
+                rest = diff.days % 7
+            ),
+            "date_diff_days",
+        ),
+        small_text(
+            &t!(
+                "date_calc_diff_span",
+                years = diff.years,
+                months = diff.months,
+                days = diff.rem_days
+            ),
+            "date_diff_span",
+        ),
+        small_text(
+            &t!("date_calc_diff_weekdays", count = diff.weekdays),
+            "date_diff_weekdays",
+        ),
+        small_text(
+            &t!("date_calc_diff_hours", hours = hours, minutes = minutes),
+            "date_diff_hours",
+        ),
+    ];
+    if diff.total_seconds < 0 {
+        rows.push(small_text(
+            &t!("date_calc_diff_reversed"),
+            "date_diff_reversed",
+        ));
+    }
+    let title = t!("date_calc_difference_title");
+    to_value_or_text(
+        UiCard::new(rows).title(&title).padding(12),
+        "date_diff_card",
+    )
+}
+
+fn facts_card(facts: &DateFacts) -> Value {
+    let rows = vec![
+        small_text(&weekday_name(facts.weekday), "date_facts_weekday"),
+        small_text(
+            &t!(
+                "date_calc_iso_week",
+                week = facts.iso_week,
+                weeks = facts.iso_weeks_in_year,
+                year = facts.iso_year
+            ),
+            "date_facts_week",
+        ),
+        small_text(
+            &t!(
+                "date_calc_day_of_year",
+                day = facts.day_of_year,
+                total = facts.days_in_year
+            ),
+            "date_facts_day_of_year",
+        ),
+        small_text(
+            &t!("date_calc_quarter", quarter = facts.quarter),
+            "date_facts_quarter",
+        ),
+    ];
+    let title = t!("date_calc_facts_title", date = facts.date);
+    to_value_or_text(
+        UiCard::new(rows).title(&title).padding(12),
+        "date_facts_card",
+    )
+}
+
+fn zone_line(time: &str, dst: bool) -> String {
+    let kind = if dst {
+        t!("date_tz_dst")
+    } else {
+        t!("date_tz_standard")
+    };
+    format!("{time} · {kind}")
+}
+
+pub fn render_date_calc_screen(state: &AppState) -> Value {
+    let calc = &state.date_calc;
+    let mut children = vec![
+        to_value_or_text(
+            UiText::new(&t!("date_calc_title")).size(20.0),
+            "date_calc_title",
+        ),
+        to_value_or_text(
+            UiText::new(&t!("date_calc_description")).size(14.0),
+            "date_calc_subtitle",
+        ),
+    ];
+
+    let mut dates = vec![
+        date_input("date_a", &calc.date_a, &t!("date_calc_from_hint")),
+        date_input("date_b", &calc.date_b, &t!("date_calc_to_hint")),
+    ];
+    if let Some(err) = &calc.date_error {
+        dates.push(small_text(&error_text(err), "date_error"));
+    }
+    if let Some(diff) = &calc.difference {
+        dates.push(difference_card(diff));
+    }
+    if let Some(facts) = &calc.facts {
+        dates.push(facts_card(facts));
+    }
+    let dates_title = t!("date_calc_dates_section");
+    children.push(to_value_or_text(
+        UiSection::new(dates).title(&dates_title).padding(12),
+        "date_dates_section",
+    ));
+
+    let unit_buttons = ShiftUnit::ALL
+        .iter()
+        .map(|unit| {
+            let label = t!(unit.label_key());
+            let label = if *unit == calc.shift_unit {
+                format!("✓ {label}")
+            } else {
+                label.to_string()
+            };
+            to_value_or_text(
+                UiButton::new(&label, "date_calc_compute")
+                    .payload(json!({ "date_shift_unit": unit.id() })),
+                "date_shift_unit",
+            )
+        })
+        .collect();
+    let mut shift = vec![
+        date_input(
+            "date_shift_amount",
+            &calc.shift_amount,
+            &t!("date_calc_amount_hint"),
+        ),
+        to_value_or_text(UiRow::new(unit_buttons), "date_shift_units"),
+    ];
+    if let Some(err) = &calc.shift_error {
+        shift.push(small_text(&error_text(err), "date_shift_error"));
+    }
+    if let Some(shifted) = &calc.shifted {
+        shift.push(to_value_or_text(
+            UiText::new(&t!(
+                "date_calc_shift_result",
+                date = shifted.text,
+                weekday = weekday_name(shifted.weekday)
+            ))
+            .size(14.0)
+            .content_description("date_shift_result"),
+            "date_shift_result",
+        ));
+    }
+    let shift_title = t!("date_calc_shift_section");
+    children.push(to_value_or_text(
+        UiSection::new(shift).title(&shift_title).padding(12),
+        "date_shift_section",
+    ));
+
+    let mut zones = vec![
+        date_input("date_tz_time", &calc.tz_time, &t!("date_calc_tz_time_hint")),
+        date_input("date_tz_from", &calc.from_tz, &t!("date_calc_tz_from_hint")),
+        date_input("date_tz_to", &calc.to_tz, &t!("date_calc_tz_to_hint")),
+        to_value_or_text(
+            UiButton::new(&t!("date_calc_swap_tz"), "date_calc_swap_tz"),
+            "date_swap_tz",
+        ),
+    ];
+    if let Some(err) = &calc.tz_error {
+        zones.push(small_text(&error_text(err), "date_tz_error"));
+    }
+    if let Some(conversion) = &calc.conversion {
+        zones.push(small_text(
+            &zone_line(&conversion.from, conversion.from_dst),
+            "date_tz_from_result",
+        ));
+        zones.push(to_value_or_text(
+            UiText::new(&format!(
+                "→ {}",
+                zone_line(&conversion.to, conversion.to_dst)
+            ))
+            .size(14.0)
+            .content_description("date_tz_to_result"),
+            "date_tz_to_result",
+        ));
+        if let Some(note) = &conversion.note {
+            zones.push(small_text(&t!(note.as_str()), "date_tz_note"));
+        }
+    }
+    let zones_title = t!("date_calc_tz_section");
+    children.push(to_value_or_text(
+        UiSection::new(zones).title(&zones_title).padding(12),
+        "date_tz_section",
+    ));
+
+    let mut cron = vec![date_input(
+        "date_cron",
+        &calc.cron,
+        &t!("date_calc_cron_hint"),
+    )];
+    if let Some(err) = &calc.cron_error {
+        cron.push(small_text(&error_text(err), "date_cron_error"));
+    }
+    if !calc.cron_runs.is_empty() {
+        let rows = calc
+            .cron_runs
+            .iter()
+            .map(|run| {
+                let line = format!("{} ({})", run.text, weekday_name(run.weekday));
+                small_text(&line, "date_cron_run")
+            })
+            .collect();
+        let runs_title = t!("date_calc_cron_runs");
+        cron.push(to_value_or_text(
+            UiCard::new(rows).title(&runs_title).padding(12),
+            "date_cron_runs",
+        ));
+    }
+    let cron_title = t!("date_calc_cron_section");
+    children.push(to_value_or_text(
+        UiSection::new(cron).title(&cron_title).padding(12),
+        "date_cron_section",
+    ));
+
+    children.push(json!({
+        "type": "Button",
+        "text": t!("presets_title"),
+        "action": "presets_list",
+        "id": "date_calculator_presets",
+        "payload": { "tool_id": "date_calculator" }
+    }));
+    children.push(json!({
+        "type": "Button",
+        "text": t!("presets_save_title"),
+        "action": "preset_save_dialog",
+        "id": "date_calculator_preset_save",
+        "payload": { "tool_id": "date_calculator" }
+    }));
+
+    maybe_push_back(&mut children, state);
+    to_value_or_text(UiColumn::new(children).padding(20), "date_calc_root")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(input: &str) -> NaiveDateTime {
+        parse_date_time(input).unwrap().unwrap()
+    }
+
+    fn utc(input: &str) -> DateTime<Utc> {
+        Utc.from_utc_datetime(&at(input))
+    }
+
+    #[test]
+    fn differences_count_calendar_spans_and_weekdays() {
+        let diff = date_difference(at("2024-01-31"), at("2024-03-15 06:30"));
+        assert_eq!(diff.days, 44);
+        assert_eq!((diff.years, diff.months, diff.rem_days), (0, 1, 15));
+        assert_eq!(diff.total_seconds, 44 * 86_400 + 6 * 3600 + 1800);
+        // Wednesday Jan 31 up to Friday Mar 15: six full weeks and two weekdays.
+        assert_eq!(diff.weekdays, 32);
+
+        let back = date_difference(at("2025-06-01"), at("2020-02-29"));
+        assert_eq!(back.days, -1919);
+        assert_eq!((back.years, back.months, back.rem_days), (5, 3, 3));
+        assert!(back.total_seconds < 0);
+
+        assert!(parse_date_time("").unwrap().is_none());
+        assert_eq!(
+            parse_date_time("2024-02-30").unwrap_err(),
+            "date_invalid:2024-02-30"
+        );
+    }
+
+    #[test]
+    fn shifts_clamp_months_and_report_overflow() {
+        let start = at("2024-01-31 08:00");
+        assert_eq!(
+            format_date_time(shift_date(start, 1, ShiftUnit::Months).unwrap()),
+            "2024-02-29 08:00"
+        );
+        assert_eq!(
+            format_date_time(shift_date(start, -2, ShiftUnit::Weeks).unwrap()),
+            "2024-01-17 08:00"
+        );
+        assert_eq!(
+            format_date_time(shift_date(at("2024-02-29"), 1, ShiftUnit::Years).unwrap()),
+            "2025-02-28"
+        );
+        assert_eq!(
+            shift_date(start, i32::MAX, ShiftUnit::Years).unwrap_err(),
+            "date_out_of_range"
+        );
+
+        let facts = date_facts(at("2021-01-03").date());
+        assert_eq!(
+            (facts.iso_year, facts.iso_week, facts.weekday),
+            (2020, 53, 7)
+        );
+        assert_eq!((facts.iso_weeks_in_year, facts.day_of_year), (53, 3));
+        assert_eq!((facts.days_in_year, facts.quarter), (365, 1));
+    }
+
+    #[test]
+    fn time_zones_follow_daylight_saving_rules() {
+        let paris = find_time_zone("europe/paris").unwrap();
+        let new_york = find_time_zone("America/New York").unwrap();
+        assert_eq!(
+            find_time_zone("Mars/Olympus").unwrap_err(),
+            "tz_unknown:Mars/Olympus"
+        );
+
+        let now = utc("2024-01-01");
+        let summer = convert_time_zone(Some(at("2024-07-01 18:00")), paris, new_york, now);
+        assert_eq!(summer.from, "2024-07-01 18:00 CEST (UTC+02:00)");
+        assert_eq!(summer.to, "2024-07-01 12:00 EDT (UTC-04:00)");
+        assert!(summer.from_dst && summer.to_dst && summer.note.is_none());
+
+        // Europe moved its clocks on March 31, the US three weeks earlier.
+        let gap = convert_time_zone(Some(at("2024-03-31 02:30")), paris, Tz::UTC, now);
+        assert_eq!(gap.from, "2024-03-31 03:30 CEST (UTC+02:00)");
+        assert_eq!(gap.to, "2024-03-31 01:30 UTC (UTC+00:00)");
+        assert_eq!(gap.note.as_deref(), Some("date_tz_gap"));
+
+        let repeated = convert_time_zone(Some(at("2024-10-27 02:30")), paris, Tz::UTC, now);
+        assert_eq!(repeated.to, "2024-10-27 00:30 UTC (UTC+00:00)");
+        assert_eq!(repeated.note.as_deref(), Some("date_tz_ambiguous"));
+
+        let winter = convert_time_zone(None, Tz::UTC, paris, now);
+        assert_eq!(winter.to, "2024-01-01 01:00 CET (UTC+01:00)");
+        assert!(!winter.to_dst);
+    }
+
+    #[test]
+    fn cron_previews_use_crontab_day_numbers() {
+        assert_eq!(normalize_cron("30 9 * * 1-5"), "0 30 9 * * 2-6");
+        assert_eq!(normalize_cron("0 0 * * 5-7"), "0 0 0 * * 6-7,1");
+        assert_eq!(normalize_cron("0 0 * * 1-7/2"), "0 0 0 * * 2-7/2,1");
+        assert_eq!(normalize_cron("0 0 * * 2-7/2"), "0 0 0 * * 3-7/2");
+        assert_eq!(normalize_cron("0 0 * * 0,MON"), "0 0 0 * * 1,MON");
+        assert_eq!(normalize_cron("0 0 12 * * *"), "0 0 12 * * *");
+
+        // Friday evening in Paris; the next weekday 09:30 runs are Monday on.
+        let runs = cron_preview(
+            "30 9 * * 1-5",
+            Tz::Europe__Paris,
+            utc("2024-03-29 18:00"),
+            3,
+        )
+        .unwrap();
+        let texts: Vec<String> = runs
+            .iter()
+            .map(|run| run.format("%Y-%m-%d %H:%M %Z").to_string())
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                "2024-04-01 09:30 CEST",
+                "2024-04-02 09:30 CEST",
+                "2024-04-03 09:30 CEST"
+            ]
+        );
+
+        assert!(cron_preview("61 * * * *", Tz::UTC, utc("2024-01-01"), 3)
+            .unwrap_err()
+            .starts_with("cron_invalid:"));
+        assert_eq!(
+            cron_preview("0 0 0 1 1 * 2020", Tz::UTC, utc("2024-01-01"), 3).unwrap_err(),
+            "cron_no_upcoming_runs"
+        );
+    }
+}
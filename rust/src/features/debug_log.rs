@@ -0,0 +1,103 @@
+use crate::logging::{Level, LogEntry, LOG_CAPACITY};
+use crate::state::AppState;
+use crate::ui::{maybe_push_back, Button as UiButton, Column as UiColumn, Text as UiText};
+use serde_json::Value;
+
+/// Warnings and errors shown under the summary, newest first.
+const RECENT_PROBLEMS_MAX: usize = 5;
+
+fn text(value: &str, size: f64) -> Value {
+    serde_json::to_value(UiText::new(value).size(size)).unwrap()
+}
+
+/// Error and warning counts, in that order.
+fn problem_counts(entries: &[LogEntry]) -> (usize, usize) {
+    let count = |level| entries.iter().filter(|e| e.level == level).count();
+    (count(Level::Error), count(Level::Warn))
+}
+
+fn summary(entries: &[LogEntry]) -> String {
+    let (errors, warnings) = problem_counts(entries);
+    t!(
+        "debug_log_summary",
+        entries = entries.len(),
+        capacity = LOG_CAPACITY,
+        errors = errors,
+        warnings = warnings
+    )
+    .into_owned()
+}
+
+pub fn render_debug_log_screen(state: &AppState, entries: &[LogEntry]) -> Value {
+    let mut children = vec![
+        text(&t!("debug_log_title"), 20.0),
+        text(&t!("debug_log_description"), 12.0),
+        text(&summary(entries), 14.0),
+    ];
+
+    let problems: Vec<Value> = entries
+        .iter()
+        .rev()
+        .filter(|e| e.level >= Level::Warn)
+        .take(RECENT_PROBLEMS_MAX)
+        .map(|e| text(&e.line(), 12.0))
+        .collect();
+    if !problems.is_empty() {
+        children.push(text(&t!("debug_log_latest_problems"), 16.0));
+        children.extend(problems);
+    }
+
+    if entries.is_empty() {
+        children.push(text(&t!("debug_log_empty"), 14.0));
+    } else {
+        children.push(
+            serde_json::to_value(UiButton::new(
+                &t!("debug_log_view_button"),
+                "debug_log_view",
+            ))
+            .unwrap(),
+        );
+        children.push(
+            serde_json::to_value(UiButton::new(
+                &t!("debug_log_export_button"),
+                "debug_log_export",
+            ))
+            .unwrap(),
+        );
+        children.push(
+            serde_json::to_value(UiButton::new(
+                &t!("debug_log_clear_button"),
+                "debug_log_clear",
+            ))
+            .unwrap(),
+        );
+    }
+
+    maybe_push_back(&mut children, state);
+    serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: Level, message: &str) -> LogEntry {
+        LogEntry {
+            at_ms: 0,
+            level,
+            tag: "test",
+            message: message.into(),
+        }
+    }
+
+    #[test]
+    fn summary_counts_problems_by_level() {
+        let entries = [
+            entry(Level::Info, "started"),
+            entry(Level::Warn, "slow"),
+            entry(Level::Error, "failed"),
+            entry(Level::Error, "failed again"),
+        ];
+        assert_eq!(problem_counts(&entries), (2, 1));
+    }
+}
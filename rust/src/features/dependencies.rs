@@ -106,22 +106,20 @@ pub fn render_dependencies_list(state: &DependencyState) -> Value {
     let mut items: Vec<Value> = Vec::new();
 
     if deps.is_empty() {
-        items.push(
-            to_value(UiText::new("Dependencies unavailable").size(12.0)).unwrap(),
-        );
+        items.push(to_value(UiText::new(&t!("dependencies_unavailable")).size(12.0)).unwrap());
     } else if featured.is_empty() && grouped.is_empty() {
         let query = state.query.trim();
         let message = if query.is_empty() {
-            "No dependencies available".to_string()
+            t!("dependencies_none")
         } else {
-            format!("No dependencies match \"{query}\"")
+            t!("dependencies_no_match", query = query)
         };
         items.push(to_value(UiText::new(&message).size(12.0)).unwrap());
     } else {
         // Render Featured Section
         if !featured.is_empty() {
             let mut section_children = vec![to_value(
-                UiText::new("Featured Components").size(16.0), // Slightly larger/bolder
+                UiText::new(&t!("dependencies_featured")).size(16.0), // Slightly larger/bolder
             )
             .unwrap()];
             for dep in featured {
@@ -1,15 +1,26 @@
+use crate::error::error_text;
+use crate::features::color_tools::{extract_palette, parse_hex_list};
+use crate::features::image_resize::open_oriented;
 use crate::features::storage::{output_dir_for, preferred_temp_dir};
 use crate::state::{AppState, DitheringMode, DitheringPalette};
-use crate::ui::{maybe_push_back, Button as UiButton, Column as UiColumn, Text as UiText};
+use crate::ui::{
+    maybe_push_back, Button as UiButton, Checkbox as UiCheckbox, ColorSwatch as UiColorSwatch,
+    Column as UiColumn, Image as UiImage, Row as UiRow, Text as UiText, TextInput as UiTextInput,
+};
+use image::imageops::FilterType;
 use image::{Rgba, RgbaImage};
 use serde_json::{json, Value};
 use std::fs;
 use std::fs::File;
 use std::os::unix::io::{FromRawFd, RawFd};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use tempfile::Builder;
 use rust_i18n::t;
 
+/// Keeps result previews from pushing the screen's buttons out of view; tapping opens them full size.
+pub(crate) const RESULT_PREVIEW_MAX_HEIGHT_DP: u32 = 360;
+
 const MONOCHROME: &[[u8; 3]] = &[[0, 0, 0], [255, 255, 255]];
 const CGA: &[[u8; 3]] = &[[0, 0, 0], [85, 255, 255], [255, 85, 255], [255, 255, 85]];
 const GAME_BOY: &[[u8; 3]] = &[[15, 56, 15], [48, 98, 48], [139, 172, 15], [155, 188, 15]];
@@ -50,15 +61,105 @@ const ATKINSON_KERNEL: &[(i32, i32, f32)] = &[
     (0, 2, 1.0 / 8.0),
 ];
 
-fn palette_colors(palette: DitheringPalette) -> &'static [[u8; 3]] {
+const JARVIS_KERNEL: &[(i32, i32, f32)] = &[
+    (1, 0, 7.0 / 48.0),
+    (2, 0, 5.0 / 48.0),
+    (-2, 1, 3.0 / 48.0),
+    (-1, 1, 5.0 / 48.0),
+    (0, 1, 7.0 / 48.0),
+    (1, 1, 5.0 / 48.0),
+    (2, 1, 3.0 / 48.0),
+    (-2, 2, 1.0 / 48.0),
+    (-1, 2, 3.0 / 48.0),
+    (0, 2, 5.0 / 48.0),
+    (1, 2, 3.0 / 48.0),
+    (2, 2, 1.0 / 48.0),
+];
+
+const STUCKI_KERNEL: &[(i32, i32, f32)] = &[
+    (1, 0, 8.0 / 42.0),
+    (2, 0, 4.0 / 42.0),
+    (-2, 1, 2.0 / 42.0),
+    (-1, 1, 4.0 / 42.0),
+    (0, 1, 8.0 / 42.0),
+    (1, 1, 4.0 / 42.0),
+    (2, 1, 2.0 / 42.0),
+    (-2, 2, 1.0 / 42.0),
+    (-1, 2, 2.0 / 42.0),
+    (0, 2, 4.0 / 42.0),
+    (1, 2, 2.0 / 42.0),
+    (2, 2, 1.0 / 42.0),
+];
+
+const BURKES_KERNEL: &[(i32, i32, f32)] = &[
+    (1, 0, 8.0 / 32.0),
+    (2, 0, 4.0 / 32.0),
+    (-2, 1, 2.0 / 32.0),
+    (-1, 1, 4.0 / 32.0),
+    (0, 1, 8.0 / 32.0),
+    (1, 1, 4.0 / 32.0),
+    (2, 1, 2.0 / 32.0),
+];
+
+/// Custom palettes above this size gain little over the source image and slow every pixel down.
+pub const MAX_CUSTOM_COLORS: usize = 64;
+/// Number of colors pulled from an image by "palette from image".
+pub const EXTRACTED_PALETTE_SIZE: usize = 8;
+
+const BLUE_NOISE_SIZE: usize = 32;
+const BLUE_NOISE_SIGMA: f32 = 1.5;
+
+pub(crate) fn palette_colors(palette: &DitheringPalette) -> &[[u8; 3]] {
     match palette {
         DitheringPalette::Monochrome => MONOCHROME,
         DitheringPalette::Cga => CGA,
         DitheringPalette::GameBoy => GAME_BOY,
+        DitheringPalette::Custom(colors) => colors,
     }
 }
 
-fn nearest_color(palette: &[[u8; 3]], r: f32, g: f32, b: f32) -> [u8; 3] {
+/// Parses the custom palette field (`#RRGGBB` entries) into a palette.
+pub fn parse_custom_palette(raw: &str) -> Result<DitheringPalette, String> {
+    let colors = parse_hex_list(raw).map_err(|e| format!("dithering_palette_invalid:{e}"))?;
+    if colors.len() < 2 {
+        return Err("dithering_palette_too_small".into());
+    }
+    if colors.len() > MAX_CUSTOM_COLORS {
+        return Err(format!("dithering_palette_too_large:{MAX_CUSTOM_COLORS}"));
+    }
+    Ok(DitheringPalette::Custom(
+        colors.iter().map(|c| [c.r, c.g, c.b]).collect(),
+    ))
+}
+
+/// Extracts the dominant colors of the image at `path` as a custom palette.
+pub fn palette_from_image(path: &str) -> Result<DitheringPalette, String> {
+    let mut img = open_oriented(path)?;
+    if img.width() > 128 || img.height() > 128 {
+        // Nearest keeps real source colors instead of inventing blends at edges.
+        img = img.resize(128, 128, FilterType::Nearest);
+    }
+    let img = img.to_rgb8();
+    let pixels: Vec<[u8; 3]> = img.pixels().map(|p| p.0).collect();
+    let colors = extract_palette(&pixels, EXTRACTED_PALETTE_SIZE);
+    if colors.len() < 2 {
+        return Err("dithering_palette_too_small".into());
+    }
+    Ok(DitheringPalette::Custom(
+        colors.iter().map(|c| [c.r, c.g, c.b]).collect(),
+    ))
+}
+
+/// Formats palette colors back into the text accepted by [`parse_custom_palette`].
+pub fn palette_hex(colors: &[[u8; 3]]) -> String {
+    colors
+        .iter()
+        .map(|[r, g, b]| format!("#{r:02X}{g:02X}{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub(crate) fn nearest_color(palette: &[[u8; 3]], r: f32, g: f32, b: f32) -> [u8; 3] {
     let mut best = palette[0];
     let mut best_dist = f32::MAX;
     for color in palette {
@@ -78,6 +179,7 @@ fn apply_error_diffusion(
     input: &RgbaImage,
     palette: &[[u8; 3]],
     kernel: &[(i32, i32, f32)],
+    serpentine: bool,
 ) -> RgbaImage {
     let width = input.width() as i32;
     let height = input.height() as i32;
@@ -88,7 +190,11 @@ fn apply_error_diffusion(
     let mut output = RgbaImage::new(input.width(), input.height());
 
     for y in 0..height {
-        for x in 0..width {
+        // Serpentine scanning walks odd rows right-to-left and mirrors the kernel,
+        // which breaks up the diagonal "worm" artifacts of a plain raster scan.
+        let reverse = serpentine && y % 2 == 1;
+        for step in 0..width {
+            let x = if reverse { width - 1 - step } else { step };
             let idx = (y * width + x) as usize;
             let [r, g, b] = buffer[idx];
             let src_alpha = input.get_pixel(x as u32, y as u32)[3];
@@ -104,7 +210,8 @@ fn apply_error_diffusion(
                 b - target[2] as f32,
             ];
             for (dx, dy, factor) in kernel {
-                add_error(&mut buffer, width, height, x + *dx, y + *dy, err, *factor);
+                let dx = if reverse { -*dx } else { *dx };
+                add_error(&mut buffer, width, height, x + dx, y + *dy, err, *factor);
             }
         }
     }
@@ -134,14 +241,32 @@ fn apply_bayer<const N: usize>(
     palette: &[[u8; 3]],
     matrix: &[[i32; N]; N],
 ) -> RgbaImage {
-    let mut output = RgbaImage::new(input.width(), input.height());
     let scale = (N * N) as f32;
+    apply_ordered(input, palette, |x, y| {
+        (matrix[(y as usize) % N][(x as usize) % N] as f32 + 0.5) / scale - 0.5
+    })
+}
+
+fn apply_blue_noise(input: &RgbaImage, palette: &[[u8; 3]]) -> RgbaImage {
+    let ranks = blue_noise_ranks();
+    let scale = ranks.len() as f32;
+    apply_ordered(input, palette, |x, y| {
+        let idx = (y as usize % BLUE_NOISE_SIZE) * BLUE_NOISE_SIZE + x as usize % BLUE_NOISE_SIZE;
+        (ranks[idx] as f32 + 0.5) / scale - 0.5
+    })
+}
+
+fn apply_ordered(
+    input: &RgbaImage,
+    palette: &[[u8; 3]],
+    threshold_at: impl Fn(u32, u32) -> f32,
+) -> RgbaImage {
+    let mut output = RgbaImage::new(input.width(), input.height());
 
     for (idx, pixel) in input.pixels().enumerate() {
         let x = (idx as u32) % input.width();
         let y = (idx as u32) / input.width();
-        let threshold = (matrix[(y as usize) % N][(x as usize) % N] as f32 + 0.5) / scale - 0.5;
-        let adjust = threshold * 255.0;
+        let adjust = threshold_at(x, y) * 255.0;
         let r = (pixel[0] as f32 + adjust).clamp(0.0, 255.0);
         let g = (pixel[1] as f32 + adjust).clamp(0.0, 255.0);
         let b = (pixel[2] as f32 + adjust).clamp(0.0, 255.0);
@@ -152,21 +277,117 @@ fn apply_bayer<const N: usize>(
     output
 }
 
+/// Tileable blue-noise threshold ranks built once with Ulichney's void-and-cluster method.
+fn blue_noise_ranks() -> &'static [u32] {
+    static RANKS: OnceLock<Vec<u32>> = OnceLock::new();
+    RANKS.get_or_init(|| void_and_cluster(BLUE_NOISE_SIZE, BLUE_NOISE_SIGMA))
+}
+
+fn void_and_cluster(size: usize, sigma: f32) -> Vec<u32> {
+    let total = size * size;
+    let mut weights = vec![0.0f32; total];
+    for dy in 0..size {
+        for dx in 0..size {
+            let wx = dx.min(size - dx) as f32;
+            let wy = dy.min(size - dy) as f32;
+            weights[dy * size + dx] = (-(wx * wx + wy * wy) / (2.0 * sigma * sigma)).exp();
+        }
+    }
+    let toggle = |pattern: &mut [bool], energy: &mut [f32], idx: usize, on: bool| {
+        pattern[idx] = on;
+        let sign = if on { 1.0 } else { -1.0 };
+        let (px, py) = (idx % size, idx / size);
+        for y in 0..size {
+            for x in 0..size {
+                let dx = (x + size - px) % size;
+                let dy = (y + size - py) % size;
+                energy[y * size + x] += sign * weights[dy * size + dx];
+            }
+        }
+    };
+    let tightest_cluster = |pattern: &[bool], energy: &[f32]| {
+        (0..total)
+            .filter(|&i| pattern[i])
+            .max_by(|&a, &b| energy[a].total_cmp(&energy[b]))
+    };
+    let largest_void = |pattern: &[bool], energy: &[f32]| {
+        (0..total)
+            .filter(|&i| !pattern[i])
+            .min_by(|&a, &b| energy[a].total_cmp(&energy[b]))
+    };
+
+    // Seed ~10% of the cells with a fixed xorshift sequence so the matrix is deterministic.
+    let mut pattern = vec![false; total];
+    let mut energy = vec![0.0f32; total];
+    let mut seed: u32 = 0x9E37_79B9;
+    let mut ones = 0;
+    while ones < total / 10 {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        let idx = seed as usize % total;
+        if !pattern[idx] {
+            toggle(&mut pattern, &mut energy, idx, true);
+            ones += 1;
+        }
+    }
+
+    // Spread the seed points until moving the tightest cluster no longer changes anything.
+    for _ in 0..total {
+        let Some(cluster) = tightest_cluster(&pattern, &energy) else {
+            break;
+        };
+        toggle(&mut pattern, &mut energy, cluster, false);
+        let Some(void) = largest_void(&pattern, &energy) else {
+            break;
+        };
+        toggle(&mut pattern, &mut energy, void, true);
+        if void == cluster {
+            break;
+        }
+    }
+
+    let mut ranks = vec![0u32; total];
+    let (mut work, mut work_energy) = (pattern.clone(), energy.clone());
+    for rank in (0..ones).rev() {
+        if let Some(cluster) = tightest_cluster(&work, &work_energy) {
+            toggle(&mut work, &mut work_energy, cluster, false);
+            ranks[cluster] = rank as u32;
+        }
+    }
+    for rank in ones..total {
+        if let Some(void) = largest_void(&pattern, &energy) {
+            toggle(&mut pattern, &mut energy, void, true);
+            ranks[void] = rank as u32;
+        }
+    }
+    ranks
+}
+
 pub fn process_dithering(
     path: &str,
     mode: DitheringMode,
-    palette: DitheringPalette,
+    palette: &DitheringPalette,
+    serpentine: bool,
     output_dir: Option<&str>,
 ) -> Result<String, String> {
-    let img = image::open(path).map_err(|e| format!("open_failed:{e}"))?;
-    let rgba = img.to_rgba8();
     let palette = palette_colors(palette);
+    if palette.is_empty() {
+        return Err("dithering_palette_too_small".into());
+    }
+    let img = open_oriented(path)?;
+    let rgba = img.to_rgba8();
+    let diffuse = |kernel| apply_error_diffusion(&rgba, palette, kernel, serpentine);
     let processed = match mode {
-        DitheringMode::FloydSteinberg => apply_error_diffusion(&rgba, palette, FLOYD_KERNEL),
-        DitheringMode::Sierra => apply_error_diffusion(&rgba, palette, SIERRA_KERNEL),
-        DitheringMode::Atkinson => apply_error_diffusion(&rgba, palette, ATKINSON_KERNEL),
+        DitheringMode::FloydSteinberg => diffuse(FLOYD_KERNEL),
+        DitheringMode::Sierra => diffuse(SIERRA_KERNEL),
+        DitheringMode::Atkinson => diffuse(ATKINSON_KERNEL),
+        DitheringMode::JarvisJudiceNinke => diffuse(JARVIS_KERNEL),
+        DitheringMode::Stucki => diffuse(STUCKI_KERNEL),
+        DitheringMode::Burkes => diffuse(BURKES_KERNEL),
         DitheringMode::Bayer4x4 => apply_bayer(&rgba, palette, &BAYER_4X4),
         DitheringMode::Bayer8x8 => apply_bayer(&rgba, palette, &BAYER_8X8),
+        DitheringMode::BlueNoise => apply_blue_noise(&rgba, palette),
     };
 
     let target_dir = output_dir
@@ -290,6 +511,21 @@ pub fn render_dithering_screen(state: &AppState) -> Value {
             "dithering_mode_fs",
         ),
         (DitheringMode::Sierra, &t!("dithering_mode_sierra"), "dithering_mode_sierra"),
+        (
+            DitheringMode::JarvisJudiceNinke,
+            &t!("dithering_mode_jjn"),
+            "dithering_mode_jjn",
+        ),
+        (
+            DitheringMode::Stucki,
+            &t!("dithering_mode_stucki"),
+            "dithering_mode_stucki",
+        ),
+        (
+            DitheringMode::Burkes,
+            &t!("dithering_mode_burkes"),
+            "dithering_mode_burkes",
+        ),
         (
             DitheringMode::Bayer4x4,
             &t!("dithering_mode_bayer4"),
@@ -300,6 +536,11 @@ pub fn render_dithering_screen(state: &AppState) -> Value {
             &t!("dithering_mode_bayer8"),
             "dithering_mode_bayer8",
         ),
+        (
+            DitheringMode::BlueNoise,
+            &t!("dithering_mode_blue_noise"),
+            "dithering_mode_blue_noise",
+        ),
     ];
     children.push(serde_json::to_value(UiText::new(&t!("dithering_algorithm_section")).size(14.0)).unwrap());
     for (mode, label, action) in modes {
@@ -309,6 +550,15 @@ pub fn render_dithering_screen(state: &AppState) -> Value {
         }
         children.push(serde_json::to_value(button).unwrap());
     }
+    children.push(
+        serde_json::to_value(
+            UiCheckbox::new(&t!("dithering_serpentine_checkbox"), "dithering_serpentine")
+                .checked(state.dithering_serpentine)
+                .action("dithering_serpentine_toggle")
+                .content_description("dithering_serpentine"),
+        )
+        .unwrap(),
+    );
 
     let palettes = [
         (
@@ -331,13 +581,59 @@ pub fn render_dithering_screen(state: &AppState) -> Value {
         }
         children.push(serde_json::to_value(button).unwrap());
     }
+    children.push(
+        serde_json::to_value(
+            UiTextInput::new("dithering_custom_palette")
+                .text(&state.dithering_custom_palette)
+                .hint(&t!("dithering_custom_palette_hint"))
+                .action_on_submit("dithering_palette_custom"),
+        )
+        .unwrap(),
+    );
+    let custom_label = t!("dithering_palette_custom");
+    let mut custom =
+        UiButton::new(&custom_label, "dithering_palette_custom").id("dithering_palette_custom");
+    if matches!(state.dithering_palette, DitheringPalette::Custom(_)) {
+        custom = custom.content_description("selected");
+    }
+    children.push(serde_json::to_value(custom).unwrap());
+    children.push(
+        serde_json::to_value(
+            UiButton::new(
+                &t!("dithering_palette_from_image_button"),
+                "dithering_palette_from_image",
+            )
+            .requires_file_picker(true)
+            .id("dithering_palette_from_image"),
+        )
+        .unwrap(),
+    );
+    if let DitheringPalette::Custom(colors) = &state.dithering_palette {
+        let swatches = colors
+            .iter()
+            .map(|[r, g, b]| {
+                let color =
+                    (0xFF000000u32 | (*r as u32) << 16 | (*g as u32) << 8 | *b as u32) as i64;
+                serde_json::to_value(UiColorSwatch::new(color)).unwrap()
+            })
+            .collect();
+        children.push(serde_json::to_value(UiRow::new(swatches)).unwrap());
+    }
 
     if let Some(err) = &state.dithering_error {
-        children
-            .push(serde_json::to_value(UiText::new(&format!("{}{}", t!("multi_hash_error_prefix"), err)).size(12.0)).unwrap());
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
     }
 
     if let Some(result) = &state.dithering_result_path {
+        children.push(
+            serde_json::to_value(
+                UiImage::from_path(result)
+                    .zoomable(true)
+                    .max_height_dp(RESULT_PREVIEW_MAX_HEIGHT_DP)
+                    .content_description("dithering_result_image"),
+            )
+            .unwrap(),
+        );
         children.push(
             serde_json::to_value(
                 UiButton::new(&t!("dithering_copy_result_path_button"), "copy_clipboard")
@@ -353,7 +649,7 @@ pub fn render_dithering_screen(state: &AppState) -> Value {
             serde_json::to_value(
                 UiButton::new(&t!("dithering_apply_button"), "dithering_apply")
                     .id("dithering_apply")
-                    .content_description("Apply dithering"),
+                    .content_description(&t!("dithering_apply_content_description")),
             )
             .unwrap(),
         );
@@ -383,8 +679,8 @@ mod tests {
     #[test]
     fn atkinson_monochrome_produces_dithered_output() {
         let src = load_app_icon();
-        let palette = palette_colors(DitheringPalette::Monochrome);
-        let out = apply_error_diffusion(&src, palette, ATKINSON_KERNEL);
+        let palette = palette_colors(&DitheringPalette::Monochrome);
+        let out = apply_error_diffusion(&src, palette, ATKINSON_KERNEL, false);
 
         assert_eq!(out.dimensions(), src.dimensions());
 
@@ -407,7 +703,7 @@ mod tests {
 
     #[test]
     fn palette_quantization_picks_expected_color() {
-        let palette = palette_colors(DitheringPalette::Monochrome);
+        let palette = palette_colors(&DitheringPalette::Monochrome);
         let white = nearest_color(palette, 255.0, 255.0, 255.0);
         assert_eq!(white, [255, 255, 255]);
 
@@ -418,8 +714,8 @@ mod tests {
     #[test]
     fn zero_sized_image_is_handled() {
         let input = RgbaImage::new(0, 0);
-        let palette = palette_colors(DitheringPalette::Monochrome);
-        let out = apply_error_diffusion(&input, palette, ATKINSON_KERNEL);
+        let palette = palette_colors(&DitheringPalette::Monochrome);
+        let out = apply_error_diffusion(&input, palette, ATKINSON_KERNEL, false);
         assert_eq!(out.dimensions(), (0, 0));
     }
 
@@ -430,9 +726,102 @@ mod tests {
         let result = process_dithering(
             tmp.path().to_str().unwrap(),
             DitheringMode::Atkinson,
-            DitheringPalette::Monochrome,
+            &DitheringPalette::Monochrome,
+            false,
             None,
         );
         assert!(result.is_err(), "expected error, got {:?}", result);
     }
+
+    #[test]
+    fn new_kernels_distribute_the_full_error() {
+        for kernel in [JARVIS_KERNEL, STUCKI_KERNEL, BURKES_KERNEL] {
+            let sum: f32 = kernel.iter().map(|(_, _, f)| f).sum();
+            assert!((sum - 1.0).abs() < 1e-5, "kernel weights sum to {sum}");
+            assert!(kernel.iter().all(|(dx, dy, _)| *dy > 0 || *dx > 0));
+        }
+    }
+
+    #[test]
+    fn serpentine_scan_keeps_palette_and_differs_from_raster() {
+        let src = load_app_icon();
+        let palette = palette_colors(&DitheringPalette::Monochrome);
+        let raster = apply_error_diffusion(&src, palette, STUCKI_KERNEL, false);
+        let serpentine = apply_error_diffusion(&src, palette, STUCKI_KERNEL, true);
+
+        assert_eq!(serpentine.dimensions(), src.dimensions());
+        assert!(serpentine
+            .pixels()
+            .all(|p| palette.contains(&[p[0], p[1], p[2]])));
+        assert_ne!(raster, serpentine);
+    }
+
+    #[test]
+    fn blue_noise_ranks_are_a_permutation() {
+        let ranks = blue_noise_ranks();
+        assert_eq!(ranks.len(), BLUE_NOISE_SIZE * BLUE_NOISE_SIZE);
+        let unique: HashSet<u32> = ranks.iter().copied().collect();
+        assert_eq!(unique.len(), ranks.len());
+        assert!(ranks.iter().all(|r| (*r as usize) < ranks.len()));
+    }
+
+    #[test]
+    fn blue_noise_renders_mid_gray_as_an_even_mix() {
+        let input = RgbaImage::from_pixel(64, 64, Rgba([128, 128, 128, 255]));
+        let out = apply_blue_noise(&input, palette_colors(&DitheringPalette::Monochrome));
+        let white = out.pixels().filter(|p| p[0] == 255).count();
+        let ratio = white as f32 / (64.0 * 64.0);
+        assert!((0.45..=0.55).contains(&ratio), "white ratio {ratio}");
+    }
+
+    #[test]
+    fn custom_palette_parses_and_round_trips() {
+        let palette = parse_custom_palette("#0F380F, #9BBC0F").unwrap();
+        let DitheringPalette::Custom(colors) = &palette else {
+            panic!("expected custom palette");
+        };
+        assert_eq!(colors, &vec![[15, 56, 15], [155, 188, 15]]);
+        assert_eq!(palette_hex(colors), "#0F380F, #9BBC0F");
+
+        assert_eq!(
+            parse_custom_palette("#000000").unwrap_err(),
+            "dithering_palette_too_small"
+        );
+        assert_eq!(
+            parse_custom_palette("#000000, teal").unwrap_err(),
+            "dithering_palette_invalid:invalid_hex_length:teal"
+        );
+    }
+
+    #[test]
+    fn custom_palette_limits_output_colors() {
+        let src = load_app_icon();
+        let palette = DitheringPalette::Custom(vec![[20, 20, 60], [240, 200, 120], [90, 160, 90]]);
+        let colors = palette_colors(&palette);
+        let out = apply_error_diffusion(&src, colors, FLOYD_KERNEL, true);
+        assert!(out.pixels().all(|p| colors.contains(&[p[0], p[1], p[2]])));
+    }
+
+    #[test]
+    fn palette_from_image_picks_dominant_colors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("split.png");
+        image::RgbImage::from_fn(40, 20, |x, _| {
+            if x < 20 {
+                image::Rgb([200, 30, 30])
+            } else {
+                image::Rgb([30, 30, 200])
+            }
+        })
+        .save(&path)
+        .unwrap();
+
+        let DitheringPalette::Custom(colors) = palette_from_image(path.to_str().unwrap()).unwrap()
+        else {
+            panic!("expected custom palette");
+        };
+        assert_eq!(colors.len(), 2);
+        assert!(colors.contains(&[200, 30, 30]));
+        assert!(colors.contains(&[30, 30, 200]));
+    }
 }
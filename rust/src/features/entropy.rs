@@ -0,0 +1,388 @@
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Blocks sampled across the file; small files are read entirely.
+const SAMPLE_BLOCKS: u64 = 16;
+const SAMPLE_BLOCK_BYTES: u64 = 4096;
+/// Above this (out of 8 bits/byte) data is indistinguishable from compressed or random bytes.
+const HIGH_ENTROPY_BITS: f64 = 7.5;
+/// Fewer bytes than this cannot reach a meaningful entropy figure.
+const MIN_ENTROPY_SAMPLE: u64 = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentVerdict {
+    Empty,
+    Text,
+    /// Mostly zero bytes: disk images, preallocated or padded files.
+    Sparse,
+    /// Low-to-medium entropy binary such as executables or databases.
+    Binary,
+    /// High entropy in a format that is compressed by design (archives, images, media).
+    Compressed,
+    /// High entropy without a recognised container.
+    LikelyEncrypted,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntropyReport {
+    /// Shannon entropy of all sampled bytes, in bits per byte (0..=8).
+    pub bits_per_byte: f64,
+    pub block_min: f64,
+    pub block_max: f64,
+    /// Entropy of each sampled block, in file order.
+    #[serde(default)]
+    pub block_bits: Vec<f64>,
+    pub sampled_bytes: u64,
+    /// Share of printable ASCII (plus tab, CR and LF) in the sample.
+    pub printable_ratio: f64,
+    pub zero_ratio: f64,
+    pub verdict: ContentVerdict,
+}
+
+fn entropy(counts: &[u64; 256], total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    let total = total as f64;
+    counts
+        .iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Shannon entropy of `bytes`, in bits per byte.
+pub fn shannon_entropy(bytes: &[u8]) -> f64 {
+    let mut counts = [0u64; 256];
+    for &b in bytes {
+        counts[b as usize] += 1;
+    }
+    entropy(&counts, bytes.len() as u64)
+}
+
+/// True when `block` is UTF-8, tolerating a sequence cut at either edge of the sample.
+fn looks_like_utf8(block: &[u8]) -> bool {
+    let start = block
+        .iter()
+        .take(3)
+        .take_while(|b| (**b & 0xC0) == 0x80)
+        .count();
+    match std::str::from_utf8(&block[start..]) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+fn is_control(b: u8) -> bool {
+    (b < 0x20 && !matches!(b, b'\t' | b'\n' | b'\r')) || b == 0x7F
+}
+
+/// Samples up to 16 evenly spaced 4 KiB blocks and classifies the content.
+/// `compressed_format` is whether the detected type is compressed by design, which
+/// turns a high-entropy verdict from "likely encrypted" into "compressed".
+pub fn sample_entropy<R: Read + Seek>(
+    reader: &mut R,
+    size: u64,
+    compressed_format: bool,
+) -> io::Result<EntropyReport> {
+    let blocks = size.div_ceil(SAMPLE_BLOCK_BYTES).clamp(1, SAMPLE_BLOCKS);
+    let stride = if blocks > 1 {
+        size.saturating_sub(SAMPLE_BLOCK_BYTES) / (blocks - 1)
+    } else {
+        0
+    };
+
+    let mut counts = [0u64; 256];
+    let mut total = 0u64;
+    let mut block_min = f64::INFINITY;
+    let mut block_max = 0.0f64;
+    let mut block_bits = Vec::with_capacity(blocks as usize);
+    let mut utf8 = true;
+    let mut buf = vec![0u8; SAMPLE_BLOCK_BYTES as usize];
+    for i in 0..blocks {
+        reader.seek(SeekFrom::Start(i * stride))?;
+        let mut filled = 0;
+        while filled < buf.len() {
+            match reader.read(&mut buf[filled..])? {
+                0 => break,
+                n => filled += n,
+            }
+        }
+        let block = &buf[..filled];
+        if block.is_empty() {
+            continue;
+        }
+        let block_entropy = shannon_entropy(block);
+        block_min = block_min.min(block_entropy);
+        block_max = block_max.max(block_entropy);
+        block_bits.push(block_entropy);
+        utf8 &= looks_like_utf8(block);
+        for &b in block {
+            counts[b as usize] += 1;
+        }
+        total += filled as u64;
+    }
+
+    let ratio = |n: u64| {
+        if total == 0 {
+            0.0
+        } else {
+            n as f64 / total as f64
+        }
+    };
+    let printable = counts
+        .iter()
+        .enumerate()
+        .filter(|(b, _)| (0x20..0x7F).contains(b) || matches!(*b as u8, b'\t' | b'\n' | b'\r'))
+        .map(|(_, c)| c)
+        .sum();
+    let control = (0..=255u8)
+        .filter(|b| is_control(*b))
+        .map(|b| counts[b as usize])
+        .sum();
+    let bits_per_byte = entropy(&counts, total);
+    let zero_ratio = ratio(counts[0]);
+
+    let verdict = if total == 0 {
+        ContentVerdict::Empty
+    } else if utf8 && ratio(control) < 0.01 {
+        ContentVerdict::Text
+    } else if zero_ratio >= 0.5 {
+        ContentVerdict::Sparse
+    } else if total >= MIN_ENTROPY_SAMPLE && bits_per_byte >= HIGH_ENTROPY_BITS {
+        if compressed_format {
+            ContentVerdict::Compressed
+        } else {
+            ContentVerdict::LikelyEncrypted
+        }
+    } else {
+        ContentVerdict::Binary
+    };
+
+    Ok(EntropyReport {
+        bits_per_byte,
+        block_min: if total == 0 { 0.0 } else { block_min },
+        block_max,
+        block_bits,
+        sampled_bytes: total,
+        printable_ratio: ratio(printable),
+        zero_ratio,
+        verdict,
+    })
+}
+
+/// Content sniffing for text-based and armored formats that magic-byte detection misses.
+/// Returns the MIME type and its usual extension (`None` when any extension is fine).
+pub fn guess_mime_from_content(head: &[u8]) -> Option<(&'static str, Option<&'static str>)> {
+    if head.starts_with(b"age-encryption.org/v1\n") {
+        return Some(("application/x-age", Some("age")));
+    }
+    let text = match std::str::from_utf8(head) {
+        Ok(text) => text,
+        // A sample cut inside a multi-byte character is still text.
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&head[..e.valid_up_to()]).ok()?,
+        Err(_) => return None,
+    };
+    if text.bytes().any(is_control) {
+        return None;
+    }
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    if text.is_empty() {
+        return None;
+    }
+    let lower = text
+        .chars()
+        .take(64)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let guess = if text.starts_with("-----BEGIN AGE ENCRYPTED FILE-----") {
+        ("application/x-age", Some("age"))
+    } else if text.starts_with("-----BEGIN ") {
+        ("application/x-pem-file", Some("pem"))
+    } else if text.starts_with('{') || text.starts_with('[') {
+        ("application/json", Some("json"))
+    } else if lower.starts_with("<!doctype html") || lower.starts_with("<html") {
+        ("text/html", Some("html"))
+    } else if lower.starts_with("<svg") || (lower.starts_with("<?xml") && text.contains("<svg")) {
+        ("image/svg+xml", Some("svg"))
+    } else if lower.starts_with("<?xml") {
+        ("application/xml", Some("xml"))
+    } else if text.starts_with("#!") {
+        ("text/x-script", None)
+    } else if text.starts_with("BEGIN:VCARD") {
+        ("text/vcard", Some("vcf"))
+    } else if text.starts_with("BEGIN:VCALENDAR") {
+        ("text/calendar", Some("ics"))
+    } else if looks_like_csv(text) {
+        ("text/csv", Some("csv"))
+    } else {
+        ("text/plain", None)
+    };
+    Some(guess)
+}
+
+/// At least two lines with the same, non-zero number of commas.
+fn looks_like_csv(text: &str) -> bool {
+    let mut lines = text.lines().filter(|l| !l.trim().is_empty());
+    let commas = |l: &str| l.matches(',').count();
+    match (lines.next(), lines.next()) {
+        (Some(first), Some(second)) => commas(first) > 0 && commas(first) == commas(second),
+        _ => false,
+    }
+}
+
+/// Extensions that are equally valid for the same detected type.
+const EXTENSION_ALIASES: &[&[&str]] = &[
+    &["jpg", "jpeg", "jpe", "jfif"],
+    &["tif", "tiff", "dng"],
+    &["htm", "html", "xhtml"],
+    &["mp4", "m4v", "m4a", "m4b", "m4p", "f4v", "mov", "3gp"],
+    &["heic", "heif"],
+    &["aiff", "aif", "aifc"],
+    &["mid", "midi", "kar"],
+    &["mpg", "mpeg", "mpe", "m2p", "vob"],
+    &["ts", "m2ts", "mts"],
+    &["asf", "wmv", "wma"],
+    &["ogg", "oga", "ogv", "opus"],
+    &["mkv", "mka", "webm"],
+    &["gz", "tgz"],
+    &["bz2", "tbz", "tbz2"],
+    &["xz", "txz"],
+    &["zst", "tzst"],
+    &["rar", "cbr"],
+    &["7z", "cb7"],
+    &["ttf", "otf"],
+    &["eps", "ps", "epsf"],
+    &["mobi", "azw", "azw3", "prc"],
+    &["sqlite", "sqlite3", "db", "db3"],
+    &["h5", "hdf5", "he5"],
+    &["img", "iso", "raw", "bin", "dd"],
+    // OLE2 compound files.
+    &["doc", "dot", "xls", "xlt", "ppt", "pps", "msi", "msg", "vsd", "mpp"],
+    // PE images.
+    &["exe", "dll", "sys", "scr", "ocx", "cpl", "efi"],
+    &["xml", "gpx", "kml", "plist", "xsd", "xsl", "rss", "atom"],
+    &["json", "geojson", "ipynb", "webmanifest"],
+    &["pem", "crt", "cer", "der", "key", "csr", "pub"],
+    &["asc", "gpg", "pgp", "sig"],
+    // Any `#!` script is reported as a shell script.
+    &[
+        "sh", "bash", "zsh", "py", "pl", "rb", "js", "php", "lua", "awk",
+    ],
+    // ZIP-based containers.
+    &[
+        "zip", "jar", "apk", "aar", "docx", "docm", "xlsx", "xlsm", "pptx", "pptm", "odt", "ods",
+        "odp", "odg", "epub", "kmz", "kra", "ora", "xps", "oxps", "cbz", "xpi", "ipa", "whl",
+    ],
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionCheck {
+    /// Extension of the file name, `None` when the name has none.
+    pub actual: Option<String>,
+    pub expected: String,
+}
+
+/// Compares the extension of `name` with the one expected for the detected type.
+/// Returns `None` when they agree (aliases included).
+pub fn check_extension(name: &str, expected: &str) -> Option<ExtensionCheck> {
+    let expected = expected.to_ascii_lowercase();
+    let actual = Path::new(name)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase());
+    if let Some(actual) = &actual {
+        let same = *actual == expected
+            || EXTENSION_ALIASES.iter().any(|group| {
+                group.contains(&actual.as_str()) && group.contains(&expected.as_str())
+            });
+        if same {
+            return None;
+        }
+    }
+    Some(ExtensionCheck { actual, expected })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn report(data: &[u8], compressed: bool) -> EntropyReport {
+        sample_entropy(&mut Cursor::new(data), data.len() as u64, compressed).unwrap()
+    }
+
+    #[test]
+    fn entropy_bounds() {
+        assert_eq!(shannon_entropy(&[7; 100]), 0.0);
+        let all: Vec<u8> = (0..=255).collect();
+        assert!((shannon_entropy(&all) - 8.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn classifies_text_sparse_and_random_content() {
+        let text = "Hello, wörld!\n".repeat(500);
+        let text = report(text.as_bytes(), false);
+        assert_eq!(text.verdict, ContentVerdict::Text);
+        assert!(text.printable_ratio > 0.8);
+
+        let mut sparse = vec![0u8; 200_000];
+        sparse[1000] = 0x42;
+        assert_eq!(report(&sparse, false).verdict, ContentVerdict::Sparse);
+
+        // xorshift noise stands in for ciphertext.
+        let mut x = 0x2545_F491_4F6C_DD1Du64;
+        let noise: Vec<u8> = (0..300_000)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                (x >> 24) as u8
+            })
+            .collect();
+        let random = report(&noise, false);
+        assert_eq!(random.verdict, ContentVerdict::LikelyEncrypted);
+        assert_eq!(random.sampled_bytes, SAMPLE_BLOCKS * SAMPLE_BLOCK_BYTES);
+        assert!(random.block_min > 7.9);
+        assert_eq!(random.block_bits.len(), SAMPLE_BLOCKS as usize);
+        assert_eq!(report(&noise, true).verdict, ContentVerdict::Compressed);
+        assert_eq!(report(&[], false).verdict, ContentVerdict::Empty);
+    }
+
+    #[test]
+    fn guesses_text_formats() {
+        let guess = |s: &str| guess_mime_from_content(s.as_bytes()).map(|(mime, _)| mime);
+        assert_eq!(guess("  {\"a\": 1}"), Some("application/json"));
+        assert_eq!(guess("<!DOCTYPE html><html>"), Some("text/html"));
+        assert_eq!(
+            guess("<?xml version=\"1.0\"?><gpx>"),
+            Some("application/xml")
+        );
+        assert_eq!(guess("a,b,c\n1,2,3\n"), Some("text/csv"));
+        assert_eq!(
+            guess("-----BEGIN AGE ENCRYPTED FILE-----\n"),
+            Some("application/x-age")
+        );
+        assert_eq!(guess("just some notes"), Some("text/plain"));
+        assert_eq!(guess(&"é".repeat(40)), Some("text/plain"));
+        assert_eq!(guess_mime_from_content(&[0xFF, 0x00, 0x13]), None);
+    }
+
+    #[test]
+    fn extension_check_honours_aliases() {
+        assert_eq!(check_extension("photo.JPEG", "jpg"), None);
+        assert_eq!(check_extension("report.docx", "zip"), None);
+        assert_eq!(
+            check_extension("notes.txt", "png"),
+            Some(ExtensionCheck {
+                actual: Some("txt".into()),
+                expected: "png".into()
+            })
+        );
+        assert_eq!(check_extension("download", "pdf").unwrap().actual, None);
+    }
+}
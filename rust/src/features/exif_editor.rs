@@ -0,0 +1,533 @@
+use crate::error::error_text;
+use crate::features::exif_info::{ascii, date, orientation_label};
+use crate::state::AppState;
+use crate::ui::{
+    maybe_push_back, Button as UiButton, Column as UiColumn, Grid as UiGrid, Text as UiText,
+    TextInput as UiTextInput,
+};
+use exif::experimental::Writer;
+use exif::{Error as ExifError, Field, In, Reader, Tag, Value as ExifValue};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Cursor, Seek};
+use std::path::Path;
+
+/// Largest TIFF block an APP1 segment can hold (65535 minus the length and `Exif\0\0`).
+const MAX_TIFF_BYTES: usize = 65_535 - 2 - 6;
+
+/// The editable fields; empty strings and `None` remove the tag.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExifEdits {
+    /// `YYYY-MM-DD HH:MM:SS`, written to `DateTimeOriginal` and `DateTimeDigitized`.
+    pub taken_at: String,
+    pub artist: String,
+    pub copyright: String,
+    pub orientation: Option<u32>,
+}
+
+impl ExifEdits {
+    /// Reads the current values from an EXIF block; missing tags stay empty.
+    pub fn read<R: BufRead + Seek>(reader: &mut R) -> Result<Self, String> {
+        let exif = match Reader::new().read_from_container(reader) {
+            Ok(exif) => exif,
+            Err(ExifError::NotFound(_)) => return Ok(Self::default()),
+            Err(e) => return Err(format!("exif_edit_read_failed:{e}")),
+        };
+        Ok(Self {
+            taken_at: date(&exif, Tag::DateTimeOriginal).unwrap_or_default(),
+            artist: ascii(&exif, Tag::Artist).unwrap_or_default(),
+            copyright: ascii(&exif, Tag::Copyright).unwrap_or_default(),
+            orientation: exif
+                .get_field(Tag::Orientation, In::PRIMARY)
+                .and_then(|f| f.value.get_uint(0))
+                .filter(|o| (1..=8).contains(o)),
+        })
+    }
+
+    pub fn sync_bindings(&mut self, bindings: &HashMap<String, String>) {
+        if let Some(v) = bindings.get("exif_edit_taken_at") {
+            self.taken_at = v.trim().to_string();
+        }
+        if let Some(v) = bindings.get("exif_edit_artist") {
+            self.artist = v.trim().to_string();
+        }
+        if let Some(v) = bindings.get("exif_edit_copyright") {
+            self.copyright = v.trim().to_string();
+        }
+        if let Some(v) = bindings.get("exif_edit_orientation") {
+            self.orientation = v.trim().parse().ok().filter(|o| (1..=8).contains(o));
+        }
+    }
+}
+
+/// Accepts `YYYY-MM-DD HH:MM[:SS]` (or EXIF's `YYYY:MM:DD ...`) and returns the EXIF form.
+pub fn exif_datetime(input: &str) -> Result<String, String> {
+    let invalid = || format!("exif_edit_invalid_date:{input}");
+    let (day, time) = input.trim().split_once(' ').ok_or_else(invalid)?;
+    let day: Vec<u32> = day
+        .split(['-', ':', '/'])
+        .map(|p| p.parse().map_err(|_| invalid()))
+        .collect::<Result<_, _>>()?;
+    let mut time: Vec<u32> = time
+        .trim()
+        .split(':')
+        .map(|p| p.parse().map_err(|_| invalid()))
+        .collect::<Result<_, _>>()?;
+    if time.len() == 2 {
+        time.push(0);
+    }
+    let &[year, month, dom] = day.as_slice() else {
+        return Err(invalid());
+    };
+    let &[hour, minute, second] = time.as_slice() else {
+        return Err(invalid());
+    };
+    let days_in_month = match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    if !(1..=9999).contains(&year)
+        || !(1..=12).contains(&month)
+        || !(1..=days_in_month).contains(&dom)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return Err(invalid());
+    }
+    Ok(format!(
+        "{year:04}:{month:02}:{dom:02} {hour:02}:{minute:02}:{second:02}"
+    ))
+}
+
+fn ascii_field(tag: Tag, text: &str) -> Field {
+    Field {
+        tag,
+        ifd_num: In::PRIMARY,
+        value: ExifValue::Ascii(vec![text.as_bytes().to_vec()]),
+    }
+}
+
+/// Re-encodes the EXIF block with `edits` applied, keeping every other tag and the thumbnail.
+fn build_tiff(data: &[u8], edits: &ExifEdits) -> Result<Vec<u8>, String> {
+    let existing = match Reader::new().read_from_container(&mut Cursor::new(data)) {
+        Ok(exif) => Some(exif),
+        Err(ExifError::NotFound(_)) => None,
+        Err(e) => return Err(format!("exif_edit_read_failed:{e}")),
+    };
+
+    let mut added = Vec::new();
+    if !edits.taken_at.is_empty() {
+        let stamp = exif_datetime(&edits.taken_at)?;
+        added.push(ascii_field(Tag::DateTimeOriginal, &stamp));
+        added.push(ascii_field(Tag::DateTimeDigitized, &stamp));
+    }
+    if !edits.artist.is_empty() {
+        added.push(ascii_field(Tag::Artist, &edits.artist));
+    }
+    if !edits.copyright.is_empty() {
+        added.push(ascii_field(Tag::Copyright, &edits.copyright));
+    }
+    if let Some(orientation) = edits.orientation {
+        added.push(Field {
+            tag: Tag::Orientation,
+            ifd_num: In::PRIMARY,
+            value: ExifValue::Short(vec![orientation as u16]),
+        });
+    }
+
+    let edited = [
+        Tag::DateTimeOriginal,
+        Tag::DateTimeDigitized,
+        Tag::Artist,
+        Tag::Copyright,
+        Tag::Orientation,
+    ];
+    let mut writer = Writer::new();
+    let mut little_endian = false;
+    if let Some(exif) = &existing {
+        little_endian = exif.little_endian();
+        for field in exif.fields() {
+            let replaced = field.ifd_num == In::PRIMARY && edited.contains(&field.tag);
+            // Tags of unknown type cannot be re-encoded.
+            if !replaced && !matches!(field.value, ExifValue::Unknown(..)) {
+                writer.push_field(field);
+            }
+        }
+        let thumbnail_field = |tag| {
+            exif.get_field(tag, In::THUMBNAIL)
+                .and_then(|f| f.value.get_uint(0))
+                .map(|v| v as usize)
+        };
+        if let (Some(offset), Some(len)) = (
+            thumbnail_field(Tag::JPEGInterchangeFormat),
+            thumbnail_field(Tag::JPEGInterchangeFormatLength),
+        ) {
+            if let Some(jpeg) = exif.buf().get(offset..offset + len) {
+                writer.set_jpeg(jpeg, In::THUMBNAIL);
+            }
+        }
+    }
+    for field in &added {
+        writer.push_field(field);
+    }
+
+    let mut tiff = Cursor::new(Vec::new());
+    writer
+        .write(&mut tiff, little_endian)
+        .map_err(|e| format!("exif_edit_write_failed:{e}"))?;
+    let tiff = tiff.into_inner();
+    if tiff.len() > MAX_TIFF_BYTES {
+        return Err("exif_edit_too_large".into());
+    }
+    Ok(tiff)
+}
+
+/// Replaces the EXIF APP1 segment of a JPEG without touching the compressed image data.
+pub fn apply_exif_edits(data: &[u8], edits: &ExifEdits) -> Result<Vec<u8>, String> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return Err("exif_edit_unsupported_format".into());
+    }
+    let tiff = build_tiff(data, edits)?;
+    let truncated = || "exif_edit_truncated".to_string();
+
+    let mut out = Vec::with_capacity(data.len() + tiff.len());
+    out.extend_from_slice(&data[..2]);
+    // EXIF goes right after SOI, or after a leading JFIF APP0 which must come first.
+    let mut insert_at = 2;
+    let mut pos = 2;
+    loop {
+        if data.get(pos) != Some(&0xFF) {
+            return Err("exif_edit_corrupt_jpeg".into());
+        }
+        while data.get(pos) == Some(&0xFF) {
+            pos += 1;
+        }
+        let marker = *data.get(pos).ok_or_else(truncated)?;
+        pos += 1;
+        if marker == 0xD9 {
+            out.extend_from_slice(&[0xFF, 0xD9]);
+            out.extend_from_slice(&data[pos..]);
+            break;
+        }
+        let len = data
+            .get(pos..pos + 2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]) as usize)
+            .ok_or_else(truncated)?;
+        let end = pos + len;
+        if len < 2 || end > data.len() {
+            return Err(truncated());
+        }
+        let is_exif = marker == 0xE1 && data[pos + 2..end].starts_with(b"Exif\0");
+        if !is_exif {
+            out.extend_from_slice(&[0xFF, marker]);
+            out.extend_from_slice(&data[pos..end]);
+            if marker == 0xE0 && insert_at == 2 && out.len() == len + 4 {
+                insert_at = out.len();
+            }
+        }
+        pos = end;
+        if marker == 0xDA {
+            // Everything from the first scan on is image data; copy it verbatim.
+            out.extend_from_slice(&data[pos..]);
+            break;
+        }
+    }
+
+    let mut segment = vec![0xFF, 0xE1];
+    segment.extend_from_slice(&((tiff.len() + 8) as u16).to_be_bytes());
+    segment.extend_from_slice(b"Exif\0\0");
+    segment.extend_from_slice(&tiff);
+    out.splice(insert_at..insert_at, segment);
+    Ok(out)
+}
+
+/// `name_exif.jpg`, or `name_exif_2.jpg` and so on when that is taken.
+fn edited_copy_path(dir: &Path, name: &str) -> std::path::PathBuf {
+    let stem = Path::new(name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or("photo");
+    let mut candidate = dir.join(format!("{stem}_exif.jpg"));
+    let mut n = 2;
+    while candidate.exists() {
+        candidate = dir.join(format!("{stem}_exif_{n}.jpg"));
+        n += 1;
+    }
+    candidate
+}
+
+/// Writes an edited copy of `source` into `output_dir`; the original is untouched.
+pub fn save_exif_edits(
+    source: &str,
+    name: &str,
+    edits: &ExifEdits,
+    output_dir: &str,
+) -> Result<String, String> {
+    let data = fs::read(source).map_err(|e| format!("read_failed:{e}"))?;
+    let edited = apply_exif_edits(&data, edits)?;
+    fs::create_dir_all(output_dir).map_err(|e| format!("output_dir_create_failed:{e}"))?;
+    let path = edited_copy_path(Path::new(output_dir), name);
+    fs::write(&path, edited).map_err(|e| format!("save_failed:{e}"))?;
+    path.to_str()
+        .map(str::to_string)
+        .ok_or_else(|| "path_utf8".to_string())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExifEditorState {
+    pub source_name: Option<String>,
+    pub source_path: Option<String>,
+    pub output_dir: Option<String>,
+    /// Values found in the picked file, shown next to the form.
+    pub original: ExifEdits,
+    pub edits: ExifEdits,
+    pub result_path: Option<String>,
+    pub error: Option<String>,
+}
+
+impl ExifEditorState {
+    pub const fn new() -> Self {
+        Self {
+            source_name: None,
+            source_path: None,
+            output_dir: None,
+            original: ExifEdits {
+                taken_at: String::new(),
+                artist: String::new(),
+                copyright: String::new(),
+                orientation: None,
+            },
+            edits: ExifEdits {
+                taken_at: String::new(),
+                artist: String::new(),
+                copyright: String::new(),
+                orientation: None,
+            },
+            result_path: None,
+            error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn load_source(&mut self, path: String) -> Result<(), String> {
+        let file = fs::File::open(&path).map_err(|e| format!("open_failed:{e}"))?;
+        let mut reader = BufReader::new(file);
+        let head = reader.fill_buf().map_err(|e| format!("read_failed:{e}"))?;
+        if !head.starts_with(&[0xFF, 0xD8]) {
+            return Err("exif_edit_unsupported_format".into());
+        }
+        let original = ExifEdits::read(&mut reader)?;
+        self.edits = original.clone();
+        self.original = original;
+        self.source_path = Some(path);
+        self.result_path = None;
+        self.error = None;
+        Ok(())
+    }
+}
+
+fn text(value: &str, size: f64) -> Value {
+    serde_json::to_value(UiText::new(value).size(size)).unwrap()
+}
+
+fn text_field(label: &str, bind_key: &str, value: &str, hint: Option<&str>) -> [Value; 2] {
+    let mut input = UiTextInput::new(bind_key).text(value).single_line(true);
+    if let Some(hint) = hint {
+        input = input.hint(hint);
+    }
+    [text(label, 14.0), serde_json::to_value(input).unwrap()]
+}
+
+pub fn render_exif_editor_screen(state: &AppState) -> Value {
+    let editor = &state.exif_editor;
+    let mut children = vec![
+        text(&t!("exif_edit_title"), 20.0),
+        text(&t!("exif_edit_description"), 14.0),
+        serde_json::to_value(
+            UiButton::new(&t!("exif_edit_pick_button"), "exif_edit_pick")
+                .requires_file_picker(true)
+                .id("exif_edit_pick"),
+        )
+        .unwrap(),
+    ];
+
+    if let Some(err) = &editor.error {
+        children.push(text(&error_text(err), 12.0));
+    }
+
+    if editor.source_path.is_some() {
+        if let Some(name) = &editor.source_name {
+            children.push(text(
+                &format!("{}{name}", t!("image_edit_source_prefix")),
+                12.0,
+            ));
+        }
+        let edits = &editor.edits;
+        let unset = t!("exif_edit_unset");
+        let original = &editor.original;
+        let or_unset = |v: &str| {
+            if v.is_empty() {
+                unset.to_string()
+            } else {
+                v.to_string()
+            }
+        };
+        children.push(text(
+            &format!(
+                "{}{} · {} · {}",
+                t!("exif_edit_current_prefix"),
+                or_unset(&original.taken_at),
+                or_unset(&original.artist),
+                or_unset(&original.copyright)
+            ),
+            12.0,
+        ));
+
+        children.extend(text_field(
+            &t!("exif_edit_taken_at_label"),
+            "exif_edit_taken_at",
+            &edits.taken_at,
+            Some("YYYY-MM-DD HH:MM:SS"),
+        ));
+        children.extend(text_field(
+            &t!("exif_edit_artist_label"),
+            "exif_edit_artist",
+            &edits.artist,
+            None,
+        ));
+        children.extend(text_field(
+            &t!("exif_edit_copyright_label"),
+            "exif_edit_copyright",
+            &edits.copyright,
+            Some("© 2024 …"),
+        ));
+
+        children.push(text(&t!("exif_edit_orientation_label"), 14.0));
+        let orientation_buttons = (1..=8u32)
+            .map(|o| {
+                let label = format!("{o} · {}", orientation_label(o));
+                let id = format!("exif_edit_orientation_{o}");
+                let mut button = UiButton::new(&label, "exif_edit_sync")
+                    .payload(json!({ "exif_edit_orientation": o.to_string() }))
+                    .id(&id);
+                if edits.orientation == Some(o) {
+                    button = button.content_description("selected");
+                }
+                serde_json::to_value(button).unwrap()
+            })
+            .collect();
+        children.push(serde_json::to_value(UiGrid::new(orientation_buttons).columns(2)).unwrap());
+
+        children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("exif_edit_save_button"), "exif_edit_save").id("exif_edit_save"),
+            )
+            .unwrap(),
+        );
+    }
+
+    if let Some(path) = &editor.result_path {
+        children.push(text(
+            &format!("{}{path}", t!("image_edit_result_prefix")),
+            12.0,
+        ));
+        children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("dithering_copy_result_path_button"), "copy_clipboard")
+                    .copy_text(path),
+            )
+            .unwrap(),
+        );
+    }
+
+    maybe_push_back(&mut children, state);
+    serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::exif_info::read_exif;
+    use crate::features::exif_info::tests::sample_jpeg;
+    use image::ImageEncoder;
+
+    #[test]
+    fn dates_are_validated_and_converted() {
+        assert_eq!(
+            exif_datetime("2003-07-14 09:05").unwrap(),
+            "2003:07:14 09:05:00"
+        );
+        assert_eq!(
+            exif_datetime("2024:02:29 23:59:59").unwrap(),
+            "2024:02:29 23:59:59"
+        );
+        assert!(exif_datetime("2023-02-29 10:00:00").is_err());
+        assert!(exif_datetime("2023-01-01").is_err());
+        assert!(exif_datetime("2023-01-01 24:00").is_err());
+    }
+
+    #[test]
+    fn edits_replace_tags_and_keep_the_rest() {
+        let image = image::RgbImage::from_pixel(8, 8, image::Rgb([200, 30, 30]));
+        let mut encoded = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut encoded, 90)
+            .write_image(image.as_raw(), 8, 8, image::ColorType::Rgb8)
+            .unwrap();
+        // Sample EXIF (camera, date, orientation 6, GPS) spliced in front of a real JPEG.
+        let sample = sample_jpeg();
+        let mut data = sample[..sample.len() - 2].to_vec();
+        data.extend_from_slice(&encoded[2..]);
+
+        let mut edits = ExifEdits::read(&mut Cursor::new(&data)).unwrap();
+        assert_eq!(edits.taken_at, "2024-06-21 12:30:00");
+        assert_eq!(edits.orientation, Some(6));
+        edits.sync_bindings(&HashMap::from([
+            ("exif_edit_taken_at".into(), "1998-12-24 18:00".into()),
+            ("exif_edit_artist".into(), " Ada ".into()),
+            ("exif_edit_orientation".into(), "1".into()),
+        ]));
+
+        let edited = apply_exif_edits(&data, &edits).unwrap();
+        let reread = ExifEdits::read(&mut Cursor::new(&edited)).unwrap();
+        assert_eq!(reread.taken_at, "1998-12-24 18:00:00");
+        assert_eq!(reread.artist, "Ada");
+        assert_eq!(reread.copyright, "");
+        assert_eq!(reread.orientation, Some(1));
+        let summary = read_exif(&mut Cursor::new(&edited)).unwrap();
+        assert_eq!(summary.make.as_deref(), Some("Kista"));
+        assert!(summary.gps.is_some());
+        // Only the EXIF segment changed; the image still decodes.
+        assert!(edited.ends_with(&encoded[encoded.len() - 64..]));
+        let decoded = image::load_from_memory(&edited).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (8, 8));
+
+        // A JPEG without EXIF gains a fresh block; other formats are refused.
+        let fresh = apply_exif_edits(
+            &encoded,
+            &ExifEdits {
+                copyright: "CC BY 4.0".into(),
+                ..ExifEdits::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            ExifEdits::read(&mut Cursor::new(&fresh)).unwrap().copyright,
+            "CC BY 4.0"
+        );
+        assert_eq!(
+            apply_exif_edits(b"\x89PNG\r\n\x1a\n", &edits).unwrap_err(),
+            "exif_edit_unsupported_format"
+        );
+        assert!(exif_datetime("soon").is_err());
+    }
+}
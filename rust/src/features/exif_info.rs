@@ -0,0 +1,227 @@
+use exif::{Exif, Field, In, Reader, Tag, Value as ExifValue};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Seek};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GpsPosition {
+    pub lat: f64,
+    pub lon: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub altitude_m: Option<f64>,
+}
+
+impl GpsPosition {
+    /// `geo:` URI understood by Android map apps.
+    pub fn geo_uri(&self) -> String {
+        format!("geo:{0:.6},{1:.6}?q={0:.6},{1:.6}", self.lat, self.lon)
+    }
+
+    pub fn osm_url(&self) -> String {
+        format!(
+            "https://www.openstreetmap.org/?mlat={0:.6}&mlon={1:.6}#map=16/{0:.6}/{1:.6}",
+            self.lat, self.lon
+        )
+    }
+}
+
+/// The EXIF fields shown by the File Inspector.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExifSummary {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub make: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lens: Option<String>,
+    /// `DateTimeOriginal`, formatted `YYYY-MM-DD HH:MM:SS`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub taken_at: Option<String>,
+    /// `DateTime` (last modification), same format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gps: Option<GpsPosition>,
+}
+
+impl ExifSummary {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Human-readable transform for the EXIF orientation tag (1..=8).
+pub fn orientation_label(orientation: u32) -> &'static str {
+    match orientation {
+        1 => "normal",
+        2 => "mirrored horizontally",
+        3 => "rotated 180°",
+        4 => "mirrored vertically",
+        5 => "mirrored, rotated 90° CCW",
+        6 => "rotated 90° CW",
+        7 => "mirrored, rotated 90° CW",
+        8 => "rotated 90° CCW",
+        _ => "unknown",
+    }
+}
+
+pub(crate) fn ascii(exif: &Exif, tag: Tag) -> Option<String> {
+    let field = exif.get_field(tag, In::PRIMARY)?;
+    let ExifValue::Ascii(parts) = &field.value else {
+        return None;
+    };
+    let text = String::from_utf8_lossy(parts.first()?)
+        .trim_end_matches('\0')
+        .trim()
+        .to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// EXIF dates use `YYYY:MM:DD HH:MM:SS`; only the date separators change.
+pub(crate) fn date(exif: &Exif, tag: Tag) -> Option<String> {
+    let raw = ascii(exif, tag)?;
+    match raw.split_once(' ') {
+        Some((day, time)) if day.len() == 10 => Some(format!("{} {time}", day.replace(':', "-"))),
+        _ => Some(raw),
+    }
+}
+
+fn rationals(field: &Field) -> Option<Vec<f64>> {
+    match &field.value {
+        ExifValue::Rational(values) => Some(values.iter().map(|r| r.to_f64()).collect()),
+        _ => None,
+    }
+}
+
+fn coordinate(exif: &Exif, value_tag: Tag, ref_tag: Tag, negative_ref: char) -> Option<f64> {
+    let parts = rationals(exif.get_field(value_tag, In::PRIMARY)?)?;
+    let degrees = parts.first()? + parts.get(1).unwrap_or(&0.0) / 60.0
+        + parts.get(2).unwrap_or(&0.0) / 3600.0;
+    let negative = ascii(exif, ref_tag)
+        .and_then(|r| r.chars().next())
+        .is_some_and(|c| c.eq_ignore_ascii_case(&negative_ref));
+    let value = if negative { -degrees } else { degrees };
+    value.is_finite().then_some(value)
+}
+
+fn gps(exif: &Exif) -> Option<GpsPosition> {
+    let lat = coordinate(exif, Tag::GPSLatitude, Tag::GPSLatitudeRef, 'S')?;
+    let lon = coordinate(exif, Tag::GPSLongitude, Tag::GPSLongitudeRef, 'W')?;
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+        return None;
+    }
+    let altitude_m = exif
+        .get_field(Tag::GPSAltitude, In::PRIMARY)
+        .and_then(rationals)
+        .and_then(|v| v.first().copied())
+        .filter(|v| v.is_finite())
+        .map(|alt| {
+            // Reference 1 means below sea level.
+            let below = exif
+                .get_field(Tag::GPSAltitudeRef, In::PRIMARY)
+                .and_then(|f| f.value.get_uint(0))
+                == Some(1);
+            if below {
+                -alt
+            } else {
+                alt
+            }
+        });
+    Some(GpsPosition {
+        lat,
+        lon,
+        altitude_m,
+    })
+}
+
+/// Reads EXIF from a JPEG, PNG (`eXIf`), WebP, TIFF or HEIF container.
+/// Returns `None` when there is no EXIF block or none of the summarised tags.
+pub fn read_exif<R: BufRead + Seek>(reader: &mut R) -> Option<ExifSummary> {
+    let exif = Reader::new().read_from_container(reader).ok()?;
+    let summary = ExifSummary {
+        make: ascii(&exif, Tag::Make),
+        model: ascii(&exif, Tag::Model),
+        lens: ascii(&exif, Tag::LensModel),
+        taken_at: date(&exif, Tag::DateTimeOriginal),
+        modified_at: date(&exif, Tag::DateTime),
+        orientation: exif
+            .get_field(Tag::Orientation, In::PRIMARY)
+            .and_then(|f| f.value.get_uint(0)),
+        gps: gps(&exif),
+    };
+    (!summary.is_empty()).then_some(summary)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use exif::experimental::Writer;
+    use exif::Rational;
+    use std::io::Cursor;
+
+    /// Minimal JPEG (SOI, APP1 Exif, EOI) carrying camera, date, orientation and GPS tags.
+    pub(crate) fn sample_jpeg() -> Vec<u8> {
+        let ascii = |tag, text: &str| Field {
+            tag,
+            ifd_num: In::PRIMARY,
+            value: ExifValue::Ascii(vec![text.as_bytes().to_vec()]),
+        };
+        let dms = |tag, d: u32, m: u32, s: u32| Field {
+            tag,
+            ifd_num: In::PRIMARY,
+            value: ExifValue::Rational(vec![
+                Rational::from((d, 1)),
+                Rational::from((m, 1)),
+                Rational::from((s, 1)),
+            ]),
+        };
+        let fields = [
+            ascii(Tag::Make, "Kista"),
+            ascii(Tag::Model, "Verk 1"),
+            ascii(Tag::DateTimeOriginal, "2024:06:21 12:30:00"),
+            Field {
+                tag: Tag::Orientation,
+                ifd_num: In::PRIMARY,
+                value: ExifValue::Short(vec![6]),
+            },
+            ascii(Tag::GPSLatitudeRef, "N"),
+            dms(Tag::GPSLatitude, 64, 9, 0),
+            ascii(Tag::GPSLongitudeRef, "W"),
+            dms(Tag::GPSLongitude, 21, 57, 0),
+        ];
+        let mut writer = Writer::new();
+        for field in &fields {
+            writer.push_field(field);
+        }
+        let mut tiff = Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+        let tiff = tiff.into_inner();
+
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend_from_slice(&((tiff.len() + 8) as u16).to_be_bytes());
+        jpeg.extend_from_slice(b"Exif\0\0");
+        jpeg.extend_from_slice(&tiff);
+        jpeg.extend_from_slice(&[0xFF, 0xD9]);
+        jpeg
+    }
+
+    #[test]
+    fn reads_camera_date_orientation_and_gps() {
+        let summary = read_exif(&mut Cursor::new(sample_jpeg())).unwrap();
+        assert_eq!(summary.make.as_deref(), Some("Kista"));
+        assert_eq!(summary.model.as_deref(), Some("Verk 1"));
+        assert_eq!(summary.taken_at.as_deref(), Some("2024-06-21 12:30:00"));
+        assert_eq!(summary.orientation, Some(6));
+        assert_eq!(orientation_label(6), "rotated 90° CW");
+        let gps = summary.gps.unwrap();
+        assert!((gps.lat - 64.15).abs() < 1e-9);
+        assert!((gps.lon + 21.95).abs() < 1e-9);
+        assert_eq!(gps.geo_uri(), "geo:64.150000,-21.950000?q=64.150000,-21.950000");
+    }
+
+    #[test]
+    fn files_without_exif_yield_none() {
+        assert!(read_exif(&mut Cursor::new(b"not an image".to_vec())).is_none());
+    }
+}
@@ -0,0 +1,260 @@
+//! Native code for math tool expressions sampled many times (plots, numeric integrals).
+//!
+//! The expression tree is printed as a one-function C source, `double kv_expr(double x)`,
+//! compiled with c2mir and generated by the MIR JIT. Only the operators and functions of
+//! [`Symbol`] are supported; anything else is left to the interpreter by the caller.
+
+use crate::features::c_scripting::{getc_func, StringReader};
+use crate::features::math_tool::Symbol;
+use libc::c_void;
+use mir_sys::*;
+use std::ffi::{CStr, CString};
+use std::fmt::Write;
+use std::ptr;
+
+/// Below this many evaluations compiling costs more than walking the RPN.
+pub const JIT_MIN_SAMPLES: usize = 256;
+
+const ENTRY: &str = "kv_expr";
+
+type ExprFn = extern "C" fn(f64) -> f64;
+
+/// A compiled expression of one variable. Owns its MIR context, so it must stay on the
+/// thread that compiled it.
+pub struct JitExpr {
+    ctx: MIR_context_t,
+    func: ExprFn,
+}
+
+impl JitExpr {
+    /// Value at `x`; NaN or infinite where the interpreter would report an error.
+    pub fn eval(&self, x: f64) -> f64 {
+        (self.func)(x)
+    }
+}
+
+impl Drop for JitExpr {
+    fn drop(&mut self) {
+        unsafe {
+            MIR_gen_finish(self.ctx);
+            MIR_finish(self.ctx);
+        }
+    }
+}
+
+/// Compiles `expr` as a function of `var`.
+pub fn compile_expression(expr: &Symbol, var: &str) -> Result<JitExpr, String> {
+    let source = c_source(expr, var)?;
+    unsafe {
+        let ctx = _MIR_init(ptr::null_mut(), ptr::null_mut());
+        if ctx.is_null() {
+            return Err("jit_init_failed".into());
+        }
+        MIR_gen_init(ctx);
+        MIR_gen_set_optimize_level(ctx, 2);
+        match link(ctx, source) {
+            Ok(func) => Ok(JitExpr { ctx, func }),
+            Err(e) => {
+                MIR_gen_finish(ctx);
+                MIR_finish(ctx);
+                Err(e)
+            }
+        }
+    }
+}
+
+/// C translation of `expr`. Division by (nearly) zero yields NaN, matching the
+/// interpreter's `division_by_zero` error.
+fn c_source(expr: &Symbol, var: &str) -> Result<String, String> {
+    let mut body = String::new();
+    write_c(expr, var, &mut body)?;
+    let mut source = String::new();
+    for (name, params, _) in externals() {
+        let _ = writeln!(source, "double {name}({params});");
+    }
+    let eps = f64::EPSILON;
+    let _ = writeln!(
+        source,
+        "static double kv_div(double a, double b) {{ \
+         return (b < {eps:e} && b > -{eps:e}) ? 0.0 / 0.0 : a / b; }}"
+    );
+    let _ = writeln!(source, "double {ENTRY}(double x) {{ return {body}; }}");
+    Ok(source)
+}
+
+fn write_c(expr: &Symbol, var: &str, out: &mut String) -> Result<(), String> {
+    use Symbol::*;
+    let call = |name: &str, args: &[&Symbol], out: &mut String| -> Result<(), String> {
+        out.push_str(name);
+        out.push('(');
+        for (i, arg) in args.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            write_c(arg, var, out)?;
+        }
+        out.push(')');
+        Ok(())
+    };
+    match expr {
+        // `{:e}` keeps every digit and always reads back as a double literal.
+        Number(n) if n.is_finite() => {
+            let _ = write!(out, "({n:e})");
+        }
+        Number(_) => return Err("jit_non_finite_constant".into()),
+        Var(name) if name == var => out.push('x'),
+        Var(name) => return Err(format!("jit_unknown_variable:{name}")),
+        Add(a, b) | Sub(a, b) | Mul(a, b) => {
+            let op = match expr {
+                Add(..) => " + ",
+                Sub(..) => " - ",
+                _ => " * ",
+            };
+            out.push('(');
+            write_c(a, var, out)?;
+            out.push_str(op);
+            write_c(b, var, out)?;
+            out.push(')');
+        }
+        Div(a, b) => call("kv_div", &[a, b], out)?,
+        Pow(a, b) => call("kv_pow", &[a, b], out)?,
+        Neg(a) => {
+            out.push_str("(-");
+            write_c(a, var, out)?;
+            out.push(')');
+        }
+        Sin(a) => call("kv_sin", &[a], out)?,
+        Cos(a) => call("kv_cos", &[a], out)?,
+        Tan(a) => call("kv_tan", &[a], out)?,
+        Exp(a) => call("kv_exp", &[a], out)?,
+        Atan(a) => call("kv_atan", &[a], out)?,
+        Sqrt(a) => call("kv_sqrt", &[a], out)?,
+        Log(a) => call("kv_log", &[a], out)?,
+    }
+    Ok(())
+}
+
+// Rust's own float functions, so JIT and interpreter results agree bit for bit.
+extern "C" fn kv_sin(x: f64) -> f64 {
+    x.sin()
+}
+extern "C" fn kv_cos(x: f64) -> f64 {
+    x.cos()
+}
+extern "C" fn kv_tan(x: f64) -> f64 {
+    x.tan()
+}
+extern "C" fn kv_exp(x: f64) -> f64 {
+    x.exp()
+}
+extern "C" fn kv_atan(x: f64) -> f64 {
+    x.atan()
+}
+extern "C" fn kv_sqrt(x: f64) -> f64 {
+    x.sqrt()
+}
+extern "C" fn kv_log(x: f64) -> f64 {
+    x.ln()
+}
+extern "C" fn kv_pow(x: f64, y: f64) -> f64 {
+    x.powf(y)
+}
+
+/// Functions the generated source imports: name, C parameter list, address.
+fn externals() -> Vec<(&'static str, &'static str, *mut c_void)> {
+    vec![
+        ("kv_sin", "double", kv_sin as *mut c_void),
+        ("kv_cos", "double", kv_cos as *mut c_void),
+        ("kv_tan", "double", kv_tan as *mut c_void),
+        ("kv_exp", "double", kv_exp as *mut c_void),
+        ("kv_atan", "double", kv_atan as *mut c_void),
+        ("kv_sqrt", "double", kv_sqrt as *mut c_void),
+        ("kv_log", "double", kv_log as *mut c_void),
+        ("kv_pow", "double, double", kv_pow as *mut c_void),
+    ]
+}
+
+unsafe fn link(ctx: MIR_context_t, source: String) -> Result<ExprFn, String> {
+    c2mir_init(ctx);
+    let mut reader = StringReader {
+        data: source.into_bytes(),
+        cursor: 0,
+    };
+    let mut options: c2mir_options = std::mem::zeroed();
+    let compiled = c2mir_compile(
+        ctx,
+        &mut options,
+        Some(getc_func),
+        &mut reader as *mut _ as *mut c_void,
+        b"expr.c\0".as_ptr() as *const _,
+        ptr::null_mut(),
+    );
+    c2mir_finish(ctx);
+    if compiled != 1 {
+        return Err("jit_compile_failed".into());
+    }
+
+    let module = (*MIR_get_module_list(ctx)).tail;
+    if module.is_null() {
+        return Err("jit_compile_failed".into());
+    }
+    MIR_load_module(ctx, module);
+    for (name, _, addr) in externals() {
+        let name = CString::new(name).unwrap();
+        MIR_load_external(ctx, name.as_ptr(), addr);
+    }
+    MIR_link(ctx, Some(MIR_set_gen_interface), None);
+
+    let mut item = (*module).items.head;
+    while !item.is_null() {
+        if (*item).item_type == MIR_item_type_t_MIR_func_item
+            && CStr::from_ptr(MIR_item_name(ctx, item)).to_bytes() == ENTRY.as_bytes()
+        {
+            let addr = MIR_gen(ctx, item);
+            if addr.is_null() {
+                return Err("jit_no_address".into());
+            }
+            return Ok(std::mem::transmute::<*mut c_void, ExprFn>(addr));
+        }
+        item = (*item).item_link.next;
+    }
+    Err("jit_no_address".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Symbol::*;
+
+    fn b(sym: Symbol) -> Box<Symbol> {
+        Box::new(sym)
+    }
+
+    #[test]
+    fn compiled_expression_matches_the_tree() {
+        // sin(x)^2 + sqrt(x) / (x - 1)
+        let expr = Add(
+            b(Pow(b(Sin(b(Var("x".into())))), b(Number(2.0)))),
+            b(Div(
+                b(Sqrt(b(Var("x".into())))),
+                b(Sub(b(Var("x".into())), b(Number(1.0)))),
+            )),
+        );
+        let jit = compile_expression(&expr, "x").expect("compiles");
+        for x in [0.0f64, 0.5, 2.0, 10.0] {
+            let expected = x.sin().powf(2.0) + x.sqrt() / (x - 1.0);
+            assert_eq!(jit.eval(x), expected);
+        }
+        assert!(jit.eval(1.0).is_nan());
+        assert!(jit.eval(-4.0).is_nan());
+    }
+
+    #[test]
+    fn other_variables_are_left_to_the_interpreter() {
+        let expr = Mul(b(Var("x".into())), b(Var("y".into())));
+        assert_eq!(
+            compile_expression(&expr, "x").err().as_deref(),
+            Some("jit_unknown_variable:y")
+        );
+    }
+}
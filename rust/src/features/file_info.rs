@@ -1,15 +1,31 @@
+use crate::error::error_text;
+use crate::features::apk_info::{inspect_package, PackageInfo, PackageKind};
+use crate::features::entropy::{
+    check_extension, guess_mime_from_content, sample_entropy, ContentVerdict, EntropyReport,
+    ExtensionCheck,
+};
+use crate::features::exif_info::{orientation_label, read_exif, ExifSummary};
+use crate::features::media_info::{format_duration, probe_media, MediaInfo, TrackKind};
+use crate::features::signatures::{identify, Confidence, SignatureMatch, SIGNATURE_PROBE_BYTES};
+use crate::features::thumbnail::{image_thumbnail, pdf_thumbnail, Thumbnail, ThumbnailSource};
 use crate::state::AppState;
-use crate::ui::{maybe_push_back, CodeView as UiCodeView, Text as UiText};
-use infer::Infer;
-use serde::Serialize;
+use crate::ui::{
+    format_bytes, maybe_push_back, Button as UiButton, Chart as UiChart, ChartSeries,
+    CodeView as UiCodeView, Grid as UiGrid, ImageBase64 as UiImageBase64, Section as UiSection,
+    Table as UiTable, TableColumn as UiTableColumn, Text as UiText, TextInput as UiTextInput,
+};
+use infer::{Infer, MatcherType};
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::os::unix::fs::MetadataExt;
 use std::os::unix::io::{FromRawFd, RawFd};
 use rust_i18n::t;
 
 const HEX_PREVIEW_BYTES: usize = 512;
+pub const HEX_WINDOW_DEFAULT_BYTES: usize = 256;
+pub const HEX_WINDOW_MAX_BYTES: usize = 4096;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct FileInfoResult {
@@ -19,9 +35,29 @@ pub struct FileInfoResult {
     pub hex_dump: Option<String>,
     pub is_utf8: Option<bool>,
     pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exif: Option<ExifSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media: Option<MediaInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entropy: Option<EntropyReport>,
+    /// Content-based guess when magic bytes do not identify the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guessed_mime: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension_check: Option<ExtensionCheck>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<PackageInfo>,
+    /// Magic-byte signature that identified the file, when one matched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<SignatureMatch>,
+    /// Small PNG preview for images and PDFs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<Thumbnail>,
 }
 
-pub fn file_info_from_fd(fd: RawFd) -> FileInfoResult {
+/// `name` is the display name of the picked file, used for the extension check.
+pub fn file_info_from_fd(fd: RawFd, name: Option<&str>) -> FileInfoResult {
     if fd < 0 {
         return FileInfoResult {
             path: None,
@@ -29,17 +65,25 @@ pub fn file_info_from_fd(fd: RawFd) -> FileInfoResult {
             mime: None,
             hex_dump: None,
             is_utf8: None,
+            exif: None,
+            media: None,
+            entropy: None,
+            guessed_mime: None,
+            extension_check: None,
+            package: None,
+            signature: None,
+            thumbnail: None,
             error: Some("invalid_fd".into()),
         };
     }
     let file = unsafe { File::from_raw_fd(fd) };
-    info_from_reader(file)
+    info_from_reader(file, name)
 }
 
 pub fn file_info_from_path(path: &str) -> FileInfoResult {
     match File::open(path) {
         Ok(file) => {
-            let mut info = info_from_reader(file);
+            let mut info = info_from_reader(file, Some(path));
             info.path = Some(path.to_string());
             info
         }
@@ -49,12 +93,20 @@ pub fn file_info_from_path(path: &str) -> FileInfoResult {
             mime: None,
             hex_dump: None,
             is_utf8: None,
+            exif: None,
+            media: None,
+            entropy: None,
+            guessed_mime: None,
+            extension_check: None,
+            package: None,
+            signature: None,
+            thumbnail: None,
             error: Some(format!("open_failed:{e}")),
         },
     }
 }
 
-fn info_from_reader(file: File) -> FileInfoResult {
+fn info_from_reader(file: File, name: Option<&str>) -> FileInfoResult {
     let metadata = match file.metadata() {
         Ok(m) => m,
         Err(e) => {
@@ -64,6 +116,14 @@ fn info_from_reader(file: File) -> FileInfoResult {
                 mime: None,
                 hex_dump: None,
                 is_utf8: None,
+                exif: None,
+                media: None,
+                entropy: None,
+                guessed_mime: None,
+                extension_check: None,
+                package: None,
+                signature: None,
+                thumbnail: None,
                 error: Some(format!("metadata_failed:{e}")),
             }
         }
@@ -76,36 +136,591 @@ fn info_from_reader(file: File) -> FileInfoResult {
         hex_dump: None,
         is_utf8: None,
         error: None,
+        exif: None,
+        media: None,
+        entropy: None,
+        guessed_mime: None,
+        extension_check: None,
+        package: None,
+        signature: None,
+        thumbnail: None,
     };
 
-    let mut buf = [0u8; 8192];
+    let mut buf = Vec::with_capacity(SIGNATURE_PROBE_BYTES);
     let mut reader = BufReader::new(file);
-    let read = match reader.read(&mut buf) {
-        Ok(r) => r,
-        Err(e) => {
-            info.error = Some(format!("read_failed:{e}"));
-            return info;
-        }
-    };
+    if let Err(e) = (&mut reader)
+        .take(SIGNATURE_PROBE_BYTES as u64)
+        .read_to_end(&mut buf)
+    {
+        info.error = Some(format!("read_failed:{e}"));
+        return info;
+    }
 
-    let header_len = read.min(HEX_PREVIEW_BYTES);
-    let header = &buf[..header_len];
+    let header = &buf[..buf.len().min(HEX_PREVIEW_BYTES)];
     if !header.is_empty() {
         info.hex_dump = Some(format_hex_dump(header));
     }
-    info.is_utf8 = Some(is_utf8_sample(header));
+    let is_utf8 = is_utf8_sample(header);
+    info.is_utf8 = Some(is_utf8);
 
-    let detector = Infer::new();
-    info.mime = detector
-        .get(&buf[..read])
-        .map(|t| t.mime_type().to_string());
+    // Short magic numbers ("BM", "G" ...) also start plenty of text files.
+    info.signature = identify(&buf).filter(|s| s.confidence == Confidence::High || !is_utf8);
+    let detected = Infer::new().get(&buf);
+    let mut expected_extension = None;
+    let mut compressed_format = false;
+    if let Some(signature) = &info.signature {
+        info.mime = Some(signature.mime.clone());
+        expected_extension = Some(signature.extension.as_str()).filter(|e| !e.is_empty());
+        compressed_format = signature.category.is_compressed();
+    } else if let Some(detected) = detected {
+        info.mime = Some(detected.mime_type().to_string());
+        expected_extension = Some(detected.extension());
+        compressed_format = matches!(
+            detected.matcher_type(),
+            MatcherType::Archive | MatcherType::Image | MatcherType::Video | MatcherType::Audio
+        );
+    } else if let Some((mime, extension)) = guess_mime_from_content(&buf[..buf.len().min(8192)]) {
+        info.guessed_mime = Some(mime.to_string());
+        expected_extension = extension;
+    }
+    if let (Some(name), Some(expected)) = (name, expected_extension) {
+        info.extension_check = check_extension(name, expected);
+    }
+    if info.mime.as_deref().is_some_and(|m| m.starts_with("image/"))
+        && reader.seek(SeekFrom::Start(0)).is_ok()
+    {
+        info.exif = read_exif(&mut reader);
+        let orientation = info.exif.as_ref().and_then(|e| e.orientation);
+        info.thumbnail = image_thumbnail(&mut reader, orientation).ok();
+    }
+    if info.mime.as_deref() == Some("application/pdf") {
+        info.thumbnail = pdf_thumbnail(&mut reader, metadata.size()).ok();
+    }
+    if info
+        .mime
+        .as_deref()
+        .is_some_and(|m| m.starts_with("video/") || m.starts_with("audio/"))
+    {
+        info.media = probe_media(&mut reader, metadata.size());
+    }
+    if matches!(
+        info.mime.as_deref(),
+        Some("application/zip" | "application/vnd.android.package-archive" | "application/java-archive")
+    ) {
+        info.package = inspect_package(&mut reader);
+    }
+    info.entropy = sample_entropy(&mut reader, metadata.size(), compressed_format).ok();
     info
 }
 
+/// One row of the comparison table shown for a multi-selection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchEntry {
+    pub name: String,
+    /// Path to re-read when hashing; `None` for picked fds, which Kotlin reopens.
+    pub path: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub mime: Option<String>,
+    pub description: Option<String>,
+    pub extension_mismatch: bool,
+    pub error: Option<String>,
+    pub sha256: Option<String>,
+}
+
+impl BatchEntry {
+    fn from_info(info: FileInfoResult, name: String, path: Option<String>) -> Self {
+        Self {
+            name,
+            path,
+            size_bytes: info.size_bytes,
+            mime: info.mime.or(info.guessed_mime),
+            description: info.signature.map(|s| s.description),
+            extension_mismatch: info.extension_check.is_some(),
+            error: info.error,
+            sha256: None,
+        }
+    }
+}
+
+/// Inspects every picked file. `fds` take precedence over `paths` at the same index;
+/// `names` are display names for the fds.
+/// Inspects each picked file, calling `on_entry(index, total)` before each one.
+pub fn batch_file_info(
+    paths: &[String],
+    fds: &[i32],
+    names: &[String],
+    mut on_entry: impl FnMut(usize, usize),
+) -> Vec<BatchEntry> {
+    let total = paths.len().max(fds.len());
+    (0..total)
+        .map(|i| {
+            on_entry(i, total);
+            let path = paths.get(i);
+            let name = names
+                .get(i)
+                .cloned()
+                .or_else(|| path.map(|p| p.rsplit('/').next().unwrap_or(p).to_string()))
+                .unwrap_or_else(|| format!("#{}", i + 1));
+            match (fds.get(i), path) {
+                (Some(&fd), _) => {
+                    BatchEntry::from_info(file_info_from_fd(fd, Some(&name)), name, None)
+                }
+                (None, Some(path)) => {
+                    BatchEntry::from_info(file_info_from_path(path), name, Some(path.clone()))
+                }
+                (None, None) => unreachable!("index is below paths.len().max(fds.len())"),
+            }
+        })
+        .collect()
+}
+
+fn render_batch_section(entries: &[BatchEntry]) -> Value {
+    let name_title = t!("file_inspector_batch_name_header");
+    let size_title = t!("file_inspector_batch_size_header");
+    let type_title = t!("file_inspector_batch_type_header");
+    let sha256_title = t!("file_inspector_batch_sha256_header");
+    let mut table = UiTable::new(vec![
+        UiTableColumn::new(&name_title).width_dp(160),
+        UiTableColumn::new(&size_title).align("end").width_dp(80),
+        UiTableColumn::new(&type_title).width_dp(180),
+        UiTableColumn::new(&sha256_title).width_dp(170),
+    ])
+    .horizontal_scroll(true);
+    for (index, entry) in entries.iter().enumerate() {
+        let size = entry.size_bytes.map(format_bytes).unwrap_or_default();
+        let kind = match &entry.error {
+            Some(err) => error_text(err),
+            None => {
+                let mut kind = entry
+                    .description
+                    .clone()
+                    .or_else(|| entry.mime.clone())
+                    .unwrap_or_else(|| t!("file_inspector_batch_unknown_type").to_string());
+                if entry.extension_mismatch {
+                    kind.push_str(" ⚠");
+                }
+                kind
+            }
+        };
+        let hash_cell = match &entry.sha256 {
+            Some(hash) => {
+                let short = format!("{}…", &hash[..hash.len().min(16)]);
+                json!(UiButton::new(&short, "noop").copy_text(hash))
+            }
+            None => {
+                let action = format!("file_info_batch_hash:{index}");
+                json!(UiButton::new(
+                    &t!("file_inspector_batch_hash_button"),
+                    &action
+                ))
+            }
+        };
+        table = table.row(vec![
+            json!(UiText::new(&entry.name).size(14.0)),
+            json!(UiText::new(&size).size(14.0)),
+            json!(UiText::new(&kind).size(14.0)),
+            hash_cell,
+        ]);
+    }
+    let mut rows = vec![serde_json::to_value(table).unwrap()];
+    if entries.iter().any(|e| e.extension_mismatch) {
+        rows.push(
+            serde_json::to_value(UiText::new(&t!("file_inspector_batch_mismatch_note")).size(12.0))
+                .unwrap(),
+        );
+    }
+    serde_json::to_value(
+        UiSection::new(rows)
+            .title(&format!(
+                "{}{}",
+                t!("file_inspector_batch_section_prefix"),
+                entries.len()
+            ))
+            .padding(8),
+    )
+    .unwrap()
+}
+
+/// A window of the inspected file shown in hex+ASCII, beyond the header preview.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HexWindow {
+    pub offset: u64,
+    pub length: usize,
+    pub total_bytes: u64,
+    pub dump: String,
+}
+
+impl HexWindow {
+    pub fn has_previous(&self) -> bool {
+        self.offset > 0
+    }
+
+    pub fn has_next(&self) -> bool {
+        self.offset.saturating_add(self.length as u64) < self.total_bytes
+    }
+}
+
+/// Dumps `length` bytes (clamped to 16..=[`HEX_WINDOW_MAX_BYTES`]) from `offset`.
+/// Offsets past the end show the last window of the file.
+pub fn read_hex_window<R: Read + Seek>(
+    reader: &mut R,
+    offset: u64,
+    length: usize,
+) -> Result<HexWindow, String> {
+    let length = length.clamp(16, HEX_WINDOW_MAX_BYTES);
+    let total_bytes = reader
+        .seek(SeekFrom::End(0))
+        .map_err(|e| format!("seek_failed:{e}"))?;
+    let offset = offset.min(total_bytes.saturating_sub(length as u64));
+    reader
+        .seek(SeekFrom::Start(offset))
+        .map_err(|e| format!("seek_failed:{e}"))?;
+    let mut buf = Vec::with_capacity(length);
+    reader
+        .take(length as u64)
+        .read_to_end(&mut buf)
+        .map_err(|e| format!("read_failed:{e}"))?;
+    Ok(HexWindow {
+        offset,
+        length,
+        total_bytes,
+        dump: format_hex_dump_at(&buf, offset),
+    })
+}
+
+pub fn hex_window_from_fd(fd: RawFd, offset: u64, length: usize) -> Result<HexWindow, String> {
+    if fd < 0 {
+        return Err("invalid_fd".into());
+    }
+    let mut file = unsafe { File::from_raw_fd(fd) };
+    read_hex_window(&mut file, offset, length)
+}
+
+pub fn hex_window_from_path(path: &str, offset: u64, length: usize) -> Result<HexWindow, String> {
+    let mut file = File::open(path).map_err(|e| format!("open_failed:{e}"))?;
+    read_hex_window(&mut file, offset, length)
+}
+
+fn render_hex_window_section(window: Option<&HexWindow>, error: Option<&str>) -> Value {
+    let offset = window.map(|w| format!("0x{:x}", w.offset)).unwrap_or_default();
+    let length = window
+        .map(|w| w.length)
+        .unwrap_or(HEX_WINDOW_DEFAULT_BYTES)
+        .to_string();
+    let mut rows = vec![serde_json::to_value(
+        UiGrid::new(vec![
+            json!(UiTextInput::new("file_info_hex_offset")
+                .text(&offset)
+                .hint(&t!("file_inspector_hex_offset_hint"))
+                .single_line(true)
+                .action_on_submit("file_info_hex")),
+            json!(UiTextInput::new("file_info_hex_length")
+                .text(&length)
+                .hint(&t!("file_inspector_hex_length_hint"))
+                .single_line(true)
+                .action_on_submit("file_info_hex")),
+            json!(UiButton::new(&t!("file_inspector_hex_show_button"), "file_info_hex")),
+        ])
+        .columns(3),
+    )
+    .unwrap()];
+    if let Some(err) = error {
+        rows.push(serde_json::to_value(UiText::new(&error_text(err)).size(14.0)).unwrap());
+    }
+    if let Some(window) = window {
+        let mut paging = Vec::new();
+        if window.has_previous() {
+            paging.push(json!(UiButton::new(
+                &t!("file_inspector_hex_prev_button"),
+                "file_info_hex_prev"
+            )));
+        }
+        if window.has_next() {
+            paging.push(json!(UiButton::new(
+                &t!("file_inspector_hex_next_button"),
+                "file_info_hex_next"
+            )));
+        }
+        if !paging.is_empty() {
+            let columns = paging.len() as u32;
+            rows.push(serde_json::to_value(UiGrid::new(paging).columns(columns)).unwrap());
+        }
+        let end = (window.offset + window.length as u64).min(window.total_bytes);
+        rows.push(
+            serde_json::to_value(
+                UiText::new(&format!(
+                    "{}0x{:08x}–0x{:08x} / {}",
+                    t!("file_inspector_hex_range_prefix"),
+                    window.offset,
+                    end.saturating_sub(1),
+                    window.total_bytes
+                ))
+                .size(14.0),
+            )
+            .unwrap(),
+        );
+        rows.push(
+            serde_json::to_value(
+                UiCodeView::new(&window.dump)
+                    .wrap(false)
+                    .line_numbers(false),
+            )
+            .unwrap(),
+        );
+    }
+    serde_json::to_value(
+        UiSection::new(rows)
+            .title(&t!("file_inspector_hex_window_section"))
+            .padding(8),
+    )
+    .unwrap()
+}
+
+fn render_thumbnail(thumbnail: &Thumbnail) -> [Value; 2] {
+    let source = match thumbnail.source {
+        ThumbnailSource::Image => t!("file_inspector_thumbnail_source_image"),
+        ThumbnailSource::PdfPageThumbnail => t!("file_inspector_thumbnail_source_pdf_thumb"),
+        ThumbnailSource::PdfPageImage => t!("file_inspector_thumbnail_source_pdf_image"),
+    };
+    [
+        serde_json::to_value(
+            UiImageBase64::new(&thumbnail.png_base64).content_description("file_info_thumbnail"),
+        )
+        .unwrap(),
+        serde_json::to_value(
+            UiText::new(&format!(
+                "{}{}×{} · {source}",
+                t!("file_inspector_thumbnail_prefix"),
+                thumbnail.width,
+                thumbnail.height
+            ))
+            .size(12.0),
+        )
+        .unwrap(),
+    ]
+}
+
+fn render_exif_section(exif: &ExifSummary) -> Value {
+    let mut rows = Vec::new();
+    let mut line = |prefix: &str, value: String| {
+        rows.push(serde_json::to_value(UiText::new(&format!("{prefix}{value}")).size(14.0)).unwrap());
+    };
+    let camera = [exif.make.as_deref(), exif.model.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if !camera.is_empty() {
+        line(&t!("file_inspector_exif_camera_prefix"), camera);
+    }
+    if let Some(lens) = &exif.lens {
+        line(&t!("file_inspector_exif_lens_prefix"), lens.clone());
+    }
+    if let Some(taken) = &exif.taken_at {
+        line(&t!("file_inspector_exif_taken_prefix"), taken.clone());
+    }
+    if let Some(modified) = &exif.modified_at {
+        line(&t!("file_inspector_exif_modified_prefix"), modified.clone());
+    }
+    if let Some(orientation) = exif.orientation {
+        line(
+            &t!("file_inspector_exif_orientation_prefix"),
+            format!("{orientation} ({})", orientation_label(orientation)),
+        );
+    }
+    if let Some(gps) = &exif.gps {
+        let mut position = format!("{:.6}, {:.6}", gps.lat, gps.lon);
+        if let Some(alt) = gps.altitude_m {
+            position.push_str(&format!(" ({alt:.0} m)"));
+        }
+        line(&t!("file_inspector_exif_gps_prefix"), position);
+        rows.push(
+            serde_json::to_value(
+                UiButton::new(&t!("file_inspector_exif_open_map_button"), "file_info_open_map")
+                    .payload(json!({ "geo_uri": gps.geo_uri() })),
+            )
+            .unwrap(),
+        );
+        rows.push(serde_json::to_value(UiText::new(&gps.osm_url()).size(12.0)).unwrap());
+    }
+    serde_json::to_value(
+        UiSection::new(rows)
+            .title(&t!("file_inspector_exif_section"))
+            .padding(8),
+    )
+    .unwrap()
+}
+
+fn render_media_section(media: &MediaInfo) -> Value {
+    let mut rows = Vec::new();
+    let mut line = |prefix: &str, value: String| {
+        rows.push(serde_json::to_value(UiText::new(&format!("{prefix}{value}")).size(14.0)).unwrap());
+    };
+    line(&t!("file_inspector_media_container_prefix"), media.container.clone());
+    if let Some(duration) = media.duration_s {
+        line(&t!("file_inspector_media_duration_prefix"), format_duration(duration));
+    }
+    if let Some(kbps) = media.bitrate_kbps {
+        line(&t!("file_inspector_media_bitrate_prefix"), format!("{kbps} kbit/s"));
+    }
+    for track in &media.tracks {
+        let prefix = match track.kind {
+            TrackKind::Video => t!("file_inspector_media_video_prefix"),
+            TrackKind::Audio => t!("file_inspector_media_audio_prefix"),
+        };
+        line(&prefix, track.describe());
+    }
+    serde_json::to_value(
+        UiSection::new(rows)
+            .title(&t!("file_inspector_media_section"))
+            .padding(8),
+    )
+    .unwrap()
+}
+
+fn render_package_section(package: &PackageInfo) -> Value {
+    let mut rows = Vec::new();
+    let mut line = |text: String, size: f64| {
+        rows.push(serde_json::to_value(UiText::new(&text).size(size)).unwrap());
+    };
+    if let Some(name) = &package.package {
+        line(format!("{}{name}", t!("file_inspector_apk_package_prefix")), 14.0);
+    }
+    let version = match (&package.version_name, &package.version_code) {
+        (Some(name), Some(code)) => Some(format!("{name} ({code})")),
+        (Some(version), None) | (None, Some(version)) => Some(version.clone()),
+        (None, None) => None,
+    };
+    if let Some(version) = version {
+        line(format!("{}{version}", t!("file_inspector_apk_version_prefix")), 14.0);
+    }
+    if let Some(min_sdk) = &package.min_sdk {
+        line(format!("{}{min_sdk}", t!("file_inspector_apk_min_sdk_prefix")), 14.0);
+    }
+    if let Some(target_sdk) = &package.target_sdk {
+        line(format!("{}{target_sdk}", t!("file_inspector_apk_target_sdk_prefix")), 14.0);
+    }
+    if package.schemes.is_empty() {
+        line(t!("file_inspector_apk_unsigned").to_string(), 14.0);
+    } else {
+        line(
+            format!(
+                "{}{}",
+                t!("file_inspector_apk_schemes_prefix"),
+                package.schemes.join(", ")
+            ),
+            14.0,
+        );
+    }
+    for cert in &package.certificates {
+        if let Some(subject) = &cert.subject {
+            line(format!("{}{subject}", t!("file_inspector_apk_cert_prefix")), 14.0);
+        }
+        line(format!("SHA-256: {}", cert.sha256), 12.0);
+    }
+    let title = match package.kind {
+        PackageKind::Apk => t!("file_inspector_apk_section"),
+        PackageKind::Jar => t!("file_inspector_jar_section"),
+    };
+    serde_json::to_value(UiSection::new(rows).title(&title).padding(8)).unwrap()
+}
+
+fn render_content_section(
+    entropy: Option<&EntropyReport>,
+    guessed_mime: Option<&str>,
+    extension_check: Option<&ExtensionCheck>,
+) -> Value {
+    let mut rows = Vec::new();
+    let mut line = |text: String| {
+        rows.push(serde_json::to_value(UiText::new(&text).size(14.0)).unwrap());
+    };
+    if let Some(report) = entropy {
+        line(format!(
+            "{}{:.2} bits/byte ({:.2}–{:.2})",
+            t!("file_inspector_entropy_prefix"),
+            report.bits_per_byte,
+            report.block_min,
+            report.block_max
+        ));
+        line(format!(
+            "{}{:.1}%",
+            t!("file_inspector_printable_prefix"),
+            report.printable_ratio * 100.0
+        ));
+        line(format!(
+            "{}{:.1}%",
+            t!("file_inspector_zero_bytes_prefix"),
+            report.zero_ratio * 100.0
+        ));
+        let verdict = match report.verdict {
+            ContentVerdict::Empty => None,
+            ContentVerdict::Text => Some(t!("file_inspector_verdict_text")),
+            ContentVerdict::Sparse => Some(t!("file_inspector_verdict_sparse")),
+            ContentVerdict::Binary => Some(t!("file_inspector_verdict_binary")),
+            ContentVerdict::Compressed => Some(t!("file_inspector_verdict_compressed")),
+            ContentVerdict::LikelyEncrypted => Some(t!("file_inspector_verdict_encrypted")),
+        };
+        if let Some(verdict) = verdict {
+            line(verdict.to_string());
+        }
+    }
+    if let Some(mime) = guessed_mime {
+        line(format!("{}{mime}", t!("file_inspector_guessed_type_prefix")));
+    }
+    if let Some(check) = extension_check {
+        match &check.actual {
+            Some(actual) => line(format!(
+                "{}.{actual} → .{}",
+                t!("file_inspector_extension_mismatch_prefix"),
+                check.expected
+            )),
+            None => line(format!(
+                "{}.{}",
+                t!("file_inspector_extension_missing_prefix"),
+                check.expected
+            )),
+        }
+    }
+    if let Some(report) = entropy.filter(|r| r.block_bits.len() > 1) {
+        let label = t!("file_inspector_entropy_prefix");
+        let x_axis = t!("file_inspector_entropy_chart_x");
+        let y_axis = t!("file_inspector_entropy_chart_y");
+        let blocks = (1..=report.block_bits.len())
+            .map(|i| i.to_string())
+            .collect();
+        rows.push(
+            serde_json::to_value(
+                UiChart::new(vec![ChartSeries::new(
+                    label.trim_end_matches([':', ' ']),
+                    report.block_bits.clone(),
+                )])
+                .bar()
+                .axes(&x_axis, &y_axis)
+                .y_range(0.0, 8.0)
+                .categories(blocks)
+                .height_dp(120)
+                .content_description("file_info_entropy_blocks"),
+            )
+            .unwrap(),
+        );
+    }
+    serde_json::to_value(
+        UiSection::new(rows)
+            .title(&t!("file_inspector_content_section"))
+            .padding(8),
+    )
+    .unwrap()
+}
+
 fn format_hex_dump(bytes: &[u8]) -> String {
+    format_hex_dump_at(bytes, 0)
+}
+
+/// Like [`format_hex_dump`], with offsets counted from `base`.
+fn format_hex_dump_at(bytes: &[u8], base: u64) -> String {
     let mut out = String::new();
     for (line, chunk) in bytes.chunks(16).enumerate() {
-        let offset = line * 16;
+        let offset = base + line as u64 * 16;
         out.push_str(&format!("{offset:08x}  "));
 
         for i in 0..16 {
@@ -152,16 +767,25 @@ pub fn render_file_info_screen(state: &AppState) -> Value {
             "type": "Button",
             "text": t!("file_inspector_pick_file_button"),
             "action": "file_info",
-            "requires_file_picker": true
+            "requires_file_picker": true,
+            "allow_multiple_files": true
         }),
     ];
 
+    if let Some(err) = &state.last_error {
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(14.0)).unwrap());
+    }
+
+    if !state.file_info_batch.is_empty() {
+        children.push(render_batch_section(&state.file_info_batch));
+    }
+
     if let Some(info_json) = &state.last_file_info {
         if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(info_json) {
             if let Some(err) = parsed.get("error").and_then(|e| e.as_str()) {
                 children.push(json!({
                     "type": "Text",
-                    "text": format!("{}{}", t!("multi_hash_error_prefix"), err),
+                    "text": error_text(err),
                     "size": 14.0
                 }));
             } else {
@@ -171,6 +795,12 @@ pub fn render_file_info_screen(state: &AppState) -> Value {
                         "text": format!("{}{}", t!("file_inspector_path_prefix"), path),
                     }));
                 }
+                if let Some(thumbnail) = parsed
+                    .get("thumbnail")
+                    .and_then(|t| serde_json::from_value::<Thumbnail>(t.clone()).ok())
+                {
+                    children.extend(render_thumbnail(&thumbnail));
+                }
                 if let Some(size) = parsed.get("size_bytes").and_then(|s| s.as_u64()) {
                     children.push(json!({
                         "type": "Text",
@@ -183,6 +813,32 @@ pub fn render_file_info_screen(state: &AppState) -> Value {
                         "text": format!("{}{}", t!("file_inspector_mime_prefix"), mime),
                     }));
                 }
+                if let Some(signature) = parsed
+                    .get("signature")
+                    .and_then(|s| serde_json::from_value::<SignatureMatch>(s.clone()).ok())
+                {
+                    children.push(json!({
+                        "type": "Text",
+                        "text": format!(
+                            "{}{} @ {} — {}",
+                            t!("file_inspector_signature_prefix"),
+                            signature.magic_hex,
+                            signature.offset,
+                            signature.description
+                        ),
+                        "size": 14.0
+                    }));
+                    let confidence = match signature.confidence {
+                        Confidence::High => t!("file_inspector_confidence_high"),
+                        Confidence::Medium => t!("file_inspector_confidence_medium"),
+                        Confidence::Low => t!("file_inspector_confidence_low"),
+                    };
+                    children.push(json!({
+                        "type": "Text",
+                        "text": format!("{}{}", t!("file_inspector_confidence_prefix"), confidence),
+                        "size": 14.0
+                    }));
+                }
                 if let Some(is_utf8) = parsed.get("is_utf8").and_then(|v| v.as_bool()) {
                     let status = if is_utf8 {
                         t!("file_inspector_utf8_detected")
@@ -195,6 +851,38 @@ pub fn render_file_info_screen(state: &AppState) -> Value {
                         "size": 14.0
                     }));
                 }
+                if let Some(exif) = parsed
+                    .get("exif")
+                    .and_then(|e| serde_json::from_value::<ExifSummary>(e.clone()).ok())
+                {
+                    children.push(render_exif_section(&exif));
+                }
+                if let Some(media) = parsed
+                    .get("media")
+                    .and_then(|m| serde_json::from_value::<MediaInfo>(m.clone()).ok())
+                {
+                    children.push(render_media_section(&media));
+                }
+                if let Some(package) = parsed
+                    .get("package")
+                    .and_then(|p| serde_json::from_value::<PackageInfo>(p.clone()).ok())
+                {
+                    children.push(render_package_section(&package));
+                }
+                let entropy = parsed
+                    .get("entropy")
+                    .and_then(|e| serde_json::from_value::<EntropyReport>(e.clone()).ok());
+                let guessed_mime = parsed.get("guessed_mime").and_then(|m| m.as_str());
+                let extension_check = parsed
+                    .get("extension_check")
+                    .and_then(|c| serde_json::from_value::<ExtensionCheck>(c.clone()).ok());
+                if entropy.is_some() || guessed_mime.is_some() || extension_check.is_some() {
+                    children.push(render_content_section(
+                        entropy.as_ref(),
+                        guessed_mime,
+                        extension_check.as_ref(),
+                    ));
+                }
                 if let Some(hex) = parsed.get("hex_dump").and_then(|h| h.as_str()) {
                     children.push(json!({
                         "type": "Text",
@@ -206,6 +894,10 @@ pub fn render_file_info_screen(state: &AppState) -> Value {
                             .unwrap(),
                     );
                 }
+                children.push(render_hex_window_section(
+                    state.file_info_hex.as_ref(),
+                    state.file_info_hex_error.as_deref(),
+                ));
             }
         }
     }
@@ -251,4 +943,21 @@ mod tests {
         assert!(lines[1].starts_with("00000010  67"));
         assert!(lines[1].ends_with("|g|"));
     }
+
+    #[test]
+    fn hex_window_uses_file_offsets_and_clamps_to_the_end() {
+        let data: Vec<u8> = (0..=255).collect();
+        let mut cursor = std::io::Cursor::new(&data);
+
+        let window = read_hex_window(&mut cursor, 0x20, 32).unwrap();
+        assert_eq!(window.total_bytes, 256);
+        assert!(window.dump.starts_with("00000020  20 21 22"));
+        assert_eq!(window.dump.lines().count(), 2);
+        assert!(window.has_previous() && window.has_next());
+
+        let last = read_hex_window(&mut cursor, 10_000, 64).unwrap();
+        assert_eq!(last.offset, 192);
+        assert!(last.dump.lines().last().unwrap().starts_with("000000f0  f0"));
+        assert!(!last.has_next());
+    }
 }
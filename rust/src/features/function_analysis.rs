@@ -11,40 +11,48 @@ use crate::features::c_based_ad::CBasedAutomaticDifferentiator;
 use std::time::Instant;
 
 pub fn render_function_analysis_screen(state: &AppState) -> Value {
-    let title = "Function Analysis";
-    let description = "Advanced MIR-based function analysis and visualization";
-    let ad_mode_text = "AD Mode:";
+    let title = t!("function_analysis_title");
+    let description = t!("function_analysis_description");
+    let ad_mode_text = t!("function_analysis_ad_mode");
     
-    let forward_label = if state.math_tool.get_ad_mode() == ADMode::Forward { "Forward Mode (✓)" } else { "Forward Mode" };
-    let reverse_label = if state.math_tool.get_ad_mode() == ADMode::Reverse { "Reverse Mode (✓)" } else { "Reverse Mode" };
+    let mode_label = |mode: ADMode, key: &str| {
+        let label = t!(key);
+        if state.math_tool.get_ad_mode() == mode {
+            format!("{label} (✓)")
+        } else {
+            label.into_owned()
+        }
+    };
+    let forward_label = mode_label(ADMode::Forward, "function_analysis_forward_mode");
+    let reverse_label = mode_label(ADMode::Reverse, "function_analysis_reverse_mode");
     
     let mut children = vec![
-        serde_json::to_value(UiText::new(title).size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&title).size(20.0)).unwrap(),
         serde_json::to_value(
-            UiText::new(description)
+            UiText::new(&description)
                 .size(14.0),
         )
         .unwrap(),
         serde_json::to_value(
             UiTextInput::new("function_analysis_expr")
-                .hint("Enter function to analyze (e.g., x^2 + sin(x))")
+                .hint(&t!("function_analysis_expr_hint"))
                 .text(&state.math_tool.expression)
                 .single_line(true)
                 .debounce_ms(150),
         )
         .unwrap(),
-        serde_json::to_value(UiText::new(ad_mode_text).size(14.0)).unwrap(),
-        serde_json::to_value(UiButton::new(forward_label, "function_analysis_set_forward")).unwrap(),
-        serde_json::to_value(UiButton::new(reverse_label, "function_analysis_set_reverse")).unwrap(),
-        serde_json::to_value(UiButton::new("Analyze Performance", "function_analysis_performance")).unwrap(),
-        serde_json::to_value(UiButton::new("Compute Derivative", "function_analysis_derivative")).unwrap(),
-        serde_json::to_value(UiButton::new("Plot Function", "function_analysis_plot")).unwrap(),
-        serde_json::to_value(UiButton::new("Stability Test", "function_analysis_stability")).unwrap(),
+        serde_json::to_value(UiText::new(&ad_mode_text).size(14.0)).unwrap(),
+        serde_json::to_value(UiButton::new(&forward_label, "function_analysis_set_forward")).unwrap(),
+        serde_json::to_value(UiButton::new(&reverse_label, "function_analysis_set_reverse")).unwrap(),
+        serde_json::to_value(UiButton::new(&t!("function_analysis_performance_button"), "function_analysis_performance")).unwrap(),
+        serde_json::to_value(UiButton::new(&t!("function_analysis_derivative_button"), "function_analysis_derivative")).unwrap(),
+        serde_json::to_value(UiButton::new(&t!("function_analysis_plot_button"), "function_analysis_plot")).unwrap(),
+        serde_json::to_value(UiButton::new(&t!("function_analysis_stability_button"), "function_analysis_stability")).unwrap(),
     ];
 
     // Show analysis results if available
     if let Some(error) = &state.math_tool.error {
-        let error_msg = format!("Result: {}", error);
+        let error_msg = t!("function_analysis_result", result = error);
         children.push(
             serde_json::to_value(UiText::new(&error_msg).size(12.0)).unwrap(),
         );
@@ -52,9 +60,9 @@ pub fn render_function_analysis_screen(state: &AppState) -> Value {
 
     // Add visualization placeholder
     {
-        let viz_text = "Visualization will appear here";
+        let viz_text = t!("function_analysis_visualization_placeholder");
         children.push(
-            serde_json::to_value(UiText::new(viz_text).size(14.0)).unwrap(),
+            serde_json::to_value(UiText::new(&viz_text).size(14.0)).unwrap(),
         );
     }
 
@@ -0,0 +1,414 @@
+use crate::error::error_text;
+use crate::state::AppState;
+use crate::ui::{
+    maybe_push_back, Button as UiButton, Column as UiColumn, Section as UiSection, Text as UiText,
+    TextInput as UiTextInput,
+};
+use image::codecs::gif::{GifDecoder, GifEncoder, Repeat};
+use image::codecs::png::PngEncoder;
+use image::imageops::FilterType;
+use image::{imageops, AnimationDecoder, Delay, Frame, ImageEncoder};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+/// Frames past this are not extracted, to keep storage and memory bounded.
+pub const MAX_FRAMES: usize = 500;
+pub const MIN_DELAY_MS: u32 = 20;
+pub const MAX_DELAY_MS: u32 = 10_000;
+pub const DEFAULT_DELAY_MS: u32 = 100;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GifFrameFile {
+    pub path: String,
+    pub delay_ms: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExplodedGif {
+    pub frames_dir: String,
+    pub width: u32,
+    pub height: u32,
+    pub frames: Vec<GifFrameFile>,
+    /// The animation had more than `MAX_FRAMES` frames.
+    pub truncated: bool,
+}
+
+impl ExplodedGif {
+    pub fn average_delay_ms(&self) -> u32 {
+        if self.frames.is_empty() {
+            return DEFAULT_DELAY_MS;
+        }
+        let total: u64 = self.frames.iter().map(|f| f.delay_ms as u64).sum();
+        (total / self.frames.len() as u64) as u32
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GifFramesState {
+    pub source_name: Option<String>,
+    pub output_dir: Option<String>,
+    pub exploded: Option<ExplodedGif>,
+    /// Frame list typed by the user, e.g. `1-10, 12, 20-15`.
+    pub selection: String,
+    pub delay_ms: u32,
+    pub result_path: Option<String>,
+    pub error: Option<String>,
+}
+
+impl GifFramesState {
+    pub const fn new() -> Self {
+        Self {
+            source_name: None,
+            output_dir: None,
+            exploded: None,
+            selection: String::new(),
+            delay_ms: DEFAULT_DELAY_MS,
+            result_path: None,
+            error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn sync_bindings(&mut self, bindings: &HashMap<String, String>) {
+        if let Some(selection) = bindings.get("gif_frames_selection") {
+            self.selection = selection.trim().to_string();
+        }
+        if let Some(Ok(delay)) = bindings
+            .get("gif_frames_delay_ms")
+            .map(|v| v.trim().parse::<u32>())
+        {
+            self.delay_ms = delay.clamp(MIN_DELAY_MS, MAX_DELAY_MS);
+        }
+    }
+
+    pub fn stem(&self) -> String {
+        self.source_name
+            .as_deref()
+            .map(|name| name.rsplit_once('.').map_or(name, |(stem, _)| stem))
+            .filter(|stem| !stem.is_empty())
+            .unwrap_or("animation")
+            .to_string()
+    }
+}
+
+/// Parses 1-based frame numbers and ranges into 0-based indices, in the order given.
+/// Descending ranges play backwards and frames may repeat.
+pub fn parse_frame_selection(raw: &str, frame_count: usize) -> Result<Vec<usize>, String> {
+    let mut out = Vec::new();
+    for part in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let parse = |s: &str| -> Result<usize, String> {
+            match s.trim().parse::<usize>() {
+                Ok(n) if (1..=frame_count).contains(&n) => Ok(n - 1),
+                _ => Err(format!("gif_frames_invalid_selection:{part}")),
+            }
+        };
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse(start)?, parse(end)?);
+                if start <= end {
+                    out.extend(start..=end);
+                } else {
+                    out.extend((end..=start).rev());
+                }
+            }
+            None => out.push(parse(part)?),
+        }
+    }
+    if out.is_empty() {
+        return Err("gif_frames_empty_selection".into());
+    }
+    Ok(out)
+}
+
+/// `dir/{stem}{suffix}`, or `dir/{stem}{suffix}_2`, ... if that name is taken.
+fn unique_path(dir: &Path, stem: &str, suffix: &str, extension: &str) -> PathBuf {
+    let mut n = 1;
+    loop {
+        let name = if n == 1 {
+            format!("{stem}{suffix}{extension}")
+        } else {
+            format!("{stem}{suffix}_{n}{extension}")
+        };
+        let candidate = dir.join(name);
+        if !candidate.exists() {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+fn path_string(path: &Path) -> Result<String, String> {
+    path.to_str()
+        .map(str::to_string)
+        .ok_or_else(|| "path_utf8".to_string())
+}
+
+/// Writes every composited frame as `frame_NNN.png` into a new `{stem}_frames` folder.
+pub fn explode_gif(source: &str, stem: &str, output_dir: &str) -> Result<ExplodedGif, String> {
+    let file = File::open(source).map_err(|e| format!("open_failed:{e}"))?;
+    let decoder =
+        GifDecoder::new(BufReader::new(file)).map_err(|e| format!("gif_decode_failed:{e}"))?;
+    let frames_dir = unique_path(Path::new(output_dir), stem, "_frames", "");
+    fs::create_dir_all(&frames_dir).map_err(|e| format!("output_dir_create_failed:{e}"))?;
+
+    let mut frames = Vec::new();
+    let mut size = (0, 0);
+    let mut truncated = false;
+    for (i, frame) in decoder.into_frames().enumerate() {
+        if i == MAX_FRAMES {
+            truncated = true;
+            break;
+        }
+        let frame = frame.map_err(|e| format!("gif_decode_failed:{e}"))?;
+        let (numer, denom) = frame.delay().numer_denom_ms();
+        let delay_ms = numer / denom.max(1);
+        let buffer = frame.into_buffer();
+        size = buffer.dimensions();
+        let path = frames_dir.join(format!("frame_{:03}.png", i + 1));
+        let out = File::create(&path).map_err(|e| format!("save_failed:{e}"))?;
+        PngEncoder::new(BufWriter::new(out))
+            .write_image(
+                buffer.as_raw(),
+                buffer.width(),
+                buffer.height(),
+                image::ColorType::Rgba8,
+            )
+            .map_err(|e| format!("encode_failed:{e}"))?;
+        frames.push(GifFrameFile {
+            path: path_string(&path)?,
+            delay_ms,
+        });
+    }
+    if frames.is_empty() {
+        return Err("gif_frames_no_frames".into());
+    }
+    Ok(ExplodedGif {
+        frames_dir: path_string(&frames_dir)?,
+        width: size.0,
+        height: size.1,
+        frames,
+        truncated,
+    })
+}
+
+/// Encodes `frames` (PNG paths) into a looping GIF; frames are scaled to the first one's size.
+pub fn assemble_gif(
+    frames: &[String],
+    delay_ms: u32,
+    stem: &str,
+    output_dir: &str,
+) -> Result<String, String> {
+    let first = frames.first().ok_or("gif_frames_empty_selection")?;
+    let (width, height) = image::image_dimensions(first).map_err(|e| format!("open_failed:{e}"))?;
+    fs::create_dir_all(output_dir).map_err(|e| format!("output_dir_create_failed:{e}"))?;
+    let path = unique_path(Path::new(output_dir), stem, "_assembled", ".gif");
+    let out = File::create(&path).map_err(|e| format!("save_failed:{e}"))?;
+    let mut encoder = GifEncoder::new_with_speed(BufWriter::new(out), 10);
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .map_err(|e| format!("encode_failed:{e}"))?;
+    let delay = Delay::from_numer_denom_ms(delay_ms.clamp(MIN_DELAY_MS, MAX_DELAY_MS), 1);
+    for frame_path in frames {
+        let mut rgba = image::open(frame_path)
+            .map_err(|e| format!("open_failed:{e}"))?
+            .to_rgba8();
+        if rgba.dimensions() != (width, height) {
+            rgba = imageops::resize(&rgba, width, height, FilterType::Triangle);
+        }
+        encoder
+            .encode_frame(Frame::from_parts(rgba, 0, 0, delay))
+            .map_err(|e| format!("encode_failed:{e}"))?;
+    }
+    drop(encoder);
+    path_string(&path)
+}
+
+fn text(value: &str, size: f64) -> Value {
+    serde_json::to_value(UiText::new(value).size(size)).unwrap()
+}
+
+pub fn render_gif_frames_screen(state: &AppState) -> Value {
+    let gif = &state.gif_frames;
+    let mut children = vec![
+        text(&t!("gif_frames_title"), 20.0),
+        text(&t!("gif_frames_description"), 14.0),
+        serde_json::to_value(
+            UiButton::new(&t!("gif_frames_pick_button"), "gif_frames_pick")
+                .requires_file_picker(true)
+                .id("gif_frames_pick"),
+        )
+        .unwrap(),
+    ];
+
+    if let Some(err) = &gif.error {
+        children.push(text(&error_text(err), 12.0));
+    }
+
+    if let Some(exploded) = &gif.exploded {
+        let mut summary = format!(
+            "{}{} · {}×{} · {}{} ms",
+            t!("gif_frames_count_prefix"),
+            exploded.frames.len(),
+            exploded.width,
+            exploded.height,
+            t!("gif_frames_average_delay_prefix"),
+            exploded.average_delay_ms()
+        );
+        if let Some(name) = &gif.source_name {
+            summary = format!("{name} — {summary}");
+        }
+        children.push(text(&summary, 14.0));
+        if exploded.truncated {
+            children.push(text(
+                &format!("{}{MAX_FRAMES}", t!("gif_frames_truncated_prefix")),
+                12.0,
+            ));
+        }
+        children.push(text(
+            &format!(
+                "{}{}",
+                t!("gif_frames_saved_to_prefix"),
+                exploded.frames_dir
+            ),
+            12.0,
+        ));
+        children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("dithering_copy_result_path_button"), "copy_clipboard")
+                    .copy_text(&exploded.frames_dir),
+            )
+            .unwrap(),
+        );
+
+        let delay = gif.delay_ms.to_string();
+        let assemble = vec![
+            text(&t!("gif_frames_selection_label"), 12.0),
+            serde_json::to_value(
+                UiTextInput::new("gif_frames_selection")
+                    .text(&gif.selection)
+                    .hint(&t!("gif_frames_selection_hint"))
+                    .single_line(true),
+            )
+            .unwrap(),
+            text(&t!("gif_frames_delay_label"), 12.0),
+            serde_json::to_value(
+                UiTextInput::new("gif_frames_delay_ms")
+                    .text(&delay)
+                    .single_line(true),
+            )
+            .unwrap(),
+            serde_json::to_value(
+                UiButton::new(&t!("gif_frames_assemble_button"), "gif_frames_assemble")
+                    .id("gif_frames_assemble"),
+            )
+            .unwrap(),
+        ];
+        children.push(
+            serde_json::to_value(
+                UiSection::new(assemble)
+                    .title(&t!("gif_frames_assemble_section"))
+                    .padding(12),
+            )
+            .unwrap(),
+        );
+    }
+
+    if let Some(path) = &gif.result_path {
+        children.push(text(
+            &format!("{}{path}", t!("gif_frames_result_prefix")),
+            12.0,
+        ));
+        children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("dithering_copy_result_path_button"), "copy_clipboard")
+                    .copy_text(path),
+            )
+            .unwrap(),
+        );
+    }
+
+    maybe_push_back(&mut children, state);
+    serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    fn write_gif(path: &Path, colors: &[[u8; 4]], delay_ms: u32) {
+        let file = File::create(path).unwrap();
+        let mut encoder = GifEncoder::new_with_speed(file, 10);
+        encoder.set_repeat(Repeat::Infinite).unwrap();
+        for color in colors {
+            let frame = Frame::from_parts(
+                RgbaImage::from_pixel(6, 4, Rgba(*color)),
+                0,
+                0,
+                Delay::from_numer_denom_ms(delay_ms, 1),
+            );
+            encoder.encode_frame(frame).unwrap();
+        }
+    }
+
+    #[test]
+    fn selection_supports_ranges_reversal_and_repeats() {
+        assert_eq!(
+            parse_frame_selection("1-3, 5,3-1", 5).unwrap(),
+            vec![0, 1, 2, 4, 2, 1, 0]
+        );
+        assert_eq!(
+            parse_frame_selection("2-9", 5).unwrap_err(),
+            "gif_frames_invalid_selection:2-9"
+        );
+        assert_eq!(
+            parse_frame_selection(" , ", 5).unwrap_err(),
+            "gif_frames_empty_selection"
+        );
+    }
+
+    #[test]
+    fn explodes_and_reassembles_frames() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("blink.gif");
+        write_gif(
+            &source,
+            &[[255, 0, 0, 255], [0, 0, 255, 255], [0, 255, 0, 255]],
+            70,
+        );
+        let out_dir = dir.path().to_str().unwrap();
+
+        let exploded = explode_gif(source.to_str().unwrap(), "blink", out_dir).unwrap();
+        assert_eq!(exploded.frames.len(), 3);
+        assert_eq!((exploded.width, exploded.height), (6, 4));
+        assert_eq!(exploded.average_delay_ms(), 70);
+        assert!(exploded.frames_dir.ends_with("blink_frames"));
+        let second = image::open(&exploded.frames[1].path).unwrap().to_rgba8();
+        assert_eq!(second.get_pixel(0, 0).0, [0, 0, 255, 255]);
+
+        let again = explode_gif(source.to_str().unwrap(), "blink", out_dir).unwrap();
+        assert!(again.frames_dir.ends_with("blink_frames_2"));
+
+        let picked: Vec<String> = parse_frame_selection("3,1", 3)
+            .unwrap()
+            .into_iter()
+            .map(|i| exploded.frames[i].path.clone())
+            .collect();
+        let gif = assemble_gif(&picked, 200, "blink", out_dir).unwrap();
+        assert!(gif.ends_with("blink_assembled.gif"));
+        let decoder = GifDecoder::new(BufReader::new(File::open(&gif).unwrap())).unwrap();
+        let frames = decoder.into_frames().collect_frames().unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].delay().numer_denom_ms(), (200, 1));
+        assert_eq!(frames[0].buffer().get_pixel(0, 0).0, [0, 255, 0, 255]);
+        assert_eq!(frames[1].buffer().get_pixel(0, 0).0, [255, 0, 0, 255]);
+    }
+}
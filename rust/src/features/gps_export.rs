@@ -0,0 +1,302 @@
+use crate::features::sensor_logger::ndjson_line_to_csv;
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlite::{Connection, State};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Track formats the sensor log can be converted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpsExportFormat {
+    Gpx,
+    Kml,
+}
+
+impl GpsExportFormat {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "gpx" => Ok(Self::Gpx),
+            "kml" => Ok(Self::Kml),
+            other => Err(format!("gps_export_unknown_format:{other}")),
+        }
+    }
+
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Gpx => "gpx",
+            Self::Kml => "kml",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpsPoint {
+    pub ts_ms: i64,
+    pub lat: f64,
+    pub lon: f64,
+    pub accuracy_m: Option<f64>,
+}
+
+/// Extracts the `GPS` rows (`ts,GPS,lat,lon,accuracy,..`) from a sensor log CSV
+/// or JSON Lines log. Rows for other sensors are ignored; malformed GPS rows are skipped.
+pub fn parse_gps_points(csv: &str) -> Result<Vec<GpsPoint>, String> {
+    let mut points = Vec::new();
+    for line in csv.lines() {
+        let converted;
+        let line = if line.trim_start().starts_with('{') {
+            match ndjson_line_to_csv(line) {
+                Some(row) => {
+                    converted = row;
+                    converted.as_str()
+                }
+                None => continue,
+            }
+        } else {
+            line
+        };
+        let mut cols = line.split(',').map(str::trim);
+        let (Some(ts), Some("GPS"), Some(lat), Some(lon)) =
+            (cols.next(), cols.next(), cols.next(), cols.next())
+        else {
+            continue;
+        };
+        let (Ok(ts_ms), Ok(lat), Ok(lon)) = (ts.parse::<i64>(), lat.parse::<f64>(), lon.parse::<f64>())
+        else {
+            continue;
+        };
+        if !valid_point(ts_ms, lat, lon) {
+            continue;
+        }
+        let accuracy_m = cols
+            .next()
+            .and_then(|v| v.parse::<f64>().ok())
+            .filter(|v| v.is_finite() && *v >= 0.0);
+        points.push(GpsPoint {
+            ts_ms,
+            lat,
+            lon,
+            accuracy_m,
+        });
+    }
+    if points.is_empty() {
+        return Err("gps_export_no_points".into());
+    }
+    Ok(points)
+}
+
+fn valid_point(ts_ms: i64, lat: f64, lon: f64) -> bool {
+    (-90.0..=90.0).contains(&lat)
+        && (-180.0..=180.0).contains(&lon)
+        && DateTime::<Utc>::from_timestamp_millis(ts_ms).is_some()
+}
+
+/// Reads the `gps` table written by the SQLite logging backend.
+pub fn load_gps_points_from_db(db_path: &str) -> Result<Vec<GpsPoint>, String> {
+    if !Path::new(db_path).is_file() {
+        return Err("gps_export_read_failed:missing database".into());
+    }
+    let conn = Connection::open(db_path).map_err(|e| format!("gps_export_read_failed:{e}"))?;
+    // A log without GPS samples simply has no gps table.
+    let mut stmt = match conn.prepare("SELECT ts, x, y, z FROM gps ORDER BY ts") {
+        Ok(stmt) => stmt,
+        Err(_) => return Err("gps_export_no_points".into()),
+    };
+    let mut points = Vec::new();
+    while let State::Row = stmt.next().map_err(|e| format!("gps_export_read_failed:{e}"))? {
+        let ts_ms = stmt.read::<i64, usize>(0).map_err(|e| format!("gps_export_read_failed:{e}"))?;
+        let lat = stmt.read::<Option<f64>, usize>(1).ok().flatten();
+        let lon = stmt.read::<Option<f64>, usize>(2).ok().flatten();
+        let accuracy_m = stmt
+            .read::<Option<f64>, usize>(3)
+            .ok()
+            .flatten()
+            .filter(|v| v.is_finite() && *v >= 0.0);
+        if let (Some(lat), Some(lon)) = (lat, lon) {
+            if valid_point(ts_ms, lat, lon) {
+                points.push(GpsPoint {
+                    ts_ms,
+                    lat,
+                    lon,
+                    accuracy_m,
+                });
+            }
+        }
+    }
+    if points.is_empty() {
+        return Err("gps_export_no_points".into());
+    }
+    Ok(points)
+}
+
+fn iso_time(ts_ms: i64) -> Option<String> {
+    DateTime::<Utc>::from_timestamp_millis(ts_ms)
+        .map(|dt| dt.to_rfc3339_opts(SecondsFormat::Millis, true))
+}
+
+fn escape_xml(raw: &str) -> String {
+    raw.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn render_gpx(points: &[GpsPoint], name: &str) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <gpx version=\"1.1\" creator=\"kistaverk\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n",
+    );
+    out.push_str(&format!(
+        "  <trk>\n    <name>{}</name>\n    <trkseg>\n",
+        escape_xml(name)
+    ));
+    for p in points {
+        out.push_str(&format!(
+            "      <trkpt lat=\"{:.7}\" lon=\"{:.7}\">",
+            p.lat, p.lon
+        ));
+        if let Some(time) = iso_time(p.ts_ms) {
+            out.push_str(&format!("<time>{time}</time>"));
+        }
+        if let Some(acc) = p.accuracy_m {
+            out.push_str(&format!("<desc>accuracy {acc:.1} m</desc>"));
+        }
+        out.push_str("</trkpt>\n");
+    }
+    out.push_str("    </trkseg>\n  </trk>\n</gpx>\n");
+    out
+}
+
+pub fn render_kml(points: &[GpsPoint], name: &str) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <kml xmlns=\"http://www.opengis.net/kml/2.2\" xmlns:gx=\"http://www.google.com/kml/ext/2.2\">\n",
+    );
+    out.push_str(&format!(
+        "  <Document>\n    <name>{0}</name>\n    <Placemark>\n      <name>{0}</name>\n      <gx:Track>\n",
+        escape_xml(name)
+    ));
+    // gx:Track lists every <when> before the matching <gx:coord> entries.
+    for p in points {
+        if let Some(time) = iso_time(p.ts_ms) {
+            out.push_str(&format!("        <when>{time}</when>\n"));
+        }
+    }
+    for p in points {
+        // KML coordinates are longitude first.
+        out.push_str(&format!(
+            "        <gx:coord>{:.7} {:.7} 0</gx:coord>\n",
+            p.lon, p.lat
+        ));
+    }
+    out.push_str("      </gx:Track>\n    </Placemark>\n  </Document>\n</kml>\n");
+    out
+}
+
+fn export_path(csv_path: &Path, format: GpsExportFormat) -> PathBuf {
+    csv_path.with_extension(format.extension())
+}
+
+/// Converts a sensor log (CSV, or `.db` from the SQLite backend) into a GPX/KML
+/// track saved next to it. Returns the path of the written file.
+pub fn export_gps_log(log_path: &str, format: GpsExportFormat) -> Result<String, String> {
+    let path = Path::new(log_path);
+    let points = if path.extension().is_some_and(|ext| ext == "db") {
+        load_gps_points_from_db(log_path)?
+    } else {
+        let csv =
+            fs::read_to_string(log_path).map_err(|e| format!("gps_export_read_failed:{e}"))?;
+        parse_gps_points(&csv)?
+    };
+    let name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "kistaverk track".into());
+    let body = match format {
+        GpsExportFormat::Gpx => render_gpx(&points, &name),
+        GpsExportFormat::Kml => render_kml(&points, &name),
+    };
+    let out = export_path(path, format);
+    fs::write(&out, body).map_err(|e| format!("gps_export_write_failed:{e}"))?;
+    Ok(out.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "ts,sensor,x,y,z,extra1,extra2\n\
+        1700000000000,ACCEL,0.1,0.2,9.8,0,0\n\
+        1700000000500,GPS,64.1466,-21.9426,4.5,-1,-1\n\
+        1700000001500,GPS,64.1467,-21.9427,3.0,-1,-1\n\
+        1700000002000,GPS,not-a-number,-21.9,3.0,-1,-1\n";
+
+    #[test]
+    fn parses_only_valid_gps_rows() {
+        let points = parse_gps_points(LOG).unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].ts_ms, 1_700_000_000_500);
+        assert!((points[1].lon + 21.9427).abs() < 1e-9);
+        assert_eq!(points[0].accuracy_m, Some(4.5));
+    }
+
+    #[test]
+    fn rejects_logs_without_gps() {
+        let err = parse_gps_points("ts,sensor,x,y,z,extra1,extra2\n1,ACCEL,0,0,0,0,0\n").unwrap_err();
+        assert_eq!(err, "gps_export_no_points");
+    }
+
+    #[test]
+    fn parses_gps_rows_from_json_lines() {
+        let log = "{\"ts\":1700000000500,\"sensor\":\"GPS\",\"x\":64.1466,\"y\":-21.9426,\"z\":4.5}\n\
+            {\"ts\":1700000000600,\"sensor\":\"ACCEL\",\"x\":0.1,\"y\":0.2,\"z\":9.8}\n";
+        let points = parse_gps_points(log).unwrap();
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0].accuracy_m, Some(4.5));
+    }
+
+    #[test]
+    fn gpx_and_kml_contain_track_points() {
+        let points = parse_gps_points(LOG).unwrap();
+        let gpx = render_gpx(&points, "a&b");
+        assert!(gpx.contains("<name>a&amp;b</name>"));
+        assert!(gpx.contains("<trkpt lat=\"64.1466000\" lon=\"-21.9426000\">"));
+        assert!(gpx.contains("<time>2023-11-14T22:13:20.500Z</time>"));
+
+        let kml = render_kml(&points, "track");
+        assert!(kml.contains("<gx:coord>-21.9427000 64.1467000 0</gx:coord>"));
+        assert_eq!(kml.matches("<when>").count(), 2);
+    }
+
+    #[test]
+    fn export_reads_gps_table_from_sqlite_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("sensors_2.db");
+        let conn = Connection::open(&db).unwrap();
+        conn.execute(
+            "CREATE TABLE gps (ts INTEGER NOT NULL, x REAL, y REAL, z REAL, extra1 REAL, extra2 REAL);
+             INSERT INTO gps VALUES (1700000000500, 64.1466, -21.9426, 4.5, -1, -1);
+             INSERT INTO gps VALUES (1700000001500, 64.1467, -21.9427, NULL, -1, -1);",
+        )
+        .unwrap();
+        drop(conn);
+
+        let points = load_gps_points_from_db(db.to_str().unwrap()).unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[1].accuracy_m, None);
+        let out = export_gps_log(db.to_str().unwrap(), GpsExportFormat::Gpx).unwrap();
+        assert!(out.ends_with("sensors_2.gpx"));
+    }
+
+    #[test]
+    fn export_writes_file_next_to_csv() {
+        let dir = tempfile::tempdir().unwrap();
+        let csv = dir.path().join("sensors_1.csv");
+        fs::write(&csv, LOG).unwrap();
+        let out = export_gps_log(csv.to_str().unwrap(), GpsExportFormat::Kml).unwrap();
+        assert!(out.ends_with("sensors_1.kml"));
+        assert!(fs::read_to_string(out).unwrap().starts_with("<?xml"));
+        assert_eq!(
+            GpsExportFormat::parse("svg").unwrap_err(),
+            "gps_export_unknown_format:svg"
+        );
+    }
+}
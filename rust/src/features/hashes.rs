@@ -1,36 +1,115 @@
+use crate::error::error_text;
+use crate::features::text_tools::hex_decode;
 use crate::state::{AppState, MultiHashResults};
-use crate::ui::{maybe_push_back, Button as UiButton, Text as UiText, TextInput as UiTextInput};
+use crate::ui::{
+    maybe_push_back, Button as UiButton, Checkbox as UiCheckbox, Row as UiRow, Text as UiText,
+    TextInput as UiTextInput,
+};
 use blake3::Hasher as Blake3;
 use crc32fast::Hasher as Crc32;
+use hmac::{Hmac, Mac};
 use md4::Md4;
 use md5::Md5;
 use serde_json::{json, Value};
 use sha1::Sha1;
-use sha2::{digest::Digest, Sha256};
+use sha2::{digest::Digest, Sha256, Sha512};
+use sha3::{Sha3_256, Sha3_512};
 use std::fs::File;
 use std::io::{BufReader, Read};
 use std::os::unix::io::{FromRawFd, RawFd};
+use xxhash_rust::{xxh3::Xxh3, xxh64::Xxh64};
 use rust_i18n::t;
 
 #[derive(Debug, Clone, Copy)]
 pub enum HashAlgo {
     Sha256,
+    Sha512,
+    Sha3_256,
+    Sha3_512,
     Sha1,
     Md5,
     Md4,
     Crc32,
     Blake3,
+    Xxh64,
+    Xxh3,
 }
 
 #[allow(dead_code)]
 pub fn hash_label(algo: HashAlgo) -> &'static str {
     match algo {
         HashAlgo::Sha256 => "SHA-256",
+        HashAlgo::Sha512 => "SHA-512",
+        HashAlgo::Sha3_256 => "SHA3-256",
+        HashAlgo::Sha3_512 => "SHA3-512",
         HashAlgo::Sha1 => "SHA-1",
         HashAlgo::Md5 => "MD5",
         HashAlgo::Md4 => "MD4",
         HashAlgo::Crc32 => "CRC32",
         HashAlgo::Blake3 => "BLAKE3",
+        HashAlgo::Xxh64 => "xxHash64",
+        HashAlgo::Xxh3 => "XXH3-64",
+    }
+}
+
+/// Keyed digests offered by the verify screen's HMAC mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HmacAlgo {
+    #[default]
+    Sha256,
+    Sha1,
+    /// BLAKE3's own keyed mode rather than HMAC; it takes a 32-byte key.
+    Blake3,
+}
+
+impl HmacAlgo {
+    pub const ALL: [HmacAlgo; 3] = [HmacAlgo::Sha256, HmacAlgo::Sha1, HmacAlgo::Blake3];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            HmacAlgo::Sha256 => "sha256",
+            HmacAlgo::Sha1 => "sha1",
+            HmacAlgo::Blake3 => "blake3",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|algo| algo.id() == id)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HmacAlgo::Sha256 => "HMAC-SHA256",
+            HmacAlgo::Sha1 => "HMAC-SHA1",
+            HmacAlgo::Blake3 => "BLAKE3 keyed",
+        }
+    }
+}
+
+/// HMAC mode of the verify screen. Kept out of snapshots along with its key.
+#[derive(Debug, Clone, Default)]
+pub struct HmacConfig {
+    pub enabled: bool,
+    pub algo: HmacAlgo,
+    pub key: String,
+    /// The key field holds hex digits rather than text.
+    pub key_hex: bool,
+}
+
+impl HmacConfig {
+    pub fn key_bytes(&self) -> Result<Vec<u8>, String> {
+        let key = if self.key_hex {
+            hex_decode(&self.key.split_whitespace().collect::<String>())?
+        } else {
+            self.key.as_bytes().to_vec()
+        };
+        if key.is_empty() {
+            return Err("hmac_key_empty".into());
+        }
+        if self.algo == HmacAlgo::Blake3 && key.len() != blake3::KEY_LEN {
+            return Err(format!("hmac_blake3_key_length:{}", key.len()));
+        }
+        Ok(key)
     }
 }
 
@@ -158,12 +237,114 @@ pub enum HashSource<'a> {
     Path(&'a str),
 }
 
+/// Bytes read between two progress reports.
+const PROGRESS_STEP_BYTES: u64 = 1 << 20;
+
+/// Passes reads through, reporting the fraction of `total` consumed so far every
+/// [`PROGRESS_STEP_BYTES`].
+pub(crate) struct ProgressReader<R, F> {
+    inner: R,
+    total: u64,
+    done: u64,
+    next_report: u64,
+    on_progress: F,
+}
+
+impl<F: FnMut(f32)> ProgressReader<File, F> {
+    /// Files without a known size (pipes) read normally but never report.
+    pub(crate) fn new(file: File, on_progress: F) -> Self {
+        let total = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Self {
+            inner: file,
+            total,
+            done: 0,
+            next_report: PROGRESS_STEP_BYTES,
+            on_progress,
+        }
+    }
+}
+
+impl<R: Read, F: FnMut(f32)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.done += read as u64;
+        if self.total > 0 && self.done >= self.next_report {
+            (self.on_progress)((self.done as f64 / self.total as f64).min(1.0) as f32);
+            self.next_report = self.done + PROGRESS_STEP_BYTES;
+        }
+        Ok(read)
+    }
+}
+
+fn open_source(source: HashSource<'_>) -> Result<File, String> {
+    match source {
+        HashSource::RawFd(fd) => Ok(unsafe { File::from_raw_fd(fd) }),
+        HashSource::Path(path) => File::open(path).map_err(|e| format!("open_failed:{e}")),
+    }
+}
+
 pub fn compute_hash(source: HashSource<'_>, algo: HashAlgo) -> Result<String, String> {
-    let file = match source {
-        HashSource::RawFd(fd) => unsafe { File::from_raw_fd(fd) },
-        HashSource::Path(path) => File::open(path).map_err(|e| format!("open_failed:{e}"))?,
-    };
-    hash_stream(file, algo)
+    compute_hash_with_progress(source, algo, |_| {})
+}
+
+/// Like [`compute_hash`], calling `on_progress` with the fraction of the file read so far.
+pub fn compute_hash_with_progress(
+    source: HashSource<'_>,
+    algo: HashAlgo,
+    on_progress: impl FnMut(f32),
+) -> Result<String, String> {
+    let file = open_source(source)?;
+    hash_stream(ProgressReader::new(file, on_progress), algo)
+}
+
+/// Keyed digest of the file; `key` comes from [`HmacConfig::key_bytes`].
+pub fn compute_hmac_with_progress(
+    source: HashSource<'_>,
+    algo: HmacAlgo,
+    key: &[u8],
+    on_progress: impl FnMut(f32),
+) -> Result<String, String> {
+    let file = open_source(source)?;
+    hmac_stream(ProgressReader::new(file, on_progress), algo, key)
+}
+
+fn hmac_stream<R: Read>(reader: R, algo: HmacAlgo, key: &[u8]) -> Result<String, String> {
+    fn feed<R: Read>(reader: R, mut update: impl FnMut(&[u8])) -> Result<(), String> {
+        let mut reader = BufReader::new(reader);
+        let mut buffer = [0u8; 8192];
+        loop {
+            let read = reader
+                .read(&mut buffer)
+                .map_err(|e| format!("read_failed:{e}"))?;
+            if read == 0 {
+                return Ok(());
+            }
+            update(&buffer[..read]);
+        }
+    }
+
+    match algo {
+        HmacAlgo::Sha256 => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(key).map_err(|_| "hmac_key_empty")?;
+            feed(reader, |chunk| mac.update(chunk))?;
+            Ok(format!("{:x}", mac.finalize().into_bytes()))
+        }
+        HmacAlgo::Sha1 => {
+            let mut mac = Hmac::<Sha1>::new_from_slice(key).map_err(|_| "hmac_key_empty")?;
+            feed(reader, |chunk| mac.update(chunk))?;
+            Ok(format!("{:x}", mac.finalize().into_bytes()))
+        }
+        HmacAlgo::Blake3 => {
+            let key: &[u8; blake3::KEY_LEN] = key
+                .try_into()
+                .map_err(|_| format!("hmac_blake3_key_length:{}", key.len()))?;
+            let mut hasher = Blake3::new_keyed(key);
+            feed(reader, |chunk| {
+                hasher.update(chunk);
+            })?;
+            Ok(hasher.finalize().to_hex().to_string())
+        }
+    }
 }
 
 fn hash_stream<R: Read>(reader: R, algo: HashAlgo) -> Result<String, String> {
@@ -183,6 +364,45 @@ fn hash_stream<R: Read>(reader: R, algo: HashAlgo) -> Result<String, String> {
             }
             Ok(format!("{:x}", hasher.finalize()))
         }
+        HashAlgo::Sha512 => {
+            let mut hasher = Sha512::new();
+            loop {
+                let read = reader
+                    .read(&mut buffer)
+                    .map_err(|e| format!("read_failed:{e}"))?;
+                if read == 0 {
+                    break;
+                }
+                hasher.update(&buffer[..read]);
+            }
+            Ok(format!("{:x}", hasher.finalize()))
+        }
+        HashAlgo::Sha3_256 => {
+            let mut hasher = Sha3_256::new();
+            loop {
+                let read = reader
+                    .read(&mut buffer)
+                    .map_err(|e| format!("read_failed:{e}"))?;
+                if read == 0 {
+                    break;
+                }
+                hasher.update(&buffer[..read]);
+            }
+            Ok(format!("{:x}", hasher.finalize()))
+        }
+        HashAlgo::Sha3_512 => {
+            let mut hasher = Sha3_512::new();
+            loop {
+                let read = reader
+                    .read(&mut buffer)
+                    .map_err(|e| format!("read_failed:{e}"))?;
+                if read == 0 {
+                    break;
+                }
+                hasher.update(&buffer[..read]);
+            }
+            Ok(format!("{:x}", hasher.finalize()))
+        }
         HashAlgo::Sha1 => {
             let mut hasher = Sha1::new();
             loop {
@@ -248,6 +468,32 @@ fn hash_stream<R: Read>(reader: R, algo: HashAlgo) -> Result<String, String> {
             }
             Ok(hasher.finalize().to_hex().to_string())
         }
+        HashAlgo::Xxh64 => {
+            let mut hasher = Xxh64::new(0);
+            loop {
+                let read = reader
+                    .read(&mut buffer)
+                    .map_err(|e| format!("read_failed:{e}"))?;
+                if read == 0 {
+                    break;
+                }
+                hasher.update(&buffer[..read]);
+            }
+            Ok(format!("{:016x}", hasher.digest()))
+        }
+        HashAlgo::Xxh3 => {
+            let mut hasher = Xxh3::new();
+            loop {
+                let read = reader
+                    .read(&mut buffer)
+                    .map_err(|e| format!("read_failed:{e}"))?;
+                if read == 0 {
+                    break;
+                }
+                hasher.update(&buffer[..read]);
+            }
+            Ok(format!("{:016x}", hasher.digest()))
+        }
     }
 }
 
@@ -255,17 +501,28 @@ pub fn compute_all_hashes(
     source: HashSource<'_>,
     file_path_for_display: String,
 ) -> Result<MultiHashResults, String> {
-    let file = match source {
-        HashSource::RawFd(fd) => unsafe { File::from_raw_fd(fd) },
-        HashSource::Path(path) => File::open(path).map_err(|e| format!("open_failed:{e}"))?,
-    };
-    let mut reader = BufReader::new(file);
+    compute_all_hashes_with_progress(source, file_path_for_display, |_| {})
+}
+
+/// Like [`compute_all_hashes`], calling `on_progress` with the fraction of the file read so far.
+pub fn compute_all_hashes_with_progress(
+    source: HashSource<'_>,
+    file_path_for_display: String,
+    on_progress: impl FnMut(f32),
+) -> Result<MultiHashResults, String> {
+    let file = open_source(source)?;
+    let mut reader = BufReader::new(ProgressReader::new(file, on_progress));
     let mut buffer = [0u8; 8192];
 
     let mut sha256_hasher = Sha256::new();
+    let mut sha512_hasher = Sha512::new();
+    let mut sha3_256_hasher = Sha3_256::new();
+    let mut sha3_512_hasher = Sha3_512::new();
     let mut sha1_hasher = Sha1::new();
     let mut md5_hasher = Md5::new();
     let mut blake3_hasher = Blake3::new();
+    let mut xxh64_hasher = Xxh64::new(0);
+    let mut xxh3_hasher = Xxh3::new();
 
     loop {
         let read = reader
@@ -275,20 +532,74 @@ pub fn compute_all_hashes(
             break;
         }
         sha256_hasher.update(&buffer[..read]);
+        sha512_hasher.update(&buffer[..read]);
+        sha3_256_hasher.update(&buffer[..read]);
+        sha3_512_hasher.update(&buffer[..read]);
         sha1_hasher.update(&buffer[..read]);
         md5_hasher.update(&buffer[..read]);
         blake3_hasher.update(&buffer[..read]);
+        xxh64_hasher.update(&buffer[..read]);
+        xxh3_hasher.update(&buffer[..read]);
     }
 
     Ok(MultiHashResults {
         md5: format!("{:x}", md5_hasher.finalize()),
         sha1: format!("{:x}", sha1_hasher.finalize()),
         sha256: format!("{:x}", sha256_hasher.finalize()),
+        sha512: format!("{:x}", sha512_hasher.finalize()),
+        sha3_256: format!("{:x}", sha3_256_hasher.finalize()),
+        sha3_512: format!("{:x}", sha3_512_hasher.finalize()),
         blake3: blake3_hasher.finalize().to_hex().to_string(),
+        xxh64: format!("{:016x}", xxh64_hasher.digest()),
+        xxh3: format!("{:016x}", xxh3_hasher.digest()),
         file_path: file_path_for_display,
     })
 }
 
+fn hmac_controls(state: &AppState) -> Vec<Value> {
+    let algo_buttons = HmacAlgo::ALL
+        .iter()
+        .map(|algo| {
+            let label = if *algo == state.hmac.algo {
+                format!("✓ {}", algo.label())
+            } else {
+                algo.label().to_string()
+            };
+            serde_json::to_value(
+                UiButton::new(&label, "hash_hmac_config")
+                    .payload(json!({ "hmac_algo": algo.id() })),
+            )
+            .unwrap()
+        })
+        .collect();
+    let mut controls = vec![
+        serde_json::to_value(UiRow::new(algo_buttons)).unwrap(),
+        serde_json::to_value(
+            UiTextInput::new("hmac_key")
+                .hint(&t!("hash_hmac_key_hint"))
+                .text(&state.hmac.key)
+                .single_line(true)
+                .password_mask(true)
+                .debounce_ms(300)
+                .action_on_submit("hash_hmac_config")
+                .content_description("hmac_key"),
+        )
+        .unwrap(),
+        serde_json::to_value(
+            UiCheckbox::new(&t!("hash_hmac_key_hex"), "hmac_key_hex")
+                .checked(state.hmac.key_hex)
+                .action("hash_hmac_config"),
+        )
+        .unwrap(),
+    ];
+    if state.hmac.algo == HmacAlgo::Blake3 {
+        controls.push(
+            serde_json::to_value(UiText::new(&t!("hash_hmac_blake3_note")).size(12.0)).unwrap(),
+        );
+    }
+    controls
+}
+
 pub fn render_hash_verify_screen(state: &AppState) -> Value {
     let mut children = vec![
         serde_json::to_value(UiText::new(&t!("hash_verify_title")).size(20.0)).unwrap(),
@@ -313,12 +624,28 @@ pub fn render_hash_verify_screen(state: &AppState) -> Value {
         )
         .unwrap(),
         serde_json::to_value(
-            UiButton::new(&t!("button_pick_file_and_verify"), "hash_verify")
+            UiCheckbox::new(&t!("hash_hmac_mode"), "hmac_enabled")
+                .checked(state.hmac.enabled)
+                .action("hash_hmac_config"),
+        )
+        .unwrap(),
+    ];
+    if state.hmac.enabled {
+        children.extend(hmac_controls(state));
+    }
+    let verify_label = if state.hmac.enabled {
+        t!("button_pick_file_and_hmac")
+    } else {
+        t!("button_pick_file_and_verify")
+    };
+    children.push(
+        serde_json::to_value(
+            UiButton::new(&verify_label, "hash_verify")
                 .requires_file_picker(true)
                 .id("hash_verify_btn"),
         )
         .unwrap(),
-    ];
+    );
 
     if let Some(matches) = state.hash_match {
         let status = if matches { t!("hash_verify_match") } else { t!("hash_verify_mismatch") };
@@ -354,9 +681,7 @@ pub fn render_hash_verify_screen(state: &AppState) -> Value {
         );
     }
     if let Some(err) = &state.last_error {
-        children.push(
-            serde_json::to_value(UiText::new(&format!("{}{}", t!("multi_hash_error_prefix"), err)).size(12.0)).unwrap(),
-        );
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
     }
 
     maybe_push_back(&mut children, state);
@@ -367,3 +692,134 @@ pub fn render_hash_verify_screen(state: &AppState) -> Value {
         "children": children
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn progress_is_reported_per_step_and_ends_complete() {
+        let mut file = NamedTempFile::new().unwrap();
+        let data = vec![7u8; (PROGRESS_STEP_BYTES * 3 + 10) as usize];
+        file.write_all(&data).unwrap();
+        file.flush().unwrap();
+
+        let mut reports = Vec::new();
+        let path = file.path().to_str().unwrap();
+        let with_progress =
+            compute_hash_with_progress(HashSource::Path(path), HashAlgo::Crc32, |f| {
+                reports.push(f)
+            })
+            .unwrap();
+
+        assert_eq!(
+            with_progress,
+            compute_hash(HashSource::Path(path), HashAlgo::Crc32).unwrap()
+        );
+        assert_eq!(reports.len(), 3);
+        assert!(reports.windows(2).all(|w| w[0] < w[1]));
+        assert!(reports[2] > 0.99 && reports[2] < 1.0);
+    }
+
+    #[test]
+    fn sha512_and_sha3_match_published_vectors() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"abc").unwrap();
+        file.flush().unwrap();
+        let path = file.path().to_str().unwrap();
+        let hash = |algo| compute_hash(HashSource::Path(path), algo).unwrap();
+
+        let sha512 = "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+                      2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f";
+        let sha3_256 = "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532";
+        let sha3_512 = "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e\
+                        10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0";
+        assert_eq!(hash(HashAlgo::Sha512), sha512);
+        assert_eq!(hash(HashAlgo::Sha3_256), sha3_256);
+        assert_eq!(hash(HashAlgo::Sha3_512), sha3_512);
+
+        let all = compute_all_hashes(HashSource::Path(path), "abc.txt".into()).unwrap();
+        assert_eq!(all.sha512, sha512);
+        assert_eq!(all.sha3_256, sha3_256);
+        assert_eq!(all.sha3_512, sha3_512);
+        assert_eq!(all.sha256, hash(HashAlgo::Sha256));
+    }
+
+    #[test]
+    fn xxhash_streams_match_one_shot_digests() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"abc").unwrap();
+        file.flush().unwrap();
+        let path = file.path().to_str().unwrap();
+        assert_eq!(
+            compute_hash(HashSource::Path(path), HashAlgo::Xxh64).unwrap(),
+            "44bc2cf5ad770999"
+        );
+        assert_eq!(
+            compute_hash(HashSource::Path(path), HashAlgo::Xxh3).unwrap(),
+            "78af5f94892f3950"
+        );
+
+        // Long enough to cross both the read buffer and XXH3's internal stripes.
+        let data: Vec<u8> = (0..100_003u32).map(|i| (i * 31 % 251) as u8).collect();
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&data).unwrap();
+        file.flush().unwrap();
+        let path = file.path().to_str().unwrap();
+        let all = compute_all_hashes(HashSource::Path(path), "data.bin".into()).unwrap();
+        let xxh64 = format!("{:016x}", xxhash_rust::xxh64::xxh64(&data, 0));
+        let xxh3 = format!("{:016x}", xxhash_rust::xxh3::xxh3_64(&data));
+        let hash = |algo| compute_hash(HashSource::Path(path), algo).unwrap();
+        assert_eq!(hash(HashAlgo::Xxh64), xxh64);
+        assert_eq!(hash(HashAlgo::Xxh3), xxh3);
+        assert_eq!(all.xxh64, xxh64);
+        assert_eq!(all.xxh3, xxh3);
+    }
+
+    #[test]
+    fn hmac_matches_published_vectors() {
+        const DATA: &[u8] = b"what do ya want for nothing?";
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(DATA).unwrap();
+        file.flush().unwrap();
+        let path = file.path().to_str().unwrap();
+        let keyed = |algo, key: &[u8]| {
+            compute_hmac_with_progress(HashSource::Path(path), algo, key, |_| {}).unwrap()
+        };
+
+        // RFC 4231 and RFC 2202, test case 2.
+        assert_eq!(
+            keyed(HmacAlgo::Sha256, b"Jefe"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            keyed(HmacAlgo::Sha1, b"Jefe"),
+            "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"
+        );
+        let key = [7u8; blake3::KEY_LEN];
+        assert_eq!(
+            keyed(HmacAlgo::Blake3, &key),
+            blake3::keyed_hash(&key, DATA).to_hex().to_string()
+        );
+    }
+
+    #[test]
+    fn hmac_keys_are_checked_before_hashing() {
+        let mut config = HmacConfig {
+            enabled: true,
+            algo: HmacAlgo::Blake3,
+            key: "4a 65 66 65".into(),
+            key_hex: true,
+        };
+        assert_eq!(config.key_bytes().unwrap_err(), "hmac_blake3_key_length:4");
+        config.algo = HmacAlgo::Sha256;
+        assert_eq!(config.key_bytes().unwrap(), b"Jefe");
+        config.key = "zz".into();
+        assert_eq!(config.key_bytes().unwrap_err(), "invalid_hex_digit");
+        config.key_hex = false;
+        config.key.clear();
+        assert_eq!(config.key_bytes().unwrap_err(), "hmac_key_empty");
+    }
+}
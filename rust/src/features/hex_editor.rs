@@ -1,3 +1,4 @@
+use crate::error::error_text;
 use crate::features::storage::preferred_temp_dir;
 use crate::state::AppState;
 use crate::ui::{
@@ -280,14 +281,14 @@ fn format_hex_dump(bytes: &[u8], start_offset: u64, dirty: &BTreeMap<u64, u8>) -
 pub fn render_hex_editor_screen(state: &AppState) -> Value {
     let editor = &state.hex_editor;
     let mut children = vec![
-        serde_json::to_value(UiText::new("Hex / Binary Editor").size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("hex_editor_title")).size(20.0)).unwrap(),
         serde_json::to_value(
             UiText::new(&format!(
                 "Path: {}",
                 editor
                     .display_path
-                    .as_deref()
-                    .unwrap_or("No file selected. Pick a file to begin.")
+                    .clone()
+                    .unwrap_or_else(|| t!("hex_editor_no_file").into_owned())
             ))
             .size(12.0),
         )
@@ -298,22 +299,28 @@ pub fn render_hex_editor_screen(state: &AppState) -> Value {
         children.push(serde_json::to_value(UiText::new(status).size(12.0)).unwrap());
     }
     if let Some(err) = &editor.error {
-        children.push(
-            serde_json::to_value(UiText::new(&format!("Error: {err}")).size(12.0)).unwrap(),
-        );
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
     }
     if !editor.dirty_bytes.is_empty() {
         children.push(
-            serde_json::to_value(UiText::new("Patched bytes are marked with * in the hex view.").size(12.0))
-                .unwrap(),
+            serde_json::to_value(UiText::new(&t!("hex_editor_patched_hint")).size(12.0)).unwrap(),
         );
     }
 
     // File controls
     let file_controls = UiGrid::new(vec![
-        json!(UiButton::new("Pick file", "hex_editor_open").requires_file_picker(true)),
-        json!(UiButton::new("Save", "hex_editor_save")),
-        json!(UiButton::new("Save As…", "hex_editor_save_as_picker")),
+        json!(
+            UiButton::new(&t!("hex_editor_pick_file_button"), "hex_editor_open")
+                .requires_file_picker(true)
+        ),
+        json!(UiButton::new(
+            &t!("hex_editor_save_button"),
+            "hex_editor_save"
+        )),
+        json!(UiButton::new(
+            &t!("hex_editor_save_as_button"),
+            "hex_editor_save_as_picker"
+        )),
     ])
     .columns(3)
     .padding(8);
@@ -321,10 +328,16 @@ pub fn render_hex_editor_screen(state: &AppState) -> Value {
 
     // Navigation
     let nav_inputs = UiGrid::new(vec![
-        json!(UiButton::new("Prev chunk", "hex_editor_prev")),
-        json!(UiButton::new("Next chunk", "hex_editor_next")),
+        json!(UiButton::new(
+            &t!("hex_editor_prev_button"),
+            "hex_editor_prev"
+        )),
+        json!(UiButton::new(
+            &t!("hex_editor_next_button"),
+            "hex_editor_next"
+        )),
         json!(UiTextInput::new("hex_jump_offset")
-            .hint("Jump offset (dec or 0x)")
+            .hint(&t!("hex_editor_jump_hint"))
             .single_line(true)
             .debounce_ms(200)
             .action_on_submit("hex_editor_jump")),
@@ -334,18 +347,22 @@ pub fn render_hex_editor_screen(state: &AppState) -> Value {
     children.push(serde_json::to_value(nav_inputs).unwrap());
 
     // Patch controls
+    let patch_title = t!("hex_editor_edit_section");
     let patch_section = UiSection::new(vec![
         json!(UiTextInput::new("hex_patch_offset")
-            .hint("Offset (dec or 0x)")
+            .hint(&t!("hex_editor_offset_hint"))
             .single_line(true)
             .debounce_ms(200)),
         json!(UiTextInput::new("hex_patch_value")
-            .hint("Byte value (00-ff)")
+            .hint(&t!("hex_editor_value_hint"))
             .single_line(true)
             .debounce_ms(200)),
-        json!(UiButton::new("Patch byte", "hex_editor_patch")),
+        json!(UiButton::new(
+            &t!("hex_editor_patch_button"),
+            "hex_editor_patch"
+        )),
     ])
-    .title("Edit")
+    .title(&patch_title)
     .padding(12);
     children.push(serde_json::to_value(patch_section).unwrap());
 
@@ -361,9 +378,8 @@ pub fn render_hex_editor_screen(state: &AppState) -> Value {
             .unwrap(),
         );
     } else {
-        children.push(
-            serde_json::to_value(UiText::new("No data loaded yet.").size(12.0)).unwrap(),
-        );
+        children
+            .push(serde_json::to_value(UiText::new(&t!("hex_editor_no_data")).size(12.0)).unwrap());
     }
 
     maybe_push_back(&mut children, state);
@@ -0,0 +1,375 @@
+use crate::error::error_text;
+use crate::features::image_edit::{encode_png_base64, write_result_image, PREVIEW_MAX_EDGE};
+use crate::features::image_resize::open_oriented;
+use crate::state::AppState;
+use crate::ui::{
+    maybe_push_back, Button as UiButton, Checkbox as UiCheckbox, Column as UiColumn,
+    ImageBase64 as UiImageBase64, Slider as UiSlider, Text as UiText,
+};
+use image::DynamicImage;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Blur at 100 uses a sigma of 2% of the shorter edge, so the preview matches the result.
+const MAX_BLUR_EDGE_FRACTION: f32 = 0.02;
+
+/// Slider values; brightness and contrast span -100..=100, blur and sharpen 0..=100.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Adjustments {
+    pub brightness: i32,
+    pub contrast: i32,
+    pub blur: i32,
+    pub sharpen: i32,
+    pub grayscale: bool,
+}
+
+impl Adjustments {
+    pub const fn new() -> Self {
+        Self {
+            brightness: 0,
+            contrast: 0,
+            blur: 0,
+            sharpen: 0,
+            grayscale: false,
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::new()
+    }
+
+    /// Reads the slider bindings; missing or unparsable values keep their current setting.
+    pub fn sync_bindings(&mut self, bindings: &HashMap<String, String>) {
+        let read = |key: &str, current: i32, min: i32, max: i32| {
+            bindings
+                .get(key)
+                .and_then(|v| v.trim().parse::<i32>().ok())
+                .map_or(current, |v| v.clamp(min, max))
+        };
+        self.brightness = read("image_adjust_brightness", self.brightness, -100, 100);
+        self.contrast = read("image_adjust_contrast", self.contrast, -100, 100);
+        self.blur = read("image_adjust_blur", self.blur, 0, 100);
+        self.sharpen = read("image_adjust_sharpen", self.sharpen, 0, 100);
+        if let Some(v) = bindings.get("image_adjust_grayscale") {
+            self.grayscale = v == "true";
+        }
+    }
+
+    /// Grayscale, brightness, contrast, blur, then sharpen.
+    pub fn apply(&self, image: DynamicImage) -> DynamicImage {
+        let mut out = if self.grayscale {
+            image.grayscale()
+        } else {
+            image
+        };
+        if self.brightness != 0 {
+            out = out.brighten(self.brightness * 255 / 100);
+        }
+        if self.contrast != 0 {
+            out = out.adjust_contrast(self.contrast as f32);
+        }
+        if self.blur > 0 {
+            let edge = out.width().min(out.height()) as f32;
+            let sigma = self.blur as f32 / 100.0 * MAX_BLUR_EDGE_FRACTION * edge;
+            if sigma >= 0.1 {
+                out = out.blur(sigma);
+            }
+        }
+        if self.sharpen > 0 {
+            let a = self.sharpen as f32 / 100.0;
+            out = out.filter3x3(&[0.0, -a, 0.0, -a, 1.0 + 4.0 * a, -a, 0.0, -a, 0.0]);
+        }
+        out
+    }
+}
+
+impl Default for Adjustments {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageAdjustState {
+    pub source_path: Option<String>,
+    pub output_dir: Option<String>,
+    pub source_width: u32,
+    pub source_height: u32,
+    pub adjustments: Adjustments,
+    /// Downscaled upright source the worker renders previews from; kept out of snapshots.
+    #[serde(skip)]
+    pub preview_base: Option<DynamicImage>,
+    #[serde(skip)]
+    pub preview_png_base64: Option<String>,
+    pub result_path: Option<String>,
+    pub error: Option<String>,
+}
+
+impl ImageAdjustState {
+    pub const fn new() -> Self {
+        Self {
+            source_path: None,
+            output_dir: None,
+            source_width: 0,
+            source_height: 0,
+            adjustments: Adjustments::new(),
+            preview_base: None,
+            preview_png_base64: None,
+            result_path: None,
+            error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn load_source(&mut self, path: String) -> Result<(), String> {
+        let image = open_oriented(&path)?;
+        self.source_width = image.width();
+        self.source_height = image.height();
+        let base = image.thumbnail(PREVIEW_MAX_EDGE, PREVIEW_MAX_EDGE);
+        self.preview_png_base64 = encode_png_base64(&base).ok();
+        self.preview_base = Some(base);
+        self.source_path = Some(path);
+        self.adjustments = Adjustments::new();
+        self.result_path = None;
+        self.error = None;
+        Ok(())
+    }
+}
+
+pub fn render_adjusted_preview(base: &DynamicImage, adj: &Adjustments) -> Result<String, String> {
+    encode_png_base64(&adj.apply(base.clone()))
+}
+
+/// Applies `adj` to the full-resolution source and writes it into `output_dir`.
+pub fn process_image_adjust(
+    source: &str,
+    adj: &Adjustments,
+    output_dir: &str,
+) -> Result<String, String> {
+    let adjusted = adj.apply(open_oriented(source)?);
+    write_result_image(&adjusted, source, "adjusted_", output_dir)
+}
+
+fn text(value: &str, size: f64) -> Value {
+    serde_json::to_value(UiText::new(value).size(size)).unwrap()
+}
+
+fn signed(v: i32) -> String {
+    if v > 0 {
+        format!("+{v}")
+    } else {
+        v.to_string()
+    }
+}
+
+pub fn render_image_adjust_screen(state: &AppState) -> Value {
+    let adjust = &state.image_adjust;
+    let mut children = vec![
+        text(&t!("image_adjust_title"), 20.0),
+        text(&t!("image_adjust_description"), 14.0),
+        serde_json::to_value(
+            UiButton::new(&t!("image_adjust_pick_button"), "image_adjust_pick")
+                .requires_file_picker(true)
+                .id("image_adjust_pick"),
+        )
+        .unwrap(),
+    ];
+
+    if let Some(err) = &adjust.error {
+        children.push(text(&error_text(err), 12.0));
+    }
+
+    if let Some(path) = &adjust.source_path {
+        children.push(text(
+            &format!(
+                "{}{} ({}×{})",
+                t!("image_edit_source_prefix"),
+                path,
+                adjust.source_width,
+                adjust.source_height
+            ),
+            12.0,
+        ));
+        if let Some(preview) = &adjust.preview_png_base64 {
+            children.push(
+                serde_json::to_value(
+                    UiImageBase64::new(preview).content_description("image_adjust_preview"),
+                )
+                .unwrap(),
+            );
+        }
+
+        let adj = adjust.adjustments;
+        let sliders = [
+            (
+                "image_adjust_brightness",
+                t!("image_adjust_brightness_label"),
+                signed(adj.brightness),
+                adj.brightness,
+                -100,
+            ),
+            (
+                "image_adjust_contrast",
+                t!("image_adjust_contrast_label"),
+                signed(adj.contrast),
+                adj.contrast,
+                -100,
+            ),
+            (
+                "image_adjust_blur",
+                t!("image_adjust_blur_label"),
+                adj.blur.to_string(),
+                adj.blur,
+                0,
+            ),
+            (
+                "image_adjust_sharpen",
+                t!("image_adjust_sharpen_label"),
+                adj.sharpen.to_string(),
+                adj.sharpen,
+                0,
+            ),
+        ];
+        for (key, label, shown, value, min) in &sliders {
+            children.push(text(&format!("{label}{shown}"), 14.0));
+            children.push(
+                serde_json::to_value(
+                    UiSlider::new(key, *min, 100)
+                        .value(*value)
+                        .action("image_adjust_update")
+                        .content_description(key),
+                )
+                .unwrap(),
+            );
+        }
+        children.push(
+            serde_json::to_value(
+                UiCheckbox::new(
+                    &t!("image_adjust_grayscale_label"),
+                    "image_adjust_grayscale",
+                )
+                .checked(adj.grayscale)
+                .action("image_adjust_update"),
+            )
+            .unwrap(),
+        );
+
+        if !adj.is_identity() {
+            children.push(
+                serde_json::to_value(UiButton::new(
+                    &t!("image_adjust_reset_button"),
+                    "image_adjust_reset",
+                ))
+                .unwrap(),
+            );
+        }
+        children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("image_adjust_apply_button"), "image_adjust_apply")
+                    .id("image_adjust_apply"),
+            )
+            .unwrap(),
+        );
+    }
+
+    if let Some(out) = &adjust.result_path {
+        children.push(
+            serde_json::to_value(
+                UiText::new(&format!("{}{}", t!("image_edit_result_prefix"), out))
+                    .size(12.0)
+                    .content_description("image_adjust_result"),
+            )
+            .unwrap(),
+        );
+        children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("dithering_copy_result_path_button"), "copy_clipboard")
+                    .copy_text(out),
+            )
+            .unwrap(),
+        );
+    }
+
+    maybe_push_back(&mut children, state);
+    serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    #[test]
+    fn bindings_are_clamped_and_missing_keys_kept() {
+        let mut adj = Adjustments::new();
+        adj.sharpen = 30;
+        adj.sync_bindings(&HashMap::from([
+            ("image_adjust_brightness".into(), "250".into()),
+            ("image_adjust_contrast".into(), " -40 ".into()),
+            ("image_adjust_blur".into(), "oops".into()),
+            ("image_adjust_grayscale".into(), "true".into()),
+        ]));
+        assert_eq!(adj.brightness, 100);
+        assert_eq!(adj.contrast, -40);
+        assert_eq!(adj.blur, 0);
+        assert_eq!(adj.sharpen, 30);
+        assert!(adj.grayscale);
+    }
+
+    #[test]
+    fn filters_change_pixels_as_expected() {
+        let img = DynamicImage::ImageRgb8(RgbImage::from_fn(20, 20, |x, _| {
+            if x < 10 {
+                Rgb([40, 80, 120])
+            } else {
+                Rgb([200, 160, 120])
+            }
+        }));
+
+        let identity = Adjustments::new().apply(img.clone());
+        assert_eq!(identity.to_rgb8(), img.to_rgb8());
+
+        let brighter = Adjustments {
+            brightness: 20,
+            ..Adjustments::new()
+        }
+        .apply(img.clone())
+        .to_rgb8();
+        assert_eq!(brighter.get_pixel(0, 0).0, [91, 131, 171]);
+
+        let flat = Adjustments {
+            contrast: -100,
+            ..Adjustments::new()
+        }
+        .apply(img.clone())
+        .to_rgb8();
+        assert_eq!(flat.get_pixel(0, 0), flat.get_pixel(19, 0));
+
+        let gray = Adjustments {
+            grayscale: true,
+            ..Adjustments::new()
+        }
+        .apply(img.clone())
+        .to_rgb8();
+        let [r, g, b] = gray.get_pixel(15, 5).0;
+        assert!(r == g && g == b);
+
+        // Blur softens the edge between the halves; sharpening exaggerates it.
+        let edge = |adj: Adjustments| adj.apply(img.clone()).to_rgb8().get_pixel(9, 10).0[0];
+        let blurred = edge(Adjustments {
+            blur: 100,
+            ..Adjustments::new()
+        });
+        let sharpened = edge(Adjustments {
+            sharpen: 100,
+            ..Adjustments::new()
+        });
+        assert!(blurred > 40, "blurred edge {blurred}");
+        assert!(sharpened < 40, "sharpened edge {sharpened}");
+    }
+}
@@ -0,0 +1,627 @@
+use crate::error::error_text;
+use crate::features::image_resize::{encode_jpeg, open_oriented};
+use crate::state::AppState;
+use crate::ui::{
+    maybe_push_back, Button as UiButton, Column as UiColumn, Grid as UiGrid,
+    ImageBase64 as UiImageBase64, Section as UiSection, Text as UiText, TextInput as UiTextInput,
+};
+use base64::Engine;
+use image::codecs::png::PngEncoder;
+use image::{DynamicImage, ImageEncoder};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use tempfile::Builder;
+
+pub const PREVIEW_MAX_EDGE: u32 = 320;
+const OUTPUT_JPEG_QUALITY: u8 = 92;
+const CROP_SECTION_KEY: &str = "image_edit_crop";
+const TRANSFORM_SECTION_KEY: &str = "image_edit_transform";
+
+/// Crop rectangle in percent of the upright source, like the PDF signature placement.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CropRect {
+    pub x_pct: f64,
+    pub y_pct: f64,
+    pub w_pct: f64,
+    pub h_pct: f64,
+}
+
+impl CropRect {
+    pub const FULL: CropRect = CropRect {
+        x_pct: 0.0,
+        y_pct: 0.0,
+        w_pct: 100.0,
+        h_pct: 100.0,
+    };
+
+    pub fn new(x_pct: f64, y_pct: f64, w_pct: f64, h_pct: f64) -> Result<Self, String> {
+        let valid = [x_pct, y_pct, w_pct, h_pct].iter().all(|v| v.is_finite())
+            && (0.0..100.0).contains(&x_pct)
+            && (0.0..100.0).contains(&y_pct)
+            && w_pct > 0.0
+            && h_pct > 0.0;
+        if !valid {
+            return Err("image_edit_invalid_crop".into());
+        }
+        Ok(Self {
+            x_pct,
+            y_pct,
+            w_pct: w_pct.min(100.0 - x_pct),
+            h_pct: h_pct.min(100.0 - y_pct),
+        })
+    }
+
+    pub fn is_full(&self) -> bool {
+        *self == Self::FULL
+    }
+
+    /// Pixel rectangle `(x, y, w, h)` for an image of the given size; never empty.
+    pub fn pixel_rect(&self, width: u32, height: u32) -> (u32, u32, u32, u32) {
+        let span = |pct: f64, len: u32| ((pct / 100.0) * len as f64).round() as u32;
+        let x = span(self.x_pct, width).min(width.saturating_sub(1));
+        let y = span(self.y_pct, height).min(height.saturating_sub(1));
+        let w = span(self.w_pct, width).clamp(1, width - x);
+        let h = span(self.h_pct, height).clamp(1, height - y);
+        (x, y, w, h)
+    }
+}
+
+/// Crop first (in source coordinates), then rotate clockwise, then flip.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EditOps {
+    pub crop: CropRect,
+    /// Clockwise quarter turns: 0, 90, 180 or 270.
+    pub rotation: u16,
+    pub flip_h: bool,
+    pub flip_v: bool,
+}
+
+impl EditOps {
+    pub const fn new() -> Self {
+        Self {
+            crop: CropRect::FULL,
+            rotation: 0,
+            flip_h: false,
+            flip_v: false,
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::new()
+    }
+
+    pub fn rotate_by(&mut self, degrees: i32) {
+        self.rotation = (self.rotation as i32 + degrees).rem_euclid(360) as u16;
+    }
+
+    pub fn apply(&self, image: DynamicImage) -> DynamicImage {
+        let mut out = if self.crop.is_full() {
+            image
+        } else {
+            let (x, y, w, h) = self.crop.pixel_rect(image.width(), image.height());
+            image.crop_imm(x, y, w, h)
+        };
+        out = match self.rotation {
+            90 => out.rotate90(),
+            180 => out.rotate180(),
+            270 => out.rotate270(),
+            _ => out,
+        };
+        if self.flip_h {
+            out = out.fliph();
+        }
+        if self.flip_v {
+            out = out.flipv();
+        }
+        out
+    }
+
+    pub fn output_dimensions(&self, width: u32, height: u32) -> (u32, u32) {
+        let (w, h) = if self.crop.is_full() {
+            (width, height)
+        } else {
+            let (_, _, w, h) = self.crop.pixel_rect(width, height);
+            (w, h)
+        };
+        if self.rotation % 180 == 90 {
+            (h, w)
+        } else {
+            (w, h)
+        }
+    }
+}
+
+impl Default for EditOps {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageEditState {
+    pub source_path: Option<String>,
+    /// Where the edited image goes, derived from the picked URI like dithering does.
+    pub output_dir: Option<String>,
+    pub source_width: u32,
+    pub source_height: u32,
+    pub ops: EditOps,
+    /// Downscaled upright source the preview is rendered from; kept out of snapshots.
+    #[serde(skip)]
+    pub preview_base: Option<DynamicImage>,
+    #[serde(skip)]
+    pub preview_png_base64: Option<String>,
+    pub result_path: Option<String>,
+    pub error: Option<String>,
+}
+
+impl ImageEditState {
+    pub const fn new() -> Self {
+        Self {
+            source_path: None,
+            output_dir: None,
+            source_width: 0,
+            source_height: 0,
+            ops: EditOps::new(),
+            preview_base: None,
+            preview_png_base64: None,
+            result_path: None,
+            error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Decodes the picked image once and keeps a small copy for live previews.
+    pub fn load_source(&mut self, path: String) -> Result<(), String> {
+        let image = open_oriented(&path)?;
+        self.source_width = image.width();
+        self.source_height = image.height();
+        self.preview_base = Some(image.thumbnail(PREVIEW_MAX_EDGE, PREVIEW_MAX_EDGE));
+        self.source_path = Some(path);
+        self.ops = EditOps::new();
+        self.result_path = None;
+        self.error = None;
+        self.refresh_preview();
+        Ok(())
+    }
+
+    pub fn refresh_preview(&mut self) {
+        self.preview_png_base64 = self
+            .preview_base
+            .as_ref()
+            .and_then(|base| encode_png_base64(&self.ops.apply(base.clone())).ok());
+    }
+}
+
+/// Reads the four crop fields; missing fields keep their current value.
+pub fn parse_crop_bindings(
+    current: CropRect,
+    bindings: &HashMap<String, String>,
+) -> Result<CropRect, String> {
+    let field = |key: &str, fallback: f64| -> Result<f64, String> {
+        match bindings.get(key).map(|v| v.trim()) {
+            None | Some("") => Ok(fallback),
+            Some(raw) => raw
+                .parse::<f64>()
+                .map_err(|_| "image_edit_invalid_crop".to_string()),
+        }
+    };
+    CropRect::new(
+        field("image_edit_crop_x_pct", current.x_pct)?,
+        field("image_edit_crop_y_pct", current.y_pct)?,
+        field("image_edit_crop_w_pct", current.w_pct)?,
+        field("image_edit_crop_h_pct", current.h_pct)?,
+    )
+}
+
+/// Applies `ops` to the full-resolution source and writes it into `output_dir`.
+/// JPEG sources stay JPEG; everything else is written as PNG.
+pub fn process_image_edit(source: &str, ops: &EditOps, output_dir: &str) -> Result<String, String> {
+    let edited = ops.apply(open_oriented(source)?);
+    write_result_image(&edited, source, "edited_", output_dir)
+}
+
+/// Saves a processed image next to the others in `output_dir`: JPEG (q92) when the source
+/// was a JPEG, PNG otherwise so transparency survives.
+pub fn write_result_image(
+    image: &DynamicImage,
+    source: &str,
+    prefix: &str,
+    output_dir: &str,
+) -> Result<String, String> {
+    let (bytes, suffix) = if is_jpeg_source(source) {
+        (encode_jpeg(image, OUTPUT_JPEG_QUALITY)?, ".jpg")
+    } else {
+        (encode_png(image)?, ".png")
+    };
+    fs::create_dir_all(output_dir).map_err(|e| format!("output_dir_create_failed:{e}"))?;
+    let mut tmp = Builder::new()
+        .prefix(prefix)
+        .suffix(suffix)
+        .tempfile_in(output_dir)
+        .map_err(|e| format!("tempfile_failed:{e}"))?;
+    tmp.write_all(&bytes)
+        .map_err(|e| format!("save_failed:{e}"))?;
+    let path = tmp.into_temp_path();
+    let final_path = path
+        .to_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "path_utf8".to_string())?;
+    path.keep().map_err(|e| format!("persist_failed:{e}"))?;
+    Ok(final_path)
+}
+
+/// By extension, or by the SOI marker for picked copies that lost theirs (`.bin`).
+fn is_jpeg_source(source: &str) -> bool {
+    let by_extension = Path::new(source)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("jpg") || e.eq_ignore_ascii_case("jpeg"));
+    by_extension || {
+        let mut magic = [0u8; 2];
+        fs::File::open(source)
+            .and_then(|mut f| f.read_exact(&mut magic))
+            .is_ok_and(|_| magic == [0xFF, 0xD8])
+    }
+}
+
+fn encode_png(image: &DynamicImage) -> Result<Vec<u8>, String> {
+    let rgba = image.to_rgba8();
+    let mut buf = Vec::new();
+    PngEncoder::new(&mut buf)
+        .write_image(
+            rgba.as_raw(),
+            rgba.width(),
+            rgba.height(),
+            image::ColorType::Rgba8,
+        )
+        .map_err(|e| format!("encode_failed:{e}"))?;
+    Ok(buf)
+}
+
+pub fn encode_png_base64(image: &DynamicImage) -> Result<String, String> {
+    encode_png(image).map(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+fn crop_presets(state: &ImageEditState) -> Vec<(String, CropRect)> {
+    let mut presets = vec![
+        (t!("image_edit_preset_full").to_string(), CropRect::FULL),
+        (
+            t!("image_edit_preset_center").to_string(),
+            CropRect {
+                x_pct: 10.0,
+                y_pct: 10.0,
+                w_pct: 80.0,
+                h_pct: 80.0,
+            },
+        ),
+    ];
+    let (w, h) = (state.source_width as f64, state.source_height as f64);
+    if w > 0.0 && h > 0.0 {
+        let side = w.min(h);
+        let (w_pct, h_pct) = (side / w * 100.0, side / h * 100.0);
+        presets.push((
+            t!("image_edit_preset_square").to_string(),
+            CropRect {
+                x_pct: (100.0 - w_pct) / 2.0,
+                y_pct: (100.0 - h_pct) / 2.0,
+                w_pct,
+                h_pct,
+            },
+        ));
+    }
+    let half = |x_pct, y_pct, w_pct, h_pct| CropRect {
+        x_pct,
+        y_pct,
+        w_pct,
+        h_pct,
+    };
+    presets.extend([
+        (
+            t!("image_edit_preset_left").to_string(),
+            half(0.0, 0.0, 50.0, 100.0),
+        ),
+        (
+            t!("image_edit_preset_right").to_string(),
+            half(50.0, 0.0, 50.0, 100.0),
+        ),
+        (
+            t!("image_edit_preset_top").to_string(),
+            half(0.0, 0.0, 100.0, 50.0),
+        ),
+        (
+            t!("image_edit_preset_bottom").to_string(),
+            half(0.0, 50.0, 100.0, 50.0),
+        ),
+    ]);
+    presets
+}
+
+fn pct_text(v: f64) -> String {
+    let rounded = (v * 10.0).round() / 10.0;
+    if rounded.fract() == 0.0 {
+        format!("{}", rounded as i64)
+    } else {
+        format!("{rounded:.1}")
+    }
+}
+
+pub fn render_image_edit_screen(state: &AppState) -> Value {
+    let edit = &state.image_edit;
+    let mut children = vec![
+        serde_json::to_value(UiText::new(&t!("image_edit_title")).size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("image_edit_description")).size(14.0)).unwrap(),
+        serde_json::to_value(
+            UiButton::new(&t!("image_edit_pick_button"), "image_edit_pick")
+                .requires_file_picker(true)
+                .id("image_edit_pick"),
+        )
+        .unwrap(),
+    ];
+
+    if let Some(err) = &edit.error {
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
+    }
+
+    if let Some(path) = &edit.source_path {
+        children.push(
+            serde_json::to_value(
+                UiText::new(&format!(
+                    "{}{} ({}×{})",
+                    t!("image_edit_source_prefix"),
+                    path,
+                    edit.source_width,
+                    edit.source_height
+                ))
+                .size(12.0),
+            )
+            .unwrap(),
+        );
+
+        if let Some(preview) = &edit.preview_png_base64 {
+            children.push(
+                serde_json::to_value(
+                    UiImageBase64::new(preview).content_description("image_edit_preview"),
+                )
+                .unwrap(),
+            );
+        }
+        let (out_w, out_h) = edit
+            .ops
+            .output_dimensions(edit.source_width, edit.source_height);
+        children.push(
+            serde_json::to_value(
+                UiText::new(&format!(
+                    "{}{out_w}×{out_h}",
+                    t!("image_edit_output_prefix")
+                ))
+                .size(12.0),
+            )
+            .unwrap(),
+        );
+
+        let crop = edit.ops.crop;
+        let fields = [
+            (
+                "image_edit_crop_x_pct",
+                t!("image_edit_crop_left"),
+                crop.x_pct,
+            ),
+            (
+                "image_edit_crop_y_pct",
+                t!("image_edit_crop_top"),
+                crop.y_pct,
+            ),
+            (
+                "image_edit_crop_w_pct",
+                t!("image_edit_crop_width"),
+                crop.w_pct,
+            ),
+            (
+                "image_edit_crop_h_pct",
+                t!("image_edit_crop_height"),
+                crop.h_pct,
+            ),
+        ];
+        let mut crop_children: Vec<Value> = Vec::new();
+        for (key, label, value) in &fields {
+            crop_children.push(serde_json::to_value(UiText::new(label).size(12.0)).unwrap());
+            crop_children.push(
+                serde_json::to_value(
+                    UiTextInput::new(key)
+                        .text(&pct_text(*value))
+                        .single_line(true),
+                )
+                .unwrap(),
+            );
+        }
+        crop_children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("image_edit_crop_apply_button"), "image_edit_crop")
+                    .id("image_edit_crop"),
+            )
+            .unwrap(),
+        );
+        let preset_buttons: Vec<Value> = crop_presets(edit)
+            .into_iter()
+            .map(|(label, rect)| {
+                let mut button = json!({
+                    "type": "Button",
+                    "text": label,
+                    "action": "image_edit_crop",
+                    "payload": {
+                        "image_edit_crop_x_pct": pct_text(rect.x_pct),
+                        "image_edit_crop_y_pct": pct_text(rect.y_pct),
+                        "image_edit_crop_w_pct": pct_text(rect.w_pct),
+                        "image_edit_crop_h_pct": pct_text(rect.h_pct)
+                    }
+                });
+                if (rect.x_pct - crop.x_pct).abs() < 0.05
+                    && (rect.y_pct - crop.y_pct).abs() < 0.05
+                    && (rect.w_pct - crop.w_pct).abs() < 0.05
+                    && (rect.h_pct - crop.h_pct).abs() < 0.05
+                {
+                    button["content_description"] = json!("selected");
+                }
+                button
+            })
+            .collect();
+        crop_children.push(serde_json::to_value(UiGrid::new(preset_buttons).columns(3)).unwrap());
+        children.push(
+            serde_json::to_value(
+                UiSection::new(crop_children)
+                    .title(&t!("image_edit_crop_section"))
+                    .padding(12)
+                    .collapsible(
+                        CROP_SECTION_KEY,
+                        state.is_section_expanded(CROP_SECTION_KEY, true),
+                    ),
+            )
+            .unwrap(),
+        );
+
+        let (flip_h_label, flip_v_label) = (t!("image_edit_flip_h"), t!("image_edit_flip_v"));
+        let mut flip_h = UiButton::new(&flip_h_label, "image_edit_flip_h");
+        if edit.ops.flip_h {
+            flip_h = flip_h.content_description("selected");
+        }
+        let mut flip_v = UiButton::new(&flip_v_label, "image_edit_flip_v");
+        if edit.ops.flip_v {
+            flip_v = flip_v.content_description("selected");
+        }
+        let transform_buttons = vec![
+            serde_json::to_value(UiButton::new(
+                &t!("image_edit_rotate_left"),
+                "image_edit_rotate_left",
+            ))
+            .unwrap(),
+            serde_json::to_value(UiButton::new(
+                &t!("image_edit_rotate_right"),
+                "image_edit_rotate_right",
+            ))
+            .unwrap(),
+            serde_json::to_value(flip_h).unwrap(),
+            serde_json::to_value(flip_v).unwrap(),
+        ];
+        children.push(
+            serde_json::to_value(
+                UiSection::new(vec![
+                    serde_json::to_value(
+                        UiText::new(&format!(
+                            "{}{}°",
+                            t!("image_edit_rotation_prefix"),
+                            edit.ops.rotation
+                        ))
+                        .size(12.0),
+                    )
+                    .unwrap(),
+                    serde_json::to_value(UiGrid::new(transform_buttons).columns(2)).unwrap(),
+                ])
+                .title(&t!("image_edit_transform_section"))
+                .padding(12)
+                .collapsible(
+                    TRANSFORM_SECTION_KEY,
+                    state.is_section_expanded(TRANSFORM_SECTION_KEY, true),
+                ),
+            )
+            .unwrap(),
+        );
+
+        if !edit.ops.is_identity() {
+            children.push(
+                serde_json::to_value(UiButton::new(
+                    &t!("image_edit_reset_button"),
+                    "image_edit_reset",
+                ))
+                .unwrap(),
+            );
+        }
+        children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("image_edit_apply_button"), "image_edit_apply")
+                    .id("image_edit_apply"),
+            )
+            .unwrap(),
+        );
+    }
+
+    if let Some(out) = &edit.result_path {
+        children.push(
+            serde_json::to_value(
+                UiText::new(&format!("{}{}", t!("image_edit_result_prefix"), out))
+                    .size(12.0)
+                    .content_description("image_edit_result"),
+            )
+            .unwrap(),
+        );
+        children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("dithering_copy_result_path_button"), "copy_clipboard")
+                    .copy_text(out),
+            )
+            .unwrap(),
+        );
+    }
+
+    maybe_push_back(&mut children, state);
+    serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    #[test]
+    fn crop_rect_validates_and_maps_to_pixels() {
+        assert!(CropRect::new(100.0, 0.0, 10.0, 10.0).is_err());
+        assert!(CropRect::new(0.0, 0.0, 0.0, 10.0).is_err());
+        let rect = CropRect::new(50.0, 25.0, 80.0, 50.0).unwrap();
+        assert_eq!(rect.w_pct, 50.0);
+        assert_eq!(rect.pixel_rect(200, 100), (100, 25, 100, 50));
+
+        let current = CropRect::FULL;
+        let bindings = HashMap::from([("image_edit_crop_w_pct".to_string(), "40".to_string())]);
+        let parsed = parse_crop_bindings(current, &bindings).unwrap();
+        assert_eq!(
+            (parsed.x_pct, parsed.w_pct, parsed.h_pct),
+            (0.0, 40.0, 100.0)
+        );
+        let bad = HashMap::from([("image_edit_crop_x_pct".to_string(), "abc".to_string())]);
+        assert!(parse_crop_bindings(current, &bad).is_err());
+    }
+
+    #[test]
+    fn edit_ops_crop_rotate_and_flip_in_order() {
+        // Left half red, right half blue.
+        let image = DynamicImage::ImageRgb8(RgbImage::from_fn(40, 20, |x, _| {
+            if x < 20 {
+                Rgb([255, 0, 0])
+            } else {
+                Rgb([0, 0, 255])
+            }
+        }));
+        let mut ops = EditOps::new();
+        ops.crop = CropRect::new(25.0, 0.0, 75.0, 100.0).unwrap();
+        ops.rotate_by(-90);
+        assert_eq!(ops.rotation, 270);
+        assert_eq!(ops.output_dimensions(40, 20), (20, 30));
+
+        let out = ops.apply(image.clone()).to_rgb8();
+        assert_eq!(out.dimensions(), (20, 30));
+        // Counter-clockwise turn puts the original right edge (blue) on top.
+        assert_eq!(out.get_pixel(0, 0), &Rgb([0, 0, 255]));
+        assert_eq!(out.get_pixel(0, 29), &Rgb([255, 0, 0]));
+
+        ops.flip_v = true;
+        let flipped = ops.apply(image).to_rgb8();
+        assert_eq!(flipped.get_pixel(0, 0), &Rgb([255, 0, 0]));
+    }
+}
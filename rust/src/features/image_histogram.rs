@@ -0,0 +1,309 @@
+use crate::error::error_text;
+use crate::features::image_resize::open_oriented;
+use crate::state::AppState;
+use crate::ui::{
+    maybe_push_back, Button as UiButton, Chart as UiChart, ChartSeries, Column as UiColumn,
+    Grid as UiGrid, Text as UiText,
+};
+use image::DynamicImage;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const BINS: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelStats {
+    /// Pixel count per value 0..=255.
+    pub histogram: Vec<u64>,
+    pub mean: f64,
+    pub median: u8,
+    /// Share of pixels at 0.
+    pub shadows_clipped_pct: f64,
+    /// Share of pixels at 255.
+    pub highlights_clipped_pct: f64,
+}
+
+impl ChannelStats {
+    fn from_histogram(histogram: &[u64; BINS], total: u64) -> Self {
+        let sum: u64 = histogram
+            .iter()
+            .enumerate()
+            .map(|(value, count)| value as u64 * count)
+            .sum();
+        let half = total.div_ceil(2);
+        let mut seen = 0;
+        let median = histogram
+            .iter()
+            .position(|count| {
+                seen += count;
+                seen >= half
+            })
+            .unwrap_or(0) as u8;
+        Self {
+            histogram: histogram.to_vec(),
+            mean: sum as f64 / total as f64,
+            median,
+            shadows_clipped_pct: pct(histogram[0], total),
+            highlights_clipped_pct: pct(histogram[BINS - 1], total),
+        }
+    }
+
+    /// Bin heights as a share of all pixels, for charting.
+    pub fn percentages(&self) -> Vec<f64> {
+        let total: u64 = self.histogram.iter().sum();
+        self.histogram.iter().map(|c| pct(*c, total)).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistogramStats {
+    pub width: u32,
+    pub height: u32,
+    /// Pixels that were counted; fully transparent ones are skipped.
+    pub pixel_count: u64,
+    /// Rec. 709 luminance.
+    pub luma: ChannelStats,
+    pub red: ChannelStats,
+    pub green: ChannelStats,
+    pub blue: ChannelStats,
+    /// Pixels with at least one channel at 0.
+    pub any_shadows_clipped_pct: f64,
+    /// Pixels with at least one channel at 255.
+    pub any_highlights_clipped_pct: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageHistogramState {
+    pub source_name: Option<String>,
+    pub stats: Option<HistogramStats>,
+    pub error: Option<String>,
+}
+
+impl ImageHistogramState {
+    pub const fn new() -> Self {
+        Self {
+            source_name: None,
+            stats: None,
+            error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+fn pct(count: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 * 100.0 / total as f64
+    }
+}
+
+pub fn compute_histogram(image: &DynamicImage) -> Result<HistogramStats, String> {
+    let rgba = image.to_rgba8();
+    let mut red = [0u64; BINS];
+    let mut green = [0u64; BINS];
+    let mut blue = [0u64; BINS];
+    let mut luma = [0u64; BINS];
+    let (mut any_low, mut any_high, mut total) = (0u64, 0u64, 0u64);
+    for pixel in rgba.pixels() {
+        let [r, g, b, a] = pixel.0;
+        if a == 0 {
+            continue;
+        }
+        red[r as usize] += 1;
+        green[g as usize] += 1;
+        blue[b as usize] += 1;
+        let y = (2126 * r as u32 + 7152 * g as u32 + 722 * b as u32 + 5000) / 10_000;
+        luma[y as usize] += 1;
+        if r == 0 || g == 0 || b == 0 {
+            any_low += 1;
+        }
+        if r == 255 || g == 255 || b == 255 {
+            any_high += 1;
+        }
+        total += 1;
+    }
+    if total == 0 {
+        return Err("image_histogram_empty".into());
+    }
+    Ok(HistogramStats {
+        width: rgba.width(),
+        height: rgba.height(),
+        pixel_count: total,
+        luma: ChannelStats::from_histogram(&luma, total),
+        red: ChannelStats::from_histogram(&red, total),
+        green: ChannelStats::from_histogram(&green, total),
+        blue: ChannelStats::from_histogram(&blue, total),
+        any_shadows_clipped_pct: pct(any_low, total),
+        any_highlights_clipped_pct: pct(any_high, total),
+    })
+}
+
+pub fn analyze_image_file(path: &str) -> Result<HistogramStats, String> {
+    compute_histogram(&open_oriented(path)?)
+}
+
+fn text(value: &str, size: f64) -> Value {
+    serde_json::to_value(UiText::new(value).size(size)).unwrap()
+}
+
+pub fn render_image_histogram_screen(state: &AppState) -> Value {
+    let hist = &state.image_histogram;
+    let mut children = vec![
+        text(&t!("image_histogram_title"), 20.0),
+        text(&t!("image_histogram_description"), 14.0),
+        serde_json::to_value(
+            UiButton::new(&t!("image_histogram_pick_button"), "image_histogram_pick")
+                .requires_file_picker(true)
+                .id("image_histogram_pick"),
+        )
+        .unwrap(),
+    ];
+
+    if let Some(err) = &hist.error {
+        children.push(text(&error_text(err), 12.0));
+    }
+
+    if let Some(stats) = &hist.stats {
+        let mut summary = format!(
+            "{}{}×{}",
+            t!("image_dimensions_prefix"),
+            stats.width,
+            stats.height
+        );
+        if let Some(name) = &hist.source_name {
+            summary = format!("{name} — {summary}");
+        }
+        children.push(text(&summary, 12.0));
+        children.push(text(
+            &format!(
+                "{}{:.1} / 255 ({:.0}%)",
+                t!("image_histogram_mean_brightness_prefix"),
+                stats.luma.mean,
+                stats.luma.mean * 100.0 / 255.0
+            ),
+            16.0,
+        ));
+        children.push(text(
+            &format!(
+                "{}{} · {}{:.2}% · {}{:.2}%",
+                t!("image_histogram_median_prefix"),
+                stats.luma.median,
+                t!("image_histogram_shadows_clipped_prefix"),
+                stats.any_shadows_clipped_pct,
+                t!("image_histogram_highlights_clipped_prefix"),
+                stats.any_highlights_clipped_pct
+            ),
+            12.0,
+        ));
+
+        let luma_label = t!("image_histogram_luma_label");
+        let red_label = t!("image_histogram_red_label");
+        let green_label = t!("image_histogram_green_label");
+        let blue_label = t!("image_histogram_blue_label");
+        let intensity_axis = t!("image_histogram_axis_intensity");
+        let share_axis = t!("image_histogram_axis_share");
+        let levels: Vec<String> = (0..=255).map(|v: u32| v.to_string()).collect();
+        children.push(
+            serde_json::to_value(
+                UiChart::new(vec![ChartSeries::new(
+                    &luma_label,
+                    stats.luma.percentages(),
+                )
+                .color("#616161")])
+                .bar()
+                .axes(&intensity_axis, &share_axis)
+                .categories(levels.clone())
+                .height_dp(120)
+                .content_description("image_histogram_luma"),
+            )
+            .unwrap(),
+        );
+        children.push(
+            serde_json::to_value(
+                UiChart::new(vec![
+                    ChartSeries::new(&red_label, stats.red.percentages()).color("#E53935"),
+                    ChartSeries::new(&green_label, stats.green.percentages()).color("#43A047"),
+                    ChartSeries::new(&blue_label, stats.blue.percentages()).color("#1E88E5"),
+                ])
+                .axes(&intensity_axis, &share_axis)
+                .categories(levels)
+                .height_dp(140)
+                .content_description("image_histogram_rgb"),
+            )
+            .unwrap(),
+        );
+
+        let mut cells = vec![
+            text(&t!("image_histogram_channel_header"), 12.0),
+            text(&t!("image_histogram_mean_header"), 12.0),
+            text(&t!("image_histogram_shadows_header"), 12.0),
+            text(&t!("image_histogram_highlights_header"), 12.0),
+        ];
+        for (label, channel) in [
+            (&luma_label, &stats.luma),
+            (&red_label, &stats.red),
+            (&green_label, &stats.green),
+            (&blue_label, &stats.blue),
+        ] {
+            cells.push(text(label, 14.0));
+            cells.push(text(&format!("{:.1}", channel.mean), 14.0));
+            cells.push(text(&format!("{:.2}%", channel.shadows_clipped_pct), 14.0));
+            cells.push(text(
+                &format!("{:.2}%", channel.highlights_clipped_pct),
+                14.0,
+            ));
+        }
+        children.push(serde_json::to_value(UiGrid::new(cells).columns(4)).unwrap());
+    }
+
+    maybe_push_back(&mut children, state);
+    serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn histogram_counts_channels_and_clipping() {
+        // Left half black, right half white, bottom row transparent (ignored).
+        let img = RgbaImage::from_fn(4, 3, |x, y| match (x, y) {
+            (_, 2) => Rgba([10, 20, 30, 0]),
+            (0..=1, _) => Rgba([0, 0, 0, 255]),
+            _ => Rgba([255, 255, 255, 255]),
+        });
+        let stats = compute_histogram(&DynamicImage::ImageRgba8(img)).unwrap();
+        assert_eq!(stats.pixel_count, 8);
+        assert_eq!((stats.width, stats.height), (4, 3));
+        assert_eq!(stats.luma.histogram[0], 4);
+        assert_eq!(stats.luma.histogram[255], 4);
+        assert!((stats.luma.mean - 127.5).abs() < 1e-9);
+        assert_eq!(stats.luma.median, 0);
+        assert_eq!(stats.red.shadows_clipped_pct, 50.0);
+        assert_eq!(stats.blue.highlights_clipped_pct, 50.0);
+        assert_eq!(stats.any_shadows_clipped_pct, 50.0);
+        assert_eq!(stats.luma.percentages().iter().sum::<f64>(), 100.0);
+    }
+
+    #[test]
+    fn luma_uses_rec709_weights_and_rejects_empty_images() {
+        let green = RgbaImage::from_pixel(2, 2, Rgba([0, 200, 0, 255]));
+        let stats = compute_histogram(&DynamicImage::ImageRgba8(green)).unwrap();
+        assert_eq!(stats.luma.median, 143);
+        assert_eq!(stats.green.median, 200);
+        assert_eq!(stats.any_highlights_clipped_pct, 0.0);
+        assert_eq!(stats.any_shadows_clipped_pct, 100.0);
+
+        let clear = RgbaImage::from_pixel(2, 2, Rgba([0, 0, 0, 0]));
+        assert_eq!(
+            compute_histogram(&DynamicImage::ImageRgba8(clear)).unwrap_err(),
+            "image_histogram_empty"
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use crate::features::exif_info::read_exif;
+use crate::features::thumbnail::apply_orientation;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::io::{Limits, Reader as ImageReader};
+use image::{ColorType, DynamicImage};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use tempfile::Builder;
+
+pub const MAX_DIMENSION: u32 = 16_384;
+const MAX_DECODE_ALLOC: u64 = 512 * 1024 * 1024;
+/// Lowest JPEG quality the size budget loop will go down to.
+const BUDGET_MIN_QUALITY: u8 = 40;
+const BUDGET_MAX_ATTEMPTS: usize = 5;
+/// ISO-BMFF `ftyp` brands used by HEIC/HEIF and AVIF stills and sequences.
+const HEIF_BRANDS: &[&[u8; 4]] = &[
+    b"heic", b"heix", b"hevc", b"hevx", b"heim", b"heis", b"mif1", b"msf1", b"avif", b"avis",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResizeFilter {
+    /// Sharpest result, slowest; good for photos.
+    Lanczos3,
+    /// Bilinear; fast and smooth, fine for thumbnails and batches.
+    Triangle,
+}
+
+impl ResizeFilter {
+    fn filter_type(self) -> FilterType {
+        match self {
+            ResizeFilter::Lanczos3 => FilterType::Lanczos3,
+            ResizeFilter::Triangle => FilterType::Triangle,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResizeMode {
+    Percent,
+    Dimensions,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResizeOptions {
+    pub mode: ResizeMode,
+    pub scale_pct: u32,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// With both width and height set, fit inside the box instead of stretching.
+    pub keep_aspect: bool,
+    pub filter: ResizeFilter,
+    pub quality: u8,
+    pub target_bytes: Option<u64>,
+    /// WebP output is lossless; quality and the size budget only apply to JPEG.
+    pub use_webp: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResizeOutput {
+    pub path: String,
+    pub source_width: u32,
+    pub source_height: u32,
+    pub width: u32,
+    pub height: u32,
+    pub size_bytes: u64,
+    pub format: &'static str,
+}
+
+pub fn target_dimensions(
+    src_w: u32,
+    src_h: u32,
+    opts: &ResizeOptions,
+) -> Result<(u32, u32), String> {
+    if src_w == 0 || src_h == 0 {
+        return Err("resize_empty_image".into());
+    }
+    let scale = |len: u32, num: u64, den: u64| -> u32 {
+        ((len as u64 * num + den / 2) / den).clamp(1, MAX_DIMENSION as u64) as u32
+    };
+    let dims = match opts.mode {
+        ResizeMode::Percent => {
+            let pct = opts.scale_pct.clamp(1, 100) as u64;
+            (scale(src_w, pct, 100), scale(src_h, pct, 100))
+        }
+        ResizeMode::Dimensions => match (opts.width, opts.height) {
+            (None, None) => return Err("resize_missing_dimensions".into()),
+            (Some(w), None) => (w, scale(src_h, w as u64, src_w as u64)),
+            (None, Some(h)) => (scale(src_w, h as u64, src_h as u64), h),
+            (Some(w), Some(h)) if !opts.keep_aspect => (w, h),
+            (Some(w), Some(h)) => {
+                // Fit inside the box: the tighter side decides the scale.
+                if (w as u64) * (src_h as u64) <= (h as u64) * (src_w as u64) {
+                    (w, scale(src_h, w as u64, src_w as u64))
+                } else {
+                    (scale(src_w, h as u64, src_h as u64), h)
+                }
+            }
+        },
+    };
+    let (w, h) = dims;
+    if w == 0 || h == 0 || w > MAX_DIMENSION || h > MAX_DIMENSION {
+        return Err("resize_invalid_dimensions".into());
+    }
+    Ok((w, h))
+}
+
+/// Parses a width/height field; blank means "not set".
+pub fn parse_dimension(raw: &str) -> Result<Option<u32>, String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    match trimmed.parse::<u32>() {
+        Ok(v) if (1..=MAX_DIMENSION).contains(&v) => Ok(Some(v)),
+        _ => Err("resize_invalid_dimensions".into()),
+    }
+}
+
+/// True for HEIC/HEIF and AVIF files, which need the platform decoder.
+pub fn is_heif_container(header: &[u8]) -> bool {
+    header.len() >= 12
+        && &header[4..8] == b"ftyp"
+        && HEIF_BRANDS.iter().any(|brand| &header[8..12] == *brand)
+}
+
+/// Decodes an image file with an allocation cap and turns it upright per its EXIF orientation.
+///
+/// The format comes from the file contents, not the extension. HEIC/AVIF picks are
+/// transcoded to PNG on the Android side; one that still reaches here is reported as
+/// `heif_decode_unsupported` (the device decoder could not read it).
+pub fn open_oriented(source: &str) -> Result<DynamicImage, String> {
+    let file = File::open(source).map_err(|e| format!("open_failed:{e}"))?;
+    let mut reader = BufReader::new(file);
+    let mut header = Vec::with_capacity(12);
+    reader
+        .by_ref()
+        .take(12)
+        .read_to_end(&mut header)
+        .map_err(|e| format!("open_failed:{e}"))?;
+    if is_heif_container(&header) {
+        return Err("heif_decode_unsupported".into());
+    }
+    reader
+        .seek(SeekFrom::Start(0))
+        .map_err(|e| format!("open_failed:{e}"))?;
+    let orientation = read_exif(&mut reader).and_then(|e| e.orientation);
+    reader
+        .seek(SeekFrom::Start(0))
+        .map_err(|e| format!("open_failed:{e}"))?;
+    let mut decoder = ImageReader::new(reader)
+        .with_guessed_format()
+        .map_err(|e| format!("open_failed:{e}"))?;
+    let mut limits = Limits::default();
+    limits.max_alloc = Some(MAX_DECODE_ALLOC);
+    decoder.limits(limits);
+    let decoded = decoder.decode().map_err(|e| format!("decode_failed:{e}"))?;
+    Ok(apply_orientation(decoded, orientation))
+}
+
+/// Decodes `source`, resizes it and writes a JPEG (or lossless WebP) into `output_dir`.
+pub fn resize_image_file(
+    source: &str,
+    output_dir: &Path,
+    opts: &ResizeOptions,
+) -> Result<ResizeOutput, String> {
+    let image = open_oriented(source)?;
+    let (source_width, source_height) = (image.width(), image.height());
+    let (width, height) = target_dimensions(source_width, source_height, opts)?;
+    let resized = if (width, height) == (source_width, source_height) {
+        image
+    } else {
+        image.resize_exact(width, height, opts.filter.filter_type())
+    };
+
+    let (bytes, format, suffix) = if opts.use_webp {
+        (encode_webp(&resized)?, "WEBP", ".webp")
+    } else {
+        let bytes = encode_jpeg_with_budget(&resized, opts.quality, opts.target_bytes)?;
+        (bytes, "JPEG", ".jpg")
+    };
+
+    fs::create_dir_all(output_dir).map_err(|e| format!("output_dir_create_failed:{e}"))?;
+    let mut tmp = Builder::new()
+        .prefix(&format!("resized_{width}x{height}_"))
+        .suffix(suffix)
+        .tempfile_in(output_dir)
+        .map_err(|e| format!("tempfile_failed:{e}"))?;
+    tmp.write_all(&bytes)
+        .map_err(|e| format!("save_failed:{e}"))?;
+    let path = tmp.into_temp_path();
+    let final_path = path
+        .to_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "path_utf8".to_string())?;
+    path.keep().map_err(|e| format!("persist_failed:{e}"))?;
+
+    Ok(ResizeOutput {
+        path: final_path,
+        source_width,
+        source_height,
+        width,
+        height,
+        size_bytes: bytes.len() as u64,
+        format,
+    })
+}
+
+pub fn encode_jpeg(image: &DynamicImage, quality: u8) -> Result<Vec<u8>, String> {
+    let rgb = image.to_rgb8();
+    let mut buf = Vec::new();
+    JpegEncoder::new_with_quality(&mut buf, quality)
+        .encode(rgb.as_raw(), rgb.width(), rgb.height(), ColorType::Rgb8)
+        .map_err(|e| format!("encode_failed:{e}"))?;
+    Ok(buf)
+}
+
+/// Steps quality down by 10 (never below 40) until the output fits `target_bytes`.
+fn encode_jpeg_with_budget(
+    image: &DynamicImage,
+    quality: u8,
+    target_bytes: Option<u64>,
+) -> Result<Vec<u8>, String> {
+    let mut q = quality.clamp(1, 100);
+    let mut bytes = encode_jpeg(image, q)?;
+    if let Some(target) = target_bytes {
+        let mut attempts = 0;
+        while bytes.len() as u64 > target
+            && attempts < BUDGET_MAX_ATTEMPTS
+            && q > BUDGET_MIN_QUALITY
+        {
+            q = q.saturating_sub(10).max(BUDGET_MIN_QUALITY);
+            bytes = encode_jpeg(image, q)?;
+            attempts += 1;
+        }
+    }
+    Ok(bytes)
+}
+
+fn encode_webp(image: &DynamicImage) -> Result<Vec<u8>, String> {
+    let rgba = image.to_rgba8();
+    let mut buf = Vec::new();
+    WebPEncoder::new_lossless(&mut buf)
+        .encode(rgba.as_raw(), rgba.width(), rgba.height(), ColorType::Rgba8)
+        .map_err(|e| format!("encode_failed:{e}"))?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, Rgb, RgbImage};
+
+    fn opts(mode: ResizeMode) -> ResizeOptions {
+        ResizeOptions {
+            mode,
+            scale_pct: 50,
+            width: None,
+            height: None,
+            keep_aspect: true,
+            filter: ResizeFilter::Lanczos3,
+            quality: 85,
+            target_bytes: None,
+            use_webp: false,
+        }
+    }
+
+    #[test]
+    fn target_dimensions_cover_percent_and_box_modes() {
+        assert_eq!(
+            target_dimensions(4000, 3000, &opts(ResizeMode::Percent)),
+            Ok((2000, 1500))
+        );
+
+        let mut o = opts(ResizeMode::Dimensions);
+        assert!(target_dimensions(4000, 3000, &o).is_err());
+        o.width = Some(800);
+        assert_eq!(target_dimensions(4000, 3000, &o), Ok((800, 600)));
+        o.height = Some(300);
+        assert_eq!(target_dimensions(4000, 3000, &o), Ok((400, 300)));
+        o.keep_aspect = false;
+        assert_eq!(target_dimensions(4000, 3000, &o), Ok((800, 300)));
+
+        assert_eq!(parse_dimension(" "), Ok(None));
+        assert_eq!(parse_dimension("640"), Ok(Some(640)));
+        assert!(parse_dimension("0").is_err());
+        assert!(parse_dimension("abc").is_err());
+    }
+
+    #[test]
+    fn resize_image_file_writes_jpeg_and_webp() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.png");
+        RgbImage::from_fn(120, 80, |x, y| Rgb([(x * 2) as u8, (y * 3) as u8, 90]))
+            .save_with_format(&src, ImageFormat::Png)
+            .unwrap();
+        let src = src.to_str().unwrap();
+
+        let out = resize_image_file(src, dir.path(), &opts(ResizeMode::Percent)).unwrap();
+        assert_eq!((out.width, out.height), (60, 40));
+        assert_eq!((out.source_width, out.source_height), (120, 80));
+        assert_eq!(out.format, "JPEG");
+        let written = image::open(&out.path).unwrap();
+        assert_eq!((written.width(), written.height()), (60, 40));
+
+        let mut webp = opts(ResizeMode::Dimensions);
+        webp.height = Some(20);
+        webp.filter = ResizeFilter::Triangle;
+        webp.use_webp = true;
+        let out = resize_image_file(src, dir.path(), &webp).unwrap();
+        assert_eq!((out.width, out.height), (30, 20));
+        assert!(out.path.ends_with(".webp"));
+        assert_eq!(fs::metadata(&out.path).unwrap().len(), out.size_bytes);
+    }
+
+    #[test]
+    fn open_oriented_sniffs_content_and_flags_heif() {
+        let dir = tempfile::tempdir().unwrap();
+        // PNG bytes behind a misleading extension still decode.
+        let misnamed = dir.path().join("photo.heic");
+        RgbImage::from_pixel(6, 4, Rgb([1, 2, 3]))
+            .save_with_format(&misnamed, ImageFormat::Png)
+            .unwrap();
+        let image = open_oriented(misnamed.to_str().unwrap()).unwrap();
+        assert_eq!((image.width(), image.height()), (6, 4));
+
+        let heic = dir.path().join("real.heic");
+        fs::write(&heic, b"\0\0\0\x18ftypheic\0\0\0\0mif1heic").unwrap();
+        assert_eq!(
+            open_oriented(heic.to_str().unwrap()).unwrap_err(),
+            "heif_decode_unsupported"
+        );
+        assert!(is_heif_container(b"\0\0\0\x1cftypavif\0\0\0\0"));
+        assert!(!is_heif_container(b"\0\0\0\x18ftypisom\0\0\0\0"));
+    }
+}
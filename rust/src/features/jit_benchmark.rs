@@ -0,0 +1,399 @@
+use crate::error::error_text;
+use crate::features::c_scripting::{getc_func, StringReader};
+use crate::state::AppState;
+use crate::ui::{
+    format_bytes, maybe_push_back, Button as UiButton, Chart as UiChart, ChartSeries,
+    Column as UiColumn, Text as UiText,
+};
+use libc::c_void;
+use mir_sys::*;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::ffi::CStr;
+use std::ptr;
+use std::time::{Duration, Instant};
+
+/// How long each kernel is repeated for, in each execution mode.
+pub const KERNEL_TIME_BUDGET: Duration = Duration::from_millis(250);
+
+/// Compiled once for the interpreter and once for the JIT. Each kernel does a fixed
+/// amount of work per call and returns a checksum, so the two modes can be compared and
+/// the loops cannot be optimized away.
+const KERNELS_SOURCE: &str = r#"
+static long src_words[8192];
+static long dst_words[8192];
+static unsigned char bytes[65536];
+static double ma[1024];
+static double mb[1024];
+static double mc[1024];
+
+void bench_init(void) {
+    for (long i = 0; i < 8192; i++) src_words[i] = i * 2654435761L;
+    for (long i = 0; i < 65536; i++) bytes[i] = (unsigned char) (i * 31 + 7);
+    for (long i = 0; i < 1024; i++) {
+        ma[i] = (double) (i % 17) * 0.5;
+        mb[i] = (double) (i % 13) * 0.25;
+    }
+}
+
+long bench_memcpy(void) {
+    for (long i = 0; i < 8192; i++) dst_words[i] = src_words[i];
+    return dst_words[8191] ^ dst_words[4096];
+}
+
+long bench_hash(void) {
+    unsigned long h = 14695981039346656037UL;
+    for (long i = 0; i < 65536; i++) {
+        h ^= bytes[i];
+        h *= 1099511628211UL;
+    }
+    return (long) h;
+}
+
+long bench_matmul(void) {
+    for (long i = 0; i < 32; i++) {
+        for (long j = 0; j < 32; j++) {
+            double sum = 0.0;
+            for (long k = 0; k < 32; k++) sum += ma[i * 32 + k] * mb[k * 32 + j];
+            mc[i * 32 + j] = sum;
+        }
+    }
+    return (long) (mc[0] + mc[527] + mc[1023]);
+}
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Kernel {
+    Memcpy,
+    Hash,
+    MatMul,
+}
+
+impl Kernel {
+    pub const ALL: [Kernel; 3] = [Kernel::Memcpy, Kernel::Hash, Kernel::MatMul];
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Kernel::Memcpy => "bench_memcpy",
+            Kernel::Hash => "bench_hash",
+            Kernel::MatMul => "bench_matmul",
+        }
+    }
+
+    /// Bytes copied or hashed per call, or floating-point operations for the matrix
+    /// product (one multiply and one add per inner step).
+    pub fn work_per_call(self) -> u64 {
+        match self {
+            Kernel::Memcpy | Kernel::Hash => 65_536,
+            Kernel::MatMul => 2 * 32 * 32 * 32,
+        }
+    }
+
+    fn label(self) -> String {
+        match self {
+            Kernel::Memcpy => t!("jit_bench_kernel_memcpy"),
+            Kernel::Hash => t!("jit_bench_kernel_hash"),
+            Kernel::MatMul => t!("jit_bench_kernel_matmul"),
+        }
+        .to_string()
+    }
+
+    fn format_rate(self, per_sec: f64) -> String {
+        match self {
+            Kernel::MatMul => format!("{:.1} MFLOP/s", per_sec / 1_000_000.0),
+            Kernel::Memcpy | Kernel::Hash => format!("{}/s", format_bytes(per_sec as u64)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KernelResult {
+    pub kernel: Kernel,
+    /// Units of [`Kernel::work_per_call`] per second on one core.
+    pub interp_per_sec: f64,
+    pub jit_per_sec: f64,
+    /// Whether the interpreter and the JIT returned the same checksum.
+    pub checksums_match: bool,
+}
+
+impl KernelResult {
+    pub fn speedup(&self) -> f64 {
+        if self.interp_per_sec > 0.0 {
+            self.jit_per_sec / self.interp_per_sec
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JitBenchmark {
+    pub cores: usize,
+    pub interp_compile_ms: u64,
+    pub jit_compile_ms: u64,
+    pub kernels: Vec<KernelResult>,
+}
+
+struct ModeRun {
+    compile_ms: u64,
+    /// Per kernel, in [`Kernel::ALL`] order: throughput and checksum.
+    rates: Vec<(f64, i64)>,
+}
+
+type KernelFn = extern "C" fn() -> i64;
+
+/// Runs every kernel through the MIR interpreter, then as generated code, on the
+/// calling thread. Rates are therefore per core.
+pub fn run_jit_benchmark(budget: Duration) -> Result<JitBenchmark, String> {
+    let interp = run_mode(false, budget)?;
+    let jit = run_mode(true, budget)?;
+    let kernels = Kernel::ALL
+        .iter()
+        .zip(interp.rates.iter().zip(&jit.rates))
+        .map(
+            |(&kernel, (&(interp_per_sec, interp_sum), &(jit_per_sec, jit_sum)))| KernelResult {
+                kernel,
+                interp_per_sec,
+                jit_per_sec,
+                checksums_match: interp_sum == jit_sum,
+            },
+        )
+        .collect();
+    Ok(JitBenchmark {
+        cores: std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+        interp_compile_ms: interp.compile_ms,
+        jit_compile_ms: jit.compile_ms,
+        kernels,
+    })
+}
+
+/// Compiles the kernels in a fresh MIR context and times each one for `budget`. With
+/// `use_jit` the code is generated natively, which goes through `__clear_cache` on ARM
+/// before it first runs.
+fn run_mode(use_jit: bool, budget: Duration) -> Result<ModeRun, String> {
+    unsafe {
+        let ctx = _MIR_init(ptr::null_mut(), ptr::null_mut());
+        if ctx.is_null() {
+            return Err("jit_bench_mir_init_failed".into());
+        }
+        MIR_gen_init(ctx);
+        MIR_gen_set_optimize_level(ctx, 2);
+        c2mir_init(ctx);
+        let result = compile_and_time(ctx, use_jit, budget);
+        c2mir_finish(ctx);
+        MIR_gen_finish(ctx);
+        MIR_finish(ctx);
+        result
+    }
+}
+
+unsafe fn compile_and_time(
+    ctx: MIR_context_t,
+    use_jit: bool,
+    budget: Duration,
+) -> Result<ModeRun, String> {
+    let started = Instant::now();
+    let mut reader = StringReader {
+        data: KERNELS_SOURCE.bytes().collect(),
+        cursor: 0,
+    };
+    let mut options: c2mir_options = std::mem::zeroed();
+    let compiled = c2mir_compile(
+        ctx,
+        &mut options,
+        Some(getc_func),
+        &mut reader as *mut _ as *mut c_void,
+        b"kernels.c\0".as_ptr() as *const _,
+        ptr::null_mut(),
+    );
+    let module = (*MIR_get_module_list(ctx)).tail;
+    if compiled != 1 || module.is_null() {
+        return Err("jit_bench_compile_failed".into());
+    }
+    MIR_load_module(ctx, module);
+    if use_jit {
+        MIR_link(ctx, Some(MIR_set_gen_interface), None);
+    } else {
+        MIR_link(ctx, Some(MIR_set_interp_interface), None);
+    }
+
+    let init = function_address(ctx, module, "bench_init", use_jit)?;
+    let init = std::mem::transmute::<*mut c_void, extern "C" fn()>(init);
+    let mut kernels = Vec::with_capacity(Kernel::ALL.len());
+    for kernel in Kernel::ALL {
+        let addr = function_address(ctx, module, kernel.symbol(), use_jit)?;
+        kernels.push(std::mem::transmute::<*mut c_void, KernelFn>(addr));
+    }
+    let compile_ms = started.elapsed().as_millis() as u64;
+
+    init();
+    let rates = Kernel::ALL
+        .iter()
+        .zip(kernels)
+        .map(|(kernel, run)| {
+            // The first call warms up caches and gives the checksum.
+            let checksum = run();
+            let started = Instant::now();
+            let mut calls = 0u64;
+            while calls == 0 || started.elapsed() < budget {
+                run();
+                calls += 1;
+            }
+            let work = calls as f64 * kernel.work_per_call() as f64;
+            (work / started.elapsed().as_secs_f64(), checksum)
+        })
+        .collect();
+    Ok(ModeRun { compile_ms, rates })
+}
+
+unsafe fn function_address(
+    ctx: MIR_context_t,
+    module: MIR_module_t,
+    name: &str,
+    use_jit: bool,
+) -> Result<*mut c_void, String> {
+    let mut item = (*module).items.head;
+    while !item.is_null() {
+        if (*item).item_type == MIR_item_type_t_MIR_func_item
+            && CStr::from_ptr(MIR_item_name(ctx, item)).to_bytes() == name.as_bytes()
+        {
+            let addr = if use_jit {
+                MIR_gen(ctx, item)
+            } else {
+                (*item).addr
+            };
+            return if addr.is_null() {
+                Err(format!("jit_bench_no_address:{name}"))
+            } else {
+                Ok(addr)
+            };
+        }
+        item = (*item).item_link.next;
+    }
+    Err(format!("jit_bench_missing_kernel:{name}"))
+}
+
+fn to_value_or_text<T: Serialize>(value: T, context: &str) -> Value {
+    serde_json::to_value(value).unwrap_or_else(|e| {
+        json!({
+            "type": "Text",
+            "text": format!("{context}_serialize_error:{e}")
+        })
+    })
+}
+
+pub fn render_jit_benchmark_screen(state: &AppState) -> Value {
+    let mut children = vec![
+        to_value_or_text(
+            UiText::new(&t!("jit_bench_title")).size(20.0),
+            "jit_bench_title",
+        ),
+        to_value_or_text(
+            UiText::new(&t!("jit_bench_description")).size(14.0),
+            "jit_bench_subtitle",
+        ),
+        to_value_or_text(
+            UiButton::new(&t!("jit_bench_run_button"), "jit_benchmark_run")
+                .content_description("jit_benchmark_run_btn"),
+            "jit_benchmark_run_btn",
+        ),
+    ];
+
+    if let Some(bench) = &state.jit_benchmark {
+        children.push(render_results(bench));
+    }
+
+    if let Some(err) = &state.jit_benchmark_error {
+        children.push(to_value_or_text(
+            UiText::new(&error_text(err))
+                .size(12.0)
+                .content_description("jit_benchmark_error"),
+            "jit_benchmark_error",
+        ));
+    }
+
+    maybe_push_back(&mut children, state);
+
+    to_value_or_text(UiColumn::new(children).padding(20), "jit_bench_root")
+}
+
+fn render_results(bench: &JitBenchmark) -> Value {
+    let mut rows = vec![
+        to_value_or_text(
+            UiText::new(&t!("jit_bench_cores", cores = bench.cores)).size(12.0),
+            "jit_bench_cores",
+        ),
+        to_value_or_text(
+            UiText::new(&t!(
+                "jit_bench_compile_times",
+                interp = bench.interp_compile_ms,
+                jit = bench.jit_compile_ms
+            ))
+            .size(12.0),
+            "jit_bench_compile_times",
+        ),
+    ];
+    for result in &bench.kernels {
+        let label = result.kernel.label();
+        rows.push(to_value_or_text(
+            UiText::new(&t!(
+                "jit_bench_row",
+                kernel = label,
+                interp = result.kernel.format_rate(result.interp_per_sec),
+                jit = result.kernel.format_rate(result.jit_per_sec),
+                speedup = format!("{:.1}", result.speedup())
+            ))
+            .size(12.0),
+            "jit_bench_row",
+        ));
+        if !result.checksums_match {
+            rows.push(to_value_or_text(
+                UiText::new(&t!("jit_bench_checksum_mismatch", kernel = label))
+                    .size(12.0)
+                    .content_description("jit_bench_checksum_mismatch"),
+                "jit_bench_checksum_mismatch",
+            ));
+        }
+    }
+    let title = t!("jit_bench_chart_title");
+    let kernel_axis = t!("jit_bench_axis_kernel");
+    let speedup_axis = t!("jit_bench_axis_speedup");
+    let speedups = bench.kernels.iter().map(KernelResult::speedup).collect();
+    let labels = bench.kernels.iter().map(|r| r.kernel.label()).collect();
+    rows.push(to_value_or_text(
+        UiChart::new(vec![ChartSeries::new(&title, speedups)])
+            .bar()
+            .axes(&kernel_axis, &speedup_axis)
+            .categories(labels)
+            .height_dp(140)
+            .content_description("jit_bench_chart"),
+        "jit_bench_chart",
+    ));
+    to_value_or_text(UiColumn::new(rows).padding(8), "jit_bench_results")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpreter_and_jit_agree_on_every_kernel() {
+        let bench = run_jit_benchmark(Duration::from_millis(20)).expect("benchmark ok");
+        let kernels: Vec<Kernel> = bench.kernels.iter().map(|r| r.kernel).collect();
+        assert_eq!(kernels, Kernel::ALL);
+        assert!(bench.cores >= 1);
+        for result in &bench.kernels {
+            assert!(result.checksums_match, "{:?} differs", result.kernel);
+            assert!(result.interp_per_sec > 0.0 && result.jit_per_sec > 0.0);
+        }
+
+        let mut state = AppState::new();
+        state.jit_benchmark = Some(bench);
+        let ui = render_jit_benchmark_screen(&state);
+        assert!(ui.to_string().contains("\"jit_bench_chart\""));
+        assert!(!ui.to_string().contains("jit_bench_checksum_mismatch"));
+    }
+}
@@ -0,0 +1,360 @@
+use crate::ui::{Button as UiButton, Card as UiCard, Column as UiColumn, Text as UiText};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Finished and cancelled jobs kept on the queue screen before the oldest are dropped.
+pub const JOB_HISTORY_MAX: usize = 20;
+/// Refresh interval of the queue screen while something is pending or running.
+const REFRESH_MS: u64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Finished,
+    Cancelled,
+    /// A newer job for the same result slot was submitted; this one's result is dropped.
+    Superseded,
+}
+
+impl JobStatus {
+    pub fn is_active(self) -> bool {
+        matches!(self, JobStatus::Pending | JobStatus::Running)
+    }
+
+    fn label(self) -> String {
+        let key = match self {
+            JobStatus::Pending => "job_status_pending",
+            JobStatus::Running => "job_status_running",
+            JobStatus::Finished => "job_status_finished",
+            JobStatus::Cancelled => "job_status_cancelled",
+            JobStatus::Superseded => "job_status_superseded",
+        };
+        t!(key).into_owned()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JobEntry {
+    pub id: u64,
+    pub label: &'static str,
+    /// Jobs sharing a slot write the same state; only the newest one may deliver.
+    pub slot: Option<&'static str>,
+    pub status: JobStatus,
+    pub queued_at: Instant,
+    pub started_at: Option<Instant>,
+    pub finished_at: Option<Instant>,
+    /// Last fraction the job reported while running.
+    pub progress: Option<f32>,
+}
+
+impl JobEntry {
+    /// Time spent waiting while pending, running so far, or in total once done.
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        let start = self.started_at.unwrap_or(self.queued_at);
+        self.finished_at
+            .unwrap_or(now)
+            .saturating_duration_since(start)
+    }
+}
+
+/// Worker jobs by id, in submission order. The worker thread reports start and finish;
+/// a cancelled job is skipped if still pending and its result dropped if already running,
+/// since the worker cannot interrupt a job mid-way.
+#[derive(Debug, Default)]
+pub struct JobTracker {
+    next_id: u64,
+    entries: Vec<JobEntry>,
+    /// Newest job id per result slot: the generation a result must match to be applied.
+    latest: HashMap<&'static str, u64>,
+}
+
+impl JobTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn submit(&mut self, label: &'static str, slot: Option<&'static str>) -> u64 {
+        self.next_id += 1;
+        if let Some(slot) = slot {
+            self.latest.insert(slot, self.next_id);
+        }
+        self.entries.push(JobEntry {
+            id: self.next_id,
+            label,
+            slot,
+            status: JobStatus::Pending,
+            queued_at: Instant::now(),
+            started_at: None,
+            finished_at: None,
+            progress: None,
+        });
+        self.next_id
+    }
+
+    /// Marks `id` running; `false` means it was cancelled or superseded and must not run.
+    pub fn start(&mut self, id: u64) -> bool {
+        self.supersede_if_stale(id);
+        match self.entry_mut(id) {
+            Some(entry) if entry.status == JobStatus::Pending => {
+                entry.status = JobStatus::Running;
+                entry.started_at = Some(Instant::now());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Marks `id` finished; `false` means it was cancelled or superseded and its result
+    /// must be dropped.
+    pub fn finish(&mut self, id: u64) -> bool {
+        self.supersede_if_stale(id);
+        let delivered = match self.entry_mut(id) {
+            Some(entry) if entry.status == JobStatus::Running => {
+                entry.status = JobStatus::Finished;
+                entry.finished_at = Some(Instant::now());
+                true
+            }
+            _ => false,
+        };
+        self.trim_history();
+        delivered
+    }
+
+    pub fn set_progress(&mut self, id: u64, fraction: Option<f32>) {
+        if let Some(entry) = self.entry_mut(id) {
+            entry.progress = fraction;
+        }
+    }
+
+    pub fn cancel(&mut self, id: u64) -> Result<(), String> {
+        let entry = self
+            .entry_mut(id)
+            .ok_or_else(|| format!("job_not_found:{id}"))?;
+        if !entry.status.is_active() {
+            return Err(format!("job_not_active:{id}"));
+        }
+        entry.status = JobStatus::Cancelled;
+        entry.finished_at = Some(Instant::now());
+        self.trim_history();
+        Ok(())
+    }
+
+    /// Forgets a job that never reached the worker.
+    pub fn discard(&mut self, id: u64) {
+        self.entries.retain(|e| e.id != id);
+        self.latest.retain(|_, latest| *latest != id);
+    }
+
+    pub fn clear_finished(&mut self) {
+        self.entries.retain(|e| e.status.is_active());
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.entries.iter().filter(|e| e.status.is_active()).count()
+    }
+
+    pub fn entries(&self) -> &[JobEntry] {
+        &self.entries
+    }
+
+    fn entry_mut(&mut self, id: u64) -> Option<&mut JobEntry> {
+        self.entries.iter_mut().find(|e| e.id == id)
+    }
+
+    /// Retires an active job whose slot has moved on to a newer generation.
+    fn supersede_if_stale(&mut self, id: u64) {
+        let latest = &self.latest;
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.id == id) {
+            let stale = entry
+                .slot
+                .and_then(|slot| latest.get(slot))
+                .is_some_and(|&newest| newest != id);
+            if stale && entry.status.is_active() {
+                entry.status = JobStatus::Superseded;
+                entry.finished_at = Some(Instant::now());
+            }
+        }
+    }
+
+    fn trim_history(&mut self) {
+        let done = self.entries.len() - self.active_count();
+        let mut excess = done.saturating_sub(JOB_HISTORY_MAX);
+        self.entries.retain(|e| {
+            if excess > 0 && !e.status.is_active() {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{:.1}s", elapsed.as_secs_f64())
+    }
+}
+
+pub fn render_job_queue_screen(jobs: &[JobEntry], nav_depth: u32) -> Value {
+    let now = Instant::now();
+    let active = jobs.iter().filter(|j| j.status.is_active()).count();
+    let mut children = vec![
+        serde_json::to_value(UiText::new(&t!("job_queue_title")).size(20.0)).unwrap(),
+        serde_json::to_value(
+            UiText::new(&t!(
+                "job_queue_summary",
+                active = active,
+                done = jobs.len() - active
+            ))
+            .size(12.0),
+        )
+        .unwrap(),
+    ];
+
+    if jobs.is_empty() {
+        children
+            .push(serde_json::to_value(UiText::new(&t!("job_queue_empty")).size(14.0)).unwrap());
+    }
+
+    // Newest first: what the user just started is what they are looking for.
+    for job in jobs.iter().rev() {
+        let percent = match (job.status, job.progress) {
+            (JobStatus::Running, Some(fraction)) => format!(" · {:.0}%", fraction * 100.0),
+            _ => String::new(),
+        };
+        let mut card_children = vec![serde_json::to_value(
+            UiText::new(&format!(
+                "{}{percent} · {}",
+                job.status.label(),
+                format_elapsed(job.elapsed(now))
+            ))
+            .size(14.0),
+        )
+        .unwrap()];
+        if job.status.is_active() {
+            card_children.push(
+                serde_json::to_value(
+                    UiButton::new(&t!("job_cancel_button"), "job_cancel")
+                        .id(&format!("job_cancel_{}", job.id))
+                        .payload(json!({ "job_id": job.id.to_string() })),
+                )
+                .unwrap(),
+            );
+        }
+        children.push(
+            serde_json::to_value(
+                UiCard::new(card_children)
+                    .title(&format!("#{} {}", job.id, job.label))
+                    .padding(12),
+            )
+            .unwrap(),
+        );
+    }
+
+    if active < jobs.len() {
+        children.push(
+            serde_json::to_value(UiButton::new(
+                &t!("job_queue_clear_button"),
+                "job_queue_clear",
+            ))
+            .unwrap(),
+        );
+    }
+    if nav_depth > 1 {
+        children.push(serde_json::to_value(UiButton::new(&t!("button_back"), "back")).unwrap());
+    }
+
+    let mut root = json!(UiColumn::new(children).padding(20));
+    if active > 0 {
+        if let Some(obj) = root.as_object_mut() {
+            obj.insert("auto_refresh_ms".into(), Value::Number(REFRESH_MS.into()));
+            obj.insert(
+                "auto_refresh_action".into(),
+                Value::String("job_queue_refresh".into()),
+            );
+        }
+    }
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancelled_jobs_are_skipped_or_dropped() {
+        let mut tracker = JobTracker::new();
+        let running = tracker.submit("Hash", None);
+        let pending = tracker.submit("PDF", None);
+        assert!(tracker.start(running));
+        assert_eq!(tracker.active_count(), 2);
+
+        tracker.cancel(pending).unwrap();
+        assert!(!tracker.start(pending));
+        tracker.cancel(running).unwrap();
+        assert!(!tracker.finish(running));
+        assert_eq!(tracker.active_count(), 0);
+        assert_eq!(
+            tracker.cancel(running),
+            Err(format!("job_not_active:{running}"))
+        );
+        assert_eq!(tracker.cancel(99), Err("job_not_found:99".to_string()));
+
+        let done = tracker.submit("Archive", None);
+        assert!(tracker.start(done));
+        tracker.set_progress(done, Some(0.5));
+        assert_eq!(tracker.entries()[2].progress, Some(0.5));
+        assert!(tracker.finish(done));
+        assert_eq!(tracker.entries()[2].status, JobStatus::Finished);
+        tracker.clear_finished();
+        assert!(tracker.entries().is_empty());
+    }
+
+    #[test]
+    fn history_keeps_active_jobs_and_the_newest_finished() {
+        let mut tracker = JobTracker::new();
+        let waiting = tracker.submit("Pending", None);
+        for _ in 0..JOB_HISTORY_MAX + 3 {
+            let id = tracker.submit("Quick", None);
+            tracker.start(id);
+            tracker.finish(id);
+        }
+        assert_eq!(tracker.entries().len(), JOB_HISTORY_MAX + 1);
+        assert_eq!(tracker.entries()[0].id, waiting);
+        assert_eq!(
+            tracker.entries().last().map(|e| e.id),
+            Some(JOB_HISTORY_MAX as u64 + 4)
+        );
+    }
+
+    #[test]
+    fn newer_jobs_supersede_older_ones_in_the_same_slot() {
+        let mut tracker = JobTracker::new();
+        let first = tracker.submit("Hash", Some("hash"));
+        assert!(tracker.start(first));
+        let queued = tracker.submit("Hash", Some("hash"));
+        let other = tracker.submit("PDF", Some("pdf"));
+        let latest = tracker.submit("Hash", Some("hash"));
+        assert!(!tracker.finish(first));
+        assert!(!tracker.start(queued));
+        assert_eq!(tracker.entries()[0].status, JobStatus::Superseded);
+        assert_eq!(tracker.entries()[1].status, JobStatus::Superseded);
+
+        assert!(tracker.start(other));
+        assert!(tracker.finish(other));
+        assert!(tracker.start(latest));
+        assert!(tracker.finish(latest));
+    }
+
+    #[test]
+    fn elapsed_formats_seconds_and_minutes() {
+        assert_eq!(format_elapsed(Duration::from_millis(1500)), "1.5s");
+        assert_eq!(format_elapsed(Duration::from_secs(125)), "2m 05s");
+    }
+}
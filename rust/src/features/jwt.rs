@@ -1,3 +1,4 @@
+use crate::error::error_text;
 use crate::state::AppState;
 use crate::ui::{maybe_push_back, Button as UiButton, CodeView as UiCodeView, Column as UiColumn, Text as UiText, TextInput as UiTextInput};
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
@@ -51,11 +52,8 @@ pub fn decode_jwt(token: &str) -> Result<(String, String), String> {
 
 pub fn render_jwt_screen(state: &AppState) -> serde_json::Value {
     let mut children = vec![
-        serde_json::to_value(UiText::new("JWT Decoder").size(20.0)).unwrap(),
-        serde_json::to_value(
-            UiText::new("Paste a JWT to inspect its header and payload.").size(14.0),
-        )
-        .unwrap(),
+        serde_json::to_value(UiText::new(&t!("jwt_title")).size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("jwt_description")).size(14.0)).unwrap(),
         serde_json::to_value(
             UiTextInput::new("jwt_input")
                 .text(&state.jwt.input_token)
@@ -63,15 +61,19 @@ pub fn render_jwt_screen(state: &AppState) -> serde_json::Value {
                 .max_lines(4),
         )
         .unwrap(),
-        serde_json::to_value(UiButton::new("Decode", "jwt_decode")).unwrap(),
-        serde_json::to_value(UiButton::new("Clear", "jwt_clear")).unwrap(),
-        serde_json::to_value(UiButton::new("Paste from Clipboard", "jwt_paste")).unwrap(),
+        serde_json::to_value(UiButton::new(&t!("jwt_decode_button"), "jwt_decode")).unwrap(),
+        serde_json::to_value(UiButton::new(&t!("jwt_clear_button"), "jwt_clear")).unwrap(),
+        serde_json::to_value(UiButton::new(
+            &t!("button_paste_from_clipboard"),
+            "jwt_paste",
+        ))
+        .unwrap(),
     ];
 
     if let Some(err) = &state.jwt.error {
         children.push(
             serde_json::to_value(
-                UiText::new(&format!("Error: {}", err))
+                UiText::new(&error_text(err))
                     .size(12.0)
                     .content_description("jwt_error"),
             )
@@ -82,7 +84,9 @@ pub fn render_jwt_screen(state: &AppState) -> serde_json::Value {
     if let Some(h) = &state.jwt.decoded_header {
         children.push(
             serde_json::to_value(
-                UiText::new("Header").size(16.0).content_description("jwt_header_title"),
+                UiText::new(&t!("jwt_header_title"))
+                    .size(16.0)
+                    .content_description("jwt_header_title"),
             )
             .unwrap(),
         );
@@ -100,7 +104,9 @@ pub fn render_jwt_screen(state: &AppState) -> serde_json::Value {
     if let Some(p) = &state.jwt.decoded_payload {
         children.push(
             serde_json::to_value(
-                UiText::new("Payload").size(16.0).content_description("jwt_payload_title"),
+                UiText::new(&t!("jwt_payload_title"))
+                    .size(16.0)
+                    .content_description("jwt_payload_title"),
             )
             .unwrap(),
         );
@@ -1,5 +1,11 @@
+use crate::error::error_text;
+use crate::features::image_resize::{
+    parse_dimension, ResizeFilter, ResizeMode, ResizeOptions, ResizeOutput,
+};
 use crate::state::AppState;
-use crate::ui::{maybe_push_back, Button, Checkbox, Column, Grid, Text, TextInput};
+use crate::ui::{
+    format_bytes, maybe_push_back, Button, Checkbox, Column, Grid, Text, TextInput,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -9,11 +15,18 @@ use rust_i18n::t;
 pub struct KotlinImageState {
     pub active_tool: Option<ImageTool>,
     pub source_path: Option<String>,
+    /// Directory for Rust-side resize output, derived from the picked URI like dithering does.
+    pub local_output_dir: Option<String>,
     pub result: Option<ImageConversionResult>,
     pub resize_scale_pct: u32,
     pub resize_quality: u32,
     pub resize_target_kb: Option<u64>,
     pub resize_use_webp: bool,
+    pub resize_mode: ResizeMode,
+    pub resize_width: Option<u32>,
+    pub resize_height: Option<u32>,
+    pub resize_keep_aspect: bool,
+    pub resize_filter: ResizeFilter,
     pub output_dir: Option<String>,
     pub batch_queue: Vec<String>,
 }
@@ -29,11 +42,17 @@ impl KotlinImageState {
         Self {
             active_tool: None,
             source_path: None,
+            local_output_dir: None,
             result: None,
             resize_scale_pct: 70,
             resize_quality: 85,
             resize_target_kb: None,
             resize_use_webp: false,
+            resize_mode: ResizeMode::Percent,
+            resize_width: None,
+            resize_height: None,
+            resize_keep_aspect: true,
+            resize_filter: ResizeFilter::Lanczos3,
             output_dir: None,
             batch_queue: Vec::new(),
         }
@@ -42,11 +61,17 @@ impl KotlinImageState {
     pub fn reset(&mut self) {
         self.active_tool = None;
         self.source_path = None;
+        self.local_output_dir = None;
         self.result = None;
         self.resize_scale_pct = 70;
         self.resize_quality = 85;
         self.resize_target_kb = None;
         self.resize_use_webp = false;
+        self.resize_mode = ResizeMode::Percent;
+        self.resize_width = None;
+        self.resize_height = None;
+        self.resize_keep_aspect = true;
+        self.resize_filter = ResizeFilter::Lanczos3;
         self.batch_queue.clear();
     }
 }
@@ -63,9 +88,23 @@ pub struct ImageConversionResult {
     pub path: Option<String>,
     pub size: Option<String>,
     pub format: Option<String>,
+    /// Extra summary line, e.g. the dimensions a resize produced.
+    pub details: Option<String>,
     pub error: Option<String>,
 }
 
+impl ImageConversionResult {
+    pub fn from_error(error: String) -> Self {
+        Self {
+            path: None,
+            size: None,
+            format: None,
+            details: None,
+            error: Some(error),
+        }
+    }
+}
+
 fn to_value_or_text<T: Serialize>(value: T, context: &str) -> Value {
     serde_json::to_value(value).unwrap_or_else(|e| {
         json!({
@@ -78,12 +117,14 @@ fn to_value_or_text<T: Serialize>(value: T, context: &str) -> Value {
 pub fn handle_screen_entry(state: &mut AppState, _target: ImageTarget) {
     state.image.active_tool = Some(ImageTool::Convert);
     state.image.source_path = None;
+    state.image.local_output_dir = None;
     state.image.result = None;
 }
 
 pub fn handle_resize_screen(state: &mut AppState) {
     state.image.active_tool = Some(ImageTool::Resize);
     state.image.source_path = None;
+    state.image.local_output_dir = None;
     state.image.result = None;
 }
 
@@ -108,6 +149,102 @@ pub fn handle_resize_sync(state: &mut AppState, bindings: &HashMap<String, Strin
     if let Some(val) = bindings.get("resize_use_webp") {
         state.image.resize_use_webp = val == "true";
     }
+    match bindings.get("resize_mode").map(|s| s.as_str()) {
+        Some("percent") => state.image.resize_mode = ResizeMode::Percent,
+        Some("dimensions") => state.image.resize_mode = ResizeMode::Dimensions,
+        _ => {}
+    }
+    if let Some(Ok(v)) = bindings.get("resize_width").map(|s| parse_dimension(s)) {
+        state.image.resize_width = v;
+    }
+    if let Some(Ok(v)) = bindings.get("resize_height").map(|s| parse_dimension(s)) {
+        state.image.resize_height = v;
+    }
+    if let Some(val) = bindings.get("resize_keep_aspect") {
+        state.image.resize_keep_aspect = val == "true";
+    }
+    match bindings.get("resize_filter").map(|s| s.as_str()) {
+        Some("lanczos3") => state.image.resize_filter = ResizeFilter::Lanczos3,
+        Some("triangle") => state.image.resize_filter = ResizeFilter::Triangle,
+        _ => {}
+    }
+}
+
+/// Syncs the resize form and validates it into options for the Rust resize worker.
+pub fn resize_options(
+    state: &mut AppState,
+    bindings: &HashMap<String, String>,
+) -> Result<ResizeOptions, String> {
+    handle_resize_sync(state, bindings);
+    for key in ["resize_width", "resize_height"] {
+        if let Some(raw) = bindings.get(key) {
+            parse_dimension(raw)?;
+        }
+    }
+    let image = &state.image;
+    if image.resize_mode == ResizeMode::Dimensions
+        && image.resize_width.is_none()
+        && image.resize_height.is_none()
+    {
+        return Err("resize_missing_dimensions".into());
+    }
+    Ok(ResizeOptions {
+        mode: image.resize_mode,
+        scale_pct: image.resize_scale_pct,
+        width: image.resize_width,
+        height: image.resize_height,
+        keep_aspect: image.resize_keep_aspect,
+        filter: image.resize_filter,
+        quality: image.resize_quality.clamp(10, 100) as u8,
+        target_bytes: image
+            .resize_target_kb
+            .filter(|kb| *kb > 0)
+            .map(|kb| kb.min(10_000) * 1024),
+        use_webp: image.resize_use_webp,
+    })
+}
+
+/// Summarizes single or batch resize outcomes for the result area.
+pub fn resize_result(
+    outputs: &[Result<ResizeOutput, String>],
+    output_dir: &str,
+) -> ImageConversionResult {
+    let done: Vec<&ResizeOutput> = outputs.iter().filter_map(|r| r.as_ref().ok()).collect();
+    let Some(last) = done.last() else {
+        let err = outputs
+            .iter()
+            .find_map(|r| r.as_ref().err().cloned())
+            .unwrap_or_else(|| "no_image_selected".into());
+        return ImageConversionResult::from_error(err);
+    };
+    if outputs.len() == 1 {
+        return ImageConversionResult {
+            path: Some(last.path.clone()),
+            size: Some(format_bytes(last.size_bytes)),
+            format: Some(last.format.to_string()),
+            details: Some(format!(
+                "{}{}×{} → {}×{}",
+                t!("image_dimensions_prefix"),
+                last.source_width,
+                last.source_height,
+                last.width,
+                last.height
+            )),
+            error: None,
+        };
+    }
+    ImageConversionResult {
+        path: Some(output_dir.to_string()),
+        size: Some(format_bytes(done.iter().map(|o| o.size_bytes).sum())),
+        format: Some(last.format.to_string()),
+        details: Some(format!(
+            "{}{}/{}",
+            t!("image_batch_resized_prefix"),
+            done.len(),
+            outputs.len()
+        )),
+        error: None,
+    }
 }
 
 pub fn handle_result(
@@ -275,20 +412,86 @@ fn render_resizer(state: &AppState) -> Value {
             "selected_path",
         ));
 
+        // Mode
         children.push(to_value_or_text(
-            TextInput::new("image_source_path")
-                .text(path)
-                .content_description("hidden_source_path"),
-            "input_source_path",
+            Text::new(&t!("image_resize_mode_label")),
+            "lbl_mode",
         ));
+        let modes = [
+            (ResizeMode::Percent, t!("image_resize_mode_percent"), "percent"),
+            (ResizeMode::Dimensions, t!("image_resize_mode_dimensions"), "dimensions"),
+        ];
+        let mode_buttons = modes
+            .into_iter()
+            .map(|(mode, label, key)| {
+                let id = format!("resize_mode_{key}");
+                let mut button = Button::new(&label, "kotlin_image_resize_sync")
+                    .payload(json!({ "resize_mode": key }))
+                    .id(&id);
+                if mode == state.image.resize_mode {
+                    button = button.content_description("selected");
+                }
+                to_value_or_text(button, "btn_mode")
+            })
+            .collect();
+        children.push(to_value_or_text(Grid::new(mode_buttons).columns(2), "grid_mode"));
+
+        match state.image.resize_mode {
+            ResizeMode::Percent => {
+                children.push(to_value_or_text(Text::new(&t!("image_scale_label")), "lbl_scale"));
+                children.push(to_value_or_text(
+                    TextInput::new("resize_scale_pct")
+                        .text(&state.image.resize_scale_pct.to_string()),
+                    "input_scale",
+                ));
+            }
+            ResizeMode::Dimensions => {
+                let dim = |v: Option<u32>| v.map(|v| v.to_string()).unwrap_or_default();
+                children.push(to_value_or_text(Text::new(&t!("image_width_label")), "lbl_width"));
+                children.push(to_value_or_text(
+                    TextInput::new("resize_width")
+                        .text(&dim(state.image.resize_width))
+                        .hint(&t!("image_dimension_hint")),
+                    "input_width",
+                ));
+                children.push(to_value_or_text(Text::new(&t!("image_height_label")), "lbl_height"));
+                children.push(to_value_or_text(
+                    TextInput::new("resize_height")
+                        .text(&dim(state.image.resize_height))
+                        .hint(&t!("image_dimension_hint")),
+                    "input_height",
+                ));
+                children.push(to_value_or_text(
+                    Checkbox::new(&t!("image_keep_aspect_checkbox"), "resize_keep_aspect")
+                        .checked(state.image.resize_keep_aspect),
+                    "check_aspect",
+                ));
+            }
+        }
 
-        // Scale
-        children.push(to_value_or_text(Text::new(&t!("image_scale_label")), "lbl_scale"));
+        // Filter
         children.push(to_value_or_text(
-            TextInput::new("resize_scale_pct")
-                .text(&state.image.resize_scale_pct.to_string()),
-            "input_scale",
+            Text::new(&t!("image_filter_label")),
+            "lbl_filter",
         ));
+        let filters = [
+            (ResizeFilter::Lanczos3, t!("image_filter_lanczos"), "lanczos3"),
+            (ResizeFilter::Triangle, t!("image_filter_triangle"), "triangle"),
+        ];
+        let filter_buttons = filters
+            .into_iter()
+            .map(|(filter, label, key)| {
+                let id = format!("resize_filter_{key}");
+                let mut button = Button::new(&label, "kotlin_image_resize_sync")
+                    .payload(json!({ "resize_filter": key }))
+                    .id(&id);
+                if filter == state.image.resize_filter {
+                    button = button.content_description("selected");
+                }
+                to_value_or_text(button, "btn_filter")
+            })
+            .collect();
+        children.push(to_value_or_text(Grid::new(filter_buttons).columns(2), "grid_filter"));
 
         // Quality
         children.push(to_value_or_text(
@@ -327,7 +530,7 @@ fn render_resizer(state: &AppState) -> Value {
 
         // Action
         children.push(to_value_or_text(
-            Button::new(&t!("image_process_image_button"), "kotlin_image_resize"),
+            Button::new(&t!("image_process_image_button"), "image_resize"),
             "btn_process",
         ));
     }
@@ -335,10 +538,7 @@ fn render_resizer(state: &AppState) -> Value {
     if !state.image.batch_queue.is_empty() {
         children.push(render_batch_list(&state.image.batch_queue, "resize"));
         children.push(to_value_or_text(
-            Button::new(&t!("image_process_batch_resize"), "kotlin_image_batch_process").payload(json!({
-                "image_batch_paths": state.image.batch_queue,
-                "image_batch_mode": "resize"
-            })),
+            Button::new(&t!("image_process_batch_resize"), "image_resize_batch"),
             "batch_resize",
         ));
     }
@@ -355,10 +555,7 @@ fn render_result_area(children: &mut Vec<Value>, state: &AppState) {
         children.push(to_value_or_text(Text::new(&t!("image_separator")).size(12.0), "div_res"));
 
         if let Some(err) = &res.error {
-            children.push(to_value_or_text(
-                Text::new(&format!("{}{}", t!("multi_hash_error_prefix"), err)),
-                "err_msg",
-            ));
+            children.push(to_value_or_text(Text::new(&error_text(err)), "err_msg"));
         } else if let Some(dest) = &res.path {
             children.push(to_value_or_text(
                 Text::new(&t!("image_success")).size(18.0),
@@ -380,11 +577,21 @@ fn render_result_area(children: &mut Vec<Value>, state: &AppState) {
                     "success_fmt",
                 ));
             }
+            if let Some(details) = &res.details {
+                children.push(to_value_or_text(
+                    Text::new(details).size(12.0),
+                    "success_details",
+                ));
+            }
 
-            children.push(to_value_or_text(
-                Button::new(&t!("image_save_as_button"), "kotlin_image_save_as"),
-                "btn_save_as",
-            ));
+            // Batch resizes report their output directory, which has nothing to save.
+            if !std::path::Path::new(dest).is_dir() {
+                children.push(to_value_or_text(
+                    Button::new(&t!("image_save_as_button"), "kotlin_image_save_as")
+                        .payload(json!({ "image_result_path": dest })),
+                    "btn_save_as",
+                ));
+            }
         }
     }
 }
@@ -91,18 +91,14 @@ impl TripleStore {
 
 pub fn render_logic_screen(state: &AppState) -> Value {
     let mut children = vec![
-        serde_json::to_value(UiText::new("Logical Engine").size(20.0)).unwrap(),
-        serde_json::to_value(
-            UiText::new("Add triples, import CSV, and query with simple patterns.")
-                .size(14.0),
-        )
-        .unwrap(),
+        serde_json::to_value(UiText::new(&t!("logic_title")).size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("logic_description")).size(14.0)).unwrap(),
     ];
 
     if let Some(err) = &state.logic.import_error {
         children.push(
             serde_json::to_value(
-                UiText::new(&format!("Import error: {err}"))
+                UiText::new(&t!("logic_import_error", error = err))
                     .size(12.0)
                     .content_description("logic_import_error"),
             )
@@ -110,37 +106,37 @@ pub fn render_logic_screen(state: &AppState) -> Value {
         );
     }
 
+    children.push(serde_json::to_value(UiText::new(&t!("logic_add_section")).size(16.0)).unwrap());
     children.push(
-        serde_json::to_value(UiText::new("Add triple").size(16.0)).unwrap(),
-    );
-    children.push(
-        serde_json::to_value(UiTextInput::new("logic_add_s").hint("Subject")).unwrap(),
+        serde_json::to_value(UiTextInput::new("logic_add_s").hint(&t!("logic_subject_hint")))
+            .unwrap(),
     );
     children.push(
-        serde_json::to_value(UiTextInput::new("logic_add_p").hint("Predicate")).unwrap(),
+        serde_json::to_value(UiTextInput::new("logic_add_p").hint(&t!("logic_predicate_hint")))
+            .unwrap(),
     );
     children.push(
-        serde_json::to_value(UiTextInput::new("logic_add_o").hint("Object")).unwrap(),
+        serde_json::to_value(UiTextInput::new("logic_add_o").hint(&t!("logic_object_hint")))
+            .unwrap(),
     );
     children.push(
-        serde_json::to_value(UiButton::new("Add", "logic_add_triple")).unwrap(),
+        serde_json::to_value(UiButton::new(&t!("logic_add_button"), "logic_add_triple")).unwrap(),
     );
     children.push(
         serde_json::to_value(
-            UiButton::new("Import CSV", "logic_import")
+            UiButton::new(&t!("logic_import_button"), "logic_import")
                 .requires_file_picker(true)
                 .content_description("logic_import_btn"),
         )
         .unwrap(),
     );
 
-    children.push(
-        serde_json::to_value(UiText::new("Query (use * for any)").size(16.0)).unwrap(),
-    );
+    children
+        .push(serde_json::to_value(UiText::new(&t!("logic_query_section")).size(16.0)).unwrap());
     children.push(
         serde_json::to_value(
             UiTextInput::new("logic_query_s")
-                .hint("Subject pattern")
+                .hint(&t!("logic_subject_pattern_hint"))
                 .debounce_ms(150)
                 .action_on_submit("logic_query"),
         )
@@ -149,7 +145,7 @@ pub fn render_logic_screen(state: &AppState) -> Value {
     children.push(
         serde_json::to_value(
             UiTextInput::new("logic_query_p")
-                .hint("Predicate pattern")
+                .hint(&t!("logic_predicate_pattern_hint"))
                 .debounce_ms(150)
                 .action_on_submit("logic_query"),
         )
@@ -158,14 +154,14 @@ pub fn render_logic_screen(state: &AppState) -> Value {
     children.push(
         serde_json::to_value(
             UiTextInput::new("logic_query_o")
-                .hint("Object pattern")
+                .hint(&t!("logic_object_pattern_hint"))
                 .debounce_ms(150)
                 .action_on_submit("logic_query"),
         )
         .unwrap(),
     );
     children.push(
-        serde_json::to_value(UiButton::new("Run query", "logic_query")).unwrap(),
+        serde_json::to_value(UiButton::new(&t!("logic_run_query_button"), "logic_query")).unwrap(),
     );
 
     if !state.logic.results.is_empty() {
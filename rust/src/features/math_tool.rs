@@ -1,13 +1,87 @@
-use crate::state::{AppState, MathHistoryEntry};
+use crate::error::error_text;
+use crate::features::expr_jit::{compile_expression, JitExpr, JIT_MIN_SAMPLES};
+use crate::features::presets::{load_tool_history, save_tool_history};
+use crate::state::{AppState, MathHistoryEntry, MathResultFormat};
 use crate::ui::{
-    maybe_push_back, Button as UiButton, Column as UiColumn, Text as UiText,
-    TextInput as UiTextInput, VirtualList as UiVirtualList,
+    maybe_push_back, Button as UiButton, Column as UiColumn, ImageBase64, Row as UiRow,
+    Text as UiText, TextInput as UiTextInput, VirtualList as UiVirtualList,
 };
-use serde_json::Value;
+use base64::Engine;
+use image::{codecs::png::PngEncoder, ColorType, ImageEncoder, Rgb, RgbImage};
+use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::f64::consts::{E, PI};
+use std::path::Path;
+use tempfile::Builder;
 use crate::features::cas_types::Number;
 
+const PLOT_WIDTH: u32 = 640;
+const PLOT_HEIGHT: u32 = 400;
+const PLOT_MARGIN: u32 = 24;
+const PLOT_GRID_DIVISIONS: u32 = 10;
+const PLOT_DEFAULT_RANGE: (f64, f64) = (-10.0, 10.0);
+const MATH_HISTORY_TOOL_ID: &str = "math_tool";
+const MATH_HISTORY_MAX_CAP: usize = 500;
+const TAYLOR_MAX_ORDER: u32 = 12;
+const MAX_EXACT_INTEGER: u64 = 1 << 53;
+/// Simpson slices used by `nintegrate`; must be even.
+const NINTEGRATE_INTERVALS: usize = 10_000;
+
+/// A named constant usable in expressions, e.g. `m*g0` or `h*c/500e-9`.
+pub struct PhysicalConstant {
+    pub symbol: &'static str,
+    pub name: &'static str,
+    pub value: f64,
+    pub unit: &'static str,
+}
+
+/// CODATA 2018 values; symbols are matched case-sensitively.
+pub const PHYSICAL_CONSTANTS: &[PhysicalConstant] = &[
+    PhysicalConstant {
+        symbol: "c",
+        name: "Speed of light in vacuum",
+        value: 299_792_458.0,
+        unit: "m/s",
+    },
+    PhysicalConstant {
+        symbol: "G",
+        name: "Newtonian constant of gravitation",
+        value: 6.674_30e-11,
+        unit: "m^3/(kg*s^2)",
+    },
+    PhysicalConstant {
+        symbol: "h",
+        name: "Planck constant",
+        value: 6.626_070_15e-34,
+        unit: "J*s",
+    },
+    PhysicalConstant {
+        symbol: "k_B",
+        name: "Boltzmann constant",
+        value: 1.380_649e-23,
+        unit: "J/K",
+    },
+    PhysicalConstant {
+        symbol: "N_A",
+        name: "Avogadro constant",
+        value: 6.022_140_76e23,
+        unit: "1/mol",
+    },
+    PhysicalConstant {
+        symbol: "g0",
+        name: "Standard acceleration of gravity",
+        value: 9.806_65,
+        unit: "m/s^2",
+    },
+];
+
+fn physical_constant(symbol: &str) -> Option<f64> {
+    PHYSICAL_CONSTANTS
+        .iter()
+        .find(|c| c.symbol == symbol)
+        .map(|c| c.value)
+}
+
 pub fn render_math_tool_screen(state: &AppState) -> Value {
     let math_tool_title = t!("screen_math_tool_title");
     let math_tool_description = t!("screen_math_tool_description");
@@ -21,29 +95,90 @@ pub fn render_math_tool_screen(state: &AppState) -> Value {
         .unwrap(),
         serde_json::to_value(
             UiTextInput::new("math_expr")
-                .hint("e.g., sin(pi/2) + 3^2")
+                .hint(&t!("math_tool_expression_hint"))
                 .text(&state.math_tool.expression)
                 .single_line(true)
                 .debounce_ms(150),
         )
         .unwrap(),
-        serde_json::to_value(UiButton::new("Calculate", "math_calculate")).unwrap(),
-        serde_json::to_value(UiButton::new("Clear history", "math_clear_history")).unwrap(),
+        serde_json::to_value(UiButton::new(
+            &t!("math_tool_calculate_button"),
+            "math_calculate",
+        ))
+        .unwrap(),
+        serde_json::to_value(UiButton::new(
+            &t!("math_tool_clear_history_button"),
+            "math_clear_history",
+        ))
+        .unwrap(),
+        serde_json::to_value(UiButton::new(
+            &t!("math_tool_constants_button"),
+            "math_constants_screen",
+        ))
+        .unwrap(),
     ];
 
+    let format_rows = [
+        [
+            (MathResultFormat::Decimal, "Dec", "math_format_dec"),
+            (MathResultFormat::Hex, "Hex", "math_format_hex"),
+            (MathResultFormat::Binary, "Bin", "math_format_bin"),
+        ],
+        [
+            (MathResultFormat::Fraction, "Frac", "math_format_frac"),
+            (MathResultFormat::Scientific, "Sci", "math_format_sci"),
+            (MathResultFormat::Engineering, "Eng", "math_format_eng"),
+        ],
+    ];
+    for formats in format_rows {
+        let format_buttons: Vec<Value> = formats
+            .iter()
+            .map(|(format, label, action)| {
+                let mut button = UiButton::new(label, action);
+                if *format == state.math_tool.result_format {
+                    button = button.content_description("selected");
+                }
+                serde_json::to_value(button).unwrap()
+            })
+            .collect();
+        children.push(serde_json::to_value(UiRow::new(format_buttons)).unwrap());
+    }
+
     if let Some(err) = &state.math_tool.error {
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
+    }
+
+    if let Some(b64) = &state.math_tool.plot_base64 {
+        children.push(
+            serde_json::to_value(
+                ImageBase64::new(b64).content_description(&t!("math_tool_plot_description")),
+            )
+            .unwrap(),
+        );
+    }
+    if let Some(path) = &state.math_tool.plot_path {
+        children.push(
+            serde_json::to_value(UiText::new(&t!("math_tool_plot_saved", path = path)).size(12.0))
+                .unwrap(),
+        );
         children.push(
-            serde_json::to_value(UiText::new(&format!("Error: {err}")).size(12.0)).unwrap(),
+            serde_json::to_value(
+                UiButton::new(&t!("math_tool_copy_plot_path_button"), "noop").copy_text(path),
+            )
+            .unwrap(),
         );
     }
 
     if !state.math_tool.history.is_empty() {
-        children.push(serde_json::to_value(UiText::new("History").size(16.0)).unwrap());
+        children.push(
+            serde_json::to_value(UiText::new(&t!("math_tool_history_section")).size(16.0)).unwrap(),
+        );
         let items: Vec<Value> = state
             .math_tool
             .history
             .iter()
-            .map(|entry| {
+            .enumerate()
+            .map(|(idx, entry)| {
                 let mut line = format!("{} = {}", entry.expression, entry.result);
                 if let Some(err) = entry.error_estimate {
                     line.push_str(&format!("  [error≈{:.2e}", err));
@@ -54,42 +189,105 @@ pub fn render_math_tool_screen(state: &AppState) -> Value {
                     }
                     line.push(']');
                 }
-                serde_json::to_value(UiText::new(&line).size(12.0)).unwrap()
+                serde_json::to_value(
+                    UiButton::new(&line, "math_recall")
+                        .payload(json!({ "index": idx.to_string() })),
+                )
+                .unwrap()
             })
             .collect();
         children.push(serde_json::to_value(UiVirtualList::new(items).id("math_history")).unwrap());
     }
+    children.push(
+        serde_json::to_value(
+            UiTextInput::new("math_history_cap")
+                .hint(&t!("math_tool_history_cap_hint"))
+                .text(&state.math_tool.history_cap.to_string())
+                .single_line(true),
+        )
+        .unwrap(),
+    );
+    children.push(
+        serde_json::to_value(UiButton::new(
+            &t!("math_tool_set_history_cap_button"),
+            "math_set_history_cap",
+        ))
+        .unwrap(),
+    );
 
     // Add cumulative error display if there's any error accumulated
     if state.math_tool.cumulative_error > 0.0 {
         children.push(
             serde_json::to_value(
-                UiText::new(&format!("Cumulative FP Error: {:.2e}", state.math_tool.cumulative_error))
-                    .size(12.0)
-                    .color("#FF9800")  // Orange color for warning/attention
-            ).unwrap()
+                UiText::new(&t!(
+                    "math_tool_cumulative_error",
+                    error = format!("{:.2e}", state.math_tool.cumulative_error)
+                ))
+                .size(12.0)
+                .color("#FF9800"), // Orange color for warning/attention
+            )
+            .unwrap(),
         );
     }
 
     // Precision/backend info
     let backend = get_math_backend_info();
     let precision_label = if state.math_tool.precision_bits == 0 {
-        "f64 (fast)".to_string()
+        t!("math_tool_precision_f64")
     } else {
-        format!("{}-bit (arbitrary precision)", state.math_tool.precision_bits)
+        t!(
+            "math_tool_precision_bits",
+            bits = state.math_tool.precision_bits
+        )
     };
     children.push(
         serde_json::to_value(
-            UiText::new(&format!("Backend: {backend} — Precision: {precision_label}")).size(12.0)
-        ).unwrap()
+            UiText::new(&t!(
+                "math_tool_backend",
+                backend = backend,
+                precision = precision_label
+            ))
+            .size(12.0),
+        )
+        .unwrap(),
     );
     let toggle_label = if state.math_tool.precision_bits == 0 {
-        "Use high precision (128-bit)"
+        t!("math_tool_high_precision_button")
     } else {
-        "Use standard precision (f64)"
+        t!("math_tool_standard_precision_button")
     };
-    children.push(serde_json::to_value(UiButton::new(toggle_label, "math_toggle_precision")).unwrap());
+    children
+        .push(serde_json::to_value(UiButton::new(&toggle_label, "math_toggle_precision")).unwrap());
+
+    maybe_push_back(&mut children, state);
+    serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
+}
 
+pub fn render_math_constants_screen(state: &AppState) -> Value {
+    let mut children = vec![
+        serde_json::to_value(UiText::new(&t!("math_constants_title")).size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("math_constants_description")).size(14.0)).unwrap(),
+    ];
+    for constant in PHYSICAL_CONSTANTS {
+        let details = format!("{:e} {}", constant.value, constant.unit);
+        children.push(json!({
+            "type": "Card",
+            "padding": 8,
+            "child": {
+                "type": "Column",
+                "children": [
+                    serde_json::to_value(
+                        UiText::new(&format!("{} — {}", constant.symbol, constant.name)).size(16.0)
+                    ).unwrap(),
+                    serde_json::to_value(UiText::new(&details).size(12.0)).unwrap(),
+                    serde_json::to_value(
+                        UiButton::new(&t!("math_constants_insert_button"), "math_insert_constant")
+                            .payload(json!({ "symbol": constant.symbol })),
+                    ).unwrap(),
+                ]
+            }
+        }));
+    }
     maybe_push_back(&mut children, state);
     serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
 }
@@ -111,7 +309,7 @@ pub fn handle_math_action(
             }
             match evaluate_expression(expr, state.math_tool.precision_bits) {
                 Ok(value) => {
-                    let result = format_result(value.clone());
+                    let result = format_result(value.clone(), state.math_tool.result_format);
                     
                     // Calculate floating-point error for this operation
                     // We'll use a simple approach: compare the result with a higher precision calculation
@@ -126,17 +324,13 @@ pub fn handle_math_action(
                     state.math_tool.cumulative_error += estimated_error;
                     
                     state.math_tool.error = None;
-                    state.math_tool
-                        .history
-                        .insert(0, MathHistoryEntry {
-                            expression: expr.to_string(),
-                            result,
-                            error_estimate: Some(estimated_error),
-                            precision_bits: state.math_tool.precision_bits,
-                        });
-                    if state.math_tool.history.len() > 20 {
-                        state.math_tool.history.truncate(20);
-                    }
+                    let precision_bits = state.math_tool.precision_bits;
+                    state.math_tool.push_history(MathHistoryEntry {
+                        expression: expr.to_string(),
+                        result,
+                        error_estimate: Some(estimated_error),
+                        precision_bits,
+                    });
                 }
                 Err(e) => {
                     state.math_tool.error = Some(e);
@@ -148,6 +342,39 @@ pub fn handle_math_action(
             state.math_tool.expression.clear();
             state.math_tool.error = None;
         }
+        "math_insert_constant" => match bindings.get("symbol") {
+            Some(symbol) if physical_constant(symbol).is_some() => {
+                state.math_tool.expression.push_str(symbol);
+                state.math_tool.error = None;
+            }
+            _ => state.math_tool.error = Some("unknown_constant".into()),
+        },
+        "math_recall" => {
+            let entry = bindings
+                .get("index")
+                .and_then(|v| v.parse::<usize>().ok())
+                .and_then(|idx| state.math_tool.history.get(idx));
+            match entry {
+                Some(entry) => {
+                    state.math_tool.expression = entry.expression.clone();
+                    state.math_tool.error = None;
+                }
+                None => state.math_tool.error = Some("history_entry_missing".into()),
+            }
+        }
+        "math_set_history_cap" => {
+            match bindings
+                .get("math_history_cap")
+                .and_then(|v| v.trim().parse::<usize>().ok())
+            {
+                Some(cap) if (1..=MATH_HISTORY_MAX_CAP).contains(&cap) => {
+                    state.math_tool.history_cap = cap;
+                    state.math_tool.history.truncate(cap);
+                    state.math_tool.error = None;
+                }
+                _ => state.math_tool.error = Some("invalid_history_cap".into()),
+            }
+        }
         "math_toggle_precision" => {
             // Toggle between fast f64 and a safer high-precision default
             if state.math_tool.precision_bits == 0 {
@@ -162,6 +389,31 @@ pub fn handle_math_action(
     }
 }
 
+/// Write the history and its cap to the tool-history store so it survives restarts.
+pub fn persist_math_history(state: &AppState) -> Result<(), String> {
+    let data = json!({
+        "cap": state.math_tool.history_cap,
+        "entries": state.math_tool.history,
+    });
+    save_tool_history(MATH_HISTORY_TOOL_ID, &data)
+}
+
+/// Restore a previously persisted history; a missing file leaves the state untouched.
+pub fn restore_math_history(state: &mut AppState) -> Result<(), String> {
+    let Some(data) = load_tool_history(MATH_HISTORY_TOOL_ID)? else {
+        return Ok(());
+    };
+    if let Some(cap) = data.get("cap").and_then(|v| v.as_u64()) {
+        state.math_tool.history_cap = (cap as usize).clamp(1, MATH_HISTORY_MAX_CAP);
+    }
+    let mut entries: Vec<MathHistoryEntry> =
+        serde_json::from_value(data.get("entries").cloned().unwrap_or(Value::Null))
+            .map_err(|e| format!("bad_history:{e}"))?;
+    entries.truncate(state.math_tool.history_cap);
+    state.math_tool.history = entries;
+    Ok(())
+}
+
 pub fn get_math_backend_info() -> String {
     #[cfg(feature = "precision")]
     {
@@ -175,8 +427,18 @@ pub fn get_math_backend_info() -> String {
 }
 
 pub fn evaluate_expression(expr: &str, precision_bits: u32) -> Result<Number, String> {
+    if let Some(integral) = numeric_integral(expr) {
+        return integral.map(Number::from_f64);
+    }
+    if let Some(expansion) = taylor_expansion(expr) {
+        return Err(format!("symbolic_result:{}", expansion?));
+    }
+    if let Some(arg) = extract_factor_call(expr) {
+        let n = as_integer(evaluate_expression(&arg, 0)?, "integer_required")?;
+        return Err(format!("symbolic_result:{}", factorize(n)));
+    }
     if let Some((inner, var)) = extract_integ_call(expr) {
-        let ast = parse_symbolic(&inner)?;
+        let ast = parse_symbolic_in(&inner, &var)?;
         let integral = integrate(&ast, &var);
         let simplified = simplify(&integral);
         let rendered = render_symbol(&simplified);
@@ -193,7 +455,335 @@ pub fn evaluate_expression(expr: &str, precision_bits: u32) -> Result<Number, St
 
     let tokens = tokenize(expr)?;
     let rpn = shunting_yard(&tokens)?;
-    eval_rpn(&rpn, precision_bits)
+    eval_rpn(&rpn, precision_bits, &[])
+}
+
+/// Parsed `plot(expr, x_min, x_max)` request, rendered on the worker thread.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlotRequest {
+    pub expression: String,
+    pub x_min: f64,
+    pub x_max: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlotOutput {
+    pub path: String,
+    pub base64: String,
+}
+
+/// Returns `None` when `expr` is not a plot call, so callers can fall back to evaluation.
+pub fn parse_plot_call(expr: &str) -> Option<Result<PlotRequest, String>> {
+    let trimmed = expr.trim();
+    if !trimmed.to_ascii_lowercase().starts_with("plot(") {
+        return None;
+    }
+    let args = extract_call_args(trimmed)?;
+    let parsed = match args.as_slice() {
+        [body] => Ok((body.clone(), PLOT_DEFAULT_RANGE.0, PLOT_DEFAULT_RANGE.1)),
+        [body, lo, hi] => eval_bound(lo)
+            .and_then(|lo| eval_bound(hi).map(|hi| (body.clone(), lo, hi))),
+        _ => Err("plot_usage:plot(expr, x_min, x_max)".to_string()),
+    };
+    Some(parsed.and_then(|(expression, x_min, x_max)| {
+        if expression.is_empty() {
+            return Err("expression_empty".into());
+        }
+        if x_min.partial_cmp(&x_max) != Some(std::cmp::Ordering::Less) {
+            return Err("plot_invalid_range".into());
+        }
+        Ok(PlotRequest {
+            expression,
+            x_min,
+            x_max,
+        })
+    }))
+}
+
+fn eval_bound(raw: &str) -> Result<f64, String> {
+    evaluate_expression(raw, 0).map(|n| n.to_f64())
+}
+
+/// Splits the arguments of a `name(a, b, ...)` call on top-level commas.
+fn extract_call_args(expr: &str) -> Option<Vec<String>> {
+    let open = expr.find('(')?;
+    if !expr.ends_with(')') {
+        return None;
+    }
+    let inner = expr.get(open + 1..expr.len() - 1)?;
+    let mut args = Vec::new();
+    let mut depth = 0i32;
+    let mut current = String::new();
+    for ch in inner.chars() {
+        match ch {
+            '(' => {
+                depth += 1;
+                current.push(ch);
+            }
+            ')' => {
+                depth -= 1;
+                if depth < 0 {
+                    return None;
+                }
+                current.push(ch);
+            }
+            ',' if depth == 0 => {
+                args.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(ch),
+        }
+    }
+    if depth != 0 {
+        return None;
+    }
+    args.push(current.trim().to_string());
+    Some(args)
+}
+
+/// An expression of `x` evaluated many times: as native code when it compiles through
+/// the MIR JIT, otherwise by walking its RPN for every sample.
+enum Sampler {
+    Jit(JitExpr),
+    Rpn(Vec<RpnToken>),
+}
+
+impl Sampler {
+    /// `samples` is how many evaluations are coming; few ones are not worth compiling.
+    fn new(expr: &str, samples: usize) -> Result<Self, String> {
+        let tokens = tokenize(expr)?;
+        let rpn = shunting_yard(&tokens)?;
+        if samples >= JIT_MIN_SAMPLES {
+            let compiled = rpn_to_symbol(&rpn, "x").and_then(|sym| compile_expression(&sym, "x"));
+            if let Ok(jit) = compiled {
+                return Ok(Sampler::Jit(jit));
+            }
+        }
+        Ok(Sampler::Rpn(rpn))
+    }
+
+    /// `None` where the expression is undefined or not finite.
+    fn sample(&self, x: f64) -> Option<f64> {
+        let y = match self {
+            Sampler::Jit(jit) => jit.eval(x),
+            Sampler::Rpn(rpn) => eval_rpn(rpn, 0, &[("x", x)]).ok()?.to_f64(),
+        };
+        y.is_finite().then_some(y)
+    }
+}
+
+/// `nintegrate(expr, a, b)`: ∫ expr dx from a to b by the composite Simpson rule.
+fn numeric_integral(expr: &str) -> Option<Result<f64, String>> {
+    let trimmed = expr.trim();
+    if !trimmed.to_ascii_lowercase().starts_with("nintegrate(") {
+        return None;
+    }
+    let args = extract_call_args(trimmed)?;
+    let [body, lo, hi] = args.as_slice() else {
+        return Some(Err("nintegrate_usage:nintegrate(expr, a, b)".into()));
+    };
+    Some((|| {
+        let (a, b) = (eval_bound(lo)?, eval_bound(hi)?);
+        let sampler = Sampler::new(body, NINTEGRATE_INTERVALS + 1)?;
+        let h = (b - a) / NINTEGRATE_INTERVALS as f64;
+        let mut sum = 0.0;
+        for i in 0..=NINTEGRATE_INTERVALS {
+            let y = sampler
+                .sample(a + h * i as f64)
+                .ok_or_else(|| "nintegrate_not_finite".to_string())?;
+            let weight = match i {
+                0 | NINTEGRATE_INTERVALS => 1.0,
+                _ if i % 2 == 1 => 4.0,
+                _ => 2.0,
+            };
+            sum += weight * y;
+        }
+        Ok(sum * h / 3.0)
+    })())
+}
+
+/// Samples `request.expression` across the range and writes a PNG with grid, axes, and curve.
+pub fn render_plot_png(request: &PlotRequest, output_dir: &Path) -> Result<PlotOutput, String> {
+    let plot_w = PLOT_WIDTH - 2 * PLOT_MARGIN;
+    let plot_h = PLOT_HEIGHT - 2 * PLOT_MARGIN;
+    let sampler = Sampler::new(&request.expression, plot_w as usize + 1)?;
+
+    let span = request.x_max - request.x_min;
+    let samples: Vec<Option<f64>> = (0..=plot_w)
+        .map(|i| sampler.sample(request.x_min + span * (i as f64 / plot_w as f64)))
+        .collect();
+
+    let (mut y_min, mut y_max) = samples
+        .iter()
+        .flatten()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), y| (lo.min(*y), hi.max(*y)));
+    if !y_min.is_finite() || !y_max.is_finite() {
+        return Err("plot_no_finite_points".into());
+    }
+    if (y_max - y_min).abs() < f64::EPSILON {
+        y_min -= 1.0;
+        y_max += 1.0;
+    }
+    let pad = (y_max - y_min) * 0.05;
+    y_min -= pad;
+    y_max += pad;
+
+    let to_px_x = |x: f64| PLOT_MARGIN as f64 + (x - request.x_min) / span * plot_w as f64;
+    let to_px_y = |y: f64| PLOT_MARGIN as f64 + (y_max - y) / (y_max - y_min) * plot_h as f64;
+
+    let mut img = RgbImage::from_pixel(PLOT_WIDTH, PLOT_HEIGHT, Rgb([255, 255, 255]));
+    let grid = Rgb([225, 225, 225]);
+    for i in 0..=PLOT_GRID_DIVISIONS {
+        let gx = (PLOT_MARGIN + plot_w * i / PLOT_GRID_DIVISIONS) as i64;
+        let gy = (PLOT_MARGIN + plot_h * i / PLOT_GRID_DIVISIONS) as i64;
+        draw_line(&mut img, (gx, PLOT_MARGIN as i64), (gx, (PLOT_MARGIN + plot_h) as i64), grid);
+        draw_line(&mut img, (PLOT_MARGIN as i64, gy), ((PLOT_MARGIN + plot_w) as i64, gy), grid);
+    }
+
+    let axis = Rgb([60, 60, 60]);
+    if request.x_min <= 0.0 && request.x_max >= 0.0 {
+        let ax = to_px_x(0.0).round() as i64;
+        draw_line(&mut img, (ax, PLOT_MARGIN as i64), (ax, (PLOT_MARGIN + plot_h) as i64), axis);
+    }
+    if y_min <= 0.0 && y_max >= 0.0 {
+        let ay = to_px_y(0.0).round() as i64;
+        draw_line(&mut img, (PLOT_MARGIN as i64, ay), ((PLOT_MARGIN + plot_w) as i64, ay), axis);
+    }
+
+    let curve = Rgb([25, 118, 210]);
+    let mut prev: Option<(i64, i64)> = None;
+    for (i, sample) in samples.iter().enumerate() {
+        let Some(y) = sample else {
+            prev = None;
+            continue;
+        };
+        let x = request.x_min + span * (i as f64 / plot_w as f64);
+        let point = (to_px_x(x).round() as i64, to_px_y(*y).round() as i64);
+        match prev {
+            // Skip near-vertical jumps so poles (e.g. tan) don't draw a wall across the plot.
+            Some(p) if (p.1 - point.1).abs() < plot_h as i64 => draw_line(&mut img, p, point, curve),
+            _ => put_pixel_checked(&mut img, point.0, point.1, curve),
+        }
+        prev = Some(point);
+    }
+
+    let mut buf = Vec::new();
+    PngEncoder::new(&mut buf)
+        .write_image(img.as_raw(), img.width(), img.height(), ColorType::Rgb8)
+        .map_err(|e| format!("plot_png_failed:{e}"))?;
+
+    std::fs::create_dir_all(output_dir).map_err(|e| format!("output_dir_create_failed:{e}"))?;
+    let mut tmp = Builder::new()
+        .prefix("plot_")
+        .suffix(".png")
+        .tempfile_in(output_dir)
+        .map_err(|e| format!("tempfile_failed:{e}"))?;
+    std::io::Write::write_all(&mut tmp, &buf).map_err(|e| format!("save_failed:{e}"))?;
+    let path = tmp
+        .into_temp_path()
+        .keep()
+        .map_err(|e| format!("persist_failed:{e}"))?
+        .to_string_lossy()
+        .into_owned();
+
+    Ok(PlotOutput {
+        path,
+        base64: base64::engine::general_purpose::STANDARD.encode(buf),
+    })
+}
+
+fn put_pixel_checked(img: &mut RgbImage, x: i64, y: i64, color: Rgb<u8>) {
+    if x >= 0 && y >= 0 && (x as u32) < img.width() && (y as u32) < img.height() {
+        img.put_pixel(x as u32, y as u32, color);
+    }
+}
+
+fn draw_line(img: &mut RgbImage, from: (i64, i64), to: (i64, i64), color: Rgb<u8>) {
+    // Bresenham
+    let (mut x0, mut y0) = from;
+    let (x1, y1) = to;
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+    let mut err = dx + dy;
+    loop {
+        put_pixel_checked(img, x0, y0, color);
+        if x0 == x1 && y0 == y1 {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x0 += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y0 += sy;
+        }
+    }
+}
+
+/// Expands `taylor(expr, x0, n)` into the degree-n polynomial around x0.
+fn taylor_expansion(expr: &str) -> Option<Result<String, String>> {
+    let trimmed = expr.trim();
+    if !trimmed.to_ascii_lowercase().starts_with("taylor(") {
+        return None;
+    }
+    let args = extract_call_args(trimmed)?;
+    let [body, center, order] = args.as_slice() else {
+        return Some(Err("taylor_usage:taylor(expr, x0, n)".into()));
+    };
+    Some((|| {
+        let x0 = eval_bound(center)?;
+        let order = eval_bound(order)?;
+        if order.fract() != 0.0 || !(0.0..=TAYLOR_MAX_ORDER as f64).contains(&order) {
+            return Err("taylor_invalid_order".into());
+        }
+        let mut current = parse_symbolic(body)?;
+        let mut polynomial: Option<Symbol> = None;
+        let mut factorial = 1.0;
+        for k in 0..=order as u32 {
+            if k > 0 {
+                current = simplify(&differentiate(&current, "x"));
+                factorial *= k as f64;
+            }
+            let coeff = eval_symbol(&current, "x", x0)? / factorial;
+            if !coeff.is_finite() {
+                return Err("taylor_not_finite".into());
+            }
+            if coeff.abs() < 1e-12 {
+                continue;
+            }
+            let power = match k {
+                0 => Symbol::Number(1.0),
+                1 => shifted_var("x", x0),
+                _ => Symbol::Pow(
+                    Box::new(shifted_var("x", x0)),
+                    Box::new(Symbol::Number(k as f64)),
+                ),
+            };
+            let term = |c: f64| Symbol::Mul(Box::new(Symbol::Number(c)), Box::new(power.clone()));
+            polynomial = Some(match polynomial {
+                None => term(coeff),
+                Some(acc) if coeff < 0.0 => Symbol::Sub(Box::new(acc), Box::new(term(-coeff))),
+                Some(acc) => Symbol::Add(Box::new(acc), Box::new(term(coeff))),
+            });
+        }
+        let polynomial = polynomial.unwrap_or(Symbol::Number(0.0));
+        Ok(render_symbol(&simplify(&polynomial)))
+    })())
+}
+
+fn extract_factor_call(expr: &str) -> Option<String> {
+    let trimmed = expr.trim();
+    if !trimmed.to_ascii_lowercase().starts_with("factor(") {
+        return None;
+    }
+    match extract_call_args(trimmed)?.as_slice() {
+        [arg] => Some(arg.clone()),
+        _ => None,
+    }
 }
 
 fn extract_deriv_call(expr: &str) -> Option<String> {
@@ -281,12 +871,17 @@ pub enum Symbol {
 }
 
 fn parse_symbolic(expr: &str) -> Result<Symbol, String> {
+    parse_symbolic_in(expr, "x")
+}
+
+/// Parses `expr` as a function of `var`; a constant spelled like `var` stays the variable.
+fn parse_symbolic_in(expr: &str, var: &str) -> Result<Symbol, String> {
     let tokens = tokenize(expr)?;
     let rpn = shunting_yard(&tokens)?;
-    rpn_to_symbol(&rpn)
+    rpn_to_symbol(&rpn, var)
 }
 
-fn rpn_to_symbol(tokens: &[RpnToken]) -> Result<Symbol, String> {
+fn rpn_to_symbol(tokens: &[RpnToken], var: &str) -> Result<Symbol, String> {
     let mut stack: Vec<Symbol> = Vec::new();
     for token in tokens {
         match token {
@@ -300,6 +895,12 @@ fn rpn_to_symbol(tokens: &[RpnToken]) -> Result<Symbol, String> {
                 stack.push(Symbol::Number(value));
             }
             RpnToken::Variable(name) => stack.push(Symbol::Var(name.clone())),
+            RpnToken::Constant(symbol) if symbol == var => stack.push(Symbol::Var(symbol.clone())),
+            RpnToken::Constant(symbol) => {
+                let value = physical_constant(symbol)
+                    .ok_or_else(|| format!("unknown_variable:{symbol}"))?;
+                stack.push(Symbol::Number(value));
+            }
             RpnToken::Operator(op) => {
                 let sym = match op {
                     Operator::Add => {
@@ -326,6 +927,12 @@ fn rpn_to_symbol(tokens: &[RpnToken]) -> Result<Symbol, String> {
                         let a = stack.pop().ok_or_else(|| "missing_operand".to_string())?;
                         Symbol::Neg(Box::new(a))
                     }
+                    Operator::Mod
+                    | Operator::BitAnd
+                    | Operator::BitOr
+                    | Operator::BitXor
+                    | Operator::Shl
+                    | Operator::Shr => return Err("symbolic_unsupported_operator".into()),
                 };
                 stack.push(sym);
             }
@@ -439,7 +1046,21 @@ fn differentiate(expr: &Symbol, var: &str) -> Symbol {
     }
 }
 
+const UNSUPPORTED_INTEGRAL: &str = "∫unsupported";
+const SUBSTITUTION_VAR: &str = "__u";
+
 fn integrate(expr: &Symbol, var: &str) -> Symbol {
+    let direct = integrate_direct(expr, var);
+    if !is_unsupported(&direct) {
+        return direct;
+    }
+    integrate_linear_substitution(expr, var)
+        .or_else(|| integrate_by_parts(expr, var))
+        .or_else(|| integrate_partial_fractions(expr, var))
+        .unwrap_or(direct)
+}
+
+fn integrate_direct(expr: &Symbol, var: &str) -> Symbol {
     use Symbol::*;
     match expr {
         Number(c) => Mul(Box::new(Number(*c)), Box::new(Var(var.to_string()))),
@@ -583,9 +1204,242 @@ fn integrate(expr: &Symbol, var: &str) -> Symbol {
                     return Mul(Box::new(Number(c)), Box::new(Atan(Box::new(inner_var))));
                 }
             }
+            // ∫ f/c dx = (∫ f dx)/c
+            if let Number(c) = &**den {
+                if c.abs() > f64::EPSILON {
+                    return Div(Box::new(integrate(num, var)), Box::new(Number(*c)));
+                }
+            }
             Var("∫unsupported".into())
         }
-        Neg(_) => Var("∫unsupported".into()),
+        Neg(a) => Neg(Box::new(integrate(a, var))),
+    }
+}
+
+fn is_unsupported(sym: &Symbol) -> bool {
+    use Symbol::*;
+    match sym {
+        Var(name) => name == UNSUPPORTED_INTEGRAL,
+        Number(_) => false,
+        Add(a, b) | Sub(a, b) | Mul(a, b) | Div(a, b) | Pow(a, b) => {
+            is_unsupported(a) || is_unsupported(b)
+        }
+        Neg(a) | Sin(a) | Cos(a) | Tan(a) | Exp(a) | Atan(a) | Sqrt(a) | Log(a) => {
+            is_unsupported(a)
+        }
+    }
+}
+
+fn contains_var(sym: &Symbol, var: &str) -> bool {
+    use Symbol::*;
+    match sym {
+        Var(name) => name == var,
+        Number(_) => false,
+        Add(a, b) | Sub(a, b) | Mul(a, b) | Div(a, b) | Pow(a, b) => {
+            contains_var(a, var) || contains_var(b, var)
+        }
+        Neg(a) | Sin(a) | Cos(a) | Tan(a) | Exp(a) | Atan(a) | Sqrt(a) | Log(a) => {
+            contains_var(a, var)
+        }
+    }
+}
+
+fn substitute(sym: &Symbol, var: &str, replacement: &Symbol) -> Symbol {
+    use Symbol::*;
+    let sub = |a: &Symbol| Box::new(substitute(a, var, replacement));
+    match sym {
+        Var(name) if name == var => replacement.clone(),
+        Var(_) | Number(_) => sym.clone(),
+        Add(a, b) => Add(sub(a), sub(b)),
+        Sub(a, b) => Sub(sub(a), sub(b)),
+        Mul(a, b) => Mul(sub(a), sub(b)),
+        Div(a, b) => Div(sub(a), sub(b)),
+        Pow(a, b) => Pow(sub(a), sub(b)),
+        Neg(a) => Neg(sub(a)),
+        Sin(a) => Sin(sub(a)),
+        Cos(a) => Cos(sub(a)),
+        Tan(a) => Tan(sub(a)),
+        Exp(a) => Exp(sub(a)),
+        Atan(a) => Atan(sub(a)),
+        Sqrt(a) => Sqrt(sub(a)),
+        Log(a) => Log(sub(a)),
+    }
+}
+
+/// Coefficients (lowest degree first) of `expr` as a polynomial in `var`, up to degree 2.
+fn polynomial_coeffs(expr: &Symbol, var: &str) -> Option<Vec<f64>> {
+    use Symbol::*;
+    fn add(a: &[f64], b: &[f64], sign: f64) -> Vec<f64> {
+        (0..a.len().max(b.len()))
+            .map(|i| a.get(i).copied().unwrap_or(0.0) + sign * b.get(i).copied().unwrap_or(0.0))
+            .collect()
+    }
+    fn mul(a: &[f64], b: &[f64]) -> Vec<f64> {
+        let mut out = vec![0.0; a.len() + b.len() - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                out[i + j] += x * y;
+            }
+        }
+        out
+    }
+    let mut coeffs = match expr {
+        Number(n) => vec![*n],
+        Var(name) if name == var => vec![0.0, 1.0],
+        Add(a, b) => add(&polynomial_coeffs(a, var)?, &polynomial_coeffs(b, var)?, 1.0),
+        Sub(a, b) => add(&polynomial_coeffs(a, var)?, &polynomial_coeffs(b, var)?, -1.0),
+        Neg(a) => polynomial_coeffs(a, var)?.iter().map(|c| -c).collect(),
+        Mul(a, b) => mul(&polynomial_coeffs(a, var)?, &polynomial_coeffs(b, var)?),
+        Div(a, b) => match &**b {
+            Number(c) if c.abs() > f64::EPSILON => {
+                polynomial_coeffs(a, var)?.iter().map(|x| x / c).collect()
+            }
+            _ => return None,
+        },
+        Pow(base, exp) => match &**exp {
+            Number(n) if n.fract() == 0.0 && (0.0..=2.0).contains(n) => {
+                let base = polynomial_coeffs(base, var)?;
+                (0..*n as usize).fold(vec![1.0], |acc, _| mul(&acc, &base))
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+    while coeffs.len() > 1 && coeffs.last().is_some_and(|c| c.abs() < 1e-12) {
+        coeffs.pop();
+    }
+    (coeffs.len() <= 3).then_some(coeffs)
+}
+
+/// Builds `var - root`, folding the sign of the root into the operator.
+fn shifted_var(var: &str, root: f64) -> Symbol {
+    use Symbol::*;
+    let x = Box::new(Var(var.to_string()));
+    if root == 0.0 {
+        *x
+    } else if root < 0.0 {
+        Add(x, Box::new(Number(-root)))
+    } else {
+        Sub(x, Box::new(Number(root)))
+    }
+}
+
+fn eval_symbol(sym: &Symbol, var: &str, value: f64) -> Result<f64, String> {
+    use Symbol::*;
+    let ev = |s: &Symbol| eval_symbol(s, var, value);
+    Ok(match sym {
+        Number(n) => *n,
+        Var(name) if name == var => value,
+        Var(name) => return Err(format!("unknown_variable:{name}")),
+        Add(a, b) => ev(a)? + ev(b)?,
+        Sub(a, b) => ev(a)? - ev(b)?,
+        Mul(a, b) => ev(a)? * ev(b)?,
+        Div(a, b) => ev(a)? / ev(b)?,
+        Pow(a, b) => ev(a)?.powf(ev(b)?),
+        Neg(a) => -ev(a)?,
+        Sin(a) => ev(a)?.sin(),
+        Cos(a) => ev(a)?.cos(),
+        Tan(a) => ev(a)?.tan(),
+        Exp(a) => ev(a)?.exp(),
+        Atan(a) => ev(a)?.atan(),
+        Sqrt(a) => ev(a)?.sqrt(),
+        Log(a) => ev(a)?.ln(),
+    })
+}
+
+/// ∫ f(a*x+b) dx = F(a*x+b)/a for any f with a known antiderivative F.
+fn integrate_linear_substitution(expr: &Symbol, var: &str) -> Option<Symbol> {
+    use Symbol::*;
+    let u = || Box::new(Var(SUBSTITUTION_VAR.to_string()));
+    let (inner, outer) = match expr {
+        Sin(a) => (a, Sin(u())),
+        Cos(a) => (a, Cos(u())),
+        Tan(a) => (a, Tan(u())),
+        Exp(a) => (a, Exp(u())),
+        Sqrt(a) => (a, Sqrt(u())),
+        Log(a) => (a, Log(u())),
+        Pow(base, exp) if !contains_var(exp, var) => (base, Pow(u(), exp.clone())),
+        Div(num, den) if matches!(&**num, Number(_)) => (den, Div(num.clone(), u())),
+        _ => return None,
+    };
+    let coeffs = polynomial_coeffs(inner, var)?;
+    if coeffs.len() != 2 || (coeffs[1] == 1.0 && coeffs[0] == 0.0) {
+        return None;
+    }
+    let antiderivative = integrate(&outer, SUBSTITUTION_VAR);
+    if is_unsupported(&antiderivative) {
+        return None;
+    }
+    Some(Div(
+        Box::new(substitute(&antiderivative, SUBSTITUTION_VAR, inner)),
+        Box::new(Number(coeffs[1])),
+    ))
+}
+
+/// ∫ x*g(x) dx = x*G(x) - ∫ G(x) dx, e.g. x*exp(x) or x*sin(x).
+fn integrate_by_parts(expr: &Symbol, var: &str) -> Option<Symbol> {
+    use Symbol::*;
+    let Mul(a, b) = expr else {
+        return None;
+    };
+    let is_x = |s: &Symbol| matches!(s, Var(name) if name == var);
+    let dv = match (is_x(a.as_ref()), is_x(b.as_ref())) {
+        (true, false) => b,
+        (false, true) => a,
+        _ => return None,
+    };
+    if polynomial_coeffs(dv, var).is_some() {
+        return None;
+    }
+    let v = integrate(dv, var);
+    if is_unsupported(&v) {
+        return None;
+    }
+    let v_integral = integrate(&v, var);
+    if is_unsupported(&v_integral) {
+        return None;
+    }
+    Some(Sub(
+        Box::new(Mul(Box::new(Var(var.to_string())), Box::new(v))),
+        Box::new(v_integral),
+    ))
+}
+
+/// ∫ (p*x+q)/(c2*x^2+c1*x+c0) dx when the denominator has real roots.
+fn integrate_partial_fractions(expr: &Symbol, var: &str) -> Option<Symbol> {
+    use Symbol::*;
+    let Div(num, den) = expr else {
+        return None;
+    };
+    let num = polynomial_coeffs(num, var)?;
+    let den = polynomial_coeffs(den, var)?;
+    if num.len() > 2 || den.len() != 3 {
+        return None;
+    }
+    let (p0, p1) = (num[0], num.get(1).copied().unwrap_or(0.0));
+    let (c0, c1, c2) = (den[0], den[1], den[2]);
+    let shifted = |root: f64| shifted_var(var, root);
+    let log_term = |coeff: f64, root: f64| -> Symbol {
+        Mul(Box::new(Number(coeff)), Box::new(Log(Box::new(shifted(root)))))
+    };
+    let discriminant = c1 * c1 - 4.0 * c2 * c0;
+    if discriminant > 1e-12 {
+        let sqrt_d = discriminant.sqrt();
+        let r1 = (-c1 + sqrt_d) / (2.0 * c2);
+        let r2 = (-c1 - sqrt_d) / (2.0 * c2);
+        let a = (p1 * r1 + p0) / (c2 * (r1 - r2));
+        let b = (p1 * r2 + p0) / (c2 * (r2 - r1));
+        Some(Add(Box::new(log_term(a, r1)), Box::new(log_term(b, r2))))
+    } else if discriminant.abs() <= 1e-12 {
+        // Repeated root: (p1*x+p0)/(c2*(x-r)^2) = p1/(c2*(x-r)) + (p1*r+p0)/(c2*(x-r)^2)
+        let r = -c1 / (2.0 * c2);
+        let rest = (p1 * r + p0) / c2;
+        Some(Sub(
+            Box::new(log_term(p1 / c2, r)),
+            Box::new(Div(Box::new(Number(rest)), Box::new(shifted(r)))),
+        ))
+    } else {
+        None
     }
 }
 
@@ -628,6 +1482,14 @@ fn simplify(expr: &Symbol) -> Symbol {
                 (Number(x), Number(y)) => Number(x + y),
                 (Number(0.0), other) => other.clone(),
                 (other, Number(0.0)) => other.clone(),
+                (_, Neg(inner)) => Sub(Box::new(sa.clone()), inner.clone()),
+                (_, Mul(c, rest)) if matches!(**c, Number(k) if k < 0.0) => {
+                    let Number(k) = **c else { unreachable!() };
+                    simplify(&Sub(
+                        Box::new(sa.clone()),
+                        Box::new(Mul(Box::new(Number(-k)), rest.clone())),
+                    ))
+                }
                 _ => Add(Box::new(sa), Box::new(sb)),
             }
         }
@@ -637,6 +1499,8 @@ fn simplify(expr: &Symbol) -> Symbol {
             match (&sa, &sb) {
                 (Number(x), Number(y)) => Number(x - y),
                 (other, Number(0.0)) => other.clone(),
+                (Number(0.0), _) => simplify(&Neg(Box::new(sb.clone()))),
+                (_, Neg(inner)) => Add(Box::new(sa.clone()), inner.clone()),
                 _ => Sub(Box::new(sa), Box::new(sb)),
             }
         }
@@ -648,6 +1512,7 @@ fn simplify(expr: &Symbol) -> Symbol {
                 (Number(0.0), _) | (_, Number(0.0)) => Number(0.0),
                 (Number(1.0), other) => other.clone(),
                 (other, Number(1.0)) => other.clone(),
+                (_, Neg(inner)) => Neg(Box::new(Mul(Box::new(sa.clone()), inner.clone()))),
                 _ => Mul(Box::new(sa), Box::new(sb)),
             }
         }
@@ -657,6 +1522,10 @@ fn simplify(expr: &Symbol) -> Symbol {
             match (&sa, &sb) {
                 (Number(x), Number(y)) if *y != 0.0 => Number(x / y),
                 (other, Number(1.0)) => other.clone(),
+                (Div(inner, c1), Number(c2)) if matches!(**c1, Number(_)) => {
+                    let Number(c1) = **c1 else { unreachable!() };
+                    Div(inner.clone(), Box::new(Number(c1 * c2)))
+                }
                 _ => Div(Box::new(sa), Box::new(sb)),
             }
         }
@@ -752,17 +1621,28 @@ enum Operator {
     Sub,
     Mul,
     Div,
+    Mod,
     Pow,
     Neg,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
 }
 
 impl Operator {
+    // C-like ordering: | < xor < & < shifts < additive < multiplicative.
     fn precedence(self) -> u8 {
         match self {
-            Operator::Add | Operator::Sub => 1,
-            Operator::Mul | Operator::Div => 2,
-            Operator::Pow => 3,
-            Operator::Neg => 4,
+            Operator::BitOr => 1,
+            Operator::BitXor => 2,
+            Operator::BitAnd => 3,
+            Operator::Shl | Operator::Shr => 4,
+            Operator::Add | Operator::Sub => 5,
+            Operator::Mul | Operator::Div | Operator::Mod => 6,
+            Operator::Pow => 7,
+            Operator::Neg => 8,
         }
     }
 
@@ -786,16 +1666,20 @@ impl Operator {
 enum Token {
     NumberStr(String),
     Variable(String),
+    /// A physical constant symbol; a bound variable of the same name takes precedence.
+    Constant(String),
     Operator(Operator),
     Function(String),
     LeftParen,
     RightParen,
+    Comma,
 }
 
 #[derive(Debug, Clone)]
 enum RpnToken {
     NumberStr(String),
     Variable(String),
+    Constant(String),
     Operator(Operator),
     Function(String),
 }
@@ -811,20 +1695,36 @@ fn tokenize(expr: &str) -> Result<Vec<Token>, String> {
                 chars.next();
             }
             c if c.is_ascii_digit() || c == '.' => {
-                let number_str = parse_number(&mut chars)?;
+                let number_str = match radix_prefix(&chars) {
+                    Some(radix) => parse_radix_number(&mut chars, radix)?,
+                    None => parse_number(&mut chars)?,
+                };
                 tokens.push(Token::NumberStr(number_str));
                 prev_is_value = true;
             }
             c if c.is_ascii_alphabetic() => {
                 let ident = parse_identifier(&mut chars);
                 let lowered = ident.to_lowercase();
-                if lowered == "pi" {
+                if physical_constant(&ident).is_some() {
+                    tokens.push(Token::Constant(ident));
+                    prev_is_value = true;
+                } else if lowered == "pi" {
                     tokens.push(Token::NumberStr("pi".to_string()));
                     prev_is_value = true;
                 } else if lowered == "e" {
                     tokens.push(Token::NumberStr("e".to_string()));
                     prev_is_value = true;
-                } else if matches!(lowered.as_str(), "sin" | "cos" | "tan" | "exp" | "atan" | "sqrt" | "log" | "deriv") {
+                } else if lowered == "xor" || lowered == "mod" {
+                    let op = if lowered == "xor" {
+                        Operator::BitXor
+                    } else {
+                        Operator::Mod
+                    };
+                    tokens.push(Token::Operator(op));
+                    prev_is_value = false;
+                } else if matches!(lowered.as_str(), "sin" | "cos" | "tan" | "exp" | "atan" | "sqrt" | "log" | "deriv")
+                    || is_integer_function(&lowered)
+                {
                     tokens.push(Token::Function(lowered));
                     prev_is_value = false;
                 } else {
@@ -864,11 +1764,36 @@ fn tokenize(expr: &str) -> Result<Vec<Token>, String> {
                 tokens.push(Token::Operator(Operator::Pow));
                 prev_is_value = false;
             }
+            '&' => {
+                chars.next();
+                tokens.push(Token::Operator(Operator::BitAnd));
+                prev_is_value = false;
+            }
+            '|' => {
+                chars.next();
+                tokens.push(Token::Operator(Operator::BitOr));
+                prev_is_value = false;
+            }
+            '<' | '>' => {
+                chars.next();
+                if chars.peek() != Some(&ch) {
+                    return Err(format!("unexpected_char:{ch}"));
+                }
+                chars.next();
+                let op = if ch == '<' { Operator::Shl } else { Operator::Shr };
+                tokens.push(Token::Operator(op));
+                prev_is_value = false;
+            }
             '(' => {
                 chars.next();
                 tokens.push(Token::LeftParen);
                 prev_is_value = false;
             }
+            ',' => {
+                chars.next();
+                tokens.push(Token::Comma);
+                prev_is_value = false;
+            }
             ')' => {
                 chars.next();
                 tokens.push(Token::RightParen);
@@ -914,10 +1839,53 @@ fn parse_number(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Result<
     Ok(buf)
 }
 
+/// Detects a `0x`/`0b`/`0o` prefix without consuming input.
+fn radix_prefix(chars: &std::iter::Peekable<std::str::Chars<'_>>) -> Option<u32> {
+    let mut look = chars.clone();
+    if look.next() != Some('0') {
+        return None;
+    }
+    match look.next() {
+        Some('x') | Some('X') => Some(16),
+        Some('b') | Some('B') => Some(2),
+        Some('o') | Some('O') => Some(8),
+        _ => None,
+    }
+}
+
+/// Consumes a prefixed integer literal and returns it as a decimal string.
+fn parse_radix_number(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    radix: u32,
+) -> Result<String, String> {
+    chars.next();
+    chars.next();
+    let mut digits = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_digit(radix) {
+            digits.push(c);
+            chars.next();
+        } else if c == '_' {
+            chars.next();
+        } else {
+            break;
+        }
+    }
+    if let Some(&c) = chars.peek() {
+        if c.is_ascii_alphanumeric() {
+            return Err(format!("invalid_digit_for_base_{radix}:{c}"));
+        }
+    }
+    i64::from_str_radix(&digits, radix)
+        .map(|v| v.to_string())
+        .map_err(|_| format!("invalid_number:{digits}"))
+}
+
 fn parse_identifier(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
     let mut buf = String::new();
     while let Some(&c) = chars.peek() {
-        if c.is_ascii_alphabetic() {
+        // Digits and underscores only continue an identifier (k_B, g0), never start one.
+        if c.is_ascii_alphabetic() || (!buf.is_empty() && (c.is_ascii_digit() || c == '_')) {
             buf.push(c);
             chars.next();
         } else {
@@ -935,6 +1903,7 @@ fn shunting_yard(tokens: &[Token]) -> Result<Vec<RpnToken>, String> {
         match token {
             Token::NumberStr(n) => output.push(RpnToken::NumberStr(n.clone())),
             Token::Variable(name) => output.push(RpnToken::Variable(name.clone())),
+            Token::Constant(symbol) => output.push(RpnToken::Constant(symbol.clone())),
             Token::Function(name) => stack.push(Token::Function(name.clone())),
             Token::Operator(op) => {
                 while let Some(top) = stack.last() {
@@ -961,6 +1930,21 @@ fn shunting_yard(tokens: &[Token]) -> Result<Vec<RpnToken>, String> {
                 stack.push(Token::Operator(*op));
             }
             Token::LeftParen => stack.push(Token::LeftParen),
+            Token::Comma => {
+                // Flush the current argument; the enclosing "(" stays for the next one.
+                while let Some(top) = stack.last() {
+                    match top {
+                        Token::LeftParen => break,
+                        Token::Operator(o) => output.push(RpnToken::Operator(*o)),
+                        Token::Function(name) => output.push(RpnToken::Function(name.clone())),
+                        _ => {}
+                    }
+                    stack.pop();
+                }
+                if stack.is_empty() {
+                    return Err("misplaced_comma".into());
+                }
+            }
             Token::RightParen => {
                 while let Some(top) = stack.pop() {
                     if matches!(top, Token::LeftParen) {
@@ -984,6 +1968,7 @@ fn shunting_yard(tokens: &[Token]) -> Result<Vec<RpnToken>, String> {
     while let Some(top) = stack.pop() {
         match top {
             Token::LeftParen | Token::RightParen => return Err("mismatched_parentheses".into()),
+            Token::Comma => return Err("misplaced_comma".into()),
             Token::Operator(o) => output.push(RpnToken::Operator(o)),
             Token::Function(name) => output.push(RpnToken::Function(name)),
             _ => return Err("invalid_expression".into()),
@@ -993,7 +1978,28 @@ fn shunting_yard(tokens: &[Token]) -> Result<Vec<RpnToken>, String> {
     Ok(output)
 }
 
-fn eval_rpn(tokens: &[RpnToken], precision_bits: u32) -> Result<Number, String> {
+/// Wraps an f64 at the requested precision; 0 keeps plain f64.
+fn number_with_precision(value: f64, precision_bits: u32) -> Result<Number, String> {
+    if precision_bits == 0 {
+        return Ok(Number::from_f64(value));
+    }
+    #[cfg(feature = "precision")]
+    {
+        use rug::Float;
+        let value = Float::with_val(precision_bits, value);
+        Ok(Number::from_rug_float(value))
+    }
+    #[cfg(not(feature = "precision"))]
+    {
+        Err("precision feature required for arbitrary precision".into())
+    }
+}
+
+fn eval_rpn(
+    tokens: &[RpnToken],
+    precision_bits: u32,
+    vars: &[(&str, f64)],
+) -> Result<Number, String> {
     let mut stack: Vec<Number> = Vec::new();
     for token in tokens {
         match token {
@@ -1031,7 +2037,20 @@ fn eval_rpn(tokens: &[RpnToken], precision_bits: u32) -> Result<Number, String>
                 stack.push(value);
             }
             RpnToken::Variable(name) => {
-                return Err(format!("unknown_variable:{name}"));
+                let Some((_, value)) = vars.iter().find(|(var, _)| *var == name.as_str()) else {
+                    return Err(format!("unknown_variable:{name}"));
+                };
+                stack.push(Number::from_f64(*value));
+            }
+            RpnToken::Constant(symbol) => {
+                match vars.iter().find(|(var, _)| *var == symbol.as_str()) {
+                    Some((_, value)) => stack.push(Number::from_f64(*value)),
+                    None => {
+                        let value = physical_constant(symbol)
+                            .ok_or_else(|| format!("unknown_variable:{symbol}"))?;
+                        stack.push(number_with_precision(value, precision_bits)?);
+                    }
+                }
             }
             RpnToken::Operator(op) => {
                 let arity = op.arity();
@@ -1066,12 +2085,60 @@ fn eval_rpn(tokens: &[RpnToken], precision_bits: u32) -> Result<Number, String>
                         let a = stack.pop().unwrap();
                         Number::from_f64(-a.to_f64())
                     }
+                    Operator::Mod => {
+                        let (b, a) = pop_two(&mut stack);
+                        let divisor = b.to_f64();
+                        if divisor.abs() < f64::EPSILON {
+                            return Err("division_by_zero".into());
+                        }
+                        Number::from_f64(a.to_f64().rem_euclid(divisor))
+                    }
+                    Operator::BitAnd
+                    | Operator::BitOr
+                    | Operator::BitXor
+                    | Operator::Shl
+                    | Operator::Shr => {
+                        let (b, a) = pop_two(&mut stack);
+                        let (a, b) = (
+                            as_integer(a, "bitwise_requires_integers")?,
+                            as_integer(b, "bitwise_requires_integers")?,
+                        );
+                        let value = match op {
+                            Operator::BitAnd => a & b,
+                            Operator::BitOr => a | b,
+                            Operator::BitXor => a ^ b,
+                            _ => {
+                                let shift = u32::try_from(b)
+                                    .ok()
+                                    .filter(|s| *s < 64)
+                                    .ok_or_else(|| "shift_out_of_range".to_string())?;
+                                if matches!(op, Operator::Shl) {
+                                    a.checked_shl(shift).ok_or("shift_out_of_range")?
+                                } else {
+                                    a >> shift
+                                }
+                            }
+                        };
+                        Number::from_f64(value as f64)
+                    }
                 };
                 if !result.clone().to_f64().is_finite() {
                     return Err("non_finite_result".into());
                 }
                 stack.push(result);
             }
+            RpnToken::Function(name) if is_integer_function(name) => {
+                let arity = function_arity(name);
+                if stack.len() < arity {
+                    return Err("missing_operand".into());
+                }
+                let args = stack
+                    .split_off(stack.len() - arity)
+                    .into_iter()
+                    .map(|n| as_integer(n, "integer_required"))
+                    .collect::<Result<Vec<_>, _>>()?;
+                stack.push(Number::from_f64(eval_integer_function(name, &args)?));
+            }
             RpnToken::Function(name) => {
                 let Some(arg) = stack.pop() else {
                     return Err("missing_operand".into());
@@ -1164,15 +2231,206 @@ fn eval_rpn(tokens: &[RpnToken], precision_bits: u32) -> Result<Number, String>
     }
 }
 
+/// Bitwise operators and integer functions only accept exactly representable integers.
+fn as_integer(value: Number, error: &str) -> Result<i64, String> {
+    let v = value.to_f64();
+    if v.fract() != 0.0 || v.abs() > MAX_EXACT_INTEGER as f64 {
+        return Err(error.into());
+    }
+    Ok(v as i64)
+}
+
+fn is_integer_function(name: &str) -> bool {
+    matches!(name, "isprime" | "gcd" | "lcm" | "ncr" | "npr")
+}
+
+fn function_arity(name: &str) -> usize {
+    match name {
+        "gcd" | "lcm" | "ncr" | "npr" => 2,
+        _ => 1,
+    }
+}
+
+fn eval_integer_function(name: &str, args: &[i64]) -> Result<f64, String> {
+    let value = match (name, args) {
+        ("isprime", [n]) => u64::try_from(*n).map_or(0, |n| is_prime(n) as u64),
+        ("gcd", [a, b]) => gcd(a.unsigned_abs(), b.unsigned_abs()),
+        ("lcm", [a, b]) => {
+            let (a, b) = (a.unsigned_abs(), b.unsigned_abs());
+            if a == 0 || b == 0 {
+                0
+            } else {
+                (a / gcd(a, b))
+                    .checked_mul(b)
+                    .ok_or_else(|| "integer_overflow".to_string())?
+            }
+        }
+        ("ncr", [n, k]) | ("npr", [n, k]) => {
+            let (n, k) = match (u64::try_from(*n), u64::try_from(*k)) {
+                (Ok(n), Ok(k)) => (n, k),
+                _ => return Err("negative_argument".into()),
+            };
+            if k > n {
+                0
+            } else if name == "ncr" {
+                // Multiplicative form keeps every intermediate value an exact integer.
+                let k = k.min(n - k);
+                (0..k).try_fold(1u64, |acc, i| {
+                    acc.checked_mul(n - i).map(|v| v / (i + 1))
+                })
+                .ok_or_else(|| "integer_overflow".to_string())?
+            } else {
+                (0..k)
+                    .try_fold(1u64, |acc, i| acc.checked_mul(n - i))
+                    .ok_or_else(|| "integer_overflow".to_string())?
+            }
+        }
+        _ => return Err(format!("wrong_argument_count:{name}")),
+    };
+    if value > MAX_EXACT_INTEGER {
+        return Err("integer_overflow".into());
+    }
+    Ok(value as f64)
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Deterministic Miller-Rabin for all 64-bit inputs.
+fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    for p in WITNESSES {
+        if n % p == 0 {
+            return n == p;
+        }
+    }
+    let mul_mod = |a: u64, b: u64| ((a as u128 * b as u128) % n as u128) as u64;
+    let pow_mod = |mut base: u64, mut exp: u64| {
+        let mut result = 1u64;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = mul_mod(result, base);
+            }
+            base = mul_mod(base, base);
+            exp >>= 1;
+        }
+        result
+    };
+    let (mut d, mut r) = (n - 1, 0);
+    while d % 2 == 0 {
+        d /= 2;
+        r += 1;
+    }
+    WITNESSES.iter().all(|&a| {
+        let mut x = pow_mod(a, d);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..r {
+            x = mul_mod(x, x);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Prime factorization rendered as `2^2*3*5`, using trial division up to sqrt(n).
+fn factorize(n: i64) -> String {
+    if n.unsigned_abs() < 2 {
+        return n.to_string();
+    }
+    let mut rest = n.unsigned_abs();
+    let mut factors: Vec<(u64, u32)> = Vec::new();
+    let mut divisor = 2u64;
+    let mut rest_is_prime = is_prime(rest);
+    while !rest_is_prime && divisor * divisor <= rest {
+        let mut count = 0;
+        while rest % divisor == 0 {
+            rest /= divisor;
+            count += 1;
+        }
+        if count > 0 {
+            factors.push((divisor, count));
+            rest_is_prime = is_prime(rest);
+        }
+        divisor += if divisor == 2 { 1 } else { 2 };
+    }
+    if rest > 1 {
+        factors.push((rest, 1));
+    }
+    let rendered = factors
+        .iter()
+        .map(|(p, e)| if *e == 1 { p.to_string() } else { format!("{p}^{e}") })
+        .collect::<Vec<_>>()
+        .join("*");
+    if n < 0 {
+        format!("-{rendered}")
+    } else {
+        rendered
+    }
+}
+
 fn pop_two(stack: &mut Vec<Number>) -> (Number, Number) {
     let b = stack.pop().unwrap();
     let a = stack.pop().unwrap();
     (b, a)
 }
 
-fn format_result(value: Number) -> String {
+fn format_result(value: Number, format: MathResultFormat) -> String {
     let f64_value = value.to_f64();
-    let mut out = format!("{:.10}", f64_value);
+    let as_int = (f64_value.fract() == 0.0 && f64_value.abs() <= MAX_EXACT_INTEGER as f64)
+        .then_some(f64_value as i64);
+    match (format, as_int) {
+        (MathResultFormat::Hex, Some(v)) => {
+            let sign = if v < 0 { "-" } else { "" };
+            return format!("{sign}0x{:X}", v.unsigned_abs());
+        }
+        (MathResultFormat::Binary, Some(v)) => {
+            let sign = if v < 0 { "-" } else { "" };
+            return format!("{sign}0b{:b}", v.unsigned_abs());
+        }
+        (MathResultFormat::Fraction, _) => {
+            if let Some((num, den)) = to_fraction(f64_value) {
+                return if den == 1 {
+                    num.to_string()
+                } else {
+                    format!("{num}/{den}")
+                };
+            }
+        }
+        (MathResultFormat::Scientific, _) if f64_value != 0.0 => {
+            let exponent = f64_value.abs().log10().floor() as i32;
+            return format_exponent(f64_value / 10f64.powi(exponent), exponent);
+        }
+        (MathResultFormat::Engineering, _) if f64_value != 0.0 => {
+            let mut exponent = (f64_value.abs().log10().floor() as i32).div_euclid(3) * 3;
+            let mut mantissa = f64_value / 10f64.powi(exponent);
+            // Rounding can carry the mantissa to 1000 (999.99999999999 -> 1000); renormalize.
+            if trim_decimal(mantissa.abs()) == "1000" {
+                mantissa /= 1000.0;
+                exponent += 3;
+            }
+            return match si_prefix(exponent) {
+                Some(prefix) => format!("{} {prefix}", trim_decimal(mantissa)).trim_end().to_string(),
+                None => format_exponent(mantissa, exponent),
+            };
+        }
+        _ => {}
+    }
+    trim_decimal(f64_value)
+}
+
+fn trim_decimal(value: f64) -> String {
+    let mut out = format!("{:.10}", value);
     while out.contains('.') && out.ends_with('0') {
         out.pop();
     }
@@ -1182,6 +2440,56 @@ fn format_result(value: Number) -> String {
     out
 }
 
+fn format_exponent(mantissa: f64, exponent: i32) -> String {
+    let mantissa = trim_decimal(mantissa);
+    // Rounding can carry the mantissa to 10 (9.99999999999 -> 10); renormalize.
+    if mantissa.trim_start_matches('-') == "10" {
+        let sign = if mantissa.starts_with('-') { "-" } else { "" };
+        return format!("{sign}1e{}", exponent + 1);
+    }
+    format!("{mantissa}e{exponent}")
+}
+
+fn si_prefix(exponent: i32) -> Option<&'static str> {
+    const PREFIXES: [&str; 17] = [
+        "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+    ];
+    usize::try_from(exponent / 3 + 8)
+        .ok()
+        .filter(|_| exponent % 3 == 0)
+        .and_then(|idx| PREFIXES.get(idx).copied())
+}
+
+/// Best rational approximation via continued fractions, bounded to keep fractions readable.
+fn to_fraction(value: f64) -> Option<(i64, i64)> {
+    const MAX_DENOMINATOR: i64 = 100_000;
+    if !value.is_finite() || value.abs() > MAX_EXACT_INTEGER as f64 {
+        return None;
+    }
+    let (mut h_prev, mut h) = (0i64, 1i64);
+    let (mut k_prev, mut k) = (1i64, 0i64);
+    let mut x = value;
+    for _ in 0..64 {
+        let a = x.floor();
+        let a_int = a as i64;
+        let h_next = a_int.checked_mul(h)?.checked_add(h_prev)?;
+        let k_next = a_int.checked_mul(k)?.checked_add(k_prev)?;
+        if k_next > MAX_DENOMINATOR {
+            break;
+        }
+        (h_prev, h, k_prev, k) = (h, h_next, k, k_next);
+        if (h as f64 / k as f64 - value).abs() <= value.abs().max(1.0) * 1e-12 {
+            return Some((h, k));
+        }
+        let frac = x - a;
+        if frac.abs() < f64::EPSILON {
+            break;
+        }
+        x = 1.0 / frac;
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1310,6 +2618,72 @@ mod tests {
         assert_eq!(render_symbol(&integ), "2*log(x)");
     }
 
+    #[test]
+    fn integrate_linear_substitution() {
+        let cases = [
+            ("sin(2*x+3)", "-cos(2*x+3)/2"),
+            ("exp(2*x+3)", "exp(2*x+3)/2"),
+            ("(2*x+3)^3", "(2*x+3)^4/8"),
+            ("1/(2*x+3)", "log(2*x+3)/2"),
+        ];
+        for (input, expected) in cases {
+            let ast = parse_symbolic(input).unwrap();
+            let integ = simplify(&integrate(&ast, "x"));
+            assert_eq!(render_symbol(&integ), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn integrate_by_parts_for_x_times_exp_and_sin() {
+        let ast = parse_symbolic("x*exp(x)").unwrap();
+        let integ = simplify(&integrate(&ast, "x"));
+        assert_eq!(render_symbol(&integ), "x*exp(x)-exp(x)");
+
+        let ast = parse_symbolic("x*sin(x)").unwrap();
+        let integ = simplify(&integrate(&ast, "x"));
+        assert_eq!(render_symbol(&integ), "-(x*cos(x))+sin(x)");
+    }
+
+    #[test]
+    fn integrate_partial_fractions_with_real_roots() {
+        let ast = parse_symbolic("1/(x^2-1)").unwrap();
+        let integ = simplify(&integrate(&ast, "x"));
+        assert_eq!(render_symbol(&integ), "0.5*log(x-1)-0.5*log(x+1)");
+
+        let ast = parse_symbolic("(x+1)/((x-2)*(x-3))").unwrap();
+        let integ = simplify(&integrate(&ast, "x"));
+        assert_eq!(render_symbol(&integ), "4*log(x-3)-3*log(x-2)");
+    }
+
+    #[test]
+    fn integrate_still_flags_unsupported_forms() {
+        let ast = parse_symbolic("atan(x)").unwrap();
+        assert!(is_unsupported(&integrate(&ast, "x")));
+    }
+
+    #[test]
+    fn taylor_expands_around_center() {
+        let res = evaluate_expression("taylor(exp(x), 0, 3)", 0).unwrap_err();
+        assert_eq!(res, "symbolic_result:1+x+0.5*x^2+0.1666666667*x^3");
+        let res = evaluate_expression("taylor(log(x), 1, 3)", 0).unwrap_err();
+        assert_eq!(res, "symbolic_result:x-1-0.5*(x-1)^2+0.3333333333*(x-1)^3");
+    }
+
+    #[test]
+    fn taylor_rejects_bad_arguments() {
+        assert_eq!(
+            evaluate_expression("taylor(sin(x), 0, 2.5)", 0).unwrap_err(),
+            "taylor_invalid_order"
+        );
+        assert_eq!(
+            evaluate_expression("taylor(log(x), 0, 2)", 0).unwrap_err(),
+            "taylor_not_finite"
+        );
+        assert!(evaluate_expression("taylor(sin(x))", 0)
+            .unwrap_err()
+            .starts_with("taylor_usage"));
+    }
+
     #[test]
     fn integrate_dispatches_in_eval() {
         let res = evaluate_expression("integ(x^3)", 0).unwrap_err();
@@ -1317,6 +2691,159 @@ mod tests {
         assert!(res.contains("x^4/4"));
     }
 
+    #[test]
+    fn parses_base_n_literals() {
+        assert!(approx_eq(evaluate_expression("0xFF + 0b101 + 0o17", 0).unwrap(), 275.0));
+        assert!(evaluate_expression("0b102", 0).unwrap_err().contains("invalid_digit"));
+    }
+
+    #[test]
+    fn bitwise_operators_follow_c_precedence() {
+        assert!(approx_eq(evaluate_expression("0b1100 & 0b1010", 0).unwrap(), 8.0));
+        assert!(approx_eq(evaluate_expression("0b1100 | 0b1010", 0).unwrap(), 14.0));
+        assert!(approx_eq(evaluate_expression("12 xor 10", 0).unwrap(), 6.0));
+        assert!(approx_eq(evaluate_expression("1 << 4 | 1", 0).unwrap(), 17.0));
+        assert!(approx_eq(evaluate_expression("256 >> 2 + 2", 0).unwrap(), 16.0));
+        assert!(approx_eq(evaluate_expression("-7 mod 3", 0).unwrap(), 2.0));
+        assert_eq!(
+            evaluate_expression("1.5 & 1", 0).unwrap_err(),
+            "bitwise_requires_integers"
+        );
+    }
+
+    #[test]
+    fn integer_functions_take_multiple_arguments() {
+        assert!(approx_eq(evaluate_expression("gcd(12, 18)", 0).unwrap(), 6.0));
+        assert!(approx_eq(evaluate_expression("lcm(gcd(4, 6), 3)", 0).unwrap(), 6.0));
+        assert!(approx_eq(evaluate_expression("ncr(5, 2) + npr(5, 2)", 0).unwrap(), 30.0));
+        assert!(approx_eq(evaluate_expression("isprime(97)", 0).unwrap(), 1.0));
+        assert!(approx_eq(evaluate_expression("isprime(91)", 0).unwrap(), 0.0));
+        assert_eq!(evaluate_expression("gcd(1.5, 3)", 0).unwrap_err(), "integer_required");
+        assert_eq!(evaluate_expression("ncr(60, 30)", 0).unwrap_err(), "integer_overflow");
+    }
+
+    #[test]
+    fn factor_renders_prime_powers() {
+        assert_eq!(
+            evaluate_expression("factor(360)", 0).unwrap_err(),
+            "symbolic_result:2^3*3^2*5"
+        );
+        assert_eq!(
+            evaluate_expression("factor(600851475143)", 0).unwrap_err(),
+            "symbolic_result:71*839*1471*6857"
+        );
+    }
+
+    #[test]
+    fn physical_constants_resolve_case_sensitively() {
+        assert!(approx_eq(evaluate_expression("c", 0).unwrap(), 299_792_458.0));
+        assert!(approx_eq(evaluate_expression("2*g0", 0).unwrap(), 19.6133));
+        let r = evaluate_expression("k_B*N_A", 0).unwrap().to_f64();
+        assert!((r - 8.314_462_618).abs() < 1e-8);
+        assert!(evaluate_expression("g", 0).unwrap_err().starts_with("unknown_variable"));
+    }
+
+    #[test]
+    fn bound_variables_shadow_physical_constants() {
+        let ast = parse_symbolic_in("c^2", "c").unwrap();
+        assert_eq!(render_symbol(&simplify(&integrate(&ast, "c"))), "c^3/3");
+        let rpn = shunting_yard(&tokenize("2*c").unwrap()).unwrap();
+        assert!(approx_eq(eval_rpn(&rpn, 0, &[("c", 3.0)]).unwrap(), 6.0));
+        assert!(approx_eq(eval_rpn(&rpn, 0, &[]).unwrap(), 599_584_916.0));
+    }
+
+    #[test]
+    fn formats_integers_in_hex_and_binary() {
+        assert_eq!(format_result(Number::from_f64(255.0), MathResultFormat::Hex), "0xFF");
+        assert_eq!(
+            format_result(Number::from_f64(255.0), MathResultFormat::Binary),
+            "0b11111111"
+        );
+        assert_eq!(format_result(Number::from_f64(-10.0), MathResultFormat::Hex), "-0xA");
+        assert_eq!(format_result(Number::from_f64(0.5), MathResultFormat::Hex), "0.5");
+    }
+
+    #[test]
+    fn formats_fraction_scientific_and_engineering() {
+        let n = Number::from_f64;
+        assert_eq!(format_result(n(0.75), MathResultFormat::Fraction), "3/4");
+        assert_eq!(format_result(n(0.1 + 0.2), MathResultFormat::Fraction), "3/10");
+        assert_eq!(format_result(n(-1.0 / 3.0), MathResultFormat::Fraction), "-1/3");
+        assert_eq!(format_result(n(PI), MathResultFormat::Fraction), "3.1415926536");
+        assert_eq!(format_result(n(123456.0), MathResultFormat::Scientific), "1.23456e5");
+        assert_eq!(format_result(n(-0.000123), MathResultFormat::Scientific), "-1.23e-4");
+        assert_eq!(format_result(n(123456.0), MathResultFormat::Engineering), "123.456 k");
+        assert_eq!(format_result(n(2.5e-7), MathResultFormat::Engineering), "250 n");
+        assert_eq!(format_result(n(1e30), MathResultFormat::Engineering), "1e30");
+        let carried = n(999_999.999_999_99);
+        assert_eq!(format_result(carried, MathResultFormat::Engineering), "1 M");
+    }
+
+    #[test]
+    fn plot_call_parses_bounds() {
+        let req = parse_plot_call("plot(sin(x), -pi, pi)").unwrap().unwrap();
+        assert_eq!(req.expression, "sin(x)");
+        assert!((req.x_min + PI).abs() < 1e-12);
+        assert!((req.x_max - PI).abs() < 1e-12);
+        assert!(parse_plot_call("sin(x)").is_none());
+        assert_eq!(
+            parse_plot_call("plot(x, 2, 1)").unwrap().unwrap_err(),
+            "plot_invalid_range"
+        );
+    }
+
+    #[test]
+    fn plot_writes_png_to_output_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let req = parse_plot_call("plot(x^2, -2, 2)").unwrap().unwrap();
+        let out = render_plot_png(&req, dir.path()).expect("plot ok");
+        let img = image::open(&out.path).unwrap();
+        assert_eq!((img.width(), img.height()), (PLOT_WIDTH, PLOT_HEIGHT));
+        assert!(!out.base64.is_empty());
+    }
+
+    #[test]
+    fn plot_without_finite_points_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let req = parse_plot_call("plot(log(x), -2, -1)").unwrap().unwrap();
+        let err = render_plot_png(&req, dir.path()).unwrap_err();
+        assert_eq!(err, "plot_no_finite_points");
+    }
+
+    #[test]
+    fn jit_and_rpn_samplers_agree() {
+        let expr = "sin(x)^2 + sqrt(x) / (x - 1) - 3*x";
+        let jit = Sampler::new(expr, JIT_MIN_SAMPLES).unwrap();
+        let rpn = Sampler::new(expr, 1).unwrap();
+        assert!(matches!(jit, Sampler::Jit(_)));
+        assert!(matches!(rpn, Sampler::Rpn(_)));
+        for x in [0.0, 0.25, 2.0, 7.5] {
+            assert_eq!(jit.sample(x), rpn.sample(x));
+        }
+        assert_eq!(jit.sample(1.0), None);
+        assert_eq!(jit.sample(-1.0), None);
+
+        // `mod` has no native form, so the interpreter stays in charge.
+        let fallback = Sampler::new("x mod 3", JIT_MIN_SAMPLES).unwrap();
+        assert!(matches!(fallback, Sampler::Rpn(_)));
+        assert_eq!(fallback.sample(7.0), Some(1.0));
+    }
+
+    #[test]
+    fn nintegrate_uses_simpson_rule() {
+        let integral = |expr: &str| evaluate_expression(expr, 0).unwrap();
+        assert!(approx_eq(integral("nintegrate(x^2, 0, 3)"), 9.0));
+        assert!(approx_eq(integral("nintegrate(sin(x), 0, pi)"), 2.0));
+        assert!(approx_eq(integral("nintegrate(x, 2, 0)"), -2.0));
+        assert_eq!(
+            evaluate_expression("nintegrate(log(x), -1, 1)", 0).unwrap_err(),
+            "nintegrate_not_finite"
+        );
+        assert!(evaluate_expression("nintegrate(x, 1)", 0)
+            .unwrap_err()
+            .starts_with("nintegrate_usage"));
+    }
+
     #[test]
     fn history_renders_as_virtual_list() {
         let mut state = AppState::new();
@@ -1345,6 +2872,31 @@ mod tests {
         assert!(has_virtual, "expected history to use VirtualList");
     }
 
+    #[test]
+    fn format_rows_mark_only_the_selected_format() {
+        let mut state = AppState::new();
+        state.math_tool.result_format = MathResultFormat::Engineering;
+        let ui = render_math_tool_screen(&state);
+        let buttons: Vec<&Value> = ui["children"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|c| c["type"] == "Row")
+            .flat_map(|row| row["children"].as_array().unwrap())
+            .filter(|c| {
+                c["action"]
+                    .as_str()
+                    .is_some_and(|a| a.starts_with("math_format_"))
+            })
+            .collect();
+        assert_eq!(buttons.len(), 6);
+        for button in buttons {
+            let selected = button.get("content_description").and_then(Value::as_str);
+            let expected = (button["action"] == "math_format_eng").then_some("selected");
+            assert_eq!(selected, expected, "{button}");
+        }
+    }
+
     #[test]
     fn virtual_list_serializes_estimated_height() {
         let items = vec![serde_json::to_value(UiText::new("a")).unwrap()];
@@ -1370,6 +2922,68 @@ mod tests {
         assert!(state.math_tool.expression.is_empty());
     }
 
+    #[test]
+    fn history_recall_and_cap() {
+        let mut state = AppState::new();
+        for expr in ["1+1", "2+2", "3+3"] {
+            handle_math_action(
+                &mut state,
+                "math_calculate",
+                &HashMap::from([("math_expr".into(), expr.into())]),
+            );
+        }
+        handle_math_action(
+            &mut state,
+            "math_recall",
+            &HashMap::from([("index".into(), "2".into())]),
+        );
+        assert_eq!(state.math_tool.expression, "1+1");
+
+        handle_math_action(
+            &mut state,
+            "math_set_history_cap",
+            &HashMap::from([("math_history_cap".into(), "2".into())]),
+        );
+        assert_eq!(state.math_tool.history_cap, 2);
+        assert_eq!(state.math_tool.history.len(), 2);
+        assert_eq!(state.math_tool.history[0].expression, "3+3");
+
+        handle_math_action(
+            &mut state,
+            "math_set_history_cap",
+            &HashMap::from([("math_history_cap".into(), "0".into())]),
+        );
+        assert_eq!(state.math_tool.error.as_deref(), Some("invalid_history_cap"));
+    }
+
+    #[test]
+    fn history_persists_across_states() {
+        use crate::features::storage::test_env_lock;
+
+        let _guard = test_env_lock().lock().expect("lock env");
+        let root_dir = tempfile::tempdir().unwrap();
+        let cache_dir = root_dir.path().join("cache");
+        std::fs::create_dir(&cache_dir).unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", &cache_dir);
+
+        let mut state = AppState::new();
+        state.math_tool.history_cap = 5;
+        handle_math_action(
+            &mut state,
+            "math_calculate",
+            &HashMap::from([("math_expr".into(), "6*7".into())]),
+        );
+        persist_math_history(&state).expect("persist");
+
+        let mut restored = AppState::new();
+        restore_math_history(&mut restored).expect("restore");
+        assert_eq!(restored.math_tool.history_cap, 5);
+        assert_eq!(restored.math_tool.history.len(), 1);
+        assert_eq!(restored.math_tool.history[0].result, "42");
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
+
     #[cfg(feature = "precision")]
     #[test]
     fn test_precision_evaluation() {
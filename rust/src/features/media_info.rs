@@ -0,0 +1,698 @@
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Seek, SeekFrom};
+
+/// Bytes read from the start of the file for header-based formats (MKV, MP3, FLAC).
+const MEDIA_PROBE_BYTES: u64 = 1 << 20;
+/// Largest `moov` box loaded into memory; bigger ones are skipped.
+const MP4_MAX_MOOV_BYTES: u64 = 16 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackKind {
+    Video,
+    Audio,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MediaTrack {
+    pub kind: TrackKind,
+    pub codec: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channels: Option<u32>,
+}
+
+impl MediaTrack {
+    fn new(kind: TrackKind, codec: impl Into<String>) -> Self {
+        Self {
+            kind,
+            codec: codec.into(),
+            width: None,
+            height: None,
+            sample_rate: None,
+            channels: None,
+        }
+    }
+
+    /// One-line description, e.g. `avc1 1920×1080` or `mp4a 44100 Hz, 2 ch`.
+    pub fn describe(&self) -> String {
+        let mut out = self.codec.clone();
+        if let (Some(w), Some(h)) = (self.width, self.height) {
+            out.push_str(&format!(" {w}×{h}"));
+        }
+        let mut audio = Vec::new();
+        if let Some(rate) = self.sample_rate {
+            audio.push(format!("{rate} Hz"));
+        }
+        if let Some(channels) = self.channels {
+            audio.push(format!("{channels} ch"));
+        }
+        if !audio.is_empty() {
+            out.push(' ');
+            out.push_str(&audio.join(", "));
+        }
+        out
+    }
+}
+
+/// Container and stream details shown by the File Inspector for audio/video files.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MediaInfo {
+    pub container: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_s: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitrate_kbps: Option<u32>,
+    #[serde(default)]
+    pub tracks: Vec<MediaTrack>,
+}
+
+impl MediaInfo {
+    fn new(container: impl Into<String>) -> Self {
+        Self {
+            container: container.into(),
+            duration_s: None,
+            bitrate_kbps: None,
+            tracks: Vec::new(),
+        }
+    }
+}
+
+/// Formats seconds as `m:ss` or `h:mm:ss`.
+pub fn format_duration(seconds: f64) -> String {
+    let total = seconds.max(0.0).round() as u64;
+    let (h, m, s) = (total / 3600, total / 60 % 60, total % 60);
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}")
+    } else {
+        format!("{m}:{s:02}")
+    }
+}
+
+/// Probes MP4/MOV, Matroska/WebM, MP3 and FLAC files. `size` is the file length,
+/// used for the overall bitrate. Returns `None` for other formats or unreadable headers.
+pub fn probe_media<R: Read + Seek>(reader: &mut R, size: u64) -> Option<MediaInfo> {
+    reader.seek(SeekFrom::Start(0)).ok()?;
+    let mut head = Vec::new();
+    reader
+        .by_ref()
+        .take(MEDIA_PROBE_BYTES)
+        .read_to_end(&mut head)
+        .ok()?;
+
+    let mut info = if head.get(4..8) == Some(b"ftyp") {
+        probe_mp4(reader, &head, size)?
+    } else if head.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+        probe_mkv(&head)?
+    } else {
+        let start = id3v2_len(&head);
+        let body = head.get(start..)?;
+        if body.starts_with(b"fLaC") {
+            probe_flac(body)?
+        } else {
+            probe_mp3(body, size.saturating_sub(start as u64))?
+        }
+    };
+
+    info.duration_s = info.duration_s.filter(|d| d.is_finite() && *d > 0.0);
+    if info.bitrate_kbps.is_none() {
+        info.bitrate_kbps = info
+            .duration_s
+            .map(|d| (size as f64 * 8.0 / d / 1000.0).round() as u32);
+    }
+    Some(info)
+}
+
+fn be_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn be_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn be_u64(bytes: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(bytes.get(at..at + 8)?.try_into().ok()?))
+}
+
+fn fourcc(bytes: &[u8], at: usize) -> Option<String> {
+    let raw = bytes.get(at..at + 4)?;
+    Some(String::from_utf8_lossy(raw).trim().to_string())
+}
+
+// --- MP4 / QuickTime -------------------------------------------------------
+
+/// Iterates `(type, body)` pairs of the ISO BMFF boxes contained in `data`.
+fn mp4_boxes(data: &[u8]) -> impl Iterator<Item = ([u8; 4], &[u8])> {
+    let mut pos = 0usize;
+    std::iter::from_fn(move || {
+        let size = be_u32(data, pos)? as usize;
+        let kind: [u8; 4] = data.get(pos + 4..pos + 8)?.try_into().ok()?;
+        let (header, size) = match size {
+            0 => (8, data.len() - pos),
+            1 => (16, usize::try_from(be_u64(data, pos + 8)?).ok()?),
+            n => (8, n),
+        };
+        if size < header {
+            return None;
+        }
+        let body = data.get(pos + header..pos + size)?;
+        pos += size;
+        Some((kind, body))
+    })
+}
+
+fn mp4_child<'a>(data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    mp4_boxes(data)
+        .find(|(k, _)| k == kind)
+        .map(|(_, body)| body)
+}
+
+/// Walks the top-level boxes with seeks so a `moov` stored after `mdat` is still found.
+fn probe_mp4<R: Read + Seek>(reader: &mut R, head: &[u8], size: u64) -> Option<MediaInfo> {
+    let brand = fourcc(head, 8)?;
+    let container = match brand.as_str() {
+        "qt" => "QuickTime".to_string(),
+        "M4A" | "M4B" => "MPEG-4 audio".to_string(),
+        _ => format!("MP4 ({brand})"),
+    };
+
+    let mut pos = 0u64;
+    let moov = loop {
+        if pos + 8 > size {
+            return None;
+        }
+        reader.seek(SeekFrom::Start(pos)).ok()?;
+        let mut header = [0u8; 16];
+        reader.read_exact(&mut header[..8]).ok()?;
+        let mut box_size = u64::from(be_u32(&header, 0)?);
+        let mut header_len = 8;
+        if box_size == 1 {
+            reader.read_exact(&mut header[8..]).ok()?;
+            box_size = be_u64(&header, 8)?;
+            header_len = 16;
+        } else if box_size == 0 {
+            box_size = size - pos;
+        }
+        if box_size < header_len {
+            return None;
+        }
+        if &header[4..8] == b"moov" {
+            let body_len = box_size - header_len;
+            if body_len > MP4_MAX_MOOV_BYTES {
+                return None;
+            }
+            let mut body = vec![0u8; body_len as usize];
+            reader.read_exact(&mut body).ok()?;
+            break body;
+        }
+        pos += box_size;
+    };
+
+    let mut info = MediaInfo::new(container);
+    if let Some(mvhd) = mp4_child(&moov, b"mvhd") {
+        let (timescale, duration) = if mvhd.first() == Some(&1) {
+            (be_u32(mvhd, 20)?, be_u64(mvhd, 24)?)
+        } else {
+            (be_u32(mvhd, 12)?, u64::from(be_u32(mvhd, 16)?))
+        };
+        if timescale > 0 {
+            info.duration_s = Some(duration as f64 / f64::from(timescale));
+        }
+    }
+    for (_, trak) in mp4_boxes(&moov).filter(|(k, _)| k == b"trak") {
+        if let Some(track) = mp4_track(trak) {
+            info.tracks.push(track);
+        }
+    }
+    Some(info)
+}
+
+fn mp4_track(trak: &[u8]) -> Option<MediaTrack> {
+    let mdia = mp4_child(trak, b"mdia")?;
+    let kind = match mp4_child(mdia, b"hdlr").and_then(|h| h.get(8..12))? {
+        b"vide" => TrackKind::Video,
+        b"soun" => TrackKind::Audio,
+        _ => return None,
+    };
+    let stsd = mp4_child(mdia, b"minf")
+        .and_then(|minf| mp4_child(minf, b"stbl"))
+        .and_then(|stbl| mp4_child(stbl, b"stsd"));
+    // The first sample entry starts after the full-box header and entry count.
+    let entry = stsd.and_then(|s| s.get(8..));
+    let codec = entry
+        .and_then(|e| fourcc(e, 4))
+        .unwrap_or_else(|| "?".into());
+    let mut track = MediaTrack::new(kind, codec);
+    match kind {
+        TrackKind::Video => {
+            // tkhd stores the presentation size as 16.16 fixed point at its end.
+            if let Some(tkhd) = mp4_child(trak, b"tkhd") {
+                let at = if tkhd.first() == Some(&1) { 88 } else { 76 };
+                track.width = be_u32(tkhd, at).map(|w| w >> 16).filter(|w| *w > 0);
+                track.height = be_u32(tkhd, at + 4).map(|h| h >> 16).filter(|h| *h > 0);
+            }
+            if track.width.is_none() {
+                track.width = entry.and_then(|e| be_u16(e, 32)).map(u32::from);
+                track.height = entry.and_then(|e| be_u16(e, 34)).map(u32::from);
+            }
+        }
+        TrackKind::Audio => {
+            track.channels = entry.and_then(|e| be_u16(e, 24)).map(u32::from);
+            track.sample_rate = entry.and_then(|e| be_u32(e, 32)).map(|r| r >> 16);
+        }
+    }
+    Some(track)
+}
+
+// --- Matroska / WebM -------------------------------------------------------
+
+const EBML_DOC_TYPE: u32 = 0x4282;
+const MKV_SEGMENT: u32 = 0x1853_8067;
+const MKV_INFO: u32 = 0x1549_A966;
+const MKV_TIMECODE_SCALE: u32 = 0x2A_D7B1;
+const MKV_DURATION: u32 = 0x4489;
+const MKV_TRACKS: u32 = 0x1654_AE6B;
+const MKV_TRACK_ENTRY: u32 = 0xAE;
+const MKV_TRACK_TYPE: u32 = 0x83;
+const MKV_CODEC_ID: u32 = 0x86;
+const MKV_VIDEO: u32 = 0xE0;
+const MKV_PIXEL_WIDTH: u32 = 0xB0;
+const MKV_PIXEL_HEIGHT: u32 = 0xBA;
+const MKV_AUDIO: u32 = 0xE1;
+const MKV_SAMPLING_FREQUENCY: u32 = 0xB5;
+const MKV_CHANNELS: u32 = 0x9F;
+const MKV_CLUSTER: u32 = 0x1F43_B675;
+
+/// Reads an EBML variable-length integer, returning `(value, length)`.
+/// IDs keep their length marker; sizes have it stripped.
+fn ebml_vint(data: &[u8], pos: usize, keep_marker: bool) -> Option<(u64, usize)> {
+    let first = *data.get(pos)?;
+    let len = first.leading_zeros() as usize + 1;
+    if len > 8 {
+        return None;
+    }
+    let mut value = if keep_marker {
+        u64::from(first)
+    } else {
+        u64::from(first) & (0xFF >> len)
+    };
+    for byte in data.get(pos + 1..pos + len)? {
+        value = (value << 8) | u64::from(*byte);
+    }
+    Some((value, len))
+}
+
+/// Iterates `(id, body)` pairs. An unknown-size element (all size bits set) or one
+/// running past the probed bytes yields the remaining data and ends the iteration.
+fn ebml_elements(data: &[u8]) -> impl Iterator<Item = (u32, &[u8])> {
+    let mut pos = 0usize;
+    std::iter::from_fn(move || {
+        let (id, id_len) = ebml_vint(data, pos, true)?;
+        let (size, size_len) = ebml_vint(data, pos + id_len, false)?;
+        let start = pos + id_len + size_len;
+        let unknown = size == (1u64 << (7 * size_len)) - 1;
+        let end = match usize::try_from(size) {
+            Ok(size) if !unknown && start + size <= data.len() => start + size,
+            _ => data.len(),
+        };
+        let body = data.get(start..end)?;
+        pos = end;
+        Some((id as u32, body))
+    })
+}
+
+fn ebml_uint(body: &[u8]) -> Option<u64> {
+    (body.len() <= 8).then(|| body.iter().fold(0u64, |acc, b| (acc << 8) | u64::from(*b)))
+}
+
+fn ebml_float(body: &[u8]) -> Option<f64> {
+    match body.len() {
+        4 => Some(f64::from(f32::from_be_bytes(body.try_into().ok()?))),
+        8 => Some(f64::from_be_bytes(body.try_into().ok()?)),
+        _ => None,
+    }
+}
+
+fn probe_mkv(head: &[u8]) -> Option<MediaInfo> {
+    let mut elements = ebml_elements(head);
+    let (_, ebml_header) = elements.next()?;
+    let doc_type = ebml_elements(ebml_header)
+        .find(|(id, _)| *id == EBML_DOC_TYPE)
+        .map(|(_, body)| {
+            String::from_utf8_lossy(body)
+                .trim_end_matches('\0')
+                .to_string()
+        });
+    let container = match doc_type.as_deref() {
+        Some("webm") => "WebM",
+        _ => "Matroska",
+    };
+    let (_, segment) = elements.find(|(id, _)| *id == MKV_SEGMENT)?;
+
+    let mut info = MediaInfo::new(container);
+    for (id, body) in ebml_elements(segment) {
+        match id {
+            MKV_INFO => {
+                let mut scale = 1_000_000u64;
+                let mut duration = None;
+                for (id, value) in ebml_elements(body) {
+                    match id {
+                        MKV_TIMECODE_SCALE => scale = ebml_uint(value).unwrap_or(scale),
+                        MKV_DURATION => duration = ebml_float(value),
+                        _ => {}
+                    }
+                }
+                info.duration_s = duration.map(|d| d * scale as f64 / 1e9);
+            }
+            MKV_TRACKS => {
+                for (_, entry) in ebml_elements(body).filter(|(id, _)| *id == MKV_TRACK_ENTRY) {
+                    if let Some(track) = mkv_track(entry) {
+                        info.tracks.push(track);
+                    }
+                }
+            }
+            // Tracks and Info precede the first cluster.
+            MKV_CLUSTER => break,
+            _ => {}
+        }
+    }
+    Some(info)
+}
+
+fn mkv_track(entry: &[u8]) -> Option<MediaTrack> {
+    let mut kind = None;
+    let mut codec = String::from("?");
+    let mut video = None;
+    let mut audio = None;
+    for (id, body) in ebml_elements(entry) {
+        match id {
+            MKV_TRACK_TYPE => {
+                kind = match ebml_uint(body) {
+                    Some(1) => Some(TrackKind::Video),
+                    Some(2) => Some(TrackKind::Audio),
+                    _ => None,
+                }
+            }
+            MKV_CODEC_ID => {
+                codec = String::from_utf8_lossy(body)
+                    .trim_end_matches('\0')
+                    .to_string()
+            }
+            MKV_VIDEO => video = Some(body),
+            MKV_AUDIO => audio = Some(body),
+            _ => {}
+        }
+    }
+    let mut track = MediaTrack::new(kind?, codec);
+    for (id, body) in video.into_iter().flat_map(ebml_elements) {
+        match id {
+            MKV_PIXEL_WIDTH => track.width = ebml_uint(body).map(|v| v as u32),
+            MKV_PIXEL_HEIGHT => track.height = ebml_uint(body).map(|v| v as u32),
+            _ => {}
+        }
+    }
+    // Matroska defaults: 8000 Hz, one channel.
+    if track.kind == TrackKind::Audio {
+        track.sample_rate = Some(8000);
+        track.channels = Some(1);
+    }
+    for (id, body) in audio.into_iter().flat_map(ebml_elements) {
+        match id {
+            MKV_SAMPLING_FREQUENCY => {
+                track.sample_rate = ebml_float(body).map(|v| v.round() as u32)
+            }
+            MKV_CHANNELS => track.channels = ebml_uint(body).map(|v| v as u32),
+            _ => {}
+        }
+    }
+    Some(track)
+}
+
+// --- MP3 / FLAC ------------------------------------------------------------
+
+/// Length of a leading ID3v2 tag (header, body and optional footer), or 0.
+fn id3v2_len(data: &[u8]) -> usize {
+    if !data.starts_with(b"ID3") || data.len() < 10 {
+        return 0;
+    }
+    let size = data[6..10]
+        .iter()
+        .fold(0usize, |acc, b| (acc << 7) | usize::from(b & 0x7F));
+    let footer = if data[5] & 0x10 != 0 { 10 } else { 0 };
+    10 + size + footer
+}
+
+const MP3_BITRATES_V1: [u32; 15] = [
+    0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+];
+const MP3_BITRATES_V2: [u32; 15] = [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+
+/// Parses the first MPEG-1/2/2.5 Layer III frame header at the start of `data`.
+/// The duration comes from a Xing/Info header when present, otherwise from the
+/// frame bitrate (constant bitrate assumption).
+fn probe_mp3(data: &[u8], audio_bytes: u64) -> Option<MediaInfo> {
+    let header = be_u32(data, 0)?;
+    if header >> 21 != 0x7FF {
+        return None;
+    }
+    let version = (header >> 19) & 0b11;
+    let layer = (header >> 17) & 0b11;
+    let bitrate_idx = ((header >> 12) & 0xF) as usize;
+    let rate_idx = ((header >> 10) & 0b11) as usize;
+    let mono = (header >> 6) & 0b11 == 0b11;
+    // Version 01 is reserved; layer 01 is Layer III.
+    if version == 0b01 || layer != 0b01 || bitrate_idx == 0xF || rate_idx == 3 {
+        return None;
+    }
+    let mpeg1 = version == 0b11;
+    let base_rate = [44_100, 48_000, 32_000][rate_idx];
+    let sample_rate = match version {
+        0b11 => base_rate,
+        0b10 => base_rate / 2,
+        _ => base_rate / 4,
+    };
+    let frame_bitrate = if mpeg1 {
+        MP3_BITRATES_V1[bitrate_idx]
+    } else {
+        MP3_BITRATES_V2[bitrate_idx]
+    };
+    let samples_per_frame = if mpeg1 { 1152 } else { 576 };
+
+    let side_info = match (mpeg1, mono) {
+        (true, false) => 32,
+        (true, true) => 17,
+        (false, false) => 17,
+        (false, true) => 9,
+    };
+    let xing = 4 + side_info;
+    let xing_frames = match data.get(xing..xing + 4) {
+        Some(b"Xing") | Some(b"Info") => be_u32(data, xing + 4)
+            .filter(|flags| flags & 1 != 0)
+            .and_then(|_| be_u32(data, xing + 8)),
+        _ => None,
+    };
+
+    let mut info = MediaInfo::new("MP3");
+    let mut track = MediaTrack::new(TrackKind::Audio, "mp3");
+    track.sample_rate = Some(sample_rate);
+    track.channels = Some(if mono { 1 } else { 2 });
+    info.tracks.push(track);
+    if let Some(frames) = xing_frames {
+        info.duration_s =
+            Some(f64::from(frames) * f64::from(samples_per_frame) / f64::from(sample_rate));
+    } else if frame_bitrate > 0 {
+        info.bitrate_kbps = Some(frame_bitrate);
+        info.duration_s = Some(audio_bytes as f64 * 8.0 / (f64::from(frame_bitrate) * 1000.0));
+    }
+    Some(info)
+}
+
+/// Reads the mandatory STREAMINFO block that follows the `fLaC` marker.
+fn probe_flac(data: &[u8]) -> Option<MediaInfo> {
+    // Block header: last-block flag and type (0 = STREAMINFO), then a 24-bit length.
+    if data.get(4).map(|b| b & 0x7F) != Some(0) {
+        return None;
+    }
+    let packed = be_u64(data, 8 + 10)?;
+    let sample_rate = (packed >> 44) as u32;
+    let channels = ((packed >> 41) & 0b111) as u32 + 1;
+    let total_samples = packed & 0xF_FFFF_FFFF;
+
+    let mut info = MediaInfo::new("FLAC");
+    let mut track = MediaTrack::new(TrackKind::Audio, "flac");
+    track.sample_rate = Some(sample_rate).filter(|r| *r > 0);
+    track.channels = Some(channels);
+    info.tracks.push(track);
+    if sample_rate > 0 && total_samples > 0 {
+        info.duration_s = Some(total_samples as f64 / f64::from(sample_rate));
+    }
+    Some(info)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn mp4_box(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut out = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        out.extend_from_slice(kind);
+        out.extend_from_slice(body);
+        out
+    }
+
+    fn mp4_trak(handler: &[u8; 4], entry: &[u8], size: (u32, u32)) -> Vec<u8> {
+        let mut tkhd = vec![0u8; 84];
+        tkhd[76..80].copy_from_slice(&(size.0 << 16).to_be_bytes());
+        tkhd[80..84].copy_from_slice(&(size.1 << 16).to_be_bytes());
+        let mut hdlr = vec![0u8; 24];
+        hdlr[8..12].copy_from_slice(handler);
+        let mut stsd = vec![0, 0, 0, 0, 0, 0, 0, 1];
+        stsd.extend_from_slice(entry);
+        let stbl = mp4_box(b"stbl", &mp4_box(b"stsd", &stsd));
+        let minf = mp4_box(b"minf", &stbl);
+        let mdia = mp4_box(b"mdia", &[mp4_box(b"hdlr", &hdlr), minf].concat());
+        mp4_box(b"trak", &[mp4_box(b"tkhd", &tkhd), mdia].concat())
+    }
+
+    /// An MP4 with `mdat` before `moov`: 10 s, 1280×720 avc1 video and stereo 48 kHz AAC.
+    pub(crate) fn sample_mp4() -> Vec<u8> {
+        let mut mvhd = vec![0u8; 100];
+        mvhd[12..16].copy_from_slice(&1000u32.to_be_bytes());
+        mvhd[16..20].copy_from_slice(&10_000u32.to_be_bytes());
+        let video = mp4_trak(b"vide", &mp4_box(b"avc1", &[0u8; 78]), (1280, 720));
+        let mut audio_entry = vec![0u8; 28];
+        audio_entry[16..18].copy_from_slice(&2u16.to_be_bytes());
+        audio_entry[24..28].copy_from_slice(&(48_000u32 << 16).to_be_bytes());
+        let audio = mp4_trak(b"soun", &mp4_box(b"mp4a", &audio_entry), (0, 0));
+        let moov = mp4_box(b"moov", &[mp4_box(b"mvhd", &mvhd), video, audio].concat());
+        [
+            mp4_box(b"ftyp", b"isom\0\0\x02\0isomavc1"),
+            mp4_box(b"mdat", &[0u8; 4096]),
+            moov,
+        ]
+        .concat()
+    }
+
+    fn probe(data: &[u8]) -> Option<MediaInfo> {
+        probe_media(&mut Cursor::new(data), data.len() as u64)
+    }
+
+    #[test]
+    fn probes_mp4_tracks_after_mdat() {
+        let data = sample_mp4();
+        let info = probe(&data).unwrap();
+        assert_eq!(info.container, "MP4 (isom)");
+        assert_eq!(info.duration_s, Some(10.0));
+        assert_eq!(info.tracks.len(), 2);
+        assert_eq!(info.tracks[0].describe(), "avc1 1280×720");
+        assert_eq!(info.tracks[1].describe(), "mp4a 48000 Hz, 2 ch");
+        assert_eq!(
+            info.bitrate_kbps,
+            Some((data.len() as f64 * 8.0 / 10_000.0).round() as u32)
+        );
+    }
+
+    fn ebml(id: &[u8], body: &[u8]) -> Vec<u8> {
+        let mut out = id.to_vec();
+        out.extend_from_slice(&(0x0100_0000_0000_0000u64 | body.len() as u64).to_be_bytes());
+        out.extend_from_slice(body);
+        out
+    }
+
+    #[test]
+    fn probes_matroska_info_and_tracks() {
+        let header = ebml(&[0x1A, 0x45, 0xDF, 0xA3], &ebml(&[0x42, 0x82], b"webm"));
+        let info = ebml(
+            &[0x15, 0x49, 0xA9, 0x66],
+            &[
+                ebml(&[0x2A, 0xD7, 0xB1], &1_000_000u32.to_be_bytes()),
+                ebml(&[0x44, 0x89], &90_500.0f64.to_be_bytes()),
+            ]
+            .concat(),
+        );
+        let video = ebml(
+            &[0xAE],
+            &[
+                ebml(&[0x83], &[1]),
+                ebml(&[0x86], b"V_VP9"),
+                ebml(
+                    &[0xE0],
+                    &[ebml(&[0xB0], &[0x07, 0x80]), ebml(&[0xBA], &[0x04, 0x38])].concat(),
+                ),
+            ]
+            .concat(),
+        );
+        let audio = ebml(
+            &[0xAE],
+            &[
+                ebml(&[0x83], &[2]),
+                ebml(&[0x86], b"A_OPUS"),
+                ebml(
+                    &[0xE1],
+                    &[
+                        ebml(&[0xB5], &48_000.0f32.to_be_bytes()),
+                        ebml(&[0x9F], &[2]),
+                    ]
+                    .concat(),
+                ),
+            ]
+            .concat(),
+        );
+        // Unknown-size segment, as written by live encoders.
+        let mut segment = vec![
+            0x18, 0x53, 0x80, 0x67, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+        ];
+        segment.extend(info);
+        segment.extend(ebml(&[0x16, 0x54, 0xAE, 0x6B], &[video, audio].concat()));
+        segment.extend(ebml(&[0x1F, 0x43, 0xB6, 0x75], &[0u8; 64]));
+
+        let info = probe(&[header, segment].concat()).unwrap();
+        assert_eq!(info.container, "WebM");
+        assert_eq!(format_duration(info.duration_s.unwrap()), "1:31");
+        assert_eq!(info.tracks[0].describe(), "V_VP9 1920×1080");
+        assert_eq!(info.tracks[1].describe(), "A_OPUS 48000 Hz, 2 ch");
+    }
+
+    #[test]
+    fn probes_cbr_mp3_after_id3_tag() {
+        let mut data = b"ID3\x04\0\0\0\0\0\x05title".to_vec();
+        // MPEG-1 Layer III, 128 kbps, 44.1 kHz, joint stereo.
+        for _ in 0..100 {
+            data.extend_from_slice(&[0xFF, 0xFB, 0x90, 0x40]);
+            data.extend_from_slice(&[0u8; 413]);
+        }
+        let info = probe(&data).unwrap();
+        assert_eq!(info.container, "MP3");
+        assert_eq!(info.bitrate_kbps, Some(128));
+        assert!((info.duration_s.unwrap() - 41_700.0 * 8.0 / 128_000.0).abs() < 1e-9);
+        assert_eq!(info.tracks[0].describe(), "mp3 44100 Hz, 2 ch");
+    }
+
+    #[test]
+    fn probes_flac_streaminfo() {
+        let mut data = b"fLaC\x80\0\0\x22".to_vec();
+        data.extend_from_slice(&[0u8; 10]);
+        // 44100 Hz, 2 channels, 16 bits, 441000 samples.
+        let packed: u64 = (44_100 << 44) | (1 << 41) | (15 << 36) | 441_000;
+        data.extend_from_slice(&packed.to_be_bytes());
+        data.extend_from_slice(&[0u8; 16]);
+        let info = probe(&data).unwrap();
+        assert_eq!(info.container, "FLAC");
+        assert_eq!(info.duration_s, Some(10.0));
+        assert_eq!(info.tracks[0].describe(), "flac 44100 Hz, 2 ch");
+    }
+
+    #[test]
+    fn ignores_other_files_and_formats_durations() {
+        assert!(probe(b"plain text, not media").is_none());
+        assert_eq!(format_duration(59.6), "1:00");
+        assert_eq!(format_duration(3725.0), "1:02:05");
+    }
+}
@@ -0,0 +1,614 @@
+use crate::error::error_text;
+use crate::features::exif_info::{orientation_label, read_exif, ExifSummary};
+use crate::state::AppState;
+use crate::ui::{
+    format_bytes, maybe_push_back, Button as UiButton, Column as UiColumn, Section as UiSection,
+    Text as UiText,
+};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, File};
+use std::io::{Cursor, Read};
+use std::os::unix::io::{FromRawFd, RawFd};
+use std::path::Path;
+
+const MAX_INPUT_BYTES: u64 = 64 * 1024 * 1024;
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+const XMP_PREFIX: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const XMP_EXTENSION_PREFIX: &[u8] = b"http://ns.adobe.com/xmp/extension/\0";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataKind {
+    Exif,
+    Xmp,
+    Iptc,
+    Comment,
+    /// PNG `tEXt`/`zTXt`/`iTXt` chunks other than XMP.
+    Text,
+    /// PNG `tIME`.
+    Timestamp,
+    /// Camera/app specific JPEG APPn segments (MPF, FlashPix, Ducky, ...).
+    Vendor,
+    /// Bytes after the end of the image, e.g. appended previews or gain maps.
+    Trailing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemovedBlock {
+    pub kind: MetadataKind,
+    pub count: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StripReport {
+    pub removed: Vec<RemovedBlock>,
+    /// What the removed EXIF block revealed (camera, date, GPS).
+    pub exif: Option<ExifSummary>,
+    /// Orientation re-written into a minimal EXIF block so the photo stays upright (JPEG only).
+    pub kept_orientation: Option<u32>,
+}
+
+impl StripReport {
+    fn add(&mut self, kind: MetadataKind, bytes: usize) {
+        match self.removed.iter_mut().find(|b| b.kind == kind) {
+            Some(block) => {
+                block.count += 1;
+                block.bytes += bytes as u64;
+            }
+            None => self.removed.push(RemovedBlock {
+                kind,
+                count: 1,
+                bytes: bytes as u64,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StripOutcome {
+    pub name: String,
+    pub output_path: Option<String>,
+    pub size_before: u64,
+    pub size_after: u64,
+    pub report: StripReport,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetadataStripState {
+    pub outcomes: Vec<StripOutcome>,
+    pub error: Option<String>,
+}
+
+impl MetadataStripState {
+    pub const fn new() -> Self {
+        Self {
+            outcomes: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.outcomes.clear();
+        self.error = None;
+    }
+}
+
+/// One picked photo: an fd from the picker or a plain path.
+#[derive(Debug, Clone)]
+pub struct StripInput {
+    pub name: String,
+    pub fd: Option<i32>,
+    pub path: Option<String>,
+}
+
+/// Removes EXIF, XMP, IPTC, comments and text chunks from a JPEG, PNG or WebP file without
+/// re-encoding the pixels.
+pub fn strip_metadata(data: &[u8]) -> Result<(Vec<u8>, StripReport), String> {
+    let mut report = StripReport {
+        exif: read_exif(&mut Cursor::new(data)),
+        ..StripReport::default()
+    };
+    let cleaned = if data.starts_with(&[0xFF, 0xD8]) {
+        let orientation = report
+            .exif
+            .as_ref()
+            .and_then(|e| e.orientation)
+            .filter(|o| (2..=8).contains(o));
+        let cleaned = strip_jpeg(data, &mut report, orientation)?;
+        report.kept_orientation = orientation;
+        cleaned
+    } else if data.starts_with(PNG_SIGNATURE) {
+        strip_png(data, &mut report)?
+    } else if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        strip_webp(data, &mut report)?
+    } else {
+        return Err("metadata_strip_unsupported_format".into());
+    };
+    Ok((cleaned, report))
+}
+
+fn jpeg_segment_kind(marker: u8, payload: &[u8]) -> Option<MetadataKind> {
+    match marker {
+        0xE1 if payload.starts_with(b"Exif\0") => Some(MetadataKind::Exif),
+        0xE1 if payload.starts_with(XMP_PREFIX) || payload.starts_with(XMP_EXTENSION_PREFIX) => {
+            Some(MetadataKind::Xmp)
+        }
+        0xE1 => Some(MetadataKind::Vendor),
+        // ICC profiles stay: dropping them shifts colours.
+        0xE2 if payload.starts_with(b"ICC_PROFILE\0") => None,
+        0xE2..=0xEC | 0xEF => Some(MetadataKind::Vendor),
+        0xED => Some(MetadataKind::Iptc),
+        0xFE => Some(MetadataKind::Comment),
+        _ => None,
+    }
+}
+
+/// Big-endian TIFF block holding only the orientation tag.
+fn orientation_app1(orientation: u32) -> Vec<u8> {
+    let mut payload = b"Exif\0\0MM\0\x2a\0\0\0\x08\0\x01\x01\x12\0\x03\0\0\0\x01".to_vec();
+    payload.extend_from_slice(&(orientation as u16).to_be_bytes());
+    payload.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+    let mut segment = vec![0xFF, 0xE1];
+    segment.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+    segment.extend_from_slice(&payload);
+    segment
+}
+
+fn strip_jpeg(
+    data: &[u8],
+    report: &mut StripReport,
+    orientation: Option<u32>,
+) -> Result<Vec<u8>, String> {
+    let truncated = || "metadata_strip_truncated".to_string();
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&data[..2]);
+    // New EXIF goes right after SOI, or after a leading JFIF APP0 which must come first.
+    let mut insert_at = 2;
+    let mut pos = 2;
+    loop {
+        if data.get(pos) != Some(&0xFF) {
+            return Err("metadata_strip_corrupt_jpeg".into());
+        }
+        while data.get(pos) == Some(&0xFF) {
+            pos += 1;
+        }
+        let marker = *data.get(pos).ok_or_else(truncated)?;
+        pos += 1;
+        match marker {
+            0x01 | 0xD0..=0xD7 => {
+                out.extend_from_slice(&[0xFF, marker]);
+                continue;
+            }
+            0xD9 => {
+                out.extend_from_slice(&[0xFF, 0xD9]);
+                if pos < data.len() {
+                    report.add(MetadataKind::Trailing, data.len() - pos);
+                }
+                break;
+            }
+            _ => {}
+        }
+        let len = data
+            .get(pos..pos + 2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]) as usize)
+            .ok_or_else(truncated)?;
+        let end = pos + len;
+        if len < 2 || end > data.len() {
+            return Err(truncated());
+        }
+        match jpeg_segment_kind(marker, &data[pos + 2..end]) {
+            Some(kind) => report.add(kind, len + 2),
+            None => {
+                out.extend_from_slice(&[0xFF, marker]);
+                out.extend_from_slice(&data[pos..end]);
+                if marker == 0xE0 && insert_at == 2 && out.len() == len + 4 {
+                    insert_at = out.len();
+                }
+            }
+        }
+        pos = end;
+        if marker == 0xDA {
+            // Entropy-coded data runs until the next real marker; 0xFF00 and RSTn are data.
+            let start = pos;
+            while pos + 1 < data.len() {
+                if data[pos] == 0xFF
+                    && data[pos + 1] != 0x00
+                    && !(0xD0..=0xD7).contains(&data[pos + 1])
+                {
+                    break;
+                }
+                pos += 1;
+            }
+            if pos + 1 >= data.len() {
+                return Err(truncated());
+            }
+            out.extend_from_slice(&data[start..pos]);
+        }
+    }
+    if let Some(orientation) = orientation {
+        out.splice(insert_at..insert_at, orientation_app1(orientation));
+    }
+    Ok(out)
+}
+
+fn strip_png(data: &[u8], report: &mut StripReport) -> Result<Vec<u8>, String> {
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(PNG_SIGNATURE);
+    let mut pos = PNG_SIGNATURE.len();
+    loop {
+        let header = data
+            .get(pos..pos + 8)
+            .ok_or_else(|| "metadata_strip_truncated".to_string())?;
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let kind = &header[4..8];
+        let end = pos
+            .checked_add(12)
+            .and_then(|v| v.checked_add(len))
+            .filter(|end| *end <= data.len())
+            .ok_or_else(|| "metadata_strip_truncated".to_string())?;
+        let body = &data[pos + 8..pos + 8 + len];
+        let removed = match kind {
+            b"eXIf" => Some(MetadataKind::Exif),
+            b"iTXt" if body.starts_with(b"XML:com.adobe.xmp\0") => Some(MetadataKind::Xmp),
+            b"tEXt" | b"zTXt" | b"iTXt" => Some(MetadataKind::Text),
+            b"tIME" => Some(MetadataKind::Timestamp),
+            _ => None,
+        };
+        match removed {
+            Some(kind) => report.add(kind, end - pos),
+            None => out.extend_from_slice(&data[pos..end]),
+        }
+        pos = end;
+        if kind == b"IEND" {
+            break;
+        }
+    }
+    if pos < data.len() {
+        report.add(MetadataKind::Trailing, data.len() - pos);
+    }
+    Ok(out)
+}
+
+fn strip_webp(data: &[u8], report: &mut StripReport) -> Result<Vec<u8>, String> {
+    let riff_end = (u32::from_le_bytes([data[4], data[5], data[6], data[7]]) as usize)
+        .saturating_add(8)
+        .min(data.len());
+    let mut out = data[..12].to_vec();
+    let mut pos = 12;
+    let mut vp8x_flags_at = None;
+    while pos + 8 <= riff_end {
+        let fourcc = &data[pos..pos + 4];
+        let len = u32::from_le_bytes([data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]])
+            as usize;
+        let end = len
+            .checked_add(len & 1)
+            .and_then(|padded| (pos + 8).checked_add(padded))
+            .ok_or_else(|| "metadata_strip_truncated".to_string())?
+            .min(riff_end);
+        match fourcc {
+            b"EXIF" => report.add(MetadataKind::Exif, end - pos),
+            b"XMP " => report.add(MetadataKind::Xmp, end - pos),
+            _ => {
+                if fourcc == b"VP8X" && len >= 1 {
+                    vp8x_flags_at = Some(out.len() + 8);
+                }
+                out.extend_from_slice(&data[pos..end]);
+            }
+        }
+        pos = end;
+    }
+    if let Some(at) = vp8x_flags_at.filter(|at| *at < out.len()) {
+        // Clear the EXIF (0x08) and XMP (0x04) presence flags.
+        out[at] &= !0x0C;
+    }
+    if riff_end < data.len() {
+        report.add(MetadataKind::Trailing, data.len() - riff_end);
+    }
+    let riff_size = (out.len() - 8) as u32;
+    out[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Ok(out)
+}
+
+fn extension_for(data: &[u8]) -> &'static str {
+    if data.starts_with(PNG_SIGNATURE) {
+        "png"
+    } else if data.starts_with(b"RIFF") {
+        "webp"
+    } else {
+        "jpg"
+    }
+}
+
+/// `name_clean.ext`, or `name_clean_2.ext` and so on when that is taken.
+fn clean_copy_path(dir: &Path, name: &str, ext: &str) -> std::path::PathBuf {
+    let stem = Path::new(name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or("photo");
+    let mut candidate = dir.join(format!("{stem}_clean.{ext}"));
+    let mut n = 2;
+    while candidate.exists() {
+        candidate = dir.join(format!("{stem}_clean_{n}.{ext}"));
+        n += 1;
+    }
+    candidate
+}
+
+fn read_input(input: &StripInput) -> Result<Vec<u8>, String> {
+    let file = match (input.fd, input.path.as_deref()) {
+        (Some(fd), _) if fd >= 0 => unsafe { File::from_raw_fd(fd as RawFd) },
+        (_, Some(path)) => File::open(path).map_err(|e| format!("open_failed:{e}"))?,
+        _ => return Err("missing_source".into()),
+    };
+    let mut data = Vec::new();
+    file.take(MAX_INPUT_BYTES + 1)
+        .read_to_end(&mut data)
+        .map_err(|e| format!("read_failed:{e}"))?;
+    if data.len() as u64 > MAX_INPUT_BYTES {
+        return Err("metadata_strip_file_too_large".into());
+    }
+    Ok(data)
+}
+
+/// Strips every input and writes `*_clean.*` copies into `output_dir`; originals are untouched.
+pub fn strip_files(inputs: &[StripInput], output_dir: &Path) -> Vec<StripOutcome> {
+    inputs
+        .iter()
+        .map(|input| {
+            let mut outcome = StripOutcome {
+                name: input.name.clone(),
+                output_path: None,
+                size_before: 0,
+                size_after: 0,
+                report: StripReport::default(),
+                error: None,
+            };
+            let result = read_input(input).and_then(|data| {
+                outcome.size_before = data.len() as u64;
+                let (cleaned, report) = strip_metadata(&data)?;
+                fs::create_dir_all(output_dir)
+                    .map_err(|e| format!("output_dir_create_failed:{e}"))?;
+                let path = clean_copy_path(output_dir, &input.name, extension_for(&data));
+                fs::write(&path, &cleaned).map_err(|e| format!("save_failed:{e}"))?;
+                outcome.size_after = cleaned.len() as u64;
+                outcome.report = report;
+                Ok(path.to_string_lossy().into_owned())
+            });
+            match result {
+                Ok(path) => outcome.output_path = Some(path),
+                Err(e) => outcome.error = Some(e),
+            }
+            outcome
+        })
+        .collect()
+}
+
+fn kind_label(kind: MetadataKind) -> String {
+    match kind {
+        MetadataKind::Exif => t!("metadata_strip_kind_exif"),
+        MetadataKind::Xmp => t!("metadata_strip_kind_xmp"),
+        MetadataKind::Iptc => t!("metadata_strip_kind_iptc"),
+        MetadataKind::Comment => t!("metadata_strip_kind_comment"),
+        MetadataKind::Text => t!("metadata_strip_kind_text"),
+        MetadataKind::Timestamp => t!("metadata_strip_kind_timestamp"),
+        MetadataKind::Vendor => t!("metadata_strip_kind_vendor"),
+        MetadataKind::Trailing => t!("metadata_strip_kind_trailing"),
+    }
+    .to_string()
+}
+
+/// e.g. `EXIF (GPS location, camera: Google Pixel 7, date taken) · XMP`.
+pub fn removed_summary(report: &StripReport) -> String {
+    report
+        .removed
+        .iter()
+        .map(|block| {
+            let mut label = kind_label(block.kind);
+            if block.count > 1 {
+                label = format!("{label} ×{}", block.count);
+            }
+            if block.kind == MetadataKind::Exif {
+                if let Some(exif) = &report.exif {
+                    let mut details = Vec::new();
+                    if exif.gps.is_some() {
+                        details.push(t!("metadata_strip_exif_gps").to_string());
+                    }
+                    let camera = [exif.make.as_deref(), exif.model.as_deref()]
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    if !camera.is_empty() {
+                        details.push(format!(
+                            "{}{camera}",
+                            t!("metadata_strip_exif_camera_prefix")
+                        ));
+                    }
+                    if exif.taken_at.is_some() {
+                        details.push(t!("metadata_strip_exif_date").to_string());
+                    }
+                    if !details.is_empty() {
+                        label = format!("{label} ({})", details.join(", "));
+                    }
+                }
+            }
+            format!("{label} — {}", format_bytes(block.bytes))
+        })
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+fn render_outcome(outcome: &StripOutcome) -> Value {
+    let mut rows = Vec::new();
+    let mut line = |text: String| {
+        rows.push(serde_json::to_value(UiText::new(&text).size(12.0)).unwrap());
+    };
+    if let Some(err) = &outcome.error {
+        line(error_text(err));
+    } else {
+        if outcome.report.removed.is_empty() {
+            line(t!("metadata_strip_nothing_found").to_string());
+        } else {
+            line(format!(
+                "{}{}",
+                t!("metadata_strip_removed_prefix"),
+                removed_summary(&outcome.report)
+            ));
+        }
+        if let Some(orientation) = outcome.report.kept_orientation {
+            line(format!(
+                "{}{orientation} ({})",
+                t!("metadata_strip_kept_orientation_prefix"),
+                orientation_label(orientation)
+            ));
+        }
+        line(format!(
+            "{}{} → {}",
+            t!("metadata_strip_size_prefix"),
+            format_bytes(outcome.size_before),
+            format_bytes(outcome.size_after)
+        ));
+        if let Some(path) = &outcome.output_path {
+            line(format!("{}{path}", t!("metadata_strip_saved_prefix")));
+        }
+    }
+    if let Some(path) = &outcome.output_path {
+        rows.push(
+            serde_json::to_value(
+                UiButton::new(&t!("dithering_copy_result_path_button"), "copy_clipboard")
+                    .copy_text(path),
+            )
+            .unwrap(),
+        );
+    }
+    serde_json::to_value(UiSection::new(rows).title(&outcome.name).padding(12)).unwrap()
+}
+
+pub fn render_metadata_strip_screen(state: &AppState) -> Value {
+    let strip = &state.metadata_strip;
+    let mut children = vec![
+        serde_json::to_value(UiText::new(&t!("metadata_strip_title")).size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("metadata_strip_description")).size(14.0)).unwrap(),
+        serde_json::to_value(
+            UiButton::new(&t!("metadata_strip_pick_button"), "metadata_strip_pick")
+                .requires_file_picker(true)
+                .allow_multiple_files(true)
+                .id("metadata_strip_pick"),
+        )
+        .unwrap(),
+    ];
+    if let Some(err) = &strip.error {
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
+    }
+    if !strip.outcomes.is_empty() {
+        let cleaned = strip
+            .outcomes
+            .iter()
+            .filter(|o| o.output_path.is_some())
+            .count();
+        children.push(
+            serde_json::to_value(
+                UiText::new(&format!(
+                    "{}{cleaned}/{}",
+                    t!("metadata_strip_cleaned_prefix"),
+                    strip.outcomes.len()
+                ))
+                .size(14.0),
+            )
+            .unwrap(),
+        );
+        children.extend(strip.outcomes.iter().map(render_outcome));
+    }
+    maybe_push_back(&mut children, state);
+    serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::ImageEncoder;
+
+    fn segment(marker: u8, payload: &[u8]) -> Vec<u8> {
+        let mut seg = vec![0xFF, marker];
+        seg.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        seg.extend_from_slice(payload);
+        seg
+    }
+
+    #[test]
+    fn jpeg_strip_drops_metadata_and_keeps_orientation() {
+        let image = image::RgbImage::from_pixel(8, 8, image::Rgb([10, 120, 200]));
+        let mut encoded = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut encoded, 90)
+            .encode(image.as_raw(), 8, 8, image::ColorType::Rgb8)
+            .unwrap();
+
+        // SOI, then EXIF (orientation 6), XMP and a comment before the encoder's own segments.
+        let mut exif =
+            b"Exif\0\0MM\0\x2a\0\0\0\x08\0\x01\x01\x12\0\x03\0\0\0\x01\0\x06\0\0\0\0\0\0".to_vec();
+        exif.extend_from_slice(&[0u8; 16]);
+        let mut data = encoded[..2].to_vec();
+        data.extend(segment(0xE1, &exif));
+        data.extend(segment(0xE1, &[XMP_PREFIX, b"<x:xmpmeta/>"].concat()));
+        data.extend(segment(0xFE, b"shot on my phone"));
+        data.extend_from_slice(&encoded[2..]);
+        data.extend_from_slice(b"trailing preview");
+
+        let (cleaned, report) = strip_metadata(&data).unwrap();
+        let kinds: Vec<_> = report.removed.iter().map(|b| b.kind).collect();
+        assert!(kinds.contains(&MetadataKind::Exif));
+        assert!(kinds.contains(&MetadataKind::Xmp));
+        assert!(kinds.contains(&MetadataKind::Comment));
+        assert!(kinds.contains(&MetadataKind::Trailing));
+        assert_eq!(report.kept_orientation, Some(6));
+
+        let text = String::from_utf8_lossy(&cleaned);
+        assert!(!text.contains("xmpmeta") && !text.contains("shot on my phone"));
+        assert!(cleaned.ends_with(&[0xFF, 0xD9]));
+        let summary = read_exif(&mut Cursor::new(&cleaned)).unwrap();
+        assert_eq!(summary.orientation, Some(6));
+        let decoded = image::load_from_memory(&cleaned).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (8, 8));
+    }
+
+    #[test]
+    fn png_and_webp_strip_text_and_exif_chunks() {
+        let image = image::RgbaImage::from_pixel(4, 4, image::Rgba([1, 2, 3, 255]));
+        let mut png = Vec::new();
+        image::codecs::png::PngEncoder::new(&mut png)
+            .write_image(image.as_raw(), 4, 4, image::ColorType::Rgba8)
+            .unwrap();
+        let chunk = |kind: &[u8], body: &[u8]| {
+            let mut c = (body.len() as u32).to_be_bytes().to_vec();
+            c.extend_from_slice(kind);
+            c.extend_from_slice(body);
+            c.extend_from_slice(&[0, 0, 0, 0]);
+            c
+        };
+        let mut data = png[..33].to_vec();
+        data.extend(chunk(b"tEXt", b"Author\0me"));
+        data.extend(chunk(b"iTXt", b"XML:com.adobe.xmp\0\0\0\0\0<x/>"));
+        data.extend_from_slice(&png[33..]);
+        let (cleaned, report) = strip_metadata(&data).unwrap();
+        assert_eq!(cleaned, png);
+        assert_eq!(report.removed.len(), 2);
+
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\x0c\0\0\0\0\0\0\0\0\0".to_vec();
+        webp.extend_from_slice(b"EXIF\x03\0\0\0abc\0");
+        webp.extend_from_slice(b"XMP \x02\0\0\0<>");
+        let size = (webp.len() - 8) as u32;
+        webp[4..8].copy_from_slice(&size.to_le_bytes());
+        let (cleaned, report) = strip_metadata(&webp).unwrap();
+        assert_eq!(cleaned.len(), 30);
+        assert_eq!(&cleaned[4..8], &22u32.to_le_bytes());
+        assert_eq!(cleaned[20] & 0x0C, 0);
+        assert_eq!(report.removed[0].bytes, 12);
+
+        assert!(strip_metadata(b"GIF89a").is_err());
+    }
+}
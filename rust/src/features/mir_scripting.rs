@@ -8,23 +8,10 @@ use std::time::Instant;
 
 static MIR_GLOBAL_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
 
-#[cfg(target_os = "android")]
 fn logcat(msg: &str) {
-    unsafe {
-        let tag = b"kistaverk-mir\0";
-        let c_msg = CString::new(msg).unwrap_or_else(|_| CString::new("<log msg had NUL>").unwrap());
-        android_log_sys::__android_log_print(
-            android_log_sys::LogPriority::INFO as _,
-            tag.as_ptr() as *const _,
-            b"%s\0".as_ptr() as *const _,
-            c_msg.as_ptr(),
-        );
-    }
+    crate::logging::info("mir", msg);
 }
 
-#[cfg(not(target_os = "android"))]
-fn logcat(_msg: &str) {}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MirScriptingState {
     pub source: String,
@@ -269,7 +256,7 @@ pub fn render_mir_scripting_screen(state: &AppState) -> serde_json::Value {
 
     components.push(json!({
         "type": "Text",
-        "text": "MIR Scripting Lab",
+        "text": t!("mir_scripting_title"),
         "size": 24.0,
         "bold": true,
         "margin_bottom": 16.0
@@ -277,7 +264,7 @@ pub fn render_mir_scripting_screen(state: &AppState) -> serde_json::Value {
 
     components.push(json!({
         "type": "Text",
-        "text": "Entry function (called as: extern \"C\" fn() -> i64):",
+        "text": t!("mir_scripting_entry_label"),
         "size": 14.0,
         "margin_bottom": 6.0
     }));
@@ -296,7 +283,7 @@ pub fn render_mir_scripting_screen(state: &AppState) -> serde_json::Value {
         "type": "TextInput",
         "bind_key": "mir_scripting.source",
         "text": ms.source,
-        "hint": "Enter your MIR module here...",
+        "hint": t!("mir_scripting_source_hint"),
         "single_line": false,
         "max_lines": 24,
         "margin_bottom": 12.0
@@ -307,31 +294,31 @@ pub fn render_mir_scripting_screen(state: &AppState) -> serde_json::Value {
         "children": [
             {
                 "type": "Button",
-                "text": "Run (Interpreter)",
+                "text": t!("mir_scripting_run_interp_button"),
                 "action": "mir_scripting_execute_interp",
                 "margin_bottom": 8.0
             },
             {
                 "type": "Button",
-                "text": "Run (JIT)",
+                "text": t!("mir_scripting_run_jit_button"),
                 "action": "mir_scripting_execute_jit",
                 "margin_bottom": 8.0
             },
             {
                 "type": "Button",
-                "text": "Clear Output",
+                "text": t!("mir_scripting_clear_output_button"),
                 "action": "mir_scripting_clear_output",
                 "margin_bottom": 8.0
             },
             {
                 "type": "Button",
-                "text": "Clear Source",
+                "text": t!("mir_scripting_clear_source_button"),
                 "action": "mir_scripting_clear_source",
                 "margin_bottom": 8.0
             },
             {
                 "type": "Button",
-                "text": "Load Example",
+                "text": t!("mir_scripting_load_example_button"),
                 "action": "mir_scripting_load_example"
             }
         ]
@@ -339,7 +326,7 @@ pub fn render_mir_scripting_screen(state: &AppState) -> serde_json::Value {
 
     components.push(json!({
         "type": "Text",
-        "text": "Output:",
+        "text": t!("mir_scripting_output_label"),
         "size": 18.0,
         "bold": true,
         "margin_top": 16.0,
@@ -347,9 +334,9 @@ pub fn render_mir_scripting_screen(state: &AppState) -> serde_json::Value {
     }));
 
     let output_text = if let Some(error) = &ms.error {
-        format!("Error: {}", error)
+        t!("mir_scripting_error", error = error).into_owned()
     } else if ms.output.is_empty() {
-        "No output yet. Execute to see results.".to_string()
+        t!("mir_scripting_no_output").into_owned()
     } else {
         ms.output.clone()
     };
@@ -385,7 +372,7 @@ pub fn handle_mir_scripting_actions(
             state.mir_scripting.entry = entry;
             let runtime_ms = state.mir_scripting.execute_jit();
             if let Some(ms) = runtime_ms {
-                state.toast = Some(format!("MIR JIT runtime: {} ms", ms));
+                state.push_toast(t!("mir_scripting_jit_runtime", ms = ms));
             }
             Some(render_mir_scripting_screen(state))
         }
@@ -394,7 +381,7 @@ pub fn handle_mir_scripting_actions(
             state.mir_scripting.entry = entry;
             let runtime_ms = state.mir_scripting.execute_interp();
             if let Some(ms) = runtime_ms {
-                state.toast = Some(format!("MIR interpreter runtime: {} ms", ms));
+                state.push_toast(t!("mir_scripting_interp_runtime", ms = ms));
             }
             Some(render_mir_scripting_screen(state))
         }
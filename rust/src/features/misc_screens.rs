@@ -1,8 +1,10 @@
 use crate::features::dependencies::render_dependencies_list;
-use crate::state::AppState;
+use crate::i18n::SUPPORTED_LOCALES;
+use crate::state::{AppState, ThemeMode};
 use crate::ui::{
-    maybe_push_back, Barometer as UiBarometer, Button as UiButton, Column as UiColumn,
-    Compass as UiCompass, Magnetometer as UiMagnetometer, Progress as UiProgress, Text as UiText,
+    maybe_push_back, Barometer as UiBarometer, Button as UiButton, Card as UiCard,
+    Column as UiColumn, Compass as UiCompass, Magnetometer as UiMagnetometer,
+    Progress as UiProgress, ProgressBar as UiProgressBar, RichText as UiRichText, Text as UiText,
     TextInput as UiTextInput,
 };
 use serde_json::{json, Value};
@@ -20,15 +22,51 @@ void main() {
 }
 "#;
 
+const SAMPLE_SHADER_SYNTAX: &str = "precision mediump float;\nuniform float u_time;\nuniform vec2 u_resolution;\nvoid main(){ vec2 uv=gl_FragCoord.xy/u_resolution.xy; vec3 col=0.5+0.5*cos(u_time*0.2+uv.xyx+vec3(0.,2.,4.)); gl_FragColor=vec4(col,1.0); }";
+
+/// How often the loading screen polls for worker progress and results.
+const LOADING_POLL_MS: u64 = 500;
+
 pub fn render_loading_screen(state: &AppState) -> Value {
-    let message = state.loading_message.as_deref().unwrap_or("Working...");
-    let mut children = vec![serde_json::to_value(UiText::new(message).size(16.0)).unwrap()];
-    if state.loading_with_spinner {
+    let message = state
+        .loading_message
+        .clone()
+        .unwrap_or_else(|| t!("loading_working").into_owned());
+    let mut children = vec![serde_json::to_value(UiText::new(&message).size(16.0)).unwrap()];
+    let in_progress = t!("loading_in_progress");
+    if let Some(progress) = &state.job_progress {
+        let bar = match progress.fraction {
+            Some(fraction) => {
+                let mut bar = UiProgressBar::new(fraction).content_description(&in_progress);
+                if let Some(message) = progress.message.as_deref() {
+                    bar = bar.label(message);
+                }
+                serde_json::to_value(bar)
+            }
+            None => serde_json::to_value(
+                UiProgress::new()
+                    .text(progress.message.as_deref().unwrap_or(&progress.stage))
+                    .content_description(&in_progress),
+            ),
+        };
+        children.push(bar.unwrap());
+    } else if state.loading_with_spinner {
         children.push(
-            serde_json::to_value(UiProgress::new().content_description("In progress")).unwrap(),
+            serde_json::to_value(UiProgress::new().content_description(&in_progress)).unwrap(),
         );
     }
-    serde_json::to_value(UiColumn::new(children).padding(24)).unwrap()
+    children.push(
+        serde_json::to_value(
+            UiButton::new(&t!("loading_show_jobs_button"), "job_queue_screen").id("loading_jobs"),
+        )
+        .unwrap(),
+    );
+    let mut root = serde_json::to_value(UiColumn::new(children).padding(24)).unwrap();
+    if let Some(obj) = root.as_object_mut() {
+        obj.insert("auto_refresh_ms".into(), json!(LOADING_POLL_MS));
+        obj.insert("auto_refresh_action".into(), json!("poll_progress"));
+    }
+    root
 }
 
 pub fn render_shader_screen(state: &AppState) -> Value {
@@ -39,21 +77,23 @@ pub fn render_shader_screen(state: &AppState) -> Value {
         .unwrap_or(SAMPLE_SHADER);
 
     let mut children = vec![
-        serde_json::to_value(UiText::new("Shader toy demo").size(20.0)).unwrap(),
-        serde_json::to_value(
-            UiText::new("Simple fragment shader with time and resolution uniforms."),
-        )
-        .unwrap(),
+        serde_json::to_value(UiText::new(&t!("shader_demo_title")).size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("shader_demo_description"))).unwrap(),
         json!({
             "type": "ShaderToy",
             "fragment": fragment
         }),
         serde_json::to_value(
-            UiButton::new("Load shader from file", "load_shader_file").requires_file_picker(true),
+            UiButton::new(&t!("shader_demo_load_file_button"), "load_shader_file")
+                .requires_file_picker(true),
         )
         .unwrap(),
         serde_json::to_value(
-            UiText::new("Sample syntax:\nprecision mediump float;\nuniform float u_time;\nuniform vec2 u_resolution;\nvoid main(){ vec2 uv=gl_FragCoord.xy/u_resolution.xy; vec3 col=0.5+0.5*cos(u_time*0.2+uv.xyx+vec3(0.,2.,4.)); gl_FragColor=vec4(col,1.0); }").size(12.0),
+            UiText::new(&format!(
+                "{}\n{SAMPLE_SHADER_SYNTAX}",
+                t!("shader_demo_sample_syntax")
+            ))
+            .size(12.0),
         )
         .unwrap(),
     ];
@@ -62,28 +102,94 @@ pub fn render_shader_screen(state: &AppState) -> Value {
     serde_json::to_value(UiColumn::new(children).padding(16)).unwrap()
 }
 
+fn offset_text(value: f64) -> String {
+    if value == 0.0 {
+        String::new()
+    } else {
+        value.to_string()
+    }
+}
+
+fn calibration_input(id: &str, hint: &str, value: f64) -> Value {
+    serde_json::to_value(
+        UiTextInput::new(id)
+            .hint(hint)
+            .text(&offset_text(value))
+            .single_line(true),
+    )
+    .unwrap()
+}
+
+/// "Apply" plus the preset buttons shared by the calibrated sensor screens.
+fn calibration_buttons(tool_id: &str) -> Vec<Value> {
+    vec![
+        serde_json::to_value(
+            UiButton::new(
+                &t!("sensor_calibration_apply_button"),
+                "sensor_calibration_set",
+            )
+            .content_description(&format!("{tool_id}_calibration_apply")),
+        )
+        .unwrap(),
+        json!({
+            "type": "Button",
+            "text": t!("presets_title"),
+            "action": "presets_list",
+            "id": format!("{tool_id}_presets"),
+            "payload": { "tool_id": tool_id }
+        }),
+        json!({
+            "type": "Button",
+            "text": t!("presets_save_title"),
+            "action": "preset_save_dialog",
+            "id": format!("{tool_id}_preset_save"),
+            "payload": { "tool_id": tool_id }
+        }),
+    ]
+}
+
 pub fn render_compass_screen(state: &AppState) -> Value {
     let degrees = state.compass_angle_radians.to_degrees();
     let mut children = vec![
-        serde_json::to_value(UiText::new("Compass (AGSL)").size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("compass_title")).size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("compass_description")).size(12.0)).unwrap(),
         serde_json::to_value(
-            UiText::new("Compass dial driven by device sensors. Heading auto-updates when sensors are available.")
-                .size(12.0),
+            UiText::new(&t!("compass_heading", degrees = format!("{degrees:.1}"))).size(14.0),
         )
         .unwrap(),
-        serde_json::to_value(UiText::new(&format!("Heading: {:.1}°", degrees)).size(14.0)).unwrap(),
         serde_json::to_value(UiCompass::new(state.compass_angle_radians)).unwrap(),
         serde_json::to_value(
             UiText::new(
                 state
                     .compass_error
                     .as_deref()
-                    .unwrap_or("Sensor updates will appear automatically.")
+                    .unwrap_or(&t!("compass_waiting"))
             )
             .size(12.0),
         )
         .unwrap(),
     ];
+    let cal = state.sensor_calibration;
+    let north = if cal.compass_declination_deg == 0.0 {
+        t!("compass_reference_magnetic")
+    } else {
+        t!(
+            "compass_reference_true",
+            declination = format!("{:+.1}", cal.compass_declination_deg)
+        )
+    };
+    children.push(serde_json::to_value(UiText::new(&north).size(12.0)).unwrap());
+    children.push(calibration_input(
+        "compass_declination_deg",
+        &t!("compass_declination_hint"),
+        cal.compass_declination_deg,
+    ));
+    children.push(calibration_input(
+        "compass_offset_deg",
+        &t!("compass_offset_hint"),
+        cal.compass_offset_deg,
+    ));
+    children.extend(calibration_buttons("compass"));
     maybe_push_back(&mut children, state);
     serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
 }
@@ -91,23 +197,50 @@ pub fn render_compass_screen(state: &AppState) -> Value {
 pub fn render_barometer_screen(state: &AppState) -> Value {
     let reading = state.barometer_hpa.map(|v| format!("{:.1} hPa", v));
     let mut children = vec![
-        serde_json::to_value(UiText::new("Barometer").size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("barometer_title")).size(20.0)).unwrap(),
         serde_json::to_value(
             UiText::new(
                 state
                     .barometer_error
                     .as_deref()
-                    .unwrap_or("Live pressure readout from the device barometer (if present)."),
+                    .unwrap_or(&t!("barometer_description")),
             )
             .size(12.0),
         )
         .unwrap(),
         serde_json::to_value(
-            UiText::new(reading.as_deref().unwrap_or("Waiting for sensor...")).size(14.0),
+            UiText::new(reading.as_deref().unwrap_or(&t!("sensor_waiting"))).size(14.0),
         )
         .unwrap(),
         serde_json::to_value(UiBarometer::new(state.barometer_hpa.unwrap_or(0.0))).unwrap(),
     ];
+    let offset = state.sensor_calibration.barometer_offset_hpa;
+    if offset != 0.0 {
+        children.push(
+            serde_json::to_value(
+                UiText::new(&t!(
+                    "barometer_offset_applied",
+                    offset = format!("{offset:+.2}")
+                ))
+                .size(12.0),
+            )
+            .unwrap(),
+        );
+    }
+    children.push(calibration_input(
+        "barometer_offset_hpa",
+        &t!("barometer_offset_hint"),
+        offset,
+    ));
+    children.push(
+        serde_json::to_value(
+            UiTextInput::new("barometer_station_hpa")
+                .hint(&t!("barometer_station_hint"))
+                .single_line(true),
+        )
+        .unwrap(),
+    );
+    children.extend(calibration_buttons("barometer"));
     maybe_push_back(&mut children, state);
     serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
 }
@@ -116,22 +249,28 @@ pub fn render_magnetometer_screen(state: &AppState) -> Value {
     let reading = state
         .magnetometer_ut
         .map(|v| format!("{:.1} µT", v))
-        .unwrap_or_else(|| "Waiting for sensor...".into());
+        .unwrap_or_else(|| t!("sensor_waiting").into_owned());
     let mut children = vec![
-        serde_json::to_value(UiText::new("Magnetometer").size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("magnetometer_title")).size(20.0)).unwrap(),
         serde_json::to_value(
             UiText::new(
                 state
                     .magnetometer_error
                     .as_deref()
-                    .unwrap_or("Live magnetic field strength (device sensor)."),
+                    .unwrap_or(&t!("magnetometer_description")),
             )
             .size(12.0),
         )
         .unwrap(),
         serde_json::to_value(UiText::new(&reading).size(14.0)).unwrap(),
         serde_json::to_value(UiMagnetometer::new(state.magnetometer_ut.unwrap_or(0.0))).unwrap(),
+        calibration_input(
+            "magnetometer_offset_ut",
+            &t!("magnetometer_offset_hint"),
+            state.sensor_calibration.magnetometer_offset_ut,
+        ),
     ];
+    children.extend(calibration_buttons("magnetometer"));
     maybe_push_back(&mut children, state);
     serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
 }
@@ -140,17 +279,17 @@ pub fn render_progress_demo_screen(state: &AppState) -> Value {
     let mut children = vec![
         json!({
             "type": "Text",
-            "text": "Progress demo",
+            "text": t!("progress_demo_title"),
             "size": 20.0
         }),
         json!({
             "type": "Text",
-            "text": "Tap start to show a 10 second simulated progress and return here when done.",
+            "text": t!("progress_demo_description"),
             "size": 14.0
         }),
         json!({
             "type": "Button",
-            "text": "Start 10s work",
+            "text": t!("progress_demo_start_button"),
             "action": "progress_demo_start"
         }),
     ];
@@ -158,7 +297,7 @@ pub fn render_progress_demo_screen(state: &AppState) -> Value {
     if let Some(status) = &state.progress_status {
         children.push(json!({
             "type": "Text",
-            "text": format!("Status: {}", status),
+            "text": t!("progress_demo_status", status = status),
             "size": 14.0
         }));
     }
@@ -175,28 +314,23 @@ pub fn render_progress_demo_screen(state: &AppState) -> Value {
 pub fn render_about_screen(state: &AppState) -> Value {
     let filter_value = state.dependencies.query.as_str();
     let mut children = vec![
-        serde_json::to_value(UiText::new("About Kistaverk").size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("about_title")).size(20.0)).unwrap(),
         serde_json::to_value(
-            UiText::new(&format!("Version: {}", env!("CARGO_PKG_VERSION"))).size(14.0),
-        )
-        .unwrap(),
-        serde_json::to_value(UiText::new("Copyright © 2025 Kistaverk").size(14.0)).unwrap(),
-        serde_json::to_value(UiText::new("License: AGPL-3.0-or-later").size(14.0)).unwrap(),
-        serde_json::to_value(
-            UiText::new("This app is open-source under AGPL-3.0-or-later; contributions welcome.")
-                .size(12.0),
+            UiText::new(&t!("about_version", version = env!("CARGO_PKG_VERSION"))).size(14.0),
         )
         .unwrap(),
+        serde_json::to_value(UiText::new(&t!("about_copyright")).size(14.0)).unwrap(),
+        serde_json::to_value(UiRichText::new(&t!("about_license_markdown")).size(14.0)).unwrap(),
         serde_json::to_value(
             UiTextInput::new("deps_filter")
-                .hint("Filter dependencies")
+                .hint(&t!("about_filter_dependencies_hint"))
                 .text(filter_value)
                 .single_line(true)
                 .debounce_ms(200)
                 .action_on_submit("deps_filter"),
         )
         .unwrap(),
-        serde_json::to_value(UiText::new("Open source licenses").size(16.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("about_licenses_section")).size(16.0)).unwrap(),
         render_dependencies_list(&state.dependencies),
     ];
     maybe_push_back(&mut children, state);
@@ -209,105 +343,30 @@ pub fn render_settings_screen(state: &AppState) -> Value {
     let settings_title = t!("settings_locale");
     let settings_description = t!("settings_locale_description");
     let system_default = t!("settings_system_default");
-    let english = t!("locale_english");
-    let french = t!("locale_french");
-    let german = t!("locale_german");
-    let icelandic = t!("locale_icelandic");
-    let spanish = t!("locale_spanish");
-    let portuguese = t!("locale_portuguese");
-    let chinese = t!("locale_chinese");
-    let latin = t!("locale_latin");
-    
-    let current_locale = state.locale.clone();
-    let preferred_locale = state.preferred_locale.clone();
-    
-    // Check if we're using the system locale (no preferred locale set or it's empty)
-    let is_using_system = preferred_locale.is_empty() || preferred_locale == "system";
-    
-    let locale_buttons = vec![
-        {
-            let mut button = UiButton::new(&system_default, "set_locale")
-                .payload(json!({"locale": ""}))  // Empty string means use system
-            ;
-            if is_using_system {
-                button = button.content_description("selected_locale");
-            }
-            serde_json::to_value(button).unwrap()
-        },
-        {
-            let mut button = UiButton::new(&english, "set_locale")
-                .payload(json!({"locale": "en"}))
-            ;
-            if current_locale == "en" && !is_using_system {
-                button = button.content_description("selected_locale");
-            }
-            serde_json::to_value(button).unwrap()
-        },
-        {
-            let mut button = UiButton::new(&french, "set_locale")
-                .payload(json!({"locale": "fr"}))
-            ;
-            if current_locale == "fr" && !is_using_system {
-                button = button.content_description("selected_locale");
-            }
-            serde_json::to_value(button).unwrap()
-        },
-        {
-            let mut button = UiButton::new(&german, "set_locale")
-                .payload(json!({"locale": "de"}))
-            ;
-            if current_locale == "de" && !is_using_system {
-                button = button.content_description("selected_locale");
-            }
-            serde_json::to_value(button).unwrap()
-        },
-        {
-            let mut button = UiButton::new(&icelandic, "set_locale")
-                .payload(json!({"locale": "is"}))
-            ;
-            if current_locale == "is" && !is_using_system {
-                button = button.content_description("selected_locale");
-            }
-            serde_json::to_value(button).unwrap()
-        },
-        {
-            let mut button = UiButton::new(&spanish, "set_locale")
-                .payload(json!({"locale": "es"}))
-            ;
-            if current_locale == "es" && !is_using_system {
-                button = button.content_description("selected_locale");
-            }
-            serde_json::to_value(button).unwrap()
-        },
-        {
-            let mut button = UiButton::new(&portuguese, "set_locale")
-                .payload(json!({"locale": "pt"}))
-            ;
-            if current_locale == "pt" && !is_using_system {
-                button = button.content_description("selected_locale");
-            }
-            serde_json::to_value(button).unwrap()
-        },
-        {
-            let mut button = UiButton::new(&chinese, "set_locale")
-                .payload(json!({"locale": "zh"})) // Using 'zh' as the standard locale code for Chinese
-            ;
-            if current_locale == "zh" && !is_using_system {
-                button = button.content_description("selected_locale");
-            }
-            serde_json::to_value(button).unwrap()
-        },
-        {
-            let mut button = UiButton::new(&latin, "set_locale")
-                .payload(json!({"locale": "la"}))
-            ;
-            if current_locale == "la" && !is_using_system {
-                button = button.content_description("selected_locale");
-            }
-            serde_json::to_value(button).unwrap()
-        },
-    ];
-    
+
+    // Empty means follow the device locale.
+    let is_using_system = state.preferred_locale.is_empty();
+    let locale_button = |label: &str, locale: &str, selected: bool| {
+        let id = if locale.is_empty() {
+            "locale_system".to_string()
+        } else {
+            format!("locale_{locale}")
+        };
+        let mut button = UiButton::new(label, "set_locale")
+            .payload(json!({ "locale": locale }))
+            .id(&id);
+        if selected {
+            button = button.content_description("selected_locale");
+        }
+        serde_json::to_value(button).unwrap()
+    };
+
+    let mut locale_buttons = vec![locale_button(&system_default, "", is_using_system)];
+    for &(code, label_key) in SUPPORTED_LOCALES {
+        let selected = !is_using_system && state.locale == code;
+        locale_buttons.push(locale_button(&t!(label_key), code, selected));
+    }
+
     let locale_card = UiCard::new(vec![
         serde_json::to_value(UiColumn::new(locale_buttons).padding(8)).unwrap()
     ])
@@ -317,6 +376,7 @@ pub fn render_settings_screen(state: &AppState) -> Value {
     
     let mut children = vec![
         serde_json::to_value(locale_card).unwrap(),
+        render_theme_card(state.theme_mode),
     ];
     
     maybe_push_back(&mut children, state);
@@ -324,6 +384,33 @@ pub fn render_settings_screen(state: &AppState) -> Value {
     serde_json::to_value(UiColumn::new(children).padding(20).scrollable(false)).unwrap()
 }
 
+/// Theme picker shared by the home and settings screens.
+pub fn render_theme_card(current: ThemeMode) -> Value {
+    let buttons: Vec<Value> = ThemeMode::ALL
+        .iter()
+        .map(|&mode| {
+            let label = match mode {
+                ThemeMode::System => t!("theme_system"),
+                ThemeMode::Light => t!("theme_light"),
+                ThemeMode::Dark => t!("theme_dark"),
+            };
+            let id = format!("theme_{}", mode.as_str());
+            let mut button = UiButton::new(&label, "set_theme")
+                .id(&id)
+                .payload(json!({ "theme_mode": mode.as_str() }));
+            if mode == current {
+                button = button.content_description("selected_theme");
+            }
+            serde_json::to_value(button).unwrap()
+        })
+        .collect();
+    let title = t!("theme_section");
+    let card = UiCard::new(vec![serde_json::to_value(UiColumn::new(buttons)).unwrap()])
+        .title(&title)
+        .padding(12);
+    serde_json::to_value(card).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::render_settings_screen;
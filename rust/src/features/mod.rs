@@ -1,40 +1,70 @@
+pub mod apk_info;
 pub mod archive;
+pub mod ascii_art;
 pub mod cas_types;
+pub mod cleanup;
+pub mod collage;
 pub mod color_tools;
 pub mod compression;
 pub mod dithering;
+pub mod entropy;
+pub mod exif_editor;
+pub mod exif_info;
 pub mod file_info;
+pub mod gif_frames;
 pub mod hashes;
+pub mod image_adjust;
+pub mod image_edit;
+pub mod image_histogram;
+pub mod image_resize;
+pub mod job_queue;
 pub mod kotlin_image;
+pub mod media_info;
+pub mod metadata_strip;
 pub mod misc_screens;
 pub mod pdf;
 pub mod pixel_art;
 pub mod presets;
 pub mod qr;
 pub mod qr_transfer;
+pub mod regex_explain;
 pub mod regex_tester;
 pub mod sensor_utils;
 pub mod sensor_logger;
+pub mod gps_export;
+pub mod sensor_summary;
+pub mod sensor_alerts;
+pub mod signatures;
 pub mod storage;
 pub mod system_info;
 pub mod dependencies;
 pub mod text_tools;
 pub mod text_viewer;
+pub mod thumbnail;
 pub mod uuid_gen;
 pub mod math_tool;
 pub mod mir_math;
 pub mod vault;
+pub mod watermark;
 pub mod logic;
 pub mod jwt;
+pub mod cert_inspector;
+pub mod password_strength;
+pub mod date_calc;
 pub mod hex_editor;
 pub mod plotting;
 pub mod sql_engine;
 pub mod mir_scripting;
 pub mod c_scripting;
+pub mod jit_benchmark;
+pub mod plugins;
+pub mod expr_jit;
 pub mod automatic_differentiation;
 pub mod function_analysis;
 pub mod c_based_ad;
 pub mod synthesizer;
 pub mod scheduler;
+pub mod usage_stats;
+pub mod debug_log;
 pub mod unit_converter;
 pub mod math_tool_test;
\ No newline at end of file
@@ -0,0 +1,1174 @@
+//! Password strength estimate in the spirit of zxcvbn. The password is covered by the
+//! cheapest sequence of guessable patterns (common passwords and words, keyboard walks,
+//! sequences, repeats, dates) with brute force for whatever is left, and the guess count
+//! of that cover is the estimate. The word lists are short, so an uncommon word counts
+//! as brute force and scores better than it would against a real cracking dictionary.
+
+use crate::error::error_text;
+use crate::state::AppState;
+use crate::ui::{
+    maybe_push_back, Card as UiCard, Checkbox as UiCheckbox, Column as UiColumn,
+    ProgressBar as UiProgressBar, Text as UiText, TextInput as UiTextInput,
+};
+use chrono::{Datelike, Utc};
+use rust_i18n::t;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+
+/// The matchers are quadratic in length; longer input is pasted text, not a password.
+pub const MAX_PASSWORD_CHARS: usize = 128;
+
+/// Guesses per second: online with and without rate limiting, offline against a slow
+/// (bcrypt, scrypt, Argon2) and a fast (unsalted SHA/MD5) hash.
+pub const CRACK_SCENARIOS: [(&str, f64); 4] = [
+    ("password_crack_online_throttled", 100.0 / 3600.0),
+    ("password_crack_online", 10.0),
+    ("password_crack_offline_slow", 1e4),
+    ("password_crack_offline_fast", 1e10),
+];
+
+const SCORE_LABELS: [&str; 5] = [
+    "password_score_0",
+    "password_score_1",
+    "password_score_2",
+    "password_score_3",
+    "password_score_4",
+];
+
+const BRUTEFORCE_CARDINALITY: f64 = 10.0;
+const MIN_GUESSES_SINGLE_CHAR: f64 = 10.0;
+const MIN_GUESSES_MULTI_CHAR: f64 = 50.0;
+/// Added per extra pattern so that many small patterns do not look cheaper than one.
+const MIN_GUESSES_BEFORE_GROWING_SEQUENCE: f64 = 10_000.0;
+const MIN_WORD_CHARS: usize = 3;
+const MAX_SEQUENCE_DELTA: i32 = 5;
+/// Guessers try years around the present first.
+const MIN_YEAR_SPACE: i32 = 20;
+const L33T_MAX_COMBINATIONS: usize = 64;
+
+/// Ranked by frequency in leaked password lists.
+#[rustfmt::skip]
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456", "password", "123456789", "12345678", "12345", "qwerty", "1234567", "111111",
+    "1234567890", "123123", "abc123", "1234", "password1", "iloveyou", "1q2w3e4r", "000000",
+    "qwerty123", "zaq12wsx", "dragon", "sunshine", "princess", "letmein", "654321", "monkey",
+    "1qaz2wsx", "123321", "qwertyuiop", "superman", "asdfghjkl", "trustno1", "football",
+    "baseball", "welcome", "shadow", "master", "michael", "jennifer", "hunter", "jordan",
+    "harley", "ranger", "buster", "soccer", "hockey", "killer", "george", "charlie", "andrew",
+    "michelle", "love", "jessica", "pepper", "daniel", "access", "123qwe", "starwars",
+    "mustang", "freedom", "whatever", "qazwsx", "ninja", "azerty", "solo", "loveme", "flower",
+    "hello", "hottie", "passw0rd", "admin", "login", "welcome1", "secret", "batman", "tigger",
+    "cheese", "computer", "maggie", "summer", "ginger", "joshua", "thomas", "robert", "matrix",
+    "cookie", "chocolate", "samsung", "nicole", "purple", "orange", "banana", "pokemon",
+    "internet", "snoopy", "butterfly", "qwe123", "changeme", "default", "guest", "root", "toor",
+    "test", "pass", "abcd1234", "aa123456", "q1w2e3r4", "1qazxsw2", "zxcvbnm", "asdfgh",
+    "password123", "admin123", "iloveu", "666666", "121212", "7777777", "987654321", "qwertz",
+    "letmein1", "football1", "monkey1", "dragon1", "master1", "abcdef", "abcdefg", "147258369",
+    "159753", "102030", "112233", "aaaaaa", "asdf", "qwer", "zxcv", "p@ssw0rd", "p@ssword",
+    "pa55word", "mypassword", "secret1",
+];
+
+/// Common English words and first names, most frequent first.
+#[rustfmt::skip]
+const COMMON_WORDS: &[&str] = &[
+    "the", "you", "and", "love", "this", "that", "baby", "angel", "god", "money", "blue", "red",
+    "black", "green", "star", "sun", "moon", "king", "queen", "winter", "spring", "autumn",
+    "house", "home", "family", "friend", "happy", "life", "world", "music", "game", "fire",
+    "water", "tiger", "lion", "eagle", "horse", "apple", "coffee", "pizza", "magic", "power",
+    "sweet", "heart", "dream", "forever", "girl", "boy", "man", "woman", "lady", "prince",
+    "word", "correct", "battery", "staple", "mother", "father", "sister", "brother", "jesus",
+    "christ", "heaven", "hell", "devil", "super", "hero", "spider", "iron", "rock", "metal",
+    "gold", "silver", "diamond", "crystal", "rose", "lily", "daisy", "tree", "forest", "river",
+    "ocean", "beach", "island", "mountain", "snow", "rain", "storm", "thunder", "light", "dark",
+    "night", "day", "morning", "time", "garden", "kitty", "puppy", "bear", "wolf", "fox",
+    "shark", "snake", "bird", "fish", "cherry", "lemon", "peach", "berry", "candy", "cake",
+    "sugar", "honey", "bunny", "teddy", "pirate", "knight", "wizard", "castle", "guitar",
+    "piano", "dance", "party", "beer", "vodka", "whisky", "car", "ford", "honda", "toyota",
+    "ferrari", "london", "paris", "berlin", "tokyo", "america", "canada", "france", "iceland",
+    "phone", "school", "teacher", "doctor", "secret", "private", "david", "john", "james",
+    "anna", "maria", "sarah", "emma", "olivia", "alex", "chris", "ashley", "matthew", "taylor",
+    "william", "jack", "harry", "sophie", "laura", "julia", "peter", "paul", "mark", "lucas",
+    "max", "leo", "mia", "lisa", "kevin", "jason", "justin", "brandon", "ryan", "eric", "adam",
+    "troubadour", "monday", "friday", "sunday", "january", "december",
+];
+
+const QWERTY: [&str; 4] = [
+    "`1234567890-=",
+    "qwertyuiop[]\\",
+    "asdfghjkl;'",
+    "zxcvbnm,./",
+];
+const QWERTY_SHIFTED: [&str; 4] = [
+    "~!@#$%^&*()_+",
+    "QWERTYUIOP{}|",
+    "ASDFGHJKL:\"",
+    "ZXCVBNM<>?",
+];
+const KEYBOARD_KEYS: f64 = 47.0;
+/// Mean number of neighbours per key on the slanted grid above.
+const KEYBOARD_AVERAGE_DEGREE: f64 = 4.6;
+
+/// Letters and the symbols commonly typed in their place.
+const L33T_TABLE: &[(char, &str)] = &[
+    ('a', "4@"),
+    ('b', "8"),
+    ('c', "({[<"),
+    ('e', "3"),
+    ('g', "69"),
+    ('i', "1!|"),
+    ('l', "1|7"),
+    ('o', "0"),
+    ('s', "$5"),
+    ('t', "+7"),
+    ('x', "%"),
+    ('z', "2"),
+];
+
+/// Date layouts without separators: split points for each token length.
+const DATE_SPLITS: [(usize, &[(usize, usize)]); 5] = [
+    (4, &[(1, 2), (2, 3)]),
+    (5, &[(1, 3), (2, 3)]),
+    (6, &[(1, 2), (2, 4), (4, 5)]),
+    (7, &[(1, 3), (2, 3), (4, 5), (4, 6)]),
+    (8, &[(2, 4), (4, 6)]),
+];
+const DATE_SEPARATORS: &str = " /\\_.-";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternKind {
+    Dictionary,
+    Reversed,
+    L33t,
+    Spatial,
+    Sequence,
+    Repeat,
+    Year,
+    Date,
+    Bruteforce,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordList {
+    Passwords,
+    Words,
+}
+
+/// One guessable stretch of the password, `start..=end` in characters.
+#[derive(Debug, Clone)]
+pub struct PatternMatch {
+    pub kind: PatternKind,
+    pub start: usize,
+    pub end: usize,
+    pub token: String,
+    pub guesses_log10: f64,
+    /// Dictionary entry behind dictionary, reversed and l33t matches.
+    pub word: Option<(WordList, String, usize)>,
+    /// Direction changes of a keyboard walk.
+    pub turns: usize,
+    /// Repeated unit of a repeat match.
+    pub base: Option<String>,
+}
+
+impl PatternMatch {
+    fn new(kind: PatternKind, chars: &[char], start: usize, end: usize, guesses: f64) -> Self {
+        Self {
+            kind,
+            start,
+            end,
+            token: chars[start..=end].iter().collect(),
+            guesses_log10: guesses.log10(),
+            word: None,
+            turns: 0,
+            base: None,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.end - self.start + 1
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StrengthReport {
+    pub length: usize,
+    pub guesses_log10: f64,
+    /// 0 (too guessable) to 4 (very unguessable), on zxcvbn's thresholds.
+    pub score: u8,
+    pub sequence: Vec<PatternMatch>,
+    /// Locale keys.
+    pub warning: Option<&'static str>,
+    pub suggestions: Vec<&'static str>,
+}
+
+impl StrengthReport {
+    pub fn entropy_bits(&self) -> f64 {
+        self.guesses_log10 * std::f64::consts::LOG2_10
+    }
+
+    /// Seconds an attacker making `guesses_per_second` needs, at worst.
+    pub fn crack_seconds(&self, guesses_per_second: f64) -> f64 {
+        10f64.powf(self.guesses_log10 - guesses_per_second.log10())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PasswordStrengthState {
+    pub password: String,
+    /// Shows the password and the matched tokens in clear.
+    pub show: bool,
+    pub report: Option<StrengthReport>,
+    pub error: Option<String>,
+}
+
+impl PasswordStrengthState {
+    pub const fn new() -> Self {
+        Self {
+            password: String::new(),
+            show: false,
+            report: None,
+            error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn check(&mut self, password: String) {
+        self.password = password;
+        self.report = None;
+        self.error = None;
+        if self.password.is_empty() {
+            return;
+        }
+        match analyze(&self.password) {
+            Ok(report) => self.report = Some(report),
+            Err(e) => self.error = Some(e),
+        }
+    }
+}
+
+pub fn analyze(password: &str) -> Result<StrengthReport, String> {
+    analyze_at(password, Utc::now().year())
+}
+
+/// Estimate with years measured from `reference_year`.
+pub fn analyze_at(password: &str, reference_year: i32) -> Result<StrengthReport, String> {
+    let chars: Vec<char> = password.chars().collect();
+    if chars.len() > MAX_PASSWORD_CHARS {
+        return Err("password_too_long".into());
+    }
+    let mut units = HashMap::new();
+    let (guesses_log10, sequence) = estimate(&chars, reference_year, &mut units);
+    let score = match guesses_log10 {
+        g if g < 3.0 => 0,
+        g if g < 6.0 => 1,
+        g if g < 8.0 => 2,
+        g if g < 10.0 => 3,
+        _ => 4,
+    };
+    let (warning, suggestions) = feedback(score, &sequence, chars.len());
+    Ok(StrengthReport {
+        length: chars.len(),
+        guesses_log10,
+        score,
+        sequence,
+        warning,
+        suggestions,
+    })
+}
+
+/// Guesses (log10) of the cheapest cover of `chars`, and that cover. `units` caches
+/// the estimates of repeated units, which are estimated recursively.
+fn estimate(
+    chars: &[char],
+    year: i32,
+    units: &mut HashMap<String, f64>,
+) -> (f64, Vec<PatternMatch>) {
+    if chars.is_empty() {
+        return (0.0, Vec::new());
+    }
+    let mut matches = Vec::new();
+    dictionary_matches(chars, &mut matches);
+    spatial_matches(chars, &mut matches);
+    sequence_matches(chars, &mut matches);
+    date_matches(chars, year, &mut matches);
+    repeat_matches(chars, year, units, &mut matches);
+    most_guessable_sequence(chars, matches)
+}
+
+struct Step {
+    /// log10 of the product of the guesses of the steps so far.
+    product: f64,
+    /// log10 of the total guesses of a cover made of these steps.
+    total: f64,
+    pattern: PatternMatch,
+}
+
+/// zxcvbn's search: for each end position and pattern count, the cover with the fewest
+/// guesses, where `l` patterns cost `l! * product + D^(l - 1)`.
+fn most_guessable_sequence(chars: &[char], matches: Vec<PatternMatch>) -> (f64, Vec<PatternMatch>) {
+    let n = chars.len();
+    let mut by_end: Vec<Vec<PatternMatch>> = vec![Vec::new(); n];
+    for m in matches {
+        by_end[m.end].push(m);
+    }
+    let mut best: Vec<BTreeMap<usize, Step>> = (0..n).map(|_| BTreeMap::new()).collect();
+    for (k, ending) in by_end.into_iter().enumerate() {
+        for m in ending {
+            if m.start == 0 {
+                update(&mut best, &m, 1, 0.0, n);
+            } else {
+                let previous: Vec<(usize, f64)> = best[m.start - 1]
+                    .iter()
+                    .map(|(&l, s)| (l, s.product))
+                    .collect();
+                for (l, product) in previous {
+                    update(&mut best, &m, l + 1, product, n);
+                }
+            }
+        }
+        update(&mut best, &bruteforce(chars, 0, k), 1, 0.0, n);
+        for i in 1..=k {
+            // Two brute force stretches in a row are one longer stretch.
+            let previous: Vec<(usize, f64)> = best[i - 1]
+                .iter()
+                .filter(|(_, s)| s.pattern.kind != PatternKind::Bruteforce)
+                .map(|(&l, s)| (l, s.product))
+                .collect();
+            let stretch = bruteforce(chars, i, k);
+            for (l, product) in previous {
+                update(&mut best, &stretch, l + 1, product, n);
+            }
+        }
+    }
+
+    let Some((&count, last)) = best[n - 1]
+        .iter()
+        .min_by(|a, b| a.1.total.total_cmp(&b.1.total))
+    else {
+        return (0.0, Vec::new());
+    };
+    let guesses = last.total;
+    let mut sequence = Vec::with_capacity(count);
+    let (mut k, mut l) = (n - 1, count);
+    while let Some(step) = best[k].get(&l) {
+        sequence.push(step.pattern.clone());
+        if step.pattern.start == 0 {
+            break;
+        }
+        k = step.pattern.start - 1;
+        l -= 1;
+    }
+    sequence.reverse();
+    (guesses, sequence)
+}
+
+fn update(
+    best: &mut [BTreeMap<usize, Step>],
+    pattern: &PatternMatch,
+    l: usize,
+    previous_product: f64,
+    n: usize,
+) {
+    let mut guesses = pattern.guesses_log10;
+    if pattern.len() < n {
+        let floor = if pattern.len() == 1 {
+            MIN_GUESSES_SINGLE_CHAR
+        } else {
+            MIN_GUESSES_MULTI_CHAR
+        };
+        guesses = guesses.max(floor.log10());
+    }
+    let product = previous_product + guesses;
+    let total = log10_add(
+        log10_factorial(l) + product,
+        MIN_GUESSES_BEFORE_GROWING_SEQUENCE.log10() * (l - 1) as f64,
+    );
+    let slot = &mut best[pattern.end];
+    if slot.range(..=l).any(|(_, s)| s.total <= total) {
+        return;
+    }
+    let mut pattern = pattern.clone();
+    pattern.guesses_log10 = guesses;
+    slot.insert(
+        l,
+        Step {
+            product,
+            total,
+            pattern,
+        },
+    );
+}
+
+fn bruteforce(chars: &[char], start: usize, end: usize) -> PatternMatch {
+    let len = end - start + 1;
+    let floor = if len == 1 {
+        MIN_GUESSES_SINGLE_CHAR + 1.0
+    } else {
+        MIN_GUESSES_MULTI_CHAR + 1.0
+    };
+    let mut m = PatternMatch::new(PatternKind::Bruteforce, chars, start, end, 1.0);
+    m.guesses_log10 = (len as f64 * BRUTEFORCE_CARDINALITY.log10()).max(floor.log10());
+    m
+}
+
+struct Dictionary {
+    ranks: HashMap<&'static str, (WordList, usize)>,
+    longest: usize,
+}
+
+fn dictionary() -> &'static Dictionary {
+    static DICTIONARY: OnceLock<Dictionary> = OnceLock::new();
+    DICTIONARY.get_or_init(|| {
+        let mut ranks = HashMap::new();
+        for (list, words) in [
+            (WordList::Passwords, COMMON_PASSWORDS),
+            (WordList::Words, COMMON_WORDS),
+        ] {
+            for (i, word) in words.iter().enumerate() {
+                ranks.entry(*word).or_insert((list, i + 1));
+            }
+        }
+        let longest = ranks.keys().map(|w| w.chars().count()).max().unwrap_or(0);
+        Dictionary { ranks, longest }
+    })
+}
+
+fn dictionary_matches(chars: &[char], out: &mut Vec<PatternMatch>) {
+    let dict = dictionary();
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    let n = chars.len();
+    for i in 0..n {
+        for j in (i + MIN_WORD_CHARS - 1)..n.min(i + dict.longest) {
+            let token = &lower[i..=j];
+            let word: String = token.iter().collect();
+            if let Some(&(list, rank)) = dict.ranks.get(word.as_str()) {
+                let guesses = rank as f64 * uppercase_variations(&chars[i..=j]);
+                let mut m = PatternMatch::new(PatternKind::Dictionary, chars, i, j, guesses);
+                m.word = Some((list, word.clone(), rank));
+                out.push(m);
+            }
+            let reversed: String = token.iter().rev().collect();
+            if reversed != word {
+                if let Some(&(list, rank)) = dict.ranks.get(reversed.as_str()) {
+                    let guesses = rank as f64 * uppercase_variations(&chars[i..=j]) * 2.0;
+                    let mut m = PatternMatch::new(PatternKind::Reversed, chars, i, j, guesses);
+                    m.word = Some((list, reversed, rank));
+                    out.push(m);
+                }
+            }
+            l33t_matches(chars, token, i, j, out);
+        }
+    }
+}
+
+/// Dictionary words spelled with look-alike symbols. Ambiguous symbols such as `1`
+/// (i or l) are tried both ways.
+fn l33t_matches(chars: &[char], token: &[char], i: usize, j: usize, out: &mut Vec<PatternMatch>) {
+    let mut symbols: Vec<(char, Vec<char>)> = Vec::new();
+    for &c in token {
+        if symbols.iter().any(|(s, _)| *s == c) {
+            continue;
+        }
+        let letters: Vec<char> = L33T_TABLE
+            .iter()
+            .filter(|(_, subs)| subs.contains(c))
+            .map(|(letter, _)| *letter)
+            .collect();
+        if !letters.is_empty() {
+            symbols.push((c, letters));
+        } else if !c.is_alphabetic() {
+            return;
+        }
+    }
+    if symbols.is_empty() {
+        return;
+    }
+    let combinations: usize = symbols.iter().map(|(_, l)| l.len()).product();
+    let dict = dictionary();
+    for mut index in 0..combinations.min(L33T_MAX_COMBINATIONS) {
+        let mut subs = Vec::with_capacity(symbols.len());
+        for (symbol, letters) in &symbols {
+            subs.push((*symbol, letters[index % letters.len()]));
+            index /= letters.len();
+        }
+        let word: String = token
+            .iter()
+            .map(|c| {
+                subs.iter()
+                    .find(|(s, _)| s == c)
+                    .map_or(*c, |(_, letter)| *letter)
+            })
+            .collect();
+        let Some(&(list, rank)) = dict.ranks.get(word.as_str()) else {
+            continue;
+        };
+        let mut variations = 1.0;
+        for (symbol, letter) in &subs {
+            let subbed = token.iter().filter(|c| *c == symbol).count();
+            let plain = token.iter().filter(|c| *c == letter).count();
+            variations *= if plain == 0 {
+                2.0
+            } else {
+                (1..=subbed.min(plain))
+                    .map(|k| n_choose_k(subbed + plain, k))
+                    .sum::<f64>()
+            };
+        }
+        let guesses = rank as f64 * uppercase_variations(&chars[i..=j]) * variations;
+        let mut m = PatternMatch::new(PatternKind::L33t, chars, i, j, guesses);
+        m.word = Some((list, word, rank));
+        out.push(m);
+    }
+}
+
+/// Ways to capitalize a word: all lower costs nothing, a capital at either end or all
+/// capitals doubles, anything else counts the mixes.
+fn uppercase_variations(token: &[char]) -> f64 {
+    let upper = token.iter().filter(|c| c.is_uppercase()).count();
+    let lower = token.iter().filter(|c| c.is_lowercase()).count();
+    if upper == 0 {
+        return 1.0;
+    }
+    let first = token.first().is_some_and(|c| c.is_uppercase());
+    let last = token.last().is_some_and(|c| c.is_uppercase());
+    if lower == 0 || (upper == 1 && (first || last)) {
+        return 2.0;
+    }
+    (1..=upper.min(lower))
+        .map(|k| n_choose_k(upper + lower, k))
+        .sum()
+}
+
+fn key_position(c: char) -> Option<(i32, i32, bool)> {
+    for (shifted, rows) in [(false, &QWERTY), (true, &QWERTY_SHIFTED)] {
+        for (row, keys) in rows.iter().enumerate() {
+            if let Some(col) = keys.chars().position(|k| k == c) {
+                return Some((row as i32, col as i32, shifted));
+            }
+        }
+    }
+    None
+}
+
+/// Runs of three or more adjacent keys on a QWERTY layout.
+fn spatial_matches(chars: &[char], out: &mut Vec<PatternMatch>) {
+    let n = chars.len();
+    let mut i = 0;
+    while i + 2 < n {
+        let Some(mut previous) = key_position(chars[i]) else {
+            i += 1;
+            continue;
+        };
+        let mut shifted = usize::from(previous.2);
+        let mut turns = 0;
+        let mut direction = None;
+        let mut j = i;
+        while j + 1 < n {
+            let Some(next) = key_position(chars[j + 1]) else {
+                break;
+            };
+            // Each key touches two keys in its row and two in each neighbouring row.
+            let step = (next.0 - previous.0, next.1 - previous.1);
+            if !matches!(
+                step,
+                (0, -1) | (0, 1) | (-1, 0) | (-1, 1) | (1, -1) | (1, 0)
+            ) {
+                break;
+            }
+            if direction != Some(step) {
+                turns += 1;
+                direction = Some(step);
+            }
+            shifted += usize::from(next.2);
+            previous = next;
+            j += 1;
+        }
+        if j - i + 1 >= 3 {
+            let len = j - i + 1;
+            let mut guesses = 0.0;
+            for l in 2..=len {
+                for t in 1..=turns.min(l - 1) {
+                    guesses += n_choose_k(l - 1, t - 1)
+                        * KEYBOARD_KEYS
+                        * KEYBOARD_AVERAGE_DEGREE.powi(t as i32);
+                }
+            }
+            let unshifted = len - shifted;
+            if shifted > 0 {
+                guesses *= if unshifted == 0 {
+                    2.0
+                } else {
+                    (1..=shifted.min(unshifted))
+                        .map(|k| n_choose_k(len, k))
+                        .sum::<f64>()
+                };
+            }
+            let mut m = PatternMatch::new(PatternKind::Spatial, chars, i, j, guesses);
+            m.turns = turns;
+            out.push(m);
+            i = j;
+        } else {
+            i += 1;
+        }
+    }
+}
+
+fn char_class(c: char) -> Option<u8> {
+    match c {
+        'a'..='z' => Some(0),
+        'A'..='Z' => Some(1),
+        '0'..='9' => Some(2),
+        _ => None,
+    }
+}
+
+/// Letters or digits with a constant small step, such as `abcd`, `9753` or `ZYX`.
+fn sequence_matches(chars: &[char], out: &mut Vec<PatternMatch>) {
+    let n = chars.len();
+    let mut i = 0;
+    while i + 2 < n {
+        let class = char_class(chars[i]);
+        let delta = chars[i + 1] as i32 - chars[i] as i32;
+        if class.is_none()
+            || class != char_class(chars[i + 1])
+            || delta == 0
+            || delta.abs() > MAX_SEQUENCE_DELTA
+        {
+            i += 1;
+            continue;
+        }
+        let mut j = i + 1;
+        while j + 1 < n
+            && char_class(chars[j + 1]) == class
+            && chars[j + 1] as i32 - chars[j] as i32 == delta
+        {
+            j += 1;
+        }
+        if j - i + 1 < 3 {
+            i += 1;
+            continue;
+        }
+        let base = match chars[i] {
+            'a' | 'A' | 'z' | 'Z' | '0' | '1' | '9' => 4.0,
+            c if c.is_ascii_digit() => 10.0,
+            _ => 26.0,
+        };
+        let direction = if delta > 0 { 1.0 } else { 2.0 };
+        let guesses = base * direction * (j - i + 1) as f64;
+        out.push(PatternMatch::new(
+            PatternKind::Sequence,
+            chars,
+            i,
+            j,
+            guesses,
+        ));
+        i = j;
+    }
+}
+
+/// The longest run of a repeated unit starting at each position; the unit itself is
+/// estimated like a password of its own.
+fn repeat_matches(
+    chars: &[char],
+    year: i32,
+    units: &mut HashMap<String, f64>,
+    out: &mut Vec<PatternMatch>,
+) {
+    let n = chars.len();
+    for i in 0..n {
+        let mut best: Option<(usize, usize)> = None;
+        for unit in 1..=(n - i) / 2 {
+            let mut count = 1;
+            while i + (count + 1) * unit <= n
+                && chars[i + count * unit..i + (count + 1) * unit] == chars[i..i + unit]
+            {
+                count += 1;
+            }
+            if count >= 2 && best.is_none_or(|(u, c)| unit * count > u * c) {
+                best = Some((unit, count));
+            }
+        }
+        let Some((unit, count)) = best else {
+            continue;
+        };
+        let base: String = chars[i..i + unit].iter().collect();
+        let unit_guesses = match units.get(&base) {
+            Some(&g) => g,
+            None => {
+                let (g, _) = estimate(&chars[i..i + unit], year, units);
+                units.insert(base.clone(), g);
+                g
+            }
+        };
+        let end = i + unit * count - 1;
+        let mut m = PatternMatch::new(PatternKind::Repeat, chars, i, end, count as f64);
+        m.guesses_log10 += unit_guesses;
+        m.base = Some(base);
+        out.push(m);
+    }
+}
+
+fn two_digit_year(y: i32) -> i32 {
+    if y > 50 {
+        1900 + y
+    } else {
+        2000 + y
+    }
+}
+
+/// Year of the most plausible reading of three numbers as a day, month and year.
+fn date_year(parts: [i32; 3], reference: i32) -> Option<i32> {
+    let valid_day_month = |a: i32, b: i32| {
+        ((1..=31).contains(&a) && (1..=12).contains(&b))
+            || ((1..=31).contains(&b) && (1..=12).contains(&a))
+    };
+    let [a, b, c] = parts;
+    let mut years = Vec::new();
+    for (year, x, y) in [(c, a, b), (a, b, c)] {
+        if !valid_day_month(x, y) {
+            continue;
+        }
+        if (1000..=2050).contains(&year) {
+            years.push(year);
+        } else if (0..=99).contains(&year) {
+            years.push(two_digit_year(year));
+        }
+    }
+    years.into_iter().min_by_key(|y| (y - reference).abs())
+}
+
+fn year_space(year: i32, reference: i32) -> f64 {
+    (year - reference).abs().max(MIN_YEAR_SPACE) as f64
+}
+
+/// Recent years alone, and dates written as digits with or without separators.
+fn date_matches(chars: &[char], reference: i32, out: &mut Vec<PatternMatch>) {
+    let n = chars.len();
+    let number = |from: usize, to: usize| -> Option<i32> {
+        let s: String = chars[from..to].iter().collect();
+        s.parse().ok()
+    };
+    for i in 0..n {
+        if i + 4 <= n && chars[i..i + 4].iter().all(char::is_ascii_digit) {
+            if let Some(year) = number(i, i + 4).filter(|y| (1900..=2039).contains(y)) {
+                let guesses = year_space(year, reference);
+                out.push(PatternMatch::new(
+                    PatternKind::Year,
+                    chars,
+                    i,
+                    i + 3,
+                    guesses,
+                ));
+            }
+        }
+        for (len, splits) in DATE_SPLITS {
+            if i + len > n || !chars[i..i + len].iter().all(char::is_ascii_digit) {
+                continue;
+            }
+            let year = splits
+                .iter()
+                .filter_map(|&(k, l)| {
+                    let parts = [
+                        number(i, i + k)?,
+                        number(i + k, i + l)?,
+                        number(i + l, i + len)?,
+                    ];
+                    date_year(parts, reference)
+                })
+                .min_by_key(|y| (y - reference).abs());
+            if let Some(year) = year {
+                let guesses = 365.0 * year_space(year, reference);
+                out.push(PatternMatch::new(
+                    PatternKind::Date,
+                    chars,
+                    i,
+                    i + len - 1,
+                    guesses,
+                ));
+            }
+        }
+        for len in 6..=10 {
+            if i + len > n {
+                break;
+            }
+            let token = &chars[i..i + len];
+            let Some(first) = token.iter().position(|c| DATE_SEPARATORS.contains(*c)) else {
+                continue;
+            };
+            let separator = token[first];
+            let pieces: Vec<&[char]> = token.split(|c| *c == separator).collect();
+            let lengths_ok = pieces.len() == 3
+                && (1..=4).contains(&pieces[0].len())
+                && (1..=2).contains(&pieces[1].len())
+                && (1..=4).contains(&pieces[2].len());
+            if !lengths_ok || !pieces.iter().all(|p| p.iter().all(char::is_ascii_digit)) {
+                continue;
+            }
+            let parse = |p: &[char]| p.iter().collect::<String>().parse::<i32>().ok();
+            let parts = match (parse(pieces[0]), parse(pieces[1]), parse(pieces[2])) {
+                (Some(a), Some(b), Some(c)) => [a, b, c],
+                _ => continue,
+            };
+            if let Some(year) = date_year(parts, reference) {
+                let guesses = 365.0 * year_space(year, reference) * 4.0;
+                out.push(PatternMatch::new(
+                    PatternKind::Date,
+                    chars,
+                    i,
+                    i + len - 1,
+                    guesses,
+                ));
+            }
+        }
+    }
+}
+
+fn n_choose_k(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+fn log10_factorial(n: usize) -> f64 {
+    (2..=n).map(|i| (i as f64).log10()).sum()
+}
+
+fn log10_add(a: f64, b: f64) -> f64 {
+    let (high, low) = if a > b { (a, b) } else { (b, a) };
+    high + (1.0 + 10f64.powf(low - high)).log10()
+}
+
+/// Warning about the most telling pattern and tips, as locale keys, for weak passwords.
+fn feedback(
+    score: u8,
+    sequence: &[PatternMatch],
+    length: usize,
+) -> (Option<&'static str>, Vec<&'static str>) {
+    if sequence.is_empty() || score > 2 {
+        return (None, Vec::new());
+    }
+    let whole = |m: &PatternMatch| m.len() == length;
+    let warning = sequence
+        .iter()
+        .filter(|m| m.kind != PatternKind::Bruteforce)
+        .max_by_key(|m| m.len())
+        .and_then(|m| match (m.kind, &m.word) {
+            (PatternKind::Dictionary, Some((WordList::Passwords, _, rank))) if whole(m) => {
+                Some(match *rank {
+                    0..=10 => "password_warn_top10",
+                    11..=100 => "password_warn_top100",
+                    _ => "password_warn_common",
+                })
+            }
+            (_, Some((WordList::Passwords, _, _))) => Some("password_warn_similar_common"),
+            (_, Some((WordList::Words, _, _))) if whole(m) => Some("password_warn_word"),
+            (PatternKind::Spatial, _) if m.turns == 1 => Some("password_warn_keyboard_row"),
+            (PatternKind::Spatial, _) => Some("password_warn_keyboard_pattern"),
+            (PatternKind::Repeat, _) if m.base.as_ref().is_some_and(|b| b.chars().count() == 1) => {
+                Some("password_warn_repeat_char")
+            }
+            (PatternKind::Repeat, _) => Some("password_warn_repeat"),
+            (PatternKind::Sequence, _) => Some("password_warn_sequence"),
+            (PatternKind::Year, _) => Some("password_warn_recent_year"),
+            (PatternKind::Date, _) => Some("password_warn_date"),
+            _ => None,
+        });
+
+    let mut suggestions = vec!["password_tip_add_words"];
+    for m in sequence {
+        let tip = match m.kind {
+            PatternKind::Dictionary | PatternKind::Reversed | PatternKind::L33t => {
+                let chars: Vec<char> = m.token.chars().collect();
+                let letters = chars.iter().filter(|c| c.is_alphabetic()).count();
+                let upper = chars.iter().filter(|c| c.is_uppercase()).count();
+                if upper > 0 && upper == letters {
+                    Some("password_tip_all_caps")
+                } else if chars.first().is_some_and(|c| c.is_uppercase()) {
+                    Some("password_tip_capitals")
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        let kind_tip = match m.kind {
+            PatternKind::Reversed => Some("password_tip_reversed"),
+            PatternKind::L33t => Some("password_tip_l33t"),
+            PatternKind::Spatial => Some("password_tip_keyboard"),
+            PatternKind::Repeat => Some("password_tip_repeats"),
+            PatternKind::Sequence => Some("password_tip_sequences"),
+            PatternKind::Year | PatternKind::Date => Some("password_tip_dates"),
+            _ => None,
+        };
+        for key in [tip, kind_tip].into_iter().flatten() {
+            if !suggestions.contains(&key) {
+                suggestions.push(key);
+            }
+        }
+    }
+    (warning, suggestions)
+}
+
+fn format_duration(seconds: f64) -> String {
+    const YEAR: f64 = 31_556_952.0;
+    const UNITS: [(f64, &str); 5] = [
+        (YEAR, "password_time_years"),
+        (2_629_746.0, "password_time_months"),
+        (86_400.0, "password_time_days"),
+        (3_600.0, "password_time_hours"),
+        (60.0, "password_time_minutes"),
+    ];
+    if seconds < 1.0 {
+        return t!("password_time_instant").to_string();
+    }
+    if seconds >= 100.0 * YEAR {
+        return t!("password_time_centuries").to_string();
+    }
+    // Switch units at two of them so counts read as plurals.
+    for (unit, key) in UNITS {
+        if seconds >= unit * 2.0 {
+            let count = (seconds / unit).round() as u64;
+            return t!(key, count = count).to_string();
+        }
+    }
+    let count = (seconds.round() as u64).max(2);
+    t!("password_time_seconds", count = count).to_string()
+}
+
+fn kind_label(m: &PatternMatch) -> String {
+    match (m.kind, &m.word) {
+        (PatternKind::Dictionary, Some((WordList::Passwords, _, _))) => {
+            t!("password_kind_password")
+        }
+        (PatternKind::Dictionary, _) => t!("password_kind_word"),
+        (PatternKind::Reversed, _) => t!("password_kind_reversed"),
+        (PatternKind::L33t, _) => t!("password_kind_l33t"),
+        (PatternKind::Spatial, _) => t!("password_kind_keyboard"),
+        (PatternKind::Sequence, _) => t!("password_kind_sequence"),
+        (PatternKind::Repeat, _) => t!("password_kind_repeat"),
+        (PatternKind::Year, _) => t!("password_kind_year"),
+        (PatternKind::Date, _) => t!("password_kind_date"),
+        (PatternKind::Bruteforce, _) => t!("password_kind_bruteforce"),
+    }
+    .to_string()
+}
+
+fn to_value_or_text<T: Serialize>(value: T, context: &str) -> Value {
+    serde_json::to_value(value).unwrap_or_else(|e| {
+        json!({
+            "type": "Text",
+            "text": format!("{context}_serialize_error:{e}")
+        })
+    })
+}
+
+pub fn render_password_strength_screen(state: &AppState) -> Value {
+    let strength = &state.password_strength;
+    let mut children = vec![
+        to_value_or_text(
+            UiText::new(&t!("password_strength_title")).size(20.0),
+            "password_strength_title",
+        ),
+        to_value_or_text(
+            UiText::new(&t!("password_strength_description")).size(14.0),
+            "password_strength_subtitle",
+        ),
+        to_value_or_text(
+            UiTextInput::new("password_input")
+                .text(&strength.password)
+                .hint(&t!("password_strength_hint"))
+                .single_line(true)
+                .password_mask(!strength.show)
+                .debounce_ms(150)
+                .action_on_submit("password_strength_check")
+                .content_description("password_input"),
+            "password_input",
+        ),
+        to_value_or_text(
+            UiCheckbox::new(&t!("password_strength_show"), "password_show")
+                .checked(strength.show)
+                .action("password_strength_check"),
+            "password_show",
+        ),
+    ];
+
+    if let Some(err) = &strength.error {
+        children.push(to_value_or_text(
+            UiText::new(&error_text(err))
+                .size(12.0)
+                .content_description("password_strength_error"),
+            "password_strength_error",
+        ));
+    }
+
+    if let Some(report) = &strength.report {
+        let score_label = t!(SCORE_LABELS[usize::from(report.score)]);
+        let score_text = t!("password_score", score = report.score, label = score_label);
+        children.push(to_value_or_text(
+            UiProgressBar::new((report.score + 1) as f32 / 5.0)
+                .label(&score_text)
+                .content_description("password_score"),
+            "password_score",
+        ));
+        children.push(to_value_or_text(
+            UiText::new(&t!(
+                "password_guesses",
+                exponent = format!("{:.1}", report.guesses_log10),
+                bits = format!("{:.0}", report.entropy_bits())
+            ))
+            .size(12.0)
+            .content_description("password_guesses"),
+            "password_guesses",
+        ));
+        if let Some(warning) = report.warning {
+            children.push(to_value_or_text(
+                UiText::new(&t!(warning))
+                    .size(14.0)
+                    .content_description("password_warning"),
+                "password_warning",
+            ));
+        }
+        for &tip in &report.suggestions {
+            children.push(to_value_or_text(
+                UiText::new(&format!("• {}", t!(tip))).size(12.0),
+                "password_tip",
+            ));
+        }
+
+        let crack_rows = CRACK_SCENARIOS
+            .iter()
+            .map(|&(key, rate)| {
+                let time = format_duration(report.crack_seconds(rate));
+                let line = format!("{}: {}", t!(key), time);
+                to_value_or_text(UiText::new(&line).size(12.0), "password_crack_time")
+            })
+            .collect();
+        let crack_title = t!("password_crack_title");
+        children.push(to_value_or_text(
+            UiCard::new(crack_rows).title(&crack_title).padding(12),
+            "password_crack_card",
+        ));
+
+        let pattern_rows = report
+            .sequence
+            .iter()
+            .map(|m| {
+                let kind = kind_label(m);
+                let line = if strength.show {
+                    t!("password_pattern_row", token = m.token, kind = kind)
+                } else {
+                    t!(
+                        "password_pattern_position",
+                        from = m.start + 1,
+                        to = m.end + 1,
+                        kind = kind
+                    )
+                };
+                to_value_or_text(UiText::new(&line).size(12.0), "password_pattern")
+            })
+            .collect();
+        let patterns_title = t!("password_patterns_title");
+        children.push(to_value_or_text(
+            UiCard::new(pattern_rows).title(&patterns_title).padding(12),
+            "password_patterns_card",
+        ));
+    }
+
+    maybe_push_back(&mut children, state);
+    to_value_or_text(
+        UiColumn::new(children).padding(20),
+        "password_strength_root",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YEAR: i32 = 2026;
+
+    fn kinds(report: &StrengthReport) -> Vec<PatternKind> {
+        report.sequence.iter().map(|m| m.kind).collect()
+    }
+
+    #[test]
+    fn common_passwords_and_their_disguises_score_low() {
+        let report = analyze_at("password", YEAR).unwrap();
+        assert_eq!(report.score, 0);
+        assert_eq!(kinds(&report), [PatternKind::Dictionary]);
+        assert_eq!(report.warning, Some("password_warn_top10"));
+
+        let report = analyze_at("P4ssw0rd", YEAR).unwrap();
+        assert!(report.score <= 1, "score {}", report.score);
+        assert_eq!(kinds(&report), [PatternKind::L33t]);
+        let (list, word, _) = report.sequence[0].word.clone().unwrap();
+        assert_eq!((list, word.as_str()), (WordList::Passwords, "password"));
+        assert!(report.suggestions.contains(&"password_tip_l33t"));
+        assert!(report.suggestions.contains(&"password_tip_capitals"));
+
+        let report = analyze_at("nogard", YEAR).unwrap();
+        assert_eq!(kinds(&report), [PatternKind::Reversed]);
+    }
+
+    #[test]
+    fn finds_keyboard_walks_sequences_and_repeats() {
+        let report = analyze_at("zxcvbnm,./", YEAR).unwrap();
+        assert_eq!(kinds(&report), [PatternKind::Spatial]);
+        assert_eq!(report.sequence[0].turns, 1);
+        assert_eq!(report.warning, Some("password_warn_keyboard_row"));
+
+        let report = analyze_at("lmnopq", YEAR).unwrap();
+        assert_eq!(kinds(&report), [PatternKind::Sequence]);
+        let report = analyze_at("97531", YEAR).unwrap();
+        assert_eq!(kinds(&report), [PatternKind::Sequence]);
+
+        let report = analyze_at("xq7xq7xq7xq7", YEAR).unwrap();
+        assert_eq!(kinds(&report), [PatternKind::Repeat]);
+        assert_eq!(report.sequence[0].base.as_deref(), Some("xq7"));
+        assert_eq!(report.warning, Some("password_warn_repeat"));
+        let bare = analyze_at("xq7", YEAR).unwrap();
+        assert!(report.guesses_log10 < bare.guesses_log10 + 1.0);
+    }
+
+    #[test]
+    fn finds_dates_and_years() {
+        let report = analyze_at("13.05.1987", YEAR).unwrap();
+        assert_eq!(kinds(&report), [PatternKind::Date]);
+        let expected = (365.0 * 39.0 * 4.0f64).log10();
+        assert!((report.guesses_log10 - expected).abs() < 0.01);
+
+        let report = analyze_at("19870513", YEAR).unwrap();
+        assert_eq!(kinds(&report), [PatternKind::Date]);
+
+        let report = analyze_at("dragon2024", YEAR).unwrap();
+        assert_eq!(kinds(&report), [PatternKind::Dictionary, PatternKind::Year]);
+        assert!(report.score <= 2, "score {}", report.score);
+        assert!(report.suggestions.contains(&"password_tip_dates"));
+    }
+
+    #[test]
+    fn random_passwords_score_high_and_limits_hold() {
+        let report = analyze_at("vX9#qL2!mR7&tB4@", YEAR).unwrap();
+        assert_eq!(report.score, 4);
+        assert!(report.warning.is_none());
+        assert!(report.suggestions.is_empty());
+        assert!(report.entropy_bits() > 40.0);
+        assert!(report.crack_seconds(1e10) > 1.0);
+
+        let covered: usize = report.sequence.iter().map(|m| m.len()).sum();
+        assert_eq!(covered, report.length);
+
+        assert!(analyze_at("", YEAR).unwrap().sequence.is_empty());
+        let long = "a".repeat(MAX_PASSWORD_CHARS + 1);
+        assert_eq!(analyze_at(&long, YEAR).unwrap_err(), "password_too_long");
+        assert!(analyze_at(&long[1..], YEAR).unwrap().score <= 1);
+    }
+}
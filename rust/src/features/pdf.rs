@@ -1,3 +1,4 @@
+use crate::error::error_text;
 use crate::features::storage::{output_dir_for, parse_file_uri_path};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -85,27 +86,42 @@ fn output_filename(source_uri: Option<&str>) -> String {
 use crate::state::{AppState, Screen};
 use crate::ui::{
     maybe_push_back, Button as UiButton, Column as UiColumn, PdfPagePicker as UiPdfPagePicker,
-    Text as UiText, VirtualList as UiVirtualList,
+    ReorderItem as UiReorderItem, ReorderableList as UiReorderableList, RichText as UiRichText,
+    Tabs as UiTabs, Text as UiText, VirtualList as UiVirtualList,
 };
 use std::collections::VecDeque;
 
-#[cfg(target_os = "android")]
 fn log_pdf_debug(message: &str) {
-    use android_log_sys::__android_log_write;
-    use std::ffi::CString;
+    crate::logging::debug("pdf", message);
+}
 
-    const ANDROID_LOG_DEBUG: i32 = 3; // matches android/log.h debug priority
+/// Pane of the PDF screen: page operations, document metadata or signing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PdfTab {
+    #[default]
+    Pages,
+    Metadata,
+    Signature,
+}
 
-    if let (Ok(tag), Ok(text)) = (CString::new("kistaverk"), CString::new(message)) {
-        unsafe {
-            __android_log_write(ANDROID_LOG_DEBUG, tag.as_ptr(), text.as_ptr());
+impl PdfTab {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim() {
+            "pages" => Some(PdfTab::Pages),
+            "metadata" => Some(PdfTab::Metadata),
+            "signature" => Some(PdfTab::Signature),
+            _ => None,
         }
     }
-}
 
-#[cfg(not(target_os = "android"))]
-fn log_pdf_debug(message: &str) {
-    eprintln!("[kistaverk][pdf][debug] {message}");
+    pub fn id(self) -> &'static str {
+        match self {
+            PdfTab::Pages => "pages",
+            PdfTab::Metadata => "metadata",
+            PdfTab::Signature => "signature",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -127,6 +143,8 @@ pub struct PdfState {
     pub signature_height_pt: Option<f64>,
     pub signature_grid_selection: Option<(u32, f64, f64)>,
     pub merge_queue: Vec<String>,
+    #[serde(default)]
+    pub tab: PdfTab,
 }
 
 impl PdfState {
@@ -149,6 +167,7 @@ impl PdfState {
             signature_grid_selection: None,
             preview_page: None,
             merge_queue: Vec::new(),
+            tab: PdfTab::Pages,
         }
     }
 
@@ -377,13 +396,17 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
         );
     }
 
+    let mut pages: Vec<Value> = Vec::new();
+    let mut metadata: Vec<Value> = Vec::new();
+    let mut signature: Vec<Value> = Vec::new();
+
     if let (Some(count), Some(uri)) = (state.pdf.page_count, state.pdf.source_uri.as_ref()) {
         children.push(
             serde_json::to_value(UiText::new(&format!("{}{}", t!("pdf_tools_pages_prefix"), count)).size(12.0)).unwrap(),
         );
 
         // Page picker rendered in Kotlin using PdfRenderer.
-        children.push(
+        pages.push(
             serde_json::to_value(
                 UiColumn::new(vec![serde_json::to_value(
                     UiPdfPagePicker::new(count, "pdf_selected_pages", uri)
@@ -396,7 +419,7 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
             .unwrap(),
         );
         let selected_len = state.pdf.selected_pages.len();
-        children.push(
+        pages.push(
             serde_json::to_value(
                 UiText::new(&format!("{}{}{}{}", t!("pdf_selected_summary_prefix"), selected_len, t!("pdf_selected_summary_middle"), count))
                     .size(12.0)
@@ -405,49 +428,45 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
             .unwrap(),
         );
 
-        let reorder_default: String = if !state.pdf.selected_pages.is_empty() {
-            state
-                .pdf
-                .selected_pages
-                .iter()
-                .map(|p| p.to_string())
-                .collect::<Vec<_>>()
-                .join(", ")
+        // A selection reorders just those pages; otherwise the whole document.
+        let reorder_pages: Vec<u32> = if !state.pdf.selected_pages.is_empty() {
+            state.pdf.selected_pages.clone()
         } else {
-            (1..=count)
-                .map(|p| p.to_string())
-                .collect::<Vec<_>>()
-                .join(", ")
+            (1..=count).collect()
         };
-        children.push(
-            serde_json::to_value(
-                crate::ui::TextInput::new("pdf_reorder_pages")
-                    .hint(&t!("pdf_reorder_pages_hint"))
-                    .text(&reorder_default)
-                    .single_line(true),
-            )
-            .unwrap(),
+        let page_prefix = t!("pdf_reorder_page_prefix");
+        let reorder_items = reorder_pages
+            .iter()
+            .map(|p| UiReorderItem::new(p.to_string(), format!("{page_prefix}{p}")))
+            .collect();
+        pages.push(
+            serde_json::to_value(UiRichText::new(&t!("pdf_reorder_pages_help")).size(12.0))
+                .unwrap(),
         );
-        children.push(
+        pages.push(
+            serde_json::to_value(UiReorderableList::new("pdf_reorder_pages", reorder_items))
+                .unwrap(),
+        );
+        pages.push(
             serde_json::to_value(
                 UiButton::new(&t!("pdf_reorder_pages_button"), "pdf_reorder").id("pdf_reorder_btn"),
             )
             .unwrap(),
         );
 
-        children.push(
+        pages.push(
             serde_json::to_value(
                 UiButton::new(&t!("pdf_extract_selected_pages_button"), "pdf_extract").id("pdf_extract_btn"),
             )
             .unwrap(),
         );
-        children.push(
+        pages.push(
             serde_json::to_value(
                 UiButton::new(&t!("pdf_delete_selected_pages_button"), "pdf_delete").id("pdf_delete_btn"),
             )
             .unwrap(),
         );
-        children.push(
+        pages.push(
             serde_json::to_value(
                 UiButton::new(&t!("pdf_merge_another_pdf_button"), "pdf_merge")
                     .id("pdf_merge_btn")
@@ -455,7 +474,7 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
             )
             .unwrap(),
         );
-        children.push(
+        pages.push(
             serde_json::to_value(
                 UiButton::new(&t!("pdf_open_viewer_button"), "pdf_preview_screen").id("pdf_preview_btn"),
             )
@@ -480,10 +499,10 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
                 .unwrap()
             })
             .collect();
-        children.push(
+        pages.push(
             serde_json::to_value(UiVirtualList::new(items).estimated_item_height(48)).unwrap(),
         );
-        children.push(
+        pages.push(
             serde_json::to_value(
                 UiButton::new(&t!("pdf_merge_batch_button"), "pdf_merge_batch").payload(json!({
                     "pdf_merge_paths": state.pdf.merge_queue
@@ -495,7 +514,7 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
 
     // Title editing
     if let Some(title) = &state.pdf.current_title {
-        children.push(
+        metadata.push(
             serde_json::to_value(
                 UiText::new(&format!("{}{}", t!("pdf_current_title_prefix"), title))
                     .size(12.0)
@@ -504,7 +523,7 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
             .unwrap(),
         );
     }
-    children.push(
+    metadata.push(
         serde_json::to_value(
             crate::ui::TextInput::new("pdf_title")
                 .hint(&t!("pdf_document_title_hint"))
@@ -513,10 +532,10 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
         )
         .unwrap(),
     );
-    children.push(serde_json::to_value(UiButton::new(&t!("pdf_set_title_button"), "pdf_set_title")).unwrap());
+    metadata.push(serde_json::to_value(UiButton::new(&t!("pdf_set_title_button"), "pdf_set_title")).unwrap());
     if let (Some(count), Some(uri)) = (state.pdf.page_count, state.pdf.source_uri.as_ref()) {
         let aspect = state.pdf.page_aspect_ratio;
-        children.push(json!({
+        signature.push(json!({
             "type": "PdfSignPlacement",
             "source_uri": uri,
             "page_count": count,
@@ -526,12 +545,12 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
             "bind_key_y_pct": "pdf_signature_y_pct",
             "selected_x_pct": state.pdf.signature_x_pct,
             "selected_y_pct": state.pdf.signature_y_pct,
-            "content_description": "Signature placement picker",
+            "content_description": t!("pdf_signature_picker_content_description"),
             "page_aspect_ratio": aspect
         }));
 
         // Compact thumbnail preview with overlay marker; mirrors placement state
-        children.push(json!({
+        signature.push(json!({
             "type": "PdfSignPreview",
             "source_uri": uri,
             "page_count": count,
@@ -541,7 +560,7 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
             "bind_key_y_pct": "pdf_signature_y_pct",
             "selected_x_pct": state.pdf.signature_x_pct,
             "selected_y_pct": state.pdf.signature_y_pct,
-            "content_description": "Signature preview thumbnail",
+            "content_description": t!("pdf_signature_preview_content_description"),
             "page_aspect_ratio": aspect
         }));
 
@@ -573,7 +592,7 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
                 }
             }));
         }
-        children.push(
+        signature.push(
             serde_json::to_value(
                 UiColumn::new(vec![
                     serde_json::to_value(
@@ -595,41 +614,34 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
         );
     }
 
-    if state.pdf.last_output.is_some() {
-        children.push(
-            serde_json::to_value(UiButton::new(&t!("pdf_save_as_button"), "pdf_save_as").id("pdf_save_as_btn"))
-                .unwrap(),
-        );
-    }
-
     // Signature section
-    children.push(serde_json::to_value(UiText::new(&t!("pdf_signature_section_title")).size(16.0)).unwrap());
-    children.push(
+    signature.push(serde_json::to_value(UiText::new(&t!("pdf_signature_section_title")).size(16.0)).unwrap());
+    signature.push(
         serde_json::to_value(
             UiText::new(&t!("pdf_signature_section_description"))
                 .size(12.0),
         )
         .unwrap(),
     );
-    children.push(json!({
+    signature.push(json!({
         "type": "SignaturePad",
         "bind_key": "signature_base64",
         "height_dp": 200,
         "content_description": &t!("pdf_signature_pad_description")
     }));
-    children.push(
+    signature.push(
         serde_json::to_value(
             UiButton::new(&t!("pdf_load_signature_image_button"), "pdf_signature_load").requires_file_picker(true),
         )
         .unwrap(),
     );
-    children.push(
+    signature.push(
         serde_json::to_value(UiButton::new(&t!("pdf_clear_signature_button"), "pdf_signature_clear")).unwrap(),
     );
     if state.pdf.signature_base64.is_some() {
-        children.push(serde_json::to_value(UiText::new(&t!("pdf_signature_ready")).size(12.0)).unwrap());
+        signature.push(serde_json::to_value(UiText::new(&t!("pdf_signature_ready")).size(12.0)).unwrap());
     }
-    children.push(
+    signature.push(
         serde_json::to_value(
             crate::ui::TextInput::new("pdf_signature_page")
                 .hint(&t!("pdf_signature_page_hint"))
@@ -637,7 +649,7 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
         )
         .unwrap(),
     );
-    children.push(
+    signature.push(
         serde_json::to_value(
             crate::ui::TextInput::new("pdf_signature_x")
                 .hint(&t!("pdf_signature_x_pos_hint"))
@@ -645,7 +657,7 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
         )
         .unwrap(),
     );
-    children.push(
+    signature.push(
         serde_json::to_value(
             crate::ui::TextInput::new("pdf_signature_y")
                 .hint(&t!("pdf_signature_y_pos_hint"))
@@ -653,7 +665,7 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
         )
         .unwrap(),
     );
-    children.push(
+    signature.push(
         serde_json::to_value(
             crate::ui::TextInput::new("pdf_signature_width")
                 .hint(&t!("pdf_signature_width_hint"))
@@ -668,7 +680,7 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
         )
         .unwrap(),
     );
-    children.push(
+    signature.push(
         serde_json::to_value(
             crate::ui::TextInput::new("pdf_signature_height")
                 .hint(&t!("pdf_signature_height_hint"))
@@ -683,12 +695,33 @@ pub fn render_pdf_screen(state: &AppState) -> serde_json::Value {
         )
         .unwrap(),
     );
-    children.push(serde_json::to_value(UiButton::new(&t!("pdf_apply_signature_button"), "pdf_sign")).unwrap());
+    signature.push(serde_json::to_value(UiButton::new(&t!("pdf_apply_signature_button"), "pdf_sign")).unwrap());
+
+    let pages_label = t!("pdf_tab_pages");
+    let metadata_label = t!("pdf_tab_metadata");
+    let signature_label = t!("pdf_tab_signature");
+    children.push(
+        serde_json::to_value(
+            UiTabs::new("pdf_tab", state.pdf.tab.id())
+                .action("pdf_tab_select")
+                .tab("pages", &pages_label, pages)
+                .tab("metadata", &metadata_label, metadata)
+                .tab("signature", &signature_label, signature),
+        )
+        .unwrap(),
+    );
+
+    if state.pdf.last_output.is_some() {
+        children.push(
+            serde_json::to_value(UiButton::new(&t!("pdf_save_as_button"), "pdf_save_as").id("pdf_save_as_btn"))
+                .unwrap(),
+        );
+    }
 
     if let Some(err) = &state.pdf.last_error {
         children.push(
             serde_json::to_value(
-                UiText::new(&format!("{}{}", t!("multi_hash_error_prefix"), err))
+                UiText::new(&error_text(err))
                     .size(12.0)
                     .content_description(&t!("pdf_error_content_description")),
             )
@@ -711,7 +744,8 @@ pub fn render_pdf_preview_screen(state: &AppState) -> serde_json::Value {
                 children.push(json!({
                     "type": "PdfSinglePage",
                     "source_uri": uri,
-                    "page": page
+                    "page": page,
+                    "zoomable": true
                 }));
                 if page > 1 {
                     children.push(json!({
@@ -1,9 +1,15 @@
+use crate::error::error_text;
+use crate::features::dithering::{nearest_color, palette_colors, RESULT_PREVIEW_MAX_HEIGHT_DP};
+use crate::features::image_resize::open_oriented;
 use crate::features::storage::preferred_temp_dir;
-use crate::state::{AppState, PixelArtState};
-use crate::ui::{maybe_push_back, Button as UiButton, Column as UiColumn, Text as UiText};
-use image::imageops::FilterType;
-use image::DynamicImage;
+use crate::state::{AppState, DitheringPalette, PixelArtState, PixelArtUpscaler};
+use crate::ui::{
+    maybe_push_back, Button as UiButton, Checkbox as UiCheckbox, Column as UiColumn,
+    Image as UiImage, Text as UiText,
+};
+use image::imageops::{self, FilterType};
 use image::GenericImageView;
+use image::{DynamicImage, Rgba, RgbaImage};
 use serde_json::{json, Value};
 use std::fs::File;
 use std::os::unix::io::{FromRawFd, RawFd};
@@ -57,9 +63,88 @@ pub fn render_pixel_art_screen(state: &AppState) -> Value {
         }));
     }
 
+    let upscalers = [
+        (
+            PixelArtUpscaler::Nearest,
+            t!("pixel_art_upscaler_nearest"),
+            "pixel_art_upscaler_nearest",
+        ),
+        (
+            PixelArtUpscaler::Hq2x,
+            t!("pixel_art_upscaler_hq2x"),
+            "pixel_art_upscaler_hq2x",
+        ),
+        (
+            PixelArtUpscaler::Xbr,
+            t!("pixel_art_upscaler_xbr"),
+            "pixel_art_upscaler_xbr",
+        ),
+    ];
+    children.push(
+        serde_json::to_value(UiText::new(&t!("pixel_art_upscaler_section")).size(14.0)).unwrap(),
+    );
+    for (upscaler, label, action) in upscalers {
+        let mut button = UiButton::new(&label, action).id(action);
+        if upscaler == state.pixel_art.upscaler {
+            button = button.content_description("selected");
+        }
+        children.push(serde_json::to_value(button).unwrap());
+    }
+    children.push(
+        serde_json::to_value(
+            UiCheckbox::new(&t!("pixel_art_crt_checkbox"), "pixel_art_crt")
+                .checked(state.pixel_art.crt)
+                .action("pixel_art_crt_toggle"),
+        )
+        .unwrap(),
+    );
+
+    let locks = [
+        (
+            Some(DitheringPalette::Monochrome),
+            t!("dithering_palette_monochrome"),
+            "pixel_art_palette_lock_mono",
+        ),
+        (
+            Some(DitheringPalette::Cga),
+            t!("dithering_palette_cga"),
+            "pixel_art_palette_lock_cga",
+        ),
+        (
+            Some(DitheringPalette::GameBoy),
+            t!("dithering_palette_gameboy"),
+            "pixel_art_palette_lock_gb",
+        ),
+        (
+            None,
+            t!("pixel_art_palette_lock_off"),
+            "pixel_art_palette_lock_off",
+        ),
+    ];
+    children.push(
+        serde_json::to_value(UiText::new(&t!("pixel_art_palette_lock_section")).size(14.0))
+            .unwrap(),
+    );
+    for (palette, label, action) in locks {
+        let mut button = UiButton::new(&label, action).id(action);
+        if palette == state.pixel_art.palette_lock {
+            button = button.content_description("selected");
+        }
+        children.push(serde_json::to_value(button).unwrap());
+    }
+    let dithering_label = t!("pixel_art_palette_lock_dithering");
+    let mut dithering_lock = UiButton::new(&dithering_label, "pixel_art_palette_lock_dithering")
+        .id("pixel_art_palette_lock_dithering");
+    if matches!(
+        state.pixel_art.palette_lock,
+        Some(DitheringPalette::Custom(_))
+    ) {
+        dithering_lock = dithering_lock.content_description("selected");
+    }
+    children.push(serde_json::to_value(dithering_lock).unwrap());
+
     if let Some(err) = &state.pixel_art.error {
-        children
-            .push(serde_json::to_value(UiText::new(&format!("{}{}", t!("multi_hash_error_prefix"), err)).size(12.0)).unwrap());
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
     }
 
     if state.pixel_art.source_path.is_some() {
@@ -70,6 +155,15 @@ pub fn render_pixel_art_screen(state: &AppState) -> Value {
     }
 
     if let Some(out) = &state.pixel_art.result_path {
+        children.push(
+            serde_json::to_value(
+                UiImage::from_path(out)
+                    .zoomable(true)
+                    .max_height_dp(RESULT_PREVIEW_MAX_HEIGHT_DP)
+                    .content_description("pixel_art_result_image"),
+            )
+            .unwrap(),
+        );
         children.push(
             serde_json::to_value(
                 UiText::new(&format!("{}{}", t!("pixel_art_result_prefix"), out))
@@ -90,17 +184,42 @@ pub fn render_pixel_art_screen(state: &AppState) -> Value {
     serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
 }
 
-pub fn process_pixel_art(path: &str, factor: u32) -> Result<String, String> {
-    let factor = factor.max(2);
-    let img = image::open(path).map_err(|e| format!("open_failed:{e}"))?;
+#[derive(Debug, Clone, PartialEq)]
+pub struct PixelArtOptions {
+    pub scale: u32,
+    pub upscaler: PixelArtUpscaler,
+    pub crt: bool,
+    pub palette_lock: Option<DitheringPalette>,
+}
+
+impl PixelArtOptions {
+    pub fn from_state(state: &PixelArtState) -> Self {
+        Self {
+            scale: state.scale_factor,
+            upscaler: state.upscaler,
+            crt: state.crt,
+            palette_lock: state.palette_lock.clone(),
+        }
+    }
+}
+
+pub fn process_pixel_art(path: &str, options: &PixelArtOptions) -> Result<String, String> {
+    let factor = options.scale.max(2);
+    let img = open_oriented(path)?;
     let (w, h) = img.dimensions();
     if w == 0 || h == 0 {
         return Err("empty_image".into());
     }
     let down_w = (w / factor.max(1)).max(1);
     let down_h = (h / factor.max(1)).max(1);
-    let small = resize_nearest(&img, down_w, down_h);
-    let up = small.resize_exact(w, h, FilterType::Nearest);
+    let mut small = resize_nearest(&img, down_w, down_h).to_rgba8();
+    if let Some(palette) = &options.palette_lock {
+        lock_palette(&mut small, palette_colors(palette))?;
+    }
+    let mut up = upscale(small, options.upscaler, w, h);
+    if options.crt {
+        apply_crt(&mut up);
+    }
 
     let tmp = new_temp_file("pixel_art_", ".png")?;
     up.save(&tmp).map_err(|e| format!("save_failed:{e}"))?;
@@ -117,6 +236,148 @@ fn resize_nearest(img: &DynamicImage, w: u32, h: u32) -> DynamicImage {
     img.resize_exact(w, h, FilterType::Nearest)
 }
 
+fn lock_palette(img: &mut RgbaImage, palette: &[[u8; 3]]) -> Result<(), String> {
+    if palette.is_empty() {
+        return Err("dithering_palette_too_small".into());
+    }
+    for p in img.pixels_mut() {
+        let [r, g, b] = nearest_color(palette, p[0] as f32, p[1] as f32, p[2] as f32);
+        *p = Rgba([r, g, b, p[3]]);
+    }
+    Ok(())
+}
+
+/// Computes one output quadrant from a neighbour lookup oriented towards it.
+type CornerRule = fn(&dyn Fn(i32, i32) -> Rgba<u8>) -> Rgba<u8>;
+
+/// Brings the pixelated image back to `w`×`h`. The smoothing scalers double the image
+/// until it covers the target, then a nearest resize absorbs any rounding remainder.
+fn upscale(small: RgbaImage, upscaler: PixelArtUpscaler, w: u32, h: u32) -> RgbaImage {
+    let corner: CornerRule = match upscaler {
+        PixelArtUpscaler::Nearest => return imageops::resize(&small, w, h, FilterType::Nearest),
+        PixelArtUpscaler::Hq2x => hq2x_corner,
+        PixelArtUpscaler::Xbr => xbr_corner,
+    };
+    let mut current = small;
+    while current.width() < w || current.height() < h {
+        current = scale2x_with(&current, corner);
+    }
+    if current.dimensions() == (w, h) {
+        current
+    } else {
+        imageops::resize(&current, w, h, FilterType::Nearest)
+    }
+}
+
+/// Doubles `src`, computing each output quadrant with `corner`. The closure handed to
+/// `corner` reads neighbours rotated so that (1, 1) always points at the quadrant being
+/// filled, which lets a single bottom-right rule serve all four corners.
+fn scale2x_with(src: &RgbaImage, corner: CornerRule) -> RgbaImage {
+    let (w, h) = src.dimensions();
+    let mut out = RgbaImage::new(w * 2, h * 2);
+    for y in 0..h as i32 {
+        for x in 0..w as i32 {
+            for rot in 0..4 {
+                let at = |dx: i32, dy: i32| {
+                    let (rx, ry) = rotate(dx, dy, rot);
+                    let sx = (x + rx).clamp(0, w as i32 - 1) as u32;
+                    let sy = (y + ry).clamp(0, h as i32 - 1) as u32;
+                    *src.get_pixel(sx, sy)
+                };
+                let (qx, qy) = rotate(1, 1, rot);
+                let ox = (x * 2 + (qx + 1) / 2) as u32;
+                let oy = (y * 2 + (qy + 1) / 2) as u32;
+                out.put_pixel(ox, oy, corner(&at));
+            }
+        }
+    }
+    out
+}
+
+fn rotate(dx: i32, dy: i32, quarter_turns: u32) -> (i32, i32) {
+    (0..quarter_turns).fold((dx, dy), |(x, y), _| (-y, x))
+}
+
+/// hq2x-style corner: uses hqx's YUV similarity thresholds and rounds a corner when the
+/// two orthogonal neighbours agree with each other but not with the centre. This keeps
+/// the character of hq2x without its 256-case lookup table.
+fn hq2x_corner(at: &dyn Fn(i32, i32) -> Rgba<u8>) -> Rgba<u8> {
+    let (e, f, h, i) = (at(0, 0), at(1, 0), at(0, 1), at(1, 1));
+    if similar(f, h) && !similar(e, f) && !similar(e, i) {
+        mix(&[(e, 2), (f, 1), (h, 1)])
+    } else {
+        e
+    }
+}
+
+/// xBR level 1 bottom-right rule: compares the edge strength along the two diagonals of
+/// the 5×5 neighbourhood and blends towards the closer neighbour across a detected edge.
+fn xbr_corner(at: &dyn Fn(i32, i32) -> Rgba<u8>) -> Rgba<u8> {
+    let e = at(0, 0);
+    let (b, c, d, f) = (at(0, -1), at(1, -1), at(-1, 0), at(1, 0));
+    let (g, h, i) = (at(-1, 1), at(0, 1), at(1, 1));
+    let (f4, i4, h5, i5) = (at(2, 0), at(2, 1), at(0, 2), at(1, 2));
+    let wd1 = yuv_distance(e, c)
+        + yuv_distance(e, g)
+        + yuv_distance(i, f4)
+        + yuv_distance(i, h5)
+        + 4 * yuv_distance(h, f);
+    let wd2 = yuv_distance(h, d)
+        + yuv_distance(h, i5)
+        + yuv_distance(f, i4)
+        + yuv_distance(f, b)
+        + 4 * yuv_distance(e, i);
+    if wd1 < wd2 && e != f && e != h {
+        let towards = if yuv_distance(e, f) <= yuv_distance(e, h) {
+            f
+        } else {
+            h
+        };
+        mix(&[(e, 1), (towards, 1)])
+    } else {
+        e
+    }
+}
+
+fn yuv(p: Rgba<u8>) -> [i32; 3] {
+    let [r, g, b] = [p[0] as i32, p[1] as i32, p[2] as i32];
+    [
+        (299 * r + 587 * g + 114 * b) / 1000,
+        (-169 * r - 331 * g + 500 * b) / 1000,
+        (500 * r - 419 * g - 81 * b) / 1000,
+    ]
+}
+
+fn yuv_distance(a: Rgba<u8>, b: Rgba<u8>) -> u32 {
+    let (a, b) = (yuv(a), yuv(b));
+    48 * a[0].abs_diff(b[0]) + 7 * a[1].abs_diff(b[1]) + 6 * a[2].abs_diff(b[2])
+}
+
+fn similar(a: Rgba<u8>, b: Rgba<u8>) -> bool {
+    let (a, b) = (yuv(a), yuv(b));
+    a[0].abs_diff(b[0]) <= 48 && a[1].abs_diff(b[1]) <= 7 && a[2].abs_diff(b[2]) <= 6
+}
+
+fn mix(weighted: &[(Rgba<u8>, u32)]) -> Rgba<u8> {
+    let total: u32 = weighted.iter().map(|(_, w)| w).sum();
+    let channel = |c: usize| {
+        let sum: u32 = weighted.iter().map(|(p, w)| p[c] as u32 * w).sum();
+        ((sum + total / 2) / total) as u8
+    };
+    Rgba([channel(0), channel(1), channel(2), channel(3)])
+}
+
+/// Darkens every other scanline and applies an RGB aperture-grille mask.
+fn apply_crt(img: &mut RgbaImage) {
+    for (x, y, p) in img.enumerate_pixels_mut() {
+        let line = if y % 2 == 1 { 0.6 } else { 1.0 };
+        for c in 0..3 {
+            let mask = if x as usize % 3 == c { 1.0 } else { 0.8 };
+            p[c] = (p[c] as f32 * line * mask).round() as u8;
+        }
+    }
+}
+
 fn new_temp_file(prefix: &str, suffix: &str) -> Result<tempfile::NamedTempFile, String> {
     let dir = preferred_temp_dir();
     Builder::new()
@@ -176,7 +437,13 @@ mod tests {
         let path = dir.path().join("input.png");
         img.save(&path).unwrap();
 
-        let out = process_pixel_art(path.to_str().unwrap(), 4).expect("process ok");
+        let options = PixelArtOptions {
+            scale: 4,
+            upscaler: PixelArtUpscaler::Nearest,
+            crt: false,
+            palette_lock: None,
+        };
+        let out = process_pixel_art(path.to_str().unwrap(), &options).expect("process ok");
         let out_img = image::open(out).unwrap();
         assert_eq!(out_img.dimensions(), (8, 8));
 
@@ -185,4 +452,53 @@ mod tests {
             None => std::env::remove_var("KISTAVERK_TEMP_DIR"),
         }
     }
+
+    fn two_tone(size: u32) -> RgbaImage {
+        // Lower-left triangle dark, the rest light: a clean diagonal edge.
+        RgbaImage::from_fn(size, size, |x, y| {
+            if x < y {
+                Rgba([20, 20, 20, 255])
+            } else {
+                Rgba([230, 230, 230, 255])
+            }
+        })
+    }
+
+    #[test]
+    fn smoothing_scalers_double_and_soften_diagonals() {
+        let src = two_tone(8);
+        for upscaler in [PixelArtUpscaler::Hq2x, PixelArtUpscaler::Xbr] {
+            let out = upscale(src.clone(), upscaler, 32, 32);
+            assert_eq!(out.dimensions(), (32, 32));
+            let blended = out.pixels().filter(|p| p[0] != 20 && p[0] != 230).count();
+            assert!(blended > 0, "{upscaler:?} left the diagonal jagged");
+        }
+
+        let flat = RgbaImage::from_pixel(4, 4, Rgba([90, 120, 200, 255]));
+        for upscaler in [PixelArtUpscaler::Hq2x, PixelArtUpscaler::Xbr] {
+            let out = upscale(flat.clone(), upscaler, 8, 8);
+            assert!(out.pixels().all(|p| *p == Rgba([90, 120, 200, 255])));
+        }
+    }
+
+    #[test]
+    fn rotation_maps_bottom_right_to_every_quadrant() {
+        let quadrants: Vec<_> = (0..4).map(|r| rotate(1, 1, r)).collect();
+        assert_eq!(quadrants, vec![(1, 1), (-1, 1), (-1, -1), (1, -1)]);
+    }
+
+    #[test]
+    fn palette_lock_and_crt_constrain_output() {
+        let mut img = two_tone(4);
+        img.put_pixel(0, 0, Rgba([200, 40, 40, 128]));
+        lock_palette(&mut img, palette_colors(&DitheringPalette::GameBoy)).unwrap();
+        let gb = palette_colors(&DitheringPalette::GameBoy);
+        assert!(img.pixels().all(|p| gb.contains(&[p[0], p[1], p[2]])));
+        assert_eq!(img.get_pixel(0, 0)[3], 128);
+
+        let mut white = RgbaImage::from_pixel(3, 2, Rgba([255, 255, 255, 255]));
+        apply_crt(&mut white);
+        assert_eq!(white.get_pixel(0, 0).0, [255, 204, 204, 255]);
+        assert_eq!(white.get_pixel(1, 1).0, [122, 153, 122, 255]);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::error::error_text;
 use crate::state::{AppState, PlotType};
 use crate::ui::{
     maybe_push_back, Button as UiButton, Column as UiColumn, Grid as UiGrid, HtmlView as UiHtmlView,
@@ -17,41 +18,42 @@ const HIST_BINS: usize = 10;
 pub fn render_plotting_screen(state: &AppState) -> Value {
     let plotting = &state.plotting;
     let mut children = vec![
-        serde_json::to_value(UiText::new("The Lab: Data Plotting").size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("plotting_title")).size(20.0)).unwrap(),
         serde_json::to_value(
             UiText::new(
-                plotting
+                &plotting
                     .display_path
-                    .as_deref()
-                    .unwrap_or("Pick a CSV file to begin."),
+                    .clone()
+                    .unwrap_or_else(|| t!("plotting_no_file").into_owned()),
             )
             .size(12.0),
         )
         .unwrap(),
-        serde_json::to_value(UiButton::new("Pick CSV", "plotting_pick").requires_file_picker(true))
-            .unwrap(),
+        serde_json::to_value(
+            UiButton::new(&t!("plotting_pick_button"), "plotting_pick").requires_file_picker(true),
+        )
+        .unwrap(),
     ];
 
     if !plotting.headers.is_empty() {
         children.push(
-            serde_json::to_value(UiText::new(&format!(
-                "Columns: {}",
-                plotting.headers.join(", ")
+            serde_json::to_value(UiText::new(&t!(
+                "plotting_columns",
+                columns = plotting.headers.join(", ")
             )))
             .unwrap(),
         );
     }
 
     if let Some(err) = &plotting.error {
-        children.push(
-            serde_json::to_value(UiText::new(&format!("Error: {err}")).size(12.0)).unwrap(),
-        );
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
     }
 
+    let columns_title = t!("plotting_columns_section");
     let inputs = UiSection::new(vec![
         json!(
             UiTextInput::new("plot_x_col")
-                .hint("X column name")
+                .hint(&t!("plotting_x_hint"))
                 .text(plotting.x_col.as_deref().unwrap_or(""))
                 .single_line(true)
                 .debounce_ms(200)
@@ -59,28 +61,41 @@ pub fn render_plotting_screen(state: &AppState) -> Value {
         ),
         json!(
             UiTextInput::new("plot_y_col")
-                .hint("Y column name (ignored for histogram)")
+                .hint(&t!("plotting_y_hint"))
                 .text(plotting.y_col.as_deref().unwrap_or(""))
                 .single_line(true)
                 .debounce_ms(200)
                 .action_on_submit("plotting_set_y")
         ),
     ])
-    .title("Columns")
+    .title(&columns_title)
     .padding(12);
     children.push(serde_json::to_value(inputs).unwrap());
 
     let plot_buttons = UiGrid::new(vec![
-        json!(UiButton::new("Line", "plotting_type_line")),
-        json!(UiButton::new("Scatter", "plotting_type_scatter")),
-        json!(UiButton::new("Histogram", "plotting_type_hist")),
+        json!(UiButton::new(
+            &t!("plotting_line_button"),
+            "plotting_type_line"
+        )),
+        json!(UiButton::new(
+            &t!("plotting_scatter_button"),
+            "plotting_type_scatter"
+        )),
+        json!(UiButton::new(
+            &t!("plotting_histogram_button"),
+            "plotting_type_hist"
+        )),
     ])
     .columns(3)
     .padding(8);
     children.push(serde_json::to_value(plot_buttons).unwrap());
 
     children.push(
-        serde_json::to_value(UiButton::new("Plot", "plotting_generate")).unwrap(),
+        serde_json::to_value(UiButton::new(
+            &t!("plotting_plot_button"),
+            "plotting_generate",
+        ))
+        .unwrap(),
     );
 
     if let Some(svg_html) = &plotting.generated_svg {
@@ -0,0 +1,664 @@
+//! User plugins: extra tools compiled with MIR from a C source (`.c`) or a binary MIR
+//! module (`.bmir`) dropped into [`plugins_dir`].
+//!
+//! A plugin exports two functions exchanging NUL-terminated text:
+//!
+//! ```c
+//! /* UI tree, as JSON, for the plugin's current state. */
+//! const char *kv_render(const char *state);
+//! /* Applies `action` with the screen's `bindings` (a JSON object); returns the new state. */
+//! const char *kv_handle_action(const char *state, const char *action, const char *bindings);
+//! ```
+//!
+//! The state is opaque text owned by the plugin, `"{}"` on first open, so each call can
+//! run in a forked child process, which compiles the plugin and then calls it. Returned
+//! strings only need to stay valid until the call returns. A call that outlives
+//! [`PLUGIN_TIME_BUDGET_MS`] is killed, and a malformed or crashing plugin only loses the
+//! call. Plugins allocate from the same budgeted heap as C scripts.
+
+use crate::error::error_text;
+use crate::features::c_scripting::{
+    answer_and_exit, getc_func, run_forked, script_calloc, script_free, script_malloc,
+    script_realloc, ForkFailure, StringReader,
+};
+use crate::features::storage::plugins_dir;
+use crate::state::AppState;
+use crate::ui::{
+    maybe_push_back, Button as UiButton, Column as UiColumn, Row as UiRow, Text as UiText,
+};
+use libc::{c_char, c_void};
+use mir_sys::*;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::ffi::{CStr, CString};
+use std::io::Read;
+use std::path::Path;
+use std::ptr;
+
+pub const PLUGIN_EXTENSIONS: [&str; 2] = ["c", "bmir"];
+/// Prefix given to every action of a plugin's UI, so the router sends it back to the plugin.
+pub const PLUGIN_ACTION_PREFIX: &str = "plugin:";
+/// Largest plugin file accepted.
+pub const MAX_PLUGIN_BYTES: u64 = 1024 * 1024;
+/// Largest string a plugin may return.
+const MAX_PLUGIN_OUTPUT: usize = 256 * 1024;
+/// State handed to a plugin the first time it is opened.
+pub const INITIAL_PLUGIN_STATE: &str = "{}";
+/// Wall-clock budget of one plugin call: compilation, action and render together.
+pub const PLUGIN_TIME_BUDGET_MS: u64 = 5_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginInfo {
+    /// File name inside the plugins directory.
+    pub id: String,
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginsState {
+    pub installed: Vec<PluginInfo>,
+    pub active: Option<PluginInfo>,
+    /// Opaque state of the active plugin, handed back on every call.
+    pub data: String,
+    /// Last tree the active plugin rendered, with its actions prefixed.
+    pub ui: Option<Value>,
+    pub running: bool,
+    pub status: Option<String>,
+    pub error: Option<String>,
+}
+
+impl PluginsState {
+    pub const fn new() -> Self {
+        Self {
+            installed: Vec::new(),
+            active: None,
+            data: String::new(),
+            ui: None,
+            running: false,
+            status: None,
+            error: None,
+        }
+    }
+
+    pub fn find(&self, id: &str) -> Option<&PluginInfo> {
+        self.installed.iter().find(|p| p.id == id)
+    }
+
+    /// Closes the active plugin, keeping the installed list.
+    pub fn reset(&mut self) {
+        let installed = std::mem::take(&mut self.installed);
+        *self = Self::new();
+        self.installed = installed;
+    }
+
+    /// Makes `plugin` the active one with a fresh state.
+    pub fn open(&mut self, plugin: PluginInfo) {
+        self.active = Some(plugin);
+        self.data = INITIAL_PLUGIN_STATE.to_string();
+        self.ui = None;
+        self.error = None;
+    }
+}
+
+/// Result of one plugin call: the plugin's new state and what it rendered for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginOutput {
+    pub data: String,
+    pub ui: Value,
+}
+
+fn is_plugin_file(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| PLUGIN_EXTENSIONS.contains(&ext))
+}
+
+fn plugin_info(path: &Path) -> Option<PluginInfo> {
+    let id = path.file_name()?.to_str()?.to_string();
+    if !is_plugin_file(&id) {
+        return None;
+    }
+    let name = path.file_stem()?.to_string_lossy().replace(['_', '-'], " ");
+    Some(PluginInfo {
+        id,
+        name,
+        path: path.to_string_lossy().into_owned(),
+    })
+}
+
+/// Plugins found in `dir`, by name. A missing directory has none.
+pub fn list_plugins(dir: &Path) -> Vec<PluginInfo> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut plugins: Vec<PluginInfo> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+        .filter_map(|e| plugin_info(&e.path()))
+        .collect();
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+    plugins
+}
+
+/// Copies a picked plugin named `file_name` into `dir`, replacing one of the same name.
+pub fn install_plugin(
+    dir: &Path,
+    file_name: &str,
+    reader: impl Read,
+) -> Result<PluginInfo, String> {
+    let file_name = Path::new(file_name)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| "plugin_missing_name".to_string())?;
+    if !is_plugin_file(file_name) {
+        return Err(format!("plugin_unsupported_type:{file_name}"));
+    }
+    let mut bytes = Vec::new();
+    reader
+        .take(MAX_PLUGIN_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("read_failed:{e}"))?;
+    if bytes.len() as u64 > MAX_PLUGIN_BYTES {
+        return Err("plugin_too_large".into());
+    }
+    std::fs::create_dir_all(dir).map_err(|e| format!("mkdir_failed:{e}"))?;
+    let path = dir.join(file_name);
+    std::fs::write(&path, bytes).map_err(|e| format!("write_failed:{e}"))?;
+    plugin_info(&path).ok_or_else(|| "plugin_missing_name".into())
+}
+
+pub fn remove_plugin(plugin: &PluginInfo) -> Result<(), String> {
+    std::fs::remove_file(&plugin.path).map_err(|e| format!("delete_failed:{e}"))
+}
+
+/// Prefixes the actions of a plugin's tree with [`PLUGIN_ACTION_PREFIX`] and drops file
+/// pickers, which plugins cannot receive.
+pub fn route_actions(node: &mut Value) {
+    match node {
+        Value::Object(map) => {
+            if let Some(Value::String(action)) = map.get_mut("action") {
+                *action = format!("{PLUGIN_ACTION_PREFIX}{action}");
+            }
+            map.remove("requires_file_picker");
+            if let Some(children) = map.get_mut("children") {
+                route_actions(children);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(route_actions),
+        _ => {}
+    }
+}
+
+type RenderFn = extern "C" fn(*const c_char) -> *const c_char;
+type HandleActionFn = extern "C" fn(*const c_char, *const c_char, *const c_char) -> *const c_char;
+
+/// What a call answers from the forked child: the new state and the rendered text.
+type CallAnswer = Result<(String, String), String>;
+
+/// Calls the plugin at `path`: `kv_handle_action` first when there is an `action`, then
+/// `kv_render` for the resulting state. The plugin is compiled in the forked child on
+/// every call, so nothing it does while loading reaches the app.
+pub fn call_plugin(
+    path: &Path,
+    action: Option<&str>,
+    bindings: &str,
+    data: &str,
+) -> Result<PluginOutput, String> {
+    std::fs::metadata(path).map_err(|e| format!("plugin_missing:{e}"))?;
+    let called = unsafe {
+        run_forked(PLUGIN_TIME_BUDGET_MS, || -> CallAnswer {
+            let (render, handle_action) = load_plugin(path)?;
+            run_entry_points(render, handle_action, action, bindings, data)
+        })
+    };
+    let (data, rendered) = match called {
+        Ok(output) => output?,
+        Err(ForkFailure::TimedOut) => {
+            return Err(format!("plugin_timeout:{} s", PLUGIN_TIME_BUDGET_MS / 1000))
+        }
+        Err(ForkFailure::Crashed) => return Err("plugin_crashed".into()),
+        Err(ForkFailure::SpawnFailed) => return Err("plugin_spawn_failed".into()),
+    };
+    let mut ui: Value =
+        serde_json::from_str(&rendered).map_err(|e| format!("plugin_bad_ui:{e}"))?;
+    if !ui.is_object() {
+        return Err("plugin_bad_ui:not_an_object".into());
+    }
+    route_actions(&mut ui);
+    Ok(PluginOutput { data, ui })
+}
+
+/// Runs in the forked child: `kv_handle_action` when there is an `action`, then `kv_render`.
+fn run_entry_points(
+    render: RenderFn,
+    handle_action: Option<HandleActionFn>,
+    action: Option<&str>,
+    bindings: &str,
+    data: &str,
+) -> CallAnswer {
+    let data = match action {
+        Some(action) => {
+            let handle_action =
+                handle_action.ok_or_else(|| "plugin_no_action_handler".to_string())?;
+            let state = c_text(data)?;
+            let action = c_text(action)?;
+            let bindings = c_text(bindings)?;
+            unsafe {
+                copy_output(handle_action(
+                    state.as_ptr(),
+                    action.as_ptr(),
+                    bindings.as_ptr(),
+                ))?
+            }
+        }
+        None => data.to_string(),
+    };
+    let state = c_text(&data)?;
+    let rendered = unsafe { copy_output(render(state.as_ptr()))? };
+    Ok((data, rendered))
+}
+
+fn c_text(text: &str) -> Result<CString, String> {
+    CString::new(text).map_err(|_| "plugin_text_has_nul".to_string())
+}
+
+unsafe fn copy_output(text: *const c_char) -> Result<String, String> {
+    if text.is_null() {
+        return Err("plugin_returned_null".into());
+    }
+    let len = libc::strnlen(text, MAX_PLUGIN_OUTPUT + 1);
+    if len > MAX_PLUGIN_OUTPUT {
+        return Err("plugin_output_too_large".into());
+    }
+    let bytes = std::slice::from_raw_parts(text as *const u8, len);
+    Ok(String::from_utf8_lossy(bytes).into_owned())
+}
+
+/// Replaces MIR's default error handler, which calls `exit()` without a word, so a
+/// malformed plugin reports why it failed to load. MIR expects it not to return; it runs
+/// in the forked child, which it ends with the error as the call's answer.
+unsafe extern "C" fn plugin_mir_error(_kind: MIR_error_type_t, format: *const c_char) -> ! {
+    let message = if format.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(format).to_string_lossy().trim().to_string()
+    };
+    let answer: CallAnswer = Err(format!("plugin_invalid:{message}"));
+    answer_and_exit(&answer)
+}
+
+/// Compiles and links the plugin at `path` in a fresh MIR context. Runs in the forked
+/// child, whose exit releases the context.
+unsafe fn load_plugin(path: &Path) -> Result<(RenderFn, Option<HandleActionFn>), String> {
+    let ctx = _MIR_init(ptr::null_mut(), ptr::null_mut());
+    if ctx.is_null() {
+        return Err("plugin_mir_init_failed".into());
+    }
+    // MIR declares the handler variadic; the format argument is all it needs to read.
+    MIR_set_error_func(
+        ctx,
+        std::mem::transmute::<
+            unsafe extern "C" fn(MIR_error_type_t, *const c_char) -> !,
+            MIR_error_func_t,
+        >(plugin_mir_error),
+    );
+    MIR_gen_init(ctx);
+    MIR_gen_set_optimize_level(ctx, 1);
+    link_plugin(ctx, path)
+}
+
+unsafe fn link_plugin(
+    ctx: MIR_context_t,
+    path: &Path,
+) -> Result<(RenderFn, Option<HandleActionFn>), String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("read_failed:{e}"))?;
+    if bytes.len() as u64 > MAX_PLUGIN_BYTES {
+        return Err("plugin_too_large".into());
+    }
+    if path.extension().is_some_and(|e| e == "bmir") {
+        read_binary_module(ctx, path)?;
+    } else {
+        compile_c(ctx, bytes)?;
+    }
+
+    let mut module = (*MIR_get_module_list(ctx)).head;
+    if module.is_null() {
+        return Err("plugin_compile_failed".into());
+    }
+    while !module.is_null() {
+        MIR_load_module(ctx, module);
+        module = (*module).module_link.next;
+    }
+    for (name, addr) in externals() {
+        let name = CString::new(name).unwrap();
+        MIR_load_external(ctx, name.as_ptr(), addr);
+    }
+    MIR_link(ctx, Some(MIR_set_gen_interface), None);
+
+    let render = function_address(ctx, "kv_render")?
+        .ok_or_else(|| "plugin_missing_entry:kv_render".to_string())?;
+    let handle_action = function_address(ctx, "kv_handle_action")?;
+    Ok((
+        std::mem::transmute::<*mut c_void, RenderFn>(render),
+        handle_action.map(|addr| std::mem::transmute::<*mut c_void, HandleActionFn>(addr)),
+    ))
+}
+
+unsafe fn compile_c(ctx: MIR_context_t, source: Vec<u8>) -> Result<(), String> {
+    c2mir_init(ctx);
+    let mut reader = StringReader {
+        data: source,
+        cursor: 0,
+    };
+    let mut options: c2mir_options = std::mem::zeroed();
+    let compiled = c2mir_compile(
+        ctx,
+        &mut options,
+        Some(getc_func),
+        &mut reader as *mut _ as *mut c_void,
+        b"plugin.c\0".as_ptr() as *const _,
+        ptr::null_mut(),
+    );
+    c2mir_finish(ctx);
+    if compiled == 1 {
+        Ok(())
+    } else {
+        Err("plugin_compile_failed".into())
+    }
+}
+
+unsafe fn read_binary_module(ctx: MIR_context_t, path: &Path) -> Result<(), String> {
+    let c_path = CString::new(path.to_string_lossy().as_bytes())
+        .map_err(|_| "plugin_bad_path".to_string())?;
+    let file = libc::fopen(c_path.as_ptr(), b"rb\0".as_ptr() as *const c_char);
+    if file.is_null() {
+        return Err("plugin_open_failed".into());
+    }
+    MIR_read(ctx, file as *mut _);
+    libc::fclose(file);
+    Ok(())
+}
+
+/// Address of the exported function `name` in any loaded module, generated natively.
+unsafe fn function_address(ctx: MIR_context_t, name: &str) -> Result<Option<*mut c_void>, String> {
+    let mut module = (*MIR_get_module_list(ctx)).head;
+    while !module.is_null() {
+        let mut item = (*module).items.head;
+        while !item.is_null() {
+            if (*item).item_type == MIR_item_type_t_MIR_func_item
+                && CStr::from_ptr(MIR_item_name(ctx, item)).to_bytes() == name.as_bytes()
+            {
+                let addr = MIR_gen(ctx, item);
+                if addr.is_null() {
+                    return Err(format!("plugin_no_address:{name}"));
+                }
+                return Ok(Some(addr));
+            }
+            item = (*item).item_link.next;
+        }
+        module = (*module).module_link.next;
+    }
+    Ok(None)
+}
+
+/// C library functions plugins may import; allocation goes through the budgeted heap.
+fn externals() -> Vec<(&'static str, *mut c_void)> {
+    vec![
+        ("strlen", libc::strlen as *mut c_void),
+        ("strcmp", libc::strcmp as *mut c_void),
+        ("strncmp", libc::strncmp as *mut c_void),
+        ("strcpy", libc::strcpy as *mut c_void),
+        ("strncpy", libc::strncpy as *mut c_void),
+        ("strcat", libc::strcat as *mut c_void),
+        ("strchr", libc::strchr as *mut c_void),
+        ("strstr", libc::strstr as *mut c_void),
+        ("memcpy", libc::memcpy as *mut c_void),
+        ("memmove", libc::memmove as *mut c_void),
+        ("memset", libc::memset as *mut c_void),
+        ("memcmp", libc::memcmp as *mut c_void),
+        ("snprintf", libc::snprintf as *mut c_void),
+        ("atoi", libc::atoi as *mut c_void),
+        ("atol", libc::atol as *mut c_void),
+        ("strtol", libc::strtol as *mut c_void),
+        ("strtod", libc::strtod as *mut c_void),
+        ("malloc", script_malloc as *mut c_void),
+        ("calloc", script_calloc as *mut c_void),
+        ("realloc", script_realloc as *mut c_void),
+        ("free", script_free as *mut c_void),
+    ]
+}
+
+fn to_value_or_text<T: Serialize>(value: T, context: &str) -> Value {
+    serde_json::to_value(value).unwrap_or_else(|e| {
+        json!({
+            "type": "Text",
+            "text": format!("{context}_serialize_error:{e}")
+        })
+    })
+}
+
+/// Button opening `plugin`, for the manager and the home screen.
+pub fn plugin_button(plugin: &PluginInfo) -> Value {
+    to_value_or_text(
+        UiButton::new(&format!("🧩 {}", plugin.name), "plugin_open")
+            .id(&format!("plugin_{}", plugin.id))
+            .payload(json!({ "plugin_id": plugin.id })),
+        "plugin_button",
+    )
+}
+
+pub fn render_plugins_screen(state: &AppState) -> Value {
+    let plugins = &state.plugins;
+    let mut children = vec![
+        to_value_or_text(
+            UiText::new(&t!("plugins_title")).size(20.0),
+            "plugins_title",
+        ),
+        to_value_or_text(
+            UiText::new(&t!(
+                "plugins_description",
+                dir = plugins_dir().display().to_string()
+            ))
+            .size(14.0),
+            "plugins_subtitle",
+        ),
+        to_value_or_text(
+            UiButton::new(&t!("plugins_install_button"), "plugin_install")
+                .requires_file_picker(true)
+                .content_description("plugin_install_btn"),
+            "plugin_install_btn",
+        ),
+    ];
+
+    if plugins.installed.is_empty() {
+        children.push(to_value_or_text(
+            UiText::new(&t!("plugins_empty")).size(12.0),
+            "plugins_empty",
+        ));
+    }
+    for plugin in &plugins.installed {
+        children.push(to_value_or_text(
+            UiRow::new(vec![
+                plugin_button(plugin),
+                to_value_or_text(
+                    UiButton::new(&t!("plugins_remove_button"), "plugin_remove")
+                        .id(&format!("plugin_remove_{}", plugin.id))
+                        .payload(json!({ "plugin_id": plugin.id })),
+                    "plugin_remove_btn",
+                ),
+            ]),
+            "plugin_row",
+        ));
+    }
+
+    if let Some(status) = &plugins.status {
+        children.push(to_value_or_text(
+            UiText::new(status)
+                .size(12.0)
+                .content_description("plugins_status"),
+            "plugins_status",
+        ));
+    }
+    if let Some(err) = &plugins.error {
+        children.push(to_value_or_text(
+            UiText::new(&error_text(err))
+                .size(12.0)
+                .content_description("plugins_error"),
+            "plugins_error",
+        ));
+    }
+
+    maybe_push_back(&mut children, state);
+
+    to_value_or_text(UiColumn::new(children).padding(20), "plugins_root")
+}
+
+pub fn render_plugin_screen(state: &AppState) -> Value {
+    let plugins = &state.plugins;
+    let title = plugins
+        .active
+        .as_ref()
+        .map(|p| p.name.clone())
+        .unwrap_or_default();
+    let mut children = vec![to_value_or_text(
+        UiText::new(&title).size(20.0),
+        "plugin_title",
+    )];
+    match &plugins.ui {
+        Some(ui) => children.push(ui.clone()),
+        None if plugins.error.is_none() => children.push(to_value_or_text(
+            UiText::new(&t!("loading_running_plugin")).size(12.0),
+            "plugin_running",
+        )),
+        None => {}
+    }
+    if let Some(err) = &plugins.error {
+        children.push(to_value_or_text(
+            UiText::new(&error_text(err))
+                .size(12.0)
+                .content_description("plugin_error"),
+            "plugin_error",
+        ));
+    }
+
+    maybe_push_back(&mut children, state);
+
+    to_value_or_text(UiColumn::new(children).padding(20), "plugin_root")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const COUNTER_PLUGIN: &str = r#"
+int snprintf(char *out, unsigned long size, const char *format, ...);
+int atoi(const char *text);
+int strcmp(const char *a, const char *b);
+
+static char state[32];
+static char ui[512];
+
+const char *kv_render(const char *current) {
+    snprintf(ui, sizeof ui,
+             "{\"type\":\"Column\",\"children\":["
+             "{\"type\":\"Text\",\"text\":\"Count: %d\"},"
+             "{\"type\":\"Button\",\"text\":\"+1\",\"action\":\"increment\","
+             "\"requires_file_picker\":true}]}",
+             atoi(current));
+    return ui;
+}
+
+const char *kv_handle_action(const char *current, const char *action, const char *bindings) {
+    int count = atoi(current);
+    if (strcmp(action, "increment") == 0) count++;
+    snprintf(state, sizeof state, "%d", count);
+    return state;
+}
+"#;
+
+    #[test]
+    fn lists_and_installs_only_plugin_files() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "x").unwrap();
+        std::fs::write(dir.path().join("word_count.c"), "x").unwrap();
+        assert!(list_plugins(&dir.path().join("missing")).is_empty());
+
+        let installed = install_plugin(dir.path(), "/picked/dice-roller.bmir", &b"MIR"[..])
+            .expect("install ok");
+        assert_eq!(installed.id, "dice-roller.bmir");
+        assert_eq!(installed.name, "dice roller");
+        assert_eq!(
+            install_plugin(dir.path(), "evil.so", &b""[..]).unwrap_err(),
+            "plugin_unsupported_type:evil.so"
+        );
+
+        let names: Vec<String> = list_plugins(dir.path())
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["dice roller", "word count"]);
+    }
+
+    #[test]
+    fn plugin_actions_round_trip_through_the_jit() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("counter.c");
+        std::fs::write(&path, COUNTER_PLUGIN).unwrap();
+
+        let first = call_plugin(&path, None, "{}", INITIAL_PLUGIN_STATE).expect("render ok");
+        assert!(first.ui.to_string().contains("Count: 0"));
+        let button = &first.ui["children"][1];
+        assert_eq!(button["action"], "plugin:increment");
+        assert!(button.get("requires_file_picker").is_none());
+
+        let second = call_plugin(&path, Some("increment"), "{}", &first.data).expect("action ok");
+        assert_eq!(second.data, "1");
+        assert!(second.ui.to_string().contains("Count: 1"));
+
+        std::fs::write(dir.path().join("broken.c"), "int kv_render(").unwrap();
+        assert_eq!(
+            call_plugin(&dir.path().join("broken.c"), None, "{}", "{}").unwrap_err(),
+            "plugin_compile_failed"
+        );
+    }
+
+    #[test]
+    fn misbehaving_plugins_fail_the_call_not_the_app() {
+        let dir = tempdir().unwrap();
+        let garbage = dir.path().join("garbage.bmir");
+        std::fs::write(&garbage, b"not a MIR module").unwrap();
+        let err = call_plugin(&garbage, None, "{}", "{}").unwrap_err();
+        assert!(err.starts_with("plugin_invalid:"), "{err}");
+
+        let looping = dir.path().join("looping.c");
+        std::fs::write(
+            &looping,
+            "const char *kv_render(const char *s) { for (;;) {} }",
+        )
+        .unwrap();
+        assert_eq!(
+            call_plugin(&looping, None, "{}", "{}").unwrap_err(),
+            format!("plugin_timeout:{} s", PLUGIN_TIME_BUDGET_MS / 1000)
+        );
+
+        let crashing = dir.path().join("crashing.c");
+        std::fs::write(
+            &crashing,
+            "const char *kv_render(const char *s) { return *(const char **) 0; }",
+        )
+        .unwrap();
+        assert_eq!(
+            call_plugin(&crashing, None, "{}", "{}").unwrap_err(),
+            "plugin_crashed"
+        );
+
+        // The worker thread still serves well-behaved plugins afterwards.
+        let counter = dir.path().join("counter.c");
+        std::fs::write(&counter, COUNTER_PLUGIN).unwrap();
+        assert!(call_plugin(&counter, None, "{}", INITIAL_PLUGIN_STATE).is_ok());
+    }
+}
@@ -1,10 +1,13 @@
-use crate::features::storage::preferred_temp_dir;
-use crate::state::{AppState, Screen};
-use crate::ui::{maybe_push_back, Button, Column, Text, TextInput};
+use crate::error::error_text;
+use crate::features::dithering::palette_hex;
+use crate::features::storage::{output_dir_for, preferred_temp_dir};
+use crate::state::{AppState, DitheringPalette, PixelArtUpscaler, Screen};
+use crate::ui::{maybe_push_back, Button, Column, ReorderItem, ReorderableList, Text, TextInput};
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -14,6 +17,54 @@ pub struct Preset {
     pub tool_id: String,
     pub data: Value,
     pub created_at: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Shape of `data`, see [`payload_version`]. Presets saved before versioning read as 0.
+    #[serde(default)]
+    pub schema_version: u32,
+}
+
+/// What importing does with a preset whose id already exists on this device.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ImportConflict {
+    Skip,
+    Replace,
+    KeepBoth,
+}
+
+/// Tools whose settings can be captured by [`preset_payload_for_tool`].
+const PRESET_TOOLS: &[&str] = &[
+    "dithering",
+    "pixel_art",
+    "regex_tester",
+    "compass",
+    "barometer",
+    "magnetometer",
+    "date_calculator",
+];
+
+const BUNDLE_VERSION: u32 = 1;
+
+/// Preset ids in the user's order, one per line, next to the preset files.
+const ORDER_FILE: &str = "order.txt";
+
+/// Marks QR texts that carry a single preset rather than a file transfer frame.
+pub const PRESET_QR_PREFIX: &str = "KVPRESET1:";
+
+/// On-disk format of an exported preset file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PresetBundle {
+    pub version: u32,
+    pub exported_at: u64,
+    pub presets: Vec<Preset>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub imported: usize,
+    pub replaced: usize,
+    pub skipped: usize,
+    pub unsupported: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -21,10 +72,16 @@ pub struct PresetState {
     pub presets: Vec<Preset>,
     pub current_tool_id: Option<String>,
     pub name_input: String,
+    pub tags_input: String,
     pub filter_query: Option<String>,
     pub is_saving: bool,
     pub error: Option<String>,
     pub last_message: Option<String>,
+    pub import_conflict: ImportConflict,
+    /// Tool ids whose group is folded in the preset manager.
+    pub collapsed_tools: Vec<String>,
+    /// Groups are shown as draggable lists instead of cards.
+    pub reordering: bool,
 }
 
 impl PresetState {
@@ -33,10 +90,14 @@ impl PresetState {
             presets: Vec::new(),
             current_tool_id: None,
             name_input: String::new(),
+            tags_input: String::new(),
             filter_query: None,
             is_saving: false,
             error: None,
             last_message: None,
+            import_conflict: ImportConflict::Skip,
+            collapsed_tools: Vec::new(),
+            reordering: false,
         }
     }
 
@@ -44,10 +105,14 @@ impl PresetState {
         self.presets.clear();
         self.current_tool_id = None;
         self.name_input.clear();
+        self.tags_input.clear();
         self.filter_query = None;
         self.is_saving = false;
         self.error = None;
         self.last_message = None;
+        self.import_conflict = ImportConflict::Skip;
+        self.collapsed_tools.clear();
+        self.reordering = false;
     }
 }
 
@@ -86,10 +151,53 @@ pub fn load_presets() -> Result<Vec<Preset>, String> {
 
     // Sort by newest first
     presets.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    if let Ok(order) = fs::read_to_string(dir.join(ORDER_FILE)) {
+        let order: Vec<&str> = order.lines().collect();
+        apply_order(&mut presets, &order);
+    }
     Ok(presets)
 }
 
-pub fn save_preset(tool_id: &str, name: &str, data: Value) -> Result<Preset, String> {
+/// Puts presets listed in `order` in that order. Presets the user never placed, such as
+/// ones saved since, stay on top newest first.
+fn apply_order(presets: &mut [Preset], order: &[&str]) {
+    presets.sort_by_key(|p| order.iter().position(|id| *id == p.id).map_or(0, |i| i + 1));
+}
+
+/// Rearranges the presets named in `ids` into that order, using the slots they already
+/// occupy so presets of other tools or outside the current filter keep their places.
+pub fn move_presets(presets: &mut [Preset], ids: &[String]) {
+    let slots: Vec<usize> = presets
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| ids.contains(&p.id))
+        .map(|(i, _)| i)
+        .collect();
+    let moved: Vec<Preset> = ids
+        .iter()
+        .filter_map(|id| presets.iter().find(|p| p.id == *id).cloned())
+        .collect();
+    if moved.len() != slots.len() {
+        return;
+    }
+    for (slot, preset) in slots.into_iter().zip(moved) {
+        presets[slot] = preset;
+    }
+}
+
+pub fn save_preset_order(presets: &[Preset]) -> Result<(), String> {
+    let dir = presets_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("mkdir_failed:{e}"))?;
+    let ids: Vec<&str> = presets.iter().map(|p| p.id.as_str()).collect();
+    fs::write(dir.join(ORDER_FILE), ids.join("\n")).map_err(|e| format!("write_failed:{e}"))
+}
+
+pub fn save_preset(
+    tool_id: &str,
+    name: &str,
+    tags: Vec<String>,
+    data: Value,
+) -> Result<Preset, String> {
     let dir = presets_dir();
     fs::create_dir_all(&dir).map_err(|e| format!("mkdir_failed:{e}"))?;
 
@@ -103,13 +211,152 @@ pub fn save_preset(tool_id: &str, name: &str, data: Value) -> Result<Preset, Str
         tool_id: tool_id.to_string(),
         data,
         created_at: now.as_secs(),
+        tags,
+        schema_version: payload_version(tool_id),
     };
 
-    let path = dir.join(format!("{}.json", id));
-    let content = serde_json::to_string_pretty(&preset).map_err(|e| format!("json_err:{e}"))?;
+    write_preset(&dir, &preset)?;
+    Ok(preset)
+}
+
+/// Splits a comma separated tag list, normalising case and dropping duplicates.
+pub fn parse_tags(raw: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for part in raw.split(',') {
+        let tag = part.trim().trim_start_matches('#').trim().to_lowercase();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+fn write_preset(dir: &Path, preset: &Preset) -> Result<(), String> {
+    let path = dir.join(format!("{}.json", preset.id));
+    let content = serde_json::to_string_pretty(preset).map_err(|e| format!("json_err:{e}"))?;
+    fs::write(&path, content).map_err(|e| format!("write_failed:{e}"))
+}
+
+/// Writes `presets` as a [`PresetBundle`] into the output directory and returns its path.
+pub fn export_presets(presets: &[&Preset]) -> Result<PathBuf, String> {
+    if presets.is_empty() {
+        return Err("preset_export_empty".into());
+    }
+    let dir = output_dir_for(None);
+    fs::create_dir_all(&dir).map_err(|e| format!("mkdir_failed:{e}"))?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("clock_err:{e:?}"))?
+        .as_secs();
+    let bundle = PresetBundle {
+        version: BUNDLE_VERSION,
+        exported_at: now,
+        presets: presets.iter().map(|p| (*p).clone()).collect(),
+    };
+    let mut path = dir.join(format!("kistaverk_presets_{now}.json"));
+    let mut n = 1;
+    while path.exists() {
+        path = dir.join(format!("kistaverk_presets_{now}_{n}.json"));
+        n += 1;
+    }
+    let content = serde_json::to_string_pretty(&bundle).map_err(|e| format!("json_err:{e}"))?;
     fs::write(&path, content).map_err(|e| format!("write_failed:{e}"))?;
+    Ok(path)
+}
 
-    Ok(preset)
+/// Stores the presets of an exported bundle (or a bare preset list) in the presets
+/// directory, resolving id collisions with `conflict`.
+pub fn import_presets(content: &str, conflict: ImportConflict) -> Result<ImportSummary, String> {
+    let incoming = match serde_json::from_str::<PresetBundle>(content) {
+        Ok(bundle) if bundle.version > BUNDLE_VERSION => {
+            return Err(format!("preset_bundle_version:{}", bundle.version));
+        }
+        Ok(bundle) => bundle.presets,
+        Err(_) => serde_json::from_str::<Vec<Preset>>(content)
+            .map_err(|e| format!("preset_import_invalid:{e}"))?,
+    };
+    store_imported(incoming, conflict)
+}
+
+/// Text to encode in a QR code so another device can import `preset` by scanning it.
+pub fn preset_qr_payload(preset: &Preset) -> Result<String, String> {
+    let json = serde_json::to_string(preset).map_err(|e| format!("json_err:{e}"))?;
+    Ok(format!("{PRESET_QR_PREFIX}{json}"))
+}
+
+pub fn is_preset_qr(payload: &str) -> bool {
+    payload.trim_start().starts_with(PRESET_QR_PREFIX)
+}
+
+/// Imports a preset scanned from a QR code produced by [`preset_qr_payload`].
+pub fn import_preset_qr(payload: &str, conflict: ImportConflict) -> Result<ImportSummary, String> {
+    let json = payload
+        .trim()
+        .strip_prefix(PRESET_QR_PREFIX)
+        .ok_or_else(|| "preset_qr_invalid_prefix".to_string())?;
+    let preset =
+        serde_json::from_str::<Preset>(json).map_err(|e| format!("preset_qr_invalid:{e}"))?;
+    store_imported(vec![preset], conflict)
+}
+
+fn store_imported(
+    incoming: Vec<Preset>,
+    conflict: ImportConflict,
+) -> Result<ImportSummary, String> {
+    let dir = presets_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("mkdir_failed:{e}"))?;
+    let existing = load_presets()?;
+    let mut summary = ImportSummary::default();
+    for preset in incoming {
+        if !PRESET_TOOLS.contains(&preset.tool_id.as_str())
+            || !is_safe_id(&preset.id)
+            || preset.schema_version > payload_version(&preset.tool_id)
+        {
+            summary.unsupported += 1;
+            continue;
+        }
+        let Some(current) = existing.iter().find(|p| p.id == preset.id) else {
+            write_preset(&dir, &preset)?;
+            summary.imported += 1;
+            continue;
+        };
+        if current.tool_id == preset.tool_id && current.data == preset.data {
+            summary.skipped += 1;
+            continue;
+        }
+        match conflict {
+            ImportConflict::Skip => summary.skipped += 1,
+            ImportConflict::Replace => {
+                write_preset(&dir, &preset)?;
+                summary.replaced += 1;
+            }
+            ImportConflict::KeepBoth => {
+                let mut n = 2;
+                let mut id = format!("{}_{n}", preset.id);
+                while existing.iter().any(|p| p.id == id) || dir.join(format!("{id}.json")).exists()
+                {
+                    n += 1;
+                    id = format!("{}_{n}", preset.id);
+                }
+                let copy = Preset {
+                    id,
+                    name: format!("{} ({n})", preset.name),
+                    ..preset
+                };
+                write_preset(&dir, &copy)?;
+                summary.imported += 1;
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// Ids become file names, so imported ones must not be able to escape the presets dir.
+fn is_safe_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
 }
 
 pub fn delete_preset(id: &str) -> Result<(), String> {
@@ -121,15 +368,41 @@ pub fn delete_preset(id: &str) -> Result<(), String> {
     Ok(())
 }
 
+pub fn history_dir() -> PathBuf {
+    // Sibling of "presets" so history files never show up in the preset list
+    let mut path = presets_dir();
+    path.set_file_name("history");
+    path
+}
+
+pub fn save_tool_history(tool_id: &str, data: &Value) -> Result<(), String> {
+    let dir = history_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("mkdir_failed:{e}"))?;
+    let path = dir.join(format!("{}.json", tool_id));
+    let content = serde_json::to_string(data).map_err(|e| format!("json_err:{e}"))?;
+    fs::write(&path, content).map_err(|e| format!("write_failed:{e}"))
+}
+
+pub fn load_tool_history(tool_id: &str) -> Result<Option<Value>, String> {
+    let path = history_dir().join(format!("{}.json", tool_id));
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path).map_err(|e| format!("read_failed:{e}"))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("json_err:{e}"))
+}
+
 pub fn render_preset_manager(state: &AppState) -> Value {
     let mut children = vec![to_value_or_text(
-        Text::new("Presets").size(20.0),
+        Text::new(&t!("presets_title")).size(20.0),
         "presets_title",
     )];
 
     if let Some(tool) = &state.preset_state.current_tool_id {
         children.push(to_value_or_text(
-            Text::new(&format!("Managing presets for: {}", tool)).size(14.0),
+            Text::new(&t!("presets_managing_for", tool = tool)).size(14.0),
             "presets_tool",
         ));
     }
@@ -142,7 +415,7 @@ pub fn render_preset_manager(state: &AppState) -> Value {
     }
     if let Some(err) = &state.preset_state.error {
         children.push(to_value_or_text(
-            Text::new(&format!("Error: {}", err)).size(12.0),
+            Text::new(&error_text(err)).size(12.0),
             "presets_error",
         ));
     }
@@ -154,106 +427,194 @@ pub fn render_preset_manager(state: &AppState) -> Value {
         .unwrap_or("");
     children.push(to_value_or_text(
         TextInput::new("preset_filter")
-            .hint("Search presets")
+            .hint(&t!("presets_search_hint"))
             .text(filter_text)
             .debounce_ms(200)
             .action_on_submit("preset_filter"),
         "preset_filter_input",
     ));
 
-    let filter_query = state
-        .preset_state
-        .filter_query
-        .as_ref()
-        .map(|q| q.to_ascii_lowercase());
-
-    let filtered: Vec<&Preset> = state
-        .preset_state
-        .presets
-        .iter()
-        .filter(|p| {
-            if let Some(tid) = &state.preset_state.current_tool_id {
-                if &p.tool_id != tid {
-                    return false;
-                }
-            }
-            if let Some(q) = &filter_query {
-                let name = p.name.to_ascii_lowercase();
-                let tool_id = p.tool_id.to_ascii_lowercase();
-                if !name.contains(q) && !tool_id.contains(q) {
-                    return false;
-                }
-            }
-            true
-        })
-        .collect();
+    let filtered = visible_presets(&state.preset_state);
 
     if filtered.is_empty() {
         children.push(to_value_or_text(
-            Text::new("No presets found.").size(14.0),
+            Text::new(&t!("presets_empty")).size(14.0),
             "presets_empty",
         ));
     } else {
-        for preset in filtered {
-            let mut row_items = vec![
-                to_value_or_text(Text::new(&preset.name).size(16.0), "preset_name"),
-                to_value_or_text(
-                    Text::new(&format!("({})", preset.tool_id)).size(10.0),
-                    "preset_tool",
-                ),
-            ];
-
-            let load_btn = Button::new("Load", "preset_load").payload(json!({ "id": preset.id }));
-            row_items.push(to_value_or_text(load_btn, "preset_load_btn"));
-
-            let del_btn =
-                Button::new("Delete", "preset_delete").payload(json!({ "id": preset.id }));
-            row_items.push(to_value_or_text(del_btn, "preset_delete_btn"));
-
-            children.push(json!({
-                "type": "Card",
-                "child": {
-                    "type": "Column",
-                    "children": row_items
-                },
-                "padding": 8
-            }));
+        let mut tools: Vec<&str> = filtered.iter().map(|p| p.tool_id.as_str()).collect();
+        tools.sort_unstable();
+        tools.dedup();
+        for tool in tools {
+            let group: Vec<&Preset> = filtered
+                .iter()
+                .copied()
+                .filter(|p| p.tool_id == tool)
+                .collect();
+            let collapsed = state.preset_state.collapsed_tools.iter().any(|t| t == tool);
+            let marker = if collapsed { "▸" } else { "▾" };
+            children.push(to_value_or_text(
+                Button::new(
+                    &format!("{marker} {tool} ({})", group.len()),
+                    "preset_toggle_group",
+                )
+                .payload(json!({ "tool_id": tool })),
+                "preset_group_btn",
+            ));
+            if collapsed {
+                continue;
+            }
+            if state.preset_state.reordering {
+                let items = group
+                    .iter()
+                    .map(|p| ReorderItem::new(p.id.clone(), p.name.clone()))
+                    .collect();
+                children.push(to_value_or_text(
+                    ReorderableList::new("preset_order", items).action("preset_reorder"),
+                    "preset_order_list",
+                ));
+                continue;
+            }
+            for preset in group {
+                children.push(preset_card(preset));
+            }
         }
+        let reorder_label = if state.preset_state.reordering {
+            t!("presets_done_reordering_button")
+        } else {
+            t!("presets_reorder_button")
+        };
+        children.push(to_value_or_text(
+            Button::new(&reorder_label, "preset_reorder_mode"),
+            "preset_reorder_mode_btn",
+        ));
     }
 
     children.push(to_value_or_text(
-        Button::new("Create New Preset", "preset_save_dialog"),
+        Button::new(&t!("presets_create_button"), "preset_save_dialog"),
         "preset_create_btn",
     ));
 
+    children.push(to_value_or_text(
+        Text::new(&t!("presets_backup_section")).size(16.0),
+        "presets_backup_title",
+    ));
+    children.push(to_value_or_text(
+        Button::new(&t!("presets_export_shown_button"), "preset_export"),
+        "preset_export_btn",
+    ));
+    children.push(to_value_or_text(
+        Button::new(&t!("presets_export_all_button"), "preset_export_all"),
+        "preset_export_all_btn",
+    ));
+    children.push(to_value_or_text(
+        Text::new(&t!("presets_conflict_label")).size(12.0),
+        "presets_conflict_label",
+    ));
+    for (policy, label, action) in [
+        (
+            ImportConflict::Skip,
+            t!("presets_conflict_keep_mine"),
+            "preset_conflict_skip",
+        ),
+        (
+            ImportConflict::Replace,
+            t!("presets_conflict_replace"),
+            "preset_conflict_replace",
+        ),
+        (
+            ImportConflict::KeepBoth,
+            t!("presets_conflict_keep_both"),
+            "preset_conflict_keep_both",
+        ),
+    ] {
+        let mut button = Button::new(&label, action);
+        if policy == state.preset_state.import_conflict {
+            button = button.content_description("selected");
+        }
+        children.push(to_value_or_text(button, "preset_conflict_btn"));
+    }
+    children.push(to_value_or_text(
+        Button::new(&t!("presets_import_button"), "preset_import").requires_file_picker(true),
+        "preset_import_btn",
+    ));
+
     maybe_push_back(&mut children, state);
     to_value_or_text(Column::new(children).padding(16), "presets_root")
 }
 
+fn preset_card(preset: &Preset) -> Value {
+    let mut row_items = vec![
+        to_value_or_text(Text::new(&preset.name).size(16.0), "preset_name"),
+        to_value_or_text(
+            Text::new(&format!("({})", preset.tool_id)).size(10.0),
+            "preset_tool",
+        ),
+    ];
+
+    for tag in &preset.tags {
+        let label = format!("#{tag}");
+        let tag_btn =
+            Button::new(&label, "preset_filter").payload(json!({ "preset_filter": label }));
+        row_items.push(to_value_or_text(tag_btn, "preset_tag_btn"));
+    }
+
+    let load_label = t!("presets_load_button");
+    let load_btn = Button::new(&load_label, "preset_load").payload(json!({ "id": preset.id }));
+    row_items.push(to_value_or_text(load_btn, "preset_load_btn"));
+
+    let share_label = t!("presets_share_qr_button");
+    let share_btn =
+        Button::new(&share_label, "preset_share_qr").payload(json!({ "id": preset.id }));
+    row_items.push(to_value_or_text(share_btn, "preset_share_qr_btn"));
+
+    let delete_label = t!("presets_delete_button");
+    let del_btn = Button::new(&delete_label, "preset_delete").payload(json!({ "id": preset.id }));
+    row_items.push(to_value_or_text(del_btn, "preset_delete_btn"));
+
+    json!({
+        "type": "Card",
+        "child": {
+            "type": "Column",
+            "children": row_items
+        },
+        "padding": 8
+    })
+}
+
 pub fn render_save_preset_dialog(state: &AppState) -> Value {
     let mut children = vec![
-        to_value_or_text(Text::new("Save Preset").size(20.0), "presets_save_title"),
         to_value_or_text(
-            Text::new("Enter a name for your preset:").size(14.0),
+            Text::new(&t!("presets_save_title")).size(20.0),
+            "presets_save_title",
+        ),
+        to_value_or_text(
+            Text::new(&t!("presets_save_description")).size(14.0),
             "presets_save_subtitle",
         ),
         json!({
             "type": "Input",
             "bind_key": "preset_name",
-            "hint": "Preset Name",
+            "hint": t!("presets_name_hint"),
             "value": state.preset_state.name_input
         }),
+        to_value_or_text(
+            TextInput::new("preset_tags")
+                .hint(&t!("presets_tags_hint"))
+                .text(&state.preset_state.tags_input),
+            "presets_save_tags",
+        ),
     ];
 
     if let Some(err) = &state.preset_state.error {
         children.push(to_value_or_text(
-            Text::new(&format!("Error: {}", err)).size(12.0),
+            Text::new(&error_text(err)).size(12.0),
             "presets_save_error",
         ));
     }
 
     children.push(to_value_or_text(
-        Button::new("Save", "preset_save"),
+        Button::new(&t!("presets_save_button"), "preset_save"),
         "presets_save_btn",
     ));
 
@@ -261,6 +622,41 @@ pub fn render_save_preset_dialog(state: &AppState) -> Value {
     to_value_or_text(Column::new(children).padding(16), "presets_save_root")
 }
 
+/// Presets matching the current tool and search query, as listed on the manager screen.
+pub fn visible_presets(preset_state: &PresetState) -> Vec<&Preset> {
+    let filter_query = preset_state
+        .filter_query
+        .as_ref()
+        .map(|q| q.to_ascii_lowercase());
+
+    preset_state
+        .presets
+        .iter()
+        .filter(|p| {
+            if let Some(tid) = &preset_state.current_tool_id {
+                if &p.tool_id != tid {
+                    return false;
+                }
+            }
+            if let Some(q) = &filter_query {
+                // "#tag" only matches tags exactly; plain text also searches names and tools.
+                if let Some(tag) = q.strip_prefix('#') {
+                    return p.tags.iter().any(|t| t == tag);
+                }
+                let name = p.name.to_ascii_lowercase();
+                let tool_id = p.tool_id.to_ascii_lowercase();
+                if !name.contains(q)
+                    && !tool_id.contains(q)
+                    && !p.tags.iter().any(|t| t.contains(q))
+                {
+                    return false;
+                }
+            }
+            true
+        })
+        .collect()
+}
+
 fn to_value_or_text<T: Serialize>(value: T, context: &str) -> Value {
     serde_json::to_value(value).unwrap_or_else(|e| {
         json!({
@@ -275,26 +671,150 @@ pub fn preset_payload_for_tool(state: &AppState, tool_id: &str) -> Result<Value,
     match tool_id {
         "dithering" => Ok(json!({
             "mode": state.dithering_mode,
-            "palette": state.dithering_palette
+            "palette": state.dithering_palette,
+            "serpentine": state.dithering_serpentine
         })),
         "pixel_art" => Ok(json!({
-            "scale_factor": state.pixel_art.scale_factor
+            "scale_factor": state.pixel_art.scale_factor,
+            "upscaler": state.pixel_art.upscaler,
+            "crt": state.pixel_art.crt,
+            "palette_lock": state.pixel_art.palette_lock
+        })),
+        "regex_tester" => {
+            if state.regex_tester.pattern.trim().is_empty() {
+                return Err("regex_pattern_empty".into());
+            }
+            Ok(json!({
+                "pattern": state.regex_tester.pattern,
+                "flags": state.regex_tester.flags,
+                "global_mode": state.regex_tester.global_mode
+            }))
+        }
+        "compass" => Ok(json!({
+            "declination_deg": state.sensor_calibration.compass_declination_deg,
+            "offset_deg": state.sensor_calibration.compass_offset_deg
+        })),
+        "barometer" => Ok(json!({
+            "offset_hpa": state.sensor_calibration.barometer_offset_hpa
+        })),
+        "magnetometer" => Ok(json!({
+            "offset_ut": state.sensor_calibration.magnetometer_offset_ut
+        })),
+        "date_calculator" => Ok(json!({
+            "from_tz": state.date_calc.from_tz,
+            "to_tz": state.date_calc.to_tz,
+            "cron": state.date_calc.cron,
+            "shift_amount": state.date_calc.shift_amount,
+            "shift_unit": state.date_calc.shift_unit
         })),
         _ => Err(format!("Tool '{}' does not support presets", tool_id)),
     }
 }
 
+/// Current payload schema of each tool. Bump it together with a step in
+/// [`migrate_payload`] whenever [`preset_payload_for_tool`] changes shape.
+pub fn payload_version(tool_id: &str) -> u32 {
+    match tool_id {
+        "dithering" | "pixel_art" => 2,
+        _ => 1,
+    }
+}
+
+/// Upgrades a payload saved with schema `version` to the current one for `tool_id`.
+/// Unversioned presets are treated as version 1.
+pub fn migrate_payload(tool_id: &str, version: u32, data: &Value) -> Result<Value, String> {
+    let current = payload_version(tool_id);
+    if version > current {
+        return Err(format!("preset_version_unsupported:{version}"));
+    }
+    let mut data = data.clone();
+    let fields = data
+        .as_object_mut()
+        .ok_or_else(|| "preset_payload_not_object".to_string())?;
+    for step in version.max(1)..current {
+        match (tool_id, step) {
+            // v2 added serpentine scanning; older presets keep the raster scan.
+            ("dithering", 1) => {
+                fields.entry("serpentine").or_insert(json!(false));
+            }
+            // v2 added upscalers, CRT and palette lock; older presets only stored the scale.
+            ("pixel_art", 1) => {
+                fields
+                    .entry("upscaler")
+                    .or_insert(json!(PixelArtUpscaler::Nearest));
+                fields.entry("crt").or_insert(json!(false));
+                fields.entry("palette_lock").or_insert(Value::Null);
+            }
+            _ => return Err(format!("preset_migration_missing:{tool_id}:{step}")),
+        }
+    }
+    Ok(data)
+}
+
+fn preset_offset(data: &Value, key: &str) -> Result<f64, String> {
+    data[key]
+        .as_f64()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| format!("bad_{key}"))
+}
+
 // Helper to apply state from preset
 pub fn apply_preset_to_state(state: &mut AppState, preset: &Preset) -> Result<(), String> {
+    let data = migrate_payload(&preset.tool_id, preset.schema_version, &preset.data)?;
     if preset.tool_id == "dithering" {
-        state.dithering_mode = serde_json::from_value(preset.data["mode"].clone())
-            .map_err(|e| format!("bad_mode:{e}"))?;
-        state.dithering_palette = serde_json::from_value(preset.data["palette"].clone())
+        state.dithering_mode =
+            serde_json::from_value(data["mode"].clone()).map_err(|e| format!("bad_mode:{e}"))?;
+        state.dithering_palette = serde_json::from_value(data["palette"].clone())
             .map_err(|e| format!("bad_palette:{e}"))?;
+        if let DitheringPalette::Custom(colors) = &state.dithering_palette {
+            state.dithering_custom_palette = palette_hex(colors);
+        }
+        state.dithering_serpentine = data["serpentine"].as_bool().unwrap_or(false);
         Ok(())
     } else if preset.tool_id == "pixel_art" {
-        state.pixel_art.scale_factor = serde_json::from_value(preset.data["scale_factor"].clone())
+        state.pixel_art.scale_factor = serde_json::from_value(data["scale_factor"].clone())
             .map_err(|e| format!("bad_scale:{e}"))?;
+        state.pixel_art.upscaler = serde_json::from_value(data["upscaler"].clone())
+            .map_err(|e| format!("bad_upscaler:{e}"))?;
+        state.pixel_art.crt = data["crt"].as_bool().unwrap_or(false);
+        state.pixel_art.palette_lock = serde_json::from_value(data["palette_lock"].clone())
+            .map_err(|e| format!("bad_palette:{e}"))?;
+        Ok(())
+    } else if preset.tool_id == "regex_tester" {
+        state.regex_tester.pattern = serde_json::from_value(data["pattern"].clone())
+            .map_err(|e| format!("bad_pattern:{e}"))?;
+        state.regex_tester.flags =
+            serde_json::from_value(data["flags"].clone()).map_err(|e| format!("bad_flags:{e}"))?;
+        state.regex_tester.global_mode = data["global_mode"].as_bool().unwrap_or(false);
+        state.regex_tester.match_results.clear();
+        state.regex_tester.error = None;
+        Ok(())
+    } else if preset.tool_id == "compass" {
+        state.sensor_calibration.compass_declination_deg = preset_offset(&data, "declination_deg")?;
+        state.sensor_calibration.compass_offset_deg = preset_offset(&data, "offset_deg")?;
+        state.compass_filter_angle = None;
+        Ok(())
+    } else if preset.tool_id == "barometer" {
+        state.sensor_calibration.barometer_offset_hpa = preset_offset(&data, "offset_hpa")?;
+        state.barometer_filter_value = None;
+        Ok(())
+    } else if preset.tool_id == "magnetometer" {
+        state.sensor_calibration.magnetometer_offset_ut = preset_offset(&data, "offset_ut")?;
+        state.magnetometer_filter_value = None;
+        Ok(())
+    } else if preset.tool_id == "date_calculator" {
+        let calc = &mut state.date_calc;
+        for (key, field) in [
+            ("from_tz", &mut calc.from_tz),
+            ("to_tz", &mut calc.to_tz),
+            ("cron", &mut calc.cron),
+            ("shift_amount", &mut calc.shift_amount),
+        ] {
+            *field = data[key].as_str().unwrap_or_default().to_string();
+        }
+        calc.shift_unit = serde_json::from_value(data["shift_unit"].clone())
+            .map_err(|e| format!("bad_shift_unit:{e}"))?;
+        calc.compute(Utc::now());
         Ok(())
     } else {
         Err(format!("Unknown tool id in preset: {}", preset.tool_id))
@@ -305,6 +825,11 @@ pub fn tool_id_for_screen(screen: Screen) -> Option<&'static str> {
     match screen {
         Screen::Dithering => Some("dithering"),
         Screen::PixelArt => Some("pixel_art"),
+        Screen::RegexTester => Some("regex_tester"),
+        Screen::Compass => Some("compass"),
+        Screen::Barometer => Some("barometer"),
+        Screen::Magnetometer => Some("magnetometer"),
+        Screen::DateCalc => Some("date_calculator"),
         _ => None,
     }
 }
@@ -312,6 +837,7 @@ pub fn tool_id_for_screen(screen: Screen) -> Option<&'static str> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::features::date_calc::{ShiftUnit, CRON_PREVIEW_RUNS};
     use crate::state::{DitheringMode, DitheringPalette};
 
     #[test]
@@ -337,6 +863,8 @@ mod tests {
                 "palette": DitheringPalette::Cga
             }),
             created_at: 0,
+            tags: Vec::new(),
+            schema_version: 0,
         };
 
         apply_preset_to_state(&mut state, &preset).unwrap();
@@ -344,6 +872,175 @@ mod tests {
         assert_eq!(state.dithering_palette, DitheringPalette::Cga);
     }
 
+    #[test]
+    fn test_dithering_preset_restores_custom_palette_text() {
+        let mut state = AppState::new();
+        state.dithering_palette = DitheringPalette::Custom(vec![[0, 0, 0], [255, 136, 0]]);
+        let payload = preset_payload_for_tool(&state, "dithering").unwrap();
+
+        let mut restored = AppState::new();
+        let preset = Preset {
+            id: "custom".into(),
+            name: "Custom".into(),
+            tool_id: "dithering".into(),
+            data: payload,
+            created_at: 0,
+            tags: Vec::new(),
+            schema_version: 0,
+        };
+        apply_preset_to_state(&mut restored, &preset).unwrap();
+        assert_eq!(restored.dithering_palette, state.dithering_palette);
+        assert_eq!(restored.dithering_custom_palette, "#000000, #FF8800");
+    }
+
+    #[test]
+    fn test_pixel_art_preset_round_trips_effects() {
+        let mut state = AppState::new();
+        state.pixel_art.scale_factor = 8;
+        state.pixel_art.upscaler = PixelArtUpscaler::Hq2x;
+        state.pixel_art.crt = true;
+        state.pixel_art.palette_lock = Some(DitheringPalette::Cga);
+        let payload = preset_payload_for_tool(&state, "pixel_art").unwrap();
+
+        let mut restored = AppState::new();
+        let preset = Preset {
+            id: "retro".into(),
+            name: "Retro".into(),
+            tool_id: "pixel_art".into(),
+            data: payload,
+            created_at: 0,
+            tags: Vec::new(),
+            schema_version: 0,
+        };
+        apply_preset_to_state(&mut restored, &preset).unwrap();
+        assert_eq!(restored.pixel_art.scale_factor, 8);
+        assert_eq!(restored.pixel_art.upscaler, PixelArtUpscaler::Hq2x);
+        assert!(restored.pixel_art.crt);
+        assert_eq!(restored.pixel_art.palette_lock, Some(DitheringPalette::Cga));
+
+        let legacy = Preset {
+            data: json!({ "scale_factor": 2 }),
+            ..preset
+        };
+        apply_preset_to_state(&mut restored, &legacy).unwrap();
+        assert_eq!(restored.pixel_art.upscaler, PixelArtUpscaler::Nearest);
+        assert!(!restored.pixel_art.crt);
+        assert_eq!(restored.pixel_art.palette_lock, None);
+    }
+
+    #[test]
+    fn test_dithering_preset_round_trips_serpentine() {
+        let mut state = AppState::new();
+        state.dithering_mode = DitheringMode::Stucki;
+        state.dithering_serpentine = true;
+        let payload = preset_payload_for_tool(&state, "dithering").unwrap();
+
+        let mut restored = AppState::new();
+        let preset = Preset {
+            id: "serpentine".into(),
+            name: "Serpentine".into(),
+            tool_id: "dithering".into(),
+            data: payload,
+            created_at: 0,
+            tags: Vec::new(),
+            schema_version: 0,
+        };
+        apply_preset_to_state(&mut restored, &preset).unwrap();
+        assert_eq!(restored.dithering_mode, DitheringMode::Stucki);
+        assert!(restored.dithering_serpentine);
+    }
+
+    #[test]
+    fn test_regex_preset_round_trip() {
+        let mut state = AppState::new();
+        assert!(preset_payload_for_tool(&state, "regex_tester").is_err());
+
+        state.regex_tester.pattern = "[0-9]+".into();
+        state.regex_tester.flags.case_insensitive = true;
+        state.regex_tester.global_mode = true;
+        let preset = Preset {
+            id: "regex".into(),
+            name: "Digits".into(),
+            tool_id: "regex_tester".into(),
+            data: preset_payload_for_tool(&state, "regex_tester").unwrap(),
+            created_at: 0,
+            tags: Vec::new(),
+            schema_version: 0,
+        };
+
+        let mut restored = AppState::new();
+        apply_preset_to_state(&mut restored, &preset).unwrap();
+        assert_eq!(restored.regex_tester.pattern, "[0-9]+");
+        assert!(restored.regex_tester.flags.case_insensitive);
+        assert!(restored.regex_tester.global_mode);
+        assert_eq!(tool_id_for_screen(Screen::RegexTester), Some("regex_tester"));
+    }
+
+    #[test]
+    fn test_compass_calibration_preset_round_trip() {
+        let mut state = AppState::new();
+        state.sensor_calibration.compass_declination_deg = 4.5;
+        state.sensor_calibration.compass_offset_deg = -2.0;
+        let preset = Preset {
+            id: "north".into(),
+            name: "Reykjavik".into(),
+            tool_id: "compass".into(),
+            data: preset_payload_for_tool(&state, "compass").unwrap(),
+            created_at: 0,
+            tags: Vec::new(),
+            schema_version: 0,
+        };
+
+        let mut restored = AppState::new();
+        restored.sensor_calibration.barometer_offset_hpa = 1.0;
+        apply_preset_to_state(&mut restored, &preset).unwrap();
+        assert_eq!(restored.sensor_calibration.compass_declination_deg, 4.5);
+        assert_eq!(restored.sensor_calibration.compass_offset_deg, -2.0);
+        // Other sensors keep their own calibration.
+        assert_eq!(restored.sensor_calibration.barometer_offset_hpa, 1.0);
+        assert_eq!(tool_id_for_screen(Screen::Barometer), Some("barometer"));
+    }
+
+    #[test]
+    fn test_date_calculator_preset_round_trip() {
+        let mut state = AppState::new();
+        state.date_calc.from_tz = "Europe/Paris".into();
+        state.date_calc.to_tz = "Asia/Tokyo".into();
+        state.date_calc.cron = "0 9 * * 1".into();
+        state.date_calc.shift_amount = "90".into();
+        state.date_calc.shift_unit = ShiftUnit::Weeks;
+        let preset = Preset {
+            id: "standup".into(),
+            name: "Tokyo standup".into(),
+            tool_id: "date_calculator".into(),
+            data: preset_payload_for_tool(&state, "date_calculator").unwrap(),
+            created_at: 0,
+            tags: Vec::new(),
+            schema_version: 1,
+        };
+
+        let mut restored = AppState::new();
+        restored.date_calc.date_a = "2024-05-01".into();
+        apply_preset_to_state(&mut restored, &preset).unwrap();
+        let calc = &restored.date_calc;
+        assert_eq!(
+            (calc.from_tz.as_str(), calc.to_tz.as_str()),
+            ("Europe/Paris", "Asia/Tokyo")
+        );
+        assert_eq!(calc.shift_unit, ShiftUnit::Weeks);
+        // Dates are not part of the preset, and results are ready on return.
+        assert_eq!(calc.date_a, "2024-05-01");
+        assert_eq!(
+            calc.shifted.as_ref().map(|s| s.text.as_str()),
+            Some("2026-01-21")
+        );
+        assert_eq!(calc.cron_runs.len(), CRON_PREVIEW_RUNS);
+        assert_eq!(
+            tool_id_for_screen(Screen::DateCalc),
+            Some("date_calculator")
+        );
+    }
+
     #[test]
     fn test_persistence_cycle() {
         use std::env;
@@ -365,7 +1062,7 @@ mod tests {
         let data = json!({"foo": "bar"});
 
         // 1. Save
-        let saved = save_preset(tool_id, name, data.clone()).expect("save failed");
+        let saved = save_preset(tool_id, name, Vec::new(), data.clone()).expect("save failed");
         assert_eq!(saved.name, name);
         assert_eq!(saved.tool_id, tool_id);
 
@@ -376,6 +1073,7 @@ mod tests {
             .find(|p| p.id == saved.id)
             .expect("preset not found");
         assert_eq!(loaded.data, data);
+        assert_eq!(loaded.schema_version, payload_version(tool_id));
 
         // 3. Delete
         delete_preset(&saved.id).expect("delete failed");
@@ -387,6 +1085,119 @@ mod tests {
         env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
+    #[test]
+    fn test_export_import_resolves_conflicts() {
+        use crate::features::storage::test_env_lock;
+
+        let _guard = test_env_lock().lock().expect("lock env");
+        let root_dir = tempfile::tempdir().unwrap();
+        let cache_dir = root_dir.path().join("cache");
+        std::env::set_var("KISTAVERK_TEMP_DIR", &cache_dir);
+
+        let kept = save_preset(
+            "pixel_art",
+            "Chunky",
+            Vec::new(),
+            json!({"scale_factor": 8}),
+        )
+        .unwrap();
+        let edited = save_preset(
+            "dithering",
+            "Gloomy",
+            Vec::new(),
+            json!({"mode": "Atkinson"}),
+        )
+        .unwrap();
+        let exported = export_presets(&[&kept, &edited]).unwrap();
+        assert!(exported.starts_with(&cache_dir));
+        let content = fs::read_to_string(&exported).unwrap();
+        let bundle: PresetBundle = serde_json::from_str(&content).unwrap();
+        assert_eq!(bundle.version, BUNDLE_VERSION);
+        assert_eq!(bundle.presets.len(), 2);
+
+        // Change one preset locally so re-importing the export conflicts with it.
+        let local = Preset {
+            data: json!({"mode": "Sierra"}),
+            ..edited.clone()
+        };
+        write_preset(&presets_dir(), &local).unwrap();
+        delete_preset(&kept.id).unwrap();
+
+        let summary = import_presets(&content, ImportConflict::Skip).unwrap();
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.skipped, 1);
+        let sierra = load_presets().unwrap();
+        assert!(sierra
+            .iter()
+            .any(|p| p.id == edited.id && p.data == local.data));
+
+        let summary = import_presets(&content, ImportConflict::KeepBoth).unwrap();
+        assert_eq!((summary.imported, summary.skipped), (1, 1));
+        let all = load_presets().unwrap();
+        let copy = all
+            .iter()
+            .find(|p| p.id == format!("{}_2", edited.id))
+            .unwrap();
+        assert_eq!(copy.name, "Gloomy (2)");
+        assert_eq!(copy.data, edited.data);
+
+        let summary = import_presets(&content, ImportConflict::Replace).unwrap();
+        assert_eq!(summary.replaced, 1);
+        let all = load_presets().unwrap();
+        assert!(all
+            .iter()
+            .any(|p| p.id == edited.id && p.data == edited.data));
+
+        let hostile = json!([{
+            "id": "../escape",
+            "name": "x",
+            "tool_id": "dithering",
+            "data": {},
+            "created_at": 0
+        }, {
+            "id": "future_tool_1",
+            "name": "y",
+            "tool_id": "not_a_tool",
+            "data": {},
+            "created_at": 0
+        }]);
+        let summary = import_presets(&hostile.to_string(), ImportConflict::Replace).unwrap();
+        assert_eq!(summary.unsupported, 2);
+        assert!(import_presets("{not json", ImportConflict::Skip)
+            .unwrap_err()
+            .starts_with("preset_import_invalid:"));
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
+
+    #[test]
+    fn test_preset_qr_payload_round_trip() {
+        use crate::features::storage::test_env_lock;
+
+        let _guard = test_env_lock().lock().expect("lock env");
+        let root_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", root_dir.path().join("cache"));
+
+        let tags = vec!["film".to_string()];
+        let preset = save_preset("dithering", "Grainy", tags, json!({"mode": "Stucki"})).unwrap();
+        let payload = preset_qr_payload(&preset).unwrap();
+        assert!(is_preset_qr(&payload));
+        assert!(!is_preset_qr("QRTX|1/1|AAAA"));
+
+        delete_preset(&preset.id).unwrap();
+        let summary = import_preset_qr(&payload, ImportConflict::Skip).unwrap();
+        assert_eq!(summary.imported, 1);
+        assert_eq!(load_presets().unwrap(), vec![preset]);
+
+        let summary = import_preset_qr(&payload, ImportConflict::KeepBoth).unwrap();
+        assert_eq!(summary.skipped, 1);
+        assert!(import_preset_qr("KVPRESET1:{", ImportConflict::Skip)
+            .unwrap_err()
+            .starts_with("preset_qr_invalid:"));
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
+
     #[test]
     fn test_preset_filtering_logic() {
         let mut state = AppState::new();
@@ -399,6 +1210,8 @@ mod tests {
                 tool_id: "dithering".to_string(),
                 data: json!({"mode": "bayer", "palette": "gameboy"}),
                 created_at: 1000,
+                tags: Vec::new(),
+                schema_version: 0,
             },
             Preset {
                 id: "2".to_string(),
@@ -406,6 +1219,8 @@ mod tests {
                 tool_id: "pixel_art".to_string(),
                 data: json!({"scale_factor": 2}),
                 created_at: 2000,
+                tags: Vec::new(),
+                schema_version: 0,
             },
             Preset {
                 id: "3".to_string(),
@@ -413,6 +1228,8 @@ mod tests {
                 tool_id: "dithering".to_string(),
                 data: json!({"mode": "sierra", "palette": "cga"}),
                 created_at: 3000,
+                tags: Vec::new(),
+                schema_version: 0,
             },
         ];
 
@@ -519,4 +1336,135 @@ mod tests {
         assert_eq!(filtered.len(), 2);
         assert!(filtered.iter().all(|p| p.tool_id == "dithering"));
     }
+
+    #[test]
+    fn test_tags_are_parsed_and_searchable() {
+        assert_eq!(
+            parse_tags(" Retro, #film,, retro ,handheld "),
+            vec!["retro", "film", "handheld"]
+        );
+
+        let mut preset_state = PresetState::new();
+        preset_state.presets = vec![
+            Preset {
+                id: "1".into(),
+                name: "Game Boy".into(),
+                tool_id: "dithering".into(),
+                data: json!({}),
+                created_at: 0,
+                tags: parse_tags("retro, handheld"),
+                schema_version: 0,
+            },
+            Preset {
+                id: "2".into(),
+                name: "Retro chunky".into(),
+                tool_id: "pixel_art".into(),
+                data: json!({}),
+                created_at: 0,
+                tags: Vec::new(),
+                schema_version: 0,
+            },
+        ];
+
+        preset_state.filter_query = Some("hand".into());
+        let ids: Vec<_> = visible_presets(&preset_state)
+            .iter()
+            .map(|p| p.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["1"]);
+
+        preset_state.filter_query = Some("retro".into());
+        assert_eq!(visible_presets(&preset_state).len(), 2);
+
+        preset_state.filter_query = Some("#retro".into());
+        let ids: Vec<_> = visible_presets(&preset_state)
+            .iter()
+            .map(|p| p.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["1"]);
+
+        let legacy: Preset = serde_json::from_value(json!({
+            "id": "old",
+            "name": "Old",
+            "tool_id": "dithering",
+            "data": {},
+            "created_at": 0
+        }))
+        .unwrap();
+        assert!(legacy.tags.is_empty());
+        assert!(serde_json::to_value(&legacy).unwrap().get("tags").is_none());
+    }
+
+    #[test]
+    fn test_payload_migration_fills_new_fields() {
+        let legacy = json!({ "scale_factor": 4, "crt": true });
+        let migrated = migrate_payload("pixel_art", 0, &legacy).unwrap();
+        assert_eq!(migrated["scale_factor"], json!(4));
+        assert_eq!(migrated["crt"], json!(true));
+        assert_eq!(migrated["upscaler"], json!(PixelArtUpscaler::Nearest));
+        assert!(migrated["palette_lock"].is_null());
+
+        let migrated = migrate_payload("dithering", 1, &json!({ "mode": "Bayer4x4" })).unwrap();
+        assert_eq!(migrated["serpentine"], json!(false));
+
+        let current = json!({ "pattern": "a+" });
+        assert_eq!(
+            migrate_payload("regex_tester", 1, &current).unwrap(),
+            current
+        );
+        assert_eq!(
+            migrate_payload("dithering", 3, &json!({})).unwrap_err(),
+            "preset_version_unsupported:3"
+        );
+
+        let mut state = AppState::new();
+        let future = Preset {
+            id: "future".into(),
+            name: "Future".into(),
+            tool_id: "dithering".into(),
+            data: json!({ "mode": "Atkinson", "palette": "Cga", "serpentine": true }),
+            created_at: 0,
+            tags: Vec::new(),
+            schema_version: payload_version("dithering") + 1,
+        };
+        assert!(apply_preset_to_state(&mut state, &future).is_err());
+        assert!(!state.dithering_serpentine);
+    }
+
+    #[test]
+    fn moving_a_group_keeps_other_presets_in_place() {
+        let preset = |id: &str, tool_id: &str| Preset {
+            id: id.into(),
+            name: id.into(),
+            tool_id: tool_id.into(),
+            data: json!({}),
+            created_at: 0,
+            tags: Vec::new(),
+            schema_version: 0,
+        };
+        let ids = |presets: &[Preset]| presets.iter().map(|p| p.id.clone()).collect::<Vec<_>>();
+        let mut presets = vec![
+            preset("d1", "dithering"),
+            preset("c1", "compass"),
+            preset("d2", "dithering"),
+            preset("d3", "dithering"),
+        ];
+
+        move_presets(&mut presets, &["d3".into(), "d1".into(), "d2".into()]);
+        assert_eq!(ids(&presets), ["d3", "c1", "d1", "d2"]);
+
+        // Unknown or repeated ids leave the list alone.
+        move_presets(&mut presets, &["d1".into(), "d1".into(), "d2".into()]);
+        move_presets(&mut presets, &["d1".into(), "gone".into()]);
+        assert_eq!(ids(&presets), ["d3", "c1", "d1", "d2"]);
+
+        // A newer preset the user never placed stays on top.
+        let mut loaded = vec![
+            preset("new", "compass"),
+            preset("d1", "dithering"),
+            preset("d2", "dithering"),
+        ];
+        apply_order(&mut loaded, &["d2", "d1"]);
+        assert_eq!(ids(&loaded), ["new", "d2", "d1"]);
+    }
 }
@@ -1,5 +1,5 @@
 use crate::state::{AppState, Screen};
-use crate::ui::{Button as UiButton, Column as UiColumn, Text as UiText};
+use crate::ui::{Button as UiButton, Column as UiColumn, Image as UiImage, Text as UiText};
 use base64::Engine;
 use image::{codecs::png::PngEncoder, ColorType, ImageBuffer, ImageEncoder, Luma};
 use qrcode::{Color, QrCode};
@@ -70,7 +70,9 @@ pub fn render_qr_screen(state: &AppState) -> serde_json::Value {
         children.push(serde_json::to_value(UiText::new(&t!("qr_generator_result_label")).size(14.0)).unwrap());
         children.push(
             serde_json::to_value(
-                crate::ui::ImageBase64::new(b64).content_description(&t!("qr_generated_content_description")),
+                UiImage::from_base64(b64)
+                    .zoomable(true)
+                    .content_description(&t!("qr_generated_content_description")),
             )
             .unwrap(),
         );
@@ -1,3 +1,5 @@
+use crate::error::error_text;
+use crate::features::presets::{import_preset_qr, is_preset_qr};
 use crate::features::storage::preferred_temp_dir;
 use crate::state::AppState;
 use crate::ui::{Button as UiButton, Column as UiColumn, Text as UiText, TextInput as UiTextInput};
@@ -282,6 +284,9 @@ fn parse_qr_payload(payload: &str) -> Result<(u32, u32, Vec<u8>), String> {
 }
 
 pub fn handle_receive_scan(state: &mut AppState, payload: &str) -> Result<(), String> {
+    if is_preset_qr(payload) {
+        return receive_preset(state, payload);
+    }
     let (index, total, data) = parse_qr_payload(payload)?;
     match state.qr_receive.total_chunks {
         Some(existing_total) if existing_total != total => return Err("qr_total_mismatch".into()),
@@ -320,6 +325,28 @@ pub fn handle_receive_scan(state: &mut AppState, payload: &str) -> Result<(), St
     Ok(())
 }
 
+/// Presets fit in a single QR, so they are imported right away instead of being
+/// collected as transfer chunks.
+fn receive_preset(state: &mut AppState, payload: &str) -> Result<(), String> {
+    let summary = import_preset_qr(payload, state.preset_state.import_conflict)?;
+    if summary.unsupported > 0 {
+        return Err("preset_qr_unsupported".into());
+    }
+    state.qr_receive.last_scanned = Some(payload.to_string());
+    state.qr_receive.status = Some(
+        if summary.imported > 0 {
+            "Preset imported"
+        } else if summary.replaced > 0 {
+            "Preset replaced"
+        } else {
+            "Preset already saved"
+        }
+        .into(),
+    );
+    state.qr_receive.error = None;
+    Ok(())
+}
+
 pub fn finalize_receive(state: &mut AppState) -> Result<Vec<u8>, String> {
     let total = state
         .qr_receive
@@ -377,32 +404,37 @@ pub fn render_qr_slideshow_screen(state: &AppState) -> Value {
 
     if let Some(path) = &state.qr_slideshow.source_path {
         children.push(
-            serde_json::to_value(UiText::new(&format!("Source: {path}")).size(12.0)).unwrap(),
+            serde_json::to_value(UiText::new(&t!("qr_transfer_source", path = path)).size(12.0))
+                .unwrap(),
         );
     }
 
     if let Some(err) = &state.qr_slideshow.error {
-        children
-            .push(serde_json::to_value(UiText::new(&format!("Error: {err}")).size(12.0)).unwrap());
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
     }
 
     if !state.qr_slideshow.chunks.is_empty() {
         let total = state.qr_slideshow.chunks.len() as u32;
         let idx = state.qr_slideshow.current_index.saturating_add(1);
         children.push(
-            serde_json::to_value(UiText::new(&format!("Frame {idx}/{total}")).size(14.0)).unwrap(),
+            serde_json::to_value(
+                UiText::new(&t!("qr_transfer_frame", index = idx, total = total)).size(14.0),
+            )
+            .unwrap(),
         );
         children.push(
             serde_json::to_value(
-                UiText::new(&format!(
-                    "Interval: {} ms (playing: {})",
-                    state.qr_slideshow.interval_ms,
-                    if state.qr_slideshow.is_playing {
-                        "yes"
-                    } else {
-                        "no"
-                    }
-                ))
+                UiText::new(&if state.qr_slideshow.is_playing {
+                    t!(
+                        "qr_transfer_interval_playing",
+                        ms = state.qr_slideshow.interval_ms
+                    )
+                } else {
+                    t!(
+                        "qr_transfer_interval_paused",
+                        ms = state.qr_slideshow.interval_ms
+                    )
+                })
                 .size(12.0),
             )
             .unwrap(),
@@ -410,10 +442,10 @@ pub fn render_qr_slideshow_screen(state: &AppState) -> Value {
         children.push(
             serde_json::to_value(
                 UiButton::new(
-                    if state.qr_slideshow.is_playing {
-                        "Pause"
+                    &if state.qr_slideshow.is_playing {
+                        t!("qr_transfer_pause_button")
                     } else {
-                        "Play"
+                        t!("qr_transfer_play_button")
                     },
                     "qr_slideshow_play",
                 )
@@ -422,10 +454,16 @@ pub fn render_qr_slideshow_screen(state: &AppState) -> Value {
             .unwrap(),
         );
         children.push(
-            serde_json::to_value(UiButton::new("Prev", "qr_slideshow_prev").id("qr_prev")).unwrap(),
+            serde_json::to_value(
+                UiButton::new(&t!("qr_transfer_prev_button"), "qr_slideshow_prev").id("qr_prev"),
+            )
+            .unwrap(),
         );
         children.push(
-            serde_json::to_value(UiButton::new("Next", "qr_slideshow_next").id("qr_next")).unwrap(),
+            serde_json::to_value(
+                UiButton::new(&t!("qr_transfer_next_button"), "qr_slideshow_next").id("qr_next"),
+            )
+            .unwrap(),
         );
         for speed in [100u64, 200, 400, 800] {
             children.push(json!({
@@ -439,7 +477,8 @@ pub fn render_qr_slideshow_screen(state: &AppState) -> Value {
         if let Some(img) = &state.qr_slideshow.current_qr_base64 {
             children.push(
                 serde_json::to_value(
-                    crate::ui::ImageBase64::new(img).content_description("QR frame"),
+                    crate::ui::ImageBase64::new(img)
+                        .content_description(&t!("qr_transfer_frame_description")),
                 )
                 .unwrap(),
             );
@@ -447,7 +486,7 @@ pub fn render_qr_slideshow_screen(state: &AppState) -> Value {
     }
 
     if state.nav_depth() > 1 {
-        children.push(serde_json::to_value(UiButton::new("Back", "back")).unwrap());
+        children.push(serde_json::to_value(UiButton::new(&t!("button_back"), "back")).unwrap());
     }
 
     let mut root = json!(UiColumn::new(children).padding(20));
@@ -468,9 +507,9 @@ pub fn render_qr_slideshow_screen(state: &AppState) -> Value {
 
 pub fn render_qr_receive_screen(state: &AppState) -> Value {
     let mut children = vec![
-        serde_json::to_value(UiText::new("QR Transfer (Receiver)").size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("qr_transfer_receiver_title")).size(20.0)).unwrap(),
         serde_json::to_value(
-            UiText::new("Paste scanned frames or use the camera. Grant permission and keep the QRs in view; the preview runs behind this panel.")
+            UiText::new(&t!("qr_transfer_receiver_description"))
                 .size(14.0)
                 .content_description("qr_receive_subtitle"),
         )
@@ -481,18 +520,29 @@ pub fn render_qr_receive_screen(state: &AppState) -> Value {
                 .action_on_submit("qr_receive_scan"),
         )
         .unwrap(),
-        serde_json::to_value(UiButton::new("Submit chunk", "qr_receive_scan").id("qr_receive_scan_btn")).unwrap(),
-        serde_json::to_value(UiButton::new("Paste from clipboard", "qr_receive_paste").id("qr_receive_paste")).unwrap(),
+        serde_json::to_value(
+            UiButton::new(&t!("qr_transfer_submit_chunk_button"), "qr_receive_scan")
+                .id("qr_receive_scan_btn"),
+        )
+        .unwrap(),
+        serde_json::to_value(
+            UiButton::new(&t!("button_paste_from_clipboard"), "qr_receive_paste")
+                .id("qr_receive_paste"),
+        )
+        .unwrap(),
         // Re-rendering the screen is enough to (re)start the camera preview via MainActivity.
-        serde_json::to_value(UiButton::new("Resume camera", "qr_receive_screen").id("qr_receive_camera_resume")).unwrap(),
+        serde_json::to_value(
+            UiButton::new(&t!("qr_transfer_resume_camera_button"), "qr_receive_screen")
+                .id("qr_receive_camera_resume"),
+        )
+        .unwrap(),
     ];
 
     if let Some(status) = &state.qr_receive.status {
         children.push(serde_json::to_value(UiText::new(status).size(12.0)).unwrap());
     }
     if let Some(err) = &state.qr_receive.error {
-        children
-            .push(serde_json::to_value(UiText::new(&format!("Error: {err}")).size(12.0)).unwrap());
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
     }
     if let Some(total) = state.qr_receive.total_chunks {
         let received = state
@@ -503,7 +553,12 @@ pub fn render_qr_receive_screen(state: &AppState) -> Value {
             .count() as u32;
         children.push(
             serde_json::to_value(
-                UiText::new(&format!("Progress: {}/{}", received, total)).size(12.0),
+                UiText::new(&t!(
+                    "qr_transfer_progress",
+                    received = received,
+                    total = total
+                ))
+                .size(12.0),
             )
             .unwrap(),
         );
@@ -511,7 +566,7 @@ pub fn render_qr_receive_screen(state: &AppState) -> Value {
     if let Some(last) = &state.qr_receive.last_scanned {
         children.push(
             serde_json::to_value(
-                UiText::new(&format!("Last: {}", last))
+                UiText::new(&t!("qr_transfer_last_scanned", chunk = last))
                     .size(10.0)
                     .content_description("qr_last"),
             )
@@ -521,15 +576,18 @@ pub fn render_qr_receive_screen(state: &AppState) -> Value {
     if let Some(path) = &state.qr_receive.result_path {
         children.push(
             serde_json::to_value(
-                UiText::new(&format!("Saved: {}", path))
+                UiText::new(&t!("qr_transfer_saved", path = path))
                     .size(12.0)
                     .content_description("qr_receive_path"),
             )
             .unwrap(),
         );
         children.push(
-            serde_json::to_value(UiButton::new("Copy path", "copy_clipboard").copy_text(path))
-                .unwrap(),
+            serde_json::to_value(
+                UiButton::new(&t!("qr_transfer_copy_path_button"), "copy_clipboard")
+                    .copy_text(path),
+            )
+            .unwrap(),
         );
     } else if state
         .qr_receive
@@ -547,14 +605,15 @@ pub fn render_qr_receive_screen(state: &AppState) -> Value {
     {
         children.push(
             serde_json::to_value(
-                UiButton::new("Save file", "qr_receive_save").id("qr_receive_save"),
+                UiButton::new(&t!("qr_transfer_save_file_button"), "qr_receive_save")
+                    .id("qr_receive_save"),
             )
             .unwrap(),
         );
     }
 
     if state.nav_depth() > 1 {
-        children.push(serde_json::to_value(UiButton::new("Back", "back")).unwrap());
+        children.push(serde_json::to_value(UiButton::new(&t!("button_back"), "back")).unwrap());
     }
 
     serde_json::to_value(UiColumn::new(children).padding(20).id("QrReceiveScreen")).unwrap()
@@ -0,0 +1,341 @@
+use crate::ui::escape_markdown;
+use regex_syntax::ast::{
+    parse::Parser, Assertion, AssertionKind, Ast, ClassPerl, ClassPerlKind, ClassSet,
+    ClassSetBinaryOpKind, ClassSetItem, Flag, Flags, FlagsItemKind, GroupKind, RepetitionKind,
+    RepetitionRange, Span,
+};
+
+const INDENT: &str = "  ";
+
+/// Walks the parsed pattern AST and returns one indented line per component.
+pub fn explain_regex(pattern: &str) -> Result<Vec<String>, String> {
+    if pattern.trim().is_empty() {
+        return Err("regex_pattern_empty".into());
+    }
+    let ast = Parser::new()
+        .parse(pattern)
+        .map_err(|e| format!("regex_parse_error:{}", e.kind()))?;
+    let mut lines = Vec::new();
+    explain_node(pattern, &ast, 0, &mut lines);
+    Ok(lines)
+}
+
+/// Turns explanation lines into a `RichText` list, nesting one level per indent.
+pub fn explanation_markdown(lines: &[String]) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            let text = line.trim_start_matches(INDENT);
+            let depth = (line.len() - text.len()) / INDENT.len();
+            format!("{}- {}", INDENT.repeat(depth), escape_markdown(text))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn push(out: &mut Vec<String>, depth: usize, text: String) {
+    out.push(format!("{}{text}", INDENT.repeat(depth)));
+}
+
+fn source<'a>(pattern: &'a str, span: &Span) -> &'a str {
+    &pattern[span.start.offset..span.end.offset]
+}
+
+fn explain_node(pattern: &str, ast: &Ast, depth: usize, out: &mut Vec<String>) {
+    match ast {
+        Ast::Empty(_) => push(out, depth, "Nothing (matches the empty string)".into()),
+        Ast::Flags(set) => push(
+            out,
+            depth,
+            format!(
+                "Flags {}: {} from here on",
+                source(pattern, &set.span),
+                describe_flags(&set.flags)
+            ),
+        ),
+        Ast::Literal(lit) => push(out, depth, describe_char(lit.c)),
+        Ast::Dot(_) => push(
+            out,
+            depth,
+            "Any character except a newline (including it with the s flag)".into(),
+        ),
+        Ast::Assertion(assertion) => push(out, depth, describe_assertion(assertion)),
+        Ast::ClassUnicode(class) => push(
+            out,
+            depth,
+            format!(
+                "{} Unicode class {}",
+                if class.is_negated() {
+                    "A character outside the"
+                } else {
+                    "A character in the"
+                },
+                source(pattern, &class.span)
+            ),
+        ),
+        Ast::ClassPerl(class) => push(out, depth, describe_perl(class)),
+        Ast::ClassBracketed(class) => {
+            let items = describe_class_set(pattern, &class.kind);
+            let text = if class.negated {
+                format!("Any character except {items}")
+            } else {
+                format!("One character from {items}")
+            };
+            push(out, depth, text);
+        }
+        Ast::Repetition(rep) => {
+            let mut quantifier = match &rep.op.kind {
+                RepetitionKind::ZeroOrOne => "optional (0 or 1 time)".to_string(),
+                RepetitionKind::ZeroOrMore => "0 or more times".to_string(),
+                RepetitionKind::OneOrMore => "1 or more times".to_string(),
+                RepetitionKind::Range(RepetitionRange::Exactly(n)) => format!("exactly {n} times"),
+                RepetitionKind::Range(RepetitionRange::AtLeast(n)) => format!("at least {n} times"),
+                RepetitionKind::Range(RepetitionRange::Bounded(m, n)) => {
+                    format!("between {m} and {n} times")
+                }
+            };
+            if !rep.greedy {
+                quantifier.push_str(", lazy (as few as possible)");
+            }
+            let mut inner = Vec::new();
+            explain_node(pattern, &rep.ast, depth + 1, &mut inner);
+            if inner.len() == 1 {
+                push(
+                    out,
+                    depth,
+                    format!("{}, {quantifier}", inner[0].trim_start()),
+                );
+            } else {
+                push(out, depth, format!("Repeat {quantifier}:"));
+                out.extend(inner);
+            }
+        }
+        Ast::Group(group) => {
+            let header = match &group.kind {
+                GroupKind::CaptureIndex(idx) => format!("Capture group #{idx}:"),
+                GroupKind::CaptureName { name, .. } => {
+                    format!("Capture group #{} named \"{}\":", name.index, name.name)
+                }
+                GroupKind::NonCapturing(flags) if flags.items.is_empty() => {
+                    "Non-capturing group:".to_string()
+                }
+                GroupKind::NonCapturing(flags) => {
+                    format!("Non-capturing group with {}:", describe_flags(flags))
+                }
+            };
+            push(out, depth, header);
+            explain_node(pattern, &group.ast, depth + 1, out);
+        }
+        Ast::Alternation(alt) => {
+            push(
+                out,
+                depth,
+                format!("Either of {} alternatives:", alt.asts.len()),
+            );
+            for (idx, branch) in alt.asts.iter().enumerate() {
+                push(out, depth + 1, format!("Alternative {}:", idx + 1));
+                explain_node(pattern, branch, depth + 2, out);
+            }
+        }
+        Ast::Concat(concat) => {
+            // Runs of plain characters read better as a single quoted string.
+            let mut run = String::new();
+            for node in &concat.asts {
+                if let Ast::Literal(lit) = node {
+                    run.push(lit.c);
+                    continue;
+                }
+                flush_literal_run(&mut run, depth, out);
+                explain_node(pattern, node, depth, out);
+            }
+            flush_literal_run(&mut run, depth, out);
+        }
+    }
+}
+
+fn flush_literal_run(run: &mut String, depth: usize, out: &mut Vec<String>) {
+    match run.chars().count() {
+        0 => {}
+        1 => push(
+            out,
+            depth,
+            describe_char(run.chars().next().unwrap_or_default()),
+        ),
+        _ => push(out, depth, format!("The text {run:?}")),
+    }
+    run.clear();
+}
+
+fn describe_char(c: char) -> String {
+    match c {
+        ' ' => "A space".into(),
+        '\t' => "A tab".into(),
+        '\n' => "A newline".into(),
+        '\r' => "A carriage return".into(),
+        c => format!("The character {c:?}"),
+    }
+}
+
+fn describe_assertion(assertion: &Assertion) -> String {
+    match assertion.kind {
+        AssertionKind::StartLine => "Start of a line (start of text unless the m flag is on)",
+        AssertionKind::EndLine => "End of a line (end of text unless the m flag is on)",
+        AssertionKind::StartText => "Start of the text",
+        AssertionKind::EndText => "End of the text",
+        AssertionKind::WordBoundary => "A word boundary",
+        AssertionKind::NotWordBoundary => "A position that is not a word boundary",
+        AssertionKind::WordBoundaryStart | AssertionKind::WordBoundaryStartAngle => {
+            "The start of a word"
+        }
+        AssertionKind::WordBoundaryEnd | AssertionKind::WordBoundaryEndAngle => "The end of a word",
+        AssertionKind::WordBoundaryStartHalf => "A position not preceded by a word character",
+        AssertionKind::WordBoundaryEndHalf => "A position not followed by a word character",
+    }
+    .to_string()
+}
+
+fn perl_name(class: &ClassPerl) -> &'static str {
+    match (&class.kind, class.negated) {
+        (ClassPerlKind::Digit, false) => "a digit",
+        (ClassPerlKind::Digit, true) => "a non-digit",
+        (ClassPerlKind::Space, false) => "a whitespace character",
+        (ClassPerlKind::Space, true) => "a non-whitespace character",
+        (ClassPerlKind::Word, false) => "a word character (letter, digit, or underscore)",
+        (ClassPerlKind::Word, true) => "a non-word character",
+    }
+}
+
+fn describe_perl(class: &ClassPerl) -> String {
+    let name = perl_name(class);
+    let mut text = name.to_string();
+    text[..1].make_ascii_uppercase();
+    text
+}
+
+fn describe_class_set(pattern: &str, set: &ClassSet) -> String {
+    match set {
+        ClassSet::Item(item) => describe_class_item(pattern, item),
+        ClassSet::BinaryOp(op) => {
+            let joiner = match op.kind {
+                ClassSetBinaryOpKind::Intersection => "and also in",
+                ClassSetBinaryOpKind::Difference => "but not in",
+                ClassSetBinaryOpKind::SymmetricDifference => "or in, but not both,",
+            };
+            format!(
+                "({}) {joiner} ({})",
+                describe_class_set(pattern, &op.lhs),
+                describe_class_set(pattern, &op.rhs)
+            )
+        }
+    }
+}
+
+fn describe_class_item(pattern: &str, item: &ClassSetItem) -> String {
+    match item {
+        ClassSetItem::Empty(_) => "nothing".into(),
+        ClassSetItem::Literal(lit) => format!("{:?}", lit.c),
+        ClassSetItem::Range(range) => format!("{:?} to {:?}", range.start.c, range.end.c),
+        ClassSetItem::Ascii(class) => format!(
+            "{}ASCII class {}",
+            if class.negated { "not in " } else { "" },
+            source(pattern, &class.span)
+        ),
+        ClassSetItem::Unicode(class) => format!("Unicode class {}", source(pattern, &class.span)),
+        ClassSetItem::Perl(class) => perl_name(class).to_string(),
+        ClassSetItem::Bracketed(class) => format!(
+            "[{}{}]",
+            if class.negated { "not " } else { "" },
+            describe_class_set(pattern, &class.kind)
+        ),
+        ClassSetItem::Union(union) => union
+            .items
+            .iter()
+            .map(|item| describe_class_item(pattern, item))
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+fn describe_flags(flags: &Flags) -> String {
+    let mut enabled = true;
+    let mut parts = Vec::new();
+    for item in &flags.items {
+        match &item.kind {
+            FlagsItemKind::Negation => enabled = false,
+            FlagsItemKind::Flag(flag) => {
+                let name = match flag {
+                    Flag::CaseInsensitive => "case-insensitive",
+                    Flag::MultiLine => "multi-line",
+                    Flag::DotMatchesNewLine => "dot matches newline",
+                    Flag::SwapGreed => "swap greed",
+                    Flag::Unicode => "Unicode",
+                    Flag::CRLF => "CRLF line endings",
+                    Flag::IgnoreWhitespace => "ignore whitespace",
+                };
+                parts.push(format!("{name} {}", if enabled { "on" } else { "off" }));
+            }
+        }
+    }
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explains_anchors_literals_and_quantifiers() {
+        let lines = explain_regex(r"^ab\d+?$").unwrap();
+        assert_eq!(
+            lines,
+            vec![
+                "Start of a line (start of text unless the m flag is on)",
+                "The text \"ab\"",
+                "A digit, 1 or more times, lazy (as few as possible)",
+                "End of a line (end of text unless the m flag is on)",
+            ]
+        );
+    }
+
+    #[test]
+    fn explains_groups_classes_and_alternation() {
+        let lines = explain_regex(r"(?P<year>[0-9]{4})|(?i:x|[^a-c_])").unwrap();
+        assert_eq!(
+            lines,
+            vec![
+                "Either of 2 alternatives:",
+                "  Alternative 1:",
+                "    Capture group #1 named \"year\":",
+                "      One character from '0' to '9', exactly 4 times",
+                "  Alternative 2:",
+                "    Non-capturing group with case-insensitive on:",
+                "      Either of 2 alternatives:",
+                "        Alternative 1:",
+                "          The character 'x'",
+                "        Alternative 2:",
+                "          Any character except 'a' to 'c', '_'",
+            ]
+        );
+    }
+
+    #[test]
+    fn markdown_nests_lines_and_escapes_pattern_text() {
+        let lines = explain_regex(r"(a*)").unwrap();
+        assert_eq!(
+            explanation_markdown(&lines),
+            "- Capture group \\#1:\n  - The character 'a', 0 or more times"
+        );
+        assert_eq!(
+            explanation_markdown(&["  One character from '*'".to_string()]),
+            "  - One character from '\\*'"
+        );
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert_eq!(explain_regex("  ").unwrap_err(), "regex_pattern_empty");
+        assert!(explain_regex("(abc")
+            .unwrap_err()
+            .starts_with("regex_parse_error:"));
+    }
+}
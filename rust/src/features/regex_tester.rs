@@ -1,10 +1,125 @@
-use crate::state::{AppState, RegexMatchResult};
+use crate::error::error_text;
+use crate::features::regex_explain::{explain_regex, explanation_markdown};
+use crate::state::{AppState, RegexFileLine, RegexFileResult, RegexFlags, RegexMatchResult};
 use crate::ui::{
-    maybe_push_back, Button as UiButton, Checkbox as UiCheckbox, Column as UiColumn, Grid as UiGrid,
-    Text as UiText, TextInput as UiTextInput,
+    maybe_push_back, Button as UiButton, Checkbox as UiCheckbox, Column as UiColumn,
+    Grid as UiGrid, RichText as UiRichText, Text as UiText, TextInput as UiTextInput,
+    TextSpan as UiTextSpan, VirtualList as UiVirtualList,
 };
-use regex::Regex;
+use regex::{Captures, Regex, RegexBuilder};
 use serde_json::{json, Value};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::os::unix::io::FromRawFd;
+
+/// Matching lines kept for display; the total count still covers the whole file.
+pub const REGEX_FILE_MAX_LINES: usize = 50;
+const REGEX_FILE_LINE_PREVIEW_CHARS: usize = 200;
+/// Compiled-program and lazy-DFA budgets; the `regex` engine is linear-time, so
+/// these bound the remaining cost (huge counted repetitions like `(a{1000}){1000}`).
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+const REGEX_DFA_SIZE_LIMIT: usize = 2 << 20;
+
+/// Curated pattern inserted into the pattern field from the snippet library.
+pub struct RegexSnippet {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub keywords: &'static str,
+    pub pattern: &'static str,
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub example: &'static str,
+}
+
+/// Snippets stick to ASCII classes (`[0-9]` rather than `\d`) so they behave the same
+/// whether or not the Unicode flag is enabled.
+pub const REGEX_SNIPPETS: &[RegexSnippet] = &[
+    RegexSnippet {
+        id: "email",
+        label: "Email",
+        keywords: "mail address contact",
+        pattern: r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}",
+        example: "jane.doe+tag@example.org",
+    },
+    RegexSnippet {
+        id: "ipv4",
+        label: "IPv4 address",
+        keywords: "ip network host",
+        pattern: r"(?:(?:25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])\.){3}(?:25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])",
+        example: "192.168.0.254",
+    },
+    RegexSnippet {
+        id: "ipv6",
+        label: "IPv6 address (full form)",
+        keywords: "ip network host",
+        pattern: r"(?:[0-9A-Fa-f]{1,4}:){7}[0-9A-Fa-f]{1,4}",
+        example: "2001:0db8:85a3:0000:0000:8a2e:0370:7334",
+    },
+    RegexSnippet {
+        id: "uuid",
+        label: "UUID",
+        keywords: "guid identifier",
+        pattern: r"[0-9a-fA-F]{8}-(?:[0-9a-fA-F]{4}-){3}[0-9a-fA-F]{12}",
+        example: "123e4567-e89b-12d3-a456-426614174000",
+    },
+    RegexSnippet {
+        id: "iso_date",
+        label: "ISO 8601 date",
+        keywords: "date day yyyy-mm-dd",
+        pattern: r"[0-9]{4}-(?:0[1-9]|1[0-2])-(?:0[1-9]|[12][0-9]|3[01])",
+        example: "2024-02-29",
+    },
+    RegexSnippet {
+        id: "iso_datetime",
+        label: "ISO 8601 date-time",
+        keywords: "date time timestamp rfc3339",
+        pattern: r"[0-9]{4}-(?:0[1-9]|1[0-2])-(?:0[1-9]|[12][0-9]|3[01])T(?:[01][0-9]|2[0-3]):[0-5][0-9]:[0-5][0-9](?:\.[0-9]+)?(?:Z|[+-](?:[01][0-9]|2[0-3]):[0-5][0-9])?",
+        example: "2024-02-29T13:45:00Z",
+    },
+    RegexSnippet {
+        id: "time_hms",
+        label: "Time (HH:MM:SS)",
+        keywords: "clock hour",
+        pattern: r"(?:[01][0-9]|2[0-3]):[0-5][0-9]:[0-5][0-9]",
+        example: "23:59:07",
+    },
+    RegexSnippet {
+        id: "semver",
+        label: "Semantic version",
+        keywords: "semver release version",
+        pattern: r"(?P<major>0|[1-9][0-9]*)\.(?P<minor>0|[1-9][0-9]*)\.(?P<patch>0|[1-9][0-9]*)(?:-(?P<pre>[0-9A-Za-z-]+(?:\.[0-9A-Za-z-]+)*))?(?:\+(?P<build>[0-9A-Za-z-]+(?:\.[0-9A-Za-z-]+)*))?",
+        example: "1.4.0-rc.1+build.7",
+    },
+    RegexSnippet {
+        id: "url",
+        label: "URL",
+        keywords: "link http https ftp web",
+        pattern: r"(?:https?|ftp)://[^ \t\r\n/$.?#][^ \t\r\n]*",
+        example: "https://example.com/a?b=1",
+    },
+    RegexSnippet {
+        id: "hex_color",
+        label: "Hex color",
+        keywords: "colour css rgb",
+        pattern: r"#(?:[0-9a-fA-F]{3}){1,2}",
+        example: "#1e90ff",
+    },
+];
+
+/// Case-insensitive search over snippet ids, labels, and keywords.
+pub fn find_snippets(query: &str) -> Vec<&'static RegexSnippet> {
+    let query = query.trim().to_ascii_lowercase();
+    REGEX_SNIPPETS
+        .iter()
+        .filter(|snippet| {
+            query.is_empty()
+                || snippet.id.contains(&query)
+                || snippet.label.to_ascii_lowercase().contains(&query)
+                || snippet.keywords.contains(&query)
+        })
+        .collect()
+}
+
+const HIGHLIGHT_COLORS: [&str; 2] = ["#FFF59D", "#A5D6A7"];
 
 pub fn render_regex_tester_screen(state: &AppState) -> Value {
     let regex_tester_title = t!("screen_regex_tester_title");
@@ -19,7 +134,7 @@ pub fn render_regex_tester_screen(state: &AppState) -> Value {
         .unwrap(),
         serde_json::to_value(
             UiTextInput::new("regex_pattern")
-                .hint("Pattern (Rust syntax)")
+                .hint(&t!("regex_tester_pattern_hint"))
                 .text(&state.regex_tester.pattern)
                 .single_line(true),
         )
@@ -27,49 +142,151 @@ pub fn render_regex_tester_screen(state: &AppState) -> Value {
         serde_json::to_value(
             UiGrid::new(vec![
                 json!(UiTextInput::new("regex_sample")
-                    .hint("Sample text")
+                    .hint(&t!("regex_tester_sample_hint"))
                     .text(&state.regex_tester.sample_text)),
-                json!(UiButton::new("Clear", "regex_clear").id("regex_clear_inline")),
+                json!(
+                    UiButton::new(&t!("regex_tester_clear_button"), "regex_clear")
+                        .id("regex_clear_inline")
+                ),
             ])
             .columns(2)
             .padding(4),
         )
         .unwrap(),
         serde_json::to_value(
-            UiCheckbox::new("Global Mode", "global_mode")
+            UiCheckbox::new(&t!("regex_tester_global_mode"), "global_mode")
                 .checked(state.regex_tester.global_mode)
                 .action("regex_test"),
         )
         .unwrap(),
-        serde_json::to_value(UiButton::new("Test", "regex_test")).unwrap(),
+        serde_json::to_value(
+            UiCheckbox::new(&t!("regex_tester_split_mode"), "regex_split_mode")
+                .checked(state.regex_tester.split_mode)
+                .action("regex_test"),
+        )
+        .unwrap(),
     ];
 
-    // Add common patterns buttons
-    if !state.regex_tester.common_patterns.is_empty() {
+    let flags = state.regex_tester.flags;
+    let flag_toggles = [
+        (
+            "regex_tester_flag_case_insensitive",
+            "regex_flag_case_insensitive",
+            flags.case_insensitive,
+        ),
+        (
+            "regex_tester_flag_multi_line",
+            "regex_flag_multi_line",
+            flags.multi_line,
+        ),
+        (
+            "regex_tester_flag_dot_all",
+            "regex_flag_dot_all",
+            flags.dot_all,
+        ),
+        (
+            "regex_tester_flag_unicode",
+            "regex_flag_unicode",
+            flags.unicode,
+        ),
+    ];
+    for (label_key, bind_key, checked) in flag_toggles {
         children.push(
-            serde_json::to_value(UiText::new("Common Patterns:").size(14.0)).unwrap(),
+            serde_json::to_value(
+                UiCheckbox::new(&t!(label_key), bind_key)
+                    .checked(checked)
+                    .action("regex_test"),
+            )
+            .unwrap(),
         );
-        
-        let pattern_labels = ["Email", "IPv4", "IPv6", "Date", "Time", "URL"];
-        for (idx, _) in state.regex_tester.common_patterns.iter().enumerate() {
-            children.push(
-                serde_json::to_value(
-                    UiButton::new(pattern_labels[idx], &format!("regex_pattern_{}", idx)),
-                )
-                .unwrap(),
-            );
-        }
     }
+    children.push(
+        serde_json::to_value(UiButton::new(&t!("regex_tester_test_button"), "regex_test")).unwrap(),
+    );
+    children.push(
+        serde_json::to_value(UiButton::new(
+            &t!("regex_tester_explain_button"),
+            "regex_explain",
+        ))
+        .unwrap(),
+    );
+    children.push(
+        serde_json::to_value(
+            UiButton::new(&t!("regex_tester_search_file_button"), "regex_file")
+                .requires_file_picker(true),
+        )
+        .unwrap(),
+    );
+
+    children.push(
+        serde_json::to_value(UiText::new(&t!("regex_tester_snippets_section")).size(14.0)).unwrap(),
+    );
+    children.push(
+        serde_json::to_value(
+            UiTextInput::new("regex_snippet_query")
+                .hint(&t!("regex_tester_snippet_search_hint"))
+                .text(&state.regex_tester.snippet_query)
+                .single_line(true)
+                .debounce_ms(200)
+                .action_on_submit("regex_test"),
+        )
+        .unwrap(),
+    );
+    let snippets = find_snippets(&state.regex_tester.snippet_query);
+    if snippets.is_empty() {
+        children.push(
+            serde_json::to_value(UiText::new(&t!("regex_tester_no_snippets")).size(12.0)).unwrap(),
+        );
+    }
+    for snippet in snippets {
+        children.push(
+            serde_json::to_value(
+                UiButton::new(snippet.label, "regex_test")
+                    .payload(json!({ "regex_snippet": snippet.id })),
+            )
+            .unwrap(),
+        );
+    }
+    children.push(
+        serde_json::to_value(
+            UiButton::new(&t!("regex_tester_saved_patterns_button"), "presets_list")
+                .id("regex_presets")
+                .payload(json!({ "tool_id": "regex_tester" })),
+        )
+        .unwrap(),
+    );
+    children.push(
+        serde_json::to_value(
+            UiButton::new(
+                &t!("regex_tester_save_pattern_button"),
+                "preset_save_dialog",
+            )
+            .id("regex_preset_save")
+            .payload(json!({ "tool_id": "regex_tester" })),
+        )
+        .unwrap(),
+    );
+
+    children.push(
+        serde_json::to_value(
+            UiText::new(&t!(
+                "regex_tester_active_flags",
+                flags = flags_summary(flags)
+            ))
+            .size(12.0)
+            .content_description("regex_flags"),
+        )
+        .unwrap(),
+    );
 
     if let Some(err) = &state.regex_tester.error {
-        children
-            .push(serde_json::to_value(UiText::new(&format!("Error: {err}")).size(12.0)).unwrap());
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
     } else if !state.regex_tester.match_results.is_empty() {
         let match_count = state.regex_tester.match_results.len();
         let status = if match_count > 0 {
-            format!("Found {} match(es)", match_count)
+            t!("regex_tester_found_matches", count = match_count)
         } else {
-            "No matches".to_string()
+            t!("regex_tester_no_matches")
         };
         children.push(
             serde_json::to_value(
@@ -80,127 +297,466 @@ pub fn render_regex_tester_screen(state: &AppState) -> Value {
             .unwrap(),
         );
         
+        let sample = &state.regex_tester.sample_text;
+        let ranges: Vec<(usize, usize)> = state
+            .regex_tester
+            .match_results
+            .iter()
+            .filter(|r| r.matched)
+            .map(|r| (r.start_index, r.end_index))
+            .collect();
+        let spans: Vec<UiTextSpan> = UiTextSpan::from_byte_ranges(sample, &ranges)
+            .into_iter()
+            .enumerate()
+            .map(|(idx, span)| span.background(HIGHLIGHT_COLORS[idx % HIGHLIGHT_COLORS.len()]))
+            .collect();
+        if !spans.is_empty() {
+            children.push(
+                serde_json::to_value(
+                    UiText::new(sample)
+                        .size(14.0)
+                        .spans(spans)
+                        .content_description("regex_highlighted"),
+                )
+                .unwrap(),
+            );
+        }
+
         for (match_idx, result) in state.regex_tester.match_results.iter().enumerate() {
             if result.matched {
                 children.push(
                     serde_json::to_value(
-                        UiText::new(&format!("Match {}: {}", match_idx + 1, result.match_text))
-                            .size(12.0),
+                        UiText::new(&t!(
+                            "regex_tester_match",
+                            index = match_idx + 1,
+                            start = result.start_index,
+                            end = result.end_index,
+                            text = result.match_text
+                        ))
+                        .size(12.0),
                     )
                     .unwrap(),
                 );
-                
-                for (idx, grp) in result.groups.iter().enumerate() {
-                    let text = grp
-                        .as_deref()
-                        .map(|g| format!("  Group {idx}: {g}"))
-                        .unwrap_or_else(|| format!("  Group {idx}: <none>"));
+
+                // Group 0 is the whole match, already shown above.
+                for idx in 1..result.groups.len() {
+                    let label = match result.group_names.get(idx).cloned().flatten() {
+                        Some(name) => format!("#{idx} <{name}>"),
+                        None => format!("#{idx}"),
+                    };
+                    let text = match (&result.groups[idx], result.group_spans.get(idx).copied().flatten()) {
+                        (Some(g), Some((start, end))) => format!("  {label}: {g} [{start}..{end}]"),
+                        _ => format!("  {label}: {}", t!("regex_tester_group_none")),
+                    };
                     children.push(serde_json::to_value(UiText::new(&text).size(12.0)).unwrap());
                 }
             }
         }
     }
 
+    if state.regex_tester.error.is_none()
+        && state.regex_tester.split_mode
+        && !state.regex_tester.split_pieces.is_empty()
+    {
+        let pieces = &state.regex_tester.split_pieces;
+        children.push(
+            serde_json::to_value(
+                UiText::new(&t!("regex_tester_split_count", count = pieces.len()))
+                    .size(14.0)
+                    .content_description("regex_split_status"),
+            )
+            .unwrap(),
+        );
+        // Debug formatting keeps empty and whitespace-only pieces visible.
+        for (idx, piece) in pieces.iter().enumerate() {
+            children.push(
+                serde_json::to_value(UiText::new(&format!("[{idx}] {piece:?}")).size(12.0))
+                    .unwrap(),
+            );
+        }
+    }
+
+    if !state.regex_tester.explanation.is_empty() {
+        children.push(
+            serde_json::to_value(
+                UiText::new(&t!("regex_tester_explanation_title"))
+                    .size(14.0)
+                    .content_description("regex_explanation"),
+            )
+            .unwrap(),
+        );
+        let markdown = explanation_markdown(&state.regex_tester.explanation);
+        children.push(serde_json::to_value(UiRichText::new(&markdown).size(12.0)).unwrap());
+    }
+
+    if let Some(file) = &state.regex_tester.file_result {
+        let source = file
+            .display_path
+            .clone()
+            .unwrap_or_else(|| t!("regex_tester_picked_file").into_owned());
+        children.push(
+            serde_json::to_value(
+                UiText::new(&t!(
+                    "regex_tester_file_matches",
+                    source = source,
+                    matches = file.total_matches,
+                    lines = file.matching_lines
+                ))
+                .size(14.0)
+                .content_description("regex_file_status"),
+            )
+            .unwrap(),
+        );
+        if file.matching_lines > file.lines.len() {
+            children.push(
+                serde_json::to_value(
+                    UiText::new(&t!("regex_tester_file_truncated", count = file.lines.len()))
+                        .size(12.0),
+                )
+                .unwrap(),
+            );
+        }
+        let items: Vec<Value> = file
+            .lines
+            .iter()
+            .map(|line| {
+                serde_json::to_value(
+                    UiText::new(&format!("{:>6}: {}", line.line_number, line.text)).size(12.0),
+                )
+                .unwrap()
+            })
+            .collect();
+        if !items.is_empty() {
+            children.push(
+                serde_json::to_value(UiVirtualList::new(items).id("regex_file_lines")).unwrap(),
+            );
+        }
+    }
+
     maybe_push_back(&mut children, state);
     serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
 }
 
+/// Streams a file line by line, counting every match and keeping the first matching lines.
+pub fn scan_file_for_regex(
+    path: Option<&str>,
+    fd: Option<i32>,
+    pattern: &str,
+    flags: RegexFlags,
+) -> Result<RegexFileResult, String> {
+    if pattern.trim().is_empty() {
+        return Err("regex_pattern_empty".into());
+    }
+    let file = match (fd, path) {
+        (Some(fd), _) if fd >= 0 => unsafe { File::from_raw_fd(fd) },
+        (_, Some(p)) => File::open(p).map_err(|e| format!("open_failed:{e}"))?,
+        _ => return Err("missing_path".into()),
+    };
+    let mut result = scan_reader_for_regex(file, pattern, flags, REGEX_FILE_MAX_LINES)?;
+    result.display_path = path.map(str::to_string);
+    Ok(result)
+}
+
+pub fn scan_reader_for_regex<R: Read>(
+    reader: R,
+    pattern: &str,
+    flags: RegexFlags,
+    max_lines: usize,
+) -> Result<RegexFileResult, String> {
+    let re = build_regex(pattern, flags)?;
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+    let mut result = RegexFileResult {
+        display_path: None,
+        total_matches: 0,
+        matching_lines: 0,
+        lines: Vec::new(),
+    };
+    let mut line_number = 0;
+    loop {
+        buf.clear();
+        let read = reader
+            .read_until(b'\n', &mut buf)
+            .map_err(|e| format!("read_failed:{e}"))?;
+        if read == 0 {
+            break;
+        }
+        line_number += 1;
+        let line = String::from_utf8_lossy(&buf);
+        let line = line.trim_end_matches(['\n', '\r']);
+        let count = re.find_iter(line).count();
+        if count == 0 {
+            continue;
+        }
+        result.total_matches += count;
+        result.matching_lines += 1;
+        if result.lines.len() < max_lines {
+            result.lines.push(RegexFileLine {
+                line_number,
+                text: line.chars().take(REGEX_FILE_LINE_PREVIEW_CHARS).collect(),
+            });
+        }
+    }
+    Ok(result)
+}
+
 pub fn handle_regex_action(
     state: &mut AppState,
     bindings: &std::collections::HashMap<String, String>,
 ) {
-    // Initialize common patterns if not already done
-    if state.regex_tester.common_patterns.is_empty() {
-        state.regex_tester.init_common_patterns();
+    apply_regex_bindings(state, bindings);
+
+    // Test the regex
+    if let Some(result) = test_regex(
+        &state.regex_tester.pattern,
+        &state.regex_tester.sample_text,
+        state.regex_tester.global_mode,
+        state.regex_tester.flags,
+    ) {
+        match result {
+            Ok(res) => {
+                state.regex_tester.match_results = res;
+                state.regex_tester.error = None;
+            }
+            Err(e) => {
+                state.regex_tester.match_results.clear();
+                state.regex_tester.error = Some(e);
+            }
+        }
+    }
+
+    state.regex_tester.split_pieces.clear();
+    if state.regex_tester.split_mode && state.regex_tester.error.is_none() {
+        if let Some(Ok(pieces)) = split_regex(
+            &state.regex_tester.pattern,
+            &state.regex_tester.sample_text,
+            state.regex_tester.flags,
+        ) {
+            state.regex_tester.split_pieces = pieces;
+        }
+    }
+}
+
+/// Replaces the explanation with a breakdown of the current pattern.
+pub fn handle_regex_explain(
+    state: &mut AppState,
+    bindings: &std::collections::HashMap<String, String>,
+) {
+    apply_regex_bindings(state, bindings);
+    match explain_regex(&state.regex_tester.pattern) {
+        Ok(lines) => {
+            state.regex_tester.explanation = lines;
+            state.regex_tester.error = None;
+        }
+        Err(e) => {
+            state.regex_tester.explanation.clear();
+            state.regex_tester.error = Some(e);
+        }
     }
-    
+}
+
+/// Splits `text` on every match of `pattern`, mirroring `Regex::split`.
+pub fn split_regex(
+    pattern: &str,
+    text: &str,
+    flags: RegexFlags,
+) -> Option<Result<Vec<String>, String>> {
+    if pattern.trim().is_empty() {
+        return None;
+    }
+    Some(build_regex(pattern, flags).map(|re| re.split(text).map(str::to_string).collect()))
+}
+
+/// Copies pattern, sample text, and toggles from the UI bindings into state.
+pub fn apply_regex_bindings(
+    state: &mut AppState,
+    bindings: &std::collections::HashMap<String, String>,
+) {
     // Handle global mode toggle
     if let Some(global_mode_str) = bindings.get("global_mode") {
         state.regex_tester.global_mode = global_mode_str == "true";
     }
+    if let Some(split_mode_str) = bindings.get("regex_split_mode") {
+        state.regex_tester.split_mode = split_mode_str == "true";
+    }
     
-    // Handle common pattern buttons
-    for (idx, pattern) in state.regex_tester.common_patterns.iter().enumerate() {
-        if bindings.contains_key(&format!("regex_pattern_{}", idx)) {
-            state.regex_tester.pattern = pattern.clone();
-            break;
+    let flags = &mut state.regex_tester.flags;
+    for (bind_key, flag) in [
+        ("regex_flag_case_insensitive", &mut flags.case_insensitive),
+        ("regex_flag_multi_line", &mut flags.multi_line),
+        ("regex_flag_dot_all", &mut flags.dot_all),
+        ("regex_flag_unicode", &mut flags.unicode),
+    ] {
+        if let Some(value) = bindings.get(bind_key) {
+            *flag = value == "true";
         }
     }
-    
-    // Handle pattern and sample text inputs
-    state.regex_tester.pattern = bindings
-        .get("regex_pattern")
-        .cloned()
-        .unwrap_or_else(|| state.regex_tester.pattern.clone());
+
+    if let Some(query) = bindings.get("regex_snippet_query") {
+        state.regex_tester.snippet_query = query.clone();
+    }
+
+    // A snippet button wins over the pattern field value sent alongside it.
+    let snippet = bindings
+        .get("regex_snippet")
+        .and_then(|id| REGEX_SNIPPETS.iter().find(|s| s.id == id));
+    let previous_pattern = std::mem::take(&mut state.regex_tester.pattern);
+    state.regex_tester.pattern = match snippet {
+        Some(snippet) => snippet.pattern.to_string(),
+        None => bindings
+            .get("regex_pattern")
+            .cloned()
+            .unwrap_or_else(|| previous_pattern.clone()),
+    };
+    if state.regex_tester.pattern != previous_pattern {
+        state.regex_tester.explanation.clear();
+    }
     state.regex_tester.sample_text = bindings
         .get("regex_sample")
         .cloned()
         .unwrap_or_else(|| state.regex_tester.sample_text.clone());
+}
 
-    // Test the regex
-    if let Some(result) = test_regex(&state.regex_tester.pattern, &state.regex_tester.sample_text, state.regex_tester.global_mode) {
-        match result {
-            Ok(res) => {
-                state.regex_tester.match_results = res;
-                state.regex_tester.error = None;
-            }
-            Err(e) => {
-                state.regex_tester.match_results.clear();
-                state.regex_tester.error = Some(e);
-            }
-        }
+/// Inline-flag style summary, e.g. `ims` or `none`.
+pub fn flags_summary(flags: RegexFlags) -> String {
+    let summary: String = [
+        (flags.case_insensitive, 'i'),
+        (flags.multi_line, 'm'),
+        (flags.dot_all, 's'),
+        (flags.unicode, 'u'),
+    ]
+    .iter()
+    .filter_map(|(on, c)| on.then_some(*c))
+    .collect();
+    if summary.is_empty() {
+        "none".to_string()
+    } else {
+        summary
     }
 }
 
-pub fn test_regex(pattern: &str, text: &str, global_mode: bool) -> Option<Result<Vec<RegexMatchResult>, String>> {
+pub fn test_regex(
+    pattern: &str,
+    text: &str,
+    global_mode: bool,
+    flags: RegexFlags,
+) -> Option<Result<Vec<RegexMatchResult>, String>> {
     if pattern.trim().is_empty() {
         return None;
     }
-    match Regex::new(pattern) {
+    match build_regex(pattern, flags) {
         Ok(re) => {
             if global_mode {
-                // Find all matches
-                let mut results = Vec::new();
-                for capture in re.captures_iter(text) {
-                    let matched = true;
-                    let match_text = capture.get(0).map(|m| m.as_str().to_string()).unwrap_or_default();
-                    let start_index = capture.get(0).map(|m| m.start()).unwrap_or(0);
-                    let end_index = capture.get(0).map(|m| m.end()).unwrap_or(0);
-                    let groups = (0..capture.len())
-                        .map(|i| capture.get(i).map(|m| m.as_str().to_string()))
-                        .collect();
-                    results.push(RegexMatchResult { matched, groups, match_text, start_index, end_index });
-                }
-                Some(Ok(results))
+                Some(Ok(re
+                    .captures_iter(text)
+                    .map(|caps| capture_to_result(&re, &caps))
+                    .collect()))
             } else {
-                // Find first match only (original behavior)
-                let capture = re.captures(text);
-                let matched = capture.is_some();
-                let match_text = capture.as_ref().and_then(|caps| caps.get(0).map(|m| m.as_str().to_string())).unwrap_or_default();
-                let start_index = capture.as_ref().and_then(|caps| caps.get(0).map(|m| m.start())).unwrap_or(0);
-                let end_index = capture.as_ref().and_then(|caps| caps.get(0).map(|m| m.end())).unwrap_or(0);
-                let groups = capture
-                    .as_ref()
-                    .map(|caps| {
-                        (0..caps.len())
-                            .map(|i| caps.get(i).map(|m| m.as_str().to_string()))
-                            .collect()
-                    })
-                    .unwrap_or_default();
-                Some(Ok(vec![RegexMatchResult { matched, groups, match_text, start_index, end_index }]))
+                let result = match re.captures(text) {
+                    Some(caps) => capture_to_result(&re, &caps),
+                    None => RegexMatchResult {
+                        matched: false,
+                        groups: Vec::new(),
+                        group_names: Vec::new(),
+                        group_spans: Vec::new(),
+                        match_text: String::new(),
+                        start_index: 0,
+                        end_index: 0,
+                    },
+                };
+                Some(Ok(vec![result]))
             }
         }
         Err(e) => Some(Err(e.to_string())),
     }
 }
 
+fn build_regex(pattern: &str, flags: RegexFlags) -> Result<Regex, String> {
+    if let Some(feature) = unsupported_feature(pattern) {
+        return Err(format!(
+            "regex_unsupported_feature:{feature} is not supported; patterns run on a linear-time engine without backtracking"
+        ));
+    }
+    RegexBuilder::new(pattern)
+        .case_insensitive(flags.case_insensitive)
+        .multi_line(flags.multi_line)
+        .dot_matches_new_line(flags.dot_all)
+        .unicode(flags.unicode)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .dfa_size_limit(REGEX_DFA_SIZE_LIMIT)
+        .build()
+        .map_err(|e| match e {
+            regex::Error::CompiledTooBig(limit) => {
+                format!("regex_too_complex:compiled pattern exceeds {limit} bytes")
+            }
+            other => other.to_string(),
+        })
+}
+
+/// Detects backtracking-only syntax so users get a clear message instead of a parse error.
+fn unsupported_feature(pattern: &str) -> Option<&'static str> {
+    let mut chars = pattern.chars().peekable();
+    // Classes nest (`[a-z&&[^aeiou]]`, `[[:alpha:]]`), so track how deep we are.
+    let mut class_depth = 0usize;
+    while let Some(c) = chars.next() {
+        let in_class = class_depth > 0;
+        match c {
+            '\\' => match chars.next() {
+                Some('1'..='9') if !in_class => return Some("backreference"),
+                Some('k') if !in_class && chars.peek() == Some(&'<') => {
+                    return Some("backreference")
+                }
+                _ => {}
+            },
+            '[' => {
+                class_depth += 1;
+                // A `]` right after the opening bracket (or its `^`) is a literal.
+                chars.next_if_eq(&'^');
+                chars.next_if_eq(&']');
+            }
+            ']' if in_class => class_depth -= 1,
+            '(' if !in_class && chars.peek() == Some(&'?') => {
+                let rest: String = chars.clone().skip(1).take(2).collect();
+                if rest.starts_with('=') || rest.starts_with('!') {
+                    return Some("lookahead");
+                }
+                if rest == "<=" || rest == "<!" {
+                    return Some("lookbehind");
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn capture_to_result(re: &Regex, caps: &Captures) -> RegexMatchResult {
+    let whole = caps.get(0);
+    RegexMatchResult {
+        matched: true,
+        groups: (0..caps.len())
+            .map(|i| caps.get(i).map(|m| m.as_str().to_string()))
+            .collect(),
+        group_names: re
+            .capture_names()
+            .map(|name| name.map(str::to_string))
+            .collect(),
+        group_spans: (0..caps.len())
+            .map(|i| caps.get(i).map(|m| (m.start(), m.end())))
+            .collect(),
+        match_text: whole.map(|m| m.as_str().to_string()).unwrap_or_default(),
+        start_index: whole.map(|m| m.start()).unwrap_or(0),
+        end_index: whole.map(|m| m.end()).unwrap_or(0),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn regex_matches_and_groups() {
-        let res = test_regex("(foo)-(\\d+)", "foo-123", false).unwrap().unwrap();
+        let res = test_regex("(foo)-(\\d+)", "foo-123", false, RegexFlags::new()).unwrap().unwrap();
         assert_eq!(res.len(), 1);
         let result = &res[0];
         assert!(result.matched);
@@ -211,7 +767,7 @@ mod tests {
 
     #[test]
     fn regex_global_mode_finds_multiple_matches() {
-        let res = test_regex("\\d+", "foo 123 bar 456 baz", true).unwrap().unwrap();
+        let res = test_regex("\\d+", "foo 123 bar 456 baz", true, RegexFlags::new()).unwrap().unwrap();
         assert_eq!(res.len(), 2);
         assert_eq!(res[0].match_text, "123");
         assert_eq!(res[1].match_text, "456");
@@ -219,19 +775,184 @@ mod tests {
 
     #[test]
     fn regex_invalid_pattern_returns_error() {
-        let res = test_regex("(", "x", false).unwrap();
+        let res = test_regex("(", "x", false, RegexFlags::new()).unwrap();
         assert!(res.is_err());
     }
 
     #[test]
     fn regex_empty_pattern_skips() {
-        let res = test_regex("", "foo", false);
+        let res = test_regex("", "foo", false, RegexFlags::new());
         assert!(res.is_none());
     }
 
+    #[test]
+    fn regex_flags_change_matching() {
+        let flags = RegexFlags {
+            case_insensitive: true,
+            multi_line: true,
+            ..RegexFlags::new()
+        };
+        let res = test_regex("^foo$", "FOO\nfoo", true, flags).unwrap().unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(flags_summary(flags), "imu");
+
+        let dot_all = RegexFlags {
+            dot_all: true,
+            ..RegexFlags::new()
+        };
+        let res = test_regex("a.b", "a\nb", false, dot_all).unwrap().unwrap();
+        assert!(res[0].matched);
+        let res = test_regex("a.b", "a\nb", false, RegexFlags::new()).unwrap().unwrap();
+        assert!(!res[0].matched);
+    }
+
+    #[test]
+    fn regex_reports_named_groups_and_spans() {
+        let res = test_regex("(?P<word>[a-z]+)-(\\d+)", "id: abc-42", false, RegexFlags::new())
+            .unwrap()
+            .unwrap();
+        let result = &res[0];
+        assert_eq!(result.group_names[1].as_deref(), Some("word"));
+        assert_eq!(result.group_names[2], None);
+        assert_eq!(result.group_spans[1], Some((4, 7)));
+        assert_eq!(result.group_spans[2], Some((8, 10)));
+    }
+
+    #[test]
+    fn highlighted_sample_uses_utf16_offsets() {
+        let mut state = AppState::new();
+        state.regex_tester.pattern = "b+".into();
+        state.regex_tester.sample_text = "é😀bb x😀b".into();
+        state.regex_tester.global_mode = true;
+        handle_regex_action(&mut state, &std::collections::HashMap::new());
+        let ui = render_regex_tester_screen(&state);
+        let children = ui.get("children").and_then(Value::as_array).unwrap();
+        let highlighted = children
+            .iter()
+            .find(|c| c.get("content_description").and_then(Value::as_str) == Some("regex_highlighted"))
+            .expect("highlighted text");
+        let span = &highlighted["spans"][0];
+        assert_eq!(span["start"], 3);
+        assert_eq!(span["end"], 5);
+        let span = &highlighted["spans"][1];
+        assert_eq!(span["start"], 9);
+        assert_eq!(span["end"], 10);
+    }
+
+    #[test]
+    fn file_scan_counts_all_matches_and_caps_lines() {
+        let text = "alpha 1\nbeta\ngamma 22 33\n\ndelta 4\r\n";
+        let res = scan_reader_for_regex(text.as_bytes(), "\\d+", RegexFlags::new(), 2).unwrap();
+        assert_eq!(res.total_matches, 4);
+        assert_eq!(res.matching_lines, 3);
+        assert_eq!(res.lines.len(), 2);
+        assert_eq!(res.lines[0].line_number, 1);
+        assert_eq!(res.lines[1].line_number, 3);
+        assert_eq!(res.lines[1].text, "gamma 22 33");
+    }
+
+    #[test]
+    fn every_snippet_compiles_and_matches_its_example() {
+        for snippet in REGEX_SNIPPETS {
+            let res = test_regex(snippet.pattern, snippet.example, false, RegexFlags::new())
+                .unwrap()
+                .unwrap_or_else(|e| panic!("{}: {e}", snippet.id));
+            assert_eq!(res[0].match_text, snippet.example, "{}", snippet.id);
+        }
+    }
+
+    #[test]
+    fn snippet_search_matches_labels_and_keywords() {
+        assert_eq!(find_snippets("").len(), REGEX_SNIPPETS.len());
+        let ids: Vec<&str> = find_snippets("DATE").iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec!["iso_date", "iso_datetime"]);
+        assert_eq!(find_snippets("guid")[0].id, "uuid");
+        assert!(find_snippets("nothing-like-this").is_empty());
+    }
+
+    #[test]
+    fn snippet_binding_overrides_pattern_field() {
+        let mut state = AppState::new();
+        let bindings = std::collections::HashMap::from([
+            ("regex_pattern".to_string(), "old".to_string()),
+            ("regex_snippet".to_string(), "semver".to_string()),
+            ("regex_sample".to_string(), "v2.10.3".to_string()),
+        ]);
+        handle_regex_action(&mut state, &bindings);
+        assert!(state.regex_tester.pattern.starts_with("(?P<major>"));
+        assert_eq!(state.regex_tester.match_results[0].match_text, "2.10.3");
+    }
+
+    #[test]
+    fn split_mirrors_regex_split() {
+        let pieces = split_regex("[,;] *", "a, b;;c,", RegexFlags::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(pieces, vec!["a", "b", "", "c", ""]);
+        assert!(split_regex(" ", "x", RegexFlags::new()).is_none());
+        assert!(split_regex("(", "x", RegexFlags::new()).unwrap().is_err());
+    }
+
+    #[test]
+    fn split_mode_lists_indexed_pieces() {
+        let mut state = AppState::new();
+        let bindings = std::collections::HashMap::from([
+            ("regex_pattern".to_string(), "-+".to_string()),
+            ("regex_sample".to_string(), "one--two-".to_string()),
+            ("regex_split_mode".to_string(), "true".to_string()),
+        ]);
+        handle_regex_action(&mut state, &bindings);
+        assert_eq!(state.regex_tester.split_pieces, vec!["one", "two", ""]);
+
+        let ui = render_regex_tester_screen(&state);
+        let texts: Vec<&str> = ui["children"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|c| c.get("text").and_then(Value::as_str))
+            .collect();
+        assert!(texts.contains(&"Split into 3 piece(s)"));
+        assert!(texts.contains(&"[2] \"\""));
+    }
+
+    #[test]
+    fn backtracking_features_get_a_clear_error() {
+        let err = test_regex("foo(?=bar)", "foobar", false, RegexFlags::new())
+            .unwrap()
+            .unwrap_err();
+        assert!(err.starts_with("regex_unsupported_feature:lookahead"));
+        let err = test_regex("(?<!x)y", "y", false, RegexFlags::new())
+            .unwrap()
+            .unwrap_err();
+        assert!(err.starts_with("regex_unsupported_feature:lookbehind"));
+        let err = test_regex("(a)\\1", "aa", false, RegexFlags::new())
+            .unwrap()
+            .unwrap_err();
+        assert!(err.starts_with("regex_unsupported_feature:backreference"));
+        // Named groups and look-around lookalikes inside classes are fine.
+        assert!(test_regex("(?<y>[0-9])[(?=]", "5=", false, RegexFlags::new())
+            .unwrap()
+            .is_ok());
+        // Nested classes keep the outer one open; a leading `]` is a literal.
+        assert_eq!(unsupported_feature("[[a](?=x)]"), None);
+        let intersection = unsupported_feature("[a-z&&[^aeiou]](?=x)");
+        assert_eq!(intersection, Some("lookahead"));
+        assert_eq!(unsupported_feature("[]\\1]"), None);
+        let posix = unsupported_feature("[^]][[:alpha:]]\\1");
+        assert_eq!(posix, Some("backreference"));
+    }
+
+    #[test]
+    fn oversized_patterns_are_rejected() {
+        let err = test_regex("(a{1000}){1000}", "x", false, RegexFlags::new())
+            .unwrap()
+            .unwrap_err();
+        assert!(err.starts_with("regex_too_complex"), "{err}");
+    }
+
     #[test]
     fn regex_global_mode_with_groups() {
-        let res = test_regex("(foo)-(\\d+)", "foo-123 bar foo-456", true).unwrap().unwrap();
+        let res = test_regex("(foo)-(\\d+)", "foo-123 bar foo-456", true, RegexFlags::new()).unwrap().unwrap();
         assert_eq!(res.len(), 2);
         
         // First match
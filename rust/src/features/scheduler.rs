@@ -1,3 +1,4 @@
+use crate::error::error_text;
 use crate::state::{AppState, ScheduledTask, SchedulerLog};
 use crate::ui::{
     Button as UiButton, Card as UiCard, Column as UiColumn, Section as UiSection, Text as UiText,
@@ -20,24 +21,18 @@ fn ts_to_local(ts: i64) -> DateTime<Local> {
 
 pub fn render_scheduler_screen(state: &AppState) -> Value {
     let mut children: Vec<Value> = Vec::new();
-    children.push(
-        serde_json::to_value(UiText::new("Task Scheduler").size(20.0)).unwrap(),
-    );
-    children.push(
-        serde_json::to_value(UiText::new("Create cron-based tasks that fire actions on a background thread.").size(12.0))
-            .unwrap(),
-    );
+    children.push(serde_json::to_value(UiText::new(&t!("scheduler_title")).size(20.0)).unwrap());
+    children
+        .push(serde_json::to_value(UiText::new(&t!("scheduler_description")).size(12.0)).unwrap());
 
     if let Some(err) = &state.scheduler.last_error {
-        children.push(
-            serde_json::to_value(UiText::new(&format!("Error: {err}")).size(12.0)).unwrap(),
-        );
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
     }
 
     let form_children = vec![
         serde_json::to_value(
             UiTextInput::new("scheduler_name")
-                .hint("Task name")
+                .hint(&t!("scheduler_name_hint"))
                 .text(&state.scheduler.form_name)
                 .single_line(true)
                 .debounce_ms(150),
@@ -45,7 +40,7 @@ pub fn render_scheduler_screen(state: &AppState) -> Value {
         .unwrap(),
         serde_json::to_value(
             UiTextInput::new("scheduler_action")
-                .hint("Action ID (e.g., about)")
+                .hint(&t!("scheduler_action_hint"))
                 .text(&state.scheduler.form_action)
                 .single_line(true)
                 .debounce_ms(150),
@@ -53,16 +48,21 @@ pub fn render_scheduler_screen(state: &AppState) -> Value {
         .unwrap(),
         serde_json::to_value(
             UiTextInput::new("scheduler_cron")
-                .hint("Cron (e.g., */5 * * * *)")
+                .hint(&t!("scheduler_cron_hint"))
                 .text(&state.scheduler.form_cron)
                 .single_line(true)
                 .debounce_ms(150),
         )
         .unwrap(),
-        serde_json::to_value(UiButton::new("Add task", "scheduler_add")).unwrap(),
+        serde_json::to_value(UiButton::new(&t!("scheduler_add_button"), "scheduler_add")).unwrap(),
     ];
     children.push(
-        serde_json::to_value(UiCard::new(form_children).title("New task").padding(12)).unwrap(),
+        serde_json::to_value(
+            UiCard::new(form_children)
+                .title(&t!("scheduler_new_task_section"))
+                .padding(12),
+        )
+        .unwrap(),
     );
 
     // Active tasks list
@@ -71,29 +71,52 @@ pub fn render_scheduler_screen(state: &AppState) -> Value {
         let status = match task.last_run_epoch {
             Some(ts) => {
                 let dt = ts_to_local(ts);
-                format!("Last run: {}", dt.format("%Y-%m-%d %H:%M:%S"))
+                t!(
+                    "scheduler_last_run",
+                    time = dt.format("%Y-%m-%d %H:%M:%S").to_string()
+                )
             }
-            None => "Never run".to_string(),
+            None => t!("scheduler_never_run"),
+        };
+        let enabled_label = if task.enabled {
+            t!("scheduler_enabled")
+        } else {
+            t!("scheduler_paused")
         };
-        let enabled_label = if task.enabled { "Enabled" } else { "Paused" };
         let item_children: Vec<Value> = vec![
-            serde_json::to_value(UiText::new(&format!("{} ({enabled_label})", task.name)).size(14.0)).unwrap(),
-            serde_json::to_value(UiText::new(&format!("Action: {}", task.action)).size(12.0)).unwrap(),
-            serde_json::to_value(UiText::new(&format!("Cron: {}", task.cron)).size(12.0)).unwrap(),
+            serde_json::to_value(
+                UiText::new(&format!("{} ({enabled_label})", task.name)).size(14.0),
+            )
+            .unwrap(),
+            serde_json::to_value(
+                UiText::new(&t!("scheduler_task_action", action = task.action)).size(12.0),
+            )
+            .unwrap(),
+            serde_json::to_value(
+                UiText::new(&t!("scheduler_task_cron", cron = task.cron)).size(12.0),
+            )
+            .unwrap(),
             serde_json::to_value(UiText::new(&status).size(12.0)).unwrap(),
             serde_json::to_value(
                 UiColumn::new(vec![
-                    serde_json::to_value(UiButton::new("Run now", &format!("scheduler_run:{}", task.id))).unwrap(),
-                    serde_json::to_value(
-                        UiButton::new(
-                            if task.enabled { "Pause" } else { "Enable" },
-                            &format!("scheduler_toggle:{}", task.id),
-                        ),
-                    )
+                    serde_json::to_value(UiButton::new(
+                        &t!("scheduler_run_now_button"),
+                        &format!("scheduler_run:{}", task.id),
+                    ))
+                    .unwrap(),
+                    serde_json::to_value(UiButton::new(
+                        &if task.enabled {
+                            t!("scheduler_pause_button")
+                        } else {
+                            t!("scheduler_enable_button")
+                        },
+                        &format!("scheduler_toggle:{}", task.id),
+                    ))
                     .unwrap(),
-                    serde_json::to_value(
-                        UiButton::new("Delete", &format!("scheduler_delete:{}", task.id)),
-                    )
+                    serde_json::to_value(UiButton::new(
+                        &t!("scheduler_delete_button"),
+                        &format!("scheduler_delete:{}", task.id),
+                    ))
                     .unwrap(),
                 ])
                 .padding(4),
@@ -103,14 +126,16 @@ pub fn render_scheduler_screen(state: &AppState) -> Value {
         task_items.push(serde_json::to_value(UiCard::new(item_children).padding(10)).unwrap());
     }
     if task_items.is_empty() {
-        task_items.push(
-            serde_json::to_value(UiText::new("No scheduled tasks yet.").size(12.0)).unwrap(),
-        );
+        task_items
+            .push(serde_json::to_value(UiText::new(&t!("scheduler_no_tasks")).size(12.0)).unwrap());
     }
     children.push(
         serde_json::to_value(
-            UiSection::new(vec![serde_json::to_value(UiVirtualList::new(task_items).estimated_item_height(110)).unwrap()])
-                .title("Tasks"),
+            UiSection::new(vec![serde_json::to_value(
+                UiVirtualList::new(task_items).estimated_item_height(110),
+            )
+            .unwrap()])
+            .title(&t!("scheduler_tasks_section")),
         )
         .unwrap(),
     );
@@ -133,13 +158,15 @@ pub fn render_scheduler_screen(state: &AppState) -> Value {
     }
     if log_items.is_empty() {
         log_items.push(
-            serde_json::to_value(UiText::new("No task executions yet.").size(12.0)).unwrap(),
+            serde_json::to_value(UiText::new(&t!("scheduler_no_activity")).size(12.0)).unwrap(),
         );
     }
     children.push(
         serde_json::to_value(
-            UiSection::new(vec![serde_json::to_value(UiVirtualList::new(log_items)).unwrap()])
-                .title("Recent activity"),
+            UiSection::new(vec![
+                serde_json::to_value(UiVirtualList::new(log_items)).unwrap()
+            ])
+            .title(&t!("scheduler_activity_section")),
         )
         .unwrap(),
     );
@@ -0,0 +1,255 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Standard gravity, used to express accelerometer thresholds in g.
+const STANDARD_GRAVITY: f64 = 9.806_65;
+/// Pressure samples older than this are dropped from the drop-rate window.
+const PRESSURE_WINDOW_MS: i64 = 60_000;
+/// Shortest window over which a pressure drop rate is trusted.
+const PRESSURE_MIN_SPAN_MS: i64 = 10_000;
+/// Alerts kept for the status area.
+pub const SENSOR_RECENT_ALERTS: usize = 5;
+
+/// Per-sensor alert thresholds; `None` disables the check.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SensorThresholds {
+    /// Acceleration magnitude (gravity included), in g.
+    pub accel_g: Option<f64>,
+    /// Rotation rate magnitude, in rad/s.
+    pub gyro_rad_s: Option<f64>,
+    /// Pressure fall rate over the last minute, in hPa/min.
+    pub pressure_drop_hpa_min: Option<f64>,
+}
+
+impl SensorThresholds {
+    pub fn any(self) -> bool {
+        self.accel_g.is_some() || self.gyro_rad_s.is_some() || self.pressure_drop_hpa_min.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensorAlertKind {
+    Accel,
+    Gyro,
+    PressureDrop,
+}
+
+impl SensorAlertKind {
+    const ALL: [Self; 3] = [Self::Accel, Self::Gyro, Self::PressureDrop];
+
+    /// Value written to the `z` column of `ALERT` log rows.
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::Accel => 1,
+            Self::Gyro => 2,
+            Self::PressureDrop => 3,
+        }
+    }
+
+    fn index(self) -> usize {
+        self.code() as usize - 1
+    }
+
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Self::Accel => "|a|",
+            Self::Gyro => "|ω|",
+            Self::PressureDrop => "-Δp",
+        }
+    }
+
+    pub const fn unit(self) -> &'static str {
+        match self {
+            Self::Accel => "g",
+            Self::Gyro => "rad/s",
+            Self::PressureDrop => "hPa/min",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SensorAlert {
+    pub ts_ms: i64,
+    pub kind: SensorAlertKind,
+    pub value: f64,
+    pub threshold: f64,
+}
+
+impl SensorAlert {
+    /// Log annotation in the CSV row format: `ts,ALERT,value,threshold,code,0,0`.
+    pub fn log_row(&self) -> String {
+        format!(
+            "{},ALERT,{:.4},{:.4},{},0,0",
+            self.ts_ms,
+            self.value,
+            self.threshold,
+            self.kind.code()
+        )
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "{} {:.2} > {:.2} {}",
+            self.kind.symbol(),
+            self.value,
+            self.threshold,
+            self.kind.unit()
+        )
+    }
+}
+
+/// Edge-triggered threshold checks over the samples streamed in status updates.
+/// An alert fires when a value crosses its threshold and re-arms once it falls back.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SensorAlertMonitor {
+    above: [bool; 3],
+    pressure: VecDeque<(i64, f64)>,
+    recent: VecDeque<SensorAlert>,
+    total: usize,
+    /// Log rows not yet handed to the Android logger.
+    pending_rows: Vec<String>,
+}
+
+impl SensorAlertMonitor {
+    /// Feeds one batch of `(tag, value)` samples taken at `ts_ms`.
+    pub fn observe(&mut self, thresholds: SensorThresholds, ts_ms: i64, samples: &[(&str, f64)]) {
+        for &(tag, value) in samples {
+            match tag {
+                "ACCEL" => self.check(
+                    SensorAlertKind::Accel,
+                    ts_ms,
+                    value / STANDARD_GRAVITY,
+                    thresholds.accel_g,
+                ),
+                "GYRO" => self.check(SensorAlertKind::Gyro, ts_ms, value, thresholds.gyro_rad_s),
+                "BARO" => {
+                    let rate = self.pressure_drop_rate(ts_ms, value);
+                    if let Some(rate) = rate {
+                        self.check(
+                            SensorAlertKind::PressureDrop,
+                            ts_ms,
+                            rate,
+                            thresholds.pressure_drop_hpa_min,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn pressure_drop_rate(&mut self, ts_ms: i64, hpa: f64) -> Option<f64> {
+        self.pressure.push_back((ts_ms, hpa));
+        while self
+            .pressure
+            .front()
+            .is_some_and(|(ts, _)| ts_ms - ts > PRESSURE_WINDOW_MS)
+        {
+            self.pressure.pop_front();
+        }
+        let &(first_ts, first_hpa) = self.pressure.front()?;
+        let span_ms = ts_ms - first_ts;
+        (span_ms >= PRESSURE_MIN_SPAN_MS).then(|| (first_hpa - hpa) * 60_000.0 / span_ms as f64)
+    }
+
+    fn check(&mut self, kind: SensorAlertKind, ts_ms: i64, value: f64, threshold: Option<f64>) {
+        let Some(threshold) = threshold else {
+            return;
+        };
+        let above = value > threshold;
+        let was_above = std::mem::replace(&mut self.above[kind.index()], above);
+        if !above || was_above {
+            return;
+        }
+        let alert = SensorAlert {
+            ts_ms,
+            kind,
+            value,
+            threshold,
+        };
+        self.pending_rows.push(alert.log_row());
+        if self.recent.len() == SENSOR_RECENT_ALERTS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(alert);
+        self.total += 1;
+    }
+
+    /// Kinds whose value is currently above its threshold.
+    pub fn active(&self) -> impl Iterator<Item = SensorAlertKind> + '_ {
+        SensorAlertKind::ALL
+            .into_iter()
+            .filter(|kind| self.above[kind.index()])
+    }
+
+    pub fn recent(&self) -> impl Iterator<Item = &SensorAlert> {
+        self.recent.iter()
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    pub fn take_pending_rows(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending_rows)
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accel_alert_fires_once_per_crossing() {
+        let thresholds = SensorThresholds {
+            accel_g: Some(3.0),
+            ..Default::default()
+        };
+        let mut monitor = SensorAlertMonitor::default();
+        monitor.observe(thresholds, 0, &[("ACCEL", 9.8)]);
+        monitor.observe(thresholds, 500, &[("ACCEL", 40.0)]);
+        monitor.observe(thresholds, 1000, &[("ACCEL", 45.0)]);
+        assert_eq!(monitor.total(), 1);
+        assert_eq!(monitor.active().collect::<Vec<_>>(), vec![SensorAlertKind::Accel]);
+
+        monitor.observe(thresholds, 1500, &[("ACCEL", 9.8)]);
+        monitor.observe(thresholds, 2000, &[("ACCEL", 40.0)]);
+        assert_eq!(monitor.total(), 2);
+
+        let rows = monitor.take_pending_rows();
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].starts_with("500,ALERT,4.0789,3.0000,1,"));
+        assert!(monitor.take_pending_rows().is_empty());
+    }
+
+    #[test]
+    fn pressure_drop_uses_rate_over_the_window() {
+        let thresholds = SensorThresholds {
+            pressure_drop_hpa_min: Some(1.0),
+            ..Default::default()
+        };
+        let mut monitor = SensorAlertMonitor::default();
+        monitor.observe(thresholds, 0, &[("BARO", 1013.0)]);
+        // Too short a span to trust the rate.
+        monitor.observe(thresholds, 5_000, &[("BARO", 1010.0)]);
+        assert_eq!(monitor.total(), 0);
+        monitor.observe(thresholds, 30_000, &[("BARO", 1012.0)]);
+        assert_eq!(monitor.total(), 1);
+        let alert = monitor.recent().next().unwrap();
+        assert_eq!(alert.kind, SensorAlertKind::PressureDrop);
+        assert!((alert.value - 2.0).abs() < 1e-9);
+        assert_eq!(alert.describe(), "-Δp 2.00 > 1.00 hPa/min");
+    }
+
+    #[test]
+    fn disabled_thresholds_never_fire() {
+        let mut monitor = SensorAlertMonitor::default();
+        monitor.observe(SensorThresholds::default(), 0, &[("ACCEL", 1e6), ("GYRO", 1e6)]);
+        assert_eq!(monitor.total(), 0);
+        assert!(!SensorThresholds::default().any());
+    }
+}
@@ -1,13 +1,16 @@
+use crate::error::error_text;
+use crate::features::sensor_alerts::SensorThresholds;
 use crate::state::AppState;
 
 use crate::ui::{
-    self, maybe_push_back, Button as UiButton, Column as UiColumn, Text as UiText,
-    Warning as UiWarning,
+    self, maybe_push_back, Button as UiButton, Chart as UiChart, ChartSeries, Column as UiColumn,
+    Text as UiText, Warning as UiWarning,
 };
 use rust_i18n::t;
 /// Represents which sensors the user wants to capture.
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
+use std::collections::VecDeque;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SensorSelection {
@@ -17,18 +20,260 @@ pub struct SensorSelection {
     pub pressure: bool,
     pub gps: bool,
     pub battery: bool,
+    #[serde(default)]
+    pub light: bool,
+    #[serde(default)]
+    pub proximity: bool,
+    #[serde(default)]
+    pub gravity: bool,
+    #[serde(default)]
+    pub linear_accel: bool,
+    #[serde(default)]
+    pub rotation: bool,
+    #[serde(default)]
+    pub steps: bool,
 }
 
 impl SensorSelection {
     pub fn any(self) -> bool {
-        self.accel || self.gyro || self.mag || self.pressure || self.gps || self.battery
+        self.accel
+            || self.gyro
+            || self.mag
+            || self.pressure
+            || self.gps
+            || self.battery
+            || self.light
+            || self.proximity
+            || self.gravity
+            || self.linear_accel
+            || self.rotation
+            || self.steps
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// CSV row tag written by the Android logger and the meaning of the
+/// `x,y,z,extra1,extra2` columns for that tag. Unused columns are written as 0.
+pub const SENSOR_CSV_COLUMNS: &[(&str, &str)] = &[
+    ("ACCEL", "x, y, z (m/s²)"),
+    ("GYRO", "x, y, z (rad/s)"),
+    ("MAG", "x, y, z (µT)"),
+    ("BARO", "x = pressure (hPa)"),
+    ("GPS", "x = latitude, y = longitude, z = accuracy (m)"),
+    ("LIGHT", "x = illuminance (lx)"),
+    ("PROX", "x = distance (cm)"),
+    ("GRAVITY", "x, y, z (m/s²)"),
+    ("LINACC", "x, y, z (m/s², gravity removed)"),
+    ("ROTVEC", "x, y, z, extra1 = w, extra2 = heading accuracy (rad)"),
+    ("STEPS", "x = steps since boot"),
+    ("PAUSE", "gap marker: logging paused, session file kept open"),
+    ("RESUME", "gap marker: x = paused duration (ms)"),
+    (
+        "ALERT",
+        "threshold crossing: x = value, y = threshold, z = 1 |accel| (g), 2 |gyro| (rad/s), 3 pressure drop (hPa/min)",
+    ),
+];
+
+/// Number of recent samples kept per sensor for the live chart.
+pub const SENSOR_CHART_WINDOW: usize = 60;
+
+/// Rolling window of recent values per CSV tag, fed by the `sensor_samples`
+/// status binding (`TAG=value;TAG=value`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SensorHistory {
+    series: Vec<(String, VecDeque<f64>)>,
+}
+
+impl SensorHistory {
+    pub fn push(&mut self, tag: &str, value: f64) {
+        let idx = match self.series.iter().position(|(t, _)| t == tag) {
+            Some(idx) => idx,
+            None => {
+                self.series
+                    .push((tag.to_string(), VecDeque::with_capacity(SENSOR_CHART_WINDOW)));
+                self.series.len() - 1
+            }
+        };
+        let values = &mut self.series[idx].1;
+        if values.len() == SENSOR_CHART_WINDOW {
+            values.pop_front();
+        }
+        values.push_back(value);
+    }
+
+    pub fn series(&self) -> impl Iterator<Item = (&str, &VecDeque<f64>)> {
+        self.series.iter().map(|(tag, values)| (tag.as_str(), values))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.series.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.series.clear();
+    }
+}
+
+/// Parses `TAG=value;TAG=value`, keeping only known CSV tags with finite values.
+pub fn parse_samples(raw: &str) -> Vec<(&str, f64)> {
+    raw.split(';')
+        .filter_map(|pair| {
+            let (tag, value) = pair.split_once('=')?;
+            let tag = tag.trim();
+            if !SENSOR_CSV_COLUMNS.iter().any(|(known, _)| *known == tag) {
+                return None;
+            }
+            let value = value.trim().parse::<f64>().ok().filter(|v| v.is_finite())?;
+            Some((tag, value))
+        })
+        .collect()
+}
+
+/// Segment size cap accepted from bindings, in KiB (64 KiB .. 1 GiB).
+const MAX_FILE_KB_RANGE: std::ops::RangeInclusive<u64> = 64..=1_048_576;
+/// Segment duration cap accepted from bindings, in minutes (up to 24 h).
+const MAX_DURATION_MIN_RANGE: std::ops::RangeInclusive<u64> = 1..=1_440;
+
+/// When the Android logger rotates to the next numbered CSV segment.
+/// `None` disables the corresponding cap.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SensorLogLimits {
+    pub max_file_kb: Option<u64>,
+    pub max_duration_min: Option<u64>,
+    /// Gzip each completed segment on the worker and drop the plain CSV.
+    pub gzip_segments: bool,
+}
+
+impl SensorLogLimits {
+    pub fn rotates(self) -> bool {
+        self.max_file_kb.is_some() || self.max_duration_min.is_some()
+    }
+}
+
+/// Storage used by the Android logger for each segment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SensorBackend {
+    /// One `ts,sensor,x,y,z,extra1,extra2` row per sample.
+    #[default]
+    Csv,
+    /// JSON Lines: one `{"ts":..,"sensor":..,"x":..}` object per sample.
+    Ndjson,
+    /// A `.db` file with one table per sensor (see [`sensor_table_name`]).
+    Sqlite,
+}
+
+impl SensorBackend {
+    pub const ALL: [Self; 3] = [Self::Csv, Self::Ndjson, Self::Sqlite];
+
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "ndjson" | "jsonl" => Ok(Self::Ndjson),
+            "sqlite" | "db" => Ok(Self::Sqlite),
+            other => Err(format!("sensor_unknown_format:{other}")),
+        }
+    }
+
+    /// Binding value understood by [`SensorBackend::parse`] and the Android logger.
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Ndjson => "ndjson",
+            Self::Sqlite => "sqlite",
+        }
+    }
+
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Ndjson => "ndjson",
+            Self::Sqlite => "db",
+        }
+    }
+}
+
+/// Converts one NDJSON log line back into the CSV row layout
+/// (`ts,sensor,x,y,z,extra1,extra2`) so CSV consumers can read both formats.
+/// Missing or null values become empty columns.
+pub fn ndjson_line_to_csv(line: &str) -> Option<String> {
+    let obj: serde_json::Map<String, Value> = serde_json::from_str(line.trim()).ok()?;
+    let ts = obj.get("ts")?.as_i64()?;
+    let sensor = obj.get("sensor")?.as_str()?;
+    let mut row = format!("{ts},{sensor}");
+    for key in ["x", "y", "z", "extra1", "extra2"] {
+        row.push(',');
+        if let Some(v) = obj.get(key).and_then(Value::as_f64) {
+            row.push_str(&v.to_string());
+        }
+    }
+    Some(row)
+}
+
+/// Table holding a sensor's samples in the SQLite backend: the lowercase CSV tag
+/// (`ACCEL` -> `accel`). Unknown tags have no table.
+pub fn sensor_table_name(tag: &str) -> Option<String> {
+    SENSOR_CSV_COLUMNS
+        .iter()
+        .find(|(known, _)| *known == tag)
+        .map(|(known, _)| known.to_ascii_lowercase())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct SensorConfig {
     pub selection: SensorSelection,
     pub interval_ms: u64,
+    pub limits: SensorLogLimits,
+    pub backend: SensorBackend,
+    pub thresholds: SensorThresholds,
+}
+
+/// Reads a checkbox binding; anything other than "true"/"false" is rejected so a
+/// malformed payload cannot silently flip a sensor on or off.
+fn parse_flag(
+    bindings: &std::collections::HashMap<String, String>,
+    key: &str,
+    default: bool,
+) -> Result<bool, String> {
+    match bindings.get(key).map(|v| v.trim()) {
+        None => Ok(default),
+        Some("true") => Ok(true),
+        Some("false") => Ok(false),
+        Some(_) => Err(format!("invalid_sensor_flag:{key}")),
+    }
+}
+
+/// Reads an optional cap; empty or "0" disables it, anything else must fall in `range`.
+fn parse_cap(
+    bindings: &std::collections::HashMap<String, String>,
+    key: &str,
+    range: std::ops::RangeInclusive<u64>,
+) -> Result<Option<u64>, String> {
+    let raw = match bindings.get(key).map(|v| v.trim()) {
+        None | Some("") | Some("0") => return Ok(None),
+        Some(raw) => raw,
+    };
+    raw.parse::<u64>()
+        .ok()
+        .filter(|v| range.contains(v))
+        .map(Some)
+        .ok_or_else(|| format!("sensor_cap_out_of_range:{key}"))
+}
+
+/// Reads an optional alert threshold; empty disables it, anything else must fall in `range`.
+fn parse_threshold(
+    bindings: &std::collections::HashMap<String, String>,
+    key: &str,
+    range: std::ops::RangeInclusive<f64>,
+) -> Result<Option<f64>, String> {
+    let raw = match bindings.get(key).map(|v| v.trim()) {
+        None | Some("") => return Ok(None),
+        Some(raw) => raw,
+    };
+    raw.parse::<f64>()
+        .ok()
+        .filter(|v| range.contains(v))
+        .map(Some)
+        .ok_or_else(|| format!("sensor_threshold_out_of_range:{key}"))
 }
 
 /// Parse bindings coming from Kotlin UI to a typed sensor config.
@@ -36,30 +281,18 @@ pub fn parse_bindings(
     bindings: &std::collections::HashMap<String, String>,
 ) -> Result<SensorConfig, String> {
     let sel = SensorSelection {
-        accel: bindings
-            .get("sensor_accel")
-            .map(|v| v == "true")
-            .unwrap_or(true),
-        gyro: bindings
-            .get("sensor_gyro")
-            .map(|v| v == "true")
-            .unwrap_or(true),
-        mag: bindings
-            .get("sensor_mag")
-            .map(|v| v == "true")
-            .unwrap_or(true),
-        pressure: bindings
-            .get("sensor_pressure")
-            .map(|v| v == "true")
-            .unwrap_or(false),
-        gps: bindings
-            .get("sensor_gps")
-            .map(|v| v == "true")
-            .unwrap_or(false),
-        battery: bindings
-            .get("sensor_battery")
-            .map(|v| v == "true")
-            .unwrap_or(true),
+        accel: parse_flag(bindings, "sensor_accel", true)?,
+        gyro: parse_flag(bindings, "sensor_gyro", true)?,
+        mag: parse_flag(bindings, "sensor_mag", true)?,
+        pressure: parse_flag(bindings, "sensor_pressure", false)?,
+        gps: parse_flag(bindings, "sensor_gps", false)?,
+        battery: parse_flag(bindings, "sensor_battery", true)?,
+        light: parse_flag(bindings, "sensor_light", false)?,
+        proximity: parse_flag(bindings, "sensor_proximity", false)?,
+        gravity: parse_flag(bindings, "sensor_gravity", false)?,
+        linear_accel: parse_flag(bindings, "sensor_linear_accel", false)?,
+        rotation: parse_flag(bindings, "sensor_rotation", false)?,
+        steps: parse_flag(bindings, "sensor_steps", false)?,
     };
 
     if !sel.any() {
@@ -72,9 +305,31 @@ pub fn parse_bindings(
         .filter(|v| *v >= 50 && *v <= 10_000)
         .unwrap_or(200);
 
+    let limits = SensorLogLimits {
+        max_file_kb: parse_cap(bindings, "sensor_max_file_kb", MAX_FILE_KB_RANGE)?,
+        max_duration_min: parse_cap(bindings, "sensor_max_duration_min", MAX_DURATION_MIN_RANGE)?,
+        gzip_segments: parse_flag(bindings, "sensor_gzip_segments", false)?,
+    };
+
+    // `sensor_sqlite` predates the format selector and is still honoured.
+    let backend = match bindings.get("sensor_format") {
+        Some(raw) => SensorBackend::parse(raw)?,
+        None if parse_flag(bindings, "sensor_sqlite", false)? => SensorBackend::Sqlite,
+        None => SensorBackend::Csv,
+    };
+
+    let thresholds = SensorThresholds {
+        accel_g: parse_threshold(bindings, "sensor_alert_accel_g", 0.5..=50.0)?,
+        gyro_rad_s: parse_threshold(bindings, "sensor_alert_gyro_rad_s", 0.1..=100.0)?,
+        pressure_drop_hpa_min: parse_threshold(bindings, "sensor_alert_pressure_drop", 0.01..=50.0)?,
+    };
+
     Ok(SensorConfig {
         selection: sel,
         interval_ms,
+        limits,
+        backend,
+        thresholds,
     })
 }
 
@@ -86,8 +341,32 @@ pub fn apply_status_from_bindings(
         state.sensor_status = Some(s.clone());
     }
     if let Some(p) = bindings.get("sensor_path") {
+        if state.last_sensor_log.as_deref() != Some(p.as_str()) {
+            state.last_sensor_export = None;
+        }
         state.last_sensor_log = Some(p.clone());
     }
+    if let Some(raw) = bindings.get("sensor_samples") {
+        let samples = parse_samples(raw);
+        for &(tag, value) in &samples {
+            state.sensor_history.push(tag, value);
+        }
+        if let Some(thresholds) = state.sensor_thresholds.filter(|t| t.any()) {
+            let ts_ms = bindings
+                .get("sensor_samples_ts")
+                .and_then(|v| v.trim().parse::<i64>().ok())
+                .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+            state.sensor_alerts.observe(thresholds, ts_ms, &samples);
+        }
+    }
+}
+
+fn cap_text(cap: Option<u64>) -> String {
+    cap.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn threshold_text(threshold: Option<f64>) -> String {
+    threshold.map(|v| v.to_string()).unwrap_or_default()
 }
 
 pub fn render_sensor_logger_screen(state: &AppState) -> Value {
@@ -130,6 +409,36 @@ pub fn render_sensor_logger_screen(state: &AppState) -> Value {
                         .checked(state.sensor_selection.map(|s| s.battery).unwrap_or(true)),
                 )
                 .unwrap(),
+                serde_json::to_value(
+                    ui::Checkbox::new(&t!("sensor_light"), "sensor_light")
+                        .checked(state.sensor_selection.map(|s| s.light).unwrap_or(false)),
+                )
+                .unwrap(),
+                serde_json::to_value(
+                    ui::Checkbox::new(&t!("sensor_proximity"), "sensor_proximity")
+                        .checked(state.sensor_selection.map(|s| s.proximity).unwrap_or(false)),
+                )
+                .unwrap(),
+                serde_json::to_value(
+                    ui::Checkbox::new(&t!("sensor_gravity"), "sensor_gravity")
+                        .checked(state.sensor_selection.map(|s| s.gravity).unwrap_or(false)),
+                )
+                .unwrap(),
+                serde_json::to_value(
+                    ui::Checkbox::new(&t!("sensor_linear_accel"), "sensor_linear_accel")
+                        .checked(state.sensor_selection.map(|s| s.linear_accel).unwrap_or(false)),
+                )
+                .unwrap(),
+                serde_json::to_value(
+                    ui::Checkbox::new(&t!("sensor_rotation_vector"), "sensor_rotation")
+                        .checked(state.sensor_selection.map(|s| s.rotation).unwrap_or(false)),
+                )
+                .unwrap(),
+                serde_json::to_value(
+                    ui::Checkbox::new(&t!("sensor_step_counter"), "sensor_steps")
+                        .checked(state.sensor_selection.map(|s| s.steps).unwrap_or(false)),
+                )
+                .unwrap(),
             ])
             .padding(8),
         )
@@ -146,16 +455,100 @@ pub fn render_sensor_logger_screen(state: &AppState) -> Value {
                 .content_description(&t!("sensor_interval_ms_content_description")),
         )
         .unwrap(),
-        serde_json::to_value(UiButton::new(&t!("sensor_start_logging_button"), "sensor_logger_start")).unwrap(),
-        serde_json::to_value(UiButton::new(&t!("sensor_stop_logging_button"), "sensor_logger_stop")).unwrap(),
+        serde_json::to_value(
+            ui::TextInput::new("sensor_max_file_kb")
+                .hint(&t!("sensor_max_file_kb_hint"))
+                .text(&cap_text(state.sensor_limits.and_then(|l| l.max_file_kb)))
+                .single_line(true),
+        )
+        .unwrap(),
+        serde_json::to_value(
+            ui::TextInput::new("sensor_max_duration_min")
+                .hint(&t!("sensor_max_duration_min_hint"))
+                .text(&cap_text(state.sensor_limits.and_then(|l| l.max_duration_min)))
+                .single_line(true),
+        )
+        .unwrap(),
+        serde_json::to_value(
+            ui::Switch::new(&t!("sensor_gzip_segments"), "sensor_gzip_segments")
+                .checked(state.sensor_limits.map(|l| l.gzip_segments).unwrap_or(false)),
+        )
+        .unwrap(),
+        serde_json::to_value(UiText::new(&t!("sensor_format_section")).size(14.0)).unwrap(),
+        serde_json::to_value(
+            ui::Row::new(
+                SensorBackend::ALL
+                    .iter()
+                    .map(|backend| {
+                        let label = match backend {
+                            SensorBackend::Csv => t!("sensor_format_csv"),
+                            SensorBackend::Ndjson => t!("sensor_format_ndjson"),
+                            SensorBackend::Sqlite => t!("sensor_sqlite_backend"),
+                        };
+                        let label = if *backend == state.sensor_backend {
+                            format!("✓ {label}")
+                        } else {
+                            label.to_string()
+                        };
+                        serde_json::to_value(
+                            UiButton::new(&label, "sensor_logger_format")
+                                .payload(json!({ "sensor_format": backend.id() })),
+                        )
+                        .unwrap()
+                    })
+                    .collect(),
+            )
+            .content_description("sensor_logger_format"),
+        )
+        .unwrap(),
+        serde_json::to_value(
+            ui::TextInput::new("sensor_alert_accel_g")
+                .hint(&t!("sensor_alert_accel_hint"))
+                .text(&threshold_text(state.sensor_thresholds.and_then(|t| t.accel_g)))
+                .single_line(true),
+        )
+        .unwrap(),
+        serde_json::to_value(
+            ui::TextInput::new("sensor_alert_gyro_rad_s")
+                .hint(&t!("sensor_alert_gyro_hint"))
+                .text(&threshold_text(state.sensor_thresholds.and_then(|t| t.gyro_rad_s)))
+                .single_line(true),
+        )
+        .unwrap(),
+        serde_json::to_value(
+            ui::TextInput::new("sensor_alert_pressure_drop")
+                .hint(&t!("sensor_alert_pressure_hint"))
+                .text(&threshold_text(
+                    state.sensor_thresholds.and_then(|t| t.pressure_drop_hpa_min),
+                ))
+                .single_line(true),
+        )
+        .unwrap(),
+        serde_json::to_value(
+            UiButton::new(&t!("sensor_start_logging_button"), "sensor_logger_start")
+                .payload(json!({ "sensor_format": state.sensor_backend.id() })),
+        )
+        .unwrap(),
     ];
+    match state.sensor_status.as_deref() {
+        Some("logging") => children.push(
+            serde_json::to_value(UiButton::new(&t!("sensor_pause_logging_button"), "sensor_logger_pause")).unwrap(),
+        ),
+        Some("paused") => children.push(
+            serde_json::to_value(UiButton::new(&t!("sensor_resume_logging_button"), "sensor_logger_resume")).unwrap(),
+        ),
+        _ => {}
+    }
+    children.push(
+        serde_json::to_value(UiButton::new(&t!("sensor_stop_logging_button"), "sensor_logger_stop")).unwrap(),
+    );
 
     if let Some(status) = &state.sensor_status {
         children.push(
             serde_json::to_value(UiText::new(&format!("{}{}", t!("sensor_status_prefix"), status)).size(12.0)).unwrap(),
         );
     }
-    if state.sensor_status.as_deref() == Some("logging") {
+    if matches!(state.sensor_status.as_deref(), Some("logging") | Some("paused")) {
         children.push(
             serde_json::to_value(
                 UiWarning::new(&t!("sensor_logging_foreground_service"))
@@ -164,10 +557,48 @@ pub fn render_sensor_logger_screen(state: &AppState) -> Value {
             .unwrap(),
         );
     }
-    if let Some(err) = &state.last_error {
+    let active: Vec<&str> = state.sensor_alerts.active().map(|k| k.symbol()).collect();
+    if !active.is_empty() {
+        children.push(
+            serde_json::to_value(
+                UiWarning::new(&format!("{}{}", t!("sensor_alert_active_prefix"), active.join(", ")))
+                    .content_description("sensor_logger_alert_active"),
+            )
+            .unwrap(),
+        );
+    }
+    if state.sensor_alerts.total() > 0 {
         children.push(
-            serde_json::to_value(UiText::new(&format!("{}{}", t!("multi_hash_error_prefix"), err)).size(12.0)).unwrap(),
+            serde_json::to_value(
+                UiText::new(&format!("{}{}", t!("sensor_alerts_prefix"), state.sensor_alerts.total()))
+                    .size(12.0)
+                    .content_description("sensor_logger_alerts"),
+            )
+            .unwrap(),
         );
+        for alert in state.sensor_alerts.recent() {
+            children.push(serde_json::to_value(UiText::new(&alert.describe()).size(12.0)).unwrap());
+        }
+    }
+    if !state.sensor_history.is_empty() {
+        children.push(serde_json::to_value(UiText::new(&t!("sensor_live_chart_section")).size(14.0)).unwrap());
+        let x_axis = t!("sensor_live_chart_x_axis");
+        for (tag, values) in state.sensor_history.series() {
+            let latest = values.back().copied().unwrap_or_default();
+            let label = format!("{tag}: {latest:.3}");
+            children.push(
+                serde_json::to_value(
+                    UiChart::new(vec![ChartSeries::new(&label, values.iter().copied().collect())])
+                        .axes(&x_axis, tag)
+                        .height_dp(96)
+                        .content_description(tag),
+                )
+                .unwrap(),
+            );
+        }
+    }
+    if let Some(err) = &state.last_error {
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
     }
     if let Some(path) = &state.last_sensor_log {
         children.push(
@@ -176,6 +607,105 @@ pub fn render_sensor_logger_screen(state: &AppState) -> Value {
         children.push(
             serde_json::to_value(UiButton::new(&t!("sensor_share_last_log_button"), "sensor_logger_share")).unwrap(),
         );
+        if state.sensor_selection.map(|s| s.gps).unwrap_or(false) {
+            for (label, format) in [
+                (t!("sensor_export_gpx_button"), "gpx"),
+                (t!("sensor_export_kml_button"), "kml"),
+            ] {
+                children.push(
+                    serde_json::to_value(
+                        UiButton::new(&label, "sensor_logger_export")
+                            .payload(json!({ "sensor_export_format": format })),
+                    )
+                    .unwrap(),
+                );
+            }
+        }
+    }
+    if let Some(latest) = state.sensor_segments.last() {
+        children.push(
+            serde_json::to_value(
+                UiText::new(&format!(
+                    "{}{} ({latest})",
+                    t!("sensor_segments_prefix"),
+                    state.sensor_segments.len()
+                ))
+                .size(12.0)
+                .content_description("sensor_logger_segments"),
+            )
+            .unwrap(),
+        );
+    }
+    if let Some(path) = &state.last_sensor_export {
+        children.push(
+            serde_json::to_value(
+                UiText::new(&format!("{}{}", t!("sensor_last_export_prefix"), path))
+                    .size(12.0)
+                    .content_description("sensor_logger_export_path"),
+            )
+            .unwrap(),
+        );
+    }
+    if let Some(summary) = &state.sensor_summary {
+        children.push(
+            serde_json::to_value(
+                UiText::new(&format!("{} ({})", t!("sensor_summary_section"), summary.files))
+                    .size(14.0)
+                    .content_description("sensor_logger_summary"),
+            )
+            .unwrap(),
+        );
+        if summary.paused_ms > 0 {
+            children.push(
+                serde_json::to_value(
+                    UiText::new(&format!(
+                        "{}{:.1} s",
+                        t!("sensor_summary_paused_prefix"),
+                        summary.paused_ms as f64 / 1000.0
+                    ))
+                    .size(12.0),
+                )
+                .unwrap(),
+            );
+        }
+        for rate in &summary.rates {
+            let hz = rate
+                .rate_hz
+                .map(|hz| format!("{hz:.2} Hz"))
+                .unwrap_or_else(|| "- Hz".into());
+            children.push(
+                serde_json::to_value(
+                    UiText::new(&format!(
+                        "{}: n={}, {hz}, {:.1} s",
+                        rate.tag, rate.samples, rate.duration_s
+                    ))
+                    .size(12.0),
+                )
+                .unwrap(),
+            );
+            for ch in summary.channels.iter().filter(|c| c.tag == rate.tag) {
+                children.push(
+                    serde_json::to_value(
+                        UiText::new(&format!(
+                            "  {}: [{:.3} … {:.3}] μ={:.3} σ={:.3}",
+                            ch.channel, ch.min, ch.max, ch.mean, ch.stddev
+                        ))
+                        .size(12.0),
+                    )
+                    .unwrap(),
+                );
+            }
+        }
+    }
+
+    children.push(serde_json::to_value(UiText::new(&t!("sensor_csv_columns_section")).size(14.0)).unwrap());
+    children.push(
+        serde_json::to_value(UiText::new("ts,sensor,x,y,z,extra1,extra2").size(12.0)).unwrap(),
+    );
+    for (tag, columns) in SENSOR_CSV_COLUMNS {
+        children.push(
+            serde_json::to_value(UiText::new(&format!("{tag}: {columns}")).size(12.0)).unwrap(),
+        );
     }
 
     maybe_push_back(&mut children, state);
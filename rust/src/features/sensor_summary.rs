@@ -0,0 +1,382 @@
+use crate::features::sensor_logger::{ndjson_line_to_csv, sensor_table_name, SENSOR_CSV_COLUMNS};
+use flate2::read::GzDecoder;
+use sqlite::{Connection, State};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+/// Value columns (`x,y,z,extra1,extra2`) summarised for each CSV tag.
+/// Tags without channels (gap markers) are skipped.
+fn channels(tag: &str) -> &'static [&'static str] {
+    match tag {
+        "ACCEL" | "GYRO" | "MAG" | "GRAVITY" | "LINACC" => &["x", "y", "z"],
+        "ROTVEC" => &["x", "y", "z", "w"],
+        "GPS" => &["lat", "lon", "accuracy"],
+        "BARO" => &["pressure"],
+        "LIGHT" => &["lux"],
+        "PROX" => &["distance"],
+        "STEPS" => &["steps"],
+        _ => &[],
+    }
+}
+
+/// Welford accumulator so multi-hour logs are summarised in one pass.
+#[derive(Debug, Clone, Copy, Default)]
+struct RunningStats {
+    count: u64,
+    min: f64,
+    max: f64,
+    mean: f64,
+    m2: f64,
+}
+
+impl RunningStats {
+    fn push(&mut self, value: f64) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    fn stddev(&self) -> f64 {
+        if self.count < 2 {
+            0.0
+        } else {
+            (self.m2 / self.count as f64).sqrt()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelSummary {
+    pub tag: String,
+    pub channel: &'static str,
+    pub count: u64,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensorRate {
+    pub tag: String,
+    pub samples: u64,
+    /// Time between the first and last sample, minus paused time.
+    pub duration_s: f64,
+    /// `None` when fewer than two samples were logged.
+    pub rate_hz: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensorLogSummary {
+    pub rates: Vec<SensorRate>,
+    pub channels: Vec<ChannelSummary>,
+    pub paused_ms: u64,
+    pub files: usize,
+}
+
+#[derive(Default)]
+struct TagAccumulator {
+    samples: u64,
+    first_ts: i64,
+    last_ts: i64,
+    stats: Vec<RunningStats>,
+}
+
+#[derive(Default)]
+struct SummaryBuilder {
+    // Kept in SENSOR_CSV_COLUMNS order for a stable display.
+    tags: Vec<(&'static str, TagAccumulator)>,
+    paused_ms: u64,
+}
+
+impl SummaryBuilder {
+    fn push(&mut self, ts: i64, tag: &str, values: [Option<f64>; 5]) {
+        if tag == "RESUME" {
+            if let Some(ms) = values[0].filter(|v| v.is_finite() && *v > 0.0) {
+                self.paused_ms += ms as u64;
+            }
+            return;
+        }
+        let names = channels(tag);
+        if names.is_empty() {
+            return;
+        }
+        let Some(known) = SENSOR_CSV_COLUMNS.iter().map(|(t, _)| *t).find(|t| *t == tag) else {
+            return;
+        };
+        let idx = match self.tags.iter().position(|(t, _)| *t == known) {
+            Some(idx) => idx,
+            None => {
+                self.tags.push((
+                    known,
+                    TagAccumulator {
+                        first_ts: ts,
+                        last_ts: ts,
+                        stats: vec![RunningStats::default(); names.len()],
+                        ..Default::default()
+                    },
+                ));
+                self.tags.len() - 1
+            }
+        };
+        let acc = &mut self.tags[idx].1;
+        acc.samples += 1;
+        acc.first_ts = acc.first_ts.min(ts);
+        acc.last_ts = acc.last_ts.max(ts);
+        for (stats, value) in acc.stats.iter_mut().zip(values) {
+            if let Some(v) = value.filter(|v| v.is_finite()) {
+                stats.push(v);
+            }
+        }
+    }
+
+    fn push_csv_line(&mut self, line: &str) {
+        if line.trim_start().starts_with('{') {
+            if let Some(row) = ndjson_line_to_csv(line) {
+                self.push_csv_line(&row);
+            }
+            return;
+        }
+        let mut cols = line.split(',').map(str::trim);
+        let (Some(ts), Some(tag)) = (cols.next(), cols.next()) else {
+            return;
+        };
+        let Ok(ts) = ts.parse::<i64>() else {
+            return;
+        };
+        let mut values = [None; 5];
+        for (slot, raw) in values.iter_mut().zip(cols) {
+            *slot = raw.parse::<f64>().ok();
+        }
+        self.push(ts, tag, values);
+    }
+
+    fn finish(mut self, files: usize) -> Result<SensorLogSummary, String> {
+        if self.tags.is_empty() {
+            return Err("sensor_summary_no_samples".into());
+        }
+        let order = |tag: &str| SENSOR_CSV_COLUMNS.iter().position(|(t, _)| *t == tag);
+        self.tags.sort_by_key(|(tag, _)| order(tag));
+        let mut rates = Vec::new();
+        let mut channel_summaries = Vec::new();
+        for (tag, acc) in self.tags {
+            let span_ms = (acc.last_ts - acc.first_ts).max(0) as u64;
+            let duration_s = span_ms.saturating_sub(self.paused_ms) as f64 / 1000.0;
+            let rate_hz = (acc.samples > 1 && duration_s > 0.0)
+                .then(|| (acc.samples - 1) as f64 / duration_s);
+            rates.push(SensorRate {
+                tag: tag.to_string(),
+                samples: acc.samples,
+                duration_s,
+                rate_hz,
+            });
+            for (&channel, stats) in channels(tag).iter().zip(&acc.stats) {
+                if stats.count == 0 {
+                    continue;
+                }
+                channel_summaries.push(ChannelSummary {
+                    tag: tag.to_string(),
+                    channel,
+                    count: stats.count,
+                    min: stats.min,
+                    max: stats.max,
+                    mean: stats.mean,
+                    stddev: stats.stddev(),
+                });
+            }
+        }
+        Ok(SensorLogSummary {
+            rates,
+            channels: channel_summaries,
+            paused_ms: self.paused_ms,
+            files,
+        })
+    }
+}
+
+/// Summarises sensor log CSV text (`ts,sensor,x,y,z,extra1,extra2`); JSON Lines
+/// rows are accepted too.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn summarize_csv(csv: &str) -> Result<SensorLogSummary, String> {
+    let mut builder = SummaryBuilder::default();
+    for line in csv.lines() {
+        builder.push_csv_line(line);
+    }
+    builder.finish(1)
+}
+
+fn read_csv_lines<R: Read>(reader: R, builder: &mut SummaryBuilder) -> Result<(), String> {
+    for line in BufReader::new(reader).lines() {
+        let line = line.map_err(|e| format!("sensor_summary_read_failed:{e}"))?;
+        builder.push_csv_line(&line);
+    }
+    Ok(())
+}
+
+fn read_db(path: &str, builder: &mut SummaryBuilder) -> Result<(), String> {
+    let conn = Connection::open(path).map_err(|e| format!("sensor_summary_read_failed:{e}"))?;
+    for (tag, _) in SENSOR_CSV_COLUMNS {
+        let Some(table) = sensor_table_name(tag) else {
+            continue;
+        };
+        // Sensors that were not selected have no table.
+        let Ok(mut stmt) = conn.prepare(format!(
+            "SELECT ts, x, y, z, extra1, extra2 FROM {table} ORDER BY ts"
+        )) else {
+            continue;
+        };
+        while let State::Row = stmt
+            .next()
+            .map_err(|e| format!("sensor_summary_read_failed:{e}"))?
+        {
+            let Ok(ts) = stmt.read::<i64, usize>(0) else {
+                continue;
+            };
+            let mut values = [None; 5];
+            for (idx, slot) in values.iter_mut().enumerate() {
+                *slot = stmt.read::<Option<f64>, usize>(idx + 1).ok().flatten();
+            }
+            builder.push(ts, tag, values);
+        }
+    }
+    Ok(())
+}
+
+/// Summarises every file of a recording: CSV or JSON Lines (plain or gzipped
+/// segments), or SQLite `.db` logs. Stats are pooled across files.
+pub fn summarize_logs(paths: &[String]) -> Result<SensorLogSummary, String> {
+    if paths.is_empty() {
+        return Err("sensor_summary_no_log".into());
+    }
+    let mut builder = SummaryBuilder::default();
+    for path in paths {
+        // A segment queued for gzip before the summary is only found compressed.
+        let gz = format!("{path}.gz");
+        let path = if !Path::new(path).exists() && Path::new(&gz).is_file() {
+            &gz
+        } else {
+            path
+        };
+        let ext = Path::new(path)
+            .extension()
+            .map(|e| e.to_string_lossy().to_ascii_lowercase());
+        match ext.as_deref() {
+            Some("db") => {
+                if !Path::new(path).is_file() {
+                    return Err("sensor_summary_read_failed:missing database".into());
+                }
+                read_db(path, &mut builder)?
+            }
+            Some("gz") => {
+                let file = File::open(path).map_err(|e| format!("sensor_summary_read_failed:{e}"))?;
+                read_csv_lines(GzDecoder::new(file), &mut builder)?
+            }
+            _ => {
+                let file = File::open(path).map_err(|e| format!("sensor_summary_read_failed:{e}"))?;
+                read_csv_lines(file, &mut builder)?
+            }
+        }
+    }
+    builder.finish(paths.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    const LOG: &str = "ts,sensor,x,y,z,extra1,extra2\n\
+        1000,ACCEL,1,0,9,0,0\n\
+        1500,BARO,1013.0,0,0,0,0\n\
+        2000,ACCEL,3,0,9,0,0\n\
+        2100,PAUSE,0,0,0,0,0\n\
+        4100,RESUME,1000,0,0,0,0\n\
+        4000,ACCEL,5,0,9,0,0\n";
+
+    #[test]
+    fn computes_channel_stats_and_rates() {
+        let summary = summarize_csv(LOG).unwrap();
+        assert_eq!(summary.paused_ms, 1000);
+        assert_eq!(summary.rates[0].tag, "ACCEL");
+        assert_eq!(summary.rates[0].samples, 3);
+        // 3 s span minus 1 s paused, 2 intervals.
+        assert!((summary.rates[0].duration_s - 2.0).abs() < 1e-9);
+        assert_eq!(summary.rates[0].rate_hz, Some(1.0));
+        assert_eq!(summary.rates[1].tag, "BARO");
+        assert_eq!(summary.rates[1].rate_hz, None);
+
+        let x = &summary.channels[0];
+        assert_eq!((x.tag.as_str(), x.channel), ("ACCEL", "x"));
+        assert_eq!((x.min, x.max, x.mean), (1.0, 5.0, 3.0));
+        assert!((x.stddev - (8.0f64 / 3.0).sqrt()).abs() < 1e-9);
+        let z = &summary.channels[2];
+        assert_eq!((z.channel, z.stddev), ("z", 0.0));
+    }
+
+    #[test]
+    fn accepts_json_lines_rows() {
+        let summary = summarize_csv(
+            "{\"ts\":0,\"sensor\":\"GYRO\",\"x\":1,\"y\":2,\"z\":null}\n\
+             {\"ts\":100,\"sensor\":\"GYRO\",\"x\":3,\"y\":2,\"z\":null}\n",
+        )
+        .unwrap();
+        assert_eq!(summary.rates[0].rate_hz, Some(10.0));
+        assert_eq!(summary.channels.len(), 2);
+        assert_eq!(summary.channels[0].mean, 2.0);
+    }
+
+    #[test]
+    fn rejects_logs_without_samples() {
+        let err = summarize_csv("ts,sensor,x,y,z,extra1,extra2\n1,PAUSE,0,0,0,0,0\n").unwrap_err();
+        assert_eq!(err, "sensor_summary_no_samples");
+        assert_eq!(summarize_logs(&[]).unwrap_err(), "sensor_summary_no_log");
+    }
+
+    #[test]
+    fn pools_plain_and_gzipped_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("sensors_1_001.csv.gz");
+        let mut enc = GzEncoder::new(File::create(&first).unwrap(), Compression::default());
+        enc.write_all(b"ts,sensor,x,y,z,extra1,extra2\n0,LIGHT,10,0,0,0,0\n")
+            .unwrap();
+        enc.finish().unwrap();
+        let second = dir.path().join("sensors_1_002.csv");
+        std::fs::write(&second, "1000,LIGHT,30,0,0,0,0\n").unwrap();
+
+        let paths = [first, second].map(|p| p.to_string_lossy().into_owned());
+        let summary = summarize_logs(&paths).unwrap();
+        assert_eq!(summary.files, 2);
+        assert_eq!(summary.rates[0].rate_hz, Some(1.0));
+        assert_eq!(summary.channels[0].mean, 20.0);
+    }
+
+    #[test]
+    fn reads_sqlite_tables() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("sensors_3.db");
+        let conn = Connection::open(&db).unwrap();
+        conn.execute(
+            "CREATE TABLE steps (ts INTEGER NOT NULL, x REAL, y REAL, z REAL, extra1 REAL, extra2 REAL);
+             INSERT INTO steps VALUES (0, 100, 0, 0, 0, 0);
+             INSERT INTO steps VALUES (2000, 104, 0, 0, 0, 0);",
+        )
+        .unwrap();
+        drop(conn);
+
+        let summary = summarize_logs(&[db.to_string_lossy().into_owned()]).unwrap();
+        assert_eq!(summary.rates[0].tag, "STEPS");
+        assert_eq!(summary.rates[0].rate_hz, Some(0.5));
+        assert_eq!(summary.channels[0].max, 104.0);
+    }
+}
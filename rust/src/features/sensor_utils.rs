@@ -1,5 +1,107 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::f64::consts::TAU;
 
+/// User calibration applied to raw readings before they reach the low-pass filters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SensorCalibration {
+    /// Magnetic declination (east positive), turning magnetic into true north.
+    #[serde(default)]
+    pub compass_declination_deg: f64,
+    /// Mounting offset added on top of the declination.
+    #[serde(default)]
+    pub compass_offset_deg: f64,
+    #[serde(default)]
+    pub barometer_offset_hpa: f64,
+    #[serde(default)]
+    pub magnetometer_offset_ut: f64,
+}
+
+impl SensorCalibration {
+    pub fn heading(self, radians: f64) -> f64 {
+        radians + (self.compass_declination_deg + self.compass_offset_deg).to_radians()
+    }
+
+    pub fn pressure(self, hpa: f64) -> f64 {
+        hpa + self.barometer_offset_hpa
+    }
+
+    pub fn magnetic(self, ut: f64) -> f64 {
+        ut + self.magnetometer_offset_ut
+    }
+}
+
+fn parse_offset(
+    bindings: &HashMap<String, String>,
+    key: &str,
+    limit: f64,
+    current: f64,
+) -> Result<f64, String> {
+    match bindings.get(key).map(|v| v.trim()) {
+        None => Ok(current),
+        Some("") => Ok(0.0),
+        Some(raw) => raw
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite() && v.abs() <= limit)
+            .ok_or_else(|| format!("calibration_out_of_range:{key}")),
+    }
+}
+
+/// Updates the offsets present in `bindings`; absent keys keep their value and
+/// empty ones reset to zero. `raw_hpa` is the latest unfiltered barometer reading,
+/// used to zero the barometer to a known station pressure (`barometer_station_hpa`).
+pub fn parse_calibration(
+    current: SensorCalibration,
+    bindings: &HashMap<String, String>,
+    raw_hpa: Option<f64>,
+) -> Result<SensorCalibration, String> {
+    let mut next = SensorCalibration {
+        compass_declination_deg: parse_offset(
+            bindings,
+            "compass_declination_deg",
+            180.0,
+            current.compass_declination_deg,
+        )?,
+        compass_offset_deg: parse_offset(
+            bindings,
+            "compass_offset_deg",
+            180.0,
+            current.compass_offset_deg,
+        )?,
+        barometer_offset_hpa: parse_offset(
+            bindings,
+            "barometer_offset_hpa",
+            100.0,
+            current.barometer_offset_hpa,
+        )?,
+        magnetometer_offset_ut: parse_offset(
+            bindings,
+            "magnetometer_offset_ut",
+            1000.0,
+            current.magnetometer_offset_ut,
+        )?,
+    };
+    if let Some(raw) = bindings
+        .get("barometer_station_hpa")
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+    {
+        let station = raw
+            .parse::<f64>()
+            .ok()
+            .filter(|v| (300.0..=1100.0).contains(v))
+            .ok_or_else(|| "calibration_out_of_range:barometer_station_hpa".to_string())?;
+        let reading = raw_hpa.ok_or_else(|| "barometer_no_reading".to_string())?;
+        let offset = station - reading;
+        if offset.abs() > 100.0 {
+            return Err("calibration_out_of_range:barometer_station_hpa".into());
+        }
+        next.barometer_offset_hpa = offset;
+    }
+    Ok(next)
+}
+
 /// Low-pass filter for scalar values.
 /// Returns `None` when the sample is non-finite.
 pub fn low_pass_scalar(previous: Option<f64>, sample: f64, alpha: f64) -> Option<f64> {
@@ -93,4 +195,34 @@ mod tests {
     fn invalid_angle_sample_returns_none() {
         assert!(low_pass_angle(Some(1.0), f64::INFINITY, 0.2).is_none());
     }
+
+    #[test]
+    fn calibration_keeps_absent_offsets_and_zeroes_to_station() {
+        let current = SensorCalibration {
+            compass_declination_deg: 5.0,
+            magnetometer_offset_ut: 2.0,
+            ..Default::default()
+        };
+        let bindings = HashMap::from([
+            ("compass_offset_deg".to_string(), "-1.5".to_string()),
+            ("magnetometer_offset_ut".to_string(), "".to_string()),
+            ("barometer_station_hpa".to_string(), "1013.25".to_string()),
+        ]);
+        let next = parse_calibration(current, &bindings, Some(1010.0)).unwrap();
+        assert_eq!(next.compass_declination_deg, 5.0);
+        assert_eq!(next.compass_offset_deg, -1.5);
+        assert_eq!(next.magnetometer_offset_ut, 0.0);
+        assert!((next.barometer_offset_hpa - 3.25).abs() < 1e-9);
+        assert!((next.heading(0.0) - 3.5f64.to_radians()).abs() < 1e-12);
+
+        assert_eq!(
+            parse_calibration(current, &bindings, None).unwrap_err(),
+            "barometer_no_reading"
+        );
+        let bad = HashMap::from([("compass_declination_deg".to_string(), "200".to_string())]);
+        assert_eq!(
+            parse_calibration(current, &bad, None).unwrap_err(),
+            "calibration_out_of_range:compass_declination_deg"
+        );
+    }
 }
@@ -530,20 +530,20 @@ pub fn render_sql_screen(state: &AppState) -> Value {
         "children": [
             {
                 "type": "Section",
-                "title": "SQL Query Lab",
-                "subtitle": "Import CSV/JSON files and run SQL queries",
+                "title": t!("sql_engine_title"),
+                "subtitle": t!("sql_engine_subtitle"),
                 "children": [
                     {
                         "type": "Text",
-                        "text": "Import data files to create tables:",
+                        "text": t!("sql_engine_import_label"),
                         "size": 16
                     },
                     {
                         "type": "Button",
-                        "text": "Import CSV/JSON File",
+                        "text": t!("sql_engine_import_button"),
                         "action": "sql_import",
                         "requires_file_picker": true,
-                        "content_description": "Import CSV or JSON file for SQL querying"
+                        "content_description": t!("sql_engine_import_content_description")
                     },
                     tables_list,
                     {
@@ -552,20 +552,20 @@ pub fn render_sql_screen(state: &AppState) -> Value {
                         "text": "",
                         "hint": "SELECT * FROM users WHERE age > 30",
                         "max_lines": 8,
-                        "content_description": "SQL query editor",
+                        "content_description": t!("sql_engine_query_content_description"),
                         "debounce_ms": 300
                     },
                     {
                         "type": "Button",
-                        "text": "Execute Query",
+                        "text": t!("sql_engine_execute_button"),
                         "action": "sql_execute",
-                        "content_description": "Execute SQL query"
+                        "content_description": t!("sql_engine_execute_content_description")
                     },
                     {
                         "type": "Button",
-                        "text": "Clear All Tables",
+                        "text": t!("sql_engine_clear_button"),
                         "action": "sql_clear_all",
-                        "content_description": "Clear all imported tables"
+                        "content_description": t!("sql_engine_clear_content_description")
                     }
                 ]
             }
@@ -577,9 +577,9 @@ fn render_tables_list(tables: &[TableInfo]) -> Value {
     if tables.is_empty() {
         return json!({
             "type": "Text",
-            "text": "No tables imported yet.",
+            "text": t!("sql_engine_no_tables"),
             "size": 14,
-            "content_description": "No tables imported"
+            "content_description": t!("sql_engine_no_tables_content_description")
         });
     }
 
@@ -589,17 +589,22 @@ fn render_tables_list(tables: &[TableInfo]) -> Value {
             "children": [
                 {
                     "type": "Text",
-                    "text": format!("📄 {} ({} rows, {} columns)", table.name, table.row_count, table.column_count),
+                    "text": t!(
+                        "sql_engine_table_summary",
+                        name = table.name,
+                        rows = table.row_count,
+                        columns = table.column_count
+                    ),
                     "size": 16
                 },
                 {
                     "type": "Text",
-                    "text": format!("Source: {}", table.source_path),
+                    "text": t!("sql_engine_table_source", path = table.source_path),
                     "size": 12
                 },
                 {
                     "type": "Text",
-                    "text": format!("Columns: {}", table.column_names.join(", ")),
+                    "text": t!("sql_engine_table_columns", columns = table.column_names.join(", ")),
                     "size": 12
                 }
             ]
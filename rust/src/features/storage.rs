@@ -1,4 +1,6 @@
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[cfg(test)]
 use std::sync::{Mutex, OnceLock};
@@ -74,3 +76,209 @@ pub fn output_dir_for(source_uri: Option<&str>) -> PathBuf {
     }
     preferred_temp_dir()
 }
+
+pub const RECENT_FILES_MAX: usize = 20;
+
+/// A file picked through SAF, remembered so tools can reopen it without the picker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentFile {
+    pub name: String,
+    pub uri: String,
+    /// Action the file was picked for, e.g. `file_info` or `hex_editor_open`.
+    pub tool: String,
+    pub opened_at: u64,
+}
+
+impl RecentFile {
+    pub fn new(name: &str, uri: &str, tool: &str) -> Self {
+        let opened_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self {
+            name: name.to_string(),
+            uri: uri.to_string(),
+            tool: tool.to_string(),
+            opened_at,
+        }
+    }
+}
+
+pub fn recent_files_path() -> PathBuf {
+    // Next to the cache dir rather than inside it, so clearing the cache keeps the list.
+    let temp = preferred_temp_dir();
+    temp.parent().unwrap_or(&temp).join("recent_files.json")
+}
+
+/// Moves `entry` to the front, dropping an older entry for the same file and tool.
+pub fn remember_recent_file(list: &mut Vec<RecentFile>, entry: RecentFile) {
+    list.retain(|r| !(r.uri == entry.uri && r.tool == entry.tool));
+    list.insert(0, entry);
+    list.truncate(RECENT_FILES_MAX);
+}
+
+pub fn load_recent_files() -> Result<Vec<RecentFile>, String> {
+    let path = recent_files_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("read_failed:{e}"))?;
+    serde_json::from_str(&content).map_err(|e| format!("json_err:{e}"))
+}
+
+pub fn save_recent_files(list: &[RecentFile]) -> Result<(), String> {
+    let path = recent_files_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("mkdir_failed:{e}"))?;
+    }
+    let content = serde_json::to_string(list).map_err(|e| format!("json_err:{e}"))?;
+    std::fs::write(&path, content).map_err(|e| format!("write_failed:{e}"))
+}
+
+/// Tools pinned to the home quick access row until the user picks their own.
+pub const DEFAULT_FAVORITE_TOOLS: &[&str] =
+    &["pdf_tools", "text_tools", "text_viewer", "hash_sha256"];
+
+pub fn favorite_tools_path() -> PathBuf {
+    let temp = preferred_temp_dir();
+    temp.parent().unwrap_or(&temp).join("favorite_tools.json")
+}
+
+/// Pins `id` at the end of the list, or unpins it if it is already there.
+pub fn toggle_favorite(list: &mut Vec<String>, id: &str) {
+    if let Some(pos) = list.iter().position(|f| f == id) {
+        list.remove(pos);
+    } else {
+        list.push(id.to_string());
+    }
+}
+
+/// Moves a pinned tool `delta` places, clamped to the ends of the list.
+pub fn move_favorite(list: &mut Vec<String>, id: &str, delta: i32) {
+    let Some(pos) = list.iter().position(|f| f == id) else {
+        return;
+    };
+    let target = (pos as i64 + delta as i64).clamp(0, list.len() as i64 - 1) as usize;
+    let entry = list.remove(pos);
+    list.insert(target, entry);
+}
+
+/// `None` until the user has changed their favorites at least once.
+pub fn load_favorite_tools() -> Result<Option<Vec<String>>, String> {
+    let path = favorite_tools_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("read_failed:{e}"))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("json_err:{e}"))
+}
+
+pub fn save_favorite_tools(list: &[String]) -> Result<(), String> {
+    let path = favorite_tools_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("mkdir_failed:{e}"))?;
+    }
+    let content = serde_json::to_string(list).map_err(|e| format!("json_err:{e}"))?;
+    std::fs::write(&path, content).map_err(|e| format!("write_failed:{e}"))
+}
+
+pub fn app_state_path() -> PathBuf {
+    // Like the recent files, kept out of the cache dir that cleanup empties.
+    let temp = preferred_temp_dir();
+    temp.parent().unwrap_or(&temp).join("app_state.json")
+}
+
+/// Where installed plugins live, next to the saved state so cleanup keeps them.
+pub fn plugins_dir() -> PathBuf {
+    let temp = preferred_temp_dir();
+    temp.parent().unwrap_or(&temp).join("plugins")
+}
+
+pub fn load_app_state() -> Result<Option<String>, String> {
+    let path = app_state_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    std::fs::read_to_string(&path)
+        .map(Some)
+        .map_err(|e| format!("read_failed:{e}"))
+}
+
+/// Writes through a temporary file so a kill mid-write never leaves a truncated state.
+pub fn save_app_state(content: &str) -> Result<(), String> {
+    let path = app_state_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("mkdir_failed:{e}"))?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, content).map_err(|e| format!("write_failed:{e}"))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("write_failed:{e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn favorites_toggle_and_reorder_within_bounds() {
+        let mut list: Vec<String> = DEFAULT_FAVORITE_TOOLS
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        toggle_favorite(&mut list, "text_tools");
+        assert_eq!(list, vec!["pdf_tools", "text_viewer", "hash_sha256"]);
+        toggle_favorite(&mut list, "text_tools");
+        assert_eq!(list.last().map(String::as_str), Some("text_tools"));
+
+        move_favorite(&mut list, "text_tools", -1);
+        assert_eq!(
+            list,
+            vec!["pdf_tools", "text_viewer", "text_tools", "hash_sha256"]
+        );
+        move_favorite(&mut list, "text_tools", -10);
+        assert_eq!(list[0], "text_tools");
+        move_favorite(&mut list, "text_tools", 10);
+        assert_eq!(list[3], "text_tools");
+        move_favorite(&mut list, "missing", 1);
+        assert_eq!(list.len(), 4);
+    }
+
+    #[test]
+    fn remembering_a_file_moves_it_to_the_front_and_caps_the_list() {
+        let mut list = Vec::new();
+        for i in 0..RECENT_FILES_MAX + 5 {
+            remember_recent_file(
+                &mut list,
+                RecentFile::new(&format!("f{i}"), &format!("content://f{i}"), "file_info"),
+            );
+        }
+        assert_eq!(list.len(), RECENT_FILES_MAX);
+        assert_eq!(list[0].name, format!("f{}", RECENT_FILES_MAX + 4));
+
+        remember_recent_file(&mut list, RecentFile::new("f10", "content://f10", "file_info"));
+        remember_recent_file(&mut list, RecentFile::new("f10", "content://f10", "hex_editor_open"));
+        assert_eq!(list.len(), RECENT_FILES_MAX);
+        assert_eq!(list[0].tool, "hex_editor_open");
+        assert_eq!(list[1].tool, "file_info");
+        assert_eq!(list.iter().filter(|r| r.uri == "content://f10").count(), 2);
+    }
+
+    #[test]
+    fn recent_files_round_trip_through_disk() {
+        let _guard = test_env_lock().lock().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("cache");
+        std::fs::create_dir_all(&cache).unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", &cache);
+
+        assert!(load_recent_files().unwrap().is_empty());
+        let list = vec![RecentFile::new("a.pdf", "content://a", "pdf_select")];
+        save_recent_files(&list).unwrap();
+        assert_eq!(recent_files_path(), dir.path().join("recent_files.json"));
+        assert_eq!(load_recent_files().unwrap(), list);
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
+}
@@ -450,7 +450,7 @@ unsafe extern "C" fn aaudio_error_callback(
             CStr::from_ptr(ptr).to_string_lossy().into_owned()
         }
     };
-    eprintln!("AAudio stream error {}: {}", error, text);
+    crate::logging::error("synth", format!("AAudio stream error {error}: {text}"));
 }
 
 // Global parameters accessible by audio thread
@@ -549,7 +549,7 @@ pub fn render_synthesizer_screen(state: &AppState) -> Value {
     
     components.push(json!({
         "type": "Text",
-        "text": "Algorithmic Synthesizer",
+        "text": t!("synthesizer_title"),
         "size": 24.0,
         "bold": true,
         "margin_bottom": 16.0
@@ -557,7 +557,7 @@ pub fn render_synthesizer_screen(state: &AppState) -> Value {
 
     components.push(json!({
         "type": "Text",
-        "text": "Define 'double render(double t, double p1, double p2)'",
+        "text": t!("synthesizer_description"),
         "size": 12.0,
         "margin_bottom": 8.0
     }));
@@ -579,7 +579,7 @@ pub fn render_synthesizer_screen(state: &AppState) -> Value {
                 "type": "TextInput",
                 "bind_key": "synthesizer_p1",
                 "text": synth.param1,
-                "hint": "Param 1 (e.g. Freq)",
+                "hint": t!("synthesizer_param1_hint"),
                 "single_line": true,
                 "margin_right": 8.0
             },
@@ -587,7 +587,7 @@ pub fn render_synthesizer_screen(state: &AppState) -> Value {
                 "type": "TextInput",
                 "bind_key": "synthesizer_p2",
                 "text": synth.param2,
-                "hint": "Param 2 (e.g. Mod)",
+                "hint": t!("synthesizer_param2_hint"),
                 "single_line": true
             }
         ],
@@ -609,19 +609,19 @@ pub fn render_synthesizer_screen(state: &AppState) -> Value {
         "children": [
             {
                 "type": "Button",
-                "text": if synth.is_playing { "Stop" } else { "Play" },
+                "text": if synth.is_playing { t!("synthesizer_stop_button") } else { t!("synthesizer_play_button") },
                 "action": if synth.is_playing { "synthesizer_stop" } else { "synthesizer_play" },
                 "margin_right": 8.0
             },
             {
                 "type": "Button",
-                "text": "Apply / Compile",
+                "text": t!("synthesizer_apply_button"),
                 "action": "synthesizer_apply",
                 "margin_right": 8.0
             },
             {
                 "type": "Button",
-                "text": "Load Example",
+                "text": t!("synthesizer_load_example_button"),
                 "action": "synthesizer_example"
             }
         ]
@@ -1,3 +1,4 @@
+use crate::error::error_text;
 use crate::state::AppState;
 use crate::ui::{
     format_bytes, maybe_push_back, Card as UiCard, Column as UiColumn, Section as UiSection,
@@ -112,7 +113,7 @@ pub fn render_system_info_screen(state: &AppState) -> Value {
     if let Some(err) = &state.system_info.error {
         children.push(
             serde_json::to_value(
-                UiText::new(&format!("{}{}", t!("multi_hash_error_prefix"), err))
+                UiText::new(&error_text(err))
                     .size(12.0)
                     .content_description("system_info_error"),
             )
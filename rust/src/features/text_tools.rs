@@ -1,8 +1,9 @@
 use crate::state::{AppState, Screen};
 use crate::ui::{
-    Button as UiButton, Checkbox as UiCheckbox, Column as UiColumn, Grid as UiGrid, Text as UiText,
-    TextInput as UiTextInput,
+    Button as UiButton, Column as UiColumn, Grid as UiGrid, Switch as UiSwitch, Tabs as UiTabs,
+    Text as UiText, TextInput as UiTextInput,
 };
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use rust_i18n::t;
@@ -28,6 +29,35 @@ pub enum TextAction {
     Refresh,
 }
 
+/// Pane of the text tools screen; every action reports it so re-renders keep it selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextToolsTab {
+    #[default]
+    Transform,
+    Encode,
+    Analyze,
+}
+
+impl TextToolsTab {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim() {
+            "transform" => Some(TextToolsTab::Transform),
+            "encode" => Some(TextToolsTab::Encode),
+            "analyze" => Some(TextToolsTab::Analyze),
+            _ => None,
+        }
+    }
+
+    pub fn id(self) -> &'static str {
+        match self {
+            TextToolsTab::Transform => "transform",
+            TextToolsTab::Encode => "encode",
+            TextToolsTab::Analyze => "analyze",
+        }
+    }
+}
+
 /// Handle text tool actions by updating state based on the provided bindings.
 pub fn handle_text_action(
     state: &mut AppState,
@@ -41,6 +71,12 @@ pub fn handle_text_action(
     if let Some(flag) = parse_bool(bindings.get("aggressive_trim")) {
         state.text_aggressive_trim = flag;
     }
+    if let Some(tab) = bindings
+        .get("text_tools_tab")
+        .and_then(|t| TextToolsTab::parse(t))
+    {
+        state.text_tools_tab = tab;
+    }
 
     let input = state.text_input.clone().unwrap_or_default();
     state.replace_current(Screen::TextTools);
@@ -296,7 +332,7 @@ fn url_encode(input: &str) -> String {
     out
 }
 
-fn url_decode(input: &str) -> Result<String, String> {
+pub(crate) fn url_decode(input: &str) -> Result<String, String> {
     let bytes = input.as_bytes();
     let mut out = Vec::with_capacity(bytes.len());
     let mut i = 0;
@@ -331,7 +367,7 @@ fn hex_encode(bytes: &[u8]) -> String {
     out
 }
 
-fn hex_decode(input: &str) -> Result<Vec<u8>, String> {
+pub(crate) fn hex_decode(input: &str) -> Result<Vec<u8>, String> {
     let trimmed = input.trim();
     if trimmed.len() % 2 != 0 {
         return Err("invalid_hex_length".into());
@@ -361,51 +397,60 @@ pub fn render_text_tools_screen(state: &AppState) -> Value {
                 json!(UiTextInput::new("text_input")
                     .text(&input)
                     .hint(&t!("text_tools_input_hint"))
-                    .content_description("Input text for text tools")),
+                    .content_description(&t!("text_tools_input_content_description"))),
                 json!(UiButton::new(&t!("text_tools_clear_button"), "text_tools_clear").id("text_tools_clear_inline")),
             ])
             .columns(2)
             .padding(4),
         )
         .unwrap(),
-        serde_json::to_value(
-            UiColumn::new(vec![
-                serde_json::to_value(UiText::new(&t!("text_tools_transforms_section")).size(14.0)).unwrap(),
-                serde_json::to_value(UiButton::new(&t!("text_tools_uppercase"), "text_tools_upper")).unwrap(),
-                serde_json::to_value(UiButton::new(&t!("text_tools_lowercase"), "text_tools_lower")).unwrap(),
-                serde_json::to_value(UiButton::new(&t!("text_tools_title_case"), "text_tools_title")).unwrap(),
-            ])
-            .padding(8),
-        )
-        .unwrap(),
-        serde_json::to_value(
-            UiColumn::new(vec![
-                serde_json::to_value(UiText::new(&t!("text_tools_counts_cleanup_section")).size(14.0)).unwrap(),
+    ];
+
+    let transform_label = t!("text_tools_tab_transform");
+    let encode_label = t!("text_tools_tab_encode");
+    let analyze_label = t!("text_tools_tab_analyze");
+    let button =
+        |label: &str, action: &str| serde_json::to_value(UiButton::new(label, action)).unwrap();
+    let tabs = UiTabs::new("text_tools_tab", state.text_tools_tab.id())
+        .action("text_tools_refresh")
+        .tab(
+            "transform",
+            &transform_label,
+            vec![
+                button(&t!("text_tools_uppercase"), "text_tools_upper"),
+                button(&t!("text_tools_lowercase"), "text_tools_lower"),
+                button(&t!("text_tools_title_case"), "text_tools_title"),
                 serde_json::to_value(
-                    UiCheckbox::new(&t!("text_tools_aggressive_trim_checkbox"), "aggressive_trim")
+                    UiSwitch::new(&t!("text_tools_aggressive_trim_checkbox"), "aggressive_trim")
                         .checked(state.text_aggressive_trim)
                         .action("text_tools_refresh"),
                 )
                 .unwrap(),
-                serde_json::to_value(UiButton::new(&t!("text_tools_word_count"), "text_tools_word_count")).unwrap(),
-                serde_json::to_value(UiButton::new(&t!("text_tools_char_count"), "text_tools_char_count"))
-                    .unwrap(),
-                serde_json::to_value(UiButton::new(&t!("text_tools_trim_spacing"), "text_tools_trim")).unwrap(),
-                serde_json::to_value(UiButton::new(&t!("text_tools_wrap_72_cols"), "text_tools_wrap")).unwrap(),
-                serde_json::to_value(UiButton::new(&t!("text_tools_base64_encode"), "text_tools_base64_encode"))
-                    .unwrap(),
-                serde_json::to_value(UiButton::new(&t!("text_tools_base64_decode"), "text_tools_base64_decode"))
-                    .unwrap(),
-                serde_json::to_value(UiButton::new(&t!("text_tools_url_encode"), "text_tools_url_encode")).unwrap(),
-                serde_json::to_value(UiButton::new(&t!("text_tools_url_decode"), "text_tools_url_decode")).unwrap(),
-                serde_json::to_value(UiButton::new(&t!("text_tools_hex_encode"), "text_tools_hex_encode")).unwrap(),
-                serde_json::to_value(UiButton::new(&t!("text_tools_hex_decode"), "text_tools_hex_decode")).unwrap(),
-                serde_json::to_value(UiButton::new(&t!("text_tools_clear_button"), "text_tools_clear")).unwrap(),
-            ])
-            .padding(8),
+                button(&t!("text_tools_trim_spacing"), "text_tools_trim"),
+                button(&t!("text_tools_wrap_72_cols"), "text_tools_wrap"),
+            ],
         )
-        .unwrap(),
-    ];
+        .tab(
+            "encode",
+            &encode_label,
+            vec![
+                button(&t!("text_tools_base64_encode"), "text_tools_base64_encode"),
+                button(&t!("text_tools_base64_decode"), "text_tools_base64_decode"),
+                button(&t!("text_tools_url_encode"), "text_tools_url_encode"),
+                button(&t!("text_tools_url_decode"), "text_tools_url_decode"),
+                button(&t!("text_tools_hex_encode"), "text_tools_hex_encode"),
+                button(&t!("text_tools_hex_decode"), "text_tools_hex_decode"),
+            ],
+        )
+        .tab(
+            "analyze",
+            &analyze_label,
+            vec![
+                button(&t!("text_tools_word_count"), "text_tools_word_count"),
+                button(&t!("text_tools_char_count"), "text_tools_char_count"),
+            ],
+        );
+    children.push(serde_json::to_value(tabs).unwrap());
 
     if let Some(op) = &state.text_operation {
         children.push(
@@ -1,7 +1,8 @@
+use crate::error::error_text;
 use crate::state::AppState;
 use crate::ui::{
     format_bytes, maybe_push_back, Button as UiButton, CodeView as UiCodeView, Column as UiColumn,
-    Text as UiText,
+    RichText as UiRichText, Table as UiTable, TableColumn as UiTableColumn, Text as UiText,
 };
 use serde_json::{json, Value};
 use std::fs::File;
@@ -15,6 +16,7 @@ use rust_i18n::t;
 const MAX_BYTES: usize = 256 * 1024; // 256 KiB cap to avoid memory bloat for generic reads
 pub const CHUNK_BYTES: usize = 128 * 1024; // chunk size for incremental loads
 const HEX_PREVIEW_BYTES: usize = 4 * 1024; // cap for hex preview
+const CSV_PREVIEW_ROWS: usize = 50; // data rows shown in the table above the raw text
 
 pub fn read_text_from_reader<R: Read>(mut reader: R) -> Result<String, String> {
     let mut buf = Vec::new();
@@ -302,6 +304,7 @@ pub fn apply_text_view_result(state: &mut AppState, result: TextViewLoadResult)
     state.text_view_total_bytes = result.total_bytes;
     state.text_view_has_more = result.has_more;
     state.text_view_has_previous = result.has_previous;
+    state.text_view_wrap = true;
     if let Some(path) = result.path {
         state.text_view_path = Some(path.clone());
         if let Some(lang) = result.language {
@@ -372,6 +375,41 @@ fn temp_dirs() -> Vec<PathBuf> {
     dirs
 }
 
+/// Header and leading records of loaded CSV text. Records may be short, e.g. the one cut
+/// off at the end of the loaded window.
+fn csv_preview(content: &str) -> Option<(Vec<String>, Vec<Vec<String>>)> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let headers: Vec<String> = rdr.headers().ok()?.iter().map(str::to_string).collect();
+    if headers.is_empty() {
+        return None;
+    }
+    let rows = rdr
+        .records()
+        .map_while(Result::ok)
+        .take(CSV_PREVIEW_ROWS)
+        .map(|record| record.iter().map(str::to_string).collect())
+        .collect();
+    Some((headers, rows))
+}
+
+fn render_csv_table(headers: &[String], rows: &[Vec<String>]) -> Value {
+    let mut table = UiTable::new(headers.iter().map(|h| UiTableColumn::new(h)).collect())
+        .horizontal_scroll(true)
+        .max_height_dp(320)
+        .content_description("text_viewer_csv_preview");
+    for row in rows {
+        // Short records get blank cells so every row spans the header.
+        let cells = (0..headers.len())
+            .map(|i| json!(UiText::new(row.get(i).map(String::as_str).unwrap_or("")).size(12.0)))
+            .collect();
+        table = table.row(cells);
+    }
+    serde_json::to_value(table).unwrap()
+}
+
 pub fn render_text_viewer_screen(state: &AppState) -> Value {
     let mut children = vec![
         serde_json::to_value(UiText::new(&t!("text_viewer_title")).size(20.0)).unwrap(),
@@ -540,9 +578,7 @@ pub fn render_text_viewer_screen(state: &AppState) -> Value {
     );
 
     if let Some(err) = &state.text_view_error {
-        children.push(
-            serde_json::to_value(UiText::new(&format!("{}{}", t!("multi_hash_error_prefix"), err)).size(12.0)).unwrap(),
-        );
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
     }
 
     if let Some(lang) = state.text_view_language.as_deref() {
@@ -618,14 +654,48 @@ pub fn render_text_viewer_screen(state: &AppState) -> Value {
             "light"
         };
         let mut code = UiCodeView::new(content)
-            .wrap(true)
+            .wrap(state.text_view_wrap)
             .theme(theme)
             .line_numbers(state.text_view_line_numbers)
             .id("text_viewer_code");
         if let Some(lang_str) = lang.as_deref() {
             code = code.language(lang_str);
         }
-        children.push(serde_json::to_value(code).unwrap());
+        if lang.as_deref() == Some("csv") {
+            if let Some((headers, rows)) = csv_preview(content) {
+                children.push(
+                    serde_json::to_value(
+                        UiText::new(&t!("text_viewer_csv_preview_label")).size(14.0),
+                    )
+                    .unwrap(),
+                );
+                children.push(render_csv_table(&headers, &rows));
+            }
+        }
+        if lang.as_deref() == Some("markdown") {
+            let preview_label = if state.text_view_markdown_preview {
+                t!("text_viewer_show_markdown_source")
+            } else {
+                t!("text_viewer_show_markdown_preview")
+            };
+            children.push(
+                serde_json::to_value(
+                    UiButton::new(&preview_label, "text_viewer_toggle_markdown_preview")
+                        .content_description("text_viewer_toggle_markdown_preview"),
+                )
+                .unwrap(),
+            );
+        }
+        if lang.as_deref() == Some("markdown") && state.text_view_markdown_preview {
+            children.push(
+                serde_json::to_value(
+                    UiRichText::new(content).content_description("text_viewer_markdown_preview"),
+                )
+                .unwrap(),
+            );
+        } else {
+            children.push(serde_json::to_value(code).unwrap());
+        }
         children.push(
             serde_json::to_value(
                 UiButton::new(&t!("text_viewer_copy_visible_text_button"), "noop")
@@ -689,4 +759,23 @@ mod tests {
         });
         assert!(has_code_id, "expected CodeView to carry id");
     }
+
+    #[test]
+    fn csv_content_gets_a_table_preview() {
+        let (headers, rows) = csv_preview("name,size\na.txt,5\nb.txt\nc.txt,\"12").unwrap();
+        assert_eq!(headers, ["name", "size"]);
+        assert_eq!(rows, [vec!["a.txt", "5"], vec!["b.txt"], vec!["c.txt", "12"]]);
+
+        let mut state = AppState::new();
+        state.text_view_content = Some("name,size\na.txt,5\n".into());
+        state.text_view_language = Some("csv".into());
+        let ui = render_text_viewer_screen(&state);
+        let children = ui["children"].as_array().unwrap();
+        let table = children
+            .iter()
+            .find(|child| child["type"] == "Table")
+            .expect("csv preview table");
+        assert_eq!(table["columns"][1]["title"], "size");
+        assert_eq!(table["children"][0]["children"][0]["text"], "a.txt");
+    }
 }
@@ -0,0 +1,333 @@
+use base64::Engine;
+use flate2::read::ZlibDecoder;
+use image::codecs::jpeg::JpegDecoder;
+use image::codecs::png::PngEncoder;
+use image::io::{Limits, Reader as ImageReader};
+use image::{ColorType, DynamicImage, GrayImage, ImageEncoder, ImageFormat, RgbImage};
+use lopdf::{Dictionary, Document, Object, Stream};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Read, Seek, SeekFrom};
+
+pub const THUMBNAIL_MAX_EDGE: u32 = 160;
+const MAX_DECODE_ALLOC: u64 = 128 * 1024 * 1024;
+const MAX_PDF_BYTES: u64 = 64 * 1024 * 1024;
+const MAX_PDF_IMAGE_PIXELS: u64 = 40_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThumbnailSource {
+    Image,
+    /// The `/Thumb` image a PDF writer embedded for the first page.
+    PdfPageThumbnail,
+    /// The largest image drawn on the first page; PDFs are not rasterized here.
+    PdfPageImage,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Thumbnail {
+    pub png_base64: String,
+    pub width: u32,
+    pub height: u32,
+    pub source: ThumbnailSource,
+}
+
+/// Decodes an image and scales it to fit `THUMBNAIL_MAX_EDGE`, honouring the EXIF orientation.
+pub fn image_thumbnail<R: BufRead + Seek>(
+    reader: &mut R,
+    orientation: Option<u32>,
+) -> Result<Thumbnail, String> {
+    reader
+        .seek(SeekFrom::Start(0))
+        .map_err(|e| format!("thumbnail_seek_failed:{e}"))?;
+    let mut image_reader = ImageReader::new(reader)
+        .with_guessed_format()
+        .map_err(|e| format!("thumbnail_read_failed:{e}"))?;
+    let decoded = if image_reader.format() == Some(ImageFormat::Jpeg) {
+        let mut decoder = JpegDecoder::new(image_reader.into_inner())
+            .map_err(|e| format!("thumbnail_decode_failed:{e}"))?;
+        // DCT scaling decodes a camera photo at a fraction of its size instead of in full.
+        decoder
+            .scale(THUMBNAIL_MAX_EDGE as u16, THUMBNAIL_MAX_EDGE as u16)
+            .map_err(|e| format!("thumbnail_decode_failed:{e}"))?;
+        DynamicImage::from_decoder(decoder)
+    } else {
+        let mut limits = Limits::default();
+        limits.max_alloc = Some(MAX_DECODE_ALLOC);
+        image_reader.limits(limits);
+        image_reader.decode()
+    }
+    .map_err(|e| format!("thumbnail_decode_failed:{e}"))?;
+    encode_thumbnail(
+        apply_orientation(decoded, orientation),
+        ThumbnailSource::Image,
+    )
+}
+
+/// Uses the first page's embedded `/Thumb`, falling back to its largest image XObject.
+pub fn pdf_thumbnail<R: Read + Seek>(reader: &mut R, size: u64) -> Result<Thumbnail, String> {
+    if size > MAX_PDF_BYTES {
+        return Err("thumbnail_pdf_too_large".into());
+    }
+    reader
+        .seek(SeekFrom::Start(0))
+        .map_err(|e| format!("thumbnail_seek_failed:{e}"))?;
+    let mut bytes = Vec::with_capacity(size as usize);
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("thumbnail_read_failed:{e}"))?;
+    let doc = Document::load_mem(&bytes).map_err(|e| format!("thumbnail_pdf_parse_failed:{e}"))?;
+    let page_id = *doc
+        .get_pages()
+        .values()
+        .next()
+        .ok_or_else(|| "thumbnail_pdf_no_pages".to_string())?;
+    let page = doc
+        .get_dictionary(page_id)
+        .map_err(|e| format!("thumbnail_pdf_parse_failed:{e}"))?;
+
+    if let Some(image) = page
+        .get_deref(b"Thumb", &doc)
+        .and_then(Object::as_stream)
+        .ok()
+        .and_then(|stream| decode_pdf_image(stream, &doc))
+    {
+        return encode_thumbnail(image, ThumbnailSource::PdfPageThumbnail);
+    }
+
+    let (direct, inherited) = doc.get_page_resources(page_id);
+    let resources = direct.into_iter().chain(
+        inherited
+            .iter()
+            .filter_map(|id| doc.get_dictionary(*id).ok()),
+    );
+    let mut largest: Option<(u64, &Stream)> = None;
+    for res in resources {
+        let Ok(xobjects) = res.get_deref(b"XObject", &doc).and_then(Object::as_dict) else {
+            continue;
+        };
+        for (_, obj) in xobjects.iter() {
+            let Ok(stream) = doc.dereference(obj).and_then(|(_, o)| o.as_stream()) else {
+                continue;
+            };
+            if stream.dict.get(b"Subtype").and_then(Object::as_name).ok()
+                != Some(b"Image".as_slice())
+            {
+                continue;
+            }
+            let pixels = dimension(&stream.dict, b"Width") * dimension(&stream.dict, b"Height");
+            if largest.is_none_or(|(best, _)| pixels > best) {
+                largest = Some((pixels, stream));
+            }
+        }
+    }
+    largest
+        .and_then(|(_, stream)| decode_pdf_image(stream, &doc))
+        .ok_or_else(|| "thumbnail_pdf_no_preview".to_string())
+        .and_then(|image| encode_thumbnail(image, ThumbnailSource::PdfPageImage))
+}
+
+fn dimension(dict: &Dictionary, key: &[u8]) -> u64 {
+    dict.get(key)
+        .and_then(Object::as_i64)
+        .ok()
+        .and_then(|v| u64::try_from(v).ok())
+        .unwrap_or(0)
+}
+
+/// Handles JPEG images and 8-bit gray/RGB samples, raw or Flate-compressed without predictors.
+fn decode_pdf_image(stream: &Stream, doc: &Document) -> Option<DynamicImage> {
+    let filters = stream.filters().unwrap_or_default();
+    if filters.len() == 1 && filters[0] == "DCTDecode" {
+        return image::load_from_memory_with_format(&stream.content, ImageFormat::Jpeg).ok();
+    }
+
+    let dict = &stream.dict;
+    let width = dimension(dict, b"Width");
+    let height = dimension(dict, b"Height");
+    let bits = dict
+        .get(b"BitsPerComponent")
+        .and_then(Object::as_i64)
+        .unwrap_or(8);
+    if width == 0 || height == 0 || width * height > MAX_PDF_IMAGE_PIXELS || bits != 8 {
+        return None;
+    }
+    let components = color_components(dict.get_deref(b"ColorSpace", doc).ok()?, doc)?;
+    let expected = (width * height * components) as usize;
+
+    let samples = match filters.as_slice() {
+        [] => stream.content.clone(),
+        [flate] if flate == "FlateDecode" => {
+            let predictor = dict
+                .get_deref(b"DecodeParms", doc)
+                .and_then(Object::as_dict)
+                .and_then(|p| p.get(b"Predictor"))
+                .and_then(Object::as_i64)
+                .unwrap_or(1);
+            if predictor > 1 {
+                return None;
+            }
+            let mut out = Vec::with_capacity(expected);
+            ZlibDecoder::new(stream.content.as_slice())
+                .take(expected as u64)
+                .read_to_end(&mut out)
+                .ok()?;
+            out
+        }
+        _ => return None,
+    };
+    if samples.len() < expected {
+        return None;
+    }
+    let samples = samples[..expected].to_vec();
+    let (width, height) = (width as u32, height as u32);
+    match components {
+        1 => GrayImage::from_raw(width, height, samples).map(DynamicImage::ImageLuma8),
+        _ => RgbImage::from_raw(width, height, samples).map(DynamicImage::ImageRgb8),
+    }
+}
+
+fn color_components(space: &Object, doc: &Document) -> Option<u64> {
+    match space {
+        Object::Name(name) => match name.as_slice() {
+            b"DeviceGray" | b"CalGray" => Some(1),
+            b"DeviceRGB" | b"CalRGB" => Some(3),
+            _ => None,
+        },
+        Object::Array(items) => match items.first().and_then(|o| o.as_name().ok()) {
+            Some(b"ICCBased") => {
+                let profile = doc.dereference(items.get(1)?).ok()?.1.as_stream().ok()?;
+                match profile.dict.get(b"N").and_then(Object::as_i64).ok()? {
+                    1 => Some(1),
+                    3 => Some(3),
+                    _ => None,
+                }
+            }
+            Some(b"CalGray") => Some(1),
+            Some(b"CalRGB") => Some(3),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Rotates/flips a decoded image so it displays upright for an EXIF orientation tag.
+pub fn apply_orientation(image: DynamicImage, orientation: Option<u32>) -> DynamicImage {
+    match orientation {
+        Some(2) => image.fliph(),
+        Some(3) => image.rotate180(),
+        Some(4) => image.flipv(),
+        Some(5) => image.rotate90().fliph(),
+        Some(6) => image.rotate90(),
+        Some(7) => image.rotate270().fliph(),
+        Some(8) => image.rotate270(),
+        _ => image,
+    }
+}
+
+fn encode_thumbnail(image: DynamicImage, source: ThumbnailSource) -> Result<Thumbnail, String> {
+    // Never upscale: small icons stay pixel-exact.
+    let image = if image.width() > THUMBNAIL_MAX_EDGE || image.height() > THUMBNAIL_MAX_EDGE {
+        image.thumbnail(THUMBNAIL_MAX_EDGE, THUMBNAIL_MAX_EDGE)
+    } else {
+        image
+    };
+    let rgba = image.to_rgba8();
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png)
+        .write_image(&rgba, rgba.width(), rgba.height(), ColorType::Rgba8)
+        .map_err(|e| format!("thumbnail_png_failed:{e}"))?;
+    Ok(Thumbnail {
+        png_base64: base64::engine::general_purpose::STANDARD.encode(png),
+        width: rgba.width(),
+        height: rgba.height(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgb};
+    use lopdf::{dictionary, Object, Stream};
+    use std::io::Cursor;
+
+    fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+        let img: RgbImage = ImageBuffer::from_fn(width, height, |x, _| Rgb([x as u8, 0, 0]));
+        let mut buf = Vec::new();
+        PngEncoder::new(&mut buf)
+            .write_image(&img, width, height, ColorType::Rgb8)
+            .unwrap();
+        buf
+    }
+
+    #[test]
+    fn image_thumbnail_fits_the_edge_and_applies_orientation() {
+        let mut cursor = Cursor::new(png_bytes(640, 320));
+        let thumb = image_thumbnail(&mut cursor, None).unwrap();
+        assert_eq!((thumb.width, thumb.height), (160, 80));
+        assert_eq!(thumb.source, ThumbnailSource::Image);
+
+        let mut cursor = Cursor::new(png_bytes(640, 320));
+        let rotated = image_thumbnail(&mut cursor, Some(6)).unwrap();
+        assert_eq!((rotated.width, rotated.height), (80, 160));
+
+        let mut cursor = Cursor::new(png_bytes(16, 8));
+        let small = image_thumbnail(&mut cursor, None).unwrap();
+        assert_eq!((small.width, small.height), (16, 8));
+    }
+
+    #[test]
+    fn pdf_thumbnail_uses_the_largest_first_page_image() {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let small = doc.add_object(Stream::new(
+            dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Image",
+                "Width" => 2,
+                "Height" => 2,
+                "ColorSpace" => "DeviceGray",
+                "BitsPerComponent" => 8,
+            },
+            vec![0; 4],
+        ));
+        let large = doc.add_object(Stream::new(
+            dictionary! {
+                "Type" => "XObject",
+                "Subtype" => "Image",
+                "Width" => 4,
+                "Height" => 2,
+                "ColorSpace" => "DeviceRGB",
+                "BitsPerComponent" => 8,
+            },
+            vec![200; 24],
+        ));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "MediaBox" => vec![0.into(), 0.into(), 100.into(), 100.into()],
+            "Resources" => dictionary! {
+                "XObject" => dictionary! { "Im1" => small, "Im2" => large },
+            },
+        });
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![page_id.into()],
+                "Count" => 1,
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog_id);
+        let mut bytes = Vec::new();
+        doc.save_to(&mut bytes).unwrap();
+
+        let size = bytes.len() as u64;
+        let thumb = pdf_thumbnail(&mut Cursor::new(bytes), size).unwrap();
+        assert_eq!(thumb.source, ThumbnailSource::PdfPageImage);
+        assert_eq!((thumb.width, thumb.height), (4, 2));
+
+        assert!(pdf_thumbnail(&mut Cursor::new(b"%PDF-1.4 junk".to_vec()), 13).is_err());
+    }
+}
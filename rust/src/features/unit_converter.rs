@@ -29,10 +29,12 @@ pub fn render_unit_converter_screen(state: &AppState) -> Value {
 
     let mut cat_buttons = Vec::new();
     for cat in categories {
+        let key = format!("unit_converter_category_{}", cat.to_lowercase());
+        let name = t!(key.as_str());
         let label = if cat == current_cat {
-            format!("• {} •", cat)
+            format!("• {} •", name)
         } else {
-            cat.to_string()
+            name.into_owned()
         };
         cat_buttons.push(
             serde_json::to_value(UiButton::new(&label, "unit_converter_set_category").id(cat))
@@ -54,7 +56,9 @@ pub fn render_unit_converter_screen(state: &AppState) -> Value {
         &state.unit_converter.to_unit
     };
 
-    children.push(serde_json::to_value(UiText::new("From:").size(14.0)).unwrap());
+    children.push(
+        serde_json::to_value(UiText::new(&t!("unit_converter_from_label")).size(14.0)).unwrap(),
+    );
     let mut from_buttons = Vec::new();
     for unit in &units {
         let label = if unit == current_from {
@@ -74,7 +78,9 @@ pub fn render_unit_converter_screen(state: &AppState) -> Value {
     // For now, simple Column is safe but tall.
     children.push(serde_json::to_value(UiColumn::new(from_buttons).padding(4)).unwrap());
 
-    children.push(serde_json::to_value(UiText::new("To:").size(14.0)).unwrap());
+    children.push(
+        serde_json::to_value(UiText::new(&t!("unit_converter_to_label")).size(14.0)).unwrap(),
+    );
     let mut to_buttons = Vec::new();
     for unit in &units {
         let label = if unit == current_to {
@@ -96,7 +102,7 @@ pub fn render_unit_converter_screen(state: &AppState) -> Value {
     children.push(
         serde_json::to_value(
             UiTextInput::new("unit_input")
-                .hint("Enter value")
+                .hint(&t!("unit_converter_value_hint"))
                 .text(&state.unit_converter.input_value)
                 .single_line(true)
                 .debounce_ms(200)
@@ -105,17 +111,31 @@ pub fn render_unit_converter_screen(state: &AppState) -> Value {
         .unwrap(),
     );
 
-    children.push(serde_json::to_value(UiButton::new("Convert", "unit_converter_calculate")).unwrap());
+    children.push(
+        serde_json::to_value(UiButton::new(
+            &t!("unit_converter_convert_button"),
+            "unit_converter_calculate",
+        ))
+        .unwrap(),
+    );
 
     if !state.unit_converter.output_value.is_empty() {
         children.push(
             serde_json::to_value(
-                UiText::new(&format!("Result: {}", state.unit_converter.output_value)).size(16.0),
+                UiText::new(&t!(
+                    "unit_converter_result",
+                    value = state.unit_converter.output_value
+                ))
+                .size(16.0),
             )
             .unwrap(),
         );
         children.push(
-            serde_json::to_value(UiButton::new("Copy Result", "unit_converter_copy")).unwrap(),
+            serde_json::to_value(UiButton::new(
+                &t!("unit_converter_copy_button"),
+                "unit_converter_copy",
+            ))
+            .unwrap(),
         );
     }
 
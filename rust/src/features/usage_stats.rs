@@ -0,0 +1,118 @@
+use crate::state::{AppState, ToolUsage};
+use crate::ui::{
+    maybe_push_back, Button as UiButton, Card as UiCard, Checkbox as UiCheckbox,
+    Column as UiColumn, Text as UiText,
+};
+use serde_json::Value;
+
+fn format_duration(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60),
+    }
+}
+
+/// Tools with at least one recorded launch, most launched first.
+fn ranked(usage: &[ToolUsage]) -> Vec<&ToolUsage> {
+    let mut ranked: Vec<&ToolUsage> = usage.iter().filter(|u| u.launches > 0).collect();
+    ranked.sort_by(|a, b| {
+        b.launches
+            .cmp(&a.launches)
+            .then(b.total_secs.cmp(&a.total_secs))
+    });
+    ranked
+}
+
+/// `names` maps home catalog ids to display names; tools since removed are not listed.
+pub fn render_usage_stats_screen(state: &AppState, names: &[(&str, String)]) -> Value {
+    let mut children = vec![
+        serde_json::to_value(UiText::new(&t!("usage_stats_title")).size(20.0)).unwrap(),
+        serde_json::to_value(UiText::new(&t!("usage_stats_privacy_note")).size(12.0)).unwrap(),
+        serde_json::to_value(
+            UiCheckbox::new(&t!("usage_stats_record_checkbox"), "usage_stats_enabled")
+                .checked(state.usage_stats_enabled)
+                .action("usage_stats_toggle"),
+        )
+        .unwrap(),
+        serde_json::to_value(
+            UiCheckbox::new(&t!("usage_stats_sort_home_checkbox"), "home_sort_by_usage")
+                .checked(state.home_sort_by_usage)
+                .action("home_sort_by_usage_toggle"),
+        )
+        .unwrap(),
+    ];
+
+    let rows: Vec<Value> = ranked(&state.tool_usage)
+        .into_iter()
+        .filter_map(|usage| {
+            let name = &names.iter().find(|(id, _)| *id == usage.id)?.1;
+            let launches = if usage.launches == 1 {
+                t!("usage_stats_one_launch")
+            } else {
+                t!("usage_stats_launches", count = usage.launches)
+            };
+            let summary = format!("{launches} · {}", format_duration(usage.total_secs));
+            let card = UiCard::new(vec![
+                serde_json::to_value(UiText::new(&summary).size(14.0)).unwrap()
+            ])
+            .title(name)
+            .padding(12);
+            Some(serde_json::to_value(card).unwrap())
+        })
+        .collect();
+
+    if rows.is_empty() {
+        let hint = if state.usage_stats_enabled {
+            t!("usage_stats_empty")
+        } else {
+            t!("usage_stats_disabled_hint")
+        };
+        children.push(serde_json::to_value(UiText::new(&hint).size(14.0)).unwrap());
+    } else {
+        children.extend(rows);
+        children.push(
+            serde_json::to_value(UiButton::new(
+                &t!("usage_stats_clear_button"),
+                "usage_stats_clear",
+            ))
+            .unwrap(),
+        );
+    }
+
+    maybe_push_back(&mut children, state);
+    serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(id: &str, launches: u32, total_secs: u64) -> ToolUsage {
+        ToolUsage {
+            id: id.into(),
+            last_used: 0,
+            launches,
+            total_secs,
+        }
+    }
+
+    #[test]
+    fn ranks_by_launches_then_time_and_skips_unused() {
+        let all = [
+            usage("ruler", 2, 10),
+            usage("vault", 0, 0),
+            usage("jwt_decoder", 5, 1),
+            usage("logic_engine", 2, 90),
+        ];
+        let ids: Vec<&str> = ranked(&all).iter().map(|u| u.id.as_str()).collect();
+        assert_eq!(ids, ["jwt_decoder", "logic_engine", "ruler"]);
+    }
+
+    #[test]
+    fn durations_use_the_largest_sensible_unit() {
+        assert_eq!(format_duration(42), "42s");
+        assert_eq!(format_duration(125), "2m 05s");
+        assert_eq!(format_duration(7260), "2h 01m");
+    }
+}
@@ -1,21 +1,127 @@
-use crate::state::{AppState, StringCharset};
+use crate::error::error_text;
+use crate::state::{AppState, StringCharset, UuidField, UuidVersion};
+use chrono::{DateTime, SecondsFormat, Utc};
 use crate::ui::{
-    maybe_push_back, Button as UiButton, Column as UiColumn, Text as UiText,
-    TextInput as UiTextInput,
+    maybe_push_back, Button as UiButton, Checkbox as UiCheckbox, Column as UiColumn,
+    Text as UiText, TextInput as UiTextInput,
 };
-use rand::distributions::Alphanumeric;
+use base64::Engine;
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng};
 use serde_json::{json, Value};
-use uuid::Uuid;
+use uuid::{Uuid, Variant};
 use rust_i18n::t;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Crockford base32, as used by the ULID spec (no I, L, O, U).
+const ULID_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+pub const NANOID_DEFAULT_ALPHABET: &str =
+    "_-0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const NANOID_MAX_LENGTH: u32 = 256;
+const NANOID_MAX_ALPHABET: usize = 256;
+const RANDOM_BYTES_MAX: u32 = 1024;
+
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!#$%&*+-=?@^_~";
+const AMBIGUOUS: &str = "0O1lI|";
+const CONSONANTS: &str = "bcdfghjkmnprstvwz";
+const VOWELS: &str = "aeiou";
+
+/// Requirements applied by the random string / password generator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    pub charset: StringCharset,
+    pub min_digits: usize,
+    pub min_symbols: usize,
+    pub exclude_ambiguous: bool,
+    pub pronounceable: bool,
+}
+
+impl PasswordPolicy {
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub const fn new(charset: StringCharset) -> Self {
+        Self {
+            charset,
+            min_digits: 0,
+            min_symbols: 0,
+            exclude_ambiguous: false,
+            pronounceable: false,
+        }
+    }
+}
+
+/// Hands a generated value to the QR generator; "back" returns to this screen.
+fn show_as_qr_button(value: &str) -> Value {
+    serde_json::to_value(
+        UiButton::new(&t!("uuid_show_qr_button"), "qr_generate").payload(json!({ "qr_input": value })),
+    )
+    .unwrap()
+}
+
+/// Opens the password strength checker on a generated string.
+fn check_strength_button(value: &str) -> Value {
+    serde_json::to_value(
+        UiButton::new(
+            &t!("password_check_strength_button"),
+            "password_strength_check",
+        )
+        .payload(json!({ "password_input": value })),
+    )
+    .unwrap()
+}
 
 pub fn render_uuid_screen(state: &AppState) -> Value {
     let mut children = vec![
         serde_json::to_value(UiText::new(&t!("uuid_gen_title")).size(20.0)).unwrap(),
         serde_json::to_value(UiText::new(&t!("uuid_gen_description")).size(14.0))
             .unwrap(),
-        serde_json::to_value(UiButton::new(&t!("uuid_generate_v4_button"), "uuid_generate")).unwrap(),
+        serde_json::to_value(UiText::new(&t!("uuid_version_label")).size(14.0)).unwrap(),
+    ];
+
+    let versions = [
+        (UuidVersion::V1, "v1"),
+        (UuidVersion::V4, "v4"),
+        (UuidVersion::V5, "v5"),
+        (UuidVersion::V7, "v7"),
     ];
+    for (version, label) in versions {
+        let mut button =
+            UiButton::new(label, "uuid_generate").payload(json!({ "uuid_version": label }));
+        if version == state.uuid_generator.version {
+            button = button.content_description("selected");
+        }
+        children.push(serde_json::to_value(button).unwrap());
+    }
+
+    if state.uuid_generator.version == UuidVersion::V5 {
+        children.push(
+            serde_json::to_value(
+                UiTextInput::new("uuid_v5_namespace")
+                    .hint(&t!("uuid_v5_namespace_hint"))
+                    .text(&state.uuid_generator.v5_namespace)
+                    .single_line(true),
+            )
+            .unwrap(),
+        );
+        children.push(
+            serde_json::to_value(
+                UiTextInput::new("uuid_v5_name")
+                    .hint(&t!("uuid_v5_name_hint"))
+                    .text(&state.uuid_generator.v5_name)
+                    .single_line(true),
+            )
+            .unwrap(),
+        );
+    }
+
+    children.push(
+        serde_json::to_value(UiButton::new(&t!("uuid_generate_button"), "uuid_generate")).unwrap(),
+    );
+
+    if let Some(err) = &state.uuid_generator.error {
+        children.push(serde_json::to_value(UiText::new(&error_text(err)).size(12.0)).unwrap());
+    }
 
     if let Some(u) = &state.uuid_generator.last_uuid {
         children.push(
@@ -26,6 +132,118 @@ pub fn render_uuid_screen(state: &AppState) -> Value {
             serde_json::to_value(UiButton::new(&t!("uuid_copy_button"), "copy_clipboard").copy_text(u))
                 .unwrap(),
         );
+        children.push(show_as_qr_button(u));
+    }
+
+    children.push(serde_json::to_value(UiText::new(&t!("uuid_inspect_section")).size(16.0)).unwrap());
+    children.push(
+        serde_json::to_value(
+            UiTextInput::new("uuid_inspect_input")
+                .hint(&t!("uuid_inspect_hint"))
+                .text(&state.uuid_generator.inspect_input)
+                .single_line(true)
+                .action_on_submit("uuid_inspect"),
+        )
+        .unwrap(),
+    );
+    children.push(
+        serde_json::to_value(UiButton::new(&t!("uuid_inspect_button"), "uuid_inspect")).unwrap(),
+    );
+    for field in &state.uuid_generator.inspect_fields {
+        let value = match (&field.note, field.value.is_empty()) {
+            (Some(note), true) => t!(note.as_str()).into_owned(),
+            (Some(note), false) => format!("{} ({})", field.value, t!(note.as_str())),
+            (None, _) => field.value.clone(),
+        };
+        children.push(
+            serde_json::to_value(UiText::new(&format!("{}: {value}", t!(&field.label))).size(12.0))
+                .unwrap(),
+        );
+    }
+
+    children.push(serde_json::to_value(UiText::new(&t!("uuid_ulid_section")).size(16.0)).unwrap());
+    children.push(
+        serde_json::to_value(UiButton::new(&t!("uuid_generate_ulid_button"), "ulid_generate")).unwrap(),
+    );
+    if let Some(ulid) = &state.uuid_generator.last_ulid {
+        children.push(
+            serde_json::to_value(UiText::new(ulid).size(14.0).content_description("uuid_ulid"))
+                .unwrap(),
+        );
+        children.push(
+            serde_json::to_value(UiButton::new(&t!("uuid_copy_string_button"), "copy_clipboard").copy_text(ulid))
+                .unwrap(),
+        );
+        children.push(show_as_qr_button(ulid));
+    }
+
+    children.push(serde_json::to_value(UiText::new(&t!("uuid_nanoid_section")).size(16.0)).unwrap());
+    children.push(
+        serde_json::to_value(
+            UiTextInput::new("nanoid_alphabet")
+                .hint(&t!("uuid_nanoid_alphabet_hint"))
+                .text(&state.uuid_generator.nanoid_alphabet)
+                .single_line(true),
+        )
+        .unwrap(),
+    );
+    children.push(
+        serde_json::to_value(
+            UiTextInput::new("nanoid_length")
+                .hint(&t!("uuid_nanoid_length_hint"))
+                .text(&state.uuid_generator.nanoid_length.to_string())
+                .single_line(true),
+        )
+        .unwrap(),
+    );
+    children.push(
+        serde_json::to_value(UiButton::new(&t!("uuid_generate_nanoid_button"), "nanoid_generate")).unwrap(),
+    );
+    if let Some(id) = &state.uuid_generator.last_nanoid {
+        children.push(
+            serde_json::to_value(UiText::new(id).size(14.0).content_description("uuid_nanoid"))
+                .unwrap(),
+        );
+        children.push(
+            serde_json::to_value(UiButton::new(&t!("uuid_copy_string_button"), "copy_clipboard").copy_text(id))
+                .unwrap(),
+        );
+        children.push(show_as_qr_button(id));
+    }
+
+    children.push(serde_json::to_value(UiText::new(&t!("random_bytes_section")).size(16.0)).unwrap());
+    children.push(
+        serde_json::to_value(
+            UiTextInput::new("random_bytes_len")
+                .hint(&t!("random_bytes_length_hint"))
+                .text(&state.uuid_generator.random_bytes_len.to_string())
+                .single_line(true),
+        )
+        .unwrap(),
+    );
+    children.push(
+        serde_json::to_value(UiButton::new(&t!("random_bytes_generate_button"), "random_bytes_generate")).unwrap(),
+    );
+    children.push(
+        serde_json::to_value(UiText::new(&t!("random_bytes_source_note")).size(12.0)).unwrap(),
+    );
+    if let Some(bytes) = &state.uuid_generator.last_random_bytes {
+        let hex = encode_hex(bytes);
+        let b64 = base64::engine::general_purpose::STANDARD.encode(bytes);
+        for (text, description, copy_label) in [
+            (&hex, "random_bytes_hex", t!("random_bytes_copy_hex_button")),
+            (&b64, "random_bytes_base64", t!("random_bytes_copy_base64_button")),
+        ] {
+            children.push(
+                serde_json::to_value(UiText::new(text).size(14.0).content_description(description))
+                    .unwrap(),
+            );
+            children.push(
+                serde_json::to_value(UiButton::new(&copy_label, "copy_clipboard").copy_text(text))
+                    .unwrap(),
+            );
+            children.push(show_as_qr_button(text));
+        }
     }
 
     children.push(serde_json::to_value(UiText::new(&t!("uuid_gen_random_string_section")).size(16.0)).unwrap());
@@ -49,12 +267,48 @@ pub fn render_uuid_screen(state: &AppState) -> Value {
         children.push(json!({
             "type": "Button",
             "text": label,
-            "action": "random_string_charset",
+            "action": "random_string_generate",
             "content_description": if charset == state.uuid_generator.string_charset { Some("selected") } else { None::<&str> },
-            "payload": { "charset": label }
+            "payload": { "charset": charset_id(charset) }
         }));
     }
 
+    for (bind_key, hint, value) in [
+        ("password_min_digits", t!("password_min_digits_hint"), state.uuid_generator.min_digits),
+        ("password_min_symbols", t!("password_min_symbols_hint"), state.uuid_generator.min_symbols),
+    ] {
+        children.push(
+            serde_json::to_value(
+                UiTextInput::new(bind_key)
+                    .hint(&hint)
+                    .text(&value.to_string())
+                    .single_line(true),
+            )
+            .unwrap(),
+        );
+    }
+    for (bind_key, label, checked) in [
+        (
+            "password_exclude_ambiguous",
+            t!("password_exclude_ambiguous_label"),
+            state.uuid_generator.exclude_ambiguous,
+        ),
+        (
+            "password_pronounceable",
+            t!("password_pronounceable_label"),
+            state.uuid_generator.pronounceable,
+        ),
+    ] {
+        children.push(
+            serde_json::to_value(
+                UiCheckbox::new(&label, bind_key)
+                    .checked(checked)
+                    .action("random_string_generate"),
+            )
+            .unwrap(),
+        );
+    }
+
     children.push(
         serde_json::to_value(UiButton::new(&t!("uuid_generate_string_button"), "random_string_generate")).unwrap(),
     );
@@ -68,10 +322,22 @@ pub fn render_uuid_screen(state: &AppState) -> Value {
             )
             .unwrap(),
         );
+        if let Some(bits) = state.uuid_generator.last_entropy_bits {
+            children.push(
+                serde_json::to_value(
+                    UiText::new(&format!("{}: ~{bits:.0} bits", t!("password_entropy_label")))
+                        .size(12.0)
+                        .content_description("uuid_random_string_entropy"),
+                )
+                .unwrap(),
+            );
+        }
         children.push(
             serde_json::to_value(UiButton::new(&t!("uuid_copy_string_button"), "copy_clipboard").copy_text(s))
                 .unwrap(),
         );
+        children.push(show_as_qr_button(s));
+        children.push(check_strength_button(s));
     }
 
     maybe_push_back(&mut children, state);
@@ -85,12 +351,88 @@ pub fn handle_uuid_action(
 ) {
     match action {
         "uuid_generate" => {
-            state.uuid_generator.last_uuid = Some(generate_uuid());
+            if let Some(version) = bindings
+                .get("uuid_version")
+                .map(String::as_str)
+                .and_then(parse_version)
+            {
+                state.uuid_generator.version = version;
+            }
+            if let Some(namespace) = bindings.get("uuid_v5_namespace") {
+                state.uuid_generator.v5_namespace = namespace.trim().to_string();
+            }
+            if let Some(name) = bindings.get("uuid_v5_name") {
+                state.uuid_generator.v5_name = name.clone();
+            }
+            match generate_uuid_version(
+                state.uuid_generator.version,
+                &state.uuid_generator.v5_namespace,
+                &state.uuid_generator.v5_name,
+            ) {
+                Ok(u) => {
+                    state.uuid_generator.last_uuid = Some(u);
+                    state.uuid_generator.error = None;
+                }
+                Err(e) => {
+                    state.uuid_generator.last_uuid = None;
+                    state.uuid_generator.error = Some(e);
+                }
+            }
+        }
+        "uuid_inspect" => {
+            if let Some(input) = bindings.get("uuid_inspect_input") {
+                state.uuid_generator.inspect_input = input.trim().to_string();
+            }
+            match inspect_uuid(&state.uuid_generator.inspect_input) {
+                Ok(fields) => {
+                    state.uuid_generator.inspect_fields = fields;
+                    state.uuid_generator.error = None;
+                }
+                Err(e) => {
+                    state.uuid_generator.inspect_fields.clear();
+                    state.uuid_generator.error = Some(e);
+                }
+            }
+        }
+        "random_bytes_generate" => {
+            let len = bindings
+                .get("random_bytes_len")
+                .and_then(|v| v.trim().parse::<u32>().ok())
+                .unwrap_or(state.uuid_generator.random_bytes_len)
+                .clamp(1, RANDOM_BYTES_MAX);
+            state.uuid_generator.random_bytes_len = len;
+            match generate_random_bytes(len as usize) {
+                Ok(bytes) => {
+                    state.uuid_generator.last_random_bytes = Some(bytes);
+                    state.uuid_generator.error = None;
+                }
+                Err(e) => {
+                    state.uuid_generator.last_random_bytes = None;
+                    state.uuid_generator.error = Some(e);
+                }
+            }
+        }
+        "ulid_generate" => {
+            state.uuid_generator.last_ulid = Some(generate_ulid());
         }
-        "random_string_charset" => {
-            if let Some(label) = bindings.get("charset") {
-                if let Some(parsed) = parse_charset_label(label) {
-                    state.uuid_generator.string_charset = parsed;
+        "nanoid_generate" => {
+            if let Some(alphabet) = bindings.get("nanoid_alphabet") {
+                state.uuid_generator.nanoid_alphabet = alphabet.clone();
+            }
+            let len = bindings
+                .get("nanoid_length")
+                .and_then(|v| v.trim().parse::<u32>().ok())
+                .unwrap_or(state.uuid_generator.nanoid_length)
+                .clamp(1, NANOID_MAX_LENGTH);
+            state.uuid_generator.nanoid_length = len;
+            match generate_nanoid(&state.uuid_generator.nanoid_alphabet, len as usize) {
+                Ok(id) => {
+                    state.uuid_generator.last_nanoid = Some(id);
+                    state.uuid_generator.error = None;
+                }
+                Err(e) => {
+                    state.uuid_generator.last_nanoid = None;
+                    state.uuid_generator.error = Some(e);
                 }
             }
         }
@@ -102,50 +444,327 @@ pub fn handle_uuid_action(
                 .max(1)
                 .min(512);
             state.uuid_generator.string_length = len;
-            let s = generate_string(len as usize, state.uuid_generator.string_charset);
-            state.uuid_generator.last_string = Some(s);
+            if let Some(parsed) = bindings.get("charset").and_then(|c| parse_charset_label(c)) {
+                state.uuid_generator.string_charset = parsed;
+            }
+            for (bind_key, target) in [
+                ("password_min_digits", &mut state.uuid_generator.min_digits),
+                ("password_min_symbols", &mut state.uuid_generator.min_symbols),
+            ] {
+                if let Some(value) = bindings.get(bind_key) {
+                    *target = value.trim().parse::<u32>().unwrap_or(0).min(len);
+                }
+            }
+            for (bind_key, target) in [
+                ("password_exclude_ambiguous", &mut state.uuid_generator.exclude_ambiguous),
+                ("password_pronounceable", &mut state.uuid_generator.pronounceable),
+            ] {
+                if let Some(value) = bindings.get(bind_key) {
+                    *target = value == "true";
+                }
+            }
+            let policy = PasswordPolicy {
+                charset: state.uuid_generator.string_charset,
+                min_digits: state.uuid_generator.min_digits as usize,
+                min_symbols: state.uuid_generator.min_symbols as usize,
+                exclude_ambiguous: state.uuid_generator.exclude_ambiguous,
+                pronounceable: state.uuid_generator.pronounceable,
+            };
+            match generate_password(len as usize, &policy) {
+                Ok((s, bits)) => {
+                    state.uuid_generator.last_string = Some(s);
+                    state.uuid_generator.last_entropy_bits = Some(bits);
+                    state.uuid_generator.error = None;
+                }
+                Err(e) => {
+                    state.uuid_generator.last_string = None;
+                    state.uuid_generator.last_entropy_bits = None;
+                    state.uuid_generator.error = Some(e);
+                }
+            }
         }
         _ => {}
     }
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 pub fn generate_uuid() -> String {
     Uuid::new_v4().to_string()
 }
 
-pub fn generate_string(len: usize, charset: StringCharset) -> String {
-    let rng = thread_rng();
+/// v1 uses a random node id with the multicast bit set, as RFC 9562 suggests
+/// when no MAC address is available; v5 hashes `name` within `namespace`.
+pub fn generate_uuid_version(
+    version: UuidVersion,
+    namespace: &str,
+    name: &str,
+) -> Result<String, String> {
+    let uuid = match version {
+        UuidVersion::V1 => {
+            let mut node_id: [u8; 6] = thread_rng().gen();
+            node_id[0] |= 0x01;
+            Uuid::now_v1(&node_id)
+        }
+        UuidVersion::V4 => Uuid::new_v4(),
+        UuidVersion::V5 => {
+            if name.is_empty() {
+                return Err("uuid_v5_name_required".into());
+            }
+            Uuid::new_v5(&parse_namespace(namespace)?, name.as_bytes())
+        }
+        UuidVersion::V7 => Uuid::now_v7(),
+    };
+    Ok(uuid.to_string())
+}
+
+fn uuid_field(label: &str, value: impl Into<String>, note: Option<&str>) -> UuidField {
+    UuidField {
+        label: label.into(),
+        value: value.into(),
+        note: note.map(str::to_string),
+    }
+}
+
+/// Breaks a UUID into labelled fields: version, variant, and for time-based
+/// versions the embedded timestamp, clock sequence, and node.
+pub fn inspect_uuid(input: &str) -> Result<Vec<UuidField>, String> {
+    let uuid = Uuid::parse_str(input.trim()).map_err(|_| "uuid_parse_failed".to_string())?;
+    let mut fields = vec![uuid_field(
+        "uuid_field_canonical",
+        uuid.hyphenated().to_string(),
+        None,
+    )];
+    if uuid.is_nil() {
+        fields.push(uuid_field("uuid_field_kind", "", Some("uuid_kind_nil")));
+        return Ok(fields);
+    }
+    if uuid.is_max() {
+        fields.push(uuid_field("uuid_field_kind", "", Some("uuid_kind_max")));
+        return Ok(fields);
+    }
+
+    let variant = match uuid.get_variant() {
+        Variant::NCS => "uuid_variant_ncs",
+        Variant::RFC4122 => "uuid_variant_rfc",
+        Variant::Microsoft => "uuid_variant_microsoft",
+        _ => "uuid_variant_future",
+    };
+    fields.push(uuid_field("uuid_field_variant", "", Some(variant)));
+    if uuid.get_variant() != Variant::RFC4122 {
+        // The version nibble is only defined for the RFC variant.
+        return Ok(fields);
+    }
+
+    let version = uuid.get_version_num();
+    let kind = match version {
+        1 => "uuid_version_time_based",
+        2 => "uuid_version_dce",
+        3 => "uuid_version_md5",
+        4 => "uuid_version_random",
+        5 => "uuid_version_sha1",
+        6 => "uuid_version_reordered",
+        7 => "uuid_version_unix_time",
+        8 => "uuid_version_custom",
+        _ => "uuid_version_unknown",
+    };
+    fields.push(uuid_field(
+        "uuid_field_version",
+        version.to_string(),
+        Some(kind),
+    ));
+
+    if let Some(ts) = uuid.get_timestamp() {
+        let (secs, nanos) = ts.to_unix();
+        let field = match i64::try_from(secs)
+            .ok()
+            .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, nanos))
+        {
+            Some(dt) => uuid_field(
+                "uuid_field_timestamp",
+                dt.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+                None,
+            ),
+            None => uuid_field(
+                "uuid_field_timestamp",
+                secs.to_string(),
+                Some("uuid_timestamp_unix_seconds"),
+            ),
+        };
+        fields.push(field);
+        if matches!(version, 1 | 6) {
+            fields.push(uuid_field(
+                "uuid_field_clock_sequence",
+                ts.to_gregorian().1.to_string(),
+                None,
+            ));
+        }
+    }
+    if let Some(node) = uuid.get_node_id() {
+        let hex: Vec<String> = node.iter().map(|b| format!("{b:02x}")).collect();
+        let origin = if node[0] & 0x01 == 1 {
+            "uuid_node_random"
+        } else {
+            "uuid_node_mac"
+        };
+        fields.push(uuid_field("uuid_field_node", hex.join(":"), Some(origin)));
+    }
+    Ok(fields)
+}
+
+/// Reads straight from the OS CSPRNG rather than the userspace `thread_rng`.
+pub fn generate_random_bytes(len: usize) -> Result<Vec<u8>, String> {
+    let mut bytes = vec![0u8; len];
+    OsRng
+        .try_fill(&mut bytes[..])
+        .map_err(|e| format!("random_source_failed:{e}"))?;
+    Ok(bytes)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// 48-bit millisecond timestamp followed by 80 random bits, Crockford base32 encoded.
+pub fn generate_ulid() -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let random: u128 = thread_rng().gen::<u128>() & ((1u128 << 80) - 1);
+    encode_ulid(millis as u64, random)
+}
+
+fn encode_ulid(millis: u64, random: u128) -> String {
+    let value = ((millis as u128 & ((1u128 << 48) - 1)) << 80) | random;
+    // 26 characters cover 130 bits; the leading one only carries the top 3.
+    (0..26)
+        .rev()
+        .map(|idx| ULID_ALPHABET[((value >> (idx * 5)) & 0x1f) as usize] as char)
+        .collect()
+}
+
+/// Uniformly samples `len` characters; an empty alphabet means the URL-safe default.
+pub fn generate_nanoid(alphabet: &str, len: usize) -> Result<String, String> {
+    let alphabet = if alphabet.is_empty() {
+        NANOID_DEFAULT_ALPHABET
+    } else {
+        alphabet
+    };
+    let chars: Vec<char> = alphabet.chars().collect();
+    if chars.len() < 2 || chars.len() > NANOID_MAX_ALPHABET {
+        return Err("nanoid_invalid_alphabet".into());
+    }
+    let mut unique = chars.clone();
+    unique.sort_unstable();
+    unique.dedup();
+    if unique.len() != chars.len() {
+        // Repeated symbols would silently bias the output.
+        return Err("nanoid_duplicate_symbols".into());
+    }
+    let mut rng = thread_rng();
+    Ok((0..len)
+        .map(|_| chars[rng.gen_range(0..chars.len())])
+        .collect())
+}
+
+fn parse_namespace(namespace: &str) -> Result<Uuid, String> {
+    match namespace.to_ascii_lowercase().as_str() {
+        "" | "dns" => Ok(Uuid::NAMESPACE_DNS),
+        "url" => Ok(Uuid::NAMESPACE_URL),
+        "oid" => Ok(Uuid::NAMESPACE_OID),
+        "x500" => Ok(Uuid::NAMESPACE_X500),
+        other => Uuid::parse_str(other).map_err(|_| "uuid_invalid_namespace".to_string()),
+    }
+}
+
+fn parse_version(label: &str) -> Option<UuidVersion> {
+    match label.to_ascii_lowercase().as_str() {
+        "v1" | "1" => Some(UuidVersion::V1),
+        "v4" | "4" => Some(UuidVersion::V4),
+        "v5" | "5" => Some(UuidVersion::V5),
+        "v7" | "7" => Some(UuidVersion::V7),
+        _ => None,
+    }
+}
+
+/// Builds a string satisfying `policy` and returns it with its entropy in bits.
+///
+/// Required digits and symbols are drawn from their own class and the result is
+/// shuffled; the entropy sums `log2(pool size)` per drawn character, which
+/// ignores the placement of required characters and so errs on the low side.
+pub fn generate_password(len: usize, policy: &PasswordPolicy) -> Result<(String, f64), String> {
+    let required = policy.min_digits + policy.min_symbols;
+    if required > len {
+        return Err("password_requirements_exceed_length".into());
+    }
+    let keep = |c: &char| !(policy.exclude_ambiguous && AMBIGUOUS.contains(*c));
+    let digits: Vec<char> = DIGITS.chars().filter(keep).collect();
+    let symbols: Vec<char> = SYMBOLS.chars().filter(keep).collect();
+
+    let mut rng = thread_rng();
+    let mut entropy = 0.0;
+    let mut draw = |pool: &[char], out: &mut Vec<char>| {
+        entropy += (pool.len() as f64).log2();
+        out.push(pool[rng.gen_range(0..pool.len())]);
+    };
+
+    let mut out = Vec::with_capacity(len);
+    if policy.pronounceable {
+        let consonants: Vec<char> = CONSONANTS.chars().filter(keep).collect();
+        let vowels: Vec<char> = VOWELS.chars().filter(keep).collect();
+        for idx in 0..len - required {
+            draw(if idx % 2 == 0 { &consonants } else { &vowels }, &mut out);
+        }
+        // Keep the syllables readable by appending the required classes.
+        let mut tail = Vec::with_capacity(required);
+        (0..policy.min_digits).for_each(|_| draw(&digits, &mut tail));
+        (0..policy.min_symbols).for_each(|_| draw(&symbols, &mut tail));
+        tail.shuffle(&mut thread_rng());
+        out.extend(tail);
+    } else {
+        let mut pool: Vec<char> = charset_chars(policy.charset).filter(keep).collect();
+        if policy.min_digits > 0 {
+            pool.extend(&digits);
+        }
+        if policy.min_symbols > 0 {
+            pool.extend(&symbols);
+        }
+        pool.sort_unstable();
+        pool.dedup();
+        (0..policy.min_digits).for_each(|_| draw(&digits, &mut out));
+        (0..policy.min_symbols).for_each(|_| draw(&symbols, &mut out));
+        (required..len).for_each(|_| draw(&pool, &mut out));
+        out.shuffle(&mut thread_rng());
+    }
+    Ok((out.into_iter().collect(), entropy))
+}
+
+fn charset_chars(charset: StringCharset) -> impl Iterator<Item = char> {
+    let ranges: &[(char, char)] = match charset {
+        StringCharset::Alphanumeric => &[('0', '9'), ('A', 'Z'), ('a', 'z')],
+        StringCharset::Numeric => &[('0', '9')],
+        StringCharset::Alpha => &[('a', 'z')],
+        StringCharset::Hex => &[('0', '9'), ('a', 'f')],
+    };
+    ranges.iter().flat_map(|&(start, end)| start..=end)
+}
+
+fn charset_id(charset: StringCharset) -> &'static str {
     match charset {
-        StringCharset::Alphanumeric => rng
-            .sample_iter(&Alphanumeric)
-            .take(len)
-            .map(char::from)
-            .collect(),
-        StringCharset::Numeric => rng
-            .sample_iter(rand::distributions::Uniform::new_inclusive(b'0', b'9'))
-            .take(len)
-            .map(|b| b as char)
-            .collect(),
-        StringCharset::Alpha => rng
-            .sample_iter(rand::distributions::Uniform::new_inclusive(b'a', b'z'))
-            .take(len)
-            .map(|b| b as char)
-            .collect(),
-        StringCharset::Hex => rng
-            .sample_iter(rand::distributions::Uniform::new_inclusive(b'0', b'f'))
-            .take(len)
-            .map(|b| {
-                let c = b as char;
-                if c >= 'g' {
-                    (b'0' + (b % 16)) as char
-                } else {
-                    c
-                }
-            })
-            .collect(),
+        StringCharset::Alphanumeric => "alphanumeric",
+        StringCharset::Numeric => "numeric",
+        StringCharset::Alpha => "alphabetic",
+        StringCharset::Hex => "hex",
     }
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn generate_string(len: usize, charset: StringCharset) -> String {
+    generate_password(len, &PasswordPolicy::new(charset))
+        .map(|(s, _)| s)
+        .unwrap_or_default()
+}
+
 fn parse_charset_label(label: &str) -> Option<StringCharset> {
     match label.to_lowercase().as_str() {
         "alphanumeric" => Some(StringCharset::Alphanumeric),
@@ -167,6 +786,181 @@ mod tests {
         assert!(u.chars().filter(|c| *c == '-').count() == 4);
     }
 
+    #[test]
+    fn versions_set_the_version_nibble() {
+        for (version, digit) in [
+            (UuidVersion::V1, '1'),
+            (UuidVersion::V4, '4'),
+            (UuidVersion::V7, '7'),
+        ] {
+            let u = generate_uuid_version(version, "", "").unwrap();
+            assert_eq!(u.chars().nth(14), Some(digit), "{u}");
+        }
+    }
+
+    #[test]
+    fn v5_is_deterministic_per_namespace_and_name() {
+        // RFC 9562 appendix A.4 test vector.
+        assert_eq!(
+            generate_uuid_version(UuidVersion::V5, "dns", "www.example.com").unwrap(),
+            "2ed6657d-e927-568b-95e1-2665a8aea6a2"
+        );
+        let custom = "6ba7b811-9dad-11d1-80b4-00c04fd430c8";
+        assert_eq!(
+            generate_uuid_version(UuidVersion::V5, custom, "x").unwrap(),
+            generate_uuid_version(UuidVersion::V5, "URL", "x").unwrap()
+        );
+        assert_eq!(
+            generate_uuid_version(UuidVersion::V5, "dns", "").unwrap_err(),
+            "uuid_v5_name_required"
+        );
+        assert_eq!(
+            generate_uuid_version(UuidVersion::V5, "nope", "x").unwrap_err(),
+            "uuid_invalid_namespace"
+        );
+    }
+
+    #[test]
+    fn v7_ids_sort_by_creation_time() {
+        let a = generate_uuid_version(UuidVersion::V7, "", "").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let b = generate_uuid_version(UuidVersion::V7, "", "").unwrap();
+        assert!(a < b);
+    }
+
+    #[test]
+    fn inspect_decodes_time_based_fields() {
+        // RFC 9562 appendix A.1 / A.6 example values.
+        let v1 = inspect_uuid("C232AB00-9414-11EC-B3C8-9F6BDECED846").unwrap();
+        let get = |fields: &[UuidField], label: &str| {
+            fields
+                .iter()
+                .find(|f| f.label == label)
+                .map(|f| (f.value.clone(), f.note.clone()))
+                .unwrap_or_default()
+        };
+        assert_eq!(
+            get(&v1, "uuid_field_version"),
+            ("1".into(), Some("uuid_version_time_based".into()))
+        );
+        assert_eq!(
+            get(&v1, "uuid_field_timestamp"),
+            ("2022-02-22T19:22:22Z".into(), None)
+        );
+        assert_eq!(
+            get(&v1, "uuid_field_clock_sequence"),
+            ("13256".into(), None)
+        );
+        assert_eq!(
+            get(&v1, "uuid_field_node"),
+            ("9f:6b:de:ce:d8:46".into(), Some("uuid_node_random".into()))
+        );
+
+        let v7 = inspect_uuid("017F22E2-79B0-7CC3-98C4-DC0C0C07398F").unwrap();
+        assert_eq!(
+            get(&v7, "uuid_field_timestamp"),
+            ("2022-02-22T19:22:22Z".into(), None)
+        );
+        assert_eq!(
+            get(&v7, "uuid_field_variant"),
+            (String::new(), Some("uuid_variant_rfc".into()))
+        );
+    }
+
+    #[test]
+    fn inspect_flags_nil_max_and_garbage() {
+        let nil = inspect_uuid("00000000-0000-0000-0000-000000000000").unwrap();
+        assert_eq!(nil[1].note.as_deref(), Some("uuid_kind_nil"));
+        let max = inspect_uuid("ffffffff-ffff-ffff-ffff-ffffffffffff").unwrap();
+        assert_eq!(max[1].note.as_deref(), Some("uuid_kind_max"));
+        assert_eq!(inspect_uuid("not-a-uuid").unwrap_err(), "uuid_parse_failed");
+        let v4 = inspect_uuid(&generate_uuid()).unwrap();
+        assert!(v4.iter().any(|f| f.label == "uuid_field_version"
+            && f.value == "4"
+            && f.note.as_deref() == Some("uuid_version_random")));
+        assert!(!v4.iter().any(|f| f.label == "uuid_field_timestamp"));
+    }
+
+    #[test]
+    fn random_bytes_have_requested_length_and_encodings() {
+        let bytes = generate_random_bytes(32).unwrap();
+        assert_eq!(bytes.len(), 32);
+        assert_ne!(bytes, generate_random_bytes(32).unwrap());
+        assert_eq!(encode_hex(&[0x00, 0xab, 0x7f]), "00ab7f");
+    }
+
+    #[test]
+    fn ulid_encodes_timestamp_first() {
+        assert_eq!(encode_ulid(0, 0), "00000000000000000000000000");
+        assert_eq!(
+            encode_ulid(1_469_918_176_385, 0),
+            "01ARYZ6S410000000000000000"
+        );
+        let ulid = generate_ulid();
+        assert_eq!(ulid.len(), 26);
+        assert!(ulid.bytes().all(|b| ULID_ALPHABET.contains(&b)));
+    }
+
+    #[test]
+    fn nanoid_respects_alphabet_and_length() {
+        let id = generate_nanoid("", 21).unwrap();
+        assert_eq!(id.len(), 21);
+        assert!(id.chars().all(|c| NANOID_DEFAULT_ALPHABET.contains(c)));
+
+        let id = generate_nanoid("ab", 64).unwrap();
+        assert!(id.chars().all(|c| c == 'a' || c == 'b'));
+        assert_eq!(generate_nanoid("a", 4).unwrap_err(), "nanoid_invalid_alphabet");
+        assert_eq!(generate_nanoid("aab", 4).unwrap_err(), "nanoid_duplicate_symbols");
+    }
+
+    #[test]
+    fn password_policy_enforces_minimums() {
+        let policy = PasswordPolicy {
+            min_digits: 3,
+            min_symbols: 2,
+            ..PasswordPolicy::new(StringCharset::Alpha)
+        };
+        for _ in 0..50 {
+            let (s, bits) = generate_password(12, &policy).unwrap();
+            assert_eq!(s.chars().count(), 12);
+            assert!(s.chars().filter(|c| c.is_ascii_digit()).count() >= 3);
+            assert!(s.chars().filter(|c| SYMBOLS.contains(*c)).count() >= 2);
+            assert!(bits > 12.0 * 4.0);
+        }
+        assert_eq!(
+            generate_password(4, &policy).unwrap_err(),
+            "password_requirements_exceed_length"
+        );
+    }
+
+    #[test]
+    fn password_excludes_ambiguous_and_reports_entropy() {
+        let policy = PasswordPolicy {
+            exclude_ambiguous: true,
+            ..PasswordPolicy::new(StringCharset::Alphanumeric)
+        };
+        let (s, bits) = generate_password(200, &policy).unwrap();
+        assert!(!s.chars().any(|c| AMBIGUOUS.contains(c)));
+        // 62 symbols minus 0, O, 1, l, I.
+        assert!((bits - 200.0 * 57f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pronounceable_passwords_alternate_consonants_and_vowels() {
+        let policy = PasswordPolicy {
+            pronounceable: true,
+            min_digits: 2,
+            ..PasswordPolicy::new(StringCharset::Alphanumeric)
+        };
+        let (s, _) = generate_password(10, &policy).unwrap();
+        let chars: Vec<char> = s.chars().collect();
+        for (idx, c) in chars[..8].iter().enumerate() {
+            let class = if idx % 2 == 0 { CONSONANTS } else { VOWELS };
+            assert!(class.contains(*c), "{s}");
+        }
+        assert!(chars[8..].iter().all(char::is_ascii_digit));
+    }
+
     #[test]
     fn random_string_respects_length_and_charset() {
         let s = generate_string(10, StringCharset::Numeric);
@@ -1,3 +1,5 @@
+use crate::error::error_text;
+use crate::features::hashes::ProgressReader;
 use crate::features::storage::output_dir_for;
 use crate::state::AppState;
 use crate::ui::{maybe_push_back, Button as UiButton, Column as UiColumn, Text as UiText, TextInput as UiTextInput};
@@ -47,7 +49,17 @@ fn to_value_or_text<T: Serialize>(value: T, context: &str) -> Value {
     })
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 pub fn encrypt_file(path: &str, password: &str) -> Result<PathBuf, String> {
+    encrypt_file_with_progress(path, password, |_| {})
+}
+
+/// Like [`encrypt_file`], calling `on_progress` with the fraction of the input read so far.
+pub fn encrypt_file_with_progress(
+    path: &str,
+    password: &str,
+    on_progress: impl FnMut(f32),
+) -> Result<PathBuf, String> {
     let input = Path::new(path);
     if !input.exists() {
         return Err("vault_source_missing".into());
@@ -74,8 +86,8 @@ pub fn encrypt_file(path: &str, password: &str) -> Result<PathBuf, String> {
     };
     out_dir.push(output_name);
 
-    let mut reader =
-        BufReader::new(File::open(input).map_err(|e| format!("vault_open_failed:{e}"))?);
+    let file = File::open(input).map_err(|e| format!("vault_open_failed:{e}"))?;
+    let mut reader = BufReader::new(ProgressReader::new(file, on_progress));
     let out_file = File::create(&out_dir).map_err(|e| format!("vault_dest_open_failed:{e}"))?;
     let encryptor =
         Encryptor::with_user_passphrase(SecretString::new(password.to_owned()));
@@ -89,7 +101,17 @@ pub fn encrypt_file(path: &str, password: &str) -> Result<PathBuf, String> {
     Ok(out_dir)
 }
 
+#[cfg_attr(not(test), allow(dead_code))]
 pub fn decrypt_file(path: &str, password: &str) -> Result<PathBuf, String> {
+    decrypt_file_with_progress(path, password, |_| {})
+}
+
+/// Like [`decrypt_file`], calling `on_progress` with the fraction of the input read so far.
+pub fn decrypt_file_with_progress(
+    path: &str,
+    password: &str,
+    on_progress: impl FnMut(f32),
+) -> Result<PathBuf, String> {
     let input = Path::new(path);
     if !input.exists() {
         return Err("vault_source_missing".into());
@@ -121,7 +143,8 @@ pub fn decrypt_file(path: &str, password: &str) -> Result<PathBuf, String> {
     };
     out_dir.push(out_name);
 
-    let reader = BufReader::new(File::open(input).map_err(|e| format!("vault_open_failed:{e}"))?);
+    let file = File::open(input).map_err(|e| format!("vault_open_failed:{e}"))?;
+    let reader = BufReader::new(ProgressReader::new(file, on_progress));
     let decryptor =
         Decryptor::new(reader).map_err(|e| format!("vault_decrypt_failed:{e}"))?;
     let passphrase_decryptor = match decryptor {
@@ -142,25 +165,21 @@ pub fn decrypt_file(path: &str, password: &str) -> Result<PathBuf, String> {
 
 pub fn render_vault_screen(state: &AppState) -> Value {
     let mut children = vec![
-        to_value_or_text(UiText::new("🔐 The Vault").size(20.0), "vault_title"),
+        to_value_or_text(UiText::new(&t!("vault_title")).size(20.0), "vault_title"),
         to_value_or_text(
-            UiText::new("Encrypt or decrypt files using age + passphrase.")
-                .size(14.0),
+            UiText::new(&t!("vault_description")).size(14.0),
             "vault_subtitle",
         ),
         to_value_or_text(
-            UiButton::new("Choose file", "vault_pick")
+            UiButton::new(&t!("vault_choose_file_button"), "vault_pick")
                 .requires_file_picker(true)
                 .content_description("vault_pick_btn"),
             "vault_pick_btn",
         ),
     ];
 
-    let path_text = state
-        .vault
-        .input_path
-        .as_deref()
-        .unwrap_or("No file selected");
+    let no_file = t!("error_no_file_selected");
+    let path_text = state.vault.input_path.as_deref().unwrap_or(&no_file);
     children.push(to_value_or_text(
         UiText::new(&format!("Path: {path_text}"))
             .size(12.0)
@@ -170,7 +189,7 @@ pub fn render_vault_screen(state: &AppState) -> Value {
 
     children.push(to_value_or_text(
         UiTextInput::new("vault_password")
-            .hint("Passphrase")
+            .hint(&t!("vault_passphrase_hint"))
             .single_line(true)
             .password_mask(true)
             .content_description("vault_password_input"),
@@ -179,11 +198,13 @@ pub fn render_vault_screen(state: &AppState) -> Value {
 
     let buttons = UiColumn::new(vec![
         to_value_or_text(
-            UiButton::new("Encrypt", "vault_encrypt").content_description("vault_encrypt_btn"),
+            UiButton::new(&t!("vault_encrypt_button"), "vault_encrypt")
+                .content_description("vault_encrypt_btn"),
             "vault_encrypt_btn",
         ),
         to_value_or_text(
-            UiButton::new("Decrypt", "vault_decrypt").content_description("vault_decrypt_btn"),
+            UiButton::new(&t!("vault_decrypt_button"), "vault_decrypt")
+                .content_description("vault_decrypt_btn"),
             "vault_decrypt_btn",
         ),
     ]);
@@ -191,7 +212,7 @@ pub fn render_vault_screen(state: &AppState) -> Value {
 
     if state.vault.is_processing {
         children.push(to_value_or_text(
-            UiText::new("Working...").size(12.0),
+            UiText::new(&t!("loading_working")).size(12.0),
             "vault_processing",
         ));
     }
@@ -210,7 +231,7 @@ pub fn render_vault_screen(state: &AppState) -> Value {
             .unwrap_or(false);
         if has_output_path && state.vault.error.is_none() {
             children.push(to_value_or_text(
-                UiButton::new("Save as…", "vault_save_as").id("vault_save_as_btn"),
+                UiButton::new(&t!("vault_save_as_button"), "vault_save_as").id("vault_save_as_btn"),
                 "vault_save_as_btn",
             ));
         }
@@ -218,7 +239,7 @@ pub fn render_vault_screen(state: &AppState) -> Value {
 
     if let Some(err) = &state.vault.error {
         children.push(to_value_or_text(
-            UiText::new(&format!("Error: {}", err))
+            UiText::new(&error_text(err))
                 .size(12.0)
                 .content_description("vault_error"),
             "vault_error",
@@ -254,6 +275,30 @@ mod tests {
         assert_eq!(data, b"vault-content");
     }
 
+    #[test]
+    fn large_files_report_progress_both_ways() {
+        let dir = tempdir().unwrap();
+        let input_path = dir.path().join("big.bin");
+        let content: Vec<u8> = (0..3 << 20).map(|i| (i % 251) as u8).collect();
+        fs::write(&input_path, &content).unwrap();
+
+        let mut seen = Vec::new();
+        let enc_path =
+            encrypt_file_with_progress(input_path.to_str().unwrap(), "pw", |f| seen.push(f))
+                .expect("encrypt ok");
+        assert!(seen.len() >= 2);
+        assert!(seen.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(seen.last().copied(), Some(1.0));
+
+        fs::remove_file(&input_path).unwrap();
+        let mut seen = Vec::new();
+        let dec_path =
+            decrypt_file_with_progress(enc_path.to_str().unwrap(), "pw", |f| seen.push(f))
+                .expect("decrypt ok");
+        assert!(!seen.is_empty());
+        assert_eq!(fs::read(dec_path).unwrap(), content);
+    }
+
     #[test]
     fn decrypt_with_wrong_password_fails() {
         let dir = tempdir().unwrap();
@@ -0,0 +1,462 @@
+use crate::error::error_text;
+use crate::features::image_edit::write_result_image;
+use crate::features::image_resize::open_oriented;
+use crate::state::AppState;
+use crate::ui::{
+    maybe_push_back, Button as UiButton, Column as UiColumn, Grid as UiGrid, Text as UiText,
+    TextInput as UiTextInput,
+};
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, RgbaImage};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+pub const DEFAULT_OPACITY_PCT: u8 = 60;
+pub const DEFAULT_SCALE_PCT: u8 = 25;
+/// Gap between the mark and the image edge, as a share of the shorter side.
+const MARGIN_PCT: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WatermarkMode {
+    /// Text rendered to a transparent PNG by the Android side (system fonts, any script).
+    Text,
+    Logo,
+}
+
+impl WatermarkMode {
+    pub fn key(self) -> &'static str {
+        match self {
+            WatermarkMode::Text => "text",
+            WatermarkMode::Logo => "logo",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WatermarkPosition {
+    TopLeft,
+    TopRight,
+    Center,
+    BottomLeft,
+    BottomRight,
+}
+
+impl WatermarkPosition {
+    pub const ALL: [WatermarkPosition; 5] = [
+        WatermarkPosition::TopLeft,
+        WatermarkPosition::TopRight,
+        WatermarkPosition::Center,
+        WatermarkPosition::BottomLeft,
+        WatermarkPosition::BottomRight,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            WatermarkPosition::TopLeft => "top_left",
+            WatermarkPosition::TopRight => "top_right",
+            WatermarkPosition::Center => "center",
+            WatermarkPosition::BottomLeft => "bottom_left",
+            WatermarkPosition::BottomRight => "bottom_right",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.key() == key)
+    }
+
+    fn label(self) -> String {
+        match self {
+            WatermarkPosition::TopLeft => t!("watermark_position_top_left"),
+            WatermarkPosition::TopRight => t!("watermark_position_top_right"),
+            WatermarkPosition::Center => t!("watermark_position_center"),
+            WatermarkPosition::BottomLeft => t!("watermark_position_bottom_left"),
+            WatermarkPosition::BottomRight => t!("watermark_position_bottom_right"),
+        }
+        .to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatermarkOptions {
+    pub position: WatermarkPosition,
+    pub opacity_pct: u8,
+    /// Mark width as a share of the picture width.
+    pub scale_pct: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatermarkSource {
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatermarkOutcome {
+    pub name: String,
+    pub value: Result<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatermarkState {
+    pub mode: WatermarkMode,
+    pub text: String,
+    pub position: WatermarkPosition,
+    pub opacity_pct: u8,
+    pub scale_pct: u8,
+    pub sources: Vec<WatermarkSource>,
+    pub output_dir: Option<String>,
+    pub logo: Option<WatermarkSource>,
+    pub outcomes: Vec<WatermarkOutcome>,
+    pub error: Option<String>,
+}
+
+impl WatermarkState {
+    pub const fn new() -> Self {
+        Self {
+            mode: WatermarkMode::Text,
+            text: String::new(),
+            position: WatermarkPosition::BottomRight,
+            opacity_pct: DEFAULT_OPACITY_PCT,
+            scale_pct: DEFAULT_SCALE_PCT,
+            sources: Vec::new(),
+            output_dir: None,
+            logo: None,
+            outcomes: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn options(&self) -> WatermarkOptions {
+        WatermarkOptions {
+            position: self.position,
+            opacity_pct: self.opacity_pct,
+            scale_pct: self.scale_pct,
+        }
+    }
+
+    /// Pulls the form values; unparsable numbers keep the previous setting.
+    pub fn sync_bindings(&mut self, bindings: &HashMap<String, String>) {
+        match bindings.get("watermark_mode").map(|s| s.as_str()) {
+            Some("text") => self.mode = WatermarkMode::Text,
+            Some("logo") => self.mode = WatermarkMode::Logo,
+            _ => {}
+        }
+        if let Some(position) = bindings
+            .get("watermark_position")
+            .and_then(|key| WatermarkPosition::from_key(key))
+        {
+            self.position = position;
+        }
+        if let Some(text) = bindings.get("watermark_text") {
+            self.text = text.clone();
+        }
+        if let Some(Ok(v)) = bindings
+            .get("watermark_opacity")
+            .map(|s| s.trim().parse::<u8>())
+        {
+            self.opacity_pct = v.clamp(5, 100);
+        }
+        if let Some(Ok(v)) = bindings
+            .get("watermark_scale")
+            .map(|s| s.trim().parse::<u8>())
+        {
+            self.scale_pct = v.clamp(5, 100);
+        }
+    }
+}
+
+/// Blends `mark`, resized to `scale_pct` of the picture width, onto `base`.
+pub fn apply_watermark(base: &mut RgbaImage, mark: &RgbaImage, opts: &WatermarkOptions) {
+    let (bw, bh) = base.dimensions();
+    let (mw, mh) = mark.dimensions();
+    if bw == 0 || bh == 0 || mw == 0 || mh == 0 {
+        return;
+    }
+    let mut tw = (bw as u64 * opts.scale_pct.clamp(1, 100) as u64 / 100).max(1);
+    let mut th = (mh as u64 * tw / mw as u64).max(1);
+    if th > bh as u64 {
+        th = bh as u64;
+        tw = (mw as u64 * th / mh as u64).max(1);
+    }
+    let (tw, th) = (tw as u32, th as u32);
+    let scaled = if (tw, th) == (mw, mh) {
+        mark.clone()
+    } else {
+        imageops::resize(mark, tw, th, FilterType::Triangle)
+    };
+
+    let margin = bw.min(bh) * MARGIN_PCT / 100;
+    let right = bw.saturating_sub(tw + margin);
+    let bottom = bh.saturating_sub(th + margin);
+    let (x0, y0) = match opts.position {
+        WatermarkPosition::TopLeft => (margin, margin),
+        WatermarkPosition::TopRight => (right, margin),
+        WatermarkPosition::Center => ((bw - tw) / 2, (bh - th) / 2),
+        WatermarkPosition::BottomLeft => (margin, bottom),
+        WatermarkPosition::BottomRight => (right, bottom),
+    };
+
+    let opacity = opts.opacity_pct.min(100) as u32;
+    for (x, y, px) in scaled.enumerate_pixels() {
+        let (bx, by) = (x0 + x, y0 + y);
+        if bx >= bw || by >= bh {
+            continue;
+        }
+        // Alpha in 0..=25_500 (mark alpha × opacity percent).
+        let alpha = px[3] as u32 * opacity;
+        if alpha == 0 {
+            continue;
+        }
+        let dst = base.get_pixel_mut(bx, by);
+        for (d, s) in dst.0.iter_mut().zip(px.0).take(3) {
+            *d = ((s as u32 * alpha + *d as u32 * (25_500 - alpha) + 12_750) / 25_500) as u8;
+        }
+        dst[3] = (dst[3] as u32 + ((255 - dst[3] as u32) * alpha + 12_750) / 25_500) as u8;
+    }
+}
+
+/// Watermarks every source with the same mark; one failing picture doesn't stop the rest.
+pub fn watermark_files(
+    sources: &[WatermarkSource],
+    mark_path: &str,
+    opts: &WatermarkOptions,
+    output_dir: &str,
+) -> Vec<WatermarkOutcome> {
+    let mark = open_oriented(mark_path).map(|m| m.to_rgba8());
+    sources
+        .iter()
+        .map(|source| WatermarkOutcome {
+            name: source.name.clone(),
+            value: mark.as_ref().map_err(Clone::clone).and_then(|mark| {
+                let mut canvas = open_oriented(&source.path)?.to_rgba8();
+                apply_watermark(&mut canvas, mark, opts);
+                write_result_image(
+                    &DynamicImage::ImageRgba8(canvas),
+                    &source.path,
+                    "watermarked_",
+                    output_dir,
+                )
+            }),
+        })
+        .collect()
+}
+
+fn text(value: &str, size: f64) -> Value {
+    serde_json::to_value(UiText::new(value).size(size)).unwrap()
+}
+
+pub fn render_watermark_screen(state: &AppState) -> Value {
+    let wm = &state.watermark;
+    let mut children = vec![
+        text(&t!("watermark_title"), 20.0),
+        text(&t!("watermark_description"), 14.0),
+        serde_json::to_value(
+            UiButton::new(&t!("watermark_pick_images_button"), "watermark_pick_images")
+                .requires_file_picker(true)
+                .allow_multiple_files(true)
+                .id("watermark_pick_images"),
+        )
+        .unwrap(),
+    ];
+    if !wm.sources.is_empty() {
+        let names: Vec<&str> = wm.sources.iter().map(|s| s.name.as_str()).collect();
+        children.push(text(
+            &format!(
+                "{}{} — {}",
+                t!("watermark_selected_prefix"),
+                wm.sources.len(),
+                names.join(", ")
+            ),
+            12.0,
+        ));
+    }
+
+    let mode_buttons = [
+        (WatermarkMode::Text, t!("watermark_mode_text")),
+        (WatermarkMode::Logo, t!("watermark_mode_logo")),
+    ]
+    .into_iter()
+    .map(|(mode, label)| {
+        let id = format!("watermark_mode_{}", mode.key());
+        let mut button = UiButton::new(&label, "watermark_sync")
+            .payload(json!({ "watermark_mode": mode.key() }))
+            .id(&id);
+        if mode == wm.mode {
+            button = button.content_description("selected");
+        }
+        serde_json::to_value(button).unwrap()
+    })
+    .collect();
+    children.push(serde_json::to_value(UiGrid::new(mode_buttons).columns(2)).unwrap());
+
+    match wm.mode {
+        WatermarkMode::Text => {
+            children.push(text(&t!("watermark_text_label"), 14.0));
+            children.push(
+                serde_json::to_value(
+                    UiTextInput::new("watermark_text")
+                        .text(&wm.text)
+                        .hint(&t!("watermark_text_hint"))
+                        .single_line(true),
+                )
+                .unwrap(),
+            );
+        }
+        WatermarkMode::Logo => {
+            children.push(
+                serde_json::to_value(
+                    UiButton::new(&t!("watermark_pick_logo_button"), "watermark_pick_logo")
+                        .requires_file_picker(true)
+                        .id("watermark_pick_logo"),
+                )
+                .unwrap(),
+            );
+            if let Some(logo) = &wm.logo {
+                children.push(text(
+                    &format!("{}{}", t!("watermark_logo_prefix"), logo.name),
+                    12.0,
+                ));
+            }
+        }
+    }
+
+    children.push(text(&t!("watermark_position_label"), 14.0));
+    let position_buttons = WatermarkPosition::ALL
+        .into_iter()
+        .map(|position| {
+            let label = position.label();
+            let id = format!("watermark_position_{}", position.key());
+            let mut button = UiButton::new(&label, "watermark_sync")
+                .payload(json!({ "watermark_position": position.key() }))
+                .id(&id);
+            if position == wm.position {
+                button = button.content_description("selected");
+            }
+            serde_json::to_value(button).unwrap()
+        })
+        .collect();
+    children.push(serde_json::to_value(UiGrid::new(position_buttons).columns(3)).unwrap());
+
+    children.push(text(&t!("watermark_opacity_label"), 14.0));
+    children.push(
+        serde_json::to_value(
+            UiTextInput::new("watermark_opacity")
+                .text(&wm.opacity_pct.to_string())
+                .single_line(true),
+        )
+        .unwrap(),
+    );
+    children.push(text(&t!("watermark_scale_label"), 14.0));
+    children.push(
+        serde_json::to_value(
+            UiTextInput::new("watermark_scale")
+                .text(&wm.scale_pct.to_string())
+                .single_line(true),
+        )
+        .unwrap(),
+    );
+
+    children.push(
+        serde_json::to_value(
+            UiButton::new(&t!("watermark_apply_button"), "watermark_apply")
+                .payload(json!({ "watermark_mode": wm.mode.key() }))
+                .id("watermark_apply"),
+        )
+        .unwrap(),
+    );
+
+    if let Some(err) = &wm.error {
+        children.push(text(&error_text(err), 12.0));
+    }
+    for outcome in &wm.outcomes {
+        let line = match &outcome.value {
+            Ok(path) => format!("{}: {}{path}", outcome.name, t!("watermark_saved_prefix")),
+            Err(e) => format!("{}: {}", outcome.name, error_text(e)),
+        };
+        children.push(text(&line, 12.0));
+    }
+    if let Some(path) = wm.outcomes.iter().rev().find_map(|o| o.value.as_ref().ok()) {
+        children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("dithering_copy_result_path_button"), "copy_clipboard")
+                    .copy_text(path),
+            )
+            .unwrap(),
+        );
+    }
+
+    maybe_push_back(&mut children, state);
+    serde_json::to_value(UiColumn::new(children).padding(20)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn opts(position: WatermarkPosition) -> WatermarkOptions {
+        WatermarkOptions {
+            position,
+            opacity_pct: 100,
+            scale_pct: 20,
+        }
+    }
+
+    #[test]
+    fn watermark_lands_in_the_requested_corner() {
+        let mark = RgbaImage::from_pixel(10, 5, Rgba([255, 0, 0, 255]));
+        let mut base = RgbaImage::from_pixel(100, 50, Rgba([0, 0, 255, 255]));
+        apply_watermark(&mut base, &mark, &opts(WatermarkPosition::BottomRight));
+        // 20% of 100 px wide → 20×10 mark, 1 px margin (3% of 50).
+        assert_eq!(base.get_pixel(98, 48), &Rgba([255, 0, 0, 255]));
+        assert_eq!(base.get_pixel(79, 39), &Rgba([255, 0, 0, 255]));
+        assert_eq!(base.get_pixel(77, 48), &Rgba([0, 0, 255, 255]));
+        assert_eq!(base.get_pixel(99, 49), &Rgba([0, 0, 255, 255]));
+        assert_eq!(base.get_pixel(1, 1), &Rgba([0, 0, 255, 255]));
+
+        let mut base = RgbaImage::from_pixel(100, 50, Rgba([0, 0, 255, 255]));
+        apply_watermark(&mut base, &mark, &opts(WatermarkPosition::TopLeft));
+        assert_eq!(base.get_pixel(1, 1), &Rgba([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn opacity_blends_and_transparent_pixels_are_skipped() {
+        let mark = RgbaImage::from_fn(2, 1, |x, _| {
+            if x == 0 {
+                Rgba([255, 255, 255, 255])
+            } else {
+                Rgba([255, 255, 255, 0])
+            }
+        });
+        let mut base = RgbaImage::from_pixel(2, 1, Rgba([0, 0, 0, 255]));
+        let half = WatermarkOptions {
+            position: WatermarkPosition::TopLeft,
+            opacity_pct: 50,
+            scale_pct: 100,
+        };
+        apply_watermark(&mut base, &mark, &half);
+        assert_eq!(base.get_pixel(0, 0), &Rgba([128, 128, 128, 255]));
+        assert_eq!(base.get_pixel(1, 0), &Rgba([0, 0, 0, 255]));
+    }
+
+    #[test]
+    fn sync_bindings_clamps_and_ignores_garbage() {
+        let mut state = WatermarkState::new();
+        state.sync_bindings(&HashMap::from([
+            ("watermark_mode".to_string(), "logo".to_string()),
+            ("watermark_position".to_string(), "center".to_string()),
+            ("watermark_opacity".to_string(), "0".to_string()),
+            ("watermark_scale".to_string(), "abc".to_string()),
+        ]));
+        assert_eq!(state.mode, WatermarkMode::Logo);
+        assert_eq!(state.position, WatermarkPosition::Center);
+        assert_eq!(state.opacity_pct, 5);
+        assert_eq!(state.scale_pct, DEFAULT_SCALE_PCT);
+    }
+}
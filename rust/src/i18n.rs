@@ -1,32 +1,68 @@
 use crate::state::AppState;
 
-pub fn update_locale(state: &mut AppState, locale_str: &str) {
-    let normalized = normalize_locale(locale_str);
-    state.locale = normalized.to_string();
-    rust_i18n::set_locale(&normalized);
+/// Locales compiled from `locales/*.yml`, with the key of their display name, in the
+/// order the settings screen lists them.
+pub const SUPPORTED_LOCALES: &[(&str, &str)] = &[
+    ("en", "locale_english"),
+    ("fr", "locale_french"),
+    ("de", "locale_german"),
+    ("is", "locale_icelandic"),
+    ("es", "locale_spanish"),
+    ("pt", "locale_portuguese"),
+    ("zh", "locale_chinese"),
+    ("la", "locale_latin"),
+];
+
+/// Records the device locale; it applies whenever the user has not picked one.
+pub fn set_system_locale(state: &mut AppState, locale_str: &str) {
+    state.system_locale = normalize_locale(locale_str).to_string();
+    apply_locale(state);
 }
 
-fn normalize_locale(locale_str: &str) -> &str {
-    let trimmed = locale_str.trim();
-    if trimmed.is_empty() {
-        return "en";
+/// Makes `t!` follow the user's pick, or the device locale without one. Called on every
+/// render, so a restored state or snapshot comes back in its own language.
+pub fn apply_locale(state: &mut AppState) {
+    let locale = if state.preferred_locale.is_empty() {
+        normalize_locale(&state.system_locale)
+    } else {
+        normalize_locale(&state.preferred_locale)
+    };
+    if state.locale != locale {
+        state.locale = locale.to_string();
     }
+    rust_i18n::set_locale(locale);
+}
+
+/// Maps a BCP-47 tag like "fr-FR" / "en_US" to the compiled locale for its language.
+pub fn supported_locale(locale_str: &str) -> Option<&'static str> {
+    // rust-i18n looks up compiled locales by name (e.g. "en", "is").
+    let lang = primary_language(locale_str);
+    SUPPORTED_LOCALES
+        .iter()
+        .map(|(code, _)| *code)
+        .find(|code| *code == lang)
+}
 
-    // rust-i18n looks up compiled locales by name (e.g. "en", "is"), so normalize
-    // incoming BCP-47 tags like "fr-FR" / "en_US" down to a supported language.
-    let lower = trimmed.to_ascii_lowercase().replace('_', "-");
-    let lang = lower.split('-').next().unwrap_or("en");
+fn normalize_locale(locale_str: &str) -> &'static str {
+    supported_locale(locale_str).unwrap_or("en")
+}
 
-    match lang {
-        "is" => "is",
-        "en" => "en",
-        "fr" => "fr",
-        "de" => "de",
-        "es" => "es", // Spanish
-        "pt" => "pt", // Portuguese
-        "zh" => "zh", // Chinese (using zh as standard code)
-        "la" => "la", // Latin
-        _ => "en",
+/// "fr-FR" / "fr_FR" -> "fr".
+fn primary_language(locale_str: &str) -> String {
+    let lower = locale_str.trim().to_ascii_lowercase().replace('_', "-");
+    lower.split('-').next().unwrap_or_default().to_string()
+}
+
+/// Languages written right to left, by primary subtag ("iw" is Hebrew's legacy code).
+const RTL_LANGUAGES: &[&str] = &["ar", "dv", "fa", "he", "iw", "ps", "sd", "ug", "ur", "yi"];
+
+/// `"rtl"` or `"ltr"` for a locale tag, sent on every root as `layout_direction` so the
+/// renderer mirrors layouts for right-to-left languages.
+pub fn layout_direction(locale_str: &str) -> &'static str {
+    if RTL_LANGUAGES.contains(&primary_language(locale_str).as_str()) {
+        "rtl"
+    } else {
+        "ltr"
     }
 }
 
@@ -72,6 +108,16 @@ mod tests {
         assert_eq!(normalize_locale("   "), "en");
     }
 
+    #[test]
+    fn layout_direction_follows_the_script_of_the_language() {
+        assert_eq!(layout_direction("ar"), "rtl");
+        assert_eq!(layout_direction("he-IL"), "rtl");
+        assert_eq!(layout_direction("fa_IR"), "rtl");
+        assert_eq!(layout_direction("en"), "ltr");
+        assert_eq!(layout_direction("zh-Hans-CN"), "ltr");
+        assert_eq!(layout_direction(""), "ltr");
+    }
+
     #[test]
     fn test_locale_translations() {
         // Test that we can set different locales without panicking
@@ -1,8 +1,11 @@
+mod error;
 mod features;
 mod i18n;
+mod logging;
 mod router;
 mod state;
 mod ui;
+mod ui_patch;
 
 pub use i18n::*;
 pub use router::*;
@@ -0,0 +1,205 @@
+use chrono::{Local, TimeZone, Utc};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+/// Entries kept in memory; older ones are dropped as new ones arrive.
+pub const LOG_CAPACITY: usize = 500;
+
+static LOG: OnceLock<Mutex<LogBuffer>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+            Level::Warn => "WARN",
+            Level::Error => "ERROR",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// Milliseconds since the Unix epoch.
+    pub at_ms: i64,
+    pub level: Level,
+    /// Module the line comes from, e.g. `router` or `pdf`.
+    pub tag: &'static str,
+    pub message: String,
+}
+
+impl LogEntry {
+    /// `2026-01-31 14:02:07.412 WARN  pdf: message`, in local time.
+    pub fn line(&self) -> String {
+        let at = Local
+            .timestamp_millis_opt(self.at_ms)
+            .single()
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+            .unwrap_or_else(|| self.at_ms.to_string());
+        format!(
+            "{at} {:<5} {}: {}",
+            self.level.as_str(),
+            self.tag,
+            self.message
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct LogBuffer {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, entry: LogEntry) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+fn buffer() -> std::sync::MutexGuard<'static, LogBuffer> {
+    // A panic while logging must not silence every later line.
+    LOG.get_or_init(|| Mutex::new(LogBuffer::new(LOG_CAPACITY)))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(target_os = "android")]
+fn mirror(level: Level, tag: &str, message: &str) {
+    use android_log_sys::__android_log_write;
+    use std::ffi::CString;
+
+    // Priorities from android/log.h.
+    let priority = match level {
+        Level::Debug => 3,
+        Level::Info => 4,
+        Level::Warn => 5,
+        Level::Error => 6,
+    };
+    if let (Ok(app), Ok(text)) = (
+        CString::new("kistaverk"),
+        CString::new(format!("[{tag}] {message}")),
+    ) {
+        unsafe {
+            __android_log_write(priority, app.as_ptr(), text.as_ptr());
+        }
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+fn mirror(level: Level, tag: &str, message: &str) {
+    eprintln!("[kistaverk][{tag}][{}] {message}", level.as_str());
+}
+
+/// Records a line in the ring buffer and mirrors it to logcat (stderr off-device).
+pub fn log(level: Level, tag: &'static str, message: impl Into<String>) {
+    let message = message.into();
+    mirror(level, tag, &message);
+    buffer().push(LogEntry {
+        at_ms: Utc::now().timestamp_millis(),
+        level,
+        tag,
+        message,
+    });
+}
+
+pub fn debug(tag: &'static str, message: impl Into<String>) {
+    log(Level::Debug, tag, message);
+}
+
+pub fn info(tag: &'static str, message: impl Into<String>) {
+    log(Level::Info, tag, message);
+}
+
+pub fn warn(tag: &'static str, message: impl Into<String>) {
+    log(Level::Warn, tag, message);
+}
+
+pub fn error(tag: &'static str, message: impl Into<String>) {
+    log(Level::Error, tag, message);
+}
+
+/// Copy of the buffered entries, oldest first.
+pub fn recent() -> Vec<LogEntry> {
+    buffer().entries().cloned().collect()
+}
+
+pub fn clear() {
+    buffer().clear();
+}
+
+/// Buffered entries as text, one line each, oldest first.
+pub fn render_text(entries: &[LogEntry]) -> String {
+    entries.iter().map(|e| e.line() + "\n").collect()
+}
+
+/// Writes the buffered entries to `kistaverk-debug-<timestamp>.log` in `dir`.
+pub fn export_to(dir: &Path) -> Result<String, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("mkdir_failed:{e}"))?;
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let path = dir.join(format!("kistaverk-debug-{stamp}.log"));
+    std::fs::write(&path, render_text(&recent())).map_err(|e| format!("write_failed:{e}"))?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(message: &str) -> LogEntry {
+        LogEntry {
+            at_ms: 0,
+            level: Level::Info,
+            tag: "test",
+            message: message.into(),
+        }
+    }
+
+    #[test]
+    fn buffer_drops_the_oldest_entries_past_capacity() {
+        let mut buffer = LogBuffer::new(3);
+        for message in ["a", "b", "c", "d"] {
+            buffer.push(entry(message));
+        }
+        let kept: Vec<&str> = buffer.entries().map(|e| e.message.as_str()).collect();
+        assert_eq!(kept, ["b", "c", "d"]);
+        buffer.clear();
+        assert_eq!(buffer.entries().count(), 0);
+    }
+
+    #[test]
+    fn lines_carry_level_tag_and_message() {
+        let mut warn = entry("page 3 missing");
+        warn.level = Level::Warn;
+        warn.tag = "pdf";
+        assert!(warn.line().ends_with(" WARN  pdf: page 3 missing"));
+        assert_eq!(render_text(&[entry("x"), entry("y")]).lines().count(), 2);
+    }
+}
@@ -1,36 +1,85 @@
+use crate::error::{error_text, AppError};
 use crate::features;
 use crate::features::archive::{self, render_archive_screen, ArchiveOpenResult};
+use crate::features::ascii_art::{
+    convert_image_file, export_art, render_ascii_art_screen, AsciiArt,
+};
+use crate::features::collage::{
+    build_collage, render_collage_screen, CollageLayout, CollageSource,
+};
 use crate::features::color_tools::{handle_color_action, render_color_screen};
-use crate::features::compression::{gzip_compress, gzip_decompress, render_compression_screen};
-use crate::features::dithering::{process_dithering, render_dithering_screen, save_fd_to_temp};
+use crate::features::compression::{
+    gzip_benchmark, gzip_compress, gzip_decompress, render_compression_screen, GzipBenchmark,
+};
+use crate::features::dithering::{
+    palette_from_image, palette_hex, parse_custom_palette, process_dithering,
+    render_dithering_screen, save_fd_to_temp,
+};
+use crate::features::exif_editor::{render_exif_editor_screen, save_exif_edits, ExifEdits};
 use crate::features::file_info::{file_info_from_fd, file_info_from_path, render_file_info_screen};
+use crate::features::gif_frames::{
+    assemble_gif, explode_gif, parse_frame_selection, render_gif_frames_screen, ExplodedGif,
+};
+use crate::features::storage::{
+    load_app_state, load_favorite_tools, load_recent_files, move_favorite, plugins_dir,
+    remember_recent_file, save_app_state, save_favorite_tools, save_recent_files, toggle_favorite,
+    RecentFile,
+};
+use crate::features::cleanup::{
+    auto_clean_once, clean_stale_temp, delete_generated, now_secs, parse_max_age_days,
+    record_output, render_cleanup_screen, save_policy, summary_text,
+};
 use crate::features::hashes::{
-    compute_all_hashes, compute_hash, render_hash_verify_screen, HashAlgo,
+    compute_all_hashes_with_progress, compute_hash_with_progress, compute_hmac_with_progress,
+    render_hash_verify_screen, HashAlgo, HashSource, HmacAlgo,
+};
+use crate::features::image_adjust::{
+    process_image_adjust, render_adjusted_preview, render_image_adjust_screen, Adjustments,
 };
+use crate::features::image_edit::{
+    parse_crop_bindings, process_image_edit, render_image_edit_screen, EditOps,
+};
+use crate::features::image_histogram::{
+    analyze_image_file, render_image_histogram_screen, HistogramStats,
+};
+use crate::features::image_resize::{resize_image_file, ResizeOptions, ResizeOutput};
 use crate::features::kotlin_image::{
     handle_output_dir as handle_kotlin_image_output_dir,
     handle_resize_screen as handle_kotlin_image_resize_screen,
     handle_resize_sync as handle_kotlin_image_resize_sync,
     handle_result as handle_kotlin_image_result, handle_screen_entry as handle_kotlin_image_screen,
-    parse_image_target, render_kotlin_image_screen, ImageConversionResult, ImageTarget,
+    parse_image_target, render_kotlin_image_screen, resize_options, resize_result,
+    ImageConversionResult, ImageTarget,
+};
+use crate::features::metadata_strip::{
+    render_metadata_strip_screen, strip_files, StripInput, StripOutcome,
 };
 use crate::features::misc_screens::{
     render_about_screen, render_barometer_screen, render_compass_screen, render_loading_screen,
-    render_magnetometer_screen, render_progress_demo_screen, render_settings_screen, render_shader_screen,
+    render_magnetometer_screen, render_progress_demo_screen, render_settings_screen,
+    render_shader_screen, render_theme_card,
+};
+use crate::features::math_tool::{
+    handle_math_action, parse_plot_call, persist_math_history, render_math_constants_screen,
+    render_math_tool_screen,
+    render_plot_png, restore_math_history, PlotOutput,
+    PlotRequest,
 };
-use crate::features::math_tool::{handle_math_action, render_math_tool_screen};
 use crate::features::function_analysis::handle_function_analysis_action;
 use crate::features::unit_converter::{handle_unit_converter_action, render_unit_converter_screen};
 use crate::features::pdf::{
     perform_pdf_operation, perform_pdf_set_title, perform_pdf_sign, render_pdf_preview_screen,
-    render_pdf_screen, PdfOperation, PdfSetTitleResult, PdfSignResult,
+    render_pdf_screen, PdfOperation, PdfSetTitleResult, PdfSignResult, PdfTab,
 };
 use crate::features::pixel_art::{
     process_pixel_art, render_pixel_art_screen, reset_pixel_art, save_fd_to_temp as save_pixel_fd,
+    PixelArtOptions,
 };
 use crate::features::presets::{
-    apply_preset_to_state, delete_preset, load_presets, preset_payload_for_tool,
-    render_preset_manager, render_save_preset_dialog, save_preset, tool_id_for_screen,
+    apply_preset_to_state, delete_preset, export_presets, import_presets, load_presets,
+    move_presets, parse_tags, preset_payload_for_tool, preset_qr_payload, render_preset_manager,
+    render_save_preset_dialog, save_preset, save_preset_order, tool_id_for_screen, visible_presets,
+    ImportConflict,
 };
 use crate::features::qr::{handle_qr_action, render_qr_screen};
 use crate::features::qr_transfer::{
@@ -40,45 +89,78 @@ use crate::features::qr_transfer::{
 };
 use crate::features::plotting;
 use crate::features::plotting::render_plotting_screen;
+use crate::features::plugins::{
+    call_plugin, install_plugin, list_plugins, plugin_button, remove_plugin, render_plugin_screen,
+    render_plugins_screen, PluginInfo, PluginOutput, PLUGIN_ACTION_PREFIX,
+};
 use crate::features::mir_scripting::handle_mir_scripting_actions;
 use crate::features::c_scripting::handle_c_scripting_actions;
+use crate::features::cert_inspector::{render_cert_inspector_screen, MAX_CERT_INPUT_BYTES};
+use crate::features::password_strength::render_password_strength_screen;
+use crate::features::date_calc::{apply_date_calc_bindings, render_date_calc_screen};
+use crate::features::jit_benchmark::{
+    render_jit_benchmark_screen, run_jit_benchmark, JitBenchmark, KERNEL_TIME_BUDGET,
+};
 use crate::features::synthesizer::handle_synthesizer_actions;
+use crate::features::job_queue::{render_job_queue_screen, JobTracker};
+use crate::features::usage_stats::render_usage_stats_screen;
+use crate::features::debug_log::render_debug_log_screen;
 use crate::features::scheduler::{
     apply_scheduler_result, drain_events as drain_scheduler_events, render_scheduler_screen,
     runtime as scheduler_runtime,
 };
 use crate::features::sql_engine::{handle_sql_action, render_sql_screen};
-use crate::features::regex_tester::{handle_regex_action, render_regex_tester_screen};
-use crate::features::sensor_utils::{low_pass_angle, low_pass_scalar};
+use crate::features::regex_tester::{
+    apply_regex_bindings, handle_regex_action, handle_regex_explain, render_regex_tester_screen,
+    scan_file_for_regex,
+};
+use crate::features::sensor_utils::{low_pass_angle, low_pass_scalar, parse_calibration};
+use crate::features::gps_export::{export_gps_log, GpsExportFormat};
+use crate::features::sensor_summary::{summarize_logs, SensorLogSummary};
 use crate::features::sensor_logger::{
     apply_status_from_bindings, parse_bindings as parse_sensor_bindings,
-    render_sensor_logger_screen,
+    render_sensor_logger_screen, SensorBackend,
 };
 use crate::features::text_viewer::{apply_text_view_result, load_text_for_worker, TextViewLoadResult, TextViewSource};
 use crate::features::text_viewer::guess_language_from_path;
-use crate::features::text_tools::{handle_text_action, render_text_tools_screen, TextAction};
+use crate::features::text_tools::{
+    handle_text_action, render_text_tools_screen, url_decode, TextAction,
+};
 use crate::features::text_viewer::render_text_viewer_screen;
 use crate::features::uuid_gen::{handle_uuid_action, render_uuid_screen};
-use crate::ui::render_multi_hash_screen;
+use crate::features::watermark::{
+    render_watermark_screen, watermark_files, WatermarkMode, WatermarkOptions, WatermarkOutcome,
+    WatermarkSource,
+};
+use crate::ui::{render_multi_hash_screen, ConfirmDialog as UiConfirmDialog};
+use crate::ui_patch::{self, PATCH_TYPE};
 
 use crate::{
-    i18n,
-    state::{AppState, DitheringMode, DitheringPalette, MultiHashResults, PlotType, Screen}
+    i18n, logging,
+    state::{
+        AppState, DitheringMode, DitheringPalette, JobProgress, MathHistoryEntry, MathResultFormat,
+        MultiHashResults, PendingConfirmation, PixelArtUpscaler, PlotType, RegexFileResult,
+        RegexFlags, Screen, ThemeMode, RECENT_TOOLS_MAX,
+    }
 };
-use jni::objects::{JClass, JString};
-use jni::sys::jstring;
+use chrono::Utc;
+use jni::objects::{JByteArray, JClass, JString};
+use jni::sys::{jbyteArray, jstring};
 use jni::JNIEnv;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap},
     fs::File,
+    hash::{Hash, Hasher},
     io::Read,
     os::unix::io::{FromRawFd, RawFd},
     ptr,
+    cell::Cell,
     sync::{mpsc, Mutex, MutexGuard, OnceLock},
     thread,
     str::FromStr,
+    time::Instant,
 };
 
 #[cfg(test)]
@@ -90,7 +172,22 @@ use std::{
 struct GlobalState {
     ui: Mutex<AppState>,
     worker: OnceLock<WorkerRuntime>,
+    /// Runs plugin code only, so a slow plugin never holds up the built-in tools.
+    plugin_worker: OnceLock<WorkerRuntime>,
     notifications: Mutex<Vec<WorkerResult>>,
+    /// Worker jobs by id, for the job queue screen and cancellation.
+    jobs: Mutex<JobTracker>,
+    /// Set once the persisted state has been restored or superseded by a snapshot.
+    restored: OnceLock<()>,
+    /// Digest of the last state written to disk, to skip rewriting unchanged state.
+    persisted_digest: Mutex<Option<u64>>,
+    /// Last tree sent to a client that accepts patches, to diff the next one against.
+    sent_ui: Mutex<Option<SentUi>>,
+}
+
+struct SentUi {
+    revision: u64,
+    tree: Value,
 }
 
 impl GlobalState {
@@ -99,7 +196,12 @@ impl GlobalState {
         Self {
             ui: Mutex::new(AppState::new()),
             worker: OnceLock::new(),
+            plugin_worker: OnceLock::new(),
             notifications: Mutex::new(Vec::new()),
+            jobs: Mutex::new(JobTracker::new()),
+            restored: OnceLock::new(),
+            persisted_digest: Mutex::new(None),
+            sent_ui: Mutex::new(None),
         }
     }
 
@@ -114,7 +216,13 @@ impl GlobalState {
     }
 
     fn worker(&self) -> &WorkerRuntime {
-        self.worker.get_or_init(WorkerRuntime::new)
+        self.worker
+            .get_or_init(|| WorkerRuntime::new("kistaverk-worker"))
+    }
+
+    fn plugin_worker(&self) -> &WorkerRuntime {
+        self.plugin_worker
+            .get_or_init(|| WorkerRuntime::new("kistaverk-plugins"))
     }
 
     fn push_worker_result(&self, result: WorkerResult) {
@@ -123,28 +231,135 @@ impl GlobalState {
         }
     }
 
+    fn jobs_lock(&self) -> MutexGuard<'_, JobTracker> {
+        // The tracker is only bookkeeping; a panic mid-update leaves it usable.
+        self.jobs
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Runs `job` unless it was cancelled while queued, and delivers its result unless it
+    /// was cancelled while running.
+    fn run_tracked_job(&self, id: u64, job: WorkerJob) {
+        let label = job.label();
+        if !self.jobs_lock().start(id) {
+            logging::debug("worker", format!("#{id} {label} skipped"));
+            return;
+        }
+        let started = Instant::now();
+        CURRENT_JOB.with(|current| current.set(Some(id)));
+        let result = run_worker_job(job);
+        CURRENT_JOB.with(|current| current.set(None));
+        let elapsed_ms = started.elapsed().as_millis();
+        if self.jobs_lock().finish(id) {
+            logging::info("worker", format!("#{id} {label} done in {elapsed_ms} ms"));
+            self.push_worker_result(result);
+        } else {
+            logging::debug("worker", format!("#{id} {label} result dropped"));
+        }
+    }
+
+    /// Queues a progress update, replacing the previous one for the same job if the UI has
+    /// not picked it up yet so a fast job cannot flood the queue.
+    fn push_progress(&self, update: WorkerResult) {
+        let WorkerResult::WorkerProgress { job_id, .. } = &update else {
+            return self.push_worker_result(update);
+        };
+        if let Ok(mut guard) = self.notifications.lock() {
+            if let Some(pos) = guard.iter().rposition(
+                |r| matches!(r, WorkerResult::WorkerProgress { job_id: id, .. } if id == job_id),
+            ) {
+                guard.remove(pos);
+            }
+            guard.push(update);
+        }
+    }
+
     fn drain_worker_results(&self) -> Vec<WorkerResult> {
         self.notifications
             .lock()
             .map(|mut q| q.drain(..).collect())
             .unwrap_or_default()
     }
+
+    /// Numbers `ui` as the next revision for a client holding `client_revision` (0 when it
+    /// holds none), answering with a patch when the client has the previous tree and the
+    /// ops carry fewer nodes than the tree. Clients that send no revision get the plain tree.
+    fn encode_ui(&self, ui: Value, client_revision: Option<u64>) -> Value {
+        let Some(client_revision) = client_revision else {
+            return ui;
+        };
+        if matches!(
+            ui.get("type").and_then(Value::as_str),
+            Some("Snapshot" | PERSISTED_TYPE)
+        ) {
+            return ui;
+        }
+        let mut sent = self
+            .sent_ui
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let revision = sent.as_ref().map_or(0, |s| s.revision) + 1;
+        let ops = sent
+            .as_ref()
+            .filter(|s| s.revision == client_revision)
+            .and_then(|s| ui_patch::diff(&s.tree, &ui))
+            .filter(|ops| ui_patch::ops_node_count(ops) < ui_patch::node_count(&ui));
+        let reply = match ops {
+            Some(ops) => json!({
+                "type": PATCH_TYPE,
+                "base": client_revision,
+                "ui_revision": revision,
+                "ops": ops,
+            }),
+            None => {
+                let mut full = ui.clone();
+                if let Some(obj) = full.as_object_mut() {
+                    obj.insert("ui_revision".into(), json!(revision));
+                }
+                full
+            }
+        };
+        *sent = Some(SentUi { revision, tree: ui });
+        reply
+    }
+
+    /// Writes the state to disk for a process that gets killed in the background. Runs on
+    /// the client's [`PERSIST_STATE_ACTION`] when the app stops, not after every dispatch.
+    /// Best effort: a failed write only loses state if the process is killed before the next.
+    fn persist_state(&self) {
+        let content = match self.ui.lock() {
+            Ok(state) => state.to_persisted(),
+            Err(_) => return,
+        };
+        let Ok(content) = content else {
+            return;
+        };
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        let digest = hasher.finish();
+        let Ok(mut last) = self.persisted_digest.lock() else {
+            return;
+        };
+        if *last != Some(digest) && save_app_state(&content).is_ok() {
+            *last = Some(digest);
+        }
+    }
 }
 
 struct WorkerRuntime {
     #[cfg_attr(test, allow(dead_code))]
-    sender: mpsc::Sender<WorkerJob>,
+    sender: mpsc::Sender<(u64, WorkerJob)>,
 }
 
 impl WorkerRuntime {
-    fn new() -> Self {
-        let (tx, rx) = mpsc::channel::<WorkerJob>();
+    fn new(name: &str) -> Self {
+        let (tx, rx) = mpsc::channel::<(u64, WorkerJob)>();
         thread::Builder::new()
-            .name("kistaverk-worker".into())
+            .name(name.into())
             .spawn(move || {
-                while let Ok(job) = rx.recv() {
-                    let result = run_worker_job(job);
-                    STATE.get_or_init(GlobalState::new).push_worker_result(result);
+                while let Ok((id, job)) = rx.recv() {
+                    STATE.get_or_init(GlobalState::new).run_tracked_job(id, job);
                 }
             })
             .expect("failed to spawn worker thread");
@@ -152,22 +367,29 @@ impl WorkerRuntime {
         Self { sender: tx }
     }
 
+    fn send(&self, job: WorkerJob) -> Result<(), String> {
+        let global = STATE.get_or_init(GlobalState::new);
+        let id = global.jobs_lock().submit(job.label(), job.result_slot());
+        self.sender.send((id, job)).map_err(|e| {
+            global.jobs_lock().discard(id);
+            logging::error("worker", format!("#{id} could not be queued: {e}"));
+            format!("worker_send_failed:{e}")
+        })
+    }
+
     #[cfg(not(test))]
     fn enqueue(&self, job: WorkerJob) -> Result<(), String> {
-        self.sender
-            .send(job)
-            .map_err(|e| format!("worker_send_failed:{e}"))
+        self.send(job)
     }
 
     #[cfg(test)]
     fn enqueue(&self, job: WorkerJob) -> Result<(), String> {
         if TEST_FORCE_ASYNC_WORKER.load(Ordering::SeqCst) {
-            self.sender
-                .send(job)
-                .map_err(|e| format!("worker_send_failed:{e}"))
+            self.send(job)
         } else {
-            let result = run_worker_job(job);
-            STATE.get_or_init(GlobalState::new).push_worker_result(result);
+            let global = STATE.get_or_init(GlobalState::new);
+            let id = global.jobs_lock().submit(job.label(), job.result_slot());
+            global.run_tracked_job(id, job);
             Ok(())
         }
     }
@@ -179,6 +401,22 @@ enum HashSourceInput {
     Path(String),
 }
 
+impl HashSourceInput {
+    fn as_source(&self) -> HashSource<'_> {
+        match self {
+            HashSourceInput::Fd(fd) => HashSource::RawFd(*fd as RawFd),
+            HashSourceInput::Path(p) => HashSource::Path(p),
+        }
+    }
+}
+
+/// Hashes a worker job's source, reporting how much of it has been read.
+fn hash_with_progress(source: &HashSourceInput, algo: HashAlgo) -> Result<String, String> {
+    compute_hash_with_progress(source.as_source(), algo, |fraction| {
+        report_progress("hash", Some(fraction), None)
+    })
+}
+
 #[derive(Clone, Copy)]
 enum CompressionOp {
     Compress,
@@ -206,13 +444,15 @@ struct HashVerifyJob {
     source: HashSourceInput,
     reference: String,
     algo: HashAlgo,
+    /// Keyed digest with this key instead of `algo`.
+    hmac: Option<(HmacAlgo, Vec<u8>)>,
 }
 
 #[derive(Clone)]
 struct HashVerifyResult {
     computed: String,
     reference: String,
-    algo: HashAlgo,
+    label: &'static str,
 }
 
 #[derive(Clone)]
@@ -252,6 +492,17 @@ enum WorkerJob {
         op: CompressionOp,
         path: String,
     },
+    CompressionBenchmark {
+        path: String,
+    },
+    JitBenchmark,
+    Plugin {
+        plugin_id: String,
+        path: String,
+        action: Option<String>,
+        bindings: String,
+        data: String,
+    },
     Vault {
         op: VaultOp,
         path: String,
@@ -261,11 +512,80 @@ enum WorkerJob {
         source_path: String,
         mode: DitheringMode,
         palette: DitheringPalette,
+        serpentine: bool,
         output_dir: Option<String>,
     },
     PixelArt {
         source_path: String,
-        scale: u32,
+        options: PixelArtOptions,
+    },
+    ImageResize {
+        sources: Vec<String>,
+        options: ResizeOptions,
+        output_dir: String,
+    },
+    ImageEdit {
+        source_path: String,
+        ops: EditOps,
+        output_dir: String,
+    },
+    AsciiArt {
+        source_path: String,
+        columns: u32,
+    },
+    GifExplode {
+        source_path: String,
+        stem: String,
+        output_dir: String,
+    },
+    ExifEdit {
+        source_path: String,
+        name: String,
+        edits: ExifEdits,
+        output_dir: String,
+    },
+    GifAssemble {
+        frames: Vec<String>,
+        delay_ms: u32,
+        stem: String,
+        output_dir: String,
+    },
+    ImageAdjustPreview {
+        base: image::DynamicImage,
+        adjustments: Adjustments,
+    },
+    ImageAdjust {
+        source_path: String,
+        adjustments: Adjustments,
+        output_dir: String,
+    },
+    MetadataStrip {
+        inputs: Vec<StripInput>,
+        output_dir: String,
+    },
+    Watermark {
+        sources: Vec<WatermarkSource>,
+        mark_path: String,
+        /// The mark is the text PNG rendered for this run; delete it afterwards.
+        remove_mark: bool,
+        options: WatermarkOptions,
+        output_dir: String,
+    },
+    Collage {
+        sources: Vec<CollageSource>,
+        layout: CollageLayout,
+        output_dir: String,
+    },
+    ImageHistogram {
+        source_path: String,
+        /// The source is a private copy of a picked fd; delete it once read.
+        remove_source: bool,
+    },
+    RegexFile {
+        path: Option<String>,
+        fd: Option<i32>,
+        pattern: String,
+        flags: RegexFlags,
     },
     PdfOperation(PdfWorkerArgs),
     PdfMergeMany {
@@ -290,6 +610,16 @@ enum WorkerJob {
         path: Option<String>,
         fd: Option<i32>,
         error: Option<String>,
+        name: Option<String>,
+    },
+    FileInfoBatch {
+        paths: Vec<String>,
+        fds: Vec<i32>,
+        names: Vec<String>,
+    },
+    FileInfoBatchHash {
+        index: usize,
+        source: HashSourceInput,
     },
     PdfSelect {
         fd: i32,
@@ -327,64 +657,291 @@ enum WorkerJob {
         use_jit: bool,
         benchmark: bool,
     },
+    MathPlot {
+        request: PlotRequest,
+    },
+    GpsExport {
+        csv_path: String,
+        format: GpsExportFormat,
+    },
+    SensorSegmentGzip {
+        path: String,
+    },
+    SensorSummary {
+        paths: Vec<String>,
+    },
 }
 
 enum WorkerResult {
     Hash {
-        value: Result<String, String>,
+        value: Result<String, AppError>,
     },
     MultiHash {
-        value: Result<MultiHashResults, String>,
+        value: Result<MultiHashResults, AppError>,
     },
     HashVerify {
-        value: Result<HashVerifyResult, String>,
+        value: Result<HashVerifyResult, AppError>,
     },
     Compression {
-        value: Result<String, String>,
+        value: Result<String, AppError>,
+    },
+    CompressionBenchmark {
+        value: Result<GzipBenchmark, AppError>,
+    },
+    JitBenchmark {
+        value: Result<JitBenchmark, AppError>,
+    },
+    Plugin {
+        plugin_id: String,
+        value: Result<PluginOutput, AppError>,
     },
     Vault {
-        value: Result<String, String>,
+        value: Result<String, AppError>,
     },
     Dithering {
-        value: Result<String, String>,
+        value: Result<String, AppError>,
     },
     PixelArt {
-        value: Result<String, String>,
+        value: Result<String, AppError>,
+    },
+    ImageResize {
+        outputs: Vec<Result<ResizeOutput, String>>,
+        output_dir: String,
+    },
+    ImageEdit {
+        value: Result<String, AppError>,
+    },
+    ImageAdjustPreview {
+        adjustments: Adjustments,
+        value: Result<String, AppError>,
+    },
+    AsciiArt {
+        columns: u32,
+        value: Result<AsciiArt, AppError>,
+    },
+    GifExplode {
+        value: Result<ExplodedGif, AppError>,
+    },
+    ExifEdit {
+        value: Result<String, AppError>,
+    },
+    GifAssemble {
+        value: Result<String, AppError>,
+    },
+    ImageAdjust {
+        value: Result<String, AppError>,
+    },
+    MetadataStrip {
+        outcomes: Vec<StripOutcome>,
+    },
+    ImageHistogram {
+        value: Result<HistogramStats, AppError>,
+    },
+    Watermark {
+        outcomes: Vec<WatermarkOutcome>,
+    },
+    Collage {
+        value: Result<String, AppError>,
     },
     PdfOperation {
-        value: Result<PdfWorkerResult, String>,
+        value: Result<PdfWorkerResult, AppError>,
     },
     ArchiveOpen {
-        value: Result<ArchiveOpenResult, String>,
+        value: Result<ArchiveOpenResult, AppError>,
     },
     ArchiveCompress {
-        value: Result<ArchiveCompressResult, String>,
+        value: Result<ArchiveCompressResult, AppError>,
     },
     ArchiveExtract {
         archive_path: String,
-        value: Result<String, String>,
+        value: Result<String, AppError>,
     },
     FileInfo {
-        value: Result<features::file_info::FileInfoResult, String>,
+        value: Result<features::file_info::FileInfoResult, AppError>,
+    },
+    FileInfoBatch {
+        value: Vec<features::file_info::BatchEntry>,
+    },
+    FileInfoBatchHash {
+        index: usize,
+        value: Result<String, AppError>,
     },
     PdfSelect {
-        value: Result<PdfSelectResult, String>,
+        value: Result<PdfSelectResult, AppError>,
     },
     TextViewer {
-        value: Result<TextViewLoadResult, String>,
+        value: Result<TextViewLoadResult, AppError>,
     },
     PdfSetTitle {
-        value: Result<PdfSetTitleResult, String>,
+        value: Result<PdfSetTitleResult, AppError>,
     },
     PdfSign {
-        value: Result<PdfSignResult, String>,
+        value: Result<PdfSignResult, AppError>,
     },
     PdfMergeMany {
-        value: Result<PdfWorkerResult, String>,
+        value: Result<PdfWorkerResult, AppError>,
     },
     CScriptingExecuteResult {
-        value: Result<features::c_scripting::ExecutionResult, String>,
+        value: Result<features::c_scripting::ExecutionResult, AppError>,
     },
+    MathPlot {
+        expression: String,
+        value: Result<PlotOutput, AppError>,
+    },
+    RegexFile {
+        value: Result<RegexFileResult, AppError>,
+    },
+    GpsExport {
+        value: Result<String, AppError>,
+    },
+    SensorSegmentGzip {
+        source: String,
+        value: Result<String, AppError>,
+    },
+    SensorSummary {
+        value: Result<SensorLogSummary, AppError>,
+    },
+    /// Intermediate update from a running job; the job's real result still follows.
+    WorkerProgress {
+        job_id: u64,
+        stage: String,
+        fraction: Option<f32>,
+        message: Option<String>,
+    },
+}
+
+thread_local! {
+    /// Job the current thread is running, so deep inside a job progress can be reported
+    /// without threading the id through every feature function.
+    static CURRENT_JOB: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Reports progress of the job running on this thread; a no-op outside worker jobs.
+fn report_progress(stage: &str, fraction: Option<f32>, message: Option<String>) {
+    let Some(job_id) = CURRENT_JOB.with(Cell::get) else {
+        return;
+    };
+    let fraction = fraction.map(|f| f.clamp(0.0, 1.0));
+    let global = STATE.get_or_init(GlobalState::new);
+    global.jobs_lock().set_progress(job_id, fraction);
+    global.push_progress(WorkerResult::WorkerProgress {
+        job_id,
+        stage: stage.into(),
+        fraction,
+        message,
+    });
+}
+
+impl WorkerJob {
+    /// Name shown on the job queue screen.
+    fn label(&self) -> &'static str {
+        match self {
+            WorkerJob::Hash { .. } => "Hash",
+            WorkerJob::MultiHash { .. } => "Multi-hash",
+            WorkerJob::HashVerify(_) => "Verify hash",
+            WorkerJob::Compression {
+                op: CompressionOp::Compress,
+                ..
+            } => "GZIP compress",
+            WorkerJob::Compression {
+                op: CompressionOp::Decompress,
+                ..
+            } => "GZIP decompress",
+            WorkerJob::CompressionBenchmark { .. } => "GZIP benchmark",
+            WorkerJob::JitBenchmark => "JIT benchmark",
+            WorkerJob::Plugin { .. } => "Plugin",
+            WorkerJob::Vault {
+                op: VaultOp::Encrypt,
+                ..
+            } => "Vault encrypt",
+            WorkerJob::Vault {
+                op: VaultOp::Decrypt,
+                ..
+            } => "Vault decrypt",
+            WorkerJob::Dithering { .. } => "Dithering",
+            WorkerJob::PixelArt { .. } => "Pixel art",
+            WorkerJob::ImageResize { .. } => "Image resize",
+            WorkerJob::ImageEdit { .. } => "Image edit",
+            WorkerJob::AsciiArt { .. } => "ASCII art",
+            WorkerJob::GifExplode { .. } => "GIF frames",
+            WorkerJob::ExifEdit { .. } => "EXIF edit",
+            WorkerJob::GifAssemble { .. } => "GIF assemble",
+            WorkerJob::ImageAdjustPreview { .. } => "Adjustment preview",
+            WorkerJob::ImageAdjust { .. } => "Image adjust",
+            WorkerJob::MetadataStrip { .. } => "Metadata strip",
+            WorkerJob::Watermark { .. } => "Watermark",
+            WorkerJob::Collage { .. } => "Collage",
+            WorkerJob::ImageHistogram { .. } => "Histogram",
+            WorkerJob::RegexFile { .. } => "Regex on file",
+            WorkerJob::PdfOperation(_) => "PDF operation",
+            WorkerJob::PdfMergeMany { .. } => "PDF merge",
+            WorkerJob::ArchiveOpen { .. } => "Open archive",
+            WorkerJob::ArchiveCompress { .. } => "Create archive",
+            WorkerJob::ArchiveExtractAll { .. } => "Extract archive",
+            WorkerJob::ArchiveExtractEntry { .. } => "Extract entry",
+            WorkerJob::FileInfo { .. } => "File info",
+            WorkerJob::FileInfoBatch { .. } => "Batch file info",
+            WorkerJob::FileInfoBatchHash { .. } => "Batch hash",
+            WorkerJob::PdfSelect { .. } => "Open PDF",
+            WorkerJob::TextViewerLoad { .. } => "Load text",
+            WorkerJob::PdfSetTitle { .. } => "PDF title",
+            WorkerJob::PdfSign { .. } => "PDF sign",
+            WorkerJob::CScriptingExecute { .. } => "C script",
+            WorkerJob::MathPlot { .. } => "Plot",
+            WorkerJob::GpsExport { .. } => "GPS export",
+            WorkerJob::SensorSegmentGzip { .. } => "Compress sensor log",
+            WorkerJob::SensorSummary { .. } => "Sensor summary",
+        }
+    }
+
+    /// State a job's result overwrites. Only the newest job submitted for a slot may
+    /// deliver, so a slow earlier run cannot clobber the result of a later request.
+    /// `None` for jobs whose results accumulate instead of replacing each other.
+    fn result_slot(&self) -> Option<&'static str> {
+        Some(match self {
+            WorkerJob::Hash { .. } | WorkerJob::HashVerify(_) => "hash",
+            WorkerJob::MultiHash { .. } => "multi_hash",
+            WorkerJob::Compression { .. } => "compression",
+            WorkerJob::CompressionBenchmark { .. } => "compression_benchmark",
+            WorkerJob::JitBenchmark => "jit_benchmark",
+            WorkerJob::Plugin { .. } => "plugin",
+            WorkerJob::Vault { .. } => "vault",
+            WorkerJob::Dithering { .. } => "dithering",
+            WorkerJob::PixelArt { .. } => "pixel_art",
+            WorkerJob::ImageResize { .. } => "image_resize",
+            WorkerJob::ImageEdit { .. } => "image_edit",
+            WorkerJob::AsciiArt { .. } => "ascii_art",
+            WorkerJob::GifExplode { .. } => "gif_explode",
+            WorkerJob::ExifEdit { .. } => "exif_edit",
+            WorkerJob::GifAssemble { .. } => "gif_assemble",
+            WorkerJob::ImageAdjustPreview { .. } => "image_adjust_preview",
+            WorkerJob::ImageAdjust { .. } => "image_adjust",
+            WorkerJob::MetadataStrip { .. } => "metadata_strip",
+            WorkerJob::Watermark { .. } => "watermark",
+            WorkerJob::Collage { .. } => "collage",
+            WorkerJob::ImageHistogram { .. } => "image_histogram",
+            WorkerJob::RegexFile { .. } => "regex_file",
+            WorkerJob::PdfSelect { .. } => "pdf_select",
+            WorkerJob::PdfOperation(_)
+            | WorkerJob::PdfMergeMany { .. }
+            | WorkerJob::PdfSetTitle { .. }
+            | WorkerJob::PdfSign { .. } => "pdf_output",
+            WorkerJob::ArchiveOpen { .. } => "archive_open",
+            WorkerJob::ArchiveCompress { .. } => "archive_compress",
+            WorkerJob::FileInfo { .. } => "file_info",
+            WorkerJob::FileInfoBatch { .. } => "file_info_batch",
+            WorkerJob::TextViewerLoad { .. } => "text_viewer",
+            WorkerJob::CScriptingExecute { .. } => "c_scripting",
+            WorkerJob::MathPlot { .. } => "math_plot",
+            WorkerJob::GpsExport { .. } => "gps_export",
+            WorkerJob::SensorSummary { .. } => "sensor_summary",
+            // Each extraction, batch row hash and segment gzip targets its own output.
+            WorkerJob::ArchiveExtractAll { .. }
+            | WorkerJob::ArchiveExtractEntry { .. }
+            | WorkerJob::FileInfoBatchHash { .. }
+            | WorkerJob::SensorSegmentGzip { .. } => return None,
+        })
+    }
 }
 
 const COMPASS_SMOOTH_ALPHA: f64 = 0.2;
@@ -395,87 +952,312 @@ fn run_worker_job(job: WorkerJob) -> WorkerResult {
     match job {
         WorkerJob::Hash { source, algo } => {
             test_worker_delay();
-            let value = match source {
-                HashSourceInput::Fd(fd) => {
-                    compute_hash(features::hashes::HashSource::RawFd(fd as RawFd), algo)
-                }
-                HashSourceInput::Path(p) => {
-                    compute_hash(features::hashes::HashSource::Path(&p), algo)
-                }
-            };
-            WorkerResult::Hash { value }
+            let value = hash_with_progress(&source, algo);
+            WorkerResult::Hash {
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::MultiHash {
             source,
             display_path,
         } => {
             test_worker_delay();
-            let value = match source {
-                HashSourceInput::Fd(fd) => compute_all_hashes(
-                    features::hashes::HashSource::RawFd(fd as RawFd),
-                    display_path,
-                ),
-                HashSourceInput::Path(p) => {
-                    compute_all_hashes(features::hashes::HashSource::Path(&p), display_path)
-                }
-            };
-            WorkerResult::MultiHash { value }
+            let value =
+                compute_all_hashes_with_progress(source.as_source(), display_path, |fraction| {
+                    report_progress("hash", Some(fraction), None)
+                });
+            WorkerResult::MultiHash {
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::HashVerify(job) => {
             test_worker_delay();
-            let value = match job.source {
-                HashSourceInput::Fd(fd) => {
-                    compute_hash(features::hashes::HashSource::RawFd(fd as RawFd), job.algo)
-                }
-                HashSourceInput::Path(p) => {
-                    compute_hash(features::hashes::HashSource::Path(&p), job.algo)
+            let value = match &job.hmac {
+                Some((algo, key)) => {
+                    compute_hmac_with_progress(job.source.as_source(), *algo, key, |fraction| {
+                        report_progress("hash", Some(fraction), None)
+                    })
+                    .map(|computed| (computed, algo.label()))
                 }
+                None => hash_with_progress(&job.source, job.algo)
+                    .map(|computed| (computed, hash_label(job.algo))),
             }
-            .map(|computed| HashVerifyResult {
+            .map(|(computed, label)| HashVerifyResult {
                 computed,
                 reference: job.reference,
-                algo: job.algo,
+                label,
             });
-            WorkerResult::HashVerify { value }
+            WorkerResult::HashVerify {
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::Compression { op, path } => {
             test_worker_delay();
             let value = match op {
-                CompressionOp::Compress => {
-                    gzip_compress(&path)
-                        .map(|out| format!("Result saved to: {}", out.display()))
-                }
-                CompressionOp::Decompress => {
-                    gzip_decompress(&path)
-                        .map(|out| format!("Result saved to: {}", out.display()))
-                }
-            };
-            WorkerResult::Compression { value }
+                CompressionOp::Compress => gzip_compress(&path),
+                CompressionOp::Decompress => gzip_decompress(&path),
+            }
+            .map(|out| {
+                record_output(&out);
+                format!("Result saved to: {}", out.display())
+            });
+            WorkerResult::Compression {
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::CompressionBenchmark { path } => {
+            test_worker_delay();
+            WorkerResult::CompressionBenchmark {
+                value: gzip_benchmark(&path).map_err(AppError::from),
+            }
+        }
+        WorkerJob::JitBenchmark => {
+            test_worker_delay();
+            WorkerResult::JitBenchmark {
+                value: run_jit_benchmark(KERNEL_TIME_BUDGET).map_err(AppError::from),
+            }
+        }
+        WorkerJob::Plugin {
+            plugin_id,
+            path,
+            action,
+            bindings,
+            data,
+        } => {
+            let value = call_plugin(
+                std::path::Path::new(&path),
+                action.as_deref(),
+                &bindings,
+                &data,
+            );
+            WorkerResult::Plugin {
+                plugin_id,
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::Vault { op, path, password } => {
             test_worker_delay();
+            let on_progress = |fraction| report_progress("vault", Some(fraction), None);
             let value = match op {
-                VaultOp::Encrypt => features::vault::encrypt_file(&path, &password)
-                    .map(|out| format!("Result saved to: {}", out.display())),
-                VaultOp::Decrypt => features::vault::decrypt_file(&path, &password)
-                    .map(|out| format!("Result saved to: {}", out.display())),
-            };
-            WorkerResult::Vault { value }
+                VaultOp::Encrypt => {
+                    features::vault::encrypt_file_with_progress(&path, &password, on_progress)
+                }
+                VaultOp::Decrypt => {
+                    features::vault::decrypt_file_with_progress(&path, &password, on_progress)
+                }
+            }
+            .map(|out| {
+                record_output(&out);
+                format!("Result saved to: {}", out.display())
+            });
+            WorkerResult::Vault {
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::Dithering {
             source_path,
             mode,
             palette,
+            serpentine,
+            output_dir,
+        } => {
+            test_worker_delay();
+            let value = process_dithering(
+                &source_path,
+                mode,
+                &palette,
+                serpentine,
+                output_dir.as_deref(),
+            );
+            if let Ok(out) = &value {
+                record_output(out);
+            }
+            WorkerResult::Dithering {
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::PixelArt {
+            source_path,
+            options,
+        } => {
+            test_worker_delay();
+            let value = process_pixel_art(&source_path, &options);
+            if let Ok(out) = &value {
+                record_output(out);
+            }
+            WorkerResult::PixelArt {
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::ImageResize {
+            sources,
+            options,
+            output_dir,
+        } => {
+            test_worker_delay();
+            let total = sources.len();
+            let outputs: Vec<_> = sources
+                .iter()
+                .enumerate()
+                .map(|(i, source)| {
+                    report_progress(
+                        "resize",
+                        Some(i as f32 / total as f32),
+                        Some(format!("Resizing {} of {total}", i + 1)),
+                    );
+                    resize_image_file(source, std::path::Path::new(&output_dir), &options)
+                })
+                .collect();
+            for out in outputs.iter().flatten() {
+                record_output(&out.path);
+            }
+            WorkerResult::ImageResize {
+                outputs,
+                output_dir,
+            }
+        }
+        WorkerJob::ImageEdit {
+            source_path,
+            ops,
             output_dir,
         } => {
             test_worker_delay();
-            let value = process_dithering(&source_path, mode, palette, output_dir.as_deref());
-            WorkerResult::Dithering { value }
+            let value = process_image_edit(&source_path, &ops, &output_dir);
+            if let Ok(out) = &value {
+                record_output(out);
+            }
+            WorkerResult::ImageEdit {
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::AsciiArt {
+            source_path,
+            columns,
+        } => {
+            test_worker_delay();
+            let value = convert_image_file(&source_path, columns);
+            WorkerResult::AsciiArt {
+                columns,
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::GifExplode {
+            source_path,
+            stem,
+            output_dir,
+        } => {
+            test_worker_delay();
+            let value = explode_gif(&source_path, &stem, &output_dir);
+            if let Ok(exploded) = &value {
+                record_output(&exploded.frames_dir);
+            }
+            WorkerResult::GifExplode {
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::ExifEdit {
+            source_path,
+            name,
+            edits,
+            output_dir,
+        } => {
+            test_worker_delay();
+            let value = save_exif_edits(&source_path, &name, &edits, &output_dir);
+            if let Ok(out) = &value {
+                record_output(out);
+            }
+            WorkerResult::ExifEdit {
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::GifAssemble {
+            frames,
+            delay_ms,
+            stem,
+            output_dir,
+        } => {
+            test_worker_delay();
+            let value = assemble_gif(&frames, delay_ms, &stem, &output_dir);
+            if let Ok(out) = &value {
+                record_output(out);
+            }
+            WorkerResult::GifAssemble {
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::ImageAdjustPreview { base, adjustments } => {
+            test_worker_delay();
+            let value = render_adjusted_preview(&base, &adjustments);
+            WorkerResult::ImageAdjustPreview {
+                adjustments,
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::ImageAdjust {
+            source_path,
+            adjustments,
+            output_dir,
+        } => {
+            test_worker_delay();
+            let value = process_image_adjust(&source_path, &adjustments, &output_dir);
+            if let Ok(out) = &value {
+                record_output(out);
+            }
+            WorkerResult::ImageAdjust {
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::MetadataStrip { inputs, output_dir } => {
+            test_worker_delay();
+            let outcomes = strip_files(&inputs, std::path::Path::new(&output_dir));
+            for path in outcomes.iter().filter_map(|o| o.output_path.as_deref()) {
+                record_output(path);
+            }
+            WorkerResult::MetadataStrip { outcomes }
+        }
+        WorkerJob::Watermark {
+            sources,
+            mark_path,
+            remove_mark,
+            options,
+            output_dir,
+        } => {
+            test_worker_delay();
+            let outcomes = watermark_files(&sources, &mark_path, &options, &output_dir);
+            if remove_mark {
+                let _ = std::fs::remove_file(&mark_path);
+            }
+            for path in outcomes.iter().filter_map(|o| o.value.as_ref().ok()) {
+                record_output(path);
+            }
+            WorkerResult::Watermark { outcomes }
+        }
+        WorkerJob::Collage {
+            sources,
+            layout,
+            output_dir,
+        } => {
+            test_worker_delay();
+            let value = build_collage(&sources, &layout, &output_dir);
+            if let Ok(out) = &value {
+                record_output(out);
+            }
+            WorkerResult::Collage {
+                value: value.map_err(AppError::from),
+            }
         }
-        WorkerJob::PixelArt { source_path, scale } => {
+        WorkerJob::ImageHistogram {
+            source_path,
+            remove_source,
+        } => {
             test_worker_delay();
-            let value = process_pixel_art(&source_path, scale);
-            WorkerResult::PixelArt { value }
+            let value = analyze_image_file(&source_path);
+            if remove_source {
+                let _ = std::fs::remove_file(&source_path);
+            }
+            WorkerResult::ImageHistogram {
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::PdfOperation(args) => {
             test_worker_delay();
@@ -494,16 +1276,24 @@ fn run_worker_job(job: WorkerJob) -> WorkerResult {
                 selected_pages: args.selected_pages.clone(),
                 source_uri: args.primary_uri.clone(),
             });
-            WorkerResult::PdfOperation { value }
+            if let Ok(res) = &value {
+                record_output(&res.out_path);
+            }
+            WorkerResult::PdfOperation {
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::ArchiveOpen { fd, path } => {
             test_worker_delay();
             let value = archive::open_archive_from_fd(fd as RawFd, path.as_deref());
-            WorkerResult::ArchiveOpen { value }
+            WorkerResult::ArchiveOpen {
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::ArchiveCompress { source_path } => {
             test_worker_delay();
             let value = archive::create_archive(&source_path).and_then(|out| {
+                record_output(&out);
                 let open_res = archive::open_archive_from_path(
                     out.to_string_lossy().as_ref(),
                 )?;
@@ -512,19 +1302,29 @@ fn run_worker_job(job: WorkerJob) -> WorkerResult {
                     open: open_res,
                 })
             });
-            WorkerResult::ArchiveCompress { value }
+            WorkerResult::ArchiveCompress {
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::ArchiveExtractAll { archive_path } => {
             test_worker_delay();
             let value = {
                 let dest = archive::archive_output_root(&archive_path);
-                archive::extract_all(&archive_path, &dest).map(|count| {
+                let on_entry = |i: usize, total: usize| {
+                    report_progress(
+                        "extract",
+                        Some(i as f32 / total as f32),
+                        Some(format!("Extracting {} of {total}", i + 1)),
+                    )
+                };
+                archive::extract_all(&archive_path, &dest, on_entry).map(|count| {
+                    record_output(&dest);
                     format!("Extracted {count} entries to {}", dest.display())
                 })
             };
             WorkerResult::ArchiveExtract {
                 archive_path,
-                value,
+                value: value.map_err(AppError::from),
             }
         }
         WorkerJob::ArchiveExtractEntry {
@@ -534,26 +1334,67 @@ fn run_worker_job(job: WorkerJob) -> WorkerResult {
             test_worker_delay();
             let value = {
                 let dest = archive::archive_output_root(&archive_path);
-                archive::extract_entry(&archive_path, &dest, index)
-                    .map(|out| format!("Extracted to {}", out.display()))
+                archive::extract_entry(&archive_path, &dest, index).map(|out| {
+                    record_output(&dest);
+                    format!("Extracted to {}", out.display())
+                })
             };
             WorkerResult::ArchiveExtract {
                 archive_path,
-                value,
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::RegexFile {
+            path,
+            fd,
+            pattern,
+            flags,
+        } => {
+            test_worker_delay();
+            WorkerResult::RegexFile {
+                value: scan_file_for_regex(path.as_deref(), fd, &pattern, flags)
+                    .map_err(AppError::from),
             }
         }
-        WorkerJob::FileInfo { path, fd, error } => {
+        WorkerJob::FileInfo {
+            path,
+            fd,
+            error,
+            name,
+        } => {
             test_worker_delay();
             let value = if let Some(err) = error {
                 Err(err)
             } else if let Some(fd) = fd {
-                Ok(file_info_from_fd(fd as RawFd))
+                Ok(file_info_from_fd(fd as RawFd, name.as_deref()))
             } else if let Some(p) = path {
                 Ok(file_info_from_path(&p))
             } else {
                 Err("missing_path".into())
             };
-            WorkerResult::FileInfo { value }
+            WorkerResult::FileInfo {
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::FileInfoBatch { paths, fds, names } => {
+            test_worker_delay();
+            WorkerResult::FileInfoBatch {
+                value: features::file_info::batch_file_info(&paths, &fds, &names, |i, total| {
+                    report_progress(
+                        "inspect",
+                        Some(i as f32 / total as f32),
+                        Some(format!("Inspecting {} of {total}", i + 1)),
+                    )
+                }),
+            }
+        }
+        WorkerJob::FileInfoBatchHash { index, source } => {
+            test_worker_delay();
+            let value = hash_with_progress(&source, HashAlgo::Sha256);
+            WorkerResult::FileInfoBatchHash {
+                index,
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::PdfSelect { fd, uri } => {
             test_worker_delay();
@@ -566,7 +1407,9 @@ fn run_worker_job(job: WorkerJob) -> WorkerResult {
                 }),
                 Err(e) => Err(e),
             };
-            WorkerResult::PdfSelect { value }
+            WorkerResult::PdfSelect {
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::TextViewerLoad {
             source,
@@ -576,12 +1419,19 @@ fn run_worker_job(job: WorkerJob) -> WorkerResult {
         } => {
             test_worker_delay();
             let value = load_text_for_worker(source, offset, force_text, can_page);
-            WorkerResult::TextViewer { value }
+            WorkerResult::TextViewer {
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::PdfSetTitle { fd, uri, title } => {
             test_worker_delay();
             let value = perform_pdf_set_title(fd as RawFd, uri.as_deref(), title.as_deref());
-            WorkerResult::PdfSetTitle { value }
+            if let Ok(res) = &value {
+                record_output(&res.out_path);
+            }
+            WorkerResult::PdfSetTitle {
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::PdfSign {
             fd,
@@ -620,7 +1470,12 @@ fn run_worker_job(job: WorkerJob) -> WorkerResult {
                 title: res.title,
                 source_uri: res.source_uri,
             });
-            WorkerResult::PdfSign { value }
+            if let Ok(res) = &value {
+                record_output(&res.out_path);
+            }
+            WorkerResult::PdfSign {
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::PdfMergeMany { fds, uris } => {
             test_worker_delay();
@@ -631,7 +1486,12 @@ fn run_worker_job(job: WorkerJob) -> WorkerResult {
                 selected_pages: Vec::new(),
                 source_uri: uris.first().cloned(),
             });
-            WorkerResult::PdfMergeMany { value }
+            if let Ok(res) = &value {
+                record_output(&res.out_path);
+            }
+            WorkerResult::PdfMergeMany {
+                value: value.map_err(AppError::from),
+            }
         }
         WorkerJob::CScriptingExecute {
             source,
@@ -640,8 +1500,54 @@ fn run_worker_job(job: WorkerJob) -> WorkerResult {
             benchmark,
         } => {
             test_worker_delay();
-            let value = features::c_scripting::execute_c_code(source, args, use_jit, benchmark);
-            WorkerResult::CScriptingExecuteResult { value }
+            let value = features::c_scripting::execute_with_budget(
+                source,
+                args,
+                use_jit,
+                benchmark,
+                features::c_scripting::TIME_BUDGET_MS,
+            );
+            WorkerResult::CScriptingExecuteResult {
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::MathPlot { request } => {
+            test_worker_delay();
+            let out_dir = features::storage::output_dir_for(None);
+            let value = render_plot_png(&request, &out_dir);
+            WorkerResult::MathPlot {
+                expression: format!(
+                    "plot({}, {}, {})",
+                    request.expression, request.x_min, request.x_max
+                ),
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::GpsExport { csv_path, format } => {
+            test_worker_delay();
+            let value = export_gps_log(&csv_path, format);
+            WorkerResult::GpsExport {
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::SensorSegmentGzip { path } => {
+            test_worker_delay();
+            // The plain CSV is only dropped once the .gz is fully written.
+            let value = gzip_compress(&path).and_then(|out| {
+                std::fs::remove_file(&path).map_err(|e| format!("gzip_cleanup_failed:{e}"))?;
+                Ok(out.to_string_lossy().into_owned())
+            });
+            WorkerResult::SensorSegmentGzip {
+                source: path,
+                value: value.map_err(AppError::from),
+            }
+        }
+        WorkerJob::SensorSummary { paths } => {
+            test_worker_delay();
+            let value = summarize_logs(&paths);
+            WorkerResult::SensorSummary {
+                value: value.map_err(AppError::from),
+            }
         }
     }
 }
@@ -666,7 +1572,7 @@ fn test_worker_delay() {
 #[cfg(not(test))]
 fn test_worker_delay() {}
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Default)]
 struct Command {
     action: String,
     path: Option<String>,
@@ -685,6 +1591,8 @@ struct Command {
     primary_fd: Option<i32>,
     primary_path: Option<String>,
     angle_radians: Option<f64>,
+    /// UI revision the client holds; present only when it can apply patches.
+    ui_revision: Option<u64>,
 }
 
 #[derive(Debug)]
@@ -692,8 +1600,23 @@ pub(crate) enum Action {
     Init { bindings: HashMap<String, String> },
     Reset,
     Back,
+    ConfirmPending,
+    CancelPending,
     HomeFilter { query: String },
+    HomeEditFavorites,
+    FavoriteToggle {
+        id: String,
+    },
+    FavoriteMove {
+        id: String,
+        delta: i32,
+    },
+    SectionToggle {
+        key: String,
+        expanded: bool,
+    },
     SetLocale { locale: String },
+    SetTheme { mode: ThemeMode },
     RulerScreen,
     ShaderDemo,
     LoadShader {
@@ -720,6 +1643,123 @@ pub(crate) enum Action {
         fd: Option<i32>,
         error: Option<String>,
     },
+    ImageResize {
+        bindings: HashMap<String, String>,
+    },
+    ImageResizeBatch {
+        bindings: HashMap<String, String>,
+    },
+    ImageEditScreen,
+    ImageEditPick {
+        path: Option<String>,
+        fd: Option<i32>,
+        error: Option<String>,
+    },
+    ImageEditCrop {
+        bindings: HashMap<String, String>,
+    },
+    ImageEditRotate {
+        degrees: i32,
+    },
+    ImageEditFlip {
+        horizontal: bool,
+    },
+    ImageEditReset,
+    ImageEditApply,
+    ImageAdjustScreen,
+    ImageAdjustPick {
+        path: Option<String>,
+        fd: Option<i32>,
+        error: Option<String>,
+    },
+    ImageAdjustUpdate {
+        bindings: HashMap<String, String>,
+    },
+    ImageAdjustReset,
+    ImageAdjustApply {
+        bindings: HashMap<String, String>,
+    },
+    AsciiArtScreen,
+    AsciiArtPick {
+        path: Option<String>,
+        fd: Option<i32>,
+        error: Option<String>,
+        name: Option<String>,
+    },
+    AsciiArtSync {
+        bindings: HashMap<String, String>,
+    },
+    AsciiArtView,
+    AsciiArtExport,
+    GifFramesScreen,
+    GifFramesPick {
+        path: Option<String>,
+        fd: Option<i32>,
+        error: Option<String>,
+        name: Option<String>,
+    },
+    GifFramesAssemble {
+        bindings: HashMap<String, String>,
+    },
+    ExifEditScreen,
+    ExifEditPick {
+        path: Option<String>,
+        fd: Option<i32>,
+        error: Option<String>,
+        name: Option<String>,
+    },
+    ExifEditSync {
+        bindings: HashMap<String, String>,
+    },
+    ExifEditSave {
+        bindings: HashMap<String, String>,
+    },
+    MetadataStripScreen,
+    ImageHistogramScreen,
+    WatermarkScreen,
+    WatermarkPickImages {
+        paths: Vec<String>,
+        fds: Vec<i32>,
+        names: Vec<String>,
+        error: Option<String>,
+    },
+    WatermarkPickLogo {
+        path: Option<String>,
+        fd: Option<i32>,
+        error: Option<String>,
+        name: Option<String>,
+    },
+    WatermarkSync {
+        bindings: HashMap<String, String>,
+    },
+    WatermarkApply {
+        bindings: HashMap<String, String>,
+    },
+    CollageScreen,
+    CollagePickImages {
+        paths: Vec<String>,
+        fds: Vec<i32>,
+        names: Vec<String>,
+        error: Option<String>,
+    },
+    CollageSync {
+        bindings: HashMap<String, String>,
+    },
+    CollageBuild {
+        bindings: HashMap<String, String>,
+    },
+    ImageHistogramPick {
+        path: Option<String>,
+        fd: Option<i32>,
+        error: Option<String>,
+        name: Option<String>,
+    },
+    MetadataStripPick {
+        paths: Vec<String>,
+        fds: Vec<i32>,
+        names: Vec<String>,
+        error: Option<String>,
+    },
     DitheringScreen,
     DitheringPickImage {
         path: Option<String>,
@@ -732,6 +1772,17 @@ pub(crate) enum Action {
     DitheringSetPalette {
         palette: DitheringPalette,
     },
+    DitheringSetSerpentine {
+        enabled: bool,
+    },
+    DitheringSetCustomPalette {
+        input: String,
+    },
+    DitheringPaletteFromImage {
+        path: Option<String>,
+        fd: Option<i32>,
+        error: Option<String>,
+    },
     DitheringApply {
         loading_only: bool,
     },
@@ -740,6 +1791,13 @@ pub(crate) enum Action {
         path: Option<String>,
         fd: Option<i32>,
         reference: Option<String>,
+        hmac_key: Option<String>,
+    },
+    HashHmacConfig {
+        enabled: Option<bool>,
+        algo: Option<HmacAlgo>,
+        key: Option<String>,
+        key_hex: Option<bool>,
     },
     HashVerifyPaste {
         reference: Option<String>,
@@ -790,8 +1848,60 @@ pub(crate) enum Action {
         path: Option<String>,
         fd: Option<i32>,
         error: Option<String>,
+        name: Option<String>,
+    },
+    FileInfoBatch {
+        paths: Vec<String>,
+        fds: Vec<i32>,
+        names: Vec<String>,
+    },
+    FileInfoBatchHash {
+        index: usize,
+        fd: Option<i32>,
+    },
+    FileInfoHex {
+        path: Option<String>,
+        fd: Option<i32>,
+        direction: String,
+        offset: Option<u64>,
+        length: Option<usize>,
     },
     FileInfoScreen,
+    RecentFilesClear,
+    JobQueueScreen,
+    JobQueueRefresh,
+    JobQueueClear,
+    PollProgress,
+    JobCancel {
+        id: Option<u64>,
+    },
+    UsageStatsScreen,
+    UsageStatsToggle {
+        enabled: bool,
+    },
+    HomeSortByUsageToggle {
+        enabled: bool,
+    },
+    UsageStatsClear,
+    DebugLogScreen,
+    DebugLogView,
+    DebugLogExport,
+    DebugLogClear,
+    CleanupScreen,
+    CleanupRefresh,
+    CleanupToggle {
+        index: usize,
+        selected: Option<bool>,
+    },
+    CleanupSelectAll,
+    CleanupSelectStale,
+    CleanupSelectNone,
+    CleanupDeleteSelected,
+    CleanupSavePolicy {
+        auto_clean: bool,
+        max_age_days: Option<String>,
+    },
+    CleanupCleanStale,
     TextToolsScreen {
         bindings: HashMap<String, String>,
     },
@@ -836,6 +1946,9 @@ pub(crate) enum Action {
         page: u32,
     },
     PdfPageClose,
+    PdfTabSelect {
+        tab: PdfTab,
+    },
     PdfSign {
         fd: Option<i32>,
         uri: Option<String>,
@@ -863,6 +1976,7 @@ pub(crate) enum Action {
     },
     KotlinImageBatchPick {
         paths: Vec<String>,
+        fds: Vec<i32>,
     },
     KotlinImageBatchRemove {
         path: String,
@@ -909,6 +2023,7 @@ pub(crate) enum Action {
     },
     TextViewerToggleTheme,
     TextViewerToggleLineNumbers,
+    TextViewerToggleMarkdownPreview,
     TextViewerLoadAnyway,
     TextViewerLoadMore,
     TextViewerLoadPrev,
@@ -958,13 +2073,27 @@ pub(crate) enum Action {
     SensorLoggerStart {
         bindings: HashMap<String, String>,
     },
-    SensorLoggerStop,
+    SensorLoggerStop {
+        bindings: HashMap<String, String>,
+    },
     SensorLoggerShare,
     SensorLoggerStatus {
         bindings: HashMap<String, String>,
     },
+    SensorLoggerExport {
+        format: Option<String>,
+    },
+    SensorLoggerSegment {
+        path: Option<String>,
+    },
+    SensorLoggerPause,
+    SensorLoggerResume,
+    SensorLoggerFormat {
+        format: Option<String>,
+    },
     Increment,
     Snapshot,
+    PersistState,
     Restore {
         snapshot: String,
     },
@@ -995,6 +2124,11 @@ pub(crate) enum Action {
         fd: Option<i32>,
         error: Option<String>,
     },
+    GzipBenchmark {
+        path: Option<String>,
+        fd: Option<i32>,
+        error: Option<String>,
+    },
     SystemInfoScreen,
     SystemInfoUpdate {
         bindings: HashMap<String, String>,
@@ -1011,6 +2145,9 @@ pub(crate) enum Action {
         loading_only: bool,
     },
     CompassDemo,
+    SensorCalibrationSet {
+        bindings: HashMap<String, String>,
+    },
     CompassSet {
         angle_radians: f64,
         error: Option<String>,
@@ -1036,6 +2173,7 @@ pub(crate) enum Action {
     },
     PresetSave {
         name: Option<String>,
+        tags: Option<String>,
     },
     PresetLoad {
         id: String,
@@ -1043,6 +2181,27 @@ pub(crate) enum Action {
     PresetDelete {
         id: String,
     },
+    PresetShareQr {
+        id: String,
+    },
+    PresetToggleGroup {
+        tool_id: String,
+    },
+    PresetReorderMode,
+    PresetReorder {
+        order: Vec<String>,
+    },
+    PresetExport {
+        all: bool,
+    },
+    PresetSetImportConflict {
+        conflict: ImportConflict,
+    },
+    PresetImport {
+        path: Option<String>,
+        fd: Option<i32>,
+        error: Option<String>,
+    },
     PixelArtScreen,
     PixelArtPick {
         path: Option<String>,
@@ -1052,6 +2211,16 @@ pub(crate) enum Action {
     PixelArtSetScale {
         scale: u32,
     },
+    PixelArtSetUpscaler {
+        upscaler: PixelArtUpscaler,
+    },
+    PixelArtSetCrt {
+        enabled: bool,
+    },
+    PixelArtSetPaletteLock {
+        palette: Option<DitheringPalette>,
+    },
+    PixelArtLockDitheringPalette,
     PixelArtApply {
         loading_only: bool,
     },
@@ -1060,11 +2229,33 @@ pub(crate) enum Action {
         bindings: HashMap<String, String>,
     },
     RegexClear,
+    RegexExplain {
+        bindings: HashMap<String, String>,
+    },
+    RegexFile {
+        path: Option<String>,
+        fd: Option<i32>,
+        error: Option<String>,
+        bindings: HashMap<String, String>,
+    },
     MathToolScreen,
+    MathConstantsScreen,
+    MathInsertConstant {
+        bindings: HashMap<String, String>,
+    },
     MathCalculate {
         bindings: HashMap<String, String>,
     },
     MathClearHistory,
+    MathRecall {
+        bindings: HashMap<String, String>,
+    },
+    MathSetHistoryCap {
+        bindings: HashMap<String, String>,
+    },
+    MathSetFormat {
+        format: MathResultFormat,
+    },
     FunctionAnalysisAction {
         action: String,
     },
@@ -1074,7 +2265,19 @@ pub(crate) enum Action {
         bindings: HashMap<String, String>,
     },
     UuidScreen,
-    UuidGenerate,
+    UuidGenerate {
+        bindings: HashMap<String, String>,
+    },
+    UlidGenerate,
+    RandomBytesGenerate {
+        bindings: HashMap<String, String>,
+    },
+    UuidInspect {
+        bindings: HashMap<String, String>,
+    },
+    NanoidGenerate {
+        bindings: HashMap<String, String>,
+    },
     RandomStringGenerate {
         bindings: HashMap<String, String>,
     },
@@ -1113,6 +2316,28 @@ pub(crate) enum Action {
         object: Option<String>,
     },
     JwtScreen { bindings: HashMap<String, String> },
+    CertInspectorScreen,
+    CertInspect {
+        input: Option<String>,
+    },
+    CertOpen {
+        path: Option<String>,
+        fd: Option<i32>,
+        error: Option<String>,
+        name: Option<String>,
+    },
+    PasswordStrengthScreen,
+    PasswordStrengthCheck {
+        password: Option<String>,
+        show: Option<bool>,
+    },
+    DateCalcScreen,
+    DateCalcCompute {
+        bindings: HashMap<String, String>,
+    },
+    DateCalcSwapTz {
+        bindings: HashMap<String, String>,
+    },
     JwtDecode {
         token: Option<String>,
     },
@@ -1150,9 +2375,33 @@ pub(crate) enum Action {
     },
     CScriptingClear,
     CScriptingLoadExample,
+    CScriptingOpen {
+        path: Option<String>,
+        fd: Option<i32>,
+        error: Option<String>,
+    },
     CScriptingToggleJit { enabled: bool },
     CScriptingToggleBenchmark { enabled: bool },
     CScriptingToggleThread { enabled: bool },
+    JitBenchmarkScreen,
+    JitBenchmarkRun,
+    PluginsScreen,
+    PluginInstall {
+        path: Option<String>,
+        fd: Option<i32>,
+        error: Option<String>,
+        name: Option<String>,
+    },
+    PluginRemove {
+        id: String,
+    },
+    PluginOpen {
+        id: String,
+    },
+    PluginAction {
+        action: String,
+        bindings: HashMap<String, String>,
+    },
     SynthesizerScreen,
     SynthesizerPlay,
     SynthesizerStop,
@@ -1201,6 +2450,69 @@ impl Drop for FdHandle {
     }
 }
 
+/// Resolves `open_tool` into the command the tool's home menu entry sends. The tool comes
+/// from a `kistaverk://<tool_id>?key=value` deep link in the `uri` binding or from the
+/// `tool` binding; a `path` query parameter becomes the command path, others bindings.
+fn open_tool_command(
+    mut bindings: HashMap<String, String>,
+    mut path: Option<String>,
+    fd: Option<i32>,
+) -> Result<Command, String> {
+    let tool_id = match bindings.remove("uri") {
+        Some(uri) => {
+            let rest = uri
+                .strip_prefix("kistaverk://")
+                .ok_or_else(|| "open_tool_bad_uri".to_string())?;
+            let (tool, query) = rest.split_once('?').unwrap_or((rest, ""));
+            for pair in query.split('&').filter(|p| !p.is_empty()) {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                let (key, value) = (url_decode(key)?, url_decode(value)?);
+                if key == "path" {
+                    path = Some(value);
+                } else {
+                    bindings.insert(key, value);
+                }
+            }
+            tool.trim_end_matches('/').to_string()
+        }
+        None => bindings
+            .remove("tool")
+            .ok_or_else(|| "missing_tool_id".to_string())?,
+    };
+    let feature = feature_catalog()
+        .into_iter()
+        .find(|f| f.id == tool_id)
+        .ok_or_else(|| format!("unknown_tool:{tool_id}"))?;
+    if feature.requires_file_picker && path.is_none() && fd.is_none() {
+        return Err(format!("open_tool_missing_path:{tool_id}"));
+    }
+    Ok(Command {
+        action: feature.action.to_string(),
+        path,
+        fd,
+        bindings: Some(bindings),
+        ..Command::default()
+    })
+}
+
+fn favorite_id(bindings: &HashMap<String, String>) -> Result<String, String> {
+    let id = bindings
+        .get("favorite_id")
+        .ok_or_else(|| "missing_favorite_id".to_string())?;
+    if !feature_catalog().iter().any(|f| f.id == id) {
+        return Err(format!("unknown_tool:{id}"));
+    }
+    Ok(id.clone())
+}
+
+fn plugin_id(bindings: &HashMap<String, String>) -> Result<String, String> {
+    bindings
+        .get("plugin_id")
+        .filter(|id| !id.is_empty())
+        .cloned()
+        .ok_or_else(|| "missing_plugin_id".to_string())
+}
+
 fn parse_action(command: Command) -> Result<Action, String> {
     let Command {
         action,
@@ -1220,6 +2532,7 @@ fn parse_action(command: Command) -> Result<Action, String> {
         primary_fd,
         primary_path,
         angle_radians,
+        ui_revision: _,
     } = command;
 
     let bindings = bindings.unwrap_or_default();
@@ -1227,14 +2540,49 @@ fn parse_action(command: Command) -> Result<Action, String> {
 
     match action.as_str() {
         "init" => Ok(Action::Init { bindings }),
+        "open_tool" => open_tool_command(bindings, path, fd).and_then(parse_action),
+        "home_edit_favorites" => Ok(Action::HomeEditFavorites),
+        "favorite_toggle" => favorite_id(&bindings).map(|id| Action::FavoriteToggle { id }),
+        "favorite_move_up" => {
+            favorite_id(&bindings).map(|id| Action::FavoriteMove { id, delta: -1 })
+        }
+        "favorite_move_down" => {
+            favorite_id(&bindings).map(|id| Action::FavoriteMove { id, delta: 1 })
+        }
+        "section_toggle" => bindings
+            .get("section_key")
+            .filter(|key| !key.is_empty())
+            .cloned()
+            .ok_or_else(|| "missing_section_key".to_string())
+            .map(|key| Action::SectionToggle {
+                key,
+                expanded: bindings
+                    .get("section_expanded")
+                    .is_some_and(|v| v == "true"),
+            }),
         "reset" => Ok(Action::Reset),
         "back" => Ok(Action::Back),
+        "confirm_pending" => Ok(Action::ConfirmPending),
+        "cancel_pending" => Ok(Action::CancelPending),
         "home_filter" => Ok(Action::HomeFilter {
             query: bindings.get("home_filter").cloned().unwrap_or_default(),
         }),
-        "set_locale" => Ok(Action::SetLocale {
-            locale: bindings.get("locale").cloned().unwrap_or_default(),
-        }),
+        "set_locale" => match bindings.get("locale").map(|l| l.trim()).unwrap_or_default() {
+            // Empty goes back to following the device.
+            "" => Ok(Action::SetLocale {
+                locale: String::new(),
+            }),
+            tag => i18n::supported_locale(tag)
+                .map(|locale| Action::SetLocale {
+                    locale: locale.to_string(),
+                })
+                .ok_or_else(|| format!("unsupported_locale:{tag}")),
+        },
+        "set_theme" => bindings
+            .get("theme_mode")
+            .and_then(|mode| ThemeMode::parse(mode))
+            .map(|mode| Action::SetTheme { mode })
+            .ok_or_else(|| "invalid_theme_mode".to_string()),
         "ruler_screen" => Ok(Action::RulerScreen),
         "pdf_tools_screen" => Ok(Action::PdfToolsScreen),
         "pdf_select" => Ok(Action::PdfSelect {
@@ -1311,20 +2659,80 @@ fn parse_action(command: Command) -> Result<Action, String> {
             page: parse_u32_binding(&bindings, "page").unwrap_or(1),
         }),
         "pdf_page_close" => Ok(Action::PdfPageClose),
+        "pdf_tab_select" => bindings
+            .get("pdf_tab")
+            .and_then(|tab| PdfTab::parse(tab))
+            .map(|tab| Action::PdfTabSelect { tab })
+            .ok_or_else(|| "invalid_pdf_tab".to_string()),
         "pixel_art_screen" => Ok(Action::PixelArtScreen),
         "pixel_art_pick" => Ok(Action::PixelArtPick { path, fd, error }),
         "pixel_art_set_scale" => Ok(Action::PixelArtSetScale {
             scale: parse_u32_binding(&bindings, "scale").unwrap_or(4),
         }),
+        "pixel_art_upscaler_nearest" => Ok(Action::PixelArtSetUpscaler {
+            upscaler: PixelArtUpscaler::Nearest,
+        }),
+        "pixel_art_upscaler_hq2x" => Ok(Action::PixelArtSetUpscaler {
+            upscaler: PixelArtUpscaler::Hq2x,
+        }),
+        "pixel_art_upscaler_xbr" => Ok(Action::PixelArtSetUpscaler {
+            upscaler: PixelArtUpscaler::Xbr,
+        }),
+        "pixel_art_crt_toggle" => Ok(Action::PixelArtSetCrt {
+            enabled: bindings
+                .get("pixel_art_crt")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+        }),
+        "pixel_art_palette_lock_off" => Ok(Action::PixelArtSetPaletteLock { palette: None }),
+        "pixel_art_palette_lock_mono" => Ok(Action::PixelArtSetPaletteLock {
+            palette: Some(DitheringPalette::Monochrome),
+        }),
+        "pixel_art_palette_lock_cga" => Ok(Action::PixelArtSetPaletteLock {
+            palette: Some(DitheringPalette::Cga),
+        }),
+        "pixel_art_palette_lock_gb" => Ok(Action::PixelArtSetPaletteLock {
+            palette: Some(DitheringPalette::GameBoy),
+        }),
+        "pixel_art_palette_lock_dithering" => Ok(Action::PixelArtLockDitheringPalette),
         "pixel_art_apply" => Ok(Action::PixelArtApply { loading_only }),
         "regex_tester_screen" => Ok(Action::RegexTesterScreen),
         "regex_test" => Ok(Action::RegexTest { bindings }),
         "regex_clear" => Ok(Action::RegexClear),
+        "regex_explain" => Ok(Action::RegexExplain { bindings }),
+        "regex_file" => Ok(Action::RegexFile {
+            path,
+            fd,
+            error,
+            bindings,
+        }),
         "math_tool_screen" => Ok(Action::MathToolScreen),
+        "math_constants_screen" => Ok(Action::MathConstantsScreen),
+        "math_insert_constant" => Ok(Action::MathInsertConstant { bindings }),
         "math_calculate" => Ok(Action::MathCalculate { bindings }),
         "math_clear_history" => Ok(Action::MathClearHistory),
-        "function_analysis_screen" => Ok(Action::FunctionAnalysisAction { action: "screen".to_string() }),
-        other if other.starts_with("function_analysis_") => Ok(Action::FunctionAnalysisAction {
+        "math_recall" => Ok(Action::MathRecall { bindings }),
+        "math_set_history_cap" => Ok(Action::MathSetHistoryCap { bindings }),
+        "math_format_dec" => Ok(Action::MathSetFormat {
+            format: MathResultFormat::Decimal,
+        }),
+        "math_format_hex" => Ok(Action::MathSetFormat {
+            format: MathResultFormat::Hex,
+        }),
+        "math_format_bin" => Ok(Action::MathSetFormat {
+            format: MathResultFormat::Binary,
+        }),
+        "math_format_frac" => Ok(Action::MathSetFormat {
+            format: MathResultFormat::Fraction,
+        }),
+        "math_format_sci" => Ok(Action::MathSetFormat {
+            format: MathResultFormat::Scientific,
+        }),
+        "math_format_eng" => Ok(Action::MathSetFormat {
+            format: MathResultFormat::Engineering,
+        }),
+        "function_analysis_screen" => Ok(Action::FunctionAnalysisAction { action: "screen".to_string() }),
+        other if other.starts_with("function_analysis_") => Ok(Action::FunctionAnalysisAction {
             action: other.to_string(),
         }),
         "unit_converter_screen" => Ok(Action::UnitConverterScreen),
@@ -1333,7 +2741,11 @@ fn parse_action(command: Command) -> Result<Action, String> {
             bindings,
         }),
         "uuid_screen" => Ok(Action::UuidScreen),
-        "uuid_generate" => Ok(Action::UuidGenerate),
+        "uuid_generate" => Ok(Action::UuidGenerate { bindings }),
+        "ulid_generate" => Ok(Action::UlidGenerate),
+        "random_bytes_generate" => Ok(Action::RandomBytesGenerate { bindings }),
+        "uuid_inspect" => Ok(Action::UuidInspect { bindings }),
+        "nanoid_generate" => Ok(Action::NanoidGenerate { bindings }),
         "random_string_generate" => Ok(Action::RandomStringGenerate { bindings }),
         "vault_screen" => Ok(Action::VaultScreen),
         "vault_pick" => Ok(Action::VaultPick { path, fd, error }),
@@ -1362,6 +2774,24 @@ fn parse_action(command: Command) -> Result<Action, String> {
             object: bindings.get("logic_query_o").cloned(),
         }),
         "jwt_screen" => Ok(Action::JwtScreen { bindings }),
+        "cert_inspector_screen" => Ok(Action::CertInspectorScreen),
+        "cert_inspect" => Ok(Action::CertInspect {
+            input: bindings.get("cert_input").cloned(),
+        }),
+        "cert_open" => Ok(Action::CertOpen {
+            path,
+            fd,
+            error,
+            name: bindings.get("file_name").cloned(),
+        }),
+        "password_strength_screen" => Ok(Action::PasswordStrengthScreen),
+        "password_strength_check" => Ok(Action::PasswordStrengthCheck {
+            password: bindings.get("password_input").cloned(),
+            show: bindings.get("password_show").map(|v| v == "true"),
+        }),
+        "date_calc_screen" => Ok(Action::DateCalcScreen),
+        "date_calc_compute" => Ok(Action::DateCalcCompute { bindings }),
+        "date_calc_swap_tz" => Ok(Action::DateCalcSwapTz { bindings }),
         "jwt_decode" => Ok(Action::JwtDecode {
             token: bindings.get("jwt_input").cloned(),
         }),
@@ -1406,6 +2836,22 @@ fn parse_action(command: Command) -> Result<Action, String> {
         }),
         "c_scripting_clear" => Ok(Action::CScriptingClear),
         "c_scripting_load_example" => Ok(Action::CScriptingLoadExample),
+        "c_scripting_open" => Ok(Action::CScriptingOpen { path, fd, error }),
+        "jit_benchmark_screen" => Ok(Action::JitBenchmarkScreen),
+        "jit_benchmark_run" => Ok(Action::JitBenchmarkRun),
+        "plugins_screen" => Ok(Action::PluginsScreen),
+        "plugin_install" => Ok(Action::PluginInstall {
+            path,
+            fd,
+            error,
+            name: bindings.get("file_name").cloned(),
+        }),
+        "plugin_remove" => plugin_id(&bindings).map(|id| Action::PluginRemove { id }),
+        "plugin_open" => plugin_id(&bindings).map(|id| Action::PluginOpen { id }),
+        other if other.starts_with(PLUGIN_ACTION_PREFIX) => Ok(Action::PluginAction {
+            action: other[PLUGIN_ACTION_PREFIX.len()..].to_string(),
+            bindings,
+        }),
         "c_scripting_toggle_jit" => {
             let enabled = bindings.get("c_scripting_use_jit").map(|v| v == "true").unwrap_or(false);
             Ok(Action::CScriptingToggleJit { enabled })
@@ -1471,6 +2917,7 @@ fn parse_action(command: Command) -> Result<Action, String> {
         "text_viewer_open" => Ok(Action::TextViewerOpen { fd, path, error }),
         "text_viewer_toggle_theme" => Ok(Action::TextViewerToggleTheme),
         "text_viewer_toggle_line_numbers" => Ok(Action::TextViewerToggleLineNumbers),
+        "text_viewer_toggle_markdown_preview" => Ok(Action::TextViewerToggleMarkdownPreview),
         "text_viewer_load_anyway" => Ok(Action::TextViewerLoadAnyway),
         "text_viewer_load_more" => Ok(Action::TextViewerLoadMore),
         "text_viewer_load_prev" => Ok(Action::TextViewerLoadPrev),
@@ -1522,9 +2969,20 @@ fn parse_action(command: Command) -> Result<Action, String> {
         "hex_editor_save_as_picker" => Ok(Action::HexEditorSaveAsPicker),
         "sensor_logger_screen" => Ok(Action::SensorLoggerScreen),
         "sensor_logger_start" => Ok(Action::SensorLoggerStart { bindings }),
-        "sensor_logger_stop" => Ok(Action::SensorLoggerStop),
+        "sensor_logger_stop" => Ok(Action::SensorLoggerStop { bindings }),
         "sensor_logger_share" => Ok(Action::SensorLoggerShare),
         "sensor_logger_status" => Ok(Action::SensorLoggerStatus { bindings }),
+        "sensor_logger_export" => Ok(Action::SensorLoggerExport {
+            format: bindings.get("sensor_export_format").cloned(),
+        }),
+        "sensor_logger_pause" => Ok(Action::SensorLoggerPause),
+        "sensor_logger_resume" => Ok(Action::SensorLoggerResume),
+        "sensor_logger_format" => Ok(Action::SensorLoggerFormat {
+            format: bindings.get("sensor_format").cloned(),
+        }),
+        "sensor_logger_segment" => Ok(Action::SensorLoggerSegment {
+            path: bindings.get("sensor_segment_path").cloned(),
+        }),
         "shader_demo" => Ok(Action::ShaderDemo),
         "load_shader_file" => Ok(Action::LoadShader { path, fd, error }),
         "kotlin_image_screen_webp" => Ok(Action::KotlinImageScreen(ImageTarget::Webp)),
@@ -1539,6 +2997,7 @@ fn parse_action(command: Command) -> Result<Action, String> {
                     path: None,
                     size: None,
                     format: None,
+                    details: None,
                     error: Some(err),
                 }
             } else {
@@ -1546,6 +3005,7 @@ fn parse_action(command: Command) -> Result<Action, String> {
                     path: result_path,
                     size: result_size,
                     format: result_format,
+                    details: None,
                     error: None,
                 }
             },
@@ -1557,8 +3017,118 @@ fn parse_action(command: Command) -> Result<Action, String> {
         }),
         "kotlin_image_pick" => Ok(Action::KotlinImagePick { path, fd, error }),
         "kotlin_image_batch_pick" => Ok(Action::KotlinImageBatchPick {
-            paths: path_list.unwrap_or_default(),
+            paths: path_list.unwrap_or_else(|| path.clone().into_iter().collect()),
+            fds: fd_list.unwrap_or_else(|| fd.into_iter().collect()),
+        }),
+        "image_resize" => Ok(Action::ImageResize { bindings }),
+        "image_resize_batch" => Ok(Action::ImageResizeBatch { bindings }),
+        "image_edit_screen" => Ok(Action::ImageEditScreen),
+        "image_edit_pick" => Ok(Action::ImageEditPick { path, fd, error }),
+        "image_edit_crop" => Ok(Action::ImageEditCrop { bindings }),
+        "image_edit_rotate_left" => Ok(Action::ImageEditRotate { degrees: -90 }),
+        "image_edit_rotate_right" => Ok(Action::ImageEditRotate { degrees: 90 }),
+        "image_edit_flip_h" => Ok(Action::ImageEditFlip { horizontal: true }),
+        "image_edit_flip_v" => Ok(Action::ImageEditFlip { horizontal: false }),
+        "image_edit_reset" => Ok(Action::ImageEditReset),
+        "image_edit_apply" => Ok(Action::ImageEditApply),
+        "image_adjust_screen" => Ok(Action::ImageAdjustScreen),
+        "image_adjust_pick" => Ok(Action::ImageAdjustPick { path, fd, error }),
+        "image_adjust_update" => Ok(Action::ImageAdjustUpdate { bindings }),
+        "image_adjust_reset" => Ok(Action::ImageAdjustReset),
+        "image_adjust_apply" => Ok(Action::ImageAdjustApply { bindings }),
+        "ascii_art_screen" => Ok(Action::AsciiArtScreen),
+        "ascii_art_pick" => Ok(Action::AsciiArtPick {
+            path,
+            fd,
+            error,
+            name: bindings.get("file_name").cloned(),
+        }),
+        "ascii_art_sync" => Ok(Action::AsciiArtSync { bindings }),
+        "ascii_art_view" => Ok(Action::AsciiArtView),
+        "ascii_art_export" => Ok(Action::AsciiArtExport),
+        "gif_frames_screen" => Ok(Action::GifFramesScreen),
+        "gif_frames_pick" => Ok(Action::GifFramesPick {
+            path,
+            fd,
+            error,
+            name: bindings.get("file_name").cloned(),
         }),
+        "gif_frames_assemble" => Ok(Action::GifFramesAssemble { bindings }),
+        "exif_edit_screen" => Ok(Action::ExifEditScreen),
+        "exif_edit_pick" => Ok(Action::ExifEditPick {
+            path,
+            fd,
+            error,
+            name: bindings.get("file_name").cloned(),
+        }),
+        "exif_edit_sync" => Ok(Action::ExifEditSync { bindings }),
+        "exif_edit_save" => Ok(Action::ExifEditSave { bindings }),
+        "metadata_strip_screen" => Ok(Action::MetadataStripScreen),
+        "image_histogram_screen" => Ok(Action::ImageHistogramScreen),
+        "watermark_screen" => Ok(Action::WatermarkScreen),
+        "watermark_pick_images" | "collage_pick_images" => {
+            let mut paths = path_list.unwrap_or_default();
+            let mut fds = fd_list.unwrap_or_default();
+            if paths.is_empty() && fds.is_empty() {
+                paths.extend(path);
+                fds.extend(fd);
+            }
+            let names = bindings
+                .get("file_names")
+                .map(|names| names.lines().map(str::to_string).collect())
+                .or_else(|| bindings.get("file_name").map(|name| vec![name.clone()]))
+                .unwrap_or_default();
+            if action == "collage_pick_images" {
+                return Ok(Action::CollagePickImages {
+                    paths,
+                    fds,
+                    names,
+                    error,
+                });
+            }
+            Ok(Action::WatermarkPickImages {
+                paths,
+                fds,
+                names,
+                error,
+            })
+        }
+        "watermark_pick_logo" => Ok(Action::WatermarkPickLogo {
+            path,
+            fd,
+            error,
+            name: bindings.get("file_name").cloned(),
+        }),
+        "watermark_sync" => Ok(Action::WatermarkSync { bindings }),
+        "watermark_apply" => Ok(Action::WatermarkApply { bindings }),
+        "collage_screen" => Ok(Action::CollageScreen),
+        "collage_sync" => Ok(Action::CollageSync { bindings }),
+        "collage_build" => Ok(Action::CollageBuild { bindings }),
+        "image_histogram_pick" => Ok(Action::ImageHistogramPick {
+            path,
+            fd,
+            error,
+            name: bindings.get("file_name").cloned(),
+        }),
+        "metadata_strip_pick" => {
+            let mut paths = path_list.unwrap_or_default();
+            let mut fds = fd_list.unwrap_or_default();
+            if paths.is_empty() && fds.is_empty() {
+                paths.extend(path);
+                fds.extend(fd);
+            }
+            let names = bindings
+                .get("file_names")
+                .map(|names| names.lines().map(str::to_string).collect())
+                .or_else(|| bindings.get("file_name").map(|name| vec![name.clone()]))
+                .unwrap_or_default();
+            Ok(Action::MetadataStripPick {
+                paths,
+                fds,
+                names,
+                error,
+            })
+        }
         "kotlin_image_batch_remove" => Ok(Action::KotlinImageBatchRemove {
             path: bindings
                 .get("image_batch_path")
@@ -1583,6 +3153,24 @@ fn parse_action(command: Command) -> Result<Action, String> {
         "dithering_mode_bayer8" => Ok(Action::DitheringSetMode {
             mode: DitheringMode::Bayer8x8,
         }),
+        "dithering_mode_jjn" => Ok(Action::DitheringSetMode {
+            mode: DitheringMode::JarvisJudiceNinke,
+        }),
+        "dithering_mode_stucki" => Ok(Action::DitheringSetMode {
+            mode: DitheringMode::Stucki,
+        }),
+        "dithering_mode_burkes" => Ok(Action::DitheringSetMode {
+            mode: DitheringMode::Burkes,
+        }),
+        "dithering_mode_blue_noise" => Ok(Action::DitheringSetMode {
+            mode: DitheringMode::BlueNoise,
+        }),
+        "dithering_serpentine_toggle" => Ok(Action::DitheringSetSerpentine {
+            enabled: bindings
+                .get("dithering_serpentine")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+        }),
         "dithering_palette_mono" => Ok(Action::DitheringSetPalette {
             palette: DitheringPalette::Monochrome,
         }),
@@ -1592,6 +3180,13 @@ fn parse_action(command: Command) -> Result<Action, String> {
         "dithering_palette_gb" => Ok(Action::DitheringSetPalette {
             palette: DitheringPalette::GameBoy,
         }),
+        "dithering_palette_custom" => Ok(Action::DitheringSetCustomPalette {
+            input: bindings
+                .get("dithering_custom_palette")
+                .cloned()
+                .unwrap_or_default(),
+        }),
+        "dithering_palette_from_image" => Ok(Action::DitheringPaletteFromImage { path, fd, error }),
         "dithering_apply" => Ok(Action::DitheringApply { loading_only }),
         "hash_file_sha256" => Ok(Action::Hash {
             algo: HashAlgo::Sha256,
@@ -1605,6 +3200,15 @@ fn parse_action(command: Command) -> Result<Action, String> {
             path,
             fd,
             reference: bindings.get("hash_reference").cloned(),
+            hmac_key: bindings.get("hmac_key").cloned(),
+        }),
+        "hash_hmac_config" => Ok(Action::HashHmacConfig {
+            enabled: bindings.get("hmac_enabled").map(|v| v == "true"),
+            algo: bindings
+                .get("hmac_algo")
+                .and_then(|id| HmacAlgo::from_id(id)),
+            key: bindings.get("hmac_key").cloned(),
+            key_hex: bindings.get("hmac_key_hex").map(|v| v == "true"),
         }),
         "hash_verify_paste" => Ok(Action::HashVerifyPaste {
             reference: bindings
@@ -1619,6 +3223,27 @@ fn parse_action(command: Command) -> Result<Action, String> {
                 .or_else(|| bindings.get("hash_reference").cloned()),
         }),
         "hash_qr_last" => Ok(Action::HashQrFromLast),
+        "hash_file_sha512" => Ok(Action::Hash {
+            algo: HashAlgo::Sha512,
+            path,
+            fd,
+            error,
+            loading_only,
+        }),
+        "hash_file_sha3_256" => Ok(Action::Hash {
+            algo: HashAlgo::Sha3_256,
+            path,
+            fd,
+            error,
+            loading_only,
+        }),
+        "hash_file_sha3_512" => Ok(Action::Hash {
+            algo: HashAlgo::Sha3_512,
+            path,
+            fd,
+            error,
+            loading_only,
+        }),
         "hash_file_sha1" => Ok(Action::Hash {
             algo: HashAlgo::Sha1,
             path,
@@ -1654,14 +3279,102 @@ fn parse_action(command: Command) -> Result<Action, String> {
             error,
             loading_only,
         }),
+        "hash_file_xxh64" => Ok(Action::Hash {
+            algo: HashAlgo::Xxh64,
+            path,
+            fd,
+            error,
+            loading_only,
+        }),
+        "hash_file_xxh3" => Ok(Action::Hash {
+            algo: HashAlgo::Xxh3,
+            path,
+            fd,
+            error,
+            loading_only,
+        }),
         "progress_demo_screen" => Ok(Action::ProgressDemoScreen),
         "progress_demo_start" => Ok(Action::ProgressDemoStart { loading_only }),
         "progress_demo_finish" => Ok(Action::ProgressDemoFinish),
         "file_info_screen" => Ok(Action::FileInfoScreen),
-        "file_info" => Ok(Action::FileInfo { path, fd, error }),
+        "recent_files_clear" => Ok(Action::RecentFilesClear),
+        "job_queue_screen" => Ok(Action::JobQueueScreen),
+        "job_queue_refresh" => Ok(Action::JobQueueRefresh),
+        "job_queue_clear" => Ok(Action::JobQueueClear),
+        "poll_progress" => Ok(Action::PollProgress),
+        "job_cancel" => Ok(Action::JobCancel {
+            id: parse_u64_binding(&bindings, "job_id"),
+        }),
+        "usage_stats_screen" => Ok(Action::UsageStatsScreen),
+        "usage_stats_toggle" => Ok(Action::UsageStatsToggle {
+            enabled: bindings
+                .get("usage_stats_enabled")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+        }),
+        "home_sort_by_usage_toggle" => Ok(Action::HomeSortByUsageToggle {
+            enabled: bindings
+                .get("home_sort_by_usage")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+        }),
+        "usage_stats_clear" => Ok(Action::UsageStatsClear),
+        "debug_log_screen" => Ok(Action::DebugLogScreen),
+        "debug_log_view" => Ok(Action::DebugLogView),
+        "debug_log_export" => Ok(Action::DebugLogExport),
+        "debug_log_clear" => Ok(Action::DebugLogClear),
+        "cleanup_screen" => Ok(Action::CleanupScreen),
+        "cleanup_refresh" => Ok(Action::CleanupRefresh),
+        "cleanup_select_all" => Ok(Action::CleanupSelectAll),
+        "cleanup_select_stale" => Ok(Action::CleanupSelectStale),
+        "cleanup_select_none" => Ok(Action::CleanupSelectNone),
+        "cleanup_delete_selected" => Ok(Action::CleanupDeleteSelected),
+        "cleanup_save_policy" => Ok(Action::CleanupSavePolicy {
+            auto_clean: bindings
+                .get("cleanup_auto_clean")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            max_age_days: bindings.get("cleanup_max_age_days").cloned(),
+        }),
+        "cleanup_clean_stale" => Ok(Action::CleanupCleanStale),
+        "file_info" if path_list.as_ref().is_some_and(|p| p.len() > 1)
+            || fd_list.as_ref().is_some_and(|f| f.len() > 1) =>
+        {
+            Ok(Action::FileInfoBatch {
+                paths: path_list.unwrap_or_default(),
+                fds: fd_list.unwrap_or_default(),
+                names: bindings
+                    .get("file_names")
+                    .map(|names| names.lines().map(str::to_string).collect())
+                    .unwrap_or_default(),
+            })
+        }
+        "file_info" => Ok(Action::FileInfo {
+            path,
+            fd,
+            error,
+            name: bindings.get("file_name").cloned(),
+        }),
+        "file_info_hex" | "file_info_hex_prev" | "file_info_hex_next" => {
+            Ok(Action::FileInfoHex {
+                path,
+                fd,
+                direction: match action.as_str() {
+                    "file_info_hex_prev" => "prev",
+                    "file_info_hex_next" => "next",
+                    _ => "jump",
+                }
+                .into(),
+                offset: features::hex_editor::parse_offset_binding(&bindings, "file_info_hex_offset"),
+                length: bindings
+                    .get("file_info_hex_length")
+                    .and_then(|v| v.trim().parse::<usize>().ok()),
+            })
+        }
         "text_tools_screen" => Ok(Action::TextToolsScreen { bindings }),
         "increment" => Ok(Action::Increment),
         "snapshot" => Ok(Action::Snapshot),
+        PERSIST_STATE_ACTION => Ok(Action::PersistState),
         "restore_state" => snapshot
             .ok_or_else(|| "missing_snapshot".to_string())
             .map(|snap| Action::Restore { snapshot: snap }),
@@ -1715,9 +3428,11 @@ fn parse_action(command: Command) -> Result<Action, String> {
         "gzip_screen" => Ok(Action::CompressionScreen),
         "gzip_compress" => Ok(Action::GzipCompress { path, fd, error }),
         "gzip_decompress" => Ok(Action::GzipDecompress { path, fd, error }),
+        "gzip_benchmark" => Ok(Action::GzipBenchmark { path, fd, error }),
         "system_info_screen" => Ok(Action::SystemInfoScreen),
         "system_info_update" => Ok(Action::SystemInfoUpdate { bindings }),
         "compass_demo" => Ok(Action::CompassDemo),
+        "sensor_calibration_set" => Ok(Action::SensorCalibrationSet { bindings }),
         "compass_set" => Ok(Action::CompassSet {
             angle_radians: angle_radians.unwrap_or(0.0),
             error,
@@ -1743,6 +3458,7 @@ fn parse_action(command: Command) -> Result<Action, String> {
         }),
         "preset_save" => Ok(Action::PresetSave {
             name: bindings.get("preset_name").cloned(),
+            tags: bindings.get("preset_tags").cloned(),
         }),
         "preset_load" => bindings
             .get("id")
@@ -1754,6 +3470,46 @@ fn parse_action(command: Command) -> Result<Action, String> {
             .cloned()
             .ok_or_else(|| "missing_preset_id".to_string())
             .map(|id| Action::PresetDelete { id }),
+        "preset_share_qr" => bindings
+            .get("id")
+            .cloned()
+            .ok_or_else(|| "missing_preset_id".to_string())
+            .map(|id| Action::PresetShareQr { id }),
+        "preset_toggle_group" => bindings
+            .get("tool_id")
+            .cloned()
+            .ok_or_else(|| "missing_tool_id".to_string())
+            .map(|tool_id| Action::PresetToggleGroup { tool_id }),
+        "preset_reorder_mode" => Ok(Action::PresetReorderMode),
+        "preset_reorder" => {
+            let order: Vec<String> = bindings
+                .get("preset_order")
+                .map(|raw| {
+                    raw.split(',')
+                        .map(str::trim)
+                        .filter(|id| !id.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+            if order.is_empty() {
+                Err("missing_preset_order".into())
+            } else {
+                Ok(Action::PresetReorder { order })
+            }
+        }
+        "preset_export" => Ok(Action::PresetExport { all: false }),
+        "preset_export_all" => Ok(Action::PresetExport { all: true }),
+        "preset_conflict_skip" => Ok(Action::PresetSetImportConflict {
+            conflict: ImportConflict::Skip,
+        }),
+        "preset_conflict_replace" => Ok(Action::PresetSetImportConflict {
+            conflict: ImportConflict::Replace,
+        }),
+        "preset_conflict_keep_both" => Ok(Action::PresetSetImportConflict {
+            conflict: ImportConflict::KeepBoth,
+        }),
+        "preset_import" => Ok(Action::PresetImport { path, fd, error }),
         other => {
             if let Some(idx) = other.strip_prefix("archive_open_text:") {
                 let index = idx
@@ -1767,6 +3523,19 @@ fn parse_action(command: Command) -> Result<Action, String> {
                     .parse::<u32>()
                     .map_err(|_| format!("invalid_archive_index:{idx}"))?;
                 Ok(Action::ArchiveExtractEntry { index })
+            } else if let Some(idx) = other.strip_prefix("file_info_batch_hash:") {
+                let index = idx
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid_batch_index:{idx}"))?;
+                Ok(Action::FileInfoBatchHash { index, fd })
+            } else if let Some(idx) = other.strip_prefix("cleanup_toggle:") {
+                let index = idx
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid_cleanup_index:{idx}"))?;
+                let selected = bindings
+                    .get(&format!("cleanup_select_{index}"))
+                    .map(|v| v == "true");
+                Ok(Action::CleanupToggle { index, selected })
             } else if other == "multi_hash_screen" {
                 Ok(Action::MultiHashScreen)
             } else if other == "hash_all" {
@@ -1845,67 +3614,136 @@ fn parse_f64_binding(bindings: &HashMap<String, String>, key: &str) -> Option<f6
 fn hash_label(algo: HashAlgo) -> &'static str {
     match algo {
         HashAlgo::Sha256 => "SHA-256",
+        HashAlgo::Sha512 => "SHA-512",
+        HashAlgo::Sha3_256 => "SHA3-256",
+        HashAlgo::Sha3_512 => "SHA3-512",
         HashAlgo::Sha1 => "SHA-1",
         HashAlgo::Md5 => "MD5",
         HashAlgo::Md4 => "MD4",
         HashAlgo::Crc32 => "CRC32",
         HashAlgo::Blake3 => "BLAKE3",
+        HashAlgo::Xxh64 => "xxHash64",
+        HashAlgo::Xxh3 => "XXH3-64",
     }
 }
 
-fn hash_loading_message(algo: HashAlgo) -> &'static str {
-    match algo {
-        HashAlgo::Sha256 => "Computing SHA-256...",
-        HashAlgo::Sha1 => "Computing SHA-1...",
-        HashAlgo::Md5 => "Computing MD5...",
-        HashAlgo::Md4 => "Computing MD4...",
-        HashAlgo::Crc32 => "Computing CRC32...",
-        HashAlgo::Blake3 => "Computing BLAKE3...",
-    }
+fn hash_loading_message(algo: HashAlgo) -> String {
+    t!("loading_computing_hash", algo = hash_label(algo)).to_string()
 }
 
-#[no_mangle]
-pub extern "system" fn Java_aeska_kistaverk_MainActivity_dispatch(
-    mut env: JNIEnv,
-    _class: JClass,
-    input: JString,
-) -> jstring {
-    let response = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        let input_str: String = env
-            .get_string(&input)
-            .map(|s| s.into())
-            .unwrap_or_else(|_| "{}".to_string());
+/// `dispatch` takes one command object or an array of them; ticking screens batch their
+/// commands so a burst costs one JNI crossing and one lock. Unparseable input, including
+/// any bad element of an array, becomes a single `invalid_json` error command.
+fn parse_commands(input: &str) -> Vec<Command> {
+    let parsed = if input.trim_start().starts_with('[') {
+        serde_json::from_str::<Vec<Command>>(input)
+    } else {
+        serde_json::from_str::<Command>(input).map(|command| vec![command])
+    };
+    parsed.unwrap_or_else(|_| invalid_input_commands("invalid_json"))
+}
 
-        let command: Command = serde_json::from_str(&input_str).unwrap_or(Command {
-            action: "error".into(),
-            path: None,
-            path_list: None,
-            fd: None,
-            fd_list: None,
-            error: Some("invalid_json".into()),
-            target: None,
-            result_path: None,
-            result_size: None,
-            result_format: None,
-            output_dir: None,
-            bindings: None,
-            loading_only: None,
-            snapshot: None,
-            primary_fd: None,
-            primary_path: None,
-            angle_radians: None,
-        });
+/// Same shapes as [`parse_commands`], CBOR-encoded. Input that does not decode is an
+/// `invalid_cbor` error rather than a command, so nothing runs for it.
+fn parse_cbor_commands(input: &[u8]) -> Result<Vec<Command>, String> {
+    ciborium::de::from_reader::<Value, _>(input)
+        .map_err(|e| e.to_string())
+        .and_then(|value| {
+            match value {
+                Value::Array(_) => serde_json::from_value::<Vec<Command>>(value),
+                other => serde_json::from_value::<Command>(other).map(|command| vec![command]),
+            }
+            .map_err(|e| e.to_string())
+        })
+        .map_err(|e| format!("invalid_cbor:{e}"))
+}
+
+fn invalid_input_commands(error: &str) -> Vec<Command> {
+    vec![Command {
+        action: "error".into(),
+        error: Some(error.into()),
+        ..Command::default()
+    }]
+}
+
+/// Sent by the client when the app stops; the only command that writes the state to disk.
+const PERSIST_STATE_ACTION: &str = "persist_state";
+/// Reply to [`PERSIST_STATE_ACTION`]; not a UI tree, so it is never patched.
+const PERSISTED_TYPE: &str = "Persisted";
 
-        handle_command(command)
+/// Encodings the dispatch entry points accept, most compact last; the client probes this
+/// once and falls back to JSON when the library predates an encoding.
+const UI_ENCODINGS: &str = "json,cbor";
+
+/// Runs `commands` and returns the UI to send back, patched against the client's revision.
+fn dispatch_commands(commands: Vec<Command>) -> Value {
+    let client_revision = commands.iter().find_map(|c| c.ui_revision);
+    let persist = commands.iter().any(|c| c.action == PERSIST_STATE_ACTION);
+    let response = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let result = handle_commands(commands);
+        if persist {
+            STATE.get_or_init(GlobalState::new).persist_state();
+        }
+        result
     }));
 
     let json_value = match response {
         Ok(Ok(value)) => value,
         Ok(Err(err)) => error_ui(&err),
-        Err(_) => error_ui("panic"),
+        Err(_) => {
+            logging::error("router", "panic while handling a command");
+            error_ui("panic")
+        }
+    };
+
+    STATE
+        .get_or_init(GlobalState::new)
+        .encode_ui(json_value, client_revision)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_aeska_kistaverk_MainActivity_nativeUiEncodings(
+    env: JNIEnv,
+    _class: JClass,
+) -> jstring {
+    env.new_string(UI_ENCODINGS)
+        .map(|s| s.into_raw())
+        .unwrap_or(ptr::null_mut())
+}
+
+#[no_mangle]
+pub extern "system" fn Java_aeska_kistaverk_MainActivity_nativeDispatchCbor(
+    env: JNIEnv,
+    _class: JClass,
+    input: JByteArray,
+) -> jbyteArray {
+    let input_bytes = env.convert_byte_array(&input).unwrap_or_default();
+    let ui = match parse_cbor_commands(&input_bytes) {
+        Ok(commands) => dispatch_commands(commands),
+        Err(err) => error_ui(&err),
     };
+    let mut output = Vec::new();
+    if ciborium::ser::into_writer(&ui, &mut output).is_err() {
+        output.clear();
+        let _ = ciborium::ser::into_writer(&error_ui("cbor_encode_failed"), &mut output);
+    }
+    env.byte_array_from_slice(&output)
+        .map(|array| array.into_raw())
+        .unwrap_or(ptr::null_mut())
+}
+
+#[no_mangle]
+pub extern "system" fn Java_aeska_kistaverk_MainActivity_nativeDispatch(
+    mut env: JNIEnv,
+    _class: JClass,
+    input: JString,
+) -> jstring {
+    let input_str: String = env
+        .get_string(&input)
+        .map(|s| s.into())
+        .unwrap_or_else(|_| "{}".to_string());
 
-    let output_string = json_value.to_string();
+    let output_string = dispatch_commands(parse_commands(&input_str)).to_string();
     match env.new_string(output_string) {
         Ok(java_str) => java_str.into_raw(),
         Err(_) => {
@@ -1945,7 +3783,7 @@ pub extern "system" fn Java_aeska_kistaverk_MainActivity_processQrCameraFrame(
             Ok(None) => Ok(ptr::null_mut()), // No QR code found
             Err(e) => {
                 // Log the error and return null, or potentially a special error string
-                eprintln!("QR decoding error: {}", e);
+                logging::debug("qr", format!("decoding error: {e}"));
                 Ok(ptr::null_mut())
             }
         }
@@ -1968,7 +3806,131 @@ pub extern "system" fn Java_aeska_kistaverk_MainActivity_getMathBackendInfo(
         .unwrap_or(ptr::null_mut())
 }
 
+/// Files handed over by the SAF picker arrive with an fd, their URI and display name.
+fn picked_recent_file(command: &Command) -> Option<RecentFile> {
+    command.fd?;
+    let uri = command.path.as_deref()?;
+    let name = command.bindings.as_ref()?.get("file_name")?;
+    Some(RecentFile::new(name, uri, &command.action))
+}
+
+/// Catalog tool opened by `command`, if its action is one of the home screen entries.
+fn used_tool(command: &Command) -> Option<&'static str> {
+    feature_catalog()
+        .into_iter()
+        .find(|f| f.action == command.action)
+        .map(|f| f.id)
+}
+
+/// Binding set on a held command once the user confirmed it, so it runs without asking again.
+const CONFIRMED_BINDING: &str = "confirmed";
+
+/// Archives with at least this many entries ask before everything is extracted.
+const ARCHIVE_CONFIRM_ENTRIES: usize = 1000;
+
+/// Confirmation `command` needs before it runs: it deletes, clears or overwrites something
+/// the user cannot get back, or writes an unusually large number of files.
+fn confirmation_for(state: &AppState, command: &Command) -> Option<PendingConfirmation> {
+    let bindings = command.bindings.clone().unwrap_or_default();
+    if bindings.get(CONFIRMED_BINDING).is_some_and(|v| v == "true") {
+        return None;
+    }
+    let (title, message, confirm_label) = match command.action.as_str() {
+        "preset_delete" => (
+            t!("confirm_preset_delete_title"),
+            t!("confirm_preset_delete_message"),
+            t!("confirm_action_delete"),
+        ),
+        "math_clear_history" => (
+            t!("confirm_math_history_title"),
+            t!("confirm_math_history_message"),
+            t!("confirm_action_clear"),
+        ),
+        "recent_files_clear" => (
+            t!("confirm_recent_files_title"),
+            t!("confirm_recent_files_message"),
+            t!("confirm_action_clear"),
+        ),
+        "plugin_remove" => (
+            t!("confirm_plugin_remove_title"),
+            t!("confirm_plugin_remove_message"),
+            t!("confirm_action_delete"),
+        ),
+        "usage_stats_clear" => (
+            t!("confirm_usage_stats_title"),
+            t!("confirm_usage_stats_message"),
+            t!("confirm_action_clear"),
+        ),
+        "cleanup_delete_selected" => (
+            t!("confirm_cleanup_delete_title"),
+            t!("confirm_cleanup_delete_message"),
+            t!("confirm_action_delete"),
+        ),
+        "hex_editor_save" if !state.hex_editor.dirty_bytes.is_empty() => (
+            t!("confirm_hex_save_title"),
+            t!("confirm_hex_save_message"),
+            t!("confirm_action_overwrite"),
+        ),
+        "hex_editor_save_as"
+            if bindings.get("hex_save_as_path").is_some_and(|target| {
+                let target = target.trim();
+                !target.is_empty()
+                    && std::path::Path::new(target).exists()
+                    && state.hex_editor.file_path.as_deref() != Some(target)
+            }) =>
+        {
+            (
+                t!("confirm_replace_file_title"),
+                t!("confirm_replace_file_message"),
+                t!("confirm_action_overwrite"),
+            )
+        }
+        "archive_extract_all"
+            if state.archive.truncated
+                || state.archive.entries.len() >= ARCHIVE_CONFIRM_ENTRIES =>
+        {
+            let count = state.archive.entries.len();
+            let more = if state.archive.truncated { "+" } else { "" };
+            (
+                t!("confirm_extract_all_title"),
+                format!("{}{count}{more}", t!("confirm_extract_all_count_prefix")).into(),
+                t!("confirm_action_extract"),
+            )
+        }
+        _ => return None,
+    };
+    Some(PendingConfirmation {
+        title: title.into_owned(),
+        message: Some(message.into_owned()),
+        confirm_label: confirm_label.into_owned(),
+        action: command.action.clone(),
+        path: command.path.clone(),
+        bindings,
+    })
+}
+
+/// Rebuilds the held command, marked so it is not held again.
+fn confirmed_command(pending: PendingConfirmation) -> Command {
+    let mut bindings = pending.bindings;
+    bindings.insert(CONFIRMED_BINDING.into(), "true".into());
+    Command {
+        action: pending.action,
+        path: pending.path,
+        bindings: Some(bindings),
+        ..Command::default()
+    }
+}
+
+#[cfg(test)]
 fn handle_command(command: Command) -> Result<Value, String> {
+    handle_commands(vec![command])
+}
+
+/// Applies `commands` in order under a single lock, so nothing else interleaves, and
+/// returns the UI left by the last one. There is no rollback: when a command fails, the
+/// ones before it stay applied, since their queued jobs and written files cannot be undone
+/// anyway, and the rest of the batch is skipped.
+fn handle_commands(commands: Vec<Command>) -> Result<Value, String> {
     let mut lock_poisoned = false;
     let mut state = match STATE.get_or_init(GlobalState::new).ui.lock() {
         Ok(guard) => guard,
@@ -1978,28 +3940,117 @@ fn handle_command(command: Command) -> Result<Value, String> {
         }
     };
 
-    apply_worker_results(&mut state);
+    let mut ui = None;
+    for command in commands {
+        ui = run_command(&mut state, command, lock_poisoned)?;
+    }
+    Ok(ui.unwrap_or_else(|| render_root(&mut state)))
+}
+
+/// Applies one command. `None` asks for the root screen, which the caller renders once the
+/// whole batch is done; `Some` is a UI the command built itself.
+fn run_command(
+    state: &mut AppState,
+    command: Command,
+    lock_poisoned: bool,
+) -> Result<Option<Value>, String> {
+    apply_worker_results(state);
     state.ensure_navigation();
 
+    if let Some(pending) = confirmation_for(state, &command) {
+        state.pending_confirmation = Some(pending);
+        return Ok(None);
+    }
+
+    let picked = picked_recent_file(&command);
+    let used = used_tool(&command);
     let action = match parse_action(command) {
         Ok(action) => action,
         Err(err) => {
+            logging::warn("router", format!("rejected command: {err}"));
             state.last_error = Some(err);
-            return Ok(render_root(&mut state));
+            return Ok(None);
         }
     };
+    if let Some(entry) = picked {
+        remember_recent_file(&mut state.recent_files, entry);
+        // Best effort: a read-only data dir only loses the list across restarts.
+        let _ = save_recent_files(&state.recent_files);
+    }
+    if let Some(id) = used {
+        state.note_tool_used(id);
+    }
 
     match action {
         Action::Init { bindings } => {
+            // A fresh process picks up where the killed one left off; later inits keep the
+            // live state.
+            if STATE.get_or_init(GlobalState::new).restored.set(()).is_ok() {
+                match load_app_state()
+                    .and_then(|c| c.map(|c| AppState::from_persisted(&c)).transpose())
+                {
+                    Ok(Some(restored)) => *state = restored,
+                    Ok(None) => {}
+                    Err(e) => {
+                        logging::warn("state", format!("restore failed: {e}"));
+                        state.last_error = Some(e);
+                    }
+                }
+            }
             // Keep current state; ensure navigation is initialized.
             state.ensure_navigation();
             if let Some(locale) = bindings.get("system_locale") {
-                crate::update_locale(&mut state, locale);
+                i18n::set_system_locale(state, locale);
+            }
+            if let Some(mode) = bindings.get("theme_mode").and_then(|m| ThemeMode::parse(m)) {
+                state.theme_mode = mode;
+            }
+            if state.math_tool.history.is_empty() {
+                if let Err(e) = restore_math_history(state) {
+                    state.math_tool.error = Some(e);
+                }
+            }
+            if state.recent_files.is_empty() {
+                state.recent_files = load_recent_files().unwrap_or_default();
+            }
+            if let Ok(Some(favorites)) = load_favorite_tools() {
+                state.favorite_tools = favorites;
+            }
+            state.plugins.installed = list_plugins(&plugins_dir());
+            if let Some(summary) = auto_clean_once() {
+                if summary.deleted > 0 {
+                    state.push_toast(format!(
+                        "{}{}",
+                        t!("cleanup_auto_cleaned_prefix"),
+                        summary_text(&summary)
+                    ));
+                }
+            }
+        }
+        Action::HomeEditFavorites => {
+            state.home_edit_favorites = !state.home_edit_favorites;
+            if matches!(state.current_screen(), Screen::Home) {
+                state.replace_current(Screen::Home);
+            }
+        }
+        a @ Action::FavoriteToggle { .. } | a @ Action::FavoriteMove { .. } => {
+            match a {
+                Action::FavoriteToggle { id } => toggle_favorite(&mut state.favorite_tools, &id),
+                Action::FavoriteMove { id, delta } => {
+                    move_favorite(&mut state.favorite_tools, &id, delta)
+                }
+                _ => {}
+            }
+            if let Err(e) = save_favorite_tools(&state.favorite_tools) {
+                state.last_error = Some(e);
             }
-            if let Some(mode) = bindings.get("theme_mode") {
-                state.theme_mode = Some(mode.clone());
+            if matches!(state.current_screen(), Screen::Home) {
+                state.replace_current(Screen::Home);
             }
         }
+        Action::SectionToggle { key, expanded } => {
+            state.expanded_sections.insert(key, expanded);
+        }
         Action::HomeFilter { query } => {
             state.home_filter = query;
             if matches!(state.current_screen(), Screen::Home) {
@@ -2007,42 +4058,45 @@ fn handle_command(command: Command) -> Result<Value, String> {
             }
         }
         Action::SetLocale { locale } => {
-            // Handle empty locale (system default)
-            if locale.is_empty() {
-                // Use system locale - this would be set during initialization
-                // For now, we'll just clear the preferred locale
-                state.preferred_locale = String::new();
-                // Don't change the current locale, keep using whatever was set from system
-            } else {
-                // Update the locale
-                i18n::update_locale(&mut *state, &locale);
-                
-                // Store the preference (this would be enhanced with persistent storage)
-                state.preferred_locale = locale.clone();
-            }
-            
-            // Force UI refresh to show new translations by reloading current screen
+            state.preferred_locale = locale;
+            i18n::apply_locale(state);
+            // Re-render the current screen with the new translations.
+            let current_screen = state.current_screen().clone();
+            state.replace_current(current_screen);
+        }
+        Action::SetTheme { mode } => {
+            state.theme_mode = mode;
             let current_screen = state.current_screen().clone();
             state.replace_current(current_screen);
         }
         Action::Snapshot => {
             state.ensure_navigation();
-            let snap =
-                serde_json::to_string(&*state).map_err(|e| format!("snapshot_failed:{e}"))?;
-            return Ok(json!({
+            let snap = state.to_snapshot()?;
+            return Ok(Some(json!({
                 "type": "Snapshot",
                 "snapshot": snap
-            }));
+            })));
         }
-        Action::Restore { snapshot } => match serde_json::from_str::<AppState>(&snapshot) {
-            Ok(mut restored) => {
-                restored.ensure_navigation();
+        Action::PersistState => {
+            // Written by `dispatch_commands` once the state lock is released.
+            return Ok(Some(json!({ "type": PERSISTED_TYPE })));
+        }
+        Action::Restore { snapshot } => match AppState::from_snapshot(&snapshot) {
+            Ok(restored) => {
                 *state = restored;
+                // The snapshot is newer than anything on disk.
+                let _ = STATE.get_or_init(GlobalState::new).restored.set(());
             }
             Err(e) => {
-                state.last_error = Some(format!("restore_failed:{e}"));
+                state.last_error = Some(e);
             }
         },
+        Action::ConfirmPending => {
+            if let Some(pending) = state.pending_confirmation.take() {
+                return run_command(state, confirmed_command(pending), lock_poisoned);
+            }
+        }
+        Action::CancelPending => state.pending_confirmation = None,
         Action::Reset => {
             state.reset_runtime();
             state.reset_navigation();
@@ -2065,60 +4119,140 @@ fn handle_command(command: Command) -> Result<Value, String> {
         | a @ Action::ArchiveExtractAll
         | a @ Action::ArchiveExtractEntry { .. }
         | a @ Action::ArchiveFilter { .. } => {
-            if let Some(ui) = handle_archive_actions(&mut state, a) {
-                return Ok(ui);
+            if let Some(ui) = handle_archive_actions(state, a) {
+                return Ok(Some(ui));
             }
         }
         a @ Action::CompressionScreen
         | a @ Action::GzipCompress { .. }
-        | a @ Action::GzipDecompress { .. } => {
-            handle_compression_actions(&mut state, a);
+        | a @ Action::GzipDecompress { .. }
+        | a @ Action::GzipBenchmark { .. } => {
+            handle_compression_actions(state, a);
         }
         a @ Action::KotlinImageBatchPick { .. } | a @ Action::KotlinImageBatchRemove { .. } => {
-            handle_kotlin_image_batch_actions(&mut state, a);
+            handle_kotlin_image_batch_actions(state, a);
         }
         a @ Action::VaultScreen
         | a @ Action::VaultPick { .. }
         | a @ Action::VaultEncrypt { .. }
         | a @ Action::VaultDecrypt { .. } => {
-            handle_vault_actions(&mut state, a);
+            handle_vault_actions(state, a);
         }
         a @ Action::LogicScreen
         | a @ Action::LogicAddTriple { .. }
         | a @ Action::LogicImport { .. }
         | a @ Action::LogicQuery { .. } => {
-            if let Some(ui) = handle_logic_actions(&mut state, a) {
-                return Ok(ui);
+            if let Some(ui) = handle_logic_actions(state, a) {
+                return Ok(Some(ui));
             }
         }
         a @ Action::JwtScreen { .. }
         | a @ Action::JwtDecode { .. }
         | a @ Action::JwtClear
         | a @ Action::JwtPaste { .. } => {
-            handle_jwt_actions(&mut state, a);
-        }
-        Action::SystemInfoScreen => {
-            state.push_screen(Screen::SystemInfo);
-            state.system_info.error = None;
+            handle_jwt_actions(state, a);
         }
-        Action::SystemInfoUpdate { bindings } => {
-            state.push_screen(Screen::SystemInfo);
-            match features::system_info::apply_system_info_bindings(&mut state, &bindings) {
-                Ok(_) => {} // No-op
-                Err(e) => state.system_info.error = Some(e),
-            }
+        Action::CertInspectorScreen => {
+            state.push_screen(Screen::CertInspector);
         }
-        Action::MultiHashScreen => {
-            if let Some(ui) = handle_multi_hash_actions(&mut state, Action::MultiHashScreen) {
-                return Ok(ui);
+        Action::CertInspect { input } => {
+            let input = input.unwrap_or_else(|| state.cert_inspector.input.clone());
+            state
+                .cert_inspector
+                .load(Ok(input.clone().into_bytes()), None);
+            state.cert_inspector.input = input;
+            if !matches!(state.current_screen(), Screen::CertInspector) {
+                state.push_screen(Screen::CertInspector);
             }
         }
-        Action::HashAll {
+        Action::CertOpen {
             path,
             fd,
-            loading_only,
+            error,
+            name,
+        } => {
+            let mut fd_handle = FdHandle::new(fd);
+            let name = name.or_else(|| {
+                path.as_deref()
+                    .and_then(|p| std::path::Path::new(p).file_name())
+                    .map(|n| n.to_string_lossy().into_owned())
+            });
+            let file = match (error, fd_handle.take(), path.as_deref()) {
+                (Some(e), _, _) => Err(e),
+                (None, Some(fd), _) => Ok(unsafe { File::from_raw_fd(fd as RawFd) }),
+                (None, None, Some(p)) => File::open(p).map_err(|e| format!("read_failed:{e}")),
+                (None, None, None) => Err("missing_source".into()),
+            };
+            // One byte over the cap is enough for the parser to refuse the file.
+            let bytes = file.and_then(|f| {
+                let mut bytes = Vec::new();
+                f.take(MAX_CERT_INPUT_BYTES + 1)
+                    .read_to_end(&mut bytes)
+                    .map(|_| bytes)
+                    .map_err(|e| format!("read_failed:{e}"))
+            });
+            state.cert_inspector.input.clear();
+            state.cert_inspector.load(bytes, name);
+            if !matches!(state.current_screen(), Screen::CertInspector) {
+                state.push_screen(Screen::CertInspector);
+            }
+        }
+        Action::PasswordStrengthScreen => {
+            state.password_strength.reset();
+            state.push_screen(Screen::PasswordStrength);
+        }
+        Action::PasswordStrengthCheck { password, show } => {
+            if let Some(show) = show {
+                state.password_strength.show = show;
+            }
+            let password = password.unwrap_or_else(|| state.password_strength.password.clone());
+            state.password_strength.check(password);
+            if !matches!(state.current_screen(), Screen::PasswordStrength) {
+                state.push_screen(Screen::PasswordStrength);
+            }
+        }
+        Action::DateCalcScreen => {
+            state.date_calc.compute(Utc::now());
+            state.push_screen(Screen::DateCalc);
+        }
+        Action::DateCalcCompute { bindings } => {
+            apply_date_calc_bindings(state, &bindings);
+            state.date_calc.compute(Utc::now());
+            if !matches!(state.current_screen(), Screen::DateCalc) {
+                state.push_screen(Screen::DateCalc);
+            }
+        }
+        Action::DateCalcSwapTz { bindings } => {
+            apply_date_calc_bindings(state, &bindings);
+            let calc = &mut state.date_calc;
+            std::mem::swap(&mut calc.from_tz, &mut calc.to_tz);
+            calc.compute(Utc::now());
+            if !matches!(state.current_screen(), Screen::DateCalc) {
+                state.push_screen(Screen::DateCalc);
+            }
+        }
+        Action::SystemInfoScreen => {
+            state.push_screen(Screen::SystemInfo);
+            state.system_info.error = None;
+        }
+        Action::SystemInfoUpdate { bindings } => {
+            state.push_screen(Screen::SystemInfo);
+            match features::system_info::apply_system_info_bindings(state, &bindings) {
+                Ok(_) => {} // No-op
+                Err(e) => state.system_info.error = Some(e),
+            }
+        }
+        Action::MultiHashScreen => {
+            if let Some(ui) = handle_multi_hash_actions(state, Action::MultiHashScreen) {
+                return Ok(Some(ui));
+            }
+        }
+        Action::HashAll {
+            path,
+            fd,
+            loading_only,
         } => {
-            return handle_multi_hash_job(state, path, fd, loading_only);
+            return handle_multi_hash_job(state, path, fd, loading_only).map(Some);
         }
         Action::PresetFilter { query } => {
             let trimmed = query.and_then(|q| {
@@ -2167,9 +4301,10 @@ fn handle_command(command: Command) -> Result<Value, String> {
                 state.preset_state.current_tool_id = Some(tool);
             }
             state.preset_state.name_input.clear();
+            state.preset_state.tags_input.clear();
             state.push_screen(Screen::PresetSave);
         }
-        Action::PresetSave { name } => {
+        Action::PresetSave { name, tags } => {
             state.preset_state.is_saving = true;
             let tool_id = state
                 .preset_state
@@ -2180,7 +4315,7 @@ fn handle_command(command: Command) -> Result<Value, String> {
                 state.preset_state.error = Some("preset_missing_tool".into());
                 state.preset_state.is_saving = false;
                 state.replace_current(Screen::PresetSave);
-                return Ok(render_root(&mut state));
+                return Ok(None);
             };
             state.preset_state.current_tool_id = Some(tool_id.clone());
 
@@ -2198,26 +4333,29 @@ fn handle_command(command: Command) -> Result<Value, String> {
                 state.preset_state.error = Some("preset_name_empty".into());
                 state.preset_state.is_saving = false;
                 state.replace_current(Screen::PresetSave);
-                return Ok(render_root(&mut state));
+                return Ok(None);
             }
             state.preset_state.name_input = trimmed.to_string();
+            if let Some(tags) = tags {
+                state.preset_state.tags_input = tags;
+            }
 
-            let payload = match preset_payload_for_tool(&state, &tool_id) {
+            let payload = match preset_payload_for_tool(state, &tool_id) {
                 Ok(p) => p,
                 Err(e) => {
                     state.preset_state.error = Some(e);
                     state.preset_state.is_saving = false;
                     state.replace_current(Screen::PresetSave);
-                    return Ok(render_root(&mut state));
+                    return Ok(None);
                 }
             };
 
-            match save_preset(&tool_id, trimmed, payload) {
+            let tags = parse_tags(&state.preset_state.tags_input);
+            match save_preset(&tool_id, trimmed, tags, payload) {
                 Ok(saved) => {
                     state.preset_state.is_saving = false;
                     state.preset_state.error = None;
-                    state.preset_state.last_message =
-                        Some(format!("Saved preset \"{}\"", saved.name));
+                    state.push_toast(t!("presets_saved_toast", name = saved.name));
                     if !state.preset_state.presets.iter().any(|p| p.id == saved.id) {
                         state.preset_state.presets.insert(0, saved);
                         state
@@ -2263,11 +4401,10 @@ fn handle_command(command: Command) -> Result<Value, String> {
 
             if let Some(preset) = preset {
                 state.preset_state.current_tool_id = Some(preset.tool_id.clone());
-                match apply_preset_to_state(&mut state, &preset) {
+                match apply_preset_to_state(state, &preset) {
                     Ok(_) => {
                         state.preset_state.error = None;
-                        state.preset_state.last_message =
-                            Some(format!("Applied \"{}\"", preset.name));
+                        state.push_toast(t!("presets_applied_toast", name = preset.name));
                     }
                     Err(e) => {
                         state.preset_state.error = Some(e);
@@ -2292,9 +4429,116 @@ fn handle_command(command: Command) -> Result<Value, String> {
                 state.replace_current(Screen::PresetManager);
             }
         }
+        Action::PresetShareQr { id } => {
+            let payload = state
+                .preset_state
+                .presets
+                .iter()
+                .find(|p| p.id == id)
+                .ok_or_else(|| "preset_not_found".to_string())
+                .and_then(preset_qr_payload);
+            match payload {
+                Ok(text) => {
+                    state.push_screen(Screen::Qr);
+                    if let Err(e) = handle_qr_action(state, &text) {
+                        state.last_error = Some(e);
+                    }
+                }
+                Err(e) => {
+                    state.preset_state.error = Some(e);
+                    if matches!(state.current_screen(), Screen::PresetManager) {
+                        state.replace_current(Screen::PresetManager);
+                    }
+                }
+            }
+        }
+        Action::PresetToggleGroup { tool_id } => {
+            let collapsed = &mut state.preset_state.collapsed_tools;
+            if let Some(pos) = collapsed.iter().position(|t| *t == tool_id) {
+                collapsed.remove(pos);
+            } else {
+                collapsed.push(tool_id);
+            }
+            if matches!(state.current_screen(), Screen::PresetManager) {
+                state.replace_current(Screen::PresetManager);
+            }
+        }
+        Action::PresetReorderMode => {
+            state.preset_state.reordering = !state.preset_state.reordering;
+            if matches!(state.current_screen(), Screen::PresetManager) {
+                state.replace_current(Screen::PresetManager);
+            }
+        }
+        Action::PresetReorder { order } => {
+            move_presets(&mut state.preset_state.presets, &order);
+            if let Err(e) = save_preset_order(&state.preset_state.presets) {
+                state.preset_state.error = Some(e);
+            }
+            if matches!(state.current_screen(), Screen::PresetManager) {
+                state.replace_current(Screen::PresetManager);
+            }
+        }
+        Action::PresetExport { all } => {
+            let exported = if all {
+                load_presets().and_then(|list| export_presets(&list.iter().collect::<Vec<_>>()))
+            } else {
+                export_presets(&visible_presets(&state.preset_state))
+            };
+            match exported {
+                Ok(path) => {
+                    record_output(&path);
+                    state.preset_state.error = None;
+                    state.preset_state.last_message =
+                        Some(format!("Exported to {}", path.display()));
+                }
+                Err(e) => state.preset_state.error = Some(e),
+            }
+            if matches!(state.current_screen(), Screen::PresetManager) {
+                state.replace_current(Screen::PresetManager);
+            }
+        }
+        Action::PresetSetImportConflict { conflict } => {
+            state.preset_state.import_conflict = conflict;
+            if matches!(state.current_screen(), Screen::PresetManager) {
+                state.replace_current(Screen::PresetManager);
+            }
+        }
+        Action::PresetImport { path, fd, error } => {
+            let mut fd_handle = FdHandle::new(fd);
+            let content = match (error, fd_handle.take(), path.as_deref()) {
+                (Some(e), _, _) => Err(e),
+                (None, Some(fd), _) => read_text_from_fd(fd as RawFd),
+                (None, None, Some(path)) => {
+                    std::fs::read_to_string(path).map_err(|e| format!("read_failed:{e}"))
+                }
+                (None, None, None) => Err("missing_source".into()),
+            };
+            let conflict = state.preset_state.import_conflict;
+            match content.and_then(|c| import_presets(&c, conflict)) {
+                Ok(summary) => {
+                    state.preset_state.error = None;
+                    state.preset_state.last_message = Some(format!(
+                        "Imported {}, replaced {}, skipped {}, unsupported {}",
+                        summary.imported, summary.replaced, summary.skipped, summary.unsupported
+                    ));
+                    match load_presets() {
+                        Ok(list) => state.preset_state.presets = list,
+                        Err(e) => state.preset_state.error = Some(e),
+                    }
+                }
+                Err(e) => state.preset_state.error = Some(e),
+            }
+            if matches!(state.current_screen(), Screen::PresetManager) {
+                state.replace_current(Screen::PresetManager);
+            }
+        }
         a @ Action::PixelArtScreen
         | a @ Action::PixelArtPick { .. }
         | a @ Action::PixelArtSetScale { .. }
+        | a @ Action::PixelArtSetUpscaler { .. }
+        | a @ Action::PixelArtSetCrt { .. }
+        | a @ Action::PixelArtSetPaletteLock { .. }
+        | a @ Action::PixelArtLockDitheringPalette
         | a @ Action::PixelArtApply { .. }
         | a @ Action::KotlinImageScreen(_)
         | a @ Action::KotlinImageResizeScreen
@@ -2302,23 +4546,34 @@ fn handle_command(command: Command) -> Result<Value, String> {
         | a @ Action::KotlinImageResult { .. }
         | a @ Action::KotlinImageOutputDir { .. }
         | a @ Action::KotlinImagePick { .. }
+        | a @ Action::ImageResize { .. }
+        | a @ Action::ImageResizeBatch { .. }
         | a @ Action::DitheringScreen
         | a @ Action::DitheringPickImage { .. }
         | a @ Action::DitheringSetMode { .. }
         | a @ Action::DitheringSetPalette { .. }
-        | a @ Action::DitheringApply { .. } => {
-            if let Some(ui) = handle_media_actions(&mut state, a) {
-                return Ok(ui);
+        | a @ Action::DitheringSetSerpentine { .. }
+        | a @ Action::DitheringSetCustomPalette { .. }
+        | a @ Action::DitheringPaletteFromImage { .. }
+        | a @ Action::DitheringApply { .. }
+        | a @ Action::ImageAdjustScreen
+        | a @ Action::ImageAdjustPick { .. }
+        | a @ Action::ImageAdjustUpdate { .. }
+        | a @ Action::ImageAdjustReset
+        | a @ Action::ImageAdjustApply { .. } => {
+            if let Some(ui) = handle_media_actions(state, a) {
+                return Ok(Some(ui));
             }
         }
         Action::RegexTesterScreen => {
             state.push_screen(Screen::RegexTester);
             state.regex_tester.error = None;
             state.regex_tester.match_results.clear();
+            state.regex_tester.split_pieces.clear();
         }
         Action::RegexTest { bindings } => {
             state.push_screen(Screen::RegexTester);
-            handle_regex_action(&mut state, &bindings);
+            handle_regex_action(state, &bindings);
             if matches!(state.current_screen(), Screen::RegexTester) {
                 state.replace_current(Screen::RegexTester);
             }
@@ -2327,32 +4582,137 @@ fn handle_command(command: Command) -> Result<Value, String> {
             state.push_screen(Screen::RegexTester);
             state.regex_tester.sample_text.clear();
             state.regex_tester.match_results.clear();
+            state.regex_tester.split_pieces.clear();
             state.regex_tester.error = None;
             if matches!(state.current_screen(), Screen::RegexTester) {
                 state.replace_current(Screen::RegexTester);
             }
         }
+        Action::RegexExplain { bindings } => {
+            handle_regex_explain(state, &bindings);
+            if matches!(state.current_screen(), Screen::RegexTester) {
+                state.replace_current(Screen::RegexTester);
+            }
+        }
+        Action::RegexFile {
+            path,
+            fd,
+            error,
+            bindings,
+        } => {
+            apply_regex_bindings(state, &bindings);
+            state.regex_tester.file_result = None;
+            if let Some(e) = error {
+                state.regex_tester.error = Some(e);
+            } else {
+                state.regex_tester.error = None;
+                state.loading_message = Some(t!("loading_searching_file").to_string());
+                state.loading_with_spinner = true;
+                let job = WorkerJob::RegexFile {
+                    path,
+                    fd,
+                    pattern: state.regex_tester.pattern.clone(),
+                    flags: state.regex_tester.flags,
+                };
+                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                    state.regex_tester.error = Some(e);
+                    state.loading_message = None;
+                    state.loading_with_spinner = false;
+                }
+                #[cfg(test)]
+                {
+                    apply_worker_results(state);
+                }
+            }
+            if matches!(state.current_screen(), Screen::RegexTester) {
+                state.replace_current(Screen::RegexTester);
+            }
+        }
         Action::MathToolScreen => {
             state.push_screen(Screen::MathTool);
             state.math_tool.error = None;
         }
+        Action::MathConstantsScreen => {
+            state.push_screen(Screen::MathConstants);
+        }
+        Action::MathInsertConstant { bindings } => {
+            handle_math_action(state, "math_insert_constant", &bindings);
+            if matches!(state.current_screen(), Screen::MathConstants) {
+                state.pop_screen();
+            }
+            if matches!(state.current_screen(), Screen::MathTool) {
+                state.replace_current(Screen::MathTool);
+            }
+        }
         Action::MathCalculate { bindings } => {
             state.push_screen(Screen::MathTool);
-            handle_math_action(&mut state, "math_calculate", &bindings);
+            if let Some(input) = bindings.get("math_expr") {
+                state.math_tool.expression = input.clone();
+            }
+            // A stale plot would sit under an unrelated result; a new plot fills these again.
+            state.math_tool.plot_path = None;
+            state.math_tool.plot_base64 = None;
+            match parse_plot_call(&state.math_tool.expression) {
+                Some(Ok(request)) => {
+                    state.math_tool.error = None;
+                    state.loading_message = Some(t!("loading_rendering_plot").to_string());
+                    state.loading_with_spinner = true;
+                    let job = WorkerJob::MathPlot { request };
+                    if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                        state.math_tool.error = Some(e);
+                        state.loading_message = None;
+                    }
+                    #[cfg(test)]
+                    {
+                        apply_worker_results(state);
+                    }
+                }
+                Some(Err(e)) => state.math_tool.error = Some(e),
+                None => {
+                    handle_math_action(state, "math_calculate", &bindings);
+                    if let Err(e) = persist_math_history(state) {
+                        state.math_tool.error = Some(e);
+                    }
+                }
+            }
             if matches!(state.current_screen(), Screen::MathTool) {
                 state.replace_current(Screen::MathTool);
             }
         }
         Action::MathClearHistory => {
             state.push_screen(Screen::MathTool);
-            handle_math_action(&mut state, "math_clear_history", &HashMap::new());
+            handle_math_action(state, "math_clear_history", &HashMap::new());
+            if let Err(e) = persist_math_history(state) {
+                state.math_tool.error = Some(e);
+            }
+            if matches!(state.current_screen(), Screen::MathTool) {
+                state.replace_current(Screen::MathTool);
+            }
+        }
+        Action::MathRecall { bindings } => {
+            handle_math_action(state, "math_recall", &bindings);
+            if matches!(state.current_screen(), Screen::MathTool) {
+                state.replace_current(Screen::MathTool);
+            }
+        }
+        Action::MathSetHistoryCap { bindings } => {
+            handle_math_action(state, "math_set_history_cap", &bindings);
+            if let Err(e) = persist_math_history(state) {
+                state.math_tool.error = Some(e);
+            }
+            if matches!(state.current_screen(), Screen::MathTool) {
+                state.replace_current(Screen::MathTool);
+            }
+        }
+        Action::MathSetFormat { format } => {
+            state.math_tool.result_format = format;
             if matches!(state.current_screen(), Screen::MathTool) {
                 state.replace_current(Screen::MathTool);
             }
         }
         Action::FunctionAnalysisAction { action } => {
             state.push_screen(Screen::FunctionAnalysis);
-            handle_function_analysis_action(&mut state, &action);
+            handle_function_analysis_action(state, &action);
             if matches!(state.current_screen(), Screen::FunctionAnalysis) {
                 state.replace_current(Screen::FunctionAnalysis);
             }
@@ -2363,7 +4723,7 @@ fn handle_command(command: Command) -> Result<Value, String> {
         }
         Action::UnitConverterAction { action, bindings } => {
             state.push_screen(Screen::UnitConverter);
-            handle_unit_converter_action(&mut state, &action, &bindings);
+            handle_unit_converter_action(state, &action, &bindings);
             if matches!(state.current_screen(), Screen::UnitConverter) {
                 state.replace_current(Screen::UnitConverter);
             }
@@ -2371,16 +4731,44 @@ fn handle_command(command: Command) -> Result<Value, String> {
         Action::UuidScreen => {
             state.push_screen(Screen::UuidGenerator);
         }
-        Action::UuidGenerate => {
+        Action::UuidGenerate { bindings } => {
+            state.push_screen(Screen::UuidGenerator);
+            handle_uuid_action(state, "uuid_generate", &bindings);
+            if matches!(state.current_screen(), Screen::UuidGenerator) {
+                state.replace_current(Screen::UuidGenerator);
+            }
+        }
+        Action::UlidGenerate => {
+            state.push_screen(Screen::UuidGenerator);
+            handle_uuid_action(state, "ulid_generate", &HashMap::new());
+            if matches!(state.current_screen(), Screen::UuidGenerator) {
+                state.replace_current(Screen::UuidGenerator);
+            }
+        }
+        Action::UuidInspect { bindings } => {
+            state.push_screen(Screen::UuidGenerator);
+            handle_uuid_action(state, "uuid_inspect", &bindings);
+            if matches!(state.current_screen(), Screen::UuidGenerator) {
+                state.replace_current(Screen::UuidGenerator);
+            }
+        }
+        Action::RandomBytesGenerate { bindings } => {
+            state.push_screen(Screen::UuidGenerator);
+            handle_uuid_action(state, "random_bytes_generate", &bindings);
+            if matches!(state.current_screen(), Screen::UuidGenerator) {
+                state.replace_current(Screen::UuidGenerator);
+            }
+        }
+        Action::NanoidGenerate { bindings } => {
             state.push_screen(Screen::UuidGenerator);
-            handle_uuid_action(&mut state, "uuid_generate", &HashMap::new());
+            handle_uuid_action(state, "nanoid_generate", &bindings);
             if matches!(state.current_screen(), Screen::UuidGenerator) {
                 state.replace_current(Screen::UuidGenerator);
             }
         }
         Action::RandomStringGenerate { bindings } => {
             state.push_screen(Screen::UuidGenerator);
-            handle_uuid_action(&mut state, "random_string_generate", &bindings);
+            handle_uuid_action(state, "random_string_generate", &bindings);
             if matches!(state.current_screen(), Screen::UuidGenerator) {
                 state.replace_current(Screen::UuidGenerator);
             }
@@ -2396,15 +4784,15 @@ fn handle_command(command: Command) -> Result<Value, String> {
         | a @ Action::QrReceiveScan { .. }
         | a @ Action::QrReceiveSave
         | a @ Action::QrGenerate { .. } => {
-            handle_qr_actions(&mut state, a);
+            handle_qr_actions(state, a);
         }
         Action::SchedulerScreen
         | Action::SchedulerAdd { .. }
         | Action::SchedulerToggle { .. }
         | Action::SchedulerDelete { .. }
         | Action::SchedulerRunNow { .. } => {
-            if let Some(ui) = handle_scheduler_actions(&mut state, &action) {
-                return Ok(ui);
+            if let Some(ui) = handle_scheduler_actions(state, &action) {
+                return Ok(Some(ui));
             }
         }
         a @ Action::PdfToolsScreen
@@ -2420,21 +4808,23 @@ fn handle_command(command: Command) -> Result<Value, String> {
         | a @ Action::PdfPreviewScreen
         | a @ Action::PdfPageOpen { .. }
         | a @ Action::PdfPageClose
+        | a @ Action::PdfTabSelect { .. }
         | a @ Action::PdfSign { .. }
         | a @ Action::PdfSignGrid { .. } => {
-            handle_pdf_actions(&mut state, a);
+            handle_pdf_actions(state, a);
         }
         a @ Action::HashVerifyScreen
         | a @ Action::HashVerify { .. }
+        | a @ Action::HashHmacConfig { .. }
         | a @ Action::HashVerifyPaste { .. }
         | a @ Action::HashPasteReference { .. }
         | a @ Action::HashQrFromLast => {
-            if let Some(ui) = handle_hash_actions(&mut state, a) {
-                return Ok(ui);
+            if let Some(ui) = handle_hash_actions(state, a) {
+                return Ok(Some(ui));
             }
         }
         a @ Action::PdfSignatureStore { .. } | a @ Action::PdfSignatureClear => {
-            handle_pdf_actions(&mut state, a);
+            handle_pdf_actions(state, a);
         }
         Action::SettingsScreen => {
             state.push_screen(Screen::Settings);
@@ -2450,12 +4840,13 @@ fn handle_command(command: Command) -> Result<Value, String> {
         | a @ Action::TextViewerOpen { .. }
         | a @ Action::TextViewerToggleTheme
         | a @ Action::TextViewerToggleLineNumbers
+        | a @ Action::TextViewerToggleMarkdownPreview
         | a @ Action::TextViewerLoadAnyway
         | a @ Action::TextViewerLoadMore
         | a @ Action::TextViewerLoadPrev
         | a @ Action::TextViewerJump { .. }
         | a @ Action::TextViewerFind { .. } => {
-            handle_text_viewer_actions(&mut state, a);
+            handle_text_viewer_actions(state, a);
         }
         a @ Action::HexEditorScreen
         | a @ Action::HexEditorOpen { .. }
@@ -2464,16 +4855,16 @@ fn handle_command(command: Command) -> Result<Value, String> {
         | a @ Action::HexEditorSave
         | a @ Action::HexEditorSaveAs { .. }
         | a @ Action::HexEditorSaveAsPicker => {
-            if let Some(ui) = handle_hex_editor_actions(&mut state, a) {
-                return Ok(ui);
+            if let Some(ui) = handle_hex_editor_actions(state, a) {
+                return Ok(Some(ui));
             }
         }
         a @ Action::SqlScreen
         | a @ Action::SqlImport { .. }
         | a @ Action::SqlExecute { .. }
         | a @ Action::SqlClearAll => {
-            if let Some(ui) = handle_sql_actions(&mut state, a) {
-                return Ok(ui);
+            if let Some(ui) = handle_sql_actions(state, a) {
+                return Ok(Some(ui));
             }
         }
         a @ Action::MirScriptingScreen
@@ -2482,8 +4873,8 @@ fn handle_command(command: Command) -> Result<Value, String> {
         | a @ Action::MirScriptingClearOutput
         | a @ Action::MirScriptingClearSource
         | a @ Action::MirScriptingLoadExample => {
-            if let Some(ui) = handle_mir_scripting_actions(&mut state, a) {
-                return Ok(inject_root_extras(ui, &mut state));
+            if let Some(ui) = handle_mir_scripting_actions(state, a) {
+                return Ok(Some(inject_root_extras(ui, state)));
             }
         }
         Action::CScriptingExecute { source, args } => {
@@ -2498,7 +4889,7 @@ fn handle_command(command: Command) -> Result<Value, String> {
                 state.c_scripting.output.clear();
                 state.c_scripting.compilation_time_us = None;
                 state.c_scripting.avg_execution_time_us = None;
-                state.loading_message = Some("Running C script in background...".to_string());
+                state.loading_message = Some(t!("loading_running_c_script").to_string());
                 state.loading_with_spinner = true;
 
                 let job = WorkerJob::CScriptingExecute {
@@ -2515,11 +4906,28 @@ fn handle_command(command: Command) -> Result<Value, String> {
                     state.loading_with_spinner = false;
                 }
 
-                return Ok(features::c_scripting::render_c_scripting_screen(&state));
+                return Ok(Some(features::c_scripting::render_c_scripting_screen(
+                    state,
+                )));
             } else {
-                if let Some(ui) = handle_c_scripting_actions(&mut state, Action::CScriptingExecute { source, args }) {
-                    return Ok(ui);
+                if let Some(ui) = handle_c_scripting_actions(state, Action::CScriptingExecute { source, args }) {
+                    return Ok(Some(ui));
+                }
+            }
+        }
+        Action::CScriptingOpen { path, fd, error } => {
+            let mut fd_handle = FdHandle::new(fd);
+            let content = match (error, fd_handle.take(), path.as_deref()) {
+                (Some(e), _, _) => Err(e),
+                (None, Some(fd), _) => read_text_from_fd(fd as RawFd),
+                (None, None, Some(path)) => {
+                    std::fs::read_to_string(path).map_err(|e| format!("read_failed:{e}"))
                 }
+                (None, None, None) => Err("missing_source".into()),
+            };
+            state.c_scripting.load_source(content);
+            if !matches!(state.current_screen(), Screen::CScripting) {
+                state.push_screen(Screen::CScripting);
             }
         }
         a @ Action::CScriptingScreen
@@ -2528,9 +4936,108 @@ fn handle_command(command: Command) -> Result<Value, String> {
         | a @ Action::CScriptingToggleJit { .. }
         | a @ Action::CScriptingToggleBenchmark { .. }
         | a @ Action::CScriptingToggleThread { .. } => {
-            if let Some(ui) = handle_c_scripting_actions(&mut state, a) {
-                return Ok(ui);
+            if let Some(ui) = handle_c_scripting_actions(state, a) {
+                return Ok(Some(ui));
+            }
+        }
+        Action::JitBenchmarkScreen => {
+            state.push_screen(Screen::JitBenchmark);
+        }
+        Action::JitBenchmarkRun => {
+            if !matches!(state.current_screen(), Screen::JitBenchmark) {
+                state.push_screen(Screen::JitBenchmark);
+            }
+            state.jit_benchmark = None;
+            state.jit_benchmark_error = None;
+            state.loading_with_spinner = true;
+            state.loading_message = Some(t!("loading_benchmarking").to_string());
+            if let Err(e) = STATE
+                .get_or_init(GlobalState::new)
+                .worker()
+                .enqueue(WorkerJob::JitBenchmark)
+            {
+                state.jit_benchmark_error = Some(e);
+            }
+            #[cfg(test)]
+            {
+                apply_worker_results(state);
+            }
+        }
+        Action::PluginsScreen => {
+            state.plugins.installed = list_plugins(&plugins_dir());
+            state.plugins.status = None;
+            state.plugins.error = None;
+            state.push_screen(Screen::Plugins);
+        }
+        Action::PluginInstall {
+            path,
+            fd,
+            error,
+            name,
+        } => {
+            let mut fd_handle = FdHandle::new(fd);
+            let name = name.or_else(|| {
+                path.as_deref()
+                    .and_then(|p| std::path::Path::new(p).file_name())
+                    .map(|n| n.to_string_lossy().into_owned())
+            });
+            let installed = match (error, name) {
+                (Some(e), _) => Err(e),
+                (None, None) => Err("plugin_missing_name".into()),
+                (None, Some(name)) => match (fd_handle.take(), path.as_deref()) {
+                    (Some(fd), _) => install_plugin(&plugins_dir(), &name, unsafe {
+                        File::from_raw_fd(fd as RawFd)
+                    }),
+                    (None, Some(p)) => File::open(p)
+                        .map_err(|e| format!("read_failed:{e}"))
+                        .and_then(|file| install_plugin(&plugins_dir(), &name, file)),
+                    (None, None) => Err("missing_path".into()),
+                },
+            };
+            match installed {
+                Ok(plugin) => {
+                    state.plugins.status =
+                        Some(t!("plugins_installed", name = plugin.name).to_string());
+                    state.plugins.error = None;
+                }
+                Err(e) => {
+                    state.plugins.status = None;
+                    state.plugins.error = Some(e);
+                }
+            }
+            state.plugins.installed = list_plugins(&plugins_dir());
+            if !matches!(state.current_screen(), Screen::Plugins) {
+                state.push_screen(Screen::Plugins);
+            }
+        }
+        Action::PluginRemove { id } => {
+            match state.plugins.find(&id).cloned() {
+                Some(plugin) => match remove_plugin(&plugin) {
+                    Ok(()) => {
+                        if state.plugins.active.as_ref().is_some_and(|p| p.id == id) {
+                            state.plugins.reset();
+                        }
+                        state.plugins.status =
+                            Some(t!("plugins_removed", name = plugin.name).to_string());
+                        state.plugins.error = None;
+                    }
+                    Err(e) => state.plugins.error = Some(e),
+                },
+                None => state.plugins.error = Some(format!("plugin_not_found:{id}")),
+            }
+            state.plugins.installed = list_plugins(&plugins_dir());
+        }
+        Action::PluginOpen { id } => match state.plugins.find(&id).cloned() {
+            Some(plugin) => {
+                state.plugins.open(plugin);
+                state.push_screen(Screen::Plugin);
+                enqueue_plugin_call(state, None, String::from("{}"));
             }
+            None => state.plugins.error = Some(format!("plugin_not_found:{id}")),
+        },
+        Action::PluginAction { action, bindings } => {
+            let bindings = serde_json::to_string(&bindings).unwrap_or_else(|_| "{}".into());
+            enqueue_plugin_call(state, Some(action), bindings);
         }
         a @ Action::SynthesizerScreen
         | a @ Action::SynthesizerPlay
@@ -2538,8 +5045,8 @@ fn handle_command(command: Command) -> Result<Value, String> {
         | a @ Action::SynthesizerApply
         | a @ Action::SynthesizerUpdateCode { .. }
         | a @ Action::SynthesizerLoadExample => {
-            if let Some(ui) = handle_synthesizer_actions(&mut state, a) {
-                return Ok(ui);
+            if let Some(ui) = handle_synthesizer_actions(state, a) {
+                return Ok(Some(ui));
             }
         }
 
@@ -2549,20 +5056,26 @@ fn handle_command(command: Command) -> Result<Value, String> {
         | a @ Action::PlottingSetY { .. }
         | a @ Action::PlottingSetType { .. }
         | a @ Action::PlottingGenerate => {
-            handle_plotting_actions(&mut state, a);
+            handle_plotting_actions(state, a);
         }
         a @ Action::SensorLoggerScreen
         | a @ Action::SensorLoggerStart { .. }
-        | a @ Action::SensorLoggerStop
+        | a @ Action::SensorLoggerStop { .. }
         | a @ Action::SensorLoggerShare
         | a @ Action::SensorLoggerStatus { .. }
+        | a @ Action::SensorLoggerExport { .. }
+        | a @ Action::SensorLoggerSegment { .. }
+        | a @ Action::SensorLoggerPause
+        | a @ Action::SensorLoggerResume
+        | a @ Action::SensorLoggerFormat { .. }
         | a @ Action::CompassDemo
         | a @ Action::CompassSet { .. }
+        | a @ Action::SensorCalibrationSet { .. }
         | a @ Action::BarometerScreen
         | a @ Action::BarometerSet { .. }
         | a @ Action::MagnetometerScreen
         | a @ Action::MagnetometerSet { .. } => {
-            handle_sensor_actions(&mut state, a);
+            handle_sensor_actions(state, a);
         }
         Action::ShaderDemo => state.push_screen(Screen::ShaderDemo),
         Action::LoadShader { path, fd, error } => {
@@ -2594,23 +5107,23 @@ fn handle_command(command: Command) -> Result<Value, String> {
         Action::ColorFromHex { input } => {
             state.push_screen(Screen::ColorTools);
             let txt = input.unwrap_or_default();
-            handle_color_action(&mut state, "color_from_hex", &txt);
+            handle_color_action(state, "color_from_hex", &txt);
         }
         Action::ColorFromRgb { input } => {
             state.push_screen(Screen::ColorTools);
             let txt = input.unwrap_or_default();
-            handle_color_action(&mut state, "color_from_rgb", &txt);
+            handle_color_action(state, "color_from_rgb", &txt);
         }
         Action::ColorCopyHexInput { input } => {
             state.push_screen(Screen::ColorTools);
             let val = input
                 .or_else(|| state.text_input.clone())
                 .unwrap_or_default();
-            handle_color_action(&mut state, "color_copy_hex_input", &val);
+            handle_color_action(state, "color_copy_hex_input", &val);
         }
         Action::ColorCopyClipboard => {
             state.push_screen(Screen::ColorTools);
-            state.toast = Some("Copied to clipboard".into());
+            state.push_toast(t!("toast_copied_to_clipboard"));
             state.haptic = true;
         }
         Action::Hash {
@@ -2620,7 +5133,7 @@ fn handle_command(command: Command) -> Result<Value, String> {
             error,
             loading_only,
         } => {
-            return handle_hash_job(state, algo, path, fd, error, loading_only);
+            return handle_hash_job(state, algo, path, fd, error, loading_only).map(Some);
         }
         Action::ProgressDemoScreen => {
             state.push_screen(Screen::ProgressDemo);
@@ -2630,28 +5143,171 @@ fn handle_command(command: Command) -> Result<Value, String> {
         Action::ProgressDemoStart { loading_only } => {
             if loading_only {
                 state.replace_current(Screen::Loading);
-                state.loading_message = Some("Working...".into());
-                return Ok(render_root(&mut state));
+                state.loading_message = Some(t!("loading_working").to_string());
+                return Ok(None);
             } else {
                 state.replace_current(Screen::ProgressDemo);
-                state.progress_status = Some("Starting...".into());
+                state.progress_status = Some(t!("progress_demo_starting").to_string());
             }
         }
         Action::ProgressDemoFinish => {
             state.replace_current(Screen::ProgressDemo);
-            state.progress_status = Some("Done after simulated delay.".into());
+            state.progress_status = Some(t!("progress_demo_done").to_string());
             state.loading_message = None;
         }
         Action::FileInfoScreen => {
             state.push_screen(Screen::FileInfo);
             state.last_file_info = None;
+            state.file_info_hex = None;
+            state.file_info_hex_error = None;
+            state.file_info_batch.clear();
             state.last_error = None;
         }
-        Action::FileInfo { path, fd, error } => {
+        Action::RecentFilesClear => {
+            state.recent_files.clear();
+            if let Err(e) = save_recent_files(&state.recent_files) {
+                state.last_error = Some(e);
+            }
+        }
+        Action::JobQueueScreen => {
+            if !matches!(state.current_screen(), Screen::JobQueue) {
+                state.push_screen(Screen::JobQueue);
+            }
+        }
+        Action::JobQueueRefresh | Action::PollProgress => {
+            // Worker results and progress were applied above; the render shows them.
+        }
+        Action::JobQueueClear => {
+            STATE
+                .get_or_init(GlobalState::new)
+                .jobs_lock()
+                .clear_finished();
+        }
+        Action::JobCancel { id } => {
+            let global = STATE.get_or_init(GlobalState::new);
+            let cancelled = id
+                .ok_or_else(|| "missing_job_id".to_string())
+                .and_then(|id| global.jobs_lock().cancel(id));
+            match cancelled {
+                Ok(()) => {
+                    // Nothing left to wait for: drop the spinner the cancelled job left behind.
+                    if global.jobs_lock().active_count() == 0 {
+                        state.nav_stack.retain(|screen| *screen != Screen::Loading);
+                        state.ensure_navigation();
+                        state.loading_message = None;
+                    }
+                }
+                Err(e) => state.last_error = Some(e),
+            }
+        }
+        Action::UsageStatsScreen => {
+            state.push_screen(Screen::UsageStats);
+        }
+        Action::UsageStatsToggle { enabled } => {
+            if !enabled {
+                state.end_tool_session();
+            }
+            state.usage_stats_enabled = enabled;
+        }
+        Action::HomeSortByUsageToggle { enabled } => {
+            state.home_sort_by_usage = enabled;
+        }
+        Action::UsageStatsClear => state.clear_usage_stats(),
+        Action::DebugLogScreen => {
+            state.push_screen(Screen::DebugLog);
+        }
+        Action::DebugLogView => {
+            let content = logging::render_text(&logging::recent());
+            state.push_screen(Screen::TextViewer);
+            state.text_view_path = Some("kistaverk-debug.log".into());
+            state.text_view_total_bytes = Some(content.len() as u64);
+            state.text_view_loaded_bytes = content.len() as u64;
+            state.text_view_content = Some(content);
+            state.text_view_error = None;
+            state.text_view_language = None;
+            state.text_view_hex_preview = None;
+            state.text_view_find_query = None;
+            state.text_view_find_match = None;
+            state.text_view_window_offset = 0;
+            state.text_view_has_more = false;
+            state.text_view_has_previous = false;
+            state.text_view_cached_path = None;
+            state.text_view_wrap = true;
+        }
+        Action::DebugLogExport => {
+            let dir = features::storage::downloads_dir()
+                .unwrap_or_else(features::storage::preferred_temp_dir);
+            match logging::export_to(&dir) {
+                Ok(path) => {
+                    record_output(&path);
+                    state.push_toast(t!("toast_result_saved", path = path));
+                }
+                Err(e) => state.last_error = Some(e),
+            }
+        }
+        Action::DebugLogClear => logging::clear(),
+        a @ Action::CleanupScreen
+        | a @ Action::CleanupRefresh
+        | a @ Action::CleanupToggle { .. }
+        | a @ Action::CleanupSelectAll
+        | a @ Action::CleanupSelectStale
+        | a @ Action::CleanupSelectNone
+        | a @ Action::CleanupDeleteSelected
+        | a @ Action::CleanupSavePolicy { .. }
+        | a @ Action::CleanupCleanStale => {
+            handle_cleanup_actions(state, a);
+        }
+        a @ Action::ImageEditScreen
+        | a @ Action::ImageEditPick { .. }
+        | a @ Action::ImageEditCrop { .. }
+        | a @ Action::ImageEditRotate { .. }
+        | a @ Action::ImageEditFlip { .. }
+        | a @ Action::ImageEditReset
+        | a @ Action::ImageEditApply => {
+            handle_image_edit_actions(state, a);
+        }
+        a @ Action::MetadataStripScreen | a @ Action::MetadataStripPick { .. } => {
+            handle_metadata_strip_actions(state, a);
+        }
+        a @ Action::ImageHistogramScreen | a @ Action::ImageHistogramPick { .. } => {
+            handle_image_histogram_actions(state, a);
+        }
+        a @ Action::AsciiArtScreen
+        | a @ Action::AsciiArtPick { .. }
+        | a @ Action::AsciiArtSync { .. }
+        | a @ Action::AsciiArtView
+        | a @ Action::AsciiArtExport => {
+            handle_ascii_art_actions(state, a);
+        }
+        a @ Action::GifFramesScreen
+        | a @ Action::GifFramesPick { .. }
+        | a @ Action::GifFramesAssemble { .. } => {
+            handle_gif_frames_actions(state, a);
+        }
+        a @ Action::ExifEditScreen
+        | a @ Action::ExifEditPick { .. }
+        | a @ Action::ExifEditSync { .. }
+        | a @ Action::ExifEditSave { .. } => {
+            handle_exif_edit_actions(state, a);
+        }
+        a @ Action::WatermarkScreen
+        | a @ Action::WatermarkPickImages { .. }
+        | a @ Action::WatermarkPickLogo { .. }
+        | a @ Action::WatermarkSync { .. }
+        | a @ Action::WatermarkApply { .. } => {
+            handle_watermark_actions(state, a);
+        }
+        a @ Action::CollageScreen
+        | a @ Action::CollagePickImages { .. }
+        | a @ Action::CollageSync { .. }
+        | a @ Action::CollageBuild { .. } => {
+            handle_collage_actions(state, a);
+        }
+        Action::FileInfoBatch { paths, fds, names } => {
             state.replace_current(Screen::Loading);
-            state.loading_message = Some("Reading file info...".into());
+            state.loading_message = Some(t!("loading_reading_file_info").to_string());
             state.loading_with_spinner = true;
-            let job = WorkerJob::FileInfo { path, fd, error };
+            let job = WorkerJob::FileInfoBatch { paths, fds, names };
             if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
                 state.last_error = Some(e);
                 state.loading_message = None;
@@ -2660,59 +5316,179 @@ fn handle_command(command: Command) -> Result<Value, String> {
             }
             #[cfg(test)]
             {
-                apply_worker_results(&mut state);
+                apply_worker_results(state);
             }
         }
-        Action::TextToolsScreen { bindings } => {
-            state.push_screen(Screen::TextTools);
-            state.text_output = None;
-            state.text_operation = None;
-            if let Some(input) = bindings.get("text_input") {
-                state.text_input = Some(input.clone());
+        Action::FileInfoBatchHash { index, fd } => {
+            // Picked files come back as a fresh fd; paths are re-read directly.
+            let source = match state.file_info_batch.get(index) {
+                None => Err(format!("invalid_batch_index:{index}")),
+                Some(entry) => match (fd, &entry.path) {
+                    (Some(fd), _) => Ok(HashSourceInput::Fd(fd)),
+                    (None, Some(path)) => Ok(HashSourceInput::Path(path.clone())),
+                    (None, None) => Err("missing_source".to_string()),
+                },
+            };
+            let source = match source {
+                Ok(source) => source,
+                Err(e) => {
+                    state.last_error = Some(e);
+                    state.replace_current(Screen::FileInfo);
+                    return Ok(None);
+                }
+            };
+            state.replace_current(Screen::Loading);
+            state.loading_message = Some(t!("loading_hashing").to_string());
+            state.loading_with_spinner = true;
+            let job = WorkerJob::FileInfoBatchHash { index, source };
+            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                state.last_error = Some(e);
+                state.loading_message = None;
+                state.loading_with_spinner = false;
+                state.replace_current(Screen::FileInfo);
+            }
+            #[cfg(test)]
+            {
+                apply_worker_results(state);
             }
         }
-        Action::TextTools { action, bindings } => {
-            handle_text_action(&mut state, action, &bindings);
-        }
-        Action::Increment => state.counter += 1,
-    }
+        Action::FileInfoHex {
+            path,
+            fd,
+            direction,
+            offset,
+            length,
+        } => {
+            state.replace_current(Screen::FileInfo);
+            let current = state.file_info_hex.as_ref();
+            let base = current.map(|w| w.offset).unwrap_or(0);
+            let length = length
+                .or(current.map(|w| w.length))
+                .unwrap_or(features::file_info::HEX_WINDOW_DEFAULT_BYTES);
+            let target = match direction.as_str() {
+                "next" => base.saturating_add(length as u64),
+                "prev" => base.saturating_sub(length as u64),
+                _ => offset.unwrap_or(base),
+            };
+            // Picked files come back as a fresh fd; paths are re-read directly.
+            let inspected_path = state
+                .last_file_info
+                .as_deref()
+                .and_then(|info| serde_json::from_str::<Value>(info).ok())
+                .and_then(|info| info.get("path")?.as_str().map(str::to_string));
+            let result = if let Some(fd) = fd {
+                features::file_info::hex_window_from_fd(fd as RawFd, target, length)
+            } else if let Some(p) = path.or(inspected_path) {
+                features::file_info::hex_window_from_path(&p, target, length)
+            } else {
+                Err("missing_source".into())
+            };
+            match result {
+                Ok(window) => {
+                    state.file_info_hex = Some(window);
+                    state.file_info_hex_error = None;
+                }
+                Err(e) => state.file_info_hex_error = Some(e),
+            }
+        }
+        Action::FileInfo {
+            path,
+            fd,
+            error,
+            name,
+        } => {
+            state.replace_current(Screen::Loading);
+            state.loading_message = Some(t!("loading_reading_file_info").to_string());
+            state.loading_with_spinner = true;
+            let job = WorkerJob::FileInfo {
+                path,
+                fd,
+                error,
+                name,
+            };
+            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                state.last_error = Some(e);
+                state.loading_message = None;
+                state.loading_with_spinner = false;
+                state.replace_current(Screen::FileInfo);
+            }
+            #[cfg(test)]
+            {
+                apply_worker_results(state);
+            }
+        }
+        Action::TextToolsScreen { bindings } => {
+            state.push_screen(Screen::TextTools);
+            state.text_output = None;
+            state.text_operation = None;
+            if let Some(input) = bindings.get("text_input") {
+                state.text_input = Some(input.clone());
+            }
+        }
+        Action::TextTools { action, bindings } => {
+            handle_text_action(state, action, &bindings);
+        }
+        Action::Increment => state.counter += 1,
+    }
+
+    // Back on the home screen, so the visit to the timed tool is over.
+    if matches!(state.current_screen(), Screen::Home) {
+        state.end_tool_session();
+    }
 
     if lock_poisoned && state.last_error.is_none() {
         state.last_error = Some("state_poisoned".into());
     }
 
-    Ok(render_root(&mut state))
+    Ok(None)
 }
 
 fn render_root(state: &mut AppState) -> Value {
-    let mut ui = render_ui(state);
-    if state.haptic {
-        if let Some(obj) = ui.as_object_mut() {
-            obj.insert("haptic".into(), Value::Bool(true));
-        }
-        state.haptic = false;
-    }
-    if let Some(toast) = state.toast.take() {
-        if let Some(obj) = ui.as_object_mut() {
-            obj.insert("toast".into(), Value::String(toast));
-        }
-    }
-    ui
+    i18n::apply_locale(state);
+    let ui = render_ui(state);
+    inject_root_extras(ui, state)
 }
 
 fn inject_root_extras(ui: Value, state: &mut AppState) -> Value {
     let mut ui = ui;
+    // Kotlin applies the app-wide night mode from this, whatever the screen.
+    let theme = state.theme_mode.as_str();
+    if let Some(obj) = ui.as_object_mut() {
+        obj.insert("theme".into(), Value::String(theme.into()));
+        obj.insert(
+            "layout_direction".into(),
+            Value::String(i18n::layout_direction(&state.locale).into()),
+        );
+    }
     if state.haptic {
         if let Some(obj) = ui.as_object_mut() {
             obj.insert("haptic".into(), Value::Bool(true));
         }
         state.haptic = false;
     }
-    if let Some(toast) = state.toast.take() {
+    if let Some(pending) = &state.pending_confirmation {
+        let cancel = t!("confirm_dialog_cancel");
+        let mut dialog = UiConfirmDialog::new(&pending.title, "confirm_pending", "cancel_pending")
+            .confirm_label(&pending.confirm_label)
+            .cancel_label(&cancel);
+        if let Some(message) = &pending.message {
+            dialog = dialog.message(message);
+        }
+        if let Some(children) = ui.get_mut("children").and_then(Value::as_array_mut) {
+            children.push(serde_json::to_value(dialog).unwrap());
+        }
+    }
+    if let Some(toast) = state.take_toast() {
         if let Some(obj) = ui.as_object_mut() {
             obj.insert("toast".into(), Value::String(toast));
         }
     }
+    let rows = state.sensor_alerts.take_pending_rows();
+    if !rows.is_empty() {
+        if let Some(obj) = ui.as_object_mut() {
+            obj.insert("sensor_log_rows".into(), json!(rows));
+        }
+    }
     ui
 }
 
@@ -2836,7 +5612,7 @@ fn handle_archive_actions(state: &mut AppState, action: Action) -> Option<Value>
                 state.archive.error = Some(err);
             } else if let Some(raw_fd) = fd_handle.take() {
                 state.loading_with_spinner = true;
-                state.loading_message = Some("Opening archive...".into());
+                state.loading_message = Some(t!("loading_opening_archive").to_string());
                 state.replace_current(Screen::Loading);
                 let job = WorkerJob::ArchiveOpen { fd: raw_fd, path };
                 if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
@@ -2865,7 +5641,7 @@ fn handle_archive_actions(state: &mut AppState, action: Action) -> Option<Value>
                     state.archive.error = Some("archive_compress_requires_path".into());
                 } else {
                     state.loading_with_spinner = true;
-                    state.loading_message = Some("Compressing...".into());
+                    state.loading_message = Some(t!("loading_compressing").to_string());
                     state.replace_current(Screen::Loading);
                     let job = WorkerJob::ArchiveCompress { source_path: path };
                     if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
@@ -2885,6 +5661,7 @@ fn handle_archive_actions(state: &mut AppState, action: Action) -> Option<Value>
         }
         Action::ArchiveOpenText { index } => {
             state.push_screen(Screen::TextViewer);
+            state.text_view_wrap = true;
             match features::archive::read_text_entry(state, index) {
                 Ok((label, text)) => {
                     state.text_view_path = Some(label);
@@ -2918,7 +5695,7 @@ fn handle_archive_actions(state: &mut AppState, action: Action) -> Option<Value>
             if let Some(path) = state.archive.path.clone() {
                 state.archive.error = None;
                 state.loading_with_spinner = true;
-                state.loading_message = Some("Extracting...".into());
+                state.loading_message = Some(t!("loading_extracting").to_string());
                 state.replace_current(Screen::Loading);
                 let job = WorkerJob::ArchiveExtractAll {
                     archive_path: path,
@@ -2941,7 +5718,7 @@ fn handle_archive_actions(state: &mut AppState, action: Action) -> Option<Value>
             if let Some(path) = state.archive.path.clone() {
                 state.archive.error = None;
                 state.loading_with_spinner = true;
-                state.loading_message = Some("Extracting...".into());
+                state.loading_message = Some(t!("loading_extracting").to_string());
                 state.replace_current(Screen::Loading);
                 let job = WorkerJob::ArchiveExtractEntry {
                     archive_path: path,
@@ -2969,6 +5746,7 @@ fn handle_compression_actions(state: &mut AppState, action: Action) {
             state.push_screen(Screen::Compression);
             state.compression_error = None;
             state.compression_status = None;
+            state.compression_benchmark = None;
         }
         Action::GzipCompress { path, fd, error } => {
             state.push_screen(Screen::Compression);
@@ -2978,7 +5756,7 @@ fn handle_compression_actions(state: &mut AppState, action: Action) {
                 state.compression_error = Some(err);
             } else if let Some(p) = path {
                 state.loading_with_spinner = true;
-                state.loading_message = Some("Compressing...".into());
+                state.loading_message = Some(t!("loading_compressing").to_string());
                 if fd.is_some() {
                     state.compression_error = Some("gzip_requires_path".into());
                 } else {
@@ -3008,7 +5786,7 @@ fn handle_compression_actions(state: &mut AppState, action: Action) {
                 state.compression_error = Some(err);
             } else if let Some(p) = path {
                 state.loading_with_spinner = true;
-                state.loading_message = Some("Decompressing...".into());
+                state.loading_message = Some(t!("loading_decompressing").to_string());
                 if fd.is_some() {
                     state.compression_error = Some("gzip_requires_path".into());
                 } else {
@@ -3030,133 +5808,218 @@ fn handle_compression_actions(state: &mut AppState, action: Action) {
                 state.compression_error = Some("missing_path".into());
             }
         }
+        Action::GzipBenchmark { path, fd, error } => {
+            state.push_screen(Screen::Compression);
+            state.compression_error = None;
+            state.compression_status = None;
+            state.compression_benchmark = None;
+            if let Some(err) = error {
+                state.compression_error = Some(err);
+            } else if let Some(p) = path {
+                state.loading_with_spinner = true;
+                state.loading_message = Some(t!("loading_benchmarking").to_string());
+                if fd.is_some() {
+                    state.compression_error = Some("gzip_requires_path".into());
+                } else {
+                    let job = WorkerJob::CompressionBenchmark { path: p };
+                    if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                        state.compression_error = Some(e);
+                    }
+                    #[cfg(test)]
+                    {
+                        apply_worker_results(state);
+                    }
+                }
+            } else if fd.is_some() {
+                state.compression_error = Some("gzip_requires_path".into());
+            } else {
+                state.compression_error = Some("missing_path".into());
+            }
+        }
         _ => {}
     }
 }
 
-fn handle_kotlin_image_batch_actions(state: &mut AppState, action: Action) {
+fn handle_image_edit_actions(state: &mut AppState, action: Action) {
+    if !matches!(state.current_screen(), Screen::ImageEditor) {
+        state.push_screen(Screen::ImageEditor);
+    }
+    let edit = &mut state.image_edit;
     match action {
-        Action::KotlinImageBatchPick { paths } => {
-            state.replace_current(Screen::KotlinImage);
-            state.image.batch_queue.extend(paths);
+        Action::ImageEditScreen => edit.reset(),
+        Action::ImageEditPick { path, fd, error } => {
+            edit.reset();
+            edit.output_dir = Some(
+                features::storage::output_dir_for(path.as_deref())
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+            let mut fd_handle = FdHandle::new(fd);
+            let source = match (error, fd_handle.take(), path) {
+                (Some(err), _, _) => Err(err),
+                (None, Some(raw_fd), path) => save_fd_to_temp(raw_fd as RawFd, path.as_deref()),
+                (None, None, Some(path)) => Ok(path),
+                (None, None, None) => Err("missing_source".into()),
+            };
+            if let Err(e) = source.and_then(|path| edit.load_source(path)) {
+                edit.error = Some(e);
+            }
         }
-        Action::KotlinImageBatchRemove { path } => {
-            state.replace_current(Screen::KotlinImage);
-            state.image.batch_queue.retain(|p| p != &path);
+        Action::ImageEditCrop { bindings } => match parse_crop_bindings(edit.ops.crop, &bindings) {
+            Ok(crop) => {
+                edit.ops.crop = crop;
+                edit.error = None;
+                edit.refresh_preview();
+            }
+            Err(e) => edit.error = Some(e),
+        },
+        Action::ImageEditRotate { degrees } => {
+            edit.ops.rotate_by(degrees);
+            edit.refresh_preview();
+        }
+        Action::ImageEditFlip { horizontal } => {
+            if horizontal {
+                edit.ops.flip_h = !edit.ops.flip_h;
+            } else {
+                edit.ops.flip_v = !edit.ops.flip_v;
+            }
+            edit.refresh_preview();
+        }
+        Action::ImageEditReset => {
+            edit.ops = EditOps::new();
+            edit.error = None;
+            edit.refresh_preview();
+        }
+        Action::ImageEditApply => {
+            let Some(source_path) = edit.source_path.clone() else {
+                edit.error = Some("no_image_selected".into());
+                return;
+            };
+            edit.error = None;
+            edit.result_path = None;
+            let job = WorkerJob::ImageEdit {
+                source_path,
+                ops: edit.ops,
+                output_dir: edit.output_dir.clone().unwrap_or_else(|| {
+                    features::storage::preferred_temp_dir()
+                        .to_string_lossy()
+                        .into_owned()
+                }),
+            };
+            state.loading_message = Some(t!("image_edit_applying_message").to_string());
+            state.loading_with_spinner = true;
+            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                state.image_edit.error = Some(e);
+            }
+            #[cfg(test)]
+            {
+                apply_worker_results(state);
+            }
         }
         _ => {}
     }
 }
 
-fn handle_vault_actions(state: &mut AppState, action: Action) {
+fn handle_metadata_strip_actions(state: &mut AppState, action: Action) {
+    if !matches!(state.current_screen(), Screen::MetadataStrip) {
+        state.push_screen(Screen::MetadataStrip);
+    }
     match action {
-        Action::VaultScreen => {
-            state.push_screen(Screen::Vault);
-            state.vault.reset();
-        }
-        Action::VaultPick { path, fd, error } => {
-            state.push_screen(Screen::Vault);
-            state.vault.status = None;
-            state.vault.error = None;
-            state.vault.is_processing = false;
-            if let Some(err) = error {
-                state.vault.error = Some(err);
-            } else if fd.is_some() {
-                state.vault.error = Some("vault_requires_path".into());
-            } else if let Some(p) = path {
-                state.vault.input_path = Some(p);
-            } else {
-                state.vault.error = Some("vault_missing_input".into());
-            }
-        }
-        Action::VaultEncrypt {
-            path,
-            fd,
+        Action::MetadataStripScreen => state.metadata_strip.reset(),
+        Action::MetadataStripPick {
+            paths,
+            fds,
+            names,
             error,
-            password,
         } => {
-            state.push_screen(Screen::Vault);
-            state.vault.status = None;
-            state.vault.error = None;
-            state.vault.is_processing = false;
+            state.metadata_strip.reset();
             if let Some(err) = error {
-                state.vault.error = Some(err);
-                return;
-            }
-            if fd.is_some() {
-                state.vault.error = Some("vault_requires_path".into());
+                state.metadata_strip.error = Some(err);
                 return;
             }
-            let pwd = password.unwrap_or_else(|| state.vault.password.clone());
-            state.vault.password = pwd.clone();
-            if pwd.trim().is_empty() {
-                state.vault.error = Some("vault_missing_password".into());
+            let output_dir = features::storage::output_dir_for(paths.first().map(String::as_str))
+                .to_string_lossy()
+                .into_owned();
+            let inputs: Vec<StripInput> = (0..paths.len().max(fds.len()))
+                .map(|i| {
+                    let path = paths.get(i).cloned();
+                    let name = names
+                        .get(i)
+                        .cloned()
+                        .or_else(|| {
+                            path.as_deref()
+                                .and_then(|p| p.rsplit('/').next())
+                                .map(str::to_string)
+                        })
+                        .unwrap_or_else(|| format!("#{}", i + 1));
+                    StripInput {
+                        name,
+                        fd: fds.get(i).copied(),
+                        path,
+                    }
+                })
+                .collect();
+            if inputs.is_empty() {
+                state.metadata_strip.error = Some("missing_source".into());
                 return;
             }
-            let Some(src_path) = path.or_else(|| state.vault.input_path.clone()) else {
-                state.vault.error = Some("vault_missing_input".into());
-                return;
-            };
-            state.vault.is_processing = true;
+            let job = WorkerJob::MetadataStrip { inputs, output_dir };
+            state.loading_message = Some(t!("metadata_strip_working_message").to_string());
             state.loading_with_spinner = true;
-            state.loading_message = Some("Encrypting...".into());
-            let job = WorkerJob::Vault {
-                op: VaultOp::Encrypt,
-                path: src_path,
-                password: pwd,
-            };
             if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                state.vault.error = Some(e);
-                state.vault.is_processing = false;
-                state.loading_with_spinner = false;
-                state.loading_message = None;
+                state.metadata_strip.error = Some(e);
             }
             #[cfg(test)]
             {
                 apply_worker_results(state);
             }
         }
-        Action::VaultDecrypt {
+        _ => {}
+    }
+}
+
+fn handle_image_histogram_actions(state: &mut AppState, action: Action) {
+    if !matches!(state.current_screen(), Screen::ImageHistogram) {
+        state.push_screen(Screen::ImageHistogram);
+    }
+    match action {
+        Action::ImageHistogramScreen => state.image_histogram.reset(),
+        Action::ImageHistogramPick {
             path,
             fd,
             error,
-            password,
+            name,
         } => {
-            state.push_screen(Screen::Vault);
-            state.vault.status = None;
-            state.vault.error = None;
-            state.vault.is_processing = false;
-            if let Some(err) = error {
-                state.vault.error = Some(err);
-                return;
-            }
-            if fd.is_some() {
-                state.vault.error = Some("vault_requires_path".into());
-                return;
-            }
-            let pwd = password.unwrap_or_else(|| state.vault.password.clone());
-            state.vault.password = pwd.clone();
-            if pwd.trim().is_empty() {
-                state.vault.error = Some("vault_missing_password".into());
-                return;
-            }
-            let Some(src_path) = path.or_else(|| state.vault.input_path.clone()) else {
-                state.vault.error = Some("vault_missing_input".into());
-                return;
+            state.image_histogram.reset();
+            state.image_histogram.source_name = name.or_else(|| {
+                path.as_deref()
+                    .and_then(|p| p.rsplit('/').next())
+                    .map(str::to_string)
+            });
+            let mut fd_handle = FdHandle::new(fd);
+            let source = match (error, fd_handle.take(), path) {
+                (Some(err), _, _) => Err(err),
+                (None, Some(raw_fd), path) => {
+                    save_fd_to_temp(raw_fd as RawFd, path.as_deref()).map(|p| (p, true))
+                }
+                (None, None, Some(path)) => Ok((path, false)),
+                (None, None, None) => Err("missing_source".into()),
             };
-            state.vault.is_processing = true;
-            state.loading_with_spinner = true;
-            state.loading_message = Some("Decrypting...".into());
-            let job = WorkerJob::Vault {
-                op: VaultOp::Decrypt,
-                path: src_path,
-                password: pwd,
+            let (source_path, remove_source) = match source {
+                Ok(source) => source,
+                Err(e) => {
+                    state.image_histogram.error = Some(e);
+                    return;
+                }
             };
+            let job = WorkerJob::ImageHistogram {
+                source_path,
+                remove_source,
+            };
+            state.loading_message = Some(t!("image_histogram_computing_message").to_string());
+            state.loading_with_spinner = true;
             if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                state.vault.error = Some(e);
-                state.vault.is_processing = false;
-                state.loading_with_spinner = false;
-                state.loading_message = None;
+                state.image_histogram.error = Some(e);
             }
             #[cfg(test)]
             {
@@ -3167,1971 +6030,3441 @@ fn handle_vault_actions(state: &mut AppState, action: Action) {
     }
 }
 
-fn handle_logic_actions(state: &mut AppState, action: Action) -> Option<Value> {
-    match action {
-        Action::LogicScreen => {
-            state.push_screen(Screen::Logic);
-            state.logic.import_error = None;
-            None
-        }
-        Action::LogicAddTriple {
-            subject,
-            predicate,
-            object,
-        } => {
-            state.push_screen(Screen::Logic);
-            let s = subject.unwrap_or_default();
-            let p = predicate.unwrap_or_default();
-            let o = object.unwrap_or_default();
-            if s.is_empty() || p.is_empty() || o.is_empty() {
-                state.logic.import_error = Some("logic_add_missing_fields".into());
-            } else {
-                features::logic::add_triple(state, &s, &p, &o);
-                state.logic.import_error = None;
-            }
-            None
+/// Hands the active plugin its state, and `action` if any, on the plugin worker.
+fn enqueue_plugin_call(state: &mut AppState, action: Option<String>, bindings: String) {
+    let Some(plugin) = state.plugins.active.clone() else {
+        state.plugins.error = Some("plugin_not_open".into());
+        return;
+    };
+    state.plugins.error = None;
+    let job = WorkerJob::Plugin {
+        plugin_id: plugin.id,
+        path: plugin.path,
+        action,
+        bindings,
+        data: state.plugins.data.clone(),
+    };
+    match STATE
+        .get_or_init(GlobalState::new)
+        .plugin_worker()
+        .enqueue(job)
+    {
+        Ok(()) => {
+            state.plugins.running = true;
+            state.loading_with_spinner = true;
+            state.loading_message = Some(t!("loading_running_plugin").to_string());
         }
-        Action::LogicImport { path, fd, error } => {
-            state.push_screen(Screen::Logic);
-            state.logic.import_error = error.clone();
-            if let Some(err) = error {
-                state.logic.import_error = Some(err);
-            } else if let Some(raw_fd) = fd {
-                if let Err(e) = features::logic::import_csv_from_fd(state, raw_fd) {
-                    state.logic.import_error = Some(e);
-                } else {
-                    state.logic.import_error = None;
-                }
-            } else if let Some(p) = path {
-                if let Err(e) = features::logic::import_csv_from_path(state, &p) {
-                    state.logic.import_error = Some(e);
-                } else {
-                    state.logic.import_error = None;
+        Err(e) => state.plugins.error = Some(e),
+    }
+    #[cfg(test)]
+    {
+        apply_worker_results(state);
+    }
+}
+
+fn enqueue_ascii_art(state: &mut AppState) {
+    let Some(source_path) = state.ascii_art.source_path.clone() else {
+        return;
+    };
+    let job = WorkerJob::AsciiArt {
+        source_path,
+        columns: state.ascii_art.columns,
+    };
+    state.loading_message = Some(t!("ascii_art_converting_message").to_string());
+    state.loading_with_spinner = true;
+    if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+        state.ascii_art.error = Some(e);
+    }
+    #[cfg(test)]
+    {
+        apply_worker_results(state);
+    }
+}
+
+fn handle_ascii_art_actions(state: &mut AppState, action: Action) {
+    if matches!(action, Action::AsciiArtView) {
+        let Some(art) = &state.ascii_art.art else {
+            state.ascii_art.error = Some("ascii_art_nothing_to_view".into());
+            return;
+        };
+        let content = art.plain.clone();
+        let label = format!("{}.txt", state.ascii_art.stem());
+        state.push_screen(Screen::TextViewer);
+        state.text_view_path = Some(label);
+        state.text_view_total_bytes = Some(content.len() as u64);
+        state.text_view_loaded_bytes = content.len() as u64;
+        state.text_view_content = Some(content);
+        state.text_view_error = None;
+        state.text_view_language = None;
+        state.text_view_hex_preview = None;
+        state.text_view_find_query = None;
+        state.text_view_find_match = None;
+        state.text_view_window_offset = 0;
+        state.text_view_has_more = false;
+        state.text_view_has_previous = false;
+        state.text_view_cached_path = None;
+        state.text_view_wrap = false;
+        return;
+    }
+    if !matches!(state.current_screen(), Screen::AsciiArt) {
+        state.push_screen(Screen::AsciiArt);
+    }
+    match action {
+        Action::AsciiArtScreen => state.ascii_art.reset(),
+        Action::AsciiArtPick {
+            path,
+            fd,
+            error,
+            name,
+        } => {
+            let ascii = &mut state.ascii_art;
+            ascii.art = None;
+            ascii.saved_path = None;
+            ascii.source_path = None;
+            ascii.source_name = name.or_else(|| {
+                path.as_deref()
+                    .and_then(|p| p.rsplit('/').next())
+                    .map(str::to_string)
+            });
+            ascii.output_dir = Some(
+                features::storage::output_dir_for(path.as_deref())
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+            let source = match error {
+                Some(err) => Err(err),
+                None => picked_image_path(fd, path),
+            };
+            match source {
+                Ok(path) => {
+                    ascii.source_path = Some(path);
+                    ascii.error = None;
+                    enqueue_ascii_art(state);
                 }
-            } else {
-                state.logic.import_error = Some("logic_import_missing_path".into());
+                Err(e) => ascii.error = Some(e),
             }
-            None
         }
-        Action::LogicQuery {
-            subject,
-            predicate,
-            object,
-        } => {
-            state.push_screen(Screen::Logic);
-            let s = subject.unwrap_or_default();
-            let p = predicate.unwrap_or_default();
-            let o = object.unwrap_or_default();
-            features::logic::run_query(state, &s, &p, &o);
-            None
+        Action::AsciiArtSync { bindings } => {
+            if state.ascii_art.sync_bindings(&bindings) {
+                state.ascii_art.saved_path = None;
+                enqueue_ascii_art(state);
+            }
         }
-        _ => None,
+        Action::AsciiArtExport => {
+            let ascii = &mut state.ascii_art;
+            let Some(art) = &ascii.art else {
+                ascii.error = Some("ascii_art_nothing_to_view".into());
+                return;
+            };
+            let output_dir = ascii.output_dir.clone().unwrap_or_else(|| {
+                features::storage::preferred_temp_dir()
+                    .to_string_lossy()
+                    .into_owned()
+            });
+            match export_art(art, ascii.mode, &ascii.stem(), &output_dir) {
+                Ok(path) => {
+                    record_output(&path);
+                    let toast = t!("toast_result_saved", path = path);
+                    ascii.saved_path = Some(path);
+                    ascii.error = None;
+                    state.push_toast(toast);
+                }
+                Err(e) => ascii.error = Some(e),
+            }
+        }
+        _ => {}
     }
 }
 
-fn handle_jwt_actions(state: &mut AppState, action: Action) {
-    fn looks_like_jwt(value: &str) -> bool {
-        let trimmed = value.trim();
-        if trimmed.len() < 10 {
-            return false;
+fn handle_gif_frames_actions(state: &mut AppState, action: Action) {
+    if !matches!(state.current_screen(), Screen::GifFrames) {
+        state.push_screen(Screen::GifFrames);
+    }
+    let gif = &mut state.gif_frames;
+    let (job, message) = match action {
+        Action::GifFramesScreen => {
+            gif.reset();
+            return;
         }
-        if !trimmed.starts_with("ey") {
-            return false;
+        Action::GifFramesPick {
+            path,
+            fd,
+            error,
+            name,
+        } => {
+            gif.exploded = None;
+            gif.result_path = None;
+            gif.source_name = name.or_else(|| {
+                path.as_deref()
+                    .and_then(|p| p.rsplit('/').next())
+                    .map(str::to_string)
+            });
+            let output_dir = features::storage::output_dir_for(path.as_deref())
+                .to_string_lossy()
+                .into_owned();
+            gif.output_dir = Some(output_dir.clone());
+            let source = match error {
+                Some(err) => Err(err),
+                None => picked_image_path(fd, path),
+            };
+            match source {
+                Ok(source_path) => {
+                    gif.error = None;
+                    let job = WorkerJob::GifExplode {
+                        source_path,
+                        stem: gif.stem(),
+                        output_dir,
+                    };
+                    (job, t!("gif_frames_exploding_message"))
+                }
+                Err(e) => {
+                    gif.error = Some(e);
+                    return;
+                }
+            }
         }
-        if trimmed.contains(char::is_whitespace) {
-            return false;
+        Action::GifFramesAssemble { bindings } => {
+            gif.sync_bindings(&bindings);
+            gif.result_path = None;
+            let Some(exploded) = &gif.exploded else {
+                gif.error = Some("gif_frames_no_frames".into());
+                return;
+            };
+            let frames = match parse_frame_selection(&gif.selection, exploded.frames.len()) {
+                Ok(indices) => indices
+                    .into_iter()
+                    .map(|i| exploded.frames[i].path.clone())
+                    .collect(),
+                Err(e) => {
+                    gif.error = Some(e);
+                    return;
+                }
+            };
+            gif.error = None;
+            let output_dir = gif.output_dir.clone().unwrap_or_else(|| {
+                features::storage::preferred_temp_dir()
+                    .to_string_lossy()
+                    .into_owned()
+            });
+            let job = WorkerJob::GifAssemble {
+                frames,
+                delay_ms: gif.delay_ms,
+                stem: gif.stem(),
+                output_dir,
+            };
+            (job, t!("gif_frames_assembling_message"))
         }
-        trimmed.split('.').count() == 3
+        _ => return,
+    };
+    state.loading_message = Some(message.to_string());
+    state.loading_with_spinner = true;
+    if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+        state.gif_frames.error = Some(e);
     }
+    #[cfg(test)]
+    {
+        apply_worker_results(state);
+    }
+}
 
+fn handle_exif_edit_actions(state: &mut AppState, action: Action) {
+    if !matches!(state.current_screen(), Screen::ExifEditor) {
+        state.push_screen(Screen::ExifEditor);
+    }
+    let editor = &mut state.exif_editor;
     match action {
-        Action::JwtScreen { bindings } => {
-            state.push_screen(Screen::Jwt);
-            state.replace_current(Screen::Jwt);
-            state.jwt.input_token.clear();
-            state.jwt.decoded_header = None;
-            state.jwt.decoded_payload = None;
-            state.jwt.error = None;
-
-            if let Some(clip) = bindings.get("clipboard") {
-                if looks_like_jwt(clip) {
-                    state.jwt.input_token = clip.trim().to_string();
+        Action::ExifEditScreen => editor.reset(),
+        Action::ExifEditPick {
+            path,
+            fd,
+            error,
+            name,
+        } => {
+            editor.reset();
+            editor.source_name = name.or_else(|| {
+                path.as_deref()
+                    .and_then(|p| p.rsplit('/').next())
+                    .map(str::to_string)
+            });
+            editor.output_dir = Some(
+                features::storage::output_dir_for(path.as_deref())
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+            let loaded = match error {
+                Some(err) => Err(err),
+                None => picked_image_path(fd, path).and_then(|p| editor.load_source(p)),
+            };
+            if let Err(e) = loaded {
+                editor.error = Some(e);
+            }
+        }
+        Action::ExifEditSync { bindings } => editor.edits.sync_bindings(&bindings),
+        Action::ExifEditSave { bindings } => {
+            editor.edits.sync_bindings(&bindings);
+            editor.result_path = None;
+            let Some(source_path) = editor.source_path.clone() else {
+                editor.error = Some("missing_source".into());
+                return;
+            };
+            if !editor.edits.taken_at.is_empty() {
+                if let Err(e) = features::exif_editor::exif_datetime(&editor.edits.taken_at) {
+                    editor.error = Some(e);
+                    return;
                 }
             }
+            editor.error = None;
+            let job = WorkerJob::ExifEdit {
+                source_path,
+                name: editor.source_name.clone().unwrap_or_else(|| "photo.jpg".into()),
+                edits: editor.edits.clone(),
+                output_dir: editor.output_dir.clone().unwrap_or_else(|| {
+                    features::storage::preferred_temp_dir()
+                        .to_string_lossy()
+                        .into_owned()
+                }),
+            };
+            state.loading_message = Some(t!("exif_edit_saving_message").to_string());
+            state.loading_with_spinner = true;
+            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                state.exif_editor.error = Some(e);
+            }
+            #[cfg(test)]
+            {
+                apply_worker_results(state);
+            }
         }
-        Action::JwtDecode { token } => {
-            state.replace_current(Screen::Jwt);
-            let token = token.unwrap_or_else(|| state.jwt.input_token.clone());
-            state.jwt.input_token = token.clone();
-            if token.trim().is_empty() {
-                state.jwt.error = Some("jwt_empty".into());
-                state.jwt.decoded_header = None;
-                state.jwt.decoded_payload = None;
+        _ => {}
+    }
+}
+
+fn handle_collage_actions(state: &mut AppState, action: Action) {
+    if !matches!(state.current_screen(), Screen::Collage) {
+        state.push_screen(Screen::Collage);
+    }
+    let collage = &mut state.collage;
+    match action {
+        Action::CollageScreen => collage.reset(),
+        Action::CollagePickImages {
+            paths,
+            fds,
+            names,
+            error,
+        } => {
+            collage.sources.clear();
+            collage.result_path = None;
+            collage.error = error;
+            if collage.error.is_some() {
                 return;
             }
-            match features::jwt::decode_jwt(&token) {
-                Ok((h, p)) => {
-                    state.jwt.decoded_header = Some(h);
-                    state.jwt.decoded_payload = Some(p);
-                    state.jwt.error = None;
-                }
+            collage.output_dir = Some(
+                features::storage::output_dir_for(paths.first().map(String::as_str))
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+            for (name, path) in picked_image_paths(paths, fds, names) {
+                match path {
+                    Ok(path) => collage.sources.push(CollageSource { name, path }),
+                    Err(e) => collage.error = Some(format!("{name}: {e}")),
+                }
+            }
+        }
+        Action::CollageSync { bindings } => collage.sync_bindings(&bindings),
+        Action::CollageBuild { bindings } => {
+            collage.sync_bindings(&bindings);
+            collage.result_path = None;
+            let layout = collage
+                .layout()
+                .and_then(|layout| layout.grid(collage.sources.len()).map(|_| layout));
+            let layout = match layout {
+                Ok(layout) => layout,
                 Err(e) => {
-                    state.jwt.error = Some(e);
-                    state.jwt.decoded_header = None;
-                    state.jwt.decoded_payload = None;
+                    collage.error = Some(e);
+                    return;
                 }
+            };
+            collage.error = None;
+            let job = WorkerJob::Collage {
+                sources: collage.sources.clone(),
+                layout,
+                output_dir: collage.output_dir.clone().unwrap_or_else(|| {
+                    features::storage::preferred_temp_dir()
+                        .to_string_lossy()
+                        .into_owned()
+                }),
+            };
+            state.loading_message = Some(t!("collage_building_message").to_string());
+            state.loading_with_spinner = true;
+            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                state.collage.error = Some(e);
             }
-        }
-        Action::JwtClear => {
-            state.replace_current(Screen::Jwt);
-            state.jwt.input_token.clear();
-            state.jwt.decoded_header = None;
-            state.jwt.decoded_payload = None;
-            state.jwt.error = None;
-        }
-        Action::JwtPaste { token } => {
-            state.replace_current(Screen::Jwt);
-            if let Some(t) = token {
-                state.jwt.input_token = t.clone();
-                if let Ok((h, p)) = features::jwt::decode_jwt(&t) {
-                    state.jwt.decoded_header = Some(h);
-                    state.jwt.decoded_payload = Some(p);
-                    state.jwt.error = None;
-                }
+            #[cfg(test)]
+            {
+                apply_worker_results(state);
             }
         }
         _ => {}
     }
 }
 
-fn handle_multi_hash_actions(state: &mut AppState, action: Action) -> Option<Value> {
-    match action {
-        Action::MultiHashScreen => {
-            state.push_screen(Screen::MultiHash);
-            state.multi_hash_results = None;
-            state.multi_hash_error = None;
-            None
-        }
-        _ => None,
+/// Pairs each picked file with a display name and a path the worker can open.
+fn picked_image_paths(
+    paths: Vec<String>,
+    fds: Vec<i32>,
+    names: Vec<String>,
+) -> Vec<(String, Result<String, String>)> {
+    (0..paths.len().max(fds.len()))
+        .map(|i| {
+            let path = paths.get(i).cloned();
+            let name = names
+                .get(i)
+                .cloned()
+                .or_else(|| {
+                    path.as_deref()
+                        .and_then(|p| p.rsplit('/').next())
+                        .map(str::to_string)
+                })
+                .unwrap_or_else(|| format!("#{}", i + 1));
+            (name, picked_image_path(fds.get(i).copied(), path))
+        })
+        .collect()
+}
+
+/// Copies a picked fd to a private temp file the worker can reopen by path.
+fn picked_image_path(fd: Option<i32>, path: Option<String>) -> Result<String, String> {
+    let mut fd_handle = FdHandle::new(fd);
+    match (fd_handle.take(), path) {
+        (Some(raw_fd), path) => save_fd_to_temp(raw_fd as RawFd, path.as_deref()),
+        (None, Some(path)) => Ok(path),
+        (None, None) => Err("missing_source".into()),
     }
 }
 
-fn handle_hash_actions(state: &mut AppState, action: Action) -> Option<Value> {
+fn handle_watermark_actions(state: &mut AppState, action: Action) {
+    if !matches!(state.current_screen(), Screen::Watermark) {
+        state.push_screen(Screen::Watermark);
+    }
+    let wm = &mut state.watermark;
     match action {
-        Action::HashVerifyScreen => {
-            state.push_screen(Screen::HashVerify);
-            state.hash_reference = None;
-            state.hash_match = None;
-            state.last_hash = None;
-            state.last_hash_algo = Some("SHA-256".into());
-            None
+        Action::WatermarkScreen => wm.reset(),
+        Action::WatermarkPickImages {
+            paths,
+            fds,
+            names,
+            error,
+        } => {
+            wm.sources.clear();
+            wm.outcomes.clear();
+            wm.error = error;
+            if wm.error.is_some() {
+                return;
+            }
+            wm.output_dir = Some(
+                features::storage::output_dir_for(paths.first().map(String::as_str))
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+            for (name, path) in picked_image_paths(paths, fds, names) {
+                match path {
+                    Ok(path) => wm.sources.push(WatermarkSource { name, path }),
+                    Err(e) => wm.error = Some(format!("{name}: {e}")),
+                }
+            }
         }
-        Action::HashVerify {
+        Action::WatermarkPickLogo {
             path,
             fd,
-            reference,
+            error,
+            name,
         } => {
-            let mut fd_handle = FdHandle::new(fd);
-            state.push_screen(Screen::HashVerify);
-            if let Some(err) = reference
-                .as_ref()
-                .filter(|s| s.trim().is_empty())
-                .map(|_| "reference_empty".to_string())
-            {
-                state.last_error = Some(err);
-                state.hash_match = None;
-            } else {
-                let algo = HashAlgo::Sha256;
-                if let Some(err) = reference
-                    .clone()
-                    .is_none()
-                    .then(|| "missing_reference".to_string())
-                {
-                    state.last_error = Some(err);
-                } else {
-                    let source = hash_job_source(fd_handle.take(), path.as_deref());
-                    if let Some(src) = source {
-                        let reference = reference.unwrap();
-                        let job = WorkerJob::HashVerify(HashVerifyJob {
-                            source: src,
-                            reference: reference.clone(),
-                            algo,
-                        });
-                        state.hash_reference = Some(reference);
-                        state.hash_match = None;
-                        state.last_hash = None;
-                        state.last_error = None;
-                        state.loading_with_spinner = true;
-                        state.loading_message = Some(hash_loading_message(algo).into());
-                        state.replace_current(Screen::Loading);
-                        if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                            state.last_error = Some(e);
-                        }
-                        #[cfg(test)]
-                        {
-                            apply_worker_results(state);
-                        }
-                    } else {
-                        state.last_error = Some("missing_path".into());
-                        state.hash_match = None;
-                        state.last_hash = None;
-                    }
+            let name = name
+                .or_else(|| path.as_deref().and_then(|p| p.rsplit('/').next()).map(str::to_string))
+                .unwrap_or_else(|| "logo".to_string());
+            let source = match error {
+                Some(err) => Err(err),
+                None => picked_image_path(fd, path),
+            };
+            match source {
+                Ok(path) => {
+                    wm.logo = Some(WatermarkSource { name, path });
+                    wm.error = None;
                 }
+                Err(e) => wm.error = Some(e),
             }
-            None
         }
-        Action::HashVerifyPaste { reference } => {
-            state.push_screen(Screen::HashVerify);
-            if let Some(text) = reference {
-                state.hash_reference = Some(text);
-                state.hash_match = None;
-                state.last_hash = None;
-                state.last_error = None;
-            } else {
-                state.last_error = Some("clipboard_empty".into());
+        Action::WatermarkSync { bindings } => wm.sync_bindings(&bindings),
+        Action::WatermarkApply { bindings } => {
+            wm.sync_bindings(&bindings);
+            wm.outcomes.clear();
+            wm.error = None;
+            if wm.sources.is_empty() {
+                wm.error = Some("watermark_no_images".into());
+                return;
             }
-            None
-        }
-        Action::HashPasteReference { reference } => {
-            state.push_screen(Screen::Home);
-            if let Some(text) = reference {
-                state.hash_reference = Some(text.clone());
-                state.hash_match = None;
-                state.last_error = None;
-                if let Some(hash) = state.last_hash.clone() {
-                    let cleaned_ref = text.trim().to_ascii_lowercase();
-                    let cleaned_hash = hash.trim().to_ascii_lowercase();
-                    state.hash_match = Some(cleaned_ref == cleaned_hash);
+            // Text marks arrive as a PNG the Android side rendered with the system fonts.
+            let mark = match wm.mode {
+                WatermarkMode::Text if wm.text.trim().is_empty() => {
+                    Err("watermark_missing_text".to_string())
+                }
+                WatermarkMode::Text => bindings
+                    .get("watermark_text_png")
+                    .map(|p| (p.clone(), true))
+                    .ok_or_else(|| "watermark_text_render_failed".to_string()),
+                WatermarkMode::Logo => wm
+                    .logo
+                    .as_ref()
+                    .map(|logo| (logo.path.clone(), false))
+                    .ok_or_else(|| "watermark_missing_logo".to_string()),
+            };
+            let (mark_path, remove_mark) = match mark {
+                Ok(mark) => mark,
+                Err(e) => {
+                    wm.error = Some(e);
+                    return;
                 }
-            } else {
-                state.last_error = Some("clipboard_empty".into());
+            };
+            let job = WorkerJob::Watermark {
+                sources: wm.sources.clone(),
+                mark_path,
+                remove_mark,
+                options: wm.options(),
+                output_dir: wm.output_dir.clone().unwrap_or_else(|| {
+                    features::storage::preferred_temp_dir()
+                        .to_string_lossy()
+                        .into_owned()
+                }),
+            };
+            state.loading_message = Some(t!("watermark_applying_message").to_string());
+            state.loading_with_spinner = true;
+            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                state.watermark.error = Some(e);
             }
-            None
-        }
-        Action::HashQrFromLast => {
-            if let Some(hash) = state.last_hash.clone() {
-                state.push_screen(Screen::Qr);
-                if let Err(e) = handle_qr_action(state, &hash) {
-                    state.last_error = Some(e);
-                }
-            } else {
-                state.last_error = Some("no_hash_available".into());
+            #[cfg(test)]
+            {
+                apply_worker_results(state);
             }
-            None
         }
-        _ => None,
+        _ => {}
     }
 }
 
-fn handle_hash_job(
-    mut state: MutexGuard<'_, AppState>,
-    algo: HashAlgo,
-    path: Option<String>,
-    fd: Option<i32>,
-    error: Option<String>,
-    loading_only: bool,
-) -> Result<Value, String> {
-    let mut fd_handle = FdHandle::new(fd);
-    if loading_only {
-        state.loading_with_spinner = false;
-        state.replace_current(Screen::Loading);
-        state.loading_message = Some(hash_loading_message(algo).into());
-        return Ok(render_root(&mut state));
-    }
-    state.reset_navigation();
-    state.last_hash_algo = Some(hash_label(algo).into());
-    if let Some(err) = error {
-        state.last_error = Some(err);
-        state.last_hash = None;
-        state.loading_message = None;
-        state.loading_with_spinner = true;
-        return Ok(render_root(&mut state));
-    }
-
-    let source = hash_job_source(fd_handle.take(), path.as_deref());
-    if source.is_none() {
-        state.last_error = Some("missing_path".into());
-        state.last_hash = None;
-        state.loading_message = None;
-        state.loading_with_spinner = true;
-        return Ok(render_root(&mut state));
-    }
-
-    drop(fd_handle);
-    let job = WorkerJob::Hash {
-        source: source.unwrap(),
-        algo,
-    };
-    if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-        state.last_error = Some(e);
-        state.last_hash = None;
+fn handle_cleanup_actions(state: &mut AppState, action: Action) {
+    if matches!(action, Action::CleanupScreen) {
+        state.push_screen(Screen::StorageCleanup);
+        state.cleanup.reset();
+        state.cleanup.policy = features::cleanup::load_policy();
+        state.cleanup.refresh();
+        return;
     }
-    #[cfg(test)]
-    {
-        apply_worker_results(&mut state);
+    state.replace_current(Screen::StorageCleanup);
+    state.cleanup.status = None;
+    state.cleanup.error = None;
+    match action {
+        Action::CleanupRefresh => state.cleanup.refresh(),
+        Action::CleanupToggle { index, selected } => {
+            if let Some(path) = state.cleanup.entries.get(index).map(|e| e.path.clone()) {
+                let selected = selected.unwrap_or(!state.cleanup.is_selected(&path));
+                state.cleanup.set_selected(&path, selected);
+            }
+        }
+        Action::CleanupSelectAll => {
+            state.cleanup.selected = state.cleanup.entries.iter().map(|e| e.path.clone()).collect();
+        }
+        Action::CleanupSelectStale => state.cleanup.select_stale(now_secs()),
+        Action::CleanupSelectNone => state.cleanup.selected.clear(),
+        Action::CleanupDeleteSelected => {
+            let selected = std::mem::take(&mut state.cleanup.selected);
+            let summary = delete_generated(&selected);
+            state.cleanup.status = Some(summary_text(&summary));
+            if !summary.failed.is_empty() {
+                state.cleanup.error = Some(format!(
+                    "{}{}",
+                    t!("cleanup_failed_prefix"),
+                    summary.failed.join(", ")
+                ));
+            }
+            state.cleanup.refresh();
+        }
+        Action::CleanupSavePolicy {
+            auto_clean,
+            max_age_days,
+        } => {
+            let days = match max_age_days.as_deref() {
+                Some(raw) => parse_max_age_days(raw),
+                None => Ok(state.cleanup.policy.temp_max_age_days),
+            };
+            match days {
+                Ok(days) => {
+                    state.cleanup.policy.auto_clean = auto_clean;
+                    state.cleanup.policy.temp_max_age_days = days;
+                    match save_policy(&state.cleanup.policy) {
+                        Ok(()) => state.cleanup.status = Some(t!("cleanup_policy_saved").to_string()),
+                        Err(e) => state.cleanup.error = Some(e),
+                    }
+                }
+                Err(e) => state.cleanup.error = Some(e),
+            }
+        }
+        Action::CleanupCleanStale => {
+            let summary = clean_stale_temp(state.cleanup.policy.temp_max_age_days, now_secs());
+            state.cleanup.status = Some(summary_text(&summary));
+            state.cleanup.refresh();
+        }
+        _ => {}
     }
-    state.loading_message = None;
-    state.loading_with_spinner = true;
-    Ok(render_root(&mut state))
 }
 
-fn handle_multi_hash_job(
-    mut state: MutexGuard<'_, AppState>,
-    path: Option<String>,
-    fd: Option<i32>,
-    loading_only: bool,
-) -> Result<Value, String> {
-    let mut fd_handle = FdHandle::new(fd);
-    if loading_only {
-        state.loading_with_spinner = false;
-        state.replace_current(Screen::Loading);
-        state.loading_message = Some("Computing all hashes...".into());
-        state.multi_hash_results = None;
-        state.multi_hash_error = None;
-        return Ok(render_root(&mut state));
-    }
-    let source = hash_job_source(fd_handle.take(), path.as_deref());
-    state.reset_navigation();
-    state.push_screen(Screen::MultiHash);
-    state.multi_hash_results = None;
-    state.multi_hash_error = None;
-
-    match source {
-        Some(src) => {
-            let display = path.clone().unwrap_or_else(|| "Selected file".to_string());
-            drop(fd_handle);
-            let job = WorkerJob::MultiHash {
-                source: src,
-                display_path: display,
-            };
-            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                state.multi_hash_error = Some(e);
-                state.multi_hash_results = None;
+fn handle_kotlin_image_batch_actions(state: &mut AppState, action: Action) {
+    match action {
+        Action::KotlinImageBatchPick { paths, fds } => {
+            state.replace_current(Screen::KotlinImage);
+            if let Some(first) = paths.first() {
+                state.image.local_output_dir = Some(
+                    features::storage::output_dir_for(Some(first))
+                        .to_string_lossy()
+                        .into_owned(),
+                );
             }
-            #[cfg(test)]
-            {
-                apply_worker_results(&mut state);
+            let handles: Vec<FdHandle> = fds.into_iter().map(|fd| FdHandle::new(Some(fd))).collect();
+            let mut fds = handles.into_iter();
+            for path in paths {
+                // Keep a readable copy so the Rust resizer can open content URIs.
+                let copied = fds
+                    .next()
+                    .and_then(|mut handle| handle.take())
+                    .and_then(|fd| save_fd_to_temp(fd as RawFd, Some(&path)).ok());
+                state.image.batch_queue.push(copied.unwrap_or(path));
             }
-            state.loading_message = None;
-            state.loading_with_spinner = true;
-            return Ok(render_root(&mut state));
         }
-        None => {
-            state.multi_hash_error = Some("missing_path".into());
-            state.multi_hash_results = None;
-            state.loading_message = None;
-            state.loading_with_spinner = true;
-            return Ok(render_root(&mut state));
+        Action::KotlinImageBatchRemove { path } => {
+            state.replace_current(Screen::KotlinImage);
+            state.image.batch_queue.retain(|p| p != &path);
         }
+        _ => {}
     }
 }
 
-fn handle_scheduler_actions(
-    state: &mut AppState,
-    action: &Action,
-) -> Option<Value> {
+fn handle_vault_actions(state: &mut AppState, action: Action) {
     match action {
-        Action::SchedulerScreen => {
-            state.push_screen(Screen::Scheduler);
-            Some(render_scheduler_screen(state))
+        Action::VaultScreen => {
+            state.push_screen(Screen::Vault);
+            state.vault.reset();
         }
-        Action::SchedulerAdd {
-            name,
-            action_id,
-            cron,
+        Action::VaultPick { path, fd, error } => {
+            state.push_screen(Screen::Vault);
+            state.vault.status = None;
+            state.vault.error = None;
+            state.vault.is_processing = false;
+            if let Some(err) = error {
+                state.vault.error = Some(err);
+            } else if fd.is_some() {
+                state.vault.error = Some("vault_requires_path".into());
+            } else if let Some(p) = path {
+                state.vault.input_path = Some(p);
+            } else {
+                state.vault.error = Some("vault_missing_input".into());
+            }
+        }
+        Action::VaultEncrypt {
+            path,
+            fd,
+            error,
+            password,
         } => {
-            if let Some(n) = name {
-                state.scheduler.form_name = n.clone();
+            state.push_screen(Screen::Vault);
+            state.vault.status = None;
+            state.vault.error = None;
+            state.vault.is_processing = false;
+            if let Some(err) = error {
+                state.vault.error = Some(err);
+                return;
             }
-            if let Some(a) = action_id {
-                state.scheduler.form_action = a.clone();
+            if fd.is_some() {
+                state.vault.error = Some("vault_requires_path".into());
+                return;
             }
-            if let Some(c) = cron {
-                state.scheduler.form_cron = c.clone();
+            let pwd = password.unwrap_or_else(|| state.vault.password.clone());
+            state.vault.password = pwd.clone();
+            if pwd.trim().is_empty() {
+                state.vault.error = Some("vault_missing_password".into());
+                return;
             }
-
-            if state.scheduler.form_name.trim().is_empty()
-                || state.scheduler.form_action.trim().is_empty()
+            let Some(src_path) = path.or_else(|| state.vault.input_path.clone()) else {
+                state.vault.error = Some("vault_missing_input".into());
+                return;
+            };
+            state.vault.is_processing = true;
+            state.loading_with_spinner = true;
+            state.loading_message = Some(t!("loading_encrypting").to_string());
+            let job = WorkerJob::Vault {
+                op: VaultOp::Encrypt,
+                path: src_path,
+                password: pwd,
+            };
+            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                state.vault.error = Some(e);
+                state.vault.is_processing = false;
+                state.loading_with_spinner = false;
+                state.loading_message = None;
+            }
+            #[cfg(test)]
             {
-                state.scheduler.last_error = Some("name_and_action_required".into());
-                state.replace_current(Screen::Scheduler);
-                return Some(render_scheduler_screen(state));
+                apply_worker_results(state);
             }
-
-            match cron::Schedule::from_str(&state.scheduler.form_cron) {
-                Ok(_) => {
-                    let task = crate::state::ScheduledTask {
-                        id: state.scheduler.next_id,
-                        name: state.scheduler.form_name.trim().to_string(),
-                        action: state.scheduler.form_action.trim().to_string(),
-                        cron: state.scheduler.form_cron.trim().to_string(),
-                        enabled: true,
-                        last_run_epoch: None,
-                        last_status: None,
-                    };
-                    state.scheduler.next_id = state.scheduler.next_id.saturating_add(1);
-                    state.scheduler.tasks.push(task);
-                    state.scheduler.last_error = None;
-                    scheduler_runtime().sync_tasks(&state.scheduler.tasks);
-                    state.scheduler.form_name.clear();
-                    state.scheduler.form_action.clear();
-                }
-                Err(e) => {
-                    state.scheduler.last_error = Some(format!("invalid_cron:{e}"));
-                }
+        }
+        Action::VaultDecrypt {
+            path,
+            fd,
+            error,
+            password,
+        } => {
+            state.push_screen(Screen::Vault);
+            state.vault.status = None;
+            state.vault.error = None;
+            state.vault.is_processing = false;
+            if let Some(err) = error {
+                state.vault.error = Some(err);
+                return;
+            }
+            if fd.is_some() {
+                state.vault.error = Some("vault_requires_path".into());
+                return;
             }
+            let pwd = password.unwrap_or_else(|| state.vault.password.clone());
+            state.vault.password = pwd.clone();
+            if pwd.trim().is_empty() {
+                state.vault.error = Some("vault_missing_password".into());
+                return;
+            }
+            let Some(src_path) = path.or_else(|| state.vault.input_path.clone()) else {
+                state.vault.error = Some("vault_missing_input".into());
+                return;
+            };
+            state.vault.is_processing = true;
+            state.loading_with_spinner = true;
+            state.loading_message = Some(t!("loading_decrypting").to_string());
+            let job = WorkerJob::Vault {
+                op: VaultOp::Decrypt,
+                path: src_path,
+                password: pwd,
+            };
+            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                state.vault.error = Some(e);
+                state.vault.is_processing = false;
+                state.loading_with_spinner = false;
+                state.loading_message = None;
+            }
+            #[cfg(test)]
+            {
+                apply_worker_results(state);
+            }
+        }
+        _ => {}
+    }
+}
 
-            state.replace_current(Screen::Scheduler);
-            Some(render_scheduler_screen(state))
+fn handle_logic_actions(state: &mut AppState, action: Action) -> Option<Value> {
+    match action {
+        Action::LogicScreen => {
+            state.push_screen(Screen::Logic);
+            state.logic.import_error = None;
+            None
         }
-        Action::SchedulerToggle { id } => {
-            if let Some(task) = state.scheduler.tasks.iter_mut().find(|t| t.id == *id) {
-                task.enabled = !task.enabled;
+        Action::LogicAddTriple {
+            subject,
+            predicate,
+            object,
+        } => {
+            state.push_screen(Screen::Logic);
+            let s = subject.unwrap_or_default();
+            let p = predicate.unwrap_or_default();
+            let o = object.unwrap_or_default();
+            if s.is_empty() || p.is_empty() || o.is_empty() {
+                state.logic.import_error = Some("logic_add_missing_fields".into());
+            } else {
+                features::logic::add_triple(state, &s, &p, &o);
+                state.logic.import_error = None;
             }
-            scheduler_runtime().sync_tasks(&state.scheduler.tasks);
-            state.replace_current(Screen::Scheduler);
-            Some(render_scheduler_screen(state))
+            None
         }
-        Action::SchedulerDelete { id } => {
-            state.scheduler.tasks.retain(|t| t.id != *id);
-            scheduler_runtime().sync_tasks(&state.scheduler.tasks);
-            state.replace_current(Screen::Scheduler);
-            Some(render_scheduler_screen(state))
+        Action::LogicImport { path, fd, error } => {
+            state.push_screen(Screen::Logic);
+            state.logic.import_error = error.clone();
+            if let Some(err) = error {
+                state.logic.import_error = Some(err);
+            } else if let Some(raw_fd) = fd {
+                if let Err(e) = features::logic::import_csv_from_fd(state, raw_fd) {
+                    state.logic.import_error = Some(e);
+                } else {
+                    state.logic.import_error = None;
+                }
+            } else if let Some(p) = path {
+                if let Err(e) = features::logic::import_csv_from_path(state, &p) {
+                    state.logic.import_error = Some(e);
+                } else {
+                    state.logic.import_error = None;
+                }
+            } else {
+                state.logic.import_error = Some("logic_import_missing_path".into());
+            }
+            None
         }
-        Action::SchedulerRunNow { id } => {
-            scheduler_runtime().trigger_now(*id);
-            state.replace_current(Screen::Scheduler);
-            Some(render_scheduler_screen(state))
+        Action::LogicQuery {
+            subject,
+            predicate,
+            object,
+        } => {
+            state.push_screen(Screen::Logic);
+            let s = subject.unwrap_or_default();
+            let p = predicate.unwrap_or_default();
+            let o = object.unwrap_or_default();
+            features::logic::run_query(state, &s, &p, &o);
+            None
         }
         _ => None,
     }
 }
 
-fn hash_job_source(fd: Option<i32>, path: Option<&str>) -> Option<HashSourceInput> {
-    if let Some(fd) = fd {
-        Some(HashSourceInput::Fd(fd))
-    } else {
-        path.map(|p| HashSourceInput::Path(p.to_string()))
+fn handle_jwt_actions(state: &mut AppState, action: Action) {
+    fn looks_like_jwt(value: &str) -> bool {
+        let trimmed = value.trim();
+        if trimmed.len() < 10 {
+            return false;
+        }
+        if !trimmed.starts_with("ey") {
+            return false;
+        }
+        if trimmed.contains(char::is_whitespace) {
+            return false;
+        }
+        trimmed.split('.').count() == 3
     }
-}
 
-fn handle_pdf_actions(state: &mut AppState, action: Action) {
     match action {
-        Action::PdfToolsScreen => {
-            state.push_screen(Screen::PdfTools);
-            state.pdf.last_error = None;
-            state.pdf.last_output = None;
+        Action::JwtScreen { bindings } => {
+            state.push_screen(Screen::Jwt);
+            state.replace_current(Screen::Jwt);
+            state.jwt.input_token.clear();
+            state.jwt.decoded_header = None;
+            state.jwt.decoded_payload = None;
+            state.jwt.error = None;
+
+            if let Some(clip) = bindings.get("clipboard") {
+                if looks_like_jwt(clip) {
+                    state.jwt.input_token = clip.trim().to_string();
+                }
+            }
         }
-        Action::PdfSelect { fd, uri, error } => {
-            state.push_screen(Screen::PdfTools);
-            state.pdf.last_error = error.clone();
-            state.pdf.preview_page = None;
-            state.pdf.page_count = None;
-            state.pdf.selected_pages.clear();
-            state.pdf.last_output = None;
-            state.pdf.current_title = None;
-            state.pdf.signature_target_page = None;
-            state.pdf.signature_x_pct = None;
-            state.pdf.signature_y_pct = None;
-            if let Some(err) = error {
-                state.pdf.last_error = Some(err);
-            } else if let Some(raw_fd) = fd {
-                state.loading_message = Some("Loading PDF...".into());
-                state.loading_with_spinner = true;
-                state.replace_current(Screen::Loading);
-                let job = WorkerJob::PdfSelect {
-                    fd: raw_fd,
-                    uri: uri.clone(),
-                };
-                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                    state.pdf.last_error = Some(e);
+        Action::JwtDecode { token } => {
+            state.replace_current(Screen::Jwt);
+            let token = token.unwrap_or_else(|| state.jwt.input_token.clone());
+            state.jwt.input_token = token.clone();
+            if token.trim().is_empty() {
+                state.jwt.error = Some("jwt_empty".into());
+                state.jwt.decoded_header = None;
+                state.jwt.decoded_payload = None;
+                return;
+            }
+            match features::jwt::decode_jwt(&token) {
+                Ok((h, p)) => {
+                    state.jwt.decoded_header = Some(h);
+                    state.jwt.decoded_payload = Some(p);
+                    state.jwt.error = None;
                 }
-                #[cfg(test)]
-                {
-                    apply_worker_results(state);
+                Err(e) => {
+                    state.jwt.error = Some(e);
+                    state.jwt.decoded_header = None;
+                    state.jwt.decoded_payload = None;
                 }
-            } else {
-                state.pdf.last_error = Some("missing_fd".into());
             }
         }
-        Action::PdfExtract { fd, uri, selection } => {
-            state.push_screen(Screen::PdfTools);
-            state.pdf.last_error = None;
-            state.pdf.last_output = None;
-            let mut fd_handle = FdHandle::new(fd);
-            if selection.is_empty() {
-                state.pdf.last_error = Some("no_pages_selected".into());
-            } else if let Some(raw_fd) = fd_handle.take() {
-                state.loading_with_spinner = true;
-                state.loading_message = Some("Processing PDF...".into());
-                let job = WorkerJob::PdfOperation(PdfWorkerArgs {
-                    op: PdfOperation::Extract,
-                    primary_fd: raw_fd,
-                    secondary_fd: None,
-                    primary_uri: uri.clone(),
-                    secondary_uri: None,
-                    selected_pages: selection.clone(),
-                });
-                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                    state.pdf.last_error = Some(e);
-                }
-                #[cfg(test)]
-                {
-                    apply_worker_results(state);
+        Action::JwtClear => {
+            state.replace_current(Screen::Jwt);
+            state.jwt.input_token.clear();
+            state.jwt.decoded_header = None;
+            state.jwt.decoded_payload = None;
+            state.jwt.error = None;
+        }
+        Action::JwtPaste { token } => {
+            state.replace_current(Screen::Jwt);
+            if let Some(t) = token {
+                state.jwt.input_token = t.clone();
+                if let Ok((h, p)) = features::jwt::decode_jwt(&t) {
+                    state.jwt.decoded_header = Some(h);
+                    state.jwt.decoded_payload = Some(p);
+                    state.jwt.error = None;
                 }
-            } else {
-                state.pdf.last_error = Some("missing_fd".into());
             }
         }
-        Action::PdfDelete { fd, uri, selection } => {
-            state.push_screen(Screen::PdfTools);
-            state.pdf.last_error = None;
-            state.pdf.last_output = None;
-            let mut fd_handle = FdHandle::new(fd);
-            if selection.is_empty() {
-                state.pdf.last_error = Some("no_pages_selected".into());
-            } else if let Some(raw_fd) = fd_handle.take() {
-                state.loading_with_spinner = true;
-                state.loading_message = Some("Processing PDF...".into());
-                let job = WorkerJob::PdfOperation(PdfWorkerArgs {
-                    op: PdfOperation::Delete,
-                    primary_fd: raw_fd,
-                    secondary_fd: None,
-                    primary_uri: uri.clone(),
-                    secondary_uri: None,
-                    selected_pages: selection.clone(),
-                });
-                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                    state.pdf.last_error = Some(e);
-                }
-                #[cfg(test)]
-                {
-                    apply_worker_results(state);
-                }
-            } else {
-                state.pdf.last_error = Some("missing_fd".into());
-            }
+        _ => {}
+    }
+}
+
+fn handle_multi_hash_actions(state: &mut AppState, action: Action) -> Option<Value> {
+    match action {
+        Action::MultiHashScreen => {
+            state.push_screen(Screen::MultiHash);
+            state.multi_hash_results = None;
+            state.multi_hash_error = None;
+            None
         }
-        Action::PdfReorder { fd, uri, order } => {
-            state.push_screen(Screen::PdfTools);
-            state.pdf.last_error = None;
-            state.pdf.last_output = None;
-            let mut fd_handle = FdHandle::new(fd);
-            if order.is_empty() {
-                state.pdf.last_error = Some("no_pages_selected".into());
-            } else if let Some(raw_fd) = fd_handle.take() {
-                state.loading_with_spinner = true;
-                state.loading_message = Some("Processing PDF...".into());
-                let job = WorkerJob::PdfOperation(PdfWorkerArgs {
-                    op: PdfOperation::Reorder,
-                    primary_fd: raw_fd,
-                    secondary_fd: None,
-                    primary_uri: uri.clone(),
-                    secondary_uri: None,
-                    selected_pages: order.clone(),
-                });
-                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                    state.pdf.last_error = Some(e);
-                }
-                #[cfg(test)]
-                {
-                    apply_worker_results(state);
-                }
-            } else {
-                state.pdf.last_error = Some("missing_fd".into());
-            }
+        _ => None,
+    }
+}
+
+fn handle_hash_actions(state: &mut AppState, action: Action) -> Option<Value> {
+    match action {
+        Action::HashVerifyScreen => {
+            state.push_screen(Screen::HashVerify);
+            state.hash_reference = None;
+            state.hash_match = None;
+            state.last_hash = None;
+            state.last_hash_algo = Some("SHA-256".into());
+            None
         }
-        Action::PdfMerge {
-            primary_fd,
-            primary_uri,
-            secondary_fd,
-            secondary_uri,
+        Action::HashVerify {
+            path,
+            fd,
+            reference,
+            hmac_key,
         } => {
-            state.push_screen(Screen::PdfTools);
-            state.pdf.last_error = None;
-            state.pdf.last_output = None;
-            let mut primary = FdHandle::new(primary_fd);
-            let mut secondary = FdHandle::new(secondary_fd);
-            if let (Some(p_fd), Some(s_fd)) = (primary.take(), secondary.take()) {
-                state.loading_with_spinner = true;
-                state.loading_message = Some("Processing PDF...".into());
-                let job = WorkerJob::PdfOperation(PdfWorkerArgs {
-                    op: PdfOperation::Merge,
-                    primary_fd: p_fd,
-                    secondary_fd: Some(s_fd),
-                    primary_uri: primary_uri.clone(),
-                    secondary_uri: secondary_uri.clone(),
-                    selected_pages: Vec::new(),
-                });
-                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                    state.pdf.last_error = Some(e);
-                }
-                #[cfg(test)]
-                {
-                    apply_worker_results(state);
+            let mut fd_handle = FdHandle::new(fd);
+            state.push_screen(Screen::HashVerify);
+            if let Some(key) = hmac_key {
+                state.hmac.key = key;
+            }
+            let hmac = if state.hmac.enabled {
+                match state.hmac.key_bytes() {
+                    Ok(key) => Some((state.hmac.algo, key)),
+                    Err(e) => {
+                        state.last_error = Some(e);
+                        state.hash_match = None;
+                        return None;
+                    }
                 }
             } else {
-                state.pdf.last_error = Some("missing_fd".into());
-            }
-        }
-        Action::PdfMergePick { paths } => {
-            state.push_screen(Screen::PdfTools);
-            state.pdf.merge_queue.extend(paths);
-        }
-        Action::PdfMergeRemove { path } => {
-            state.push_screen(Screen::PdfTools);
-            state.pdf.merge_queue.retain(|p| p != &path);
-        }
-        Action::PdfMergeBatch { paths, fds } => {
-            state.push_screen(Screen::PdfTools);
-            state.pdf.last_error = None;
-            if paths.is_empty() || fds.is_empty() || paths.len() != fds.len() {
-                state.pdf.last_error = Some("pdf_merge_batch_requires_paths".into());
+                None
+            };
+            // A keyed digest is worth showing even without a reference to compare to.
+            if let Some(err) = reference
+                .as_ref()
+                .filter(|s| hmac.is_none() && s.trim().is_empty())
+                .map(|_| "reference_empty".to_string())
+            {
+                state.last_error = Some(err);
+                state.hash_match = None;
             } else {
-                let mut fd_handle = FdListHandle::new(fds);
-                let raw_fds = fd_handle.take_all();
-                if raw_fds.is_empty() {
-                    state.pdf.last_error = Some("missing_fd".into());
-                    return;
-                }
-                state.loading_with_spinner = true;
-                state.loading_message = Some("Merging PDFs...".into());
-                state.replace_current(Screen::Loading);
-                let job = WorkerJob::PdfMergeMany { fds: raw_fds, uris: paths };
-                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                    state.pdf.last_error = Some(e);
-                    state.loading_with_spinner = false;
-                    state.loading_message = None;
-                }
-                #[cfg(test)]
+                let algo = HashAlgo::Sha256;
+                if let Some(err) =
+                    (reference.is_none() && hmac.is_none()).then(|| "missing_reference".to_string())
                 {
-                    apply_worker_results(state);
+                    state.last_error = Some(err);
+                } else {
+                    let source = hash_job_source(fd_handle.take(), path.as_deref());
+                    if let Some(src) = source {
+                        let reference = reference.unwrap_or_default();
+                        let loading_message = match &hmac {
+                            Some((hmac_algo, _)) => {
+                                t!("loading_computing_hash", algo = hmac_algo.label()).to_string()
+                            }
+                            None => hash_loading_message(algo),
+                        };
+                        let job = WorkerJob::HashVerify(HashVerifyJob {
+                            source: src,
+                            reference: reference.clone(),
+                            algo,
+                            hmac,
+                        });
+                        state.hash_reference = Some(reference);
+                        state.hash_match = None;
+                        state.last_hash = None;
+                        state.last_error = None;
+                        state.loading_with_spinner = true;
+                        state.loading_message = Some(loading_message);
+                        state.replace_current(Screen::Loading);
+                        if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                            state.last_error = Some(e);
+                        }
+                        #[cfg(test)]
+                        {
+                            apply_worker_results(state);
+                        }
+                    } else {
+                        state.last_error = Some("missing_path".into());
+                        state.hash_match = None;
+                        state.last_hash = None;
+                    }
                 }
             }
+            None
         }
-        Action::PdfSetTitle { fd, uri, title } => {
-            state.push_screen(Screen::PdfTools);
-            if let Some(raw_fd) = fd {
-                state.loading_message = Some("Updating title...".into());
-                state.loading_with_spinner = true;
-                let job = WorkerJob::PdfSetTitle {
-                    fd: raw_fd,
-                    uri: uri.clone(),
-                    title,
-                };
-                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                    state.pdf.last_error = Some(e);
-                }
-                #[cfg(test)]
-                {
-                    apply_worker_results(state);
-                }
-            } else {
-                state.pdf.last_error = Some("missing_fd".into());
-            }
+        Action::HashHmacConfig {
+            enabled,
+            algo,
+            key,
+            key_hex,
+        } => {
+            if !matches!(state.current_screen(), Screen::HashVerify) {
+                state.push_screen(Screen::HashVerify);
+            }
+            let hmac = &mut state.hmac;
+            hmac.enabled = enabled.unwrap_or(hmac.enabled);
+            hmac.algo = algo.unwrap_or(hmac.algo);
+            hmac.key = key.unwrap_or_else(|| hmac.key.clone());
+            hmac.key_hex = key_hex.unwrap_or(hmac.key_hex);
+            // The stored digest no longer belongs to these settings.
+            state.last_hash = None;
+            state.hash_match = None;
+            state.last_error = None;
+            None
         }
-        Action::PdfPreviewScreen => {
-            if matches!(state.current_screen(), Screen::PdfPreview) {
-                state.replace_current(Screen::PdfPreview);
+        Action::HashVerifyPaste { reference } => {
+            state.push_screen(Screen::HashVerify);
+            if let Some(text) = reference {
+                state.hash_reference = Some(text);
+                state.hash_match = None;
+                state.last_hash = None;
+                state.last_error = None;
             } else {
-                state.push_screen(Screen::PdfPreview);
+                state.last_error = Some("clipboard_empty".into());
             }
-            state.pdf.preview_page = None;
+            None
         }
-        Action::PdfPageOpen { page } => {
-            if matches!(state.current_screen(), Screen::PdfPreview) {
-                state.replace_current(Screen::PdfPreview);
-            } else {
-                state.push_screen(Screen::PdfPreview);
-            }
-            let mut target_page = page.max(1);
-            if let Some(count) = state.pdf.page_count {
-                if target_page > count {
-                    target_page = count;
+        Action::HashPasteReference { reference } => {
+            state.push_screen(Screen::Home);
+            if let Some(text) = reference {
+                state.hash_reference = Some(text.clone());
+                state.hash_match = None;
+                state.last_error = None;
+                if let Some(hash) = state.last_hash.clone() {
+                    let cleaned_ref = text.trim().to_ascii_lowercase();
+                    let cleaned_hash = hash.trim().to_ascii_lowercase();
+                    state.hash_match = Some(cleaned_ref == cleaned_hash);
                 }
+            } else {
+                state.last_error = Some("clipboard_empty".into());
             }
-            state.pdf.preview_page = Some(target_page);
+            None
         }
-        Action::PdfPageClose => {
-            if matches!(state.current_screen(), Screen::PdfPreview) {
-                state.replace_current(Screen::PdfPreview);
+        Action::HashQrFromLast => {
+            if let Some(hash) = state.last_hash.clone() {
+                state.push_screen(Screen::Qr);
+                if let Err(e) = handle_qr_action(state, &hash) {
+                    state.last_error = Some(e);
+                }
             } else {
-                state.push_screen(Screen::PdfPreview);
+                state.last_error = Some("no_hash_available".into());
             }
-            state.pdf.preview_page = None;
+            None
         }
-        Action::PdfSign {
-            fd,
-            uri,
-            signature,
-            page,
-            page_x_pct,
-            page_y_pct,
-            pos_x,
-            pos_y,
-            width,
-            height,
-            img_width_px,
-            img_height_px,
-            img_dpi,
+        _ => None,
+    }
+}
+
+fn handle_hash_job(
+    state: &mut AppState,
+    algo: HashAlgo,
+    path: Option<String>,
+    fd: Option<i32>,
+    error: Option<String>,
+    loading_only: bool,
+) -> Result<Value, String> {
+    let mut fd_handle = FdHandle::new(fd);
+    if loading_only {
+        state.loading_with_spinner = false;
+        state.replace_current(Screen::Loading);
+        state.loading_message = Some(hash_loading_message(algo));
+        return Ok(render_root(state));
+    }
+    state.reset_navigation();
+    state.last_hash_algo = Some(hash_label(algo).into());
+    if let Some(err) = error {
+        state.last_error = Some(err);
+        state.last_hash = None;
+        state.loading_message = None;
+        state.loading_with_spinner = true;
+        return Ok(render_root(state));
+    }
+
+    let source = hash_job_source(fd_handle.take(), path.as_deref());
+    if source.is_none() {
+        state.last_error = Some("missing_path".into());
+        state.last_hash = None;
+        state.loading_message = None;
+        state.loading_with_spinner = true;
+        return Ok(render_root(state));
+    }
+
+    drop(fd_handle);
+    let job = WorkerJob::Hash {
+        source: source.unwrap(),
+        algo,
+    };
+    if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+        state.last_error = Some(e);
+        state.last_hash = None;
+    }
+    #[cfg(test)]
+    {
+        apply_worker_results(state);
+    }
+    state.loading_message = None;
+    state.loading_with_spinner = true;
+    Ok(render_root(state))
+}
+
+fn handle_multi_hash_job(
+    state: &mut AppState,
+    path: Option<String>,
+    fd: Option<i32>,
+    loading_only: bool,
+) -> Result<Value, String> {
+    let mut fd_handle = FdHandle::new(fd);
+    if loading_only {
+        state.loading_with_spinner = false;
+        state.replace_current(Screen::Loading);
+        state.loading_message = Some(t!("loading_computing_all_hashes").to_string());
+        state.multi_hash_results = None;
+        state.multi_hash_error = None;
+        return Ok(render_root(state));
+    }
+    let source = hash_job_source(fd_handle.take(), path.as_deref());
+    state.reset_navigation();
+    state.push_screen(Screen::MultiHash);
+    state.multi_hash_results = None;
+    state.multi_hash_error = None;
+
+    match source {
+        Some(src) => {
+            let display = path.clone().unwrap_or_else(|| "Selected file".to_string());
+            drop(fd_handle);
+            let job = WorkerJob::MultiHash {
+                source: src,
+                display_path: display,
+            };
+            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                state.multi_hash_error = Some(e);
+                state.multi_hash_results = None;
+            }
+            #[cfg(test)]
+            {
+                apply_worker_results(state);
+            }
+            state.loading_message = None;
+            state.loading_with_spinner = true;
+            return Ok(render_root(state));
+        }
+        None => {
+            state.multi_hash_error = Some("missing_path".into());
+            state.multi_hash_results = None;
+            state.loading_message = None;
+            state.loading_with_spinner = true;
+            return Ok(render_root(state));
+        }
+    }
+}
+
+fn handle_scheduler_actions(
+    state: &mut AppState,
+    action: &Action,
+) -> Option<Value> {
+    match action {
+        Action::SchedulerScreen => {
+            state.push_screen(Screen::Scheduler);
+            Some(render_scheduler_screen(state))
+        }
+        Action::SchedulerAdd {
+            name,
+            action_id,
+            cron,
         } => {
-            state.push_screen(Screen::PdfTools);
-            if let Some(sig) = signature.or_else(|| state.pdf.signature_base64.clone()) {
-                if let Some(raw_fd) = fd {
-                    state.loading_message = Some("Signing PDF...".into());
-                    state.loading_with_spinner = true;
-                    let job = WorkerJob::PdfSign {
-                        fd: raw_fd,
-                        uri: uri.clone(),
-                        signature: sig,
-                        page,
-                        page_x_pct,
-                        page_y_pct,
-                        pos_x,
-                        pos_y,
-                        width,
-                        height,
-                        img_width_px,
-                        img_height_px,
-                        img_dpi,
+            if let Some(n) = name {
+                state.scheduler.form_name = n.clone();
+            }
+            if let Some(a) = action_id {
+                state.scheduler.form_action = a.clone();
+            }
+            if let Some(c) = cron {
+                state.scheduler.form_cron = c.clone();
+            }
+
+            if state.scheduler.form_name.trim().is_empty()
+                || state.scheduler.form_action.trim().is_empty()
+            {
+                state.scheduler.last_error = Some("name_and_action_required".into());
+                state.replace_current(Screen::Scheduler);
+                return Some(render_scheduler_screen(state));
+            }
+
+            match cron::Schedule::from_str(&state.scheduler.form_cron) {
+                Ok(_) => {
+                    let task = crate::state::ScheduledTask {
+                        id: state.scheduler.next_id,
+                        name: state.scheduler.form_name.trim().to_string(),
+                        action: state.scheduler.form_action.trim().to_string(),
+                        cron: state.scheduler.form_cron.trim().to_string(),
+                        enabled: true,
+                        last_run_epoch: None,
+                        last_status: None,
                     };
-                    if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                        state.pdf.last_error = Some(e);
-                    }
-                    #[cfg(test)]
-                    {
-                        apply_worker_results(state);
-                    }
-                } else {
-                    state.pdf.last_error = Some("missing_fd".into());
+                    state.scheduler.next_id = state.scheduler.next_id.saturating_add(1);
+                    state.scheduler.tasks.push(task);
+                    state.scheduler.last_error = None;
+                    scheduler_runtime().sync_tasks(&state.scheduler.tasks);
+                    state.scheduler.form_name.clear();
+                    state.scheduler.form_action.clear();
+                }
+                Err(e) => {
+                    state.scheduler.last_error = Some(format!("invalid_cron:{e}"));
                 }
-            } else {
-                state.pdf.last_error = Some("missing_signature".into());
             }
+
+            state.replace_current(Screen::Scheduler);
+            Some(render_scheduler_screen(state))
         }
-        Action::PdfSignGrid { page, x_pct, y_pct } => {
-            state.push_screen(Screen::PdfTools);
-            state.pdf.signature_target_page = Some(page);
-            state.pdf.signature_x_pct = Some(x_pct);
-            state.pdf.signature_y_pct = Some(y_pct);
-            state.pdf.signature_grid_selection = Some((page, x_pct, y_pct));
+        Action::SchedulerToggle { id } => {
+            if let Some(task) = state.scheduler.tasks.iter_mut().find(|t| t.id == *id) {
+                task.enabled = !task.enabled;
+            }
+            scheduler_runtime().sync_tasks(&state.scheduler.tasks);
+            state.replace_current(Screen::Scheduler);
+            Some(render_scheduler_screen(state))
         }
-        Action::PdfSignatureStore { data } => {
-            state.pdf.signature_base64 = data;
-            state.pdf.signature_width_pt = None;
-            state.pdf.signature_height_pt = None;
-            state.pdf.last_error = None;
-            state.push_screen(Screen::PdfTools);
+        Action::SchedulerDelete { id } => {
+            state.scheduler.tasks.retain(|t| t.id != *id);
+            scheduler_runtime().sync_tasks(&state.scheduler.tasks);
+            state.replace_current(Screen::Scheduler);
+            Some(render_scheduler_screen(state))
         }
-        Action::PdfSignatureClear => {
-            state.pdf.signature_base64 = None;
-            state.pdf.signature_width_pt = None;
-            state.pdf.signature_height_pt = None;
-            state.pdf.last_error = None;
-            state.push_screen(Screen::PdfTools);
+        Action::SchedulerRunNow { id } => {
+            scheduler_runtime().trigger_now(*id);
+            state.replace_current(Screen::Scheduler);
+            Some(render_scheduler_screen(state))
         }
-        _ => {}
+        _ => None,
     }
 }
 
-fn handle_text_viewer_actions(state: &mut AppState, action: Action) {
+fn hash_job_source(fd: Option<i32>, path: Option<&str>) -> Option<HashSourceInput> {
+    if let Some(fd) = fd {
+        Some(HashSourceInput::Fd(fd))
+    } else {
+        path.map(|p| HashSourceInput::Path(p.to_string()))
+    }
+}
+
+fn handle_pdf_actions(state: &mut AppState, action: Action) {
     match action {
-        Action::TextViewerScreen => {
-            state.push_screen(Screen::TextViewer);
-            state.text_view_error = None;
-            state.text_view_language = None;
-            state.text_view_hex_preview = None;
-            state.text_view_loaded_bytes = 0;
-            state.text_view_total_bytes = None;
-            state.text_view_has_more = false;
-            state.text_view_window_offset = 0;
-            state.text_view_has_previous = false;
-            state.text_view_cached_path = None;
+        Action::PdfToolsScreen => {
+            state.push_screen(Screen::PdfTools);
+            state.pdf.last_error = None;
+            state.pdf.last_output = None;
         }
-        Action::TextViewerOpen { fd, path, error } => {
-            state.push_screen(Screen::TextViewer);
-            state.text_view_error = error.clone();
-            state.text_view_find_query = None;
-            state.text_view_find_match = None;
-            state.text_view_loaded_bytes = 0;
-            state.text_view_total_bytes = None;
-            state.text_view_has_more = false;
-            state.text_view_window_offset = 0;
-            state.text_view_has_previous = false;
-            state.text_view_cached_path = None;
-            if error.is_some() {
-                state.text_view_content = None;
-                state.text_view_language = None;
-                state.text_view_hex_preview = None;
+        Action::PdfSelect { fd, uri, error } => {
+            state.push_screen(Screen::PdfTools);
+            state.pdf.last_error = error.clone();
+            state.pdf.preview_page = None;
+            state.pdf.page_count = None;
+            state.pdf.selected_pages.clear();
+            state.pdf.last_output = None;
+            state.pdf.current_title = None;
+            state.pdf.signature_target_page = None;
+            state.pdf.signature_x_pct = None;
+            state.pdf.signature_y_pct = None;
+            if let Some(err) = error {
+                state.pdf.last_error = Some(err);
             } else if let Some(raw_fd) = fd {
-                state.loading_message = Some("Loading text...".into());
+                state.loading_message = Some(t!("loading_pdf").to_string());
                 state.loading_with_spinner = true;
                 state.replace_current(Screen::Loading);
-                let source = TextViewSource::Fd {
+                let job = WorkerJob::PdfSelect {
                     fd: raw_fd,
-                    display_path: path.clone(),
-                };
-                let job = WorkerJob::TextViewerLoad {
-                    source,
-                    offset: 0,
-                    force_text: false,
-                    can_page: true,
+                    uri: uri.clone(),
                 };
                 if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                    state.text_view_error = Some(e);
-                    state.replace_current(Screen::TextViewer);
+                    state.pdf.last_error = Some(e);
                 }
                 #[cfg(test)]
                 {
                     apply_worker_results(state);
                 }
-            } else if let Some(p) = path.clone() {
-                state.loading_message = Some("Loading text...".into());
+            } else {
+                state.pdf.last_error = Some("missing_fd".into());
+            }
+        }
+        Action::PdfExtract { fd, uri, selection } => {
+            state.push_screen(Screen::PdfTools);
+            state.pdf.last_error = None;
+            state.pdf.last_output = None;
+            let mut fd_handle = FdHandle::new(fd);
+            if selection.is_empty() {
+                state.pdf.last_error = Some("no_pages_selected".into());
+            } else if let Some(raw_fd) = fd_handle.take() {
                 state.loading_with_spinner = true;
-                state.replace_current(Screen::Loading);
-                let source = TextViewSource::Path {
-                    read_path: p.clone(),
-                    display_path: Some(p),
-                };
-                let job = WorkerJob::TextViewerLoad {
-                    source,
-                    offset: 0,
-                    force_text: false,
-                    can_page: true,
-                };
+                state.loading_message = Some(t!("loading_processing_pdf").to_string());
+                let job = WorkerJob::PdfOperation(PdfWorkerArgs {
+                    op: PdfOperation::Extract,
+                    primary_fd: raw_fd,
+                    secondary_fd: None,
+                    primary_uri: uri.clone(),
+                    secondary_uri: None,
+                    selected_pages: selection.clone(),
+                });
                 if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                    state.text_view_error = Some(e);
-                    state.replace_current(Screen::TextViewer);
+                    state.pdf.last_error = Some(e);
                 }
                 #[cfg(test)]
                 {
                     apply_worker_results(state);
                 }
             } else {
-                state.text_view_error = Some("missing_source".into());
-                state.text_view_content = None;
-                state.text_view_language = None;
-                state.text_view_hex_preview = None;
+                state.pdf.last_error = Some("missing_fd".into());
             }
         }
-        Action::TextViewerToggleTheme => {
-            state.text_view_dark = !state.text_view_dark;
-            state.replace_current(Screen::TextViewer);
-        }
-        Action::TextViewerToggleLineNumbers => {
-            state.text_view_line_numbers = !state.text_view_line_numbers;
-            state.replace_current(Screen::TextViewer);
-        }
-        Action::TextViewerLoadAnyway => {
-            state.text_view_hex_preview = None;
-            if let Some(path) = state.text_view_path.clone() {
-                let effective = state.text_view_cached_path.clone().unwrap_or(path.clone());
-                state.loading_message = Some("Loading text...".into());
+        Action::PdfDelete { fd, uri, selection } => {
+            state.push_screen(Screen::PdfTools);
+            state.pdf.last_error = None;
+            state.pdf.last_output = None;
+            let mut fd_handle = FdHandle::new(fd);
+            if selection.is_empty() {
+                state.pdf.last_error = Some("no_pages_selected".into());
+            } else if let Some(raw_fd) = fd_handle.take() {
                 state.loading_with_spinner = true;
-                state.replace_current(Screen::Loading);
-                let source = TextViewSource::Path {
-                    read_path: effective,
-                    display_path: Some(path),
-                };
-                let job = WorkerJob::TextViewerLoad {
-                    source,
-                    offset: 0,
-                    force_text: true,
-                    can_page: true,
-                };
+                state.loading_message = Some(t!("loading_processing_pdf").to_string());
+                let job = WorkerJob::PdfOperation(PdfWorkerArgs {
+                    op: PdfOperation::Delete,
+                    primary_fd: raw_fd,
+                    secondary_fd: None,
+                    primary_uri: uri.clone(),
+                    secondary_uri: None,
+                    selected_pages: selection.clone(),
+                });
                 if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                    state.text_view_error = Some(e);
-                    state.replace_current(Screen::TextViewer);
+                    state.pdf.last_error = Some(e);
                 }
                 #[cfg(test)]
                 {
                     apply_worker_results(state);
                 }
             } else {
-                state.text_view_error = Some("nothing_to_reload".into());
-                state.text_view_content = None;
-                state.replace_current(Screen::TextViewer);
-            }
-        }
-        Action::TextViewerLoadMore => {
-            let path = match state.text_view_path.clone() {
-                Some(p) => p,
-                None => {
-                    state.text_view_error = Some("missing_path".into());
-                    state.replace_current(Screen::TextViewer);
-                    return;
-                }
-            };
-            let offset = state
-                .text_view_window_offset
-                .saturating_add(features::text_viewer::CHUNK_BYTES as u64);
-            let effective = state.text_view_cached_path.clone().unwrap_or(path.clone());
-            state.loading_message = Some("Loading text...".into());
-            state.loading_with_spinner = true;
-            state.replace_current(Screen::Loading);
-            let source = TextViewSource::Path {
-                read_path: effective,
-                display_path: Some(path),
-            };
-            let job = WorkerJob::TextViewerLoad {
-                source,
-                offset,
-                force_text: true,
-                can_page: true,
-            };
-            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                state.text_view_error = Some(e);
-                state.replace_current(Screen::TextViewer);
-            }
-            #[cfg(test)]
-            {
-                apply_worker_results(state);
-            }
-        }
-        Action::TextViewerLoadPrev => {
-            let path = match state.text_view_path.clone() {
-                Some(p) => p,
-                None => {
-                    state.text_view_error = Some("missing_path".into());
-                    state.replace_current(Screen::TextViewer);
-                    return;
-                }
-            };
-            let offset = state
-                .text_view_window_offset
-                .saturating_sub(features::text_viewer::CHUNK_BYTES as u64);
-            let effective = state.text_view_cached_path.clone().unwrap_or(path.clone());
-            state.loading_message = Some("Loading text...".into());
-            state.loading_with_spinner = true;
-            state.replace_current(Screen::Loading);
-            let source = TextViewSource::Path {
-                read_path: effective,
-                display_path: Some(path),
-            };
-            let job = WorkerJob::TextViewerLoad {
-                source,
-                offset,
-                force_text: true,
-                can_page: true,
-            };
-            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                state.text_view_error = Some(e);
-                state.replace_current(Screen::TextViewer);
-            }
-            #[cfg(test)]
-            {
-                apply_worker_results(state);
+                state.pdf.last_error = Some("missing_fd".into());
             }
         }
-        Action::TextViewerJump { offset } => {
-            let target = offset.unwrap_or(0);
-            if let Some(path) = state.text_view_path.clone() {
-                let effective = state.text_view_cached_path.clone().unwrap_or(path.clone());
-                let clamped = state
-                    .text_view_total_bytes
-                    .map(|total| {
-                        let window = features::text_viewer::CHUNK_BYTES as u64;
-                        let max_offset = total.saturating_sub(window.min(total));
-                        target.min(max_offset)
-                    })
-                    .unwrap_or(target);
-                state.loading_message = Some("Loading text...".into());
+        Action::PdfReorder { fd, uri, order } => {
+            state.push_screen(Screen::PdfTools);
+            state.pdf.last_error = None;
+            state.pdf.last_output = None;
+            let mut fd_handle = FdHandle::new(fd);
+            if order.is_empty() {
+                state.pdf.last_error = Some("no_pages_selected".into());
+            } else if let Some(raw_fd) = fd_handle.take() {
                 state.loading_with_spinner = true;
-                state.replace_current(Screen::Loading);
-                let source = TextViewSource::Path {
-                    read_path: effective,
-                    display_path: Some(path),
-                };
-                let job = WorkerJob::TextViewerLoad {
-                    source,
-                    offset: clamped,
-                    force_text: true,
-                    can_page: true,
-                };
+                state.loading_message = Some(t!("loading_processing_pdf").to_string());
+                let job = WorkerJob::PdfOperation(PdfWorkerArgs {
+                    op: PdfOperation::Reorder,
+                    primary_fd: raw_fd,
+                    secondary_fd: None,
+                    primary_uri: uri.clone(),
+                    secondary_uri: None,
+                    selected_pages: order.clone(),
+                });
                 if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                    state.text_view_error = Some(e);
-                    state.replace_current(Screen::TextViewer);
+                    state.pdf.last_error = Some(e);
                 }
                 #[cfg(test)]
                 {
                     apply_worker_results(state);
                 }
             } else {
-                state.text_view_error = Some("missing_path".into());
-                state.replace_current(Screen::TextViewer);
+                state.pdf.last_error = Some("missing_fd".into());
             }
         }
-        Action::TextViewerFind { query, direction } => {
-            if let Some(q) = query {
-                let trimmed = q.trim();
-                if trimmed.is_empty() {
-                    state.text_view_find_query = None;
-                    state.text_view_find_match = Some("Cleared search".into());
-                } else {
-                    state.text_view_find_query = Some(trimmed.to_string());
-                    state.text_view_find_match = None;
-                }
-            }
-            if let Some(dir) = direction {
-                state.text_view_find_match = Some(
-                    match dir.as_str() {
-                        "next" => "Searching next match",
-                        "prev" => "Searching previous match",
-                        _ => "Searching",
-                    }
-                    .into(),
-                );
+        Action::PdfMerge {
+            primary_fd,
+            primary_uri,
+            secondary_fd,
+            secondary_uri,
+        } => {
+            state.push_screen(Screen::PdfTools);
+            state.pdf.last_error = None;
+            state.pdf.last_output = None;
+            let mut primary = FdHandle::new(primary_fd);
+            let mut secondary = FdHandle::new(secondary_fd);
+            if let (Some(p_fd), Some(s_fd)) = (primary.take(), secondary.take()) {
+                state.loading_with_spinner = true;
+                state.loading_message = Some(t!("loading_processing_pdf").to_string());
+                let job = WorkerJob::PdfOperation(PdfWorkerArgs {
+                    op: PdfOperation::Merge,
+                    primary_fd: p_fd,
+                    secondary_fd: Some(s_fd),
+                    primary_uri: primary_uri.clone(),
+                    secondary_uri: secondary_uri.clone(),
+                    selected_pages: Vec::new(),
+                });
+                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                    state.pdf.last_error = Some(e);
+                }
+                #[cfg(test)]
+                {
+                    apply_worker_results(state);
+                }
+            } else {
+                state.pdf.last_error = Some("missing_fd".into());
             }
-            state.replace_current(Screen::TextViewer);
         }
-        _ => {}
-    }
-}
-
-fn handle_plotting_actions(state: &mut AppState, action: Action) {
-    match action {
-        Action::PlottingScreen => {
-            state.plotting = crate::state::PlottingState::new();
-            state.push_screen(Screen::Plotting);
+        Action::PdfMergePick { paths } => {
+            state.push_screen(Screen::PdfTools);
+            state.pdf.merge_queue.extend(paths);
         }
-        Action::PlottingPick { fd, path, error } => {
-            state.push_screen(Screen::Plotting);
-            state.plotting.generated_svg = None;
-            if let Some(err) = error {
-                state.plotting.error = Some(err);
-                return;
-            }
-            let target_path = if let Some(fd) = fd {
-                plotting::copy_csv_from_fd(fd, "plot.csv")
-            } else if let Some(p) = path.clone() {
-                Ok(p)
+        Action::PdfMergeRemove { path } => {
+            state.push_screen(Screen::PdfTools);
+            state.pdf.merge_queue.retain(|p| p != &path);
+        }
+        Action::PdfMergeBatch { paths, fds } => {
+            state.push_screen(Screen::PdfTools);
+            state.pdf.last_error = None;
+            if paths.is_empty() || fds.is_empty() || paths.len() != fds.len() {
+                state.pdf.last_error = Some("pdf_merge_batch_requires_paths".into());
             } else {
-                Err("missing_source".into())
-            };
-            match target_path {
-                Ok(p) => {
-                    state.plotting.file_path = Some(p.clone());
-                    state.plotting.display_path = Some(path.unwrap_or(p.clone()));
-                    match plotting::load_headers(&p) {
-                        Ok(headers) => {
-                            state.plotting.headers = headers;
-                            state.plotting.error = None;
-                        }
-                        Err(e) => state.plotting.error = Some(e),
-                    }
+                let mut fd_handle = FdListHandle::new(fds);
+                let raw_fds = fd_handle.take_all();
+                if raw_fds.is_empty() {
+                    state.pdf.last_error = Some("missing_fd".into());
+                    return;
+                }
+                state.loading_with_spinner = true;
+                state.loading_message = Some(t!("loading_merging_pdfs").to_string());
+                state.replace_current(Screen::Loading);
+                let job = WorkerJob::PdfMergeMany { fds: raw_fds, uris: paths };
+                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                    state.pdf.last_error = Some(e);
+                    state.loading_with_spinner = false;
+                    state.loading_message = None;
+                }
+                #[cfg(test)]
+                {
+                    apply_worker_results(state);
                 }
-                Err(e) => state.plotting.error = Some(e),
             }
         }
-        Action::PlottingSetX { col } => {
-            state.replace_current(Screen::Plotting);
-            let value = col.and_then(|c| {
-                let t = c.trim();
-                if t.is_empty() {
-                    None
-                } else {
-                    Some(t.to_string())
+        Action::PdfSetTitle { fd, uri, title } => {
+            state.push_screen(Screen::PdfTools);
+            if let Some(raw_fd) = fd {
+                state.loading_message = Some(t!("loading_updating_title").to_string());
+                state.loading_with_spinner = true;
+                let job = WorkerJob::PdfSetTitle {
+                    fd: raw_fd,
+                    uri: uri.clone(),
+                    title,
+                };
+                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                    state.pdf.last_error = Some(e);
                 }
-            });
-            state.plotting.x_col = value;
+                #[cfg(test)]
+                {
+                    apply_worker_results(state);
+                }
+            } else {
+                state.pdf.last_error = Some("missing_fd".into());
+            }
         }
-        Action::PlottingSetY { col } => {
-            state.replace_current(Screen::Plotting);
-            let value = col.and_then(|c| {
-                let t = c.trim();
-                if t.is_empty() {
-                    None
-                } else {
-                    Some(t.to_string())
+        Action::PdfPreviewScreen => {
+            if matches!(state.current_screen(), Screen::PdfPreview) {
+                state.replace_current(Screen::PdfPreview);
+            } else {
+                state.push_screen(Screen::PdfPreview);
+            }
+            state.pdf.preview_page = None;
+        }
+        Action::PdfPageOpen { page } => {
+            if matches!(state.current_screen(), Screen::PdfPreview) {
+                state.replace_current(Screen::PdfPreview);
+            } else {
+                state.push_screen(Screen::PdfPreview);
+            }
+            let mut target_page = page.max(1);
+            if let Some(count) = state.pdf.page_count {
+                if target_page > count {
+                    target_page = count;
                 }
-            });
-            state.plotting.y_col = value;
+            }
+            state.pdf.preview_page = Some(target_page);
         }
-        Action::PlottingSetType { plot_type } => {
-            state.replace_current(Screen::Plotting);
-            state.plotting.plot_type = plot_type;
+        Action::PdfPageClose => {
+            if matches!(state.current_screen(), Screen::PdfPreview) {
+                state.replace_current(Screen::PdfPreview);
+            } else {
+                state.push_screen(Screen::PdfPreview);
+            }
+            state.pdf.preview_page = None;
         }
-        Action::PlottingGenerate => {
-            state.replace_current(Screen::Plotting);
-            match plotting::generate_plot(&mut state.plotting) {
-                Ok(_) => {}
-                Err(e) => state.plotting.error = Some(e),
+        Action::PdfTabSelect { tab } => {
+            state.pdf.tab = tab;
+            state.replace_current(Screen::PdfTools);
+        }
+        Action::PdfSign {
+            fd,
+            uri,
+            signature,
+            page,
+            page_x_pct,
+            page_y_pct,
+            pos_x,
+            pos_y,
+            width,
+            height,
+            img_width_px,
+            img_height_px,
+            img_dpi,
+        } => {
+            state.push_screen(Screen::PdfTools);
+            if let Some(sig) = signature.or_else(|| state.pdf.signature_base64.clone()) {
+                if let Some(raw_fd) = fd {
+                    state.loading_message = Some(t!("loading_signing_pdf").to_string());
+                    state.loading_with_spinner = true;
+                    let job = WorkerJob::PdfSign {
+                        fd: raw_fd,
+                        uri: uri.clone(),
+                        signature: sig,
+                        page,
+                        page_x_pct,
+                        page_y_pct,
+                        pos_x,
+                        pos_y,
+                        width,
+                        height,
+                        img_width_px,
+                        img_height_px,
+                        img_dpi,
+                    };
+                    if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                        state.pdf.last_error = Some(e);
+                    }
+                    #[cfg(test)]
+                    {
+                        apply_worker_results(state);
+                    }
+                } else {
+                    state.pdf.last_error = Some("missing_fd".into());
+                }
+            } else {
+                state.pdf.last_error = Some("missing_signature".into());
             }
         }
+        Action::PdfSignGrid { page, x_pct, y_pct } => {
+            state.push_screen(Screen::PdfTools);
+            state.pdf.signature_target_page = Some(page);
+            state.pdf.signature_x_pct = Some(x_pct);
+            state.pdf.signature_y_pct = Some(y_pct);
+            state.pdf.signature_grid_selection = Some((page, x_pct, y_pct));
+        }
+        Action::PdfSignatureStore { data } => {
+            state.pdf.signature_base64 = data;
+            state.pdf.signature_width_pt = None;
+            state.pdf.signature_height_pt = None;
+            state.pdf.last_error = None;
+            state.push_screen(Screen::PdfTools);
+        }
+        Action::PdfSignatureClear => {
+            state.pdf.signature_base64 = None;
+            state.pdf.signature_width_pt = None;
+            state.pdf.signature_height_pt = None;
+            state.pdf.last_error = None;
+            state.push_screen(Screen::PdfTools);
+        }
         _ => {}
     }
 }
 
-fn handle_sql_actions(state: &mut AppState, action: Action) -> Option<Value> {
+fn handle_text_viewer_actions(state: &mut AppState, action: Action) {
     match action {
-        Action::SqlScreen => {
-            state.push_screen(Screen::SqlQuery);
-            Some(handle_sql_action(state, "sql_screen", &HashMap::new()))
+        Action::TextViewerScreen => {
+            state.push_screen(Screen::TextViewer);
+            state.text_view_wrap = true;
+            state.text_view_error = None;
+            state.text_view_language = None;
+            state.text_view_hex_preview = None;
+            state.text_view_loaded_bytes = 0;
+            state.text_view_total_bytes = None;
+            state.text_view_has_more = false;
+            state.text_view_window_offset = 0;
+            state.text_view_has_previous = false;
+            state.text_view_cached_path = None;
         }
-        Action::SqlImport { path, fd, table_name, is_json } => {
-            state.push_screen(Screen::SqlQuery);
-            let mut bindings = HashMap::new();
-            bindings.insert("path".to_string(), path.clone());
-            if let Some(fd_val) = fd {
-                bindings.insert("fd".to_string(), fd_val.to_string());
+        Action::TextViewerOpen { fd, path, error } => {
+            state.push_screen(Screen::TextViewer);
+            state.text_view_error = error.clone();
+            state.text_view_find_query = None;
+            state.text_view_find_match = None;
+            state.text_view_loaded_bytes = 0;
+            state.text_view_total_bytes = None;
+            state.text_view_has_more = false;
+            state.text_view_window_offset = 0;
+            state.text_view_has_previous = false;
+            state.text_view_cached_path = None;
+            if error.is_some() {
+                state.text_view_content = None;
+                state.text_view_language = None;
+                state.text_view_hex_preview = None;
+            } else if let Some(raw_fd) = fd {
+                state.loading_message = Some(t!("loading_text").to_string());
+                state.loading_with_spinner = true;
+                state.replace_current(Screen::Loading);
+                let source = TextViewSource::Fd {
+                    fd: raw_fd,
+                    display_path: path.clone(),
+                };
+                let job = WorkerJob::TextViewerLoad {
+                    source,
+                    offset: 0,
+                    force_text: false,
+                    can_page: true,
+                };
+                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                    state.text_view_error = Some(e);
+                    state.replace_current(Screen::TextViewer);
+                }
+                #[cfg(test)]
+                {
+                    apply_worker_results(state);
+                }
+            } else if let Some(p) = path.clone() {
+                state.loading_message = Some(t!("loading_text").to_string());
+                state.loading_with_spinner = true;
+                state.replace_current(Screen::Loading);
+                let source = TextViewSource::Path {
+                    read_path: p.clone(),
+                    display_path: Some(p),
+                };
+                let job = WorkerJob::TextViewerLoad {
+                    source,
+                    offset: 0,
+                    force_text: false,
+                    can_page: true,
+                };
+                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                    state.text_view_error = Some(e);
+                    state.replace_current(Screen::TextViewer);
+                }
+                #[cfg(test)]
+                {
+                    apply_worker_results(state);
+                }
+            } else {
+                state.text_view_error = Some("missing_source".into());
+                state.text_view_content = None;
+                state.text_view_language = None;
+                state.text_view_hex_preview = None;
             }
-            bindings.insert("table_name".to_string(), table_name.clone());
-            
-            let action = if is_json { "sql_import_json" } else { "sql_import_csv" };
-            Some(handle_sql_action(state, action, &bindings))
-        }
-        Action::SqlExecute { query } => {
-            state.push_screen(Screen::SqlQuery);
-            let mut bindings = HashMap::new();
-            bindings.insert("query".to_string(), query.clone());
-            Some(handle_sql_action(state, "sql_execute", &bindings))
         }
-        Action::SqlClearAll => {
-            state.push_screen(Screen::SqlQuery);
-            Some(handle_sql_action(state, "sql_clear_all", &HashMap::new()))
+        Action::TextViewerToggleTheme => {
+            state.text_view_dark = !state.text_view_dark;
+            state.replace_current(Screen::TextViewer);
         }
-        _ => None,
-    }
-}
-
-fn handle_hex_editor_actions(state: &mut AppState, action: Action) -> Option<Value> {
-    use crate::features::hex_editor;
-    fn maybe_toast_hex_saved(state: &mut AppState) {
-        let msg = state.hex_editor.status.clone().unwrap_or_default();
-        if msg.starts_with("Result saved to:") {
-            state.toast = Some(msg);
-            state.hex_editor.status = None;
+        Action::TextViewerToggleLineNumbers => {
+            state.text_view_line_numbers = !state.text_view_line_numbers;
+            state.replace_current(Screen::TextViewer);
         }
-    }
-    match action {
-        Action::HexEditorScreen => {
-            state.hex_editor.reset();
-            state.push_screen(Screen::HexEditor);
-            None
+        Action::TextViewerToggleMarkdownPreview => {
+            state.text_view_markdown_preview = !state.text_view_markdown_preview;
+            state.replace_current(Screen::TextViewer);
         }
-        Action::HexEditorOpen { fd, path, error } => {
-            state.push_screen(Screen::HexEditor);
-            state.hex_editor.status = None;
-            if let Some(err) = error {
-                state.hex_editor.error = Some(err);
-                state.hex_editor.current_dump = None;
-                return None;
-            }
-            if let Some(raw) = fd {
-                match hex_editor::copy_fd_to_temp(raw as RawFd) {
-                    Ok(tmp) => {
-                        if let Err(e) =
-                            hex_editor::set_file(&mut state.hex_editor, tmp.clone(), path.clone())
-                        {
-                            state.hex_editor.error = Some(e);
-                        }
-                    }
-                    Err(e) => state.hex_editor.error = Some(e),
+        Action::TextViewerLoadAnyway => {
+            state.text_view_hex_preview = None;
+            if let Some(path) = state.text_view_path.clone() {
+                let effective = state.text_view_cached_path.clone().unwrap_or(path.clone());
+                state.loading_message = Some(t!("loading_text").to_string());
+                state.loading_with_spinner = true;
+                state.replace_current(Screen::Loading);
+                let source = TextViewSource::Path {
+                    read_path: effective,
+                    display_path: Some(path),
+                };
+                let job = WorkerJob::TextViewerLoad {
+                    source,
+                    offset: 0,
+                    force_text: true,
+                    can_page: true,
+                };
+                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                    state.text_view_error = Some(e);
+                    state.replace_current(Screen::TextViewer);
                 }
-            } else if let Some(p) = path {
-                if let Err(e) =
-                    hex_editor::set_file(&mut state.hex_editor, p.clone(), Some(p.clone()))
+                #[cfg(test)]
                 {
-                    state.hex_editor.error = Some(e);
+                    apply_worker_results(state);
                 }
             } else {
-                state.hex_editor.error = Some("missing_source".into());
-                state.hex_editor.current_dump = None;
+                state.text_view_error = Some("nothing_to_reload".into());
+                state.text_view_content = None;
+                state.replace_current(Screen::TextViewer);
             }
-            None
         }
-        Action::HexEditorNav { direction, offset } => {
-            state.replace_current(Screen::HexEditor);
-            let mut target = state.hex_editor.offset;
-            match direction.as_str() {
-                "next" => {
-                    target = target.saturating_add(state.hex_editor.chunk_size as u64);
-                }
-                "prev" => {
-                    target = target.saturating_sub(state.hex_editor.chunk_size as u64);
-                }
-                "jump" => {
-                    if let Some(off) = offset {
-                        target = off;
-                    }
+        Action::TextViewerLoadMore => {
+            let path = match state.text_view_path.clone() {
+                Some(p) => p,
+                None => {
+                    state.text_view_error = Some("missing_path".into());
+                    state.replace_current(Screen::TextViewer);
+                    return;
                 }
-                _ => {}
+            };
+            let offset = state
+                .text_view_window_offset
+                .saturating_add(features::text_viewer::CHUNK_BYTES as u64);
+            let effective = state.text_view_cached_path.clone().unwrap_or(path.clone());
+            state.loading_message = Some(t!("loading_text").to_string());
+            state.loading_with_spinner = true;
+            state.replace_current(Screen::Loading);
+            let source = TextViewSource::Path {
+                read_path: effective,
+                display_path: Some(path),
+            };
+            let job = WorkerJob::TextViewerLoad {
+                source,
+                offset,
+                force_text: true,
+                can_page: true,
+            };
+            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                state.text_view_error = Some(e);
+                state.replace_current(Screen::TextViewer);
             }
-            state.hex_editor.offset = target;
-            if let Err(e) = hex_editor::refresh_view(&mut state.hex_editor) {
-                state.hex_editor.error = Some(e);
+            #[cfg(test)]
+            {
+                apply_worker_results(state);
             }
-            None
         }
-        Action::HexEditorPatch { offset, byte } => {
-            state.replace_current(Screen::HexEditor);
-            let off = match offset {
-                Some(v) => v,
+        Action::TextViewerLoadPrev => {
+            let path = match state.text_view_path.clone() {
+                Some(p) => p,
                 None => {
-                    state.hex_editor.error = Some("missing_offset".into());
-                    return None;
+                    state.text_view_error = Some("missing_path".into());
+                    state.replace_current(Screen::TextViewer);
+                    return;
                 }
             };
-            let value = match byte {
-                Some(v) => v,
-                None => {
-                    state.hex_editor.error = Some("missing_byte".into());
-                    return None;
-                }
+            let offset = state
+                .text_view_window_offset
+                .saturating_sub(features::text_viewer::CHUNK_BYTES as u64);
+            let effective = state.text_view_cached_path.clone().unwrap_or(path.clone());
+            state.loading_message = Some(t!("loading_text").to_string());
+            state.loading_with_spinner = true;
+            state.replace_current(Screen::Loading);
+            let source = TextViewSource::Path {
+                read_path: effective,
+                display_path: Some(path),
             };
-            if let Err(e) = hex_editor::patch_byte(&mut state.hex_editor, off, value) {
-                state.hex_editor.error = Some(e);
+            let job = WorkerJob::TextViewerLoad {
+                source,
+                offset,
+                force_text: true,
+                can_page: true,
+            };
+            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                state.text_view_error = Some(e);
+                state.replace_current(Screen::TextViewer);
             }
-            None
-        }
-        Action::HexEditorSave => {
-            state.replace_current(Screen::HexEditor);
-            if let Err(e) = hex_editor::save_changes(&mut state.hex_editor) {
-                state.hex_editor.error = Some(e);
+            #[cfg(test)]
+            {
+                apply_worker_results(state);
             }
-            maybe_toast_hex_saved(state);
-            None
         }
-        Action::HexEditorSaveAs { path } => {
-            state.replace_current(Screen::HexEditor);
-            let target = path.or_else(|| {
-                state
-                    .hex_editor
-                    .file_path
-                    .as_ref()
-                    .map(|p| format!("{p}.patched"))
-            });
-            match target {
-                Some(p) => {
-                    if let Err(e) = hex_editor::save_as(&mut state.hex_editor, &p) {
-                        state.hex_editor.error = Some(e);
-                    }
+        Action::TextViewerJump { offset } => {
+            let target = offset.unwrap_or(0);
+            if let Some(path) = state.text_view_path.clone() {
+                let effective = state.text_view_cached_path.clone().unwrap_or(path.clone());
+                let clamped = state
+                    .text_view_total_bytes
+                    .map(|total| {
+                        let window = features::text_viewer::CHUNK_BYTES as u64;
+                        let max_offset = total.saturating_sub(window.min(total));
+                        target.min(max_offset)
+                    })
+                    .unwrap_or(target);
+                state.loading_message = Some(t!("loading_text").to_string());
+                state.loading_with_spinner = true;
+                state.replace_current(Screen::Loading);
+                let source = TextViewSource::Path {
+                    read_path: effective,
+                    display_path: Some(path),
+                };
+                let job = WorkerJob::TextViewerLoad {
+                    source,
+                    offset: clamped,
+                    force_text: true,
+                    can_page: true,
+                };
+                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                    state.text_view_error = Some(e);
+                    state.replace_current(Screen::TextViewer);
                 }
-                None => {
-                    state.hex_editor.error = Some("save_as_path_missing".into());
+                #[cfg(test)]
+                {
+                    apply_worker_results(state);
                 }
+            } else {
+                state.text_view_error = Some("missing_path".into());
+                state.replace_current(Screen::TextViewer);
             }
-            maybe_toast_hex_saved(state);
-            None
         }
-        Action::HexEditorSaveAsPicker => {
-            state.replace_current(Screen::HexEditor);
-            if let Err(e) = hex_editor::export_to_temp(&mut state.hex_editor) {
-                state.hex_editor.error = Some(e);
+        Action::TextViewerFind { query, direction } => {
+            if let Some(q) = query {
+                let trimmed = q.trim();
+                if trimmed.is_empty() {
+                    state.text_view_find_query = None;
+                    state.text_view_find_match = Some("Cleared search".into());
+                } else {
+                    state.text_view_find_query = Some(trimmed.to_string());
+                    state.text_view_find_match = None;
+                }
             }
-            maybe_toast_hex_saved(state);
-            None
+            if let Some(dir) = direction {
+                state.text_view_find_match = Some(
+                    match dir.as_str() {
+                        "next" => "Searching next match",
+                        "prev" => "Searching previous match",
+                        _ => "Searching",
+                    }
+                    .into(),
+                );
+            }
+            state.replace_current(Screen::TextViewer);
         }
-        _ => None
+        _ => {}
     }
 }
 
-fn handle_sensor_actions(state: &mut AppState, action: Action) {
+fn handle_plotting_actions(state: &mut AppState, action: Action) {
     match action {
-        Action::SensorLoggerScreen => {
-            state.push_screen(Screen::SensorLogger);
+        Action::PlottingScreen => {
+            state.plotting = crate::state::PlottingState::new();
+            state.push_screen(Screen::Plotting);
         }
-        Action::SensorLoggerStart { bindings } => {
-            match parse_sensor_bindings(&bindings) {
-                Ok(cfg) => {
-                    state.last_error = None;
-                    state.sensor_status = Some("logging".into());
-                    state.sensor_interval_ms = Some(cfg.interval_ms);
-                    state.sensor_selection = Some(cfg.selection);
-                }
-                Err(e) => {
-                    state.last_error = Some(e);
-                }
-            }
-            if matches!(state.current_screen(), Screen::SensorLogger) {
-                state.replace_current(Screen::SensorLogger);
+        Action::PlottingPick { fd, path, error } => {
+            state.push_screen(Screen::Plotting);
+            state.plotting.generated_svg = None;
+            if let Some(err) = error {
+                state.plotting.error = Some(err);
+                return;
             }
-        }
-        Action::SensorLoggerStop => {
-            state.last_error = None;
-            state.sensor_status = Some("stopped".into());
-            if matches!(state.current_screen(), Screen::SensorLogger) {
-                state.replace_current(Screen::SensorLogger);
+            let target_path = if let Some(fd) = fd {
+                plotting::copy_csv_from_fd(fd, "plot.csv")
+            } else if let Some(p) = path.clone() {
+                Ok(p)
+            } else {
+                Err("missing_source".into())
+            };
+            match target_path {
+                Ok(p) => {
+                    state.plotting.file_path = Some(p.clone());
+                    state.plotting.display_path = Some(path.unwrap_or(p.clone()));
+                    match plotting::load_headers(&p) {
+                        Ok(headers) => {
+                            state.plotting.headers = headers;
+                            state.plotting.error = None;
+                        }
+                        Err(e) => state.plotting.error = Some(e),
+                    }
+                }
+                Err(e) => state.plotting.error = Some(e),
             }
         }
-        Action::SensorLoggerShare => {
-            if matches!(state.current_screen(), Screen::SensorLogger) {
-                state.replace_current(Screen::SensorLogger);
-            }
+        Action::PlottingSetX { col } => {
+            state.replace_current(Screen::Plotting);
+            let value = col.and_then(|c| {
+                let t = c.trim();
+                if t.is_empty() {
+                    None
+                } else {
+                    Some(t.to_string())
+                }
+            });
+            state.plotting.x_col = value;
         }
-        Action::SensorLoggerStatus { bindings } => {
-            apply_status_from_bindings(state, &bindings);
-            if matches!(state.current_screen(), Screen::SensorLogger) {
-                state.replace_current(Screen::SensorLogger);
-            }
+        Action::PlottingSetY { col } => {
+            state.replace_current(Screen::Plotting);
+            let value = col.and_then(|c| {
+                let t = c.trim();
+                if t.is_empty() {
+                    None
+                } else {
+                    Some(t.to_string())
+                }
+            });
+            state.plotting.y_col = value;
         }
-        Action::CompassDemo => {
-            state.push_screen(Screen::Compass);
+        Action::PlottingSetType { plot_type } => {
+            state.replace_current(Screen::Plotting);
+            state.plotting.plot_type = plot_type;
         }
-        Action::CompassSet {
-            angle_radians,
-            error,
-        } => {
-            if let Some(err) = error {
-                state.compass_error = Some(err);
-            } else if let Some(filtered) =
-                low_pass_angle(state.compass_filter_angle, angle_radians, COMPASS_SMOOTH_ALPHA)
-            {
-                state.compass_filter_angle = Some(filtered);
-                state.compass_angle_radians = filtered;
-                state.compass_error = None;
-            } else {
-                state.compass_error = Some("invalid_angle".into());
-            }
-            if matches!(state.current_screen(), Screen::Compass) {
-                state.replace_current(Screen::Compass);
+        Action::PlottingGenerate => {
+            state.replace_current(Screen::Plotting);
+            match plotting::generate_plot(&mut state.plotting) {
+                Ok(_) => {}
+                Err(e) => state.plotting.error = Some(e),
             }
         }
-        Action::BarometerScreen => {
-            state.push_screen(Screen::Barometer);
+        _ => {}
+    }
+}
+
+fn handle_sql_actions(state: &mut AppState, action: Action) -> Option<Value> {
+    match action {
+        Action::SqlScreen => {
+            state.push_screen(Screen::SqlQuery);
+            Some(handle_sql_action(state, "sql_screen", &HashMap::new()))
         }
-        Action::BarometerSet { hpa, error } => {
-            if let Some(err) = error {
-                state.barometer_error = Some(err);
-            } else if let Some(filtered) =
-                low_pass_scalar(state.barometer_filter_value, hpa, BAROMETER_SMOOTH_ALPHA)
-            {
-                state.barometer_filter_value = Some(filtered);
-                state.barometer_hpa = Some(filtered);
-                state.barometer_error = None;
-            } else {
-                state.barometer_error = Some("invalid_pressure".into());
-            }
-            if matches!(state.current_screen(), Screen::Barometer) {
-                state.replace_current(Screen::Barometer);
+        Action::SqlImport { path, fd, table_name, is_json } => {
+            state.push_screen(Screen::SqlQuery);
+            let mut bindings = HashMap::new();
+            bindings.insert("path".to_string(), path.clone());
+            if let Some(fd_val) = fd {
+                bindings.insert("fd".to_string(), fd_val.to_string());
             }
+            bindings.insert("table_name".to_string(), table_name.clone());
+            
+            let action = if is_json { "sql_import_json" } else { "sql_import_csv" };
+            Some(handle_sql_action(state, action, &bindings))
         }
-        Action::MagnetometerScreen => {
-            state.push_screen(Screen::Magnetometer);
+        Action::SqlExecute { query } => {
+            state.push_screen(Screen::SqlQuery);
+            let mut bindings = HashMap::new();
+            bindings.insert("query".to_string(), query.clone());
+            Some(handle_sql_action(state, "sql_execute", &bindings))
         }
-        Action::MagnetometerSet {
-            magnitude_ut,
-            error,
-        } => {
-            if let Some(err) = error {
-                state.magnetometer_error = Some(err);
-            } else if let Some(filtered) = low_pass_scalar(
-                state.magnetometer_filter_value,
-                magnitude_ut,
-                MAGNETOMETER_SMOOTH_ALPHA,
-            ) {
-                state.magnetometer_filter_value = Some(filtered);
-                state.magnetometer_ut = Some(filtered);
-                state.magnetometer_error = None;
-            } else {
-                state.magnetometer_error = Some("invalid_magnetometer".into());
-            }
-            if matches!(state.current_screen(), Screen::Magnetometer) {
-                state.replace_current(Screen::Magnetometer);
-            }
+        Action::SqlClearAll => {
+            state.push_screen(Screen::SqlQuery);
+            Some(handle_sql_action(state, "sql_clear_all", &HashMap::new()))
         }
-        _ => {}
+        _ => None,
     }
 }
 
-fn handle_media_actions(state: &mut AppState, action: Action) -> Option<Value> {
-    match action {
-        Action::PixelArtScreen => {
-            state.push_screen(Screen::PixelArt);
-            reset_pixel_art(&mut state.pixel_art);
-            None
+fn handle_hex_editor_actions(state: &mut AppState, action: Action) -> Option<Value> {
+    use crate::features::hex_editor;
+    fn maybe_toast_hex_saved(state: &mut AppState) {
+        let msg = state.hex_editor.status.clone().unwrap_or_default();
+        if let Some(path) = msg.strip_prefix("Result saved to:") {
+            state.push_toast(t!("toast_result_saved", path = path.trim()));
+            state.hex_editor.status = None;
         }
-        Action::PixelArtPick { path, fd, error } => {
-            state.push_screen(Screen::PixelArt);
-            state.pixel_art.error = error.clone();
-            state.pixel_art.result_path = None;
-            let mut fd_handle = FdHandle::new(fd);
-            if error.is_none() {
-                if let Some(raw_fd) = fd_handle.take() {
-                    match save_pixel_fd(raw_fd as RawFd, path.as_deref()) {
-                        Ok(saved) => {
-                            state.pixel_art.source_path = Some(saved);
-                            state.pixel_art.error = None;
-                        }
-                        Err(e) => state.pixel_art.error = Some(e),
-                    }
-                } else if let Some(p) = path {
-                    state.pixel_art.source_path = Some(p);
-                    state.pixel_art.error = None;
-                } else {
-                    state.pixel_art.error = Some("missing_source".into());
-                }
-            }
-            None
-        }
-        Action::PixelArtSetScale { scale } => {
-            state.pixel_art.scale_factor = scale.max(2);
-            if matches!(state.current_screen(), Screen::PixelArt) {
-                state.replace_current(Screen::PixelArt);
-            }
+    }
+    match action {
+        Action::HexEditorScreen => {
+            state.hex_editor.reset();
+            state.push_screen(Screen::HexEditor);
             None
         }
-        Action::PixelArtApply { loading_only } => {
-            if loading_only {
-                state.loading_with_spinner = false;
-                state.loading_message = Some("Pixelating...".into());
-                state.replace_current(Screen::Loading);
-                return Some(render_root(state));
+        Action::HexEditorOpen { fd, path, error } => {
+            state.push_screen(Screen::HexEditor);
+            state.hex_editor.status = None;
+            if let Some(err) = error {
+                state.hex_editor.error = Some(err);
+                state.hex_editor.current_dump = None;
+                return None;
             }
-            state.loading_message = Some("Pixelating...".into());
-            state.loading_with_spinner = true;
-            state.replace_current(Screen::PixelArt);
-            if let Some(path) = state.pixel_art.source_path.clone() {
-                let job = WorkerJob::PixelArt {
-                    source_path: path,
-                    scale: state.pixel_art.scale_factor,
-                };
-                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                    state.pixel_art.error = Some(e);
+            if let Some(raw) = fd {
+                match hex_editor::copy_fd_to_temp(raw as RawFd) {
+                    Ok(tmp) => {
+                        if let Err(e) =
+                            hex_editor::set_file(&mut state.hex_editor, tmp.clone(), path.clone())
+                        {
+                            state.hex_editor.error = Some(e);
+                        }
+                    }
+                    Err(e) => state.hex_editor.error = Some(e),
                 }
-                #[cfg(test)]
+            } else if let Some(p) = path {
+                if let Err(e) =
+                    hex_editor::set_file(&mut state.hex_editor, p.clone(), Some(p.clone()))
                 {
-                    apply_worker_results(state);
+                    state.hex_editor.error = Some(e);
                 }
             } else {
-                state.pixel_art.error = Some("no_image_selected".into());
+                state.hex_editor.error = Some("missing_source".into());
+                state.hex_editor.current_dump = None;
             }
             None
         }
-        Action::KotlinImagePick { path, fd, error } => {
-            // Ensure we stay on the image screen
-            if !matches!(state.current_screen(), Screen::KotlinImage) {
-                state.push_screen(Screen::KotlinImage);
-            }
-            state.image.result = None;
-            let mut fd_handle = FdHandle::new(fd);
-            if let Some(err) = error {
-                state.image.result = Some(features::kotlin_image::ImageConversionResult {
-                    path: None,
-                    size: None,
-                    format: None,
-                    error: Some(err),
-                });
-            } else {
-                if let Some(raw_fd) = fd_handle.take() {
-                    // Reuse save_fd_to_temp from dithering module as it is a generic helper
-                    match save_fd_to_temp(raw_fd as RawFd, path.as_deref()) {
-                        Ok(saved) => {
-                            state.image.source_path = Some(saved);
-                        }
-                        Err(e) => {
-                            state.image.result =
-                                Some(features::kotlin_image::ImageConversionResult {
-                                    path: None,
-                                    size: None,
-                                    format: None,
-                                    error: Some(e),
-                                });
-                        }
+        Action::HexEditorNav { direction, offset } => {
+            state.replace_current(Screen::HexEditor);
+            let mut target = state.hex_editor.offset;
+            match direction.as_str() {
+                "next" => {
+                    target = target.saturating_add(state.hex_editor.chunk_size as u64);
+                }
+                "prev" => {
+                    target = target.saturating_sub(state.hex_editor.chunk_size as u64);
+                }
+                "jump" => {
+                    if let Some(off) = offset {
+                        target = off;
                     }
-                } else if let Some(p) = path {
-                    state.image.source_path = Some(p);
-                } else {
-                    state.image.result = Some(features::kotlin_image::ImageConversionResult {
-                        path: None,
-                        size: None,
-                        format: None,
-                        error: Some("missing_source".into()),
-                    });
                 }
+                _ => {}
+            }
+            state.hex_editor.offset = target;
+            if let Err(e) = hex_editor::refresh_view(&mut state.hex_editor) {
+                state.hex_editor.error = Some(e);
             }
             None
         }
-        Action::KotlinImageScreen(target) => {
-            handle_kotlin_image_screen(state, target);
-            None
-        }
-        Action::KotlinImageResizeScreen => {
-            handle_kotlin_image_resize_screen(state);
-            None
-        }
-        Action::KotlinImageResizeSync { bindings } => {
-            handle_kotlin_image_resize_sync(state, &bindings);
-            None
-        }
-        Action::KotlinImageResult {
-            target,
-            result,
-            bindings,
-        } => {
-            handle_kotlin_image_result(state, target, result, Some(&bindings));
-            None
-        }
-        Action::KotlinImageOutputDir { target, output_dir } => {
-            handle_kotlin_image_output_dir(state, target, output_dir);
+        Action::HexEditorPatch { offset, byte } => {
+            state.replace_current(Screen::HexEditor);
+            let off = match offset {
+                Some(v) => v,
+                None => {
+                    state.hex_editor.error = Some("missing_offset".into());
+                    return None;
+                }
+            };
+            let value = match byte {
+                Some(v) => v,
+                None => {
+                    state.hex_editor.error = Some("missing_byte".into());
+                    return None;
+                }
+            };
+            if let Err(e) = hex_editor::patch_byte(&mut state.hex_editor, off, value) {
+                state.hex_editor.error = Some(e);
+            }
             None
         }
-        Action::DitheringScreen => {
-            state.push_screen(Screen::Dithering);
-            state.dithering_error = None;
-            state.dithering_result_path = None;
+        Action::HexEditorSave => {
+            state.replace_current(Screen::HexEditor);
+            if let Err(e) = hex_editor::save_changes(&mut state.hex_editor) {
+                state.hex_editor.error = Some(e);
+            }
+            maybe_toast_hex_saved(state);
             None
         }
-        Action::DitheringPickImage { path, fd, error } => {
-            state.push_screen(Screen::Dithering);
-            state.dithering_error = error.clone();
-            state.dithering_result_path = None;
-            let output_dir = path
-                .as_deref()
-                .map(|p| {
-                    features::storage::output_dir_for(Some(p))
-                        .to_string_lossy()
-                        .into_owned()
-                })
-                .unwrap_or_else(|| {
-                    features::storage::preferred_temp_dir()
-                        .to_string_lossy()
-                        .into_owned()
-                });
-            state.dithering_output_dir = Some(output_dir);
-            let mut fd_handle = FdHandle::new(fd);
-            if error.is_none() {
-                if let Some(raw_fd) = fd_handle.take() {
-                    match save_fd_to_temp(raw_fd as RawFd, path.as_deref()) {
-                        Ok(saved) => {
-                            state.dithering_source_path = Some(saved);
-                            state.dithering_error = None;
-                        }
-                        Err(e) => state.dithering_error = Some(e),
+        Action::HexEditorSaveAs { path } => {
+            state.replace_current(Screen::HexEditor);
+            let target = path.or_else(|| {
+                state
+                    .hex_editor
+                    .file_path
+                    .as_ref()
+                    .map(|p| format!("{p}.patched"))
+            });
+            match target {
+                Some(p) => {
+                    if let Err(e) = hex_editor::save_as(&mut state.hex_editor, &p) {
+                        state.hex_editor.error = Some(e);
                     }
-                } else if let Some(p) = path {
-                    state.dithering_source_path = Some(p);
-                    state.dithering_error = None;
-                } else {
-                    state.dithering_error = Some("missing_source".into());
+                }
+                None => {
+                    state.hex_editor.error = Some("save_as_path_missing".into());
                 }
             }
+            maybe_toast_hex_saved(state);
             None
         }
-        Action::DitheringSetMode { mode } => {
-            state.dithering_mode = mode;
-            if matches!(state.current_screen(), Screen::Dithering) {
-                state.replace_current(Screen::Dithering);
+        Action::HexEditorSaveAsPicker => {
+            state.replace_current(Screen::HexEditor);
+            if let Err(e) = hex_editor::export_to_temp(&mut state.hex_editor) {
+                state.hex_editor.error = Some(e);
             }
+            maybe_toast_hex_saved(state);
             None
         }
-        Action::DitheringSetPalette { palette } => {
-            state.dithering_palette = palette;
-            if matches!(state.current_screen(), Screen::Dithering) {
-                state.replace_current(Screen::Dithering);
-            }
-            None
+        _ => None
+    }
+}
+
+fn handle_sensor_actions(state: &mut AppState, action: Action) {
+    match action {
+        Action::SensorLoggerScreen => {
+            state.push_screen(Screen::SensorLogger);
         }
-        Action::DitheringApply { loading_only } => {
-            if loading_only {
-                state.loading_with_spinner = false;
-                state.loading_message = Some("Applying dithering...".into());
-                state.replace_current(Screen::Loading);
-                return Some(render_root(state));
+        Action::SensorLoggerStart { bindings } => {
+            match parse_sensor_bindings(&bindings) {
+                Ok(cfg) => {
+                    state.last_error = None;
+                    state.sensor_status = Some("logging".into());
+                    state.sensor_interval_ms = Some(cfg.interval_ms);
+                    state.sensor_selection = Some(cfg.selection);
+                    state.sensor_limits = Some(cfg.limits);
+                    state.sensor_backend = cfg.backend;
+                    state.sensor_history.clear();
+                    state.sensor_segments.clear();
+                    state.sensor_summary = None;
+                    state.sensor_thresholds = Some(cfg.thresholds);
+                    state.sensor_alerts.clear();
+                }
+                Err(e) => {
+                    state.last_error = Some(e);
+                }
             }
-            state.loading_message = Some("Applying dithering...".into());
-            state.loading_with_spinner = true;
-            state.replace_current(Screen::Dithering);
-            if let Some(path) = state.dithering_source_path.clone() {
-                let output_dir = state.dithering_output_dir.clone();
-                let job = WorkerJob::Dithering {
-                    source_path: path,
-                    mode: state.dithering_mode,
-                    palette: state.dithering_palette,
-                    output_dir,
-                };
+            if matches!(state.current_screen(), Screen::SensorLogger) {
+                state.replace_current(Screen::SensorLogger);
+            }
+        }
+        Action::SensorLoggerStop { bindings } => {
+            state.last_error = None;
+            apply_status_from_bindings(state, &bindings);
+            state.sensor_status = Some("stopped".into());
+            state.sensor_summary = None;
+            // Completed segments first, then the segment that was open at stop.
+            let mut paths = state.sensor_segments.clone();
+            if let Some(last) = &state.last_sensor_log {
+                if !paths.contains(last) {
+                    paths.push(last.clone());
+                }
+            }
+            if !paths.is_empty() {
+                state.loading_with_spinner = true;
+                state.loading_message = Some(t!("loading_summarizing_log").to_string());
+                let job = WorkerJob::SensorSummary { paths };
                 if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
-                    state.dithering_error = Some(e);
+                    state.last_error = Some(e);
                 }
                 #[cfg(test)]
                 {
                     apply_worker_results(state);
                 }
+            }
+            if matches!(state.current_screen(), Screen::SensorLogger) {
+                state.replace_current(Screen::SensorLogger);
+            }
+        }
+        Action::SensorLoggerShare => {
+            if matches!(state.current_screen(), Screen::SensorLogger) {
+                state.replace_current(Screen::SensorLogger);
+            }
+        }
+        Action::SensorLoggerStatus { bindings } => {
+            apply_status_from_bindings(state, &bindings);
+            if matches!(state.current_screen(), Screen::SensorLogger) {
+                state.replace_current(Screen::SensorLogger);
+            }
+        }
+        Action::SensorLoggerPause => {
+            if state.sensor_status.as_deref() == Some("logging") {
+                state.sensor_status = Some("paused".into());
+                state.last_error = None;
             } else {
-                state.dithering_error = Some("no_image_selected".into());
+                state.last_error = Some("sensor_not_logging".into());
+            }
+            if matches!(state.current_screen(), Screen::SensorLogger) {
+                state.replace_current(Screen::SensorLogger);
             }
-            None
         }
-        _ => None,
+        Action::SensorLoggerResume => {
+            if state.sensor_status.as_deref() == Some("paused") {
+                state.sensor_status = Some("logging".into());
+                state.last_error = None;
+            } else {
+                state.last_error = Some("sensor_not_paused".into());
+            }
+            if matches!(state.current_screen(), Screen::SensorLogger) {
+                state.replace_current(Screen::SensorLogger);
+            }
+        }
+        Action::SensorLoggerSegment { path } => {
+            match path {
+                None => state.last_error = Some("missing_path".into()),
+                Some(path) => {
+                    state.sensor_segments.push(path.clone());
+                    if state.sensor_limits.map(|l| l.gzip_segments).unwrap_or(false) {
+                        let job = WorkerJob::SensorSegmentGzip { path };
+                        if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                            state.last_error = Some(e);
+                        }
+                        #[cfg(test)]
+                        {
+                            apply_worker_results(state);
+                        }
+                    }
+                }
+            }
+            if matches!(state.current_screen(), Screen::SensorLogger) {
+                state.replace_current(Screen::SensorLogger);
+            }
+        }
+        Action::SensorLoggerFormat { format } => {
+            // Applies to the next recording; the running one keeps its file.
+            match SensorBackend::parse(format.as_deref().unwrap_or_default()) {
+                Ok(backend) => {
+                    state.sensor_backend = backend;
+                    state.last_error = None;
+                }
+                Err(e) => state.last_error = Some(e),
+            }
+            if matches!(state.current_screen(), Screen::SensorLogger) {
+                state.replace_current(Screen::SensorLogger);
+            }
+        }
+        Action::SensorLoggerExport { format } => {
+            state.last_error = None;
+            let format = GpsExportFormat::parse(format.as_deref().unwrap_or("gpx"));
+            match (format, state.last_sensor_log.clone()) {
+                (Err(e), _) => state.last_error = Some(e),
+                (Ok(_), None) => state.last_error = Some("gps_export_no_log".into()),
+                (Ok(format), Some(csv_path)) => {
+                    state.loading_with_spinner = true;
+                    state.loading_message = Some(t!("loading_exporting_track").to_string());
+                    let job = WorkerJob::GpsExport { csv_path, format };
+                    if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                        state.last_error = Some(e);
+                    }
+                    #[cfg(test)]
+                    {
+                        apply_worker_results(state);
+                    }
+                }
+            }
+            if matches!(state.current_screen(), Screen::SensorLogger) {
+                state.replace_current(Screen::SensorLogger);
+            }
+        }
+        Action::CompassDemo => {
+            state.push_screen(Screen::Compass);
+        }
+        Action::CompassSet {
+            angle_radians,
+            error,
+        } => {
+            if let Some(err) = error {
+                state.compass_error = Some(err);
+            } else if let Some(filtered) = low_pass_angle(
+                state.compass_filter_angle,
+                state.sensor_calibration.heading(angle_radians),
+                COMPASS_SMOOTH_ALPHA,
+            ) {
+                state.compass_filter_angle = Some(filtered);
+                state.compass_angle_radians = filtered;
+                state.compass_error = None;
+            } else {
+                state.compass_error = Some("invalid_angle".into());
+            }
+            if matches!(state.current_screen(), Screen::Compass) {
+                state.replace_current(Screen::Compass);
+            }
+        }
+        Action::SensorCalibrationSet { bindings } => {
+            let screen = state.current_screen();
+            let result =
+                parse_calibration(state.sensor_calibration, &bindings, state.barometer_raw_hpa);
+            let error_slot = match screen {
+                Screen::Barometer => &mut state.barometer_error,
+                Screen::Magnetometer => &mut state.magnetometer_error,
+                _ => &mut state.compass_error,
+            };
+            match result {
+                Ok(calibration) => {
+                    *error_slot = None;
+                    state.sensor_calibration = calibration;
+                    // Restart smoothing so the new offsets show up immediately.
+                    state.compass_filter_angle = None;
+                    state.barometer_filter_value = None;
+                    state.magnetometer_filter_value = None;
+                    if let Some(raw) = state.barometer_raw_hpa {
+                        state.barometer_hpa = Some(calibration.pressure(raw));
+                    }
+                }
+                Err(e) => *error_slot = Some(e),
+            }
+            if matches!(screen, Screen::Compass | Screen::Barometer | Screen::Magnetometer) {
+                state.replace_current(screen);
+            }
+        }
+        Action::BarometerScreen => {
+            state.push_screen(Screen::Barometer);
+        }
+        Action::BarometerSet { hpa, error } => {
+            if let Some(err) = error {
+                state.barometer_error = Some(err);
+            } else if let Some(filtered) = low_pass_scalar(
+                state.barometer_filter_value,
+                state.sensor_calibration.pressure(hpa),
+                BAROMETER_SMOOTH_ALPHA,
+            ) {
+                state.barometer_raw_hpa = Some(hpa);
+                state.barometer_filter_value = Some(filtered);
+                state.barometer_hpa = Some(filtered);
+                state.barometer_error = None;
+            } else {
+                state.barometer_error = Some("invalid_pressure".into());
+            }
+            if matches!(state.current_screen(), Screen::Barometer) {
+                state.replace_current(Screen::Barometer);
+            }
+        }
+        Action::MagnetometerScreen => {
+            state.push_screen(Screen::Magnetometer);
+        }
+        Action::MagnetometerSet {
+            magnitude_ut,
+            error,
+        } => {
+            if let Some(err) = error {
+                state.magnetometer_error = Some(err);
+            } else if let Some(filtered) = low_pass_scalar(
+                state.magnetometer_filter_value,
+                state.sensor_calibration.magnetic(magnitude_ut),
+                MAGNETOMETER_SMOOTH_ALPHA,
+            ) {
+                state.magnetometer_filter_value = Some(filtered);
+                state.magnetometer_ut = Some(filtered);
+                state.magnetometer_error = None;
+            } else {
+                state.magnetometer_error = Some("invalid_magnetometer".into());
+            }
+            if matches!(state.current_screen(), Screen::Magnetometer) {
+                state.replace_current(Screen::Magnetometer);
+            }
+        }
+        _ => {}
     }
 }
 
-fn read_text_from_fd(fd: RawFd) -> Result<String, String> {
-    if fd < 0 {
-        return Err("invalid_fd".into());
+fn start_image_resize(state: &mut AppState, bindings: &HashMap<String, String>, sources: Vec<String>) {
+    if !matches!(state.current_screen(), Screen::KotlinImage) {
+        state.push_screen(Screen::KotlinImage);
+    }
+    state.image.result = None;
+    let options = match resize_options(state, bindings) {
+        Ok(options) => options,
+        Err(e) => {
+            state.image.result = Some(ImageConversionResult::from_error(e));
+            return;
+        }
+    };
+    if sources.is_empty() {
+        state.image.result = Some(ImageConversionResult::from_error("no_image_selected".into()));
+        return;
+    }
+    let output_dir = state.image.local_output_dir.clone().unwrap_or_else(|| {
+        features::storage::preferred_temp_dir()
+            .to_string_lossy()
+            .into_owned()
+    });
+    state.loading_message = Some(t!("image_resizing_message").to_string());
+    state.loading_with_spinner = true;
+    let job = WorkerJob::ImageResize {
+        sources,
+        options,
+        output_dir,
+    };
+    if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+        state.image.result = Some(ImageConversionResult::from_error(e));
+    }
+    #[cfg(test)]
+    {
+        apply_worker_results(state);
     }
-
-    let mut file = unsafe { File::from_raw_fd(fd) };
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)
-        .map_err(|e| format!("read_failed:{e}"))?;
-    Ok(contents)
-}
-
-fn error_ui(message: &str) -> Value {
-    json!({
-        "type": "Column",
-        "padding": 24,
-        "children": [
-            { "type": "Text", "text": "Error", "size": 18.0 },
-            { "type": "Text", "text": message }
-        ]
-    })
 }
 
-fn render_ui(state: &AppState) -> Value {
-    match state.current_screen() {
-        Screen::Home => render_menu(state, &feature_catalog()),
-        Screen::Ruler => render_ruler_screen(state),
-        Screen::ShaderDemo => render_shader_screen(state),
-        Screen::KotlinImage => render_kotlin_image_screen(state),
-        Screen::HashVerify => render_hash_verify_screen(state),
-        Screen::FileInfo => render_file_info_screen(state),
-        Screen::TextTools => render_text_tools_screen(state),
-        Screen::Loading => render_loading_screen(state),
-        Screen::ProgressDemo => render_progress_demo_screen(state),
-        Screen::Qr => render_qr_screen(state),
-        Screen::ColorTools => render_color_screen(state),
-        Screen::PdfTools => render_pdf_screen(state),
-        Screen::PdfPreview => render_pdf_preview_screen(state),
-        Screen::About => render_about_screen(state),
-        Screen::Settings => render_settings_screen(state),
-        Screen::SensorLogger => render_sensor_logger_screen(state),
-        Screen::TextViewer => render_text_viewer_screen(state),
-        Screen::Dithering => render_dithering_screen(state),
-        Screen::ArchiveTools => render_archive_screen(state),
-        Screen::Compression => render_compression_screen(state),
-        Screen::SystemInfo => features::system_info::render_system_info_screen(state),
-        Screen::Compass => render_compass_screen(state),
-        Screen::Barometer => render_barometer_screen(state),
-        Screen::Magnetometer => render_magnetometer_screen(state),
-        Screen::MultiHash => render_multi_hash_screen(state),
-        Screen::PixelArt => render_pixel_art_screen(state),
-        Screen::RegexTester => render_regex_tester_screen(state),
-        Screen::MathTool => render_math_tool_screen(state),
-        Screen::FunctionAnalysis => features::function_analysis::render_function_analysis_screen(state),
-        Screen::UnitConverter => render_unit_converter_screen(state),
-        Screen::UuidGenerator => render_uuid_screen(state),
-        Screen::PresetManager => render_preset_manager(state),
-        Screen::PresetSave => render_save_preset_dialog(state),
-        Screen::QrSlideshow => render_qr_slideshow_screen(state),
-        Screen::QrReceive => render_qr_receive_screen(state),
-        Screen::Vault => features::vault::render_vault_screen(state),
-        Screen::Logic => features::logic::render_logic_screen(state),
-        Screen::Jwt => features::jwt::render_jwt_screen(state),
-        Screen::HexEditor => features::hex_editor::render_hex_editor_screen(state),
-        Screen::Plotting => render_plotting_screen(state),
-        Screen::SqlQuery => render_sql_screen(state),
-        Screen::MirScripting => features::mir_scripting::render_mir_scripting_screen(state),
-        Screen::CScripting => features::c_scripting::render_c_scripting_screen(state),
-        Screen::Synthesizer => features::synthesizer::render_synthesizer_screen(state),
-        Screen::Scheduler => render_scheduler_screen(state),
+/// Renders the small preview off the UI thread; results for stale slider values are dropped.
+fn enqueue_image_adjust_preview(state: &mut AppState) {
+    let Some(base) = state.image_adjust.preview_base.clone() else {
+        return;
+    };
+    let job = WorkerJob::ImageAdjustPreview {
+        base,
+        adjustments: state.image_adjust.adjustments,
+    };
+    state.loading_message = Some(t!("image_adjust_preview_message").to_string());
+    state.loading_with_spinner = true;
+    if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+        state.image_adjust.error = Some(e);
+    }
+    #[cfg(test)]
+    {
+        apply_worker_results(state);
     }
 }
 
-fn render_ruler_screen(state: &AppState) -> Value {
-    use crate::ui::{maybe_push_back, Column as UiColumn, Ruler as UiRuler, Text as UiText};
-
-    let ruler_title = t!("screen_ruler_title");
-    let ruler_description = t!("screen_ruler_description");
-    
-    let mut children = vec![
-        serde_json::to_value(UiText::new(&ruler_title).size(20.0)).unwrap(),
-        serde_json::to_value(
-            UiText::new(&ruler_description).size(14.0),
-        )
-        .unwrap(),
-        serde_json::to_value(UiRuler::new().fill_height(true)).unwrap(),
-    ];
-
-    maybe_push_back(&mut children, state);
-    serde_json::to_value(UiColumn::new(children).padding(20).scrollable(false)).unwrap()
-}
-
-/// A feature entry for the home menu.
-pub struct Feature {
-    pub id: &'static str,
-    pub name: &'static str,
-    pub category: &'static str,
-    pub action: &'static str,
-    pub requires_file_picker: bool,
-    pub description: &'static str,
-}
-
-/// Render the home screen using a catalog of features.
-pub fn render_menu(state: &AppState, catalog: &[Feature]) -> Value {
-    use crate::ui::{
-        Button as UiButton, Card as UiCard, Column as UiColumn, Section as UiSection,
-        Text as UiText, TextInput as UiTextInput,
-    };
-
-    let home_title = t!("home_title");
-    let home_subtitle = t!("home_subtitle");
-    let home_quick_access = t!("home_quick_access");
-    let home_tools_suffix = t!("home_tools_suffix");
-    let search_hint = t!("search_hint");
-    let legacy_notice = t!("legacy_notice");
-    let theme_section = t!("theme_section");
-
-    let mut children = vec![
-        serde_json::to_value(UiText::new(&home_title).size(22.0)).unwrap(),
-        serde_json::to_value(UiText::new(&home_subtitle).size(14.0)).unwrap(),
-        serde_json::to_value(
-            UiTextInput::new("home_filter")
-                .text(&state.home_filter)
-                .hint(&search_hint)
-                .action_on_submit("home_filter")
-                .debounce_ms(120)
-                .single_line(true),
-        )
-        .unwrap(),
-        serde_json::to_value(
-            UiText::new(&legacy_notice)
-                .size(12.0),
-        )
-        .unwrap(),
-    ];
-
-    let filter = state.home_filter.trim().to_ascii_lowercase();
-    let filtered: Vec<&Feature> = if filter.is_empty() {
-        catalog.iter().collect()
-    } else {
-        catalog
-            .iter()
-            .filter(|f| {
-                f.name.to_ascii_lowercase().contains(&filter)
-                    || f.description.to_ascii_lowercase().contains(&filter)
-            })
-            .collect()
-    };
-
-    // Quick access row (static for now; prefer high-traffic tools).
-    let quick_ids = ["pdf_tools", "text_tools", "text_viewer", "hash_sha256"];
-    let quick_buttons: Vec<Value> = filtered
-        .iter()
-        .filter(|f| quick_ids.contains(&f.id))
-        .map(|f| {
-            serde_json::to_value(
-                UiButton::new(f.name, f.action)
-                    .id(f.id)
-                    .requires_file_picker(f.requires_file_picker),
-            )
-            .unwrap()
-        })
-        .collect();
-    if !quick_buttons.is_empty() {
-        let quick = UiCard::new(vec![
-            serde_json::to_value(UiColumn::new(quick_buttons)).unwrap()
-        ])
-        .title(&home_quick_access)
-        .padding(12);
-        children.push(serde_json::to_value(quick).unwrap());
-    }
-
-    let theme_mode = state
-        .theme_mode
-        .as_deref()
-        .unwrap_or("system")
-        .to_ascii_lowercase();
-    let mut theme_buttons: Vec<Value> = Vec::new();
-    let theme_system = t!("theme_system");
-    let theme_light = t!("theme_light");
-    let theme_dark = t!("theme_dark");
-    
-    for (label, value) in [(&theme_system, "system"), (&theme_light, "light"), (&theme_dark, "dark")] {
-        let mut button = json!({
-            "type": "Button",
-            "text": label,
-            "action": "set_theme",
-            "id": format!("theme_{value}"),
-            "payload": { "theme_mode": value }
-        });
-        if theme_mode == value {
-            if let Some(obj) = button.as_object_mut() {
-                obj.insert("content_description".into(), Value::String("selected_theme".into()));
+fn handle_media_actions(state: &mut AppState, action: Action) -> Option<Value> {
+    match action {
+        Action::PixelArtScreen => {
+            state.push_screen(Screen::PixelArt);
+            reset_pixel_art(&mut state.pixel_art);
+            None
+        }
+        Action::PixelArtPick { path, fd, error } => {
+            state.push_screen(Screen::PixelArt);
+            state.pixel_art.error = error.clone();
+            state.pixel_art.result_path = None;
+            let mut fd_handle = FdHandle::new(fd);
+            if error.is_none() {
+                if let Some(raw_fd) = fd_handle.take() {
+                    match save_pixel_fd(raw_fd as RawFd, path.as_deref()) {
+                        Ok(saved) => {
+                            state.pixel_art.source_path = Some(saved);
+                            state.pixel_art.error = None;
+                        }
+                        Err(e) => state.pixel_art.error = Some(e),
+                    }
+                } else if let Some(p) = path {
+                    state.pixel_art.source_path = Some(p);
+                    state.pixel_art.error = None;
+                } else {
+                    state.pixel_art.error = Some("missing_source".into());
+                }
             }
+            None
         }
-        theme_buttons.push(button);
-    }
-    let theme_card = UiCard::new(vec![serde_json::to_value(UiColumn::new(theme_buttons)).unwrap()])
-        .title(&theme_section)
-        .padding(12);
-    children.push(serde_json::to_value(theme_card).unwrap());
-
-    let mut grouped: BTreeMap<&str, Vec<&Feature>> = BTreeMap::new();
-    for feature in filtered.iter().copied() {
-        grouped.entry(feature.category).or_default().push(feature);
-    }
-
-    if !filter.is_empty() && grouped.is_empty() {
-    let no_matching_tools = t!("no_matching_tools");
-        children.push(
-            serde_json::to_value(UiText::new(&no_matching_tools).size(14.0)).unwrap(),
-        );
-    }
-
-    for (category, feats) in grouped {
-        let mut section_children: Vec<Value> = Vec::new();
-    let legacy_hash_warning = t!("legacy_hash_warning");
-        if category.contains("Hash") {
-            section_children.push(
-                serde_json::to_value(
-                    UiText::new(&legacy_hash_warning).size(12.0),
-                )
-                .unwrap(),
-            );
+        Action::PixelArtSetScale { scale } => {
+            state.pixel_art.scale_factor = scale.max(2);
+            if matches!(state.current_screen(), Screen::PixelArt) {
+                state.replace_current(Screen::PixelArt);
+            }
+            None
         }
-        let list: Vec<Value> = feats
-            .iter()
-            .map(|f| {
-                serde_json::to_value(
-                    UiButton::new(&format!("{} – {}", f.name, f.description), f.action)
-                        .id(f.id)
-                        .requires_file_picker(f.requires_file_picker),
-                )
-                .unwrap()
-            })
-            .collect();
-        section_children.push(
-            serde_json::to_value(UiColumn::new(list).padding(4).content_description(category))
-                .unwrap(),
-        );
-
-        let subtitle = format!("{} {}", feats.len(), home_tools_suffix);
-        let mut title = category;
-        let mut icon: Option<&str> = None;
-        if let Some(first) = category.split_whitespace().next() {
-            if first.chars().all(|c| !c.is_ascii_alphanumeric()) {
-                icon = Some(first);
-                let trimmed = category[first.len()..].trim_start();
-                if !trimmed.is_empty() {
-                    title = trimmed;
-                }
+        Action::PixelArtSetUpscaler { upscaler } => {
+            state.pixel_art.upscaler = upscaler;
+            if matches!(state.current_screen(), Screen::PixelArt) {
+                state.replace_current(Screen::PixelArt);
             }
+            None
         }
-        let mut section = UiSection::new(section_children)
-            .title(title)
-            .subtitle(&subtitle)
-            .padding(12);
-        if let Some(ic) = icon {
-            section = section.icon(ic);
+        Action::PixelArtSetCrt { enabled } => {
+            state.pixel_art.crt = enabled;
+            if matches!(state.current_screen(), Screen::PixelArt) {
+                state.replace_current(Screen::PixelArt);
+            }
+            None
         }
-        children.push(serde_json::to_value(section).unwrap());
-    }
-
-    if let Some(hash) = &state.last_hash {
-        children.push(
-            serde_json::to_value(
-                UiText::new(&format!(
-                    "{}: {}",
-                    state
-                        .last_hash_algo
-                        .clone()
-                        .unwrap_or_else(|| "Hash".into()),
-                    hash
-                ))
-                .size(14.0),
-            )
-            .unwrap(),
-        );
-        children.push(
-            serde_json::to_value(
-                UiButton::new("Copy last hash", "noop")
-                    .copy_text(hash)
-                    .id("copy_last_hash_home"),
-            )
-            .unwrap(),
-        );
-        children.push(
-            serde_json::to_value(
-                UiButton::new("Paste reference (clipboard)", "hash_paste_reference")
-                    .id("hash_paste_reference_btn"),
-            )
-            .unwrap(),
-        );
-        children.push(
-            serde_json::to_value(
-                UiButton::new("Show QR for last hash", "hash_qr_last").id("hash_qr_last_btn"),
-            )
-            .unwrap(),
-        );
-        if let Some(matches) = state.hash_match {
-            let status = if matches {
-                "Reference match ✅"
+        Action::PixelArtSetPaletteLock { palette } => {
+            state.pixel_art.palette_lock = palette;
+            if matches!(state.current_screen(), Screen::PixelArt) {
+                state.replace_current(Screen::PixelArt);
+            }
+            None
+        }
+        Action::PixelArtLockDitheringPalette => {
+            state.pixel_art.palette_lock = Some(state.dithering_palette.clone());
+            if matches!(state.current_screen(), Screen::PixelArt) {
+                state.replace_current(Screen::PixelArt);
+            }
+            None
+        }
+        Action::PixelArtApply { loading_only } => {
+            if loading_only {
+                state.loading_with_spinner = false;
+                state.loading_message = Some(t!("loading_pixelating").to_string());
+                state.replace_current(Screen::Loading);
+                return Some(render_root(state));
+            }
+            state.loading_message = Some(t!("loading_pixelating").to_string());
+            state.loading_with_spinner = true;
+            state.replace_current(Screen::PixelArt);
+            if let Some(path) = state.pixel_art.source_path.clone() {
+                let job = WorkerJob::PixelArt {
+                    source_path: path,
+                    options: PixelArtOptions::from_state(&state.pixel_art),
+                };
+                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                    state.pixel_art.error = Some(e);
+                }
+                #[cfg(test)]
+                {
+                    apply_worker_results(state);
+                }
             } else {
-                "Reference mismatch ❌"
-            };
-            children.push(
-                serde_json::to_value(
-                    UiText::new(status)
-                        .size(12.0)
-                        .content_description("hash_ref_status"),
-                )
-                .unwrap(),
-            );
+                state.pixel_art.error = Some("no_image_selected".into());
+            }
+            None
         }
+        Action::KotlinImagePick { path, fd, error } => {
+            // Ensure we stay on the image screen
+            if !matches!(state.current_screen(), Screen::KotlinImage) {
+                state.push_screen(Screen::KotlinImage);
+            }
+            state.image.result = None;
+            state.image.local_output_dir = Some(
+                features::storage::output_dir_for(path.as_deref())
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+            let mut fd_handle = FdHandle::new(fd);
+            if let Some(err) = error {
+                state.image.result = Some(features::kotlin_image::ImageConversionResult {
+                    path: None,
+                    size: None,
+                    format: None,
+                    details: None,
+                    error: Some(err),
+                });
+            } else {
+                if let Some(raw_fd) = fd_handle.take() {
+                    // Reuse save_fd_to_temp from dithering module as it is a generic helper
+                    match save_fd_to_temp(raw_fd as RawFd, path.as_deref()) {
+                        Ok(saved) => {
+                            state.image.source_path = Some(saved);
+                        }
+                        Err(e) => {
+                            state.image.result =
+                                Some(features::kotlin_image::ImageConversionResult {
+                                    path: None,
+                                    size: None,
+                                    format: None,
+                                    details: None,
+                                    error: Some(e),
+                                });
+                        }
+                    }
+                } else if let Some(p) = path {
+                    state.image.source_path = Some(p);
+                } else {
+                    state.image.result = Some(features::kotlin_image::ImageConversionResult {
+                        path: None,
+                        size: None,
+                        format: None,
+                        details: None,
+                        error: Some("missing_source".into()),
+                    });
+                }
+            }
+            None
+        }
+        Action::ImageResize { bindings } => {
+            let sources = state.image.source_path.clone().into_iter().collect();
+            start_image_resize(state, &bindings, sources);
+            None
+        }
+        Action::ImageResizeBatch { bindings } => {
+            let sources = state.image.batch_queue.clone();
+            start_image_resize(state, &bindings, sources);
+            None
+        }
+        Action::KotlinImageScreen(target) => {
+            handle_kotlin_image_screen(state, target);
+            None
+        }
+        Action::KotlinImageResizeScreen => {
+            handle_kotlin_image_resize_screen(state);
+            None
+        }
+        Action::KotlinImageResizeSync { bindings } => {
+            handle_kotlin_image_resize_sync(state, &bindings);
+            None
+        }
+        Action::KotlinImageResult {
+            target,
+            result,
+            bindings,
+        } => {
+            handle_kotlin_image_result(state, target, result, Some(&bindings));
+            None
+        }
+        Action::KotlinImageOutputDir { target, output_dir } => {
+            handle_kotlin_image_output_dir(state, target, output_dir);
+            None
+        }
+        Action::ImageAdjustScreen => {
+            state.push_screen(Screen::ImageAdjust);
+            state.image_adjust.reset();
+            None
+        }
+        Action::ImageAdjustPick { path, fd, error } => {
+            if !matches!(state.current_screen(), Screen::ImageAdjust) {
+                state.push_screen(Screen::ImageAdjust);
+            }
+            let adjust = &mut state.image_adjust;
+            adjust.reset();
+            adjust.output_dir = Some(
+                features::storage::output_dir_for(path.as_deref())
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+            let mut fd_handle = FdHandle::new(fd);
+            let source = match (error, fd_handle.take(), path) {
+                (Some(err), _, _) => Err(err),
+                (None, Some(raw_fd), path) => save_fd_to_temp(raw_fd as RawFd, path.as_deref()),
+                (None, None, Some(path)) => Ok(path),
+                (None, None, None) => Err("missing_source".into()),
+            };
+            if let Err(e) = source.and_then(|path| adjust.load_source(path)) {
+                adjust.error = Some(e);
+            }
+            None
+        }
+        Action::ImageAdjustUpdate { bindings } => {
+            state.image_adjust.adjustments.sync_bindings(&bindings);
+            enqueue_image_adjust_preview(state);
+            None
+        }
+        Action::ImageAdjustReset => {
+            state.image_adjust.adjustments = Adjustments::new();
+            enqueue_image_adjust_preview(state);
+            None
+        }
+        Action::ImageAdjustApply { bindings } => {
+            let adjust = &mut state.image_adjust;
+            adjust.adjustments.sync_bindings(&bindings);
+            let Some(source_path) = adjust.source_path.clone() else {
+                adjust.error = Some("no_image_selected".into());
+                return None;
+            };
+            adjust.error = None;
+            adjust.result_path = None;
+            let job = WorkerJob::ImageAdjust {
+                source_path,
+                adjustments: adjust.adjustments,
+                output_dir: adjust.output_dir.clone().unwrap_or_else(|| {
+                    features::storage::preferred_temp_dir()
+                        .to_string_lossy()
+                        .into_owned()
+                }),
+            };
+            state.loading_message = Some(t!("image_adjust_applying_message").to_string());
+            state.loading_with_spinner = true;
+            if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                state.image_adjust.error = Some(e);
+            }
+            #[cfg(test)]
+            {
+                apply_worker_results(state);
+            }
+            None
+        }
+        Action::DitheringScreen => {
+            state.push_screen(Screen::Dithering);
+            state.dithering_error = None;
+            state.dithering_result_path = None;
+            None
+        }
+        Action::DitheringPickImage { path, fd, error } => {
+            state.push_screen(Screen::Dithering);
+            state.dithering_error = error.clone();
+            state.dithering_result_path = None;
+            let output_dir = path
+                .as_deref()
+                .map(|p| {
+                    features::storage::output_dir_for(Some(p))
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .unwrap_or_else(|| {
+                    features::storage::preferred_temp_dir()
+                        .to_string_lossy()
+                        .into_owned()
+                });
+            state.dithering_output_dir = Some(output_dir);
+            let mut fd_handle = FdHandle::new(fd);
+            if error.is_none() {
+                if let Some(raw_fd) = fd_handle.take() {
+                    match save_fd_to_temp(raw_fd as RawFd, path.as_deref()) {
+                        Ok(saved) => {
+                            state.dithering_source_path = Some(saved);
+                            state.dithering_error = None;
+                        }
+                        Err(e) => state.dithering_error = Some(e),
+                    }
+                } else if let Some(p) = path {
+                    state.dithering_source_path = Some(p);
+                    state.dithering_error = None;
+                } else {
+                    state.dithering_error = Some("missing_source".into());
+                }
+            }
+            None
+        }
+        Action::DitheringSetMode { mode } => {
+            state.dithering_mode = mode;
+            if matches!(state.current_screen(), Screen::Dithering) {
+                state.replace_current(Screen::Dithering);
+            }
+            None
+        }
+        Action::DitheringSetPalette { palette } => {
+            state.dithering_palette = palette;
+            if matches!(state.current_screen(), Screen::Dithering) {
+                state.replace_current(Screen::Dithering);
+            }
+            None
+        }
+        Action::DitheringSetCustomPalette { input } => {
+            match parse_custom_palette(&input) {
+                Ok(palette) => {
+                    state.dithering_palette = palette;
+                    state.dithering_error = None;
+                }
+                Err(e) => state.dithering_error = Some(e),
+            }
+            state.dithering_custom_palette = input;
+            if matches!(state.current_screen(), Screen::Dithering) {
+                state.replace_current(Screen::Dithering);
+            }
+            None
+        }
+        Action::DitheringPaletteFromImage { path, fd, error } => {
+            let from_fd = fd.is_some();
+            let extracted = match error {
+                Some(e) => Err(e),
+                None => picked_image_path(fd, path).and_then(|source| {
+                    let palette = palette_from_image(&source);
+                    if from_fd {
+                        let _ = std::fs::remove_file(&source);
+                    }
+                    palette
+                }),
+            };
+            match extracted {
+                Ok(palette) => {
+                    if let DitheringPalette::Custom(colors) = &palette {
+                        state.dithering_custom_palette = palette_hex(colors);
+                    }
+                    state.dithering_palette = palette;
+                    state.dithering_error = None;
+                }
+                Err(e) => state.dithering_error = Some(e),
+            }
+            if matches!(state.current_screen(), Screen::Dithering) {
+                state.replace_current(Screen::Dithering);
+            }
+            None
+        }
+        Action::DitheringSetSerpentine { enabled } => {
+            state.dithering_serpentine = enabled;
+            if matches!(state.current_screen(), Screen::Dithering) {
+                state.replace_current(Screen::Dithering);
+            }
+            None
+        }
+        Action::DitheringApply { loading_only } => {
+            if loading_only {
+                state.loading_with_spinner = false;
+                state.loading_message = Some(t!("loading_applying_dithering").to_string());
+                state.replace_current(Screen::Loading);
+                return Some(render_root(state));
+            }
+            state.loading_message = Some(t!("loading_applying_dithering").to_string());
+            state.loading_with_spinner = true;
+            state.replace_current(Screen::Dithering);
+            if let Some(path) = state.dithering_source_path.clone() {
+                let output_dir = state.dithering_output_dir.clone();
+                let job = WorkerJob::Dithering {
+                    source_path: path,
+                    mode: state.dithering_mode,
+                    palette: state.dithering_palette.clone(),
+                    serpentine: state.dithering_serpentine,
+                    output_dir,
+                };
+                if let Err(e) = STATE.get_or_init(GlobalState::new).worker().enqueue(job) {
+                    state.dithering_error = Some(e);
+                }
+                #[cfg(test)]
+                {
+                    apply_worker_results(state);
+                }
+            } else {
+                state.dithering_error = Some("no_image_selected".into());
+            }
+            None
+        }
+        _ => None,
     }
+}
 
-    if let Some(status) = &state.sensor_status {
-        children.push(
-            serde_json::to_value(
-                UiText::new(&format!("Sensor logger: {}", status))
-                    .size(12.0)
-                    .content_description("sensor_logger_status_home"),
-            )
-            .unwrap(),
-        );
-    }
-    if let Some(path) = &state.last_sensor_log {
-        children.push(
-            serde_json::to_value(
-                UiText::new(&format!("Last log: {}", path))
-                    .size(12.0)
-                    .content_description("sensor_logger_path_home"),
-            )
-            .unwrap(),
-        );
-    }
-
-    if let Some(err) = &state.last_error {
-        children.push(
-            serde_json::to_value(
-                UiText::new(&format!("Error: {}", err))
-                    .size(14.0)
-                    .content_description("error_text"),
-            )
-            .unwrap(),
-        );
+fn read_text_from_fd(fd: RawFd) -> Result<String, String> {
+    if fd < 0 {
+        return Err("invalid_fd".into());
     }
 
-    serde_json::to_value(UiColumn::new(children).padding(32)).unwrap()
+    let mut file = unsafe { File::from_raw_fd(fd) };
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)
+        .map_err(|e| format!("read_failed:{e}"))?;
+    Ok(contents)
 }
 
-fn feature_catalog() -> Vec<Feature> {
-    vec![
-        Feature {
-            id: "hash_sha256",
-            name: "🔒 SHA-256",
-            category: "🔐 Hashes",
-            action: "hash_file_sha256",
-            requires_file_picker: true,
-            description: "secure hash",
-        },
-        Feature {
-            id: "hash_verify",
-            name: "✅ Verify hash",
-            category: "🔐 Hashes",
-            action: "hash_verify_screen",
-            requires_file_picker: false,
-            description: "compare to reference",
-        },
-        Feature {
+fn error_ui(message: &str) -> Value {
+    json!({
+        "type": "Column",
+        "padding": 24,
+        "children": [
+            { "type": "Text", "text": t!("error_title"), "size": 18.0 },
+            { "type": "Text", "text": message }
+        ]
+    })
+}
+
+fn render_ui(state: &AppState) -> Value {
+    let ui = render_screen(state);
+    match state.current_screen() {
+        Screen::Home | Screen::Loading => ui,
+        _ => attach_recent_files(ui, state),
+    }
+}
+
+fn render_screen(state: &AppState) -> Value {
+    match state.current_screen() {
+        Screen::Home => render_menu(state, &feature_catalog()),
+        Screen::Ruler => render_ruler_screen(state),
+        Screen::ShaderDemo => render_shader_screen(state),
+        Screen::KotlinImage => render_kotlin_image_screen(state),
+        Screen::HashVerify => render_hash_verify_screen(state),
+        Screen::FileInfo => render_file_info_screen(state),
+        Screen::TextTools => render_text_tools_screen(state),
+        Screen::Loading => render_loading_screen(state),
+        Screen::ProgressDemo => render_progress_demo_screen(state),
+        Screen::Qr => render_qr_screen(state),
+        Screen::ColorTools => render_color_screen(state),
+        Screen::PdfTools => render_pdf_screen(state),
+        Screen::PdfPreview => render_pdf_preview_screen(state),
+        Screen::About => render_about_screen(state),
+        Screen::Settings => render_settings_screen(state),
+        Screen::StorageCleanup => render_cleanup_screen(state),
+        Screen::ImageEditor => render_image_edit_screen(state),
+        Screen::ImageAdjust => render_image_adjust_screen(state),
+        Screen::AsciiArt => render_ascii_art_screen(state),
+        Screen::GifFrames => render_gif_frames_screen(state),
+        Screen::ExifEditor => render_exif_editor_screen(state),
+        Screen::MetadataStrip => render_metadata_strip_screen(state),
+        Screen::ImageHistogram => render_image_histogram_screen(state),
+        Screen::Watermark => render_watermark_screen(state),
+        Screen::Collage => render_collage_screen(state),
+        Screen::SensorLogger => render_sensor_logger_screen(state),
+        Screen::TextViewer => render_text_viewer_screen(state),
+        Screen::Dithering => render_dithering_screen(state),
+        Screen::ArchiveTools => render_archive_screen(state),
+        Screen::Compression => render_compression_screen(state),
+        Screen::SystemInfo => features::system_info::render_system_info_screen(state),
+        Screen::Compass => render_compass_screen(state),
+        Screen::Barometer => render_barometer_screen(state),
+        Screen::Magnetometer => render_magnetometer_screen(state),
+        Screen::MultiHash => render_multi_hash_screen(state),
+        Screen::PixelArt => render_pixel_art_screen(state),
+        Screen::RegexTester => render_regex_tester_screen(state),
+        Screen::MathTool => render_math_tool_screen(state),
+        Screen::MathConstants => render_math_constants_screen(state),
+        Screen::FunctionAnalysis => features::function_analysis::render_function_analysis_screen(state),
+        Screen::UnitConverter => render_unit_converter_screen(state),
+        Screen::UuidGenerator => render_uuid_screen(state),
+        Screen::PresetManager => render_preset_manager(state),
+        Screen::PresetSave => render_save_preset_dialog(state),
+        Screen::QrSlideshow => render_qr_slideshow_screen(state),
+        Screen::QrReceive => render_qr_receive_screen(state),
+        Screen::Vault => features::vault::render_vault_screen(state),
+        Screen::Logic => features::logic::render_logic_screen(state),
+        Screen::Jwt => features::jwt::render_jwt_screen(state),
+        Screen::CertInspector => render_cert_inspector_screen(state),
+        Screen::PasswordStrength => render_password_strength_screen(state),
+        Screen::DateCalc => render_date_calc_screen(state),
+        Screen::HexEditor => features::hex_editor::render_hex_editor_screen(state),
+        Screen::Plotting => render_plotting_screen(state),
+        Screen::SqlQuery => render_sql_screen(state),
+        Screen::MirScripting => features::mir_scripting::render_mir_scripting_screen(state),
+        Screen::CScripting => features::c_scripting::render_c_scripting_screen(state),
+        Screen::JitBenchmark => render_jit_benchmark_screen(state),
+        Screen::Plugins => render_plugins_screen(state),
+        Screen::Plugin => render_plugin_screen(state),
+        Screen::Synthesizer => features::synthesizer::render_synthesizer_screen(state),
+        Screen::Scheduler => render_scheduler_screen(state),
+        Screen::JobQueue => render_job_queue_screen(
+            STATE.get_or_init(GlobalState::new).jobs_lock().entries(),
+            state.nav_depth(),
+        ),
+        Screen::UsageStats => {
+            let names: Vec<(&str, String)> = feature_catalog()
+                .iter()
+                .map(|f| (f.id, f.display_name()))
+                .collect();
+            render_usage_stats_screen(state, &names)
+        }
+        Screen::DebugLog => render_debug_log_screen(state, &logging::recent()),
+    }
+}
+
+fn collect_picker_actions(node: &Value, actions: &mut Vec<String>) {
+    if node.get("requires_file_picker").and_then(Value::as_bool) == Some(true) {
+        if let Some(action) = node.get("action").and_then(Value::as_str) {
+            actions.push(action.to_string());
+        }
+    }
+    if let Some(children) = node.get("children").and_then(Value::as_array) {
+        for child in children {
+            collect_picker_actions(child, actions);
+        }
+    }
+}
+
+fn recent_tool_label(tool: &str) -> String {
+    feature_catalog()
+        .iter()
+        .find(|f| f.action == tool || tool.starts_with(f.action.trim_end_matches("_screen")))
+        .map(Feature::display_name)
+        .unwrap_or_else(|| tool.replace('_', " "))
+}
+
+fn recent_file_button(entry: &RecentFile, with_tool: bool) -> Value {
+    use crate::ui::Button as UiButton;
+
+    let text = if with_tool {
+        format!("{} · {}", entry.name, recent_tool_label(&entry.tool))
+    } else {
+        entry.name.clone()
+    };
+    // Kotlin reopens the URI and replays the tool action as if it had just been picked.
+    serde_json::to_value(UiButton::new(&text, "recent_reopen").payload(json!({
+        "recent_uri": entry.uri,
+        "recent_tool": entry.tool,
+    })))
+    .unwrap()
+}
+
+/// Adds a "Reopen recent" section to tool screens whose file pickers were used before.
+fn attach_recent_files(mut ui: Value, state: &AppState) -> Value {
+    use crate::ui::Section as UiSection;
+
+    let mut actions = Vec::new();
+    collect_picker_actions(&ui, &mut actions);
+    let recent: Vec<Value> = state
+        .recent_files
+        .iter()
+        .filter(|r| actions.contains(&r.tool))
+        .take(5)
+        .map(|r| recent_file_button(r, false))
+        .collect();
+    if recent.is_empty() {
+        return ui;
+    }
+    let section = serde_json::to_value(
+        UiSection::new(recent)
+            .title(&t!("recent_reopen_title"))
+            .padding(8),
+    )
+    .unwrap();
+    if let Some(children) = ui.get_mut("children").and_then(Value::as_array_mut) {
+        let before_back = children
+            .last()
+            .and_then(|c| c.get("action"))
+            .and_then(Value::as_str)
+            == Some("back");
+        let at = if before_back {
+            children.len() - 1
+        } else {
+            children.len()
+        };
+        children.insert(at, section);
+    }
+    ui
+}
+
+fn render_ruler_screen(state: &AppState) -> Value {
+    use crate::ui::{maybe_push_back, Column as UiColumn, Ruler as UiRuler, Text as UiText};
+
+    let ruler_title = t!("screen_ruler_title");
+    let ruler_description = t!("screen_ruler_description");
+    
+    let mut children = vec![
+        serde_json::to_value(UiText::new(&ruler_title).size(20.0)).unwrap(),
+        serde_json::to_value(
+            UiText::new(&ruler_description).size(14.0),
+        )
+        .unwrap(),
+        serde_json::to_value(UiRuler::new().fill_height(true)).unwrap(),
+    ];
+
+    maybe_push_back(&mut children, state);
+    serde_json::to_value(UiColumn::new(children).padding(20).scrollable(false)).unwrap()
+}
+
+/// A feature entry for the home menu.
+pub struct Feature {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub category: &'static str,
+    pub action: &'static str,
+    pub requires_file_picker: bool,
+    pub description: &'static str,
+}
+
+impl Feature {
+    /// Localized `name`, from the `tool_{id}` key.
+    pub fn display_name(&self) -> String {
+        translate_or(&format!("tool_{}", self.id), self.name)
+    }
+
+    /// Localized `description`, from the `tool_{id}_description` key.
+    pub fn display_description(&self) -> String {
+        translate_or(&format!("tool_{}_description", self.id), self.description)
+    }
+}
+
+/// Localized title of a catalog category such as "📸 Media", without its icon: the
+/// `category_media` key, falling back to the English word.
+fn category_title(category: &str) -> String {
+    let title = category
+        .split_once(' ')
+        .filter(|(icon, _)| icon.chars().all(|c| !c.is_ascii_alphanumeric()))
+        .map_or(category, |(_, rest)| rest.trim_start());
+    translate_or(&format!("category_{}", title.to_lowercase()), title)
+}
+
+/// The catalog keeps English text next to the key, so tools added without translations
+/// still read well; rust-i18n answers a missing key with the key itself.
+fn translate_or(key: &str, fallback: &str) -> String {
+    let text = t!(key);
+    if text.ends_with(key) {
+        fallback.to_string()
+    } else {
+        text.into_owned()
+    }
+}
+
+/// Scores `query` as a case-insensitive subsequence of `text`, so "mhsh" finds
+/// "Multi-hash". Consecutive characters and word starts weigh more and a plain substring
+/// beats any scattered match; `None` when a query character is missing.
+fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let query = query.to_lowercase();
+    let text = text.to_lowercase();
+    if text.contains(&query) {
+        return Some(100 + query.chars().count() as u32);
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut prev: Option<usize> = None;
+    for qc in query.chars() {
+        let idx = pos + chars[pos..].iter().position(|&c| c == qc)?;
+        score += 1;
+        if idx > 0 && prev == Some(idx - 1) {
+            score += 3;
+        }
+        if idx == 0 || !chars[idx - 1].is_alphanumeric() {
+            score += 2;
+        }
+        prev = Some(idx);
+        pos = idx + 1;
+    }
+    Some(score)
+}
+
+/// Every whitespace-separated term of `query` must match the name or category (fuzzily)
+/// or the description (as a substring; long sentences would match almost any scattered
+/// letters). Name matches count double so titles rank above incidental hits.
+fn feature_match_score(feature: &Feature, query: &str) -> Option<u32> {
+    query.split_whitespace().try_fold(0, |total, term| {
+        let name = fuzzy_score(term, &feature.display_name()).map(|s| s * 2);
+        let category = fuzzy_score(term, &category_title(feature.category));
+        let description = feature
+            .display_description()
+            .to_lowercase()
+            .contains(term)
+            .then_some(50);
+        let best = name.max(category).max(description)?;
+        Some(total + best)
+    })
+}
+
+/// Render the home screen using a catalog of features.
+pub fn render_menu(state: &AppState, catalog: &[Feature]) -> Value {
+    use crate::ui::{
+        Button as UiButton, Card as UiCard, Column as UiColumn, Row as UiRow, Section as UiSection,
+        Text as UiText, TextInput as UiTextInput,
+    };
+
+    let home_title = t!("home_title");
+    let home_subtitle = t!("home_subtitle");
+    let home_quick_access = t!("home_quick_access");
+    let home_tools_suffix = t!("home_tools_suffix");
+    let search_hint = t!("search_hint");
+    let legacy_notice = t!("legacy_notice");
+
+    let mut children = vec![
+        serde_json::to_value(UiText::new(&home_title).size(22.0)).unwrap(),
+        serde_json::to_value(UiText::new(&home_subtitle).size(14.0)).unwrap(),
+        serde_json::to_value(
+            UiTextInput::new("home_filter")
+                .text(&state.home_filter)
+                .hint(&search_hint)
+                .action_on_submit("home_filter")
+                .debounce_ms(120)
+                .single_line(true),
+        )
+        .unwrap(),
+        serde_json::to_value(
+            UiText::new(&legacy_notice)
+                .size(12.0),
+        )
+        .unwrap(),
+    ];
+
+    let filter = state.home_filter.trim().to_lowercase();
+    let filtered: Vec<&Feature> = if filter.is_empty() {
+        catalog.iter().collect()
+    } else {
+        let mut scored: Vec<(u32, &Feature)> = catalog
+            .iter()
+            .filter_map(|f| feature_match_score(f, &filter).map(|score| (score, f)))
+            .collect();
+        // Stable sort keeps catalog order among equally good matches.
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.into_iter().map(|(_, f)| f).collect()
+    };
+
+    // Recently opened tools, hidden while searching or rearranging favorites.
+    if filter.is_empty() && !state.home_edit_favorites {
+        let recent_tools: Vec<Value> = state
+            .tool_usage
+            .iter()
+            .filter_map(|u| catalog.iter().find(|f| f.id == u.id))
+            .take(RECENT_TOOLS_MAX)
+            .map(|f| {
+                serde_json::to_value(
+                    UiButton::new(&f.display_name(), f.action)
+                        .id(&format!("recent_{}", f.id))
+                        .requires_file_picker(f.requires_file_picker),
+                )
+                .unwrap()
+            })
+            .collect();
+        if !recent_tools.is_empty() {
+            let title = t!("home_recent_tools");
+            let card = UiCard::new(vec![
+                serde_json::to_value(UiColumn::new(recent_tools)).unwrap()
+            ])
+            .title(&title)
+            .padding(12);
+            children.push(serde_json::to_value(card).unwrap());
+        }
+    }
+
+    // Quick access row: the user's pinned tools, in their order.
+    let editing = state.home_edit_favorites;
+    let favorites: Vec<&Feature> = state
+        .favorite_tools
+        .iter()
+        .filter_map(|id| filtered.iter().copied().find(|f| f.id == id))
+        .collect();
+    let mut quick_buttons: Vec<Value> = favorites
+        .iter()
+        .map(|f| {
+            if !editing {
+                return serde_json::to_value(
+                    UiButton::new(&f.display_name(), f.action)
+                        .id(f.id)
+                        .requires_file_picker(f.requires_file_picker),
+                )
+                .unwrap();
+            }
+            let payload = json!({ "favorite_id": f.id });
+            serde_json::to_value(UiRow::new(vec![
+                serde_json::to_value(UiText::new(&f.display_name()).size(14.0)).unwrap(),
+                serde_json::to_value(
+                    UiButton::new("↑", "favorite_move_up").payload(payload.clone()),
+                )
+                .unwrap(),
+                serde_json::to_value(
+                    UiButton::new("↓", "favorite_move_down").payload(payload.clone()),
+                )
+                .unwrap(),
+                serde_json::to_value(UiButton::new("✕", "favorite_toggle").payload(payload))
+                    .unwrap(),
+            ]))
+            .unwrap()
+        })
+        .collect();
+    if filter.is_empty() || !quick_buttons.is_empty() {
+        if editing {
+            quick_buttons.push(
+                serde_json::to_value(UiText::new(&t!("home_favorites_hint")).size(12.0)).unwrap(),
+            );
+        } else if favorites.is_empty() {
+            quick_buttons.push(
+                serde_json::to_value(UiText::new(&t!("home_favorites_empty")).size(12.0)).unwrap(),
+            );
+        }
+        let edit_label = if editing {
+            t!("home_favorites_done")
+        } else {
+            t!("home_favorites_edit")
+        };
+        quick_buttons.push(
+            serde_json::to_value(
+                UiButton::new(&edit_label, "home_edit_favorites").id("home_edit_favorites"),
+            )
+            .unwrap(),
+        );
+        let quick = UiCard::new(vec![
+            serde_json::to_value(UiColumn::new(quick_buttons)).unwrap()
+        ])
+        .title(&home_quick_access)
+        .padding(12);
+        children.push(serde_json::to_value(quick).unwrap());
+    }
+
+    if !state.recent_files.is_empty() {
+        let mut recent: Vec<Value> = state
+            .recent_files
+            .iter()
+            .take(5)
+            .map(|r| recent_file_button(r, true))
+            .collect();
+        recent.push(
+            serde_json::to_value(UiButton::new(
+                &t!("recent_clear_button"),
+                "recent_files_clear",
+            ))
+            .unwrap(),
+        );
+        let title = t!("home_recent_files");
+        let card = UiCard::new(vec![serde_json::to_value(UiColumn::new(recent)).unwrap()])
+            .title(&title)
+            .padding(12);
+        children.push(serde_json::to_value(card).unwrap());
+    }
+
+    children.push(render_theme_card(state.theme_mode));
+
+    let mut grouped: BTreeMap<&str, Vec<&Feature>> = BTreeMap::new();
+    for feature in filtered.iter().copied() {
+        grouped.entry(feature.category).or_default().push(feature);
+    }
+    if state.home_sort_by_usage && filter.is_empty() {
+        let launches = |f: &Feature| {
+            state
+                .tool_usage
+                .iter()
+                .find(|u| u.id == f.id)
+                .map_or(0, |u| u.launches)
+        };
+        for feats in grouped.values_mut() {
+            // Stable: tools never launched keep catalog order.
+            feats.sort_by_key(|f| std::cmp::Reverse(launches(f)));
+        }
+    }
+
+    // Installed plugins form their own section, matched on their name alone.
+    let plugin_matches: Vec<&PluginInfo> = state
+        .plugins
+        .installed
+        .iter()
+        .filter(|p| {
+            filter
+                .split_whitespace()
+                .all(|term| fuzzy_score(term, &p.name).is_some())
+        })
+        .collect();
+
+    if !filter.is_empty() && grouped.is_empty() && plugin_matches.is_empty() {
+    let no_matching_tools = t!("no_matching_tools");
+        children.push(
+            serde_json::to_value(UiText::new(&no_matching_tools).size(14.0)).unwrap(),
+        );
+    }
+
+    for (category, feats) in grouped {
+        let mut section_children: Vec<Value> = Vec::new();
+    let legacy_hash_warning = t!("legacy_hash_warning");
+        if category.contains("Hash") {
+            section_children.push(
+                serde_json::to_value(
+                    UiText::new(&legacy_hash_warning).size(12.0),
+                )
+                .unwrap(),
+            );
+        }
+        let list: Vec<Value> = feats
+            .iter()
+            .map(|f| {
+                let label = format!("{} – {}", f.display_name(), f.display_description());
+                if !editing {
+                    return serde_json::to_value(
+                        UiButton::new(&label, f.action)
+                            .id(f.id)
+                            .requires_file_picker(f.requires_file_picker),
+                    )
+                    .unwrap();
+                }
+                // While editing favorites, tapping a tool pins or unpins it instead.
+                let pinned = state.favorite_tools.iter().any(|id| id == f.id);
+                let marker = if pinned { "★" } else { "☆" };
+                serde_json::to_value(
+                    UiButton::new(&format!("{marker} {label}"), "favorite_toggle")
+                        .id(f.id)
+                        .payload(json!({ "favorite_id": f.id })),
+                )
+                .unwrap()
+            })
+            .collect();
+        section_children.push(
+            serde_json::to_value(UiColumn::new(list).padding(4).content_description(category))
+                .unwrap(),
+        );
+
+        let subtitle = format!("{} {}", feats.len(), home_tools_suffix);
+        let title = category_title(category);
+        let icon = category
+            .split_whitespace()
+            .next()
+            .filter(|first| first.chars().all(|c| !c.is_ascii_alphanumeric()));
+        let section_key = format!("home_category:{category}");
+        let mut section = UiSection::new(section_children)
+            .title(&title)
+            .subtitle(&subtitle)
+            .padding(12);
+        if let Some(ic) = icon {
+            section = section.icon(ic);
+        }
+        // While filtering, every match stays visible whatever the saved state.
+        if filter.is_empty() {
+            let expanded = state.is_section_expanded(&section_key, true);
+            section = section.collapsible(&section_key, expanded);
+        }
+        children.push(serde_json::to_value(section).unwrap());
+    }
+
+    if !plugin_matches.is_empty() {
+        let list: Vec<Value> = plugin_matches.iter().map(|p| plugin_button(p)).collect();
+        let subtitle = format!("{} {}", list.len(), home_tools_suffix);
+        let title = t!("category_plugins");
+        let column = serde_json::to_value(UiColumn::new(list).padding(4)).unwrap();
+        let mut section = UiSection::new(vec![column])
+            .title(&title)
+            .subtitle(&subtitle)
+            .icon("🧩")
+            .padding(12);
+        if filter.is_empty() {
+            let expanded = state.is_section_expanded("home_category:plugins", true);
+            section = section.collapsible("home_category:plugins", expanded);
+        }
+        children.push(serde_json::to_value(section).unwrap());
+    }
+
+    if let Some(hash) = &state.last_hash {
+        children.push(
+            serde_json::to_value(
+                UiText::new(&format!(
+                    "{}: {}",
+                    state
+                        .last_hash_algo
+                        .clone()
+                        .unwrap_or_else(|| "Hash".into()),
+                    hash
+                ))
+                .size(14.0),
+            )
+            .unwrap(),
+        );
+        children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("home_copy_last_hash"), "noop")
+                    .copy_text(hash)
+                    .id("copy_last_hash_home"),
+            )
+            .unwrap(),
+        );
+        children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("home_paste_reference"), "hash_paste_reference")
+                    .id("hash_paste_reference_btn"),
+            )
+            .unwrap(),
+        );
+        children.push(
+            serde_json::to_value(
+                UiButton::new(&t!("home_show_last_hash_qr"), "hash_qr_last").id("hash_qr_last_btn"),
+            )
+            .unwrap(),
+        );
+        if let Some(matches) = state.hash_match {
+            let status = if matches {
+                t!("home_reference_match")
+            } else {
+                t!("home_reference_mismatch")
+            };
+            children.push(
+                serde_json::to_value(
+                    UiText::new(&status)
+                        .size(12.0)
+                        .content_description("hash_ref_status"),
+                )
+                .unwrap(),
+            );
+        }
+    }
+
+    if let Some(status) = &state.sensor_status {
+        children.push(
+            serde_json::to_value(
+                UiText::new(&t!("home_sensor_status", status = status))
+                    .size(12.0)
+                    .content_description("sensor_logger_status_home"),
+            )
+            .unwrap(),
+        );
+    }
+    if let Some(path) = &state.last_sensor_log {
+        children.push(
+            serde_json::to_value(
+                UiText::new(&t!("home_last_sensor_log", path = path))
+                    .size(12.0)
+                    .content_description("sensor_logger_path_home"),
+            )
+            .unwrap(),
+        );
+    }
+
+    if let Some(err) = &state.last_error {
+        children.push(
+            serde_json::to_value(
+                UiText::new(&error_text(err))
+                    .size(14.0)
+                    .content_description("error_text"),
+            )
+            .unwrap(),
+        );
+    }
+
+    serde_json::to_value(UiColumn::new(children).padding(32)).unwrap()
+}
+
+fn feature_catalog() -> Vec<Feature> {
+    vec![
+        Feature {
+            id: "hash_sha256",
+            name: "🔒 SHA-256",
+            category: "🔐 Hashes",
+            action: "hash_file_sha256",
+            requires_file_picker: true,
+            description: "secure hash",
+        },
+        Feature {
+            id: "hash_verify",
+            name: "✅ Verify hash",
+            category: "🔐 Hashes",
+            action: "hash_verify_screen",
+            requires_file_picker: false,
+            description: "compare to reference",
+        },
+        Feature {
             id: "multi_hash",
             name: "Multi-hash",
             category: "🔐 Hashes",
             action: "multi_hash_screen",
             requires_file_picker: false,
-            description: "Compute MD5, SHA-1, SHA-256, BLAKE3",
+            description: "Compute MD5, SHA-1, SHA-2, SHA-3, BLAKE3, xxHash",
+        },
+        Feature {
+            id: "hash_sha512",
+            name: "🔒 SHA-512",
+            category: "🔐 Hashes",
+            action: "hash_file_sha512",
+            requires_file_picker: true,
+            description: "secure hash",
+        },
+        Feature {
+            id: "hash_sha3_256",
+            name: "🧊 SHA3-256",
+            category: "🔐 Hashes",
+            action: "hash_file_sha3_256",
+            requires_file_picker: true,
+            description: "Keccak-based hash",
+        },
+        Feature {
+            id: "hash_sha3_512",
+            name: "🧊 SHA3-512",
+            category: "🔐 Hashes",
+            action: "hash_file_sha3_512",
+            requires_file_picker: true,
+            description: "Keccak-based hash",
         },
         Feature {
             id: "hash_sha1",
@@ -5181,6 +9514,22 @@ fn feature_catalog() -> Vec<Feature> {
             requires_file_picker: false,
             description: "inspect JWT header/payload offline",
         },
+        Feature {
+            id: "cert_inspector",
+            name: "📜 Certificate inspector",
+            category: "🔐 Security",
+            action: "cert_inspector_screen",
+            requires_file_picker: false,
+            description: "X.509 subject, SANs, validity, fingerprints",
+        },
+        Feature {
+            id: "password_strength",
+            name: "🔑 Password strength",
+            category: "🔐 Security",
+            action: "password_strength_screen",
+            requires_file_picker: false,
+            description: "guess estimate, crack times, weak patterns",
+        },
         Feature {
             id: "pixel_art",
             name: "🟫 Pixel artifier",
@@ -5229,6 +9578,14 @@ fn feature_catalog() -> Vec<Feature> {
             requires_file_picker: false,
             description: "uuid v4 + configurable strings",
         },
+        Feature {
+            id: "date_calculator",
+            name: "📅 Date calculator",
+            category: "🧰 Utilities",
+            action: "date_calc_screen",
+            requires_file_picker: false,
+            description: "date math, time zones, cron preview",
+        },
         Feature {
             id: "hash_md4",
             name: "📜 MD4",
@@ -5317,6 +9674,14 @@ fn feature_catalog() -> Vec<Feature> {
             requires_file_picker: false,
             description: "single-file .gz compress/decompress",
         },
+        Feature {
+            id: "storage_cleanup",
+            name: "🧹 Storage cleanup",
+            category: "📁 Files",
+            action: "cleanup_screen",
+            requires_file_picker: false,
+            description: "delete temp and generated files",
+        },
         Feature {
             id: "system_info",
             name: "📊 System panels",
@@ -5343,7 +9708,7 @@ fn feature_catalog() -> Vec<Feature> {
         },
         Feature {
             id: "image_resize_kotlin",
-            name: "📉 Image resize (Kotlin)",
+            name: "📉 Image resize",
             category: "📸 Media",
             action: "kotlin_image_resize_screen",
             requires_file_picker: false,
@@ -5365,6 +9730,78 @@ fn feature_catalog() -> Vec<Feature> {
             requires_file_picker: false,
             description: "Kotlin conversion with Rust UI",
         },
+        Feature {
+            id: "image_edit",
+            name: "✂️ Crop & rotate",
+            category: "📸 Media",
+            action: "image_edit_screen",
+            requires_file_picker: false,
+            description: "crop, rotate, flip with preview",
+        },
+        Feature {
+            id: "image_adjust",
+            name: "🎚️ Adjust image",
+            category: "📸 Media",
+            action: "image_adjust_screen",
+            requires_file_picker: false,
+            description: "brightness, contrast, blur, sharpen, grayscale",
+        },
+        Feature {
+            id: "ascii_art",
+            name: "🔡 ASCII art",
+            category: "📸 Media",
+            action: "ascii_art_screen",
+            requires_file_picker: false,
+            description: "image to ASCII or ANSI colour text",
+        },
+        Feature {
+            id: "gif_frames",
+            name: "🎞️ GIF frames",
+            category: "📸 Media",
+            action: "gif_frames_screen",
+            requires_file_picker: false,
+            description: "explode an animated GIF, reassemble chosen frames",
+        },
+        Feature {
+            id: "metadata_strip",
+            name: "🕵️ Strip photo metadata",
+            category: "📸 Media",
+            action: "metadata_strip_screen",
+            requires_file_picker: false,
+            description: "remove EXIF/GPS & XMP before sharing",
+        },
+        Feature {
+            id: "image_histogram",
+            name: "📊 Histogram & exposure",
+            category: "📸 Media",
+            action: "image_histogram_screen",
+            requires_file_picker: false,
+            description: "RGB/luma histograms, brightness, clipping",
+        },
+        Feature {
+            id: "watermark",
+            name: "💧 Watermark",
+            category: "📸 Media",
+            action: "watermark_screen",
+            requires_file_picker: false,
+            description: "text or logo overlay on one or many pictures",
+        },
+        Feature {
+            id: "exif_editor",
+            name: "🏷️ Edit EXIF",
+            category: "📸 Media",
+            action: "exif_edit_screen",
+            requires_file_picker: false,
+            description: "date taken, artist, copyright, orientation",
+        },
+        Feature {
+            id: "collage",
+            name: "🧩 Collage",
+            category: "📸 Media",
+            action: "collage_screen",
+            requires_file_picker: false,
+            description: "grid or contact sheet from several pictures",
+        },
         Feature {
             id: "image_dithering",
             name: "🟪 Retro dithering",
@@ -5397,6 +9834,22 @@ fn feature_catalog() -> Vec<Feature> {
             requires_file_picker: true,
             description: "fast hash",
         },
+        Feature {
+            id: "hash_xxh64",
+            name: "💨 xxHash64",
+            category: "🔐 Hashes",
+            action: "hash_file_xxh64",
+            requires_file_picker: true,
+            description: "quick checksum, not secure",
+        },
+        Feature {
+            id: "hash_xxh3",
+            name: "💨 XXH3",
+            category: "🔐 Hashes",
+            action: "hash_file_xxh3",
+            requires_file_picker: true,
+            description: "quick checksum, not secure",
+        },
         Feature {
             id: "progress_demo",
             name: "⏳ Progress demo",
@@ -5481,9 +9934,33 @@ fn feature_catalog() -> Vec<Feature> {
             id: "mir_scripting",
             name: "🧩 MIR Lab",
             category: "🧰 Utilities",
-            action: "mir_scripting_screen",
+            action: "mir_scripting_screen",
+            requires_file_picker: false,
+            description: "MIR JIT playground",
+        },
+        Feature {
+            id: "job_queue",
+            name: "🧵 Background jobs",
+            category: "🧰 Utilities",
+            action: "job_queue_screen",
+            requires_file_picker: false,
+            description: "pending, running and finished work",
+        },
+        Feature {
+            id: "usage_stats",
+            name: "📊 Usage statistics",
+            category: "🧰 Utilities",
+            action: "usage_stats_screen",
+            requires_file_picker: false,
+            description: "opt-in launch counts and time per tool, on device only",
+        },
+        Feature {
+            id: "debug_log",
+            name: "🪵 Debug log",
+            category: "🧰 Utilities",
+            action: "debug_log_screen",
             requires_file_picker: false,
-            description: "MIR JIT playground",
+            description: "recent events of this session, exportable for bug reports",
         },
         Feature {
             id: "scheduler",
@@ -5501,6 +9978,22 @@ fn feature_catalog() -> Vec<Feature> {
             requires_file_picker: false,
             description: "Run C code (JIT)",
         },
+        Feature {
+            id: "jit_benchmark",
+            name: "⏱️ JIT benchmark",
+            category: "🧰 Utilities",
+            action: "jit_benchmark_screen",
+            requires_file_picker: false,
+            description: "interpreter vs JIT speed per core",
+        },
+        Feature {
+            id: "plugins",
+            name: "🧩 Plugins",
+            category: "🧰 Utilities",
+            action: "plugins_screen",
+            requires_file_picker: false,
+            description: "install and run C or MIR tools",
+        },
         Feature {
             id: "synthesizer",
             name: "🎹 Synthesizer",
@@ -5512,121 +10005,1693 @@ fn feature_catalog() -> Vec<Feature> {
     ]
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::features::sensor_logger::parse_bindings as parse_sensor_bindings;
-    use crate::ui::{Card as UiCard, Section as UiSection, Text as UiText};
-    use image::codecs::png::PngEncoder;
-    use image::{ColorType, ImageEncoder};
-    use serde_json::Value;
-    use std::collections::HashMap;
-    use std::fs::File;
-    use std::io::Write;
-    use std::os::unix::io::IntoRawFd;
-    use std::thread;
-    use std::sync::{atomic::Ordering, Mutex};
-    use std::time::{Duration, Instant};
-    use tempfile::NamedTempFile;
-    use lopdf::dictionary;
-    use zip::write::FileOptions;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::sensor_logger::parse_bindings as parse_sensor_bindings;
+    use crate::state::{StringCharset, SNAPSHOT_VERSION};
+    use crate::ui::{Card as UiCard, Section as UiSection, Text as UiText};
+    use image::codecs::png::PngEncoder;
+    use image::{ColorType, ImageEncoder};
+    use serde_json::Value;
+    use std::collections::HashMap;
+    use std::fs::File;
+    use std::io::Write;
+    use std::os::unix::io::IntoRawFd;
+    use std::thread;
+    use std::sync::{atomic::Ordering, Mutex};
+    use std::time::{Duration, Instant};
+    use tempfile::NamedTempFile;
+    use lopdf::dictionary;
+    use zip::write::FileOptions;
+
+    static TEST_MUTEX: Mutex<()> = Mutex::new(());
+
+    const SAMPLE_CONTENT: &str = "abc";
+    const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    const SHA1_ABC: &str = "a9993e364706816aba3e25717850c26c9cd0d89d";
+    const MD5_ABC: &str = "900150983cd24fb0d6963f7d28e17f72";
+    const SAMPLE_WRAP: &str = "rust keeps your memory safe by design and gives you fearless concurrency without data races";
+
+    fn make_command(action: &str) -> Command {
+        Command {
+            action: action.into(),
+            path: None,
+            path_list: None,
+            fd: None,
+            fd_list: None,
+            error: None,
+            target: None,
+            result_path: None,
+            result_size: None,
+            result_format: None,
+            output_dir: None,
+            bindings: None,
+            loading_only: None,
+            snapshot: None,
+            primary_fd: None,
+            primary_path: None,
+            angle_radians: None,
+            ui_revision: None,
+        }
+    }
+
+    /// Runs one command on a state outside the global lock and renders its UI.
+    fn apply_command(
+        state: &mut AppState,
+        command: Command,
+        lock_poisoned: bool,
+    ) -> Result<Value, String> {
+        let ui = run_command(state, command, lock_poisoned)?;
+        Ok(ui.unwrap_or_else(|| render_root(state)))
+    }
+
+    fn reset_state() {
+        handle_command(make_command("reset")).expect("reset command should succeed");
+        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
+        TEST_WORKER_DELAY_MS.store(0, Ordering::SeqCst);
+    }
+
+    fn extract_texts(ui: &Value) -> Vec<String> {
+        fn walk(node: &Value, acc: &mut Vec<String>) {
+            if let Some(text) = node.get("text").and_then(|t| t.as_str()) {
+                acc.push(text.to_string());
+            }
+            if let Some(children) = node.get("children").and_then(|c| c.as_array()) {
+                for child in children {
+                    walk(child, acc);
+                }
+            }
+        }
+
+        let mut out = Vec::new();
+        walk(ui, &mut out);
+        out
+    }
+
+    fn assert_contains_text(ui: &Value, needle: &str) {
+        let texts = extract_texts(ui);
+        assert!(
+            texts.iter().any(|t| t.contains(needle)),
+            "expected UI to contain text with `{needle}`, found: {texts:?}"
+        );
+    }
+
+    #[test]
+    fn section_and_card_serialize_with_headers() {
+        let body = vec![serde_json::to_value(UiText::new("Body")).unwrap()];
+        let section = UiSection::new(body.clone())
+            .title("📁 Files")
+            .subtitle("2 tools")
+            .icon("📁")
+            .padding(8);
+        let card = UiCard::new(body).title("⚡ Quick access").padding(6);
+
+        let section_val = serde_json::to_value(section).expect("section should serialize");
+        assert_eq!(
+            section_val.get("type"),
+            Some(&Value::String("Section".into()))
+        );
+        assert_eq!(
+            section_val.get("title"),
+            Some(&Value::String("📁 Files".into()))
+        );
+        assert_eq!(section_val.get("icon"), Some(&Value::String("📁".into())));
+        assert!(section_val
+            .get("children")
+            .and_then(|c| c.as_array())
+            .is_some());
+
+        let card_val = serde_json::to_value(card).expect("card should serialize");
+        assert_eq!(card_val.get("type"), Some(&Value::String("Card".into())));
+        assert!(card_val
+            .get("children")
+            .and_then(|c| c.as_array())
+            .is_some());
+    }
+
+    #[test]
+    fn hash_file_sha256_via_path_updates_ui_and_state() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let mut command = make_command("hash_file_sha256");
+        command.path = Some(file.path().to_string_lossy().into_owned());
+
+        let ui = handle_command(command).expect("hash command should succeed");
+
+        assert_contains_text(&ui, &format!("SHA-256: {SHA256_ABC}"));
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.last_hash.as_deref(), Some(SHA256_ABC));
+        assert_eq!(state.last_hash_algo.as_deref(), Some("SHA-256"));
+        assert!(state.last_error.is_none());
+    }
+
+    #[test]
+    fn hash_file_loading_then_result() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let mut loading_cmd = make_command("hash_file_sha256");
+        loading_cmd.loading_only = Some(true);
+        let loading_ui = handle_command(loading_cmd).expect("loading command should succeed");
+        assert_contains_text(&loading_ui, "Computing SHA-256");
+
+        let mut command = make_command("hash_file_sha256");
+        command.path = Some(file.path().to_string_lossy().into_owned());
+
+        let ui = handle_command(command).expect("hash command should succeed");
+
+        assert_contains_text(&ui, &format!("SHA-256: {SHA256_ABC}"));
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.last_hash.as_deref(), Some(SHA256_ABC));
+        assert_eq!(state.last_hash_algo.as_deref(), Some("SHA-256"));
+        assert!(state.last_error.is_none());
+        assert!(matches!(state.current_screen(), Screen::Home));
+    }
+
+    #[test]
+    fn hash_verify_enqueues_and_releases_mutex() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        TEST_FORCE_ASYNC_WORKER.store(true, Ordering::SeqCst);
+        TEST_WORKER_DELAY_MS.store(200, Ordering::SeqCst);
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let mut command = make_command("hash_verify");
+        command.path = Some(file.path().to_string_lossy().into_owned());
+        command.bindings = Some(HashMap::from([(
+            "hash_reference".into(),
+            SHA256_ABC.into(),
+        )]));
+
+        let start = Instant::now();
+        let ui = handle_command(command).expect("hash verify dispatch should succeed");
+        assert!(
+            start.elapsed() < Duration::from_millis(100),
+            "dispatch held the UI mutex for too long"
+        );
+        assert_contains_text(&ui, "Computing SHA-256");
+        assert!(
+            STATE.get_or_init(GlobalState::new).ui_try_lock().is_some(),
+            "state mutex should be free while worker runs"
+        );
+
+        std::thread::sleep(Duration::from_millis(250));
+        let refreshed =
+            handle_command(make_command("init")).expect("refresh after worker should succeed");
+        assert_contains_text(&refreshed, SHA256_ABC);
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.last_hash.as_deref(), Some(SHA256_ABC));
+        assert_eq!(state.hash_match, Some(true));
+
+        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
+        TEST_WORKER_DELAY_MS.store(0, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn concurrent_jni_call_proceeds_while_worker_runs() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        TEST_FORCE_ASYNC_WORKER.store(true, Ordering::SeqCst);
+        TEST_WORKER_DELAY_MS.store(300, Ordering::SeqCst);
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let mut hash_cmd = make_command("hash_file_sha256");
+        hash_cmd.path = Some(file.path().to_string_lossy().into_owned());
+        handle_command(hash_cmd).expect("hash dispatch should succeed");
+
+        let start = Instant::now();
+        let inc_ui = handle_command(make_command("increment"))
+            .expect("increment should not be blocked by worker");
+        assert!(
+            start.elapsed() < Duration::from_millis(100),
+            "increment waited too long for state mutex"
+        );
+        assert_contains_text(&inc_ui, "Tool menu");
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.counter, 1);
+        drop(state);
+
+        std::thread::sleep(Duration::from_millis(350));
+        handle_command(make_command("init")).expect("refresh should apply worker result");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.last_hash.as_deref(), Some(SHA256_ABC));
+        assert!(state.last_error.is_none());
+
+        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
+        TEST_WORKER_DELAY_MS.store(0, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn archive_open_enqueues_and_releases_mutex() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        TEST_FORCE_ASYNC_WORKER.store(true, Ordering::SeqCst);
+        TEST_WORKER_DELAY_MS.store(200, Ordering::SeqCst);
+
+        let mut zip_file = NamedTempFile::new().unwrap();
+        {
+            let mut writer = zip::ZipWriter::new(&mut zip_file);
+            let options =
+                FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+            writer.start_file("note.txt", options).unwrap();
+            writer.write_all(b"hello from zip").unwrap();
+            writer.finish().unwrap();
+        }
+
+        let fd = File::open(zip_file.path()).unwrap().into_raw_fd();
+        let mut open_cmd = make_command("archive_open");
+        open_cmd.fd = Some(fd);
+        open_cmd.path = Some(zip_file.path().to_string_lossy().into_owned());
+
+        let start = Instant::now();
+        let ui = handle_command(open_cmd).expect("archive open dispatch should succeed");
+        assert!(
+            start.elapsed() < Duration::from_millis(100),
+            "dispatch held the UI mutex for too long"
+        );
+        assert_contains_text(&ui, "Opening archive");
+        assert!(
+            STATE.get_or_init(GlobalState::new).ui_try_lock().is_some(),
+            "state mutex should be free while archive worker runs"
+        );
+
+        std::thread::sleep(Duration::from_millis(250));
+        handle_command(make_command("init")).expect("refresh after worker should succeed");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert!(state
+            .archive
+            .entries
+            .iter()
+            .any(|e| e.name == "note.txt"));
+        assert!(matches!(state.current_screen(), Screen::ArchiveTools));
+
+        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
+        TEST_WORKER_DELAY_MS.store(0, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn hash_file_sha1_via_fd_updates_ui_and_state() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let fd = File::open(file.path()).unwrap().into_raw_fd();
+
+        let mut command = make_command("hash_file_sha1");
+        command.fd = Some(fd);
+
+        let ui = handle_command(command).expect("hash command should succeed");
+
+        assert_contains_text(&ui, &format!("SHA-1: {SHA1_ABC}"));
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.last_hash.as_deref(), Some(SHA1_ABC));
+        assert_eq!(state.last_hash_algo.as_deref(), Some("SHA-1"));
+        assert!(state.last_error.is_none());
+    }
+
+    #[test]
+    fn hash_file_sha3_and_sha512_report_their_labels() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let sha3_256_abc = "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532";
+        let mut command = make_command("hash_file_sha3_256");
+        command.path = Some(file.path().to_string_lossy().into_owned());
+        let ui = handle_command(command).expect("hash command should succeed");
+        assert_contains_text(&ui, &format!("SHA3-256: {sha3_256_abc}"));
+
+        let mut command = make_command("hash_file_sha512");
+        command.path = Some(file.path().to_string_lossy().into_owned());
+        handle_command(command).expect("hash command should succeed");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.last_hash_algo.as_deref(), Some("SHA-512"));
+        assert_eq!(state.last_hash.as_ref().map(String::len), Some(128));
+        assert!(state.last_error.is_none());
+    }
+
+    #[test]
+    fn hash_file_xxhash_reports_fixed_width_digests() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        for (action, label, digest) in [
+            ("hash_file_xxh64", "xxHash64", "44bc2cf5ad770999"),
+            ("hash_file_xxh3", "XXH3-64", "78af5f94892f3950"),
+        ] {
+            let mut command = make_command(action);
+            command.path = Some(file.path().to_string_lossy().into_owned());
+            let ui = handle_command(command).expect("hash command should succeed");
+            assert_contains_text(&ui, &format!("{label}: {digest}"));
+        }
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.last_hash_algo.as_deref(), Some("XXH3-64"));
+        assert!(state.last_error.is_none());
+    }
+
+    #[test]
+    fn text_tools_uppercase_consumes_binding_and_updates_state() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut command = make_command("text_tools_upper");
+        command.bindings = Some(HashMap::from([("text_input".into(), "Hello rust".into())]));
+
+        let ui = handle_command(command).expect("text command should succeed");
+
+        assert_contains_text(&ui, "HELLO RUST");
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.text_input.as_deref(), Some("Hello rust"));
+        assert_eq!(state.text_output.as_deref(), Some("HELLO RUST"));
+        assert!(matches!(state.current_screen(), Screen::TextTools));
+    }
+
+    #[test]
+    fn text_tools_word_count_reports_count() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut command = make_command("text_tools_word_count");
+        command.bindings = Some(HashMap::from([(
+            "text_input".into(),
+            "one two  three".into(),
+        )]));
+
+        let ui = handle_command(command).expect("text command should succeed");
+
+        assert_contains_text(&ui, "Word count: 3");
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.text_output.as_deref(), Some("Word count: 3"));
+    }
+
+    #[test]
+    fn text_tools_wrap_splits_lines() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut command = make_command("text_tools_wrap");
+        command.bindings = Some(HashMap::from([("text_input".into(), SAMPLE_WRAP.into())]));
+
+        let ui = handle_command(command).expect("text command should succeed");
+        let texts = extract_texts(&ui);
+        // Wrapped text should introduce a line break (result block shows raw text)
+        let result = texts
+            .into_iter()
+            .find(|t| t == "Result")
+            .and_then(|_| {
+                // result text is next entry after label in traversal
+                STATE.get_or_init(GlobalState::new).ui_try_lock().and_then(|s| s.text_output.clone())
+            })
+            .unwrap_or_default();
+
+        assert!(
+            result.contains('\n'),
+            "expected wrapped text to contain newline, got {result:?}"
+        );
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert!(state
+            .text_output
+            .as_deref()
+            .unwrap_or_default()
+            .contains('\n'));
+    }
+
+    #[test]
+    fn text_tools_trim_respects_aggressive_flag() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut command = make_command("text_tools_trim");
+        command.bindings = Some(HashMap::from([
+            ("text_input".into(), "  a   b  ".into()),
+            ("aggressive_trim".into(), "true".into()),
+        ]));
+
+        let ui = handle_command(command).expect("trim command should succeed");
+        assert_contains_text(&ui, "Trim spacing (collapse)");
+        fn find_bound<'a>(node: &'a Value, key: &str) -> Option<&'a Value> {
+            if node.get("bind_key").and_then(Value::as_str) == Some(key) {
+                return Some(node);
+            }
+            node.get("children")
+                .and_then(Value::as_array)
+                .and_then(|children| children.iter().find_map(|c| find_bound(c, key)))
+        }
+        let toggle = find_bound(&ui, "aggressive_trim").expect("aggressive trim toggle");
+        assert_eq!(toggle["type"], "Switch");
+        assert_eq!(toggle["checked"], true);
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.text_output.as_deref(), Some("a b"));
+
+        drop(state);
+        reset_state();
+
+        let mut command2 = make_command("text_tools_trim");
+        command2.bindings = Some(HashMap::from([
+            ("text_input".into(), "  a   b  ".into()),
+            ("aggressive_trim".into(), "false".into()),
+        ]));
+
+        let ui2 = handle_command(command2).expect("trim command should succeed");
+        assert_contains_text(&ui2, "Trim edges");
+        let state2 = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state2.text_output.as_deref(), Some("a   b"));
+    }
+
+    #[test]
+    fn back_from_home_does_not_underflow_stack() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let ui = handle_command(make_command("back")).expect("back should succeed");
+        assert_contains_text(&ui, "Tool menu");
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.nav_depth(), 1);
+        assert!(matches!(state.current_screen(), Screen::Home));
+    }
+
+    #[test]
+    fn back_pops_to_previous_screen() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        handle_command(make_command("text_tools_screen")).expect("screen switch should work");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.nav_depth(), 2);
+            assert!(matches!(state.current_screen(), Screen::TextTools));
+        }
+
+        handle_command(make_command("back")).expect("back should succeed");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.nav_depth(), 1);
+        assert!(matches!(state.current_screen(), Screen::Home));
+    }
+
+    #[test]
+    fn snapshot_and_restore_round_trip() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        // Move into text tools and set some state
+        let mut cmd = make_command("text_tools_upper");
+        cmd.bindings = Some(HashMap::from([("text_input".into(), "hi".into())]));
+        handle_command(cmd).expect("text action should succeed");
+
+        let snap_value = handle_command(make_command("snapshot")).expect("snapshot should succeed");
+        let snap_str = snap_value
+            .get("snapshot")
+            .and_then(|v| v.as_str())
+            .expect("snapshot missing");
+
+        // Reset state and ensure we go back to home
+        reset_state();
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(matches!(state.current_screen(), Screen::Home));
+            assert!(state.text_output.is_none());
+        }
+
+        let mut restore_cmd = make_command("restore_state");
+        restore_cmd.snapshot = Some(snap_str.to_string());
+        let ui_after_restore =
+            handle_command(restore_cmd).expect("restore should succeed and return UI");
+        assert_contains_text(&ui_after_restore, "Result");
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert!(matches!(state.current_screen(), Screen::TextTools));
+        assert_eq!(state.text_output.as_deref(), Some("HI"));
+        assert_eq!(state.text_input.as_deref(), Some("hi"));
+    }
+
+    #[test]
+    fn restore_accepts_snapshots_from_other_versions() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        let mut cmd = make_command("text_tools_upper");
+        cmd.bindings = Some(HashMap::from([("text_input".into(), "hi".into())]));
+        handle_command(cmd).unwrap();
+        let snap_value = handle_command(make_command("snapshot")).unwrap();
+        let mut snap: Value =
+            serde_json::from_str(snap_value["snapshot"].as_str().unwrap()).unwrap();
+        assert_eq!(snap["snapshot_version"], json!(SNAPSHOT_VERSION));
+
+        // Older app: a field it never had; newer app: a field this one does not know, and
+        // one whose type changed.
+        let obj = snap.as_object_mut().unwrap();
+        obj.remove("dithering_serpentine");
+        obj.insert("field_from_the_future".into(), json!({ "x": 1 }));
+        obj.insert("text_input".into(), json!(42));
+        obj.insert("snapshot_version".into(), json!(SNAPSHOT_VERSION + 1));
+
+        reset_state();
+        let mut restore_cmd = make_command("restore_state");
+        restore_cmd.snapshot = Some(snap.to_string());
+        handle_command(restore_cmd).unwrap();
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert!(state.last_error.is_none());
+        assert!(matches!(state.current_screen(), Screen::TextTools));
+        assert_eq!(state.text_output.as_deref(), Some("HI"));
+        assert_eq!(state.text_input, None);
+        assert!(!state.dithering_serpentine);
+    }
+
+    #[test]
+    fn restore_upgrades_older_snapshot_layouts() {
+        let mut snap: Value =
+            serde_json::from_str(&AppState::new().to_snapshot().unwrap()).unwrap();
+        snap["snapshot_version"] = json!(1);
+        snap["uuid_generator"]["inspect_input"] = json!("kept");
+        snap["uuid_generator"]["inspect_fields"] = json!([["Version", "4 (random)"]]);
+
+        let restored = AppState::from_snapshot(&snap.to_string()).unwrap();
+        assert_eq!(restored.uuid_generator.inspect_input, "kept");
+        assert!(restored.uuid_generator.inspect_fields.is_empty());
+    }
+
+    #[test]
+    fn text_tools_base64_roundtrip() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut enc = make_command("text_tools_base64_encode");
+        enc.bindings = Some(HashMap::from([("text_input".into(), "hi".into())]));
+        handle_command(enc).expect("encode should work");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.text_output.as_deref(), Some("aGk="));
+        }
+
+        let mut dec = make_command("text_tools_base64_decode");
+        dec.bindings = Some(HashMap::from([("text_input".into(), "aGk=".into())]));
+        let ui = handle_command(dec).expect("decode should work");
+        assert_contains_text(&ui, "hi");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.text_output.as_deref(), Some("hi"));
+    }
+
+    #[test]
+    fn text_tools_hex_roundtrip() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut enc = make_command("text_tools_hex_encode");
+        enc.bindings = Some(HashMap::from([("text_input".into(), "hi".into())]));
+        handle_command(enc).expect("encode should work");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.text_output.as_deref(), Some("6869"));
+        }
+
+        let mut dec = make_command("text_tools_hex_decode");
+        dec.bindings = Some(HashMap::from([("text_input".into(), "6869".into())]));
+        let ui = handle_command(dec).expect("decode should work");
+        assert_contains_text(&ui, "hi");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.text_output.as_deref(), Some("hi"));
+    }
+
+    #[test]
+    fn sensor_bindings_default_and_interval_clamp() {
+        let cfg =
+            parse_sensor_bindings(&HashMap::from([("sensor_interval_ms".into(), "5".into())]))
+                .expect("parse should succeed with defaults");
+        assert!(cfg.selection.accel);
+        assert!(cfg.selection.gyro);
+        assert!(cfg.selection.mag);
+        assert!(cfg.selection.battery);
+        assert!(!cfg.selection.gps);
+        assert_eq!(cfg.interval_ms, 200);
+    }
+
+    #[test]
+    fn sensor_bindings_require_selection() {
+        let err = parse_sensor_bindings(&HashMap::from([
+            ("sensor_accel".into(), "false".into()),
+            ("sensor_gyro".into(), "false".into()),
+            ("sensor_mag".into(), "false".into()),
+            ("sensor_pressure".into(), "false".into()),
+            ("sensor_gps".into(), "false".into()),
+            ("sensor_battery".into(), "false".into()),
+        ]))
+        .unwrap_err();
+        assert_eq!(err, "no_sensor_selected");
+    }
+
+    #[test]
+    fn sensor_bindings_accept_extended_sensors() {
+        let cfg = parse_sensor_bindings(&HashMap::from([
+            ("sensor_accel".into(), "false".into()),
+            ("sensor_gyro".into(), "false".into()),
+            ("sensor_mag".into(), "false".into()),
+            ("sensor_battery".into(), "false".into()),
+            ("sensor_light".into(), "true".into()),
+            ("sensor_rotation".into(), "true".into()),
+            ("sensor_steps".into(), "true".into()),
+        ]))
+        .expect("extended sensors alone are a valid selection");
+        assert!(cfg.selection.light);
+        assert!(cfg.selection.rotation);
+        assert!(cfg.selection.steps);
+        assert!(!cfg.selection.proximity);
+        assert!(!cfg.selection.gravity);
+        assert!(!cfg.selection.linear_accel);
+    }
+
+    #[test]
+    fn sensor_bindings_reject_malformed_flags() {
+        let err = parse_sensor_bindings(&HashMap::from([(
+            "sensor_proximity".into(),
+            "yes".into(),
+        )]))
+        .unwrap_err();
+        assert_eq!(err, "invalid_sensor_flag:sensor_proximity");
+    }
+
+    #[test]
+    fn sensor_screen_renders_share_button_when_path_present() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        handle_command(make_command("sensor_logger_screen")).unwrap();
+
+        let mut cmd = make_command("sensor_logger_status");
+        cmd.bindings = Some(HashMap::from([
+            ("sensor_status".into(), "logging".into()),
+            ("sensor_path".into(), "/tmp/sensors.csv".into()),
+        ]));
+
+        let ui = handle_command(cmd).expect("status command should succeed");
+        assert_contains_text(&ui, "Share last log");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.last_sensor_log.as_deref(), Some("/tmp/sensors.csv"));
+        assert_eq!(state.sensor_status.as_deref(), Some("logging"));
+    }
+
+    #[test]
+    fn sensor_logger_exports_gps_track_from_last_log() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut csv = tempfile::Builder::new().suffix(".csv").tempfile().unwrap();
+        csv.write_all(
+            b"ts,sensor,x,y,z,extra1,extra2\n1700000000000,GPS,48.8584,2.2945,5.0,-1,-1\n",
+        )
+        .unwrap();
+        csv.flush().unwrap();
+        let csv_path = csv.path().to_string_lossy().into_owned();
+
+        handle_command(make_command("sensor_logger_screen")).unwrap();
+        let mut start = make_command("sensor_logger_start");
+        start.bindings = Some(HashMap::from([("sensor_gps".into(), "true".into())]));
+        handle_command(start).unwrap();
+        let mut status = make_command("sensor_logger_status");
+        status.bindings = Some(HashMap::from([("sensor_path".into(), csv_path.clone())]));
+        let ui = handle_command(status).unwrap();
+        assert_contains_text(&ui, "Export GPX track");
+
+        let mut export = make_command("sensor_logger_export");
+        export.bindings = Some(HashMap::from([("sensor_export_format".into(), "kml".into())]));
+        handle_command(export).unwrap();
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        let out = state.last_sensor_export.clone().expect("export path recorded");
+        assert_eq!(state.last_error, None);
+        assert!(out.ends_with(".kml"));
+        let kml = std::fs::read_to_string(&out).unwrap();
+        assert!(kml.contains("<gx:coord>2.2945000 48.8584000 0</gx:coord>"));
+        let _ = std::fs::remove_file(out);
+    }
+
+    #[test]
+    fn sensor_logger_charts_samples_from_status_bindings() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        handle_command(make_command("sensor_logger_screen")).unwrap();
+        for sample in ["ACCEL=9.81;LIGHT=120", "ACCEL=9.79;UNKNOWN=1"] {
+            let mut status = make_command("sensor_logger_status");
+            status.bindings = Some(HashMap::from([
+                ("sensor_status".into(), "logging".into()),
+                ("sensor_samples".into(), sample.into()),
+            ]));
+            handle_command(status).unwrap();
+        }
+        let ui = handle_command(make_command("sensor_logger_screen")).unwrap();
+        assert_contains_text(&ui, "Live values");
+        assert!(ui.to_string().contains("\"type\":\"Chart\""));
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        let series: Vec<_> = state
+            .sensor_history
+            .series()
+            .map(|(tag, values)| (tag.to_string(), values.len()))
+            .collect();
+        assert_eq!(series, vec![("ACCEL".to_string(), 2), ("LIGHT".to_string(), 1)]);
+    }
+
+    #[test]
+    fn sensor_bindings_validate_rotation_caps() {
+        let cfg = parse_sensor_bindings(&HashMap::from([
+            ("sensor_max_file_kb".into(), "2048".into()),
+            ("sensor_max_duration_min".into(), "".into()),
+            ("sensor_gzip_segments".into(), "true".into()),
+        ]))
+        .unwrap();
+        assert_eq!(cfg.limits.max_file_kb, Some(2048));
+        assert_eq!(cfg.limits.max_duration_min, None);
+        assert!(cfg.limits.gzip_segments);
+
+        let err = parse_sensor_bindings(&HashMap::from([(
+            "sensor_max_duration_min".into(),
+            "5000".into(),
+        )]))
+        .unwrap_err();
+        assert_eq!(err, "sensor_cap_out_of_range:sensor_max_duration_min");
+    }
+
+    #[test]
+    fn sensor_logger_gzips_completed_segments() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let dir = tempfile::tempdir().unwrap();
+        let segment = dir.path().join("sensors_1_001.csv");
+        std::fs::write(&segment, "ts,sensor,x,y,z,extra1,extra2\n1,ACCEL,0,0,9.8,0,0\n").unwrap();
+
+        handle_command(make_command("sensor_logger_screen")).unwrap();
+        let mut start = make_command("sensor_logger_start");
+        start.bindings = Some(HashMap::from([
+            ("sensor_max_file_kb".into(), "64".into()),
+            ("sensor_gzip_segments".into(), "true".into()),
+        ]));
+        handle_command(start).unwrap();
+        let mut done = make_command("sensor_logger_segment");
+        done.bindings = Some(HashMap::from([(
+            "sensor_segment_path".into(),
+            segment.to_string_lossy().into_owned(),
+        )]));
+        let ui = handle_command(done).unwrap();
+        assert_contains_text(&ui, "Completed segments: 1");
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.last_error, None);
+        let gz = dir.path().join("sensors_1_001.csv.gz");
+        assert_eq!(state.sensor_segments, vec![gz.to_string_lossy().into_owned()]);
+        assert!(gz.exists());
+        assert!(!segment.exists());
+    }
+
+    #[test]
+    fn sensor_logger_start_records_sqlite_backend() {
+        use crate::features::sensor_logger::sensor_table_name;
+
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        handle_command(make_command("sensor_logger_screen")).unwrap();
+        let mut start = make_command("sensor_logger_start");
+        start.bindings = Some(HashMap::from([("sensor_sqlite".into(), "true".into())]));
+        handle_command(start).unwrap();
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.last_error, None);
+            assert_eq!(state.sensor_backend, SensorBackend::Sqlite);
+        }
+        assert_eq!(sensor_table_name("LINACC").as_deref(), Some("linacc"));
+        assert_eq!(sensor_table_name("bogus"), None);
+
+        let mut bad = make_command("sensor_logger_start");
+        bad.bindings = Some(HashMap::from([("sensor_sqlite".into(), "yes".into())]));
+        handle_command(bad).unwrap();
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.last_error.as_deref(), Some("invalid_sensor_flag:sensor_sqlite"));
+    }
+
+    #[test]
+    fn sensor_logger_stop_summarizes_recording() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let dir = tempfile::tempdir().unwrap();
+        let csv = dir.path().join("sensors_5.csv");
+        std::fs::write(
+            &csv,
+            "ts,sensor,x,y,z,extra1,extra2\n0,BARO,1000,0,0,0,0\n500,BARO,1002,0,0,0,0\n1000,BARO,1004,0,0,0,0\n",
+        )
+        .unwrap();
+
+        handle_command(make_command("sensor_logger_screen")).unwrap();
+        handle_command(make_command("sensor_logger_start")).unwrap();
+        let mut stop = make_command("sensor_logger_stop");
+        stop.bindings = Some(HashMap::from([(
+            "sensor_path".into(),
+            csv.to_string_lossy().into_owned(),
+        )]));
+        let ui = handle_command(stop).unwrap();
+        assert_contains_text(&ui, "Recording summary (1)");
+        assert_contains_text(&ui, "BARO: n=3, 2.00 Hz, 1.0 s");
+        assert_contains_text(&ui, "pressure: [1000.000 … 1004.000] μ=1002.000");
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.sensor_status.as_deref(), Some("stopped"));
+        assert_eq!(state.last_error, None);
+        assert!(!state.loading_with_spinner);
+    }
+
+    #[test]
+    fn sensor_logger_flags_threshold_crossings() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let err = parse_sensor_bindings(&HashMap::from([(
+            "sensor_alert_accel_g".into(),
+            "500".into(),
+        )]))
+        .unwrap_err();
+        assert_eq!(err, "sensor_threshold_out_of_range:sensor_alert_accel_g");
+
+        handle_command(make_command("sensor_logger_screen")).unwrap();
+        let mut start = make_command("sensor_logger_start");
+        start.bindings = Some(HashMap::from([("sensor_alert_accel_g".into(), "3".into())]));
+        handle_command(start).unwrap();
+
+        let status = |samples: &str, ts: &str| {
+            let mut cmd = make_command("sensor_logger_status");
+            cmd.bindings = Some(HashMap::from([
+                ("sensor_samples".into(), samples.into()),
+                ("sensor_samples_ts".into(), ts.into()),
+            ]));
+            handle_command(cmd).unwrap()
+        };
+        let ui = status("ACCEL=9.8", "1000");
+        assert!(ui.get("sensor_log_rows").is_none());
+
+        let ui = status("ACCEL=39.5", "1500");
+        assert_contains_text(&ui, "Threshold exceeded: |a|");
+        assert_contains_text(&ui, "Threshold alerts: 1");
+        let rows = ui.get("sensor_log_rows").and_then(|v| v.as_array()).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].as_str().unwrap().starts_with("1500,ALERT,"));
+
+        // Still above: no new annotation until the value falls back.
+        let ui = status("ACCEL=40.0", "2000");
+        assert!(ui.get("sensor_log_rows").is_none());
+        assert_contains_text(&ui, "Threshold alerts: 1");
+    }
+
+    #[test]
+    fn sensor_logger_format_selection_feeds_start() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        handle_command(make_command("sensor_logger_screen")).unwrap();
+        let mut pick = make_command("sensor_logger_format");
+        pick.bindings = Some(HashMap::from([("sensor_format".into(), "ndjson".into())]));
+        let ui = handle_command(pick).unwrap();
+        assert_contains_text(&ui, "✓ JSON Lines");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.sensor_backend, SensorBackend::Ndjson);
+        }
+
+        let cfg = parse_sensor_bindings(&HashMap::from([(
+            "sensor_format".into(),
+            "ndjson".into(),
+        )]))
+        .unwrap();
+        assert_eq!(cfg.backend, SensorBackend::Ndjson);
+
+        let mut bad = make_command("sensor_logger_format");
+        bad.bindings = Some(HashMap::from([("sensor_format".into(), "xml".into())]));
+        handle_command(bad).unwrap();
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.last_error.as_deref(), Some("sensor_unknown_format:xml"));
+        assert_eq!(state.sensor_backend, SensorBackend::Ndjson);
+    }
+
+    #[test]
+    fn sensor_logger_pause_and_resume_toggle_status() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        handle_command(make_command("sensor_logger_screen")).unwrap();
+        handle_command(make_command("sensor_logger_resume")).unwrap();
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.last_error.as_deref(), Some("sensor_not_paused"));
+        }
+
+        handle_command(make_command("sensor_logger_start")).unwrap();
+        let ui = handle_command(make_command("sensor_logger_pause")).unwrap();
+        assert_contains_text(&ui, "Resume logging");
+        assert_contains_text(&ui, "Logging continues in a foreground service.");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.sensor_status.as_deref(), Some("paused"));
+            assert_eq!(state.last_error, None);
+        }
+
+        let ui = handle_command(make_command("sensor_logger_resume")).unwrap();
+        assert_contains_text(&ui, "Pause logging");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.sensor_status.as_deref(), Some("logging"));
+    }
+
+    #[test]
+    fn sensor_logger_foreground_indicator_shown_when_logging() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        handle_command(make_command("sensor_logger_screen")).unwrap();
+        let mut status = make_command("sensor_logger_status");
+        status.bindings = Some(HashMap::from([
+            ("sensor_status".into(), "logging".into()),
+            ("sensor_path".into(), "/tmp/sensors.csv".into()),
+        ]));
+
+        let ui = handle_command(status).expect("status command should succeed");
+        assert_contains_text(&ui, "foreground service");
+    }
+
+    #[test]
+    fn text_viewer_reads_file_via_fd() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "hello from csv").unwrap();
+        let fd = File::open(file.path()).unwrap().into_raw_fd();
+
+        let mut cmd = make_command("text_viewer_open");
+        cmd.fd = Some(fd);
+        cmd.path = Some(file.path().to_string_lossy().into_owned());
+
+        let ui = handle_command(cmd).expect("text viewer should succeed");
+        assert_contains_text(&ui, "hello from csv");
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(
+            state.text_view_path.as_deref(),
+            Some(file.path().to_string_lossy().as_ref())
+        );
+        assert!(state.text_view_error.is_none());
+    }
+
+    #[test]
+    fn text_viewer_previews_markdown_as_rich_text() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        {
+            let mut state = STATE.get_or_init(GlobalState::new).ui_lock();
+            state.push_screen(Screen::TextViewer);
+            state.text_view_content = Some("# Notes\n\n- **done**".into());
+            state.text_view_language = Some("markdown".into());
+        }
+
+        fn find_type<'a>(node: &'a Value, kind: &str) -> Option<&'a Value> {
+            if node.get("type").and_then(|t| t.as_str()) == Some(kind) {
+                return Some(node);
+            }
+            node.get("children")
+                .and_then(|c| c.as_array())
+                .and_then(|children| children.iter().find_map(|c| find_type(c, kind)))
+        }
+
+        let ui = handle_command(make_command("text_viewer_toggle_markdown_preview")).unwrap();
+        let rich = find_type(&ui, "RichText").expect("rendered markdown");
+        assert_eq!(rich["markdown"], json!("# Notes\n\n- **done**"));
+        assert!(find_type(&ui, "CodeView").is_none());
+
+        let ui = handle_command(make_command("text_viewer_toggle_markdown_preview")).unwrap();
+        assert!(find_type(&ui, "RichText").is_none());
+        assert!(find_type(&ui, "CodeView").is_some());
+    }
+
+    #[test]
+    fn text_viewer_supports_chunked_loading() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut file = NamedTempFile::new().unwrap();
+        let block = vec![b'a'; 140_000];
+        file.write_all(&block).unwrap();
+        file.write_all(&block).unwrap(); // ~280 KB
+        file.flush().unwrap();
+        let fd = File::open(file.path()).unwrap().into_raw_fd();
+
+        let mut cmd = make_command("text_viewer_open");
+        cmd.fd = Some(fd);
+        cmd.path = Some(file.path().to_string_lossy().into_owned());
+
+        handle_command(cmd).expect("text viewer should succeed");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        let initial_loaded_end = state.text_view_loaded_bytes;
+        let initial_offset = state.text_view_window_offset;
+        let total = state.text_view_total_bytes.unwrap();
+        assert_eq!(initial_offset, 0);
+        let _initial_len = state
+            .text_view_content
+            .as_ref()
+            .map(|c| c.len())
+            .unwrap_or(0);
+        assert!(state.text_view_has_more);
+        assert!(initial_loaded_end < total);
+        drop(state);
+
+        handle_command(make_command("text_viewer_load_more")).expect("load more should succeed");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        let after_len = state
+            .text_view_content
+            .as_ref()
+            .map(|c| c.len())
+            .unwrap_or(0);
+        assert!(after_len > 0);
+        assert!(after_len <= 150_000);
+        assert_eq!(state.text_view_total_bytes, Some(total));
+        assert!(state.text_view_window_offset > initial_offset);
+        assert!(state.text_view_loaded_bytes > initial_loaded_end);
+        assert!(state.text_view_has_previous);
+        assert!(state.text_view_loaded_bytes - state.text_view_window_offset > 0);
+        assert_eq!(
+            state.text_view_content.as_ref().unwrap().chars().next(),
+            Some('a')
+        );
+    }
+
+    #[test]
+    fn pdf_preview_screen_sets_state() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        {
+            let mut state = STATE.get_or_init(GlobalState::new).ui_lock();
+            state.pdf.source_uri = Some("file://dummy.pdf".into());
+            state.pdf.page_count = Some(3);
+        }
+        let ui = handle_command(make_command("pdf_preview_screen")).expect("preview screen");
+        assert_contains_text(&ui, "PDF viewer");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert!(matches!(state.current_screen(), Screen::PdfPreview));
+        assert!(state.pdf.preview_page.is_none());
+    }
+
+    #[test]
+    fn pdf_page_open_sets_page() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        {
+            let mut state = STATE.get_or_init(GlobalState::new).ui_lock();
+            state.pdf.source_uri = Some("file://dummy.pdf".into());
+            state.pdf.page_count = Some(5);
+        }
+        let mut cmd = make_command("pdf_page_open");
+        let mut bindings = HashMap::new();
+        bindings.insert("page".to_string(), "2".to_string());
+        cmd.bindings = Some(bindings);
+        handle_command(cmd).expect("open page");
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.pdf.preview_page, Some(2));
+        assert!(matches!(state.current_screen(), Screen::PdfPreview));
+    }
+
+    fn write_test_image(w: u32, h: u32, color: [u8; 3]) -> NamedTempFile {
+        let mut img = image::RgbaImage::new(w, h);
+        for pixel in img.pixels_mut() {
+            *pixel = image::Rgba([color[0], color[1], color[2], 255]);
+        }
+        let file = tempfile::Builder::new().suffix(".png").tempfile().unwrap();
+        img.save(file.path()).unwrap();
+        file
+    }
+
+    #[test]
+    fn pixel_art_screen_sets_defaults() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        handle_command(make_command("pixel_art_screen")).expect("screen");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert!(matches!(state.current_screen(), Screen::PixelArt));
+        assert_eq!(state.pixel_art.scale_factor, 4);
+    }
+
+    #[test]
+    fn pixel_art_apply_produces_result() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        let img = write_test_image(8, 8, [10, 20, 30]);
+        {
+            let mut cmd = make_command("pixel_art_pick");
+            cmd.path = Some(img.path().to_string_lossy().into_owned());
+            handle_command(cmd).expect("pick");
+        }
+        let mut apply = make_command("pixel_art_apply");
+        apply.loading_only = Some(false);
+        let ui = handle_command(apply).expect("apply");
+        assert_contains_text(&ui, "Result:");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert!(state.pixel_art.result_path.is_some());
+        assert!(state.pixel_art.error.is_none());
+
+        fn find_image(node: &Value) -> Option<&Value> {
+            if node.get("type").and_then(Value::as_str) == Some("Image") {
+                return Some(node);
+            }
+            node.get("children")
+                .and_then(Value::as_array)
+                .and_then(|children| children.iter().find_map(find_image))
+        }
+        let image = find_image(&ui).expect("result preview shown");
+        assert_eq!(
+            image["path"].as_str(),
+            state.pixel_art.result_path.as_deref()
+        );
+        assert_eq!(image["zoomable"], true);
+    }
+
+    #[test]
+    fn pixel_art_upscaler_crt_and_palette_lock_apply() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        let img = write_test_image(16, 16, [200, 40, 40]);
+        {
+            let mut cmd = make_command("pixel_art_pick");
+            cmd.path = Some(img.path().to_string_lossy().into_owned());
+            handle_command(cmd).expect("pick");
+        }
+        handle_command(make_command("pixel_art_upscaler_xbr")).unwrap();
+        handle_command(make_command("pixel_art_palette_lock_gb")).unwrap();
+        let mut crt = make_command("pixel_art_crt_toggle");
+        crt.bindings = Some(HashMap::from([("pixel_art_crt".into(), "true".into())]));
+        handle_command(crt).unwrap();
+
+        let mut apply = make_command("pixel_art_apply");
+        apply.loading_only = Some(false);
+        handle_command(apply).expect("apply");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.pixel_art.upscaler, PixelArtUpscaler::Xbr);
+        assert_eq!(
+            state.pixel_art.palette_lock,
+            Some(DitheringPalette::GameBoy)
+        );
+        assert!(state.pixel_art.crt);
+        assert!(state.pixel_art.error.is_none());
+        let out = image::open(state.pixel_art.result_path.clone().unwrap())
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(out.dimensions(), (16, 16));
+        // Even rows only carry the aperture mask, so the locked Game Boy green survives there.
+        let p = out.get_pixel(1, 0);
+        assert!(p[1] > p[0] && p[1] > p[2], "expected a green tint, got {p:?}");
+    }
+
+    #[test]
+    fn math_plot_runs_on_worker_and_shows_image() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
+        reset_state();
+        let mut cmd = make_command("math_calculate");
+        cmd.bindings = Some(HashMap::from([(
+            "math_expr".into(),
+            "plot(sin(x), -pi, pi)".into(),
+        )]));
+        let ui = handle_command(cmd).expect("plot");
+        assert_contains_text(&ui, "Plot saved to:");
+        let path = {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(state.math_tool.plot_base64.is_some());
+            state.math_tool.plot_path.clone().expect("plot path")
+        };
+        assert!(path.ends_with(".png"));
+        let _ = std::fs::remove_file(path);
+
+        let mut cmd = make_command("math_calculate");
+        cmd.bindings = Some(HashMap::from([("math_expr".into(), "1+1".into())]));
+        let ui = handle_command(cmd).expect("calculate");
+        assert!(!ui.to_string().contains("Plot saved to:"));
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert!(state.math_tool.plot_path.is_none());
+        assert!(state.math_tool.plot_base64.is_none());
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
+
+    #[test]
+    fn math_history_entry_recalls_into_input() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
+        reset_state();
+        let mut cmd = make_command("math_calculate");
+        cmd.bindings = Some(HashMap::from([("math_expr".into(), "2^10".into())]));
+        handle_command(cmd).expect("calculate");
+        let mut cmd = make_command("math_calculate");
+        cmd.bindings = Some(HashMap::from([("math_expr".into(), "1+1".into())]));
+        handle_command(cmd).expect("calculate");
+
+        let mut cmd = make_command("math_recall");
+        cmd.bindings = Some(HashMap::from([("index".into(), "1".into())]));
+        handle_command(cmd).expect("recall");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.math_tool.expression, "2^10");
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
+
+    #[test]
+    fn math_constants_screen_inserts_symbol() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        handle_command(make_command("math_tool_screen")).unwrap();
+        let ui = handle_command(make_command("math_constants_screen")).unwrap();
+        assert_contains_text(&ui, "Physical constants");
+
+        let mut cmd = make_command("math_insert_constant");
+        cmd.bindings = Some(HashMap::from([("symbol".into(), "N_A".into())]));
+        handle_command(cmd).unwrap();
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.current_screen(), Screen::MathTool);
+        assert_eq!(state.math_tool.expression, "N_A");
+    }
+
+    #[test]
+    fn regex_file_search_runs_on_worker() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        handle_command(make_command("regex_tester_screen")).unwrap();
+
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "error: one").unwrap();
+        writeln!(file, "ok").unwrap();
+        writeln!(file, "error: two error").unwrap();
+        file.flush().unwrap();
+
+        let mut cmd = make_command("regex_file");
+        cmd.path = Some(file.path().to_string_lossy().into_owned());
+        cmd.bindings = Some(HashMap::from([("regex_pattern".into(), "error".into())]));
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "3 match(es) on 2 line(s)");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        let result = state.regex_tester.file_result.as_ref().expect("file result");
+        assert_eq!(result.lines[1].line_number, 3);
+    }
+
+    #[test]
+    fn regex_explain_lists_components_until_pattern_changes() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        handle_command(make_command("regex_tester_screen")).unwrap();
+
+        let mut cmd = make_command("regex_explain");
+        cmd.bindings = Some(HashMap::from([("regex_pattern".into(), "^a+".into())]));
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "Explanation");
+        assert!(ui
+            .to_string()
+            .contains("- The character 'a', 1 or more times"));
+
+        let mut cmd = make_command("regex_test");
+        cmd.bindings = Some(HashMap::from([("regex_pattern".into(), "b".into())]));
+        handle_command(cmd).unwrap();
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert!(state.regex_tester.explanation.is_empty());
+    }
+
+    #[test]
+    fn uuid_generate_uses_selected_version() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        handle_command(make_command("uuid_screen")).unwrap();
+
+        let mut cmd = make_command("uuid_generate");
+        cmd.bindings = Some(HashMap::from([
+            ("uuid_version".into(), "v5".into()),
+            ("uuid_v5_namespace".into(), "dns".into()),
+            ("uuid_v5_name".into(), "www.example.com".into()),
+        ]));
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "2ed6657d-e927-568b-95e1-2665a8aea6a2");
+
+        let mut cmd = make_command("uuid_generate");
+        cmd.bindings = Some(HashMap::from([("uuid_version".into(), "v7".into())]));
+        handle_command(cmd).unwrap();
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        let uuid = state.uuid_generator.last_uuid.clone().expect("uuid");
+        assert_eq!(uuid.chars().nth(14), Some('7'));
+    }
+
+    #[test]
+    fn ulid_and_nanoid_generate_on_uuid_screen() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        handle_command(make_command("uuid_screen")).unwrap();
+        handle_command(make_command("ulid_generate")).unwrap();
+
+        let mut cmd = make_command("nanoid_generate");
+        cmd.bindings = Some(HashMap::from([
+            ("nanoid_alphabet".into(), "01".into()),
+            ("nanoid_length".into(), "8".into()),
+        ]));
+        handle_command(cmd).unwrap();
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.uuid_generator.last_ulid.as_ref().map(String::len), Some(26));
+        let nanoid = state.uuid_generator.last_nanoid.clone().expect("nanoid");
+        assert_eq!(nanoid.len(), 8);
+        assert!(nanoid.chars().all(|c| c == '0' || c == '1'));
+    }
 
-    static TEST_MUTEX: Mutex<()> = Mutex::new(());
+    #[test]
+    fn random_string_applies_password_policy() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        handle_command(make_command("uuid_screen")).unwrap();
 
-    const SAMPLE_CONTENT: &str = "abc";
-    const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
-    const SHA1_ABC: &str = "a9993e364706816aba3e25717850c26c9cd0d89d";
-    const MD5_ABC: &str = "900150983cd24fb0d6963f7d28e17f72";
-    const SAMPLE_WRAP: &str = "rust keeps your memory safe by design and gives you fearless concurrency without data races";
+        let mut cmd = make_command("random_string_generate");
+        cmd.bindings = Some(HashMap::from([
+            ("charset".into(), "numeric".into()),
+            ("uuid_str_len".into(), "10".into()),
+            ("password_min_symbols".into(), "3".into()),
+            ("password_exclude_ambiguous".into(), "true".into()),
+        ]));
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "bits");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.uuid_generator.string_charset, StringCharset::Numeric);
+        let s = state.uuid_generator.last_string.clone().expect("string");
+        assert_eq!(s.len(), 10);
+        assert!(s.chars().filter(|c| !c.is_ascii_digit()).count() >= 3);
+        assert!(!s.contains('0') && !s.contains('1'));
+    }
 
-    fn make_command(action: &str) -> Command {
-        Command {
-            action: action.into(),
-            path: None,
-            path_list: None,
-            fd: None,
-            fd_list: None,
-            error: None,
-            target: None,
-            result_path: None,
-            result_size: None,
-            result_format: None,
-            output_dir: None,
-            bindings: None,
-            loading_only: None,
-            snapshot: None,
-            primary_fd: None,
-            primary_path: None,
-            angle_radians: None,
+    #[test]
+    fn uuid_inspect_lists_decoded_fields() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        handle_command(make_command("uuid_screen")).unwrap();
+
+        let mut cmd = make_command("uuid_inspect");
+        cmd.bindings = Some(HashMap::from([(
+            "uuid_inspect_input".into(),
+            " 017f22e2-79b0-7cc3-98c4-dc0c0c07398f ".into(),
+        )]));
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "Version: 7 (Unix time-ordered)");
+        assert_contains_text(&ui, "Timestamp: 2022-02-22T19:22:22Z");
+
+        let mut cmd = make_command("uuid_inspect");
+        cmd.bindings = Some(HashMap::from([("uuid_inspect_input".into(), "xyz".into())]));
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "uuid_parse_failed");
+    }
+
+    #[test]
+    fn random_bytes_generate_clamps_length() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        handle_command(make_command("uuid_screen")).unwrap();
+
+        let mut cmd = make_command("random_bytes_generate");
+        cmd.bindings = Some(HashMap::from([("random_bytes_len".into(), "5000".into())]));
+        handle_command(cmd).unwrap();
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.uuid_generator.random_bytes_len, 1024);
+        assert_eq!(state.uuid_generator.last_random_bytes.as_ref().map(Vec::len), Some(1024));
+    }
+
+    #[test]
+    fn uuid_show_as_qr_renders_value_and_returns_on_back() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        handle_command(make_command("uuid_screen")).unwrap();
+        handle_command(make_command("ulid_generate")).unwrap();
+        let ulid = {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            state.uuid_generator.last_ulid.clone().expect("ulid generated")
+        };
+
+        let mut cmd = make_command("qr_generate");
+        cmd.bindings = Some(HashMap::from([("qr_input".into(), ulid)]));
+        handle_command(cmd).unwrap();
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(matches!(state.current_screen(), Screen::Qr));
+            assert!(state.last_qr_base64.is_some());
         }
+
+        handle_command(make_command("back")).unwrap();
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert!(matches!(state.current_screen(), Screen::UuidGenerator));
     }
 
-    fn reset_state() {
-        handle_command(make_command("reset")).expect("reset command should succeed");
-        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
-        TEST_WORKER_DELAY_MS.store(0, Ordering::SeqCst);
+    #[test]
+    fn barometer_calibration_zeroes_to_station_pressure() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        {
+            let mut state = STATE.get_or_init(GlobalState::new).ui_lock();
+            state.sensor_calibration = Default::default();
+        }
+
+        let zero = || {
+            let mut cmd = make_command("sensor_calibration_set");
+            cmd.bindings = Some(HashMap::from([(
+                "barometer_station_hpa".into(),
+                "1013".into(),
+            )]));
+            cmd
+        };
+        handle_command(make_command("barometer_screen")).unwrap();
+        handle_command(zero()).unwrap();
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.barometer_error.as_deref(), Some("barometer_no_reading"));
+        }
+
+        let mut reading = make_command("barometer_set");
+        reading.angle_radians = Some(1010.0);
+        handle_command(reading).unwrap();
+        let ui = handle_command(zero()).unwrap();
+        assert_contains_text(&ui, "Offset applied: +3.00 hPa");
+        assert_contains_text(&ui, "1013.0 hPa");
+
+        let mut reading = make_command("barometer_set");
+        reading.angle_radians = Some(1000.0);
+        handle_command(reading).unwrap();
+        let mut state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.barometer_error, None);
+        assert_eq!(state.barometer_hpa, Some(1003.0));
+        state.sensor_calibration = Default::default();
     }
 
-    fn extract_texts(ui: &Value) -> Vec<String> {
-        fn walk(node: &Value, acc: &mut Vec<String>) {
-            if let Some(text) = node.get("text").and_then(|t| t.as_str()) {
-                acc.push(text.to_string());
-            }
-            if let Some(children) = node.get("children").and_then(|c| c.as_array()) {
-                for child in children {
-                    walk(child, acc);
-                }
-            }
+    #[test]
+    fn compass_declination_shifts_heading_to_true_north() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        {
+            let mut state = STATE.get_or_init(GlobalState::new).ui_lock();
+            state.sensor_calibration = Default::default();
         }
 
-        let mut out = Vec::new();
-        walk(ui, &mut out);
-        out
+        handle_command(make_command("compass_demo")).unwrap();
+        let mut cal = make_command("sensor_calibration_set");
+        cal.bindings = Some(HashMap::from([(
+            "compass_declination_deg".into(),
+            "10".into(),
+        )]));
+        let ui = handle_command(cal).unwrap();
+        assert_contains_text(&ui, "true north (declination +10.0°)");
+
+        let mut heading = make_command("compass_set");
+        heading.angle_radians = Some(0.0);
+        let ui = handle_command(heading).unwrap();
+        assert_contains_text(&ui, "Heading: 10.0°");
+        let mut state = STATE.get_or_init(GlobalState::new).ui_lock();
+        state.sensor_calibration = Default::default();
     }
 
-    fn assert_contains_text(ui: &Value, needle: &str) {
-        let texts = extract_texts(ui);
-        assert!(
-            texts.iter().any(|t| t.contains(needle)),
-            "expected UI to contain text with `{needle}`, found: {texts:?}"
-        );
+    #[test]
+    fn pixel_art_set_scale_clamps_and_sets() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        handle_command(make_command("pixel_art_screen")).unwrap();
+        let mut cmd = make_command("pixel_art_set_scale");
+        cmd.bindings = Some(HashMap::from([("scale".into(), "1".into())]));
+        handle_command(cmd).unwrap();
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.pixel_art.scale_factor, 2);
     }
 
     #[test]
-    fn section_and_card_serialize_with_headers() {
-        let body = vec![serde_json::to_value(UiText::new("Body")).unwrap()];
-        let section = UiSection::new(body.clone())
-            .title("📁 Files")
-            .subtitle("2 tools")
-            .icon("📁")
-            .padding(8);
-        let card = UiCard::new(body).title("⚡ Quick access").padding(6);
+    fn text_viewer_jump_and_prev_work() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut file = NamedTempFile::new().unwrap();
+        let mut block = vec![b'a'; 64_000];
+        block.extend(vec![b'b'; 64_000]);
+        file.write_all(&block).unwrap();
+        file.flush().unwrap();
+
+        let mut cmd = make_command("text_viewer_open");
+        cmd.path = Some(file.path().to_string_lossy().into_owned());
+        handle_command(cmd).expect("text viewer should succeed");
+
+        // Jump to second half
+        let mut jump = make_command("text_viewer_jump");
+        jump.bindings = Some(HashMap::from([("offset_bytes".into(), "64000".into())]));
+        handle_command(jump).expect("jump should succeed");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            // If the file is smaller than a window, jump clamps to 0.
+            assert!(state.text_view_window_offset <= 64_000);
+            assert!(
+                state.text_view_content.as_ref().unwrap().starts_with('b')
+                    || state.text_view_content.as_ref().unwrap().contains('b')
+            );
+            assert!(state.text_view_has_previous || state.text_view_window_offset == 0);
+        }
+
+        // Load previous should move window back toward start
+        handle_command(make_command("text_viewer_load_prev")).expect("prev should succeed");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.text_view_window_offset, 0);
+        assert!(state.text_view_content.as_ref().unwrap().starts_with('a'));
+    }
+
+    #[test]
+    fn archive_text_entry_opens_in_viewer() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut zip_file = NamedTempFile::new().unwrap();
+        {
+            let mut writer = zip::ZipWriter::new(&mut zip_file);
+            let options = FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+            writer.start_file("note.txt", options).unwrap();
+            writer.write_all(b"hello from zip").unwrap();
+            writer.start_file("data.bin", options).unwrap();
+            writer.write_all(&[0u8, 1, 2]).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let fd = File::open(zip_file.path()).unwrap().into_raw_fd();
+        let mut open_cmd = make_command("archive_open");
+        open_cmd.fd = Some(fd);
+        open_cmd.path = Some(zip_file.path().to_string_lossy().into_owned());
+        handle_command(open_cmd).expect("archive open should succeed");
+
+        let ui = handle_command(make_command("archive_open_text:0"))
+            .expect("text entry open should succeed");
+        assert_contains_text(&ui, "hello from zip");
+
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert!(state
+            .text_view_path
+            .as_deref()
+            .map(|p| p.contains("note.txt"))
+            .unwrap_or(false));
+        assert_eq!(state.text_view_content.as_deref(), Some("hello from zip"));
+        assert!(matches!(state.current_screen(), Screen::TextViewer));
+        assert_eq!(state.nav_depth(), 3);
+    }
+
+    #[test]
+    fn qr_screen_has_back_button_when_nested() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut cmd = make_command("qr_generate");
+        cmd.bindings = Some(HashMap::from([("qr_input".into(), "hi".into())]));
+        let ui = handle_command(cmd).expect("qr generate should succeed");
+
+        assert_contains_text(&ui, "Back");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert!(matches!(state.current_screen(), Screen::Qr));
+        assert!(state.nav_depth() > 1);
+    }
+
+    #[test]
+    fn sensor_logger_actions_do_not_stack_nav() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        handle_command(make_command("sensor_logger_screen")).unwrap();
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.nav_depth(), 2);
+            assert!(matches!(state.current_screen(), Screen::SensorLogger));
+        }
+
+        let mut start = make_command("sensor_logger_start");
+        start.bindings = Some(HashMap::from([("sensor_accel".into(), "true".into())]));
+        handle_command(start).unwrap();
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.nav_depth(), 2);
+            assert!(matches!(state.current_screen(), Screen::SensorLogger));
+        }
+
+        handle_command(make_command("back")).unwrap();
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.nav_depth(), 1);
+        assert!(matches!(state.current_screen(), Screen::Home));
+    }
 
-        let section_val = serde_json::to_value(section).expect("section should serialize");
-        assert_eq!(
-            section_val.get("type"),
-            Some(&Value::String("Section".into()))
-        );
-        assert_eq!(
-            section_val.get("title"),
-            Some(&Value::String("📁 Files".into()))
-        );
-        assert_eq!(section_val.get("icon"), Some(&Value::String("📁".into())));
-        assert!(section_val
-            .get("children")
-            .and_then(|c| c.as_array())
-            .is_some());
+    #[test]
+    fn text_viewer_missing_source_sets_error() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
 
-        let card_val = serde_json::to_value(card).expect("card should serialize");
-        assert_eq!(card_val.get("type"), Some(&Value::String("Card".into())));
-        assert!(card_val
-            .get("children")
-            .and_then(|c| c.as_array())
-            .is_some());
+        let ui = handle_command(make_command("text_viewer_open")).expect("should return UI");
+        assert_contains_text(&ui, "missing_source");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.text_view_error.as_deref(), Some("missing_source"));
+        assert!(state.text_view_content.is_none());
     }
 
     #[test]
-    fn hash_file_sha256_via_path_updates_ui_and_state() {
+    fn missing_source_sets_error_and_clears_previous_hash() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
 
@@ -5634,1069 +11699,2808 @@ mod tests {
         file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
         file.flush().unwrap();
 
-        let mut command = make_command("hash_file_sha256");
-        command.path = Some(file.path().to_string_lossy().into_owned());
+        let mut initial = make_command("hash_file_md5");
+        initial.path = Some(file.path().to_string_lossy().into_owned());
 
-        let ui = handle_command(command).expect("hash command should succeed");
+        handle_command(initial).expect("initial hash should succeed");
 
-        assert_contains_text(&ui, &format!("SHA-256: {SHA256_ABC}"));
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.last_hash.as_deref(), Some(MD5_ABC));
+            assert_eq!(state.last_hash_algo.as_deref(), Some("MD5"));
+        }
+
+        let ui = handle_command(make_command("hash_file_md4"))
+            .expect("hash command should still return UI even when failing");
+
+        assert_contains_text(&ui, "missing_path");
 
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.last_hash.as_deref(), Some(SHA256_ABC));
-        assert_eq!(state.last_hash_algo.as_deref(), Some("SHA-256"));
-        assert!(state.last_error.is_none());
+        assert_eq!(state.last_hash, None);
+        assert_eq!(state.last_error.as_deref(), Some("missing_path"));
+        assert_eq!(state.last_hash_algo.as_deref(), Some("MD4"));
     }
 
     #[test]
-    fn hash_file_loading_then_result() {
+    fn text_viewer_open_runs_on_worker() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
+        TEST_FORCE_ASYNC_WORKER.store(true, Ordering::SeqCst);
 
         let mut file = NamedTempFile::new().unwrap();
         file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
         file.flush().unwrap();
 
-        let mut loading_cmd = make_command("hash_file_sha256");
-        loading_cmd.loading_only = Some(true);
-        let loading_ui = handle_command(loading_cmd).expect("loading command should succeed");
-        assert_contains_text(&loading_ui, "Computing SHA-256");
-
-        let mut command = make_command("hash_file_sha256");
-        command.path = Some(file.path().to_string_lossy().into_owned());
-
-        let ui = handle_command(command).expect("hash command should succeed");
+        let mut cmd = make_command("text_viewer_open");
+        cmd.path = Some(file.path().to_string_lossy().into_owned());
+        let ui_loading = handle_command(cmd).expect("text_viewer_open should enqueue");
+        assert_contains_text(&ui_loading, "Loading text");
 
-        assert_contains_text(&ui, &format!("SHA-256: {SHA256_ABC}"));
+        thread::sleep(Duration::from_millis(10));
+        let _ = handle_command(make_command("snapshot")).unwrap();
 
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.last_hash.as_deref(), Some(SHA256_ABC));
-        assert_eq!(state.last_hash_algo.as_deref(), Some("SHA-256"));
-        assert!(state.last_error.is_none());
-        assert!(matches!(state.current_screen(), Screen::Home));
+        assert!(matches!(state.current_screen(), Screen::TextViewer));
+        let content = state.text_view_content.as_deref().unwrap_or("");
+        assert!(content.contains(SAMPLE_CONTENT));
+
+        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
     }
 
     #[test]
-    fn hash_verify_enqueues_and_releases_mutex() {
+    fn file_info_runs_on_worker_and_updates_state() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
-
         TEST_FORCE_ASYNC_WORKER.store(true, Ordering::SeqCst);
-        TEST_WORKER_DELAY_MS.store(200, Ordering::SeqCst);
 
         let mut file = NamedTempFile::new().unwrap();
         file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
         file.flush().unwrap();
 
-        let mut command = make_command("hash_verify");
-        command.path = Some(file.path().to_string_lossy().into_owned());
-        command.bindings = Some(HashMap::from([(
-            "hash_reference".into(),
-            SHA256_ABC.into(),
-        )]));
-
-        let start = Instant::now();
-        let ui = handle_command(command).expect("hash verify dispatch should succeed");
-        assert!(
-            start.elapsed() < Duration::from_millis(100),
-            "dispatch held the UI mutex for too long"
-        );
-        assert_contains_text(&ui, "Computing SHA-256");
-        assert!(
-            STATE.get_or_init(GlobalState::new).ui_try_lock().is_some(),
-            "state mutex should be free while worker runs"
-        );
+        let mut cmd = make_command("file_info");
+        cmd.path = Some(file.path().to_string_lossy().into_owned());
+        let ui_loading = handle_command(cmd).expect("file_info should enqueue");
+        assert_contains_text(&ui_loading, "Reading file info");
 
-        std::thread::sleep(Duration::from_millis(250));
-        let refreshed =
-            handle_command(make_command("init")).expect("refresh after worker should succeed");
-        assert_contains_text(&refreshed, SHA256_ABC);
+        thread::sleep(Duration::from_millis(10));
+        let _ = handle_command(make_command("snapshot")).unwrap();
 
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.last_hash.as_deref(), Some(SHA256_ABC));
-        assert_eq!(state.hash_match, Some(true));
+        assert_eq!(state.current_screen(), Screen::FileInfo);
+        assert!(state.last_file_info.is_some());
+        assert!(state.last_error.is_none());
 
         TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
-        TEST_WORKER_DELAY_MS.store(0, Ordering::SeqCst);
     }
 
     #[test]
-    fn concurrent_jni_call_proceeds_while_worker_runs() {
+    fn file_info_renders_exif_section_for_jpeg() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
 
-        TEST_FORCE_ASYNC_WORKER.store(true, Ordering::SeqCst);
-        TEST_WORKER_DELAY_MS.store(300, Ordering::SeqCst);
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&crate::features::exif_info::tests::sample_jpeg())
+            .unwrap();
+        file.flush().unwrap();
+
+        handle_command(make_command("file_info_screen")).unwrap();
+        let mut cmd = make_command("file_info");
+        cmd.path = Some(file.path().to_string_lossy().into_owned());
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "Camera: Kista Verk 1");
+        assert_contains_text(&ui, "Taken: 2024-06-21 12:30:00");
+        assert_contains_text(&ui, "Orientation: 6 (rotated 90° CW)");
+        assert_contains_text(&ui, "GPS: 64.150000, -21.950000");
+        assert_contains_text(&ui, "https://www.openstreetmap.org/?mlat=64.150000&mlon=-21.950000");
+    }
+
+    #[test]
+    fn file_info_renders_media_section_for_mp4() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
 
         let mut file = NamedTempFile::new().unwrap();
-        file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
+        file.write_all(&crate::features::media_info::tests::sample_mp4())
+            .unwrap();
         file.flush().unwrap();
 
-        let mut hash_cmd = make_command("hash_file_sha256");
-        hash_cmd.path = Some(file.path().to_string_lossy().into_owned());
-        handle_command(hash_cmd).expect("hash dispatch should succeed");
+        handle_command(make_command("file_info_screen")).unwrap();
+        let mut cmd = make_command("file_info");
+        cmd.path = Some(file.path().to_string_lossy().into_owned());
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "Container: MP4 (isom)");
+        assert_contains_text(&ui, "Duration: 0:10");
+        assert_contains_text(&ui, "Video: avc1 1280×720");
+        assert_contains_text(&ui, "Audio: mp4a 48000 Hz, 2 ch");
+    }
 
-        let start = Instant::now();
-        let inc_ui = handle_command(make_command("increment"))
-            .expect("increment should not be blocked by worker");
-        assert!(
-            start.elapsed() < Duration::from_millis(100),
-            "increment waited too long for state mutex"
-        );
-        assert_contains_text(&inc_ui, "Tool menu");
+    #[test]
+    fn file_info_flags_extension_that_does_not_match_content() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
 
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.counter, 1);
-        drop(state);
+        let mut file = tempfile::Builder::new().suffix(".bin").tempfile().unwrap();
+        file.write_all(b"{\"name\": \"kistaverk\", \"tags\": [1, 2, 3]}\n")
+            .unwrap();
+        file.flush().unwrap();
 
-        std::thread::sleep(Duration::from_millis(350));
-        handle_command(make_command("init")).expect("refresh should apply worker result");
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.last_hash.as_deref(), Some(SHA256_ABC));
-        assert!(state.last_error.is_none());
+        handle_command(make_command("file_info_screen")).unwrap();
+        let mut cmd = make_command("file_info");
+        cmd.path = Some(file.path().to_string_lossy().into_owned());
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "Content analysis");
+        assert_contains_text(&ui, "Mostly text");
+        assert_contains_text(&ui, "Detected from content: application/json");
+        assert_contains_text(&ui, "Extension does not match content: .bin → .json");
+    }
 
-        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
-        TEST_WORKER_DELAY_MS.store(0, Ordering::SeqCst);
+    #[test]
+    fn file_info_lists_apk_package_and_signing_certificates() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut file = tempfile::Builder::new().suffix(".apk").tempfile().unwrap();
+        file.write_all(&crate::features::apk_info::tests::sample_apk())
+            .unwrap();
+        file.flush().unwrap();
+
+        handle_command(make_command("file_info_screen")).unwrap();
+        let mut cmd = make_command("file_info");
+        cmd.path = Some(file.path().to_string_lossy().into_owned());
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "Android package (APK)");
+        assert_contains_text(&ui, "Package: aeska.kistaverk");
+        assert_contains_text(&ui, "Version: 1.4.2 (42)");
+        assert_contains_text(&ui, "Target SDK: 34");
+        assert_contains_text(&ui, "Signature schemes: v1 (JAR), v2");
+        assert_contains_text(&ui, "Certificate: CN=Kistaverk");
     }
 
     #[test]
-    fn archive_open_enqueues_and_releases_mutex() {
+    fn file_info_shows_matched_signature_and_confidence() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
 
-        TEST_FORCE_ASYNC_WORKER.store(true, Ordering::SeqCst);
-        TEST_WORKER_DELAY_MS.store(200, Ordering::SeqCst);
+        let mut file = tempfile::Builder::new().suffix(".db").tempfile().unwrap();
+        let mut header = b"SQLite format 3\0".to_vec();
+        header.resize(4096, 0);
+        file.write_all(&header).unwrap();
+        file.flush().unwrap();
 
-        let mut zip_file = NamedTempFile::new().unwrap();
-        {
-            let mut writer = zip::ZipWriter::new(&mut zip_file);
-            let options =
-                FileOptions::default().compression_method(zip::CompressionMethod::Stored);
-            writer.start_file("note.txt", options).unwrap();
-            writer.write_all(b"hello from zip").unwrap();
-            writer.finish().unwrap();
-        }
+        handle_command(make_command("file_info_screen")).unwrap();
+        let mut cmd = make_command("file_info");
+        cmd.path = Some(file.path().to_string_lossy().into_owned());
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "MIME: application/vnd.sqlite3");
+        assert_contains_text(&ui, "Signature: 53 51 4C 69 74 65 20 66");
+        assert_contains_text(&ui, "@ 0 — SQLite 3 database");
+        assert_contains_text(&ui, "Confidence: high");
+        let texts = extract_texts(&ui);
+        assert!(!texts.iter().any(|t| t.contains("Extension does not match")));
+    }
 
-        let fd = File::open(zip_file.path()).unwrap().into_raw_fd();
-        let mut open_cmd = make_command("archive_open");
-        open_cmd.fd = Some(fd);
-        open_cmd.path = Some(zip_file.path().to_string_lossy().into_owned());
+    #[test]
+    fn file_info_shows_a_thumbnail_for_images() {
+        use image::codecs::png::PngEncoder;
+        use image::{ColorType, ImageEncoder};
 
-        let start = Instant::now();
-        let ui = handle_command(open_cmd).expect("archive open dispatch should succeed");
-        assert!(
-            start.elapsed() < Duration::from_millis(100),
-            "dispatch held the UI mutex for too long"
-        );
-        assert_contains_text(&ui, "Opening archive");
-        assert!(
-            STATE.get_or_init(GlobalState::new).ui_try_lock().is_some(),
-            "state mutex should be free while archive worker runs"
-        );
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
 
-        std::thread::sleep(Duration::from_millis(250));
-        handle_command(make_command("init")).expect("refresh after worker should succeed");
+        let pixels = vec![128u8; 320 * 200 * 3];
+        let mut png = Vec::new();
+        PngEncoder::new(&mut png)
+            .write_image(&pixels, 320, 200, ColorType::Rgb8)
+            .unwrap();
+        let mut file = tempfile::Builder::new().suffix(".png").tempfile().unwrap();
+        file.write_all(&png).unwrap();
+        file.flush().unwrap();
+
+        handle_command(make_command("file_info_screen")).unwrap();
+        let mut cmd = make_command("file_info");
+        cmd.path = Some(file.path().to_string_lossy().into_owned());
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "Preview: 160×100 · decoded image");
+        assert!(ui.to_string().contains("\"content_description\":\"file_info_thumbnail\""));
+    }
+
+    #[test]
+    fn file_info_hex_window_pages_through_the_file() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&(0..2048u32).map(|i| i as u8).collect::<Vec<_>>())
+            .unwrap();
+        file.flush().unwrap();
+
+        handle_command(make_command("file_info_screen")).unwrap();
+        let mut cmd = make_command("file_info");
+        cmd.path = Some(file.path().to_string_lossy().into_owned());
+        handle_command(cmd).unwrap();
+
+        let mut cmd = make_command("file_info_hex");
+        cmd.bindings = Some(HashMap::from([
+            ("file_info_hex_offset".to_string(), "0x400".to_string()),
+            ("file_info_hex_length".to_string(), "64".to_string()),
+        ]));
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "Showing: 0x00000400–0x0000043f / 2048");
+
+        handle_command(make_command("file_info_hex_next")).unwrap();
+        let ui = handle_command(make_command("file_info_hex_next")).unwrap();
+        assert_contains_text(&ui, "Showing: 0x00000480–0x000004bf / 2048");
+
+        let ui = handle_command(make_command("file_info_hex_prev")).unwrap();
+        assert_contains_text(&ui, "Showing: 0x00000440–0x0000047f / 2048");
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert!(state
-            .archive
-            .entries
-            .iter()
-            .any(|e| e.name == "note.txt"));
-        assert!(matches!(state.current_screen(), Screen::ArchiveTools));
+        let window = state.file_info_hex.as_ref().unwrap();
+        assert!(window.dump.starts_with("00000440  40 41 42"));
+    }
+
+    #[test]
+    fn file_info_batch_compares_files_and_hashes_on_demand() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        let mut db = tempfile::Builder::new().suffix(".bin").tempfile().unwrap();
+        let mut header = b"SQLite format 3\0".to_vec();
+        header.resize(2048, 0);
+        db.write_all(&header).unwrap();
+        db.flush().unwrap();
+        let mut text = tempfile::Builder::new().suffix(".txt").tempfile().unwrap();
+        text.write_all(b"hello").unwrap();
+        text.flush().unwrap();
+
+        handle_command(make_command("file_info_screen")).unwrap();
+        let mut cmd = make_command("file_info");
+        cmd.path_list = Some(vec![
+            db.path().to_string_lossy().into_owned(),
+            text.path().to_string_lossy().into_owned(),
+        ]);
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "Files: 2");
+        assert_contains_text(&ui, "SQLite 3 database ⚠");
+        assert_contains_text(&ui, "2.0 KB");
+        assert_contains_text(&ui, "text/plain");
+
+        let ui = handle_command(make_command("file_info_batch_hash:1")).unwrap();
+        assert_contains_text(&ui, "2cf24dba5fb0a30e…");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert!(state.file_info_batch[0].sha256.is_none());
+        assert_eq!(
+            state.file_info_batch[1].sha256.as_deref(),
+            Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+        );
+    }
+
+    #[test]
+    fn picked_files_are_remembered_and_offered_for_reopening() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        let cache = data_dir.path().join("cache");
+        std::fs::create_dir_all(&cache).unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", &cache);
+        reset_state();
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"hello").unwrap();
+        file.flush().unwrap();
 
-        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
-        TEST_WORKER_DELAY_MS.store(0, Ordering::SeqCst);
+        handle_command(make_command("file_info_screen")).unwrap();
+        let mut cmd = make_command("file_info");
+        cmd.fd = Some(File::open(file.path()).unwrap().into_raw_fd());
+        cmd.path = Some("content://docs/report.txt".into());
+        cmd.bindings = Some(HashMap::from([(
+            "file_name".to_string(),
+            "report.txt".to_string(),
+        )]));
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "Reopen recent");
+        assert_contains_text(&ui, "report.txt");
+
+        let saved = crate::features::storage::load_recent_files().unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].uri, "content://docs/report.txt");
+        assert_eq!(saved[0].tool, "file_info");
+
+        let ui = handle_command(make_command("back")).unwrap();
+        assert_contains_text(&ui, "Recent files");
+        assert_contains_text(&ui, "report.txt · 📂 File Inspector");
+
+        handle_command(make_command("recent_files_clear")).unwrap();
+        assert_eq!(
+            crate::features::storage::load_recent_files().unwrap().len(),
+            1
+        );
+        handle_command(make_command("confirm_pending")).unwrap();
+        assert!(crate::features::storage::load_recent_files()
+            .unwrap()
+            .is_empty());
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn hash_file_sha1_via_fd_updates_ui_and_state() {
+    fn cleanup_screen_lists_generated_files_and_deletes_selection() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        let cache = data_dir.path().join("cache");
+        std::fs::create_dir_all(&cache).unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", &cache);
         reset_state();
 
-        let mut file = NamedTempFile::new().unwrap();
-        file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
-        file.flush().unwrap();
+        std::fs::write(cache.join("dither_src_a.png"), [0u8; 2048]).unwrap();
+        let output = data_dir.path().join("notes.txt.gz");
+        std::fs::write(&output, b"gz").unwrap();
+        record_output(&output);
 
-        let fd = File::open(file.path()).unwrap().into_raw_fd();
+        let ui = handle_command(make_command("cleanup_screen")).unwrap();
+        assert_contains_text(&ui, "Storage cleanup");
+        assert_contains_text(&ui, "Temporary files (1, 2.0 KB)");
+        assert_contains_text(&ui, "dither_src_a.png — 2.0 KB");
+        assert_contains_text(&ui, "Generated outputs (1, 2 B)");
 
-        let mut command = make_command("hash_file_sha1");
-        command.fd = Some(fd);
+        let index = {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            state
+                .cleanup
+                .entries
+                .iter()
+                .position(|e| e.name == "dither_src_a.png")
+                .unwrap()
+        };
+        let mut cmd = make_command(&format!("cleanup_toggle:{index}"));
+        cmd.bindings = Some(HashMap::from([(
+            format!("cleanup_select_{index}"),
+            "true".to_string(),
+        )]));
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "Selected: 1 (2.0 KB)");
 
-        let ui = handle_command(command).expect("hash command should succeed");
+        handle_command(make_command("cleanup_delete_selected")).unwrap();
+        assert!(cache.join("dither_src_a.png").exists());
+        let ui = handle_command(make_command("confirm_pending")).unwrap();
+        assert_contains_text(&ui, "Deleted: 1 (2.0 KB)");
+        assert!(!cache.join("dither_src_a.png").exists());
+        assert!(output.exists());
 
-        assert_contains_text(&ui, &format!("SHA-1: {SHA1_ABC}"));
+        let mut cmd = make_command("cleanup_save_policy");
+        cmd.bindings = Some(HashMap::from([
+            ("cleanup_auto_clean".to_string(), "true".to_string()),
+            ("cleanup_max_age_days".to_string(), "0".to_string()),
+        ]));
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "cleanup_invalid_max_age");
 
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.last_hash.as_deref(), Some(SHA1_ABC));
-        assert_eq!(state.last_hash_algo.as_deref(), Some("SHA-1"));
-        assert!(state.last_error.is_none());
+        let mut cmd = make_command("cleanup_save_policy");
+        cmd.bindings = Some(HashMap::from([
+            ("cleanup_auto_clean".to_string(), "true".to_string()),
+            ("cleanup_max_age_days".to_string(), "3".to_string()),
+        ]));
+        let ui = handle_command(cmd).unwrap();
+        assert_contains_text(&ui, "Policy saved.");
+        let policy = crate::features::cleanup::load_policy();
+        assert!(policy.auto_clean);
+        assert_eq!(policy.temp_max_age_days, 3);
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn text_tools_uppercase_consumes_binding_and_updates_state() {
+    fn text_viewer_find_clear_removes_query() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
 
-        let mut command = make_command("text_tools_upper");
-        command.bindings = Some(HashMap::from([("text_input".into(), "Hello rust".into())]));
+        handle_command(make_command("text_viewer_screen")).expect("enter text viewer");
 
-        let ui = handle_command(command).expect("text command should succeed");
+        let mut find_cmd = make_command("text_viewer_find_submit");
+        find_cmd.bindings = Some(HashMap::from_iter([("find_query".into(), "needle".into())]));
+        handle_command(find_cmd).expect("set find query");
 
-        assert_contains_text(&ui, "HELLO RUST");
+        let mut clear_cmd = make_command("text_viewer_find_clear");
+        clear_cmd.bindings = Some(HashMap::from_iter([("find_query".into(), "".into())]));
+        let ui = handle_command(clear_cmd).expect("clear find query");
 
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.text_input.as_deref(), Some("Hello rust"));
-        assert_eq!(state.text_output.as_deref(), Some("HELLO RUST"));
-        assert!(matches!(state.current_screen(), Screen::TextTools));
+        assert!(state.text_view_find_query.is_none());
+        assert_eq!(
+            state.text_view_find_match.as_deref(),
+            Some("Cleared search")
+        );
+        drop(state);
+
+        assert!(ui.get("find_query").is_none());
     }
 
     #[test]
-    fn text_tools_word_count_reports_count() {
+    fn pdf_select_sets_aspect_ratio() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
+        TEST_FORCE_ASYNC_WORKER.store(true, Ordering::SeqCst);
 
-        let mut command = make_command("text_tools_word_count");
-        command.bindings = Some(HashMap::from([(
-            "text_input".into(),
-            "one two  three".into(),
-        )]));
+        // Build a tiny PDF with known aspect ratio 2:1
+        let mut doc = lopdf::Document::with_version("1.4");
+        let page_id = doc.new_object_id();
+        let contents_id = doc.add_object(lopdf::Stream::new(dictionary! {}, Vec::new()));
+        let pages_id = doc.add_object(dictionary! {
+            "Type" => "Pages",
+            "Kids" => vec![lopdf::Object::Reference(page_id)],
+            "Count" => 1i64
+        });
+        let media_box = vec![0.into(), 0.into(), 200.into(), 100.into()];
+        let page_dict = dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "MediaBox" => media_box,
+            "Contents" => contents_id,
+            "Resources" => dictionary! {}
+        };
+        doc.objects.insert(page_id, lopdf::Object::Dictionary(page_dict));
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id
+        });
+        doc.trailer.set("Root", catalog_id);
 
-        let ui = handle_command(command).expect("text command should succeed");
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        doc.save(file.path()).unwrap();
+        file.flush().unwrap();
 
-        assert_contains_text(&ui, "Word count: 3");
+        let mut cmd = make_command("pdf_select");
+        cmd.fd = Some(File::open(file.path()).unwrap().into_raw_fd());
+        let _ = handle_command(cmd).expect("pdf_select should enqueue");
+
+        thread::sleep(Duration::from_millis(10));
+        let _ = handle_command(make_command("snapshot")).unwrap();
 
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.text_output.as_deref(), Some("Word count: 3"));
+        assert_eq!(state.pdf.page_count, Some(1));
+        assert_eq!(state.pdf.page_aspect_ratio, Some(2.0));
+
+        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
     }
 
     #[test]
-    fn text_tools_wrap_splits_lines() {
+    fn pdf_sign_runs_on_worker() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
+        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
 
-        let mut command = make_command("text_tools_wrap");
-        command.bindings = Some(HashMap::from([("text_input".into(), SAMPLE_WRAP.into())]));
+        // Build a simple single-page PDF
+        let mut doc = lopdf::Document::with_version("1.4");
+        let page_id = doc.new_object_id();
+        let contents_id = doc.add_object(lopdf::Stream::new(dictionary! {}, Vec::new()));
+        let pages_id = doc.add_object(dictionary! {
+            "Type" => "Pages",
+            "Kids" => vec![lopdf::Object::Reference(page_id)],
+            "Count" => 1i64
+        });
+        let media_box = vec![0.into(), 0.into(), 200.into(), 200.into()];
+        let page_dict = dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "MediaBox" => media_box,
+            "Contents" => contents_id,
+            "Resources" => dictionary! {}
+        };
+        doc.objects.insert(page_id, lopdf::Object::Dictionary(page_dict));
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id
+        });
+        doc.trailer.set("Root", catalog_id);
 
-        let ui = handle_command(command).expect("text command should succeed");
-        let texts = extract_texts(&ui);
-        // Wrapped text should introduce a line break (result block shows raw text)
-        let result = texts
-            .into_iter()
-            .find(|t| t == "Result")
-            .and_then(|_| {
-                // result text is next entry after label in traversal
-                STATE.get_or_init(GlobalState::new).ui_try_lock().and_then(|s| s.text_output.clone())
-            })
-            .unwrap_or_default();
+        let mut pdf_file = tempfile::NamedTempFile::new().unwrap();
+        doc.save(pdf_file.path()).unwrap();
+        pdf_file.flush().unwrap();
+
+        // 1x1 transparent PNG
+        let mut png_buf = Vec::new();
+        {
+            let img = image::RgbaImage::from_pixel(1, 1, image::Rgba([0, 0, 0, 0]));
+            let encoder = PngEncoder::new(&mut png_buf);
+            encoder.write_image(&img, 1, 1, ColorType::Rgba8).unwrap();
+        }
+        use base64::Engine;
+        let sig_b64 = base64::engine::general_purpose::STANDARD.encode(&png_buf);
+
+        let mut cmd = make_command("pdf_sign");
+        cmd.fd = Some(File::open(pdf_file.path()).unwrap().into_raw_fd());
+        cmd.bindings = Some(HashMap::from_iter([
+            ("signature_base64".into(), sig_b64),
+            ("pdf_signature_page".into(), "1".into()),
+            ("pdf_signature_x_pct".into(), "0.5".into()),
+            ("pdf_signature_y_pct".into(), "0.5".into()),
+            ("pdf_signature_x".into(), "10".into()),
+            ("pdf_signature_y".into(), "20".into()),
+            ("pdf_signature_width".into(), "30".into()),
+            ("pdf_signature_height".into(), "40".into()),
+        ]));
+
+        let _ui = handle_command(cmd).expect("pdf_sign should enqueue");
+
+        let _ = handle_command(make_command("snapshot")).unwrap();
 
-        assert!(
-            result.contains('\n'),
-            "expected wrapped text to contain newline, got {result:?}"
-        );
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert!(state
-            .text_output
-            .as_deref()
-            .unwrap_or_default()
-            .contains('\n'));
+        if let Some(err) = &state.pdf.last_error {
+            panic!("pdf_sign worker returned error: {err}");
+        }
+        assert!(state.pdf.last_output.is_some());
+        assert_eq!(state.current_screen(), Screen::PdfTools);
+
+        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
     }
 
     #[test]
-    fn text_tools_trim_respects_aggressive_flag() {
+    fn pdf_sign_grid_updates_coordinates() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
+        {
+            let mut state = STATE.get_or_init(GlobalState::new).ui_lock();
+            state.pdf.page_count = Some(3);
+            state.pdf.source_uri = Some("file://dummy.pdf".into());
+        }
 
-        let mut command = make_command("text_tools_trim");
-        command.bindings = Some(HashMap::from([
-            ("text_input".into(), "  a   b  ".into()),
-            ("aggressive_trim".into(), "true".into()),
+        let mut cmd = make_command("pdf_sign_grid");
+        cmd.bindings = Some(HashMap::from_iter([
+            ("pdf_signature_page".into(), "2".into()),
+            ("pdf_signature_x_pct".into(), "0.8".into()),
+            ("pdf_signature_y_pct".into(), "0.3".into()),
         ]));
-
-        let ui = handle_command(command).expect("trim command should succeed");
-        assert_contains_text(&ui, "Trim spacing (collapse)");
+        handle_command(cmd).expect("grid action should succeed");
 
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.text_output.as_deref(), Some("a b"));
+        assert_eq!(state.pdf.signature_target_page, Some(2));
+        assert_eq!(state.pdf.signature_x_pct, Some(0.8));
+        assert_eq!(state.pdf.signature_y_pct, Some(0.3));
+        assert_eq!(state.pdf.signature_grid_selection, Some((2, 0.8, 0.3)));
+    }
 
-        drop(state);
-        reset_state();
+    #[test]
+    fn render_menu_uses_icon_without_dup_title() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let state = AppState::new();
+        let catalog = vec![Feature {
+            id: "hash_sha256",
+            name: "SHA-256",
+            category: "🔐 Hashes",
+            action: "hash_file_sha256",
+            requires_file_picker: true,
+            description: "secure hash",
+        }];
+        let ui = render_menu(&state, &catalog);
+        fn find_section(node: &Value) -> Option<&Value> {
+            if node.get("type").and_then(|t| t.as_str()) == Some("Section") {
+                return Some(node);
+            }
+            if let Some(children) = node.get("children").and_then(|c| c.as_array()) {
+                for child in children {
+                    if let Some(sec) = find_section(child) {
+                        return Some(sec);
+                    }
+                }
+            }
+            None
+        }
+        let section = find_section(&ui).expect("section present");
+        assert_eq!(section.get("icon").and_then(|v| v.as_str()), Some("🔐"));
+        assert_eq!(section.get("title").and_then(|v| v.as_str()), Some("Hashes"));
+    }
+
+    #[test]
+    fn render_menu_filters_fuzzily_by_name_category_and_description() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let catalog = vec![
+            Feature {
+                id: "hash_sha256",
+                name: "SHA-256",
+                category: "🔐 Hashes",
+                action: "hash_file_sha256",
+                requires_file_picker: true,
+                description: "secure hash",
+            },
+            Feature {
+                id: "multi_hash",
+                name: "Multi-hash",
+                category: "🔐 Hashes",
+                action: "multi_hash_screen",
+                requires_file_picker: false,
+                description: "Compute MD5, SHA-1, SHA-256, BLAKE3",
+            },
+            Feature {
+                id: "doc_tools",
+                name: "📄 PDF tools",
+                category: "📄 Documents",
+                action: "pdf_tools_screen",
+                requires_file_picker: false,
+                description: "merge, split, reorder",
+            },
+        ];
+        let tools_for = |query: &str| {
+            let mut state = AppState::new();
+            state.home_filter = query.into();
+            let ui = render_menu(&state, &catalog);
+            extract_texts(&ui)
+                .into_iter()
+                .filter(|t| t.contains(" – "))
+                .collect::<Vec<_>>()
+        };
 
-        let mut command2 = make_command("text_tools_trim");
-        command2.bindings = Some(HashMap::from([
-            ("text_input".into(), "  a   b  ".into()),
-            ("aggressive_trim".into(), "false".into()),
-        ]));
+        let tools = tools_for("mhsh");
+        assert_eq!(tools.len(), 1);
+        assert!(tools[0].starts_with("Multi-hash"));
+        assert_eq!(tools_for("HASHES").len(), 2);
+        assert_eq!(tools_for("docs split").len(), 1);
+        assert_eq!(tools_for("blake3").len(), 1);
+        assert!(tools_for("docs blake3").is_empty());
+
+        let mut state = AppState::new();
+        state.home_filter = "zzz".into();
+        assert_contains_text(&render_menu(&state, &catalog), "No matching tools.");
+    }
 
-        let ui2 = handle_command(command2).expect("trim command should succeed");
-        assert_contains_text(&ui2, "Trim edges");
-        let state2 = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state2.text_output.as_deref(), Some("a   b"));
+    #[test]
+    fn catalog_is_translated_in_every_supported_locale() {
+        for &(code, _) in i18n::SUPPORTED_LOCALES {
+            for feature in feature_catalog() {
+                let (_, category) = feature.category.split_once(' ').unwrap();
+                for key in [
+                    format!("tool_{}", feature.id),
+                    format!("tool_{}_description", feature.id),
+                    format!("category_{}", category.to_lowercase()),
+                ] {
+                    let text = t!(&key, locale = code);
+                    assert!(!text.ends_with(key.as_str()), "{code} lacks {key}");
+                }
+            }
+        }
+
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let mut state = AppState::new();
+        state.preferred_locale = "de".into();
+        i18n::apply_locale(&mut state);
+        let ui = render_menu(&state, &feature_catalog());
+        state.preferred_locale.clear();
+        i18n::apply_locale(&mut state);
+        assert_contains_text(&ui, "Prüfsumme prüfen – mit Referenz vergleichen");
+        assert!(ui.to_string().contains(r#""title":"Prüfsummen""#));
     }
 
     #[test]
-    fn back_from_home_does_not_underflow_stack() {
+    fn c_scripting_open_loads_the_picked_source() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hello.c");
+        std::fs::write(&path, "int main() { return 7; }\n").unwrap();
 
-        let ui = handle_command(make_command("back")).expect("back should succeed");
-        assert_contains_text(&ui, "Tool menu");
+        let mut cmd = make_command("c_scripting_open");
+        cmd.path = Some(path.to_string_lossy().into_owned());
+        handle_command(cmd).expect("open should succeed");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(matches!(state.current_screen(), Screen::CScripting));
+            assert_eq!(state.c_scripting.source, "int main() { return 7; }\n");
+            assert!(state.c_scripting.error.is_none());
+        }
 
+        let mut cmd = make_command("c_scripting_open");
+        cmd.path = Some(dir.path().join("missing.c").to_string_lossy().into_owned());
+        handle_command(cmd).expect("a failed read still renders");
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.nav_depth(), 1);
-        assert!(matches!(state.current_screen(), Screen::Home));
+        assert_eq!(state.c_scripting.source, "int main() { return 7; }\n");
+        let error = state.c_scripting.error.as_deref().unwrap();
+        assert!(error.starts_with("read_failed:"));
     }
 
     #[test]
-    fn back_pops_to_previous_screen() {
+    fn plugins_install_open_act_and_remove() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        let cache = data_dir.path().join("cache");
+        std::fs::create_dir_all(&cache).unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", &cache);
         reset_state();
 
-        handle_command(make_command("text_tools_screen")).expect("screen switch should work");
+        let picked = data_dir.path().join("picked.tmp");
+        std::fs::write(
+            &picked,
+            r#"
+int snprintf(char *out, unsigned long size, const char *format, ...);
+int atoi(const char *text);
+static char state[16];
+static char ui[128];
+const char *kv_render(const char *current) {
+    snprintf(ui, sizeof ui, "{\"type\":\"Text\",\"text\":\"Taps: %d\"}", atoi(current));
+    return ui;
+}
+const char *kv_handle_action(const char *current, const char *action, const char *bindings) {
+    snprintf(state, sizeof state, "%d", atoi(current) + 1);
+    return state;
+}
+"#,
+        )
+        .unwrap();
+
+        let mut cmd = make_command("plugin_install");
+        cmd.path = Some(picked.to_string_lossy().into_owned());
+        cmd.bindings = Some(HashMap::from([(
+            "file_name".into(),
+            "tap_counter.c".into(),
+        )]));
+        handle_command(cmd).expect("install should succeed");
         {
             let state = STATE.get_or_init(GlobalState::new).ui_lock();
-            assert_eq!(state.nav_depth(), 2);
-            assert!(matches!(state.current_screen(), Screen::TextTools));
+            assert!(matches!(state.current_screen(), Screen::Plugins));
+            assert!(state.plugins.error.is_none());
+            assert_eq!(state.plugins.installed.len(), 1);
+            assert_eq!(state.plugins.installed[0].name, "tap counter");
         }
 
-        handle_command(make_command("back")).expect("back should succeed");
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.nav_depth(), 1);
-        assert!(matches!(state.current_screen(), Screen::Home));
+        let mut cmd = make_command("plugin_open");
+        cmd.bindings = Some(HashMap::from([(
+            "plugin_id".into(),
+            "tap_counter.c".into(),
+        )]));
+        handle_command(cmd).expect("open should succeed");
+        handle_command(make_command("plugin:tap")).expect("action should succeed");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(matches!(state.current_screen(), Screen::Plugin));
+            assert!(!state.plugins.running);
+            assert_eq!(state.plugins.data, "1");
+            assert!(state
+                .plugins
+                .ui
+                .as_ref()
+                .unwrap()
+                .to_string()
+                .contains("Taps: 1"));
+        }
+
+        let mut cmd = make_command("plugin_remove");
+        cmd.bindings = Some(HashMap::from([
+            ("plugin_id".into(), "tap_counter.c".into()),
+            ("confirmed".into(), "true".into()),
+        ]));
+        handle_command(cmd).expect("remove should succeed");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(state.plugins.installed.is_empty());
+            assert!(state.plugins.active.is_none());
+        }
+        assert!(!data_dir.path().join("plugins/tap_counter.c").exists());
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn snapshot_and_restore_round_trip() {
+    fn cert_open_and_paste_show_certificates() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("server.pem");
+        std::fs::write(&path, crate::features::cert_inspector::TEST_CERT_PEM).unwrap();
 
-        // Move into text tools and set some state
-        let mut cmd = make_command("text_tools_upper");
-        cmd.bindings = Some(HashMap::from([("text_input".into(), "hi".into())]));
-        handle_command(cmd).expect("text action should succeed");
+        let mut cmd = make_command("cert_open");
+        cmd.path = Some(path.to_string_lossy().into_owned());
+        handle_command(cmd).expect("open should succeed");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(matches!(state.current_screen(), Screen::CertInspector));
+            assert_eq!(state.cert_inspector.source.as_deref(), Some("server.pem"));
+            assert_eq!(state.cert_inspector.certificates.len(), 1);
+            assert!(state.cert_inspector.error.is_none());
+        }
 
-        let snap_value = handle_command(make_command("snapshot")).expect("snapshot should succeed");
-        let snap_str = snap_value
-            .get("snapshot")
-            .and_then(|v| v.as_str())
-            .expect("snapshot missing");
+        let mut cmd = make_command("cert_inspect");
+        cmd.bindings = Some(HashMap::from([("cert_input".into(), "hello".into())]));
+        let ui = handle_command(cmd).expect("a bad paste still renders");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert!(state.cert_inspector.certificates.is_empty());
+        assert!(state.cert_inspector.source.is_none());
+        assert_eq!(state.cert_inspector.input, "hello");
+        assert!(state.cert_inspector.error.is_some());
+        assert!(ui.to_string().contains("cert_error"));
+    }
 
-        // Reset state and ensure we go back to home
+    #[test]
+    fn password_strength_checks_typed_passwords() {
+        let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
+
+        let mut cmd = make_command("password_strength_check");
+        cmd.bindings = Some(HashMap::from([(
+            "password_input".into(),
+            "dragon2024".into(),
+        )]));
+        let ui = handle_command(cmd).expect("check should succeed");
         {
             let state = STATE.get_or_init(GlobalState::new).ui_lock();
-            assert!(matches!(state.current_screen(), Screen::Home));
-            assert!(state.text_output.is_none());
-        }
+            assert!(matches!(state.current_screen(), Screen::PasswordStrength));
+            let report = state.password_strength.report.as_ref().expect("report");
+            assert!(report.score <= 2);
+            assert!(!state.password_strength.show);
+        }
+        assert!(ui.to_string().contains("password_warning"));
+        // While masked, only the input itself carries the password.
+        let mentions = |ui: &Value| {
+            extract_texts(ui)
+                .iter()
+                .filter(|t| t.contains("dragon"))
+                .count()
+        };
+        assert_eq!(mentions(&ui), 1);
 
-        let mut restore_cmd = make_command("restore_state");
-        restore_cmd.snapshot = Some(snap_str.to_string());
-        let ui_after_restore =
-            handle_command(restore_cmd).expect("restore should succeed and return UI");
-        assert_contains_text(&ui_after_restore, "Result");
+        let mut cmd = make_command("password_strength_check");
+        cmd.bindings = Some(HashMap::from([("password_show".into(), "true".into())]));
+        let ui = handle_command(cmd).expect("toggle should succeed");
+        assert!(mentions(&ui) > 1);
 
+        let mut cmd = make_command("password_strength_check");
+        cmd.bindings = Some(HashMap::from([("password_input".into(), "x".repeat(200))]));
+        handle_command(cmd).expect("a long input still renders");
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert!(matches!(state.current_screen(), Screen::TextTools));
-        assert_eq!(state.text_output.as_deref(), Some("HI"));
-        assert_eq!(state.text_input.as_deref(), Some("hi"));
+        assert_eq!(
+            state.password_strength.error.as_deref(),
+            Some("password_too_long")
+        );
+        assert!(state.password_strength.report.is_none());
     }
 
     #[test]
-    fn text_tools_base64_roundtrip() {
+    fn date_calc_computes_every_section() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
 
-        let mut enc = make_command("text_tools_base64_encode");
-        enc.bindings = Some(HashMap::from([("text_input".into(), "hi".into())]));
-        handle_command(enc).expect("encode should work");
+        handle_command(make_command("date_calc_screen")).expect("screen should render");
+        let mut cmd = make_command("date_calc_compute");
+        cmd.bindings = Some(HashMap::from([
+            ("date_a".into(), "2024-01-31".into()),
+            ("date_b".into(), "2024-03-15".into()),
+            ("date_shift_amount".into(), "1".into()),
+            ("date_shift_unit".into(), "months".into()),
+            ("date_tz_time".into(), "2024-07-01 18:00".into()),
+            ("date_tz_from".into(), "Europe/Paris".into()),
+            ("date_tz_to".into(), "america/new york".into()),
+            ("date_cron".into(), "30 9 * * 1-5".into()),
+        ]));
+        let ui = handle_command(cmd).expect("compute should succeed");
         {
             let state = STATE.get_or_init(GlobalState::new).ui_lock();
-            assert_eq!(state.text_output.as_deref(), Some("aGk="));
+            assert!(matches!(state.current_screen(), Screen::DateCalc));
+            let calc = &state.date_calc;
+            assert_eq!(calc.difference.as_ref().map(|d| d.days), Some(44));
+            assert_eq!(
+                calc.shifted.as_ref().map(|d| d.text.as_str()),
+                Some("2024-02-29")
+            );
+            assert_eq!(calc.cron_runs.len(), 5);
+            assert!(calc.tz_error.is_none() && calc.cron_error.is_none());
         }
+        assert_contains_text(&ui, "2024-07-01 12:00 EDT (UTC-04:00)");
 
-        let mut dec = make_command("text_tools_base64_decode");
-        dec.bindings = Some(HashMap::from([("text_input".into(), "aGk=".into())]));
-        let ui = handle_command(dec).expect("decode should work");
-        assert_contains_text(&ui, "hi");
+        let mut cmd = make_command("date_calc_swap_tz");
+        cmd.bindings = Some(HashMap::from([(
+            "date_tz_to".into(),
+            "Mars/Olympus".into(),
+        )]));
+        handle_command(cmd).expect("swap should succeed");
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.text_output.as_deref(), Some("hi"));
+        assert_eq!(state.date_calc.from_tz, "Mars/Olympus");
+        assert_eq!(state.date_calc.to_tz, "Europe/Paris");
+        assert_eq!(
+            state.date_calc.tz_error.as_deref(),
+            Some("tz_unknown:Mars/Olympus")
+        );
+        assert!(state.date_calc.conversion.is_none());
     }
 
     #[test]
-    fn text_tools_hex_roundtrip() {
+    fn hash_verify_computes_keyed_digests_in_hmac_mode() {
+        const HMAC_SHA256_KEY_ABC: &str =
+            "9c196e32dc0175f86f4b1cb89289d6619de6bee699e4c378e68309ed97a1a6ab";
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
 
-        let mut enc = make_command("text_tools_hex_encode");
-        enc.bindings = Some(HashMap::from([("text_input".into(), "hi".into())]));
-        handle_command(enc).expect("encode should work");
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let mut config = make_command("hash_hmac_config");
+        config.bindings = Some(HashMap::from([("hmac_enabled".into(), "true".into())]));
+        let ui = handle_command(config).expect("config should succeed");
+        assert!(ui.to_string().contains("\"bind_key\":\"hmac_key\""));
+
+        let verify = |key: &str, reference: &str| {
+            let mut command = make_command("hash_verify");
+            command.path = Some(file.path().to_string_lossy().into_owned());
+            command.bindings = Some(HashMap::from([
+                ("hash_reference".into(), reference.into()),
+                ("hmac_key".into(), key.into()),
+            ]));
+            handle_command(command).expect("verify should succeed")
+        };
+
+        let ui = verify("", "");
         {
             let state = STATE.get_or_init(GlobalState::new).ui_lock();
-            assert_eq!(state.text_output.as_deref(), Some("6869"));
+            assert_eq!(state.last_error.as_deref(), Some("hmac_key_empty"));
         }
+        assert!(!ui.to_string().contains(HMAC_SHA256_KEY_ABC));
 
-        let mut dec = make_command("text_tools_hex_decode");
-        dec.bindings = Some(HashMap::from([("text_input".into(), "6869".into())]));
-        let ui = handle_command(dec).expect("decode should work");
-        assert_contains_text(&ui, "hi");
+        // Without a reference the digest is shown but not judged.
+        let ui = verify("key", "");
+        assert_contains_text(&ui, &format!("HMAC-SHA256: {HMAC_SHA256_KEY_ABC}"));
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.hash_match, None);
+            assert!(state.last_error.is_none());
+        }
+
+        verify("key", &HMAC_SHA256_KEY_ABC.to_ascii_uppercase());
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.hash_match, Some(true));
+            assert!(!state.to_persisted().unwrap().contains("hmac"));
+        }
+
+        let mut config = make_command("hash_hmac_config");
+        config.bindings = Some(HashMap::from([("hmac_algo".into(), "blake3".into())]));
+        handle_command(config).expect("config should succeed");
+        verify("key", HMAC_SHA256_KEY_ABC);
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.text_output.as_deref(), Some("hi"));
+        assert_eq!(
+            state.last_error.as_deref(),
+            Some("hmac_blake3_key_length:3")
+        );
+        assert!(matches!(state.current_screen(), Screen::HashVerify));
     }
 
     #[test]
-    fn sensor_bindings_default_and_interval_clamp() {
-        let cfg =
-            parse_sensor_bindings(&HashMap::from([("sensor_interval_ms".into(), "5".into())]))
-                .expect("parse should succeed with defaults");
-        assert!(cfg.selection.accel);
-        assert!(cfg.selection.gyro);
-        assert!(cfg.selection.mag);
-        assert!(cfg.selection.battery);
-        assert!(!cfg.selection.gps);
-        assert_eq!(cfg.interval_ms, 200);
+    fn pdf_merge_pick_populates_queue() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        let mut cmd = make_command("pdf_merge_pick");
+        cmd.path_list = Some(vec!["a.pdf".into(), "b.pdf".into()]);
+        handle_command(cmd).expect("merge pick should succeed");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.pdf.merge_queue, vec!["a.pdf", "b.pdf"]);
     }
 
     #[test]
-    fn sensor_bindings_require_selection() {
-        let err = parse_sensor_bindings(&HashMap::from([
-            ("sensor_accel".into(), "false".into()),
-            ("sensor_gyro".into(), "false".into()),
-            ("sensor_mag".into(), "false".into()),
-            ("sensor_pressure".into(), "false".into()),
-            ("sensor_gps".into(), "false".into()),
-            ("sensor_battery".into(), "false".into()),
-        ]))
-        .unwrap_err();
-        assert_eq!(err, "no_sensor_selected");
+    fn kotlin_image_batch_pick_populates_queue() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        let mut cmd = make_command("kotlin_image_batch_pick");
+        cmd.path_list = Some(vec!["/tmp/1.png".into(), "/tmp/2.png".into()]);
+        handle_command(cmd).expect("batch pick should succeed");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.image.batch_queue, vec!["/tmp/1.png", "/tmp/2.png"]);
     }
 
     #[test]
-    fn sensor_screen_renders_share_button_when_path_present() {
+    fn image_resize_runs_in_rust_for_single_and_batch() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
         reset_state();
 
-        handle_command(make_command("sensor_logger_screen")).unwrap();
+        let sources: Vec<String> = ["a.png", "b.png"]
+            .iter()
+            .map(|name| {
+                let path = data_dir.path().join(name);
+                image::RgbImage::from_pixel(200, 100, image::Rgb([10, 200, 30]))
+                    .save(&path)
+                    .unwrap();
+                path.to_string_lossy().into_owned()
+            })
+            .collect();
 
-        let mut cmd = make_command("sensor_logger_status");
-        cmd.bindings = Some(HashMap::from([
-            ("sensor_status".into(), "logging".into()),
-            ("sensor_path".into(), "/tmp/sensors.csv".into()),
+        handle_command(make_command("kotlin_image_resize_screen")).unwrap();
+        let mut pick = make_command("kotlin_image_pick");
+        pick.path = Some(sources[0].clone());
+        handle_command(pick).unwrap();
+
+        let mut mode = make_command("kotlin_image_resize_sync");
+        mode.bindings = Some(HashMap::from([("resize_mode".into(), "dimensions".into())]));
+        let ui = handle_command(mode).unwrap();
+        assert!(ui.to_string().contains("\"bind_key\":\"resize_width\""));
+
+        let mut missing = make_command("image_resize");
+        missing.bindings = Some(HashMap::from([("resize_width".into(), "".into())]));
+        let ui = handle_command(missing).unwrap();
+        assert_contains_text(&ui, "resize_missing_dimensions");
+
+        let mut resize = make_command("image_resize");
+        resize.bindings = Some(HashMap::from([
+            ("resize_width".into(), "50".into()),
+            ("resize_filter".into(), "triangle".into()),
+        ]));
+        let ui = handle_command(resize).unwrap();
+        assert_contains_text(&ui, "Dimensions: 200×100 → 50×25");
+        let out = {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.image.resize_filter, features::image_resize::ResizeFilter::Triangle);
+            state.image.result.as_ref().and_then(|r| r.path.clone()).unwrap()
+        };
+        assert!(out.starts_with(&*data_dir.path().to_string_lossy()));
+        let written = image::open(&out).unwrap();
+        assert_eq!((written.width(), written.height()), (50, 25));
+
+        let mut batch = make_command("kotlin_image_batch_pick");
+        batch.path_list = Some(sources.clone());
+        handle_command(batch).unwrap();
+        let mut run = make_command("image_resize_batch");
+        run.bindings = Some(HashMap::from([
+            ("resize_mode".into(), "percent".into()),
+            ("resize_scale_pct".into(), "10".into()),
         ]));
+        let ui = handle_command(run).unwrap();
+        assert_contains_text(&ui, "Resized: 2/2");
+        let resized = std::fs::read_dir(data_dir.path())
+            .unwrap()
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().starts_with("resized_20x10_"))
+            .count();
+        assert_eq!(resized, 2);
 
-        let ui = handle_command(cmd).expect("status command should succeed");
-        assert_contains_text(&ui, "Share last log");
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.last_sensor_log.as_deref(), Some("/tmp/sensors.csv"));
-        assert_eq!(state.sensor_status.as_deref(), Some("logging"));
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn sensor_logger_foreground_indicator_shown_when_logging() {
+    fn image_edit_crops_rotates_and_writes_result() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
         reset_state();
 
-        handle_command(make_command("sensor_logger_screen")).unwrap();
-        let mut status = make_command("sensor_logger_status");
-        status.bindings = Some(HashMap::from([
-            ("sensor_status".into(), "logging".into()),
-            ("sensor_path".into(), "/tmp/sensors.csv".into()),
+        let source = data_dir.path().join("photo.png");
+        image::RgbImage::from_pixel(200, 100, image::Rgb([200, 40, 40]))
+            .save(&source)
+            .unwrap();
+
+        handle_command(make_command("image_edit_screen")).unwrap();
+        let mut pick = make_command("image_edit_pick");
+        pick.path = Some(source.to_string_lossy().into_owned());
+        let ui = handle_command(pick).unwrap();
+        assert_contains_text(&ui, "Output: 200×100");
+        assert!(ui.to_string().contains("\"content_description\":\"image_edit_preview\""));
+
+        let mut crop = make_command("image_edit_crop");
+        crop.bindings = Some(HashMap::from([
+            ("image_edit_crop_x_pct".into(), "50".into()),
+            ("image_edit_crop_w_pct".into(), "50".into()),
         ]));
+        handle_command(crop).unwrap();
+        let ui = handle_command(make_command("image_edit_rotate_right")).unwrap();
+        assert_contains_text(&ui, "Output: 50×100");
+        assert_contains_text(&ui, "Rotation: 90°");
+
+        let mut bad = make_command("image_edit_crop");
+        bad.bindings = Some(HashMap::from([("image_edit_crop_y_pct".into(), "120".into())]));
+        let ui = handle_command(bad).unwrap();
+        assert_contains_text(&ui, "image_edit_invalid_crop");
+
+        let ui = handle_command(make_command("image_edit_apply")).unwrap();
+        let out = {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(state.image_edit.error.is_none());
+            state.image_edit.result_path.clone().unwrap()
+        };
+        assert_contains_text(&ui, &format!("Result: {out}"));
+        assert!(out.ends_with(".png"));
+        let written = image::open(&out).unwrap();
+        assert_eq!((written.width(), written.height()), (50, 100));
 
-        let ui = handle_command(status).expect("status command should succeed");
-        assert_contains_text(&ui, "foreground service");
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn text_viewer_reads_file_via_fd() {
+    fn metadata_strip_writes_clean_copies_for_each_photo() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
         reset_state();
 
-        let mut file = NamedTempFile::new().unwrap();
-        writeln!(file, "hello from csv").unwrap();
-        let fd = File::open(file.path()).unwrap().into_raw_fd();
+        let pixels = image::RgbImage::from_pixel(16, 16, image::Rgb([30, 90, 160]));
+        let mut encoded = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new(&mut encoded)
+            .encode(pixels.as_raw(), 16, 16, image::ColorType::Rgb8)
+            .unwrap();
+        let mut jpeg = encoded[..2].to_vec();
+        jpeg.extend_from_slice(&[0xFF, 0xFE, 0x00, 0x0B]);
+        jpeg.extend_from_slice(b"my secret");
+        jpeg.extend_from_slice(&encoded[2..]);
+        let jpeg_path = data_dir.path().join("IMG_0001.jpg");
+        std::fs::write(&jpeg_path, &jpeg).unwrap();
+        let png_path = data_dir.path().join("plain.png");
+        pixels.save(&png_path).unwrap();
+
+        let ui = handle_command(make_command("metadata_strip_screen")).unwrap();
+        assert_contains_text(&ui, "Strip photo metadata");
+
+        let mut pick = make_command("metadata_strip_pick");
+        pick.path_list = Some(vec![
+            jpeg_path.to_string_lossy().into_owned(),
+            png_path.to_string_lossy().into_owned(),
+        ]);
+        pick.bindings = Some(HashMap::from([(
+            "file_names".into(),
+            "holiday.jpg\nplain.png".into(),
+        )]));
+        let ui = handle_command(pick).unwrap();
+        assert_contains_text(&ui, "Cleaned: 2/2");
+        assert_contains_text(&ui, "Removed: comment");
+        assert_contains_text(&ui, "No metadata found");
+
+        let cleaned = std::fs::read(data_dir.path().join("holiday_clean.jpg")).unwrap();
+        assert!(!String::from_utf8_lossy(&cleaned).contains("my secret"));
+        assert_eq!(cleaned.len(), jpeg.len() - 13);
+        assert!(data_dir.path().join("plain_clean.png").exists());
+        assert_eq!(std::fs::read(&jpeg_path).unwrap(), jpeg);
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
 
-        let mut cmd = make_command("text_viewer_open");
-        cmd.fd = Some(fd);
-        cmd.path = Some(file.path().to_string_lossy().into_owned());
+    #[test]
+    fn image_histogram_reports_brightness_and_charts() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
+        reset_state();
 
-        let ui = handle_command(cmd).expect("text viewer should succeed");
-        assert_contains_text(&ui, "hello from csv");
+        let source = data_dir.path().join("sky.png");
+        image::RgbImage::from_fn(10, 10, |x, _| {
+            if x < 5 {
+                image::Rgb([0, 0, 0])
+            } else {
+                image::Rgb([255, 255, 255])
+            }
+        })
+        .save(&source)
+        .unwrap();
+
+        let ui = handle_command(make_command("image_histogram_screen")).unwrap();
+        assert_contains_text(&ui, "Histogram");
+
+        let mut pick = make_command("image_histogram_pick");
+        pick.path = Some(source.to_string_lossy().into_owned());
+        pick.bindings = Some(HashMap::from([("file_name".into(), "sky.png".into())]));
+        let ui = handle_command(pick).unwrap();
+        assert_contains_text(&ui, "sky.png — Dimensions: 10×10");
+        assert_contains_text(&ui, "Mean brightness: 127.5 / 255");
+        assert_contains_text(&ui, "Highlights clipped: 50.00%");
+        let rendered = ui.to_string();
+        assert!(rendered.contains("\"content_description\":\"image_histogram_rgb\""));
+        assert!(rendered.contains("\"color\":\"#E53935\""));
+        assert!(source.exists(), "plain paths are not deleted");
+
+        let missing = data_dir.path().join("missing.png");
+        let mut bad = make_command("image_histogram_pick");
+        bad.path = Some(missing.to_string_lossy().into_owned());
+        let ui = handle_command(bad).unwrap();
+        assert_contains_text(&ui, "open_failed");
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
 
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(
-            state.text_view_path.as_deref(),
-            Some(file.path().to_string_lossy().as_ref())
-        );
-        assert!(state.text_view_error.is_none());
+    #[test]
+    fn watermark_blends_logo_and_rendered_text_onto_each_picture() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
+        reset_state();
+
+        let first = data_dir.path().join("beach.png");
+        let second = data_dir.path().join("forest.png");
+        let logo = data_dir.path().join("logo.png");
+        let text_png = data_dir.path().join("text.png");
+        for path in [&first, &second] {
+            image::RgbaImage::from_pixel(40, 20, image::Rgba([0, 0, 0, 255]))
+                .save(path)
+                .unwrap();
+        }
+        for path in [&logo, &text_png] {
+            image::RgbaImage::from_pixel(4, 4, image::Rgba([255, 255, 255, 255]))
+                .save(path)
+                .unwrap();
+        }
+
+        let ui = handle_command(make_command("watermark_screen")).unwrap();
+        assert_contains_text(&ui, "Watermark");
+
+        let mut pick = make_command("watermark_pick_images");
+        pick.path_list = Some(vec![
+            first.to_string_lossy().into_owned(),
+            second.to_string_lossy().into_owned(),
+        ]);
+        pick.bindings = Some(HashMap::from([(
+            "file_names".into(),
+            "beach.png\nforest.png".into(),
+        )]));
+        let ui = handle_command(pick).unwrap();
+        assert_contains_text(&ui, "beach.png");
+
+        let mut apply = make_command("watermark_apply");
+        apply.bindings = Some(HashMap::from([("watermark_mode".into(), "logo".into())]));
+        let ui = handle_command(apply).unwrap();
+        assert_contains_text(&ui, "watermark_missing_logo");
+
+        let mut pick_logo = make_command("watermark_pick_logo");
+        pick_logo.path = Some(logo.to_string_lossy().into_owned());
+        handle_command(pick_logo).unwrap();
+
+        let mut apply = make_command("watermark_apply");
+        apply.bindings = Some(HashMap::from([
+            ("watermark_mode".into(), "logo".into()),
+            ("watermark_position".into(), "bottom_right".into()),
+            ("watermark_opacity".into(), "100".into()),
+            ("watermark_scale".into(), "50".into()),
+        ]));
+        let ui = handle_command(apply).unwrap();
+        assert_contains_text(&ui, "forest.png: Saved: ");
+        let outputs: Vec<String> = {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            state
+                .watermark
+                .outcomes
+                .iter()
+                .map(|o| o.value.clone().unwrap())
+                .collect()
+        };
+        assert_eq!(outputs.len(), 2);
+        for output in &outputs {
+            let marked = image::open(output).unwrap().to_rgba8();
+            assert_eq!(marked.dimensions(), (40, 20));
+            assert_eq!(marked.get_pixel(0, 0).0, [0, 0, 0, 255]);
+            assert_eq!(marked.get_pixel(37, 17).0, [255, 255, 255, 255]);
+        }
+        assert!(logo.exists(), "picked logos are kept for the next run");
+
+        let mut apply = make_command("watermark_apply");
+        apply.bindings = Some(HashMap::from([
+            ("watermark_mode".into(), "text".into()),
+            ("watermark_text".into(), "© Kistaverk".into()),
+            (
+                "watermark_text_png".into(),
+                text_png.to_string_lossy().into_owned(),
+            ),
+        ]));
+        handle_command(apply).unwrap();
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.watermark.outcomes.len(), 2);
+            assert!(state.watermark.outcomes.iter().all(|o| o.value.is_ok()));
+        }
+        assert!(!text_png.exists(), "rendered text marks are removed");
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn text_viewer_supports_chunked_loading() {
+    fn image_adjust_previews_sliders_and_saves_result() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
         reset_state();
 
-        let mut file = NamedTempFile::new().unwrap();
-        let block = vec![b'a'; 140_000];
-        file.write_all(&block).unwrap();
-        file.write_all(&block).unwrap(); // ~280 KB
-        file.flush().unwrap();
-        let fd = File::open(file.path()).unwrap().into_raw_fd();
+        let source = data_dir.path().join("dim.png");
+        image::RgbImage::from_pixel(12, 8, image::Rgb([40, 60, 80]))
+            .save(&source)
+            .unwrap();
 
-        let mut cmd = make_command("text_viewer_open");
-        cmd.fd = Some(fd);
-        cmd.path = Some(file.path().to_string_lossy().into_owned());
+        let ui = handle_command(make_command("image_adjust_screen")).unwrap();
+        assert_contains_text(&ui, "Adjust");
 
-        handle_command(cmd).expect("text viewer should succeed");
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        let initial_loaded_end = state.text_view_loaded_bytes;
-        let initial_offset = state.text_view_window_offset;
-        let total = state.text_view_total_bytes.unwrap();
-        assert_eq!(initial_offset, 0);
-        let _initial_len = state
-            .text_view_content
-            .as_ref()
-            .map(|c| c.len())
-            .unwrap_or(0);
-        assert!(state.text_view_has_more);
-        assert!(initial_loaded_end < total);
-        drop(state);
+        let mut apply = make_command("image_adjust_apply");
+        apply.bindings = Some(HashMap::new());
+        let ui = handle_command(apply).unwrap();
+        assert_contains_text(&ui, "no_image_selected");
+
+        let mut pick = make_command("image_adjust_pick");
+        pick.path = Some(source.to_string_lossy().into_owned());
+        let ui = handle_command(pick).unwrap();
+        assert_contains_text(&ui, "(12×8)");
+        let original_preview = {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            state.image_adjust.preview_png_base64.clone().unwrap()
+        };
 
-        handle_command(make_command("text_viewer_load_more")).expect("load more should succeed");
+        let mut update = make_command("image_adjust_update");
+        update.bindings = Some(HashMap::from([
+            ("image_adjust_brightness".into(), "20".into()),
+            ("image_adjust_grayscale".into(), "false".into()),
+        ]));
+        let ui = handle_command(update).unwrap();
+        assert_contains_text(&ui, "Brightness: +20");
+        assert!(ui.to_string().contains("\"type\":\"Slider\""));
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.image_adjust.adjustments.brightness, 20);
+            assert_ne!(
+                state.image_adjust.preview_png_base64.as_deref(),
+                Some(original_preview.as_str())
+            );
+        }
+
+        let mut apply = make_command("image_adjust_apply");
+        apply.bindings = Some(HashMap::from([(
+            "image_adjust_contrast".into(),
+            "0".into(),
+        )]));
+        handle_command(apply).unwrap();
+        let out = {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            state
+                .image_adjust
+                .result_path
+                .clone()
+                .expect("saved result")
+        };
+        assert!(out.ends_with(".png"));
+        let saved = image::open(&out).unwrap().to_rgb8();
+        assert_eq!(saved.dimensions(), (12, 8));
+        assert_eq!(saved.get_pixel(3, 3).0, [91, 111, 131]);
+
+        handle_command(make_command("image_adjust_reset")).unwrap();
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        let after_len = state
-            .text_view_content
-            .as_ref()
-            .map(|c| c.len())
-            .unwrap_or(0);
-        assert!(after_len > 0);
-        assert!(after_len <= 150_000);
-        assert_eq!(state.text_view_total_bytes, Some(total));
-        assert!(state.text_view_window_offset > initial_offset);
-        assert!(state.text_view_loaded_bytes > initial_loaded_end);
-        assert!(state.text_view_has_previous);
-        assert!(state.text_view_loaded_bytes - state.text_view_window_offset > 0);
+        assert!(state.image_adjust.adjustments.is_identity());
         assert_eq!(
-            state.text_view_content.as_ref().unwrap().chars().next(),
-            Some('a')
+            state.image_adjust.preview_png_base64.as_deref(),
+            Some(original_preview.as_str())
         );
+        drop(state);
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn pdf_preview_screen_sets_state() {
+    fn ascii_art_converts_views_and_exports_text() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
         reset_state();
+
+        let source = data_dir.path().join("moon.png");
+        image::RgbImage::from_fn(100, 50, |x, _| {
+            if x < 50 {
+                image::Rgb([0, 0, 0])
+            } else {
+                image::Rgb([255, 255, 255])
+            }
+        })
+        .save(&source)
+        .unwrap();
+
+        let ui = handle_command(make_command("ascii_art_screen")).unwrap();
+        assert_contains_text(&ui, "ASCII");
+
+        let mut pick = make_command("ascii_art_pick");
+        pick.path = Some(source.to_string_lossy().into_owned());
+        pick.bindings = Some(HashMap::from([("file_name".into(), "moon.png".into())]));
+        let ui = handle_command(pick).unwrap();
+        assert_contains_text(&ui, "80×20");
+
+        let mut sync = make_command("ascii_art_sync");
+        sync.bindings = Some(HashMap::from([("ascii_art_columns".into(), "40".into())]));
+        let ui = handle_command(sync).unwrap();
+        assert_contains_text(&ui, "40×10");
+
+        let ui = handle_command(make_command("ascii_art_view")).unwrap();
+        assert_contains_text(&ui, "moon.txt");
         {
-            let mut state = STATE.get_or_init(GlobalState::new).ui_lock();
-            state.pdf.source_uri = Some("file://dummy.pdf".into());
-            state.pdf.page_count = Some(3);
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(matches!(state.current_screen(), Screen::TextViewer));
+            assert!(!state.text_view_wrap);
+            let content = state.text_view_content.as_deref().unwrap();
+            assert_eq!(content.lines().count(), 10);
+            assert!(content.starts_with("@@@@"));
         }
-        let ui = handle_command(make_command("pdf_preview_screen")).expect("preview screen");
-        assert_contains_text(&ui, "PDF viewer");
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert!(matches!(state.current_screen(), Screen::PdfPreview));
-        assert!(state.pdf.preview_page.is_none());
+        handle_command(make_command("back")).unwrap();
+
+        let mut sync = make_command("ascii_art_sync");
+        sync.bindings = Some(HashMap::from([("ascii_art_mode".into(), "ansi".into())]));
+        handle_command(sync).unwrap();
+        let ui = handle_command(make_command("ascii_art_export")).unwrap();
+        assert_contains_text(&ui, "Saved: ");
+        let saved = {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(matches!(state.current_screen(), Screen::AsciiArt));
+            state.ascii_art.saved_path.clone().unwrap()
+        };
+        assert!(saved.contains("moon_ansi_") && saved.ends_with(".txt"));
+        let text = std::fs::read_to_string(&saved).unwrap();
+        assert!(text.starts_with("\x1b[38;2;0;0;0m"));
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn pdf_page_open_sets_page() {
+    fn gif_frames_explodes_and_reassembles_selection() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
         reset_state();
+
+        let source = data_dir.path().join("spin.gif");
         {
-            let mut state = STATE.get_or_init(GlobalState::new).ui_lock();
-            state.pdf.source_uri = Some("file://dummy.pdf".into());
-            state.pdf.page_count = Some(5);
+            let file = std::fs::File::create(&source).unwrap();
+            let mut encoder = image::codecs::gif::GifEncoder::new(file);
+            for shade in [0u8, 120, 240] {
+                let frame = image::Frame::from_parts(
+                    image::RgbaImage::from_pixel(8, 8, image::Rgba([shade, shade, shade, 255])),
+                    0,
+                    0,
+                    image::Delay::from_numer_denom_ms(50, 1),
+                );
+                encoder.encode_frame(frame).unwrap();
+            }
         }
-        let mut cmd = make_command("pdf_page_open");
-        let mut bindings = HashMap::new();
-        bindings.insert("page".to_string(), "2".to_string());
-        cmd.bindings = Some(bindings);
-        handle_command(cmd).expect("open page");
 
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.pdf.preview_page, Some(2));
-        assert!(matches!(state.current_screen(), Screen::PdfPreview));
-    }
+        let ui = handle_command(make_command("gif_frames_screen")).unwrap();
+        assert_contains_text(&ui, "GIF");
 
-    fn write_test_image(w: u32, h: u32, color: [u8; 3]) -> NamedTempFile {
-        let mut img = image::RgbaImage::new(w, h);
-        for pixel in img.pixels_mut() {
-            *pixel = image::Rgba([color[0], color[1], color[2], 255]);
-        }
-        let file = tempfile::Builder::new().suffix(".png").tempfile().unwrap();
-        img.save(file.path()).unwrap();
-        file
+        let mut pick = make_command("gif_frames_pick");
+        pick.path = Some(source.to_string_lossy().into_owned());
+        pick.bindings = Some(HashMap::from([("file_name".into(), "spin.gif".into())]));
+        let ui = handle_command(pick).unwrap();
+        assert_contains_text(&ui, "8×8");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            let gif = &state.gif_frames;
+            let exploded = gif.exploded.as_ref().unwrap();
+            assert_eq!(exploded.frames.len(), 3);
+            assert!(exploded.frames_dir.ends_with("spin_frames"));
+            assert_eq!(gif.selection, "1-3");
+            assert_eq!(gif.delay_ms, 50);
+        }
+
+        let mut assemble = make_command("gif_frames_assemble");
+        assemble.bindings = Some(HashMap::from([
+            ("gif_frames_selection".into(), "7".into()),
+            ("gif_frames_delay_ms".into(), "80".into()),
+        ]));
+        let ui = handle_command(assemble).unwrap();
+        assert_contains_text(&ui, "gif_frames_invalid_selection:7");
+
+        let mut assemble = make_command("gif_frames_assemble");
+        assemble.bindings = Some(HashMap::from([
+            ("gif_frames_selection".into(), "3-1".into()),
+            ("gif_frames_delay_ms".into(), "80".into()),
+        ]));
+        handle_command(assemble).unwrap();
+        let out = {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(state.gif_frames.error.is_none());
+            state.gif_frames.result_path.clone().unwrap()
+        };
+        assert!(out.ends_with("spin_assembled.gif"));
+        let decoder = image::codecs::gif::GifDecoder::new(std::io::BufReader::new(
+            std::fs::File::open(&out).unwrap(),
+        ))
+        .unwrap();
+        let frames = image::AnimationDecoder::into_frames(decoder)
+            .collect_frames()
+            .unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].delay().numer_denom_ms(), (80, 1));
+        assert_eq!(frames[0].buffer().get_pixel(0, 0).0[0], 240);
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn pixel_art_screen_sets_defaults() {
+    fn collage_arranges_picked_images_into_a_grid() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
         reset_state();
-        handle_command(make_command("pixel_art_screen")).expect("screen");
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert!(matches!(state.current_screen(), Screen::PixelArt));
-        assert_eq!(state.pixel_art.scale_factor, 4);
+
+        let mut paths = Vec::new();
+        for (i, color) in [[255, 0, 0], [0, 255, 0], [0, 0, 255]].into_iter().enumerate() {
+            let path = data_dir.path().join(format!("tile{i}.png"));
+            image::RgbImage::from_pixel(40, 40, image::Rgb(color))
+                .save(&path)
+                .unwrap();
+            paths.push(path.to_string_lossy().into_owned());
+        }
+
+        let ui = handle_command(make_command("collage_screen")).unwrap();
+        assert_contains_text(&ui, "Collage");
+
+        let mut pick = make_command("collage_pick_images");
+        pick.path_list = Some(paths);
+        let ui = handle_command(pick).unwrap();
+        assert_contains_text(&ui, "tile0.png, tile1.png, tile2.png");
+        assert_contains_text(&ui, "2×2");
+
+        let mut build = make_command("collage_build");
+        build.bindings = Some(HashMap::from([
+            ("collage_columns".into(), "1".into()),
+            ("collage_rows".into(), "2".into()),
+        ]));
+        let ui = handle_command(build).unwrap();
+        assert_contains_text(&ui, "collage_grid_too_small:1×2<3");
+
+        let mut build = make_command("collage_build");
+        build.bindings = Some(HashMap::from([
+            ("collage_columns".into(), "3".into()),
+            ("collage_rows".into(), "".into()),
+            ("collage_cell_size".into(), "64".into()),
+            ("collage_padding".into(), "4".into()),
+            ("collage_background".into(), "#000000".into()),
+        ]));
+        handle_command(build).unwrap();
+        let out = {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(state.collage.error.is_none());
+            state.collage.result_path.clone().unwrap()
+        };
+        assert!(out.contains("collage_") && out.ends_with(".png"));
+        let collage = image::open(&out).unwrap().to_rgb8();
+        assert_eq!(collage.dimensions(), (3 * 64 + 4 * 4, 64 + 2 * 4));
+        assert_eq!(collage.get_pixel(0, 0).0, [0, 0, 0]);
+        assert_eq!(collage.get_pixel(4 + 32, 36).0, [255, 0, 0]);
+        assert_eq!(collage.get_pixel(4 + 2 * 68 + 32, 36).0, [0, 0, 255]);
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn pixel_art_apply_produces_result() {
+    fn exif_editor_loads_fields_and_saves_an_edited_copy() {
+        use image::ImageEncoder;
+
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
         reset_state();
-        let img = write_test_image(8, 8, [10, 20, 30]);
-        {
-            let mut cmd = make_command("pixel_art_pick");
-            cmd.path = Some(img.path().to_string_lossy().into_owned());
-            handle_command(cmd).expect("pick");
-        }
-        let mut apply = make_command("pixel_art_apply");
-        apply.loading_only = Some(false);
-        let ui = handle_command(apply).expect("apply");
-        assert_contains_text(&ui, "Result:");
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert!(state.pixel_art.result_path.is_some());
-        assert!(state.pixel_art.error.is_none());
+
+        let mut encoded = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut encoded, 90)
+            .write_image(&[90u8; 4 * 4 * 3], 4, 4, image::ColorType::Rgb8)
+            .unwrap();
+        let sample = crate::features::exif_info::tests::sample_jpeg();
+        let mut data = sample[..sample.len() - 2].to_vec();
+        data.extend_from_slice(&encoded[2..]);
+        let source = data_dir.path().join("scan.jpg");
+        std::fs::write(&source, &data).unwrap();
+
+        let ui = handle_command(make_command("exif_edit_screen")).unwrap();
+        assert_contains_text(&ui, "EXIF");
+
+        let mut pick = make_command("exif_edit_pick");
+        pick.path = Some(source.to_string_lossy().into_owned());
+        let ui = handle_command(pick).unwrap();
+        assert_contains_text(&ui, "2024-06-21 12:30:00");
+
+        let mut save = make_command("exif_edit_save");
+        save.bindings = Some(HashMap::from([(
+            "exif_edit_taken_at".into(),
+            "1987-13-01 10:00".into(),
+        )]));
+        let ui = handle_command(save).unwrap();
+        assert_contains_text(&ui, "exif_edit_invalid_date:1987-13-01 10:00");
+
+        let mut save = make_command("exif_edit_save");
+        save.bindings = Some(HashMap::from([
+            ("exif_edit_taken_at".into(), "1987-03-01 10:00".into()),
+            ("exif_edit_copyright".into(), "Family archive".into()),
+        ]));
+        handle_command(save).unwrap();
+        let out = {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(state.exif_editor.error.is_none());
+            state.exif_editor.result_path.clone().unwrap()
+        };
+        assert!(out.ends_with("scan_exif.jpg"));
+        let edited = ExifEdits::read(&mut std::io::BufReader::new(
+            std::fs::File::open(&out).unwrap(),
+        ))
+        .unwrap();
+        assert_eq!(edited.taken_at, "1987-03-01 10:00:00");
+        assert_eq!(edited.copyright, "Family archive");
+        assert_eq!(edited.orientation, Some(6));
+        assert_eq!(std::fs::read(&source).unwrap(), data);
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn pixel_art_set_scale_clamps_and_sets() {
+    fn dithering_serpentine_toggle_and_new_modes_apply() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
         reset_state();
-        handle_command(make_command("pixel_art_screen")).unwrap();
-        let mut cmd = make_command("pixel_art_set_scale");
-        cmd.bindings = Some(HashMap::from([("scale".into(), "1".into())]));
-        handle_command(cmd).unwrap();
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.pixel_art.scale_factor, 2);
+
+        let source = data_dir.path().join("gradient.png");
+        image::RgbImage::from_fn(32, 8, |x, _| image::Rgb([(x * 8) as u8; 3]))
+            .save(&source)
+            .unwrap();
+
+        let mut pick = make_command("dithering_pick_image");
+        pick.path = Some(source.to_string_lossy().into_owned());
+        let ui = handle_command(pick).unwrap();
+        assert_contains_text(&ui, "Stucki");
+        assert_contains_text(&ui, "Serpentine");
+
+        let mut toggle = make_command("dithering_serpentine_toggle");
+        toggle.bindings = Some(HashMap::from([(
+            "dithering_serpentine".into(),
+            "true".into(),
+        )]));
+        handle_command(toggle).unwrap();
+
+        for (action, mode) in [
+            ("dithering_mode_jjn", DitheringMode::JarvisJudiceNinke),
+            ("dithering_mode_blue_noise", DitheringMode::BlueNoise),
+        ] {
+            handle_command(make_command(action)).unwrap();
+            handle_command(make_command("dithering_apply")).unwrap();
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.dithering_mode, mode);
+            assert!(state.dithering_serpentine);
+            assert!(state.dithering_error.is_none());
+            let out = state.dithering_result_path.clone().unwrap();
+            assert_eq!(image::open(&out).unwrap().to_rgb8().dimensions(), (32, 8));
+        }
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn text_viewer_jump_and_prev_work() {
+    fn dithering_custom_palette_from_text_and_image() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
         reset_state();
 
-        let mut file = NamedTempFile::new().unwrap();
-        let mut block = vec![b'a'; 64_000];
-        block.extend(vec![b'b'; 64_000]);
-        file.write_all(&block).unwrap();
-        file.flush().unwrap();
-
-        let mut cmd = make_command("text_viewer_open");
-        cmd.path = Some(file.path().to_string_lossy().into_owned());
-        handle_command(cmd).expect("text viewer should succeed");
+        handle_command(make_command("dithering_screen")).unwrap();
+        let mut custom = make_command("dithering_palette_custom");
+        custom.bindings = Some(HashMap::from([(
+            "dithering_custom_palette".into(),
+            "#112233, nope".into(),
+        )]));
+        let ui = handle_command(custom).unwrap();
+        assert_contains_text(&ui, "dithering_palette_invalid:invalid_hex_length:nope");
 
-        // Jump to second half
-        let mut jump = make_command("text_viewer_jump");
-        jump.bindings = Some(HashMap::from([("offset_bytes".into(), "64000".into())]));
-        handle_command(jump).expect("jump should succeed");
+        let mut custom = make_command("dithering_palette_custom");
+        custom.bindings = Some(HashMap::from([(
+            "dithering_custom_palette".into(),
+            "#112233 #DDEEFF".into(),
+        )]));
+        handle_command(custom).unwrap();
         {
             let state = STATE.get_or_init(GlobalState::new).ui_lock();
-            // If the file is smaller than a window, jump clamps to 0.
-            assert!(state.text_view_window_offset <= 64_000);
-            assert!(
-                state.text_view_content.as_ref().unwrap().starts_with('b')
-                    || state.text_view_content.as_ref().unwrap().contains('b')
+            assert!(state.dithering_error.is_none());
+            assert_eq!(
+                state.dithering_palette,
+                DitheringPalette::Custom(vec![[0x11, 0x22, 0x33], [0xDD, 0xEE, 0xFF]])
             );
-            assert!(state.text_view_has_previous || state.text_view_window_offset == 0);
         }
 
-        // Load previous should move window back toward start
-        handle_command(make_command("text_viewer_load_prev")).expect("prev should succeed");
+        let reference = data_dir.path().join("reference.png");
+        image::RgbImage::from_fn(16, 16, |_, y| {
+            if y < 10 {
+                image::Rgb([255, 200, 0])
+            } else {
+                image::Rgb([0, 40, 80])
+            }
+        })
+        .save(&reference)
+        .unwrap();
+        let mut pick = make_command("dithering_palette_from_image");
+        pick.path = Some(reference.to_string_lossy().into_owned());
+        let ui = handle_command(pick).unwrap();
+        assert_contains_text(&ui, "#FFC800, #002850");
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.text_view_window_offset, 0);
-        assert!(state.text_view_content.as_ref().unwrap().starts_with('a'));
+        assert_eq!(
+            state.dithering_palette,
+            DitheringPalette::Custom(vec![[255, 200, 0], [0, 40, 80]])
+        );
+        drop(state);
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn archive_text_entry_opens_in_viewer() {
+    fn presets_export_and_import_round_trip() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
         reset_state();
 
-        let mut zip_file = NamedTempFile::new().unwrap();
+        save_preset(
+            "pixel_art",
+            "Chunky",
+            Vec::new(),
+            json!({ "scale_factor": 8 }),
+        )
+        .unwrap();
+        let mut list = make_command("presets_list");
+        list.bindings = Some(HashMap::from([("tool_id".into(), "pixel_art".into())]));
+        handle_command(list).unwrap();
+
+        let ui = handle_command(make_command("preset_export")).unwrap();
+        assert_contains_text(&ui, "Exported to ");
+        let exported = {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            let message = state.preset_state.last_message.clone().unwrap();
+            message.trim_start_matches("Exported to ").to_string()
+        };
+        assert!(exported.ends_with(".json"));
+
+        let presets_dir = crate::features::presets::presets_dir();
+        std::fs::remove_dir_all(&presets_dir).unwrap();
+        handle_command(make_command("preset_conflict_keep_both")).unwrap();
+        let mut import = make_command("preset_import");
+        import.path = Some(exported);
+        let ui = handle_command(import).unwrap();
+        assert_contains_text(&ui, "Imported 1, replaced 0, skipped 0, unsupported 0");
+        assert_contains_text(&ui, "Chunky");
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.preset_state.import_conflict, ImportConflict::KeepBoth);
+        assert_eq!(state.preset_state.presets.len(), 1);
+        drop(state);
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
+
+    #[test]
+    fn preset_share_qr_and_scan_import() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
+        reset_state();
+
+        let preset = save_preset(
+            "dithering",
+            "Grainy",
+            Vec::new(),
+            json!({ "mode": "Burkes" }),
+        )
+        .unwrap();
+        handle_command(make_command("presets_list")).unwrap();
+        let mut share = make_command("preset_share_qr");
+        share.bindings = Some(HashMap::from([("id".into(), preset.id.clone())]));
+        handle_command(share).unwrap();
         {
-            let mut writer = zip::ZipWriter::new(&mut zip_file);
-            let options = FileOptions::default().compression_method(zip::CompressionMethod::Stored);
-            writer.start_file("note.txt", options).unwrap();
-            writer.write_all(b"hello from zip").unwrap();
-            writer.start_file("data.bin", options).unwrap();
-            writer.write_all(&[0u8, 1, 2]).unwrap();
-            writer.finish().unwrap();
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(matches!(state.current_screen(), Screen::Qr));
+            assert!(state.last_qr_base64.is_some());
         }
 
-        let fd = File::open(zip_file.path()).unwrap().into_raw_fd();
-        let mut open_cmd = make_command("archive_open");
-        open_cmd.fd = Some(fd);
-        open_cmd.path = Some(zip_file.path().to_string_lossy().into_owned());
-        handle_command(open_cmd).expect("archive open should succeed");
+        delete_preset(&preset.id).unwrap();
+        handle_command(make_command("qr_receive_screen")).unwrap();
+        let mut scan = make_command("qr_receive_scan");
+        scan.bindings = Some(HashMap::from([(
+            "qr_scan_input".into(),
+            preset_qr_payload(&preset).unwrap(),
+        )]));
+        let ui = handle_command(scan).unwrap();
+        assert_contains_text(&ui, "Preset imported");
+        assert_eq!(load_presets().unwrap(), vec![preset]);
 
-        let ui = handle_command(make_command("archive_open_text:0"))
-            .expect("text entry open should succeed");
-        assert_contains_text(&ui, "hello from zip");
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
+
+    #[test]
+    fn preset_tags_filter_and_groups_collapse() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
+        reset_state();
 
+        let mut dialog = make_command("preset_save_dialog");
+        dialog.bindings = Some(HashMap::from([("tool_id".into(), "dithering".into())]));
+        handle_command(dialog).unwrap();
+        let mut save = make_command("preset_save");
+        save.bindings = Some(HashMap::from([
+            ("preset_name".into(), "Handheld".into()),
+            ("preset_tags".into(), "Retro, #GB".into()),
+        ]));
+        let ui = handle_command(save).unwrap();
+        assert_contains_text(&ui, "#retro");
+        assert_eq!(load_presets().unwrap()[0].tags, vec!["retro", "gb"]);
+
+        save_preset("dithering", "Plain", Vec::new(), json!({})).unwrap();
+        handle_command(make_command("presets_list")).unwrap();
+        let mut filter = make_command("preset_filter");
+        filter.bindings = Some(HashMap::from([("preset_filter".into(), "#gb".into())]));
+        let ui = handle_command(filter).unwrap();
+        assert_contains_text(&ui, "Handheld");
+        assert!(!ui.to_string().contains("Plain"));
+
+        let mut toggle = make_command("preset_toggle_group");
+        toggle.bindings = Some(HashMap::from([("tool_id".into(), "dithering".into())]));
+        let ui = handle_command(toggle).unwrap();
+        assert_contains_text(&ui, "▸ dithering (1)");
+        assert!(!ui.to_string().contains("Handheld"));
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert!(state
-            .text_view_path
-            .as_deref()
-            .map(|p| p.contains("note.txt"))
-            .unwrap_or(false));
-        assert_eq!(state.text_view_content.as_deref(), Some("hello from zip"));
-        assert!(matches!(state.current_screen(), Screen::TextViewer));
-        assert_eq!(state.nav_depth(), 3);
+        assert_eq!(state.preset_state.collapsed_tools, vec!["dithering"]);
+        drop(state);
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn qr_screen_has_back_button_when_nested() {
+    fn preset_reorder_list_persists_the_dragged_order() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
         reset_state();
 
-        let mut cmd = make_command("qr_generate");
-        cmd.bindings = Some(HashMap::from([("qr_input".into(), "hi".into())]));
-        let ui = handle_command(cmd).expect("qr generate should succeed");
+        for name in ["One", "Two", "Three"] {
+            save_preset("dithering", name, Vec::new(), json!({})).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        handle_command(make_command("presets_list")).unwrap();
+        let ui = handle_command(make_command("preset_reorder_mode")).unwrap();
+        let rendered = ui.to_string();
+        assert!(rendered.contains("\"type\":\"ReorderableList\""));
+        assert!(rendered.contains("\"bind_key\":\"preset_order\""));
 
-        assert_contains_text(&ui, "Back");
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert!(matches!(state.current_screen(), Screen::Qr));
-        assert!(state.nav_depth() > 1);
+        let mut ids: Vec<String> = load_presets().unwrap().into_iter().map(|p| p.id).collect();
+        ids.reverse();
+        let mut reorder = make_command("preset_reorder");
+        reorder.bindings = Some(HashMap::from([("preset_order".into(), ids.join(","))]));
+        handle_command(reorder).unwrap();
+
+        let saved: Vec<String> = load_presets().unwrap().into_iter().map(|p| p.id).collect();
+        assert_eq!(saved, ids);
+        let ui = handle_command(make_command("preset_reorder_mode")).unwrap();
+        assert!(!ui.to_string().contains("ReorderableList"));
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn sensor_logger_actions_do_not_stack_nav() {
+    fn persisted_state_round_trips_without_transient_fields() {
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        let cache_dir = data_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_dir).unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", &cache_dir);
+
+        let global = GlobalState::new();
+        {
+            let mut state = global.ui_lock();
+            state.push_screen(Screen::Dithering);
+            state.push_screen(Screen::Loading);
+            state.dithering_serpentine = true;
+            state.loading_message = Some("Dithering...".into());
+            state.vault.password = "hunter2".into();
+        }
+        global.persist_state();
+        let path = crate::features::storage::app_state_path();
+        assert!(path.starts_with(data_dir.path()));
+
+        let content = load_app_state().unwrap().expect("state written");
+        assert!(!content.contains("hunter2"));
+        let restored = AppState::from_persisted(&content).unwrap();
+        assert_eq!(restored.nav_stack, vec![Screen::Home, Screen::Dithering]);
+        assert!(restored.dithering_serpentine);
+        assert_eq!(restored.loading_message, None);
+        assert!(restored.vault.password.is_empty());
+
+        // Unchanged state is not rewritten.
+        std::fs::remove_file(&path).unwrap();
+        global.persist_state();
+        assert!(!path.exists());
+
+        // Files written by older versions lack newer fields; those start from defaults.
+        let mut legacy: Value = serde_json::from_str(&content).unwrap();
+        legacy
+            .as_object_mut()
+            .unwrap()
+            .remove("dithering_serpentine");
+        let restored = AppState::from_persisted(&legacy.to_string()).unwrap();
+        assert!(!restored.dithering_serpentine);
+        assert!(AppState::from_persisted("[1]").is_err());
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
+
+    #[test]
+    fn state_is_persisted_on_request_without_generated_secrets() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
         reset_state();
+        let data_dir = tempfile::tempdir().unwrap();
+        let cache_dir = data_dir.path().join("cache");
+        std::fs::create_dir_all(&cache_dir).unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", &cache_dir);
 
-        handle_command(make_command("sensor_logger_screen")).unwrap();
+        dispatch_commands(vec![make_command("uuid_screen")]);
+        assert!(load_app_state().unwrap().is_none());
         {
-            let state = STATE.get_or_init(GlobalState::new).ui_lock();
-            assert_eq!(state.nav_depth(), 2);
-            assert!(matches!(state.current_screen(), Screen::SensorLogger));
+            let mut state = STATE.get_or_init(GlobalState::new).ui_lock();
+            state.uuid_generator.v5_name = "persist-me".into();
+            state.uuid_generator.last_string = Some("Tr0ub4dor&3".into());
+            state.uuid_generator.last_random_bytes = Some(vec![0xab; 4]);
         }
 
-        let mut start = make_command("sensor_logger_start");
-        start.bindings = Some(HashMap::from([("sensor_accel".into(), "true".into())]));
-        handle_command(start).unwrap();
-        {
-            let state = STATE.get_or_init(GlobalState::new).ui_lock();
-            assert_eq!(state.nav_depth(), 2);
-            assert!(matches!(state.current_screen(), Screen::SensorLogger));
-        }
+        let reply = dispatch_commands(vec![make_command("persist_state")]);
+        assert_eq!(reply["type"], "Persisted");
+        let content = load_app_state().unwrap().expect("state written");
+        assert!(content.contains("persist-me"));
+        assert!(!content.contains("Tr0ub4dor"));
+        let restored = AppState::from_persisted(&content).unwrap();
+        assert!(restored.uuid_generator.last_string.is_none());
+        assert!(restored.uuid_generator.last_random_bytes.is_none());
 
-        handle_command(make_command("back")).unwrap();
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.nav_depth(), 1);
-        assert!(matches!(state.current_screen(), Screen::Home));
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn text_viewer_missing_source_sets_error() {
+    fn open_tool_deep_link_lands_on_tool() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
 
-        let ui = handle_command(make_command("text_viewer_open")).expect("should return UI");
-        assert_contains_text(&ui, "missing_source");
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.text_view_error.as_deref(), Some("missing_source"));
-        assert!(state.text_view_content.is_none());
+        let mut link = make_command("open_tool");
+        link.bindings = Some(HashMap::from([(
+            "uri".into(),
+            "kistaverk://text_tools?text_input=hello%20world".into(),
+        )]));
+        handle_command(link).unwrap();
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(matches!(state.current_screen(), Screen::TextTools));
+            assert_eq!(state.text_input.as_deref(), Some("hello world"));
+        }
+
+        let mut shortcut = make_command("open_tool");
+        shortcut.bindings = Some(HashMap::from([("tool".into(), "multi_hash".into())]));
+        handle_command(shortcut).unwrap();
+        assert!(matches!(
+            STATE.get_or_init(GlobalState::new).ui_lock().current_screen(),
+            Screen::MultiHash
+        ));
+
+        for (uri, error) in [
+            ("kistaverk://nope", "unknown_tool:nope"),
+            (
+                "kistaverk://hash_sha256",
+                "open_tool_missing_path:hash_sha256",
+            ),
+            ("https://hash_sha256", "open_tool_bad_uri"),
+        ] {
+            let mut link = make_command("open_tool");
+            link.bindings = Some(HashMap::from([("uri".into(), uri.into())]));
+            handle_command(link).unwrap();
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.last_error.as_deref(), Some(error));
+            assert!(matches!(state.current_screen(), Screen::MultiHash));
+        }
     }
 
     #[test]
-    fn missing_source_sets_error_and_clears_previous_hash() {
+    fn favorites_pin_reorder_and_persist() {
         let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
         reset_state();
 
-        let mut file = NamedTempFile::new().unwrap();
-        file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
-        file.flush().unwrap();
+        let favorite = |action: &str, id: &str| {
+            let mut cmd = make_command(action);
+            cmd.bindings = Some(HashMap::from([("favorite_id".into(), id.into())]));
+            handle_command(cmd).unwrap()
+        };
 
-        let mut initial = make_command("hash_file_md5");
-        initial.path = Some(file.path().to_string_lossy().into_owned());
+        let ui = handle_command(make_command("home_edit_favorites")).unwrap();
+        assert_contains_text(&ui, "☆ Multi-hash");
+        favorite("favorite_toggle", "multi_hash");
+        favorite("favorite_toggle", "pdf_tools");
+        let ui = favorite("favorite_move_up", "multi_hash");
+        assert_contains_text(&ui, "★ Multi-hash");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(
+                state.favorite_tools,
+                vec!["text_tools", "text_viewer", "multi_hash", "hash_sha256"]
+            );
+        }
+        assert_eq!(
+            crate::features::storage::load_favorite_tools().unwrap(),
+            Some(vec![
+                "text_tools".to_string(),
+                "text_viewer".to_string(),
+                "multi_hash".to_string(),
+                "hash_sha256".to_string(),
+            ])
+        );
 
-        handle_command(initial).expect("initial hash should succeed");
+        favorite("favorite_toggle", "not_a_tool");
+        assert_eq!(
+            STATE
+                .get_or_init(GlobalState::new)
+                .ui_lock()
+                .last_error
+                .as_deref(),
+            Some("unknown_tool:not_a_tool")
+        );
+
+        let ui = handle_command(make_command("home_edit_favorites")).unwrap();
+        let rendered = ui.to_string();
+        assert!(rendered.contains("\"action\":\"multi_hash_screen\""));
+        assert!(!rendered.contains("favorite_toggle"));
+
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
+
+    #[test]
+    fn recent_tools_lead_the_home_screen() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
 
+        for action in [
+            "multi_hash_screen",
+            "vault_screen",
+            "ruler_screen",
+            "logic_screen",
+            "jwt_screen",
+            "ruler_screen",
+        ] {
+            handle_command(make_command(action)).unwrap();
+        }
         {
             let state = STATE.get_or_init(GlobalState::new).ui_lock();
-            assert_eq!(state.last_hash.as_deref(), Some(MD5_ABC));
-            assert_eq!(state.last_hash_algo.as_deref(), Some("MD5"));
+            assert_eq!(state.tool_usage[0].id, "ruler");
+            assert_eq!(
+                state.tool_usage.iter().filter(|u| u.id == "ruler").count(),
+                1
+            );
         }
 
-        let ui = handle_command(make_command("hash_file_md4"))
-            .expect("hash command should still return UI even when failing");
-
-        assert_contains_text(&ui, "missing_path");
+        let ui = handle_command(make_command("reset")).unwrap();
+        assert_contains_text(&ui, "Recent tools");
+        let rendered = ui.to_string();
+        let positions: Vec<Option<usize>> = [
+            "recent_ruler",
+            "recent_jwt_decoder",
+            "recent_logic_engine",
+            "recent_vault",
+        ]
+        .iter()
+        .map(|id| rendered.find(&format!("\"id\":\"{id}\"")))
+        .collect();
+        assert!(positions.iter().all(Option::is_some));
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+        assert!(!rendered.contains("\"id\":\"recent_multi_hash\""));
 
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.last_hash, None);
-        assert_eq!(state.last_error.as_deref(), Some("missing_path"));
-        assert_eq!(state.last_hash_algo.as_deref(), Some("MD4"));
+        let ui = handle_command(make_command("home_edit_favorites")).unwrap();
+        assert!(!extract_texts(&ui)
+            .iter()
+            .any(|t| t.contains("Recent tools")));
+        handle_command(make_command("home_edit_favorites")).unwrap();
     }
 
     #[test]
-    fn text_viewer_open_runs_on_worker() {
+    fn job_queue_lists_and_cancels_worker_jobs() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
         TEST_FORCE_ASYNC_WORKER.store(true, Ordering::SeqCst);
+        TEST_WORKER_DELAY_MS.store(300, Ordering::SeqCst);
 
         let mut file = NamedTempFile::new().unwrap();
         file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
         file.flush().unwrap();
+        for _ in 0..2 {
+            let mut hash_cmd = make_command("hash_file_sha256");
+            hash_cmd.path = Some(file.path().to_string_lossy().into_owned());
+            handle_command(hash_cmd).expect("hash dispatch should succeed");
+        }
+        let global = STATE.get_or_init(GlobalState::new);
+        let (running, pending) = {
+            let jobs = global.jobs_lock();
+            let last = jobs.entries().len();
+            (jobs.entries()[last - 2].id, jobs.entries()[last - 1].id)
+        };
+        let start = Instant::now();
+        while global
+            .jobs_lock()
+            .entries()
+            .iter()
+            .any(|e| e.id == running && e.started_at.is_none())
+        {
+            assert!(
+                start.elapsed() < Duration::from_secs(2),
+                "first job never started"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        global.ui_lock().push_screen(Screen::Loading);
 
-        let mut cmd = make_command("text_viewer_open");
-        cmd.path = Some(file.path().to_string_lossy().into_owned());
-        let ui_loading = handle_command(cmd).expect("text_viewer_open should enqueue");
-        assert_contains_text(&ui_loading, "Loading text");
+        let ui = handle_command(make_command("job_queue_screen")).unwrap();
+        assert_contains_text(&ui, "Running");
+        assert_contains_text(&ui, "Pending");
+        assert_eq!(
+            ui.get("auto_refresh_action").and_then(Value::as_str),
+            Some("job_queue_refresh")
+        );
 
-        thread::sleep(Duration::from_millis(10));
-        let _ = handle_command(make_command("snapshot")).unwrap();
+        let cancel = |id: u64| {
+            let mut cmd = make_command("job_cancel");
+            cmd.bindings = Some(HashMap::from([("job_id".into(), id.to_string())]));
+            handle_command(cmd).unwrap()
+        };
+        cancel(pending);
+        assert!(global.ui_lock().nav_stack.contains(&Screen::Loading));
+        let ui = cancel(running);
+        assert!(ui.get("auto_refresh_ms").is_none());
+        {
+            let state = global.ui_lock();
+            assert!(!state.nav_stack.contains(&Screen::Loading));
+            assert!(matches!(state.current_screen(), Screen::JobQueue));
+        }
 
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert!(matches!(state.current_screen(), Screen::TextViewer));
-        let content = state.text_view_content.as_deref().unwrap_or("");
-        assert!(content.contains(SAMPLE_CONTENT));
+        std::thread::sleep(Duration::from_millis(700));
+        handle_command(make_command("job_queue_refresh")).unwrap();
+        {
+            let state = global.ui_lock();
+            assert!(
+                state.last_hash.is_none(),
+                "cancelled job result was applied"
+            );
+            assert!(matches!(state.current_screen(), Screen::JobQueue));
+        }
+        cancel(running);
+        assert_eq!(
+            global.ui_lock().last_error.as_deref(),
+            Some(format!("job_not_active:{running}").as_str())
+        );
+
+        handle_command(make_command("job_queue_clear")).unwrap();
+        assert!(!global
+            .jobs_lock()
+            .entries()
+            .iter()
+            .any(|e| e.id == running || e.id == pending));
 
         TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
+        TEST_WORKER_DELAY_MS.store(0, Ordering::SeqCst);
     }
 
     #[test]
-    fn file_info_runs_on_worker_and_updates_state() {
+    fn superseded_worker_results_are_dropped() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
-        TEST_FORCE_ASYNC_WORKER.store(true, Ordering::SeqCst);
-
         let mut file = NamedTempFile::new().unwrap();
         file.write_all(SAMPLE_CONTENT.as_bytes()).unwrap();
         file.flush().unwrap();
+        let hash_job = || WorkerJob::Hash {
+            source: HashSourceInput::Path(file.path().to_string_lossy().into_owned()),
+            algo: HashAlgo::Sha256,
+        };
 
-        let mut cmd = make_command("file_info");
-        cmd.path = Some(file.path().to_string_lossy().into_owned());
-        let ui_loading = handle_command(cmd).expect("file_info should enqueue");
-        assert_contains_text(&ui_loading, "Reading file info");
+        let global = STATE.get_or_init(GlobalState::new);
+        let (old_job, new_job) = (hash_job(), hash_job());
+        let old_id = global
+            .jobs_lock()
+            .submit(old_job.label(), old_job.result_slot());
+        let new_id = global
+            .jobs_lock()
+            .submit(new_job.label(), new_job.result_slot());
+        global.run_tracked_job(new_id, new_job);
+        global.run_tracked_job(old_id, old_job);
+        assert_eq!(global.notifications.lock().unwrap().len(), 1);
+        let old_status = global
+            .jobs_lock()
+            .entries()
+            .iter()
+            .find(|e| e.id == old_id)
+            .map(|e| e.status);
+        assert_eq!(old_status, Some(features::job_queue::JobStatus::Superseded));
 
-        thread::sleep(Duration::from_millis(10));
-        let _ = handle_command(make_command("snapshot")).unwrap();
+        handle_command(make_command("snapshot")).unwrap();
+        assert!(global.ui_lock().last_hash.is_some());
+    }
 
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.current_screen(), Screen::FileInfo);
-        assert!(state.last_file_info.is_some());
-        assert!(state.last_error.is_none());
+    #[test]
+    fn worker_progress_drives_loading_bar_until_result() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        let global = STATE.get_or_init(GlobalState::new);
+        let id = global.jobs_lock().submit("Image resize", None);
+        assert!(global.jobs_lock().start(id));
+        {
+            let mut state = global.ui_lock();
+            state.push_screen(Screen::Loading);
+            state.loading_message = Some("Resizing images...".into());
+        }
+
+        CURRENT_JOB.with(|current| current.set(Some(id)));
+        report_progress("resize", Some(0.25), Some("Resizing 1 of 4".into()));
+        report_progress("resize", Some(0.5), Some("Resizing 2 of 4".into()));
+        CURRENT_JOB.with(|current| current.set(None));
+        report_progress("resize", Some(0.75), None);
+        assert_eq!(global.notifications.lock().unwrap().len(), 1);
+
+        let ui = handle_command(make_command("poll_progress")).unwrap();
+        assert_contains_text(&ui, "Resizing images...");
+        assert_contains_text(&ui, "Resizing 2 of 4");
+        let rendered = ui.to_string();
+        assert!(rendered.contains("\"type\":\"ProgressBar\""));
+        assert!(rendered.contains("\"fraction\":0.5"));
+        assert_eq!(
+            ui.get("auto_refresh_action").and_then(Value::as_str),
+            Some("poll_progress")
+        );
+        let progress = global
+            .jobs_lock()
+            .entries()
+            .iter()
+            .find(|e| e.id == id)
+            .and_then(|e| e.progress);
+        assert_eq!(progress, Some(0.5));
 
-        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
+        assert!(global.jobs_lock().finish(id));
+        global.push_worker_result(WorkerResult::Hash {
+            value: Ok("abc".into()),
+        });
+        handle_command(make_command("poll_progress")).unwrap();
+        let state = global.ui_lock();
+        assert!(state.job_progress.is_none());
+        assert!(state.loading_message.is_none());
     }
 
     #[test]
-    fn text_viewer_find_clear_removes_query() {
+    fn picked_locale_outlives_device_locale_until_reset_to_system() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
+        fn find_id<'a>(node: &'a Value, id: &str) -> Option<&'a Value> {
+            if node.get("id").and_then(Value::as_str) == Some(id) {
+                return Some(node);
+            }
+            node.get("children")
+                .and_then(Value::as_array)?
+                .iter()
+                .find_map(|child| find_id(child, id))
+        }
+        let init = |system_locale: &str| {
+            let mut cmd = make_command("init");
+            cmd.bindings = Some(HashMap::from([(
+                "system_locale".into(),
+                system_locale.into(),
+            )]));
+            handle_command(cmd).unwrap()
+        };
+        let set_locale = |locale: &str| {
+            let mut cmd = make_command("set_locale");
+            cmd.bindings = Some(HashMap::from([("locale".into(), locale.into())]));
+            handle_command(cmd).unwrap()
+        };
 
-        handle_command(make_command("text_viewer_screen")).expect("enter text viewer");
-
-        let mut find_cmd = make_command("text_viewer_find_submit");
-        find_cmd.bindings = Some(HashMap::from_iter([("find_query".into(), "needle".into())]));
-        handle_command(find_cmd).expect("set find query");
+        init("fr-FR");
+        handle_command(make_command("settings_screen")).unwrap();
+        let ui = set_locale("de-AT");
+        let german = find_id(&ui, "locale_de").expect("one button per locale");
+        assert_eq!(german["text"], json!("Deutsch"));
+        assert_eq!(german["content_description"], json!("selected_locale"));
+        assert_eq!(german["payload"], json!({ "locale": "de" }));
 
-        let mut clear_cmd = make_command("text_viewer_find_clear");
-        clear_cmd.bindings = Some(HashMap::from_iter([("find_query".into(), "".into())]));
-        let ui = handle_command(clear_cmd).expect("clear find query");
+        // Relaunching on a French device keeps the pick.
+        let ui = init("fr-FR");
+        assert_eq!(
+            find_id(&ui, "locale_system").unwrap()["text"],
+            json!("Systemstandard")
+        );
 
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert!(state.text_view_find_query.is_none());
+        let ui = set_locale("");
         assert_eq!(
-            state.text_view_find_match.as_deref(),
-            Some("Cleared search")
+            find_id(&ui, "locale_system").unwrap()["content_description"],
+            json!("selected_locale")
+        );
+        assert_eq!(
+            find_id(&ui, "locale_de").unwrap()["text"],
+            json!("Allemand")
         );
-        drop(state);
 
-        assert!(ui.get("find_query").is_none());
+        set_locale("xx");
+        {
+            let mut state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.locale, "fr");
+            assert_eq!(state.last_error.as_deref(), Some("unsupported_locale:xx"));
+            state.system_locale.clear();
+        }
+        handle_command(make_command("reset")).unwrap();
+        assert_eq!(rust_i18n::locale().to_string(), "en");
     }
 
     #[test]
-    fn pdf_select_sets_aspect_ratio() {
+    fn theme_setting_is_rendered_on_every_root() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
-        TEST_FORCE_ASYNC_WORKER.store(true, Ordering::SeqCst);
+        let ui = handle_command(make_command("settings_screen")).unwrap();
+        assert_eq!(ui["theme"], json!("system"));
+
+        let mut cmd = make_command("set_theme");
+        cmd.bindings = Some(HashMap::from([("theme_mode".into(), "dark".into())]));
+        let ui = handle_command(cmd).unwrap();
+        assert_eq!(ui["theme"], json!("dark"));
+        fn find_id<'a>(node: &'a Value, id: &str) -> Option<&'a Value> {
+            if node.get("id").and_then(Value::as_str) == Some(id) {
+                return Some(node);
+            }
+            node.get("children")
+                .and_then(Value::as_array)?
+                .iter()
+                .find_map(|child| find_id(child, id))
+        }
+        let dark = find_id(&ui, "theme_dark").expect("theme picker on settings");
+        assert_eq!(dark["content_description"], json!("selected_theme"));
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.theme_mode, ThemeMode::Dark);
+            assert!(matches!(state.current_screen(), Screen::Settings));
+        }
 
-        // Build a tiny PDF with known aspect ratio 2:1
-        let mut doc = lopdf::Document::with_version("1.4");
-        let page_id = doc.new_object_id();
-        let contents_id = doc.add_object(lopdf::Stream::new(dictionary! {}, Vec::new()));
-        let pages_id = doc.add_object(dictionary! {
-            "Type" => "Pages",
-            "Kids" => vec![lopdf::Object::Reference(page_id)],
-            "Count" => 1i64
-        });
-        let media_box = vec![0.into(), 0.into(), 200.into(), 100.into()];
-        let page_dict = dictionary! {
-            "Type" => "Page",
-            "Parent" => pages_id,
-            "MediaBox" => media_box,
-            "Contents" => contents_id,
-            "Resources" => dictionary! {}
+        let ui = handle_command(make_command("about")).unwrap();
+        assert_eq!(ui["theme"], json!("dark"));
+        assert_eq!(ui["layout_direction"], json!("ltr"));
+    }
+
+    #[test]
+    fn usage_stats_are_opt_in_and_reorder_home() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        let global = STATE.get_or_init(GlobalState::new);
+        global.ui_lock().tool_usage.clear();
+        let toggle = |action: &str, key: &str, on: bool| {
+            let mut cmd = make_command(action);
+            cmd.bindings = Some(HashMap::from([(key.into(), on.to_string())]));
+            handle_command(cmd).unwrap()
+        };
+        let visit = |action: &str| {
+            handle_command(make_command(action)).unwrap();
+            handle_command(make_command("back")).unwrap();
         };
-        doc.objects.insert(page_id, lopdf::Object::Dictionary(page_dict));
-        let catalog_id = doc.add_object(dictionary! {
-            "Type" => "Catalog",
-            "Pages" => pages_id
-        });
-        doc.trailer.set("Root", catalog_id);
 
-        let mut file = tempfile::NamedTempFile::new().unwrap();
-        doc.save(file.path()).unwrap();
-        file.flush().unwrap();
+        visit("jwt_screen");
+        assert_eq!(global.ui_lock().tool_usage[0].launches, 0);
 
-        let mut cmd = make_command("pdf_select");
-        cmd.fd = Some(File::open(file.path()).unwrap().into_raw_fd());
-        let _ = handle_command(cmd).expect("pdf_select should enqueue");
+        toggle("usage_stats_toggle", "usage_stats_enabled", true);
+        visit("ruler_screen");
+        handle_command(make_command("jwt_screen")).unwrap();
+        if let Some(session) = global.ui_lock().tool_session.as_mut() {
+            session.since -= 120;
+        }
+        handle_command(make_command("back")).unwrap();
+        visit("jwt_screen");
+        {
+            let state = global.ui_lock();
+            assert!(state.tool_session.is_none());
+            let jwt = state
+                .tool_usage
+                .iter()
+                .find(|u| u.id == "jwt_decoder")
+                .unwrap();
+            assert_eq!(jwt.launches, 2);
+            assert!(jwt.total_secs >= 120);
+        }
 
-        thread::sleep(Duration::from_millis(10));
-        let _ = handle_command(make_command("snapshot")).unwrap();
+        let position = |ui: &Value, id: &str| {
+            let needle = format!("\"id\":\"{id}\"");
+            ui.to_string().rfind(&needle).expect("tool listed on home")
+        };
+        let home = handle_command(make_command("reset")).unwrap();
+        assert!(position(&home, "ruler") < position(&home, "jwt_decoder"));
+        let home = toggle("home_sort_by_usage_toggle", "home_sort_by_usage", true);
+        assert!(position(&home, "jwt_decoder") < position(&home, "ruler"));
+
+        let ui = handle_command(make_command("usage_stats_screen")).unwrap();
+        assert_contains_text(&ui, "2 launches · 2m 0");
+        assert_contains_text(&ui, "1 launch · ");
+        handle_command(make_command("usage_stats_clear")).unwrap();
+        let ui = handle_command(make_command("confirm_pending")).unwrap();
+        assert_contains_text(&ui, "No launches recorded yet.");
+
+        toggle("usage_stats_toggle", "usage_stats_enabled", false);
+        visit("ruler_screen");
+        let state = global.ui_lock();
+        assert!(state.tool_usage.iter().all(|u| u.launches == 0));
+    }
 
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.pdf.page_count, Some(1));
-        assert_eq!(state.pdf.page_aspect_ratio, Some(2.0));
+    #[test]
+    fn batched_commands_apply_in_order_under_one_dispatch() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
 
-        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
+        let ui = handle_commands(parse_commands(
+            r#"[{"action":"increment"},{"action":"increment"},{"action":"increment"}]"#,
+        ))
+        .expect("batch should succeed");
+        assert_contains_text(&ui, "Tool menu");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.counter, 3);
+        }
+
+        // One bad element rejects the whole batch rather than applying part of it.
+        handle_commands(parse_commands(r#"[{"action":"increment"},{"path":1}]"#))
+            .expect("invalid batch still renders");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.counter, 3);
+            assert_eq!(state.last_error.as_deref(), Some("invalid_json"));
+        }
+
+        assert_eq!(parse_commands(r#"{"action":"increment"}"#).len(), 1);
+        handle_commands(parse_commands("[]")).expect("empty batch renders current screen");
+        {
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert_eq!(state.counter, 3);
+        }
+
+        // The batch renders once, after its last command, so a one-shot flag set by an
+        // earlier command still reaches the client.
+        let ui = handle_commands(parse_commands(
+            r#"[{"action":"color_copy_clipboard"},{"action":"increment"}]"#,
+        ))
+        .expect("batch should succeed");
+        assert_eq!(ui.get("haptic"), Some(&Value::Bool(true)));
     }
 
     #[test]
-    fn pdf_sign_runs_on_worker() {
+    fn cbor_commands_dispatch_like_json() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
-        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
 
-        // Build a simple single-page PDF
-        let mut doc = lopdf::Document::with_version("1.4");
-        let page_id = doc.new_object_id();
-        let contents_id = doc.add_object(lopdf::Stream::new(dictionary! {}, Vec::new()));
-        let pages_id = doc.add_object(dictionary! {
-            "Type" => "Pages",
-            "Kids" => vec![lopdf::Object::Reference(page_id)],
-            "Count" => 1i64
-        });
-        let media_box = vec![0.into(), 0.into(), 200.into(), 200.into()];
-        let page_dict = dictionary! {
-            "Type" => "Page",
-            "Parent" => pages_id,
-            "MediaBox" => media_box,
-            "Contents" => contents_id,
-            "Resources" => dictionary! {}
+        let mut input = Vec::new();
+        ciborium::ser::into_writer(
+            &json!([{ "action": "increment" }, { "action": "increment", "ui_revision": 0 }]),
+            &mut input,
+        )
+        .unwrap();
+        let commands = parse_cbor_commands(&input).expect("valid CBOR");
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[1].ui_revision, Some(0));
+
+        let ui = handle_commands(commands).expect("batch should succeed");
+        let mut output = Vec::new();
+        ciborium::ser::into_writer(&ui, &mut output).unwrap();
+        let decoded: Value = ciborium::de::from_reader(output.as_slice()).unwrap();
+        assert_eq!(decoded, ui);
+        assert_eq!(STATE.get_or_init(GlobalState::new).ui_lock().counter, 2);
+
+        let Err(rejected) = parse_cbor_commands(b"{\"action\":\"increment\"}") else {
+            panic!("a JSON payload should not parse as CBOR");
         };
-        doc.objects.insert(page_id, lopdf::Object::Dictionary(page_dict));
-        let catalog_id = doc.add_object(dictionary! {
-            "Type" => "Catalog",
-            "Pages" => pages_id
-        });
-        doc.trailer.set("Root", catalog_id);
+        assert!(rejected.starts_with("invalid_cbor:"), "{rejected}");
+        assert!(parse_cbor_commands(&[]).is_err());
+        assert!(UI_ENCODINGS.split(',').any(|e| e == "cbor"));
+    }
 
-        let mut pdf_file = tempfile::NamedTempFile::new().unwrap();
-        doc.save(pdf_file.path()).unwrap();
-        pdf_file.flush().unwrap();
+    #[test]
+    fn debug_log_records_rejected_commands_and_exports_them() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path());
+        reset_state();
 
-        // 1x1 transparent PNG
-        let mut png_buf = Vec::new();
+        handle_command(make_command("debug_log_probe_action")).unwrap();
+        let ui = handle_command(make_command("debug_log_screen")).unwrap();
+        assert_contains_text(&ui, "Debug log");
+        assert_contains_text(
+            &ui,
+            "rejected command: unknown_action:debug_log_probe_action",
+        );
+
+        handle_command(make_command("debug_log_view")).unwrap();
         {
-            let img = image::RgbaImage::from_pixel(1, 1, image::Rgba([0, 0, 0, 0]));
-            let encoder = PngEncoder::new(&mut png_buf);
-            encoder.write_image(&img, 1, 1, ColorType::Rgba8).unwrap();
+            let state = STATE.get_or_init(GlobalState::new).ui_lock();
+            assert!(matches!(state.current_screen(), Screen::TextViewer));
+            assert!(state
+                .text_view_content
+                .as_deref()
+                .is_some_and(|c| c.contains("WARN  router: rejected command")));
         }
-        use base64::Engine;
-        let sig_b64 = base64::engine::general_purpose::STANDARD.encode(&png_buf);
+        handle_command(make_command("back")).unwrap();
+
+        let ui = handle_command(make_command("debug_log_export")).unwrap();
+        let path = ui["toast"]
+            .as_str()
+            .and_then(|t| t.strip_prefix("Result saved to: "))
+            .expect("export should report its path")
+            .to_string();
+        let exported = std::fs::read_to_string(&path).unwrap();
+        assert!(exported.contains("debug_log_probe_action"));
+        let _ = std::fs::remove_file(&path);
+
+        handle_command(make_command("debug_log_clear")).unwrap();
+        assert!(!crate::logging::recent()
+            .iter()
+            .any(|e| e.message.contains("debug_log_probe_action")));
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
+    }
+
+    #[test]
+    fn tabs_keep_every_pane_and_remember_the_selection() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+
+        fn find_tabs(node: &Value) -> Option<&Value> {
+            if node.get("type").and_then(Value::as_str) == Some("Tabs") {
+                return Some(node);
+            }
+            node.get("children")
+                .and_then(Value::as_array)
+                .and_then(|children| children.iter().find_map(find_tabs))
+        }
+        fn tab_ids(tabs: &Value) -> Vec<&str> {
+            tabs["children"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|tab| tab["id"].as_str().unwrap())
+                .collect()
+        }
+
+        let ui =
+            handle_command(make_command("text_tools_screen")).expect("text tools should render");
+        let tabs = find_tabs(&ui).expect("text tools tabs");
+        assert_eq!(tab_ids(tabs), ["transform", "encode", "analyze"]);
+        assert_eq!(tabs["selected"], "transform");
+        assert_eq!(tabs["bind_key"], "text_tools_tab");
+        assert_contains_text(&ui, "Base64 encode");
+
+        let mut command = make_command("text_tools_refresh");
+        command.bindings = Some(HashMap::from([("text_tools_tab".into(), "encode".into())]));
+        let ui = handle_command(command).expect("tab switch should succeed");
+        assert_eq!(find_tabs(&ui).unwrap()["selected"], "encode");
+
+        let ui = handle_command(make_command("pdf_tools_screen")).expect("pdf tools should render");
+        let tabs = find_tabs(&ui).expect("pdf tabs");
+        assert_eq!(tab_ids(tabs), ["pages", "metadata", "signature"]);
+        assert_contains_text(&ui, "Set PDF title");
+
+        let mut command = make_command("pdf_tab_select");
+        command.bindings = Some(HashMap::from([("pdf_tab".into(), "signature".into())]));
+        let ui = handle_command(command).expect("pdf tab switch should succeed");
+        assert_eq!(find_tabs(&ui).unwrap()["selected"], "signature");
+
+        let mut command = make_command("pdf_tab_select");
+        command.bindings = Some(HashMap::from([("pdf_tab".into(), "fonts".into())]));
+        handle_command(command).unwrap();
+        let state = STATE.get_or_init(GlobalState::new).ui_lock();
+        assert_eq!(state.last_error.as_deref(), Some("invalid_pdf_tab"));
+        assert_eq!(state.pdf.tab, PdfTab::Signature);
+    }
 
-        let mut cmd = make_command("pdf_sign");
-        cmd.fd = Some(File::open(pdf_file.path()).unwrap().into_raw_fd());
-        cmd.bindings = Some(HashMap::from_iter([
-            ("signature_base64".into(), sig_b64),
-            ("pdf_signature_page".into(), "1".into()),
-            ("pdf_signature_x_pct".into(), "0.5".into()),
-            ("pdf_signature_y_pct".into(), "0.5".into()),
-            ("pdf_signature_x".into(), "10".into()),
-            ("pdf_signature_y".into(), "20".into()),
-            ("pdf_signature_width".into(), "30".into()),
-            ("pdf_signature_height".into(), "40".into()),
-        ]));
+    #[test]
+    fn gzip_benchmark_stores_levels_for_the_chart() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
 
-        let _ui = handle_command(cmd).expect("pdf_sign should enqueue");
+        let mut file = NamedTempFile::new().unwrap();
+        let content = SAMPLE_CONTENT.repeat(200);
+        file.write_all(content.as_bytes()).unwrap();
+        file.flush().unwrap();
 
-        let _ = handle_command(make_command("snapshot")).unwrap();
+        let mut command = make_command("gzip_benchmark");
+        command.path = Some(file.path().to_string_lossy().into_owned());
+        let ui = handle_command(command).expect("benchmark should succeed");
+        assert!(ui.to_string().contains("\"Chart\""));
 
         let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        if let Some(err) = &state.pdf.last_error {
-            panic!("pdf_sign worker returned error: {err}");
-        }
-        assert!(state.pdf.last_output.is_some());
-        assert_eq!(state.current_screen(), Screen::PdfTools);
+        let bench = state.compression_benchmark.as_ref().unwrap();
+        assert_eq!(bench.levels.len(), 3);
+        assert!(state.compression_error.is_none());
+    }
 
-        TEST_FORCE_ASYNC_WORKER.store(false, Ordering::SeqCst);
+    #[test]
+    fn batch_inspection_reports_progress_per_file() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        reset_state();
+        let dir = tempfile::tempdir().unwrap();
+        let paths: Vec<String> = ["a.txt", "b.txt"]
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(name);
+                std::fs::write(&path, name).unwrap();
+                path.to_string_lossy().into_owned()
+            })
+            .collect();
+        let global = STATE.get_or_init(GlobalState::new);
+        global.drain_worker_results();
+        let id = global.jobs_lock().submit("Batch file info", None);
+        assert!(global.jobs_lock().start(id));
+
+        CURRENT_JOB.with(|current| current.set(Some(id)));
+        let result = run_worker_job(WorkerJob::FileInfoBatch {
+            paths,
+            fds: Vec::new(),
+            names: Vec::new(),
+        });
+        CURRENT_JOB.with(|current| current.set(None));
+
+        assert!(matches!(result, WorkerResult::FileInfoBatch { ref value } if value.len() == 2));
+        let notifications = global.notifications.lock().unwrap();
+        assert_eq!(notifications.len(), 1);
+        match &notifications[0] {
+            WorkerResult::WorkerProgress {
+                stage,
+                fraction,
+                message,
+                ..
+            } => {
+                assert_eq!(stage, "inspect");
+                assert_eq!(*fraction, Some(0.5));
+                assert_eq!(message.as_deref(), Some("Inspecting 2 of 2"));
+            }
+            _ => panic!("expected a progress update"),
+        }
     }
 
     #[test]
-    fn pdf_sign_grid_updates_coordinates() {
+    fn toasts_queue_until_the_next_render_and_show_once() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
         {
             let mut state = STATE.get_or_init(GlobalState::new).ui_lock();
-            state.pdf.page_count = Some(3);
-            state.pdf.source_uri = Some("file://dummy.pdf".into());
+            state.push_toast("Saved");
+            state.push_toast("Saved");
+            state.push_toast("");
         }
 
-        let mut cmd = make_command("pdf_sign_grid");
-        cmd.bindings = Some(HashMap::from_iter([
-            ("pdf_signature_page".into(), "2".into()),
-            ("pdf_signature_x_pct".into(), "0.8".into()),
-            ("pdf_signature_y_pct".into(), "0.3".into()),
-        ]));
-        handle_command(cmd).expect("grid action should succeed");
+        let ui = handle_command(make_command("color_copy_clipboard")).unwrap();
+        assert_eq!(ui["toast"], json!("Saved\nCopied to clipboard"));
+        let ui = handle_command(make_command("back")).unwrap();
+        assert!(ui.get("toast").is_none());
 
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.pdf.signature_target_page, Some(2));
-        assert_eq!(state.pdf.signature_x_pct, Some(0.8));
-        assert_eq!(state.pdf.signature_y_pct, Some(0.3));
-        assert_eq!(state.pdf.signature_grid_selection, Some((2, 0.8, 0.3)));
+        let mut state = AppState::new();
+        for i in 0..5 {
+            state.push_toast(format!("Message {i}"));
+        }
+        assert_eq!(state.toasts.len(), crate::state::TOAST_QUEUE_MAX);
+        assert_eq!(state.toasts[0], "Message 2");
     }
 
     #[test]
-    fn render_menu_uses_icon_without_dup_title() {
-        let state = AppState::new();
-        let catalog = vec![Feature {
-            id: "hash_sha256",
-            name: "SHA-256",
-            category: "🔐 Hashes",
-            action: "hash_file_sha256",
-            requires_file_picker: true,
-            description: "secure hash",
-        }];
-        let ui = render_menu(&state, &catalog);
-        fn find_section(node: &Value) -> Option<&Value> {
-            if node.get("type").and_then(|t| t.as_str()) == Some("Section") {
+    fn destructive_commands_wait_for_confirmation() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let _env = crate::features::storage::test_env_lock().lock().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        std::env::set_var("KISTAVERK_TEMP_DIR", data_dir.path().join("cache"));
+        reset_state();
+
+        fn find_dialog(node: &Value) -> Option<&Value> {
+            if node.get("type").and_then(Value::as_str) == Some("ConfirmDialog") {
                 return Some(node);
             }
-            if let Some(children) = node.get("children").and_then(|c| c.as_array()) {
-                for child in children {
-                    if let Some(sec) = find_section(child) {
-                        return Some(sec);
-                    }
-                }
-            }
-            None
+            node.get("children")
+                .and_then(Value::as_array)
+                .and_then(|children| children.iter().find_map(find_dialog))
         }
-        let section = find_section(&ui).expect("section present");
-        assert_eq!(section.get("icon").and_then(|v| v.as_str()), Some("🔐"));
-        assert_eq!(section.get("title").and_then(|v| v.as_str()), Some("Hashes"));
+
+        let mut state = AppState::new();
+        state.math_tool.history.push(MathHistoryEntry {
+            expression: "1+1".into(),
+            result: "2".into(),
+            error_estimate: None,
+            precision_bits: 64,
+        });
+
+        let ui = apply_command(&mut state, make_command("math_clear_history"), false).unwrap();
+        assert_eq!(state.math_tool.history.len(), 1);
+        let dialog = find_dialog(&ui).expect("confirmation shown");
+        assert_eq!(dialog["confirm_action"], "confirm_pending");
+        assert_eq!(dialog["cancel_action"], "cancel_pending");
+
+        let ui = apply_command(&mut state, make_command("cancel_pending"), false).unwrap();
+        assert!(state.pending_confirmation.is_none());
+        assert!(find_dialog(&ui).is_none());
+        assert_eq!(state.math_tool.history.len(), 1);
+
+        apply_command(&mut state, make_command("math_clear_history"), false).unwrap();
+        apply_command(&mut state, make_command("confirm_pending"), false).unwrap();
+        assert!(state.pending_confirmation.is_none());
+        assert!(state.math_tool.history.is_empty());
+
+        // Small archives extract straight away; only large listings ask first.
+        state.archive.entries = (0..ARCHIVE_CONFIRM_ENTRIES)
+            .map(|i| crate::features::archive::ArchiveEntry {
+                name: format!("file{i}.txt"),
+                size: 1,
+                is_dir: false,
+                original_index: i,
+            })
+            .collect();
+        assert!(confirmation_for(&state, &make_command("archive_extract_all")).is_some());
+        state.archive.entries.truncate(3);
+        assert!(confirmation_for(&state, &make_command("archive_extract_all")).is_none());
+        std::env::remove_var("KISTAVERK_TEMP_DIR");
     }
 
     #[test]
-    fn pdf_merge_pick_populates_queue() {
+    fn home_categories_collapse_and_filtering_reveals_them() {
         let _guard = TEST_MUTEX.lock().unwrap();
         reset_state();
-        let mut cmd = make_command("pdf_merge_pick");
-        cmd.path_list = Some(vec!["a.pdf".into(), "b.pdf".into()]);
-        handle_command(cmd).expect("merge pick should succeed");
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.pdf.merge_queue, vec!["a.pdf", "b.pdf"]);
+        let mut state = AppState::new();
+        let toggle = |state: &mut AppState, expanded: bool| {
+            let mut cmd = make_command("section_toggle");
+            cmd.bindings = Some(HashMap::from([
+                ("section_key".into(), "home_category:🧰 Utilities".into()),
+                ("section_expanded".into(), expanded.to_string()),
+            ]));
+            apply_command(state, cmd, false).unwrap()
+        };
+
+        let ui = apply_command(&mut state, make_command("reset"), false).unwrap();
+        assert_contains_text(&ui, "JWT decoder");
+
+        let ui = toggle(&mut state, false);
+        assert!(!ui.to_string().contains("JWT decoder"));
+        assert!(ui.to_string().contains("\"expanded\":false"));
+        assert!(!state.is_section_expanded("home_category:🧰 Utilities", true));
+
+        let mut filter = make_command("home_filter");
+        filter.bindings = Some(HashMap::from([("home_filter".into(), "jwt".into())]));
+        let ui = apply_command(&mut state, filter, false).unwrap();
+        assert_contains_text(&ui, "JWT decoder");
+
+        let mut clear = make_command("home_filter");
+        clear.bindings = Some(HashMap::from([("home_filter".into(), String::new())]));
+        apply_command(&mut state, clear, false).unwrap();
+        let ui = toggle(&mut state, true);
+        assert_contains_text(&ui, "JWT decoder");
+
+        apply_command(&mut state, make_command("section_toggle"), false).unwrap();
+        assert_eq!(state.last_error.as_deref(), Some("missing_section_key"));
     }
 
     #[test]
-    fn kotlin_image_batch_pick_populates_queue() {
-        let _guard = TEST_MUTEX.lock().unwrap();
-        reset_state();
-        let mut cmd = make_command("kotlin_image_batch_pick");
-        cmd.path_list = Some(vec!["/tmp/1.png".into(), "/tmp/2.png".into()]);
-        handle_command(cmd).expect("batch pick should succeed");
-        let state = STATE.get_or_init(GlobalState::new).ui_lock();
-        assert_eq!(state.image.batch_queue, vec!["/tmp/1.png", "/tmp/2.png"]);
+    fn ui_revisions_turn_small_changes_into_patches() {
+        let global = GlobalState::new();
+        let screen = |query: &str| {
+            json!({
+                "type": "Column",
+                "children": [
+                    { "type": "CodeView", "text": SAMPLE_WRAP.repeat(20) },
+                    { "type": "TextInput", "bind_key": "find_query", "text": query }
+                ]
+            })
+        };
+
+        let first = global.encode_ui(screen(""), Some(0));
+        assert_eq!(first["ui_revision"], json!(1));
+        assert_eq!(first["type"], json!("Column"));
+
+        let patch = global.encode_ui(screen("rust"), Some(1));
+        assert_eq!(patch["type"], json!(PATCH_TYPE));
+        assert_eq!(patch["base"], json!(1));
+        assert_eq!(patch["ui_revision"], json!(2));
+        assert_eq!(patch["ops"].as_array().map(Vec::len), Some(1));
+        assert_eq!(patch["ops"][0]["props"]["text"], json!("rust"));
+
+        // A client that missed revision 2 gets the whole tree again.
+        let resync = global.encode_ui(screen("rust!"), Some(1));
+        assert_eq!(resync["ui_revision"], json!(3));
+        assert_eq!(resync["type"], json!("Column"));
+
+        let snapshot = json!({ "type": "Snapshot", "snapshot": "{}" });
+        assert_eq!(global.encode_ui(snapshot.clone(), Some(3)), snapshot);
+        assert_eq!(global.encode_ui(screen(""), None), screen(""));
     }
 
     #[test]
@@ -6751,13 +14555,40 @@ fn apply_worker_results(state: &mut AppState) {
     for (task_id, action, fired_at) in drain_scheduler_events() {
         apply_scheduler_result(state, task_id, action, fired_at);
     }
-    let results = STATE.get_or_init(GlobalState::new).drain_worker_results();
+    let (progress, results): (Vec<_>, Vec<_>) = STATE
+        .get_or_init(GlobalState::new)
+        .drain_worker_results()
+        .into_iter()
+        .partition(|r| matches!(r, WorkerResult::WorkerProgress { .. }));
+    for update in progress {
+        if let WorkerResult::WorkerProgress {
+            job_id,
+            stage,
+            fraction,
+            message,
+        } = update
+        {
+            state.job_progress = Some(JobProgress {
+                job_id,
+                stage,
+                fraction,
+                message,
+            });
+        }
+    }
     if results.is_empty() {
         return;
     }
+    // Results land on the screen under the job queue, which stays on top until closed.
+    let viewing_jobs = matches!(state.current_screen(), Screen::JobQueue);
+    if viewing_jobs {
+        state.pop_screen();
+    }
 
     for result in results {
         match result {
+            // Applied above, before any result could clear the loading screen.
+            WorkerResult::WorkerProgress { .. } => {}
             WorkerResult::Hash { value } => match value {
                 Ok(hash) => {
                     state.last_hash = Some(hash);
@@ -6765,7 +14596,7 @@ fn apply_worker_results(state: &mut AppState) {
                     state.haptic = true;
                 }
                 Err(e) => {
-                    state.last_error = Some(e);
+                    state.last_error = Some(e.to_string());
                     state.last_hash = None;
                 }
             },
@@ -6775,7 +14606,7 @@ fn apply_worker_results(state: &mut AppState) {
                     state.multi_hash_error = None;
                 }
                 Err(e) => {
-                    state.multi_hash_error = Some(e);
+                    state.multi_hash_error = Some(e.to_string());
                     state.multi_hash_results = None;
                 }
             },
@@ -6783,15 +14614,17 @@ fn apply_worker_results(state: &mut AppState) {
                 Ok(res) => {
                     let cleaned_ref = res.reference.trim().to_ascii_lowercase();
                     let cleaned_hash = res.computed.trim().to_ascii_lowercase();
+                    // A keyed digest may be computed without anything to compare it to.
+                    state.hash_match =
+                        (!cleaned_ref.is_empty()).then_some(cleaned_ref == cleaned_hash);
                     state.hash_reference = Some(res.reference);
-                    state.last_hash_algo = Some(hash_label(res.algo).into());
+                    state.last_hash_algo = Some(res.label.into());
                     state.last_hash = Some(res.computed);
-                    state.hash_match = Some(cleaned_ref == cleaned_hash);
                     state.last_error = None;
                     state.replace_current(Screen::HashVerify);
                 }
                 Err(e) => {
-                    state.last_error = Some(e);
+                    state.last_error = Some(e.to_string());
                     state.last_hash = None;
                     state.hash_match = None;
                     state.replace_current(Screen::HashVerify);
@@ -6799,35 +14632,81 @@ fn apply_worker_results(state: &mut AppState) {
             },
             WorkerResult::Compression { value } => match value {
                 Ok(status) => {
+                    if let Some(path) = status.strip_prefix("Result saved to:") {
+                        state.push_toast(t!("toast_result_saved", path = path.trim()));
+                    }
                     state.compression_status = Some(status);
                     state.compression_error = None;
-                    if let Some(msg) = state.compression_status.as_deref() {
-                        if msg.starts_with("Result saved to:") {
-                            state.toast = Some(msg.to_string());
-                        }
-                    }
                     state.replace_current(Screen::Compression);
                 }
                 Err(e) => {
-                    state.compression_error = Some(e);
+                    state.compression_error = Some(e.to_string());
                     state.compression_status = None;
                     state.replace_current(Screen::Compression);
                 }
             },
+            WorkerResult::Plugin { plugin_id, value } => {
+                // A result for a plugin closed in the meantime has nowhere to go.
+                if state
+                    .plugins
+                    .active
+                    .as_ref()
+                    .is_some_and(|p| p.id == plugin_id)
+                {
+                    state.plugins.running = false;
+                    match value {
+                        Ok(output) => {
+                            state.plugins.data = output.data;
+                            state.plugins.ui = Some(output.ui);
+                            state.plugins.error = None;
+                        }
+                        Err(e) => state.plugins.error = Some(e.to_string()),
+                    }
+                    if matches!(state.current_screen(), Screen::Plugin) {
+                        state.replace_current(Screen::Plugin);
+                    }
+                }
+            }
+            WorkerResult::JitBenchmark { value } => {
+                match value {
+                    Ok(bench) => {
+                        state.jit_benchmark = Some(bench);
+                        state.jit_benchmark_error = None;
+                    }
+                    Err(e) => {
+                        state.jit_benchmark_error = Some(e.to_string());
+                        state.jit_benchmark = None;
+                    }
+                }
+                if matches!(state.current_screen(), Screen::JitBenchmark) {
+                    state.replace_current(Screen::JitBenchmark);
+                }
+            }
+            WorkerResult::CompressionBenchmark { value } => {
+                match value {
+                    Ok(bench) => {
+                        state.compression_benchmark = Some(bench);
+                        state.compression_error = None;
+                    }
+                    Err(e) => {
+                        state.compression_error = Some(e.to_string());
+                        state.compression_benchmark = None;
+                    }
+                }
+                state.replace_current(Screen::Compression);
+            }
             WorkerResult::Vault { value } => match value {
                 Ok(status) => {
+                    if let Some(path) = status.strip_prefix("Result saved to:") {
+                        state.push_toast(t!("toast_result_saved", path = path.trim()));
+                    }
                     state.vault.status = Some(status);
                     state.vault.error = None;
                     state.vault.is_processing = false;
-                    if let Some(msg) = state.vault.status.as_deref() {
-                        if msg.starts_with("Result saved to:") {
-                            state.toast = Some(msg.to_string());
-                        }
-                    }
                     state.replace_current(Screen::Vault);
                 }
                 Err(e) => {
-                    state.vault.error = Some(e);
+                    state.vault.error = Some(e.to_string());
                     state.vault.status = None;
                     state.vault.is_processing = false;
                     state.replace_current(Screen::Vault);
@@ -6838,16 +14717,176 @@ fn apply_worker_results(state: &mut AppState) {
                     state.dithering_result_path = Some(out);
                     state.dithering_error = None;
                     if let Some(path) = state.dithering_result_path.as_deref() {
-                        state.toast = Some(format!("Result saved to: {path}"));
+                        state.push_toast(t!("toast_result_saved", path = path));
                     }
                     state.replace_current(Screen::Dithering);
                 }
                 Err(e) => {
                     state.dithering_result_path = None;
-                    state.dithering_error = Some(e);
+                    state.dithering_error = Some(e.to_string());
                     state.replace_current(Screen::Dithering);
                 }
             },
+            WorkerResult::ImageEdit { value } => {
+                match value {
+                    Ok(out) => {
+                        state.push_toast(t!("toast_result_saved", path = out));
+                        state.image_edit.result_path = Some(out);
+                        state.image_edit.error = None;
+                    }
+                    Err(e) => {
+                        state.image_edit.result_path = None;
+                        state.image_edit.error = Some(e.to_string());
+                    }
+                }
+                if matches!(state.current_screen(), Screen::ImageEditor) {
+                    state.replace_current(Screen::ImageEditor);
+                }
+            }
+            WorkerResult::ImageAdjustPreview { adjustments, value } => {
+                if adjustments == state.image_adjust.adjustments {
+                    match value {
+                        Ok(preview) => {
+                            state.image_adjust.preview_png_base64 = Some(preview);
+                            state.image_adjust.error = None;
+                        }
+                        Err(e) => state.image_adjust.error = Some(e.to_string()),
+                    }
+                }
+                if matches!(state.current_screen(), Screen::ImageAdjust) {
+                    state.replace_current(Screen::ImageAdjust);
+                }
+            }
+            WorkerResult::AsciiArt { columns, value } => {
+                if columns == state.ascii_art.columns {
+                    match value {
+                        Ok(art) => {
+                            state.ascii_art.art = Some(art);
+                            state.ascii_art.error = None;
+                        }
+                        Err(e) => {
+                            state.ascii_art.art = None;
+                            state.ascii_art.error = Some(e.to_string());
+                        }
+                    }
+                }
+                if matches!(state.current_screen(), Screen::AsciiArt) {
+                    state.replace_current(Screen::AsciiArt);
+                }
+            }
+            WorkerResult::GifExplode { value } => {
+                let gif = &mut state.gif_frames;
+                match value {
+                    Ok(exploded) => {
+                        gif.selection = format!("1-{}", exploded.frames.len());
+                        gif.delay_ms = exploded.average_delay_ms().clamp(
+                            features::gif_frames::MIN_DELAY_MS,
+                            features::gif_frames::MAX_DELAY_MS,
+                        );
+                        gif.exploded = Some(exploded);
+                        gif.error = None;
+                    }
+                    Err(e) => gif.error = Some(e.to_string()),
+                }
+                if matches!(state.current_screen(), Screen::GifFrames) {
+                    state.replace_current(Screen::GifFrames);
+                }
+            }
+            WorkerResult::ExifEdit { value } => {
+                match value {
+                    Ok(out) => {
+                        state.push_toast(t!("toast_result_saved", path = out));
+                        state.exif_editor.result_path = Some(out);
+                        state.exif_editor.error = None;
+                    }
+                    Err(e) => state.exif_editor.error = Some(e.to_string()),
+                }
+                if matches!(state.current_screen(), Screen::ExifEditor) {
+                    state.replace_current(Screen::ExifEditor);
+                }
+            }
+            WorkerResult::GifAssemble { value } => {
+                match value {
+                    Ok(out) => {
+                        state.push_toast(t!("toast_result_saved", path = out));
+                        state.gif_frames.result_path = Some(out);
+                        state.gif_frames.error = None;
+                    }
+                    Err(e) => state.gif_frames.error = Some(e.to_string()),
+                }
+                if matches!(state.current_screen(), Screen::GifFrames) {
+                    state.replace_current(Screen::GifFrames);
+                }
+            }
+            WorkerResult::ImageAdjust { value } => {
+                match value {
+                    Ok(out) => {
+                        state.push_toast(t!("toast_result_saved", path = out));
+                        state.image_adjust.result_path = Some(out);
+                        state.image_adjust.error = None;
+                    }
+                    Err(e) => {
+                        state.image_adjust.result_path = None;
+                        state.image_adjust.error = Some(e.to_string());
+                    }
+                }
+                if matches!(state.current_screen(), Screen::ImageAdjust) {
+                    state.replace_current(Screen::ImageAdjust);
+                }
+            }
+            WorkerResult::Watermark { outcomes } => {
+                if let Some(path) = outcomes.iter().rev().find_map(|o| o.value.as_ref().ok()) {
+                    state.push_toast(t!("toast_result_saved", path = path));
+                }
+                state.watermark.outcomes = outcomes;
+                if matches!(state.current_screen(), Screen::Watermark) {
+                    state.replace_current(Screen::Watermark);
+                }
+            }
+            WorkerResult::Collage { value } => {
+                match value {
+                    Ok(out) => {
+                        state.push_toast(t!("toast_result_saved", path = out));
+                        state.collage.result_path = Some(out);
+                        state.collage.error = None;
+                    }
+                    Err(e) => state.collage.error = Some(e.to_string()),
+                }
+                if matches!(state.current_screen(), Screen::Collage) {
+                    state.replace_current(Screen::Collage);
+                }
+            }
+            WorkerResult::ImageHistogram { value } => {
+                match value {
+                    Ok(stats) => state.image_histogram.stats = Some(stats),
+                    Err(e) => state.image_histogram.error = Some(e.to_string()),
+                }
+                if matches!(state.current_screen(), Screen::ImageHistogram) {
+                    state.replace_current(Screen::ImageHistogram);
+                }
+            }
+            WorkerResult::MetadataStrip { outcomes } => {
+                if let Some(path) = outcomes.iter().rev().find_map(|o| o.output_path.as_deref()) {
+                    state.push_toast(t!("toast_result_saved", path = path));
+                }
+                state.metadata_strip.outcomes = outcomes;
+                if matches!(state.current_screen(), Screen::MetadataStrip) {
+                    state.replace_current(Screen::MetadataStrip);
+                }
+            }
+            WorkerResult::ImageResize {
+                outputs,
+                output_dir,
+            } => {
+                let result = resize_result(&outputs, &output_dir);
+                if result.error.is_none() {
+                    if let Some(path) = result.path.as_deref() {
+                        state.push_toast(t!("toast_result_saved", path = path));
+                    }
+                }
+                state.image.result = Some(result);
+                state.replace_current(Screen::KotlinImage);
+            }
             WorkerResult::PixelArt { value } => match value {
                 Ok(out) => {
                     state.pixel_art.result_path = Some(out);
@@ -6856,7 +14895,7 @@ fn apply_worker_results(state: &mut AppState) {
                 }
                 Err(e) => {
                     state.pixel_art.result_path = None;
-                    state.pixel_art.error = Some(e);
+                    state.pixel_art.error = Some(e.to_string());
                     state.replace_current(Screen::PixelArt);
                 }
             },
@@ -6874,13 +14913,13 @@ fn apply_worker_results(state: &mut AppState) {
                         state.pdf.push_recent(&uri);
                     }
                     if let Some(path) = state.pdf.last_output.as_deref() {
-                        state.toast = Some(format!("Result saved to: {path}"));
+                        state.push_toast(t!("toast_result_saved", path = path));
                     }
                     state.haptic = true;
                     state.replace_current(Screen::PdfTools);
                 }
                 Err(e) => {
-                    state.pdf.last_error = Some(e);
+                    state.pdf.last_error = Some(e.to_string());
                     state.pdf.last_output = None;
                     state.replace_current(Screen::PdfTools);
                 }
@@ -6896,7 +14935,7 @@ fn apply_worker_results(state: &mut AppState) {
                     state.replace_current(Screen::ArchiveTools);
                 }
                 Err(e) => {
-                    state.archive.error = Some(e);
+                    state.archive.error = Some(e.to_string());
                     state.archive.last_output = None;
                     state.archive.entries.clear();
                     state.archive.truncated = false;
@@ -6915,7 +14954,7 @@ fn apply_worker_results(state: &mut AppState) {
                     state.replace_current(Screen::ArchiveTools);
                 }
                 Err(e) => {
-                    state.archive.error = Some(e);
+                    state.archive.error = Some(e.to_string());
                     state.archive.last_output = None;
                     state.archive.entries.clear();
                     state.archive.truncated = false;
@@ -6950,7 +14989,7 @@ fn apply_worker_results(state: &mut AppState) {
                         .map(|p| p == archive_path)
                         .unwrap_or(true);
                     if path_matches {
-                        state.archive.error = Some(e);
+                        state.archive.error = Some(e.to_string());
                         state.archive.last_output = None;
                         state.replace_current(Screen::ArchiveTools);
                     }
@@ -6959,15 +14998,34 @@ fn apply_worker_results(state: &mut AppState) {
             WorkerResult::FileInfo { value } => match value {
                 Ok(info) => {
                     state.last_file_info = Some(serde_json::to_string(&info).unwrap_or_default());
+                    state.file_info_hex = None;
+                    state.file_info_hex_error = None;
+                    state.file_info_batch.clear();
                     state.last_error = None;
                     state.replace_current(Screen::FileInfo);
                 }
                 Err(e) => {
-                    state.last_error = Some(e);
+                    state.last_error = Some(e.to_string());
                     state.last_file_info = None;
                     state.replace_current(Screen::FileInfo);
                 }
             },
+            WorkerResult::FileInfoBatch { value } => {
+                state.file_info_batch = value;
+                state.last_file_info = None;
+                state.file_info_hex = None;
+                state.last_error = None;
+                state.replace_current(Screen::FileInfo);
+            }
+            WorkerResult::FileInfoBatchHash { index, value } => {
+                if let Some(entry) = state.file_info_batch.get_mut(index) {
+                    match value {
+                        Ok(hash) => entry.sha256 = Some(hash),
+                        Err(e) => entry.error = Some(e.to_string()),
+                    }
+                }
+                state.replace_current(Screen::FileInfo);
+            }
             WorkerResult::PdfSelect { value } => match value {
                 Ok(res) => {
                     state.pdf.page_count = Some(res.page_count);
@@ -6983,7 +15041,7 @@ fn apply_worker_results(state: &mut AppState) {
                     state.replace_current(Screen::PdfTools);
                 }
                 Err(e) => {
-                    state.pdf.last_error = Some(e);
+                    state.pdf.last_error = Some(e.to_string());
                     state.pdf.page_count = None;
                     state.pdf.page_aspect_ratio = None;
                     state.pdf.selected_pages.clear();
@@ -6997,7 +15055,7 @@ fn apply_worker_results(state: &mut AppState) {
                     state.replace_current(Screen::TextViewer);
                 }
                 Err(e) => {
-                    state.text_view_error = Some(e);
+                    state.text_view_error = Some(e.to_string());
                     state.text_view_content = None;
                     state.replace_current(Screen::TextViewer);
                 }
@@ -7013,13 +15071,13 @@ fn apply_worker_results(state: &mut AppState) {
                         state.pdf.push_recent(&uri);
                     }
                     if let Some(path) = state.pdf.last_output.as_deref() {
-                        state.toast = Some(format!("Result saved to: {path}"));
+                        state.push_toast(t!("toast_result_saved", path = path));
                     }
                     state.haptic = true;
                     state.replace_current(Screen::PdfTools);
                 }
                 Err(e) => {
-                    state.pdf.last_error = Some(e);
+                    state.pdf.last_error = Some(e.to_string());
                     state.replace_current(Screen::PdfTools);
                 }
             },
@@ -7034,13 +15092,13 @@ fn apply_worker_results(state: &mut AppState) {
                         state.pdf.push_recent(&uri);
                     }
                     if let Some(path) = state.pdf.last_output.as_deref() {
-                        state.toast = Some(format!("Result saved to: {path}"));
+                        state.push_toast(t!("toast_result_saved", path = path));
                     }
                     state.haptic = true;
                     state.replace_current(Screen::PdfTools);
                 }
                 Err(e) => {
-                    state.pdf.last_error = Some(e);
+                    state.pdf.last_error = Some(e.to_string());
                     state.replace_current(Screen::PdfTools);
                 }
             },
@@ -7056,13 +15114,13 @@ fn apply_worker_results(state: &mut AppState) {
                         state.pdf.push_recent(&uri);
                     }
                     if let Some(path) = state.pdf.last_output.as_deref() {
-                        state.toast = Some(format!("Result saved to: {path}"));
+                        state.push_toast(t!("toast_result_saved", path = path));
                     }
                     state.haptic = true;
                     state.replace_current(Screen::PdfTools);
                 }
                 Err(e) => {
-                    state.pdf.last_error = Some(e);
+                    state.pdf.last_error = Some(e.to_string());
                     state.replace_current(Screen::PdfTools);
                 }
             },
@@ -7077,7 +15135,7 @@ fn apply_worker_results(state: &mut AppState) {
                         }
                     }
                     Err(err) => {
-                        state.c_scripting.error = Some(err);
+                        state.c_scripting.error = Some(err.to_string());
                     }
                 }
                 state.c_scripting.is_running = false;
@@ -7087,8 +15145,90 @@ fn apply_worker_results(state: &mut AppState) {
                     state.replace_current(Screen::CScripting);
                 }
             }
+            WorkerResult::RegexFile { value } => {
+                match value {
+                    Ok(result) => {
+                        state.regex_tester.file_result = Some(result);
+                        state.regex_tester.error = None;
+                    }
+                    Err(e) => {
+                        state.regex_tester.file_result = None;
+                        state.regex_tester.error = Some(e.to_string());
+                    }
+                }
+                if matches!(state.current_screen(), Screen::RegexTester) {
+                    state.replace_current(Screen::RegexTester);
+                }
+            }
+            WorkerResult::MathPlot { expression, value } => match value {
+                Ok(out) => {
+                    state.push_toast(t!("toast_result_saved", path = out.path));
+                    state.math_tool.push_history(MathHistoryEntry {
+                        expression,
+                        result: out.path.clone(),
+                        error_estimate: None,
+                        precision_bits: 0,
+                    });
+                    state.math_tool.plot_path = Some(out.path);
+                    state.math_tool.plot_base64 = Some(out.base64);
+                    state.math_tool.error = persist_math_history(state).err();
+                    if matches!(state.current_screen(), Screen::MathTool) {
+                        state.replace_current(Screen::MathTool);
+                    }
+                }
+                Err(e) => {
+                    state.math_tool.plot_path = None;
+                    state.math_tool.plot_base64 = None;
+                    state.math_tool.error = Some(e.to_string());
+                }
+            },
+            WorkerResult::SensorSegmentGzip { source, value } => {
+                match value {
+                    Ok(out) => {
+                        if let Some(entry) = state.sensor_segments.iter_mut().find(|p| **p == source) {
+                            *entry = out;
+                        }
+                    }
+                    Err(e) => state.last_error = Some(e.to_string()),
+                }
+                if matches!(state.current_screen(), Screen::SensorLogger) {
+                    state.replace_current(Screen::SensorLogger);
+                }
+            }
+            WorkerResult::SensorSummary { value } => {
+                match value {
+                    Ok(summary) => {
+                        state.sensor_summary = Some(summary);
+                        state.last_error = None;
+                    }
+                    Err(e) => state.last_error = Some(e.to_string()),
+                }
+                if matches!(state.current_screen(), Screen::SensorLogger) {
+                    state.replace_current(Screen::SensorLogger);
+                }
+            }
+            WorkerResult::GpsExport { value } => {
+                match value {
+                    Ok(path) => {
+                        state.push_toast(t!("toast_result_saved", path = path));
+                        state.last_sensor_export = Some(path);
+                        state.last_error = None;
+                    }
+                    Err(e) => {
+                        state.last_sensor_export = None;
+                        state.last_error = Some(e.to_string());
+                    }
+                }
+                if matches!(state.current_screen(), Screen::SensorLogger) {
+                    state.replace_current(Screen::SensorLogger);
+                }
+            }
         }
     }
     state.loading_message = None;
     state.loading_with_spinner = false;
+    state.job_progress = None;
+    if viewing_jobs {
+        state.push_screen(Screen::JobQueue);
+    }
 }
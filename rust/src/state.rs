@@ -1,22 +1,47 @@
 use crate::features::archive::ArchiveState;
+use crate::features::ascii_art::AsciiArtState;
+use crate::features::cleanup::CleanupState;
+use crate::features::collage::CollageState;
+use crate::features::compression::GzipBenchmark;
+use crate::features::exif_editor::ExifEditorState;
+use crate::features::gif_frames::GifFramesState;
+use crate::features::image_adjust::ImageAdjustState;
+use crate::features::image_edit::ImageEditState;
+use crate::features::image_histogram::ImageHistogramState;
+use crate::features::metadata_strip::MetadataStripState;
+use crate::features::watermark::WatermarkState;
+use crate::features::file_info::{BatchEntry, HexWindow};
+use crate::features::hashes::HmacConfig;
 use crate::features::hex_editor::HexEditorState;
+use crate::features::storage::{RecentFile, DEFAULT_FAVORITE_TOOLS};
 use crate::features::kotlin_image::KotlinImageState;
 use crate::features::logic::LogicState;
 use crate::features::pdf::PdfState;
+use crate::features::text_tools::TextToolsTab;
+use crate::features::cert_inspector::CertInspectorState;
+use crate::features::date_calc::DateCalcState;
 use crate::features::jwt::JwtState;
+use crate::features::password_strength::PasswordStrengthState;
 use crate::features::presets::PresetState;
 use crate::features::qr_transfer::{QrReceiveState, QrSlideshowState};
 use crate::features::mir_scripting::MirScriptingState;
 use crate::features::mir_math::MirMathLibrary;
 use crate::features::c_scripting::CScriptingState;
-use crate::features::sensor_logger::SensorSelection;
+use crate::features::jit_benchmark::JitBenchmark;
+use crate::features::plugins::PluginsState;
+use crate::features::sensor_logger::{
+    SensorBackend, SensorHistory, SensorLogLimits, SensorSelection,
+};
+use crate::features::sensor_alerts::{SensorAlertMonitor, SensorThresholds};
+use crate::features::sensor_summary::SensorLogSummary;
+use crate::features::sensor_utils::SensorCalibration;
 use crate::features::sql_engine::{QueryResult, SqlEngine, TableInfo};
 use crate::features::system_info::SystemInfoState;
 use crate::features::vault::VaultState;
 use crate::features::automatic_differentiation::{AutomaticDifferentiator, ADMode};
 use crate::features::cas_types::Number;
 use serde::{Deserialize, Serialize};
-use rust_i18n::t;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Screen {
@@ -52,19 +77,39 @@ pub enum Screen {
     QrSlideshow,
     QrReceive,
     MathTool,
+    MathConstants,
     FunctionAnalysis,
     Vault,
     Logic,
     Jwt,
+    CertInspector,
+    PasswordStrength,
+    DateCalc,
     HexEditor,
     Plotting,
     SqlQuery,
     MirScripting,
     CScripting,
+    JitBenchmark,
+    Plugins,
+    Plugin,
     Scheduler,
+    JobQueue,
+    UsageStats,
+    DebugLog,
     UnitConverter,
     Synthesizer,
     Settings,
+    StorageCleanup,
+    ImageEditor,
+    MetadataStrip,
+    ImageHistogram,
+    Watermark,
+    ImageAdjust,
+    AsciiArt,
+    GifFrames,
+    Collage,
+    ExifEditor,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -126,13 +171,25 @@ pub enum DitheringMode {
     Bayer8x8,
     Sierra,
     Atkinson,
+    JarvisJudiceNinke,
+    Stucki,
+    Burkes,
+    BlueNoise,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum DitheringPalette {
     Monochrome,
     Cga,
     GameBoy,
+    Custom(Vec<[u8; 3]>),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PixelArtUpscaler {
+    Nearest,
+    Hq2x,
+    Xbr,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -140,7 +197,17 @@ pub struct MultiHashResults {
     pub md5: String,
     pub sha1: String,
     pub sha256: String,
+    #[serde(default)]
+    pub sha512: String,
+    #[serde(default)]
+    pub sha3_256: String,
+    #[serde(default)]
+    pub sha3_512: String,
     pub blake3: String,
+    #[serde(default)]
+    pub xxh64: String,
+    #[serde(default)]
+    pub xxh3: String,
     pub file_path: String,
 }
 
@@ -149,6 +216,10 @@ pub struct PixelArtState {
     pub source_path: Option<String>,
     pub result_path: Option<String>,
     pub scale_factor: u32,
+    pub upscaler: PixelArtUpscaler,
+    pub crt: bool,
+    /// Quantizes the pixelated image to this palette before upscaling.
+    pub palette_lock: Option<DitheringPalette>,
     pub error: Option<String>,
 }
 
@@ -156,11 +227,51 @@ pub struct PixelArtState {
 pub struct RegexMatchResult {
     pub matched: bool,
     pub groups: Vec<Option<String>>,
+    /// Capture group names aligned with `groups` (None for unnamed groups)
+    pub group_names: Vec<Option<String>>,
+    /// Byte ranges of each capture group aligned with `groups`
+    pub group_spans: Vec<Option<(usize, usize)>>,
     pub match_text: String,
     pub start_index: usize,
     pub end_index: usize,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegexFileLine {
+    pub line_number: usize,
+    pub text: String,
+}
+
+/// Summary of a regex scan over a picked file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegexFileResult {
+    pub display_path: Option<String>,
+    pub total_matches: usize,
+    pub matching_lines: usize,
+    /// First matching lines, capped by the scanner
+    pub lines: Vec<RegexFileLine>,
+}
+
+/// Compile-time flags applied through `RegexBuilder` instead of inline `(?ims)` syntax.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RegexFlags {
+    pub case_insensitive: bool,
+    pub multi_line: bool,
+    pub dot_all: bool,
+    pub unicode: bool,
+}
+
+impl RegexFlags {
+    pub const fn new() -> Self {
+        Self {
+            case_insensitive: false,
+            multi_line: false,
+            dot_all: false,
+            unicode: true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegexTesterState {
     pub pattern: String,
@@ -168,7 +279,12 @@ pub struct RegexTesterState {
     pub match_results: Vec<RegexMatchResult>,
     pub error: Option<String>,
     pub global_mode: bool,
-    pub common_patterns: Vec<String>,
+    pub split_mode: bool,
+    pub split_pieces: Vec<String>,
+    pub explanation: Vec<String>,
+    pub flags: RegexFlags,
+    pub file_result: Option<RegexFileResult>,
+    pub snippet_query: String,
 }
 
 impl RegexTesterState {
@@ -179,20 +295,14 @@ impl RegexTesterState {
             match_results: Vec::new(),
             error: None,
             global_mode: false,
-            common_patterns: Vec::new(),
+            split_mode: false,
+            split_pieces: Vec::new(),
+            explanation: Vec::new(),
+            flags: RegexFlags::new(),
+            file_result: None,
+            snippet_query: String::new(),
         }
     }
-
-    pub fn init_common_patterns(&mut self) {
-        self.common_patterns = vec![
-            t!("regex_email").to_string(),
-            t!("regex_ipv4").to_string(),
-            t!("regex_ipv6").to_string(),
-            t!("regex_date_ymd").to_string(),
-            t!("regex_time_hms").to_string(),
-            t!("regex_url").to_string(),
-        ];
-    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -273,12 +383,77 @@ pub enum StringCharset {
     Hex,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum UuidVersion {
+    V1,
+    V4,
+    V5,
+    V7,
+}
+
+/// One line of a UUID inspection. Labels and notes are locale keys, translated when the
+/// screen renders.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UuidField {
+    pub label: String,
+    /// Literal value such as hex digits or a date; empty when the note says it all.
+    pub value: String,
+    pub note: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UuidGeneratorState {
     pub last_uuid: Option<String>,
+    #[serde(skip)] // Generated passwords must never reach a snapshot
     pub last_string: Option<String>,
     pub string_length: u32,
     pub string_charset: StringCharset,
+    pub min_digits: u32,
+    pub min_symbols: u32,
+    pub exclude_ambiguous: bool,
+    pub pronounceable: bool,
+    pub last_entropy_bits: Option<f64>,
+    pub version: UuidVersion,
+    pub v5_namespace: String,
+    pub v5_name: String,
+    pub inspect_input: String,
+    pub inspect_fields: Vec<UuidField>,
+    pub last_ulid: Option<String>,
+    pub last_nanoid: Option<String>,
+    pub nanoid_alphabet: String,
+    pub nanoid_length: u32,
+    pub random_bytes_len: u32,
+    #[serde(skip)] // Key material, like `last_string`
+    pub last_random_bytes: Option<Vec<u8>>,
+    pub error: Option<String>,
+}
+
+impl UuidGeneratorState {
+    pub const fn new() -> Self {
+        Self {
+            last_uuid: None,
+            last_string: None,
+            string_length: 16,
+            string_charset: StringCharset::Alphanumeric,
+            min_digits: 0,
+            min_symbols: 0,
+            exclude_ambiguous: false,
+            pronounceable: false,
+            last_entropy_bits: None,
+            version: UuidVersion::V4,
+            v5_namespace: String::new(),
+            v5_name: String::new(),
+            inspect_input: String::new(),
+            inspect_fields: Vec::new(),
+            last_ulid: None,
+            last_nanoid: None,
+            nanoid_alphabet: String::new(),
+            nanoid_length: 21,
+            random_bytes_len: 32,
+            last_random_bytes: None,
+            error: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -289,6 +464,19 @@ pub struct MathHistoryEntry {
     pub precision_bits: u32,
 }
 
+/// Default number of math history entries kept in memory and on disk
+pub const MATH_HISTORY_DEFAULT_CAP: usize = 20;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MathResultFormat {
+    Decimal,
+    Hex,
+    Binary,
+    Fraction,
+    Scientific,
+    Engineering,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum PlotType {
     Line,
@@ -348,11 +536,20 @@ impl PlottingState {
 pub struct MathToolState {
     pub expression: String,
     pub history: Vec<MathHistoryEntry>,
+    /// Maximum number of history entries retained and persisted
+    pub history_cap: usize,
     pub error: Option<String>,
     /// Precision setting in bits (0 = f64, 64+ = arbitrary precision via rug::Float)
     pub precision_bits: u32,
     /// Cumulative floating-point error for the current session
     pub cumulative_error: f64,
+    /// How new results are rendered; integer-only formats fall back to decimal
+    pub result_format: MathResultFormat,
+    /// Path of the last `plot(...)` PNG written to the output directory
+    pub plot_path: Option<String>,
+    /// Base64 PNG of the last plot for the image component
+    #[serde(skip)] // Keep snapshots small; the file on disk is the durable copy
+    pub plot_base64: Option<String>,
     /// MIR math function library for hybrid evaluation
     #[serde(skip)] // Don't serialize the compiled function cache
     pub mir_math_library: MirMathLibrary,
@@ -371,9 +568,13 @@ impl MathToolState {
         Self {
             expression: String::new(),
             history: Vec::new(),
+            history_cap: MATH_HISTORY_DEFAULT_CAP,
             error: None,
             precision_bits: 0, // Default to f64 precision
             cumulative_error: 0.0, // Start with zero error
+            result_format: MathResultFormat::Decimal,
+            plot_path: None,
+            plot_base64: None,
             mir_math_library: MirMathLibrary::default(), // Initialize with default functions
             automatic_differentiator: differentiator,
         }
@@ -402,15 +603,111 @@ impl MathToolState {
     pub fn clear_history(&mut self) {
         self.history.clear();
     }
+
+    /// Insert a new entry at the top and drop anything beyond `history_cap`
+    pub fn push_history(&mut self, entry: MathHistoryEntry) {
+        self.history.insert(0, entry);
+        self.history.truncate(self.history_cap);
+    }
+}
+
+/// Most confirmations shown together when several pile up between two renders.
+pub const TOAST_QUEUE_MAX: usize = 3;
+
+/// Destructive command held back until the user answers its confirmation dialog.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingConfirmation {
+    pub title: String,
+    pub message: Option<String>,
+    pub confirm_label: String,
+    /// The held command, replayed as is once confirmed.
+    pub action: String,
+    pub path: Option<String>,
+    pub bindings: HashMap<String, String>,
+}
+
+/// Latest progress a worker job reported, shown on the loading screen.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobProgress {
+    pub job_id: u64,
+    /// Short machine-readable step, e.g. `resize`.
+    pub stage: String,
+    /// Completion in `0.0..=1.0`; `None` when the job cannot tell.
+    pub fraction: Option<f32>,
+    pub message: Option<String>,
+}
+
+/// How many tools the home screen lists under "Recent".
+pub const RECENT_TOOLS_MAX: usize = 4;
+
+/// Longest stretch counted for one visit to a tool, so an app left open on a tool does not
+/// inflate its time.
+pub const TOOL_SESSION_MAX_SECS: u64 = 30 * 60;
+
+/// When a home screen tool was last opened and, with usage statistics on, how often and
+/// for how long it has been used. Never leaves the device.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolUsage {
+    /// Feature id from the home catalog.
+    pub id: String,
+    /// Seconds since the Unix epoch.
+    pub last_used: u64,
+    #[serde(default)]
+    pub launches: u32,
+    #[serde(default)]
+    pub total_secs: u64,
+}
+
+/// Tool the user is in while usage statistics are on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolSession {
+    pub id: String,
+    /// Seconds since the Unix epoch.
+    pub since: u64,
+}
+
+/// App-wide color scheme. `System` follows the device's night mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeMode {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl ThemeMode {
+    pub const ALL: [ThemeMode; 3] = [ThemeMode::System, ThemeMode::Light, ThemeMode::Dark];
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "system" | "" => Some(ThemeMode::System),
+            "light" => Some(ThemeMode::Light),
+            "dark" => Some(ThemeMode::Dark),
+            _ => None,
+        }
+    }
+
+    /// Value of the `theme` field on every rendered root.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ThemeMode::System => "system",
+            ThemeMode::Light => "light",
+            ThemeMode::Dark => "dark",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppState {
     pub counter: i32,
     pub locale: String,
+    /// Locale picked in settings; empty follows [`AppState::system_locale`].
     pub preferred_locale: String,
+    /// Device locale reported by the last `init`.
+    pub system_locale: String,
     pub home_filter: String,
-    pub theme_mode: Option<String>,
+    pub theme_mode: ThemeMode,
     pub nav_stack: Vec<Screen>,
     pub last_hash: Option<String>,
     pub last_error: Option<String>,
@@ -418,15 +715,45 @@ pub struct AppState {
     pub last_hash_algo: Option<String>,
     pub hash_reference: Option<String>,
     pub hash_match: Option<bool>,
+    #[serde(skip)] // The HMAC key must never reach a snapshot
+    pub hmac: HmacConfig,
     pub image: KotlinImageState,
+    pub image_edit: ImageEditState,
+    pub image_adjust: ImageAdjustState,
+    pub ascii_art: AsciiArtState,
+    pub gif_frames: GifFramesState,
+    pub collage: CollageState,
+    pub exif_editor: ExifEditorState,
+    pub metadata_strip: MetadataStripState,
+    pub image_histogram: ImageHistogramState,
+    pub watermark: WatermarkState,
     pub last_file_info: Option<String>,
+    pub file_info_hex: Option<HexWindow>,
+    pub file_info_hex_error: Option<String>,
+    pub file_info_batch: Vec<BatchEntry>,
+    pub recent_files: Vec<RecentFile>,
+    /// Feature ids pinned to the home quick access row, in display order.
+    pub favorite_tools: Vec<String>,
+    pub home_edit_favorites: bool,
+    /// One entry per opened tool, most recently used first.
+    pub tool_usage: Vec<ToolUsage>,
+    /// Opt-in: count launches and time per tool.
+    pub usage_stats_enabled: bool,
+    /// Order each home category by launch count instead of catalog order.
+    pub home_sort_by_usage: bool,
+    /// Collapsible sections the user opened or closed, by section key.
+    pub expanded_sections: BTreeMap<String, bool>,
+    pub tool_session: Option<ToolSession>,
     pub text_input: Option<String>,
     pub text_output: Option<String>,
     pub text_operation: Option<String>,
     pub text_aggressive_trim: bool,
+    pub text_tools_tab: TextToolsTab,
     pub loading_message: Option<String>,
     pub progress_status: Option<String>,
     pub loading_with_spinner: bool,
+    pub job_progress: Option<JobProgress>,
+    pub pending_confirmation: Option<PendingConfirmation>,
     pub dependencies: DependencyState,
     pub last_qr_base64: Option<String>,
     pub pdf: PdfState,
@@ -434,6 +761,17 @@ pub struct AppState {
     pub sensor_status: Option<String>,
     pub sensor_interval_ms: Option<u64>,
     pub sensor_selection: Option<SensorSelection>,
+    pub last_sensor_export: Option<String>,
+    pub sensor_limits: Option<SensorLogLimits>,
+    pub sensor_segments: Vec<String>,
+    pub sensor_backend: SensorBackend,
+    #[serde(skip)] // Live chart samples only matter while the screen is open
+    pub sensor_history: SensorHistory,
+    #[serde(skip)]
+    pub sensor_summary: Option<SensorLogSummary>,
+    pub sensor_thresholds: Option<SensorThresholds>,
+    #[serde(skip)]
+    pub sensor_alerts: SensorAlertMonitor,
     pub text_view_content: Option<String>,
     pub text_view_path: Option<String>,
     pub text_view_cached_path: Option<String>,
@@ -442,6 +780,10 @@ pub struct AppState {
     pub text_view_language: Option<String>,
     pub text_view_dark: bool,
     pub text_view_line_numbers: bool,
+    /// Shows markdown files rendered instead of as source.
+    pub text_view_markdown_preview: bool,
+    /// Off for content whose layout matters, like ASCII art.
+    pub text_view_wrap: bool,
     pub text_view_find_query: Option<String>,
     pub text_view_find_match: Option<String>,
     pub text_view_total_bytes: Option<u64>,
@@ -452,6 +794,7 @@ pub struct AppState {
     pub archive: ArchiveState,
     pub compression_status: Option<String>,
     pub compression_error: Option<String>,
+    pub compression_benchmark: Option<GzipBenchmark>,
     pub compass_angle_radians: f64,
     pub compass_error: Option<String>,
     pub barometer_hpa: Option<f64>,
@@ -461,12 +804,16 @@ pub struct AppState {
     pub compass_filter_angle: Option<f64>,
     pub barometer_filter_value: Option<f64>,
     pub magnetometer_filter_value: Option<f64>,
+    pub barometer_raw_hpa: Option<f64>,
+    pub sensor_calibration: SensorCalibration,
     pub multi_hash_results: Option<MultiHashResults>,
     pub multi_hash_error: Option<String>,
     pub dithering_source_path: Option<String>,
     pub dithering_result_path: Option<String>,
     pub dithering_mode: DitheringMode,
     pub dithering_palette: DitheringPalette,
+    pub dithering_serpentine: bool,
+    pub dithering_custom_palette: String,
     pub dithering_error: Option<String>,
     pub dithering_output_dir: Option<String>,
     pub pixel_art: PixelArtState,
@@ -474,24 +821,33 @@ pub struct AppState {
     pub uuid_generator: UuidGeneratorState,
     pub system_info: SystemInfoState,
     pub preset_state: PresetState,
+    pub cleanup: CleanupState,
     pub qr_slideshow: QrSlideshowState,
     pub qr_receive: QrReceiveState,
     pub math_tool: MathToolState,
     pub vault: VaultState,
     pub logic: LogicState,
     pub jwt: JwtState,
+    pub cert_inspector: CertInspectorState,
+    #[serde(skip)] // The typed password must never reach a snapshot
+    pub password_strength: PasswordStrengthState,
+    pub date_calc: DateCalcState,
     pub hex_editor: HexEditorState,
     pub plotting: PlottingState,
     pub sql_query: SqlQueryState,
     pub mir_scripting: MirScriptingState,
     pub c_scripting: CScriptingState,
+    pub jit_benchmark: Option<JitBenchmark>,
+    pub jit_benchmark_error: Option<String>,
+    pub plugins: PluginsState,
     pub scheduler: SchedulerState,
     pub unit_converter: UnitConverterState,
     pub synthesizer: SynthesizerState,
     #[serde(skip)]
     pub sql_engine: Option<SqlEngine>,
+    /// Confirmations waiting for the next render, oldest first; see [`AppState::push_toast`].
     #[serde(skip)]
-    pub toast: Option<String>,
+    pub toasts: Vec<String>,
     #[serde(skip)]
     pub haptic: bool,
 }
@@ -503,8 +859,9 @@ impl AppState {
             counter: 0,
             locale: String::new(),
             preferred_locale: String::new(),
+            system_locale: String::new(),
             home_filter: String::new(),
-            theme_mode: None,
+            theme_mode: ThemeMode::System,
             nav_stack: Vec::new(),
             last_hash: None,
             last_error: None,
@@ -512,15 +869,42 @@ impl AppState {
             last_hash_algo: None,
             hash_reference: None,
             hash_match: None,
+            hmac: HmacConfig::default(),
             image: KotlinImageState::new(),
+            image_edit: ImageEditState::new(),
+            image_adjust: ImageAdjustState::new(),
+            ascii_art: AsciiArtState::new(),
+            gif_frames: GifFramesState::new(),
+            collage: CollageState::new(),
+            exif_editor: ExifEditorState::new(),
+            metadata_strip: MetadataStripState::new(),
+            image_histogram: ImageHistogramState::new(),
+            watermark: WatermarkState::new(),
             last_file_info: None,
+            file_info_hex: None,
+            file_info_hex_error: None,
+            file_info_batch: Vec::new(),
+            recent_files: Vec::new(),
+            favorite_tools: DEFAULT_FAVORITE_TOOLS
+                .iter()
+                .map(|id| id.to_string())
+                .collect(),
+            home_edit_favorites: false,
+            tool_usage: Vec::new(),
+            usage_stats_enabled: false,
+            home_sort_by_usage: false,
+            expanded_sections: BTreeMap::new(),
+            tool_session: None,
             text_input: None,
             text_output: None,
             text_operation: None,
             text_aggressive_trim: false,
+            text_tools_tab: TextToolsTab::Transform,
             loading_message: None,
             progress_status: None,
             loading_with_spinner: true,
+            job_progress: None,
+            pending_confirmation: None,
             dependencies: DependencyState::new(),
             last_qr_base64: None,
             pdf: PdfState::new(),
@@ -528,6 +912,14 @@ impl AppState {
             sensor_status: None,
             sensor_interval_ms: None,
             sensor_selection: None,
+            last_sensor_export: None,
+            sensor_limits: None,
+            sensor_segments: Vec::new(),
+            sensor_backend: SensorBackend::Csv,
+            sensor_history: SensorHistory::default(),
+            sensor_summary: None,
+            sensor_thresholds: None,
+            sensor_alerts: SensorAlertMonitor::default(),
             text_view_content: None,
             text_view_path: None,
             text_view_cached_path: None,
@@ -536,6 +928,8 @@ impl AppState {
             text_view_language: None,
             text_view_dark: false,
             text_view_line_numbers: false,
+            text_view_markdown_preview: false,
+            text_view_wrap: true,
             text_view_find_query: None,
             text_view_find_match: None,
             text_view_total_bytes: None,
@@ -546,6 +940,7 @@ impl AppState {
             archive: ArchiveState::new(),
             compression_status: None,
             compression_error: None,
+            compression_benchmark: None,
             compass_angle_radians: 0.0,
             compass_error: None,
             barometer_hpa: None,
@@ -555,55 +950,58 @@ impl AppState {
             compass_filter_angle: None,
             barometer_filter_value: None,
             magnetometer_filter_value: None,
+            barometer_raw_hpa: None,
+            sensor_calibration: SensorCalibration::default(),
             multi_hash_results: None,
             multi_hash_error: None,
             dithering_source_path: None,
             dithering_result_path: None,
             dithering_mode: DitheringMode::Atkinson,
             dithering_palette: DitheringPalette::Monochrome,
+            dithering_serpentine: false,
+            dithering_custom_palette: String::new(),
             dithering_error: None,
             dithering_output_dir: None,
             pixel_art: PixelArtState {
                 source_path: None,
                 result_path: None,
                 scale_factor: 4,
+                upscaler: PixelArtUpscaler::Nearest,
+                crt: false,
+                palette_lock: None,
                 error: None,
             },
             regex_tester: RegexTesterState::new(),
-            uuid_generator: UuidGeneratorState {
-                last_uuid: None,
-                last_string: None,
-                string_length: 16,
-                string_charset: StringCharset::Alphanumeric,
-            },
+            uuid_generator: UuidGeneratorState::new(),
             system_info: SystemInfoState::new(),
             preset_state: PresetState::new(),
+            cleanup: CleanupState::new(),
             qr_slideshow: QrSlideshowState::new(),
             qr_receive: QrReceiveState::new(),
             math_tool: MathToolState::new(),
             vault: VaultState::new(),
             logic: LogicState::new(),
             jwt: JwtState::new(),
+            cert_inspector: CertInspectorState::new(),
+            password_strength: PasswordStrengthState::new(),
+            date_calc: DateCalcState::new(),
             hex_editor: HexEditorState::new(),
             plotting: PlottingState::new(),
             sql_query: SqlQueryState::new(),
             mir_scripting: MirScriptingState::new(),
             c_scripting: CScriptingState::new(),
+            jit_benchmark: None,
+            jit_benchmark_error: None,
+            plugins: PluginsState::new(),
             scheduler: SchedulerState::new(),
             unit_converter: UnitConverterState::new(),
             synthesizer: SynthesizerState::new(),
             sql_engine: None,
-            toast: None,
+            toasts: Vec::new(),
             haptic: false,
         }
     }
 
-    pub fn ensure_regex_patterns_initialized(&mut self) {
-        if self.regex_tester.common_patterns.is_empty() {
-            self.regex_tester.init_common_patterns();
-        }
-    }
-
     pub fn ensure_navigation(&mut self) {
         if self.nav_stack.is_empty() {
             self.nav_stack.push(Screen::Home);
@@ -644,6 +1042,33 @@ impl AppState {
         }
     }
 
+    /// Whether the collapsible section `key` is open; `default` applies until the user
+    /// toggles it.
+    pub fn is_section_expanded(&self, key: &str, default: bool) -> bool {
+        self.expanded_sections.get(key).copied().unwrap_or(default)
+    }
+
+    /// Queues a brief confirmation for Kotlin to show once, without changing the layout.
+    /// Repeats of a pending message are dropped and only the newest few are kept.
+    pub fn push_toast(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        if message.trim().is_empty() || self.toasts.contains(&message) {
+            return;
+        }
+        self.toasts.push(message);
+        if self.toasts.len() > TOAST_QUEUE_MAX {
+            self.toasts.remove(0);
+        }
+    }
+
+    /// Drains the queue into the root's `toast` text, one message per line.
+    pub fn take_toast(&mut self) -> Option<String> {
+        if self.toasts.is_empty() {
+            return None;
+        }
+        Some(std::mem::take(&mut self.toasts).join("\n"))
+    }
+
     pub fn reset_navigation(&mut self) {
         self.nav_stack.clear();
         self.nav_stack.push(Screen::Home);
@@ -656,20 +1081,28 @@ impl AppState {
         self.last_shader = None;
         self.last_hash_algo = None;
         self.home_filter.clear();
-        self.theme_mode = None;
-        self.toast = None;
+        self.home_edit_favorites = false;
+        self.theme_mode = ThemeMode::System;
+        self.toasts.clear();
         self.haptic = false;
         self.hash_reference = None;
         self.hash_match = None;
+        self.hmac = HmacConfig::default();
         self.image.reset();
         self.last_file_info = None;
+        self.file_info_hex = None;
+        self.file_info_hex_error = None;
+        self.file_info_batch.clear();
         self.text_input = None;
         self.text_output = None;
         self.text_operation = None;
         self.text_aggressive_trim = false;
+        self.text_tools_tab = TextToolsTab::Transform;
         self.loading_message = None;
         self.progress_status = None;
         self.loading_with_spinner = true;
+        self.job_progress = None;
+        self.pending_confirmation = None;
         self.dependencies.reset();
         self.last_qr_base64 = None;
         self.pdf.reset();
@@ -677,6 +1110,14 @@ impl AppState {
         self.sensor_status = None;
         self.sensor_interval_ms = None;
         self.sensor_selection = None;
+        self.last_sensor_export = None;
+        self.sensor_limits = None;
+        self.sensor_segments.clear();
+        self.sensor_backend = SensorBackend::Csv;
+        self.sensor_history.clear();
+        self.sensor_summary = None;
+        self.sensor_thresholds = None;
+        self.sensor_alerts.clear();
         self.text_view_content = None;
         self.text_view_path = None;
         self.text_view_cached_path = None;
@@ -685,6 +1126,8 @@ impl AppState {
         self.text_view_language = None;
         self.text_view_dark = false;
         self.text_view_line_numbers = false;
+        self.text_view_markdown_preview = false;
+        self.text_view_wrap = true;
         self.text_view_find_query = None;
         self.text_view_find_match = None;
         self.text_view_total_bytes = None;
@@ -695,6 +1138,7 @@ impl AppState {
         self.archive.reset();
         self.compression_status = None;
         self.compression_error = None;
+        self.compression_benchmark = None;
         self.compass_angle_radians = 0.0;
         self.compass_error = None;
         self.barometer_hpa = None;
@@ -704,41 +1148,250 @@ impl AppState {
         self.compass_filter_angle = None;
         self.barometer_filter_value = None;
         self.magnetometer_filter_value = None;
+        self.barometer_raw_hpa = None;
         self.multi_hash_results = None;
         self.multi_hash_error = None;
         self.dithering_source_path = None;
         self.dithering_result_path = None;
         self.dithering_mode = DitheringMode::Atkinson;
         self.dithering_palette = DitheringPalette::Monochrome;
+        self.dithering_serpentine = false;
+        self.dithering_custom_palette.clear();
         self.dithering_error = None;
         self.dithering_output_dir = None;
         self.pixel_art.source_path = None;
         self.pixel_art.result_path = None;
         self.pixel_art.scale_factor = 4;
+        self.pixel_art.upscaler = PixelArtUpscaler::Nearest;
+        self.pixel_art.crt = false;
+        self.pixel_art.palette_lock = None;
         self.pixel_art.error = None;
         self.regex_tester.pattern.clear();
         self.regex_tester.sample_text.clear();
         self.regex_tester.match_results.clear();
         self.regex_tester.error = None;
         self.regex_tester.global_mode = false;
-        self.uuid_generator.last_uuid = None;
-        self.uuid_generator.last_string = None;
-        self.uuid_generator.string_length = 16;
-        self.uuid_generator.string_charset = StringCharset::Alphanumeric;
+        self.uuid_generator = UuidGeneratorState::new();
         self.system_info = SystemInfoState::new();
         self.preset_state.reset();
+        self.cleanup.reset();
+        self.image_edit.reset();
+        self.image_adjust.reset();
+        self.ascii_art.reset();
+        self.gif_frames.reset();
+        self.collage.reset();
+        self.exif_editor.reset();
+        self.metadata_strip.reset();
+        self.image_histogram.reset();
+        self.watermark.reset();
         self.qr_slideshow.reset();
         self.qr_receive.reset();
         self.math_tool = MathToolState::new();
         self.vault = VaultState::new();
         self.logic = LogicState::new();
         self.jwt = JwtState::new();
+        self.cert_inspector.reset();
+        self.password_strength.reset();
+        self.date_calc.reset();
         self.hex_editor = HexEditorState::new();
         self.plotting = PlottingState::new();
         self.scheduler.reset();
         self.unit_converter = UnitConverterState::new();
         self.synthesizer = SynthesizerState::new();
+        self.jit_benchmark = None;
+        self.jit_benchmark_error = None;
+        self.plugins.reset();
         self.image.batch_queue.clear();
         self.pdf.merge_queue.clear();
     }
+
+    /// Serializes the full state for the Kotlin-side saved instance state, tagged with
+    /// [`SNAPSHOT_VERSION`].
+    pub fn to_snapshot(&self) -> Result<String, String> {
+        let mut value = serde_json::to_value(self).map_err(|e| format!("snapshot_failed:{e}"))?;
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert(SNAPSHOT_VERSION_KEY.into(), SNAPSHOT_VERSION.into());
+        }
+        serde_json::to_string(&value).map_err(|e| format!("snapshot_failed:{e}"))
+    }
+
+    /// Rebuilds a state written by [`AppState::to_snapshot`], possibly by another app
+    /// version: missing fields start from their defaults and unknown ones are ignored.
+    pub fn from_snapshot(content: &str) -> Result<Self, String> {
+        Self::restore_saved(content, &[])
+    }
+
+    /// Serializes the state for the on-disk copy that survives process death, leaving out
+    /// [`PERSIST_SKIPPED_FIELDS`] and any loading screen whose worker died with the process.
+    pub fn to_persisted(&self) -> Result<String, String> {
+        let mut value = serde_json::to_value(self).map_err(|e| format!("persist_failed:{e}"))?;
+        let obj = value
+            .as_object_mut()
+            .ok_or_else(|| "persist_failed:not_object".to_string())?;
+        for field in PERSIST_SKIPPED_FIELDS {
+            obj.remove(*field);
+        }
+        obj.insert(SNAPSHOT_VERSION_KEY.into(), SNAPSHOT_VERSION.into());
+        let loading = serde_json::to_value(Screen::Loading).unwrap_or_default();
+        if let Some(serde_json::Value::Array(stack)) = obj.get_mut("nav_stack") {
+            stack.retain(|screen| *screen != loading);
+        }
+        serde_json::to_string(&value).map_err(|e| format!("persist_failed:{e}"))
+    }
+
+    /// Rebuilds a state written by [`AppState::to_persisted`]; skipped fields and fields the
+    /// file predates start from their defaults.
+    pub fn from_persisted(content: &str) -> Result<Self, String> {
+        Self::restore_saved(content, PERSIST_SKIPPED_FIELDS)
+    }
+
+    /// Overlays the saved fields, minus `skipped`, onto a fresh state. A field whose saved
+    /// shape no longer deserializes, because another version changed its type, keeps its
+    /// default instead of failing the whole restore.
+    fn restore_saved(content: &str, skipped: &[&str]) -> Result<Self, String> {
+        let saved: serde_json::Value =
+            serde_json::from_str(content).map_err(|e| format!("restore_failed:{e}"))?;
+        let serde_json::Value::Object(mut saved) = saved else {
+            return Err("restore_failed:not_object".into());
+        };
+        // Snapshots written before versioning carry no tag.
+        let version = saved
+            .get(SNAPSHOT_VERSION_KEY)
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0);
+        if version < u64::from(SNAPSHOT_VERSION) {
+            upgrade_snapshot(&mut saved, version);
+        }
+        let defaults =
+            serde_json::to_value(Self::new()).map_err(|e| format!("restore_failed:{e}"))?;
+        let fields: Vec<_> = saved
+            .into_iter()
+            .filter(|(key, _)| defaults.get(key).is_some() && !skipped.contains(&key.as_str()))
+            .collect();
+
+        let mut value = defaults.clone();
+        if let Some(obj) = value.as_object_mut() {
+            obj.extend(fields.iter().cloned());
+        }
+        if Self::deserialize(&value).is_err() {
+            // Slow path: add fields one by one and drop the ones that break the state.
+            value = defaults;
+            for (key, field) in fields {
+                let Some(obj) = value.as_object_mut() else {
+                    break;
+                };
+                let Some(default) = obj.insert(key.clone(), field) else {
+                    continue;
+                };
+                if Self::deserialize(&value).is_err() {
+                    if let Some(obj) = value.as_object_mut() {
+                        obj.insert(key, default);
+                    }
+                }
+            }
+        }
+        let mut restored: Self =
+            serde_json::from_value(value).map_err(|e| format!("restore_failed:{e}"))?;
+        restored.ensure_navigation();
+        Ok(restored)
+    }
+
+    /// Moves `id` to the front of [`AppState::tool_usage`] with the current time and, with
+    /// usage statistics on, counts the launch and starts timing it.
+    pub fn note_tool_used(&mut self, id: &str) {
+        self.end_tool_session();
+        let now = unix_now();
+        let mut usage = match self.tool_usage.iter().position(|u| u.id == id) {
+            Some(pos) => self.tool_usage.remove(pos),
+            None => ToolUsage {
+                id: id.to_string(),
+                last_used: now,
+                launches: 0,
+                total_secs: 0,
+            },
+        };
+        usage.last_used = now;
+        if self.usage_stats_enabled {
+            usage.launches += 1;
+            self.tool_session = Some(ToolSession {
+                id: id.to_string(),
+                since: now,
+            });
+        }
+        self.tool_usage.insert(0, usage);
+    }
+
+    /// Adds the time spent in the current tool, capped at [`TOOL_SESSION_MAX_SECS`].
+    pub fn end_tool_session(&mut self) {
+        let Some(session) = self.tool_session.take() else {
+            return;
+        };
+        if let Some(usage) = self.tool_usage.iter_mut().find(|u| u.id == session.id) {
+            let spent = unix_now()
+                .saturating_sub(session.since)
+                .min(TOOL_SESSION_MAX_SECS);
+            usage.total_secs = usage.total_secs.saturating_add(spent);
+        }
+    }
+
+    /// Forgets launch counts and times; the recent tools list is kept.
+    pub fn clear_usage_stats(&mut self) {
+        self.tool_session = None;
+        for usage in &mut self.tool_usage {
+            usage.launches = 0;
+            usage.total_secs = 0;
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
+
+/// Layout version written into snapshots and the persisted file. Added and removed fields
+/// need no bump, since restore fills and ignores them; bump it when a field is renamed or
+/// changes shape, and teach `upgrade_snapshot` to convert the old layout.
+pub const SNAPSHOT_VERSION: u32 = 2;
+const SNAPSHOT_VERSION_KEY: &str = "snapshot_version";
+
+/// Rewrites fields saved by an older layout so they restore instead of falling back to
+/// their defaults.
+fn upgrade_snapshot(saved: &mut serde_json::Map<String, serde_json::Value>, version: u64) {
+    if version < 2 {
+        // Version 2 turned UUID inspection lines from (label, value) pairs into `UuidField`;
+        // they are cheap to recompute, so drop them and keep the rest of the generator.
+        if let Some(uuid) = saved
+            .get_mut("uuid_generator")
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            uuid.insert(
+                "inspect_fields".into(),
+                serde_json::Value::Array(Vec::new()),
+            );
+        }
+    }
+}
+
+/// Top-level fields kept out of the persisted state: in-flight work and one-off feedback
+/// that would be stale after a restart, large previews that are cheap to rebuild, and
+/// secrets that must not be written to disk.
+pub const PERSIST_SKIPPED_FIELDS: &[&str] = &[
+    "last_error",
+    "loading_message",
+    "progress_status",
+    "loading_with_spinner",
+    "job_progress",
+    "pending_confirmation",
+    "tool_session",
+    "last_qr_base64",
+    "file_info_hex",
+    "text_view_content",
+    "text_view_hex_preview",
+    "qr_slideshow",
+    "qr_receive",
+    "vault",
+    "jwt",
+];
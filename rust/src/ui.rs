@@ -1,3 +1,4 @@
+use crate::error::error_text;
 use crate::state::AppState;
 use serde::Serialize;
 use serde_json::{json, Value};
@@ -16,6 +17,70 @@ pub struct Text<'a> {
     pub color: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content_description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spans: Option<Vec<TextSpan<'a>>>,
+}
+
+/// Styled range inside a `Text`, in UTF-16 code units to match Android's `Spannable`.
+#[derive(Serialize, Clone)]
+pub struct TextSpan<'a> {
+    pub start: usize,
+    pub end: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bold: Option<bool>,
+}
+
+impl<'a> TextSpan<'a> {
+    /// Builds spans from byte ranges into `text`, converting to UTF-16 offsets in a single
+    /// walk over the text. Offsets off a char boundary fall back to the start of the text.
+    pub fn from_byte_ranges(text: &str, ranges: &[(usize, usize)]) -> Vec<Self> {
+        let mut bounds: Vec<usize> = ranges.iter().flat_map(|&(s, e)| [s, e]).collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+        let mut utf16 = vec![None; bounds.len()];
+        let mut next = 0;
+        let mut units = 0;
+        let chars = text.char_indices().map(|(i, c)| (i, c.len_utf16()));
+        for (byte, len) in chars.chain([(text.len(), 0)]) {
+            while next < bounds.len() && bounds[next] < byte {
+                next += 1;
+            }
+            if next == bounds.len() {
+                break;
+            }
+            if bounds[next] == byte {
+                utf16[next] = Some(units);
+                next += 1;
+            }
+            units += len;
+        }
+        let lookup = |offset| bounds.binary_search(&offset).ok().and_then(|i| utf16[i]);
+        ranges
+            .iter()
+            .map(|&(start, end)| {
+                let start_u16 = lookup(start).unwrap_or(0);
+                Self {
+                    start: start_u16,
+                    end: lookup(end).unwrap_or(start_u16),
+                    background: None,
+                    bold: None,
+                }
+            })
+            .collect()
+    }
+
+    pub fn background(mut self, color: &'a str) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.bold = Some(bold);
+        self
+    }
 }
 
 impl<'a> Text<'a> {
@@ -27,9 +92,15 @@ impl<'a> Text<'a> {
             size: None,
             color: None,
             content_description: None,
+            spans: None,
         }
     }
 
+    pub fn spans(mut self, spans: Vec<TextSpan<'a>>) -> Self {
+        self.spans = Some(spans);
+        self
+    }
+
     pub fn id(mut self, id: &'a str) -> Self {
         self.id = Some(id);
         self
@@ -51,6 +122,66 @@ impl<'a> Text<'a> {
     }
 }
 
+/// Formatted text written in a small markdown subset (paragraphs, `#` headings, fenced
+/// code, `-`/`1.` lists, `**bold**`, `*italic*`, `` `code` `` and `[links](url)`),
+/// rendered natively by the client.
+#[derive(Serialize)]
+pub struct RichText<'a> {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub markdown: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<&'a str>,
+}
+
+impl<'a> RichText<'a> {
+    pub fn new(markdown: &'a str) -> Self {
+        Self {
+            kind: "RichText",
+            markdown,
+            size: None,
+            content_description: None,
+            id: None,
+        }
+    }
+
+    pub fn size(mut self, size: f64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn content_description(mut self, cd: &'a str) -> Self {
+        self.content_description = Some(cd);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn id(mut self, id: &'a str) -> Self {
+        self.id = Some(id);
+        self
+    }
+}
+
+/// Backslash-escapes characters `RichText` would otherwise read as markup, so
+/// user-supplied text (file names, regex fragments) shows up verbatim.
+pub fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '(' | ')' | '#' | '+' | '-' | '.' | '!'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
 #[derive(Serialize)]
 pub struct Warning<'a> {
     #[serde(rename = "type")]
@@ -282,8 +413,18 @@ pub struct Section<'a> {
     pub padding: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content_description: Option<&'a str>,
+    /// Set by [`Section::collapsible`]; tapping the header sends `toggle_action`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expanded: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section_key: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toggle_action: Option<&'static str>,
 }
 
+/// Sent with bindings `section_key` and `section_expanded` (the state being asked for).
+pub const SECTION_TOGGLE_ACTION: &str = "section_toggle";
+
 impl<'a> Section<'a> {
     pub fn new(children: Vec<serde_json::Value>) -> Self {
         Self {
@@ -294,7 +435,22 @@ impl<'a> Section<'a> {
             icon: None,
             padding: None,
             content_description: None,
+            expanded: None,
+            section_key: None,
+            toggle_action: None,
+        }
+    }
+
+    /// Lets the user fold the section away under `key`. A collapsed section keeps its
+    /// header but is sent without children.
+    pub fn collapsible(mut self, key: &'a str, expanded: bool) -> Self {
+        self.section_key = Some(key);
+        self.expanded = Some(expanded);
+        self.toggle_action = Some(SECTION_TOGGLE_ACTION);
+        if !expanded {
+            self.children.clear();
         }
+        self
     }
 
     pub fn title(mut self, title: &'a str) -> Self {
@@ -384,6 +540,159 @@ impl<'a> Card<'a> {
     }
 }
 
+/// One pane of a `Tabs` node.
+#[derive(Serialize)]
+pub struct Tab<'a> {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub id: &'a str,
+    pub label: &'a str,
+    pub children: Vec<serde_json::Value>,
+}
+
+/// Panes behind a row of tab headers. Every pane is sent so switching is instant on the
+/// Kotlin side, which only builds the selected one; `bind_key` carries the selected tab id
+/// and `action`, when set, reports a switch so the choice survives re-renders.
+#[derive(Serialize)]
+pub struct Tabs<'a> {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub bind_key: &'a str,
+    pub selected: &'a str,
+    pub children: Vec<Tab<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_description: Option<&'a str>,
+}
+
+impl<'a> Tabs<'a> {
+    pub fn new(bind_key: &'a str, selected: &'a str) -> Self {
+        Self {
+            kind: "Tabs",
+            bind_key,
+            selected,
+            children: Vec::new(),
+            action: None,
+            content_description: None,
+        }
+    }
+
+    pub fn tab(mut self, id: &'a str, label: &'a str, children: Vec<serde_json::Value>) -> Self {
+        self.children.push(Tab {
+            kind: "Tab",
+            id,
+            label,
+            children,
+        });
+        self
+    }
+
+    pub fn action(mut self, action: &'a str) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn content_description(mut self, cd: &'a str) -> Self {
+        self.content_description = Some(cd);
+        self
+    }
+}
+
+/// Header and layout of one `Table` column.
+#[derive(Serialize)]
+pub struct TableColumn<'a> {
+    pub title: &'a str,
+    /// `start` (default), `center` or `end`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub align: Option<&'static str>,
+    /// Fixed width; columns without one share what is left, or get a default width
+    /// when the table scrolls horizontally.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width_dp: Option<u32>,
+}
+
+impl<'a> TableColumn<'a> {
+    pub fn new(title: &'a str) -> Self {
+        Self {
+            title,
+            align: None,
+            width_dp: None,
+        }
+    }
+
+    pub fn align(mut self, align: &'static str) -> Self {
+        self.align = Some(align);
+        self
+    }
+
+    pub fn width_dp(mut self, width: u32) -> Self {
+        self.width_dp = Some(width);
+        self
+    }
+}
+
+/// One row of a `Table`; cells are regular nodes, one per column.
+#[derive(Serialize)]
+pub struct TableRow {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub children: Vec<serde_json::Value>,
+}
+
+/// Rows under a header that stays in place while they scroll, once `max_height_dp`
+/// caps the body. Rows go under `children` so tree walkers reach the cells.
+#[derive(Serialize)]
+pub struct Table<'a> {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub columns: Vec<TableColumn<'a>>,
+    pub children: Vec<TableRow>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub horizontal_scroll: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_height_dp: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_description: Option<&'a str>,
+}
+
+impl<'a> Table<'a> {
+    pub fn new(columns: Vec<TableColumn<'a>>) -> Self {
+        Self {
+            kind: "Table",
+            columns,
+            children: Vec::new(),
+            horizontal_scroll: None,
+            max_height_dp: None,
+            content_description: None,
+        }
+    }
+
+    pub fn row(mut self, cells: Vec<serde_json::Value>) -> Self {
+        self.children.push(TableRow {
+            kind: "TableRow",
+            children: cells,
+        });
+        self
+    }
+
+    pub fn horizontal_scroll(mut self, enabled: bool) -> Self {
+        self.horizontal_scroll = Some(enabled);
+        self
+    }
+
+    pub fn max_height_dp(mut self, height: u32) -> Self {
+        self.max_height_dp = Some(height);
+        self
+    }
+
+    pub fn content_description(mut self, cd: &'a str) -> Self {
+        self.content_description = Some(cd);
+        self
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Serialize)]
 pub struct Grid<'a> {
@@ -471,6 +780,51 @@ impl<'a> Checkbox<'a> {
     }
 }
 
+/// On/off setting; same binding contract as `Checkbox` ("true"/"false"), rendered as a
+/// switch so settings read differently from multi-select lists.
+#[derive(Serialize)]
+pub struct Switch<'a> {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub text: &'a str,
+    pub bind_key: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_description: Option<&'a str>,
+}
+
+#[allow(dead_code)]
+impl<'a> Switch<'a> {
+    pub fn new(text: &'a str, bind_key: &'a str) -> Self {
+        Self {
+            kind: "Switch",
+            text,
+            bind_key,
+            checked: None,
+            action: None,
+            content_description: None,
+        }
+    }
+
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    pub fn action(mut self, action: &'a str) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    pub fn content_description(mut self, cd: &'a str) -> Self {
+        self.content_description = Some(cd);
+        self
+    }
+}
+
 #[derive(Serialize)]
 pub struct Progress<'a> {
     #[serde(rename = "type")]
@@ -490,7 +844,6 @@ impl<'a> Progress<'a> {
         }
     }
 
-    #[allow(dead_code)]
     pub fn text(mut self, text: &'a str) -> Self {
         self.text = Some(text);
         self
@@ -500,6 +853,140 @@ impl<'a> Progress<'a> {
         self.content_description = Some(cd);
         self
     }
+
+}
+
+/// Determinate counterpart of [`Progress`], for work whose completion is known.
+#[derive(Serialize)]
+pub struct ProgressBar<'a> {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// Completion in `0.0..=1.0`.
+    pub fraction: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_description: Option<&'a str>,
+}
+
+impl<'a> ProgressBar<'a> {
+    pub fn new(fraction: f32) -> Self {
+        Self {
+            kind: "ProgressBar",
+            fraction: fraction.clamp(0.0, 1.0),
+            label: None,
+            content_description: None,
+        }
+    }
+
+    pub fn label(mut self, label: &'a str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    pub fn content_description(mut self, cd: &'a str) -> Self {
+        self.content_description = Some(cd);
+        self
+    }
+}
+
+/// Modal question over the current screen. Kotlin sends `confirm_action` or
+/// `cancel_action` once answered, including when the dialog is dismissed.
+#[derive(Serialize)]
+pub struct ConfirmDialog<'a> {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<&'a str>,
+    pub confirm_action: &'a str,
+    pub cancel_action: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirm_label: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cancel_label: Option<&'a str>,
+}
+
+impl<'a> ConfirmDialog<'a> {
+    pub fn new(title: &'a str, confirm_action: &'a str, cancel_action: &'a str) -> Self {
+        Self {
+            kind: "ConfirmDialog",
+            title,
+            message: None,
+            confirm_action,
+            cancel_action,
+            confirm_label: None,
+            cancel_label: None,
+        }
+    }
+
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.message = Some(message);
+        self
+    }
+
+    pub fn confirm_label(mut self, label: &'a str) -> Self {
+        self.confirm_label = Some(label);
+        self
+    }
+
+    pub fn cancel_label(mut self, label: &'a str) -> Self {
+        self.cancel_label = Some(label);
+        self
+    }
+}
+
+#[derive(Serialize)]
+pub struct ReorderItem {
+    pub id: String,
+    pub label: String,
+}
+
+impl ReorderItem {
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+        }
+    }
+}
+
+/// Rows the user drags (or moves with the arrow buttons) into a new order. The current
+/// order is always in `bind_key` as comma separated item ids; `action`, when set, is sent
+/// after every move.
+#[derive(Serialize)]
+pub struct ReorderableList<'a> {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub bind_key: &'a str,
+    pub items: Vec<ReorderItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_description: Option<&'a str>,
+}
+
+impl<'a> ReorderableList<'a> {
+    pub fn new(bind_key: &'a str, items: Vec<ReorderItem>) -> Self {
+        Self {
+            kind: "ReorderableList",
+            bind_key,
+            items,
+            action: None,
+            content_description: None,
+        }
+    }
+
+    pub fn action(mut self, action: &'a str) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn content_description(mut self, cd: &'a str) -> Self {
+        self.content_description = Some(cd);
+        self
+    }
 }
 
 #[derive(Serialize)]
@@ -587,6 +1074,50 @@ impl<'a> TextInput<'a> {
     }
 }
 
+/// Integer slider. The value is written to `bind_key` while dragging; `action` fires on release.
+#[derive(Serialize)]
+pub struct Slider<'a> {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub bind_key: &'a str,
+    pub min: i32,
+    pub max: i32,
+    pub value: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_description: Option<&'a str>,
+}
+
+impl<'a> Slider<'a> {
+    pub fn new(bind_key: &'a str, min: i32, max: i32) -> Self {
+        Self {
+            kind: "Slider",
+            bind_key,
+            min,
+            max,
+            value: min,
+            action: None,
+            content_description: None,
+        }
+    }
+
+    pub fn value(mut self, value: i32) -> Self {
+        self.value = value.clamp(self.min, self.max);
+        self
+    }
+
+    pub fn action(mut self, action: &'a str) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    pub fn content_description(mut self, cd: &'a str) -> Self {
+        self.content_description = Some(cd);
+        self
+    }
+}
+
 #[derive(Serialize)]
 pub struct ImageBase64<'a> {
     #[serde(rename = "type")]
@@ -612,6 +1143,75 @@ impl<'a> ImageBase64<'a> {
     }
 }
 
+/// A picture loaded from a file path or inline base64. When `zoomable`, it supports
+/// pinch-zoom, drag-to-pan and double-tap zoom, and a tap opens it full screen.
+#[derive(Serialize)]
+pub struct Image<'a> {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base64: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zoomable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_height_dp: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<&'a str>,
+}
+
+impl<'a> Image<'a> {
+    fn empty() -> Self {
+        Self {
+            kind: "Image",
+            path: None,
+            base64: None,
+            zoomable: None,
+            max_height_dp: None,
+            content_description: None,
+            id: None,
+        }
+    }
+
+    pub fn from_path(path: &'a str) -> Self {
+        Self {
+            path: Some(path),
+            ..Self::empty()
+        }
+    }
+
+    pub fn from_base64(base64: &'a str) -> Self {
+        Self {
+            base64: Some(base64),
+            ..Self::empty()
+        }
+    }
+
+    pub fn zoomable(mut self, enabled: bool) -> Self {
+        self.zoomable = Some(enabled);
+        self
+    }
+
+    pub fn max_height_dp(mut self, dp: u32) -> Self {
+        self.max_height_dp = Some(dp);
+        self
+    }
+
+    pub fn content_description(mut self, cd: &'a str) -> Self {
+        self.content_description = Some(cd);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn id(mut self, id: &'a str) -> Self {
+        self.id = Some(id);
+        self
+    }
+}
+
 #[derive(Serialize)]
 pub struct ColorSwatch {
     #[serde(rename = "type")]
@@ -750,6 +1350,106 @@ impl<'a> Magnetometer<'a> {
     }
 }
 
+#[derive(Serialize)]
+pub struct ChartSeries<'a> {
+    pub label: &'a str,
+    pub values: Vec<f64>,
+    /// `#RRGGBB`; without it the renderer picks from its palette by series index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<&'a str>,
+}
+
+impl<'a> ChartSeries<'a> {
+    pub fn new(label: &'a str, values: Vec<f64>) -> Self {
+        Self {
+            label,
+            values,
+            color: None,
+        }
+    }
+
+    pub fn color(mut self, color: &'a str) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// Line or bar chart of one or more series sharing the x axis (sample index).
+#[derive(Serialize)]
+pub struct Chart<'a> {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub series: Vec<ChartSeries<'a>>,
+    /// `line` (default) or `bar`; bars of several series are grouped per x position.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x_label: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y_label: Option<&'a str>,
+    /// Fixed y axis bounds; without them the axis fits the data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y_min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y_max: Option<f64>,
+    /// One label per x position; the renderer skips some when they would overlap.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height_dp: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_description: Option<&'a str>,
+}
+
+impl<'a> Chart<'a> {
+    pub fn new(series: Vec<ChartSeries<'a>>) -> Self {
+        Self {
+            kind: "Chart",
+            series,
+            style: None,
+            x_label: None,
+            y_label: None,
+            y_min: None,
+            y_max: None,
+            categories: Vec::new(),
+            height_dp: None,
+            content_description: None,
+        }
+    }
+
+    pub fn bar(mut self) -> Self {
+        self.style = Some("bar");
+        self
+    }
+
+    pub fn axes(mut self, x_label: &'a str, y_label: &'a str) -> Self {
+        self.x_label = Some(x_label);
+        self.y_label = Some(y_label);
+        self
+    }
+
+    pub fn y_range(mut self, min: f64, max: f64) -> Self {
+        self.y_min = Some(min);
+        self.y_max = Some(max);
+        self
+    }
+
+    pub fn categories(mut self, labels: Vec<String>) -> Self {
+        self.categories = labels;
+        self
+    }
+
+    pub fn height_dp(mut self, value: u32) -> Self {
+        self.height_dp = Some(value);
+        self
+    }
+
+    pub fn content_description(mut self, cd: &'a str) -> Self {
+        self.content_description = Some(cd);
+        self
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Serialize)]
 pub struct DepsList<'a> {
@@ -978,7 +1678,7 @@ pub fn render_multi_hash_screen(state: &AppState) -> Value {
 
     if let Some(err) = &state.multi_hash_error {
         children.push(to_value_or_text(
-            Text::new(&format!("{}{}", t!("multi_hash_error_prefix"), err)).size(14.0),
+            Text::new(&error_text(err)).size(14.0),
             "multi_hash_error",
         ));
     }
@@ -989,22 +1689,39 @@ pub fn render_multi_hash_screen(state: &AppState) -> Value {
             "multi_hash_path",
         ));
 
-        let hash_display = |label: &str, value: &str| {
-            json!({
-                "type": "Column",
-                "padding": 8,
-                "children": [
-                    to_value_or_text(Text::new(label).size(12.0), "multi_hash_label"),
-                    to_value_or_text(Text::new(value).size(10.0), "multi_hash_value"),
-                    to_value_or_text(Button::new(&t!("button_copy"), "noop").copy_text(value), "multi_hash_copy"),
-                ]
-            })
-        };
-
-        children.push(hash_display(&t!("multi_hash_label_md5"), &results.md5));
-        children.push(hash_display(&t!("multi_hash_label_sha1"), &results.sha1));
-        children.push(hash_display(&t!("multi_hash_label_sha256"), &results.sha256));
-        children.push(hash_display(&t!("multi_hash_label_blake3"), &results.blake3));
+        let algorithm_title = t!("multi_hash_column_algorithm");
+        let digest_title = t!("multi_hash_column_digest");
+        let mut table = Table::new(vec![
+            TableColumn::new(&algorithm_title).width_dp(72),
+            TableColumn::new(&digest_title),
+            TableColumn::new("").align("end").width_dp(88),
+        ])
+        .content_description("multi_hash_results");
+        for (label, value) in [
+            (t!("multi_hash_label_md5"), &results.md5),
+            (t!("multi_hash_label_sha1"), &results.sha1),
+            (t!("multi_hash_label_sha256"), &results.sha256),
+            (t!("multi_hash_label_sha512"), &results.sha512),
+            (t!("multi_hash_label_sha3_256"), &results.sha3_256),
+            (t!("multi_hash_label_sha3_512"), &results.sha3_512),
+            (t!("multi_hash_label_blake3"), &results.blake3),
+            (t!("multi_hash_label_xxh64"), &results.xxh64),
+            (t!("multi_hash_label_xxh3"), &results.xxh3),
+        ] {
+            // Results restored from before an algorithm was added have no digest for it.
+            if value.is_empty() {
+                continue;
+            }
+            table = table.row(vec![
+                to_value_or_text(Text::new(&label).size(12.0), "multi_hash_label"),
+                to_value_or_text(Text::new(value).size(10.0), "multi_hash_value"),
+                to_value_or_text(
+                    Button::new(&t!("button_copy"), "noop").copy_text(value),
+                    "multi_hash_copy",
+                ),
+            ]);
+        }
+        children.push(to_value_or_text(table, "multi_hash_table"));
     }
 
     to_value_or_text(Column::new(children).padding(24), "multi_hash_root")
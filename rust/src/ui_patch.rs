@@ -0,0 +1,170 @@
+//! Diffs between two rendered UI trees, so a dispatch can ship only what changed.
+//!
+//! Nodes are matched by position and key (their type plus the first id-like field the
+//! renderer also keys views by); a node whose key changed is replaced whole. Paths are
+//! child indices from the root.
+
+use serde_json::{json, Map, Value};
+
+/// Response `type` of a patch, as opposed to a full tree.
+pub const PATCH_TYPE: &str = "Patch";
+
+const KEY_FIELDS: [&str; 4] = ["id", "bind_key", "action", "content_description"];
+
+fn node_key(node: &Value) -> (Option<&str>, Option<&str>) {
+    let kind = node.get("type").and_then(Value::as_str);
+    let id = KEY_FIELDS
+        .iter()
+        .find_map(|field| node.get(*field).and_then(Value::as_str));
+    (kind, id)
+}
+
+fn children(node: &Value) -> Option<&Vec<Value>> {
+    node.get("children").and_then(Value::as_array)
+}
+
+fn props(node: &Value) -> Map<String, Value> {
+    node.as_object()
+        .map(|obj| {
+            obj.iter()
+                .filter(|(key, _)| key.as_str() != "children")
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the ops turning `old` into `new`, or `None` when the roots are different nodes
+/// (typically another screen) and only the full tree makes sense.
+///
+/// - `props`: replaces every field of the node at `path` except its children.
+/// - `children`: replaces the child list at `path` (`null` removes it).
+/// - `replace`: swaps the node at `path` for `node`.
+pub fn diff(old: &Value, new: &Value) -> Option<Vec<Value>> {
+    if node_key(old) != node_key(new) {
+        return None;
+    }
+    let mut ops = Vec::new();
+    diff_node(old, new, &mut Vec::new(), &mut ops);
+    Some(ops)
+}
+
+/// Number of nodes in `node`'s subtree, itself included.
+pub fn node_count(node: &Value) -> usize {
+    1 + children(node).map_or(0, |kids| kids.iter().map(node_count).sum())
+}
+
+/// Number of nodes the client has to take from `ops`: one per `props` op, plus every
+/// node a `children` or `replace` op ships.
+pub fn ops_node_count(ops: &[Value]) -> usize {
+    ops.iter()
+        .map(|op| match op.get("op").and_then(Value::as_str) {
+            Some("children") => op
+                .get("children")
+                .and_then(Value::as_array)
+                .map_or(0, |kids| kids.iter().map(node_count).sum()),
+            Some("replace") => op.get("node").map_or(0, node_count),
+            _ => 1,
+        })
+        .sum()
+}
+
+fn diff_node(old: &Value, new: &Value, path: &mut Vec<usize>, ops: &mut Vec<Value>) {
+    if old == new {
+        return;
+    }
+    let new_props = props(new);
+    if props(old) != new_props {
+        ops.push(json!({ "op": "props", "path": path, "props": new_props }));
+    }
+    match (children(old), children(new)) {
+        (None, None) => {}
+        (Some(before), Some(after)) if before.len() == after.len() => {
+            for (idx, (o, n)) in before.iter().zip(after).enumerate() {
+                if o == n {
+                    continue;
+                }
+                path.push(idx);
+                if node_key(o) == node_key(n) {
+                    diff_node(o, n, path, ops);
+                } else {
+                    ops.push(json!({ "op": "replace", "path": path, "node": n }));
+                }
+                path.pop();
+            }
+        }
+        (_, after) => ops.push(json!({ "op": "children", "path": path, "children": after })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen(query: &str, rows: &[&str]) -> Value {
+        json!({
+            "type": "Column",
+            "children": [
+                { "type": "Text", "text": "Title" },
+                { "type": "TextInput", "bind_key": "find_query", "text": query },
+                {
+                    "type": "VirtualList",
+                    "children": rows
+                        .iter()
+                        .map(|r| json!({ "type": "Text", "text": r }))
+                        .collect::<Vec<_>>()
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn unchanged_tree_needs_no_ops() {
+        let tree = screen("a", &["x"]);
+        assert_eq!(diff(&tree, &tree), Some(vec![]));
+    }
+
+    #[test]
+    fn changed_leaf_only_sends_its_props() {
+        let ops = diff(&screen("a", &["x", "y"]), &screen("ab", &["x", "y"])).unwrap();
+        assert_eq!(
+            ops,
+            vec![json!({
+                "op": "props",
+                "path": [1],
+                "props": { "type": "TextInput", "bind_key": "find_query", "text": "ab" }
+            })]
+        );
+    }
+
+    #[test]
+    fn resized_child_list_is_replaced_and_rekeyed_nodes_swapped() {
+        let ops = diff(&screen("a", &["x"]), &screen("a", &["x", "y"])).unwrap();
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0]["op"], json!("children"));
+        assert_eq!(ops[0]["path"], json!([2]));
+        assert_eq!(ops[0]["children"][1]["text"], json!("y"));
+
+        let mut other = screen("a", &["x"]);
+        other["children"][0] = json!({ "type": "Button", "text": "Go", "action": "go" });
+        let ops = diff(&screen("a", &["x"]), &other).unwrap();
+        assert_eq!(ops[0]["op"], json!("replace"));
+        assert_eq!(ops[0]["node"]["action"], json!("go"));
+    }
+
+    #[test]
+    fn ops_are_weighed_by_the_nodes_they_ship() {
+        assert_eq!(node_count(&screen("a", &["x", "y"])), 6);
+        let props = diff(&screen("a", &["x"]), &screen("ab", &["x"])).unwrap();
+        assert_eq!(ops_node_count(&props), 1);
+        let resized = diff(&screen("a", &["x"]), &screen("a", &["x", "y", "z"])).unwrap();
+        assert_eq!(ops_node_count(&resized), 3);
+        assert_eq!(ops_node_count(&[]), 0);
+    }
+
+    #[test]
+    fn different_roots_cannot_be_patched() {
+        let other = json!({ "type": "Column", "id": "other", "children": [] });
+        assert!(diff(&screen("a", &[]), &other).is_none());
+    }
+}
@@ -2,7 +2,10 @@
 mod tests {
     use crate::features::misc_screens::render_about_screen;
     use crate::state::AppState;
-    use crate::ui::{HtmlView, TextInput, VirtualList};
+    use crate::ui::{
+        escape_markdown, Chart, ChartSeries, ConfirmDialog, HtmlView, Image, ProgressBar,
+        ReorderItem, ReorderableList, RichText, Slider, TextInput, VirtualList,
+    };
     use serde_json::json;
 
     #[test]
@@ -43,6 +46,129 @@ mod tests {
         assert_eq!(val.get("html").and_then(|v| v.as_str()), Some("<p>ok</p>"));
     }
 
+    #[test]
+    fn chart_serializes_series() {
+        let chart = Chart::new(vec![ChartSeries::new("ACCEL", vec![9.7, 9.8])])
+            .height_dp(120)
+            .content_description("accel_chart");
+        let val = serde_json::to_value(chart).unwrap();
+        assert_eq!(val.get("type").and_then(|v| v.as_str()), Some("Chart"));
+        assert_eq!(val["series"][0]["label"], json!("ACCEL"));
+        assert_eq!(val["series"][0]["values"], json!([9.7, 9.8]));
+        assert!(val["series"][0].get("color").is_none());
+        assert_eq!(val.get("height_dp").and_then(|v| v.as_u64()), Some(120));
+
+        let colored = ChartSeries::new("R", vec![1.0]).color("#E53935");
+        let val = serde_json::to_value(colored).unwrap();
+        assert_eq!(val["color"], json!("#E53935"));
+    }
+
+    #[test]
+    fn chart_serializes_bar_style_and_axes_only_when_set() {
+        let plain = serde_json::to_value(Chart::new(vec![])).unwrap();
+        for key in [
+            "style",
+            "x_label",
+            "y_label",
+            "y_min",
+            "y_max",
+            "categories",
+        ] {
+            assert!(plain.get(key).is_none(), "{key} should be omitted");
+        }
+
+        let bars = Chart::new(vec![ChartSeries::new("Share", vec![40.0, 25.0])])
+            .bar()
+            .axes("Level", "% of original")
+            .y_range(0.0, 100.0)
+            .categories(vec!["1".into(), "9".into()]);
+        let val = serde_json::to_value(bars).unwrap();
+        assert_eq!(val["style"], json!("bar"));
+        assert_eq!(val["x_label"], json!("Level"));
+        assert_eq!(val["y_label"], json!("% of original"));
+        assert_eq!(val["y_min"], json!(0.0));
+        assert_eq!(val["y_max"], json!(100.0));
+        assert_eq!(val["categories"], json!(["1", "9"]));
+    }
+
+    #[test]
+    fn progress_bar_clamps_fraction() {
+        let bar = ProgressBar::new(1.7).label("Extracting 3 of 3");
+        let val = serde_json::to_value(bar).unwrap();
+        assert_eq!(val["type"], json!("ProgressBar"));
+        assert_eq!(val["fraction"], json!(1.0));
+        assert_eq!(val["label"], json!("Extracting 3 of 3"));
+        assert!(val.get("content_description").is_none());
+    }
+
+    #[test]
+    fn confirm_dialog_omits_unset_labels() {
+        let dialog = ConfirmDialog::new("Delete preset?", "confirm_pending", "cancel_pending")
+            .confirm_label("Delete");
+        let val = serde_json::to_value(dialog).unwrap();
+        assert_eq!(val["type"], json!("ConfirmDialog"));
+        assert_eq!(val["confirm_action"], json!("confirm_pending"));
+        assert_eq!(val["cancel_action"], json!("cancel_pending"));
+        assert_eq!(val["confirm_label"], json!("Delete"));
+        assert!(val.get("message").is_none());
+        assert!(val.get("cancel_label").is_none());
+    }
+
+    #[test]
+    fn image_serializes_only_its_source() {
+        let val = serde_json::to_value(Image::from_path("/tmp/out.png").zoomable(true)).unwrap();
+        assert_eq!(val["type"], json!("Image"));
+        assert_eq!(val["path"], json!("/tmp/out.png"));
+        assert_eq!(val["zoomable"], json!(true));
+        assert!(val.get("base64").is_none());
+        assert!(val.get("max_height_dp").is_none());
+
+        let val = serde_json::to_value(Image::from_base64("AAAA")).unwrap();
+        assert_eq!(val["base64"], json!("AAAA"));
+        assert!(val.get("path").is_none());
+        assert!(val.get("zoomable").is_none());
+    }
+
+    #[test]
+    fn reorderable_list_serializes_items_in_order() {
+        let list = ReorderableList::new(
+            "pdf_reorder_pages",
+            vec![
+                ReorderItem::new("2", "Page 2"),
+                ReorderItem::new("1", "Page 1"),
+            ],
+        );
+        let val = serde_json::to_value(list).unwrap();
+        assert_eq!(val["type"], json!("ReorderableList"));
+        assert_eq!(val["bind_key"], json!("pdf_reorder_pages"));
+        assert_eq!(val["items"][0], json!({ "id": "2", "label": "Page 2" }));
+        assert!(val.get("action").is_none());
+    }
+
+    #[test]
+    fn rich_text_carries_escaped_markdown() {
+        let name = escape_markdown("my_file (1).md");
+        assert_eq!(name, r"my\_file \(1\)\.md");
+        let markdown = format!("Opened **{name}**");
+        let val = serde_json::to_value(RichText::new(&markdown).size(12.0)).unwrap();
+        assert_eq!(val["type"], json!("RichText"));
+        assert_eq!(val["markdown"], json!(r"Opened **my\_file \(1\)\.md**"));
+        assert!(val.get("content_description").is_none());
+    }
+
+    #[test]
+    fn slider_clamps_value_and_serializes_range() {
+        let slider = Slider::new("brightness", -100, 100)
+            .value(250)
+            .action("adjust");
+        let val = serde_json::to_value(slider).unwrap();
+        assert_eq!(val.get("type").and_then(|v| v.as_str()), Some("Slider"));
+        assert_eq!(val["min"], json!(-100));
+        assert_eq!(val["value"], json!(100));
+        assert_eq!(val["action"], json!("adjust"));
+        assert!(val.get("content_description").is_none());
+    }
+
     #[test]
     fn about_screen_forwards_filter_query() {
         let mut state = AppState::new();